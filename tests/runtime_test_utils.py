@@ -1,11 +1,29 @@
 import gc
+import json
+import subprocess
 import tempfile
 from contextlib import ExitStack, contextmanager
 from pathlib import Path
 from typing import Any, Iterator
-from unittest.mock import patch
+from unittest.mock import MagicMock, patch
 
 from trainsh.core.executor_main import DSLExecutor
+from trainsh.services.host_exec import ExecResult
+
+
+def ok_response(payload: Any = None) -> MagicMock:
+    """Stand-in for a successful `urlopen` response whose body is `payload` as JSON."""
+    response = MagicMock()
+    response.read.return_value = json.dumps({} if payload is None else payload).encode("utf-8")
+    response.__enter__.return_value = response
+    return response
+
+
+def run_locally(host: Any, command: Any, *, timeout: Any = None, stdin: str = "", **_options: Any) -> ExecResult:
+    """Stand-in for `host_exec` that runs the remote command in a local shell."""
+    argv = ["bash", "-c", command] if isinstance(command, str) else list(command)
+    proc = subprocess.run(argv, input=stdin or None, capture_output=True, text=True, timeout=timeout)
+    return ExecResult(str(host), str(command), proc.returncode, proc.stdout, proc.stderr, 1.0)
 
 
 @contextmanager
@@ -29,6 +47,7 @@ def isolated_executor(
         stack.enter_context(patch("trainsh.core.executor_main.RUNTIME_STATE_DIR", config_dir / "runtime"))
         stack.enter_context(patch("trainsh.runtime.CONFIG_DIR", config_dir))
         stack.enter_context(patch("trainsh.commands.storage.load_storages", return_value={}))
+        stack.enter_context(patch("trainsh.services.audit_log.AUDIT_FILE", config_dir / "audit.jsonl"))
 
         executor = DSLExecutor(
             recipe_model,
@@ -0,0 +1,78 @@
+import json
+import tempfile
+import unittest
+from contextlib import redirect_stdout
+from datetime import datetime
+from io import StringIO
+from pathlib import Path
+from unittest.mock import MagicMock, patch
+
+from trainsh import Recipe
+from trainsh.commands import vast
+from trainsh.commands.audit import main as audit_main
+from trainsh.pyrecipe.models import ProviderStep
+from trainsh.services.audit_log import parse_time, read_entries, record
+from tests.runtime_test_utils import isolated_executor
+
+
+class AuditLogTests(unittest.TestCase):
+    def test_record_filter_and_list(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            path = Path(tmpdir) / "audit.jsonl"
+            record("vast.destroy", "42", host="vast", path=path)
+            record("secret.get", "HF_TOKEN", detail="  masked\n", path=path)
+            self.assertEqual(oct(path.stat().st_mode & 0o777), "0o600")
+            self.assertEqual([e["action"] for e in read_entries(action="vast", path=path)], ["vast.destroy"])
+            self.assertEqual(read_entries(action="secret", path=path)[0]["detail"], "masked")
+            self.assertEqual(read_entries(since=parse_time("2099-01-01"), path=path), [])
+            self.assertEqual(parse_time("2d", now=datetime(2026, 1, 3)), datetime(2026, 1, 1))
+            with self.assertRaises(ValueError):
+                parse_time("yesterday")
+
+            out = StringIO()
+            with patch("trainsh.services.audit_log.AUDIT_FILE", path), redirect_stdout(out):
+                audit_main(["list", "--since", "1h", "--limit", "1"])
+                audit_main(["--action", "vast", "--json"])
+            text = out.getvalue()
+            self.assertIn("secret.get", text)
+            self.assertIn("1 of 2 entries", text)
+            self.assertEqual(json.loads(text[text.index("["):])[0]["target"], "42")
+
+    def test_vast_remove_is_audited(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            path = Path(tmpdir) / "audit.jsonl"
+            client = MagicMock()
+            with patch("trainsh.services.audit_log.AUDIT_FILE", path), patch.object(
+                vast, "prompt_input", return_value="y"
+            ), patch("trainsh.services.vast_api.get_vast_client", return_value=client), redirect_stdout(StringIO()):
+                vast.cmd_rm(["7"])
+            client.rm_instance.assert_called_once_with(7)
+            self.assertEqual(read_entries(path=path)[0]["target"], "7")
+
+    def test_destructive_steps_and_secret_reads_are_audited(self):
+        recipe = Recipe("cleanup")
+        recipe.shell("echo wipe", id="wipe", step_options={"destructive": True})
+        recipe.shell("echo keep", id="keep")
+        model = recipe.to_recipe_model()
+        self.assertTrue(recipe.steps[0].destructive)
+        with isolated_executor(model) as (executor, config_dir):
+            with patch.object(executor, "_dispatch_step", return_value=(True, "")):
+                for step in recipe.steps:
+                    executor._execute_step(step)
+                executor._execute_step(ProviderStep("storage", "delete", {"storage": "r2", "path": "ckpt/"}, id="rm"))
+            executor.secrets = MagicMock()
+            executor.secrets.get.return_value = "value"
+            executor._interpolate("${secret:HF} ${secret:HF}")
+            entries = read_entries(path=config_dir / "audit.jsonl")
+        self.assertEqual([(e["action"], e["target"]) for e in entries], [
+            ("step", "cleanup:wipe"),
+            ("step", "cleanup:rm"),
+            ("secret.get", "HF"),
+        ])
+        self.assertEqual(entries[1]["detail"], "storage.delete ckpt/")
+        self.assertEqual(entries[1]["host"], "r2")
+        self.assertTrue(entries[0]["source"].startswith("job "))
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,114 @@
+import os
+import tempfile
+import unittest
+from contextlib import redirect_stdout
+from io import StringIO
+from pathlib import Path
+from unittest.mock import patch
+
+from trainsh.commands import backup as backup_cmd
+from trainsh.services import backup
+
+
+class FakeBackend:
+    def __init__(self, values=None):
+        self.values = dict(values or {})
+
+    def get(self, key):
+        return self.values.get(key)
+
+    def set(self, key, value):
+        self.values[key] = value
+
+    def list_set_keys(self):
+        return list(self.values)
+
+
+class FakeManager:
+    def __init__(self, backend):
+        self.backend = backend
+
+    def _get_backend(self):
+        return self.backend
+
+    def _require_backend(self):
+        return self.backend
+
+    def clear_cache(self):
+        pass
+
+
+class BackupTests(unittest.TestCase):
+    def setUp(self):
+        self.tmpdir = tempfile.TemporaryDirectory()
+        self.addCleanup(self.tmpdir.cleanup)
+        self.root = Path(self.tmpdir.name)
+
+    def machine(self, name):
+        base = self.root / name
+        dirs = {"CONFIG_DIR": base / "config", "RECIPES_DIR": base / "recipes", "STATE_DIR": base / "state"}
+        for path in dirs.values():
+            path.mkdir(parents=True, exist_ok=True)
+        patchers = [patch(f"trainsh.constants.{key}", value) for key, value in dirs.items() if key != "RECIPES_DIR"]
+        patchers.append(patch("trainsh.commands.recipe.get_recipes_dir", return_value=str(dirs["RECIPES_DIR"])))
+        for patcher in patchers:
+            patcher.start()
+            self.addCleanup(patcher.stop)
+        return dirs
+
+    def test_backup_round_trip_with_secrets_and_conflicts(self):
+        old = self.machine("old")
+        (old["CONFIG_DIR"] / "hosts.yaml").write_text("hosts:\n- name: gpu\n")
+        (old["CONFIG_DIR"] / "config.yaml").write_text("ui:\n  currency: CNY\n")
+        (old["CONFIG_DIR"] / "secrets.enc").write_bytes(b"sealed")
+        (old["RECIPES_DIR"] / "train.pyrecipe").write_text("# recipe\n")
+        (old["STATE_DIR"] / "exchange_rates.jsonl").write_text("{}\n")
+        (old["STATE_DIR"] / "search.db").write_bytes(b"index")
+        archive = self.root / "out" / "trainsh.backup"
+        with patch("trainsh.core.secrets.get_secrets_manager", return_value=FakeManager(FakeBackend({"VAST_API_KEY": "k1", "HF_TOKEN": "h1"}))):
+            manifest = backup.create_backup(archive, "pass", include_secrets=True)
+        self.assertEqual(
+            sorted(manifest["files"]),
+            ["config/config.yaml", "config/hosts.yaml", "recipes/train.pyrecipe", "state/exchange_rates.jsonl"],
+        )
+        self.assertNotIn(b"gpu", archive.read_bytes())
+        with self.assertRaises(ValueError):
+            backup.read_manifest(archive, "wrong")
+
+        new = self.machine("new")
+        (new["CONFIG_DIR"] / "config.yaml").write_text("ui:\n  currency: USD\n")
+        target = FakeBackend({"HF_TOKEN": "local"})
+        with patch("trainsh.core.secrets.get_secrets_manager", return_value=FakeManager(target)):
+            report = backup.restore_backup(archive, "pass")
+        self.assertEqual(report.conflicts, ["config/config.yaml"])
+        self.assertEqual((new["CONFIG_DIR"] / "config.yaml").read_text(), "ui:\n  currency: USD\n")
+        self.assertEqual((new["CONFIG_DIR"] / "hosts.yaml").read_text(), "hosts:\n- name: gpu\n")
+        self.assertTrue((new["RECIPES_DIR"] / "train.pyrecipe").exists())
+        self.assertEqual(target.values, {"HF_TOKEN": "local", "VAST_API_KEY": "k1"})
+        self.assertEqual(report.secrets_conflicts, ["HF_TOKEN"])
+
+        with patch("trainsh.core.secrets.get_secrets_manager", return_value=FakeManager(target)):
+            report = backup.restore_backup(archive, "pass", on_conflict="overwrite")
+        self.assertEqual(report.replaced, ["config/config.yaml"])
+        self.assertIn("CNY", (new["CONFIG_DIR"] / "config.yaml").read_text())
+        self.assertIn("USD", (new["CONFIG_DIR"] / "config.yaml.bak").read_text())
+        self.assertEqual(target.values["HF_TOKEN"], "h1")
+
+    def test_cli_create_without_secrets_and_dry_run_restore(self):
+        dirs = self.machine("one")
+        (dirs["CONFIG_DIR"] / "storages.yaml").write_text("storages: []\n")
+        archive = self.root / "b.enc"
+        out = StringIO()
+        with patch.dict(os.environ, {backup_cmd.PASSPHRASE_ENV: "pw"}), redirect_stdout(out):
+            backup_cmd.main(["create", str(archive)])
+            (dirs["CONFIG_DIR"] / "storages.yaml").unlink()
+            backup_cmd.main(["restore", str(archive), "--dry-run"])
+            with self.assertRaises(SystemExit):
+                backup_cmd.main(["create", str(archive)])
+        self.assertIn("Secrets: not included", out.getvalue())
+        self.assertIn("Would restore 1 new and 0 replaced file(s)", out.getvalue())
+        self.assertFalse((dirs["CONFIG_DIR"] / "storages.yaml").exists())
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,132 @@
+import os
+import subprocess
+import tempfile
+import unittest
+from types import SimpleNamespace
+from unittest.mock import MagicMock, patch
+
+from trainsh.core.bridge_exec import (
+    BridgeExecutionHelper,
+    begin_marker,
+    done_marker,
+    new_marker_nonce,
+    parse_marked_exit,
+    parse_status_file,
+    wrap_marked_command,
+)
+from trainsh.core.remote_cancel import status_file_for
+from trainsh.core.local_tmux import TmuxCmdResult
+
+
+class BridgeMarkerTests(unittest.TestCase):
+    def test_wrapped_command_reports_exit_code_and_hides_markers_from_the_command_line(self):
+        nonce = new_marker_nonce()
+        self.assertNotEqual(nonce, new_marker_nonce())
+        wrapped = wrap_marked_command("echo hi; exit 7", nonce)
+        self.assertNotIn(begin_marker(nonce), wrapped)
+        self.assertNotIn(done_marker(nonce), wrapped)
+
+        output = subprocess.run(["sh", "-c", wrapped], capture_output=True, text=True).stdout
+        # What the pane shows: the typed command line followed by its output.
+        self.assertEqual(parse_marked_exit(f"$ {wrapped}\n{output}", nonce), (True, 7))
+
+    def test_spoofed_stale_and_foreign_markers_are_ignored(self):
+        nonce = "n1"
+        pane = "\n".join([
+            "__train_done_n1__0",  # stale: before this step's begin marker
+            "__train_begin_n1__",
+            "echo __train_done_n1__0",  # not on its own line
+            "__train_done_other__0",  # another step's nonce
+        ])
+        self.assertEqual(parse_marked_exit(pane, nonce), (False, None))
+        self.assertEqual(parse_marked_exit(pane + "\n__train_done_n1__3\n", nonce), (True, 3))
+        self.assertEqual(parse_marked_exit("__train_done_n1__0\n", nonce, require_begin=False), (True, 0))
+
+    def test_wait_captures_from_the_step_start_offset(self):
+        tmux = SimpleNamespace(
+            display_message=MagicMock(return_value=TmuxCmdResult(0, "120 2000 50\n", "")),
+            capture_pane=MagicMock(return_value=TmuxCmdResult(0, "loss 0.1\n__train_done_n1__0\n", "")),
+        )
+        helper = BridgeExecutionHelper(
+            tmux_bridge=SimpleNamespace(tmux=tmux),
+            prefer_bridge_exec=True,
+            bridge_remote_status="keep",
+            get_tmux_client=lambda host: None,
+            log=lambda msg: None,
+            log_detail=lambda *args: None,
+            format_duration=lambda value: f"{int(value)}s",
+        )
+        self.assertEqual(helper._wait_bridge_marker("%1", "n1", 5, start_line=100), (True, 0))
+        self.assertEqual(tmux.capture_pane.call_args.kwargs["start"], "-20")
+
+        # Without a start offset the begin marker must be visible.
+        tmux.capture_pane.return_value = TmuxCmdResult(0, "__train_done_n1__0\n", "")
+        with patch("trainsh.core.bridge_exec.time.time", side_effect=[0, 10]):
+            self.assertEqual(helper._wait_bridge_marker("%1", "n1", 5), (False, None))
+        self.assertEqual(tmux.capture_pane.call_args.kwargs["start"], "-300")
+
+    def test_wait_falls_back_to_the_tail_scan_once_history_evicts_lines(self):
+        tmux = SimpleNamespace(
+            display_message=MagicMock(return_value=TmuxCmdResult(0, "2000 2000 50\n", "")),
+            capture_pane=MagicMock(return_value=TmuxCmdResult(0, "__train_done_n1__0\n", "")),
+        )
+        helper = BridgeExecutionHelper(
+            tmux_bridge=SimpleNamespace(tmux=tmux),
+            prefer_bridge_exec=True,
+            bridge_remote_status="keep",
+            get_tmux_client=lambda host: None,
+            log=lambda msg: None,
+            log_detail=lambda *args: None,
+            format_duration=lambda value: f"{int(value)}s",
+        )
+        # The step started at line 1990, but the history is full and rows have
+        # scrolled off the top, so the offset no longer points at the step start.
+        with patch("trainsh.core.bridge_exec.time.time", side_effect=[0, 10]):
+            self.assertEqual(helper._wait_bridge_marker("%1", "n1", 5, start_line=1990), (False, None))
+        self.assertEqual(tmux.capture_pane.call_args.kwargs["start"], "-300")
+
+        tmux.capture_pane.return_value = TmuxCmdResult(0, "__train_begin_n1__\n__train_done_n1__0\n", "")
+        self.assertEqual(helper._wait_bridge_marker("%1", "n1", 5, start_line=1990), (True, 0))
+        self.assertEqual(tmux.capture_pane.call_args.kwargs["start"], "-300")
+
+        # History cleared since the step started: the offset now points past the pane.
+        tmux.display_message.return_value = TmuxCmdResult(0, "500 2000 50\n", "")
+        helper._wait_bridge_marker("%1", "n1", 5, start_line=600)
+        self.assertEqual(tmux.capture_pane.call_args.kwargs["start"], "-300")
+
+    def test_status_file_records_exit_code_when_the_pane_loses_the_marker(self):
+        self.assertEqual(status_file_for("job 1", "fit/a"), "/tmp/trainsh/job_1/fit_a.rc")
+        with tempfile.TemporaryDirectory() as tmpdir:
+            status_file = os.path.join(tmpdir, "job", "fit.rc")
+            os.makedirs(os.path.dirname(status_file))
+            with open(status_file, "w") as handle:
+                handle.write("old 0\n")  # left by an earlier attempt
+            subprocess.run(["sh", "-c", wrap_marked_command("clear >/dev/null 2>&1; exit 4", "n2", status_file)], capture_output=True)
+            with open(status_file) as handle:
+                text = handle.read()
+            self.assertEqual(parse_status_file(text, "n2"), 4)
+            self.assertIsNone(parse_status_file(text, "old"))
+
+            tmux = SimpleNamespace(
+                display_message=MagicMock(return_value=TmuxCmdResult(1, "", "")),
+                capture_pane=MagicMock(return_value=TmuxCmdResult(0, "", "")),
+            )
+            details = []
+            helper = BridgeExecutionHelper(
+                tmux_bridge=SimpleNamespace(tmux=tmux),
+                prefer_bridge_exec=True,
+                bridge_remote_status="keep",
+                get_tmux_client=lambda host: SimpleNamespace(read_text=lambda path: TmuxCmdResult(0, "n2 4\n", "")),
+                log=lambda msg: None,
+                log_detail=lambda *args: details.append(args),
+                format_duration=lambda value: f"{int(value)}s",
+            )
+            with patch("trainsh.core.bridge_exec.time.time", side_effect=[0, 5, 31]), patch("trainsh.core.bridge_exec.time.sleep"):
+                self.assertEqual(helper._wait_bridge_marker("%1", "n2", 60, status_file=status_file), (True, 4))
+            with patch("trainsh.core.bridge_exec.time.time", side_effect=[0, 31]):
+                self.assertEqual(helper._wait_bridge_marker("%1", "n2", 60, status_file="/x.rc", host="gpu"), (True, 4))
+            self.assertEqual(details[-1][0], "bridge_status_file")
+
+
+if __name__ == "__main__":
+    unittest.main()
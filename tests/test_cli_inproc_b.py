@@ -9,6 +9,7 @@ from unittest.mock import MagicMock, patch
 from trainsh.commands import colab, host, storage, transfer, vast
 from trainsh.commands.remote_run import RemoteCloneRequest
 from trainsh.core.models import AuthMethod, Host, HostType, Storage, StorageType
+from trainsh.services.ssh import SSHResult
 
 
 @contextmanager
@@ -117,7 +118,7 @@ class HostCommandTests(unittest.TestCase):
             ssh_client = MagicMock()
             ssh_client.connect_interactive.return_value = 0
             ssh_client.test_connection.return_value = True
-            ssh_client.run.return_value = SimpleNamespace(exit_code=0, stdout="GPU OK\n", stderr="")
+            ssh_client.run.return_value = SSHResult(exit_code=0, stdout="GPU OK\n", stderr="")
             with patch("trainsh.services.ssh.SSHClient.from_host", return_value=ssh_client):
                 text = capture_output(host.cmd_test, ["gpu-box"])
                 self.assertIn("Connection successful!", text)
@@ -501,8 +502,9 @@ class TransferColabVastCommandTests(unittest.TestCase):
             with patch("trainsh.utils.vast_formatter.get_currency_settings", return_value=currency), patch(
                 "trainsh.services.pricing.format_currency",
                 return_value="CNY 9.00",
-            ):
+            ), patch("trainsh.services.gpu_price_history.record_offer_prices") as record_prices:
                 text = capture_output(vast.cmd_search, [])
+            record_prices.assert_called_once_with([offer])
             self.assertIn("CNY/hr", text)
             self.assertIn("A100", text)
 
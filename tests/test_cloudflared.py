@@ -0,0 +1,100 @@
+import sys
+import tempfile
+import threading
+import unittest
+from pathlib import Path
+from unittest.mock import MagicMock, patch
+
+from trainsh.core.models import Host, HostType
+from trainsh.services import cloudflared
+from trainsh.services.ssh import SSHClient, SSHResult
+from trainsh.services.tunnel import find_free_local_port
+
+# Stands in for `cloudflared access tcp --url HOST:PORT`: listens on PORT until killed.
+FAKE_FORWARD = """
+import socket, sys, time
+host, port = sys.argv[sys.argv.index("--url") + 1].rsplit(":", 1)
+server = socket.socket()
+server.setsockopt(socket.SOL_SOCKET, socket.SO_REUSEADDR, 1)
+server.bind((host, int(port)))
+server.listen()
+while True:
+    time.sleep(0.05)
+"""
+
+
+class CloudflaredTests(unittest.TestCase):
+    def setUp(self):
+        patcher = patch("trainsh.config.load_config", return_value={})
+        patcher.start()
+        self.addCleanup(patcher.stop)
+
+    def test_setup_drives_proxy_command_everywhere(self):
+        host = Host(name="home", type=HostType.SSH, hostname="10.0.0.2", env_vars={"proxy_command": "old"})
+        cloudflared.configure_host(host, "ssh.example.com", "/opt/cloudflared")
+        self.assertNotIn("proxy_command", host.env_vars)
+        client = SSHClient.from_host(host)
+        self.assertEqual(client.proxy_command, "/opt/cloudflared access ssh --hostname ssh.example.com")
+
+        with patch("trainsh.config.load_config", return_value={"cloudflared": {"command": "cfd"}}):
+            self.assertEqual(
+                cloudflared.proxy_command({"tunnel_type": "cloudflared"}, "box.example.com"),
+                "cfd access ssh --hostname box.example.com",
+            )
+        self.assertIsNone(cloudflared.proxy_command({}, "box"))
+        self.assertTrue(cloudflared.download_url("Linux", "x86_64").endswith("cloudflared-linux-amd64"))
+        with self.assertRaises(ValueError):
+            cloudflared.download_url("Darwin", "arm64")
+
+    def test_verify_reports_ssh_round_trip(self):
+        host = cloudflared.configure_host(Host(name="home", hostname="x"), "ssh.example.com", sys.executable)
+        ssh = MagicMock()
+        ssh.run.return_value = SSHResult(exit_code=0, stdout="connected\n", stderr="")
+        with patch("trainsh.services.ssh.SSHClient.from_host", return_value=ssh):
+            report = cloudflared.verify_tunnel(host)
+        self.assertTrue(report["ssh_ok"])
+        self.assertEqual(report["binary"], sys.executable)
+        self.assertIn("Python", report["version"])
+
+        ssh.run.return_value = SSHResult(exit_code=255, stdout="", stderr="websocket: bad handshake\n")
+        with patch("trainsh.services.ssh.SSHClient.from_host", return_value=ssh):
+            self.assertEqual(cloudflared.verify_tunnel(host)["error"], "websocket: bad handshake")
+
+    def test_supervisor_restarts_a_dead_forward(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            script = Path(tmpdir) / "fake.py"
+            script.write_text(FAKE_FORWARD)
+            events = []
+            supervisor = cloudflared.TunnelSupervisor(
+                "ssh.example.com",
+                binary=sys.executable,
+                local_port=find_free_local_port(),
+                interval=1,
+                on_event=lambda event, payload: events.append(event),
+            )
+            supervisor.log_path = Path(tmpdir) / "cloudflared.log"
+
+            def forward_args(binary, hostname, local_port, bind_host="127.0.0.1"):
+                return [binary, str(script), "--url", f"{bind_host}:{local_port}"]
+
+            with patch("trainsh.services.cloudflared.forward_args", side_effect=forward_args):
+                runner = threading.Thread(target=supervisor.run, kwargs={"max_restarts": 1})
+                runner.start()
+                for _ in range(100):
+                    if events == ["up"]:
+                        break
+                    threading.Event().wait(0.05)
+                supervisor.process.kill()
+                for _ in range(200):
+                    if events.count("up") == 2:
+                        break
+                    threading.Event().wait(0.05)
+                supervisor.stop()
+                runner.join(timeout=10)
+            self.assertEqual(events[:3], ["up", "down", "up"])
+            self.assertEqual(supervisor.restarts, 1)
+            self.assertIsNotNone(supervisor.process.poll())
+
+
+if __name__ == "__main__":
+    unittest.main()
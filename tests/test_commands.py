@@ -64,11 +64,16 @@ COMMANDS = [
     ("recipe remove", "Usage"),
     ("recipe run --help", "Use `train help` or `train --help`."),
     ("recipe resume", "train recipe resume"),
+    ("recipe reconnect --bogus", "Usage: train recipe reconnect"),
+    ("recipe cancel --bogus", "Usage: train recipe cancel"),
+    ("recipe run nanochat --max-runtime soon", "Invalid --max-runtime value"),
     ("recipe status", "Recipe sessions"),
     ("recipe logs", "No execution logs found"),
     ("recipe jobs", "No job states found"),
     ("recipe schedule --help", "Use `train help` or `train --help`."),
     ("recipe schedule list", "No scheduled recipes found"),
+    ("dashboard --help", "Use `train help` or `train --help`."),
+    ("dashboard --bogus", "Unknown option"),
 
     # Host
     ("host --help", "Use `train help` or `train --help`."),
@@ -82,9 +87,12 @@ COMMANDS = [
     ("host clone", "Usage"),
     ("host files", "Usage"),
     ("host check", "Usage"),
+    ("host bootstrap", "Usage"),
     ("host flash-attn", "Usage"),
     ("host flash-attn --matrix", "Compatibility Matrix"),
     ("host remove", "Usage"),
+    ("host queue", "No queued operations"),
+    ("host queue bogus", "Usage"),
     ("host connect", "Unknown subcommand"),
     ("host browse", "Unknown subcommand"),
     ("host test", "Unknown subcommand"),
@@ -145,6 +153,10 @@ COMMANDS = [
     ("vast remove", "Usage"),
     ("vast reboot", "Usage"),
     ("vast search", "GPU"),
+    ("vast create", "Usage"),
+    ("vast template show", "Usage"),
+    ("vast events --limit x", "Usage"),
+    ("vast watch --bogus", "Usage"),
     ("vast keys", "SSH"),
     ("vast attach-key", "Key file"),
     ("vast connect", "Unknown subcommand"),
@@ -157,6 +169,10 @@ COMMANDS = [
     ("pricing currency", "Display currency"),
     ("pricing colab", "Colab Subscription"),
     ("pricing vast", "Vast.ai"),
+    ("pricing billing --month 2026-13", "Invalid month"),
+    ("pricing report --from 2026-02-30", "Invalid date range"),
+    ("pricing compare 4090 --hours 0", "--hours must be positive"),
+    ("pricing history --days x", "invalid int value"),
     ("pricing convert 10 USD CNY", "="),
 
     # vLLM
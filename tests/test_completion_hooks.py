@@ -0,0 +1,128 @@
+import tempfile
+import unittest
+from contextlib import redirect_stdout
+from io import StringIO
+from pathlib import Path
+from types import SimpleNamespace
+from unittest.mock import patch
+
+from trainsh.commands.recipe_views import cmd_status
+from trainsh.core.executor_main import DSLExecutor, run_recipe
+from trainsh.core.executor_runtime import WindowInfo
+from trainsh.core.job_state import JobStateManager
+from trainsh.core.provider_recipe import ExecutorProviderRecipeMixin
+from trainsh.core.runtime_store import RuntimeStore
+from trainsh.pyrecipe import Recipe
+from trainsh.pyrecipe.models import PythonRecipeError
+
+PARENT = """from trainsh import Recipe
+
+recipe = Recipe("train")
+recipe.variables["WORKDIR"] = "{root}/run"
+recipe.shell("{command}", id="train")
+recipe.on_complete("post", variables={{"OUT": "${{WORKDIR}}/out", "CODE": "${{EXIT_CODE}}", "PARENT": "${{JOB_ID}}"}})
+recipe.on_complete("notify_failure", when="failure")
+"""
+
+POST = """from trainsh import Recipe
+
+recipe = Recipe("post")
+recipe.variables.update(OUT="", CODE="", PARENT="")
+recipe.shell("echo $OUT $CODE $PARENT > {root}/post.txt", id="collect")
+"""
+
+
+class CompletionHookTests(unittest.TestCase):
+    def _run(self, command):
+        root = Path(self.tmpdir.name)
+        (root / "train.pyrecipe").write_text(PARENT.format(root=root, command=command), encoding="utf-8")
+        (root / "post.pyrecipe").write_text(POST.format(root=root), encoding="utf-8")
+        (root / "notify_failure.pyrecipe").write_text(POST.format(root=root).replace("post.txt", "failed.txt"), encoding="utf-8")
+        with redirect_stdout(StringIO()):
+            ok = run_recipe(str(root / "train.pyrecipe"), job_id="parent1")
+        return ok, RuntimeStore(self.runtime)
+
+    def setUp(self):
+        self.tmpdir = tempfile.TemporaryDirectory()
+        self.addCleanup(self.tmpdir.cleanup)
+        root = Path(self.tmpdir.name)
+        self.runtime = root / "config" / "runtime"
+        self.manager = JobStateManager(str(self.runtime))
+        for target in (
+            patch("trainsh.core.executor_main.load_config", return_value={"tmux": {}}),
+            patch("trainsh.core.executor_main.CONFIG_DIR", root / "config"),
+            patch("trainsh.core.executor_main.RUNTIME_STATE_DIR", self.runtime),
+            patch("trainsh.runtime.CONFIG_DIR", self.runtime),
+        ):
+            target.start()
+            self.addCleanup(target.stop)
+
+    def test_completed_run_spawns_post_recipe_with_bindings(self):
+        ok, store = self._run("true")
+        self.assertTrue(ok)
+        root = Path(self.tmpdir.name)
+        self.assertEqual((root / "post.txt").read_text().strip(), f"{root}/run/out 0 parent1")
+        self.assertFalse((root / "failed.txt").exists())
+
+        [child_id] = self.manager.load("parent1").on_complete_job_ids
+        child = store.get_run(child_id)
+        self.assertEqual((child["parent_run_id"], child["parent_step_id"], child["success"]), ("parent1", "on_complete", True))
+
+        with patch("trainsh.core.job_state.JobStateManager", return_value=self.manager), redirect_stdout(StringIO()) as out:
+            cmd_status(["parent1"])
+        self.assertIn(f"On complete: job {child_id}", out.getvalue())
+
+    def test_failed_run_triggers_failure_hooks(self):
+        ok, store = self._run("exit 3")
+        self.assertFalse(ok)
+        root = Path(self.tmpdir.name)
+        self.assertIn(" 3 parent1", (root / "post.txt").read_text())
+        self.assertTrue((root / "failed.txt").exists())
+        self.assertEqual(len(self.manager.load("parent1").on_complete_job_ids), 2)
+
+    def test_interrupted_run_fires_failure_hooks_as_cancelled(self):
+        root = Path(self.tmpdir.name)
+        original = DSLExecutor._execute_step
+        interrupted = []
+
+        def interrupt_first_step(executor, step):
+            if not interrupted:
+                interrupted.append(step)
+                raise KeyboardInterrupt
+            return original(executor, step)
+
+        with patch.object(DSLExecutor, "_execute_step", interrupt_first_step):
+            with self.assertRaises(KeyboardInterrupt):
+                self._run("true")
+        self.assertEqual((root / "post.txt").read_text().split(), [f"{root}/run/out", "parent1"])
+        self.assertTrue((root / "failed.txt").exists())
+        job = self.manager.load("parent1")
+        self.assertEqual((job.outcome, len(job.on_complete_job_ids)), ("cancelled", 2))
+
+    def test_session_workdir_comes_from_the_last_open_session(self):
+        clients = {
+            "local": SimpleNamespace(display_message=lambda target, fmt: SimpleNamespace(returncode=1, stdout="")),
+            "gpu": SimpleNamespace(display_message=lambda target, fmt: SimpleNamespace(returncode=0, stdout="/w/run\n")),
+        }
+        executor = SimpleNamespace(
+            ctx=SimpleNamespace(
+                windows={
+                    "a": WindowInfo("a", "gpu", "train-a"),
+                    "b": WindowInfo("b", "local", "train-b"),
+                    "c": WindowInfo("c", "local"),
+                }
+            ),
+            get_tmux_client=clients.__getitem__,
+        )
+        self.assertEqual(ExecutorProviderRecipeMixin._session_workdir(executor), ("gpu", "/w/run"))
+
+    def test_rejects_unknown_trigger(self):
+        recipe = Recipe("demo")
+        with self.assertRaises(PythonRecipeError):
+            recipe.on_complete("post", when="sometimes")
+        recipe.on_complete("post", when="success", hosts={"gpu": "gpu"})
+        self.assertEqual(recipe.completion_hooks[0]["when"], "completed")
+
+
+if __name__ == "__main__":
+    unittest.main()
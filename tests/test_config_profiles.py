@@ -0,0 +1,102 @@
+import os
+import subprocess
+import sys
+import tempfile
+import unittest
+from contextlib import redirect_stdout
+from io import StringIO
+from pathlib import Path
+from unittest.mock import patch
+
+import yaml
+
+from trainsh import config
+from trainsh.commands import config_profile
+from trainsh.core.secrets import SecretsManager
+from trainsh.services import config_profiles
+
+ROOT = Path(__file__).resolve().parents[1]
+
+
+class ConfigProfileTests(unittest.TestCase):
+    def setUp(self):
+        self.tmpdir = tempfile.TemporaryDirectory()
+        self.addCleanup(self.tmpdir.cleanup)
+        self.root = Path(self.tmpdir.name)
+        self.config_file = self.root / "config.yaml"
+        patchers = [
+            patch("trainsh.config.CONFIG_FILE", self.config_file),
+            patch("trainsh.config.CONFIG_DIR", self.root),
+            patch("trainsh.constants.CONFIG_DIR", self.root),
+            patch("trainsh.constants.PROFILES_DIR", self.root / "profiles"),
+            patch("trainsh.constants.ACTIVE_PROFILE_FILE", self.root / "active_profile"),
+            patch.dict(os.environ, {}, clear=False),
+        ]
+        for patcher in patchers:
+            patcher.start()
+            self.addCleanup(patcher.stop)
+        os.environ.pop("TRAINSH_PROFILE", None)
+
+    def run_cmd(self, *args):
+        out = StringIO()
+        with redirect_stdout(out):
+            config_profile.main(list(args))
+        return out.getvalue()
+
+    def test_switch_overlays_profile_and_keeps_base_on_save(self):
+        config.set_config_value("ui.currency", "CNY")
+        self.run_cmd("add", "work", "--currency", "USD", "--secret-prefix", "WORK_", "--set", "vast.link_cache_secs=5")
+        self.assertEqual(config.get_config_value("ui.currency"), "CNY")
+
+        self.assertIn("Switched to profile: work", self.run_cmd("switch", "work"))
+        self.assertEqual(config.get_config_value("ui.currency"), "USD")
+        self.assertEqual(config.get_config_value("vast.link_cache_secs"), 5)
+
+        # Unrelated edits land in the base config; edits to overridden keys update the profile.
+        config.set_config_value("dashboard.cache_ttl_secs", 10)
+        config.set_config_value("ui.currency", "EUR")
+        raw = yaml.safe_load(self.config_file.read_text())
+        self.assertEqual(raw["ui"]["currency"], "CNY")
+        self.assertEqual(raw["dashboard"]["cache_ttl_secs"], 10)
+        self.assertEqual(raw["profiles"]["work"]["ui"]["currency"], "EUR")
+
+        with patch.dict(os.environ, {"TRAINSH_PROFILE": ""}):
+            self.assertEqual(config.get_config_value("ui.currency"), "CNY")
+        self.run_cmd("switch", "--clear")
+        self.assertEqual(config.get_config_value("ui.currency"), "CNY")
+        with self.assertRaises(SystemExit), redirect_stdout(StringIO()):
+            config_profile.main(["switch", "missing"])
+
+    def test_secret_prefix_scopes_secret_names(self):
+        self.run_cmd("add", "work", "--secret-prefix", "WORK_")
+        config_profiles.switch_profile("work")
+        with patch.dict(os.environ, {"VAST_API_KEY": "personal", "WORK_VAST_API_KEY": "client"}):
+            manager = SecretsManager()
+            manager._backend_loaded = True
+            self.assertEqual(manager.get_vast_api_key(), "client")
+        config_profiles.switch_profile("")
+        with patch.dict(os.environ, {"VAST_API_KEY": "personal", "WORK_VAST_API_KEY": "client"}):
+            manager = SecretsManager()
+            manager._backend_loaded = True
+            self.assertEqual(manager.get_vast_api_key(), "personal")
+
+    def test_isolated_profile_moves_data_paths(self):
+        env = {**os.environ, "XDG_CONFIG_HOME": str(self.root / "xdg"), "TRAINSH_PROFILE": "work", "PYTHONPATH": str(ROOT)}
+        (self.root / "xdg" / "tmux-trainsh" / "profiles" / "work").mkdir(parents=True)
+        script = "from trainsh import constants as c; print(c.HOSTS_FILE); print(c.RUNTIME_STATE_DIR)"
+        out = subprocess.run([sys.executable, "-c", script], env=env, capture_output=True, text=True, check=True).stdout
+        hosts_file, runtime_dir = out.splitlines()
+        self.assertEqual(hosts_file, str(self.root / "xdg" / "tmux-trainsh" / "profiles" / "work" / "hosts.yaml"))
+        self.assertTrue(runtime_dir.endswith(os.path.join("profiles", "work", "runtime")))
+
+        env["TRAINSH_PROFILE"] = "personal"
+        out = subprocess.run([sys.executable, "-c", script], env=env, capture_output=True, text=True, check=True).stdout
+        self.assertEqual(out.splitlines()[0], str(self.root / "xdg" / "tmux-trainsh" / "hosts.yaml"))
+
+        self.run_cmd("add", "lab", "--isolate")
+        self.assertTrue((self.root / "profiles" / "lab").is_dir())
+        self.assertIn("isolated data", self.run_cmd("list"))
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,177 @@
+import io
+import tempfile
+import unittest
+from contextlib import redirect_stderr, redirect_stdout
+from datetime import datetime, timedelta
+from pathlib import Path
+from types import SimpleNamespace
+from unittest.mock import patch
+
+from trainsh.commands import host_monitor
+from trainsh.commands.remote_run import run_remote_command
+from trainsh.core.models import Host, HostType
+from trainsh.core.runtime_store import RuntimeStore
+from trainsh.services import connectivity
+from trainsh.services.connectivity import ConnectivityMonitor, HostOfflineError
+from trainsh.services.ssh import SSHResult
+
+
+class ConnectivityMonitorTests(unittest.TestCase):
+    def test_record_emits_events_only_on_transition(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            monitor = ConnectivityMonitor(Path(tmpdir))
+            self.assertTrue(monitor.record("gpu", True))
+            self.assertFalse(monitor.record("gpu", True))
+            self.assertTrue(monitor.record("gpu", False, "timeout"))
+
+            events = [item["event"] for item in RuntimeStore(Path(tmpdir)).list_events("connectivity")]
+            self.assertEqual(events, ["host_online", "host_offline"])
+            self.assertEqual(monitor.host_states()["gpu"]["error"], "timeout")
+
+    def test_ensure_online_short_circuits_recent_failures(self):
+        host = Host(name="gpu", type=HostType.SSH, hostname="10.0.0.2")
+        with tempfile.TemporaryDirectory() as tmpdir:
+            monitor = ConnectivityMonitor(Path(tmpdir))
+            monitor.ensure_online("gpu", host, grace_secs=30)
+
+            monitor.record("gpu", False, "Connection refused")
+            with self.assertRaisesRegex(HostOfflineError, "Connection refused"):
+                monitor.ensure_online("gpu", host, grace_secs=30)
+
+            with monitor._locked() as payload:
+                payload["hosts"]["gpu"]["checked_at"] = (datetime.now() - timedelta(minutes=5)).isoformat()
+            monitor.ensure_online("gpu", host, grace_secs=30)
+            monitor.ensure_online("local", Host(name="local", type=HostType.LOCAL), grace_secs=30)
+
+    def test_explain_failure_distinguishes_network_loss(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            monitor = ConnectivityMonitor(Path(tmpdir))
+            with patch("trainsh.services.connectivity.network_available", return_value=False) as probe:
+                self.assertIn("Network is unavailable", monitor.explain_failure("gpu", "timeout"))
+                self.assertIn("Network is unavailable", monitor.explain_failure("gpu", "timeout"))
+            probe.assert_called_once()
+
+            with monitor._locked() as payload:
+                payload["network"]["checked_at"] = (datetime.now() - timedelta(minutes=5)).isoformat()
+            with patch("trainsh.services.connectivity.network_available", return_value=True):
+                self.assertIn("unreachable: timeout", monitor.explain_failure("gpu", "timeout"))
+
+    def test_probe_addresses_come_from_config(self):
+        with patch("trainsh.config.get_config_value", return_value="10.0.0.1:22, 10.0.0.2:22"):
+            self.assertEqual(connectivity.probe_addresses(), ["10.0.0.1:22", "10.0.0.2:22"])
+        with patch("trainsh.config.get_config_value", return_value=[]), patch("socket.create_connection") as dial:
+            self.assertTrue(connectivity.network_available())
+        dial.assert_not_called()
+
+    def test_transfer_and_tmux_paths_stop_at_offline_hosts(self):
+        from trainsh.commands import transfer
+        from trainsh.core.models import TransferEndpoint
+
+        with tempfile.TemporaryDirectory() as tmpdir, patch(
+            "trainsh.services.connectivity.RUNTIME_STATE_DIR", Path(tmpdir)
+        ), patch("trainsh.config.get_config_value", return_value=30):
+            ConnectivityMonitor().record("gpu", False, "No route to host")
+            out = io.StringIO()
+            with redirect_stdout(out), self.assertRaises(SystemExit):
+                transfer._ensure_hosts_online(
+                    TransferEndpoint(type="local", path="./data"), TransferEndpoint(type="host", path="/data", host_id="gpu")
+                )
+            self.assertIn("Host gpu was offline", out.getvalue())
+            transfer._ensure_hosts_online(TransferEndpoint(type="host", path="/data", host_id="cpu"))
+
+            from trainsh.core.executor_tmux import TmuxControlHelper
+
+            executor = SimpleNamespace(
+                _resolve_host=lambda ref: "root@10.0.0.2",
+                allocate_window_session_name=lambda: "train_x_0",
+                logger=None,
+            )
+            ok, message = TmuxControlHelper(executor, SimpleNamespace).cmd_tmux_open(["@gpu", "as", "main"])
+            self.assertFalse(ok)
+            self.assertIn("No route to host", message)
+
+            from trainsh.core.executor_transfer import TransferHelper
+
+            executor = SimpleNamespace(
+                recipe=SimpleNamespace(hosts={"gpu": "root@10.0.0.2"}, storages={}), logger=None, _interpolate=str
+            )
+            helper = TransferHelper(executor, resolve_vast_host=str, host_from_ssh_spec=str)
+            with patch("trainsh.commands.storage.load_storages", return_value={}):
+                ok, message = helper.transfer("@gpu:/data", "./data")
+        self.assertFalse(ok)
+        self.assertIn("Host gpu was offline", message)
+
+    def test_queue_replays_idempotent_operations(self):
+        calls = []
+        with tempfile.TemporaryDirectory() as tmpdir, patch.dict(
+            connectivity.QUEUEABLE_OPERATIONS,
+            {"vast.stop": lambda params: calls.append(params)},
+        ):
+            monitor = ConnectivityMonitor(Path(tmpdir))
+            with self.assertRaises(ValueError):
+                monitor.enqueue("vast.destroy", {"instance_id": 1})
+            monitor.enqueue("vast.stop", {"instance_id": 7})
+            self.assertEqual(len(monitor.list_queue()), 1)
+
+            done, failed = monitor.drain_queue()
+            self.assertEqual((len(done), len(failed)), (1, 0))
+            self.assertEqual(calls, [{"instance_id": 7}])
+            self.assertEqual(monitor.list_queue(), [])
+
+    def test_network_available_uses_addresses(self):
+        with patch("socket.create_connection", side_effect=OSError("down")):
+            self.assertFalse(connectivity.network_available(["10.0.0.1:53"], timeout=0.1))
+        self.assertTrue(connectivity.network_available([]))
+
+
+class ConnectivityCommandTests(unittest.TestCase):
+    def test_monitor_pass_records_and_drains(self):
+        hosts = {"gpu": Host(name="gpu", type=HostType.SSH, hostname="h")}
+        with tempfile.TemporaryDirectory() as tmpdir:
+            monitor = ConnectivityMonitor(Path(tmpdir))
+            out = io.StringIO()
+            with patch("trainsh.services.connectivity.network_available", return_value=False), redirect_stdout(out):
+                self.assertFalse(host_monitor.run_monitor_pass(monitor, hosts))
+            self.assertIn("gpu: offline", out.getvalue())
+
+            out = io.StringIO()
+            with patch("trainsh.services.connectivity.network_available", return_value=True), patch(
+                "trainsh.services.connectivity.probe_host", return_value=(True, "")
+            ), redirect_stdout(out):
+                self.assertTrue(host_monitor.run_monitor_pass(monitor, hosts))
+            self.assertIn("gpu: online", out.getvalue())
+
+    def test_run_remote_command_short_circuits_offline_host(self):
+        host = Host(name="gpu", type=HostType.SSH, hostname="h")
+        with tempfile.TemporaryDirectory() as tmpdir, patch(
+            "trainsh.commands.remote_run.ConnectivityMonitor",
+            side_effect=lambda: ConnectivityMonitor(Path(tmpdir)),
+        ):
+            ConnectivityMonitor(Path(tmpdir)).record("gpu", False, "No route to host")
+            out = io.StringIO()
+            with patch("trainsh.services.host_exec.SSHClient.from_host") as from_host, redirect_stdout(out):
+                with self.assertRaises(SystemExit):
+                    run_remote_command(host, "hostname", label="gpu")
+            from_host.assert_not_called()
+            self.assertIn("No route to host", out.getvalue())
+
+    def test_run_remote_command_explains_connection_failure(self):
+        host = Host(name="gpu", type=HostType.SSH, hostname="h")
+        failed = SSHResult(exit_code=255, stdout="", stderr="Connection timed out")
+        with tempfile.TemporaryDirectory() as tmpdir, patch(
+            "trainsh.commands.remote_run.ConnectivityMonitor",
+            side_effect=lambda: ConnectivityMonitor(Path(tmpdir)),
+        ), patch("trainsh.services.connectivity.network_available", return_value=False):
+            out = io.StringIO()
+            with patch(
+                "trainsh.services.host_exec.SSHClient.from_host",
+                return_value=SimpleNamespace(run=lambda command, timeout=None: failed),
+            ), redirect_stdout(out), redirect_stderr(io.StringIO()):
+                with self.assertRaises(SystemExit):
+                    run_remote_command(host, "hostname", label="gpu")
+            self.assertIn("Network is unavailable", out.getvalue())
+            self.assertFalse(ConnectivityMonitor(Path(tmpdir)).host_states()["gpu"]["online"])
+
+
+if __name__ == "__main__":
+    unittest.main()
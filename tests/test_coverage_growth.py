@@ -230,7 +230,7 @@ class CoverageGrowthTests(unittest.TestCase):
             self.assertEqual(executor._exec_provider_latest_only("bad")[0], False)
 
             missing_db = Path(tmpdir) / "runtime.db"
-            with patch("trainsh.constants.RUNTIME_STATE_DIR", Path(tmpdir) / "runtime"):
+            with patch.object(executor, "runtime_state", str(Path(tmpdir) / "runtime")):
                 ok, msg = executor._exec_provider_latest_only({"fail_if_unknown": True})
             self.assertFalse(ok)
             self.assertIn("runtime state not found", msg)
@@ -484,7 +484,7 @@ class CoverageGrowthTests(unittest.TestCase):
         state_manager = SimpleNamespace(
             list_running=MagicMock(side_effect=[[job], [], [], []]),
             list_all=MagicMock(side_effect=[[job], [job], [], [job] * 20]),
-            load=MagicMock(side_effect=[None, None]),
+            load=MagicMock(side_effect=lambda job_id: job if job_id == job.job_id else None),
         )
         with patch("trainsh.core.job_state.JobStateManager", return_value=state_manager), patch(
             "trainsh.core.local_tmux.LocalTmuxClient",
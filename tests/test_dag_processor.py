@@ -52,7 +52,7 @@ class DagProcessorEdgeTests(unittest.TestCase):
             self.assertEqual(dag.normalized_max_active_runs, 1)
             self.assertEqual(dag.normalized_max_active_runs_per_dag, 1)
             self.assertTrue(dag.is_interval_schedulable)
-            with patch("trainsh.core.dag_processor.load_python_recipe", return_value="recipe") as mocked_load:
+            with patch("trainsh.core.dag_processor.load_recipe", return_value="recipe") as mocked_load:
                 self.assertEqual(dag.load_recipe(), "recipe")
             mocked_load.assert_called_once()
 
@@ -0,0 +1,244 @@
+import io
+import json
+import tempfile
+import unittest
+from contextlib import redirect_stdout
+from pathlib import Path
+from types import SimpleNamespace
+from unittest.mock import patch
+
+from trainsh.commands import dashboard as dashboard_cmd
+from trainsh.core.models import Host, HostType
+from trainsh.core.runtime_store import RuntimeStore
+from trainsh.services import dashboard
+
+
+def _seed_store(root: Path) -> RuntimeStore:
+    store = RuntimeStore(root)
+    store.append_run({"run_id": "run-live", "recipe_name": "train", "state": "running", "started_at": "2026-01-01T00:00:00"})
+    store.append_task(
+        {
+            "run_id": "run-live",
+            "task_id": "sync_data",
+            "state": "running",
+            "start_date": "2026-01-01T00:01:00",
+            "details": {"source": "@gpu:/data", "dest": "./data"},
+        }
+    )
+    for percent in (10.0, 42.5):
+        store.append_event(
+            {
+                "run_id": "run-live",
+                "event": "transfer:progress",
+                "payload": {"source": "@gpu:/data", "dest": "./data", "bytes_done": 425, "bytes_total": 1000, "percent": percent},
+                "ts": "2026-01-01T00:02:00",
+            }
+        )
+    store.append_run(
+        {
+            "run_id": "run-bad",
+            "recipe_name": "eval",
+            "state": "failed",
+            "success": False,
+            "started_at": "2025-12-31T00:00:00",
+            "ended_at": "2025-12-31T00:05:00",
+        }
+    )
+    store.append_task({"run_id": "run-bad", "task_id": "score", "state": "failed", "updated_at": "2025-12-31T00:05:00"})
+    return store
+
+
+class DashboardSnapshotTests(unittest.TestCase):
+    def test_collectors_report_running_transfers_and_failures(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            store = _seed_store(Path(tmpdir))
+            executions, transfers = dashboard.collect_executions(store)
+            failures = dashboard.collect_recent_failures(store)
+
+        self.assertEqual(executions, [{"run_id": "run-live", "recipe": "train", "started_at": "2026-01-01T00:00:00", "current_steps": ["sync_data"]}])
+        self.assertEqual(transfers[0]["source"], "@gpu:/data")
+        self.assertEqual(transfers[0]["progress"]["percent"], 42.5)
+        self.assertEqual(failures[0]["run_id"], "run-bad")
+        self.assertEqual(failures[0]["failed_steps"], ["score"])
+
+    def test_host_status_probes_and_skips(self):
+        hosts = {
+            "box": Host(name="box", type=HostType.SSH, hostname="10.0.0.2"),
+            "here": Host(name="here", type=HostType.LOCAL, hostname="localhost"),
+        }
+        with patch("trainsh.services.ssh.SSHClient.from_host", return_value=SimpleNamespace(run=lambda *a, **k: SimpleNamespace(success=False, stdout="", stderr="refused", exit_code=255))):
+            rows = dashboard.collect_host_status(hosts)
+        self.assertEqual({row["name"]: row["reachable"] for row in rows}, {"box": False, "here": True})
+        rows = dashboard.collect_host_status(hosts, probe=False)
+        self.assertEqual({row["reachable"] for row in rows}, {None})
+
+    def test_vast_instances_sum_running_cost(self):
+        client = SimpleNamespace(
+            list_instances=lambda: [
+                SimpleNamespace(id=1, label="a", actual_status="running", num_gpus=1, gpu_name="H100", dph_total=2.5),
+                SimpleNamespace(id=2, label="", actual_status="exited", num_gpus=2, gpu_name="A100", dph_total=1.0),
+            ]
+        )
+        with patch("trainsh.services.vast_api.get_vast_client", return_value=client):
+            payload = dashboard.collect_vast_instances()
+        self.assertEqual(payload["hourly_total_usd"], 2.5)
+        self.assertEqual(len(payload["instances"]), 2)
+
+        with patch("trainsh.services.vast_api.get_vast_client", side_effect=RuntimeError("no key")):
+            payload = dashboard.collect_vast_instances()
+        self.assertEqual(payload["error"], "no key")
+
+    def test_snapshot_is_cached_until_refresh(self):
+        with tempfile.TemporaryDirectory() as tmpdir, patch(
+            "trainsh.commands.host.load_hosts", return_value={}
+        ):
+            root = Path(tmpdir)
+            _seed_store(root)
+            first = dashboard.dashboard_snapshot(include_vast=False, max_age=60, state_dir=root)
+            self.assertFalse(first["cached"])
+            self.assertTrue((root / "dashboard.json").exists())
+
+            second = dashboard.dashboard_snapshot(include_vast=False, max_age=60, state_dir=root)
+            self.assertTrue(second["cached"])
+            self.assertEqual(second["generated_at"], first["generated_at"])
+
+            third = dashboard.dashboard_snapshot(refresh=True, include_vast=False, max_age=60, state_dir=root)
+            self.assertFalse(third["cached"])
+
+            unprobed = dashboard.dashboard_snapshot(probe_hosts=False, include_vast=False, max_age=60, state_dir=root)
+            self.assertFalse(unprobed["cached"])
+            self.assertEqual(unprobed["options"], {"probe_hosts": False, "include_vast": False})
+
+    def test_snapshot_includes_queued_transfers(self):
+        from trainsh.services.transfer_queue import TransferQueue
+
+        with tempfile.TemporaryDirectory() as tmpdir, patch(
+            "trainsh.commands.host.load_hosts", return_value={}
+        ):
+            root = Path(tmpdir)
+            queue = TransferQueue(root, config={})
+            running = queue.add("./ckpt", "@gpu:/ckpt")
+            queue.add("./logs", "@gpu:/logs")
+            queue.claim_next(pid=1)
+            queue.record_progress(running["id"], {"bytes_done": 512, "bytes_total": 1024, "percent": 50.0})
+            snapshot = dashboard.dashboard_snapshot(include_vast=False, max_age=0, state_dir=root)
+
+        rows = {row["transfer_id"]: row for row in snapshot["transfers"]}
+        self.assertEqual(rows[running["id"]]["status"], "running")
+        self.assertEqual(rows[running["id"]]["progress"]["percent"], 50.0)
+        self.assertEqual(sorted(row["status"] for row in rows.values()), ["queued", "running"])
+
+    def test_command_prints_text_and_json(self):
+        snapshot = {
+            "generated_at": "2026-01-01T00:00:00",
+            "cached": False,
+            "hosts": [{"name": "box", "type": "ssh", "hostname": "h", "reachable": True}],
+            "hosts_reachable": 1,
+            "hosts_unreachable": 0,
+            "executions": [],
+            "transfers": [
+                {"run_id": "", "transfer_id": "ab12cd34", "status": "running", "source": "./ckpt", "dest": "@gpu:/ckpt",
+                 "progress": {"bytes_done": 512, "bytes_total": 1024, "percent": 50.0}},
+            ],
+            "vast": {"instances": [], "hourly_total_usd": 0.0, "error": ""},
+            "recent_failures": [],
+        }
+        with patch("trainsh.services.dashboard.dashboard_snapshot", return_value=snapshot) as mocked:
+            out = io.StringIO()
+            with redirect_stdout(out):
+                dashboard_cmd.main(["--no-probe"])
+            self.assertIn("box", out.getvalue())
+            self.assertIn("online", out.getvalue())
+            self.assertIn("ab12cd34   running  ./ckpt -> @gpu:/ckpt  [512 B / 1.0 KiB (50%)]", out.getvalue())
+            self.assertFalse(mocked.call_args.kwargs["probe_hosts"])
+
+            out = io.StringIO()
+            with redirect_stdout(out):
+                dashboard_cmd.main(["--json"])
+            self.assertEqual(json.loads(out.getvalue())["hosts_reachable"], 1)
+
+
+class PrometheusExporterTests(unittest.TestCase):
+    def test_host_samples_become_gauges(self):
+        from trainsh.services.prometheus_exporter import host_families, render_families
+
+        samples = {
+            "gpu-a": {"cpu_percent": 12.5, "mem_used": 1024, "mem_total": 4096, "net_rx_bps": None,
+                      "gpus": [{"index": 0, "name": "H100", "utilization": 97.0, "memory_used_mb": 2.0, "power_w": 310.5}]},
+            "down": {"error": "timed out"},
+        }
+        text = render_families(host_families(samples))
+        self.assertIn("# TYPE trainsh_gpu_utilization_percent gauge", text)
+        self.assertIn('trainsh_gpu_utilization_percent{gpu="0",host="gpu-a",name="H100"} 97', text)
+        self.assertIn('trainsh_gpu_memory_used_bytes{gpu="0",host="gpu-a",name="H100"} 2097152', text)
+        self.assertIn('trainsh_host_cpu_percent{host="gpu-a"} 12.5', text)
+        self.assertIn('trainsh_host_up{host="down"} 0', text)
+        self.assertNotIn("trainsh_host_network_receive_bytes_per_second{", text)
+
+    def test_runtime_gauges_cover_executions_transfers_and_queues(self):
+        from trainsh.services.prometheus_exporter import render_families, runtime_families
+        from trainsh.services.transfer_queue import TransferQueue
+
+        with tempfile.TemporaryDirectory() as tmpdir:
+            root = Path(tmpdir)
+            store = _seed_store(root)
+            store.append_event(
+                {
+                    "run_id": "run-live",
+                    "event": "transfer:progress",
+                    "payload": {"source": "@gpu:/data", "dest": "./data", "percent": 50.0, "speed_bps": 2048.0},
+                    "ts": "2026-01-01T00:03:00",
+                }
+            )
+            store.append_run({"run_id": "run-next", "recipe_name": "train", "state": "queued"})
+            queue = TransferQueue(root, config={})
+            queue.add("./ckpt", "@gpu:/ckpt")
+            queue.add("./logs", "@gpu:/logs")
+            text = render_families(runtime_families(root))
+
+        self.assertIn('trainsh_executions_running{recipe="train"} 1', text)
+        self.assertIn('trainsh_execution_steps_running{recipe="train",run_id="run-live"} 1', text)
+        self.assertIn("trainsh_transfers_active 1", text)
+        self.assertIn('trainsh_transfer_throughput_bytes_per_second{dest="./data",id="run-live/sync_data",source="@gpu:/data"} 2048', text)
+        self.assertIn('trainsh_transfer_progress_ratio{dest="./data",id="run-live/sync_data",source="@gpu:/data"} 0.5', text)
+        self.assertIn('trainsh_queue_depth{queue="transfers"} 2', text)
+        self.assertIn('trainsh_queue_depth{queue="executions"} 1', text)
+
+    def test_exporter_serves_metrics_over_http(self):
+        from urllib.error import HTTPError
+        from urllib.request import urlopen
+
+        from trainsh.services.host_metrics import _emit, clear_host_metrics
+        from trainsh.services.prometheus_exporter import CONTENT_TYPE, MetricsExporter
+
+        self.addCleanup(clear_host_metrics, "box")
+        _emit({"event": "host:metrics", "host": "box", "cpu_percent": 40.0, "gpus": []}, 5)
+        with tempfile.TemporaryDirectory() as tmpdir:
+            exporter = MetricsExporter({"box": None}, port=0, state_dir=Path(tmpdir))
+            exporter.samplers = []
+            exporter.start()
+            self.addCleanup(exporter.stop)
+            host, port = exporter.address
+            with urlopen(f"http://{host}:{port}/metrics", timeout=10) as response:
+                body = response.read().decode("utf-8")
+                self.assertEqual(response.headers["Content-Type"], CONTENT_TYPE)
+            with self.assertRaises(HTTPError):
+                urlopen(f"http://{host}:{port}/nope", timeout=10)
+        self.assertIn('trainsh_host_cpu_percent{host="box"} 40', body)
+        self.assertIn('trainsh_queue_depth{queue="transfers"} 0', body)
+
+    def test_command_starts_exporter_for_requested_hosts(self):
+        exporter = SimpleNamespace(address=("127.0.0.1", 9999), stop=lambda: None)
+        exporter.start = lambda: exporter
+        hosts = {"a": Host(name="a", type=HostType.SSH, hostname="a"), "b": Host(name="b", type=HostType.SSH, hostname="b")}
+        with patch("trainsh.commands.host.load_hosts", return_value=hosts), patch(
+            "trainsh.services.prometheus_exporter.MetricsExporter", return_value=exporter
+        ) as cls, patch("threading.Event.wait", side_effect=KeyboardInterrupt), redirect_stdout(io.StringIO()) as out:
+            dashboard_cmd.main(["--prometheus", "--port", "9999", "--hosts", "b"])
+        self.assertEqual(list(cls.call_args.args[0]), ["b"])
+        self.assertEqual(cls.call_args.kwargs["port"], 9999)
+        self.assertIn("http://127.0.0.1:9999/metrics", out.getvalue())
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,107 @@
+import tempfile
+import unittest
+from contextlib import redirect_stdout
+from io import StringIO
+from pathlib import Path
+from unittest.mock import MagicMock, patch
+
+from trainsh.commands import host
+from trainsh.core.models import Host, HostType
+from trainsh.services.disk_usage import check_deletable, find_node, host_delete_path, host_disk_usage, parse_du_output
+from trainsh.services.ssh import SSHResult
+
+DU_OUTPUT = "4\t/root/.cache/pip\n2048\t/root/.cache/huggingface\n2100\t/root/.cache\n50\t/root/code\n2200\t/root\n"
+
+
+def ok(stdout=""):
+    return SSHResult(exit_code=0, stdout=stdout, stderr="")
+
+
+class DiskUsageTests(unittest.TestCase):
+    def setUp(self):
+        self.tmpdir = tempfile.TemporaryDirectory()
+        self.addCleanup(self.tmpdir.cleanup)
+        self.state = Path(self.tmpdir.name)
+        self.ssh = MagicMock()
+        hosts = {"gpu": Host(name="gpu", type=HostType.SSH, hostname="gpu.example.com")}
+        for target in (
+            patch("trainsh.commands.host.load_hosts", return_value=hosts),
+            patch("trainsh.services.host_exec.SSHClient.from_host", return_value=self.ssh),
+            patch("trainsh.config.load_config", return_value={}),
+        ):
+            target.start()
+            self.addCleanup(target.stop)
+
+    def test_parse_and_drill_down(self):
+        tree = parse_du_output(DU_OUTPUT + "garbage line\n")
+        self.assertEqual(tree["path"], "/root")
+        self.assertEqual(tree["size"], 2200 * 1024)
+        self.assertEqual([child["name"] for child in tree["children"]], [".cache", "code"])
+        node = find_node(tree, "/root/.cache/")
+        self.assertEqual([child["name"] for child in node["children"]], ["huggingface", "pip"])
+        self.assertIsNone(find_node(tree, "/srv"))
+        self.assertIsNone(parse_du_output(""))
+
+    def test_scan_is_cached_until_refresh(self):
+        self.ssh.run.return_value = ok(DU_OUTPUT)
+        first = host_disk_usage("gpu", "~", 2, state_dir=self.state)
+        self.assertEqual(first["status"], "ready")
+        self.assertFalse(first["cached"])
+        self.assertIn("du -x -k -d 2 \"$HOME\"", self.ssh.run.call_args.args[0])
+
+        second = host_disk_usage("gpu", "~", 2, state_dir=self.state)
+        self.assertTrue(second["cached"])
+        self.assertEqual(self.ssh.run.call_count, 1)
+        host_disk_usage("gpu", "~", 2, refresh=True, state_dir=self.state)
+        self.assertEqual(self.ssh.run.call_count, 2)
+
+    def test_slow_scan_moves_to_background_job(self):
+        self.ssh.run.side_effect = [
+            SSHResult(exit_code=-1, stdout="", stderr="Command timed out"),
+            ok("status=started\n"),
+            ok("status=done\n" + DU_OUTPUT),
+        ]
+        running = host_disk_usage("gpu", "/data", 3, state_dir=self.state)
+        self.assertEqual(running["status"], "running")
+        self.assertIn("nohup sh -c", self.ssh.run.call_args.args[0])
+
+        done = host_disk_usage("gpu", "/data", 3, background=True, state_dir=self.state)
+        self.assertEqual(done["status"], "ready")
+        self.assertEqual(done["tree"]["children"][0]["name"], ".cache")
+
+    def test_delete_is_guarded_and_invalidates_cache(self):
+        for path in ("/", "~", "/root", "/home/alice", "/data/../etc", "relative", "~/*"):
+            self.assertIsNotNone(check_deletable(path), path)
+        self.assertIsNone(check_deletable("~/.cache/huggingface"))
+        with self.assertRaises(PermissionError):
+            host_delete_path("gpu", "~/.cache", state_dir=self.state)
+        with self.assertRaises(ValueError):
+            host_delete_path("gpu", "/usr", confirm=True, state_dir=self.state)
+
+        self.ssh.run.return_value = ok(DU_OUTPUT)
+        host_disk_usage("gpu", "~", 2, state_dir=self.state)
+        host_delete_path("gpu", "~/.cache/huggingface", confirm=True, state_dir=self.state)
+        self.assertEqual(self.ssh.run.call_args.args[0], "rm -rf -- \"$HOME\"/.cache/huggingface")
+        self.assertFalse(host_disk_usage("gpu", "~", 2, state_dir=self.state)["cached"])
+
+    def test_cli_renders_tree_and_confirms_delete(self):
+        self.ssh.run.return_value = ok(DU_OUTPUT)
+        out = StringIO()
+        with patch("trainsh.services.disk_usage.RUNTIME_STATE_DIR", self.state), redirect_stdout(out):
+            host.main(["du", "gpu", "--depth", "1"])
+        self.assertIn("/root", out.getvalue())
+        self.assertIn(".cache/", out.getvalue())
+
+        self.ssh.run.reset_mock()
+        with patch("trainsh.commands.host_disk.prompt_input", return_value="y"), redirect_stdout(StringIO()):
+            host.main(["du", "gpu", "--delete", "~/.cache"])
+        self.ssh.run.assert_not_called()
+        with patch("trainsh.services.disk_usage.RUNTIME_STATE_DIR", self.state), patch(
+            "trainsh.commands.host_disk.prompt_input", return_value="~/.cache"
+        ), redirect_stdout(StringIO()):
+            host.main(["du", "gpu", "--delete", "~/.cache"])
+        self.ssh.run.assert_called_once()
+
+
+if __name__ == "__main__":
+    unittest.main()
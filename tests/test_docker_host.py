@@ -0,0 +1,88 @@
+import json
+import tempfile
+import unittest
+from contextlib import redirect_stdout
+from io import StringIO
+from pathlib import Path
+from unittest.mock import MagicMock, patch
+
+from trainsh.commands import docker as docker_cmd
+from trainsh.core.models import Host, HostType
+from trainsh.services import docker_host
+from trainsh.services.ssh import SSHClient, SSHResult
+
+
+def ok(stdout=""):
+    return SSHResult(exit_code=0, stdout=stdout, stderr="")
+
+
+class DockerHostTests(unittest.TestCase):
+    def setUp(self):
+        self.ssh = MagicMock()
+        hosts = {"gpu": Host(name="gpu", type=HostType.SSH, hostname="gpu.example.com")}
+        for target in (
+            patch("trainsh.commands.host.load_hosts", return_value=hosts),
+            patch("trainsh.services.docker_host.SSHClient.from_host", return_value=self.ssh),
+            patch("trainsh.config.load_config", return_value={}),
+        ):
+            target.start()
+            self.addCleanup(target.stop)
+
+    def test_list_and_lifecycle(self):
+        rows = [
+            {"ID": "a" * 64, "Names": "old", "Image": "ubuntu", "State": "exited", "Status": "Exited (0) 2 days ago"},
+            {"ID": "b" * 64, "Names": "trainer", "Image": "pytorch/pytorch", "State": "running", "Status": "Up 3 hours"},
+        ]
+        self.ssh.run.return_value = ok("\n".join(json.dumps(row) for row in rows) + "\nWARNING: noise\n")
+        containers = docker_host.list_containers("gpu")
+        self.assertEqual([item["name"] for item in containers], ["trainer", "old"])
+        self.assertEqual(containers[0]["id"], "b" * 12)
+        self.assertIn("docker ps -a --no-trunc --format '{{json .}}'", self.ssh.run.call_args.args[0])
+
+        out = StringIO()
+        with redirect_stdout(out):
+            docker_cmd.main(["ps", "gpu"])
+        self.assertIn("trainer", out.getvalue())
+
+        self.ssh.run.return_value = ok("trainer\n")
+        with redirect_stdout(StringIO()):
+            docker_cmd.main(["rm", "gpu", "trainer", "--force", "--yes"])
+        self.assertEqual(self.ssh.run.call_args.args[0], "docker rm -f trainer")
+        with self.assertRaises(ValueError):
+            docker_host.container_action("gpu", "stop", "x; rm -rf /")
+        with patch("trainsh.config.load_config", return_value={"docker": {"command": "sudo -n docker"}}):
+            self.assertEqual(
+                docker_host.logs_command("trainer", tail=10, since="1h"),
+                "sudo -n docker logs --timestamps --tail=10 -f --since=1h trainer 2>&1",
+            )
+
+    def test_stream_logs_appends_to_log_file(self):
+        client = MagicMock(connection_targets=[None])
+        client._build_ssh_args.return_value = ["sh", "-c", "printf 'step 1\\nstep 2\\n'"]
+        seen = []
+        with tempfile.TemporaryDirectory() as tmpdir, patch(
+            "trainsh.services.docker_host.SSHClient.from_host", return_value=client
+        ):
+            log_path = Path(tmpdir) / "docker" / "trainer.log"
+            code = docker_host.stream_logs("gpu", "trainer", follow=False, on_line=seen.append, log_path=log_path)
+            content = log_path.read_text()
+        self.assertEqual(code, 0)
+        self.assertEqual(seen, ["step 1", "step 2"])
+        self.assertTrue(content.endswith("step 1\nstep 2\n"))
+        self.assertIn("docker logs --timestamps --tail=200 trainer", client._build_ssh_args.call_args.args[0])
+
+    def test_exec_uses_tty_ssh_session(self):
+        self.ssh.connect_interactive.return_value = 0
+        docker_cmd.main(["exec", "gpu", "trainer", "--shell", "zsh"])
+        self.ssh.connect_interactive.assert_called_once_with("docker exec -it trainer zsh")
+
+        client = SSHClient(hostname="gpu.example.com")
+        with patch("trainsh.services.ssh.subprocess.run", return_value=MagicMock(returncode=0)) as run:
+            client.connect_interactive("docker exec -it trainer bash")
+        args = run.call_args.args[0]
+        self.assertEqual(args[:2], ["ssh", "-t"])
+        self.assertEqual(args[-1], "docker exec -it trainer bash")
+
+
+if __name__ == "__main__":
+    unittest.main()
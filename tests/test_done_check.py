@@ -0,0 +1,64 @@
+import tempfile
+import textwrap
+import unittest
+from contextlib import redirect_stdout
+from io import StringIO
+from pathlib import Path
+from unittest.mock import patch
+
+from trainsh.core.executor_main import DSLExecutor, run_recipe
+from trainsh.core.job_state import JobStateManager
+
+
+class DoneCheckTests(unittest.TestCase):
+    def setUp(self):
+        self.tmpdir = tempfile.TemporaryDirectory()
+        self.addCleanup(self.tmpdir.cleanup)
+        self.root = Path(self.tmpdir.name)
+        self.runtime = self.root / "config" / "runtime"
+        for target in (
+            patch("trainsh.core.executor_main.load_config", return_value={"tmux": {}}),
+            patch("trainsh.core.executor_main.CONFIG_DIR", self.root / "config"),
+            patch("trainsh.core.executor_main.RUNTIME_STATE_DIR", self.runtime),
+            patch("trainsh.runtime.CONFIG_DIR", self.runtime),
+        ):
+            target.start()
+            self.addCleanup(target.stop)
+
+    def _run(self, body, job_id):
+        path = self.root / f"{job_id}.pyrecipe"
+        path.write_text("from trainsh import Recipe\n\nrecipe = Recipe(\"done\")\n" + textwrap.dedent(body), encoding="utf-8")
+        with redirect_stdout(StringIO()):
+            ok = run_recipe(str(path), job_id=job_id)
+        return ok, JobStateManager(str(self.runtime)).load(job_id)
+
+    def test_failing_probe_does_not_become_the_job_exit_code(self):
+        with patch.object(DSLExecutor, "_note_exit_code") as noted:
+            ok, job = self._run(
+                """\
+                recipe.fail("stop", id="stop", step_options={"done_check": "exit 9"})
+                """,
+                "probejob",
+            )
+        self.assertFalse(ok)
+        noted.assert_not_called()
+        self.assertEqual((job.exit_code, job.outcome), (None, "failed"))
+
+    def test_group_members_and_cleanup_steps_honour_done_check(self):
+        log_path = self.root / "log.txt"
+        ok, _job = self._run(
+            f"""\
+            with recipe.group("prep"):
+                recipe.shell("echo fetch >> {log_path}", id="fetch", step_options={{"done_check": "true"}})
+                recipe.shell("echo unpack >> {log_path}", id="unpack", step_options={{"done_check": "false"}})
+            with recipe.cleanup():
+                recipe.shell("echo upload >> {log_path}", id="upload", step_options={{"done_check": "true"}})
+            """,
+            "groupjob",
+        )
+        self.assertTrue(ok)
+        self.assertEqual(log_path.read_text(encoding="utf-8").split(), ["unpack"])
+
+
+if __name__ == "__main__":
+    unittest.main()
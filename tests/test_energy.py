@@ -0,0 +1,100 @@
+import tempfile
+import unittest
+from contextlib import redirect_stdout
+from io import StringIO
+from pathlib import Path
+from unittest.mock import patch
+
+from trainsh.commands.recipe_views import _show_execution_details, cmd_energy
+from trainsh.core.execution_log import ExecutionLogReader
+from trainsh.core.executor_main import run_recipe
+from trainsh.core.runtime_store import RuntimeStore
+from trainsh.services.energy import EnergyMeter, energy_interval, integrate_kwh, monthly_energy, parse_power_draw
+
+
+class EnergyMathTests(unittest.TestCase):
+    def test_parses_power_draw_and_integrates_kwh(self):
+        self.assertEqual(parse_power_draw("250.5\n[N/A]\n149.5\n"), 400.0)
+        self.assertIsNone(parse_power_draw("[N/A]\n"))
+        self.assertIsNone(parse_power_draw(""))
+        # 1 kW held for an hour.
+        self.assertAlmostEqual(integrate_kwh([(0, 1000.0), (1800, 1000.0), (3600, 1000.0)]), 1.0)
+        self.assertAlmostEqual(integrate_kwh([(0, 0.0), (3600, 2000.0)]), 1.0)
+        self.assertEqual(integrate_kwh([(0, 500.0)]), 0.0)
+        self.assertEqual(energy_interval({}), 30.0)
+        self.assertEqual(energy_interval({"energy": {"interval_secs": 0}}), 0.0)
+
+    def test_meter_sums_each_host_it_sees(self):
+        now = [0.0]
+        draws = {"gpu-a": 300.0, "gpu-b": 100.0, "cpu-only": None}
+        hosts = ["gpu-a", "cpu-only"]
+        meter = EnergyMeter(lambda: hosts, reader=lambda host, _args: draws[host], clock=lambda: now[0])
+        meter.sample_once()
+        hosts.append("gpu-b")
+        for step in (1800.0, 3600.0):
+            now[0] = step
+            meter.sample_once()
+        summary = meter.summary()
+        self.assertEqual(set(summary["hosts"]), {"gpu-a", "gpu-b"})
+        self.assertAlmostEqual(summary["hosts"]["gpu-a"]["kwh"], 0.3)
+        self.assertAlmostEqual(summary["hosts"]["gpu-b"]["kwh"], 0.05)
+        self.assertAlmostEqual(summary["kwh"], 0.35)
+        self.assertEqual((summary["hosts"]["gpu-a"]["peak_w"], summary["hosts"]["gpu-a"]["samples"]), (300.0, 3))
+        self.assertEqual(EnergyMeter(lambda: []).summary(), {})
+
+    def test_monthly_totals_come_from_finished_runs(self):
+        runs = [
+            {"started_at": "2026-09-30T23:00:00", "metadata": {"energy": {"kwh": 1.5}}},
+            {"started_at": "2026-10-01T01:00:00", "metadata": {"energy": {"kwh": 0.25}}},
+            {"started_at": "2026-10-02T01:00:00", "metadata": {"energy": {"kwh": 0.5}}},
+            {"started_at": "2026-10-03T01:00:00", "metadata": {}},
+        ]
+        self.assertEqual(
+            monthly_energy(runs),
+            {"2026-09": {"kwh": 1.5, "runs": 1}, "2026-10": {"kwh": 0.75, "runs": 2}},
+        )
+
+
+class RunEnergyTests(unittest.TestCase):
+    def setUp(self):
+        self.tmpdir = tempfile.TemporaryDirectory()
+        self.addCleanup(self.tmpdir.cleanup)
+        self.root = Path(self.tmpdir.name)
+        self.runtime = self.root / "config" / "runtime"
+        for target in (
+            patch("trainsh.core.executor_main.load_config", return_value={"tmux": {}, "energy": {"interval_secs": 1}}),
+            patch("trainsh.core.executor_main.CONFIG_DIR", self.root / "config"),
+            patch("trainsh.core.executor_main.RUNTIME_STATE_DIR", self.runtime),
+            patch("trainsh.runtime.CONFIG_DIR", self.runtime),
+            patch("trainsh.core.runtime_store.get_runtime_state_dir", return_value=self.runtime),
+        ):
+            target.start()
+            self.addCleanup(target.stop)
+
+    def test_run_saves_energy_in_its_summary_and_monthly_report(self):
+        path = self.root / "burn.pyrecipe"
+        path.write_text(
+            'from trainsh import Recipe\n\nrecipe = Recipe("burn")\n'
+            'recipe.shell("true", id="setup")\n'
+            'recipe.shell("sleep 2.5", id="burn", depends_on=["setup"])\n',
+            encoding="utf-8",
+        )
+        with patch("trainsh.core.executor_lifecycle.read_power_draw", return_value=250.0) as power:
+            with redirect_stdout(StringIO()):
+                self.assertTrue(run_recipe(str(path), job_id="burnjob"))
+        self.assertEqual(power.call_args.args[0], "local")
+
+        summary = ExecutionLogReader(str(self.runtime)).get_execution_summary("burnjob")
+        self.assertGreater(summary["energy"]["kwh"], 0)
+        self.assertEqual(summary["energy"]["hosts"]["local"]["peak_w"], 250.0)
+        with redirect_stdout(StringIO()) as out:
+            _show_execution_details(ExecutionLogReader(str(self.runtime)), "burnjob")
+            cmd_energy([])
+        self.assertIn("Energy: ", out.getvalue())
+        self.assertIn("local: avg 250 W, peak 250 W", out.getvalue())
+        month = RuntimeStore().get_run("burnjob")["started_at"][:7]
+        self.assertIn(month, out.getvalue())
+
+
+if __name__ == "__main__":
+    unittest.main()
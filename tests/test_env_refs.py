@@ -0,0 +1,101 @@
+import os
+import tempfile
+import unittest
+from pathlib import Path
+from unittest.mock import patch
+
+import yaml
+
+from trainsh import config
+from trainsh.commands import host as host_cmd
+from trainsh.commands import storage as storage_cmd
+from trainsh.core.env_refs import MissingEnvVarError, expand_env_refs, restore_env_refs
+
+
+class EnvRefTests(unittest.TestCase):
+    def setUp(self):
+        self.tmpdir = tempfile.TemporaryDirectory()
+        self.addCleanup(self.tmpdir.cleanup)
+        self.root = Path(self.tmpdir.name)
+        patchers = [
+            patch("trainsh.config.CONFIG_FILE", self.root / "config.yaml"),
+            patch("trainsh.config.CONFIG_DIR", self.root),
+            patch("trainsh.constants.CONFIG_DIR", self.root),
+            patch("trainsh.constants.HOSTS_FILE", self.root / "hosts.yaml"),
+            patch("trainsh.constants.STORAGES_FILE", self.root / "storages.yaml"),
+            patch("trainsh.constants.ACTIVE_PROFILE_FILE", self.root / "active_profile"),
+            patch("trainsh.services.ssh_export.refresh_if_enabled", lambda: None),
+            patch.dict(os.environ, {"TRAINSH_TEST_USER": "alice", "TRAINSH_TEST_HOME": "/home/alice"}),
+        ]
+        for patcher in patchers:
+            patcher.start()
+            self.addCleanup(patcher.stop)
+        os.environ.pop("TRAINSH_PROFILE", None)
+        os.environ.pop("TRAINSH_TEST_UNSET", None)
+
+    def test_expand_nested_values_with_defaults(self):
+        data = {
+            "paths": ["${env:TRAINSH_TEST_HOME}/data", "/fixed"],
+            "profile": "${env:TRAINSH_TEST_UNSET:-default}",
+            "port": 22,
+        }
+        self.assertEqual(
+            expand_env_refs(data),
+            {"paths": ["/home/alice/data", "/fixed"], "profile": "default", "port": 22},
+        )
+
+    def test_missing_variable_names_file_and_key(self):
+        with self.assertRaises(MissingEnvVarError) as ctx:
+            expand_env_refs({"hosts": [{"ssh_key_path": "${env:TRAINSH_TEST_UNSET}/id"}]}, where="hosts.yaml")
+        self.assertIn("hosts.yaml: hosts[0].ssh_key_path", str(ctx.exception))
+        self.assertIn("TRAINSH_TEST_UNSET is not set", str(ctx.exception))
+
+    def test_restore_keeps_unchanged_refs_only(self):
+        raw = {"a": "${env:TRAINSH_TEST_HOME}/x", "b": "${env:TRAINSH_TEST_HOME}/y"}
+        current = {"a": "/home/alice/x", "b": "/elsewhere"}
+        self.assertEqual(restore_env_refs(raw, current), {"a": "${env:TRAINSH_TEST_HOME}/x", "b": "/elsewhere"})
+
+    def test_config_load_and_save_keep_references(self):
+        (self.root / "config.yaml").write_text(
+            yaml.dump({"defaults": {"ssh_key_path": "${env:TRAINSH_TEST_HOME}/.ssh/id"}}), encoding="utf-8"
+        )
+        self.assertEqual(config.get_config_value("defaults.ssh_key_path"), "/home/alice/.ssh/id")
+        config.set_config_value("ui.currency", "EUR")
+        saved = yaml.safe_load((self.root / "config.yaml").read_text(encoding="utf-8"))
+        self.assertEqual(saved["defaults"]["ssh_key_path"], "${env:TRAINSH_TEST_HOME}/.ssh/id")
+        self.assertEqual(saved["ui"]["currency"], "EUR")
+
+    def test_hosts_and_storages_expand_and_round_trip(self):
+        (self.root / "hosts.yaml").write_text(
+            yaml.dump({"hosts": [{
+                "name": "box",
+                "type": "ssh",
+                "hostname": "box.example.com",
+                "username": "${env:TRAINSH_TEST_USER}",
+                "ssh_key_path": "${env:TRAINSH_TEST_HOME}/.ssh/id",
+            }]}),
+            encoding="utf-8",
+        )
+        (self.root / "storages.yaml").write_text(
+            yaml.dump({"storages": [{"name": "scratch", "type": "local", "config": {"path": "${env:TRAINSH_TEST_HOME}/s"}}]}),
+            encoding="utf-8",
+        )
+        hosts = host_cmd._load_configured_hosts()
+        self.assertEqual(hosts["box"].username, "alice")
+        self.assertEqual(hosts["box"].ssh_key_path, "/home/alice/.ssh/id")
+        storages = storage_cmd.load_storages()
+        self.assertEqual(storages["scratch"].config["path"], "/home/alice/s")
+
+        hosts["box"].port = 2222
+        host_cmd.save_hosts(hosts)
+        storage_cmd.save_storages(storages)
+        saved_host = yaml.safe_load((self.root / "hosts.yaml").read_text(encoding="utf-8"))["hosts"][0]
+        self.assertEqual(saved_host["username"], "${env:TRAINSH_TEST_USER}")
+        self.assertEqual(saved_host["ssh_key_path"], "${env:TRAINSH_TEST_HOME}/.ssh/id")
+        self.assertEqual(saved_host["port"], 2222)
+        saved_storage = yaml.safe_load((self.root / "storages.yaml").read_text(encoding="utf-8"))["storages"][0]
+        self.assertEqual(saved_storage["config"]["path"], "${env:TRAINSH_TEST_HOME}/s")
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,58 @@
+import unittest
+from contextlib import redirect_stdout
+from io import StringIO
+from unittest.mock import MagicMock, patch
+
+from trainsh.core.errors import AppError, ErrorCode, classify_message, classify_ssh, error_info
+from trainsh.core.models import Storage, StorageType
+from trainsh.main import main
+from trainsh.services.connectivity import HostOfflineError
+from trainsh.services.file_access import storage_cli
+from trainsh.services.ssh import SSHResult
+from trainsh.services.vast_api import VastAPIError
+
+
+class AppErrorTests(unittest.TestCase):
+    def test_ssh_failures_are_told_apart(self):
+        denied = SSHResult(255, "", "me@10.0.0.9: Permission denied (publickey).", target_hostname="10.0.0.9", target_port=22)
+        self.assertEqual(denied.error.code, ErrorCode.SSH_AUTH_FAILED)
+        self.assertFalse(denied.error.retryable)
+        unreachable = SSHResult(255, "", "ssh: connect to host 10.0.0.9 port 22: No route to host")
+        self.assertEqual(unreachable.error.code, ErrorCode.NETWORK_UNREACHABLE)
+        self.assertTrue(unreachable.error.retryable)
+        self.assertEqual(classify_ssh("", 255), ErrorCode.NETWORK_UNREACHABLE)
+        self.assertEqual(SSHResult(1, "", "boom").error.code, ErrorCode.UNKNOWN)
+        self.assertIsNone(SSHResult(0, "", "").error)
+        self.assertEqual(denied.error.to_dict()["details"]["hostname"], "10.0.0.9")
+
+    def test_vast_and_connectivity_errors_carry_codes(self):
+        error = VastAPIError(404, '{"error":"no_such_instance"}')
+        self.assertEqual(str(error), 'Vast.ai API error (404): {"error":"no_such_instance"}')
+        self.assertEqual((error.code, error.provider, error.retryable), (ErrorCode.INSTANCE_NOT_FOUND, "vast", False))
+        self.assertTrue(VastAPIError(503, "").retryable)
+        self.assertEqual(VastAPIError(0, "timed out").code, ErrorCode.TIMEOUT)
+        self.assertIsInstance(HostOfflineError("gpu is offline"), RuntimeError)
+        self.assertEqual(error_info(HostOfflineError("x"))["error_code"], "host_offline")
+        self.assertEqual(classify_message("Vast.ai instance 7 did not become SSH-ready within 600s"), ErrorCode.INSTANCE_NOT_PROVISIONED)
+        self.assertEqual(error_info("Traceback ...\nValueError: bad")["error_code"], "unknown")
+
+    def test_storage_cli_classifies_rclone_failures(self):
+        storage = Storage(name="r2", type=StorageType.R2, config={})
+        failed = MagicMock(returncode=1, stderr=b"ERROR : directory not found", stdout=b"")
+        with patch("trainsh.services.transfer_support.build_rclone_env", return_value={}), patch(
+            "trainsh.services.file_access.subprocess.run", return_value=failed
+        ):
+            with self.assertRaises(AppError) as caught:
+                storage_cli(storage, ["cat", "r2:x"])
+        self.assertEqual((caught.exception.code, caught.exception.provider), (ErrorCode.STORAGE_NOT_FOUND, "r2"))
+
+    def test_cli_prints_code_for_uncaught_app_errors(self):
+        out = StringIO()
+        with patch("trainsh.commands.vast.cmd_list", side_effect=VastAPIError(429, "slow down")), redirect_stdout(out):
+            with self.assertRaises(SystemExit):
+                main(["train", "vast", "list"])
+        self.assertIn("Error [rate_limited, retryable]: Vast.ai API error (429): slow down", out.getvalue())
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,124 @@
+import json
+import tempfile
+import textwrap
+import unittest
+from contextlib import redirect_stdout
+from io import StringIO
+from pathlib import Path
+from types import SimpleNamespace
+from unittest.mock import patch
+
+from trainsh.commands.recipe_views import cmd_timeline
+from trainsh.core.executor_main import DSLExecutor, run_recipe
+from trainsh.core.runtime_store import RuntimeStore
+from trainsh.services.execution_timeline import execution_timeline
+
+
+def _event(run_id, event, ts, step_num=None, **payload):
+    return {"run_id": run_id, "event": event, "event_name": event, "step_num": step_num, "payload": payload, "ts": ts}
+
+
+class ExecutionTimelineTests(unittest.TestCase):
+    def test_run_records_queue_time_and_retry_attempts(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            root = Path(tmpdir)
+            recipe_path = root / "inline.pyrecipe"
+            marker = root / "tried"
+            recipe_path.write_text(
+                "from trainsh import Recipe\n\nrecipe = Recipe(\"inline\")\n"
+                + textwrap.dedent(
+                    f"""\
+                    start = recipe.shell("true", id="start")
+                    recipe.shell("true", id="first", depends_on=[start])
+                    recipe.shell(
+                        "[ -f {marker} ] || {{ touch {marker}; exit 1; }}",
+                        id="flaky",
+                        depends_on=[start],
+                        step_options={{"retries": 1, "retry_delay": 1}},
+                    )
+                    """
+                ),
+                encoding="utf-8",
+            )
+            runtime = root / "config" / "runtime"
+            # The scheduler clock only moves when the loop sleeps for a retry or
+            # when "first" ends, so "flaky" waits exactly 1s for the single worker.
+            clock = SimpleNamespace(now=1000.0)
+            fake_time = SimpleNamespace(time=lambda: clock.now, sleep=lambda secs: setattr(clock, "now", clock.now + secs))
+            emit_step_end = DSLExecutor._emit_step_end
+
+            def step_end(executor, node, step_id, **kwargs):
+                if step_id == "first":
+                    clock.now += 1.0
+                return emit_step_end(executor, node, step_id, **kwargs)
+
+            with patch("trainsh.core.executor_main.load_config", return_value={"tmux": {}}), patch(
+                "trainsh.core.executor_main.CONFIG_DIR", root / "config"
+            ), patch("trainsh.core.executor_main.RUNTIME_STATE_DIR", runtime), patch(
+                "trainsh.runtime.CONFIG_DIR", runtime
+            ), patch("trainsh.core.executor_dependencies.time", fake_time), patch.object(
+                DSLExecutor, "_emit_step_end", step_end
+            ), redirect_stdout(StringIO()):
+                self.assertTrue(run_recipe(str(recipe_path), job_id="jobtl1", executor_name="sequential"))
+
+            timeline = execution_timeline("jobtl1", root=runtime)
+            steps = {step["step_id"]: step for step in timeline["steps"]}
+            flaky = steps["flaky"]
+            self.assertEqual([attempt["state"] for attempt in flaky["attempts"]], ["up_for_retry", "success"])
+            self.assertEqual([attempt["queued_ms"] for attempt in flaky["attempts"]], [1000, 0])
+            self.assertEqual([attempt["retry_delay_ms"] for attempt in flaky["attempts"]], [1000, 0])
+            self.assertEqual(timeline["breakdown"]["queued"], 1000)
+            self.assertEqual(timeline["status"], "succeeded")
+            kinds = [segment["kind"] for segment in timeline["segments"] if segment["step_id"] == "flaky"]
+            self.assertEqual(kinds, ["queued", "running", "retry_backoff", "running"])
+
+    def test_resumed_run_reports_pause_and_input_wait(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            store = RuntimeStore(Path(tmpdir))
+            store.append_run({"run_id": "job2", "recipe_name": "train", "status": "succeeded", "success": True, "ts": "x"})
+            for record in (
+                _event("job2", "execution_start", "2026-01-01T10:00:00"),
+                _event("job2", "step_start", "2026-01-01T10:00:00", 1, step_id="pick", raw="gpu.pick()", step_type="control"),
+                _event(
+                    "job2",
+                    "step_wait",
+                    "2026-01-01T10:05:00",
+                    1,
+                    step_id="pick",
+                    kind="input",
+                    started_at="2026-01-01T10:01:00",
+                    finished_at="2026-01-01T10:05:00",
+                ),
+                _event("job2", "step_end", "2026-01-01T10:06:00", 1, step_id="pick", state="success", success=True),
+                _event("job2", "step_start", "2026-01-01T10:06:00", 2, step_id="train", raw="tmux.run(train)"),
+                _event("job2", "execution_end", "2026-01-01T10:10:00", success=False),
+                _event("job2", "execution_start", "2026-01-01T10:40:00"),
+                _event("job2", "step_start", "2026-01-01T10:40:00", 2, step_id="train", raw="tmux.run(train)"),
+                _event("job2", "step_end", "2026-01-01T11:00:00", 2, step_id="train", state="success", success=True),
+                _event("job2", "execution_end", "2026-01-01T11:00:00", success=True),
+            ):
+                store.append_event(record)
+
+            timeline = execution_timeline("job2", root=Path(tmpdir))
+            self.assertEqual(timeline["wall_ms"], 3_600_000)
+            self.assertEqual(
+                timeline["breakdown"],
+                {"running": 1_560_000, "waiting": 0, "queued": 0, "retry_backoff": 0, "input": 240_000, "paused": 1_800_000},
+            )
+            train = timeline["steps"][1]
+            self.assertEqual([attempt["state"] for attempt in train["attempts"]], ["interrupted", "success"])
+
+            out = StringIO()
+            with patch("trainsh.services.execution_timeline.RuntimeStore", return_value=store), patch(
+                "trainsh.core.execution_log.RuntimeStore", return_value=store
+            ), redirect_stdout(out):
+                cmd_timeline(["job"])
+                cmd_timeline(["--last", "--json"])
+            text = out.getvalue()
+            self.assertIn("Wall time: 1h00m00s", text)
+            self.assertRegex(text, r"paused +30m00s +50\.0%")
+            self.assertEqual(json.loads(text[text.index("{"):])["run_id"], "job2")
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -222,6 +222,23 @@ class ExecutorMainCoreTests(unittest.TestCase):
             self.assertIsNone(executor._normalize_provider_timeout("bad"))
             self.assertEqual(executor._positive_provider_timeout("bad", default=7), 7)
 
+    def test_cancel_inflight_steps_marks_and_kills_commands(self):
+        with isolated_executor(RecipeModel(name="core")) as (executor, _config_dir):
+            executor.ctx.inflight = {
+                "2": {"host": "gpu", "remote_session": "s2", "pidfile": "/tmp/a.pid"},
+                "3": {"host": "gpu", "remote_session": "s3", "pidfile": "/tmp/b.pid"},
+            }
+            logs = []
+            executor.log = logs.append
+            with patch.object(executor.execute_helper, "kill_inflight", return_value="killed pid 5 with SIGTERM") as kill:
+                reports = executor._cancel_inflight_steps("step timeout after 9s", step_num=3)
+
+            self.assertEqual(reports, ["killed pid 5 with SIGTERM"])
+            self.assertEqual(kill.call_args.args[0]["pidfile"], "/tmp/b.pid")
+            self.assertEqual(executor.ctx.inflight["3"]["cancelled"], "step timeout after 9s")
+            self.assertNotIn("cancelled", executor.ctx.inflight["2"])
+            self.assertIn("Step 3 cancelled (step timeout after 9s)", logs[-1])
+
 
 if __name__ == "__main__":
     unittest.main()
@@ -410,7 +410,7 @@ class RunRecipeEdgeTests(unittest.TestCase):
         ), patch(
             "trainsh.core.executor_main.JobStateManager", return_value=manager
         ), patch(
-            "trainsh.core.executor_main.get_window_session_name", return_value="generated-sess"
+            "trainsh.core.executor_resume.get_window_session_name", return_value="generated-sess"
         ):
             ok = run_recipe(
                 "/tmp/demo.pyrecipe",
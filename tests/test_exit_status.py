@@ -0,0 +1,174 @@
+import json
+import subprocess
+import tempfile
+import unittest
+from contextlib import redirect_stdout
+from io import StringIO
+from pathlib import Path
+from unittest.mock import patch
+
+from trainsh.commands.recipe_views import cmd_status
+from trainsh.core.executor_main import run_recipe
+from trainsh.core.exit_status import (
+    OOM_EVENT,
+    classify_exit,
+    cuda_memory_stats,
+    describe_exit,
+    kernel_oom_stats,
+    needs_kernel_log,
+)
+from trainsh.core.job_state import JobStateManager
+from trainsh.core.remote_cancel import remove_status_files, status_file_for, wrap_tracked_command
+
+RECIPE = """from trainsh import Recipe
+
+recipe = Recipe("train")
+recipe.shell("{command}", id="train", {options})
+"""
+
+OOM_LOG = "[12.3] Out of memory: Killed process 4242 (python) total-vm:81920kB, anon-rss:40960kB\n"
+CUDA_OOM = (
+    "torch.OutOfMemoryError: CUDA out of memory. Tried to allocate 2.00 GiB. GPU 0 has a total capacity of "
+    "79.15 GiB of which 10.62 MiB is free. Including non-PyTorch memory, this process has 79.13 GiB memory in use. "
+    "Of the allocated memory 77.60 GiB is allocated by PyTorch, and 1.02 GiB is reserved by PyTorch but unallocated."
+)
+
+
+class ExitStatusTests(unittest.TestCase):
+    def test_classifies_exit_codes_and_kernel_messages(self):
+        self.assertEqual(classify_exit(0), "succeeded")
+        self.assertEqual(classify_exit(1), "failed")
+        self.assertEqual(classify_exit(None), "failed")
+        self.assertEqual(classify_exit(137, OOM_LOG), "oom_killed")
+        self.assertEqual(classify_exit(-9, OOM_LOG), "oom_killed")
+        self.assertEqual(classify_exit(137, ""), "killed")
+        self.assertEqual(classify_exit(143), "killed")
+        self.assertEqual(classify_exit(143, "systemd-logind: Power key pressed."), "preempted")
+        self.assertEqual(classify_exit(143, OOM_LOG, stopped="timeout"), "timeout")
+        self.assertEqual(classify_exit(None, stopped="cancelled"), "cancelled")
+        self.assertEqual(describe_exit(137), "137 (SIGKILL)")
+        self.assertEqual(describe_exit(2), "2")
+        self.assertTrue(needs_kernel_log(-15))
+        self.assertFalse(needs_kernel_log(1))
+
+    def test_detects_cuda_oom_and_parses_memory_figures(self):
+        self.assertEqual(classify_exit(1, output="step 10 loss 2.1\n" + CUDA_OOM), "cuda_oom")
+        self.assertEqual(classify_exit(0, output=CUDA_OOM), "succeeded")
+        self.assertEqual(classify_exit(1, output="RuntimeError: shape mismatch"), "failed")
+        self.assertEqual(
+            cuda_memory_stats(CUDA_OOM),
+            {"gpu": 0, "requested_mb": 2048.0, "total_mb": 81049.6, "free_mb": 10.6, "in_use_mb": 81029.1, "allocated_mb": 79462.4, "reserved_mb": 1044.5},
+        )
+        legacy = "CUDA out of memory. Tried to allocate 20.00 MiB (GPU 1; 15.78 GiB total capacity; 14.56 GiB already allocated; 3.44 MiB free; 14.68 GiB reserved in total by PyTorch)"
+        self.assertEqual(cuda_memory_stats(legacy)["gpu"], 1)
+        self.assertEqual(cuda_memory_stats(legacy)["free_mb"], 3.4)
+        self.assertEqual(kernel_oom_stats(OOM_LOG), {"pid": 4242, "process": "python", "rss_mb": 40.0})
+
+    def test_tracked_command_writes_exit_code(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            status_file = str(Path(tmpdir) / "rc" / "step.rc")
+            command = wrap_tracked_command("exit 7", "train_rc", str(Path(tmpdir) / "step.pid"), status_file)
+            subprocess.run(["bash", "-c", command.replace("tmux wait-for -S train_rc", "true")], check=False)
+            self.assertEqual(Path(status_file).read_text().split(), ["train_rc", "7"])
+
+        status_file = Path(status_file_for("cleanupjob", "train_rc"))
+        status_file.parent.mkdir(parents=True, exist_ok=True)
+        status_file.write_text("train_rc 0\n")
+        self.assertTrue(remove_status_files("local", "cleanupjob", build_ssh_args=None))
+        self.assertFalse(status_file.parent.exists())
+
+
+class JobOutcomeTests(unittest.TestCase):
+    def setUp(self):
+        self.tmpdir = tempfile.TemporaryDirectory()
+        self.addCleanup(self.tmpdir.cleanup)
+        root = Path(self.tmpdir.name)
+        self.runtime = root / "config" / "runtime"
+        self.manager = JobStateManager(str(self.runtime))
+        for target in (
+            patch("trainsh.core.executor_main.load_config", return_value={"tmux": {}}),
+            patch("trainsh.core.executor_main.CONFIG_DIR", root / "config"),
+            patch("trainsh.core.executor_main.RUNTIME_STATE_DIR", self.runtime),
+            patch("trainsh.runtime.CONFIG_DIR", self.runtime),
+        ):
+            target.start()
+            self.addCleanup(target.stop)
+
+    def _run(self, command, job_id, options=""):
+        path = Path(self.tmpdir.name) / "train.pyrecipe"
+        path.write_text(RECIPE.format(command=command, options=options), encoding="utf-8")
+        with redirect_stdout(StringIO()):
+            ok = run_recipe(str(path), job_id=job_id)
+        return ok, self.manager.load(job_id)
+
+    def test_jobs_record_exit_code_and_outcome(self):
+        ok, job = self._run("true", "okjob")
+        self.assertTrue(ok)
+        self.assertEqual((job.status, job.exit_code, job.outcome), ("completed", 0, "succeeded"))
+
+        ok, job = self._run("exit 3", "badjob")
+        self.assertFalse(ok)
+        self.assertEqual((job.status, job.exit_code, job.outcome), ("failed", 3, "failed"))
+
+        with patch("trainsh.core.executor_lifecycle.read_kernel_log", return_value=OOM_LOG) as kernel_log:
+            ok, job = self._run("kill -9 $$", "oomjob")
+        self.assertEqual((job.exit_code, job.outcome), (137, "oom_killed"))
+        self.assertEqual(kernel_log.call_args.args[0], "local")
+        events = [json.loads(line) for line in (self.runtime / "events.jsonl").read_text().splitlines()]
+        oom = [event["payload"] for event in events if event["event"] == OOM_EVENT and event["run_id"] == "oomjob"]
+        self.assertEqual((oom[0]["kind"], oom[0]["memory"]["rss_mb"]), ("host", 40.0))
+
+        with patch("trainsh.core.executor_lifecycle.read_kernel_log", return_value=OOM_LOG) as kernel_log:
+            ok, job = self._run("sleep 5", "slowjob", "step_options={'execution_timeout': 1}")
+        self.assertEqual((job.status, job.outcome), ("failed", "timeout"))
+        kernel_log.assert_not_called()
+
+        with patch("trainsh.core.job_state.JobStateManager", return_value=self.manager), redirect_stdout(StringIO()) as out:
+            cmd_status(["oomjob"])
+            cmd_status(["--all"])
+        self.assertIn("Outcome: oom_killed (exit code 137 (SIGKILL))", out.getvalue())
+        self.assertIn("oom_killed", out.getvalue().split("Recipe Jobs:")[1])
+
+    def test_cuda_oom_sets_the_outcome_and_emits_an_event(self):
+        ok, job = self._run(f"echo '{CUDA_OOM}' >&2; exit 1", "cudajob")
+        self.assertFalse(ok)
+        self.assertEqual((job.exit_code, job.outcome), (1, "cuda_oom"))
+        events = [json.loads(line) for line in (self.runtime / "events.jsonl").read_text().splitlines()]
+        oom = [event["payload"] for event in events if event["event"] == OOM_EVENT]
+        self.assertTrue(oom)
+        self.assertEqual((oom[0]["kind"], oom[0]["step_id"], oom[0]["memory"]["free_mb"]), ("cuda", "train", 10.6))
+        end = [event["payload"] for event in events if event["event"] == "step_end"][-1]
+        self.assertEqual((end["error_code"], end["failure_reason"]), ("out_of_memory", "cuda_oom"))
+
+        with patch("trainsh.core.job_state.JobStateManager", return_value=self.manager), redirect_stdout(StringIO()) as out:
+            cmd_status(["cudajob"])
+        self.assertIn("Hint: Lower the batch size", out.getvalue())
+
+    def test_exit_code_comes_from_the_step_that_failed_the_run(self):
+        marker = Path(self.tmpdir.name) / "tried"
+        command = f"test -e {marker} || {{ touch {marker}; exit 4; }}"
+        ok, job = self._run(command, "retryjob", "step_options={'retries': 1}")
+        self.assertEqual((ok, job.exit_code, job.outcome), (True, 0, "succeeded"))
+
+        path = Path(self.tmpdir.name) / "two.pyrecipe"
+        path.write_text(
+            'from trainsh import Recipe\n\nrecipe = Recipe("two")\n'
+            'recipe.shell("exit 5", id="probe", step_options={"continue_on_failure": True})\n'
+            'recipe.fail("stop", id="stop", depends_on=["probe"], step_options={"trigger_rule": "all_done"})\n',
+            encoding="utf-8",
+        )
+        with redirect_stdout(StringIO()):
+            self.assertFalse(run_recipe(str(path), job_id="twojob"))
+        job = self.manager.load("twojob")
+        self.assertEqual((job.exit_code, job.outcome), (None, "failed"))
+
+    def test_interrupted_job_is_recorded_as_cancelled(self):
+        with patch("trainsh.core.executor_main.DSLExecutor._execute_step", side_effect=KeyboardInterrupt):
+            with self.assertRaises(KeyboardInterrupt):
+                self._run("true", "intjob")
+        job = self.manager.load("intjob")
+        self.assertEqual((job.status, job.outcome), ("cancelled", "cancelled"))
+
+
+if __name__ == "__main__":
+    unittest.main()
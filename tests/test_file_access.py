@@ -0,0 +1,86 @@
+import os
+import subprocess
+import tempfile
+import unittest
+from contextlib import redirect_stdout
+from io import StringIO
+from unittest.mock import patch
+
+from trainsh.commands import storage as storage_cmd
+from trainsh.commands.file_edit import edit_file
+from trainsh.core.models import Host, HostType, Storage, StorageType
+from trainsh.services.file_access import MAX_EDIT_BYTES, file_read_head, file_write
+from tests.runtime_test_utils import run_locally
+
+
+class FileAccessTests(unittest.TestCase):
+    def setUp(self):
+        self.tmpdir = tempfile.TemporaryDirectory()
+        self.addCleanup(self.tmpdir.cleanup)
+        self.root = self.tmpdir.name
+        self.path = os.path.join(self.root, "train.yaml")
+        with open(self.path, "w") as handle:
+            handle.write("lr: 0.1\nepochs: 3\n")
+        os.chmod(self.path, 0o640)
+
+    def test_host_read_head_and_atomic_write(self):
+        host = Host(name="gpu", type=HostType.SSH, hostname="gpu.example.com")
+        with patch("trainsh.services.file_access.host_exec", side_effect=run_locally):
+            head = file_read_head(host, self.path, 4)
+            self.assertEqual((head.data, head.truncated, head.binary), (b"lr: ", True, False))
+            self.assertFalse(file_read_head(host, self.path).truncated)
+
+            file_write(host, self.path, "lr: 0.01\nepochs: 3\n")
+            with self.assertRaises(RuntimeError):
+                file_read_head(host, os.path.join(self.root, "missing's.yaml"))
+        with open(self.path) as handle:
+            self.assertEqual(handle.read(), "lr: 0.01\nepochs: 3\n")
+        self.assertEqual(os.stat(self.path).st_mode & 0o777, 0o640)
+        self.assertEqual(os.listdir(self.root), ["train.yaml"])
+
+    def test_storage_backends(self):
+        local = Storage(name="disk", type=StorageType.LOCAL, config={"path": self.root})
+        self.assertEqual(file_read_head(local, "/train.yaml").text, "lr: 0.1\nepochs: 3\n")
+        file_write(local, "train.yaml", b"\x00\x01")
+        self.assertTrue(file_read_head(local, "train.yaml").binary)
+        with self.assertRaises(ValueError):
+            file_write(local, "train.yaml", "x" * (MAX_EDIT_BYTES + 1))
+
+        r2 = Storage(name="r2", type=StorageType.R2, config={"bucket": "ckpt"})
+        completed = subprocess.CompletedProcess([], 0, stdout=b"lr: 0.1\n", stderr=b"")
+        with patch("trainsh.services.transfer_support.build_rclone_env", return_value={}), patch(
+            "subprocess.run", return_value=completed
+        ) as run:
+            head = file_read_head(r2, "configs/train.yaml", 100)
+            file_write(r2, "configs/train.yaml", "lr: 0.2\n")
+        self.assertEqual(head.text, "lr: 0.1\n")
+        self.assertEqual(run.call_args_list[0].args[0], ["rclone", "cat", "--count", "101", "r2:ckpt/configs/train.yaml"])
+        self.assertEqual(run.call_args_list[1].args[0], ["rclone", "rcat", "r2:ckpt/configs/train.yaml"])
+        self.assertEqual(run.call_args_list[1].kwargs["input"], b"lr: 0.2\n")
+
+    def test_edit_and_cat_commands(self):
+        local = Storage(name="disk", type=StorageType.LOCAL, config={"path": self.root})
+
+        def fake_editor(argv):
+            with open(argv[1]) as handle:
+                text = handle.read()
+            with open(argv[1], "w") as handle:
+                handle.write(text.replace("0.1", "0.5"))
+            return subprocess.CompletedProcess(argv, 0)
+
+        with patch.dict(os.environ, {"EDITOR": "vi"}), patch(
+            "trainsh.commands.file_edit.subprocess.run", side_effect=fake_editor
+        ), patch("trainsh.commands.file_edit.prompt_input", return_value="y"), redirect_stdout(StringIO()):
+            self.assertTrue(edit_file(local, "train.yaml"))
+        with open(self.path) as handle:
+            self.assertEqual(handle.read(), "lr: 0.5\nepochs: 3\n")
+
+        out = StringIO()
+        with patch("trainsh.commands.storage.load_storages", return_value={"disk": local}), redirect_stdout(out):
+            storage_cmd.main(["cat", "disk", "train.yaml", "--bytes", "5"])
+        self.assertIn("lr: 0", out.getvalue())
+        self.assertIn("truncated at 5 bytes", out.getvalue())
+
+
+if __name__ == "__main__":
+    unittest.main()
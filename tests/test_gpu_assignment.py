@@ -0,0 +1,84 @@
+import shlex
+import threading
+import unittest
+from types import SimpleNamespace
+from unittest.mock import MagicMock
+
+from trainsh import Host, Recipe
+from trainsh.core.executor_execute import ExecuteHelper
+from trainsh.core.executor_support import ExecutorSupportMixin
+from trainsh.core.job_state import JobState
+from trainsh.pyrecipe.models import PythonRecipeError
+
+PROBE_OUTPUT = """@@gpus
+0, GPU-aaa, 0, 81920
+1, GPU-bbb, 0, 81920
+@@apps
+trainsh-gpu-guard-done
+"""
+
+
+def fake_ssh(host, command, tty=False):
+    return ["sh", "-c", f"printf %s {shlex.quote(PROBE_OUTPUT)}"]
+
+
+class FakeExecutor(SimpleNamespace):
+    _claim_gpus = ExecutorSupportMixin._claim_gpus
+
+
+def make_executor(running=()):
+    return FakeExecutor(
+        ctx=SimpleNamespace(job_id="job-a", gpu_assignments={}, inflight={}),
+        state_manager=MagicMock(list_running=MagicMock(return_value=list(running))),
+        job_state=None,
+        _thread_lock=threading.RLock(),
+        _interpolate=lambda text: text.replace("${GPU}", "1"),
+        _emit_event=MagicMock(),
+        log=MagicMock(),
+    )
+
+
+class GpuAssignmentTests(unittest.TestCase):
+    def test_session_and_run_gpus_are_recorded_on_steps(self):
+        recipe = Recipe("demo")
+        gpu = Host("ssh://gpu-box", name="gpu")
+        with gpu.tmux("train", gpus="0,1") as tmux:
+            tmux.run("torchrun train.py")
+            tmux.run("python eval.py", gpus=["${GPU}"])
+        steps = [step for step in recipe.steps if step.gpus]
+        self.assertEqual([step.gpus for step in steps], [["0", "1"], ["${GPU}"]])
+        self.assertEqual(steps[0].commands, "torchrun train.py")
+        self.assertEqual(gpu.tmux("train").default_gpus, ("0", "1"))
+        with self.assertRaises(PythonRecipeError):
+            gpu.tmux("other", gpus=["a"])
+
+    def test_assign_validates_inventory_and_exports_devices(self):
+        executor = make_executor()
+        helper = ExecuteHelper(executor, fake_ssh, MagicMock())
+        step = SimpleNamespace(gpus=["0", "${GPU}"])
+        error, commands = helper._assign_gpus(step, "train", "root@gpu-box", "torchrun train.py")
+        self.assertEqual(error, "")
+        self.assertEqual(commands, "export CUDA_VISIBLE_DEVICES=0,1; torchrun train.py")
+        self.assertEqual(executor.ctx.gpu_assignments["train"], {"host": "root@gpu-box", "gpus": ["0", "1"]})
+        executor._emit_event.assert_called_once_with("gpu_assigned", window="train", host="root@gpu-box", gpus=["0", "1"])
+
+        error, _ = helper._assign_gpus(SimpleNamespace(gpus=["7"]), "eval", "root@gpu-box", "python eval.py")
+        self.assertEqual(error, "GPU 7 not found on eval (host has 0, 1)")
+        error, _ = helper._assign_gpus(SimpleNamespace(gpus=["1"]), "eval", "root@gpu-box", "python eval.py")
+        self.assertEqual(error, "GPU 1 on root@gpu-box is already assigned to session train")
+
+    def test_gpus_held_by_another_running_job_are_refused(self):
+        other = JobState(
+            job_id="job-b",
+            recipe_path="/tmp/other.py",
+            recipe_name="other",
+            pid=0,
+            gpu_assignments={"work": {"host": "root@gpu-box", "gpus": ["1"]}},
+        )
+        executor = make_executor([other])
+        self.assertIn("session work of other (job-b)", executor._claim_gpus("train", "root@gpu-box", ["0", "1"]))
+        self.assertEqual(executor._claim_gpus("train", "root@other-box", ["1"]), "")
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,77 @@
+import tempfile
+import unittest
+from contextlib import redirect_stdout
+from io import StringIO
+from pathlib import Path
+from types import SimpleNamespace
+from unittest.mock import patch
+
+from trainsh.commands import pricing
+from trainsh.services.gpu_compare import compare_offers, normalize_static_entry, static_usd_per_hour
+from trainsh.services.pricing import ExchangeRates, load_pricing_settings
+
+RATES = ExchangeRates(rates={"USD": 1.0, "EUR": 0.5})
+
+
+def vast_offer(offer_id, gpu, num, ram_gb, dph):
+    return SimpleNamespace(id=offer_id, gpu_name=gpu, num_gpus=num, gpu_ram=ram_gb * 1024, dph_total=dph)
+
+
+class GpuCompareTests(unittest.TestCase):
+    def test_static_entries_normalize_currency_and_amortize(self):
+        self.assertEqual(static_usd_per_hour({"price_per_hour": 1.0, "currency": "EUR"}, RATES), 2.0)
+        self.assertAlmostEqual(static_usd_per_hour({"price_per_hour": 0.1, "capex": 2000, "lifetime_hours": 10000}, RATES), 0.3)
+        with self.assertRaises(ValueError):
+            normalize_static_entry({"provider": "onprem", "gpu_name": "4090", "capex": 2000})
+        self.assertEqual(
+            normalize_static_entry({"provider": "lambda", "gpu_name": "H100", "price_per_hour": 2.5, "currency": "eur", "note": ""}),
+            {"provider": "lambda", "gpu_name": "H100", "price_per_hour": 2.5, "currency": "EUR", "num_gpus": 1},
+        )
+
+    def test_compare_filters_spec_and_sorts_by_hourly_usd(self):
+        offers = compare_offers(
+            "4090",
+            vram_gb=20,
+            count=1,
+            vast_offers=[
+                vast_offer(1, "RTX 4090", 1, 24, 0.40),
+                vast_offer(2, "RTX 4090", 2, 24, 0.70),
+                vast_offer(3, "RTX 3090", 1, 24, 0.20),
+            ],
+            static_entries=[
+                {"provider": "runpod", "gpu_name": "RTX_4090", "price_per_hour": 0.30, "currency": "EUR"},
+                {"provider": "onprem", "gpu_name": "4090", "capex": 1800, "lifetime_hours": 9000, "gpu_ram_gb": 24},
+                {"provider": "small", "gpu_name": "4090", "price_per_hour": 0.01, "gpu_ram_gb": 16},
+            ],
+            rates=RATES,
+        )
+        self.assertEqual([(o.provider, round(o.usd_per_hour, 2)) for o in offers], [
+            ("onprem", 0.2),
+            ("vast", 0.4),
+            ("runpod", 0.6),
+        ])
+        self.assertEqual(offers[1].ref, "offer 1")
+        self.assertAlmostEqual(offers[0].job_cost(10), 2.0)
+
+    def test_offers_and_compare_commands(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            pricing_file = Path(tmpdir) / "pricing.yaml"
+            with patch("trainsh.services.pricing.PRICING_FILE", pricing_file), patch(
+                "trainsh.services.pricing.CONFIG_DIR", Path(tmpdir)
+            ), patch("trainsh.commands.pricing_compare.get_pricing_context", return_value=(None, "USD", RATES)):
+                out = StringIO()
+                with redirect_stdout(out):
+                    pricing.main(["offers", "add", "lambda", "H100", "--price", "2.5", "--vram", "80"])
+                    pricing.main(["offers", "add", "lambda", "H100", "--price", "2.0", "--vram", "80"])
+                    pricing.main(["offers", "list"])
+                    pricing.main(["compare", "h100", "--hours", "10", "--no-vast"])
+                self.assertEqual(len(load_pricing_settings().gpu_offers), 1)
+                self.assertIn("$20.00", out.getvalue())
+
+                with redirect_stdout(StringIO()):
+                    pricing.main(["offers", "remove", "lambda", "h100"])
+                self.assertEqual(load_pricing_settings().gpu_offers, [])
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,78 @@
+import shlex
+import unittest
+from types import SimpleNamespace
+from unittest.mock import MagicMock, patch
+
+from trainsh.core import gpu_guard
+from trainsh.core.executor_execute import ExecuteHelper
+
+PROBE_OUTPUT = """@@gpus
+0, GPU-aaa, 30720, 81920
+1, GPU-bbb, 4, 81920
+@@apps
+4242, GPU-aaa, 30700, python
+trainsh-gpu-guard-done
+"""
+
+
+def fake_ssh(host, command, tty=False):
+    return ["sh", "-c", f"printf %s {shlex.quote(PROBE_OUTPUT)}"]
+
+
+class GpuGuardTests(unittest.TestCase):
+    def test_launch_detection_and_visible_devices(self):
+        self.assertTrue(gpu_guard.looks_like_gpu_launch("cd /w && torchrun --nproc_per_node 8 train.py"))
+        self.assertTrue(gpu_guard.looks_like_gpu_launch("CUDA_VISIBLE_DEVICES=1 python3 -u train.py"))
+        self.assertTrue(gpu_guard.looks_like_gpu_launch("python -m sglang.launch_server"))
+        self.assertFalse(gpu_guard.looks_like_gpu_launch("python -m pip install -r requirements.txt"))
+        self.assertFalse(gpu_guard.looks_like_gpu_launch("git pull && nvidia-smi"))
+
+        self.assertIsNone(gpu_guard.requested_gpus("python train.py"))
+        self.assertEqual(gpu_guard.requested_gpus("CUDA_VISIBLE_DEVICES='0,1' torchrun x.py"), ["0", "1"])
+        self.assertEqual(gpu_guard.requested_gpus("CUDA_VISIBLE_DEVICES= python train.py"), [])
+        self.assertEqual(gpu_guard.normalize_mode("false"), "off")
+        self.assertEqual(gpu_guard.normalize_mode("bogus"), "confirm")
+
+    def test_find_busy_gpus(self):
+        busy = gpu_guard.find_busy_gpus(PROBE_OUTPUT, None, min_memory_mb=1024)
+        self.assertEqual([card["index"] for card in busy], ["0"])
+        self.assertEqual(busy[0]["processes"], [{"pid": 4242, "memory_mb": 30700, "name": "python"}])
+        self.assertEqual(gpu_guard.find_busy_gpus(PROBE_OUTPUT, ["1"], min_memory_mb=1024), [])
+        self.assertEqual(gpu_guard.describe(busy), "GPU 0 (30.0/80.0 GiB; pid 4242 python)")
+
+    def _helper(self, mode):
+        executor = SimpleNamespace(
+            gpu_guard_mode=mode,
+            gpu_guard_min_memory_mb=1024,
+            is_resuming=False,
+            ctx=SimpleNamespace(inflight={}),
+            _current_step_num=lambda: 3,
+            log=MagicMock(),
+            _emit_event=MagicMock(),
+        )
+        return ExecuteHelper(executor, fake_ssh, MagicMock()), executor
+
+    def test_busy_gpu_fails_or_warns_before_launch(self):
+        helper, executor = self._helper("confirm")
+        with patch("trainsh.core.executor_execute.sys.stdin.isatty", return_value=False):
+            result = helper._guard_gpu("work", "root@gpu-box", "torchrun train.py")
+        self.assertFalse(result[0])
+        self.assertIn("GPU already in use on work: GPU 0", result[1])
+        event = executor._emit_event.call_args
+        self.assertEqual(event.args[0], "gpu_busy")
+        self.assertTrue(event.kwargs["confirmation_required"])
+
+        self.assertIsNone(helper._guard_gpu("work", "root@gpu-box", "CUDA_VISIBLE_DEVICES=1 torchrun train.py"))
+        self.assertIsNone(helper._guard_gpu("work", "root@gpu-box", "pip install torch"))
+
+        helper, executor = self._helper("warn")
+        self.assertIsNone(helper._guard_gpu("work", "root@gpu-box", "python train.py"))
+        self.assertIn("GPU already in use", executor.log.call_args.args[0])
+
+        helper, executor = self._helper("off")
+        self.assertIsNone(helper._guard_gpu("work", "root@gpu-box", "python train.py"))
+        executor._emit_event.assert_not_called()
+
+
+if __name__ == "__main__":
+    unittest.main()
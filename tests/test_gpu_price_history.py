@@ -0,0 +1,59 @@
+import tempfile
+import unittest
+from contextlib import redirect_stdout
+from datetime import datetime, timedelta
+from io import StringIO
+from pathlib import Path
+from types import SimpleNamespace
+from unittest.mock import patch
+
+from trainsh.commands import pricing
+from trainsh.services.gpu_price_history import GpuPriceHistory, gpu_price_history, price_trend
+
+
+def offer(gpu, dph, num=1):
+    return SimpleNamespace(gpu_name=gpu, dph_total=dph, num_gpus=num)
+
+
+class GpuPriceHistoryTests(unittest.TestCase):
+    def test_samples_are_per_gpu_and_throttled(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            history = GpuPriceHistory(Path(tmpdir) / "prices.jsonl")
+            t0 = datetime(2026, 10, 1, 12)
+            written = history.record(
+                [offer("RTX 4090", 0.40), offer("RTX 4090", 1.0, num=2), offer("RTX 4090", 0.30), offer("A100", 1.2), offer(None, 1.0)],
+                now=t0,
+            )
+            self.assertEqual(written, 2)
+            self.assertEqual(history.record([offer("RTX 4090", 0.1)], now=t0 + timedelta(minutes=5)), 0)
+            self.assertEqual(history.record([offer("RTX 4090", 0.2)], now=t0 + timedelta(hours=1)), 1)
+
+            sample = history.samples("4090")[0]
+            self.assertEqual((sample["min"], sample["median"], sample["count"]), (0.3, 0.4, 3))
+
+            rows = gpu_price_history("4090", 7, history=history, now=t0 + timedelta(days=1))
+            self.assertEqual(rows, [{"gpu_name": "RTX 4090", "day": "2026-10-01", "min": 0.2, "median": 0.3, "samples": 2}])
+            self.assertEqual(gpu_price_history("4090", 7, history=history, now=t0 + timedelta(days=9)), [])
+
+    def test_trend_and_history_command(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            history = GpuPriceHistory(Path(tmpdir) / "prices.jsonl")
+            now = datetime.now()
+            history.record([offer("RTX 4090", 0.50)], now=now - timedelta(days=3))
+            history.record([offer("RTX 4090", 0.40)], now=now)
+            rows = gpu_price_history("4090", 7, history=history)
+            self.assertAlmostEqual(price_trend(rows), -20.0)
+            self.assertIsNone(price_trend(rows[:1]))
+
+            out = StringIO()
+            with patch("trainsh.services.gpu_price_history.STATE_DIR", Path(tmpdir)), patch(
+                "trainsh.services.gpu_price_history.HISTORY_FILENAME", "prices.jsonl"
+            ), redirect_stdout(out):
+                pricing.main(["history", "4090", "--days", "7"])
+                pricing.main(["history"])
+        self.assertIn("median down 20.0%", out.getvalue())
+        self.assertIn("RTX 4090", out.getvalue())
+
+
+if __name__ == "__main__":
+    unittest.main()
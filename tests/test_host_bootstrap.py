@@ -0,0 +1,149 @@
+import os
+import subprocess
+import tempfile
+import unittest
+from contextlib import ExitStack, redirect_stdout
+from io import StringIO
+from pathlib import Path
+from types import SimpleNamespace
+from unittest.mock import patch
+
+from trainsh.commands import host
+from trainsh.commands.host_bootstrap import cmd_bootstrap
+from trainsh.core.models import Host, HostType
+from trainsh.services import bootstrap_profile
+
+
+class BootstrapProfileTests(unittest.TestCase):
+    def test_script_installs_sets_timezone_and_marks_host(self):
+        profile = {"packages": ["tmux", "htop"], "timezone": "Europe/Berlin", "tmux_conf": "set -g history-limit 50000\n"}
+        script = bootstrap_profile.build_bootstrap_script("basic", profile)
+        marker = bootstrap_profile.marker_path("basic", profile)
+
+        self.assertIn("apt-get install -y -qq tmux htop", script)
+        self.assertIn("ln -sf /usr/share/zoneinfo/Europe/Berlin /etc/localtime", script)
+        self.assertIn("set -g history-limit 50000\nTRAINSH_TMUX_CONF_EOF", script)
+        self.assertIn(f'if [ -e "{marker}" ]', script)
+        self.assertTrue(script.rstrip().endswith(f'touch "{marker}"'))
+        self.assertNotIn(f'if [ -e "{marker}" ]', bootstrap_profile.build_bootstrap_script("basic", profile, force=True))
+
+        edited = dict(profile, timezone="UTC")
+        self.assertNotEqual(bootstrap_profile.marker_path("basic", edited), marker)
+
+    def test_script_runs_once_per_profile_revision(self):
+        profile = {"tmux_conf": "set -g mouse on\n", "commands": ['echo run >> "$HOME/runs"']}
+        with tempfile.TemporaryDirectory() as home:
+            env = dict(os.environ, HOME=home)
+
+            def run():
+                script = bootstrap_profile.build_bootstrap_script("basic", profile)
+                return subprocess.run(["bash", "-c", script], env=env, capture_output=True, text=True)
+
+            first = run()
+            second = run()
+
+            self.assertEqual(first.returncode, 0, first.stderr)
+            self.assertIn(bootstrap_profile.ALREADY_APPLIED, second.stdout)
+            self.assertEqual(Path(home, ".tmux.conf").read_text(), "set -g mouse on\n")
+            self.assertEqual(Path(home, "runs").read_text(), "run\n")
+
+    def test_script_backs_up_a_differing_tmux_conf(self):
+        profile = {"tmux_conf": "set -g mouse on\n"}
+        with tempfile.TemporaryDirectory() as home:
+            Path(home, ".tmux.conf").write_text("set -g prefix C-a\n")
+            script = bootstrap_profile.build_bootstrap_script("basic", profile, force=True)
+            env = dict(os.environ, HOME=home)
+            for _ in range(2):
+                result = subprocess.run(["bash", "-c", script], env=env, capture_output=True, text=True)
+                self.assertEqual(result.returncode, 0, result.stderr)
+
+            backups = sorted(Path(home).glob(".tmux.conf.trainsh-backup.*"))
+            self.assertEqual([path.read_text() for path in backups], ["set -g prefix C-a\n"])
+            self.assertEqual(Path(home, ".tmux.conf").read_text(), "set -g mouse on\n")
+
+    def setUp(self):
+        tmpdir = tempfile.TemporaryDirectory()
+        self.addCleanup(tmpdir.cleanup)
+        state = patch("trainsh.services.bootstrap_profile.RUNTIME_STATE_DIR", Path(tmpdir.name))
+        state.start()
+        self.addCleanup(state.stop)
+
+    def test_ensure_bootstrapped_uses_host_or_default_profile(self):
+        config = {"bootstrap": {"default_profile": "basic", "profiles": {"basic": {"packages": ["tmux"]}, "gpu": {}}}}
+        calls = []
+
+        def fake_client(stdout, exit_code=0):
+            def run(command, timeout=None):
+                calls.append(command)
+                return SimpleNamespace(success=exit_code == 0, exit_code=exit_code, stdout=stdout, stderr="")
+
+            return SimpleNamespace(run=run)
+
+        logs = []
+        with patch("trainsh.services.ssh.SSHClient.from_host", return_value=fake_client("")):
+            result = bootstrap_profile.ensure_bootstrapped(Host(name="a"), config=config, log=logs.append)
+        self.assertEqual(result.status, "applied")
+        self.assertIn("tmux", calls[0])
+        self.assertEqual(logs, ["Applied bootstrap profile: basic"])
+
+        with patch(
+            "trainsh.services.ssh.SSHClient.from_host",
+            return_value=fake_client(bootstrap_profile.ALREADY_APPLIED + "\n"),
+        ):
+            result = bootstrap_profile.ensure_bootstrapped(Host(name="b", bootstrap_profile="gpu"), config=config, log=logs.append)
+        self.assertEqual(result.status, "already")
+        self.assertEqual(len(logs), 1)
+
+        self.assertIsNone(bootstrap_profile.ensure_bootstrapped(Host(name="c"), config={}, log=logs.append))
+        missing = bootstrap_profile.ensure_bootstrapped(Host(name="d", bootstrap_profile="nope"), config=config, log=logs.append)
+        self.assertFalse(missing.ok)
+
+    def test_ensure_bootstrapped_skips_ssh_once_applied_to_the_endpoint(self):
+        config = {"bootstrap": {"default_profile": "basic", "profiles": {"basic": {"packages": ["tmux"]}}}}
+        client = SimpleNamespace(run=lambda command, timeout=None: SimpleNamespace(success=True, exit_code=0, stdout="", stderr=""))
+        box = Host(name="box", hostname="10.0.0.1")
+
+        with patch("trainsh.services.ssh.SSHClient.from_host", return_value=client) as connect:
+            first = bootstrap_profile.ensure_bootstrapped(box, config=config, log=lambda _msg: None)
+            second = bootstrap_profile.ensure_bootstrapped(box, config=config, log=lambda _msg: None)
+            self.assertEqual((first.status, second.status), ("applied", "already"))
+            self.assertEqual(connect.call_count, 1)
+
+            moved = Host(name="box", hostname="10.0.0.2")
+            self.assertEqual(bootstrap_profile.ensure_bootstrapped(moved, config=config, log=lambda _msg: None).status, "applied")
+            config["bootstrap"]["profiles"]["basic"]["timezone"] = "UTC"
+            self.assertEqual(bootstrap_profile.ensure_bootstrapped(box, config=config, log=lambda _msg: None).status, "applied")
+            self.assertEqual(connect.call_count, 3)
+
+
+class HostBootstrapCommandTests(unittest.TestCase):
+    def test_bootstrap_command_stores_profile_and_applies_it(self):
+        with tempfile.TemporaryDirectory() as tmpdir, ExitStack() as stack:
+            config_dir = Path(tmpdir)
+            stack.enter_context(patch("trainsh.constants.CONFIG_DIR", config_dir))
+            stack.enter_context(patch("trainsh.constants.HOSTS_FILE", config_dir / "hosts.yaml"))
+            stack.enter_context(patch("trainsh.services.vast_api.get_vast_client", side_effect=RuntimeError("off")))
+            stack.enter_context(patch("trainsh.services.runpod_api.get_runpod_client", side_effect=RuntimeError("off")))
+            apply = stack.enter_context(
+                patch(
+                    "trainsh.commands.host_bootstrap.apply_profile",
+                    return_value=bootstrap_profile.BootstrapResult("applied"),
+                )
+            )
+            stack.enter_context(patch("trainsh.commands.host_bootstrap.load_profiles", return_value={"basic": {"packages": ["tmux"]}}))
+            host.save_hosts({"gpu-box": Host(name="gpu-box", type=HostType.SSH, hostname="gpu.example.com")})
+
+            out = StringIO()
+            with redirect_stdout(out):
+                cmd_bootstrap(["gpu-box", "--profile", "basic"])
+
+            self.assertIn("Bootstrap complete.", out.getvalue())
+            self.assertEqual(apply.call_args.args[1], "basic")
+            self.assertEqual(host.load_hosts(include_auto_vast=False)["gpu-box"].bootstrap_profile, "basic")
+
+            with redirect_stdout(StringIO()), self.assertRaises(SystemExit):
+                cmd_bootstrap(["gpu-box", "--profile", "missing"])
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,101 @@
+import subprocess
+import tempfile
+import unittest
+from contextlib import redirect_stdout
+from io import StringIO
+from pathlib import Path
+from types import SimpleNamespace
+from unittest.mock import MagicMock, patch
+
+import yaml
+
+from trainsh.commands import host
+from trainsh.commands.host_defaults import cmd_defaults
+from trainsh.core.executor_tmux import TmuxControlHelper
+from trainsh.core.models import Host, HostType
+from trainsh.services.host_defaults import HostDefaults, validate_defaults
+
+
+class HostDefaultsTests(unittest.TestCase):
+    def test_commands_cd_export_and_start_shell(self):
+        defaults = HostDefaults.from_dict(
+            {"workdir": "~/my project", "shell": "zsh", "history_limit": "200000", "env": {"HF_HOME": "/data/hf cache"}}
+        )
+        self.assertEqual(defaults.history_limit, 200000)
+        self.assertEqual(
+            defaults.wrap_command("python train.py"),
+            "cd \"$HOME\"/'my project' && export HF_HOME='/data/hf cache' && python train.py",
+        )
+        self.assertTrue(defaults.session_command().endswith("&& exec zsh -l"))
+        self.assertIsNone(HostDefaults.from_dict({"history_limit": 1000}).session_command())
+        self.assertEqual(HostDefaults().wrap_command("ls"), "ls")
+
+    def test_wrapped_command_runs_in_workdir_with_env(self):
+        with tempfile.TemporaryDirectory() as workdir:
+            defaults = HostDefaults(workdir=workdir, env={"GREETING": "hi there"})
+            result = subprocess.run(
+                ["sh", "-c", defaults.wrap_command('echo "$GREETING" && pwd')], capture_output=True, text=True
+            )
+        self.assertEqual(result.stdout.splitlines(), ["hi there", str(Path(workdir).resolve())])
+
+    def test_validate_rejects_bad_values(self):
+        problems = validate_defaults({"history_limit": "lots", "env": {"1BAD": "x"}, "color": "red"})
+        self.assertEqual(
+            problems,
+            ["unknown key: color", "history_limit must be a positive integer", "invalid env name: 1BAD"],
+        )
+
+    def test_cmd_defaults_updates_and_clears_stored_host(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            hosts_file = Path(tmpdir) / "hosts.yaml"
+            hosts_file.write_text(yaml.dump({"hosts": [{"name": "gpu", "type": "ssh", "hostname": "gpu.example.com"}]}))
+            with patch("trainsh.constants.CONFIG_DIR", Path(tmpdir)), patch(
+                "trainsh.constants.HOSTS_FILE", hosts_file
+            ), patch("trainsh.services.vast_api.get_vast_client", side_effect=RuntimeError("disabled")), patch(
+                "trainsh.services.ssh_export.refresh_if_enabled", lambda: None
+            ):
+                with redirect_stdout(StringIO()):
+                    cmd_defaults(["gpu", "--workdir", "/workspace", "--history-limit=200000", "--env", "A=1", "--env", "B=2"])
+                    cmd_defaults(["gpu", "--unset", "A", "--shell", "zsh"])
+                saved = yaml.safe_load(hosts_file.read_text())["hosts"][0]["defaults"]
+                self.assertEqual(
+                    saved, {"workdir": "/workspace", "shell": "zsh", "history_limit": 200000, "env": {"B": "2"}}
+                )
+                with redirect_stdout(StringIO()):
+                    cmd_defaults(["gpu", "--clear"])
+                self.assertNotIn("defaults", yaml.safe_load(hosts_file.read_text())["hosts"][0])
+
+    def test_host_run_applies_defaults(self):
+        stored = Host(name="gpu", type=HostType.SSH, hostname="gpu.example.com", defaults={"workdir": "/srv"})
+        with patch.object(host, "load_hosts", return_value={"gpu": stored}), patch.object(
+            host, "run_remote_command"
+        ) as run:
+            host.cmd_run(["gpu", "--", "ls"])
+        self.assertEqual(run.call_args.args[1], "cd /srv && ls")
+
+    def test_tmux_open_starts_session_with_host_defaults(self):
+        stored = Host(
+            name="gpu",
+            type=HostType.SSH,
+            hostname="gpu.example.com",
+            defaults={"workdir": "/srv", "history_limit": 200000},
+        )
+        tmux = MagicMock()
+        tmux.has_session.return_value = False
+        tmux.new_session.return_value = SimpleNamespace(returncode=0, stderr="")
+        executor = MagicMock()
+        executor._resolve_host.return_value = "gpu"
+        executor.allocate_window_session_name.return_value = "job-w1"
+        executor.get_tmux_client.return_value = tmux
+        executor.ctx.windows = {}
+        with patch("trainsh.core.executor_utils._configured_host_for_spec", return_value=stored), patch(
+            "trainsh.services.connectivity.host_offline_reason", return_value=""
+        ):
+            ok, _ = TmuxControlHelper(executor, SimpleNamespace).cmd_tmux_open(["@gpu", "as", "work"])
+        self.assertTrue(ok)
+        self.assertEqual(tmux.new_session.call_args.kwargs["command"], 'cd /srv && exec "${SHELL:-sh}" -l')
+        tmux.run.assert_called_once_with("set-option", "-t", "job-w1", "history-limit", "200000")
+
+
+if __name__ == "__main__":
+    unittest.main()
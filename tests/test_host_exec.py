@@ -0,0 +1,61 @@
+import base64
+import unittest
+from unittest.mock import MagicMock, patch
+
+from trainsh.core.errors import AppError, ErrorCode
+from trainsh.core.models import Host, HostType
+from trainsh.services.host_exec import ExecResult, build_command, host_exec
+from trainsh.services.ssh import SSHClient, SSHResult
+
+
+class HostExecTests(unittest.TestCase):
+    def test_build_command_quotes_env_and_cwd_per_shell(self):
+        self.assertEqual(build_command("ls -la"), "ls -la")
+        self.assertEqual(build_command(["echo", "a b", "$HOME"]), "echo 'a b' '$HOME'")
+        line = build_command("python train.py", env={"RUN": "it's"}, cwd="/data/x y")
+        self.assertTrue(line.startswith("export RUN='it'\"'\"'s'; "))
+        self.assertIn("/data/x y", line)
+        self.assertIn("python train.py", line)
+        encoded = build_command("dir", shell="powershell", env={"RUN": "it's"}).rsplit(" ", 1)[1]
+        self.assertIn("$env:RUN = 'it''s'; dir", base64.b64decode(encoded).decode("utf-16-le"))
+        with self.assertRaises(ValueError):
+            build_command("true", env={"BAD-NAME": "1"})
+
+    def test_result_error_and_check(self):
+        ok = ExecResult("gpu", "true", 0, "hi\n", "", 3.0)
+        self.assertIsNone(ok.error)
+        self.assertEqual(ok.check(), "hi\n")
+        timed_out = ExecResult("gpu", "sleep 9", -1, "", "Command timed out", 9.0, timed_out=True)
+        self.assertEqual(timed_out.error.code, ErrorCode.TIMEOUT)
+        with self.assertRaises(AppError):
+            ExecResult("gpu", "false", 1, "", "boom", 1.0).check()
+
+    def test_host_exec_runs_through_multiplexed_client(self):
+        host = Host(name="gpu", type=HostType.SSH, hostname="10.0.0.5")
+        ssh = MagicMock()
+        ssh.run.return_value = SSHResult(exit_code=0, stdout="ok\n", stderr="")
+        with patch("trainsh.services.host_exec.SSHClient.from_host", return_value=ssh), patch(
+            "trainsh.services.host_exec.multiplex_settings", return_value={"enabled": True, "persist_secs": 30}
+        ), patch("trainsh.services.host_exec.MUX_DIR"):
+            result = host_exec(host, ["nvidia-smi", "-L"], timeout=10)
+        ssh.run.assert_called_once_with("nvidia-smi -L", timeout=10)
+        self.assertEqual((result.host, result.stdout, result.success), ("gpu", "ok\n", True))
+        self.assertEqual(ssh.control_persist, 30)
+        self.assertGreaterEqual(result.duration_ms, 0)
+
+        with patch("trainsh.commands.host.load_hosts", return_value={}):
+            with self.assertRaises(KeyError):
+                host_exec("missing", "true")
+
+    def test_control_master_only_for_batch_sessions(self):
+        client = SSHClient(hostname="gpu.example.com")
+        self.assertNotIn("ControlMaster=auto", client._build_ssh_args("true"))
+        client.control_path = "/tmp/mux/%C"
+        args = client._build_ssh_args("true")
+        self.assertIn("ControlPath=/tmp/mux/%C", args)
+        self.assertIn("ControlPersist=60", args)
+        self.assertNotIn("ControlMaster=auto", client._build_ssh_args(interactive=True))
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -303,7 +303,7 @@ class HostCommandDeepTests(unittest.TestCase):
         target = self._ssh_host()
         fake_process = SimpleNamespace(pid=12345)
         with patch("trainsh.commands.host.load_hosts", return_value={"gpu-box": target}), patch(
-            "trainsh.commands.host.start_local_tunnel",
+            "trainsh.commands.host_tunnel.start_local_tunnel",
             return_value=fake_process,
         ) as mocked_start:
             out, code = capture_output(
@@ -596,7 +596,7 @@ class HostCommandDeepTests(unittest.TestCase):
                 path_exists=lambda path: False,
                 read_file_head=lambda path, lines=30: "head\n",
             )
-            ssh = SimpleNamespace(test_connection=lambda: True, connect_interactive=lambda: 0)
+            ssh = SimpleNamespace(test_connection=lambda: True, connect_interactive=lambda log_name="": 0)
             client = SimpleNamespace(
                 list_instances=lambda: [self._vast_instance()],
                 rm_instance=MagicMock(),
@@ -0,0 +1,76 @@
+import socket
+import tempfile
+import threading
+import unittest
+from contextlib import redirect_stdout
+from io import StringIO
+from pathlib import Path
+from unittest.mock import patch
+
+from trainsh.commands.host_latency import cmd_latency
+from trainsh.core.models import Host, HostType
+from trainsh.services.host_latency import fastest_route, host_latency_history, measure_host, measure_tcp, route_stats
+from trainsh.services.ssh import SSHResult
+
+
+def banner_server():
+    server = socket.socket()
+    server.bind(("127.0.0.1", 0))
+    server.listen(1)
+
+    def serve():
+        conn, _ = server.accept()
+        conn.sendall(b"SSH-2.0-OpenSSH_9.6\r\n")
+        conn.close()
+        server.close()
+
+    threading.Thread(target=serve, daemon=True).start()
+    return server.getsockname()[1]
+
+
+def sample(route, ms, ok=True):
+    return {"route": route, "endpoint": f"{route}:22", "proxied": route != "primary", "handshake_ms": ms if ok else None, "ok": ok, "ts": "2026-01-01T00:00:00"}
+
+
+class HostLatencyTests(unittest.TestCase):
+    def test_measure_tcp_reads_ssh_banner(self):
+        result = measure_tcp("127.0.0.1", banner_server(), timeout=2)
+        self.assertEqual(result["error"], "")
+        self.assertGreaterEqual(result["banner_ms"], result["connect_ms"])
+        self.assertTrue(measure_tcp("127.0.0.1", 1, timeout=1)["error"])
+
+    def test_route_stats_rank_working_routes(self):
+        stats = route_stats([sample("primary", 90), sample("proxy", 40), sample("primary", 80), sample("proxy", 50), sample("dead", 1, ok=False)])
+        self.assertEqual([(s["route"], s["fastest"]) for s in stats], [("proxy", True), ("primary", False), ("dead", False)])
+        self.assertEqual(stats[1]["median_ms"], 85)
+        self.assertEqual(stats[2]["success_rate"], 0)
+        self.assertEqual(route_stats([sample("dead", 1, ok=False)])[0]["fastest"], False)
+
+    def test_measure_host_times_each_route_and_keeps_history(self):
+        host = Host(
+            name="gpu",
+            type=HostType.SSH,
+            hostname="10.0.0.5",
+            port=22,
+            env_vars={"connection_candidates": [{"hostname": "bastion", "port": 2222, "proxy_command": "nc %h %p"}]},
+        )
+        with tempfile.TemporaryDirectory() as tmpdir:
+            path = Path(tmpdir) / "latency.json"
+            with patch("trainsh.services.host_latency.measure_tcp", return_value={"connect_ms": 5.0, "banner_ms": 9.0, "error": ""}), patch(
+                "trainsh.services.ssh.SSHClient.run", return_value=SSHResult(0, "", "")
+            ), patch("trainsh.config.get_config_value", side_effect=lambda key, default=None: 3 if key == "latency.history_size" else default):
+                for _ in range(2):
+                    samples = measure_host("gpu", host, path=path)
+            self.assertEqual([(s["route"], s["proxied"], s["banner_ms"]) for s in samples], [("primary", False, 9.0), ("candidate:dict:ssh", True, None)])
+            self.assertEqual(len(host_latency_history("gpu", path=path)), 3)
+            self.assertIsNotNone(fastest_route("gpu", path=path))
+
+            out = StringIO()
+            with patch("trainsh.services.host_latency._history_path", return_value=path), redirect_stdout(out):
+                cmd_latency(["gpu", "--history"])
+            self.assertIn("fastest", out.getvalue())
+            self.assertIn("(proxied)", out.getvalue())
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,94 @@
+import unittest
+from unittest.mock import MagicMock, patch
+
+from trainsh.commands.host_metrics import format_sample
+from trainsh.core.models import Host, HostType
+from trainsh.services import host_metrics
+from trainsh.services.ssh import SSHResult
+
+
+def sample_output(uptime, cpu, sda_read, sda_write, rx, tx):
+    return "\n".join(
+        [
+            "@@uptime",
+            str(uptime),
+            "@@stat",
+            f"cpu  {cpu[0]} 0 {cpu[1]} {cpu[2]} {cpu[3]} 0 0 0 0 0",
+            "@@mem",
+            "MemTotal:       16000000 kB",
+            "MemAvailable:    4000000 kB",
+            "SwapTotal:       2000000 kB",
+            "SwapFree:        1500000 kB",
+            "@@disk",
+            f"   8       0 sda 100 0 {sda_read} 0 50 0 {sda_write} 0 0 0 0",
+            f"   8       1 sda1 100 0 {sda_read} 0 50 0 {sda_write} 0 0 0 0",
+            " 259       0 nvme0n1 1 0 8 0 1 0 8 0 0 0 0",
+            " 259       1 nvme0n1p1 1 0 8 0 1 0 8 0 0 0 0",
+            "   7       0 loop0 9 0 9999 0 0 0 0 0 0 0 0",
+            "@@net",
+            "    lo: 5000 10 0 0 0 0 0 0 5000 10 0 0 0 0 0 0",
+            f"  eth0: {rx} 10 0 0 0 0 0 0 {tx} 10 0 0 0 0 0 0",
+            "@@gpu",
+            "0, NVIDIA H100 80GB HBM3, 87, 40960, 81559, 61, 412.50",
+            "1, NVIDIA H100 80GB HBM3, [N/A], 0, 81559, 35, 70.10",
+        ]
+    )
+
+
+class HostMetricsTests(unittest.TestCase):
+    def setUp(self):
+        host_metrics.clear_host_metrics()
+        self.addCleanup(host_metrics.clear_host_metrics)
+
+    def test_parse_and_compute_rates(self):
+        first = host_metrics.parse_sample(sample_output(100.0, (100, 50, 800, 50), 1000, 2000, 10_000, 20_000))
+        second = host_metrics.parse_sample(sample_output(102.0, (160, 70, 900, 70), 5000, 2400, 30_000, 21_000))
+        self.assertEqual(first["disk_read"], 1000 * 512 + 8 * 512)
+        self.assertEqual(second["gpus"][1]["utilization"], None)
+
+        baseline = host_metrics.compute_metrics(first)
+        self.assertIsNone(baseline["cpu_percent"])
+        self.assertEqual(baseline["mem_used"], 12_000_000 * 1024)
+        self.assertEqual(baseline["swap_used"], 500_000 * 1024)
+
+        metrics = host_metrics.compute_metrics(second, first)
+        self.assertEqual(metrics["cpu_percent"], 40.0)
+        self.assertEqual(metrics["disk_read_bps"], 4000 * 512 / 2)
+        self.assertEqual(metrics["net_rx_bps"], 10_000.0)
+        self.assertEqual(metrics["net_tx_bps"], 500.0)
+        self.assertEqual(metrics["gpus"][0]["power_w"], 412.5)
+        self.assertIsNone(host_metrics.compute_metrics(first, second)["disk_read_bps"])
+        self.assertIn("/proc/net/dev", host_metrics.sample_script())
+
+    def test_sampler_emits_events_into_ring_buffer(self):
+        host = Host(name="gpu", type=HostType.SSH, hostname="gpu.example.com")
+        ssh = MagicMock()
+        ssh.run.side_effect = [
+            SSHResult(exit_code=0, stdout=sample_output(10.0, (0, 0, 100, 0), 0, 0, 0, 0), stderr=""),
+            SSHResult(exit_code=0, stdout=sample_output(11.0, (50, 0, 150, 0), 0, 0, 1024, 0), stderr=""),
+            SSHResult(exit_code=255, stdout="", stderr="Connection refused"),
+        ]
+        received = []
+        host_metrics.add_listener(received.append)
+        self.addCleanup(host_metrics.remove_listener, received.append)
+        with patch("trainsh.services.host_exec.SSHClient.from_host", return_value=ssh):
+            sampler = host_metrics.HostMetricsSampler("gpu", host, interval=1, buffer_size=2)
+            for _ in range(3):
+                sampler.sample_once()
+
+        recent = host_metrics.host_metrics_recent("gpu")
+        self.assertEqual(len(recent), 2)
+        self.assertEqual(recent[0]["cpu_percent"], 50.0)
+        self.assertEqual(recent[1]["error"], "Connection refused")
+        self.assertEqual([event["event"] for event in received], ["host:metrics"] * 3)
+        self.assertEqual(host_metrics.host_metrics_recent("gpu", limit=1), recent[-1:])
+        self.assertEqual(host_metrics.host_metrics_recent("other"), [])
+
+        line = format_sample(recent[0])
+        self.assertIn("cpu  50.0%", line)
+        self.assertIn("net rx 1.0 KiB/s", line)
+        self.assertIn("gpu0 87% 40.0/79.6G", line)
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,69 @@
+import socket
+import unittest
+from contextlib import redirect_stdout
+from io import StringIO
+from unittest.mock import MagicMock, patch
+
+from trainsh.commands import host
+from trainsh.core.models import Host, HostType
+from trainsh.services import host_power
+from trainsh.services.ssh import SSHResult
+
+
+class HostPowerTests(unittest.TestCase):
+    def setUp(self):
+        self.host = Host(name="lab", type=HostType.SSH, hostname="10.0.0.5", env_vars={"wol_mac": "00:11:22:33:44:55"})
+        self.ssh = MagicMock()
+        for target in (
+            patch("trainsh.commands.host.load_hosts", return_value={"lab": self.host}),
+            patch("trainsh.services.host_power.SSHClient.from_host", return_value=self.ssh),
+            patch("trainsh.services.host_power.time.sleep"),
+        ):
+            target.start()
+            self.addCleanup(target.stop)
+
+    def test_magic_packet_and_mac_normalization(self):
+        self.assertEqual(host_power.normalize_mac("00-11-22-33-44-AA"), "00:11:22:33:44:aa")
+        with self.assertRaises(ValueError):
+            host_power.normalize_mac("00:11:22")
+        packet = host_power.magic_packet("001122334455")
+        self.assertEqual(len(packet), 102)
+        self.assertEqual(packet[:6], b"\xff" * 6)
+        self.assertEqual(packet[6:12], bytes.fromhex("001122334455"))
+
+        receiver = socket.socket(socket.AF_INET, socket.SOCK_DGRAM)
+        self.addCleanup(receiver.close)
+        receiver.bind(("127.0.0.1", 0))
+        receiver.settimeout(2)
+        host_power.send_magic_packet("00:11:22:33:44:55", broadcast="127.0.0.1", port=receiver.getsockname()[1])
+        self.assertEqual(receiver.recv(200), packet)
+
+    def test_wake_polls_until_reachable(self):
+        self.ssh.test_connection.side_effect = [False, False, False, True]
+        with patch("trainsh.services.host_power.send_magic_packet") as send:
+            out = StringIO()
+            with redirect_stdout(out):
+                host.main(["wake", "lab", "--timeout", "1m"])
+        send.assert_called_once_with("00:11:22:33:44:55", broadcast="255.255.255.255", port=9)
+        self.assertIn("lab is up after", out.getvalue())
+        self.assertEqual(self.ssh.test_connection.call_count, 4)
+
+        self.host.env_vars = {}
+        with self.assertRaises(ValueError):
+            host_power.host_wake("lab")
+
+    def test_power_off_runs_detached_command_and_waits_for_offline(self):
+        self.ssh.run.return_value = SSHResult(exit_code=255, stdout="", stderr="Connection closed")
+        self.ssh.test_connection.side_effect = [True, False]
+        result = host_power.host_power_off("lab", suspend=True)
+        self.assertTrue(result["down"])
+        self.assertEqual(result["command"], "sudo -n systemctl suspend")
+        self.assertIn("nohup sh -c 'sleep 1; sudo -n systemctl suspend'", self.ssh.run.call_args.args[0])
+
+        self.ssh.run.return_value = SSHResult(exit_code=1, stdout="", stderr="sudo: a password is required")
+        with self.assertRaises(RuntimeError):
+            host_power.host_power_off("lab", wait=False)
+
+
+if __name__ == "__main__":
+    unittest.main()
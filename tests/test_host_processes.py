@@ -0,0 +1,98 @@
+import subprocess
+import unittest
+from contextlib import redirect_stdout
+from io import StringIO
+from unittest.mock import MagicMock, patch
+
+from trainsh.commands import host
+from trainsh.core.models import Host, HostType
+from trainsh.services.host_processes import (
+    filter_processes,
+    host_kill_process,
+    host_list_processes,
+    normalize_signal,
+    parse_process_output,
+)
+from trainsh.services.ssh import SSHResult
+from tests.runtime_test_utils import run_locally
+
+PS_OUTPUT = """@@ps
+    1     0 root     Ss    0.0  0.1  12000 10-01:00:00 /sbin/init
+ 4242     1 alice    Sl   98.5 12.0 8000000    01:02:03 python train.py --lr 3e-4
+ 4300  4242 alice    Z     0.0  0.0      0    01:00:00 [python] <defunct>
+ 5000     1 bob      S     0.1  0.2  20000       05:00 bash
+@@gpus
+0, GPU-aaa
+1, GPU-bbb
+@@apps
+4242, GPU-aaa, 40960
+4242, GPU-bbb, 1024
+777, GPU-bbb, 2048
+@@pmon
+# gpu         pid  type    sm    mem    enc    dec    command
+# Idx           #   C/G     %      %      %      %    name
+    0        4242     C    87     40      -      -    python
+    1        4242     C     3      1      -      -    python
+"""
+
+
+class HostProcessTests(unittest.TestCase):
+    def setUp(self):
+        self.ssh = MagicMock()
+        hosts = {"gpu": Host(name="gpu", type=HostType.SSH, hostname="gpu.example.com")}
+        for target in (
+            patch("trainsh.commands.host.load_hosts", return_value=hosts),
+            patch("trainsh.services.host_exec.SSHClient.from_host", return_value=self.ssh),
+        ):
+            target.start()
+            self.addCleanup(target.stop)
+
+    def test_parse_attributes_gpu_memory_to_pids(self):
+        rows = parse_process_output(PS_OUTPUT)
+        self.assertEqual([row["pid"] for row in rows[:2]], [4242, 777])
+        trainer = rows[0]
+        self.assertEqual(trainer["gpu_memory_mb"], 41984)
+        self.assertEqual(trainer["gpus"], [0, 1])
+        self.assertEqual(trainer["gpu_sm_percent"], 87)
+        self.assertEqual(trainer["command"], "python train.py --lr 3e-4")
+        self.assertEqual(rows[1]["command"], "(not visible from this namespace)")
+        self.assertTrue(next(row for row in rows if row["pid"] == 4300)["zombie"])
+
+        self.assertEqual([row["pid"] for row in filter_processes(rows, "ALICE")], [4242, 4300])
+        self.assertEqual([row["pid"] for row in filter_processes(rows, gpu_only=True)], [4242, 777])
+        self.assertEqual(normalize_signal("sigkill"), "KILL")
+        self.assertEqual(normalize_signal(9), "KILL")
+        with self.assertRaises(ValueError):
+            normalize_signal("SEGV")
+
+    def test_list_and_kill_commands(self):
+        self.ssh.run.return_value = SSHResult(exit_code=0, stdout=PS_OUTPUT, stderr="")
+        self.assertEqual(len(host_list_processes("gpu", "train")), 1)
+        out = StringIO()
+        with redirect_stdout(out):
+            host.main(["ps", "gpu", "--gpu"])
+        self.assertIn("41.0G@0,1", out.getvalue())
+
+        with self.assertRaises(ValueError):
+            host_kill_process("gpu", 1)
+
+        sleeper = subprocess.Popen(["sleep", "30"])
+        self.addCleanup(sleeper.kill)
+        with patch("trainsh.services.host_processes.host_exec", side_effect=run_locally), patch(
+            "trainsh.commands.host_processes.prompt_input", return_value="y"
+        ), patch(
+            "trainsh.services.host_processes.parse_process_output",
+            return_value=[{**parse_process_output(PS_OUTPUT)[0], "pid": sleeper.pid}],
+        ):
+            out = StringIO()
+            with redirect_stdout(out):
+                host.main(["kill", "gpu", str(sleeper.pid)])
+        self.assertEqual(sleeper.wait(timeout=5), -15)
+        self.assertTrue(
+            "exited after SIGTERM" in out.getvalue() or "zombie" in out.getvalue(),
+            out.getvalue(),
+        )
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,82 @@
+import json
+import tempfile
+import threading
+import time
+import unittest
+from contextlib import redirect_stdout
+from io import StringIO
+from pathlib import Path
+from unittest.mock import patch
+
+from trainsh.commands import host
+from trainsh.core.models import Host, HostType
+from trainsh.services.connectivity import ConnectivityMonitor
+from trainsh.services.host_refresh import refresh_all, run_per_host
+
+
+class HostRefreshTests(unittest.TestCase):
+    def test_slow_host_only_times_out_itself(self):
+        release = threading.Event()
+        self.addCleanup(release.set)
+
+        def work(name):
+            if name == "dead":
+                release.wait(5)
+            if name == "broken":
+                raise RuntimeError("no route")
+            return name.upper()
+
+        seen = []
+        started = time.monotonic()
+        results = run_per_host(["dead", "a", "broken", "b"], work, timeout=0.3, workers=4, on_result=lambda r: seen.append(r.name))
+        self.assertLess(time.monotonic() - started, 2)
+        self.assertEqual({n: r.status for n, r in results.items()}, {"dead": "timeout", "a": "ok", "broken": "error", "b": "ok"})
+        self.assertEqual(list(results), ["dead", "a", "broken", "b"])
+        self.assertEqual(seen[-1], "dead")
+        self.assertEqual((results["a"].value, results["broken"].error), ("A", "no route"))
+
+    def test_refresh_all_streams_events_and_records_state(self):
+        hosts = {
+            "up": Host(name="up", type=HostType.SSH, hostname="10.0.0.1"),
+            "down": Host(name="down", type=HostType.SSH, hostname="10.0.0.2"),
+        }
+        with tempfile.TemporaryDirectory() as tmpdir:
+            monitor = ConnectivityMonitor(Path(tmpdir))
+            events = []
+            with patch("trainsh.services.connectivity.ConnectivityMonitor", return_value=monitor), patch(
+                "trainsh.services.connectivity.probe_host", side_effect=lambda h: (h.name == "up", "" if h.name == "up" else "refused")
+            ):
+                result, refreshed = refresh_all(hosts, timeout=5, on_event=events.append)
+            states = monitor.host_states()
+        self.assertEqual({e["host"]: e["status"] for e in events}, {"up": "online", "down": "offline"})
+        self.assertEqual(sorted(e["done"] for e in events), [1, 2])
+        self.assertEqual(result["down"]["error"], "refused")
+        self.assertEqual(refreshed, {})
+        self.assertFalse(states["down"]["online"])
+
+    def test_refresh_all_command_and_parallel_discovery(self):
+        hosts = {"up": Host(name="up", type=HostType.SSH, hostname="10.0.0.1")}
+        event = {"event": "host:refresh", "host": "up", "status": "online", "endpoint": "10.0.0.1:22", "moved_from": "", "error": "", "done": 1, "total": 1}
+
+        def fake_refresh_all(configured, timeout=None, on_event=None):
+            on_event(event)
+            return {"up": event}, {}
+
+        out = StringIO()
+        with patch("trainsh.commands.host.load_hosts", return_value=hosts), patch(
+            "trainsh.services.host_refresh.refresh_all", side_effect=fake_refresh_all
+        ), redirect_stdout(out):
+            host.main(["refresh", "--all", "--json"])
+            host.main(["refresh", "--all", "--timeout", "3"])
+        lines = out.getvalue().splitlines()
+        self.assertEqual(json.loads(lines[0])["status"], "online")
+        self.assertIn("1 of 1 host(s) online.", out.getvalue())
+
+        with patch("trainsh.commands.host._list_vast_instances", side_effect=RuntimeError("no key")), patch(
+            "trainsh.commands.host._list_runpod_pods", return_value=["pod"]
+        ):
+            self.assertEqual(host._list_provider_resources(), ([], ["pod"]))
+
+
+if __name__ == "__main__":
+    unittest.main()
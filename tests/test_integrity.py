@@ -0,0 +1,97 @@
+import json
+import os
+import tempfile
+import unittest
+from contextlib import redirect_stdout
+from io import StringIO
+from unittest.mock import patch
+
+from trainsh.commands.host_integrity import cmd_manifest, cmd_verify
+from trainsh.core.models import Host, HostType
+from trainsh.core.recipe_models import RecipeModel
+from trainsh.services.integrity import (
+    build_host_manifest,
+    build_local_manifest,
+    parse_manifest_lines,
+    remote_manifest_script,
+    verify_manifest,
+)
+from tests.runtime_test_utils import isolated_executor, run_locally
+
+
+class IntegrityTests(unittest.TestCase):
+    def setUp(self):
+        self.tmpdir = tempfile.TemporaryDirectory()
+        self.addCleanup(self.tmpdir.cleanup)
+        self.remote = os.path.join(self.tmpdir.name, "remote")
+        self.local = os.path.join(self.tmpdir.name, "local")
+        for root in (self.remote, self.local):
+            os.makedirs(os.path.join(root, "step 100"))
+            with open(os.path.join(root, "step 100", "model.bin"), "wb") as handle:
+                handle.write(b"\x00weights" * 1000)
+            with open(os.path.join(root, "config.json"), "w") as handle:
+                handle.write('{"lr": 0.1}')
+        self.host = Host(name="gpu", type=HostType.SSH, hostname="gpu.example.com")
+        ssh_patch = patch("trainsh.services.integrity.host_exec", side_effect=run_locally)
+        ssh_patch.start()
+        self.addCleanup(ssh_patch.stop)
+
+    def test_remote_manifest_matches_local_hashing(self):
+        remote = build_host_manifest(self.host, self.remote, jobs=2)
+        local = build_local_manifest(self.local)
+        self.assertEqual(remote["files"], local["files"])
+        self.assertEqual([item["path"] for item in remote["files"]], ["config.json", "step 100/model.bin"])
+        self.assertEqual(remote["total_bytes"], 8011)
+        self.assertEqual(remote["source"], f"gpu:{self.remote}")
+
+        self.assertIn("xargs -0 -n 16 -P 3", remote_manifest_script("~/out", 3))
+        self.assertEqual(parse_manifest_lines("garbage\n" + "a" * 64 + "\t5\tx\n"), [{"path": "x", "size": 5, "sha256": "a" * 64}])
+        with self.assertRaises(RuntimeError):
+            build_host_manifest(self.host, os.path.join(self.remote, "missing"))
+
+    def test_verify_flags_missing_corrupted_and_extra(self):
+        manifest = build_local_manifest(self.remote)
+        self.assertTrue(verify_manifest(manifest, self.local)["ok"])
+
+        with open(os.path.join(self.local, "config.json"), "w") as handle:
+            handle.write('{"lr": 0.2}')
+        os.remove(os.path.join(self.local, "step 100", "model.bin"))
+        with open(os.path.join(self.local, "notes.txt"), "w") as handle:
+            handle.write("local only")
+        report = verify_manifest(manifest, self.local)
+        self.assertFalse(report["ok"])
+        self.assertEqual([item["path"] for item in report["missing"]], ["step 100/model.bin"])
+        self.assertEqual([(item["path"], item["reason"]) for item in report["corrupted"]], [("config.json", "sha256")])
+        self.assertEqual(report["extra"], ["notes.txt"])
+        self.assertEqual(report["verified"], 0)
+
+    def test_commands_and_provider(self):
+        manifest_file = os.path.join(self.tmpdir.name, "ckpt.manifest.json")
+        out = StringIO()
+        with patch("trainsh.commands.host.load_hosts", return_value={"gpu": self.host}), redirect_stdout(out):
+            cmd_manifest(["gpu", self.remote, "--output", manifest_file])
+            cmd_verify(["gpu", self.remote, self.local])
+        self.assertIn("2 files", out.getvalue())
+        self.assertIn("OK: local copy matches.", out.getvalue())
+        with open(manifest_file) as handle:
+            self.assertEqual(json.load(handle)["file_count"], 2)
+
+        os.truncate(os.path.join(self.local, "config.json"), 3)
+        out = StringIO()
+        with redirect_stdout(out), self.assertRaises(SystemExit):
+            cmd_verify(["--manifest", manifest_file, self.local])
+        self.assertIn("CORRUPTED  config.json (size 3 != 11)", out.getvalue())
+
+        with isolated_executor(RecipeModel(name="integrity")) as (executor, _config_dir):
+            ok, message = executor._exec_provider_integrity_verify({"manifest": manifest_file, "local_dir": self.remote})
+            self.assertTrue(ok, message)
+            ok, message = executor._exec_provider_integrity_verify({"manifest": manifest_file, "local_dir": self.local})
+            self.assertFalse(ok)
+            self.assertIn("config.json", message)
+            ok, message = executor._exec_provider_integrity_manifest({"path": self.local})
+            self.assertTrue(ok, message)
+            self.assertIn("2 files", message)
+
+
+if __name__ == "__main__":
+    unittest.main()
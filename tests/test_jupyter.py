@@ -0,0 +1,114 @@
+import os
+import stat
+import tempfile
+import unittest
+from contextlib import redirect_stdout
+from io import StringIO
+from pathlib import Path
+from unittest.mock import MagicMock, patch
+
+from trainsh import Recipe
+from trainsh.commands import jupyter as jupyter_cmd
+from trainsh.core.models import Host, HostType
+from trainsh.pyrecipe.models import Host as RecipeHost
+from trainsh.services import jupyter_service
+from trainsh.services.ssh import SSHResult
+from tests.runtime_test_utils import isolated_executor
+
+
+class JupyterTests(unittest.TestCase):
+    def setUp(self):
+        self.tmpdir = tempfile.TemporaryDirectory()
+        self.addCleanup(self.tmpdir.cleanup)
+        self.host = Host(name="gpu", type=HostType.SSH, hostname="gpu.example.com")
+        self.ssh = MagicMock()
+        self.ssh.run.return_value = SSHResult(exit_code=0, stdout="", stderr="")
+        self.ssh.run_with_input.return_value = SSHResult(exit_code=0, stdout="", stderr="")
+        self.tmux = MagicMock()
+        self.tmux.has_session.return_value = False
+        self.tmux.new_session.return_value = MagicMock(returncode=0)
+        self.tmux.send_keys.return_value = MagicMock(returncode=0)
+        self.tmux.kill_session.return_value = MagicMock(returncode=0)
+        self.tunnel = MagicMock(pid=4242)
+        for target, value in (
+            ("STATE_DIR", Path(self.tmpdir.name)),
+            ("tmux_client_for_host", MagicMock(return_value=self.tmux)),
+            ("start_local_tunnel", MagicMock(return_value=self.tunnel)),
+            ("is_local_port_open", MagicMock(return_value=False)),
+        ):
+            patcher = patch(f"trainsh.services.jupyter_service.{target}", value)
+            patcher.start()
+            self.addCleanup(patcher.stop)
+        ssh = patch("trainsh.services.host_exec.SSHClient.from_host", return_value=self.ssh)
+        ssh.start()
+        self.addCleanup(ssh.stop)
+        hosts = patch("trainsh.commands.host.load_hosts", return_value={"gpu": self.host})
+        hosts.start()
+        self.addCleanup(hosts.stop)
+
+    def test_start_keeps_token_out_of_pane_and_persists_private_record(self):
+        record = jupyter_service.start_server("gpu", self.host, root_dir="~/work", python="/opt/py/bin/python")
+
+        self.assertEqual(self.ssh.run_with_input.call_args.args[1], record.token)
+        sent = self.tmux.send_keys.call_args.args[1]
+        self.assertNotIn(record.token, sent)
+        self.assertIn('cd "$HOME"/work && export JUPYTER_TOKEN=', sent)
+        self.assertIn("/opt/py/bin/python -m jupyterlab --no-browser --ip=127.0.0.1 --port=8888", sent)
+        self.assertEqual(self.tmux.new_session.call_args.args[0], "trainsh-jupyter-gpu")
+
+        path = jupyter_service.server_path("gpu")
+        self.assertEqual(stat.S_IMODE(os.stat(path).st_mode), 0o600)
+        self.assertEqual(jupyter_service.load_server("gpu").token, record.token)
+
+        jupyter_service.open_tunnel(record)
+        self.assertEqual(record.local_url, f"http://127.0.0.1:8888/lab?token={record.token}")
+        self.assertEqual(jupyter_service.load_server("gpu").tunnel_pid, 4242)
+
+        with patch("trainsh.services.jupyter_service.os.kill") as kill:
+            self.tmux.has_session.return_value = True
+            jupyter_service.stop_server(record)
+        kill.assert_called_once()
+        self.tmux.kill_session.assert_called_once_with("trainsh-jupyter-gpu")
+        self.assertIsNone(jupyter_service.load_server("gpu"))
+
+    def test_cli_start_waits_for_ready_then_tunnels(self):
+        self.ssh.run.side_effect = [
+            SSHResult(exit_code=1, stdout="", stderr="refused"),
+            SSHResult(exit_code=0, stdout="", stderr=""),
+        ]
+        self.tmux.has_session.side_effect = [False, True]
+        out = StringIO()
+        with redirect_stdout(out), patch("trainsh.commands.jupyter.time.sleep"):
+            jupyter_cmd.main(["start", "gpu", "--name", "nb", "--local-port", "9999"])
+        record = jupyter_service.load_server("nb")
+        self.assertEqual(record.local_port, 9999)
+        self.assertIn(f"Local URL: http://127.0.0.1:9999/lab?token={record.token}", out.getvalue())
+
+        with redirect_stdout(StringIO()), self.assertRaises(SystemExit):
+            jupyter_cmd.main(["status", "missing"])
+
+    def test_recipe_namespace_and_provider(self):
+        recipe = Recipe("nb")
+        gpu = RecipeHost("gpu.example.com", name="gpu")
+        recipe.jupyter.start(gpu, root_dir="/workspace", capture_var="NB_URL", id="nb")
+        recipe.jupyter.stop(gpu, id="nb_stop", depends_on=["nb"])
+        steps = {item.id: item for item in recipe.steps}
+        self.assertEqual(steps["nb"].params["host"], "gpu")
+        self.assertEqual(steps["nb"].params["capture_var"], "NB_URL")
+        self.assertEqual(steps["nb_stop"].params, {"host": "gpu"})
+
+        with isolated_executor(recipe.to_recipe_model()) as (executor, _config_dir):
+            with patch("trainsh.core.executor_utils._host_from_ssh_spec", return_value=self.host):
+                ok, message = executor._exec_provider_jupyter("start", steps["nb"].params)
+            self.assertTrue(ok, message)
+            url = executor.ctx.variables["NB_URL"]
+            self.assertTrue(url.startswith("http://127.0.0.1:8888/lab?token="))
+
+            with patch("trainsh.services.jupyter_service.os.kill"):
+                ok, message = executor._exec_provider_jupyter("stop", {"host": "gpu"})
+            self.assertTrue(ok, message)
+            self.assertEqual(jupyter_service.list_servers(), [])
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,113 @@
+import tempfile
+import unittest
+from contextlib import redirect_stdout
+from io import StringIO
+from pathlib import Path
+from types import SimpleNamespace
+from unittest.mock import MagicMock, patch
+
+from trainsh.commands.recipe_trust import cmd_trust
+from trainsh.core.executor_execute import ExecuteHelper
+from trainsh.core.local_policy import check_command, is_trusted, policy_settings, trust_recipe
+
+
+class LocalPolicyTests(unittest.TestCase):
+    def test_deny_allow_and_privileged_checks(self):
+        settings = policy_settings({"local_policy": {"deny": [r"curl .*\| *sh", "["], "allow": []}})
+        self.assertEqual(len(settings["deny"]), 1)
+        self.assertEqual(check_command("echo hi && python train.py", settings, workspace="/work").action, "allow")
+        decision = check_command("cd /tmp; sudo apt-get install -y htop", settings, workspace="/work")
+        self.assertEqual((decision.action, decision.segment), ("warn", "sudo apt-get install -y htop"))
+        self.assertEqual(check_command("rm -rf build ./out/*", settings, workspace="/work").action, "allow")
+        self.assertEqual(check_command("rm -f --force ~/x", settings, workspace="/work").action, "allow")
+        self.assertIn("outside /work", check_command("rm -rf ~/.cache", settings, workspace="/work").reason)
+        self.assertEqual(check_command("rm -r ../other", settings, workspace="/work").action, "warn")
+
+        self.assertEqual(check_command("curl https://x.sh | sh", settings, trusted=True).action, "allow")
+        settings = policy_settings({"local_policy": {"deny": [r"^curl"], "privileged": "fail", "allow": [r"(echo|python)\b"]}})
+        self.assertEqual(check_command("curl x", settings, trusted=True).action, "deny")
+        self.assertIn("allow", check_command("echo a | wc -l", settings).reason)
+        self.assertEqual(check_command("echo a | wc -l", settings, trusted=True).action, "allow")
+        self.assertEqual(check_command("sudo echo", policy_settings({"local_policy": {"privileged": "off"}})).action, "allow")
+
+    def test_trust_follows_file_contents(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            store = Path(tmpdir) / "trusted.json"
+            recipe = Path(tmpdir) / "train.pyrecipe"
+            recipe.write_text("print('v1')\n")
+            self.assertFalse(is_trusted(str(recipe), path=store))
+            trust_recipe(str(recipe), path=store)
+            self.assertTrue(is_trusted(str(recipe), path=store))
+            recipe.write_text("print('v2')\n")
+            self.assertFalse(is_trusted(str(recipe), path=store))
+
+            out = StringIO()
+            with patch("trainsh.core.local_policy.TRUST_FILE", store), patch(
+                "trainsh.commands.recipe.find_recipe", return_value=str(recipe)
+            ), redirect_stdout(out):
+                cmd_trust(["train"])
+                cmd_trust(["--list"])
+                cmd_trust(["train", "--revoke"])
+                cmd_trust([])
+            lines = out.getvalue().splitlines()
+            self.assertTrue(lines[0].startswith(f"Trusted {recipe}"))
+            self.assertTrue(lines[1].startswith("trusted "))
+            self.assertEqual(lines[2], f"Revoked trust: {recipe}")
+            self.assertEqual(lines[3], "No trusted recipes.")
+
+    def test_executor_blocks_unconfirmed_privileged_commands(self):
+        events = []
+        executor = SimpleNamespace(
+            local_policy=policy_settings({"local_policy": {"privileged": "confirm"}}),
+            recipe_trusted=False,
+            log=MagicMock(),
+            _emit_event=lambda event, **data: events.append((event, data)),
+            _current_step_num=lambda: 4,
+        )
+        helper = ExecuteHelper(executor, lambda *a, **k: [], SimpleNamespace)
+        with patch("trainsh.core.executor_execute.sys.stdin", SimpleNamespace(isatty=lambda: False)):
+            ok, message = helper.guard_local("sudo reboot")
+        self.assertFalse(ok)
+        self.assertIn("train recipe trust", message)
+        self.assertEqual(events[0][0], "local_policy")
+        self.assertTrue(events[0][1]["confirmation_required"])
+        self.assertIsNone(helper.guard_local("python train.py"))
+        executor.recipe_trusted = True
+        self.assertIsNone(helper.guard_local("sudo reboot"))
+
+    def test_default_policy_warns_without_blocking_non_tty_runs(self):
+        events = []
+        executor = SimpleNamespace(
+            local_policy=policy_settings({}),
+            recipe_trusted=False,
+            log=MagicMock(),
+            _emit_event=lambda event, **data: events.append((event, data)),
+            _current_step_num=lambda: 1,
+        )
+        helper = ExecuteHelper(executor, lambda *a, **k: [], SimpleNamespace)
+        with patch("trainsh.core.executor_execute.sys.stdin", SimpleNamespace(isatty=lambda: False)):
+            self.assertIsNone(helper.guard_local("sudo reboot"))
+        self.assertEqual(events[0][1]["action"], "warn")
+
+    def test_rm_targets_resolve_against_the_executor_workdir(self):
+        executor = SimpleNamespace(
+            local_policy=policy_settings({"local_policy": {"privileged": "fail"}}),
+            recipe_trusted=False,
+            log=MagicMock(),
+            _emit_event=lambda event, **data: None,
+            _current_step_num=lambda: 1,
+            get_tmux_client=lambda host: SimpleNamespace(
+                display_message=lambda target, fmt: SimpleNamespace(returncode=0, stdout="/work/run\n")
+            ),
+        )
+        helper = ExecuteHelper(executor, lambda *a, **k: [], SimpleNamespace)
+        workdir = helper._local_workdir(SimpleNamespace(remote_session="train_1"))
+        self.assertEqual(workdir, "/work/run")
+        self.assertIsNone(helper.guard_local("rm -rf build", workdir))
+        ok, message = helper.guard_local("rm -rf ../data", workdir)
+        self.assertFalse(ok)
+        self.assertIn("outside /work/run", message)
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,78 @@
+import tempfile
+import unittest
+from pathlib import Path
+from unittest.mock import patch
+
+from trainsh.core.runtime_store import RuntimeStore
+from trainsh.services import log_follow
+from trainsh.services.log_aggregate import MergedStream, merged_lines, read_page, resolve_sources, source_tags
+from trainsh.services.log_follow import LINE_EVENT, new_follow
+
+
+def line_event(record, ts, line):
+    return {"run_id": record.run_id, "event": LINE_EVENT, "step_num": None, "payload": {"line": line}, "ts": ts}
+
+
+class LogAggregateTests(unittest.TestCase):
+    def setUp(self):
+        self.tmpdir = tempfile.TemporaryDirectory()
+        self.addCleanup(self.tmpdir.cleanup)
+        root = Path(self.tmpdir.name)
+        patcher = patch.object(log_follow, "STATE_DIR", root / "state")
+        patcher.start()
+        self.addCleanup(patcher.stop)
+        self.store = RuntimeStore(root / "runtime")
+        self.node0 = new_follow("node-0", "/run/train.log", job_id="job-123")
+        self.node1 = new_follow("node-1", "/run/train.log", job_id="job-123")
+        self.other = new_follow("node-0", "/var/log/syslog")
+
+    def test_sources_resolve_by_job_host_or_id(self):
+        by_job = {record.follow_id for record in resolve_sources(job_id="job-1")}
+        self.assertEqual(by_job, {self.node0.follow_id, self.node1.follow_id})
+        self.assertEqual(len(resolve_sources(["node-0"])), 2)
+        self.assertEqual([record.follow_id for record in resolve_sources([self.other.follow_id[:6]])], [self.other.follow_id])
+
+        tags = source_tags([self.node0, self.node1])
+        self.assertEqual(tags[self.node0.follow_id], "node-0:train.log")
+        twin = log_follow.FollowRecord(follow_id="ffff0000", host="node-0", path="/other/train.log")
+        self.assertTrue(source_tags([self.node0, twin])["ffff0000"].endswith("#ffff"))
+
+    def test_history_merges_by_time_and_pages(self):
+        for event in (
+            line_event(self.node1, "2026-10-17T10:00:02", "n1 step 1"),
+            line_event(self.node0, "2026-10-17T10:00:01", "n0 step 1"),
+            line_event(self.other, "2026-10-17T10:00:01.5", "syslog noise"),
+            line_event(self.node0, "2026-10-17T10:00:03", "n0 step 2"),
+        ):
+            self.store.append_event(event)
+        sources = resolve_sources(job_id="job-123")
+
+        merged = merged_lines(sources, store=self.store)
+        self.assertEqual([line.line for line in merged], ["n0 step 1", "n1 step 1", "n0 step 2"])
+        self.assertEqual(merged[1].format(), "10:00:02 [node-1:train.log] n1 step 1")
+
+        page, number, pages = read_page(sources, page_size=2, store=self.store)
+        self.assertEqual((number, pages, [line.line for line in page]), (2, 2, ["n0 step 2"]))
+        page, number, _pages = read_page(sources, 1, page_size=2, store=self.store)
+        self.assertEqual([line.line for line in page], ["n0 step 1", "n1 step 1"])
+
+    def test_live_stream_starts_at_the_end_and_bounds_its_buffer(self):
+        self.store.append_event(line_event(self.node0, "2026-10-17T10:00:00", "before"))
+        stream = MergedStream([self.node0, self.node1], store=self.store, max_pending=2)
+        self.assertEqual(stream.poll(), [])
+
+        self.store.append_event(line_event(self.node1, "2026-10-17T10:00:05", "b"))
+        self.store.append_event(line_event(self.node0, "2026-10-17T10:00:04", "a"))
+        self.assertEqual([line.line for line in stream.poll()], ["a", "b"])
+
+        for index in range(5):
+            self.store.append_event(line_event(self.node0, f"2026-10-17T10:00:1{index}", f"burst {index}"))
+        lines = stream.poll()
+        self.assertEqual(lines[0].source, "trainsh")
+        self.assertIn("3 lines skipped", lines[0].line)
+        self.assertEqual([line.line for line in lines[1:]], ["burst 3", "burst 4"])
+        self.assertEqual(stream.dropped, 3)
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,98 @@
+import io
+import os
+import tempfile
+import unittest
+from contextlib import redirect_stdout
+from pathlib import Path
+from unittest.mock import patch
+
+from trainsh.commands.host_follow import cmd_follow
+from trainsh.services.log_alerts import ALERT_EVENT, AlertEvaluator, AlertRule, configured_rules, rule_problems
+from trainsh.services.log_follow import FollowRecord, LogFollower
+
+
+class LogAlertTests(unittest.TestCase):
+    def setUp(self):
+        self.tmpdir = tempfile.TemporaryDirectory()
+        self.addCleanup(self.tmpdir.cleanup)
+        root = Path(self.tmpdir.name)
+        patchers = [
+            patch("trainsh.config.CONFIG_FILE", root / "config.yaml"),
+            patch("trainsh.config.CONFIG_DIR", root),
+            patch("trainsh.constants.CONFIG_DIR", root),
+            patch("trainsh.constants.ACTIVE_PROFILE_FILE", root / "active_profile"),
+        ]
+        for patcher in patchers:
+            patcher.start()
+            self.addCleanup(patcher.stop)
+        os.environ.pop("TRAINSH_PROFILE", None)
+
+    def test_rules_parse_and_report_problems(self):
+        config = {
+            "alerts": {
+                "rules": {
+                    "oom": {"keyword": "CUDA out of memory (GPU 0)", "action": "cancel", "hosts": "gpu-1"},
+                    "nan": {"pattern": r"loss[=: ]+nan", "cooldown_secs": 60},
+                    "broken": {"pattern": "(", "action": "reboot"},
+                }
+            }
+        }
+        rules = {rule.name: rule for rule in configured_rules(config)}
+        self.assertEqual(set(rules), {"oom", "nan"})
+        self.assertEqual(rules["oom"].hosts, ("gpu-1",))
+        self.assertIn(r"\(GPU", rules["oom"].pattern)
+        self.assertEqual(len(rule_problems(config)), 2)
+
+    def test_cooldown_and_host_scope(self):
+        fired = []
+        now = [0.0]
+        evaluator = AlertEvaluator(
+            [
+                AlertRule("traceback", "Traceback", cooldown_secs=30),
+                AlertRule("other-host", "Traceback", hosts=("gpu-9",)),
+            ],
+            {"host": "gpu-1"},
+            handlers={"notify": lambda rule, line, context: (fired.append((rule.name, context["host"])) or True, "sent")},
+            clock=lambda: now[0],
+        )
+        self.assertEqual(len(evaluator.evaluate("Traceback (most recent call last):")), 1)
+        now[0] = 10
+        self.assertEqual(evaluator.evaluate("Traceback again"), [])
+        self.assertEqual(evaluator.evaluate("all good"), [])
+        now[0] = 31
+        self.assertTrue(evaluator.evaluate("Traceback later")[0].ok)
+        self.assertEqual(fired, [("traceback", "gpu-1"), ("traceback", "gpu-1")])
+
+    def test_follower_records_firings(self):
+        record = FollowRecord(follow_id="abc", host="gpu-1", path="/run/train.log", job_id="job-1")
+        events = []
+        alerts = AlertEvaluator(
+            [AlertRule("nan", "nan", action="cancel")],
+            {"host": "gpu-1", "job_id": "job-1"},
+            handlers={"cancel": lambda rule, line, context: (False, f"job {context['job_id']} is not running")},
+        )
+        follower = LogFollower(
+            record, emit=lambda event, rec, **payload: events.append((event, payload)), save=lambda rec: None, alerts=alerts
+        )
+        follower.handle_line("step 10 loss nan\n", io.BytesIO())
+
+        alert = [payload for event, payload in events if event == ALERT_EVENT]
+        self.assertEqual(record.alerts, 1)
+        self.assertEqual((alert[0]["rule"], alert[0]["action"], alert[0]["ok"]), ("nan", "cancel", False))
+        self.assertEqual(alert[0]["detail"], "job job-1 is not running")
+
+    def test_alert_commands_manage_config(self):
+        out = io.StringIO()
+        with redirect_stdout(out):
+            cmd_follow(["alert", "add", "oom", "--keyword", "out of memory", "--cooldown", "600", "--host", "gpu-1"])
+            cmd_follow(["alert", "list"])
+        self.assertIn("oom: /out\\ of\\ memory/ -> notify (cooldown 600s, gpu-1)", out.getvalue())
+        with redirect_stdout(io.StringIO()), self.assertRaises(SystemExit):
+            cmd_follow(["alert", "add", "bad", "--pattern", "x", "--action", "command"])
+        with redirect_stdout(io.StringIO()):
+            cmd_follow(["alert", "remove", "oom"])
+        self.assertEqual(configured_rules(), [])
+
+
+if __name__ == "__main__":
+    unittest.main()
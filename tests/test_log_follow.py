@@ -0,0 +1,90 @@
+import io
+import tempfile
+import unittest
+from pathlib import Path
+from unittest.mock import patch
+
+from trainsh.services import log_follow
+from trainsh.services.log_follow import (
+    LINE_EVENT,
+    ROTATED_EVENT,
+    FollowRecord,
+    LogFollower,
+    list_follows,
+    load_follow,
+    new_follow,
+    remove_follow,
+    tail_command,
+)
+
+
+class FakeTail:
+    def __init__(self, stdout, stderr="", code=0):
+        self.stdout = io.StringIO(stdout)
+        self.stderr = io.StringIO(stderr)
+        self.code = code
+
+    def wait(self):
+        return self.code
+
+
+class LogFollowTests(unittest.TestCase):
+    def setUp(self):
+        self.tmpdir = tempfile.TemporaryDirectory()
+        self.addCleanup(self.tmpdir.cleanup)
+        root = Path(self.tmpdir.name)
+        for patcher in (
+            patch.object(log_follow, "STATE_DIR", root / "state"),
+            patch.object(log_follow, "FOLLOW_LOGS_DIR", root / "follows"),
+        ):
+            patcher.start()
+            self.addCleanup(patcher.stop)
+
+    def test_tail_command_follows_by_name(self):
+        self.assertEqual(tail_command("/var/log/train.log", 5), "tail -n 5 -F /var/log/train.log")
+        self.assertEqual(tail_command("~/run/out log", 0), "tail -n 0 -F \"$HOME\"/'run/out log'")
+
+    def test_lines_and_rotations_reach_log_file_and_events(self):
+        record = FollowRecord(follow_id="abc", host="gpu", path="/var/log/train.log")
+        events, argvs, sleeps = [], [], []
+        runs = iter([
+            FakeTail("step 1\nstep 2\n", "tail: '/var/log/train.log' has been replaced;  following new file\n", code=255),
+            FakeTail("step 3\n"),
+        ])
+
+        def spawn(argv):
+            argvs.append(argv)
+            return next(runs)
+
+        follower = LogFollower(
+            record,
+            spawn=spawn,
+            emit=lambda event, rec, **payload: events.append((event, payload)),
+            save=lambda rec: None,
+            sleep=sleeps.append,
+        )
+        with patch("trainsh.core.executor_utils._build_ssh_args", lambda host, command: ["ssh", host, command]):
+            self.assertEqual(follower.run(lines=20), 0)
+
+        self.assertEqual(argvs[0][-1], "tail -n 20 -F /var/log/train.log")
+        self.assertEqual(argvs[1][-1], "tail -n 0 -F /var/log/train.log")
+        self.assertEqual(len(sleeps), 1)
+        self.assertEqual(record.log_path.read_text(), "step 1\nstep 2\nstep 3\n")
+        self.assertEqual(record.lines, 3)
+        self.assertEqual(record.rotations, 1)
+        self.assertEqual([payload["line"] for event, payload in events if event == LINE_EVENT], ["step 1", "step 2", "step 3"])
+        self.assertIn(ROTATED_EVENT, [event for event, _ in events])
+
+    def test_several_follows_per_host_stop_individually(self):
+        first = new_follow("gpu", "/a.log")
+        second = new_follow("gpu", "/b.log")
+        new_follow("cpu", "/c.log")
+        self.assertEqual({record.path for record in list_follows("gpu")}, {"/a.log", "/b.log"})
+
+        remove_follow(first)
+        self.assertIsNone(load_follow(first.follow_id))
+        self.assertEqual([record.follow_id for record in list_follows("gpu")], [second.follow_id])
+
+
+if __name__ == "__main__":
+    unittest.main()
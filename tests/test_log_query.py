@@ -0,0 +1,85 @@
+import io
+import json
+import tempfile
+import unittest
+from contextlib import redirect_stdout
+from pathlib import Path
+from unittest.mock import patch
+
+from trainsh.commands.host_follow import cmd_follow
+from trainsh.services import log_follow
+from trainsh.services.log_follow import new_follow
+from trainsh.services.log_query import (
+    compile_filter,
+    index_fields,
+    log_query,
+    parse_step_range,
+    query_entries,
+    read_entries,
+)
+
+LINES = [
+    json.dumps({"levelname": "INFO", "global_step": 100, "train_loss": "3.5", "msg": "warmup"}),
+    "plain text from a print()",
+    json.dumps({"level": "warn", "step": 200, "loss": 2.1, "msg": "grad norm spike", "metrics": {"lr": 0.001}}),
+    json.dumps({"level": "error", "step": 300, "loss": None, "msg": "loss is nan"}),
+]
+
+
+class LogQueryTests(unittest.TestCase):
+    def test_fields_are_indexed_under_stable_names(self):
+        self.assertEqual(
+            index_fields(json.loads(LINES[0])),
+            {"level": "info", "step": 100, "loss": 3.5, "message": "warmup"},
+        )
+        entries = read_entries(LINES)
+        self.assertIsNone(entries[1].record)
+        self.assertEqual(entries[1].value("message"), LINES[1])
+        self.assertEqual(entries[2].fields["level"], "warning")
+        self.assertEqual(entries[2].value("metrics.lr"), 0.001)
+        self.assertEqual([entry.color for entry in entries], ["", "", "yellow", "red"])
+
+    def test_filters_and_step_ranges(self):
+        entries = read_entries(LINES)
+        pick = lambda expr, steps=(None, None): [entry.number for entry in query_entries(entries, expr, steps)]
+
+        self.assertEqual(pick("level>=warning"), [3, 4])
+        self.assertEqual(pick("loss<3"), [3])
+        self.assertEqual(pick("level>=info and message~nan"), [4])
+        self.assertEqual(pick("metrics.lr==0.001"), [3])
+        self.assertEqual(pick("", parse_step_range("150:")), [3, 4])
+        self.assertEqual(pick("", parse_step_range(":200")), [1, 3])
+        self.assertEqual([entry.number for entry in query_entries(entries, limit=2)], [3, 4])
+        with self.assertRaises(ValueError):
+            compile_filter("loss")
+        with self.assertRaises(ValueError):
+            parse_step_range("100")
+
+    def test_query_a_followed_log(self):
+        with tempfile.TemporaryDirectory() as tmpdir, patch.object(
+            log_follow, "STATE_DIR", Path(tmpdir) / "state"
+        ), patch.object(log_follow, "FOLLOW_LOGS_DIR", Path(tmpdir) / "follows"):
+            record = new_follow("gpu", "/run/train.jsonl")
+            record.log_path.parent.mkdir(parents=True)
+            record.log_path.write_text("\n".join(LINES) + "\n")
+
+            self.assertEqual([entry.fields["step"] for entry in log_query(record.follow_id, "level>=warning")], [200, 300])
+            out = io.StringIO()
+            with redirect_stdout(out):
+                cmd_follow(["query", record.follow_id, "--where", "loss>3", "--json"])
+            payload = json.loads(out.getvalue())
+            self.assertEqual((payload["line"], payload["loss"], payload["color"]), (1, 3.5, ""))
+
+    def test_follower_events_carry_indexed_fields(self):
+        record = log_follow.FollowRecord(follow_id="abc", host="gpu", path="/run/train.jsonl")
+        events = []
+        follower = log_follow.LogFollower(record, emit=lambda event, rec, **payload: events.append(payload), save=lambda rec: None)
+        writer = io.BytesIO()
+        follower.handle_line(LINES[2] + "\n", writer)
+        follower.handle_line(LINES[1] + "\n", writer)
+        self.assertEqual(events[0]["fields"]["loss"], 2.1)
+        self.assertNotIn("fields", events[1])
+
+
+if __name__ == "__main__":
+    unittest.main()
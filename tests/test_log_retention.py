@@ -0,0 +1,123 @@
+import tempfile
+import threading
+import unittest
+from contextlib import redirect_stdout
+from datetime import datetime
+from io import StringIO
+from pathlib import Path
+from unittest.mock import patch
+
+from trainsh.commands.recipe_views import cmd_logs
+from trainsh.core.execution_log import ExecutionLogger, ExecutionLogReader
+from trainsh.core.runtime_store import RuntimeStore
+from trainsh.services.log_retention import apply_log_policy
+
+
+def _run(store, run_id, ended_at, status="succeeded"):
+    success = None if status == "running" else status == "succeeded"
+    store.append_run({"run_id": run_id, "status": status, "success": success, "started_at": ended_at, "ended_at": ended_at, "ts": ended_at})
+    store.append_event({"run_id": run_id, "event": "step_output", "step_num": 1, "payload": {"output": "x" * 100}, "ts": ended_at})
+    store.append_task({"run_id": run_id, "task_id": "t", "ts": ended_at})
+
+
+class LogRetentionTests(unittest.TestCase):
+    def test_output_caps_write_one_marker_per_scope(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            logger = ExecutionLogger("job1", "train", tmpdir, max_step_bytes=10, max_run_bytes=25)
+            logger.step_output(1, "0123456789abc")
+            logger.step_output(1, "more")
+            logger.step_output(2, "0123456789")
+            logger.step_output(3, "0123456789")
+            logger.close()
+
+            events = RuntimeStore(Path(tmpdir)).list_events("job1")
+            summary = [(event["event"], event["step_num"], event["payload"].get("scope")) for event in events]
+            self.assertEqual(
+                summary,
+                [
+                    ("step_output", 1, None),
+                    ("step_output_truncated", 1, "step"),
+                    ("step_output", 2, None),
+                    ("step_output", 3, None),
+                    ("step_output_truncated", 3, "run"),
+                ],
+            )
+            reader = ExecutionLogReader(tmpdir)
+            self.assertEqual(reader.get_step_output("job1", 1), "0123456789")
+            self.assertEqual(reader.get_step_output("job1", 3), "01234")
+            self.assertEqual(logger.output_tail.tail(1), ["0123456789abcmore"])
+
+    def test_rotation_keeps_events_readable_and_drops_oldest(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            store = RuntimeStore(Path(tmpdir))
+            for index in range(4):
+                store.append_event({"run_id": "job", "event": "detail", "payload": {"n": index}, "ts": f"2026-01-01T00:00:0{index}"})
+                self.assertTrue(store.rotate_events(max_bytes=10, keep=2))
+            self.assertFalse(store.rotate_events(max_bytes=10, keep=2))
+            self.assertEqual([path.name for path in store.event_files()], ["events.jsonl.2", "events.jsonl.1", "events.jsonl"])
+            self.assertEqual([event["payload"]["n"] for event in store.list_events("job")], [2, 3])
+
+    def test_prune_by_age_and_count_spares_running_runs(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            root = Path(tmpdir)
+            store = RuntimeStore(root)
+            _run(store, "old", "2026-01-01T00:00:00")
+            _run(store, "mid", "2026-03-01T00:00:00", status="failed")
+            _run(store, "new", "2026-03-10T00:00:00")
+            _run(store, "live", "2025-12-01T00:00:00", status="running")
+            store.rotate_events(max_bytes=1, keep=1)
+            store.append_event({"run_id": "old", "event": "detail", "payload": {}, "ts": "2026-01-01T00:00:01"})
+
+            self.assertEqual(store.prune_runs(older_than_days=30, now=datetime(2026, 3, 15), dry_run=True), ["old"])
+            self.assertEqual(store.prune_runs(older_than_days=30, keep_last=1, now=datetime(2026, 3, 15)), ["mid", "old"])
+            self.assertEqual(sorted(run["run_id"] for run in store.list_runs()), ["live", "new"])
+            self.assertEqual(store.list_events("old"), [])
+            self.assertEqual(len(store.list_events("new")), 1)
+            self.assertEqual({task["run_id"] for task in store.list_tasks()}, {"live", "new"})
+
+            store.append_event({"run_id": "new", "event": "detail", "payload": {}, "ts": "2026-03-10T00:00:01"})
+            config = {"logs": {"keep_runs": 1, "rotate_bytes": 1, "rotate_keep": 1}}
+            self.assertEqual(apply_log_policy(root=root, config=config), {"pruned": [], "rotated": True})
+
+    def test_prune_blocks_appends_from_other_stores_until_rewritten(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            root = Path(tmpdir)
+            store = RuntimeStore(root)
+            _run(store, "old", "2026-01-01T00:00:00")
+            _run(store, "new", "2026-03-10T00:00:00")
+            rewrite = RuntimeStore._rewrite_jsonl
+            writers = []
+
+            def rewrite_with_writer(target, path, run_ids):
+                if not writers:
+                    # Another process's store: it has its own in-process lock, so only the file lock stops it.
+                    other = RuntimeStore(root)
+                    writer = threading.Thread(target=other.append_event, args=({"run_id": "new", "event": "late", "ts": "2026-03-10T00:00:02"},))
+                    writers.append(writer)
+                    writer.start()
+                    writer.join(0.3)
+                    self.assertTrue(writer.is_alive())
+                rewrite(target, path, run_ids)
+
+            with patch.object(RuntimeStore, "_rewrite_jsonl", rewrite_with_writer):
+                self.assertEqual(store.prune_runs(keep_last=1), ["old"])
+            writers[0].join(5)
+            self.assertEqual([event["event"] for event in store.list_events("new")], ["step_output", "late"])
+
+    def test_cli_prune_dry_run_reports_policy(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            store = RuntimeStore(Path(tmpdir))
+            _run(store, "a", "2026-01-01T00:00:00")
+            _run(store, "b", "2026-01-02T00:00:00")
+            out = StringIO()
+            with patch("trainsh.services.log_retention.RuntimeStore", return_value=store), patch(
+                "trainsh.config.load_config", return_value={"logs": {"keep_runs": 1}}
+            ), redirect_stdout(out):
+                cmd_logs(["--prune", "--dry-run"])
+            self.assertIn("Policy: keep 1 run(s), no age limit.", out.getvalue())
+            self.assertIn("Would prune 1 run(s): a", out.getvalue())
+            self.assertEqual(len(store.list_runs()), 2)
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,116 @@
+import json
+import tempfile
+import unittest
+from contextlib import redirect_stdout
+from io import StringIO
+from pathlib import Path
+from unittest.mock import patch
+from urllib.error import HTTPError
+
+from trainsh.core.execution_log import ExecutionLogReader
+from trainsh.core.executor_main import run_recipe
+from trainsh.services.mlflow_tracking import MlflowClient, MlflowRunTracker, parse_metrics, tracking_settings
+from tests.runtime_test_utils import ok_response
+
+
+class FakeMlflowServer:
+    """Records requests and answers like a tracking server with no experiments yet."""
+
+    def __init__(self):
+        self.requests = []
+
+    def __call__(self, request, timeout=None):
+        body = request.data
+        if body and request.get_header("Content-type") == "application/json":
+            body = json.loads(body)
+        self.requests.append((request.get_method(), request.full_url, body))
+        if "experiments/get-by-name" in request.full_url:
+            raise HTTPError(request.full_url, 404, "not found", {}, None)
+        if "experiments/create" in request.full_url:
+            return ok_response({"experiment_id": "7"})
+        if "runs/create" in request.full_url:
+            return ok_response({"run": {"info": {"run_id": "r1", "artifact_uri": "mlflow-artifacts:/7/r1/artifacts"}}})
+        return ok_response({})
+
+    def bodies(self, suffix):
+        return [body for _, url, body in self.requests if url.endswith(suffix)]
+
+
+class MlflowTrackingTests(unittest.TestCase):
+    def test_parses_metric_series_and_merges_settings(self):
+        text = "step 1 loss=2.5 val_loss=3.0\nstep 2 loss: 1.25e-1\nlr=3e-4\n"
+        self.assertEqual(parse_metrics(text, ["loss", "lr"]), {"loss": [2.5, 0.125], "lr": [0.0003]})
+        self.assertEqual(parse_metrics(text, ["acc"]), {})
+
+        config = {"tracking": {"mlflow_uri": "http://mlflow:5000/", "experiment": "base", "metrics": ["loss"]}}
+        merged = tracking_settings(config, {"experiment": "nanochat", "artifacts": ["out/*.json"]})
+        self.assertEqual(merged["mlflow_uri"], "http://mlflow:5000")
+        self.assertEqual((merged["experiment"], merged["artifacts"], merged["enabled"]), ("nanochat", ["out/*.json"], True))
+        self.assertFalse(tracking_settings(config, {"enabled": False})["enabled"])
+        self.assertFalse(tracking_settings({}, {"experiment": "x"})["enabled"])
+
+    def test_tracker_creates_run_logs_metrics_and_uploads_artifacts(self):
+        server = FakeMlflowServer()
+        with tempfile.TemporaryDirectory() as tmpdir:
+            Path(tmpdir, "eval.json").write_text("{}", encoding="utf-8")
+            settings = tracking_settings({}, {"mlflow_uri": "http://mlflow", "metrics": ["loss"], "artifacts": ["*.json"]})
+            tracker = MlflowRunTracker(MlflowClient("http://mlflow", "tok"), settings, log=lambda _msg: None)
+            with patch("trainsh.services.mlflow_tracking.urlopen", side_effect=server):
+                tracker.start("nanochat", "job12345abc", {"LR": "3e-4"}, {"gpu": "root@box"})
+                result = tracker.finish(success=False, outcome="failed", log_text="loss=0.5\nloss=0.4", base_dir=Path(tmpdir))
+
+        self.assertEqual(server.bodies("experiments/create"), [{"name": "nanochat"}])
+        create = server.bodies("runs/create")[0]
+        tags = {tag["key"]: tag["value"] for tag in create["tags"]}
+        self.assertEqual((tags["trainsh.job_id"], tags["trainsh.host.gpu"]), ("job12345abc", "root@box"))
+        params, metrics = server.bodies("runs/log-batch")
+        self.assertEqual(params["params"], [{"key": "LR", "value": "3e-4"}])
+        self.assertEqual([(m["value"], m["step"]) for m in metrics["metrics"]], [(0.5, 0), (0.4, 1)])
+        uploads = [(method, url) for method, url, _ in server.requests if method == "PUT"]
+        self.assertEqual(uploads, [("PUT", "http://mlflow/api/2.0/mlflow-artifacts/artifacts/7/r1/artifacts/eval.json")])
+        self.assertEqual(server.bodies("runs/update")[0]["status"], "FAILED")
+        self.assertEqual(result, {"run_id": "r1", "status": "FAILED", "metrics": {"loss": 0.4}, "artifacts": 1})
+
+
+class RunTrackingTests(unittest.TestCase):
+    def setUp(self):
+        self.tmpdir = tempfile.TemporaryDirectory()
+        self.addCleanup(self.tmpdir.cleanup)
+        self.root = Path(self.tmpdir.name)
+        self.runtime = self.root / "config" / "runtime"
+        for target in (
+            patch("trainsh.core.executor_main.load_config", return_value={"tmux": {}}),
+            patch("trainsh.core.executor_lifecycle.load_config", return_value={"tmux": {}}),
+            patch("trainsh.core.executor_main.CONFIG_DIR", self.root / "config"),
+            patch("trainsh.core.executor_main.RUNTIME_STATE_DIR", self.runtime),
+            patch("trainsh.runtime.CONFIG_DIR", self.runtime),
+            patch("trainsh.core.runtime_store.get_runtime_state_dir", return_value=self.runtime),
+            patch("trainsh.core.executor_lifecycle.get_mlflow_client", side_effect=lambda uri: MlflowClient(uri)),
+        ):
+            target.start()
+            self.addCleanup(target.stop)
+
+    def test_recipe_tracking_registers_run_with_variables_and_log_metrics(self):
+        path = self.root / "train.pyrecipe"
+        path.write_text(
+            'from trainsh import Recipe\n\nrecipe = Recipe("train")\n'
+            'recipe.variables["LR"] = "3e-4"\n'
+            'recipe.tracking(mlflow_uri="http://mlflow", metrics=["loss"])\n'
+            'recipe.shell("echo loss=0.5; echo loss=0.41", id="fit")\n',
+            encoding="utf-8",
+        )
+        server = FakeMlflowServer()
+        with patch("trainsh.services.mlflow_tracking.urlopen", side_effect=server), redirect_stdout(StringIO()):
+            self.assertTrue(run_recipe(str(path), job_id="mljob"))
+
+        self.assertEqual(server.bodies("experiments/create"), [{"name": "train"}])
+        batches = server.bodies("runs/log-batch")
+        self.assertIn({"key": "LR", "value": "3e-4"}, batches[0]["params"])
+        self.assertEqual([m["value"] for m in batches[1]["metrics"]], [0.5, 0.41])
+        self.assertEqual(server.bodies("runs/update")[0]["status"], "FINISHED")
+        summary = ExecutionLogReader(str(self.runtime)).get_execution_summary("mljob")
+        self.assertEqual((summary["tracking"]["run_id"], summary["tracking"]["metrics"]), ("r1", {"loss": 0.41}))
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,126 @@
+import json
+import tempfile
+import unittest
+from contextlib import redirect_stdout
+from io import StringIO
+from pathlib import Path
+from unittest.mock import patch
+
+from trainsh.commands.recipe_test import cmd_test
+from trainsh.core.execution_log import ExecutionLogReader
+from trainsh.core.job_state import JobState, JobStateManager
+from trainsh.core.mock_executor import MockSpecError, parse_mock_spec, run_mock_recipe
+from trainsh.core.runtime_store import RuntimeStore
+
+RECIPE = '''from trainsh import Recipe
+
+recipe = Recipe("demo")
+recipe.vast_start("${VAST_HOST}", id="boot")
+recipe.shell("nvidia-smi --query-gpu=name --format=csv,noheader", id="probe", capture_var="GPU", depends_on=["boot"])
+recipe.branch("var:GPU==H100", variable="BIG", id="size", depends_on=["probe"])
+recipe.shell("python train.py", id="train", depends_on=["size"], step_options={"retries": 2, "retry_delay": 60})
+recipe.shell("python eval.py", id="eval", depends_on=["train"])
+recipe.on_complete("publish")
+'''
+
+
+class MockSpecTests(unittest.TestCase):
+    def test_parses_shorthands_and_rejects_unknown_keys(self):
+        spec = parse_mock_spec({"steps": {"train": [1, {"output": "ok"}], "fetch": "text"}, "operations": {"Vast.Start": None}})
+        self.assertEqual([r.exit_code for r in spec.steps["train"]], [1, 0])
+        self.assertEqual(spec.steps["fetch"][0].output, "text")
+        self.assertEqual(spec.responses_for("other", "vast.start"), spec.operations["vast.start"])
+        self.assertIsNone(spec.responses_for("other", "shell.run"))
+
+        for bad in ({"stepz": {}}, {"steps": {"a": {"code": 1}}}, {"steps": {"a": []}}, {"expect": {"state": 1}}, []):
+            with self.assertRaises(MockSpecError):
+                parse_mock_spec(bad)
+
+
+class MockRunTests(unittest.TestCase):
+    def setUp(self):
+        self.tmpdir = tempfile.TemporaryDirectory()
+        self.addCleanup(self.tmpdir.cleanup)
+        self.root = Path(self.tmpdir.name)
+        self.runtime = self.root / "config" / "runtime"
+        for target in (
+            patch("trainsh.core.executor_main.load_config", return_value={"tmux": {}}),
+            patch("trainsh.core.executor_main.CONFIG_DIR", self.root / "config"),
+            patch("trainsh.core.executor_main.RUNTIME_STATE_DIR", self.runtime),
+            patch("trainsh.runtime.CONFIG_DIR", self.runtime),
+        ):
+            target.start()
+            self.addCleanup(target.stop)
+        self.recipe = self.root / "demo.pyrecipe"
+        self.recipe.write_text(RECIPE, encoding="utf-8")
+
+    def test_canned_results_drive_retries_branches_and_variables(self):
+        spec = parse_mock_spec(
+            {
+                "variables": {"VAST_HOST": "123"},
+                "operations": {"vast.start": {"variables": {"VAST_ID": "123"}}},
+                "steps": {"probe": {"output": "H100\n"}, "train": [{"exit_code": 1, "output": "NCCL timeout"}, 0]},
+                "expect": {"steps": {"train": "success", "eval": "success"}, "attempts": {"train": 2}, "variables": {"BIG": "true"}},
+            }
+        )
+        logs = []
+        report = run_mock_recipe(str(self.recipe), spec, log_callback=logs.append)
+
+        self.assertTrue(report.passed, report.failures)
+        self.assertEqual((report.variables["GPU"], report.variables["VAST_ID"]), ("H100", "123"))
+        self.assertEqual(report.steps["size"]["output"], "branch=true; GPU == H100")
+        self.assertEqual(
+            [(call.step_id, call.try_number, call.operation) for call in report.calls],
+            [("boot", 1, "vast.start"), ("probe", 1, "shell.run"), ("train", 1, "shell.run"), ("train", 2, "shell.run"), ("eval", 1, "shell.run")],
+        )
+        self.assertTrue(any("would run publish" in line for line in logs))
+        self.assertIsNone(ExecutionLogReader(str(self.runtime)).get_execution_summary(report.job_id))
+
+    def test_missed_expectations_are_reported(self):
+        spec = parse_mock_spec({"steps": {"train": 3}, "expect": {"steps": {"eval": "success"}, "variables": {"BIG": "true"}}})
+        report = run_mock_recipe(str(self.recipe), spec)
+
+        self.assertFalse(report.success)
+        self.assertEqual(report.steps["train"], {"state": "failed", "attempts": 3, "output": "mock exit 3"})
+        self.assertEqual(
+            report.failures,
+            ["run failed, expected success", "step eval: skipped, expected success", "variable BIG: 'false', expected 'true'"],
+        )
+
+    def test_failed_mock_run_is_not_resumable(self):
+        spec = parse_mock_spec({"steps": {"eval": 1}, "expect": {"success": False}})
+        report = run_mock_recipe(str(self.recipe), spec)
+
+        self.assertTrue(report.passed, report.failures)
+        manager = JobStateManager(str(self.runtime))
+        self.assertIsNone(manager.find_resumable(str(self.recipe)))
+        self.assertIsNone(manager.load(report.job_id))
+        self.assertEqual(RuntimeStore(self.runtime).list_runs(), [])
+
+        # Test checkpoints left by older versions in the live store are ignored too.
+        manager.save(JobState(job_id=report.job_id, recipe_path=str(self.recipe), recipe_name="demo", status="failed", run_type="test"))
+        manager.save(JobState(job_id="test-run", recipe_path=str(self.recipe), recipe_name="demo", pid=999999, run_type="test"))
+        self.assertIsNone(manager.find_resumable(str(self.recipe)))
+        self.assertEqual(manager.list_orphaned(), [])
+
+    def test_cli_prints_json_and_fails_on_missed_expectations(self):
+        mock = self.root / "demo.mock.yaml"
+        mock.write_text("steps:\n  eval: 1\nexpect:\n  success: false\n  steps: {eval: failed}\n", encoding="utf-8")
+        out = StringIO()
+        with redirect_stdout(out):
+            cmd_test([str(self.recipe), "--mock", str(mock), "--set", "VAST_HOST=9", "--json"])
+        payload = json.loads(out.getvalue())
+        self.assertTrue(payload["passed"])
+        self.assertEqual(payload["variables"]["VAST_HOST"], "9")
+
+        mock.write_text("steps:\n  eval: 1\n", encoding="utf-8")
+        out = StringIO()
+        with redirect_stdout(out), self.assertRaises(SystemExit) as raised:
+            cmd_test([str(self.recipe), "--mock", str(mock)])
+        self.assertEqual(raised.exception.code, 1)
+        self.assertIn("✗ eval", out.getvalue())
+        self.assertIn("FAIL\n  - run failed, expected success", out.getvalue())
+
+
+if __name__ == "__main__":
+    unittest.main()
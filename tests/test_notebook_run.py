@@ -0,0 +1,149 @@
+import json
+import os
+import subprocess
+import tempfile
+import unittest
+from pathlib import Path
+from unittest.mock import MagicMock, patch
+
+from trainsh import Recipe
+from trainsh.pyrecipe.models import PythonRecipeError
+from trainsh.services.notebook_run import cell_progress, count_code_cells, papermill_command
+from tests.runtime_test_utils import isolated_executor
+
+NOTEBOOK = {
+    "cells": [
+        {"cell_type": "code", "source": ["lr = 0.1"], "metadata": {"tags": ["parameters"]}},
+        {"cell_type": "markdown", "source": ["# Eval"]},
+        {"cell_type": "code", "source": ["print(lr)"]},
+    ],
+    "metadata": {},
+    "nbformat": 4,
+    "nbformat_minor": 5,
+}
+# Stands in for papermill: logs one line per cell and echoes its -y parameters.
+FAKE_PAPERMILL = """#!/bin/sh
+in=$1 out=$2
+echo "Executing Cell 1"
+echo "Executing Cell 2"
+while [ $# -gt 0 ]; do [ "$1" = "-y" ] && echo "params $2"; shift; done
+"""
+
+
+class NotebookRunTests(unittest.TestCase):
+    def setUp(self):
+        self.tmpdir = tempfile.TemporaryDirectory()
+        self.addCleanup(self.tmpdir.cleanup)
+        self.root = Path(self.tmpdir.name)
+        self.notebook = self.root / "eval.ipynb"
+        self.notebook.write_text(json.dumps(NOTEBOOK), encoding="utf-8")
+
+    def test_helpers(self):
+        self.assertEqual(count_code_cells(str(self.notebook)), 2)
+        self.assertEqual(count_code_cells(str(self.root / "missing.ipynb")), 0)
+        self.assertEqual(cell_progress("INFO - Executing Cell 12------"), 12)
+        self.assertIsNone(cell_progress("Output: hello"))
+
+        command = papermill_command(
+            ".cache/nb/eval.ipynb",
+            "out/run 1/eval.ipynb",
+            parameters={"lr": 0.1, "name": "a b"},
+            kernel="python3",
+        )
+        self.assertTrue(command.startswith("mkdir -p \"$HOME\"/'out/run 1' && papermill \"$HOME\"/.cache/nb/eval.ipynb"))
+        self.assertIn("-k python3", command)
+        self.assertIn("""-y '{"lr": 0.1, "name": "a b"}'""", command)
+        self.assertEqual(papermill_command("/nb.ipynb", "/out.ipynb"), "papermill /nb.ipynb /out.ipynb --log-output --log-level INFO --no-progress-bar")
+
+    def test_builder(self):
+        recipe = Recipe("nb")
+        recipe.run_notebook(str(self.notebook), host="@gpu", parameters={"lr": 0.1}, variables=["DATA"], capture_var="NB", id="eval")
+        step = recipe.steps[0]
+        self.assertEqual((step.provider, step.operation), ("notebook", "run"))
+        self.assertEqual(step.params["notebook"], str(self.notebook))
+        self.assertEqual(step.params["variables"], ["DATA"])
+        with self.assertRaises(PythonRecipeError):
+            recipe.run_notebook(str(self.root / "missing.ipynb"))
+        script = self.root / "train.py"
+        script.write_text("print(1)\n", encoding="utf-8")
+        with self.assertRaises(PythonRecipeError):
+            recipe.run_notebook(str(script))
+
+    def test_provider_runs_and_downloads(self):
+        papermill = self.root / "papermill"
+        papermill.write_text(FAKE_PAPERMILL, encoding="utf-8")
+        papermill.chmod(0o755)
+        home = self.root / "home"
+        recipe = Recipe("nb")
+        recipe.run_notebook(str(self.notebook), parameters={"out": "$DATA/x"}, variables=["EPOCHS"], capture_var="NB", id="eval")
+        params = dict(recipe.steps[0].params, papermill=str(papermill))
+
+        with isolated_executor(recipe.to_recipe_model()) as (executor, _config_dir), patch.dict(os.environ, {"HOME": str(home)}):
+            executor.ctx.variables.update({"DATA": "/data", "EPOCHS": 3})
+            events = []
+            with patch.object(executor, "_emit_event", side_effect=lambda event, **kw: events.append((event, kw))), patch(
+                "trainsh.core.provider_notebook.shutil.copyfile",
+                side_effect=lambda src, dst: Path(dst).write_text(Path(src).name),
+            ) as copy:
+                ok, message = executor._exec_provider_notebook("run", params)
+
+            self.assertTrue(ok, message)
+            self.assertEqual([kw["cell"] for event, kw in events if event == "notebook_cell"], [1, 2])
+            self.assertEqual(events[0][1]["total"], 2)
+            saved = Path(executor.ctx.variables["NB"])
+            self.assertTrue(saved.is_file())
+            self.assertEqual(saved.name, "step_0000.eval.ipynb")
+            self.assertIn(f"notebooks/{executor.ctx.job_id}/", str(copy.call_args[0][0]))
+            staged = list((home / ".cache/trainsh/scripts").rglob("eval.ipynb"))
+            self.assertEqual(len(staged), 1)
+
+            papermill.write_text("#!/bin/sh\necho boom\nexit 3\n", encoding="utf-8")
+            ok, message = executor._exec_provider_notebook("run", params)
+            self.assertFalse(ok)
+            self.assertIn("boom", message)
+
+    def test_parameters_passed_as_json(self):
+        papermill = self.root / "papermill"
+        papermill.write_text(FAKE_PAPERMILL + 'cp "$in" "$out"\n', encoding="utf-8")
+        papermill.chmod(0o755)
+        recipe = Recipe("nb")
+        output = self.root / "executed.ipynb"
+        recipe.run_notebook(str(self.notebook), parameters={"out": "$DATA/x"}, variables=["EPOCHS"], output=str(output), id="eval")
+        params = dict(recipe.steps[0].params, papermill=str(papermill))
+
+        with isolated_executor(recipe.to_recipe_model()) as (executor, _config_dir), patch.dict(
+            os.environ, {"HOME": str(self.root / "home")}
+        ):
+            executor.ctx.variables.update({"DATA": "/data", "EPOCHS": 3})
+            executor.logger = MagicMock()
+            ok, message = executor._exec_provider_notebook("run", params)
+        self.assertTrue(ok, message)
+        self.assertIn('params {"EPOCHS": 3, "out": "/data/x"}', executor.logger.log_ssh.call_args[0][3])
+        self.assertEqual(json.loads(output.read_text(encoding="utf-8")), NOTEBOOK)
+
+    def test_timeout_stops_papermill_and_its_children(self):
+        papermill = self.root / "papermill"
+        child = self.root / "child.pid"
+        papermill.write_text(f"#!/bin/sh\nsleep 60 &\necho $! > {child}\nwait\n", encoding="utf-8")
+        papermill.chmod(0o755)
+        recipe = Recipe("nb")
+        recipe.run_notebook(str(self.notebook), timeout=1, id="eval")
+        params = dict(recipe.steps[0].params, papermill=str(papermill))
+
+        with isolated_executor(recipe.to_recipe_model()) as (executor, _config_dir), patch.dict(
+            os.environ, {"HOME": str(self.root / "home")}
+        ):
+            ok, message = executor._exec_provider_notebook("run", params)
+            self.assertEqual(executor.ctx.inflight, {})
+            with patch.object(executor.execute_helper, "guard_local", return_value=(False, "blocked")) as guard:
+                self.assertEqual(executor._exec_provider_notebook("run", params), (False, "blocked"))
+            self.assertIn("papermill", guard.call_args[0][0])
+        self.assertFalse(ok)
+        self.assertIn("timed out", message)
+        # Gone or a zombie waiting to be reaped: either way no longer running.
+        state = subprocess.run(["ps", "-o", "stat=", "-p", child.read_text(encoding="utf-8").strip()], capture_output=True, text=True)
+        self.assertIn(state.stdout.strip()[:1], ("", "Z"))
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,78 @@
+import json
+import unittest
+from contextlib import redirect_stdout
+from io import StringIO
+
+from trainsh.commands.recipe_operations import cmd_operations
+from trainsh.core.operations_catalog import operations_catalog
+from trainsh.pyrecipe.base import RecipeSpec
+
+
+def _by_name():
+    return {item["name"]: item for item in operations_catalog()}
+
+
+class OperationsCatalogTests(unittest.TestCase):
+    def test_every_step_builder_is_listed(self):
+        operations = _by_name()
+        self.assertIn("shell", operations)
+        self.assertIn("storage_upload", operations)
+        self.assertIn("assert", operations)
+        # Generic escape hatches have no fixed fields.
+        self.assertNotIn("provider", operations)
+        self.assertNotIn("model_step", operations)
+        for name in ("vast_start", "runpod_wait", "session_wait", "group"):
+            self.assertTrue(hasattr(RecipeSpec, name))
+            self.assertIn(name, operations)
+
+    def test_fields_come_from_the_builder_signature(self):
+        shell = _by_name()["shell"]
+        self.assertEqual(shell["step"], "shell.run")
+        self.assertEqual(shell["description"], "Run shell command.")
+        fields = {item["name"]: item for item in shell["fields"]}
+        self.assertTrue(fields["command"]["required"])
+        self.assertFalse(fields["host"]["required"])
+        self.assertNotIn("step_options", fields)
+        self.assertNotIn("depends_on", fields)
+
+    def test_runs_via_and_targets(self):
+        operations = _by_name()
+        self.assertEqual(operations["session_run"]["runs_via"], "terminal")
+        self.assertEqual(operations["tmux_open"]["step"], "tmux.open")
+        self.assertEqual(operations["tmux_open"]["runs_via"], "terminal")
+        self.assertEqual(operations["http_get"]["runs_via"], "backend")
+        self.assertEqual(operations["http_get"]["targets"], ["local"])
+        self.assertEqual(operations["shell"]["targets"], ["local", "remote"])
+        self.assertEqual(operations["storage_list"]["targets"], ["storage"])
+        self.assertEqual(operations["vast_start"]["targets"], ["vast"])
+        self.assertEqual(operations["transfer"]["step"], "transfer.<operation>")
+
+    def test_aliases_resolve_to_the_step_they_add(self):
+        operations = _by_name()
+        self.assertEqual(operations["bash"]["step"], "shell.run")
+        self.assertEqual(operations["bash"]["alias_of"], "shell")
+        self.assertEqual(operations["on_all_done"]["step"], "util.empty")
+
+    def test_catalog_is_json_serializable(self):
+        json.dumps(operations_catalog())
+
+    def test_lookup_by_name_or_step(self):
+        self.assertEqual([item["name"] for item in operations_catalog("git_clone")], ["git_clone"])
+        self.assertIn("ssh_command", [item["name"] for item in operations_catalog("util.ssh_command")])
+
+    def test_command_prints_json_and_details(self):
+        out = StringIO()
+        with redirect_stdout(out):
+            cmd_operations(["shell", "--json"])
+        self.assertEqual(json.loads(out.getvalue())[0]["name"], "shell")
+        out = StringIO()
+        with redirect_stdout(out):
+            cmd_operations(["tmux_open"])
+        self.assertIn("runs via terminal", out.getvalue())
+        self.assertIn("host", out.getvalue())
+        with redirect_stdout(StringIO()), self.assertRaises(SystemExit):
+            cmd_operations(["no_such_operation"])
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,73 @@
+import tempfile
+import unittest
+from contextlib import redirect_stdout
+from io import StringIO
+from pathlib import Path
+from unittest.mock import patch
+
+from trainsh.commands import transfer
+from trainsh.core.models import TransferEndpoint
+from trainsh.services.host_exec import ExecResult
+from trainsh.services.path_stats import host_path_stats, local_path_stats, parse_stats_output, stats_command
+from tests.runtime_test_utils import run_locally
+
+
+class PathStatsTests(unittest.TestCase):
+    def setUp(self):
+        self.tmpdir = tempfile.TemporaryDirectory()
+        self.addCleanup(self.tmpdir.cleanup)
+        self.root = Path(self.tmpdir.name) / "data"
+        (self.root / "shards dir").mkdir(parents=True)
+        (self.root / "shards dir" / "a.bin").write_bytes(b"x" * 40000)
+        (self.root / "shards dir" / "b.bin").write_bytes(b"x" * 20000)
+        (self.root / "notes.txt").write_text("hi")
+
+    def test_script_counts_files_and_lists_largest_children(self):
+        with patch("trainsh.services.path_stats.host_exec", side_effect=run_locally):
+            stats = host_path_stats("gpu", str(self.root), top=1, timeout=10)
+        self.assertEqual((stats["host"], stats["kind"], stats["files"], stats["complete"]), ("gpu", "dir", 3, True))
+        self.assertGreaterEqual(stats["size"], 60000)
+        self.assertEqual([entry["name"] for entry in stats["largest"]], ["shards dir"])
+
+        with patch("trainsh.services.path_stats.host_exec", side_effect=run_locally):
+            with self.assertRaises(FileNotFoundError):
+                host_path_stats("gpu", str(self.root / "missing"), timeout=10)
+
+    def test_parse_marks_files_and_partial_runs(self):
+        stats = parse_stats_output("kind=file\nsize_kb=8\nfiles=\n", "/w/model.pt")
+        self.assertEqual((stats["kind"], stats["size"], stats["files"], stats["largest"]), ("file", 8192, 1, []))
+        self.assertIn("timeout 5 ", stats_command("/w/it's", budget=5))
+
+        partial = ExecResult("gpu", "", 0, "kind=dir\nsize_kb=4\nfiles=2\n", "partial=1\n", 5.0)
+        with patch("trainsh.services.path_stats.host_exec", return_value=partial):
+            self.assertFalse(host_path_stats("gpu", "/w", timeout=5)["complete"])
+
+    def test_local_stats_match_byte_sizes(self):
+        stats = local_path_stats(str(self.root))
+        self.assertEqual((stats["size"], stats["files"]), (60002, 3))
+        self.assertEqual([entry["name"] for entry in stats["largest"]], ["shards dir", "notes.txt"])
+        self.assertEqual(local_path_stats(str(self.root / "notes.txt"))["kind"], "file")
+
+    def test_transfer_preflight_stops_when_destination_is_full(self):
+        source = TransferEndpoint(type="local", path=str(self.root))
+        destination = TransferEndpoint(type="host", path="/workspace/data", host_id="gpu")
+        with patch("trainsh.services.path_stats.host_free_bytes", return_value=1024), patch(
+            "trainsh.config.load_config", return_value={}
+        ):
+            out = StringIO()
+            with redirect_stdout(out):
+                ok = transfer._run_preflight(source, destination)
+        self.assertFalse(ok)
+        self.assertIn("in 3 files", out.getvalue())
+        self.assertIn("Destination free: 1.0 KiB", out.getvalue())
+        self.assertIn("larger than the free space", out.getvalue())
+
+        with patch("trainsh.services.path_stats.host_free_bytes", return_value=10 * 1024**3), patch(
+            "trainsh.config.load_config", return_value={}
+        ):
+            with redirect_stdout(StringIO()):
+                self.assertTrue(transfer._run_preflight(source, destination))
+
+
+if __name__ == "__main__":
+    unittest.main()
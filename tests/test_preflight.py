@@ -0,0 +1,47 @@
+import unittest
+
+from trainsh import Host, Recipe, local
+from trainsh.core import preflight
+
+
+class PreflightHelperTests(unittest.TestCase):
+    def test_required_tools_follow_windows_transfers_and_commands(self):
+        recipe = Recipe("demo")
+        gpu = Host("ssh://gpu-box", name="gpu")
+        work = gpu.tmux("work")
+        work.run("git pull && huggingface-cli download org/model")
+        recipe.shell("docker ps")
+        recipe.git_clone("https://example.com/repo.git", "/srv/repo", host="@gpu")
+        recipe.copy("@gpu:/out", "./out")
+        local.tmux("notes")
+
+        self.assertEqual(
+            preflight.required_tools(recipe),
+            {
+                "gpu": ["tmux", "git", "huggingface-cli", "rsync"],
+                "local": ["docker", "rsync", "tmux"],
+            },
+        )
+        self.assertEqual(preflight.command_tools("echo digit; ./git-helper; /usr/bin/git status"), [])
+
+    def test_checkable_hosts_and_local_probe(self):
+        self.assertFalse(preflight.checkable_host("placeholder"))
+        self.assertFalse(preflight.checkable_host("vast:123"))
+        self.assertFalse(preflight.checkable_host("${GPU_HOST}"))
+        self.assertTrue(preflight.checkable_host("root@gpu-box"))
+
+        reachable, missing = preflight.check_host_tools("local", ["sh", "trainsh-no-such-tool"], build_ssh_args=None)
+        self.assertTrue(reachable)
+        self.assertEqual(missing, ["trainsh-no-such-tool"])
+
+    def test_bootstrap_script_and_hints(self):
+        script = preflight.bootstrap_script(["tmux", "nvidia-smi", "huggingface-cli"])
+        self.assertIn("apt-get install -y -qq tmux", script)
+        self.assertIn("pip install -q 'huggingface_hub[cli]'", script)
+        self.assertIsNone(preflight.bootstrap_script(["nvidia-smi", "docker"]))
+        self.assertIn("NVIDIA driver", preflight.install_hint("nvidia-smi"))
+        self.assertEqual(preflight.install_hint("rsync"), "apt-get install -y rsync")
+
+
+if __name__ == "__main__":
+    unittest.main()
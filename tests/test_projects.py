@@ -0,0 +1,90 @@
+import os
+import tempfile
+import unittest
+from contextlib import redirect_stdout
+from io import StringIO
+from pathlib import Path
+from types import SimpleNamespace
+from unittest.mock import patch
+
+from trainsh.commands import project as project_cmd
+from trainsh.commands import recipe_views, storage
+from trainsh.commands.recipe_runtime import cmd_run
+from trainsh.core.job_state import JobState
+from trainsh.services import projects
+
+
+class ProjectTests(unittest.TestCase):
+    def setUp(self):
+        self.tmpdir = tempfile.TemporaryDirectory()
+        self.addCleanup(self.tmpdir.cleanup)
+        self.config = {}
+        patchers = [
+            patch("trainsh.constants.PROJECTS_FILE", Path(self.tmpdir.name) / "projects.yaml"),
+            patch("trainsh.constants.CONFIG_DIR", Path(self.tmpdir.name)),
+            patch("trainsh.config.load_config", side_effect=lambda: self.config),
+            patch("trainsh.config.save_config", side_effect=lambda config: None),
+            patch.dict(os.environ, {"TRAINSH_PROJECT": ""}),
+        ]
+        for patcher in patchers:
+            patcher.start()
+            self.addCleanup(patcher.stop)
+
+    def run_cmd(self, *args):
+        out = StringIO()
+        with redirect_stdout(out):
+            project_cmd.main(list(args))
+        return out.getvalue()
+
+    def test_add_extend_and_detach_members(self):
+        self.run_cmd("add", "llm eval", "--host", "gpu-box", "--recipe", "eval.pyrecipe", "--var", "WANDB_PROJECT=llm")
+        self.assertIn("Updated project: llm-eval", self.run_cmd("add", "llm-eval", "--host", "gpu-box", "--storage", "artifacts"))
+        saved = projects.load_projects()["llm-eval"]
+        self.assertEqual((saved.hosts, saved.recipes, saved.storages), (["gpu-box"], ["eval.pyrecipe"], ["artifacts"]))
+        self.assertEqual(saved.variables, {"WANDB_PROJECT": "llm"})
+
+        self.run_cmd("remove", "llm-eval", "--host", "gpu-box", "--var", "WANDB_PROJECT")
+        saved = projects.load_projects()["llm-eval"]
+        self.assertEqual((saved.hosts, saved.variables), ([], {}))
+        with self.assertRaises(SystemExit), redirect_stdout(StringIO()):
+            project_cmd.main(["add", "llm-eval", "--var", "missing-equals"])
+
+    def test_active_project_scopes_listings(self):
+        self.run_cmd("add", "vision", "--storage", "imgs", "--recipe", "train-vit")
+        self.run_cmd("use", "vision")
+        self.config = {"project": {"active": "vision"}}
+
+        out = StringIO()
+        with patch("trainsh.commands.storage.load_storages", return_value={"imgs": SimpleNamespace(is_default=False, type=SimpleNamespace(value="r2")), "text": SimpleNamespace(is_default=True, type=SimpleNamespace(value="s3"))}), redirect_stdout(out):
+            storage.cmd_list([])
+        self.assertIn("imgs", out.getvalue())
+        self.assertNotIn("text", out.getvalue())
+        self.assertIn("Project: vision; 1 other storages hidden", out.getvalue())
+
+        jobs = [
+            JobState(job_id="aaaa1111", recipe_path="/r/train-vit.pyrecipe", recipe_name="train-vit"),
+            JobState(job_id="bbbb2222", recipe_path="/r/sft.pyrecipe", recipe_name="sft"),
+        ]
+        out = StringIO()
+        with patch("trainsh.core.job_state.JobStateManager.list_all", return_value=jobs), redirect_stdout(out):
+            recipe_views.cmd_jobs([])
+        self.assertIn("aaaa1111", out.getvalue())
+        self.assertNotIn("bbbb2222", out.getvalue())
+
+    def test_recipe_runs_take_project_variables_as_defaults(self):
+        self.run_cmd("add", "vision", "--recipe", "demo", "--var", "MODEL=vit", "--var", "DATA=/data/imgs")
+        with patch("trainsh.commands.recipe_runtime.find_recipe", return_value="/tmp/demo.pyrecipe"), patch(
+            "trainsh.commands.recipe_runtime._maybe_auto_enter_tmux", return_value=False
+        ), patch("trainsh.commands.recipe_runtime._prompt_missing_variables", return_value={}), patch(
+            "trainsh.commands.recipe_runtime.run_recipe_via_dag", return_value=SimpleNamespace(success=True)
+        ) as mocked, redirect_stdout(StringIO()) as out:
+            cmd_run(["demo", "--set", "MODEL=tiny"])
+        self.assertEqual(mocked.call_args.kwargs["var_overrides"], {"MODEL": "tiny", "DATA": "/data/imgs"})
+        self.assertIn("Project: vision", out.getvalue())
+
+        self.run_cmd("add", "other", "--recipe", "demo")
+        self.assertIsNone(projects.project_for_recipe("/tmp/demo.pyrecipe"))
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -433,6 +433,9 @@ class ProviderBehaviorTests(unittest.TestCase):
                     delete=True,
                     exclude=[],
                     operation="sync",
+                    rsync_options=None,
+                    tar_stream=False,
+                    remote_exec=None,
                 )
 
         self.assertEqual(executor.ctx.variables["FROM_ENV"], "env-value")
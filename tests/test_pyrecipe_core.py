@@ -164,6 +164,26 @@ class PyrecipeCoreTests(unittest.TestCase):
         self.assertEqual(len(model.steps), len(recipe.steps))
         self.assertEqual(recipe.step_count(), len(recipe.steps))
 
+    def test_group_nests_members_behind_one_step(self):
+        recipe = Recipe("demo")
+        recipe.empty(id="start")
+        with recipe.group("prep", retries=2) as prep:
+            recipe.empty(id="fetch")
+            recipe.empty(id="build")
+        recipe.empty(id="train")
+
+        self.assertEqual(prep, "prep")
+        self.assertEqual([step.id for step in recipe.steps], ["start", "fetch", "build", "prep", "train"])
+        group = recipe.steps[3]
+        self.assertEqual((group.provider, group.operation, group.params), ("group", "run", {"steps": ["fetch", "build"]}))
+        self.assertEqual((group.depends_on, group.retries), (["start"], 2))
+        self.assertEqual([step.group for step in recipe.steps], ["", "prep", "prep", "", ""])
+        self.assertEqual(recipe.steps[-1].depends_on, ["prep"])
+
+        with self.assertRaises(PythonRecipeError):
+            with recipe.group("prep"):
+                pass
+
 
 if __name__ == "__main__":
     unittest.main()
@@ -0,0 +1,108 @@
+import sys
+import tempfile
+import unittest
+from pathlib import Path
+from types import SimpleNamespace
+
+from trainsh import Recipe
+from trainsh.pyrecipe.models import PythonRecipeError
+from trainsh.core.recipe_lint import lint_recipe
+from trainsh.services.python_run import interpreter_command, python_command, unpinned_requirements
+from tests.runtime_test_utils import isolated_executor
+
+
+class PythonRunTests(unittest.TestCase):
+    def test_interpreters(self):
+        self.assertEqual(interpreter_command(""), "python")
+        self.assertEqual(interpreter_command("system"), "python")
+        self.assertEqual(interpreter_command("uv"), "uv run --no-project python")
+        self.assertEqual(interpreter_command("conda:ml env"), "conda run --no-capture-output -n 'ml env' python")
+        self.assertEqual(interpreter_command("/opt/venv/bin/python"), "/opt/venv/bin/python")
+        with self.assertRaises(ValueError):
+            interpreter_command("conda:")
+
+    def test_commands(self):
+        self.assertEqual(python_command(command="print(1)"), "python -c 'print(1)'")
+        self.assertEqual(python_command(script="run me.py", interpreter="python3"), "python3 'run me.py'")
+        self.assertEqual(
+            python_command(code="print(1)", interpreter="uv", requirements=["rich==13.7.1", "req.txt"]),
+            "uv run --no-project --with rich==13.7.1 --with-requirements req.txt python - <<'PY'\nprint(1)\nPY",
+        )
+        venv = python_command(command="print(1)", interpreter="conda:ml", requirements=["numpy==1.26.4"])
+        self.assertIn('conda run --no-capture-output -n ml python -m venv "$venv"', venv)
+        self.assertIn("pip install -q --disable-pip-version-check numpy==1.26.4 >&2", venv)
+        self.assertTrue(venv.endswith(""""$venv/bin/python" -c 'print(1)'"""))
+        self.assertEqual(unpinned_requirements(["numpy==1.2", "rich", "torch>=2", "x[a]==1", "r.txt"]), ["rich", "torch>=2"])
+
+    def test_builder_and_lint(self):
+        recipe = Recipe("py")
+        recipe.python(
+            "print(1)",
+            interpreter="uv",
+            requirements=["rich", "numpy==1.26.4"],
+            exit_code_var="RC",
+            ok_exit_codes=[0, 3],
+            id="glue",
+        )
+        params = recipe.steps[0].params
+        self.assertEqual(params["interpreter"], "uv")
+        self.assertEqual(params["requirements"], ["rich", "numpy==1.26.4"])
+        self.assertEqual((params["exit_code_var"], params["ok_exit_codes"]), ("RC", [0, 3]))
+        with self.assertRaisesRegex(PythonRecipeError, "ok_exit_codes"):
+            recipe.python("print(1)", ok_exit_codes=["three"])
+        findings = lint_recipe(SimpleNamespace(steps=recipe.steps, variables={}))
+        self.assertEqual([item.message for item in findings if item.rule == "unpinned-requirement"], [
+            "requirement 'rich' has no exact version; pin it as NAME==VERSION",
+        ])
+
+    def test_typed_exit_and_captured_stdout(self):
+        recipe = Recipe("py")
+        recipe.python(
+            "import sys\nprint('partial')\nsys.exit(3)",
+            interpreter=sys.executable,
+            capture_var="OUT",
+            exit_code_var="RC",
+            ok_exit_codes=[0, 3],
+            id="glue",
+        )
+        with isolated_executor(recipe.to_recipe_model()) as (executor, _config_dir):
+            ok, message = executor._exec_provider_python(recipe.steps[0].params)
+            self.assertTrue(ok, message)
+            self.assertEqual(executor.ctx.variables["RC"], "3")
+            self.assertEqual(executor.ctx.variables["OUT"].strip(), "partial")
+
+            ok, _message = executor._exec_provider_python({"command": "raise SystemExit(4)", "interpreter": sys.executable, "exit_code_var": "RC"})
+            self.assertFalse(ok)
+            self.assertEqual(executor.ctx.variables["RC"], "4")
+
+            ok, _message = executor._exec_provider_shell({"command": "exit 3", "exit_code_var": "SHELL_RC", "ok_exit_codes": [3]})
+            self.assertFalse(ok)
+            self.assertNotIn("SHELL_RC", executor.ctx.variables)
+
+            ok, message = executor._exec_provider_python({"command": "print(1)", "interpreter": "conda:"})
+            self.assertFalse(ok)
+            self.assertIn("environment name", message)
+
+    def test_requirements_install_into_throwaway_venv(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            requirements = Path(tmpdir) / "requirements.txt"
+            requirements.write_text("", encoding="utf-8")
+            recipe = Recipe("py")
+            recipe.python(
+                "import sys\nprint(sys.prefix)",
+                interpreter=sys.executable,
+                requirements=[str(requirements)],
+                capture_var="PREFIX",
+                timeout=120,
+                id="venv",
+            )
+            with isolated_executor(recipe.to_recipe_model()) as (executor, _config_dir):
+                ok, message = executor._exec_provider_python(recipe.steps[0].params)
+                self.assertTrue(ok, message)
+                prefix = executor.ctx.variables["PREFIX"].strip()
+        self.assertNotEqual(prefix, sys.prefix)
+        self.assertFalse(Path(prefix).exists())
+
+
+if __name__ == "__main__":
+    unittest.main()
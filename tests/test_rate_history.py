@@ -0,0 +1,94 @@
+import json
+import tempfile
+import unittest
+from contextlib import redirect_stdout
+from datetime import UTC, date, datetime
+from io import StringIO
+from pathlib import Path
+from unittest.mock import patch
+
+from trainsh.commands import pricing
+from trainsh.services.cost_report import pricing_report, vast_cost_entries
+from trainsh.services.pricing import ExchangeRates
+from trainsh.services.rate_history import RateHistory
+from trainsh.services.vast_billing import BillingCache
+
+
+def rates(jpy, when):
+    return ExchangeRates(rates={"USD": 1.0, "JPY": jpy}, updated_at=when)
+
+
+class RateHistoryTests(unittest.TestCase):
+    def test_snapshot_selection_fetch_fallback_and_dedupe(self):
+        fetched = []
+
+        def fetch_day(day):
+            fetched.append(day)
+            return rates(150.0, f"{day.isoformat()}T00:00:00+00:00") if day.month == 10 else None
+
+        with tempfile.TemporaryDirectory() as tmpdir:
+            path = Path(tmpdir) / "rates.jsonl"
+            history = RateHistory(path, fetch_day=fetch_day)
+            history.record(rates(140.0, "2026-09-01T08:00:00+00:00"))
+            history.record(rates(140.0, "2026-09-01T08:00:00+00:00"))
+            history.record(rates(999.0, "not a timestamp"))
+            self.assertEqual(len(path.read_text().splitlines()), 1)
+
+            self.assertEqual(history.rates_at(datetime(2026, 9, 5, tzinfo=UTC)).get_rate("JPY"), 140.0)
+            self.assertEqual(fetched, [])
+
+            self.assertEqual(history.rates_at(datetime(2026, 10, 2)).get_rate("JPY"), 150.0)
+            self.assertEqual(fetched, [date(2026, 10, 2)])
+            self.assertEqual(history.rates_at(datetime(2026, 10, 4)).get_rate("JPY"), 150.0)
+            self.assertEqual(len(fetched), 1)
+
+            # Offline and nothing recent: fall back to the closest earlier snapshot.
+            self.assertEqual(history.rates_at(datetime(2026, 11, 30)).get_rate("JPY"), 150.0)
+            self.assertEqual(RateHistory(path, fetch_day=False).rates_at(datetime(2026, 8, 1)).get_rate("JPY"), 140.0)
+            lines = [json.loads(line) for line in path.read_text().splitlines()]
+            self.assertEqual([line["rates"]["JPY"] for line in lines], [140.0, 150.0])
+
+    def test_report_converts_each_day_at_its_own_rate(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            history = RateHistory(Path(tmpdir) / "rates.jsonl", fetch_day=False)
+            history.record(rates(100.0, "2026-09-01T00:00:00+00:00"))
+            history.record(rates(200.0, "2026-09-02T00:00:00+00:00"))
+            cache = BillingCache(Path(tmpdir))
+            cache.store("2026-09", [
+                {"instance_id": "7", "day": "2026-09-01", "amount_usd": 1.0, "label": "sweep"},
+                {"instance_id": "7", "day": "2026-09-02", "amount_usd": 1.0, "label": "sweep"},
+                {"instance_id": "8", "day": "2026-08-31", "amount_usd": 5.0, "label": ""},
+            ])
+            events = [
+                {"instance_id": "7", "ts": "2026-09-02T00:00:00", "status": "running", "dph_total": 1.0, "label": "sweep"},
+                {"instance_id": "9", "ts": "2026-09-02T10:00:00", "status": "running", "dph_total": 0.5},
+                {"instance_id": "9", "ts": "2026-09-02T12:00:00", "status": "stopped", "dph_total": 0.5},
+            ]
+            start, end = datetime(2026, 9, 1), datetime(2026, 9, 3)
+            entries = vast_cost_entries(start, end, cache=cache, events=events)
+            rows, totals = pricing_report(start, end, "jpy", entries=entries, history=history)
+
+        self.assertEqual([(r.host, r.usd, r.amount, r.estimated) for r in rows], [
+            ("sweep", 2.0, 300.0, False),
+            ("vast:9", 1.0, 200.0, True),
+        ])
+        self.assertEqual(totals, {"vast": 500.0})
+
+    def test_report_command_prints_totals(self):
+        row_list = pricing_report(
+            datetime(2026, 9, 1),
+            datetime(2026, 9, 2),
+            "USD",
+            entries=[{"provider": "vast", "host": "sweep", "day": "2026-09-01", "amount_usd": 2.5, "source": "billed"}],
+            history=RateHistory(Path("/nonexistent/rates.jsonl"), fetch_day=False),
+        )
+        out = StringIO()
+        with patch("trainsh.services.cost_report.pricing_report", return_value=row_list) as report, redirect_stdout(out):
+            pricing.main(["report", "--month", "2026-09", "--currency", "usd"])
+        self.assertEqual(report.call_args.args[2], "USD")
+        self.assertIn("vast total", out.getvalue())
+        self.assertIn("sweep", out.getvalue())
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,66 @@
+import tempfile
+import unittest
+from contextlib import redirect_stdout
+from io import StringIO
+from pathlib import Path
+
+from trainsh.commands.recipe_graph import cmd_graph
+from trainsh.core.recipe_graph import graph_nodes, render_recipe_graph
+from trainsh.pyrecipe import load_python_recipe
+
+RECIPE = '''from trainsh import Recipe
+
+recipe = Recipe("demo")
+recipe.shell("nvidia-smi", id="probe", capture_var="GPU")
+recipe.branch("var:GPU==H100", variable="BIG", id="size", depends_on=["probe"])
+recipe.shell('python train.py --tag "big"', id="train", depends_on=["size"], step_options={"on_failure_run": "alert"})
+recipe.notice("train failed", id="alert", depends_on=["probe"])
+recipe.shell("rm -rf /tmp/x", id="cleanup", depends_on=["train"], step_options={"trigger_rule": "all_done", "always_run": True})
+'''
+
+
+class RecipeGraphTests(unittest.TestCase):
+    def setUp(self):
+        self.tmpdir = tempfile.TemporaryDirectory()
+        self.addCleanup(self.tmpdir.cleanup)
+        self.path = Path(self.tmpdir.name) / "demo.pyrecipe"
+        self.path.write_text(RECIPE, encoding="utf-8")
+        self.recipe = load_python_recipe(str(self.path))
+
+    def test_nodes_carry_operation_condition_and_edges(self):
+        nodes = {node.id: node for node in graph_nodes(self.recipe)}
+        self.assertEqual((nodes["size"].operation, nodes["size"].condition), ("util.branch", True))
+        self.assertEqual(nodes["size"].detail, "var:GPU==H100")
+        self.assertEqual(nodes["cleanup"].label_lines[-1], "(always runs)")
+        self.assertEqual(nodes["train"].on_failure_run, "alert")
+
+    def test_mermaid_and_dot_output(self):
+        mermaid = render_recipe_graph(self.recipe, "mermaid")
+        self.assertTrue(mermaid.startswith("flowchart TD\n"))
+        self.assertIn('    s_size{"size<br/>util.branch<br/>var:GPU==H100"}', mermaid)
+        self.assertIn("#quot;big#quot;", mermaid)
+        self.assertIn("    s_train -->|all_done| s_cleanup", mermaid)
+        self.assertIn("    s_train -.->|on failure| s_alert", mermaid)
+
+        dot = render_recipe_graph(self.recipe, "dot")
+        self.assertIn('"size" [label="size\\nutil.branch\\nvar:GPU==H100", shape=diamond];', dot)
+        self.assertIn('--tag \\"big\\"', dot)
+        self.assertIn('"train" -> "cleanup" [label="all_done"];', dot)
+        self.assertTrue(dot.rstrip().endswith("}"))
+        with self.assertRaises(ValueError):
+            render_recipe_graph(self.recipe, "svg")
+
+    def test_cli_writes_file_and_rejects_unknown_format(self):
+        target = Path(self.tmpdir.name) / "demo.dot"
+        with redirect_stdout(StringIO()):
+            cmd_graph([str(self.path), "--format", "dot", "-o", str(target)])
+        self.assertTrue(target.read_text().startswith('digraph "demo" {'))
+
+        out = StringIO()
+        with redirect_stdout(out), self.assertRaises(SystemExit):
+            cmd_graph([str(self.path), "--format", "svg"])
+        self.assertIn("Unknown format: svg", out.getvalue())
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,105 @@
+import json
+import tempfile
+import unittest
+from contextlib import redirect_stdout
+from io import StringIO
+from pathlib import Path
+from types import SimpleNamespace
+from unittest.mock import patch
+
+from trainsh.commands.recipe_lint import cmd_lint
+from trainsh.core.recipe_lint import lint_file, lint_recipe, unquoted_variables
+from trainsh.pyrecipe import load_python_recipe
+from trainsh.pyrecipe.models import ProviderStep
+
+RECIPE = '''from trainsh import Recipe
+
+recipe = Recipe("demo")
+recipe.variables.update({"HF_TOKEN": "hf_abc123", "GH_TOKEN": "${secret:GH}"})
+recipe.shell("python train.py --out $OUT", id="train", timeout="2h")
+recipe.shell("echo \\"$OUT\\" && X=$OUT", id="quoted", timeout=60)
+recipe.wait_file("/tmp/ready", id="wait_default")
+recipe.provider("util", "wait_for_file", params={"path": "/tmp/done"}, id="wait")
+recipe.http_get("https://example.com", id="fetch")
+recipe.http_get("https://example.com", id="fetch_retry", step_options={"retries": 3})
+recipe.storage_delete("r2", path="/ckpt", id="wipe", trash=False)
+recipe.storage_upload("r2", source="/ckpt", destination="/backup", id="backup", depends_on=["fetch_retry"], step_options={"retries": 2})
+recipe.storage_delete("r2", path="/ckpt", id="wipe_after_backup", depends_on=["backup"], step_options={"retries": 2})
+'''
+
+
+def _rules(findings, step):
+    return {item.rule for item in findings if item.step == step}
+
+
+class RecipeLintTests(unittest.TestCase):
+    def setUp(self):
+        self.tmpdir = tempfile.TemporaryDirectory()
+        self.addCleanup(self.tmpdir.cleanup)
+        self.path = Path(self.tmpdir.name) / "demo.pyrecipe"
+        self.path.write_text(RECIPE, encoding="utf-8")
+        self.findings = lint_recipe(load_python_recipe(str(self.path)))
+
+    def test_timeouts_and_retries(self):
+        self.assertNotIn("no-timeout", _rules(self.findings, "train"))
+        wait = [item for item in self.findings if item.step == "wait" and item.rule == "no-timeout"]
+        self.assertEqual([item.severity for item in wait], ["warning"])
+        self.assertNotIn("no-timeout", _rules(self.findings, "wait_default"))
+        self.assertIn("network-no-retry", _rules(self.findings, "fetch"))
+        self.assertNotIn("network-no-retry", _rules(self.findings, "fetch_retry"))
+
+    def test_unquoted_variables(self):
+        self.assertIn("unquoted-variable", _rules(self.findings, "train"))
+        self.assertNotIn("unquoted-variable", _rules(self.findings, "quoted"))
+        self.assertEqual(unquoted_variables("cp $SRC '$LIT' \"$OK\" ${DST} $(date) ${TRAINSH_SCRIPT}"), ["$SRC", "${DST}"])
+
+    def test_plain_secrets(self):
+        secrets = [item for item in self.findings if item.rule == "plain-secret"]
+        self.assertEqual(len(secrets), 1)
+        self.assertIn("HF_TOKEN", secrets[0].message)
+        self.assertEqual(secrets[0].severity, "error")
+        # Errors sort first.
+        self.assertEqual(self.findings[0].severity, "error")
+
+    def test_destructive_needs_backup_upstream(self):
+        self.assertIn("destructive-without-backup", _rules(self.findings, "wipe"))
+        self.assertNotIn("destructive-without-backup", _rules(self.findings, "wipe_after_backup"))
+
+    def test_unreachable_steps(self):
+        steps = [
+            ProviderStep("util", "empty", {}, "root"),
+            ProviderStep("util", "empty", {}, "a", depends_on=["b"]),
+            ProviderStep("util", "empty", {}, "b", depends_on=["a"]),
+            ProviderStep("util", "empty", {}, "handler", depends_on=["a"]),
+            ProviderStep("util", "empty", {}, "after", depends_on=["root"], on_failure_run="cleanup"),
+            ProviderStep("util", "empty", {}, "cleanup", depends_on=["a"]),
+        ]
+        findings = lint_recipe(SimpleNamespace(steps=steps, variables={}))
+        unreachable = sorted(item.step for item in findings if item.rule == "unreachable")
+        self.assertEqual(unreachable, ["a", "b", "handler"])
+
+    def test_broken_file_is_one_error(self):
+        broken = Path(self.tmpdir.name) / "broken.pyrecipe"
+        broken.write_text("raise RuntimeError('nope')\n", encoding="utf-8")
+        findings = lint_file(str(broken))
+        self.assertEqual([(item.severity, item.rule) for item in findings], [("error", "invalid")])
+
+    def test_command_exit_codes(self):
+        with patch("trainsh.commands.recipe.find_recipe", return_value=str(self.path)):
+            out = StringIO()
+            with redirect_stdout(out), self.assertRaises(SystemExit):
+                cmd_lint(["demo", "--json"])
+        report = json.loads(out.getvalue())
+        self.assertIn("plain-secret", {item["rule"] for item in report["demo"]})
+
+        clean = Path(self.tmpdir.name) / "clean.pyrecipe"
+        clean.write_text('from trainsh import Recipe\n\nrecipe = Recipe("c")\nrecipe.shell("ls", timeout=10)\n', encoding="utf-8")
+        with patch("trainsh.commands.recipe.find_recipe", return_value=str(clean)):
+            out = StringIO()
+            with redirect_stdout(out):
+                cmd_lint(["clean", "--strict"])
+        self.assertEqual(out.getvalue().strip(), "clean: ok")
+
+
+if __name__ == "__main__":
+    unittest.main()
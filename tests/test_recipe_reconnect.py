@@ -0,0 +1,152 @@
+import fcntl
+import io
+import os
+import tempfile
+import threading
+import unittest
+from contextlib import redirect_stdout
+from pathlib import Path
+from types import SimpleNamespace
+from unittest.mock import patch
+
+from trainsh.commands import recipe_reconnect
+from trainsh.core.job_state import JobState, JobStateManager
+
+
+def _job(job_id: str, **overrides) -> JobState:
+    data = dict(
+        job_id=job_id,
+        recipe_path="/tmp/demo.pyrecipe",
+        recipe_name="demo",
+        current_step=2,
+        total_steps=5,
+        status="running",
+        pid=4242,
+        next_window_index=1,
+    )
+    data.update(overrides)
+    return JobState(**data)
+
+
+class RecipeReconnectTests(unittest.TestCase):
+    def run_cmd(self, args, orphaned):
+        manager = SimpleNamespace(list_orphaned=lambda **_: list(orphaned))
+        out = io.StringIO()
+        with patch("trainsh.core.job_state.JobStateManager", return_value=manager), patch(
+            "trainsh.commands.recipe_reconnect.run_recipe_via_dag",
+            return_value=SimpleNamespace(success=True),
+        ) as run, redirect_stdout(out):
+            try:
+                recipe_reconnect.cmd_reconnect(args)
+                code = 0
+            except SystemExit as exc:
+                code = exc.code
+        return code, out.getvalue(), run
+
+    def test_reconnects_single_orphan_with_saved_job_id(self):
+        job = _job("job-aaaa1111", inflight_steps={"3": {"remote_session": "s", "signal": "x"}})
+        code, out, run = self.run_cmd([], [job])
+        self.assertEqual(code, 0)
+        self.assertIn("Re-attaching to in-flight step(s): 3", out)
+        run.assert_called_once_with("/tmp/demo.pyrecipe", resume=True, job_id="job-aaaa1111", initial_session_index=1)
+
+    def test_lists_when_ambiguous_or_dry_run(self):
+        jobs = [_job("job-aaaa1111"), _job("job-bbbb2222")]
+        code, out, run = self.run_cmd([], jobs)
+        self.assertEqual(code, 1)
+        self.assertIn("Pass a job id", out)
+        run.assert_not_called()
+
+        code, out, run = self.run_cmd(["--dry-run"], jobs[:1])
+        self.assertEqual(code, 0)
+        self.assertIn("job-aaaa", out)
+        run.assert_not_called()
+
+        code, out, run = self.run_cmd(["job-bbbb"], jobs)
+        self.assertEqual(code, 0)
+        self.assertEqual(run.call_args.kwargs["job_id"], "job-bbbb2222")
+
+    def test_no_orphans_and_unknown_id(self):
+        self.assertIn("No orphaned recipe jobs", self.run_cmd([], [])[1])
+        code, out, _ = self.run_cmd(["nope"], [])
+        self.assertEqual(code, 1)
+        self.assertIn("No orphaned job matches", out)
+
+
+class AutoReconnectTests(unittest.TestCase):
+    def test_startup_scan_claims_orphans_and_reconnects_them_detached(self):
+        job = _job("job-aaaa1111")
+        saved = []
+        manager = SimpleNamespace(list_orphaned=lambda **_: [job], load=lambda job_id: job, save=saved.append)
+        with tempfile.TemporaryDirectory() as tmpdir, patch(
+            "trainsh.commands.recipe_reconnect.subprocess.Popen", return_value=SimpleNamespace(pid=777)
+        ) as popen, redirect_stdout(io.StringIO()) as out:
+            started = recipe_reconnect.reconnect_in_background(manager, state_dir=tmpdir)
+            self.assertTrue((Path(tmpdir) / "reconnect").is_dir())
+        self.assertEqual(started, ["job-aaaa1111"])
+        self.assertEqual(popen.call_args.args[0][-3:], ["recipe", "reconnect", "job-aaaa1111"])
+        self.assertTrue(popen.call_args.kwargs["start_new_session"])
+        self.assertEqual((saved, job.pid), ([job], 777))
+        self.assertIn("Reconnecting orphaned job job-aaaa", out.getvalue())
+
+    def test_startup_scan_skips_commands_that_handle_orphans(self):
+        with patch("trainsh.commands.recipe_reconnect.reconnect_in_background", return_value=[]) as scan, patch(
+            "trainsh.config.get_config_value", return_value=True
+        ):
+            recipe_reconnect.auto_reconnect(["train", "recipe", "cancel", "job"])
+            recipe_reconnect.auto_reconnect(["train", "--help"])
+            recipe_reconnect.auto_reconnect(["train", "recipe", "status"])
+            recipe_reconnect.auto_reconnect(["train", "recipe", "schedule", "list"])
+            recipe_reconnect.auto_reconnect(["train", "config", "show"])
+            recipe_reconnect.auto_reconnect(["train", "run", "--help"])
+            scan.assert_not_called()
+            recipe_reconnect.auto_reconnect(["train", "run", "demo"])
+            recipe_reconnect.auto_reconnect(["train", "recipe", "schedule", "run"])
+            self.assertEqual(scan.call_count, 2)
+        with patch("trainsh.commands.recipe_reconnect.reconnect_in_background") as scan, patch(
+            "trainsh.config.get_config_value", return_value=False
+        ):
+            recipe_reconnect.auto_reconnect(["train", "exec", "demo"])
+        scan.assert_not_called()
+
+    def test_concurrent_startups_spawn_one_reconnect_per_job(self):
+        with tempfile.TemporaryDirectory() as tmpdir, patch(
+            "trainsh.commands.recipe_reconnect.subprocess.Popen", return_value=SimpleNamespace(pid=os.getpid())
+        ) as popen, redirect_stdout(io.StringIO()):
+            manager = JobStateManager(tmpdir)
+            manager.save(_job("job-dddd4444", pid=999999999))
+            # Both startups listed the job as orphaned before either claimed it.
+            stale = manager.list_orphaned()
+            first = SimpleNamespace(list_orphaned=lambda **_: stale, load=manager.load, save=manager.save)
+            self.assertEqual(recipe_reconnect.reconnect_in_background(first, state_dir=tmpdir), ["job-dddd4444"])
+            self.assertEqual(recipe_reconnect.reconnect_in_background(first, state_dir=tmpdir), [])
+            popen.assert_called_once()
+
+            # Another startup holding the claim lock makes this one wait, then re-check.
+            manager.save(_job("job-dddd4444", pid=999999999))
+            lock_path = Path(tmpdir) / "reconnect" / recipe_reconnect.CLAIM_LOCK
+            started = []
+            with open(lock_path, "a") as lock:
+                fcntl.flock(lock, fcntl.LOCK_EX)
+                waiter = threading.Thread(
+                    target=lambda: started.extend(recipe_reconnect.reconnect_in_background(manager, state_dir=tmpdir))
+                )
+                waiter.start()
+                waiter.join(0.2)
+                self.assertTrue(waiter.is_alive())
+                manager.save(_job("job-dddd4444", pid=os.getpid()))  # claimed meanwhile
+            waiter.join(5)
+            self.assertEqual(started, [])
+            popen.assert_called_once()
+            self.assertEqual(sorted(path.name for path in (Path(tmpdir) / "reconnect").iterdir()), [".claim.lock", "job-dddd4444.log"])
+
+    def test_claimed_job_counts_as_orphaned_for_its_reconnect_process(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            manager = JobStateManager(tmpdir)
+            manager.save(_job("job-cccc3333", pid=os.getpid()))
+            self.assertEqual(manager.list_orphaned(), [])
+            self.assertEqual([job.job_id for job in manager.list_orphaned(claimed_by=os.getpid())], ["job-cccc3333"])
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,85 @@
+import json
+import tempfile
+import unittest
+from contextlib import redirect_stdout
+from io import StringIO
+from pathlib import Path
+from unittest.mock import patch
+
+from trainsh.commands.recipe_schema_cmd import cmd_schema
+from trainsh.core.recipe_schema import recipe_schema, validate_document, validate_text
+
+DOCUMENT = """name: sweep
+variables:
+  LR: 0.001
+steps:
+  - id: train
+    provider: shell
+    operation: run
+    params: {command: python train.py}
+    retries: -1
+    trigger_rule: sometimes
+  - type: execute
+    commands: nvidia-smi
+    bogus: 1
+  - retries: 2
+extra: true
+"""
+
+
+class RecipeSchemaTests(unittest.TestCase):
+    def test_schema_is_generated_from_step_dataclasses(self):
+        schema = recipe_schema()
+        options = schema["$defs"]["step_options"]["properties"]
+        self.assertEqual(options["retry_on_exit_codes"]["type"], "array")
+        self.assertEqual(options["continue_on_failure"]["type"], "boolean")
+        self.assertEqual(options["retry_delay"]["type"], ["integer", "string"])
+        model = schema["$defs"]["model_step"]["properties"]
+        self.assertEqual(model["type"]["enum"], ["control", "execute", "transfer", "wait"])
+        self.assertNotIn("line_num", model)
+        self.assertIn("retries", schema["$defs"]["provider_step"]["properties"])
+
+    def test_issues_point_at_key_paths_and_lines(self):
+        issues = [str(issue) for issue in validate_text(DOCUMENT)]
+        self.assertEqual(
+            issues,
+            [
+                "line 9: steps[0].retries: must be >= 0",
+                "line 10: steps[0].trigger_rule: 'sometimes' is not one of all_success, all_done, all_failed, one_success, one_failed, none_failed, none_failed_or_skipped",
+                "line 13: steps[1].bogus: unknown key",
+                "line 14: steps[2]: needs provider+operation or type",
+                "line 15: extra: unknown key",
+            ],
+        )
+        self.assertEqual(validate_text("name: [\n")[0].path, "")
+        self.assertEqual(
+            [str(issue) for issue in validate_document({"name": "x"})],
+            ["<root>: missing required key 'steps'"],
+        )
+        self.assertEqual(validate_document({"name": "x", "steps": [{"type": "wait", "target": "h", "timeout": "5m"}]}), [])
+
+    def test_cli_writes_schema_and_checks_files(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            root = Path(tmpdir)
+            target = root / "schemas" / "recipe.schema.json"
+            out = StringIO()
+            with patch("trainsh.core.recipe_schema.RECIPE_SCHEMA_FILE", target), redirect_stdout(out):
+                cmd_schema([])
+            self.assertEqual(json.loads(target.read_text())["title"], "tmux-trainsh recipe")
+
+            good = root / "good.yaml"
+            good.write_text("name: ok\nsteps:\n  - {provider: shell, operation: run}\n", encoding="utf-8")
+            bad = root / "bad.yaml"
+            bad.write_text(DOCUMENT, encoding="utf-8")
+            out = StringIO()
+            with redirect_stdout(out):
+                cmd_schema(["--check", str(good)])
+            self.assertEqual(out.getvalue(), f"{good}: ok\n")
+            out = StringIO()
+            with redirect_stdout(out), self.assertRaises(SystemExit):
+                cmd_schema(["--check", str(good), str(bad)])
+            self.assertIn(f"{bad}:line 15: extra: unknown key", out.getvalue())
+
+
+if __name__ == "__main__":
+    unittest.main()
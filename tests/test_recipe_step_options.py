@@ -0,0 +1,306 @@
+import tempfile
+import textwrap
+import unittest
+from contextlib import redirect_stdout
+from io import StringIO
+from pathlib import Path
+from types import SimpleNamespace
+from unittest.mock import patch
+
+from trainsh.config import get_default_config
+from trainsh.core.executor_main import DSLExecutor, run_recipe
+from trainsh.core.recipe_models import StepType
+
+
+class RecipeStepOptionTests(unittest.TestCase):
+    def test_run_recipe_skips_steps_whose_done_check_passes(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            root = Path(tmpdir)
+            recipe_path = root / "done_check.pyrecipe"
+            config_dir = root / "config"
+            marker = root / "dataset"
+            log_path = root / "log.txt"
+            marker.mkdir()
+
+            recipe_path.write_text(
+                textwrap.dedent(
+                    f"""\
+                    from trainsh import Recipe
+
+                    recipe = Recipe("done-check")
+                    fetch = recipe.shell(
+                        "echo fetch >> {log_path}",
+                        id="fetch",
+                        step_options={{"done_check": "test -d {marker}"}},
+                    )
+                    recipe.shell("echo train >> {log_path}", id="train", depends_on=[fetch])
+                    """
+                ),
+                encoding="utf-8",
+            )
+
+            with patch("trainsh.core.executor_main.load_config", return_value={"tmux": {}}), patch(
+                "trainsh.core.executor_main.CONFIG_DIR", config_dir
+            ), patch("trainsh.core.executor_main.RUNTIME_STATE_DIR", config_dir / "runtime"), patch(
+                "trainsh.runtime.CONFIG_DIR", config_dir / "runtime"
+            ), redirect_stdout(StringIO()):
+                ok = run_recipe(str(recipe_path), job_id="jobdone1")
+
+            self.assertTrue(ok)
+            self.assertEqual(log_path.read_text(encoding="utf-8"), "train\n")
+            from trainsh.core.runtime_store import RuntimeStore
+
+            tasks = {task["task_id"]: task for task in RuntimeStore(config_dir / "runtime").list_tasks(run_id="jobdone1")}
+            self.assertEqual(tasks["fetch"]["state"], "skipped")
+            self.assertEqual(tasks["train"]["state"], "success")
+
+    def _run_inline_recipe(self, root: Path, body: str, *, config: dict, executor_kwargs=None):
+        recipe_path = root / "inline.pyrecipe"
+        config_dir = root / "config"
+        recipe_path.write_text(
+            "from trainsh import Recipe\n\nrecipe = Recipe(\"inline\")\n" + textwrap.dedent(body),
+            encoding="utf-8",
+        )
+        with patch("trainsh.core.executor_main.load_config", return_value=config), patch(
+            "trainsh.core.executor_main.CONFIG_DIR", config_dir
+        ), patch("trainsh.core.executor_main.RUNTIME_STATE_DIR", config_dir / "runtime"), patch(
+            "trainsh.runtime.CONFIG_DIR", config_dir / "runtime"
+        ), redirect_stdout(StringIO()):
+            ok = run_recipe(str(recipe_path), job_id="jobtime1", executor_kwargs=executor_kwargs)
+        from trainsh.core.runtime_store import RuntimeStore
+
+        tasks = {task["task_id"]: task for task in RuntimeStore(config_dir / "runtime").list_tasks(run_id="jobtime1")}
+        return ok, tasks
+
+    def test_run_recipe_applies_default_command_timeout(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            ok, tasks = self._run_inline_recipe(
+                Path(tmpdir),
+                """\
+                recipe.shell("sleep 2", id="bounded", timeout=5)
+                recipe.shell("sleep 3", id="slow")
+                """,
+                config={"tmux": {}, "timeouts": {"command_secs": 1}},
+            )
+
+            self.assertFalse(ok)
+            self.assertEqual(tasks["slow"]["state"], "failed")
+            self.assertEqual(tasks["bounded"]["state"], "success")
+
+    def test_step_timeout_beats_the_class_default(self):
+        executor = DSLExecutor.__new__(DSLExecutor)
+        executor.default_timeouts = {"command_secs": 300, "session_secs": 0, "http_secs": 60}
+        own = SimpleNamespace(type=StepType.CONTROL, provider="shell", operation="run", timeout=3600, params={"command": "train"})
+        self.assertEqual(executor._default_step_timeout(own), 0)
+        bare = SimpleNamespace(type=StepType.CONTROL, provider="shell", operation="run", timeout=0, params={"timeout": 0})
+        self.assertEqual(executor._default_step_timeout(bare), 300)
+        session = SimpleNamespace(type=StepType.EXECUTE, timeout=0, params={})
+        self.assertEqual(executor._default_step_timeout(session), 0)
+        defaults = get_default_config()["timeouts"]
+        self.assertEqual((defaults["command_secs"], defaults["http_secs"], defaults["session_secs"]), (3600, 300, 0))
+
+    def test_run_recipe_stops_scheduling_after_max_runtime(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            ok, tasks = self._run_inline_recipe(
+                Path(tmpdir),
+                """\
+                first = recipe.shell("sleep 2", id="first")
+                recipe.shell("echo never", id="second", depends_on=[first])
+                """,
+                config={"tmux": {}},
+                executor_kwargs={"max_runtime": "1s"},
+            )
+
+            self.assertFalse(ok)
+            self.assertEqual(tasks["first"]["state"], "failed")
+            self.assertEqual(tasks["second"]["state"], "removed")
+
+    def test_run_recipe_retries_only_matching_failures(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            root = Path(tmpdir)
+            log_path = root / "log.txt"
+            ok, tasks = self._run_inline_recipe(
+                root,
+                f"""\
+                recipe.shell(
+                    "echo det >> {log_path}; exit 3",
+                    id="deterministic",
+                    step_options={{"retries": 2, "retry_on_exit_codes": [75], "continue_on_failure": True}},
+                )
+                recipe.shell(
+                    "echo oom >> {log_path}; echo 'CUDA out of memory'; exit 1",
+                    id="flaky",
+                    step_options={{"retries": 2, "retry_on_output_regex": "out of memory", "trigger_rule": "all_done"}},
+                )
+                """,
+                config={"tmux": {}},
+            )
+
+            self.assertFalse(ok)
+            lines = log_path.read_text(encoding="utf-8").splitlines()
+            self.assertEqual(lines.count("det"), 1)
+            self.assertEqual(lines.count("oom"), 3)
+            self.assertEqual(tasks["deterministic"]["state"], "failed")
+
+    def test_run_recipe_runs_on_failure_hook_only_when_needed(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            root = Path(tmpdir)
+            log_path = root / "log.txt"
+            body = f"""\
+                recipe.shell("echo train >> {log_path}; exit {{code}}", id="train", step_options={{{{"on_failure_run": "notify"}}}})
+                recipe.shell("echo notify >> {log_path}", id="notify")
+                recipe.shell("echo eval >> {log_path}", id="eval")
+                """
+
+            ok, tasks = self._run_inline_recipe(root, body.format(code=0), config={"tmux": {}})
+            self.assertTrue(ok)
+            self.assertEqual(log_path.read_text(encoding="utf-8"), "train\neval\n")
+
+            log_path.unlink()
+            ok, tasks = self._run_inline_recipe(root, body.format(code=1), config={"tmux": {}})
+            self.assertFalse(ok)
+            self.assertEqual(log_path.read_text(encoding="utf-8"), "train\nnotify\n")
+            self.assertEqual(tasks["notify"]["state"], "success")
+
+    def test_run_recipe_runs_cleanup_steps_last_even_after_failure(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            root = Path(tmpdir)
+            log_path = root / "log.txt"
+            body = f"""\
+                recipe.shell("echo train >> {log_path}; exit {{code}}", id="train")
+                with recipe.cleanup():
+                    recipe.shell("echo stop >> {log_path}", id="stop")
+                recipe.shell("echo eval >> {log_path}", id="eval")
+                recipe.shell("echo upload >> {log_path}", id="upload", step_options={{{{"always_run": True}}}})
+                """
+
+            ok, tasks = self._run_inline_recipe(root, body.format(code=0), config={"tmux": {}})
+            self.assertTrue(ok)
+            self.assertEqual(log_path.read_text(encoding="utf-8"), "train\neval\nstop\nupload\n")
+
+            log_path.unlink()
+            ok, tasks = self._run_inline_recipe(root, body.format(code=1), config={"tmux": {}})
+            self.assertFalse(ok)
+            self.assertEqual(log_path.read_text(encoding="utf-8"), "train\nstop\nupload\n")
+            self.assertEqual(tasks["stop"]["state"], "success")
+            self.assertEqual(tasks["eval"]["state"], "skipped")
+
+    def test_run_recipe_runs_child_recipe_linked_to_parent(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            root = Path(tmpdir)
+            log_path = root / "log.txt"
+            (root / "child.pyrecipe").write_text(
+                textwrap.dedent(
+                    f"""\
+                    from trainsh import Recipe
+
+                    recipe = Recipe("child")
+                    recipe.shell("echo child ${{GREETING}} >> {log_path}", id="say")
+                    """
+                ),
+                encoding="utf-8",
+            )
+            (root / "loop.pyrecipe").write_text(
+                'from trainsh import Recipe\n\nrecipe = Recipe("loop")\nrecipe.run_recipe("inline")\n',
+                encoding="utf-8",
+            )
+
+            ok, tasks = self._run_inline_recipe(
+                root,
+                f"""\
+                recipe.variables["NAME"] = "world"
+                recipe.run_recipe("child", variables={{"GREETING": "hi ${{NAME}}"}}, capture_var="CHILD_JOB", id="sub")
+                recipe.shell("echo parent ${{CHILD_JOB}} >> {log_path}", id="after")
+                """,
+                config={"tmux": {}},
+            )
+
+            self.assertTrue(ok)
+            lines = log_path.read_text(encoding="utf-8").splitlines()
+            self.assertEqual(lines[0], "child hi world")
+            child_job = lines[1].split()[1]
+            from trainsh.core.runtime_store import RuntimeStore
+
+            store = RuntimeStore(root / "config" / "runtime")
+            child_run = store.get_run(child_job)
+            self.assertEqual((child_run["parent_run_id"], child_run["parent_step_id"]), ("jobtime1", "sub"))
+            ends = [event for event in store.list_events("jobtime1") if event.get("event") == "subrecipe_end"]
+            self.assertEqual(ends[0]["payload"]["steps"], {"success": 1})
+
+            ok, tasks = self._run_inline_recipe(root, 'recipe.run_recipe("loop", id="sub")\n', config={"tmux": {}})
+            self.assertFalse(ok)
+            self.assertEqual(tasks["sub"]["state"], "failed")
+
+    def test_run_recipe_runs_groups_as_retryable_units(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            root = Path(tmpdir)
+            log_path = root / "log.txt"
+            flag = root / "flag"
+            ok, tasks = self._run_inline_recipe(
+                root,
+                f"""\
+                recipe.shell("echo start >> {log_path}", id="start")
+                with recipe.group("prep", retries=1):
+                    recipe.shell("echo fetch >> {log_path}", id="fetch")
+                    recipe.shell("echo build >> {log_path}; test -f {flag} || {{ touch {flag}; exit 1; }}", id="build")
+                with recipe.group("extras", continue_on_failure=True):
+                    recipe.shell("exit 3", id="lint")
+                    recipe.shell("echo docs >> {log_path}", id="docs", depends_on="start")
+                    recipe.shell("echo never >> {log_path}", id="after_lint", depends_on="lint")
+                recipe.shell("echo train >> {log_path}", id="train", step_options={{"trigger_rule": "all_done"}})
+                """,
+                config={"tmux": {}},
+            )
+
+            self.assertTrue(ok)
+            self.assertEqual(
+                log_path.read_text(encoding="utf-8"),
+                "start\nfetch\nbuild\nfetch\nbuild\ndocs\ntrain\n",
+            )
+            self.assertEqual(tasks["prep"]["state"], "success")
+            self.assertEqual(tasks["extras"]["state"], "failed")
+            self.assertEqual(tasks["after_lint"]["state"], "skipped")
+            self.assertEqual(tasks["train"]["state"], "success")
+            from trainsh.core.runtime_store import RuntimeStore
+
+            events = RuntimeStore(root / "config" / "runtime").list_events("jobtime1")
+            ends = list(
+                {
+                    (event["ts"], event["payload_json"]): event["payload"]
+                    for event in events
+                    if event.get("event") == "group_end"
+                }.values()
+            )
+            self.assertEqual([(end["step_id"], end["success"]) for end in ends], [
+                ("prep", False),
+                ("prep", True),
+                ("extras", False),
+            ])
+            self.assertEqual(ends[-1]["counts"], {"failed": 1, "success": 1, "skipped": 1})
+
+    def test_run_recipe_preflight_stops_before_first_step_when_tools_are_missing(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            root = Path(tmpdir)
+            log_path = root / "log.txt"
+            body = f"""\
+                recipe.shell("docker ps; echo ran >> {log_path}", id="ps")
+                """
+            with patch("trainsh.core.preflight.check_host_tools", return_value=(True, ["docker"])) as check:
+                ok, tasks = self._run_inline_recipe(
+                    root, body, config={"tmux": {}}, executor_kwargs={"preflight": True}
+                )
+
+            self.assertFalse(ok)
+            self.assertEqual(check.call_args.args, ("local", ["docker"]))
+            self.assertFalse(log_path.exists())
+            self.assertEqual(tasks, {})
+
+            with patch("trainsh.core.preflight.check_host_tools", return_value=(True, [])):
+                ok, tasks = self._run_inline_recipe(
+                    root, body.replace("docker ps; ", ""), config={"tmux": {}, "recipe": {"preflight": True}}
+                )
+            self.assertTrue(ok)
+
+
+if __name__ == "__main__":
+    unittest.main()
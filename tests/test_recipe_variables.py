@@ -0,0 +1,147 @@
+import io
+import json
+import tempfile
+import unittest
+from contextlib import redirect_stdout
+from pathlib import Path
+from types import SimpleNamespace
+from unittest.mock import patch
+
+from trainsh.commands.recipe_runtime import _parse_runtime_options, _prompt_missing_variables, cmd_run
+from trainsh.core.recipe_models import RecipeModel, RecipeStepModel, StepType
+from trainsh.commands.recipe_vars import cmd_vars
+from trainsh.core.recipe_variables import (
+    UnresolvedVariableError,
+    find_unresolved_variables,
+    recipe_variable_report,
+    referenced_variables,
+    usage_context,
+    variable_report,
+)
+from trainsh.pyrecipe.models import ProviderStep
+from tests.runtime_test_utils import isolated_executor
+
+
+def _recipe() -> RecipeModel:
+    return RecipeModel(
+        name="vars",
+        variables={"OUT": "${dataset_path}/out", "MODEL": "tiny"},
+        steps=[
+            RecipeStepModel(type=StepType.EXECUTE, line_num=1, raw="", host="gpu", commands="for i in 1 2; do train ${i} ${CUDA_HOME} ${dataset_path}; done"),
+            RecipeStepModel(type=StepType.EXECUTE, line_num=2, raw="", host="gpu", commands="hostname", capture_var="NODE"),
+            ProviderStep("util", "set_var", {"name": "STAGE", "value": "1"}, id="stage"),
+            ProviderStep("shell", "run", {"command": "echo ${epochs} ${_private}"}, id="echo"),
+            ProviderStep(
+                "storage", "upload", {"source": "${NODE}/${STAGE}/${epochs}", "destination": "${HOME}/${rate_usd}"}, id="upload"
+            ),
+        ],
+    )
+
+
+class RecipeVariableDetectionTests(unittest.TestCase):
+    def test_reports_only_undefined_references(self):
+        self.assertEqual(referenced_variables("${A} ${B} ${A} ${secret:X} $C"), ["A", "B"])
+        missing = find_unresolved_variables(_recipe(), environ={"HOME": "/root"})
+        self.assertEqual(missing, ["dataset_path", "epochs", "rate_usd"])
+        self.assertEqual(
+            find_unresolved_variables(_recipe(), {"dataset_path": "/d", "epochs": "3", "rate_usd": "1"}, environ={"HOME": "/root"}),
+            [],
+        )
+
+        recipe = _recipe()
+        recipe.steps.append(ProviderStep("util", "fetch_exchange_rates", {}, id="rates"))
+        self.assertEqual(find_unresolved_variables(recipe, environ={"HOME": "/root"}), ["dataset_path", "epochs"])
+
+    def test_strict_mode_fails_interpolation(self):
+        with isolated_executor(_recipe()) as (executor, _):
+            self.assertEqual(executor._interpolate("x ${epochs}"), "x ${epochs}")
+        with isolated_executor(_recipe(), executor_kwargs={"strict_variables": True}) as (executor, _):
+            self.assertEqual(executor._interpolate("${MODEL}"), "tiny")
+            self.assertEqual(executor._interpolate("echo ${CUDA_HOME} ${i}"), "echo ${CUDA_HOME} ${i}")
+            with self.assertRaisesRegex(UnresolvedVariableError, "epochs"):
+                executor._interpolate("x ${epochs}")
+
+    def test_cli_prompts_only_when_enabled_on_a_tty(self):
+        self.assertTrue(_parse_runtime_options(["--strict-vars"])[5]["strict_variables"])
+        recipe = _recipe()
+        with patch("trainsh.core.recipe_variables.os.environ", {"HOME": "/root"}):
+            with patch("trainsh.config.get_config_value", return_value=True), patch(
+                "sys.stdin.isatty", return_value=True
+            ), patch("builtins.input", side_effect=["/data", "", ""]), redirect_stdout(io.StringIO()):
+                self.assertEqual(_prompt_missing_variables(recipe, {}), {"dataset_path": "/data"})
+
+            out = io.StringIO()
+            with patch("sys.stdin.isatty", return_value=True), patch("builtins.input") as asked, redirect_stdout(out):
+                self.assertEqual(_prompt_missing_variables(recipe, {"epochs": "1"}), {})
+            asked.assert_not_called()
+            self.assertIn("undefined variables: dataset_path, rate_usd", out.getvalue())
+            self.assertEqual(_prompt_missing_variables(None, {}), {})
+
+    def test_run_reuses_the_recipe_it_checked(self):
+        recipe = _recipe()
+        with patch("trainsh.commands.recipe_runtime.find_recipe", return_value="/tmp/demo.pyrecipe"), patch(
+            "trainsh.commands.recipe_runtime._maybe_auto_enter_tmux", return_value=False
+        ), patch("trainsh.pyrecipe.load_python_recipe", return_value=recipe) as loader, patch(
+            "trainsh.commands.recipe_runtime.run_recipe_via_dag", return_value=SimpleNamespace(success=True)
+        ) as mocked, redirect_stdout(io.StringIO()):
+            cmd_run(["demo"])
+        loader.assert_called_once_with("/tmp/demo.pyrecipe")
+        self.assertIs(mocked.call_args.kwargs["recipe"], recipe)
+
+
+class VariableReportTests(unittest.TestCase):
+    def test_report_lists_defaults_producers_and_usages(self):
+        recipe = _recipe()
+        recipe.steps.append(
+            ProviderStep("shell", "run", {"command": "python train.py --data-dir ${MODEL} --lr=${NODE} X=${undeclared}"}, id="train")
+        )
+        report = {info.name: info for info in variable_report(recipe)}
+        self.assertEqual(report["MODEL"].default, "tiny")
+        self.assertFalse(report["epochs"].has_default)
+        self.assertEqual(report["STAGE"].produced_by, ["stage"])
+        self.assertEqual(report["NODE"].produced_by, ["#2"])
+        self.assertEqual([usage.describe() for usage in report["MODEL"].usages], ["step 'train' as --data-dir"])
+        self.assertIn("step 'train' as --lr", [usage.describe() for usage in report["NODE"].usages])
+        self.assertEqual(report["dataset_path"].usages[0].describe(), "recipe variables (variables.OUT)")
+        # Shell commands only count for declared or produced names.
+        self.assertNotIn("undeclared", report)
+        self.assertNotIn("i", report)
+
+    def test_usage_context(self):
+        self.assertEqual(usage_context("train --data-dir ${D}", "D"), "--data-dir")
+        self.assertEqual(usage_context('train --out="${D}"', "D"), "--out")
+        self.assertEqual(usage_context("OUT=${D} train", "D"), "OUT")
+        self.assertEqual(usage_context("cp ${D} /tmp", "D"), "")
+
+    def test_report_from_file_and_command(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            path = Path(tmpdir) / "demo.pyrecipe"
+            path.write_text(
+                'from trainsh import Recipe\n\nrecipe = Recipe("demo")\nrecipe.variables["DATA"] = "/data"\n'
+                'recipe.shell("python train.py --data-dir ${DATA}", id="train")\n',
+                encoding="utf-8",
+            )
+            report = recipe_variable_report(str(path))
+            self.assertEqual(report[0]["usages"][0]["description"], "step 'train' as --data-dir")
+            with patch("trainsh.commands.recipe.find_recipe", return_value=str(path)):
+                out = io.StringIO()
+                with redirect_stdout(out):
+                    cmd_vars(["demo"])
+                self.assertIn("DATA: default '/data'", out.getvalue())
+                self.assertIn("used in step 'train' as --data-dir", out.getvalue())
+                out = io.StringIO()
+                with redirect_stdout(out):
+                    cmd_vars(["demo", "--json"])
+                self.assertTrue(json.loads(out.getvalue())[0]["has_default"])
+
+    def test_prompt_shows_where_missing_variables_are_used(self):
+        out = io.StringIO()
+        with patch("trainsh.core.recipe_variables.os.environ", {"HOME": "/root"}), patch(
+            "trainsh.config.get_config_value", return_value=True
+        ), patch("sys.stdin.isatty", return_value=True), patch("builtins.input", return_value=""), redirect_stdout(out):
+            _prompt_missing_variables(_recipe(), {})
+        self.assertIn("epochs: used in step 'upload' (params.source)", out.getvalue())
+
+
+if __name__ == "__main__":
+    unittest.main()
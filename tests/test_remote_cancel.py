@@ -0,0 +1,109 @@
+import io
+import os
+import subprocess
+import tempfile
+import time
+import unittest
+from contextlib import redirect_stdout
+from pathlib import Path
+from types import SimpleNamespace
+from unittest.mock import patch
+
+from trainsh.commands import recipe_cancel
+from trainsh.core.job_state import JobState
+from trainsh.core.remote_cancel import (
+    build_kill_script,
+    cancel_inflight,
+    kill_tracked_process,
+    parse_kill_report,
+    wrap_tracked_command,
+)
+
+
+class RemoteCancelTests(unittest.TestCase):
+    def test_parse_kill_report(self):
+        self.assertEqual(parse_kill_report("none\n"), "no running process")
+        self.assertEqual(
+            parse_kill_report("pid=42 pgid=42 signal=KILL\n"),
+            "killed pid 42 (process group 42) with SIGKILL",
+        )
+        self.assertEqual(parse_kill_report("pid=42 pgid=7 signal=TERM"), "killed pid 42 with SIGTERM")
+        self.assertEqual(parse_kill_report(""), "kill result unknown")
+
+    def test_kill_script_escalates_after_grace(self):
+        script = build_kill_script("/tmp/x y.pid", grace_secs=3)
+        self.assertIn("f='/tmp/x y.pid'", script)
+        self.assertIn('[ "$i" -lt 3 ]', script)
+        self.assertIn("kill -KILL", script)
+
+    def test_kills_tracked_local_command_and_children(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            pidfile = str(Path(tmpdir) / "step.pid")
+            command = wrap_tracked_command("sleep 30 & sleep 30", "train_test", pidfile)
+            proc = subprocess.Popen(["bash", "-c", command], stderr=subprocess.DEVNULL)
+            try:
+                for _ in range(50):
+                    if os.path.exists(pidfile) and Path(pidfile).read_text().strip():
+                        break
+                    time.sleep(0.1)
+
+                report = kill_tracked_process("local", pidfile, build_ssh_args=None, grace_secs=2)
+
+                self.assertRegex(report, r"killed pid \d+")
+                proc.wait(timeout=10)
+                self.assertFalse(os.path.exists(pidfile))
+                self.assertEqual(kill_tracked_process("local", pidfile, build_ssh_args=None), "no running process")
+            finally:
+                if proc.poll() is None:
+                    proc.kill()
+
+    def test_cancel_inflight_runs_remote_script_and_closes_session(self):
+        calls = []
+        tmux = SimpleNamespace(kill_session=lambda name: calls.append(name) or SimpleNamespace(returncode=0))
+        with patch(
+            "subprocess.run",
+            return_value=SimpleNamespace(returncode=0, stdout="pid=9 pgid=9 signal=TERM\n", stderr=""),
+        ) as run:
+            report = cancel_inflight(
+                {"host": "gpu", "pidfile": "/tmp/s.pid", "remote_session": "sess"},
+                build_ssh_args=lambda host, command=None, tty=False: ["ssh", host, command],
+                tmux_client=tmux,
+            )
+        self.assertEqual(run.call_args.args[0][:2], ["ssh", "gpu"])
+        self.assertEqual(report, "killed pid 9 (process group 9) with SIGTERM; closed tmux session sess")
+        self.assertEqual(calls, ["sess"])
+
+
+class RecipeCancelCommandTests(unittest.TestCase):
+    def test_cancel_marks_job_cancelled_and_kills_inflight_steps(self):
+        job = JobState(
+            job_id="job-aaaa1111",
+            recipe_path="/tmp/demo.pyrecipe",
+            recipe_name="demo",
+            status="running",
+            inflight_steps={"2": {"host": "gpu", "remote_session": "sess", "pidfile": "/tmp/p.pid"}},
+        )
+        saved = []
+        manager = SimpleNamespace(list_running=lambda: [job], load=lambda job_id: job, save=saved.append)
+        out = io.StringIO()
+        with patch("trainsh.core.job_state.JobStateManager", return_value=manager), patch(
+            "trainsh.core.remote_cancel.cancel_inflight", return_value="killed pid 9 with SIGTERM"
+        ) as cancel, redirect_stdout(out):
+            recipe_cancel.cmd_cancel(["job-aaaa", "--keep-sessions"])
+
+        self.assertIsNone(cancel.call_args.kwargs["tmux_client"])
+        self.assertIn("step 2: killed pid 9", out.getvalue())
+        self.assertEqual((saved[-1].status, saved[-1].outcome), ("cancelled", "cancelled"))
+        self.assertEqual(saved[-1].inflight_steps, {})
+
+    def test_cancel_requires_job_id_when_ambiguous(self):
+        jobs = [JobState(job_id="a1", recipe_path="", recipe_name="x"), JobState(job_id="b2", recipe_path="", recipe_name="y")]
+        manager = SimpleNamespace(list_running=lambda: jobs)
+        with patch("trainsh.core.job_state.JobStateManager", return_value=manager), redirect_stdout(io.StringIO()):
+            with self.assertRaises(SystemExit) as ctx:
+                recipe_cancel.cmd_cancel([])
+        self.assertEqual(ctx.exception.code, 1)
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,86 @@
+import unittest
+from contextlib import redirect_stdout
+from io import StringIO
+from types import SimpleNamespace
+from unittest.mock import MagicMock, patch
+
+from trainsh.commands import transfer
+from trainsh.core.models import Host, HostType, Storage, StorageType, TransferEndpoint
+from trainsh.services.remote_rclone import remote_script
+from trainsh.services.transfer_engine import TransferEngine, TransferResult
+
+OK = TransferResult(success=True, exit_code=0, message="ok")
+
+
+class RemoteRcloneTests(unittest.TestCase):
+    def _host(self, name="gpu"):
+        return Host(name=name, type=HostType.SSH, hostname=f"{name}.example.com", username="root")
+
+    def test_remote_script(self):
+        script = remote_script(
+            ["copy", "~/ckpt dir", "r2:bucket/ckpt"],
+            {"RCLONE_CONFIG_R2_SECRET_ACCESS_KEY": "s3cr3t'x"},
+            local_paths=("~/ckpt dir",),
+        )
+        self.assertIn("export RCLONE_CONFIG_R2_SECRET_ACCESS_KEY='s3cr3t'\"'\"'x'", script)
+        self.assertIn('downloads.rclone.org/rclone-current-linux-$arch.zip', script)
+        self.assertTrue(script.rstrip().endswith('exec "$RCLONE" copy "$HOME"/\'ckpt dir\' r2:bucket/ckpt'))
+
+    def test_host_to_cloud_runs_rclone_on_host(self):
+        engine = TransferEngine(remote_exec=True, rclone_options={"transfers": 8})
+        storage = Storage(name="r2", type=StorageType.R2, config={"bucket": "bucket"})
+        with patch(
+            "trainsh.services.transfer_engine.build_rclone_env", return_value={"RCLONE_CONFIG_R2_TYPE": "s3"}
+        ), patch("trainsh.services.remote_rclone.run_remote_rclone", return_value=OK) as run, patch.object(
+            engine, "rsync"
+        ) as rsync, redirect_stdout(StringIO()):
+            result = engine.transfer(
+                TransferEndpoint(type="host", path="~/ckpt", host_id="gpu"),
+                TransferEndpoint(type="storage", path="/ckpt", storage_id="r2"),
+                hosts={"gpu": self._host()},
+                storages={"r2": storage},
+            )
+        self.assertTrue(result.success)
+        rsync.assert_not_called()
+        ssh_args, script = run.call_args.args
+        self.assertEqual(ssh_args[-1], "root@gpu.example.com")
+        self.assertIn("export RCLONE_CONFIG_R2_TYPE=s3", script)
+        self.assertIn('exec "$RCLONE" copy --transfers 8 --stats 5s', script)
+        self.assertIn('"$HOME"/ckpt', script)
+        self.assertNotIn("RCLONE_CONFIG", " ".join(ssh_args))
+
+    def test_host_to_host_pulls_or_refuses_local_relay(self):
+        engine = TransferEngine(remote_exec=True)
+        src, dst = self._host("src"), self._host("dst")
+        src_ep = TransferEndpoint(type="host", path="/data", host_id="src")
+        dst_ep = TransferEndpoint(type="host", path="/data", host_id="dst")
+
+        process = MagicMock(stdout=iter([]), returncode=0)
+        with patch.object(engine, "_check_host_connectivity", side_effect=[False, True]), patch(
+            "trainsh.config.load_config", return_value={}
+        ), patch("subprocess.Popen", return_value=process) as popen:
+            self.assertTrue(engine._transfer_host_to_host(src_ep, dst_ep, src, dst).success)
+        command = popen.call_args.args[0]
+        self.assertEqual(command[-2], "root@dst.example.com")
+        self.assertTrue(command[-1].endswith("root@src.example.com:/data /data"))
+
+        with patch.object(engine, "_check_host_connectivity", return_value=False), patch.object(engine, "_scp_three_way") as relay:
+            result = engine._transfer_host_to_host(src_ep, dst_ep, src, dst)
+        self.assertFalse(result.success)
+        self.assertIn("refuses to relay", result.message)
+        relay.assert_not_called()
+
+    def test_cli_remote_flag_and_config_default(self):
+        engine = MagicMock()
+        engine.rsync.return_value = SimpleNamespace(success=True, message="ok", bytes_transferred=0)
+        with patch("trainsh.services.transfer_engine.TransferEngine", return_value=engine) as engine_cls, patch(
+            "trainsh.config.load_config", return_value={"transfer": {"remote_exec": True}}
+        ), redirect_stdout(StringIO()):
+            transfer.main(["./a", "./b"])
+            self.assertTrue(engine_cls.call_args.kwargs["remote_exec"])
+            transfer.main(["./a", "./b", "--local-relay"])
+            self.assertFalse(engine_cls.call_args.kwargs["remote_exec"])
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,62 @@
+import base64
+import unittest
+from types import SimpleNamespace
+from unittest.mock import MagicMock, patch
+
+from trainsh.core.executor_tmux import TmuxControlHelper
+from trainsh.core.executor_utils import _build_ssh_args
+from trainsh.core.models import Host
+from trainsh.services.remote_shell import normalize_shell, powershell_command, wrap_for_shell
+from trainsh.services.ssh import SSHClient
+
+
+def _decode(command_line: str) -> str:
+    return base64.b64decode(command_line.rsplit(" ", 1)[1]).decode("utf-16-le")
+
+
+class RemoteShellTests(unittest.TestCase):
+    def test_powershell_command_encodes_script_and_exit_code_capture(self):
+        line = powershell_command("python train.py --name 'a b'", cwd="C:\\Users\\me's\\work")
+        self.assertTrue(line.startswith("powershell -NoLogo -NoProfile -NonInteractive -EncodedCommand "))
+        script = _decode(line)
+        self.assertIn("Set-Location -LiteralPath 'C:\\Users\\me''s\\work'", script)
+        self.assertIn("python train.py --name 'a b'\n$__train_ok = $?", script)
+        self.assertIn("if ($LASTEXITCODE) { exit $LASTEXITCODE }", script)
+        self.assertEqual(wrap_for_shell("ls", "bash", cwd="/data x"), "cd '/data x' && (ls)")
+        self.assertEqual(normalize_shell("pwsh"), "powershell")
+        with self.assertRaises(ValueError):
+            normalize_shell("fish")
+
+    def test_ssh_client_wraps_commands_for_windows_hosts(self):
+        host = Host.from_dict({"name": "rtx", "hostname": "10.0.0.9", "username": "me", "shell": "powershell"})
+        self.assertEqual(Host.from_dict(host.to_dict()).shell, "powershell")
+        client = SSHClient.from_host(host)
+        args = client._build_ssh_args("nvidia-smi")
+        self.assertEqual(_decode(args[-1]).splitlines()[2], "nvidia-smi")
+        self.assertEqual(client._build_ssh_args(args[-1])[-1], args[-1])
+        self.assertEqual(SSHClient("linux-box")._build_ssh_args("nvidia-smi")[-1], "nvidia-smi")
+
+        with patch("trainsh.commands.host.load_hosts", return_value={"rtx": host}):
+            args = _build_ssh_args("rtx", command="dir", set_term=True)
+        self.assertNotIn("TERM=", _decode(args[-1]))
+
+    def test_tmux_open_on_windows_host_registers_a_sessionless_window(self):
+        executor = SimpleNamespace(
+            _resolve_host=lambda ref: "rtx",
+            allocate_window_session_name=lambda: "sess-1",
+            logger=SimpleNamespace(log_detail=MagicMock()),
+            ctx=SimpleNamespace(windows={}),
+            log=MagicMock(),
+            get_tmux_client=MagicMock(),
+        )
+        helper = TmuxControlHelper(executor, SimpleNamespace)
+        with patch("trainsh.core.executor_utils._host_shell", return_value="powershell"):
+            ok, _ = helper.cmd_tmux_open(["@rtx", "as", "win"])
+            self.assertFalse(helper.cmd_tmux_open(["@rtx", "as", "w2", "env=hf"])[0])
+        self.assertTrue(ok)
+        self.assertIsNone(executor.ctx.windows["win"].remote_session)
+        executor.get_tmux_client.assert_not_called()
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,60 @@
+import shutil
+import subprocess
+import tempfile
+import textwrap
+import unittest
+from contextlib import redirect_stdout
+from io import StringIO
+from pathlib import Path
+from unittest.mock import patch
+
+from trainsh.core.executor_main import run_recipe
+
+
+@unittest.skipUnless(shutil.which("tmux"), "tmux is not installed")
+class TmuxRetryFilterTests(unittest.TestCase):
+    def setUp(self):
+        self.tmpdir = tempfile.TemporaryDirectory()
+        self.addCleanup(self.tmpdir.cleanup)
+        self.root = Path(self.tmpdir.name)
+        self.log_path = self.root / "log.txt"
+
+    def _run(self, job_id, pattern):
+        recipe_path = self.root / f"{job_id}.pyrecipe"
+        recipe_path.write_text(
+            textwrap.dedent(
+                f"""\
+                from trainsh import Recipe, local
+
+                recipe = Recipe("retrytmux")
+                with local.tmux("work") as tmux:
+                    tmux.run(
+                        "echo try >> {self.log_path}; echo 'RuntimeError: CUDA out of memory'; false",
+                        id="train",
+                        step_options={{"retries": 2, "retry_on_output_regex": "{pattern}"}},
+                    )
+                """
+            ),
+            encoding="utf-8",
+        )
+        config_dir = self.root / "config"
+        self.addCleanup(
+            subprocess.run, ["tmux", "kill-session", "-t", f"train_retrytmux_{job_id}_0"], capture_output=True
+        )
+        with patch("trainsh.core.executor_main.load_config", return_value={"tmux": {"auto_bridge": False}}), patch(
+            "trainsh.core.executor_main.CONFIG_DIR", config_dir
+        ), patch("trainsh.core.executor_main.RUNTIME_STATE_DIR", config_dir / "runtime"), patch(
+            "trainsh.runtime.CONFIG_DIR", config_dir / "runtime"
+        ), redirect_stdout(StringIO()):
+            ok = run_recipe(str(recipe_path), job_id=job_id)
+        tries = self.log_path.read_text(encoding="utf-8").splitlines()
+        self.log_path.unlink()
+        return ok, len(tries)
+
+    def test_output_regex_matches_the_pane_of_a_tmux_run_step(self):
+        self.assertEqual(self._run("oomjob1", "CUDA out of memory"), (False, 3))
+        self.assertEqual(self._run("oomjob2", "NCCL timeout"), (False, 1))
+
+
+if __name__ == "__main__":
+    unittest.main()
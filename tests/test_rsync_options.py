@@ -0,0 +1,71 @@
+import unittest
+from contextlib import redirect_stdout
+from io import StringIO
+from types import SimpleNamespace
+from unittest.mock import MagicMock, patch
+
+from trainsh.commands import transfer
+from trainsh.core.executor_transfer import TransferHelper
+from trainsh.services.rsync_options import normalize_rsync_options, resolve_rsync_options, rsync_option_args
+from trainsh.services.transfer_engine import TransferEngine
+
+CONFIG = {"transfer": {"rsync": {"compress": True, "partial": "partial", "bwlimit": "", "exclude": [".git"]}}}
+
+
+class RsyncOptionsTests(unittest.TestCase):
+    def test_normalize_validates_and_builds_flags(self):
+        self.assertEqual(normalize_rsync_options({"partial": True, "compress": "no", "bwlimit": None}), {"partial": "partial", "compress": False})
+        for bad in ({"partial": "resume"}, {"bwlimit": "fast"}, {"compres": False}):
+            with self.assertRaises(ValueError):
+                normalize_rsync_options(bad)
+
+        options = resolve_rsync_options({"partial": "append-verify", "bwlimit": "20M", "exclude": "*.tmp"}, config=CONFIG)
+        self.assertEqual(
+            rsync_option_args(options),
+            ["-z", "--partial", "--append-verify", "--bwlimit=20M", "--exclude=.git", "--exclude=*.tmp"],
+        )
+        self.assertEqual(rsync_option_args(resolve_rsync_options({"compress": False, "partial": False}, config={})), [])
+
+    def test_engine_applies_options_to_local_and_remote_rsync(self):
+        process = MagicMock(stdout=iter([]), returncode=0)
+        engine = TransferEngine(rsync_options={"bwlimit": "1M"})
+        with patch("trainsh.config.load_config", return_value=CONFIG), patch("subprocess.Popen", return_value=process) as popen:
+            self.assertTrue(engine.rsync("./src", "./dst", compress=False).success)
+        args = popen.call_args.args[0]
+        self.assertEqual(args[:4], ["rsync", "-av", "--progress", "--mkpath"])
+        self.assertNotIn("-z", args)
+        self.assertIn("--partial", args)
+        self.assertIn("--bwlimit=1M", args)
+
+        bad = TransferEngine(rsync_options={"partial": "nope"})
+        with patch("trainsh.config.load_config", return_value={}):
+            result = bad.rsync("./src", "./dst")
+        self.assertFalse(result.success)
+        self.assertIn("rsync partial", result.message)
+
+    def test_cli_flags_and_recipe_options_reach_engine(self):
+        engine = MagicMock()
+        engine.rsync.return_value = SimpleNamespace(success=True, message="ok", bytes_transferred=0)
+        with patch("trainsh.services.transfer_engine.TransferEngine", return_value=engine) as engine_cls, redirect_stdout(StringIO()):
+            transfer.main(["./a", "./b", "--no-compress", "--append-verify", "--bwlimit", "5M"])
+        self.assertEqual(
+            engine_cls.call_args.kwargs["rsync_options"],
+            {"compress": False, "partial": "append-verify", "bwlimit": "5M"},
+        )
+
+        out = StringIO()
+        with redirect_stdout(out), self.assertRaises(SystemExit):
+            transfer.main(["./a", "./b", "--bwlimit", "lots"])
+        self.assertIn("Invalid rsync bwlimit", out.getvalue())
+
+        executor = SimpleNamespace(_interpolate=lambda text: text, logger=None)
+        helper = TransferHelper(executor, resolve_vast_host=str, host_from_ssh_spec=lambda spec: None)
+        ok, message = helper.transfer("./a", "./b", rsync_options={"partial": "sometimes"})
+        self.assertFalse(ok)
+        self.assertIn("rsync partial", message)
+        ok, message = helper.transfer("./a", "./b", rsync_options=["--partial"])
+        self.assertEqual((ok, message), (False, "Transfer 'rsync' options must be an object"))
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -215,14 +215,14 @@ class BridgeExecutionHelperTests(unittest.TestCase):
         self.assertTrue(ok_idle)
         self.assertIn("confirmed", msg)
 
-        tmux.capture = ok("marker0\n")
+        tmux.capture = ok("__train_begin_abc__\n__train_done_abc__0\n")
         with patch("time.sleep", return_value=None):
-            found, code = helper._wait_bridge_marker("%1", "marker", 2)
+            found, code = helper._wait_bridge_marker("%1", "abc", 2)
         self.assertTrue(found)
         self.assertEqual(code, 0)
 
-        tmux.capture = ok("marker0\n")
-        found, code = helper._wait_bridge_marker("%1", "marker", None)
+        tmux.capture = ok("__train_begin_abc__\n__train_done_abc__0\n")
+        found, code = helper._wait_bridge_marker("%1", "abc", None)
         self.assertTrue(found)
         self.assertEqual(code, 0)
 
@@ -259,8 +259,16 @@ class ExecuteHelperTests(unittest.TestCase):
             get_tmux_client=lambda host: tmux,
             is_resuming=False,
             _wait_for_idle=lambda window, timeout: (True, "idle"),
-            ctx=SimpleNamespace(variables={}),
+            ctx=SimpleNamespace(job_id="job", variables={}, inflight={}),
+            _current_step_num=lambda: 2,
+            _note_exit_code=lambda code, host="": None,
+            _note_step_stopped=lambda step_id, reason: None,
+            _note_step_output=lambda text: None,
+            _current_step_id=lambda: "step",
+            _status_hosts=set(),
         )
+        executor._track_inflight = lambda step_num, info: executor.ctx.inflight.__setitem__(str(step_num), info)
+        executor._clear_inflight = lambda step_num: executor.ctx.inflight.pop(str(step_num), None)
         helper = ExecuteHelper(executor, build_ssh_args=lambda host, command=None, tty=False: ["ssh", host, command or ""], window_cls=SimpleNamespace)
         return helper, executor, tmux
 
@@ -293,8 +301,14 @@ class ExecuteHelperTests(unittest.TestCase):
         self.assertTrue(ok_run)
         self.assertIn("Command completed", msg)
         tmux.wait = TmuxCmdResult(1, "", "")
-        ok_run, msg = helper.exec_execute(step)
+        with patch(
+            "subprocess.run",
+            return_value=SimpleNamespace(returncode=0, stdout="pid=7 pgid=7 signal=TERM\n", stderr=""),
+        ) as mocked_kill:
+            ok_run, msg = helper.exec_execute(step)
         self.assertFalse(ok_run)
+        self.assertIn("killed pid 7", msg)
+        self.assertIn(".pid", mocked_kill.call_args.args[0][2])
 
         executor._resolve_window = lambda name: SimpleNamespace(host="local", remote_session="")
         with patch("subprocess.run", return_value=SimpleNamespace(returncode=0, stdout="ok", stderr="")):
@@ -346,6 +360,48 @@ class ExecuteHelperTests(unittest.TestCase):
             self.assertEqual(helper.exec_execute(step), (True, "ok"))
         self.assertIsNone(mocked_run.call_args.kwargs["timeout"])
 
+    def test_execute_reattaches_to_inflight_command(self):
+        helper, executor, tmux = self.make_helper()
+        step = SimpleNamespace(host="main", commands="sleep 60", background=False, timeout=5, capture_var="", capture_path="")
+        executor._resolve_window = lambda name: SimpleNamespace(host="gpu", remote_session="sess")
+
+        tracked = []
+        executor._track_inflight = lambda step_num, info: tracked.append((step_num, dict(info)))
+        self.assertTrue(helper.exec_execute(step)[0])
+        self.assertEqual(tracked[0][0], 2)
+        signal = tracked[0][1]["signal"]
+        self.assertIn(f"tmux wait-for -S {signal}", tmux.sent[-1][1])
+
+        executor.ctx.inflight["2"] = {"host": "gpu", "remote_session": "sess", "signal": signal}
+        tmux.sessions.add("sess")
+        sent_before = len(tmux.sent)
+        ok_run, msg = helper.exec_execute(step)
+        self.assertTrue(ok_run)
+        self.assertIn("Re-attached", msg)
+        self.assertEqual(len(tmux.sent), sent_before)
+        self.assertEqual(tmux.wait_calls[-1], (signal, 5))
+        self.assertEqual(executor.ctx.inflight, {})
+
+        executor.ctx.inflight["2"] = {"host": "gpu", "remote_session": "sess", "signal": signal}
+        tmux.sessions.discard("sess")
+        self.assertIn("Command completed", helper.exec_execute(step)[1])
+        self.assertEqual(len(tmux.sent), sent_before + 1)
+
+    def test_execute_reports_cancelled_command(self):
+        helper, executor, tmux = self.make_helper()
+        step = SimpleNamespace(host="main", commands="sleep 60", background=False, timeout=5, capture_var="", capture_path="")
+        executor._resolve_window = lambda name: SimpleNamespace(host="gpu", remote_session="sess")
+
+        def _track(step_num, info):
+            executor.ctx.inflight[str(step_num)] = dict(info, cancelled="interrupted")
+
+        executor._track_inflight = _track
+        tmux.wait = ok("")
+        ok_run, msg = helper.exec_execute(step)
+        self.assertFalse(ok_run)
+        self.assertEqual(msg, "Command cancelled: interrupted")
+        self.assertIn("echo $PPID", tmux.sent[-1][1])
+
     def test_execute_capture_var_paths(self):
         helper, executor, tmux = self.make_helper()
         step = SimpleNamespace(
@@ -1,3 +1,4 @@
+import os
 import subprocess
 import tempfile
 import unittest
@@ -126,6 +127,20 @@ class JobStateManagerTests(unittest.TestCase):
             manager.delete("job-3")
             self.assertIsNone(manager.load("job-3"))
 
+    def test_inflight_steps_and_orphaned_jobs(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            manager = JobStateManager(str(Path(tmpdir) / "runtime"))
+            inflight = {"3": {"host": "gpu", "remote_session": "train_demo_0", "signal": "train_abcd1234"}}
+            manager.save(self._state(job_id="alive", pid=os.getpid(), inflight_steps=inflight))
+            manager.save(self._state(job_id="legacy", pid=0))
+            with patch("trainsh.core.job_state._pid_alive", side_effect=lambda pid: pid == os.getpid()):
+                manager.save(self._state(job_id="dead", pid=999999))
+                orphaned = manager.list_orphaned()
+
+            self.assertEqual([job.job_id for job in orphaned], ["dead"])
+            self.assertEqual(manager.load("alive").inflight_steps, inflight)
+            self.assertEqual(manager.load("alive").pid, os.getpid())
+
     def test_cleanup_old_and_resumable_filters(self):
         with tempfile.TemporaryDirectory() as tmpdir:
             root = Path(tmpdir) / "runtime"
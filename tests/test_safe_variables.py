@@ -0,0 +1,50 @@
+import subprocess
+import tempfile
+import unittest
+from pathlib import Path
+
+from trainsh.core.recipe_models import RecipeModel
+from trainsh.core.recipe_variables import UnresolvedVariableError
+from trainsh.core.safe_variables import bind_variables, env_name, quote_context
+from tests.runtime_test_utils import isolated_executor
+
+
+class SafeVariableTests(unittest.TestCase):
+    def test_hostile_values_stay_data(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            marker = Path(tmpdir) / "pwned"
+            value = f"x; touch {marker} $(touch {marker}) `touch {marker}`"
+            command, warnings, bindings = bind_variables('echo "${NAME}" $HOME', {"NAME": value}.get)
+            self.assertEqual(warnings, [])
+            self.assertEqual(bindings, {"TRAINSH_VAR_NAME": value})
+            self.assertIn('echo "${TRAINSH_VAR_NAME}" $HOME', command)
+            out = subprocess.run(["sh", "-c", command], capture_output=True, text=True, check=True)
+            self.assertTrue(out.stdout.startswith(value + " "))
+            self.assertFalse(marker.exists())
+
+    def test_quote_context_warnings(self):
+        self.assertEqual(quote_context("echo '${A}'", 6), "single")
+        self.assertEqual(quote_context('echo "it\'s ${A}"', 11), "double")
+        self.assertEqual(quote_context("echo \\' ${A}", 8), "none")
+        _, warnings, _ = bind_variables("echo '${A}' $A", lambda ref: "1")
+        self.assertIn("will not expand", warnings[0])
+        self.assertIn('write "${A}"', warnings[1])
+        self.assertEqual(env_name("secret:HF-TOKEN"), "TRAINSH_SECRET_HF_TOKEN")
+
+    def test_executor_binds_only_in_safe_mode(self):
+        recipe = RecipeModel(name="safe", variables={"MSG": "a; b", "OUT": "/runs/${epochs}"})
+        with isolated_executor(recipe) as (executor, _):
+            self.assertEqual(executor._interpolate_command('echo "${MSG}"'), 'echo "a; b"')
+        with isolated_executor(recipe, executor_kwargs={"safe_variables": True, "strict_variables": True}) as (executor, _):
+            self.assertEqual(
+                executor._interpolate_command('echo "${MSG}" "$HOME"'),
+                "export TRAINSH_VAR_MSG='a; b'; echo \"${TRAINSH_VAR_MSG}\" \"$HOME\"",
+            )
+            self.assertEqual(executor._interpolate_command('echo "${MSG}"', "powershell"), 'echo "a; b"')
+            self.assertEqual(executor._interpolate_command('echo "${CUDA_HOME}"'), 'echo "${CUDA_HOME}"')
+            with self.assertRaisesRegex(UnresolvedVariableError, "epochs"):
+                executor._interpolate_command('echo "${epochs}"')
+
+
+if __name__ == "__main__":
+    unittest.main()
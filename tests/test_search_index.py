@@ -0,0 +1,77 @@
+import json
+import shutil
+import tempfile
+import unittest
+from contextlib import redirect_stdout
+from io import StringIO
+from pathlib import Path
+from unittest.mock import patch
+
+from trainsh.commands import search as search_cmd
+from trainsh.services import search_index
+
+
+class SearchIndexTests(unittest.TestCase):
+    def setUp(self):
+        self.tmpdir = Path(tempfile.mkdtemp())
+        self.addCleanup(shutil.rmtree, self.tmpdir, True)
+        self.recipe = self.tmpdir / "fa-setup.pyrecipe"
+        self.recipe.write_text("from trainsh import *\n\ngpu.run('pip install flash-attn --no-build-isolation')\n", encoding="utf-8")
+        self.events = self.tmpdir / "runtime" / "events.jsonl"
+        self.events.parent.mkdir()
+        self.write_events(
+            {"run_id": "run1", "event": "step_output", "step_num": 3, "payload": {"output": "RuntimeError: CUDA out of memory"}, "ts": "2026-10-17T01:00:00"},
+            {"run_id": "run1", "event": "wait_poll", "payload": {"status": "CUDA out of memory"}},
+        )
+        patchers = [
+            patch("trainsh.services.search_index.STATE_DIR", self.tmpdir),
+            patch("trainsh.constants.RUNTIME_STATE_DIR", self.tmpdir / "runtime"),
+            patch("trainsh.config.load_config", return_value={}),
+            patch("trainsh.services.search_index.recipe_files", side_effect=lambda: [self.recipe]),
+            patch("trainsh.services.search_index._host_rows", return_value=[("host", "gpu-box", "hosts.yaml", "gpu-box 10.0.0.5 root ssh a100 for flash-attn builds")]),
+            patch("trainsh.services.search_index._session_rows", return_value=[("session", "abcd1234", "job abcd1234 (fa-setup)", "abcd1234 fa-setup running train-fa-setup-abcd1234-0")]),
+            patch("trainsh.services.search_index._secret_rows", return_value=[("secret", "HF_TOKEN", "train secrets", "HF_TOKEN")]),
+        ]
+        for patcher in patchers:
+            patcher.start()
+            self.addCleanup(patcher.stop)
+
+    def write_events(self, *records):
+        with self.events.open("a", encoding="utf-8") as handle:
+            for record in records:
+                handle.write(json.dumps(record) + "\n")
+
+    def test_typed_hits_with_locations(self):
+        hits = search_index.search("flash-attn")
+        self.assertEqual({(hit["kind"], hit["name"]) for hit in hits}, {("recipe", "fa-setup"), ("host", "gpu-box")})
+        recipe_hit = next(hit for hit in hits if hit["kind"] == "recipe")
+        self.assertEqual(recipe_hit["location"], f"{self.recipe}:3")
+        self.assertIn("[flash-attn]", recipe_hit["snippet"])
+
+        log_hits = search_index.search("out of memory", kinds=["log"])
+        self.assertEqual([hit["location"] for hit in log_hits], ["run run1 step 3 @ 2026-10-17T01:00:00"])
+        self.assertEqual(search_index.search("hf_token")[0]["kind"], "secret")
+        self.assertEqual(search_index.search('"'), [])
+
+    def test_index_follows_file_changes_and_new_log_events(self):
+        self.assertTrue(search_index.search("flash-attn", kinds=["recipe"]))
+        self.recipe.write_text("gpu.run('pip install xformers')\n", encoding="utf-8")
+        self.assertFalse(search_index.search("flash-attn", kinds=["recipe"]))
+        self.assertTrue(search_index.search("xformers", kinds=["recipe"]))
+
+        self.write_events({"run_id": "run2", "event": "detail", "payload": {"message": "NCCL timeout on rank 3"}})
+        self.assertEqual([hit["name"] for hit in search_index.search("nccl")], ["run2"])
+        self.assertEqual(len(search_index.search("memory", kinds=["log"])), 1)
+
+    def test_cli_prints_hits_and_rejects_unknown_kinds(self):
+        out = StringIO()
+        with redirect_stdout(out):
+            search_cmd.main(["flash-attn", "--kind", "recipes"])
+        self.assertIn("recipe   fa-setup", out.getvalue())
+        self.assertIn("1 match(es)", out.getvalue())
+        with self.assertRaises(SystemExit), redirect_stdout(StringIO()):
+            search_cmd.main(["x", "--kind", "widgets"])
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,49 @@
+import base64
+import json
+import tempfile
+import unittest
+from unittest.mock import MagicMock
+
+from trainsh.core.execution_log import MAX_OUTPUT_CHUNK, ExecutionLogger, ExecutionLogReader
+from trainsh.core.recipe_models import RecipeModel
+from trainsh.services.session_env import redact, secret_variants
+from tests.runtime_test_utils import isolated_executor
+
+SECRET = "hf_live/s3cr3t+key"
+
+
+class SecretRedactionTests(unittest.TestCase):
+    def test_encoded_secrets_are_scrubbed(self):
+        encoded = base64.b64encode(SECRET.encode()).decode()
+        self.assertIn("hf_live%2Fs3cr3t%2Bkey", secret_variants(SECRET))
+        text = f"raw {SECRET} b64 {encoded} url hf_live%2Fs3cr3t%2Bkey json {json.dumps(SECRET)}"
+        self.assertEqual(redact(text, {SECRET}), 'raw *** b64 *** url *** json "***"')
+        self.assertEqual(redact("ab YWI=", {"ab"}), "ab YWI=")
+
+    def test_chunked_output_and_step_files_never_carry_the_secret(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            logger = ExecutionLogger("job1", "train", tmpdir)
+            logger.redactions.add(SECRET)
+            logger.step_start(1, "fit", "execute", {})
+            logger.step_output(1, "x" * (MAX_OUTPUT_CHUNK - 5) + SECRET + "\n")
+            logger.close()
+
+            reader = ExecutionLogReader(tmpdir)
+            self.assertNotIn("s3cr3t", reader.get_step_log_path("job1", 1).read_text())
+            events = [event for event in reader.store.list_events("job1") if event["event"] == "step_output"]
+            self.assertEqual(len(events), 1)
+            self.assertTrue(events[0]["payload"]["output"].endswith("***\n"))
+
+    def test_get_value_secrets_join_the_redaction_set(self):
+        with isolated_executor(RecipeModel(name="r")) as (executor, _):
+            executor.secrets = MagicMock()
+            executor.secrets.get.return_value = SECRET
+            executor.logger = ExecutionLogger("job", "r")
+            executor.logger.store = MagicMock()
+            ok, _ = executor._exec_provider_get_value({"target": "TOKEN", "source": "secret:HF"})
+        self.assertTrue(ok)
+        self.assertIn(SECRET, executor.logger.redactions)
+
+
+if __name__ == "__main__":
+    unittest.main()
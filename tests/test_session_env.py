@@ -0,0 +1,83 @@
+import json
+import os
+import stat
+import tempfile
+import unittest
+from unittest.mock import MagicMock, patch
+
+from trainsh import Recipe
+from trainsh.core.execution_log import ExecutionLogger
+from trainsh.core.executor_runtime import WindowInfo
+from trainsh.pyrecipe.models import Host, PythonRecipeError
+from trainsh.services.session_env import redact, render_env_file, resolve_env_sets, source_command
+from tests.runtime_test_utils import isolated_executor
+
+
+class SessionEnvTests(unittest.TestCase):
+    def test_recipe_env_sets_become_tmux_open_options(self):
+        recipe = Recipe("env-demo")
+        recipe.env_set("hf", HF_TOKEN="${secret:HF_TOKEN}", HF_HOME="/workspace/hf")
+        recipe.env_set("wandb", {"WANDB_API_KEY": "${secret:WANDB_API_KEY}"})
+        Host("gpu", name="gpu").tmux("train", env_sets=["hf", "wandb"], env_file="/workspace/app/.env", id="open")
+
+        step = {item.id: item for item in recipe.steps}["open"]
+        self.assertEqual(step.args, ["@gpu", "as", "train", "env=hf,wandb", "env_file=/workspace/app/.env"])
+        self.assertEqual(recipe.to_recipe_model().env_sets["hf"]["HF_HOME"], "/workspace/hf")
+        with patch("trainsh.config.load_config", return_value={}):
+            with self.assertRaises(PythonRecipeError):
+                recipe.tmux_open("gpu", as_="other", env_sets=["missing"])
+        with self.assertRaises(PythonRecipeError):
+            recipe.env_set("bad", **{"1X": "y"})
+
+    def test_resolve_render_and_redact(self):
+        with patch("trainsh.config.load_config", return_value={"env_sets": {"hf": {"HF_HOME": "/cfg", "A": "1"}}}):
+            values = resolve_env_sets(["hf", "extra"], {"extra": {"A": "it's"}}, lambda text: text.upper())
+        self.assertEqual(values, {"HF_HOME": "/CFG", "A": "IT'S"})
+        self.assertEqual(render_env_file(values), "HF_HOME=/CFG\nA='IT'\"'\"'S'\n")
+        self.assertEqual(source_command("~/x.env", remove=True), 'set -a; . "$HOME"/x.env; set +a; rm -f "$HOME"/x.env')
+        self.assertEqual(redact({"cmd": ["export T=hf_secret"], "n": 1}, {"hf_secret", "ab"}), {"cmd": ["export T=***"], "n": 1})
+
+        logger = ExecutionLogger("job", "demo")
+        logger.store = MagicMock()
+        logger.redactions.add("hf_secret")
+        logger.log_detail("execute", "run", {"commands": "HF_TOKEN=hf_secret python train.py"})
+        event = logger.store.append_event.call_args.args[0]
+        self.assertEqual(event["payload"]["data"]["commands"], "HF_TOKEN=*** python train.py")
+
+    def test_tmux_open_injects_private_env_file(self):
+        recipe = Recipe("env-inject")
+        recipe.env_set("hf", HF_TOKEN="${secret:HF_TOKEN}", HF_HOME="/workspace/hf")
+        with tempfile.TemporaryDirectory() as tmpdir, isolated_executor(recipe.to_recipe_model()) as (executor, _config_dir):
+            executor.secrets = MagicMock()
+            executor.secrets.get.return_value = "hf_live_secret"
+            executor.logger = ExecutionLogger("job", "env-inject")
+            executor.logger.store = MagicMock()
+            tmux = MagicMock()
+            tmux.send_keys.return_value = MagicMock(returncode=0)
+            env_file = os.path.join(tmpdir, "app", ".env")
+            window = WindowInfo(name="train", host="local", remote_session="train_sess")
+            with patch.object(executor, "get_tmux_client", return_value=tmux), patch(
+                "trainsh.config.load_config", return_value={}
+            ):
+                ok, message = executor.tmux_control.inject_env(window, {"env": "hf", "env_file": env_file})
+                self.assertTrue(ok, message)
+
+            with open(env_file) as handle:
+                self.assertEqual(handle.read(), "HF_TOKEN=hf_live_secret\nHF_HOME=/workspace/hf\n")
+            self.assertEqual(stat.S_IMODE(os.stat(env_file).st_mode), 0o600)
+            sent = tmux.send_keys.call_args.args[1]
+            self.assertIn("set -a; .", sent)
+            self.assertNotIn("hf_live_secret", sent)
+            self.assertNotIn("rm -f", sent)
+
+            executor.logger.log_detail("execute", "run", {"commands": "echo hf_live_secret"})
+            payloads = json.dumps([call.args[0] for call in executor.logger.store.append_event.call_args_list])
+            self.assertNotIn("hf_live_secret", payloads)
+
+            ok, message = executor.tmux_control.inject_env(window, {"env": "nope"})
+            self.assertFalse(ok)
+            self.assertIn("Unknown env set", message)
+
+
+if __name__ == "__main__":
+    unittest.main()
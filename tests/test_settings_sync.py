@@ -0,0 +1,100 @@
+import os
+import tempfile
+import time
+import unittest
+from contextlib import ExitStack, redirect_stdout
+from io import StringIO
+from pathlib import Path
+from unittest.mock import patch
+
+from trainsh.commands import sync as sync_cmd
+from trainsh.core.models import Storage, StorageType
+from trainsh.services import settings_sync
+
+
+class SettingsSyncTests(unittest.TestCase):
+    def setUp(self):
+        self.tmpdir = tempfile.TemporaryDirectory()
+        self.addCleanup(self.tmpdir.cleanup)
+        self.root = Path(self.tmpdir.name)
+        self.storage = Storage(name="shared", type=StorageType.LOCAL, config={"path": str(self.root / "bucket")})
+
+    def machine(self, device):
+        base = self.root / device
+        (base / "recipes").mkdir(parents=True, exist_ok=True)
+        stack = ExitStack()
+        for key, value in {
+            "HOSTS_FILE": base / "hosts.yaml",
+            "PROJECTS_FILE": base / "projects.yaml",
+            "VAST_TEMPLATES_FILE": base / "vast_templates.yaml",
+            "RUNTIME_STATE_DIR": base / "runtime",
+        }.items():
+            stack.enter_context(patch(f"trainsh.constants.{key}", value))
+        stack.enter_context(patch("trainsh.commands.recipe.get_recipes_dir", return_value=str(base / "recipes")))
+        stack.enter_context(patch("trainsh.commands.storage.load_storages", return_value={"shared": self.storage}))
+        stack.enter_context(
+            patch(
+                "trainsh.services.settings_sync.sync_settings",
+                return_value={"storage": "shared", "path": "sync", "device": device},
+            )
+        )
+        return base, stack
+
+    def test_two_machines_converge_and_keep_conflict_copies(self):
+        desk, stack = self.machine("desk")
+        with stack:
+            (desk / "hosts.yaml").write_text("hosts:\n- name: gpu\n")
+            (desk / "recipes" / "train.pyrecipe").write_text("v1\n")
+            result = settings_sync.sync_now()
+        self.assertEqual(result.count("pushed"), 2)
+
+        laptop, stack = self.machine("laptop")
+        with stack:
+            result = settings_sync.sync_now()
+            self.assertEqual(result.count("pulled"), 2)
+            self.assertEqual((laptop / "recipes" / "train.pyrecipe").read_text(), "v1\n")
+            self.assertEqual(settings_sync.sync_now().changes, [])
+            (laptop / "recipes" / "train.pyrecipe").write_text("laptop edit\n")
+            os.utime(laptop / "recipes" / "train.pyrecipe", (time.time() - 60, time.time() - 60))
+            (laptop / "hosts.yaml").unlink()
+
+        with self.machine("desk")[1]:
+            (desk / "recipes" / "train.pyrecipe").write_text("desk edit\n")
+            settings_sync.sync_now()
+
+        with self.machine("laptop")[1]:
+            self.assertEqual(settings_sync.pending_local_changes(), ["config/hosts.yaml", "recipes/train.pyrecipe"])
+            result = settings_sync.sync_now()
+            actions = {change["path"]: change["action"] for change in result.changes}
+            self.assertEqual(actions, {"recipes/train.pyrecipe": "conflict", "config/hosts.yaml": "deleted_remote"})
+            self.assertEqual((laptop / "recipes" / "train.pyrecipe").read_text(), "desk edit\n")
+            copies = list((laptop / "recipes").glob("train.conflict-laptop-*.pyrecipe"))
+            self.assertEqual([path.read_text() for path in copies], ["laptop edit\n"])
+            self.assertEqual([record["action"] for record in settings_sync.history()][-2:], ["deleted_remote", "conflict"])
+
+        with self.machine("desk")[1]:
+            result = settings_sync.sync_now()
+        self.assertEqual([(change["path"], change["action"]) for change in result.changes], [("config/hosts.yaml", "deleted_local")])
+        self.assertFalse((desk / "hosts.yaml").exists())
+
+    def test_cli_reports_disabled_sync_and_dry_run(self):
+        out = StringIO()
+        with patch("trainsh.services.settings_sync.sync_settings", return_value={"storage": "", "path": "sync", "device": "d"}), redirect_stdout(out):
+            sync_cmd.main(["status"])
+            with self.assertRaises(SystemExit):
+                sync_cmd.main(["now"])
+        self.assertIn("Settings sync is off.", out.getvalue())
+        self.assertIn("Sync failed: Settings sync is not configured", out.getvalue())
+
+        desk, stack = self.machine("desk")
+        out = StringIO()
+        with stack, redirect_stdout(out):
+            (desk / "recipes" / "eval.pyrecipe").write_text("x\n")
+            sync_cmd.main(["now", "--dry-run"])
+        self.assertIn("recipes/eval.pyrecipe: pushed", out.getvalue())
+        self.assertIn("Would sync: 1 pushed", out.getvalue())
+        self.assertFalse((self.root / "bucket" / "sync").exists())
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,128 @@
+import os
+import sys
+import tempfile
+import unittest
+from pathlib import Path
+from types import SimpleNamespace
+from unittest.mock import patch
+
+from trainsh.services.sftp_browser import RemoteFileBrowser
+from trainsh.services.sftp_list import SFTPError, sftp_list
+
+# Minimal SFTP v3 server over stdin/stdout, rooted at the directory in argv[1].
+FAKE_SERVER = r'''
+import os, stat, struct, sys
+root = sys.argv[1]
+inp, out = sys.stdin.buffer, sys.stdout.buffer
+handles = {}
+
+def s(b):
+    b = b if isinstance(b, bytes) else b.encode("utf-8", "surrogateescape")
+    return struct.pack(">I", len(b)) + b
+
+def attrs(st):
+    return struct.pack(">IQIIIII", 0xF, st.st_size, st.st_uid, st.st_gid, st.st_mode, int(st.st_atime), int(st.st_mtime))
+
+def send(kind, rid, payload=b""):
+    body = bytes([kind]) + struct.pack(">I", rid) + payload
+    out.write(struct.pack(">I", len(body)) + body)
+    out.flush()
+
+def status(rid, code, msg=""):
+    send(101, rid, struct.pack(">I", code) + s(msg) + s(""))
+
+while True:
+    head = inp.read(4)
+    if len(head) < 4:
+        break
+    body = inp.read(struct.unpack(">I", head)[0])
+    kind = body[0]
+    if kind == 1:
+        out.write(struct.pack(">IBI", 5, 2, 3))
+        out.flush()
+        continue
+    rid = struct.unpack(">I", body[1:5])[0]
+    n = struct.unpack(">I", body[5:9])[0]
+    arg = body[9:9 + n].decode("utf-8", "surrogateescape")
+    try:
+        if kind == 16:
+            send(104, rid, struct.pack(">I", 1) + s(root if arg == "." else arg) + s("") + struct.pack(">I", 0))
+        elif kind == 11:
+            handles[arg] = [".", ".."] + sorted(os.listdir(arg))
+            send(102, rid, s(arg))
+        elif kind == 12:
+            names = handles.get(arg)
+            if not names:
+                status(rid, 1, "EOF")
+                continue
+            handles[arg] = []
+            payload = struct.pack(">I", len(names))
+            for name in names:
+                st = os.lstat(os.path.join(arg, name))
+                payload += s(name) + s("-rw-r--r-- 1 alice staff 0 Jan 1 00:00 " + name) + attrs(st)
+            send(104, rid, payload)
+        elif kind == 4:
+            status(rid, 0)
+        elif kind in (7, 17):
+            send(105, rid, attrs((os.lstat if kind == 7 else os.stat)(arg)))
+        elif kind == 19:
+            send(104, rid, struct.pack(">I", 1) + s(os.readlink(arg)) + s("") + struct.pack(">I", 0))
+        else:
+            status(rid, 8, "unsupported")
+    except OSError as exc:
+        status(rid, 2, str(exc))
+'''
+
+
+def fake_client(root):
+    return SimpleNamespace(_build_ssh_args=lambda command=None: [sys.executable, "-c", FAKE_SERVER, str(root), "gpu-host"])
+
+
+class SFTPListTests(unittest.TestCase):
+    def test_lists_odd_names_symlinks_and_metadata(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            root = Path(tmpdir)
+            (root / "data dir").mkdir()
+            (root / "line\nbreak.txt").write_text("hello")
+            os.chmod(root / "line\nbreak.txt", 0o640)
+            os.symlink("data dir", root / "latest")
+            os.symlink("missing", root / "dangling")
+
+            base, entries = sftp_list(fake_client(root), "~")
+            by_name = {entry.name: entry for entry in entries}
+
+        self.assertEqual(base, str(root))
+        self.assertEqual([e.name for e in entries[:2]], ["data dir", "latest"])
+        odd = by_name["line\nbreak.txt"]
+        self.assertEqual((odd.kind, odd.size, odd.permissions, odd.owner), ("file", 5, "-rw-r-----", "alice"))
+        self.assertIsNotNone(odd.modified)
+        self.assertEqual((by_name["latest"].kind, by_name["latest"].link_target, by_name["latest"].is_dir), ("symlink", "data dir", True))
+        self.assertEqual((by_name["dangling"].link_target, by_name["dangling"].is_dir), ("missing", False))
+
+    def test_missing_path_and_missing_subsystem(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            with self.assertRaises(SFTPError) as ctx:
+                sftp_list(fake_client(tmpdir), os.path.join(tmpdir, "nope"))
+            self.assertEqual(ctx.exception.code, 2)
+
+        broken = SimpleNamespace(_build_ssh_args=lambda command=None: [sys.executable, "-c", "import sys; sys.exit(1)"])
+        with self.assertRaises(SFTPError) as ctx:
+            sftp_list(broken, "~", timeout=5)
+        self.assertIsNone(ctx.exception.code)
+
+    def test_browser_falls_back_to_ls_once_sftp_is_unavailable(self):
+        ssh = SimpleNamespace(
+            _build_ssh_args=lambda command=None: [sys.executable, "-c", "import sys; sys.exit(1)"],
+            run=lambda cmd: SimpleNamespace(success=True, stdout="total 0\n-rw-r--r-- 1 root root 3 2026-03-12 10:01 a.txt\n"),
+        )
+        browser = RemoteFileBrowser(ssh)
+        self.assertEqual([e.name for e in browser.list_directory("/tmp")], ["a.txt"])
+        self.assertIs(browser.sftp_available, False)
+
+        with patch("trainsh.services.sftp_list.sftp_list") as listing:
+            browser.list_directory("/tmp")
+        listing.assert_not_called()
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,84 @@
+import tempfile
+import unittest
+from contextlib import redirect_stdout
+from io import StringIO
+from pathlib import Path
+from unittest.mock import patch
+
+from trainsh.commands import host
+from trainsh.core.models import Host, HostType
+from trainsh.services import ssh_export
+from trainsh.services.vscode import ensure_ssh_include
+
+
+def inventory():
+    return {
+        "gpu-box": Host(name="gpu-box", type=HostType.SSH, hostname="10.0.0.5", port=2222, username="me"),
+        "vast-77": Host(
+            name="vast-77",
+            type=HostType.VASTAI,
+            hostname="ssh5.vast.ai",
+            port=41022,
+            username="root",
+            vast_instance_id="77",
+            env_vars={"_auto_discovered_vast": True},
+        ),
+        "stopped": Host(name="stopped", type=HostType.VASTAI, vast_instance_id="78"),
+        "laptop": Host(name="laptop", type=HostType.LOCAL),
+    }
+
+
+class SSHExportTests(unittest.TestCase):
+    def setUp(self):
+        patcher = patch("trainsh.config.load_config", return_value={})
+        patcher.start()
+        self.addCleanup(patcher.stop)
+
+    def test_render_uses_stored_endpoints_without_starting_instances(self):
+        with patch("trainsh.services.host_resolver.prepare_vast_host", side_effect=AssertionError("started")):
+            report = ssh_export.render_export(inventory())
+        self.assertEqual(report["aliases"], {"gpu-box": "gpu-box", "vast-77": "vast-77"})
+        self.assertEqual(report["skipped"], {"stopped": "no SSH endpoint (instance stopped?)"})
+        self.assertIn("Host gpu-box\n    HostName 10.0.0.5\n    Port 2222\n    User me", report["text"])
+        self.assertIn("Host vast-77\n    HostName ssh5.vast.ai\n    Port 41022", report["text"])
+        self.assertTrue(report["text"].startswith("# Generated by tmux-trainsh"))
+
+    def test_export_writes_include_and_adds_it_once(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            target = Path(tmpdir) / "config.d" / "tmux-trainsh"
+            config = Path(tmpdir) / "config"
+            config.write_text("Host other\n    HostName example.com\n", encoding="utf-8")
+            with patch("trainsh.services.ssh_export.ensure_ssh_include", side_effect=lambda path: ensure_ssh_include(path, config)):
+                first = ssh_export.export_ssh_config(inventory(), path=target)
+                second = ssh_export.export_ssh_config({"gpu-box": inventory()["gpu-box"]}, path=target)
+            self.assertTrue(first["include_added"])
+            self.assertFalse(second["include_added"])
+            self.assertTrue(config.read_text(encoding="utf-8").startswith(f"Include {target}\n"))
+            text = target.read_text(encoding="utf-8")
+            self.assertIn("Host gpu-box", text)
+            self.assertNotIn("vast-77", text)
+            self.assertEqual(target.stat().st_mode & 0o777, 0o600)
+
+    def test_saving_hosts_refreshes_export_only_when_enabled(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            with patch("trainsh.constants.HOSTS_FILE", Path(tmpdir) / "hosts.yaml"), patch(
+                "trainsh.constants.CONFIG_DIR", Path(tmpdir)
+            ), patch("trainsh.services.ssh_export.export_ssh_config") as export:
+                host.save_hosts(inventory())
+                export.assert_not_called()
+                with patch("trainsh.config.load_config", return_value={"ssh_export": {"auto_refresh": True}}):
+                    host.save_hosts(inventory())
+                export.assert_called_once_with()
+
+    def test_print_option_renders_without_writing(self):
+        out = StringIO()
+        with patch("trainsh.commands.host.load_hosts", return_value=inventory()), patch(
+            "trainsh.services.ssh_export.export_ssh_config"
+        ) as export, redirect_stdout(out):
+            host.main(["ssh-config", "--print"])
+        export.assert_not_called()
+        self.assertIn("Host gpu-box", out.getvalue())
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,70 @@
+import tempfile
+import unittest
+from contextlib import redirect_stderr, redirect_stdout
+from io import StringIO
+from pathlib import Path
+from unittest.mock import patch
+
+from trainsh.commands.recipe_views import cmd_output
+from trainsh.core.execution_log import ExecutionLogger, ExecutionLogReader
+from trainsh.core.runtime_store import RuntimeStore
+
+
+class StepOutputFileTests(unittest.TestCase):
+    def test_each_attempt_rewrites_the_step_file(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            logger = ExecutionLogger("job1", "train", tmpdir, max_step_bytes=4)
+            logger.redactions.add("hunter22")
+            logger._write("step_start", step_num=2, step_id="fit")
+            logger.step_start(2, "fit", "execute", {})
+            logger.step_output(2, "try one\n")
+            logger.step_output(2, "Traceback\n", "exception")
+            logger.step_start(2, "fit", "execute", {})
+            logger.step_output(2, "epoch 1 hunter22\n")
+            logger.step_output(2, "epoch 2\n")
+            logger.close()
+
+            reader = ExecutionLogReader(tmpdir)
+            path = reader.get_step_log_path("job1", "fit")
+            self.assertEqual(path, Path(tmpdir) / "step_logs" / "job1" / "step_0002.log")
+            self.assertEqual(path.read_text(), "epoch 1 ***\nepoch 2\n")
+            self.assertIsNone(reader.get_step_log_path("job1", "fit", "exception"))
+            self.assertEqual(reader.resolve_step("job1"), (2, "fit"))
+            self.assertIsNone(reader.resolve_step("job1", "missing"))
+
+            store = RuntimeStore(Path(tmpdir))
+            store.append_run({"run_id": "job1", "status": "succeeded", "success": True, "ts": "2026-01-01T00:00:00"})
+            store.append_run({"run_id": "job2", "status": "succeeded", "success": True, "ts": "2026-01-02T00:00:00"})
+            self.assertEqual(store.prune_runs(keep_last=1), ["job1"])
+            self.assertFalse(path.parent.exists())
+
+    def test_cli_prints_locates_and_exports_output(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            store = RuntimeStore(Path(tmpdir))
+            store.append_run({"run_id": "job3", "recipe_name": "train", "status": "succeeded", "success": True, "ts": "x"})
+            logger = ExecutionLogger("job3", "train", tmpdir)
+            logger._write("step_start", step_num=1, step_id="fit")
+            logger.step_output(1, "loss 0.5\n")
+            logger.close()
+            # A run recorded before step files existed.
+            store.append_event({"run_id": "job0", "event": "step_start", "step_num": 1, "payload": {"step_id": "old"}, "ts": "1"})
+            store.append_event({"run_id": "job0", "event": "step_output", "step_num": 1, "payload": {"output": "legacy\n"}, "ts": "2"})
+
+            out = StringIO()
+            target = Path(tmpdir) / "export.txt"
+            with patch("trainsh.core.execution_log.RuntimeStore", return_value=store), redirect_stdout(out), redirect_stderr(StringIO()):
+                cmd_output(["--last", "fit"])
+                cmd_output(["job3", "1", "--path"])
+                cmd_output(["job0", "old", "-o", str(target)])
+            lines = out.getvalue().splitlines()
+            self.assertEqual(lines[0], "loss 0.5")
+            self.assertTrue(lines[1].endswith("step_logs/job3/step_0001.log"))
+            self.assertEqual(target.read_text(), "legacy\n")
+
+            with patch("trainsh.core.execution_log.RuntimeStore", return_value=store), redirect_stdout(StringIO()):
+                with self.assertRaises(SystemExit):
+                    cmd_output(["job3", "fit", "--error"])
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,87 @@
+import json
+import tempfile
+import unittest
+from contextlib import redirect_stdout
+from io import StringIO
+from pathlib import Path
+from unittest.mock import patch
+
+from trainsh.commands.recipe_views import cmd_tail
+from trainsh.core.execution_log import ExecutionLogger, ExecutionLogReader
+from trainsh.core.output_tail import StepOutputTail, get_live_tail
+from trainsh.core.runtime_store import RuntimeStore
+
+
+class StepOutputTailTests(unittest.TestCase):
+    def test_ring_is_bounded_by_lines_and_size(self):
+        tail = StepOutputTail(max_lines=3, max_bytes=12)
+        tail.append(1, "one\ntw")
+        tail.append(1, "o\nthree\n")
+        self.assertEqual(tail.tail(1), ["one", "two", "three"])
+        tail.append(1, "four\nfive\nsix")
+        self.assertEqual(tail.tail(1), ["four", "five", "six"])
+        tail.append(1, "\nseventeen-long\n")
+        self.assertEqual(tail.tail(1), ["venteen-long"])
+        self.assertEqual(tail.tail(1, 0), [])
+        self.assertEqual(tail.tail(2), [])
+
+    def test_coalesced_logger_keeps_full_log_and_serves_tail(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            logger = ExecutionLogger("job1", "train", tmpdir, tail_lines=2, coalesce_secs=3600)
+            logger.redactions.add("hunter22")
+            logger._write("step_start", step_num=1, step_id="train")
+            for index in range(5):
+                logger.step_output(1, f"line {index} hunter22\n")
+                logger.log_wait("main", "idle", index, 10 - index, f"poll #{index + 1}")
+            store = RuntimeStore(Path(tmpdir))
+            self.assertEqual([event["event"] for event in store.list_events("job1")], ["step_start"])
+
+            reader = ExecutionLogReader(tmpdir)
+            live = reader.get_step_output_tail("job1", "train", 10)
+            self.assertEqual(live["source"], "live")
+            self.assertEqual(live["lines"], ["line 3 ***", "line 4 ***"])
+
+            logger.step_end(1, True, 10)
+            events = store.list_events("job1")
+            self.assertEqual([event["event"] for event in events], ["step_start", "step_output", "wait_poll"])
+            self.assertEqual(events[1]["payload"]["output"].count("\n"), 5)
+            self.assertEqual(events[2]["payload"]["status"], "poll #5")
+
+            logger.close()
+            self.assertIsNone(get_live_tail("job1"))
+            replayed = reader.get_step_output_tail("job1", None, 3)
+            self.assertEqual((replayed["source"], replayed["step_id"]), ("log", "train"))
+            self.assertEqual(replayed["lines"], ["line 2 ***", "line 3 ***", "line 4 ***"])
+            self.assertIsNone(reader.get_step_output_tail("job1", "missing", 3))
+
+    def test_cli_prints_tail_of_last_run(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            store = RuntimeStore(Path(tmpdir))
+            store.append_run({"run_id": "job2", "recipe_name": "train", "status": "succeeded", "success": True, "ts": "x"})
+            logger = ExecutionLogger("job2", "train", tmpdir)
+            logger._write("step_start", step_num=1, step_id="setup")
+            logger.step_output(1, "ready\n")
+            logger._write("step_start", step_num=2, step_id="fit")
+            logger.step_output(2, "\n".join(f"epoch {index}" for index in range(100)))
+            logger.close()
+
+            out = StringIO()
+            with patch("trainsh.core.execution_log.RuntimeStore", return_value=store), redirect_stdout(out):
+                cmd_tail(["--last", "-n", "2"])
+                cmd_tail(["job2", "1", "--json"])
+            text = out.getvalue()
+            self.assertIn("== job2 fit (last 2 line(s), log) ==\nepoch 98\nepoch 99\n", text)
+            self.assertEqual(json.loads(text[text.index("{"):])["lines"], ["ready"])
+
+            store.append_run({"run_id": "job3", "recipe_name": "train", "status": "running", "ts": "y"})
+            out = StringIO()
+            with patch("trainsh.core.execution_log.RuntimeStore", return_value=store), redirect_stdout(out):
+                cmd_tail(["job2", "1"])
+                with self.assertRaises(SystemExit):
+                    cmd_tail(["job", "1"])
+            self.assertIn("== job2 setup", out.getvalue())
+            self.assertIn("Job id job is ambiguous", out.getvalue())
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,103 @@
+import tempfile
+import unittest
+from contextlib import redirect_stdout
+from io import StringIO
+from pathlib import Path
+from types import SimpleNamespace
+from unittest.mock import MagicMock, patch
+
+from trainsh.commands.recipe_views import cmd_screen
+from trainsh.core.execution_log import ExecutionLogger, ExecutionLogReader
+from trainsh.core.executor_wait import WaitHelper
+from trainsh.core.local_tmux import TmuxCmdResult, capture_alternate_screen
+from trainsh.core.runtime_store import RuntimeStore
+
+
+def _client(alternate: str, frame: str = "") -> SimpleNamespace:
+    return SimpleNamespace(
+        display_message=MagicMock(return_value=TmuxCmdResult(0, alternate + "\n", "")),
+        capture_pane=MagicMock(return_value=TmuxCmdResult(0, frame, "")),
+    )
+
+
+class StepScreenTests(unittest.TestCase):
+    def test_frame_is_captured_only_on_the_alternate_screen(self):
+        self.assertIsNone(capture_alternate_screen(_client("0", "loss 0.5\n"), "sess"))
+        client = _client("1", "  CPU[||||   ]   \n  GPU 98%\n\n")
+        self.assertEqual(capture_alternate_screen(client, "sess"), "  CPU[||||   ]\n  GPU 98%\n")
+        self.assertEqual(client.capture_pane.call_args.args, ("sess",))
+
+    def test_logger_keeps_only_the_latest_frame_per_attempt(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            logger = ExecutionLogger("job1", "train", tmpdir)
+            logger.redactions.add("hunter22")
+            logger._write("step_start", step_num=2, step_id="watch")
+            logger.step_start(2, "watch", "execute", {})
+            logger.step_screen(2, "frame 1\n")
+            logger.step_screen(2, "frame 2 hunter22\n")
+            logger.step_output(2, "done\n")
+            logger.close()
+
+            reader = ExecutionLogReader(tmpdir)
+            screen = reader.get_step_screen("job1")
+            self.assertEqual((screen["step_num"], screen["step_id"]), (2, "watch"))
+            self.assertEqual(screen["frame"], "frame 2 ***\n")
+            self.assertEqual(reader.get_step_output("job1", 2), "done\n")
+            events = [event["event"] for event in RuntimeStore(Path(tmpdir)).list_events("job1")]
+            self.assertEqual(events.count("step_screen"), 1)
+
+            logger = ExecutionLogger("job1", "train", tmpdir)
+            logger.step_start(2, "watch", "execute", {})
+            logger.step_screen(2, "retry\n")
+            logger.step_start(2, "watch", "execute", {})
+            logger.close()
+            self.assertIsNone(reader.get_step_screen("job1", "watch"))
+
+    def test_idle_wait_stores_frames_instead_of_logging_lines(self):
+        logger = MagicMock()
+        logs = []
+        executor = SimpleNamespace(
+            get_tmux_client=lambda host: _client("1", "nvtop\n"),
+            logger=logger,
+            log=logs.append,
+            ctx=SimpleNamespace(job_id="job9"),
+            _current_step_num=lambda: 3,
+        )
+        helper = WaitHelper(executor, lambda *a, **k: [], lambda spec: spec, lambda secs: f"{int(secs)}s")
+        helper.is_pane_idle = MagicMock(return_value=False)
+        helper.get_pane_process_info = MagicMock(return_value=("nvtop", ""))
+        helper.get_pane_recent_output = MagicMock(return_value="garbled")
+        with patch("trainsh.core.executor_wait.time.sleep"), patch(
+            "trainsh.core.executor_wait.time.time", side_effect=[0, 1, 100]
+        ):
+            ok, _ = helper.wait_for_idle(SimpleNamespace(name="main", host="gpu", remote_session="sess"), 10)
+        self.assertFalse(ok)
+        logger.step_screen.assert_called_once_with(3, "nvtop\n")
+        helper.get_pane_recent_output.assert_not_called()
+        self.assertTrue(any("train recipe screen job9" in line for line in logs))
+
+    def test_cli_prints_the_frame_or_its_path(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            store = RuntimeStore(Path(tmpdir))
+            store.append_run({"run_id": "job3", "recipe_name": "train", "status": "running", "success": None, "ts": "x"})
+            logger = ExecutionLogger("job3", "train", tmpdir)
+            logger._write("step_start", step_num=1, step_id="watch")
+            logger.step_screen(1, "GPU 98%\n")
+            logger.close()
+
+            out = StringIO()
+            with patch("trainsh.core.execution_log.RuntimeStore", return_value=store), redirect_stdout(out):
+                cmd_screen(["--last"])
+                cmd_screen(["job3", "watch", "--path"])
+            lines = out.getvalue().splitlines()
+            self.assertTrue(lines[0].startswith("Step 1 (watch) screen at "))
+            self.assertEqual(lines[1], "GPU 98%")
+            self.assertTrue(lines[2].endswith("step_logs/job3/step_0001.screen.log"))
+
+            with patch("trainsh.core.execution_log.RuntimeStore", return_value=store), redirect_stdout(StringIO()):
+                with self.assertRaises(SystemExit):
+                    cmd_screen(["job3", "2"])
+
+
+if __name__ == "__main__":
+    unittest.main()
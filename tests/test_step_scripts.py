@@ -0,0 +1,87 @@
+import os
+import stat
+import tempfile
+import unittest
+from pathlib import Path
+from types import SimpleNamespace
+from unittest.mock import MagicMock, patch
+
+from trainsh.core.executor_execute import ExecuteHelper
+from trainsh.pyrecipe import load_recipe
+from trainsh.pyrecipe.models import PythonRecipeError
+
+RECIPE = '''from trainsh import Recipe, local
+
+recipe = Recipe("scripted")
+with local.tmux("work") as tmux:
+    tmux.run_script("scripts/train.sh", "--lr", "3e-4", cwd="/workspace", id="train")
+'''
+SCRIPT = "#!/usr/bin/env bash\necho training \"$@\"\n"
+
+
+class StepScriptTests(unittest.TestCase):
+    def setUp(self):
+        self.tmpdir = tempfile.TemporaryDirectory()
+        self.addCleanup(self.tmpdir.cleanup)
+        self.root = Path(self.tmpdir.name)
+        (self.root / "scripts").mkdir()
+        self.script = self.root / "scripts" / "train.sh"
+        self.script.write_text(SCRIPT, encoding="utf-8")
+
+    def test_run_script_resolves_against_the_recipe_file(self):
+        path = self.root / "scripted.pyrecipe"
+        path.write_text(RECIPE, encoding="utf-8")
+        step = next(step for step in load_recipe(str(path)).steps if step.id == "train")
+        self.assertEqual(step.script, str(self.script))
+        self.assertIn("${TRAINSH_SCRIPT} --lr 3e-4", step.commands)
+        self.assertIn("cd /workspace", step.commands)
+        self.assertNotIn("TRAINSH_SCRIPT", step.raw)
+        self.assertIn("scripts/train.sh --lr 3e-4", step.raw)
+
+        path.write_text(RECIPE.replace("train.sh", "missing.sh"), encoding="utf-8")
+        with self.assertRaisesRegex(PythonRecipeError, "script not found: scripts/missing.sh"):
+            load_recipe(str(path))
+
+    def test_yaml_steps_take_script_and_args(self):
+        path = self.root / "scripted.yaml"
+        path.write_text(
+            "name: scripted\nsteps:\n  - {type: execute, host: work, script: scripts/train.sh, args: [--lr, 3e-4]}\n",
+            encoding="utf-8",
+        )
+        step = load_recipe(str(path)).steps[0]
+        self.assertEqual((step.script, step.commands), (str(self.script), "${TRAINSH_SCRIPT} --lr 3e-4"))
+        self.assertEqual(step.raw, "@work > train.sh --lr 3e-4")
+
+    def test_scripts_are_staged_once_per_content_hash(self):
+        home = self.root / "home"
+        home.mkdir()
+        calls = []
+
+        def fake_ssh(host, command, tty):
+            calls.append(host)
+            return ["bash", "-c", f"cd {home} && {command}"]
+
+        logger = MagicMock()
+        helper = ExecuteHelper(SimpleNamespace(logger=logger), fake_ssh, SimpleNamespace)
+        error, target = helper._stage_script(str(self.script), "gpu-box")
+        self.assertEqual(error, "")
+        self.assertRegex(target, r"^\.cache/trainsh/scripts/[0-9a-f]{16}/train\.sh$")
+        staged = home / target
+        self.assertEqual(staged.read_text(), SCRIPT)
+        self.assertTrue(os.stat(staged).st_mode & stat.S_IXUSR)
+        self.assertEqual(helper._stage_script(str(self.script), "gpu-box"), ("", target))
+        self.assertEqual(calls, ["gpu-box", "gpu-box"])
+
+        self.script.write_text(SCRIPT + "echo v2\n", encoding="utf-8")
+        _, changed = helper._stage_script(str(self.script), "gpu-box")
+        self.assertNotEqual(changed, target)
+
+        with patch.dict(os.environ, {"HOME": str(home / "local")}):
+            error, local_target = helper._stage_script(str(self.script), "local")
+        self.assertEqual((error, local_target), ("", changed))
+        self.assertTrue((home / "local" / changed).is_file())
+        self.assertIn("Cannot read script", helper._stage_script(str(self.root / "nope.sh"), "local")[0])
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,115 @@
+import json
+import os
+import tempfile
+import unittest
+from contextlib import redirect_stdout
+from datetime import datetime, timedelta
+from io import StringIO
+from pathlib import Path
+from unittest.mock import patch
+
+from trainsh.commands import storage as storage_cmd
+from trainsh.core.models import Storage, StorageType
+from trainsh.core.recipe_models import RecipeModel
+from trainsh.services.storage_trash import MANIFEST, StorageTrash
+from tests.runtime_test_utils import isolated_executor
+
+CONFIG = {"storage": {"trash": {"enabled": False, "retention_days": 7}}}
+
+
+class StorageTrashTests(unittest.TestCase):
+    def setUp(self):
+        self.tmpdir = tempfile.TemporaryDirectory()
+        self.addCleanup(self.tmpdir.cleanup)
+        self.root = Path(self.tmpdir.name)
+        (self.root / "runs" / "exp1").mkdir(parents=True)
+        (self.root / "runs" / "exp1" / "metrics.json").write_text("{}")
+        (self.root / "notes.txt").write_text("keep me")
+        self.storage = Storage(name="artifacts", type=StorageType.LOCAL, config={"path": str(self.root)})
+        config = patch("trainsh.config.load_config", return_value=CONFIG)
+        config.start()
+        self.addCleanup(config.stop)
+
+    def test_local_delete_list_restore_and_empty(self):
+        trash = StorageTrash(self.storage)
+        with self.assertRaises(IsADirectoryError):
+            trash.delete("/runs/exp1")
+        entry = trash.delete("/runs/exp1", recursive=True)
+        self.assertFalse((self.root / "runs" / "exp1").exists())
+        self.assertTrue((self.root / entry["trash_path"] / "metrics.json").exists())
+        self.assertEqual([item["original_path"] for item in trash.list()], ["runs/exp1"])
+        self.assertIn("expires_at", trash.list()[0])
+
+        (self.root / "runs" / "exp1").mkdir()
+        with self.assertRaises(FileExistsError):
+            trash.restore(entry["id"])
+        (self.root / "runs" / "exp1").rmdir()
+        restored = trash.restore(entry["id"][:10])
+        self.assertEqual(restored["restored_to"], "runs/exp1")
+        self.assertTrue((self.root / "runs" / "exp1" / "metrics.json").exists())
+        self.assertEqual(trash.list(), [])
+
+        trash.delete("notes.txt")
+        self.assertEqual(len(trash.empty()), 1)
+        self.assertEqual(os.listdir(self.root / ".trash"), ["manifest.json"])
+        with self.assertRaises(ValueError):
+            trash.delete(".trash/manifest.json")
+        with self.assertRaises(FileNotFoundError):
+            trash.delete("missing")
+        with self.assertRaises(ValueError):
+            StorageTrash(Storage(name="hf", type=StorageType.HF, config={"bucket": "team/b"}))
+
+    def test_expired_entries_are_purged_on_next_delete(self):
+        trash = StorageTrash(self.storage)
+        old = trash.delete("notes.txt")
+        manifest = self.root / MANIFEST
+        entries = json.loads(manifest.read_text())
+        entries[0]["deleted_at"] = (datetime.now() - timedelta(days=8)).isoformat()
+        manifest.write_text(json.dumps(entries))
+
+        trash.delete("runs", recursive=True)
+        self.assertEqual([item["original_path"] for item in trash.list()], ["runs"])
+        self.assertFalse((self.root / old["trash_path"]).exists())
+
+    def test_rclone_backend_uses_server_side_moves(self):
+        storage = Storage(name="r2", type=StorageType.R2, config={"bucket": "ckpt"})
+        calls = []
+
+        def fake_cli(_storage, args, stdin=b""):
+            calls.append(args)
+            if args[0] == "cat":
+                raise RuntimeError("object not found")
+            return b""
+
+        with patch("trainsh.services.storage_trash.storage_cli", side_effect=fake_cli):
+            entry = StorageTrash(storage).delete("runs/exp1", recursive=True)
+        self.assertEqual(calls[0], ["lsjson", "--stat", "r2:ckpt/runs/exp1"])
+        self.assertIn(["moveto", "r2:ckpt/runs/exp1", f"r2:ckpt/{entry['trash_path']}"], calls)
+        self.assertEqual(calls[-1], ["rcat", "r2:ckpt/.trash/manifest.json"])
+
+    def test_provider_delete_and_cli(self):
+        recipe = RecipeModel(name="trash", storages={"artifacts": self.storage})
+        with isolated_executor(recipe) as (executor, _config_dir):
+            ok, message = executor._exec_provider_storage_delete({"storage": "artifacts", "path": "/notes.txt", "trash": True})
+            self.assertTrue(ok, message)
+            self.assertIn("Moved to trash", message)
+            ok, listing = executor._exec_provider_storage_trash("trash_list", {"storage": "artifacts"})
+            entry_id = json.loads(listing)[0]["id"]
+            ok, message = executor._exec_provider_storage_trash("trash_restore", {"storage": "artifacts", "id": entry_id})
+            self.assertTrue(ok, message)
+            self.assertEqual((self.root / "notes.txt").read_text(), "keep me")
+
+        out = StringIO()
+        with patch("trainsh.commands.storage.load_storages", return_value={"artifacts": self.storage}), redirect_stdout(out):
+            storage_cmd.main(["delete", "artifacts", "runs", "--recursive"])
+            storage_cmd.main(["trash", "artifacts", "list"])
+            with patch("trainsh.commands.storage.prompt_input", return_value="y"):
+                storage_cmd.main(["delete", "artifacts", "notes.txt", "--hard"])
+        self.assertIn("train storage trash artifacts restore", out.getvalue())
+        self.assertIn("  runs", out.getvalue())
+        self.assertFalse((self.root / "notes.txt").exists())
+        self.assertEqual(len(StorageTrash(self.storage).list()), 1)
+
+
+if __name__ == "__main__":
+    unittest.main()
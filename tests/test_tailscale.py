@@ -0,0 +1,90 @@
+import json
+import subprocess
+import unittest
+from contextlib import redirect_stdout
+from io import StringIO
+from unittest.mock import patch
+
+from trainsh.commands import host
+from trainsh.core.models import Host, HostType
+from trainsh.services import tailscale
+from trainsh.services.ssh import SSHClient
+
+STATUS = {
+    "BackendState": "Running",
+    "Self": {"HostName": "laptop", "DNSName": "laptop.tail1234.ts.net.", "TailscaleIPs": ["100.64.0.1"], "OS": "macOS"},
+    "Peer": {
+        "key1": {
+            "HostName": "Home-GPU",
+            "DNSName": "home-gpu.tail1234.ts.net.",
+            "TailscaleIPs": ["100.101.102.103", "fd7a:115c:a1e0::1"],
+            "Online": True,
+            "OS": "linux",
+        },
+        "key2": {
+            "HostName": "nas",
+            "DNSName": "nas.tail1234.ts.net.",
+            "TailscaleIPs": ["100.64.0.9"],
+            "Online": False,
+            "LastSeen": "2026-10-01T08:00:00Z",
+        },
+    },
+}
+
+
+def status_run(stdout=json.dumps(STATUS), returncode=0):
+    return patch(
+        "trainsh.services.tailscale.subprocess.run",
+        return_value=subprocess.CompletedProcess([], returncode, stdout=stdout, stderr=""),
+    )
+
+
+class TailscaleTests(unittest.TestCase):
+    def setUp(self):
+        patcher = patch("trainsh.config.load_config", return_value={})
+        patcher.start()
+        self.addCleanup(patcher.stop)
+
+    def test_resolve_nodes_by_name_and_report_offline(self):
+        nodes = tailscale.list_nodes(STATUS)
+        self.assertEqual([node["name"] for node in nodes], ["laptop", "Home-GPU", "nas"])
+        node = tailscale.resolve_node("home-gpu", STATUS)
+        self.assertEqual(tailscale.node_address(node), "100.101.102.103")
+        self.assertEqual(tailscale.find_node(nodes, "home-gpu.tail1234.ts.net"), node)
+        with self.assertRaisesRegex(tailscale.TailscaleError, "nas is offline, last seen 2026-10-01"):
+            tailscale.resolve_node("nas", STATUS)
+        with self.assertRaisesRegex(tailscale.TailscaleError, "not found"):
+            tailscale.resolve_node("gone", STATUS)
+
+    def test_status_errors_are_explained(self):
+        with patch("trainsh.services.tailscale.subprocess.run", side_effect=FileNotFoundError):
+            with self.assertRaisesRegex(tailscale.TailscaleError, "CLI not found"):
+                tailscale.tailscale_status()
+        with status_run(json.dumps({"BackendState": "NeedsLogin"})):
+            with self.assertRaisesRegex(tailscale.TailscaleError, "tailscale up"):
+                tailscale.tailscale_status()
+
+    def test_linked_host_connects_to_current_tailnet_address(self):
+        box = Host(name="home", type=HostType.SSH, hostname="192.168.1.20", username="me", env_vars={"tailscale_node": "home-gpu"})
+        with status_run():
+            client = SSHClient.from_host(box)
+        self.assertEqual(client.hostname, "100.101.102.103")
+        self.assertEqual(
+            [(target.hostname, target.source) for target in client.connection_targets],
+            [("100.101.102.103", "tailscale"), ("home-gpu.tail1234.ts.net", "tailscale-dns")],
+        )
+        self.assertEqual(box.hostname, "192.168.1.20")
+
+        saved = {}
+        with status_run(), patch("trainsh.commands.host.load_hosts", return_value={"home": Host(name="home", hostname="x")}), patch(
+            "trainsh.commands.host.save_hosts", side_effect=saved.update
+        ):
+            out = StringIO()
+            with redirect_stdout(out):
+                host.main(["tailscale", "link", "home", "home-gpu"])
+        self.assertEqual(saved["home"].env_vars["tailscale_node"], "Home-GPU")
+        self.assertIn("(online)", out.getvalue())
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,97 @@
+import shutil
+import tempfile
+import unittest
+from contextlib import redirect_stdout
+from io import StringIO
+from pathlib import Path
+from types import SimpleNamespace
+from unittest.mock import MagicMock, patch
+
+from trainsh.commands import transfer
+from trainsh.core.executor_transfer import TransferHelper
+from trainsh.core.models import Host, HostType, TransferEndpoint
+from trainsh.services.tar_stream import pack_command, stream_command, suggest_tar_stream, unpack_command
+from trainsh.services.transfer_engine import TransferEngine, TransferResult
+
+CONFIG = {"transfer": {"tar_stream": {"level": 5, "suggest_files": 3}}}
+
+
+class TarStreamTests(unittest.TestCase):
+    def test_commands_quote_paths_and_suggest_for_large_dirs(self):
+        pack = pack_command("~/data set/", exclude=["*.tmp"], level=7)
+        self.assertIn("tar -C \"$HOME\"/'data set' --exclude='*.tmp' -cf - .", pack)
+        self.assertIn("zstd -q -T0 -7 -c", pack)
+        self.assertIn("mkdir -p /out && zstd -q -d -c | tar -C /out -xf -", unpack_command("/out/"))
+
+        producer, consumer = stream_command("/src", "/dst", dst_ssh=["ssh", "gpu"])
+        self.assertEqual(producer[:2], ["sh", "-c"])
+        self.assertEqual(consumer[:2], ["ssh", "gpu"])
+
+        with tempfile.TemporaryDirectory() as tmpdir:
+            for index in range(3):
+                Path(tmpdir, f"f{index}").write_text("x")
+            self.assertIn("--tar-stream", suggest_tar_stream(tmpdir, config=CONFIG))
+            self.assertIsNone(suggest_tar_stream(tmpdir, config={}))
+
+    @unittest.skipUnless(shutil.which("zstd") and shutil.which("tar"), "tar/zstd not installed")
+    def test_local_stream_round_trip(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            src = Path(tmpdir, "src")
+            (src / "nested").mkdir(parents=True)
+            (src / "nested" / "a.txt").write_text("alpha")
+            (src / "skip.tmp").write_text("junk")
+            engine = TransferEngine(tar_stream=True)
+            with patch("trainsh.config.load_config", return_value=CONFIG):
+                result = engine.rsync(str(src), str(Path(tmpdir, "dst")), exclude=["*.tmp"])
+            self.assertTrue(result.success, result.message)
+            self.assertEqual(Path(tmpdir, "dst", "nested", "a.txt").read_text(), "alpha")
+            self.assertFalse(Path(tmpdir, "dst", "skip.tmp").exists())
+
+            self.assertIn("cannot delete", engine.rsync(str(src), tmpdir, delete=True).message)
+
+    def test_host_to_host_relays_through_local_pipeline(self):
+        hosts = {
+            name: Host(name=name, type=HostType.SSH, hostname=f"{name}.example", username="root")
+            for name in ("a", "b")
+        }
+        engine = TransferEngine(tar_stream=True)
+        with patch("trainsh.config.load_config", return_value=CONFIG), patch(
+            "trainsh.services.tar_stream.run_pipeline",
+            return_value=TransferResult(success=True, exit_code=0, message="ok"),
+        ) as run:
+            result = engine.transfer(
+                TransferEndpoint(type="host", path="/data", host_id="a"),
+                TransferEndpoint(type="host", path="/data", host_id="b"),
+                hosts=hosts,
+            )
+        self.assertTrue(result.success)
+        producer, consumer = run.call_args.args
+        self.assertEqual(producer[-2], "root@a.example")
+        self.assertIn("zstd -q -T0 -5 -c", producer[-1])
+        self.assertEqual(consumer[-2], "root@b.example")
+
+    def test_cli_flag_and_recipe_option_reach_engine(self):
+        engine = MagicMock()
+        engine.rsync.return_value = SimpleNamespace(success=True, message="ok", bytes_transferred=0)
+        with patch("trainsh.services.transfer_engine.TransferEngine", return_value=engine) as engine_cls, redirect_stdout(StringIO()):
+            transfer.main(["./a", "./b", "--tar-stream"])
+        self.assertTrue(engine_cls.call_args.kwargs["tar_stream"])
+
+        out = StringIO()
+        with redirect_stdout(out), self.assertRaises(SystemExit):
+            transfer.main(["./a", "r2:bucket/prefix", "--tar-stream"])
+        self.assertIn("--tar-stream only applies", out.getvalue())
+
+        executor = SimpleNamespace(_interpolate=lambda text: text, logger=None, recipe=SimpleNamespace(hosts={}, storages={}))
+        helper = TransferHelper(executor, resolve_vast_host=str, host_from_ssh_spec=lambda spec: None)
+        engine.transfer.return_value = TransferResult(success=True, exit_code=0, message="ok")
+        with patch("trainsh.services.transfer_engine.TransferEngine", return_value=engine) as engine_cls, patch(
+            "trainsh.commands.host.load_hosts", return_value={}
+        ), patch("trainsh.commands.storage.load_storages", return_value={}):
+            ok, _ = helper.transfer("./a", "./b", tar_stream=True)
+        self.assertTrue(ok)
+        self.assertTrue(engine_cls.call_args.kwargs["tar_stream"])
+
+
+if __name__ == "__main__":
+    unittest.main()
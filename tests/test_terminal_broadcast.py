@@ -0,0 +1,113 @@
+import functools
+import io
+import os
+import tempfile
+import unittest
+from contextlib import redirect_stdout
+from pathlib import Path
+from types import SimpleNamespace
+from unittest.mock import patch
+
+from trainsh.commands.host_broadcast import cmd_broadcast
+from trainsh.services import terminal_broadcast
+from trainsh.services.terminal_broadcast import (
+    BroadcastTarget,
+    broadcast_write,
+    configured_groups,
+    remove_group,
+    save_group,
+)
+
+
+class FakeTmux:
+    def __init__(self, host, sessions=(), fail=False):
+        self.host = host
+        self.sessions = set(sessions)
+        self.fail = fail
+        self.sent = []
+
+    def has_session(self, session):
+        return session in self.sessions
+
+    def new_session(self, session, detached=True, command=None):
+        self.sessions.add(session)
+        return SimpleNamespace(returncode=0, stderr="")
+
+    def send_keys(self, target, text, enter=True, literal=True):
+        if self.fail:
+            return SimpleNamespace(returncode=1, stderr="lost connection")
+        self.sent.append((target, text, enter))
+        return SimpleNamespace(returncode=0, stderr="")
+
+
+class TerminalBroadcastTests(unittest.TestCase):
+    def setUp(self):
+        self.tmpdir = tempfile.TemporaryDirectory()
+        self.addCleanup(self.tmpdir.cleanup)
+        root = Path(self.tmpdir.name)
+        patchers = [
+            patch("trainsh.config.CONFIG_FILE", root / "config.yaml"),
+            patch("trainsh.config.CONFIG_DIR", root),
+            patch("trainsh.constants.CONFIG_DIR", root),
+            patch("trainsh.constants.ACTIVE_PROFILE_FILE", root / "active_profile"),
+        ]
+        for patcher in patchers:
+            patcher.start()
+            self.addCleanup(patcher.stop)
+        os.environ.pop("TRAINSH_PROFILE", None)
+
+    def test_targets_and_groups_round_trip_through_config(self):
+        self.assertEqual(BroadcastTarget.parse("gpu-1:train"), BroadcastTarget("gpu-1", "train"))
+        self.assertEqual(str(BroadcastTarget.parse("gpu-2")), "gpu-2")
+        with self.assertRaises(ValueError):
+            BroadcastTarget.parse(":train")
+        with self.assertRaises(ValueError):
+            save_group("bad name", ["gpu-1"])
+
+        save_group("workers", ["gpu-1", "gpu-2:train"])
+        self.assertEqual(configured_groups(), {"workers": ["gpu-1", "gpu-2:train"]})
+        self.assertTrue(remove_group("workers"))
+        self.assertFalse(remove_group("workers"))
+        self.assertEqual(configured_groups(), {})
+
+    def test_write_reaches_every_terminal_and_reports_failures(self):
+        clients = {
+            "gpu-1": FakeTmux("gpu-1"),
+            "gpu-2": FakeTmux("gpu-2", sessions=["train"]),
+            "gpu-3": FakeTmux("gpu-3"),
+            "gpu-4": FakeTmux("gpu-4", fail=True),
+        }
+        results = broadcast_write(
+            "workers",
+            "nvidia-smi",
+            targets=["gpu-1", "gpu-2:train", "gpu-3:missing", "gpu-4"],
+            client_factory=clients.__getitem__,
+        )
+
+        self.assertEqual([result.ok for result in results], [True, True, False, False])
+        self.assertEqual(clients["gpu-1"].sent, [("train-broadcast-workers", "nvidia-smi", True)])
+        self.assertEqual(clients["gpu-2"].sent, [("train", "nvidia-smi", True)])
+        self.assertEqual(results[0].line("nvidia-smi"), "[gpu-1:train-broadcast-workers] sent: nvidia-smi")
+        self.assertEqual(results[2].line("nvidia-smi"), "[gpu-3:missing] failed: no such tmux session")
+        self.assertEqual(results[3].detail, "lost connection")
+
+        with self.assertRaises(KeyError):
+            broadcast_write("unknown", "ls")
+
+    def test_send_command_echoes_per_terminal(self):
+        save_group("workers", ["gpu-1", "gpu-2"])
+        clients = {"gpu-1": FakeTmux("gpu-1"), "gpu-2": FakeTmux("gpu-2")}
+        out = io.StringIO()
+        send = functools.partial(broadcast_write, client_factory=clients.__getitem__)
+        with patch.object(terminal_broadcast, "broadcast_write", send), redirect_stdout(out):
+            cmd_broadcast(["send", "workers", "--no-enter", "--", "df", "-h"])
+
+        self.assertEqual(
+            out.getvalue().splitlines(),
+            ["[gpu-1:train-broadcast-workers] sent: df -h", "[gpu-2:train-broadcast-workers] sent: df -h"],
+        )
+        self.assertEqual(clients["gpu-1"].sent, [("train-broadcast-workers", "df -h", False)])
+
+
+if __name__ == "__main__":
+    unittest.main()
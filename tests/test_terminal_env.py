@@ -0,0 +1,76 @@
+import unittest
+from types import SimpleNamespace
+from unittest.mock import patch
+
+from trainsh.core import executor_utils
+from trainsh.core.models import Host
+from trainsh.services.ssh import SSHClient
+from trainsh.services.terminal_env import (
+    default_env_vars,
+    env_preamble,
+    fit_window,
+    send_env_options,
+    session_defaults,
+)
+
+CONFIG = {
+    "terminal": {
+        "term": "screen-256color",
+        "locale": "C.UTF-8",
+        "send_env": ["LANG", "LC_*"],
+        "default_env_vars": {"PYTHONUNBUFFERED": "1", "bad name": "x", "HF_HOME": "/data/hf"},
+    }
+}
+
+
+class FakeTmux:
+    def __init__(self):
+        self.calls = []
+
+    def resize_window(self, target, width, height):
+        self.calls.append(("resize-window", target, width, height))
+
+    def run(self, *args):
+        self.calls.append(args)
+
+
+class TerminalEnvTests(unittest.TestCase):
+    def test_preamble_and_send_env_follow_config(self):
+        self.assertEqual(default_env_vars(CONFIG), {"PYTHONUNBUFFERED": "1", "HF_HOME": "/data/hf"})
+        self.assertEqual(env_preamble(CONFIG), "TERM=screen-256color LC_ALL=C.UTF-8 PYTHONUNBUFFERED=1 HF_HOME=/data/hf")
+        self.assertEqual(env_preamble({}), "TERM=xterm-256color LC_ALL=en_US.UTF-8")
+        self.assertEqual(send_env_options(CONFIG), ["-o", "SendEnv=LANG LC_*"])
+        self.assertEqual(send_env_options({"terminal": {"send_env": []}}), [])
+
+    def test_ssh_args_carry_send_env_and_preamble(self):
+        with patch("trainsh.config.load_config", return_value=CONFIG):
+            interactive = SSHClient(hostname="gpu.example.com")._build_ssh_args(None, interactive=True)
+            batch = SSHClient(hostname="gpu.example.com")._build_ssh_args("true")
+            with patch.object(executor_utils, "_configured_host_for_spec", return_value=None):
+                raw = executor_utils._build_ssh_args("root@gpu", command="tmux attach", tty=True, set_term=True)
+
+        self.assertIn("SendEnv=LANG LC_*", interactive)
+        self.assertNotIn("SendEnv=LANG LC_*", batch)
+        self.assertIn("SendEnv=LANG LC_*", raw)
+        self.assertEqual(raw[-1], "TERM=screen-256color LC_ALL=C.UTF-8 PYTHONUNBUFFERED=1 HF_HOME=/data/hf tmux attach")
+
+    def test_default_env_sits_under_host_defaults(self):
+        host = Host(name="gpu", hostname="gpu.example.com", defaults={"env": {"HF_HOME": "/workspace/hf"}})
+        defaults = session_defaults(host, CONFIG)
+        self.assertEqual(defaults.env, {"PYTHONUNBUFFERED": "1", "HF_HOME": "/workspace/hf"})
+        self.assertIn("exec", defaults.session_command())
+        self.assertIsNone(session_defaults(Host(name="cpu", hostname="cpu"), {}).session_command())
+
+    def test_fit_window_resizes_then_follows_clients(self):
+        tmux = FakeTmux()
+        self.assertTrue(fit_window(tmux, "train_1", (160, 48)))
+        self.assertEqual(
+            tmux.calls,
+            [("resize-window", "train_1", 160, 48), ("set-option", "-t", "train_1", "window-size", "latest")],
+        )
+        with patch("trainsh.services.terminal_env.shutil.get_terminal_size", return_value=SimpleNamespace(columns=0, lines=0)):
+            self.assertFalse(fit_window(FakeTmux(), "train_1"))
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,126 @@
+import shlex
+import tempfile
+import unittest
+from pathlib import Path
+from types import SimpleNamespace
+from unittest.mock import patch
+
+from trainsh.core.bridge_exec import BridgeExecutionHelper
+from trainsh.core.executor_utils import _build_ssh_args
+from trainsh.core.runtime_store import RuntimeStore
+from trainsh.services.ssh import SSHClient
+from trainsh.services.terminal_keepalive import (
+    CONNECTION_LOST_EVENT,
+    CONNECTION_RESTORED_EVENT,
+    KeepalivePolicy,
+    TerminalSupervisor,
+    emit_terminal_event,
+    keepalive_options,
+    keepalive_policy,
+)
+
+
+class TerminalKeepaliveTests(unittest.TestCase):
+    def test_policy_and_ssh_options(self):
+        policy = keepalive_policy({"terminal": {"keepalive_interval_secs": 20, "keepalive_count_max": 4, "reconnect": False}})
+        self.assertEqual(policy.interval_secs, 20)
+        self.assertFalse(policy.reconnect)
+        self.assertEqual(keepalive_options(policy), ["-o", "ServerAliveInterval=20", "-o", "ServerAliveCountMax=4"])
+        self.assertEqual(keepalive_options(KeepalivePolicy(interval_secs=0)), [])
+
+        with patch("trainsh.services.terminal_keepalive.keepalive_policy", return_value=KeepalivePolicy()):
+            client = SSHClient(hostname="gpu.example.com")
+            self.assertIn("ServerAliveInterval=15", client._build_ssh_args(None, interactive=True))
+            self.assertNotIn("ServerAliveInterval=15", client._build_ssh_args("true"))
+
+            # A spec's own keep-alive options win; the defaults only fill the gaps after them.
+            args = _build_ssh_args("-o ServerAliveInterval=60 root@gpu", tty=True)
+            self.assertIn("ServerAliveInterval=60", args)
+            self.assertNotIn("ServerAliveInterval=15", args)
+            self.assertLess(args.index("ServerAliveInterval=60"), args.index("ServerAliveCountMax=3"))
+        self.assertEqual(
+            keepalive_options(KeepalivePolicy(), existing=["-oServerAliveCountMax=9"]),
+            ["-o", "ServerAliveInterval=15"],
+        )
+
+    def test_supervisor_reconnects_after_connection_loss(self):
+        exits = iter([255, 0])
+        probes = iter([False, False, True])
+        events, sleeps, lines = [], [], []
+        supervisor = TerminalSupervisor(
+            lambda: next(exits),
+            lambda: next(probes),
+            window="main",
+            host="gpu",
+            run_id="job-1",
+            policy=KeepalivePolicy(delay_secs=1, max_delay_secs=3),
+            emit=lambda event, **payload: events.append((event, payload)),
+            sleep=sleeps.append,
+            clock=iter([10.0, 17.5]).__next__,
+            log=lines.append,
+        )
+
+        self.assertEqual(supervisor.run(), 0)
+        self.assertEqual(sleeps, [1, 2, 3])
+        self.assertEqual([event for event, _ in events], [CONNECTION_LOST_EVENT, CONNECTION_RESTORED_EVENT])
+        self.assertEqual(events[1][1]["attempts"], 3)
+        self.assertEqual(events[1][1]["downtime_secs"], 7.5)
+        self.assertEqual(events[1][1]["run_id"], "job-1")
+        self.assertIn("restored", lines[-1])
+
+    def test_supervisor_gives_up_and_passes_other_exits_through(self):
+        supervisor = TerminalSupervisor(
+            lambda: 255,
+            lambda: False,
+            window="main",
+            host="gpu",
+            policy=KeepalivePolicy(max_attempts=2),
+            emit=lambda *args, **kwargs: None,
+            sleep=lambda _secs: None,
+            log=lambda _line: None,
+        )
+        self.assertEqual(supervisor.run(), 255)
+
+        no_reconnect = TerminalSupervisor(
+            lambda: 255, lambda: True, window="main", host="gpu", policy=KeepalivePolicy(reconnect=False)
+        )
+        self.assertEqual(no_reconnect.run(), 255)
+
+    def test_events_are_recorded_under_the_job(self):
+        with tempfile.TemporaryDirectory() as tmpdir, patch(
+            "trainsh.core.runtime_store.RUNTIME_STATE_DIR", Path(tmpdir)
+        ):
+            emit_terminal_event(CONNECTION_LOST_EVENT, run_id="job-1", window="main", host="gpu")
+            events = RuntimeStore(Path(tmpdir)).list_events("job-1")
+        self.assertEqual(events[0]["event"], CONNECTION_LOST_EVENT)
+        self.assertEqual(events[0]["payload"], {"window": "main", "host": "gpu"})
+
+    def test_remote_bridge_panes_run_under_recipe_attach(self):
+        connected = {}
+
+        def connect(name, command):
+            connected[name] = command
+            return True, "ok"
+
+        helper = BridgeExecutionHelper(
+            tmux_bridge=SimpleNamespace(job_id="job-1", connect=connect),
+            prefer_bridge_exec=True,
+            bridge_remote_status="keep",
+            get_tmux_client=lambda host: SimpleNamespace(build_attach_command=lambda session, status_mode: f"ssh -t {host} tmux attach -t {session}"),
+            log=lambda msg: None,
+            log_detail=lambda *args, **kwargs: None,
+            format_duration=str,
+        )
+        with patch("trainsh.services.terminal_keepalive.keepalive_policy", return_value=KeepalivePolicy()):
+            helper.ensure_bridge_window(SimpleNamespace(name="main", host="gpu", remote_session="sess"))
+        args = shlex.split(connected["main"])
+        self.assertEqual(args[1:6], ["-m", "trainsh", "recipe", "attach", "job-1"])
+        self.assertEqual(args[args.index("--command") + 1], "ssh -t gpu tmux attach -t sess")
+
+        with patch("trainsh.services.terminal_keepalive.keepalive_policy", return_value=KeepalivePolicy(reconnect=False)):
+            helper.ensure_bridge_window(SimpleNamespace(name="other", host="gpu", remote_session="sess2"))
+        self.assertEqual(connected["other"], "ssh -t gpu tmux attach -t sess2")
+
+
+if __name__ == "__main__":
+    unittest.main()
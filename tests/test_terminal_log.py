@@ -0,0 +1,75 @@
+import os
+import tempfile
+import unittest
+from contextlib import redirect_stdout
+from io import StringIO
+from pathlib import Path
+from types import SimpleNamespace
+from unittest.mock import patch
+
+from trainsh.commands.host_logs import cmd_logs
+from trainsh.services import terminal_log
+from trainsh.services.ssh import SSHClient
+
+
+class TerminalLogTests(unittest.TestCase):
+    def test_writer_rotates_by_size_and_keeps_n_files(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            path = Path(tmpdir) / "box.log"
+            writer = terminal_log.RotatingLogWriter(path, max_bytes=10, keep=2)
+            for chunk in (b"aaaaaaaa", b"bbbbbbbb", b"cccccccc", b"dddd"):
+                writer.write(chunk)
+            writer.close()
+            self.assertEqual(path.read_bytes(), b"dddd")
+            self.assertEqual((Path(tmpdir) / "box.log.1").read_bytes(), b"cccccccc")
+            self.assertEqual((Path(tmpdir) / "box.log.2").read_bytes(), b"bbbbbbbb")
+            self.assertFalse((Path(tmpdir) / "box.log.3").exists())
+
+    def test_mirrored_session_is_logged_and_read_back(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            path = Path(tmpdir) / "gpu-box.log"
+            # pty.spawn copies the child's output straight to fd 1.
+            saved = os.dup(1)
+            devnull = os.open(os.devnull, os.O_WRONLY)
+            os.dup2(devnull, 1)
+            try:
+                code = terminal_log.run_mirrored(["sh", "-c", "printf '\\033[1mloss 0.5\\033[0m\\n'; exit 3"], path)
+            finally:
+                os.dup2(saved, 1)
+                os.close(saved)
+                os.close(devnull)
+            self.assertEqual(code, 3)
+            text = terminal_log.read_terminal_log(path)
+            self.assertIn("\nloss 0.5\n", text)
+            self.assertIn("=== exit 3 after", text)
+            self.assertIn("\x1b[1m", terminal_log.read_terminal_log(path, raw=True))
+            self.assertEqual(terminal_log.read_terminal_log(path, lines=1).strip().split()[:3], ["===", "exit", "3"])
+
+    def test_ssh_log_flag_mirrors_the_interactive_session(self):
+        client = SSHClient("gpu.example.com")
+        with patch("trainsh.services.terminal_log.run_interactive", return_value=0) as mirrored, patch(
+            "trainsh.services.ssh.subprocess.run", return_value=SimpleNamespace(returncode=0)
+        ) as plain:
+            self.assertEqual(client.connect_interactive(log_name="gpu-box"), 0)
+            self.assertEqual(mirrored.call_args.args[1], "gpu-box")
+            plain.assert_not_called()
+
+    def test_cli_lists_and_prints_logs(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            root = Path(tmpdir)
+            (root / "gpu-box.log").write_bytes(b"one\r\n\x1b[32mtwo\x1b[0m\r\n")
+            (root / "gpu-box.log.1").write_bytes(b"old\n")
+            out = StringIO()
+            with patch("trainsh.services.terminal_log.TERMINAL_LOGS_DIR", root), redirect_stdout(out):
+                cmd_logs([])
+                cmd_logs(["gpu-box", "-n", "1"])
+                cmd_logs(["gpu-box", "--path"])
+            text = out.getvalue()
+            self.assertRegex(text, r"gpu-box +\d+ +1 ")
+            self.assertIn("\ntwo\n", text)
+            self.assertNotIn("one", text)
+            self.assertTrue(text.rstrip().endswith("gpu-box.log"))
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,102 @@
+import unittest
+from types import SimpleNamespace
+from unittest.mock import patch
+
+from trainsh.services.terminal_paste import (
+    LARGE_PASTE_EVENT,
+    PastePolicy,
+    paste_policy,
+    paste_text,
+    split_chunks,
+)
+
+
+class FakeTmux:
+    def __init__(self, fail_on=""):
+        self.calls = []
+        self.fail_on = fail_on
+
+    def _result(self, name):
+        if name == self.fail_on:
+            return SimpleNamespace(returncode=1, stderr=f"{name} failed")
+        return SimpleNamespace(returncode=0, stderr="")
+
+    def run(self, *args):
+        self.calls.append(args)
+        return self._result(args[0])
+
+    def send_keys(self, target, text, enter=True, literal=True):
+        self.calls.append(("send-keys", target, text, literal))
+        return self._result("send-keys")
+
+
+class TerminalPasteTests(unittest.TestCase):
+    def test_chunks_split_at_line_ends_and_on_character_boundaries(self):
+        text = "".join(f"line {i}\n" for i in range(10))
+        chunks = split_chunks(text, 20)
+        self.assertEqual("".join(chunks), text)
+        self.assertTrue(all(len(chunk.encode()) <= 20 and chunk.endswith("\n") for chunk in chunks))
+
+        wide = "é" * 15
+        pieces = split_chunks(wide, 7)
+        self.assertEqual("".join(pieces), wide)
+        self.assertTrue(all(len(piece.encode()) <= 7 for piece in pieces))
+
+        policy = paste_policy({"terminal": {"paste_bracketed": False, "paste_chunk_bytes": 10, "paste_confirm_bytes": 0}})
+        self.assertFalse(policy.bracketed)
+        self.assertEqual(policy.chunk_bytes, 256)
+        self.assertEqual(policy.confirm_bytes, 0)
+
+    def test_bracketed_paste_loads_one_buffer_and_pastes_once(self):
+        client = FakeTmux()
+        text = "x" * 300 + "\n" + "y" * 300 + "\n"
+        result = paste_text(client, "train", text, enter=True, policy=PastePolicy(chunk_bytes=256, confirm_bytes=0))
+
+        self.assertTrue(result.ok)
+        self.assertEqual(result.bytes, len(text))
+        set_calls = [call for call in client.calls if call[0] == "set-buffer"]
+        self.assertEqual(len(set_calls), result.chunks)
+        self.assertNotIn("-a", set_calls[0])
+        self.assertIn("-a", set_calls[1])
+        self.assertEqual("".join(call[-1] for call in set_calls), text)
+        buffer = set_calls[0][2]
+        self.assertIn(("paste-buffer", "-d", "-p", "-b", buffer, "-t", "train"), client.calls)
+        self.assertEqual(client.calls[-1], ("send-keys", "train", "Enter", False))
+
+        failing = FakeTmux(fail_on="paste-buffer")
+        result = paste_text(failing, "train", "ls\n", policy=PastePolicy(confirm_bytes=0))
+        self.assertFalse(result.ok)
+        self.assertEqual(failing.calls[-1][0], "delete-buffer")
+
+    def test_unbracketed_paste_types_chunks_with_a_pause(self):
+        client = FakeTmux()
+        sleeps = []
+        text = "".join(f"echo {i}\n" for i in range(100))
+        result = paste_text(
+            client, "train", text, policy=PastePolicy(bracketed=False, chunk_bytes=256, chunk_delay_ms=50, confirm_bytes=0), sleep=sleeps.append
+        )
+        self.assertTrue(result.ok)
+        self.assertEqual("".join(call[2] for call in client.calls), text)
+        self.assertEqual(sleeps, [0.05] * (result.chunks - 1))
+
+    def test_large_paste_needs_confirmation(self):
+        events = []
+        policy = PastePolicy(confirm_bytes=10)
+        with patch(
+            "trainsh.services.terminal_keepalive.emit_terminal_event",
+            lambda event, **payload: events.append((event, payload)),
+        ):
+            refused = paste_text(FakeTmux(), "train", "x" * 11, policy=policy)
+            client = FakeTmux()
+            accepted = paste_text(client, "train", "x" * 11, policy=policy, confirm=lambda target, text: True)
+
+        self.assertFalse(refused.ok)
+        self.assertIn("not confirmed", refused.detail)
+        self.assertTrue(accepted.ok)
+        self.assertEqual([event for event, _ in events], [LARGE_PASTE_EVENT, LARGE_PASTE_EVENT])
+        self.assertEqual([payload["accepted"] for _, payload in events], [False, True])
+        self.assertEqual(events[0][1]["bytes"], 11)
+
+
+if __name__ == "__main__":
+    unittest.main()
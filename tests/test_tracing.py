@@ -0,0 +1,141 @@
+import json
+import tempfile
+import threading
+import unittest
+from contextlib import redirect_stdout
+from io import StringIO
+from pathlib import Path
+from unittest.mock import patch
+
+from trainsh.core.execution_log import ExecutionLogReader
+from trainsh.core.executor_main import run_recipe
+from trainsh.services.tracing import Tracer, child_span, export_spans, otlp_payload, span, traces_url, tracing_settings
+from tests.runtime_test_utils import ok_response
+
+
+class TracerTests(unittest.TestCase):
+    def test_spans_nest_per_thread_and_noop_outside_a_trace(self):
+        with span("orphan") as item:
+            item.set("ignored", 1)
+
+        tracer = Tracer("svc")
+        root = tracer.start_span("recipe demo")
+
+        def worker():
+            with child_span(tracer, root, "step b"):
+                with span("ssh", **{"net.peer.name": "gpu"}) as inner:
+                    inner.set("ssh.exit_code", 255)
+                    inner.fail("refused")
+
+        with child_span(tracer, root, "step a"):
+            thread = threading.Thread(target=worker)
+            thread.start()
+            thread.join()
+            with self.assertRaises(ValueError):
+                with span("transfer"):
+                    raise ValueError("boom")
+        tracer.end_span(root)
+
+        by_name = {item.name: item for item in tracer.spans}
+        self.assertEqual(set(by_name), {"recipe demo", "step a", "step b", "ssh", "transfer"})
+        self.assertEqual(by_name["step b"].parent_id, root.span_id)
+        self.assertEqual(by_name["ssh"].parent_id, by_name["step b"].span_id)
+        self.assertEqual(by_name["transfer"].parent_id, by_name["step a"].span_id)
+        self.assertEqual(by_name["transfer"].error, "ValueError: boom")
+        self.assertEqual({item.trace_id for item in tracer.spans}, {tracer.trace_id})
+
+        payload = otlp_payload(tracer, [by_name["ssh"]])
+        resource = payload["resourceSpans"][0]
+        self.assertIn({"key": "service.name", "value": {"stringValue": "svc"}}, resource["resource"]["attributes"])
+        encoded = resource["scopeSpans"][0]["spans"][0]
+        self.assertEqual(encoded["status"], {"code": 2, "message": "refused"})
+        self.assertIn({"key": "ssh.exit_code", "value": {"intValue": "255"}}, encoded["attributes"])
+        self.assertEqual(encoded["parentSpanId"], by_name["step b"].span_id)
+
+    def test_settings_and_export(self):
+        with patch.dict("os.environ", {"OTEL_EXPORTER_OTLP_ENDPOINT": ""}):
+            self.assertFalse(tracing_settings({})["enabled"])
+        with patch.dict("os.environ", {"OTEL_EXPORTER_OTLP_ENDPOINT": "http://collector:4318"}):
+            self.assertEqual(tracing_settings({})["otlp_endpoint"], "http://collector:4318")
+            self.assertEqual(tracing_settings({"tracing": {"otlp_endpoint": "http://mine"}})["otlp_endpoint"], "http://mine")
+        self.assertEqual(traces_url("http://c:4318/"), "http://c:4318/v1/traces")
+        self.assertEqual(traces_url("http://c:4318/v1/traces"), "http://c:4318/v1/traces")
+
+        tracer = Tracer()
+        with child_span(tracer, None, "only"):
+            pass
+        with patch("trainsh.services.tracing.urlopen", return_value=ok_response()) as urlopen:
+            self.assertEqual(export_spans(tracer, "http://c:4318", headers={"Authorization": "Bearer t"}), 1)
+        request = urlopen.call_args.args[0]
+        self.assertEqual(request.full_url, "http://c:4318/v1/traces")
+        self.assertEqual(request.get_header("Authorization"), "Bearer t")
+        self.assertEqual(json.loads(request.data)["resourceSpans"][0]["scopeSpans"][0]["spans"][0]["name"], "only")
+
+    def test_ssh_client_calls_become_client_spans(self):
+        from types import SimpleNamespace
+
+        from trainsh.services.ssh import SSHClient
+
+        tracer = Tracer()
+        client = SSHClient("gpu-box", port=2222, username="root")
+        done = SimpleNamespace(returncode=0, stdout="ok", stderr="")
+        with child_span(tracer, None, "step fit"), patch("trainsh.services.ssh.subprocess.run", return_value=done):
+            self.assertTrue(client.run("nvidia-smi").success)
+        ssh_span = next(item for item in tracer.spans if item.name == "ssh")
+        self.assertEqual(ssh_span.kind, 3)
+        self.assertEqual(
+            {key: ssh_span.attributes[key] for key in ("net.peer.name", "net.peer.port", "ssh.multiplexed", "ssh.exit_code")},
+            {"net.peer.name": "gpu-box", "net.peer.port": 2222, "ssh.multiplexed": False, "ssh.exit_code": 0},
+        )
+
+
+class RunTracingTests(unittest.TestCase):
+    def setUp(self):
+        self.tmpdir = tempfile.TemporaryDirectory()
+        self.addCleanup(self.tmpdir.cleanup)
+        self.root = Path(self.tmpdir.name)
+        self.runtime = self.root / "config" / "runtime"
+        config = {"tmux": {}, "tracing": {"otlp_endpoint": "http://collector:4318"}}
+        for target in (
+            patch("trainsh.core.executor_main.load_config", return_value=config),
+            patch("trainsh.core.executor_main.CONFIG_DIR", self.root / "config"),
+            patch("trainsh.core.executor_main.RUNTIME_STATE_DIR", self.runtime),
+            patch("trainsh.runtime.CONFIG_DIR", self.runtime),
+            patch("trainsh.core.runtime_store.get_runtime_state_dir", return_value=self.runtime),
+        ):
+            target.start()
+            self.addCleanup(target.stop)
+
+    def test_run_exports_a_trace_with_a_span_per_step(self):
+        path = self.root / "traced.pyrecipe"
+        path.write_text(
+            'from trainsh import Recipe\n\nrecipe = Recipe("traced")\n'
+            'recipe.shell("true", id="prepare")\n'
+            'recipe.shell("exit 3", id="train", depends_on=["prepare"])\n',
+            encoding="utf-8",
+        )
+        with patch("trainsh.services.tracing.urlopen", return_value=ok_response()) as urlopen, redirect_stdout(StringIO()):
+            self.assertFalse(run_recipe(str(path), job_id="tracejob"))
+
+        spans = [
+            item
+            for call in urlopen.call_args_list
+            for item in json.loads(call.args[0].data)["resourceSpans"][0]["scopeSpans"][0]["spans"]
+        ]
+        by_name = {item["name"]: item for item in spans}
+        root = by_name["recipe traced"]
+        self.assertNotIn("parentSpanId", root)
+        self.assertEqual(root["status"]["code"], 2)
+        for name in ("preflight", "step prepare", "step train"):
+            self.assertEqual(by_name[name]["parentSpanId"], root["spanId"])
+        self.assertEqual(by_name["step prepare"]["status"], {"code": 1})
+        self.assertEqual(by_name["step train"]["status"]["code"], 2)
+        self.assertEqual({item["traceId"] for item in spans}, {root["traceId"]})
+
+        summary = ExecutionLogReader(str(self.runtime)).get_execution_summary("tracejob")
+        self.assertEqual(summary["trace"]["trace_id"], root["traceId"])
+        self.assertTrue(summary["trace"]["exported"])
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,105 @@
+import json
+import tempfile
+import unittest
+from contextlib import redirect_stdout
+from io import StringIO
+from pathlib import Path
+from unittest.mock import MagicMock, patch
+
+from trainsh.core.execution_log import ExecutionLogger
+from trainsh.services.transfer_engine import TransferEngine
+from trainsh.services.transfer_progress import (
+    ProgressReporter,
+    RcloneProgressParser,
+    RsyncProgressParser,
+    format_progress,
+    parse_duration,
+)
+from trainsh.services.transfer_queue import TransferQueue
+from trainsh.services.transfer_support import TransferProgress
+
+
+class FakeClock:
+    def __init__(self):
+        self.now = 0.0
+
+    def __call__(self):
+        return self.now
+
+
+class TransferProgressTests(unittest.TestCase):
+    def test_rsync_aggregate_and_per_file(self):
+        progress = RsyncProgressParser(aggregate=True).feed("  1,048,576  25%   10.00MB/s    0:00:03 (xfr#2, to-chk=6/10)")
+        self.assertEqual(progress.bytes_transferred, 1048576)
+        self.assertEqual(progress.total_bytes, 4194304)
+        self.assertEqual((progress.files_done, progress.files_total), (4, 10))
+        self.assertEqual(progress.speed_bps, 10 * 1024**2)
+        self.assertEqual(progress.eta_seconds, 3)
+
+        parser = RsyncProgressParser()
+        parser.feed("        100 100%    1.00kB/s    0:00:00 (xfr#1, to-chk=1/2)")
+        progress = parser.feed("         50  50%    1.00kB/s    0:00:01")
+        self.assertEqual(progress.bytes_transferred, 150)
+        self.assertIsNone(parser.feed("sending incremental file list"))
+
+    def test_rclone_merges_stats_lines(self):
+        parser = RcloneProgressParser()
+        parser.feed("Transferred:   3 / 12, 25%")
+        progress = parser.feed("Transferred:   1.5 GiB / 10 GiB, 15%, 50 MiB/s, ETA 3m")
+        self.assertEqual(progress.bytes_transferred, int(1.5 * 1024**3))
+        self.assertEqual(progress.total_bytes, 10 * 1024**3)
+        self.assertEqual((progress.files_done, progress.files_total), (3, 12))
+        self.assertEqual(progress.eta_seconds, 180)
+        self.assertEqual(progress.to_dict()["backend"], "rclone")
+        self.assertEqual(parser.feed("Transferred: 2 GiB").bytes_transferred, 2 * 1024**3)
+        self.assertEqual(parse_duration("1h2m3s"), 3723)
+        self.assertIn("3/12 files", format_progress(progress))
+
+    def test_reporter_throttles_and_derives_speed(self):
+        clock = FakeClock()
+        seen = []
+        reporter = ProgressReporter([seen.append], backend="tar-stream", interval=2.0, echo=False, clock=clock)
+        reporter.update(TransferProgress(bytes_transferred=10))
+        clock.now = 1.0
+        reporter.update(TransferProgress(bytes_transferred=100, total_bytes=400))
+        clock.now = 2.5
+        reporter.update(TransferProgress(bytes_transferred=200, total_bytes=400))
+        clock.now = 3.0
+        reporter.update(TransferProgress(bytes_transferred=400, total_bytes=400))
+        final = reporter.finish()
+        self.assertEqual([item.bytes_transferred for item in seen], [10, 200, 400])
+        self.assertEqual(final.backend, "tar-stream")
+        self.assertEqual(final.percent, 100.0)
+        self.assertAlmostEqual(seen[1].speed_bps, 80.0)
+        self.assertEqual(seen[1].eta_seconds, 2)
+
+    def test_engine_rsync_reports_progress(self):
+        seen = []
+        engine = TransferEngine(progress_callback=seen.append)
+        process = MagicMock(returncode=0)
+        process.stdout = iter(["  2,048  50%  1.00kB/s    0:00:02 (xfr#1, to-chk=1/2)\n"])
+        with patch("subprocess.Popen", return_value=process), redirect_stdout(StringIO()):
+            result = engine.rsync("./a", "./b", compress=False)
+        self.assertTrue(result.success)
+        self.assertEqual(seen[-1].backend, "rsync")
+        self.assertEqual(result.progress.files_done, 1)
+
+    def test_queue_and_execution_log_record_progress_events(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            queue = TransferQueue(Path(tmpdir), config={})
+            entry = queue.add("./a", "@gpu:/a")
+            snapshot = TransferProgress(bytes_transferred=5, total_bytes=10, percent=50.0, backend="rsync").to_dict()
+            queue.record_progress(entry["id"], snapshot)
+            self.assertEqual(queue.get(entry["id"])["progress"]["bytes_done"], 5)
+            events = [json.loads(line) for line in (Path(tmpdir) / "events.jsonl").read_text().splitlines()]
+            self.assertEqual(events[-1]["event"], "transfer:progress")
+            self.assertEqual(events[-1]["payload"]["transfer_id"], entry["id"])
+
+            logger = ExecutionLogger.__new__(ExecutionLogger)
+            logger._write = MagicMock()
+            logger.log_transfer_progress("/a", "/b", snapshot)
+            logger._write.assert_called_once_with("transfer:progress", source="/a", dest="/b", **snapshot)
+
+
+if __name__ == "__main__":
+    unittest.main()
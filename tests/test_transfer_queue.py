@@ -0,0 +1,93 @@
+import os
+import tempfile
+import unittest
+from contextlib import redirect_stdout
+from io import StringIO
+from pathlib import Path
+from unittest.mock import MagicMock, patch
+
+from trainsh.commands import transfer, transfer_queue
+from trainsh.services.transfer_queue import TransferQueue, destination_key
+
+CONFIG = {"transfer": {"queue": {"max_per_host": 1, "host_limits": {"r2:data": 2}}}}
+
+
+class TransferQueueTests(unittest.TestCase):
+    def setUp(self):
+        self.tmpdir = tempfile.TemporaryDirectory()
+        self.addCleanup(self.tmpdir.cleanup)
+        self.queue = TransferQueue(Path(self.tmpdir.name), config=CONFIG)
+
+    def test_destination_keys(self):
+        self.assertEqual(destination_key("@gpu:/data"), "gpu")
+        self.assertEqual(destination_key("host:gpu:/data"), "gpu")
+        self.assertEqual(destination_key("storage:artifacts:/x"), "storage:artifacts")
+        self.assertEqual(destination_key("r2:data/prefix"), "r2:data")
+        self.assertEqual(destination_key("hf:team/bucket:/x"), "hf:team")
+        self.assertEqual(destination_key("./out"), "local")
+
+    def test_priority_limits_pause_and_reordering(self):
+        queue = self.queue
+        big = queue.add("./dataset", "@gpu:/data", args=["--tar-stream"])
+        urgent = queue.add("./fix.py", "@gpu:/work", priority=10)
+        other = queue.add("./ckpt", "@cpu:/ckpt")
+        queue.add("./a", "r2:data/a")
+        queue.add("./b", "r2:data/b")
+
+        claimed = [queue.claim_next()["id"] for _ in range(4)]
+        self.assertEqual(claimed[:2], [urgent["id"], other["id"]])
+        self.assertEqual(len(claimed), 4)
+        self.assertIsNone(queue.claim_next(), "gpu is at its limit of 1 while r2:data allows 2")
+
+        queue.finish(urgent["id"], success=True, message="ok")
+        queue.set_paused(True)
+        self.assertIsNone(queue.claim_next())
+        queue.set_paused(False)
+        self.assertEqual(queue.claim_next()["id"], big["id"])
+
+        late = queue.add("./late", "@tpu:/x")
+        early = queue.add("./early", "@tpu:/y")
+        queue.move(early["id"][:6], "top")
+        self.assertEqual(queue.claim_next()["id"], early["id"])
+        queue.set_priority(late["id"], 5)
+        self.assertEqual(queue.get(late["id"])["priority"], 5)
+        queue.cancel(late["id"])
+        self.assertEqual(queue.clear_finished(), 2)
+        with self.assertRaises(KeyError):
+            queue.get(late["id"])
+        with self.assertRaises(ValueError):
+            queue.move(big["id"], "middle")
+
+    def test_dead_runner_entries_are_requeued(self):
+        entry = self.queue.add("./a", "@gpu:/a")
+        self.queue.claim_next(pid=999999999)
+        self.assertEqual(self.queue.claim_next()["id"], entry["id"])
+        self.assertEqual(self.queue.get(entry["id"])["pid"], os.getpid())
+
+    def test_cli_add_list_and_run(self):
+        with patch("trainsh.commands.transfer_queue.TransferQueue", return_value=self.queue):
+            out = StringIO()
+            with redirect_stdout(out):
+                transfer.main(["queue", "add", "./a", "@gpu:/a", "--bwlimit", "5M", "--priority", "3"])
+                transfer.main(["queue", "add", "./b", "@gpu:/b"])
+                transfer.main(["queue", "list"])
+            self.assertIn("Queued", out.getvalue())
+            self.assertIn("./a -> @gpu:/a --bwlimit 5M", out.getvalue())
+
+            process = MagicMock()
+            process.poll.return_value = 0
+            with patch("subprocess.Popen", return_value=process) as popen, patch("time.sleep"), redirect_stdout(StringIO()):
+                transfer_queue.main(["run", "--interval", "0.1"])
+            commands = [call.args[0] for call in popen.call_args_list]
+            self.assertEqual(commands[0][3:], ["transfer", "./a", "@gpu:/a", "--bwlimit", "5M"])
+            self.assertEqual(commands[1][4:6], ["./b", "@gpu:/b"])
+            self.assertEqual([entry["status"] for entry in self.queue.entries()], ["done", "done"])
+
+            out = StringIO()
+            with redirect_stdout(out), self.assertRaises(SystemExit):
+                transfer_queue.main(["cancel", "nope"])
+            self.assertIn("Unknown transfer id", out.getvalue())
+
+
+if __name__ == "__main__":
+    unittest.main()
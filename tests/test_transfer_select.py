@@ -0,0 +1,105 @@
+import tempfile
+import unittest
+from contextlib import redirect_stdout
+from io import StringIO
+from pathlib import Path
+from types import SimpleNamespace
+from unittest.mock import MagicMock, patch
+
+from trainsh.commands import transfer
+from trainsh.core.executor_transfer import TransferHelper
+from trainsh.core.models import Host, HostType, TransferEndpoint
+from trainsh.services.transfer_select import (
+    expand_command,
+    expand_host,
+    expand_local,
+    glob_shell_word,
+    is_selection,
+    transfer_selection,
+)
+from trainsh.services.transfer_support import TransferResult
+from tests.runtime_test_utils import run_locally
+
+
+class TransferSelectTests(unittest.TestCase):
+    def setUp(self):
+        self.tmpdir = tempfile.TemporaryDirectory()
+        self.addCleanup(self.tmpdir.cleanup)
+        self.root = Path(self.tmpdir.name) / "ck pts"
+        (self.root / "logs").mkdir(parents=True)
+        for name in ("epoch_1.pt", "epoch_2.pt", "last.pt"):
+            (self.root / name).write_text(name)
+
+    def test_patterns_expand_the_same_locally_and_on_a_host(self):
+        self.assertEqual(glob_shell_word("~/ck pts/e*.pt"), "\"$HOME\"'/ck pts/e'*.pt")
+        self.assertTrue(is_selection(["a", "b"]))
+        self.assertFalse(is_selection(["/data/run-1"]))
+        patterns = [f"{self.root}/epoch_*.pt", f"{self.root}/logs", f"{self.root}/epoch_1.pt"]
+        expected = [f"{self.root}/epoch_1.pt", f"{self.root}/epoch_2.pt", f"{self.root}/logs/"]
+        self.assertEqual(expand_local(patterns), expected)
+        with patch("trainsh.services.host_exec.host_exec", side_effect=run_locally):
+            self.assertEqual(expand_host("gpu", patterns), expected)
+            with self.assertRaises(FileNotFoundError):
+                expand_host("gpu", [f"{self.root}/step_*.pt"])
+        self.assertIn("for f in", expand_command(["/a/*.pt"]))
+
+    def test_selection_copies_each_match_and_reports_files(self):
+        engine = MagicMock()
+        engine.transfer.return_value = TransferResult(success=True, exit_code=0, message="ok", bytes_transferred=10)
+        source = TransferEndpoint(type="local", path="")
+        result = transfer_selection(
+            engine, source, [f"{self.root}/epoch_*.pt", f"{self.root}/logs"], TransferEndpoint(type="host", path="/w/best", host_id="gpu")
+        )
+        self.assertTrue(result.success)
+        self.assertEqual(result.bytes_transferred, 30)
+        self.assertEqual(len(result.files), 3)
+        targets = [call.kwargs["destination"].path for call in engine.transfer.call_args_list]
+        self.assertEqual(targets, ["/w/best/epoch_1.pt", "/w/best/epoch_2.pt", "/w/best/logs"])
+
+        engine.transfer.reset_mock()
+        transfer_selection(engine, source, [f"{self.root}/*.pt"], TransferEndpoint(type="storage", path="ckpt", storage_id="r2"))
+        self.assertEqual({call.kwargs["destination"].path for call in engine.transfer.call_args_list}, {"ckpt"})
+
+        engine.transfer.return_value = TransferResult(success=False, exit_code=23, message="denied")
+        failed = transfer_selection(engine, source, [f"{self.root}/*.pt"], TransferEndpoint(type="local", path="/out"))
+        self.assertEqual((failed.success, failed.exit_code), (False, 23))
+        self.assertIn("0 of 3 paths copied", failed.message)
+        missing = transfer_selection(engine, source, ["/nope/*.pt"], TransferEndpoint(type="local", path="/out"))
+        self.assertIn("No files match", missing.message)
+
+    def test_cli_copies_several_local_sources(self):
+        out_dir = Path(self.tmpdir.name) / "out"
+        engine = MagicMock()
+        engine.transfer.return_value = TransferResult(success=True, exit_code=0, message="ok", bytes_transferred=4)
+        with patch("trainsh.services.transfer_engine.TransferEngine", return_value=engine), redirect_stdout(StringIO()) as out:
+            transfer.main([str(self.root / "epoch_*.pt"), str(self.root / "last.pt"), str(out_dir)])
+        targets = [call.kwargs["destination"].path for call in engine.transfer.call_args_list]
+        self.assertEqual(targets, [str(out_dir / name) for name in ("epoch_1.pt", "epoch_2.pt", "last.pt")])
+        self.assertIn(f"  {self.root}/last.pt", out.getvalue())
+        self.assertIn("Transferred: 12 bytes", out.getvalue())
+        with self.assertRaises(SystemExit), redirect_stdout(StringIO()):
+            transfer.main([str(self.root / "*.pt"), str(out_dir), "--delete"])
+
+    def test_recipe_step_records_resolved_files(self):
+        executor = SimpleNamespace(recipe=SimpleNamespace(hosts={}, storages={}), _interpolate=lambda value: value, logger=MagicMock())
+        helper = TransferHelper(
+            executor,
+            resolve_vast_host=lambda inst: inst,
+            host_from_ssh_spec=lambda spec: Host(name=spec, type=HostType.SSH, hostname=spec),
+        )
+        engine = MagicMock()
+        engine.transfer.return_value = TransferResult(success=True, exit_code=0, message="ok", bytes_transferred=5)
+        with patch("trainsh.services.transfer_engine.TransferEngine", return_value=engine), patch(
+            "trainsh.commands.host.load_hosts", return_value={}
+        ), patch("trainsh.commands.storage.load_storages", return_value={}):
+            ok, msg = helper.transfer([f"{self.root}/epoch_*.pt", f"{self.root}/last.pt"], "/tmp/out")
+            self.assertEqual((ok, msg), (True, "Transferred 15 bytes"))
+            self.assertEqual(len(executor.logger.log_transfer.call_args.kwargs["files"]), 3)
+
+            ok, msg = helper.transfer([f"{self.root}/last.pt", "@gpu:/w/a.pt"], "/tmp/out")
+            self.assertFalse(ok)
+            self.assertIn("same host", msg)
+
+
+if __name__ == "__main__":
+    unittest.main()
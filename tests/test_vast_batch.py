@@ -0,0 +1,118 @@
+import json
+import tempfile
+import unittest
+from contextlib import redirect_stdout
+from io import StringIO
+from pathlib import Path
+from unittest.mock import MagicMock, patch
+
+from trainsh.commands import vast
+from trainsh.services.audit_log import read_entries
+from trainsh.services.vast_api import VastAPIError
+from trainsh.services.vast_batch import label_instances, run_batch, stop_instances
+
+
+def run_cli(fn, args):
+    out = StringIO()
+    code = None
+    with redirect_stdout(out):
+        try:
+            fn(args)
+        except SystemExit as exc:
+            code = exc.code
+    return out.getvalue(), code
+
+
+class VastBatchTests(unittest.TestCase):
+    def test_failures_do_not_abort_the_rest(self):
+        client = MagicMock()
+        client.stop_instance.side_effect = lambda i: (_ for _ in ()).throw(VastAPIError(404, "no_such_instance")) if i == 2 else None
+        events = []
+        results = stop_instances(client, [1, 2, 3], max_workers=2, on_progress=events.append)
+        self.assertEqual([(r.instance_id, r.ok) for r in results], [(1, True), (2, False), (3, True)])
+        self.assertEqual(results[1].error_code, "instance_not_found")
+        self.assertEqual(client.stop_instance.call_count, 3)
+        self.assertEqual(sorted(e["done"] for e in events), [1, 2, 3])
+        self.assertEqual({e["event"] for e in events}, {"vast:batch"})
+
+        label_instances(client, {7: "a", 8: "b"})
+        self.assertEqual(sorted(c.args for c in client.label_instance.call_args_list), [(7, "a"), (8, "b")])
+        self.assertEqual(run_batch("stop", []), [])
+
+    def test_offline_label_changes_are_queued(self):
+        client = MagicMock()
+        client.label_instance.side_effect = lambda i, label: (_ for _ in ()).throw(VastAPIError(0, "timed out")) if i == 8 else None
+        monitor = MagicMock()
+        monitor.return_value.enqueue.return_value = {"id": "q1"}
+        with patch("trainsh.services.vast_api.get_vast_client", return_value=client), patch(
+            "trainsh.services.connectivity.network_available", return_value=False
+        ), patch("trainsh.services.connectivity.ConnectivityMonitor", monitor), patch(
+            "trainsh.services.vast_batch.batch_parallelism", return_value=1
+        ):
+            out, code = run_cli(vast.cmd_label, ["7=train-a", "8=train-b"])
+            self.assertIsNone(code)
+            self.assertIn("8: queued until the network is back", out)
+            self.assertIn("Labeled 1 of 2 instance(s).", out)
+            monitor.return_value.enqueue.assert_called_once_with("vast.label", {"instance_id": 8, "label": "train-b"})
+
+            out, code = run_cli(vast.cmd_label, ["8", "train-b", "--json"])
+            self.assertIsNone(code)
+            self.assertTrue(json.loads(out.splitlines()[0])["queued"])
+
+            # A real API error is still a failure, not something to replay later.
+            client.label_instance.side_effect = VastAPIError(404, "no_such_instance")
+            out, code = run_cli(vast.cmd_label, ["9=x"])
+            self.assertEqual(code, 1)
+            self.assertEqual(monitor.return_value.enqueue.call_count, 2)
+
+    def test_offline_batch_stop_queues_each_failed_instance(self):
+        client = MagicMock()
+        client.stop_instance.side_effect = VastAPIError(0, "Network is unreachable")
+        monitor = MagicMock()
+        monitor.return_value.enqueue.return_value = {"id": "q1"}
+        with patch("trainsh.services.vast_api.get_vast_client", return_value=client), patch(
+            "trainsh.services.connectivity.network_available", return_value=False
+        ), patch("trainsh.services.connectivity.ConnectivityMonitor", monitor), patch(
+            "trainsh.services.vast_batch.batch_parallelism", return_value=1
+        ):
+            out, code = run_cli(vast.cmd_stop, ["1", "2"])
+            self.assertIsNone(code)
+            self.assertIn("Queued offline: 1, 2", out)
+            out, code = run_cli(vast.cmd_stop, ["3", "--json"])
+            self.assertIsNone(code)
+            self.assertTrue(json.loads(out.splitlines()[0])["queued"])
+        self.assertEqual(
+            [c.args for c in monitor.return_value.enqueue.call_args_list],
+            [("vast.stop", {"instance_id": 1}), ("vast.stop", {"instance_id": 2}), ("vast.stop", {"instance_id": 3})],
+        )
+
+    def test_cli_batches_stop_remove_and_label(self):
+        client = MagicMock()
+        client.rm_instance.side_effect = [None, RuntimeError("boom")]
+        with tempfile.TemporaryDirectory() as tmpdir:
+            audit = Path(tmpdir) / "audit.jsonl"
+            with patch("trainsh.services.vast_api.get_vast_client", return_value=client), patch(
+                "trainsh.services.audit_log.AUDIT_FILE", audit
+            ), patch(
+                "trainsh.services.vast_batch.batch_parallelism", return_value=1
+            ), patch.object(vast, "prompt_input", return_value="y"):
+                out, code = run_cli(vast.cmd_stop, ["1", "2", "2"])
+                self.assertIsNone(code)
+                self.assertIn("Stopped 2 of 2 instance(s).", out)
+                out, code = run_cli(vast.cmd_rm, ["5", "6"])
+                self.assertEqual(code, 1)
+                self.assertIn("Failed: 6", out)
+                out, code = run_cli(vast.cmd_label, ["7=train-a", "8=train-b", "--json"])
+                self.assertIsNone(code)
+                self.assertEqual(json.loads(out.splitlines()[0])["action"], "label")
+                out, code = run_cli(vast.cmd_label, ["9", "solo"])
+                self.assertIn("Labeled 1 of 1", out)
+                _, code = run_cli(vast.cmd_label, ["nonsense"])
+                self.assertEqual(code, 1)
+            entries = read_entries(path=audit)
+        self.assertEqual([(e["target"], e["status"]) for e in entries], [("5", "ok"), ("6", "failed")])
+        client.label_instance.assert_any_call(9, "solo")
+
+
+if __name__ == "__main__":
+    unittest.main()
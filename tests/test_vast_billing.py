@@ -0,0 +1,105 @@
+import csv
+import tempfile
+import unittest
+from contextlib import redirect_stdout
+from datetime import datetime
+from io import StringIO
+from pathlib import Path
+from types import SimpleNamespace
+from unittest.mock import patch
+
+from trainsh.commands import pricing
+from trainsh.services.vast_billing import (
+    BillingCache,
+    build_report,
+    estimate_from_events,
+    fetch_month_charges,
+    month_bounds,
+    normalize_charge,
+    write_csv,
+)
+
+
+def event(instance_id, ts, status, dph, label="sweep"):
+    return {"instance_id": instance_id, "ts": ts, "status": status, "dph_total": dph, "label": label}
+
+
+class VastBillingTests(unittest.TestCase):
+    def test_normalize_charge_and_month_bounds(self):
+        self.assertEqual(month_bounds("2026-12"), (datetime(2026, 12, 1), datetime(2027, 1, 1)))
+        row = normalize_charge(
+            {"timestamp": datetime(2026, 9, 3, 10).timestamp(), "amount": "-1.5", "description": "Instance 4242 GPU charge"}
+        )
+        self.assertEqual(row, {"instance_id": "4242", "day": "2026-09-03", "amount_usd": 1.5, "label": ""})
+        self.assertIsNone(normalize_charge({"timestamp": 1, "amount": 0}))
+
+    def test_estimates_follow_running_periods_and_price_changes(self):
+        start, end = month_bounds("2026-09")
+        events = [
+            event("7", "2026-08-31T22:00:00", "running", 1.0),
+            event("7", "2026-09-01T02:00:00", "running", 2.0),
+            event("7", "2026-09-01T03:00:00", "stopped", 2.0),
+            event("8", "2026-09-30T23:00:00", "running", 0.5, label="eval"),
+        ]
+        estimates = estimate_from_events(events, start, end, now=datetime(2026, 10, 1, 5))
+
+        self.assertEqual(estimates["7"], {"2026-09-01": 2 * 1.0 + 1 * 2.0})
+        self.assertEqual(estimates["8"], {"2026-09-30": 0.5})
+
+    def test_report_groups_reconciles_and_exports(self):
+        charges = [
+            {"instance_id": "7", "day": "2026-09-01", "amount_usd": 4.4, "label": ""},
+            {"instance_id": "7", "day": "2026-09-02", "amount_usd": 0.6, "label": ""},
+            {"instance_id": "9", "day": "2026-09-02", "amount_usd": 1.0, "label": "eval"},
+        ]
+        estimates = {"7": {"2026-09-01": 4.0, "2026-08-31": 9.0}, "8": {"2026-09-30": 0.5}}
+        labels = {"7": "sweep", "8": "eval"}
+
+        rows = build_report("2026-09", charges, estimates, labels=labels)
+        self.assertEqual([(r.key, r.label, round(r.billed_usd, 2), r.estimated_usd) for r in rows], [
+            ("7", "sweep", 5.0, 4.0),
+            ("9", "eval", 1.0, 0.0),
+            ("8", "eval", 0.0, 0.5),
+        ])
+        self.assertAlmostEqual(rows[0].drift_pct, 25.0)
+        self.assertIsNone(rows[1].drift_pct)
+
+        by_label = build_report("2026-09", charges, estimates, group_by="label", labels=labels)
+        self.assertEqual([(r.key, r.billed_usd, r.estimated_usd) for r in by_label], [("sweep", 5.0, 4.0), ("eval", 1.0, 0.5)])
+
+        with tempfile.TemporaryDirectory() as tmpdir:
+            path = Path(tmpdir) / "report.csv"
+            write_csv(str(path), by_label, group_by="label")
+            with open(path, newline="") as f:
+                lines = list(csv.reader(f))
+        self.assertEqual(lines[0], ["month", "label", "billed_usd", "estimated_usd", "drift_usd", "drift_pct"])
+        self.assertEqual(lines[1], ["2026-09", "sweep", "5.0000", "4.0000", "1.0000", "25.0"])
+
+    def test_billing_command_fetches_once_then_uses_cache(self):
+        client = SimpleNamespace(
+            calls=[],
+            list_charges=lambda start, end: client.calls.append((start, end))
+            or [{"timestamp": datetime(2026, 9, 5).timestamp(), "amount": 3.0, "instance_id": 11}],
+        )
+        with tempfile.TemporaryDirectory() as tmpdir:
+            cache = BillingCache(Path(tmpdir))
+            with patch("trainsh.services.vast_billing.BillingCache", return_value=cache), patch(
+                "trainsh.services.vast_api.get_vast_client", return_value=client
+            ), patch("trainsh.services.vast_events.VastInstanceWatcher") as watcher:
+                watcher.return_value.history.return_value = []
+                out = StringIO()
+                with redirect_stdout(out):
+                    pricing.main(["billing", "--month", "2026-09", "--csv", str(Path(tmpdir) / "out.csv")])
+                    pricing.main(["billing", "--month", "2026-09"])
+
+            self.assertEqual(len(client.calls), 1)
+            self.assertEqual(cache.charges("2026-09")[0]["instance_id"], "11")
+            self.assertIn("Total", out.getvalue())
+            self.assertTrue((Path(tmpdir) / "out.csv").exists())
+
+            fetch_month_charges(client, "2026-10", cache)
+            self.assertEqual(len(client.calls), 2)
+
+
+if __name__ == "__main__":
+    unittest.main()
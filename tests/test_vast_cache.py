@@ -0,0 +1,56 @@
+import unittest
+from types import SimpleNamespace
+from unittest.mock import patch
+
+from trainsh.services import vast_api
+from trainsh.services.vast_api import VastAPIClient, get_vast_client
+
+LISTING = {"instances": [{"id": 7, "actual_status": "running"}, {"id": 8, "actual_status": "stopped"}]}
+
+
+class VastCacheTests(unittest.TestCase):
+    def test_list_is_cached_and_serves_get_instance(self):
+        client = VastAPIClient("key")
+        with patch.object(client, "_request", return_value=LISTING) as request:
+            client.list_instances()
+            self.assertEqual(client.list_instances()[1].id, 8)
+            self.assertEqual(client.get_instance(7).actual_status, "running")
+        request.assert_called_once_with("instances")
+
+        with patch("trainsh.services.vast_api.time.monotonic", return_value=10**9), patch.object(
+            client, "_request", return_value={"instances": {"id": 7, "actual_status": "exited"}}
+        ) as request:
+            self.assertEqual(client.get_instance(7).actual_status, "exited")
+            client.get_instance(7)
+        request.assert_called_once_with("instances/7")
+
+    def test_mutations_invalidate_affected_families(self):
+        client = VastAPIClient("key")
+        client._cache_put("instances", LISTING)
+        client._cache_put("ssh", {"ssh_keys": []})
+        with patch("trainsh.services.vast_api.urlopen", side_effect=RuntimeError("sent")):
+            with self.assertRaises(RuntimeError):
+                client.create_instance(1, "img")
+        self.assertIsNone(client._cache_get("instances"))
+        self.assertIsNotNone(client._cache_get("ssh"))
+        client.invalidate()
+        self.assertIsNone(client._cache_get("ssh"))
+
+        uncached = VastAPIClient("key", cache_ttls={"instances": 0})
+        with patch.object(uncached, "_request", return_value=LISTING) as request:
+            uncached.list_instances()
+            uncached.list_instances()
+        self.assertEqual(request.call_count, 2)
+
+    def test_get_vast_client_is_shared_per_key(self):
+        secrets = SimpleNamespace(get_vast_api_key=lambda: "shared-key")
+        with patch.dict(vast_api._clients, clear=True), patch(
+            "trainsh.core.secrets.get_secrets_manager", return_value=secrets
+        ), patch("trainsh.config.get_config_value", side_effect=lambda key, default=None: {"ssh": 0} if key == "vast.api_cache_ttls" else default):
+            client = get_vast_client()
+            self.assertIs(get_vast_client(), client)
+        self.assertEqual(client.cache_ttls, {"ssh": 0})
+
+
+if __name__ == "__main__":
+    unittest.main()
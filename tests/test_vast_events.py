@@ -0,0 +1,81 @@
+import os
+import tempfile
+import unittest
+from contextlib import redirect_stdout
+from io import StringIO
+from pathlib import Path
+from types import SimpleNamespace
+from unittest.mock import patch
+
+from trainsh.commands import vast_watch
+from trainsh.services.vast_events import VastInstanceWatcher
+
+
+def instance(instance_id, status, dph=0.5, port=22):
+    return SimpleNamespace(
+        id=instance_id,
+        actual_status=status,
+        dph_total=dph,
+        ssh_host="ssh.vast.ai",
+        ssh_port=port,
+        label="",
+        num_gpus=1,
+        gpu_name="RTX_4090",
+    )
+
+
+class VastInstanceWatcherTests(unittest.TestCase):
+    def test_transitions_become_persisted_events(self):
+        reachable = set()
+        with tempfile.TemporaryDirectory() as tmpdir:
+            watcher = VastInstanceWatcher(Path(tmpdir), ssh_probe=lambda host, port: port in reachable)
+
+            self.assertEqual(watcher.observe([instance(1, "running")]), [])
+            self.assertEqual(
+                [e["kind"] for e in watcher.observe([instance(1, "running"), instance(2, "loading")])],
+                ["created"],
+            )
+            events = watcher.observe([instance(1, "stopped"), instance(2, "running", dph=0.7, port=2222)])
+            self.assertEqual([e["kind"] for e in events], ["stopped", "running", "price_changed"])
+
+            reachable.add(2222)
+            events = watcher.observe([instance(1, "stopped"), instance(2, "running", dph=0.7, port=2222)])
+            self.assertEqual([(e["kind"], e["ssh"]) for e in events], [("ssh_available", "ssh.vast.ai:2222")])
+            self.assertEqual(watcher.observe([instance(1, "stopped"), instance(2, "running", dph=0.7, port=2222)]), [])
+
+            events = watcher.observe([instance(2, "running", dph=0.7, port=2222)])
+            self.assertEqual([(e["kind"], e["instance_id"]) for e in events], [("destroyed", "1")])
+
+            reloaded = VastInstanceWatcher(Path(tmpdir))
+            self.assertEqual(
+                [e["kind"] for e in reloaded.history("2")],
+                ["created", "running", "price_changed", "ssh_available"],
+            )
+            self.assertEqual(len(reloaded.history()), 6)
+
+    def test_events_command_and_exec_hook(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            watcher = VastInstanceWatcher(Path(tmpdir), ssh_probe=lambda host, port: False)
+            watcher.observe([])
+            watcher.observe([instance(5, "running", dph=1.25)])
+
+            out = StringIO()
+            with patch("trainsh.commands.vast_watch.VastInstanceWatcher", lambda: VastInstanceWatcher(Path(tmpdir))):
+                with redirect_stdout(out):
+                    vast_watch.cmd_events(["5"])
+                    vast_watch.cmd_events(["6"])
+            self.assertIn("#5  created", out.getvalue())
+            self.assertIn("No recorded events for instance 6", out.getvalue())
+
+            marker = Path(tmpdir) / "hook.txt"
+            code = vast_watch.run_event_hook(
+                f'echo "$TRAINSH_VAST_EVENT $TRAINSH_VAST_INSTANCE_ID" > {marker}',
+                {"kind": "ssh_available", "instance_id": "5"},
+            )
+            self.assertEqual(code, 0)
+            self.assertEqual(marker.read_text().strip(), "ssh_available 5")
+            self.assertNotIn("TRAINSH_VAST_EVENT", os.environ)
+
+
+if __name__ == "__main__":
+    unittest.main()
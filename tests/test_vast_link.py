@@ -0,0 +1,96 @@
+import tempfile
+import unittest
+from contextlib import redirect_stdout
+from io import StringIO
+from types import SimpleNamespace
+from unittest.mock import MagicMock, patch
+
+from trainsh.commands import host
+from trainsh.core.job_state import JobState, JobStateManager, spec_endpoint
+from trainsh.core.models import Host, HostType
+from trainsh.services import host_resolver
+from trainsh.services.ssh import SSHClient
+
+
+def instance(ip="1.2.3.4", port=41022, status="running"):
+    return SimpleNamespace(id=77, actual_status=status, public_ipaddr=ip, ports={"22/tcp": [{"HostPort": str(port)}]})
+
+
+def vast_client(*instances):
+    client = MagicMock()
+    client.get_instance.side_effect = list(instances)
+    return patch("trainsh.services.vast_api.get_vast_client", return_value=client)
+
+
+class VastLinkTests(unittest.TestCase):
+    def setUp(self):
+        host_resolver._VAST_LINK_CACHE.clear()
+        patchers = [
+            patch("trainsh.config.load_config", return_value={}),
+            patch("trainsh.services.host_resolver._test_ssh_connection", return_value=False),
+        ]
+        for patcher in patchers:
+            patcher.start()
+            self.addCleanup(patcher.stop)
+
+    def linked(self, hostname="1.2.3.4", port=40022):
+        return Host(name="box", type=HostType.SSH, hostname=hostname, port=port, username="root", env_vars={"vast_link": "vast:77"})
+
+    def test_refresh_follows_new_port_and_rejects_stopped_instance(self):
+        self.assertEqual(host_resolver.parse_vast_ref("vast:77"), "77")
+        with self.assertRaisesRegex(ValueError, "vast:<id>"):
+            host_resolver.parse_vast_ref("runpod:1")
+
+        box = self.linked()
+        with vast_client(instance(port=41022), instance(status="stopped")):
+            refreshed = host_resolver.refresh_vast_link(box)
+            with self.assertRaisesRegex(RuntimeError, "train vast start 77"):
+                host_resolver.refresh_vast_link(box)
+        self.assertEqual((refreshed.hostname, refreshed.port, refreshed.type), ("1.2.3.4", 41022, HostType.SSH))
+        self.assertEqual(box.port, 40022)
+
+    def test_connect_uses_cached_endpoint_and_survives_api_errors(self):
+        with vast_client(instance(port=41022)) as get_client:
+            first = SSHClient.from_host(self.linked())
+            second = SSHClient.from_host(self.linked())
+        self.assertEqual((first.port, second.port), (41022, 41022))
+        self.assertEqual(get_client.return_value.get_instance.call_count, 1)
+
+        host_resolver._VAST_LINK_CACHE.clear()
+        with patch("trainsh.services.vast_api.get_vast_client", side_effect=RuntimeError("offline")):
+            self.assertEqual(SSHClient.from_host(self.linked()).port, 40022)
+
+    def test_refresh_command_saves_endpoint_and_repoints_saved_sessions(self):
+        self.assertEqual(spec_endpoint("root@1.2.3.4 -i ~/.ssh/id -p 40022"), ("1.2.3.4", 40022))
+        with tempfile.TemporaryDirectory() as tmpdir:
+            manager = JobStateManager(tmpdir)
+            manager.save(JobState(job_id="j1", recipe_path="r.py", recipe_name="r", hosts={"train": "root@1.2.3.4 -p 40022", "local": "local"}, host_refs={"train": "vast:77"}))
+            manager.save(JobState(job_id="j2", recipe_path="r.py", recipe_name="r", status="completed", hosts={"train": "root@1.2.3.4 -p 40022"}))
+            saved = {}
+            out = StringIO()
+            with vast_client(instance(port=41022)), patch("trainsh.constants.RUNTIME_STATE_DIR", tmpdir), patch(
+                "trainsh.commands.host.load_hosts", return_value={"box": self.linked(), "plain": Host(name="plain", hostname="h")}
+            ), patch("trainsh.commands.host.save_hosts", side_effect=saved.update), redirect_stdout(out):
+                host.main(["refresh"])
+            self.assertEqual(sorted(saved), ["box", "plain"])
+            self.assertEqual(saved["box"].port, 41022)
+            self.assertIn("moved 1.2.3.4:40022 -> 1.2.3.4:41022", out.getvalue())
+            self.assertEqual(manager.load("j1").hosts, {"train": "root@1.2.3.4 -p 41022", "local": "local"})
+            self.assertEqual(manager.load("j1").host_refs, {"train": "vast:77"})
+            self.assertEqual(manager.load("j2").hosts["train"], "root@1.2.3.4 -p 40022")
+
+    def test_link_command_validates_reference(self):
+        saved = {}
+        with patch("trainsh.commands.host.load_hosts", return_value={"box": Host(name="box", hostname="h")}), patch(
+            "trainsh.commands.host.save_hosts", side_effect=saved.update
+        ), redirect_stdout(StringIO()):
+            with self.assertRaises(SystemExit):
+                host.main(["link", "box", "vast:abc"])
+            with vast_client(instance()):
+                host.main(["link", "box", "vast:77"])
+        self.assertEqual(saved["box"].env_vars["vast_link"], "vast:77")
+        self.assertEqual(saved["box"].hostname, "1.2.3.4")
+
+
+if __name__ == "__main__":
+    unittest.main()
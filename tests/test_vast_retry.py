@@ -0,0 +1,81 @@
+import io
+import unittest
+from email.message import Message
+from unittest.mock import patch
+from urllib.error import HTTPError, URLError
+
+from trainsh.services import vast_api
+from trainsh.services.vast_api import RetryPolicy, VastAPIClient, VastAPIError
+from tests.runtime_test_utils import ok_response
+
+
+def http_error(code, headers=None):
+    message = Message()
+    for key, value in (headers or {}).items():
+        message[key] = value
+    return HTTPError("u", code, "err", message, io.BytesIO(b"busy"))
+
+
+class VastRetryTests(unittest.TestCase):
+    def setUp(self):
+        patcher = patch.object(vast_api, "_rate_limiter", vast_api._RateLimiter())
+        patcher.start()
+        self.addCleanup(patcher.stop)
+
+    def test_idempotent_calls_retry_server_and_network_errors(self):
+        client = VastAPIClient("key", RetryPolicy(retries=2, backoff_secs=1, min_interval_secs=0))
+        side_effect = [http_error(503), URLError("reset"), ok_response({"instances": []})]
+        with patch("trainsh.services.vast_api.urlopen", side_effect=side_effect) as urlopen, patch(
+            "trainsh.services.vast_api.time.sleep"
+        ) as sleep, patch("trainsh.services.vast_api.random.uniform", side_effect=lambda lo, hi: hi):
+            self.assertEqual(client.list_instances(), [])
+        self.assertEqual(urlopen.call_count, 3)
+        self.assertEqual([c.args[0] for c in sleep.call_args_list], [1, 2])
+
+        with patch("trainsh.services.vast_api.urlopen", side_effect=[http_error(500)] * 3), patch(
+            "trainsh.services.vast_api.time.sleep"
+        ):
+            with self.assertRaises(VastAPIError) as caught:
+                client.rm_instance(7)
+        self.assertEqual(caught.exception.status_code, 500)
+
+    def test_non_idempotent_calls_only_retry_rate_limits(self):
+        client = VastAPIClient("key", RetryPolicy(retries=3, min_interval_secs=0))
+        with patch("trainsh.services.vast_api.urlopen", side_effect=[http_error(502)]) as urlopen:
+            with self.assertRaises(VastAPIError):
+                client.create_instance(9, "img")
+        self.assertEqual(urlopen.call_count, 1)
+        with patch("trainsh.services.vast_api.urlopen", side_effect=[http_error(400)]) as urlopen:
+            with self.assertRaises(VastAPIError):
+                client.list_instances()
+        self.assertEqual(urlopen.call_count, 1)
+
+        vast_api._rate_limiter = vast_api._RateLimiter()
+        side_effect = [http_error(429, {"Retry-After": "4"}), ok_response({"new_contract": 11})]
+        with patch("trainsh.services.vast_api.urlopen", side_effect=side_effect), patch(
+            "trainsh.services.vast_api.time.sleep"
+        ) as sleep, patch("trainsh.services.vast_api.time.monotonic", return_value=100.0):
+            self.assertEqual(client.create_instance(9, "img"), 11)
+        sleep.assert_called_once_with(4.0)
+
+    def test_rate_limiter_spaces_requests_per_key(self):
+        limiter = vast_api._RateLimiter()
+        with patch("trainsh.services.vast_api.time.monotonic", return_value=10.0), patch(
+            "trainsh.services.vast_api.time.sleep"
+        ) as sleep:
+            limiter.wait("a", 0.5)
+            limiter.wait("a", 0.5)
+            limiter.wait("b", 0.5)
+            limiter.wait("a", 0.5)
+        self.assertEqual([c.args[0] for c in sleep.call_args_list], [0.5, 1.0])
+
+    def test_policy_reads_config(self):
+        values = {"vast.api_retries": "5", "vast.api_min_interval_secs": 1}
+        with patch("trainsh.config.get_config_value", side_effect=lambda key, default=None: values.get(key, default)):
+            policy = RetryPolicy.from_config()
+        self.assertEqual((policy.retries, policy.min_interval_secs, policy.backoff_secs), (5, 1.0, 1.0))
+        self.assertEqual(RetryPolicy(max_backoff_secs=3).delay(0, retry_after=60), 3)
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -12,7 +12,7 @@ from unittest.mock import MagicMock, patch
 from trainsh.commands import vast
 from trainsh.core.executor_vast import VastControlHelper
 from trainsh.core.models import VastInstance, VastOffer
-from trainsh.services.vast_api import VastAPIClient, VastAPIError, get_vast_client
+from trainsh.services.vast_api import RetryPolicy, VastAPIClient, VastAPIError, get_vast_client
 from trainsh.services.vast_connection import preferred_vast_ssh_target, vast_ssh_targets
 from trainsh.utils import vast_formatter
 
@@ -29,7 +29,7 @@ def capture_output(fn, *args, **kwargs):
 
 class VastApiClientTests(unittest.TestCase):
     def test_request_and_parse_helpers(self):
-        client = VastAPIClient("token")
+        client = VastAPIClient("token", RetryPolicy(retries=0, min_interval_secs=0), cache_ttls={})
 
         response = MagicMock()
         response.read.return_value = b'{"instances": [{"id": 1, "actual_status": "running"}]}'
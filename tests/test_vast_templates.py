@@ -0,0 +1,137 @@
+import tempfile
+import unittest
+from contextlib import ExitStack, redirect_stdout
+from io import StringIO
+from pathlib import Path
+from types import SimpleNamespace
+from unittest.mock import patch
+
+from trainsh.commands import vast
+from trainsh.core.executor_vast import VastControlHelper
+from trainsh.services.vast_api import VastAPIError
+from trainsh.services.vast_templates import VastTemplate, create_from_template, load_templates, save_templates
+
+
+def run_cli(args):
+    out = StringIO()
+    with redirect_stdout(out):
+        try:
+            vast.main(args)
+        except SystemExit:
+            pass
+    return out.getvalue()
+
+
+class FakeClient:
+    def __init__(self, offers):
+        self.offers = offers
+        self.searches = []
+        self.created = []
+
+    def search_offers(self, **kwargs):
+        self.searches.append(kwargs)
+        cap = kwargs.get("max_dph")
+        return [offer for offer in self.offers if not cap or offer.dph_total <= cap]
+
+    def create_instance(self, **kwargs):
+        self.created.append(kwargs)
+        return 555
+
+
+class VastTemplateTests(unittest.TestCase):
+    def setUp(self):
+        self._stack = ExitStack()
+        config_dir = Path(self._stack.enter_context(tempfile.TemporaryDirectory()))
+        self._stack.enter_context(patch("trainsh.constants.CONFIG_DIR", config_dir))
+        self._stack.enter_context(patch("trainsh.constants.VAST_TEMPLATES_FILE", config_dir / "vast_templates.yaml"))
+        self.addCleanup(self._stack.close)
+
+    def test_template_crud_commands(self):
+        self.assertIn("No Vast.ai templates saved", run_cli(["template", "list"]))
+        self.assertIn("need --image", run_cli(["template", "add", "torch"]))
+
+        out = run_cli(
+            [
+                "template", "add", "torch",
+                "--image", "pytorch/pytorch:latest",
+                "--disk", "80",
+                "--env", "HF_HOME=/workspace/hf",
+                "--gpu", "RTX_4090",
+                "--max-price", "0.6",
+            ]
+        )
+        self.assertIn("Template saved: torch", out)
+        self.assertIn("Template updated: torch", run_cli(["template", "add", "torch", "--num-gpus", "2"]))
+
+        template = load_templates()["torch"]
+        self.assertEqual(template.disk_gb, 80.0)
+        self.assertEqual(template.env, {"HF_HOME": "/workspace/hf"})
+        self.assertEqual((template.gpu_name, template.num_gpus, template.max_dph), ("RTX_4090", 2, 0.6))
+
+        shown = run_cli(["template", "show", "torch"])
+        self.assertIn("Offers: RTX_4090, >= 2 GPU(s), <= $0.600/hr", shown)
+        self.assertIn("Env: HF_HOME", shown)
+        self.assertIn("Invalid value for --disk", run_cli(["template", "add", "torch", "--disk", "lots"]))
+
+        self.assertIn("Template removed: torch", run_cli(["template", "remove", "torch"]))
+        self.assertEqual(load_templates(), {})
+
+    def test_create_picks_cheapest_offer_and_respects_price_cap(self):
+        template = VastTemplate(
+            name="torch",
+            image="img:1",
+            disk_gb=80,
+            env={"A": "1"},
+            onstart="echo hi",
+            gpu_name="RTX_4090",
+            max_dph=0.6,
+        )
+        client = FakeClient(
+            [
+                SimpleNamespace(id=1, dph_total=0.5, reliability2=0.9),
+                SimpleNamespace(id=2, dph_total=0.4, reliability2=0.8),
+                SimpleNamespace(id=3, dph_total=0.9, reliability2=0.99),
+            ]
+        )
+
+        self.assertEqual(create_from_template(client, template), (555, 2))
+        self.assertEqual(client.searches[0]["gpu_name"], "RTX_4090")
+        created = client.created[0]
+        self.assertEqual((created["image"], created["disk"], created["label"]), ("img:1", 80, "torch"))
+        self.assertEqual(created["env"], {"A": "1"})
+        self.assertEqual(created["onstart"], "echo hi")
+
+        self.assertEqual(create_from_template(client, template, offer_id=1, label="run-1")[1], 1)
+        self.assertEqual(client.created[-1]["label"], "run-1")
+        with self.assertRaises(VastAPIError):
+            create_from_template(client, template, offer_id=3)
+
+    def test_create_command_and_vast_pick_use_saved_template(self):
+        save_templates({"torch": VastTemplate(name="torch", image="img:2", gpu_name="H100", env={"B": "2"})})
+        client = FakeClient([SimpleNamespace(id=9, dph_total=2.0, reliability2=0.9)])
+        client.list_instances = lambda: []
+
+        with patch("trainsh.services.vast_api.get_vast_client", return_value=client):
+            self.assertIn("Created instance 555 from offer 9 (template torch)", run_cli(["create", "torch"]))
+            self.assertIn("Vast template not found", run_cli(["create", "nope"]))
+
+            executor = SimpleNamespace(
+                _interpolate=lambda value: value,
+                ctx=SimpleNamespace(variables={}),
+                recipe=SimpleNamespace(hosts={}),
+                logger=None,
+                log=lambda message: None,
+            )
+            helper = VastControlHelper(executor, build_ssh_args=None, format_duration=str)
+            ok, msg = helper.cmd_vast_pick(["host=gpu", "template=torch", "create_if_missing=true", "disk_gb=120"])
+            missing = helper.cmd_vast_pick(["host=gpu", "template=nope", "skip_if_set=false"])
+
+        self.assertTrue(ok, msg)
+        self.assertEqual(client.searches[-1]["gpu_name"], "H100")
+        self.assertEqual((client.created[-1]["image"], client.created[-1]["disk"]), ("img:2", 120.0))
+        self.assertEqual(client.created[-1]["env"], {"B": "2"})
+        self.assertEqual(missing, (False, "Vast template not found: nope"))
+
+
+if __name__ == "__main__":
+    unittest.main()
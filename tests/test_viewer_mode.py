@@ -0,0 +1,72 @@
+import tempfile
+import unittest
+from contextlib import redirect_stdout
+from io import StringIO
+from pathlib import Path
+from unittest.mock import MagicMock, patch
+
+from trainsh.commands.config_cmd import cmd_viewer
+from trainsh.core.recipe_models import RecipeModel
+from trainsh.main import main
+from trainsh.pyrecipe.models import ProviderStep
+from trainsh.services.audit_log import read_entries
+from trainsh.services.viewer_mode import PermissionDenied, hash_passphrase, require_operator, verify_passphrase
+from tests.runtime_test_utils import isolated_executor
+
+VIEWER = {"viewer": {"enabled": True, "passphrase_hash": ""}}
+
+
+class ViewerModeTests(unittest.TestCase):
+    def test_passphrase_toggle(self):
+        stored = hash_passphrase("s3cret")
+        self.assertTrue(verify_passphrase("s3cret", stored))
+        self.assertFalse(verify_passphrase("guess", stored))
+        self.assertFalse(verify_passphrase("x", "garbage"))
+        require_operator("vast.destroy", {})
+
+        config = {}
+        with patch("trainsh.config.load_config", side_effect=lambda: dict(config)), patch(
+            "trainsh.config.save_config", side_effect=config.update
+        ), patch("getpass.getpass", side_effect=["pw", "pw", "wrong", "pw"]), redirect_stdout(StringIO()) as out:
+            cmd_viewer(["on", "--passphrase"])
+            with self.assertRaises(PermissionDenied):
+                require_operator("host.remove", config)
+            with self.assertRaises(PermissionDenied):
+                cmd_viewer(["off"])
+            cmd_viewer(["status"])
+            cmd_viewer(["off"])
+        self.assertFalse(config["viewer"]["enabled"])
+        self.assertIn("Viewer mode: on (passphrase protected)", out.getvalue())
+        self.assertIn("secret.delete", out.getvalue())
+
+    def test_cli_refuses_destructive_commands(self):
+        with tempfile.TemporaryDirectory() as tmpdir:
+            audit = Path(tmpdir) / "audit.jsonl"
+            client = MagicMock()
+            out = StringIO()
+            with patch("trainsh.config.load_config", return_value=VIEWER), patch(
+                "trainsh.services.audit_log.AUDIT_FILE", audit
+            ), patch("trainsh.services.vast_api.get_vast_client", return_value=client), redirect_stdout(out):
+                for argv in (["vast", "remove", "7"], ["secrets", "remove", "HF"], ["config", "set", "viewer.enabled", "false"]):
+                    with self.assertRaises(SystemExit):
+                        main(["train", *argv])
+            client.rm_instance.assert_not_called()
+            self.assertIn("Permission denied: vast.destroy is disabled in viewer mode", out.getvalue())
+            entries = read_entries(path=audit)
+            self.assertEqual([e["action"] for e in entries], ["vast.destroy", "secret.delete", "config.change"])
+            self.assertEqual({e["status"] for e in entries}, {"denied"})
+
+    def test_destructive_steps_fail_in_viewer_mode(self):
+        with isolated_executor(RecipeModel(name="r")) as (executor, _):
+            with patch("trainsh.core.executor_lifecycle.load_config", return_value=VIEWER), patch.object(
+                executor, "_dispatch_step"
+            ) as dispatch:
+                ok, message = executor._execute_step(ProviderStep("storage", "delete", {"path": "x"}, id="rm"))
+                self.assertEqual(executor._execute_step(ProviderStep("util", "sleep", {}, id="s")), dispatch.return_value)
+        self.assertFalse(ok)
+        self.assertIn("viewer mode", message)
+        dispatch.assert_called_once()
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,101 @@
+import os
+import tempfile
+import unittest
+from contextlib import redirect_stdout
+from io import StringIO
+from pathlib import Path
+from unittest.mock import MagicMock, patch
+
+from trainsh.commands.host_vscode import cmd_vscode
+from trainsh.core.models import Host, HostType
+from trainsh.services import vscode
+from trainsh.services.ssh import SSHResult
+
+
+class VSCodeTests(unittest.TestCase):
+    def setUp(self):
+        self.tmpdir = tempfile.TemporaryDirectory()
+        self.addCleanup(self.tmpdir.cleanup)
+        self.root = Path(self.tmpdir.name)
+        self.host = Host(
+            name="gpu",
+            type=HostType.SSH,
+            hostname="gpu.example.com",
+            port=2222,
+            username="root",
+            env_vars={"proxy_command": "cloudflared access ssh --hostname %h"},
+        )
+
+    def test_ssh_entry_is_written_once_and_replaced_in_place(self):
+        entry = vscode.render_ssh_config_entry("trainsh-gpu", self.host)
+        self.assertIn("Host trainsh-gpu\n    HostName gpu.example.com\n    Port 2222\n    User root\n", entry)
+        self.assertIn("    ProxyCommand cloudflared access ssh --hostname %h\n", entry)
+
+        include = self.root / "trainsh_config"
+        include.write_text("# mine\n")
+        vscode.write_ssh_entry("trainsh-gpu", entry, include)
+        vscode.write_ssh_entry("trainsh-other", "Host trainsh-other\n    HostName b\n", include)
+        vscode.write_ssh_entry("trainsh-gpu", entry.replace("2222", "2200"), include)
+        text = include.read_text()
+        self.assertTrue(text.startswith("# mine\n"))
+        self.assertEqual(text.count("Host trainsh-gpu"), 1)
+        self.assertIn("Port 2200", text)
+        self.assertIn("Host trainsh-other", text)
+
+        config = self.root / "config"
+        config.write_text("Host old\n    HostName x\n")
+        self.assertTrue(vscode.ensure_ssh_include(include, config))
+        self.assertFalse(vscode.ensure_ssh_include(include, config))
+        self.assertTrue(config.read_text().startswith(f"Include {include}\n"))
+
+        self.assertEqual(
+            vscode.vscode_remote_url("trainsh-gpu", "/workspace/my project"),
+            "vscode://vscode-remote/ssh-remote+trainsh-gpu/workspace/my%20project",
+        )
+
+    def test_command_prints_vscode_url(self):
+        ssh = MagicMock()
+        ssh.run.return_value = SSHResult(exit_code=0, stdout="/home/root", stderr="")
+        out = StringIO()
+        with patch("trainsh.commands.host.load_hosts", return_value={"gpu": self.host}), patch(
+            "trainsh.services.vscode.ssh_config_file", return_value=self.root / "trainsh_config"
+        ), patch("trainsh.services.vscode.ensure_ssh_include", return_value=True), patch(
+            "trainsh.services.vscode.SSHClient.from_host", return_value=ssh
+        ), redirect_stdout(out):
+            cmd_vscode(["gpu", "--folder", "~/proj"])
+        self.assertIn("VS Code: vscode://vscode-remote/ssh-remote+trainsh-gpu/home/root/proj", out.getvalue())
+        self.assertIn("Host trainsh-gpu", (self.root / "trainsh_config").read_text())
+
+    def test_code_server_keeps_password_out_of_pane_and_tunnels(self):
+        ssh = MagicMock()
+        ssh.run.return_value = SSHResult(exit_code=0, stdout="", stderr="")
+        ssh.run_with_input.return_value = SSHResult(exit_code=0, stdout="", stderr="")
+        tmux = MagicMock()
+        tmux.has_session.return_value = False
+        tmux.new_session.return_value = MagicMock(returncode=0)
+        tmux.send_keys.return_value = MagicMock(returncode=0)
+        with patch("trainsh.services.vscode.STATE_DIR", self.root), patch(
+            "trainsh.services.vscode.SSHClient.from_host", return_value=ssh
+        ), patch("trainsh.services.vscode.tmux_client_for_host", return_value=tmux), patch(
+            "trainsh.services.vscode.start_local_tunnel", return_value=MagicMock(pid=77)
+        ), patch("trainsh.services.vscode.is_local_port_open", return_value=False):
+            state = vscode.start_code_server("gpu", self.host, folder="~/proj", install=True)
+            self.assertIn("code-server.dev/install.sh", ssh.run.call_args_list[0].args[0])
+            sent = tmux.send_keys.call_args.args[1]
+            self.assertNotIn(state["password"], sent)
+            self.assertIn('--bind-addr 127.0.0.1:8080 --auth password --disable-telemetry "$HOME"/proj', sent)
+
+            state = vscode.open_code_server_tunnel("gpu", self.host, state)
+            self.assertEqual(vscode.load_code_server("gpu")["local_port"], 8080)
+            self.assertEqual(os.stat(self.root / "vscode" / "gpu.json").st_mode & 0o777, 0o600)
+
+            tmux.has_session.return_value = True
+            tmux.kill_session.return_value = MagicMock(returncode=0)
+            with patch("trainsh.services.vscode.os.kill") as kill:
+                self.assertTrue(vscode.stop_code_server("gpu", self.host))
+            kill.assert_called_once()
+            self.assertIsNone(vscode.load_code_server("gpu"))
+
+
+if __name__ == "__main__":
+    unittest.main()
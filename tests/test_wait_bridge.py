@@ -94,9 +94,9 @@ class BridgeExecEdgeTests(unittest.TestCase):
         self.assertFalse(ok)
         self.assertIn("Timeout", msg)
 
-        tmux.capture_pane.return_value = TmuxCmdResult(0, "__done__0\n", "")
+        tmux.capture_pane.return_value = TmuxCmdResult(0, "__train_begin_abc__\n__train_done_abc__0\n", "")
         with patch("trainsh.core.bridge_exec.time.time", side_effect=[0, 0]):
-            ok, code = helper._wait_bridge_marker("%1", "__done__", 5)
+            ok, code = helper._wait_bridge_marker("%1", "abc", 5)
         self.assertTrue(ok)
         self.assertEqual(code, 0)
 
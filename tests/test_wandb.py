@@ -0,0 +1,110 @@
+import json
+import tempfile
+import unittest
+from contextlib import redirect_stdout
+from datetime import datetime, timedelta, timezone
+from io import StringIO
+from unittest.mock import patch
+
+from trainsh.commands.recipe_views import cmd_logs
+from trainsh.core.execution_log import ExecutionLogReader
+from trainsh.core.runtime_store import RuntimeStore
+from trainsh.services.wandb_api import WandbAPIClient, WandbRun, best_match, format_metrics, link_executions, split_project
+from tests.runtime_test_utils import ok_response
+
+
+def wandb_time(local: datetime) -> str:
+    """W&B reports naive UTC timestamps."""
+    return local.astimezone(timezone.utc).replace(tzinfo=None).isoformat()
+
+
+def make_run(run_id, created, *, host="", tags=(), summary=None):
+    return WandbRun(run_id, f"run-{run_id}", "team", "proj", "finished", wandb_time(created), "", host, list(tags), summary or {})
+
+
+class WandbClientTests(unittest.TestCase):
+    def test_lists_runs_with_basic_auth_and_parses_summary(self):
+        node = {
+            "name": "abc123",
+            "displayName": "lr-3e-4",
+            "state": "finished",
+            "createdAt": "2026-10-01T10:00:00",
+            "host": "gpu-box",
+            "tags": ["nanochat"],
+            "summaryMetrics": json.dumps({"loss": 0.41, "_step": 900, "eval/acc": 0.7, "note": "x"}),
+        }
+        payload = {"data": {"project": {"runs": {"edges": [{"node": node}]}}}}
+        with patch("trainsh.services.wandb_api.urlopen", return_value=ok_response(payload)) as urlopen:
+            runs = WandbAPIClient("secret").list_runs("team/proj", limit=5)
+        request = urlopen.call_args.args[0]
+        self.assertTrue(request.get_header("Authorization").startswith("Basic "))
+        self.assertEqual(json.loads(request.data)["variables"], {"entity": "team", "project": "proj", "first": 5})
+        self.assertEqual((runs[0].id, runs[0].name, runs[0].host), ("abc123", "lr-3e-4", "gpu-box"))
+        self.assertEqual(runs[0].final_metrics(), {"loss": 0.41, "eval/acc": 0.7})
+        self.assertEqual(runs[0].url, "https://wandb.ai/team/proj/runs/abc123")
+        with self.assertRaises(ValueError):
+            split_project("just-a-project")
+
+    def test_matches_by_job_tag_then_time_and_host(self):
+        start = datetime(2026, 10, 1, 9, 0, 0)
+        execution = {
+            "run_id": "job42",
+            "recipe_name": "nanochat",
+            "started_at": start.isoformat(),
+            "ended_at": (start + timedelta(hours=2)).isoformat(),
+            "hosts": {"gpu": "root@gpu-box -p 22"},
+        }
+        elsewhere = make_run("a", start + timedelta(minutes=3), host="laptop")
+        on_host = make_run("b", start + timedelta(minutes=5), host="gpu-box")
+        too_late = make_run("c", start + timedelta(hours=3), host="gpu-box")
+        self.assertIs(best_match(execution, [elsewhere, on_host, too_late]), on_host)
+        self.assertIs(best_match(execution, [elsewhere, too_late]), elsewhere)
+        self.assertIsNone(best_match(execution, [too_late]))
+        tagged = make_run("d", start + timedelta(days=3), tags=["job42"])
+        self.assertIs(best_match(execution, [on_host, tagged]), tagged)
+        self.assertEqual(format_metrics({"loss": 0.41234, "acc": 0.9}), "loss=0.4123")
+        self.assertEqual(format_metrics({"acc": 0.9}), "")
+
+
+class WandbLinkTests(unittest.TestCase):
+    def setUp(self):
+        self.tmpdir = tempfile.TemporaryDirectory()
+        self.addCleanup(self.tmpdir.cleanup)
+        self.store = RuntimeStore(self.tmpdir.name)
+        self.start = datetime(2026, 10, 1, 9, 0, 0)
+        for run_id, offset in (("first", 0), ("second", 3)):
+            started = self.start + timedelta(hours=offset)
+            self.store.append_run(
+                {
+                    "run_id": run_id,
+                    "recipe_name": "nanochat",
+                    "started_at": started.isoformat(),
+                    "ended_at": (started + timedelta(hours=1)).isoformat(),
+                    "success": True,
+                }
+            )
+
+    def test_links_each_execution_and_logs_show_final_metrics(self):
+        runs = [
+            make_run("w1", self.start + timedelta(minutes=2), summary={"loss": 0.41}),
+            make_run("w2", self.start + timedelta(hours=3, minutes=1), summary={"loss": 0.38}),
+        ]
+        preview = link_executions(self.store, runs, dry_run=True)
+        self.assertEqual([(run_id, run.id) for run_id, run in preview], [("first", "w1"), ("second", "w2")])
+        self.assertNotIn("wandb", self.store.get_run("first"))
+
+        link_executions(self.store, runs, run_ids=["first"])
+        link = self.store.get_run("first")["wandb"]
+        self.assertEqual((link["run_id"], link["metrics"], link["project"]), ("w1", {"loss": 0.41}, "team/proj"))
+        self.assertNotIn("wandb", self.store.get_run("second"))
+
+        with patch("trainsh.core.execution_log.RuntimeStore", return_value=self.store), redirect_stdout(StringIO()) as out:
+            cmd_logs(["--list"])
+        first_line = next(line for line in out.getvalue().splitlines() if line.startswith("first"))
+        self.assertTrue(first_line.endswith("loss=0.41"))
+        executions = {ex["job_id"]: ex for ex in ExecutionLogReader(self.tmpdir.name).list_executions()}
+        self.assertEqual((executions["first"]["metrics"], executions["second"]["metrics"]), ({"loss": 0.41}, {}))
+
+
+if __name__ == "__main__":
+    unittest.main()
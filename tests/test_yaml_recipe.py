@@ -0,0 +1,119 @@
+import tempfile
+import unittest
+from contextlib import redirect_stdout
+from dataclasses import asdict
+from io import StringIO
+from pathlib import Path
+
+from trainsh.commands.recipe_convert import cmd_convert
+from trainsh.core.recipe_models import StepType
+from trainsh.pyrecipe import load_recipe
+from trainsh.pyrecipe.models import PythonRecipeError
+from trainsh.pyrecipe.yaml_recipe import dump_yaml_recipe, render_python_recipe
+
+YAML_RECIPE = """name: sweep
+variables:
+  LR: 0.001
+  FAST: true
+hosts:
+  gpu: vast:123
+defaults:
+  retries: 1
+steps:
+  - id: open
+    type: control
+    command: tmux.open
+    args: ["@gpu", as, work]
+  - id: train
+    type: execute
+    host: work
+    timeout: 2h
+    commands: |
+      cd /workspace
+      python train.py --lr ${LR}
+  - id: probe
+    provider: shell
+    operation: run
+    params: {command: nvidia-smi}
+    depends_on: []
+    retries: 0
+  - id: done
+    provider: util
+    operation: empty
+    depends_on: [train, probe]
+    trigger_rule: all_done
+"""
+
+
+def _steps(recipe):
+    rows = []
+    for step in recipe.steps:
+        row = asdict(step)
+        row.get("step_model", {}).pop("line_num", None)
+        rows.append(row)
+    return rows
+
+
+class YamlRecipeTests(unittest.TestCase):
+    def setUp(self):
+        self.tmpdir = tempfile.TemporaryDirectory()
+        self.addCleanup(self.tmpdir.cleanup)
+        self.root = Path(self.tmpdir.name)
+        self.path = self.root / "sweep.yaml"
+        self.path.write_text(YAML_RECIPE, encoding="utf-8")
+
+    def test_loads_yaml_like_a_python_recipe(self):
+        recipe = load_recipe(str(self.path))
+        self.assertEqual(recipe.variables, {"LR": "0.001", "FAST": "true"})
+        steps = {step.id: step for step in recipe.steps}
+        self.assertEqual(steps["train"].type, StepType.EXECUTE)
+        self.assertEqual(steps["train"].commands, "cd /workspace\npython train.py --lr ${LR}\n")
+        self.assertEqual(steps["train"].timeout, 7200)
+        self.assertEqual(steps["train"].raw.splitlines()[0], "@work > cd /workspace")
+        self.assertEqual(steps["train"].step_model.line_num, 14)
+        self.assertEqual((steps["train"].depends_on, steps["train"].retries), (["open"], 1))
+        self.assertEqual((steps["probe"].depends_on, steps["probe"].retries), ([], 0))
+        self.assertEqual((steps["done"].depends_on, steps["done"].trigger_rule), (["train", "probe"], "all_done"))
+
+    def test_schema_and_build_errors_name_the_line(self):
+        self.path.write_text("name: bad\nsteps:\n  - type: execute\n    comands: ls\n", encoding="utf-8")
+        with self.assertRaisesRegex(PythonRecipeError, r"line 4: steps\[0\]\.comands: unknown key"):
+            load_recipe(str(self.path))
+        self.path.write_text("name: bad\nsteps:\n  - provider: shell\n    operation: run\n    depends_on: [nope]\n", encoding="utf-8")
+        with self.assertRaisesRegex(PythonRecipeError, r"sweep\.yaml:3: steps\[0\]: unknown dependency step id: nope"):
+            load_recipe(str(self.path))
+
+    def test_format_is_detected_from_content_without_a_known_extension(self):
+        plain = self.root / "sweep.recipe"
+        plain.write_text(YAML_RECIPE, encoding="utf-8")
+        self.assertEqual(load_recipe(str(plain)).name, "sweep")
+
+    def test_conversions_round_trip(self):
+        recipe = load_recipe(str(self.path))
+        yaml_copy = self.root / "copy.yml"
+        yaml_copy.write_text(dump_yaml_recipe(recipe), encoding="utf-8")
+        self.assertIn("  commands: |\n    cd /workspace\n", yaml_copy.read_text())
+        python_copy = self.root / "copy.pyrecipe"
+        python_copy.write_text(render_python_recipe(load_recipe(str(yaml_copy))), encoding="utf-8")
+
+        for path in (yaml_copy, python_copy):
+            loaded = load_recipe(str(path))
+            self.assertEqual(_steps(loaded), _steps(recipe))
+            self.assertEqual((loaded.variables, loaded.hosts), (recipe.variables, recipe.hosts))
+
+    def test_convert_command_picks_the_other_format(self):
+        out = StringIO()
+        with redirect_stdout(out):
+            cmd_convert([str(self.path)])
+        self.assertTrue(out.getvalue().startswith("from trainsh import Recipe\n"))
+        self.assertIn("recipe.model_step('execute', id='train'", out.getvalue())
+        self.assertIn("recipe.steps[-1].depends_on = []", out.getvalue())
+
+        target = self.root / "out.yaml"
+        with redirect_stdout(StringIO()):
+            cmd_convert([str(self.path), "--to", "yaml", "-o", str(target)])
+        self.assertTrue(target.read_text().startswith("name: sweep\n"))
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -7,7 +7,7 @@ import subprocess
 from importlib.metadata import PackageNotFoundError, version
 from pathlib import Path
 
-from .pyrecipe import Host, HostPath, Recipe, RunpodHost, Storage, StoragePath, VastHost, load_python_recipe, local, official_uv_install_command
+from .pyrecipe import Host, HostPath, Recipe, RunpodHost, Storage, StoragePath, VastHost, load_python_recipe, load_recipe, local, official_uv_install_command
 from .services.flash_attn_support import flash_attn_install_script
 
 
@@ -87,6 +87,7 @@ __all__ = [
     "__version__",
     "flash_attn_install_script",
     "load_python_recipe",
+    "load_recipe",
     "local",
     "main",
     "official_uv_install_command",
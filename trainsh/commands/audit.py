@@ -0,0 +1,73 @@
+# tmux-trainsh audit command
+# List the audit trail of destructive and credential-touching operations
+
+import json
+import sys
+from typing import List, Optional
+
+from .help_catalog import render_command_help
+from .help_cmd import reject_subcommand_help
+
+usage = render_command_help("audit")
+
+
+def main(args: List[str]) -> Optional[str]:
+    """Main entry point for audit command."""
+    from ..services.audit_log import AUDIT_FILE, parse_time, read_entries
+
+    if args and args[0] in ("-h", "--help", "help"):
+        reject_subcommand_help()
+    if args and args[0] == "list":
+        args = args[1:]
+
+    options = {"--since": "", "--until": "", "--action": "", "--limit": "50"}
+    as_json = False
+    i = 0
+    while i < len(args):
+        arg = args[i]
+        if arg in options and i + 1 < len(args):
+            options[arg] = args[i + 1]
+            i += 2
+            continue
+        if arg == "--json":
+            as_json = True
+        elif arg == "--path":
+            print(AUDIT_FILE)
+            return None
+        else:
+            print(f"Unknown option: {arg}")
+            print(usage)
+            sys.exit(1)
+        i += 1
+
+    try:
+        since = parse_time(options["--since"]) if options["--since"] else None
+        until = parse_time(options["--until"]) if options["--until"] else None
+    except ValueError as exc:
+        print(str(exc))
+        sys.exit(1)
+    if not options["--limit"].isdigit():
+        print(f"Invalid --limit: {options['--limit']}")
+        sys.exit(1)
+    limit = int(options["--limit"])
+
+    entries = read_entries(since=since, until=until, action=options["--action"])
+    shown = entries[-limit:] if limit else entries
+    if as_json:
+        print(json.dumps(shown, indent=2, ensure_ascii=False))
+        return None
+    if not shown:
+        print("No audit entries.")
+        return None
+    print(f"{'Time':<20} {'User@Machine':<24} {'Action':<18} {'Host':<12} {'Status':<7} Target")
+    for entry in shown:
+        who = f"{entry.get('user', '')}@{entry.get('machine', '')}"
+        print(
+            f"{entry['ts'].replace('T', ' '):<20} {who[:24]:<24} {entry.get('action', '')[:18]:<18} "
+            f"{str(entry.get('host', ''))[:12]:<12} {entry.get('status', ''):<7} {entry.get('target', '')}"
+        )
+        if entry.get("detail"):
+            print(f"{'':<20} {entry['detail']}")
+    if len(entries) > len(shown):
+        print(f"\n{len(shown)} of {len(entries)} entries (use --limit 0 for all)")
+    return None
@@ -0,0 +1,141 @@
+# tmux-trainsh backup command
+# Encrypted backup/restore of config, hosts, storages, recipes, pricing and secrets
+
+import getpass
+import os
+import sys
+from pathlib import Path
+from typing import List, Optional
+
+from ..cli_utils import SubcommandSpec, dispatch_subcommand
+from .help_catalog import render_command_help
+from .help_cmd import reject_subcommand_help
+
+SUBCOMMAND_SPECS = (
+    SubcommandSpec("create", "Write an encrypted backup file."),
+    SubcommandSpec("show", "List what a backup contains."),
+    SubcommandSpec("restore", "Restore a backup into this machine's config and data."),
+)
+
+usage = render_command_help("backup")
+
+PASSPHRASE_ENV = "TRAINSH_BACKUP_PASSPHRASE"
+
+
+def _fail(message: str) -> None:
+    print(message)
+    print(usage)
+    sys.exit(1)
+
+
+def _passphrase(*, confirm: bool) -> str:
+    value = os.environ.get(PASSPHRASE_ENV)
+    if value:
+        return value
+    value = getpass.getpass("Backup passphrase: ")
+    if confirm and value != getpass.getpass("Repeat passphrase: "):
+        print("Passphrases do not match.")
+        sys.exit(1)
+    if not value:
+        print("A passphrase is required.")
+        sys.exit(1)
+    return value
+
+
+def _split(args: List[str], flags: tuple) -> tuple:
+    paths = [arg for arg in args if not arg.startswith("--")]
+    unknown = [arg for arg in args if arg.startswith("--") and arg not in flags]
+    if unknown:
+        _fail(f"Unknown option: {unknown[0]}")
+    if len(paths) != 1:
+        _fail("Exactly one backup path is required.")
+    return Path(paths[0]).expanduser(), set(args) - set(paths)
+
+
+def cmd_create(args: List[str]) -> None:
+    """Create an encrypted backup."""
+    from ..services.backup import create_backup
+
+    dest, flags = _split(args, ("--with-secrets", "--force"))
+    if dest.exists() and "--force" not in flags:
+        print(f"Refusing to overwrite {dest} (use --force).")
+        sys.exit(1)
+    manifest = create_backup(dest, _passphrase(confirm=True), include_secrets="--with-secrets" in flags)
+    print(f"Backup written: {dest}")
+    print(f"  Files: {len(manifest['files'])}")
+    if "--with-secrets" in flags:
+        print(f"  Secrets: {len(manifest['secrets'])}")
+    else:
+        print("  Secrets: not included (use --with-secrets)")
+
+
+def cmd_show(args: List[str]) -> None:
+    """List backup contents."""
+    from ..services.backup import read_manifest
+
+    path, _flags = _split(args, ())
+    try:
+        manifest = read_manifest(path, _passphrase(confirm=False))
+    except (OSError, ValueError) as exc:
+        print(f"Cannot read backup: {exc}")
+        sys.exit(1)
+    print(f"Backup: {path}")
+    print(f"  Created: {manifest.get('created_at', '?')} (tmux-trainsh {manifest.get('version', '?')})")
+    for name in manifest.get("files", []):
+        print(f"  {name}")
+    secrets = manifest.get("secrets", [])
+    if secrets:
+        print(f"  Secrets ({len(secrets)}): {', '.join(secrets)}")
+
+
+def cmd_restore(args: List[str]) -> None:
+    """Restore a backup."""
+    from ..services.backup import restore_backup
+
+    path, flags = _split(args, ("--overwrite", "--no-secrets", "--dry-run"))
+    try:
+        report = restore_backup(
+            path,
+            _passphrase(confirm=False),
+            on_conflict="overwrite" if "--overwrite" in flags else "skip",
+            include_secrets="--no-secrets" not in flags,
+            dry_run="--dry-run" in flags,
+        )
+    except (OSError, ValueError) as exc:
+        print(f"Cannot restore backup: {exc}")
+        sys.exit(1)
+    verb = "Would restore" if "--dry-run" in flags else "Restored"
+    print(f"{verb} {len(report.written)} new and {len(report.replaced)} replaced file(s); {len(report.unchanged)} unchanged.")
+    for name in report.replaced:
+        print(f"  replaced {name} (previous copy kept as .bak)")
+    for name in report.conflicts:
+        print(f"  kept local {name} (differs from backup)")
+    if report.secrets_written:
+        print(f"Secrets: {', '.join(report.secrets_written)}")
+    for key in report.secrets_conflicts:
+        print(f"  kept local secret {key} (differs from backup)")
+    if report.conflicts or report.secrets_conflicts:
+        print("Re-run with --overwrite to take the backup's versions.")
+
+
+def main(args: List[str]) -> Optional[str]:
+    """Main entry point for backup command."""
+    if not args:
+        print(usage)
+        return None
+    if args[0] in ("-h", "--help", "help"):
+        reject_subcommand_help()
+
+    commands = {
+        "create": cmd_create,
+        "show": cmd_show,
+        "restore": cmd_restore,
+    }
+    try:
+        handler = dispatch_subcommand(args[0], commands=commands)
+    except KeyError:
+        print(f"Unknown subcommand: {args[0]}")
+        print(usage)
+        sys.exit(1)
+    handler(args[1:])
+    return None
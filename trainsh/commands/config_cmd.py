@@ -14,6 +14,8 @@ SUBCOMMAND_SPECS = (
     SubcommandSpec("set", "Write one config key by dotted path."),
     SubcommandSpec("reset", "Reset config.yaml back to defaults."),
     SubcommandSpec("tmux", "Inspect or edit tmux-specific settings."),
+    SubcommandSpec("profile", "Manage named config profiles and switch between them."),
+    SubcommandSpec("viewer", "Turn viewer mode (no destructive commands) on or off."),
 )
 
 TMUX_SUBCOMMAND_SPECS = (
@@ -29,9 +31,10 @@ tmux_usage = render_command_help("config-tmux")
 
 def cmd_show(args: List[str]) -> None:
     """Show current configuration."""
-    from ..config import load_config
+    from ..config import active_profile_name, load_config
 
     config = load_config()
+    profile = active_profile_name()
 
     def print_dict(d: dict, indent: int = 0):
         prefix = "  " * indent
@@ -42,7 +45,7 @@ def cmd_show(args: List[str]) -> None:
             else:
                 print(f"{prefix}{key} = {value}")
 
-    print("Current configuration:")
+    print("Current configuration:" + (f" (profile: {profile})" if profile else ""))
     print("-" * 40)
     print_dict(config)
 
@@ -76,25 +79,26 @@ def cmd_set(args: List[str]) -> None:
     from ..config import set_config_value
 
     key = args[0]
-    value = " ".join(args[1:])
-
-    # Type conversion
-    if value.lower() == "true":
-        value = True
-    elif value.lower() == "false":
-        value = False
-    elif value.isdigit():
-        value = int(value)
-    else:
-        try:
-            value = float(value)
-        except ValueError:
-            pass  # Keep as string
+    value = parse_config_value(" ".join(args[1:]))
 
     set_config_value(key, value)
     print(f"Set {key} = {value}")
 
 
+def parse_config_value(value: str):
+    """Convert a CLI string to bool/int/float where it looks like one."""
+    if value.lower() == "true":
+        return True
+    if value.lower() == "false":
+        return False
+    if value.isdigit():
+        return int(value)
+    try:
+        return float(value)
+    except ValueError:
+        return value  # Keep as string
+
+
 def cmd_reset(args: List[str]) -> None:
     """Reset to default configuration."""
     confirm = prompt_input("Reset all settings to defaults? (y/N): ")
@@ -102,12 +106,61 @@ def cmd_reset(args: List[str]) -> None:
         print("Cancelled.")
         return
 
-    from ..config import save_config, get_default_config
+    from ..config import get_default_config, merge_dicts, profile_overrides, read_config_file, save_config
 
-    save_config(get_default_config())
+    config = get_default_config()
+    # Profiles are managed with `train config profile` and survive a reset.
+    config["profiles"] = read_config_file().get("profiles") or {}
+    save_config(merge_dicts(config, profile_overrides(config)))
     print("Configuration reset to defaults.")
 
 
+def cmd_viewer(args: List[str]) -> None:
+    """Show, enable or disable viewer mode."""
+    import getpass
+
+    from ..services.viewer_mode import (
+        DESTRUCTIVE_ACTIONS,
+        disable_viewer_mode,
+        enable_viewer_mode,
+        viewer_settings,
+    )
+
+    action = args[0] if args else "status"
+    if action not in ("status", "on", "off") or any(arg != "--passphrase" for arg in args[1:]):
+        print("Usage: train config viewer [status] | on [--passphrase] | off")
+        sys.exit(1)
+
+    settings = viewer_settings()
+    if action == "status":
+        state = "on" if settings["enabled"] else "off"
+        print(f"Viewer mode: {state}" + (" (passphrase protected)" if settings["passphrase_hash"] else ""))
+        if settings["enabled"]:
+            print("Disabled:")
+            for name, command in DESTRUCTIVE_ACTIONS.items():
+                print(f"  {name:<16} {command}")
+        return
+    if action == "on":
+        if settings["enabled"]:
+            print("Viewer mode is already on.")
+            return
+        passphrase = ""
+        if "--passphrase" in args:
+            passphrase = getpass.getpass("Passphrase to turn viewer mode off: ")
+            if not passphrase or passphrase != getpass.getpass("Repeat passphrase: "):
+                print("Passphrases are empty or do not match; viewer mode not changed.")
+                sys.exit(1)
+        enable_viewer_mode(passphrase)
+        print("Viewer mode on: destructive commands are refused.")
+        return
+    if not settings["enabled"]:
+        print("Viewer mode is already off.")
+        return
+    passphrase = getpass.getpass("Viewer mode passphrase: ") if settings["passphrase_hash"] else ""
+    disable_viewer_mode(passphrase)
+    print("Viewer mode off.")
+
+
 def generate_tmux_conf(tmux_options: list) -> str:
     """Generate tmux.conf content from options list."""
     lines = [
@@ -297,12 +350,22 @@ def main(args: List[str]) -> Optional[str]:
     if subcommand == "tmux":
         _handle_tmux_namespace(subargs)
         return None
+    if subcommand in ("set", "reset") or (subcommand == "profile" and subargs[:1] in (["add"], ["switch"], ["remove"])):
+        from ..services.viewer_mode import require_operator
+
+        require_operator("config.change")
+    if subcommand == "profile":
+        from .config_profile import main as profile_main
+
+        profile_main(subargs)
+        return None
 
     commands = {
         "show": cmd_show,
         "get": cmd_get,
         "set": cmd_set,
         "reset": cmd_reset,
+        "viewer": cmd_viewer,
     }
 
     try:
@@ -0,0 +1,217 @@
+# tmux-trainsh config profile command
+# Named config profiles (e.g. work vs personal) and fast switching
+
+import sys
+from typing import Any, Dict, List, Optional
+
+from ..cli_utils import SubcommandSpec, dispatch_subcommand, prompt_input
+from .help_catalog import render_command_help
+from .help_cmd import reject_subcommand_help
+
+SUBCOMMAND_SPECS = (
+    SubcommandSpec("list", "List profiles and mark the active one."),
+    SubcommandSpec("show", "Show a profile's overrides and data location."),
+    SubcommandSpec("add", "Create a profile or change its overrides."),
+    SubcommandSpec("switch", "Make a profile active for later commands."),
+    SubcommandSpec("remove", "Delete a profile."),
+)
+
+usage = render_command_help("config-profile")
+
+# Shortcut flags for the settings profiles usually differ in.
+_SHORTCUTS = {
+    "--currency": "ui.currency",
+    "--ssh-key": "defaults.ssh_key_path",
+    "--secret-prefix": "secrets.key_prefix",
+    "--notify": "notifications.channels",
+    "--webhook": "notifications.webhook_url",
+}
+
+
+def _fail(message: str) -> None:
+    print(message)
+    print(usage)
+    sys.exit(1)
+
+
+def _assign(data: Dict[str, Any], path: str, value: Any) -> None:
+    keys = path.split(".")
+    for key in keys[:-1]:
+        if not isinstance(data.get(key), dict):
+            data[key] = {}
+        data = data[key]
+    data[keys[-1]] = value
+
+
+def _profile_name(args: List[str]) -> str:
+    from ..services.config_profiles import sanitize_profile_name
+
+    if not args or args[0].startswith("--"):
+        _fail("Profile name required.")
+    name = sanitize_profile_name(args[0])
+    if not name:
+        _fail(f"Invalid profile name: {args[0]}")
+    return name
+
+
+def cmd_list(args: List[str]) -> None:
+    """List config profiles."""
+    from ..config import active_profile_name
+    from ..services.config_profiles import load_profiles, profile_summary
+
+    del args
+    profiles = load_profiles()
+    if not profiles:
+        print("No config profiles.")
+        print("Use 'train config profile add <name>' to create one.")
+        return
+    active = active_profile_name()
+    print("Config profiles:")
+    print("-" * 60)
+    for name, data in profiles.items():
+        mark = "*" if name == active else " "
+        scope = "isolated data" if data.get("isolate_data") else "shared data"
+        overrides = len(profile_summary(data))
+        print(f" {mark}{name:<16} {scope:<14} {overrides} override(s)  {data.get('description', '')}".rstrip())
+    print("-" * 60)
+    if active and active not in profiles:
+        print(f"Active profile {active!r} is not defined; the base config applies.")
+    elif not active:
+        print("No profile active; the base config applies.")
+
+
+def cmd_show(args: List[str]) -> None:
+    """Show one profile."""
+    from ..config import active_profile_name
+    from ..constants import CONFIG_DIR, STATE_DIR
+    from ..services.config_profiles import load_profiles, profile_config_dir, profile_summary
+
+    name = _profile_name(args) if args else active_profile_name()
+    if not name:
+        print("No profile active; the base config applies.")
+        return
+    data = load_profiles().get(name)
+    if data is None:
+        print(f"Profile not found: {name}")
+        sys.exit(1)
+    print(f"Profile: {name}" + (" (active)" if name == active_profile_name() else ""))
+    if data.get("description"):
+        print(f"  Description: {data['description']}")
+    if data.get("isolate_data"):
+        print(f"  Data: {profile_config_dir(name)} (state: {STATE_DIR / 'profiles' / name})")
+    else:
+        print(f"  Data: shared ({CONFIG_DIR})")
+    overrides = profile_summary(data)
+    print("  Overrides:" if overrides else "  Overrides: none")
+    for line in overrides:
+        print(f"    {line}")
+
+
+def cmd_add(args: List[str]) -> None:
+    """Create a profile or update its overrides."""
+    from ..services.config_profiles import load_profiles, save_profile
+    from .config_cmd import parse_config_value
+
+    name = _profile_name(args)
+    profiles = load_profiles()
+    data = profiles.get(name, {})
+    i = 1
+    while i < len(args):
+        arg = args[i]
+        if arg in ("--isolate", "--shared"):
+            data["isolate_data"] = arg == "--isolate"
+            i += 1
+            continue
+        if i + 1 >= len(args) or arg not in (*_SHORTCUTS, "--set", "--description"):
+            _fail(f"Unknown option: {arg}")
+        value = args[i + 1]
+        i += 2
+        if arg == "--description":
+            data["description"] = value
+        elif arg == "--notify":
+            _assign(data, _SHORTCUTS[arg], [item.strip() for item in value.split(",") if item.strip()])
+        elif arg in _SHORTCUTS:
+            _assign(data, _SHORTCUTS[arg], value)
+        else:
+            key, sep, raw = value.partition("=")
+            if not sep or not key or key.split(".")[0] == "profiles":
+                _fail(f"Invalid --set value: {value} (expected KEY=VALUE)")
+            _assign(data, key, parse_config_value(raw))
+    if data.get("isolate_data") is False:
+        data.pop("isolate_data")
+    save_profile(name, data)
+    print(f"{'Updated' if name in profiles else 'Created'} profile: {name}")
+    prefix = (data.get("secrets") or {}).get("key_prefix")
+    if prefix:
+        print(f"Secrets resolve as {prefix}<NAME> in this profile, e.g. `train secrets set VAST_API_KEY` stores {prefix}VAST_API_KEY.")
+
+
+def cmd_switch(args: List[str]) -> None:
+    """Switch the active profile."""
+    import os
+
+    from ..services.config_profiles import switch_profile
+
+    name = "" if args and args[0] == "--clear" else _profile_name(args)
+    try:
+        previous = switch_profile(name)
+    except KeyError:
+        print(f"Profile not found: {name}")
+        print("Use 'train config profile list' to see profiles.")
+        sys.exit(1)
+    if name:
+        print(f"Switched to profile: {name}" + (f" (was {previous})" if previous and previous != name else ""))
+    else:
+        print("Profile cleared; the base config applies.")
+    env_name = os.environ.get("TRAINSH_PROFILE")
+    if env_name is not None and env_name.strip() != name:
+        print(f"Note: TRAINSH_PROFILE={env_name} still overrides this in the current shell.")
+
+
+def cmd_remove(args: List[str]) -> None:
+    """Delete a profile."""
+    from ..services.config_profiles import load_profiles, remove_profile
+
+    name = _profile_name(args)
+    options = args[1:]
+    unknown = [arg for arg in options if arg not in ("--purge-data", "--yes", "-y")]
+    if unknown:
+        _fail(f"Unknown option: {unknown[0]}")
+    if name not in load_profiles():
+        print(f"Profile not found: {name}")
+        sys.exit(1)
+    purge = "--purge-data" in options
+    if "--yes" not in options and "-y" not in options:
+        extra = " and its hosts, storages, logs and run state" if purge else ""
+        confirm = prompt_input(f"Delete profile {name}{extra}? (y/N): ")
+        if confirm is None or confirm.lower() != "y":
+            print("Cancelled.")
+            return
+    remove_profile(name, purge_data=purge)
+    print(f"Removed profile: {name}")
+
+
+def main(args: List[str]) -> Optional[str]:
+    """Main entry point for `train config profile`."""
+    if not args:
+        print(usage)
+        return None
+    if args[0] in ("-h", "--help", "help"):
+        reject_subcommand_help()
+
+    commands = {
+        "list": cmd_list,
+        "show": cmd_show,
+        "add": cmd_add,
+        "switch": cmd_switch,
+        "use": cmd_switch,
+        "remove": cmd_remove,
+    }
+    try:
+        handler = dispatch_subcommand(args[0], commands=commands)
+    except KeyError:
+        print(f"Unknown profile subcommand: {args[0]}")
+        print(usage)
+        raise SystemExit(1)
+    handler(args[1:])
+    return None
@@ -0,0 +1,155 @@
+# tmux-trainsh dashboard command
+# One aggregated view over hosts, running executions, transfers, and Vast spend
+
+import json
+import sys
+import threading
+from typing import Dict, List, Optional
+
+from .help_catalog import render_command_help
+from .help_cmd import reject_subcommand_help
+from .transfer_queue import describe_progress
+
+usage = render_command_help("dashboard")
+
+
+def _reachability_label(value: Optional[bool]) -> str:
+    if value is None:
+        return "unknown"
+    return "online" if value else "offline"
+
+
+def _print_snapshot(snapshot: dict) -> None:
+    suffix = " (cached)" if snapshot.get("cached") else ""
+    print(f"Dashboard @ {str(snapshot.get('generated_at', ''))[:19]}{suffix}")
+    if snapshot.get("viewer_mode"):
+        print("Viewer mode: destructive commands are disabled.")
+
+    hosts = snapshot.get("hosts", [])
+    print(
+        f"\nHosts ({snapshot.get('hosts_reachable', 0)} online, "
+        f"{snapshot.get('hosts_unreachable', 0)} offline):"
+    )
+    if not hosts:
+        print("  No hosts configured.")
+    for row in hosts:
+        print(f"  {row['name']:<20} {row['type']:<8} {_reachability_label(row.get('reachable'))}")
+
+    executions = snapshot.get("executions", [])
+    print(f"\nRunning executions ({len(executions)}):")
+    for row in executions:
+        steps = ", ".join(row.get("current_steps") or []) or "-"
+        print(f"  {row['run_id'][:8]:<10} {row['recipe'][:20]:<20} step: {steps}")
+
+    transfers = snapshot.get("transfers", [])
+    print(f"\nActive transfers ({len(transfers)}):")
+    for row in transfers:
+        progress = row.get("progress")
+        progress_text = f"  [{describe_progress(progress)}]" if isinstance(progress, dict) and progress else ""
+        label = row.get("transfer_id") or row["run_id"][:8]
+        status = row.get("status") or "running"
+        print(f"  {label:<10} {status:<8} {row['source']} -> {row['dest']}{progress_text}")
+
+    vast = snapshot.get("vast", {})
+    instances = vast.get("instances", [])
+    print(f"\nVast.ai instances ({len(instances)}, ${float(vast.get('hourly_total_usd', 0.0)):.3f}/hr running):")
+    if vast.get("error"):
+        print(f"  unavailable: {vast['error']}")
+    for row in instances:
+        print(f"  {row['id']:<10} {row['status']:<10} {row['gpu']:<18} ${row['dph_total']:.3f}/hr {row['label']}")
+
+    failures = snapshot.get("recent_failures", [])
+    print(f"\nRecent failures ({len(failures)}):")
+    for row in failures:
+        steps = ", ".join(row.get("failed_steps") or []) or "-"
+        print(f"  {row['run_id'][:8]:<10} {row['recipe'][:20]:<20} failed: {steps}")
+
+
+def _serve_prometheus(options: Dict[str, str]) -> None:
+    """Run the `/metrics` exporter until Ctrl+C."""
+    from ..config import get_config_value
+    from ..services.prometheus_exporter import DEFAULT_BIND, DEFAULT_PORT, MetricsExporter
+    from .host import load_hosts
+
+    port = str(options.get("--port") or get_config_value("prometheus.port", DEFAULT_PORT))
+    if not port.isdigit():
+        print(f"Invalid --port: {port}")
+        sys.exit(1)
+    bind = str(options.get("--bind") or get_config_value("prometheus.bind", DEFAULT_BIND))
+    configured = options["--hosts"].split(",") if options.get("--hosts") else get_config_value("prometheus.hosts", []) or []
+    hosts = load_hosts(include_auto_vast=False)
+    names = [str(name).strip() for name in configured if str(name).strip()] or sorted(hosts)
+    missing = [name for name in names if name not in hosts]
+    if missing:
+        print(f"Host not found: {', '.join(missing)}")
+        sys.exit(1)
+
+    try:
+        exporter = MetricsExporter({name: hosts[name] for name in names}, port=int(port), bind=bind).start()
+    except OSError as exc:
+        print(f"Cannot listen on {bind}:{port}: {exc}")
+        sys.exit(1)
+    address, bound_port = exporter.address
+    print(f"Serving Prometheus metrics on http://{address}:{bound_port}/metrics for {len(names)} host(s) (Ctrl+C to stop)...")
+    try:
+        threading.Event().wait()
+    except KeyboardInterrupt:
+        print("\nStopped.")
+    finally:
+        exporter.stop()
+
+
+def main(args: List[str]) -> Optional[str]:
+    """Main entry point for dashboard command."""
+    if args and args[0] in ("-h", "--help", "help"):
+        reject_subcommand_help()
+
+    as_json = False
+    refresh = False
+    probe_hosts = True
+    include_vast = True
+    prometheus = False
+    options: Dict[str, str] = {}
+    i = 0
+    while i < len(args):
+        arg = args[i]
+        if arg == "--json":
+            as_json = True
+        elif arg == "--refresh":
+            refresh = True
+        elif arg == "--no-probe":
+            probe_hosts = False
+        elif arg == "--no-vast":
+            include_vast = False
+        elif arg == "--prometheus":
+            prometheus = True
+        elif arg in ("--port", "--bind", "--hosts") and i + 1 < len(args):
+            options[arg] = args[i + 1]
+            i += 1
+        else:
+            print(f"Unknown option: {arg}")
+            print(usage)
+            sys.exit(1)
+        i += 1
+
+    if prometheus:
+        _serve_prometheus(options)
+        return None
+
+    from ..services.dashboard import dashboard_snapshot
+
+    snapshot = dashboard_snapshot(refresh=refresh, probe_hosts=probe_hosts, include_vast=include_vast)
+    if as_json:
+        print(json.dumps(snapshot, indent=2, ensure_ascii=False))
+        return None
+    _print_snapshot(snapshot)
+    return None
+
+
+if __name__ == "__main__":
+    main(sys.argv[1:])
+elif __name__ == "__doc__":
+    cd = sys.cli_docs  # type: ignore
+    cd["usage"] = usage
+    cd["help_text"] = "Aggregated status dashboard"
+    cd["short_desc"] = "Show hosts, jobs, transfers, and spend at a glance"
@@ -0,0 +1,203 @@
+"""Docker containers on hosts: list, start/stop/rm, follow logs, and exec a shell."""
+
+from __future__ import annotations
+
+import json
+import sys
+from typing import List, Optional
+
+from ..cli_utils import SubcommandSpec, dispatch_subcommand, prompt_input
+from .help_catalog import render_command_help
+from .help_cmd import reject_subcommand_help
+
+SUBCOMMAND_SPECS = (
+    SubcommandSpec("ps", "List containers on a host."),
+    SubcommandSpec("images", "List images on a host."),
+    SubcommandSpec("start", "Start a stopped container."),
+    SubcommandSpec("stop", "Stop a running container."),
+    SubcommandSpec("restart", "Restart a container."),
+    SubcommandSpec("rm", "Remove a container."),
+    SubcommandSpec("logs", "Show or follow container logs, saved to the local log directory."),
+    SubcommandSpec("exec", "Open an interactive shell inside a container."),
+)
+
+usage = render_command_help("docker")
+
+
+def _fail(exc: Exception) -> None:
+    print(f"Docker command failed: {exc.args[0] if exc.args else exc}")
+    sys.exit(1)
+
+
+def _split(args: List[str], flags: tuple, valued: tuple, usage_line: str):
+    positional: List[str] = []
+    options = {}
+    i = 0
+    while i < len(args):
+        arg = args[i]
+        if arg in valued and i + 1 < len(args):
+            options[arg] = args[i + 1]
+            i += 2
+            continue
+        if arg in flags:
+            options[arg] = True
+        elif arg.startswith("-"):
+            print(f"Unknown option: {arg}")
+            print(f"Usage: {usage_line}")
+            sys.exit(1)
+        else:
+            positional.append(arg)
+        i += 1
+    return positional, options
+
+
+def cmd_ps(args: List[str]) -> None:
+    """List containers on a host."""
+    from ..services.docker_host import list_containers
+
+    usage_line = "train docker ps <host> [--running] [--json]"
+    positional, options = _split(args, ("--running", "--json"), (), usage_line)
+    if len(positional) != 1:
+        print(f"Usage: {usage_line}")
+        sys.exit(1)
+    try:
+        rows = list_containers(positional[0], all=not options.get("--running"))
+    except (KeyError, RuntimeError) as exc:
+        _fail(exc)
+    if options.get("--json"):
+        print(json.dumps(rows, indent=2))
+        return
+    if not rows:
+        print("No containers found.")
+        return
+    print(f"  {'NAME':<24} {'STATE':<10} {'IMAGE':<36} STATUS")
+    for row in rows:
+        print(f"  {row['name'][:24]:<24} {row['state']:<10} {row['image'][:36]:<36} {row['status']}")
+
+
+def cmd_images(args: List[str]) -> None:
+    """List images on a host."""
+    from ..services.docker_host import list_images
+
+    usage_line = "train docker images <host> [--json]"
+    positional, options = _split(args, ("--json",), (), usage_line)
+    if len(positional) != 1:
+        print(f"Usage: {usage_line}")
+        sys.exit(1)
+    try:
+        rows = list_images(positional[0])
+    except (KeyError, RuntimeError) as exc:
+        _fail(exc)
+    if options.get("--json"):
+        print(json.dumps(rows, indent=2))
+        return
+    if not rows:
+        print("No images found.")
+        return
+    print(f"  {'REPOSITORY':<40} {'TAG':<20} {'ID':<12} {'SIZE':>10}  CREATED")
+    for row in rows:
+        print(f"  {row['repository'][:40]:<40} {row['tag'][:20]:<20} {row['id']:<12} {row['size']:>10}  {row['created']}")
+
+
+def _lifecycle(action: str, args: List[str]) -> None:
+    from ..services.docker_host import container_action
+
+    usage_line = f"train docker {action} <host> <container>" + (" [--force] [--yes]" if action == "rm" else "")
+    positional, options = _split(args, ("--force", "-f", "--yes", "-y"), (), usage_line)
+    if len(positional) != 2:
+        print(f"Usage: {usage_line}")
+        sys.exit(1)
+    host_id, container = positional
+    if action == "rm" and not (options.get("--yes") or options.get("-y")):
+        confirm = prompt_input(f"Remove container {container} on {host_id}? [y/N]: ")
+        if confirm is None or confirm.strip().lower() not in ("y", "yes"):
+            print("Cancelled.")
+            return
+    try:
+        container_action(host_id, action, container, force=bool(options.get("--force") or options.get("-f")))
+    except (KeyError, RuntimeError, ValueError) as exc:
+        _fail(exc)
+    past = {"start": "Started", "stop": "Stopped", "restart": "Restarted", "rm": "Removed"}[action]
+    print(f"{past} container {container} on {host_id}.")
+
+
+def cmd_logs(args: List[str]) -> None:
+    """Print or follow container logs, appending them to the local log file."""
+    from ..services.docker_host import container_log_path, stream_logs
+
+    usage_line = "train docker logs <host> <container> [-f] [--tail N] [--since DURATION]"
+    positional, options = _split(args, ("-f", "--follow"), ("--tail", "--since"), usage_line)
+    if len(positional) != 2:
+        print(f"Usage: {usage_line}")
+        sys.exit(1)
+    host_id, container = positional
+    try:
+        tail = int(options.get("--tail", 200))
+    except ValueError:
+        print(f"Invalid --tail: {options['--tail']}")
+        sys.exit(1)
+    log_path = container_log_path(host_id, container)
+    print(f"Logging to {log_path}", file=sys.stderr)
+    try:
+        code = stream_logs(
+            host_id,
+            container,
+            follow=bool(options.get("-f") or options.get("--follow")),
+            tail=tail,
+            since=str(options.get("--since", "")),
+            on_line=lambda line: print(line, flush=True),
+            log_path=log_path,
+        )
+    except (KeyError, ValueError) as exc:
+        _fail(exc)
+    if code not in (0, None, -15, 130):
+        sys.exit(code)
+
+
+def cmd_exec(args: List[str]) -> None:
+    """Open an interactive shell in a container."""
+    from ..services.docker_host import exec_shell
+
+    usage_line = "train docker exec <host> <container> [--shell CMD]"
+    positional, options = _split(args, (), ("--shell",), usage_line)
+    if len(positional) != 2:
+        print(f"Usage: {usage_line}")
+        sys.exit(1)
+    try:
+        code = exec_shell(positional[0], positional[1], str(options.get("--shell", "")))
+    except (KeyError, ValueError) as exc:
+        _fail(exc)
+    if code:
+        sys.exit(code)
+
+
+def main(args: List[str]) -> Optional[str]:
+    """Main entry point for docker command."""
+    if not args:
+        print(usage)
+        return None
+    if args[0] in ("-h", "--help", "help"):
+        reject_subcommand_help()
+
+    commands = {
+        "ps": cmd_ps,
+        "images": cmd_images,
+        "start": lambda rest: _lifecycle("start", rest),
+        "stop": lambda rest: _lifecycle("stop", rest),
+        "restart": lambda rest: _lifecycle("restart", rest),
+        "rm": lambda rest: _lifecycle("rm", rest),
+        "logs": cmd_logs,
+        "exec": cmd_exec,
+    }
+    try:
+        handler = dispatch_subcommand(args[0], commands=commands)
+    except KeyError:
+        print(f"Unknown subcommand: {args[0]}")
+        print(usage)
+        sys.exit(1)
+    handler(args[1:])
+    return None
+
+
+if __name__ == "__main__":
+    main(sys.argv[1:])
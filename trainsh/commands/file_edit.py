@@ -0,0 +1,73 @@
+"""Preview and quick-edit helpers shared by `train host files` and `train storage cat|edit`."""
+
+from __future__ import annotations
+
+import os
+import subprocess
+import tempfile
+
+from ..cli_utils import prompt_input
+
+
+def print_preview(target, path: str, nbytes: int) -> bool:
+    """Print the head of one file; returns False when it could not be read."""
+    from ..services.file_access import file_read_head
+
+    try:
+        head = file_read_head(target, path, nbytes)
+    except RuntimeError as exc:
+        print(f"Cannot read {path}: {exc}")
+        return False
+    if head.binary:
+        print(f"{path} looks binary; not printing it.")
+        return True
+    print(head.text, end="" if head.text.endswith("\n") else "\n")
+    if head.truncated:
+        print(f"... (truncated at {len(head.data)} bytes)")
+    return True
+
+
+def edit_file(target, path: str) -> bool:
+    """Open a small text file in $EDITOR and write it back after confirmation."""
+    from ..services.file_access import MAX_EDIT_BYTES, file_read_head, file_write
+
+    try:
+        head = file_read_head(target, path, MAX_EDIT_BYTES)
+    except RuntimeError as exc:
+        print(f"Cannot read {path}: {exc}")
+        return False
+    if head.binary or head.truncated:
+        print(f"{path} is {'binary' if head.binary else 'larger than the quick-edit limit'}; open a shell to edit it.")
+        return False
+
+    editor = os.environ.get("EDITOR") or os.environ.get("VISUAL") or "nano"
+    suffix = os.path.splitext(path)[1] or ".txt"
+    with tempfile.NamedTemporaryFile(mode="wb", suffix=suffix, delete=False) as handle:
+        handle.write(head.data)
+        temp_path = handle.name
+    try:
+        if subprocess.run([editor, temp_path]).returncode != 0:
+            print("Editor exited with error, changes not saved")
+            return False
+        with open(temp_path, "rb") as handle:
+            updated = handle.read()
+    finally:
+        os.unlink(temp_path)
+
+    if updated == head.data:
+        print("No changes.")
+        return True
+    confirm = prompt_input(f"Write {len(updated)} bytes to {path}? (y/N): ")
+    if confirm is None or confirm.lower() != "y":
+        print("Cancelled.")
+        return False
+    try:
+        file_write(target, path, updated)
+    except (RuntimeError, ValueError) as exc:
+        print(f"Write failed: {exc}")
+        return False
+    print(f"Saved {path}.")
+    return True
+
+
+__all__ = ["edit_file", "print_preview"]
@@ -0,0 +1,417 @@
+"""Host, project, search, audit, and host-service command reference."""
+
+from __future__ import annotations
+
+from .help_model import CommandDoc, DocBlock
+
+
+HOST_DOCS: tuple[CommandDoc, ...] = (
+    CommandDoc(
+        key="host",
+        label="Manage Named Hosts",
+        group="Infrastructure",
+        command="train host",
+        summary="Manage named SSH or Colab-backed host definitions used by recipes and transfers.",
+        usage_lines=(
+            "train host list",
+            "train host add",
+            "train host show <name>",
+            "train host edit <name>",
+            "train host ssh <name> [--log]",
+            "train host logs [name] [-n LINES] [--raw] [--path]",
+            "train host run <name> -- <command>",
+            "train host tunnel <name> --local-port <port> --remote-port <port>",
+            "train host clone <name> <repo-url> [destination] [options]",
+            "train host files <name> [path]",
+            "train host check <name>",
+            "train host latency <name> [--count N] [--interval SECS] [--history] [--json]",
+            "train host cloudflared setup <name> --hostname <ssh.example.com> [--bin PATH] [--install]",
+            "train host cloudflared verify <name>",
+            "train host cloudflared forward <name> [--local-port N] [--interval SECS]",
+            "train host tailscale [list [--json] | link <name> <node> | unlink <name>]",
+            "train host ssh-config [--print] [--no-include]",
+            "train host link <name> vast:<id> | --unlink",
+            "train host refresh [<name>...]",
+            "train host refresh --all [--timeout SECS] [--json]",
+            "train host wake <name> [--mac MAC] [--broadcast ADDR] [--timeout 5m] [--no-wait]",
+            "train host power-off <name> [--suspend] [--command CMD] [--timeout 2m] [--no-wait] [--yes]",
+            "train host du <name> [path] [--depth N] [--refresh] [--background]",
+            "train host du <name> --delete <path> [--yes]",
+            "train host ps <name> [filter] [--gpu] [--limit N] [--json]",
+            "train host kill <name> <pid> [--signal TERM|KILL|INT|...] [--yes]",
+            "train host manifest <name> <remote_dir> [--output FILE] [--json]",
+            "train host verify <name> <remote_dir> <local_dir> [--save FILE] [--json]",
+            "train host verify --manifest FILE <local_dir> [--json]",
+            "train host vscode <name> [--folder DIR] [--alias NAME] [--open]",
+            "train host vscode <name> --code-server [--install] [--port N] [--local-port N]",
+            "train host vscode <name> --stop",
+            "train host metrics <name> [<name>...] [--interval SECS] [--count N] [--json]",
+            "train host monitor [--once] [--interval SECS] [--latency]",
+            "train host queue [list|run|clear]",
+            "train host bootstrap <name> [--profile <profile>] [--force]",
+            "train host follow start <name> <path> [-n LINES] [--job JOB_ID] [--no-alerts] [--foreground]",
+            "train host follow [list [name]] | show <id> [-n LINES] | stop <id>... | stop --all [name]",
+            "train host follow query <id> [--where EXPR] [--steps START:END] [-n LINES] [--json] [--no-color]",
+            "train host follow merge <id|name>... [--job JOB_ID] [--page N] [--page-size N] [--live] [--json]",
+            "train host follow alert [list] | add <rule> (--pattern REGEX | --keyword TEXT) [--action notify|command|cancel] [--command CMD] [--cooldown SECS] [--level LEVEL] [--host NAME]... | remove <rule>",
+            "train host broadcast [list] | create <group> <host[:session]>... | remove <group>",
+            "train host broadcast send <group> [--no-enter] -- <text>",
+            "train host paste <host>:<session> [--file PATH] [--enter] [--no-bracket] [--yes]",
+            "train host defaults <name> [--workdir DIR] [--shell SHELL] [--history-limit N] [--env K=V]... [--unset K]... [--clear]",
+            "train host flash-attn <name> [options]",
+            "train host remove <name>",
+        ),
+        blocks=(
+            DocBlock(
+                "Subcommands",
+                (
+                    "list                List named reusable host definitions.",
+                    "add                 Add a new SSH or Colab-backed host interactively.",
+                    "edit                Modify an existing named host.",
+                    "show                Inspect one host definition.",
+                    "ssh                 Open an SSH session using stored connection settings.",
+                    "logs                List or print interactive sessions mirrored with `ssh --log`.",
+                    "run                 Run one remote shell command with stored connection settings.",
+                    "tunnel              Open one local SSH port-forward tunnel to a host.",
+                    "clone               Clone one git repository on a host.",
+                    "files               Browse remote files over SFTP; preview or quick-edit small text files.",
+                    "check               Check whether a host is reachable.",
+                    "latency             Time TCP connect, SSH banner and handshake per route and keep a history.",
+                    "cloudflared         Set up, verify, or keep alive a Cloudflare Access SSH tunnel.",
+                    "tailscale           List tailnet nodes, or link a host to a Tailscale node name.",
+                    "ssh-config          Export all managed hosts to an OpenSSH include for plain `ssh <alias>`.",
+                    "link                Bind a stored SSH host to a Vast.ai instance (`vast:<id>`).",
+                    "refresh             Re-read linked hosts' SSH endpoints from Vast and update saved sessions.",
+                    "wake                Send a Wake-on-LAN packet and wait until the host answers over SSH.",
+                    "power-off           Shut down or suspend a host over SSH and wait until it is offline.",
+                    "du                  Show what is using disk space under a path, or delete one entry.",
+                    "ps                  List processes on a host with the GPU memory each one holds.",
+                    "kill                Send a signal to one process on a host.",
+                    "manifest            Hash every file under a remote directory into a sha256 manifest.",
+                    "verify              Verify a downloaded local copy against a remote directory or manifest.",
+                    "vscode              Write a VS Code Remote-SSH entry or start a tunneled code-server.",
+                    "metrics             Stream CPU, memory, disk IO, network and GPU samples from hosts.",
+                    "monitor             Track host connectivity and replay queued operations on reconnect.",
+                    "queue               List, replay, or clear operations queued while offline.",
+                    "bootstrap           Apply a bootstrap profile (packages, timezone, tmux.conf) to one host.",
+                    "follow              Follow log files on hosts in the background with `tail -F`.",
+                    "broadcast           Type the same input into a group of host tmux sessions at once.",
+                    "paste               Paste a file or stdin into a host tmux session in chunks, bracketed when supported.",
+                    "defaults            Show or set the host's session workdir, shell, tmux history limit and env.",
+                    "flash-attn          Probe flash-attn compatibility and optionally install it on one host.",
+                    "remove              Delete a stored host definition or destroy a Vast.ai instance.",
+                ),
+            ),
+        ),
+        notes=(
+            "Hosts are stored in ~/.config/tmux-trainsh/hosts.yaml.",
+            "When VAST_API_KEY is configured, Vast.ai instances are auto-discovered here, including stopped or exited ones.",
+            "When RUNPOD_API_KEY is configured, RunPod Pods are auto-discovered here.",
+            "Use `train vast` for provider-side instance lifecycle operations.",
+            "Use `train runpod` for RunPod Pod lifecycle operations.",
+            "Use `train colab` for quick one-off Colab tunnel helpers; prefer `train host add` for reusable configs.",
+            "For GitHub private repos, `train host clone` can use `GITHUB_TOKEN` from `train secrets` without rewriting the URL.",
+            "`bootstrap.default_profile` runs once per host on `train host add` and before `train host ssh`; `--force` re-runs it.",
+            "Use `train host flash-attn --matrix` to print the built-in compatibility matrix directly from the CLI.",
+            "`train host cloudflared setup` routes SSH, rsync and SFTP through `cloudflared access ssh`.",
+            "Set `shell: powershell` in hosts.yaml for Windows hosts; their tmux windows run one blocking SSH call per command.",
+            "`terminal.send_env`, `terminal.term` and `terminal.default_env_vars` set the environment of interactive SSH sessions.",
+            "Set `ssh.multiplex: false` to open a fresh SSH connection per command instead of sharing one per host.",
+            "Hosts that failed a probe within `connectivity.offline_grace_secs` fail fast with an offline error.",
+            "While the network is down, `train vast stop|start` are queued and replayed by `train host monitor`.",
+            "Built-in flash-attn matrix: CUDA Ampere/Ada -> flash-attn 2.x; CUDA Hopper/Blackwell -> auto flash-attn-4; ROCm CDNA -> flash-attn 2.x; Turing -> unsupported.",
+            "Use `train host flash-attn <name>` to auto-select a Python env with torch, then choose `flash-attn` 2.x or `flash-attn-4` based on the detected GPU family.",
+            "Use `train host flash-attn <name> --apply --background --status` for long source builds; tmux-trainsh intentionally does not support Turing GPUs here.",
+        ),
+        examples=(
+            "train host list",
+            "train host add",
+            "train host show gpu-box",
+            "train host ssh gpu-box",
+            "train host ssh gpu-box --log",
+            "train host run gpu-box -- nvidia-smi",
+            "train host tunnel gpu-box --local-port 18000 --remote-port 8000",
+            "train host clone gpu-box https://github.com/org/private-repo.git /srv/private-repo",
+            "train host check gpu-box",
+            "train host du gpu-box ~ --depth 3",
+            "train host ps gpu-box python --gpu",
+            "train host monitor --once",
+            "train host follow start gpu-box /workspace/run/train.log -n 100",
+            "train host flash-attn --matrix",
+            "train host flash-attn gpu-box",
+            "train host flash-attn gpu-box --version 2.8.3 --apply --background",
+            "train host flash-attn gpu-box --status",
+        ),
+        see_also=("train vast", "train runpod", "train colab", "train transfer"),
+    ),
+    CommandDoc(
+        key="project",
+        label="Organize Work by Project",
+        group="Workflow",
+        command="train project",
+        summary="Group hosts, recipes, storages, and default recipe variables under a named project.",
+        usage_lines=(
+            "train project list",
+            "train project show <name>",
+            "train project add <name> [--description TEXT] [--host NAME]... [--recipe NAME]... [--storage NAME]... [--var NAME=VALUE]...",
+            "train project use <name>|--clear",
+            "train project remove <name> [--host NAME]... [--recipe NAME]... [--storage NAME]... [--var NAME]...",
+        ),
+        blocks=(
+            DocBlock(
+                "Subcommands",
+                (
+                    "list                List projects and their member counts.",
+                    "show                Inspect one project and its recent jobs.",
+                    "add                 Create a project or add members and variables to it.",
+                    "use                 Scope listings and recipe runs to one project.",
+                    "remove              Detach members from a project, or delete it.",
+                ),
+            ),
+        ),
+        notes=(
+            "Projects are stored in ~/.config/tmux-trainsh/projects.yaml and only reference hosts, recipes and storages by name.",
+            "`train project use` (or `TRAINSH_PROJECT` for one shell) limits host, storage and recipe lists to the project's members.",
+            "Recipe runs take project variables as defaults; `--set` still wins.",
+            "`train project remove <name>` without options deletes the project itself, never its hosts, recipes or storages.",
+        ),
+        examples=(
+            "train project add llm-eval --host gpu-box --recipe eval-suite --storage artifacts --var WANDB_PROJECT=llm-eval",
+            "train project use llm-eval",
+            "train project show llm-eval",
+            "train project remove llm-eval --host gpu-box",
+            "TRAINSH_PROJECT=vision train recipe jobs",
+        ),
+        see_also=("train host", "train recipe", "train storage"),
+    ),
+    CommandDoc(
+        key="search",
+        label="Search Everything",
+        group="Utility",
+        command="train search",
+        summary="Full-text search over recipe contents, hosts, sessions, secret names, and recent execution logs.",
+        usage_lines=(
+            "train search <query> [--kind KIND]... [--limit N] [--json] [--reindex]",
+        ),
+        options=(
+            "--kind KIND                 recipe|host|session|secret|log; repeatable or comma-separated.",
+            "--limit N                   Maximum hits to print (default 20).",
+            "--json                      Print hits as JSON objects with kind, name, location and snippet.",
+            "--reindex                   Drop the index and rebuild it before searching.",
+        ),
+        notes=(
+            "Every term must match; terms like `flash-attn` or `cuda:12` match as phrases.",
+            "Hits show a location: `file:line`, `job <id>` or `run <id> step N`.",
+            "`--reindex` rebuilds the index in ~/.local/state/tmux-trainsh/search.db.",
+            "Logs cover the last `search.log_runs` runs; secret values are never indexed.",
+        ),
+        examples=(
+            "train search flash-attn",
+            "train search --kind recipe torchrun deepspeed",
+            "train search \"CUDA out of memory\" --kind log",
+            "train search HF --kind secret --json",
+        ),
+        see_also=("train recipe list", "train recipe logs", "train host list"),
+    ),
+    CommandDoc(
+        key="audit",
+        label="Audit Trail",
+        group="Utility",
+        command="train audit",
+        summary="List the append-only record of destructive and credential-touching operations.",
+        usage_lines=(
+            "train audit [list] [--since WHEN] [--until WHEN] [--action NAME] [--limit N] [--json]",
+            "train audit --path",
+        ),
+        options=(
+            "--since WHEN                Only entries after WHEN: 30m, 24h, 7d, 2w or an ISO date/time.",
+            "--until WHEN                Only entries before WHEN.",
+            "--action NAME               Filter by action; a prefix such as `vast` matches `vast.destroy`.",
+            "--limit N                   Newest N entries (default 50, 0 for all).",
+            "--json                      Print entries as JSON objects.",
+            "--path                      Print the audit file location.",
+        ),
+        notes=(
+            "Destroys, removals, deletes, secret reads and writes, and `destructive=True` recipe steps are recorded.",
+            "Entries are appended to ~/.local/state/tmux-trainsh/runtime/audit.jsonl (mode 0600) and never rewritten by train.",
+        ),
+        examples=(
+            "train audit --since 24h",
+            "train audit --action vast --since 7d",
+            "train audit --action secret.get --json",
+        ),
+        see_also=("train vast remove", "train storage delete", "train secrets get"),
+    ),
+    CommandDoc(
+        key="wandb",
+        label="Weights & Biases",
+        group="Utility",
+        command="train wandb",
+        summary="List runs of a W&B project and link them to recipe executions so logs show their final metrics.",
+        usage_lines=(
+            "train wandb runs [ENTITY/PROJECT] [-n N] [--json]",
+            "train wandb link [job-id...|--last|--all] [--project ENTITY/PROJECT] [--window SECS] [--dry-run]",
+        ),
+        options=(
+            "--project ENTITY/PROJECT    W&B project (default: config `wandb.project`).",
+            "-n N                        How many recent W&B runs to fetch (runs: 20, link: 100).",
+            "--window SECS               Slack around an execution's start/end when matching by time (default 600).",
+            "--all                       Link every finished execution instead of the 20 most recent.",
+            "--dry-run                   Show the matches without storing them.",
+        ),
+        notes=(
+            "The API key comes from the WANDB_API_KEY secret (`train secrets set WANDB_API_KEY`).",
+            "A W&B run tagged with the job id always matches; otherwise it must start during the execution.",
+            "`train recipe logs` shows the linked run's `wandb.list_metrics` keys.",
+        ),
+        examples=(
+            "train wandb runs my-team/llm-pretrain",
+            "train config set wandb.project my-team/llm-pretrain",
+            "train wandb link --last",
+            "train wandb link --all --dry-run",
+        ),
+        see_also=("train recipe logs", "train secrets set"),
+    ),
+    CommandDoc(
+        key="docker",
+        label="Manage Docker Containers",
+        group="Infrastructure",
+        command="train docker",
+        summary="Manage Docker containers and images on a stored host over SSH.",
+        usage_lines=(
+            "train docker ps <host> [--running] [--json]",
+            "train docker images <host> [--json]",
+            "train docker start|stop|restart <host> <container>",
+            "train docker rm <host> <container> [--force] [--yes]",
+            "train docker logs <host> <container> [-f] [--tail N] [--since DURATION]",
+            "train docker exec <host> <container> [--shell CMD]",
+        ),
+        blocks=(
+            DocBlock(
+                "Subcommands",
+                (
+                    "ps                  List containers on a host.",
+                    "images              List images on a host.",
+                    "start               Start a stopped container.",
+                    "stop                Stop a running container.",
+                    "restart             Restart a container.",
+                    "rm                  Remove a container.",
+                    "logs                Show or follow container logs, saved to the local log directory.",
+                    "exec                Open an interactive shell inside a container.",
+                ),
+            ),
+        ),
+        notes=(
+            "Set `docker.command` in config (for example `sudo -n docker`) when the SSH user is not in the docker group.",
+            "`logs` appends every line to ~/.local/share/tmux-trainsh/logs/docker/<host>/<container>.log.",
+            "`exec` opens bash when the image has it, otherwise sh.",
+        ),
+        examples=(
+            "train docker ps gpu-box",
+            "train docker logs gpu-box trainer -f --tail 50",
+            "train docker exec gpu-box trainer",
+            "train docker rm gpu-box old-run --force --yes",
+        ),
+        see_also=("train host run", "train host ps"),
+    ),
+    CommandDoc(
+        key="jupyter",
+        label="Manage Jupyter Servers",
+        group="Infrastructure",
+        command="train jupyter",
+        summary="Run Jupyter Lab inside tmux on a host with a generated token and reach it through a local SSH tunnel.",
+        usage_lines=(
+            "train jupyter list",
+            "train jupyter start <host> [options]",
+            "train jupyter status <name>",
+            "train jupyter tunnel <name> [--local-port N]",
+            "train jupyter stop <name>",
+        ),
+        blocks=(
+            DocBlock(
+                "Subcommands",
+                (
+                    "list                List managed Jupyter servers.",
+                    "start               Start Jupyter Lab in tmux on a host and tunnel it to localhost.",
+                    "status              Show one server's state and local URL.",
+                    "tunnel              Re-open the local SSH tunnel to a running server.",
+                    "stop                Stop one server, close its tunnel, and remove its record.",
+                ),
+            ),
+        ),
+        options=(
+            "start: --name NAME --port PORT --local-port PORT --dir DIR --python BIN",
+            "start: --ready-timeout 2m --no-tunnel --replace",
+        ),
+        notes=(
+            "Server metadata, including the token, lives under ~/.local/state/tmux-trainsh/jupyter/ (mode 600).",
+            "Jupyter binds to 127.0.0.1 on the host; the token is read from a private file so it never appears in the tmux pane.",
+            "`--python /venv/main/bin/python` runs `python -m jupyterlab` from that environment instead of `jupyter lab` on PATH.",
+            "In Python recipes, use `recipe.jupyter.start(gpu)` and `recipe.jupyter.stop(gpu)`.",
+            "Use `recipe.run_notebook(\"eval.ipynb\", host=gpu, parameters={...})` to run a notebook as a batch step with papermill.",
+        ),
+        examples=(
+            "train jupyter start gpu-box --dir /workspace",
+            "train jupyter status gpu-box",
+            "train jupyter tunnel gpu-box --local-port 18888",
+            "train jupyter stop gpu-box",
+        ),
+        see_also=("train host tunnel", "train vllm"),
+    ),
+    CommandDoc(
+        key="vllm",
+        label="Manage vLLM Services",
+        group="Infrastructure",
+        command="train vllm",
+        summary="Start and inspect remote vLLM servers in tmux, then run local batch clients through an SSH tunnel.",
+        usage_lines=(
+            "train vllm list",
+            "train vllm serve <host> <model> [options]",
+            "train vllm serve <host> --model <model> [options]",
+            "train vllm status <name>",
+            "train vllm logs <name> [--lines N]",
+            "train vllm stop <name>",
+            "train vllm batch <service-or-base-url> [<service-or-base-url> ...] --input <jsonl> --output <jsonl> [options]",
+        ),
+        blocks=(
+            DocBlock(
+                "Subcommands",
+                (
+                    "list                List managed vLLM services.",
+                    "serve               Start one vLLM OpenAI-compatible server in remote tmux.",
+                    "status              Inspect one managed service.",
+                    "logs                Read recent tmux pane output for one service.",
+                    "stop                Stop one managed service and remove its record.",
+                    "batch               Run local high-concurrency JSONL requests against a service or base URL.",
+                ),
+            ),
+        ),
+        options=(
+            "serve: --name NAME --port PORT --gpus IDS --tp N --bind-host HOST --workdir DIR --session NAME",
+            "serve: --env KEY=VALUE (repeatable) --arg VALUE (repeatable) --ready-timeout 10m --no-wait --replace",
+            "batch: --endpoint chat|completions|embeddings --concurrency N-per-target --retries N --timeout S",
+            "batch: --resume --overwrite --direct --local-port N --api-key KEY",
+        ),
+        notes=(
+            "Managed service metadata lives under ~/.local/state/tmux-trainsh/vllm/.",
+            "By default, `train vllm serve` adds throughput-oriented tuning flags: `--gpu-memory-utilization=0.95`, `--max-num-batched-tokens=16384`, and `--max-num-seqs=64` unless you override them explicitly.",
+            "By default, `train vllm batch <service>` opens a temporary SSH tunnel to the managed remote service.",
+            "You can pass multiple explicit services or base URLs to `train vllm batch`; each target gets its own worker pool and `--concurrency` applies per target.",
+            "For throughput on multi-GPU hosts, prefer one single-GPU vLLM service per card and batch across them. Use tensor parallelism when a model does not fit on one GPU.",
+            "Input JSONL accepts either full request objects or OpenAI-style `{custom_id, method, url, body}` objects.",
+            "For interactive local clients, use `train host tunnel <host> --local-port <p> --remote-port <p>` and point your SDK at http://127.0.0.1:<p>/v1.",
+        ),
+        examples=(
+            "train vllm serve gpu-box Qwen/Qwen2.5-32B-Instruct --gpus 0",
+            "train vllm serve gpu-box Qwen/Qwen2.5-32B-Instruct --gpus 1",
+            "train vllm serve gpu-box Qwen/Qwen2.5-32B-Instruct --gpus 0,1,2,3 --tp 4",
+            "train vllm serve gpu-box --model Qwen/Qwen2.5-32B-Instruct --arg=--gpu-memory-utilization=0.90",
+            "train vllm status qwen2.5-32b-instruct",
+            "train vllm logs qwen2.5-32b-instruct --lines 120",
+            "train vllm batch qwen2.5-32b-instruct --input requests.jsonl --output results.jsonl --concurrency 96 --resume",
+            "train vllm batch q0 q1 --input requests.jsonl --output results.jsonl --concurrency 96",
+            "train vllm batch http://127.0.0.1:18000/v1 --input requests.jsonl --output results.jsonl",
+        ),
+        see_also=("train host", "train host tunnel", "train recipe"),
+    ),
+)
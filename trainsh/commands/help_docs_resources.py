@@ -0,0 +1,635 @@
+"""Storage, transfer, secrets, config, cloud, and utility command reference."""
+
+from __future__ import annotations
+
+from .help_model import CommandDoc, DocBlock
+
+
+RESOURCE_DOCS: tuple[CommandDoc, ...] = (
+    CommandDoc(
+        key="storage",
+        label="Manage Named Storage Backends",
+        group="Infrastructure",
+        command="train storage",
+        summary="Manage storage backends used by transfers and recipes.",
+        usage_lines=(
+            "train storage list",
+            "train storage add",
+            "train storage show <name>",
+            "train storage check <name>",
+            "train storage cat <name> <path> [--bytes N]",
+            "train storage edit <name> <path>",
+            "train storage delete <name> <path> [--recursive] [--hard]",
+            "train storage trash <name> [list|restore <id> [--to <path>]|empty [--older-than DAYS]]",
+            "train storage remove <name>",
+        ),
+        blocks=(
+            DocBlock(
+                "Subcommands",
+                (
+                    "list                List configured storage backends.",
+                    "add                 Add a storage backend interactively.",
+                    "show                Inspect one backend configuration.",
+                    "check               Check connectivity for one backend.",
+                    "cat                 Print the start of one file on a backend.",
+                    "edit                Edit one small text file on a backend in $EDITOR.",
+                    "delete              Delete one path on a backend (soft delete into .trash/ by default).",
+                    "trash               List, restore, or empty a backend's .trash/ area.",
+                    "remove              Delete a stored backend.",
+                ),
+            ),
+        ),
+        notes=(
+            "Supported types: local, ssh, gdrive, hf, r2, b2, s3, gcs, smb.",
+            "Backends are stored in ~/.config/tmux-trainsh/storages.yaml.",
+            "Credential prompts can store secrets directly in train's secrets backend.",
+            "HF buckets use `HF_TOKEN` or a storage-scoped `<NAME>_HF_TOKEN` secret.",
+            "`cat` and `edit` handle small text files only: binary files are not printed and edits are capped at 1 MiB.",
+            "`delete` moves paths into `.trash/` unless `--hard`; `storage.trash.retention_days` sets how long they stay.",
+            "Set `storage.trash.enabled: true` to make recipe `storage_delete` soft-delete too; HF buckets have no trash.",
+        ),
+        examples=(
+            "train storage list",
+            "train storage add",
+            "train storage show artifacts",
+            "train storage check artifacts",
+            "train storage cat artifacts configs/train.yaml",
+            "train storage edit artifacts configs/train.yaml",
+            "train storage delete artifacts runs/exp-12 --recursive",
+            "train storage trash artifacts restore 20261017",
+        ),
+        see_also=("train transfer", "train secrets"),
+    ),
+    CommandDoc(
+        key="transfer",
+        label="Transfer Data",
+        group="Infrastructure",
+        command="train transfer",
+        summary="Copy files between local paths, named hosts, storage backends, and cloud endpoints.",
+        usage_lines=(
+            "train transfer <source> <destination> [options]",
+            "train transfer <source> [<source>...] <destination> [options]",
+            "train transfer queue add <source> <destination> [--priority N] [options]",
+            "train transfer queue [list|run|pause|resume|clear]",
+            "train transfer queue priority <id> <N> | move <id> top|bottom | cancel <id>",
+        ),
+        blocks=(
+            DocBlock(
+                "Endpoint Forms",
+                (
+                    "/local/path                     Local filesystem path.",
+                    "@host:/path                     Configured host alias.",
+                    "host:<name>:/path               Explicit host endpoint.",
+                    "storage:<name>:/path            Named storage endpoint.",
+                    "hf:<namespace>/<bucket>:/path   Hugging Face Buckets (colon path form).",
+                    "r2:<bucket>/[prefix]            Cloudflare R2 (no pre-configuration needed).",
+                    "b2:<bucket>/[prefix]            Backblaze B2.",
+                    "gcs:<bucket>/[prefix]           Google Cloud Storage.",
+                ),
+            ),
+            DocBlock(
+                "Queue",
+                (
+                    "queue add           Queue a transfer; higher --priority starts first (default 0).",
+                    "queue list          Show running, queued, and finished transfers.",
+                    "queue run           Start queued transfers as limits allow (--follow keeps waiting).",
+                    "queue pause/resume  Stop or restart new starts; running transfers continue.",
+                    "queue priority      Change one transfer's priority.",
+                    "queue move          Move a transfer to the top or bottom of its priority band.",
+                    "queue cancel        Drop a queued transfer or stop a running one.",
+                    "queue clear         Forget finished transfers.",
+                ),
+            ),
+        ),
+        options=(
+            "--delete, -d            Delete files at destination that do not exist in the source.",
+            "--exclude, -e PAT       Exclude a glob pattern; repeat to add more patterns.",
+            "--dry-run               Show what would change without transferring files.",
+            "--transfers N           Parallel rclone transfers (default: 32 for cloud).",
+            "--checkers N            Parallel rclone checkers (default: 64 for cloud).",
+            "--upload-concurrency N  S3 multipart upload threads per file (default: 16 for cloud).",
+            "--chunk-size SIZE       Multipart chunk size (default: 64M for cloud).",
+            "--include PAT           rclone include pattern (repeatable).",
+            "--no-compress           Disable rsync compression (already-compressed data).",
+            "--partial               Keep partially transferred files so a rerun continues them.",
+            "--append-verify         Resume large files by appending, then verify checksums.",
+            "--bwlimit RATE          Limit rsync bandwidth, e.g. 20M or 500K.",
+            "--tar-stream            Stream tar | zstd over SSH instead of rsync (many small files).",
+            "--preflight             Size the source, check free space at the destination, print an ETA.",
+            "--remote                Run rclone on the host for host <-> cloud; no local relay.",
+            "--local-relay           Relay through this machine even if `transfer.remote_exec` is on.",
+        ),
+        notes=(
+            "Cloud endpoint shortcuts (hf:/r2:/b2:/gcs:) resolve credentials from secrets automatically.",
+            "No `train storage add` step is needed for hf:/r2:/b2:/gcs: endpoint prefixes.",
+            "HF bucket ids are `namespace/bucket`, so direct HF paths use `hf:<namespace>/<bucket>:/path`.",
+            "Use named storage endpoints for Amazon S3, for example `storage:s3-artifacts:/path`.",
+            "Host <-> cloud storage transfers relay through a local temp directory unless --remote is set.",
+            "--remote installs rclone into ~/.local/bin on the host when missing and sends only that storage's credentials.",
+            "With --remote, host <-> host rsync runs on whichever host can reach the other; it never relays locally.",
+            "Dry runs work for direct rsync/rclone paths; relayed transfers fail fast instead.",
+            "rsync defaults (compress, partial, bwlimit, exclude) come from `transfer.rsync` in config.",
+            "--tar-stream needs tar and zstd on both ends; it copies directory contents and never deletes.",
+            "Local sources above `transfer.tar_stream.suggest_files` files print a --tar-stream hint.",
+            "Sources may be quoted globs (`'@gpu:/ckpt/epoch_*.pt'`) or several paths; storage sources and --delete take one path.",
+            "--preflight stops when the source will not fit at the destination; with --dry-run it only reports.",
+            "Queued transfers run at most `transfer.queue.max_per_host` at a time per destination (`host_limits` overrides).",
+        ),
+        examples=(
+            "train transfer ./artifacts @gpu:/workspace/out",
+            "train transfer @gpu:/workspace/checkpoints ./checkpoints",
+            "train transfer ./data storage:artifacts:/datasets/run-01",
+            "train transfer ./checkpoints hf:team/run-artifacts:/nightly",
+            "train transfer ./data r2:my-bucket/prefix",
+            "train transfer ./shards storage:s3-artifacts:/datasets --transfers 64 --chunk-size 128M",
+            "train transfer ./ckpt.tar @gpu:/workspace/ --append-verify --bwlimit 50M",
+            "train transfer ./imagenet/train @gpu:/data/train --tar-stream",
+            "train transfer @gpu:/workspace/checkpoints ./checkpoints --preflight",
+            "train transfer '@gpu:/workspace/ckpt/epoch_*.pt' @gpu:/workspace/config.json ./best",
+            "train transfer queue add ./hotfix.py @gpu:/workspace/ --priority 10",
+            "train transfer @gpu:/workspace/checkpoints r2:my-bucket/ckpt --remote",
+        ),
+        see_also=("train host", "train storage", "train secrets"),
+    ),
+    CommandDoc(
+        key="secrets",
+        label="Manage Secrets",
+        group="Infrastructure",
+        command="train secrets",
+        summary="Manage API keys and credentials used by hosts, storage, and cloud integrations.",
+        usage_lines=(
+            "train secrets list",
+            "train secrets set <key>",
+            "train secrets get <key>",
+            "train secrets remove <key>",
+            "train secrets backend [name]",
+        ),
+        blocks=(
+            DocBlock(
+                "Common Keys",
+                (
+                    "VAST_API_KEY",
+                    "RUNPOD_API_KEY",
+                    "POE_API_KEY",
+                    "HF_TOKEN",
+                    "OPENAI_API_KEY",
+                    "OPENROUTER_API_KEY",
+                    "ANTHROPIC_API_KEY",
+                    "GITHUB_TOKEN",
+                    "WANDB_API_KEY",
+                    "MLFLOW_TRACKING_TOKEN",
+                    "GOOGLE_DRIVE_CREDENTIALS",
+                    "R2_CREDENTIALS",
+                    "B2_CREDENTIALS",
+                ),
+            ),
+        ),
+        notes=(
+            "Secret values are prompted securely for `train secrets set`.",
+            "`train secrets get <key>` prints the raw value to stdout; use `train secrets get --masked <key>` for a preview.",
+            "Cloud bundle secrets are supported for providers such as R2 and B2.",
+            "`GITHUB_TOKEN` is used automatically for GitHub HTTPS clones from `train host clone`, `train vast clone`, `train runpod clone`, and `recipe.git_clone(..., auth='github_token')`.",
+            "Secrets a recipe resolves are shown as `***` in its logs and step output.",
+        ),
+        examples=(
+            "train secrets list",
+            "train secrets set VAST_API_KEY",
+            "train secrets set RUNPOD_API_KEY",
+            "train secrets set POE_API_KEY",
+            "train secrets set GITHUB_TOKEN",
+            "train secrets get OPENAI_API_KEY",
+            "train secrets get --masked OPENAI_API_KEY",
+            "train secrets backend",
+        ),
+        see_also=("train host", "train storage", "train vast", "train runpod"),
+    ),
+    CommandDoc(
+        key="config",
+        label="Manage Configuration",
+        group="Infrastructure",
+        command="train config",
+        summary="Inspect and update tmux-trainsh configuration.",
+        usage_lines=(
+            "train config show",
+            "train config get <key>",
+            "train config set <key> <value>",
+            "train config reset",
+            "train config tmux <show|edit|apply>",
+            "train config profile <list|show|add|switch|remove>",
+            "train config viewer [status] | on [--passphrase] | off",
+        ),
+        blocks=(
+            DocBlock(
+                "Subcommands",
+                (
+                    "show                Show the merged configuration.",
+                    "get                 Read one config key by dotted path.",
+                    "set                 Write one config key by dotted path.",
+                    "reset               Reset config.yaml back to defaults.",
+                    "tmux                Inspect or edit tmux-specific settings.",
+                    "profile             Manage named config profiles and switch between them.",
+                    "viewer              Turn viewer mode (no destructive commands) on or off.",
+                ),
+            ),
+        ),
+        notes=(
+            "Main config file: ~/.config/tmux-trainsh/config.yaml.",
+            "With a profile active, `show`/`get` include its overrides and `set` on an overridden key updates the profile.",
+            "Config, hosts and storages values may use `${env:NAME}` or `${env:NAME:-default}`.",
+            "Viewer mode refuses removals, deletes, config changes and destructive recipe steps; `--passphrase` guards `viewer off`.",
+        ),
+        examples=(
+            "train config show",
+            "train config get ui.currency",
+            "train config set ui.currency CNY",
+            "train config tmux show",
+            "train config tmux edit",
+            "train config tmux apply",
+            "train config profile switch work",
+            "train config viewer on --passphrase",
+        ),
+        see_also=("train config tmux", "train config profile", "train pricing"),
+    ),
+    CommandDoc(
+        key="sync",
+        label="Settings Sync",
+        group="Infrastructure",
+        command="train sync",
+        summary="Keep recipes, hosts, Vast templates and projects the same on every machine through one storage backend.",
+        usage_lines=(
+            "train sync now [--dry-run]",
+            "train sync status",
+            "train sync log [--limit N]",
+        ),
+        blocks=(
+            DocBlock(
+                "Subcommands",
+                (
+                    "now                 Sync with the configured storage now.",
+                    "status              Show sync settings and local changes since the last sync.",
+                    "log                 Show recent sync changes.",
+                ),
+            ),
+        ),
+        notes=(
+            "Enable with `train config set sync.storage <name>` using a storage from `train storage list`, e.g. a private R2 bucket.",
+            "Secrets and storages.yaml are never synced.",
+            "When both machines changed a file, the newer edit wins; the other version is kept as <name>.conflict-<device>-<time>.",
+            "Each change is recorded as a `settings_sync_changed` runtime event; `train sync log` lists them.",
+        ),
+        examples=(
+            "train config set sync.storage private-r2",
+            "train sync now --dry-run",
+            "train sync now",
+            "train sync log",
+        ),
+        see_also=("train storage", "train backup"),
+    ),
+    CommandDoc(
+        key="backup",
+        label="Backup and Restore",
+        group="Infrastructure",
+        command="train backup",
+        summary="Move tmux-trainsh to a new machine with one passphrase-encrypted file.",
+        usage_lines=(
+            "train backup create <file> [--with-secrets] [--force]",
+            "train backup show <file>",
+            "train backup restore <file> [--overwrite] [--no-secrets] [--dry-run]",
+        ),
+        blocks=(
+            DocBlock(
+                "Subcommands",
+                (
+                    "create              Write an encrypted backup file.",
+                    "show                List what a backup contains.",
+                    "restore             Restore a backup into this machine's config and data.",
+                ),
+            ),
+            DocBlock(
+                "Options",
+                (
+                    "--with-secrets      Include secret values exported from the active secrets backend.",
+                    "--overwrite         Replace local files and secrets that differ (old files kept as .bak).",
+                    "--no-secrets        Restore files only.",
+                    "--dry-run           Report what restore would change without writing.",
+                ),
+            ),
+        ),
+        notes=(
+            "Backups cover ~/.config/tmux-trainsh, the current project's recipes/ and pricing history.",
+            "Restore keeps local files that differ unless --overwrite; identical files are skipped.",
+            "Secrets are restored into this machine's secrets backend, which is chosen on first use.",
+            "The passphrase is prompted for, or read from TRAINSH_BACKUP_PASSPHRASE.",
+        ),
+        examples=(
+            "train backup create ~/trainsh-backup.enc --with-secrets",
+            "train backup show ~/trainsh-backup.enc",
+            "train backup restore ~/trainsh-backup.enc --dry-run",
+            "train backup restore ~/trainsh-backup.enc --overwrite",
+        ),
+        see_also=("train config", "train secrets"),
+    ),
+    CommandDoc(
+        key="config-profile",
+        label="train config profile",
+        group="Infrastructure",
+        command="train config profile",
+        summary="Keep separate settings, API keys and optionally data per profile, e.g. client work vs personal experiments.",
+        usage_lines=(
+            "train config profile list",
+            "train config profile show [name]",
+            "train config profile add <name> [--isolate|--shared] [--description TEXT] [--currency CODE] [--ssh-key PATH]",
+            "                         [--secret-prefix PREFIX] [--notify CHANNELS] [--webhook URL] [--set KEY=VALUE ...]",
+            "train config profile switch <name>|--clear",
+            "train config profile remove <name> [--purge-data] [--yes]",
+        ),
+        blocks=(
+            DocBlock(
+                "Subcommands",
+                (
+                    "list                List profiles and mark the active one.",
+                    "show                Show a profile's overrides and data location.",
+                    "add                 Create a profile or change its overrides.",
+                    "switch              Make a profile active for later commands.",
+                    "remove              Delete a profile.",
+                ),
+            ),
+            DocBlock(
+                "Options",
+                (
+                    "--isolate           Give the profile its own hosts, storages, projects, Vast templates, logs and run state.",
+                    "--currency CODE     Override ui.currency (pricing display).",
+                    "--ssh-key PATH      Override defaults.ssh_key_path (host add default, key attached to new Vast.ai instances).",
+                    "--secret-prefix P   Look up and store secrets as P<NAME>, e.g. WORK_VAST_API_KEY for VAST_API_KEY.",
+                    "--notify CHANNELS   Override notifications.channels (comma-separated).",
+                    "--set KEY=VALUE     Override any other config key by dotted path.",
+                ),
+            ),
+        ),
+        notes=(
+            "Profiles live under `profiles:` in config.yaml and override the base config only while active.",
+            "TRAINSH_PROFILE=<name> selects a profile for one shell or command and wins over `switch`.",
+            "Isolated profiles keep their data in ~/.config/tmux-trainsh/profiles/<name>/; recipes stay shared.",
+            "`remove --purge-data` also deletes an isolated profile's data directories.",
+        ),
+        examples=(
+            "train config profile add work --isolate --secret-prefix WORK_ --currency USD --ssh-key ~/.ssh/id_work",
+            "train config profile switch work",
+            "train secrets set VAST_API_KEY",
+            "TRAINSH_PROFILE=personal train vast list",
+            "train config profile switch --clear",
+        ),
+        see_also=("train config", "train secrets"),
+    ),
+    CommandDoc(
+        key="config-tmux",
+        label="train config tmux",
+        group="Infrastructure",
+        command="train config tmux",
+        summary="Inspect and apply tmux-specific settings stored in config.yaml.",
+        usage_lines=("train config tmux <show|edit|apply>",),
+        blocks=(
+            DocBlock(
+                "Subcommands",
+                (
+                    "show                List tmux options from config.",
+                    "edit                Edit tmux options in $EDITOR.",
+                    "apply               Write ~/.tmux.conf from config.",
+                ),
+            ),
+        ),
+        notes=("`apply` writes ~/.tmux.conf; `edit` only changes config.yaml.",),
+        examples=(
+            "train config tmux show",
+            "train config tmux edit",
+            "train config tmux apply",
+        ),
+        see_also=("train config",),
+    ),
+    CommandDoc(
+        key="vast",
+        label="Manage Vast.ai Instances",
+        group="Cloud",
+        command="train vast",
+        summary="Manage Vast.ai instances and offers.",
+        usage_lines=(
+            "train vast list",
+            "train vast show <id>",
+            "train vast ssh <id> [--log]",
+            "train vast run <id> -- <command>",
+            "train vast clone <id> <repo-url> [destination] [options]",
+            "train vast start <id>",
+            "train vast stop <id>... [--json]",
+            "train vast reboot <id>",
+            "train vast remove <id>... [--json]",
+            "train vast label <id> <label> | <id>=<label>... [--json]",
+            "train vast search",
+            "train vast create <template> [--offer <offer-id>] [--label <label>]",
+            "train vast template [list|show|add|remove] [name] [options]",
+            "train vast snapshot <id|host> [--name NAME] [--output PATH | --print] [--template NAME] [--force]",
+            "train vast watch [--once] [--interval SECS] [--on KIND]... [--exec <command>]",
+            "train vast events [<id>] [--limit N]",
+            "train vast keys",
+            "train vast attach-key [path]",
+        ),
+        blocks=(
+            DocBlock(
+                "Subcommands",
+                (
+                    "list                List your current Vast.ai instances.",
+                    "show                Inspect one instance in detail.",
+                    "ssh                 Open SSH to a running instance.",
+                    "run                 Run one remote shell command on an instance.",
+                    "clone               Clone one git repository on an instance.",
+                    "start               Start an instance.",
+                    "stop                Stop an instance.",
+                    "reboot              Reboot an instance.",
+                    "remove              Destroy an instance.",
+                    "label               Set instance labels.",
+                    "search              Search available GPU offers.",
+                    "create              Create an instance from a saved template.",
+                    "template            List, show, add, or remove saved creation templates.",
+                    "snapshot            Capture a running instance into a provisioning recipe.",
+                    "watch               Poll instances and record state changes.",
+                    "events              Show recorded state changes for instances.",
+                    "keys                List registered SSH public keys.",
+                    "attach-key          Upload a local SSH public key.",
+                ),
+            ),
+        ),
+        notes=(
+            "Requires VAST_API_KEY. Configure it with `train secrets set VAST_API_KEY`.",
+            "Templates are stored in ~/.config/tmux-trainsh/vast_templates.yaml.",
+            "`train vast create <template>` rents the cheapest matching offer unless `--offer` picks one.",
+            "`train vast snapshot` writes a recipe that rents a similar offer and replays the instance's setup.",
+            "`train vast watch` records instance events; the first poll only sets a baseline.",
+            "`train vast watch --exec` runs a command per event (filtered by `--on`) with TRAINSH_VAST_* set.",
+            "`vast.api_retries` and `vast.api_min_interval_secs` tune API retries and request spacing.",
+            "`stop`, `remove` and `label` accept several instances and exit 1 if any fails.",
+            "`vast.api_cache_ttls` sets how long instance and SSH key lookups are reused.",
+        ),
+        examples=(
+            "train vast list",
+            "train vast search",
+            "train vast template add torch --image pytorch/pytorch:latest --disk 80 --env HF_HOME=/workspace/hf --gpu RTX_4090 --max-price 0.6",
+            "train vast create torch",
+            "train vast snapshot 12345 --name llm-box --template llm-box",
+            "train vast watch --on ssh_available --exec 'train recipe run setup --host gpu=vast:$TRAINSH_VAST_INSTANCE_ID'",
+            "train vast ssh 12345",
+            "train vast run 12345 -- nvidia-smi",
+            "train vast clone 12345 https://github.com/org/private-repo.git /workspace/repo",
+            "train vast remove 12345",
+            "train vast stop 12345 12346 12347",
+            "train vast label 12345=train-a 12346=train-b",
+        ),
+        see_also=("train host", "train pricing vast"),
+    ),
+    CommandDoc(
+        key="runpod",
+        label="Manage RunPod Pods",
+        group="Cloud",
+        command="train runpod",
+        summary="Manage RunPod Pods and current GPU type price hints.",
+        usage_lines=(
+            "train runpod list",
+            "train runpod show <id>",
+            "train runpod ssh <id> [--log]",
+            "train runpod run <id> -- <command>",
+            "train runpod clone <id> <repo-url> [destination] [options]",
+            "train runpod start <id>",
+            "train runpod stop <id>",
+            "train runpod reboot <id>",
+            "train runpod remove <id>",
+            "train runpod search [gpu_name=A100] [num_gpus=1] [min_gpu_ram=16] [max_dph=1.0]",
+        ),
+        blocks=(
+            DocBlock(
+                "Subcommands",
+                (
+                    "list                List your current RunPod Pods.",
+                    "show                Inspect one Pod in detail.",
+                    "ssh                 Open SSH to a running Pod.",
+                    "run                 Run one remote shell command on a Pod.",
+                    "clone               Clone one git repository on a Pod.",
+                    "start               Start or resume a Pod.",
+                    "stop                Stop a Pod.",
+                    "reboot              Restart a Pod.",
+                    "remove              Delete a Pod.",
+                    "search              Show current GPU type price hints and stock.",
+                ),
+            ),
+        ),
+        notes=("Requires RUNPOD_API_KEY. Configure it with `train secrets set RUNPOD_API_KEY`.",),
+        examples=(
+            "train runpod list",
+            "train runpod search gpu_name=A100 max_dph=2.0",
+            "train runpod ssh abc123xyz",
+            "train runpod run abc123xyz -- nvidia-smi",
+            "train runpod clone abc123xyz https://github.com/org/private-repo.git /workspace/repo",
+            "train runpod remove abc123xyz",
+        ),
+        see_also=("train host", "train run", "train exec"),
+    ),
+    CommandDoc(
+        key="colab",
+        label="Manage Colab Connections",
+        group="Cloud",
+        command="train colab",
+        summary="Quick helper for one-off Google Colab SSH tunnels.",
+        usage_lines=(
+            "train colab list",
+            "train colab connect",
+            "train colab ssh [name]",
+            "train colab run <command>",
+        ),
+        blocks=(
+            DocBlock(
+                "Subcommands",
+                (
+                    "list                List saved Colab tunnel definitions.",
+                    "connect             Add a Colab tunnel definition.",
+                    "ssh                 Open SSH to one saved Colab runtime.",
+                    "run                 Run one remote shell command over SSH.",
+                ),
+            ),
+        ),
+        notes=(
+            "For reusable named connections inside recipes, prefer `train host add` and choose a Colab host type.",
+            "You need a running Colab notebook with SSH enabled plus a cloudflared or ngrok tunnel.",
+        ),
+        examples=(
+            "train colab connect",
+            "train colab list",
+            "train colab ssh my-colab",
+            "train colab run nvidia-smi",
+        ),
+        see_also=("train host",),
+    ),
+    CommandDoc(
+        key="pricing",
+        label="Inspect Pricing",
+        group="Cloud",
+        command="train pricing",
+        summary="Show exchange rates, display currency settings, and Colab or Vast.ai cost estimates.",
+        usage_lines=(
+            "train pricing rates [--refresh]",
+            "train pricing currency [--set CODE]",
+            "train pricing colab [--subscription SPEC]",
+            "train pricing vast",
+            "train pricing billing [--month YYYY-MM] [--by instance|label] [--csv PATH] [--refresh]",
+            "train pricing report [--month YYYY-MM | --from YYYY-MM-DD --to YYYY-MM-DD] [--currency CODE]",
+            "train pricing compare [<gpu>] [--vram GB] [--count N] [--hours H] [--currency CODE] [--no-vast]",
+            "train pricing history [<gpu>] [--days N] [--sample]",
+            "train pricing offers [list|add <provider> <gpu> --price P|remove <provider> <gpu>]",
+            "train pricing convert <amount> <from> <to>",
+        ),
+        notes=(
+            "Cross-currency views auto-refresh cached exchange rates when needed.",
+            "Exchange rates are refreshed at most once every 3 days unless you force --refresh.",
+            "`billing` compares Vast.ai charges with `train vast watch` estimates; `--refresh` re-fetches.",
+            "`report` totals cost per provider and host at each day's exchange rate.",
+            "`compare` ranks Vast.ai offers with your `offers` entries by $/hr.",
+            "`history --sample` records today's $/GPU-hr; schedule it for regular polls.",
+        ),
+        examples=(
+            "train pricing rates --refresh",
+            "train pricing currency --set CNY",
+            "train pricing colab",
+            "train pricing vast",
+            "train pricing billing --month 2026-09 --by label --csv billing.csv",
+            "train pricing report --month 2026-09 --currency JPY",
+            "train pricing offers add lambda H100 --price 2.49 --vram 80",
+            "train pricing compare H100 --count 1 --hours 48",
+            "train pricing history 4090 --days 14",
+            "train pricing convert 10 USD CNY",
+        ),
+        see_also=("train config", "train vast"),
+    ),
+    CommandDoc(
+        key="update",
+        label="Update tmux-trainsh",
+        group="Utility",
+        command="train update",
+        summary="Check for updates and optionally install them.",
+        usage_lines=(
+            "train update",
+            "train update --check",
+        ),
+        options=("--check            Only check for updates; do not install.",),
+        notes=("The updater detects whether tmux-trainsh came from uv, pip, or another supported method.",),
+        examples=("train update", "train update --check"),
+        see_also=("train version",),
+    ),
+    CommandDoc(
+        key="version",
+        label="Version",
+        group="Utility",
+        command="train version / train --version",
+        summary="Print the installed tmux-trainsh version string.",
+        usage_lines=(
+            "train version",
+            "train --version",
+        ),
+        examples=("train version", "train --version"),
+    ),
+)
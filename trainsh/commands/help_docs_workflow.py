@@ -0,0 +1,387 @@
+"""Recipe, run, status, scheduling, and dashboard command reference."""
+
+from __future__ import annotations
+
+from ..constants import RECIPE_FILE_EXTENSION
+from .help_model import CommandDoc, DocBlock, _bundled_examples, _joined, _template_names, _template_usage_fragment
+
+
+WORKFLOW_DOCS: tuple[CommandDoc, ...] = (
+    CommandDoc(
+        key="recipe",
+        label="Recipe Command",
+        group="Workflow",
+        command="train recipe",
+        summary="Single entry point for recipe files, run/exec aliases, resume, status, logs, jobs, and schedules.",
+        usage_lines=(
+            "train recipe list",
+            "train recipe show <name> [--source|--compiled]",
+            f"train recipe new <name> [--template {_template_usage_fragment()}]",
+            "train recipe edit <name>",
+            "train recipe remove <name>",
+            "train recipe run <name> [options]",
+            "train recipe exec <name-or-path> [options]",
+            "train recipe resume <name> [options]",
+            "train recipe reconnect [job-id] [--dry-run]",
+            "train recipe attach <job-id> <window> [--no-reconnect]",
+            "train recipe cancel [job-id] [--keep-sessions]",
+            "train recipe status [job-id|--last|--all]",
+            "train recipe logs [job-id|--last|--list|--prune [--dry-run]]",
+            "train recipe timeline <job-id|--last> [--json]",
+            "train recipe energy [--json]",
+            "train recipe tail <job-id|--last> [step] [-n LINES] [--json]",
+            "train recipe output <job-id|--last> [step] [--error] [--path | -o FILE]",
+            "train recipe screen <job-id|--last> [step] [--path]",
+            "train recipe trust <name> [--revoke] | --list",
+            "train recipe test <name> [--mock FILE] [--set NAME=VALUE] [--json] [-v]",
+            "train recipe convert <name> [--to yaml|python] [-o FILE]",
+            "train recipe graph <name> [--format mermaid|dot] [-o FILE]",
+            "train recipe schema [--print | -o FILE | --check FILE...]",
+            "train recipe operations [name] [--json]",
+            "train recipe lint <name>... [--json] [--strict]",
+            "train recipe vars <name> [--json]",
+            "train recipe jobs [--all]",
+            "train recipe schedule <run|list|status> [args...]",
+        ),
+        blocks=(
+            DocBlock(
+                "File Commands",
+                (
+                    "list                List user recipes and bundled examples.",
+                    "show <name>         Print raw .pyrecipe source by default; use --compiled for normalized steps.",
+                    "new <name>          Create a recipe file from a bundled template.",
+                    "edit <name>         Open a recipe file in $EDITOR.",
+                    "remove <name>       Delete a recipe file after confirmation.",
+                ),
+            ),
+            DocBlock(
+                "Runtime Commands",
+                (
+                    "run <name>          Execute a recipe immediately from a stored file.",
+                    "exec <source>       Execute from a recipe file, path, inline code, or stdin.",
+                    "resume <name>       Resume the latest failed or interrupted run.",
+                    "reconnect [job-id]  Continue running jobs whose train process exited.",
+                    "attach <job> <win>  Attach to a window's tmux session, re-attaching after connection loss.",
+                    "cancel [job-id]     Stop a running job and kill its remote commands.",
+                    "status              Inspect running jobs and tmux attach commands.",
+                    "logs                Inspect persisted execution summaries.",
+                    "timeline <job-id>   Per-step attempts and where the time went (running, queued, retry, input, paused).",
+                    "energy              Approximate GPU kWh per month of finished runs.",
+                    "tail <job-id>       Last output lines of a step (default: the latest started one).",
+                    "output <job-id>     Raw output of a step: print it, --path to locate the file, -o to export.",
+                    "screen <job-id>     Last frame of a step running a full-screen program (htop, nvtop, installers).",
+                    "trust <name>        Let a recipe's local commands skip the allowlist and privileged prompts.",
+                    "test <name>         Run the recipe's DAG against canned step results from a mock file.",
+                    "convert <name>      Rewrite a recipe as YAML (from .pyrecipe) or Python (from .yaml).",
+                    "graph <name>        Print the step DAG as Mermaid (default) or Graphviz DOT.",
+                    "schema              Write the recipe JSON Schema for editors, or check recipe documents against it.",
+                    "operations [name]   List every step operation with its fields, targets and whether it runs in a terminal.",
+                    "lint <name>...      Best-practice warnings: timeouts, retries, quoting, unreachable steps, secrets, deletes.",
+                    "vars <name>         Each variable's default and the steps (and flags) it is used in.",
+                    "jobs                Show recent job history.",
+                    "schedule            Run, list, or inspect scheduled recipes.",
+                ),
+            ),
+        ),
+        notes=(
+            f"Recipe files live in project-local paths such as ./recipes/*{RECIPE_FILE_EXTENSION}.",
+            "Bundled templates: " + _joined(_template_names()) + ".",
+            "Current bundled examples: " + _joined(_bundled_examples()) + ".",
+            "Fast paths: `train run <recipe>` for files and `train exec ...` for files or inline recipe code.",
+            "`reconnect` waits on commands still running in tmux instead of re-sending them.",
+            "`run`/`exec` first reconnect orphaned jobs in the background; `recipe.auto_reconnect: false` turns this off.",
+            "Bridge panes re-attach to remote tmux sessions after a lost connection; `terminal.reconnect: false` turns this off.",
+            "`cancel` sends SIGTERM, then SIGKILL, to each in-flight step's process group and closes its tmux session.",
+            "`timeline --json` prints every attempt's start/finish plus Gantt segments for your own charts.",
+            "`energy` totals the kWh sampled every `energy.interval_secs` per month (0 disables sampling).",
+            "Set `tracking.mlflow_uri` (or `recipe.tracking(...)`) to log each run to MLflow.",
+            "Set `tracing.otlp_endpoint` (or OTEL_EXPORTER_OTLP_ENDPOINT) to send each run as an OpenTelemetry trace.",
+            "`tail` keeps at most `recipe.output_tail_lines` lines per step in memory; the full output stays in the log.",
+            "Raw step output is saved to `step_logs/<job-id>/step_NNNN.log` in the runtime state dir.",
+            "Waits on full-screen programs store the current frame in `step_NNNN.screen.log`.",
+            "`test --mock FILE` answers steps from a YAML file and checks its `expect` block; mock runs are never resumed.",
+            "Recipes may also be `.yaml`/`.yml` documents in the `train recipe schema` format.",
+            "`graph` draws condition steps as diamonds and `on_failure_run` handlers as dashed edges.",
+            "`schema` writes `schemas/recipe.schema.json` for editors; `--check FILE` reports errors with key path and line.",
+            "`operations --json` prints each recipe operation's fields, targets and step type.",
+            "`lint` exits 1 on errors, or on warnings too with `--strict`.",
+            "`vars` lists each `${NAME}` with its default, the steps that set it and where it is used.",
+            "Local commands follow `local_policy`; privileged ones only warn unless `local_policy.privileged` is confirm or fail.",
+            "`trust` pins a recipe's sha256, so editing it revokes trust.",
+        ),
+        examples=(
+            "train recipe list",
+            "train recipe show nanochat",
+            "train recipe show nanochat --compiled",
+            "train recipe run nanochat",
+            "train exec nanochat",
+            "train recipe status --last",
+            "train recipe timeline --last",
+            "train recipe tail --last train -n 20",
+            "train recipe attach 1a2b3c4d main",
+            "train recipe output --last train | grep loss",
+            "train recipe test nanochat --mock tests/nanochat.mock.yaml",
+            "train recipe convert nanochat -o recipes/nanochat.yaml",
+            "train recipe graph nanochat --format dot -o nanochat.dot",
+            "train recipe schema --check recipes/sweep.yaml",
+            "train recipe operations session_run",
+            "train recipe lint nanochat --strict",
+            "train recipe vars nanochat",
+        ),
+        see_also=("train help", "train run", "train exec"),
+    ),
+    CommandDoc(
+        key="run",
+        label="Run A Recipe",
+        group="Workflow",
+        command="train run / train recipe run",
+        summary="Load one Python recipe from disk, build its dependency graph, and execute it immediately.",
+        usage_lines=(
+            "train recipe run <name> [options]",
+            "train run <name> [options]",
+        ),
+        options=(
+            "--host NAME=SPEC            Override one recipe host binding.",
+            "--set NAME=VALUE            Override one recipe variable.",
+            "--pick-host NAME            Interactively choose a running Vast host for one recipe host.",
+            "--executor NAME             sequential|thread_pool|process_pool|local|airflow|celery|dask|debug",
+            "--executor-workers N        Worker limit override for parallel executors.",
+            "--executor-option KEY=VALUE Repeatable executor option override.",
+            "--executor-options SPEC     JSON object or comma-separated key=value list.",
+            "--callback NAME             console|jsonl; repeatable or comma-separated.",
+            "--strict-vars               Fail steps that still reference an undefined ${NAME}.",
+            "--max-runtime DURATION      Stop scheduling new steps after this long (e.g. 6h).",
+            "--preflight                 Check hosts for tmux, git, rsync, ... before the first step.",
+            "--preflight-install         Like --preflight, and install missing tools where possible.",
+            "--gpu-guard MODE            off|warn|confirm|fail when a launch targets a GPU already in use.",
+        ),
+        notes=(
+            "`train run` is the file-oriented fast alias for `train recipe run`.",
+            "Kubernetes executor aliases are intentionally unsupported in this runtime.",
+            "Set `recipe.prompt_missing_variables: true` to be asked for undefined `${NAME}` references on a TTY.",
+            "Steps without their own timeout use `timeouts.<class>_secs` (command 1h, http 5m; session, transfer and wait unbounded).",
+            "Set `recipe.preflight: true` to check hosts before every run.",
+            "`--gpu-guard` checks for busy GPUs (`gpu_guard.min_memory_mb`) before torchrun/python/vllm launches.",
+            "`Recipe(..., safe_variables=True)` passes `${NAME}` to shell commands as quoted `TRAINSH_VAR_NAME` variables.",
+        ),
+        examples=(
+            "train recipe run nanochat",
+            "train run nanochat",
+            "train recipe run nanochat --host gpu=vast:12345",
+            "train recipe run nanochat --host gpu=runpod:abc123xyz",
+            "train recipe run nanochat --executor thread_pool --executor-workers 4 --callback console",
+        ),
+        see_also=("train exec", "train recipe resume", "train help"),
+    ),
+    CommandDoc(
+        key="exec",
+        label="Exec A Recipe",
+        group="Workflow",
+        command="train exec / train recipe exec",
+        summary=f"Execute a recipe name, a {RECIPE_FILE_EXTENSION} path, inline Python recipe code, or recipe code read from stdin.",
+        usage_lines=(
+            "train recipe exec <name-or-path> [options]",
+            "train exec <name-or-path> [options]",
+            "train recipe exec -c 'from trainsh import Recipe ...' [options]",
+            "train exec --code 'from trainsh import Recipe ...' [options]",
+            "train exec <<'EOF'",
+            "from trainsh import Recipe",
+            "recipe = Recipe('demo')",
+            "recipe.empty(id='start')",
+            "EOF",
+        ),
+        options=(
+            "-c, --code PYTHON           Execute inline Python recipe code.",
+            "--host NAME=SPEC            Override one recipe host binding.",
+            "--set NAME=VALUE            Override one recipe variable.",
+            "--pick-host NAME            Interactively choose a running Vast host.",
+            "--executor NAME             sequential|thread_pool|process_pool|local|airflow|celery|dask|debug",
+            "--executor-workers N        Worker limit override for parallel executors.",
+            "--executor-option KEY=VALUE Repeatable executor option override.",
+            "--executor-options SPEC     JSON object or comma-separated key=value list.",
+            "--callback NAME             console|jsonl; repeatable or comma-separated.",
+            "--strict-vars               Fail steps that still reference an undefined ${NAME}.",
+            "--max-runtime DURATION      Stop scheduling new steps after this long (e.g. 6h).",
+            "--preflight                 Check hosts for tmux, git, rsync, ... before the first step.",
+            "--preflight-install         Like --preflight, and install missing tools where possible.",
+            "--gpu-guard MODE            off|warn|confirm|fail when a launch targets a GPU already in use.",
+        ),
+        notes=(
+            f"`train exec` accepts recipe names, {RECIPE_FILE_EXTENSION} paths, inline code, or stdin.",
+            "`train run` stays file-oriented; `train exec` is the direct execution entry for files or inline code.",
+        ),
+        examples=(
+            "train exec nanochat",
+            f"train recipe exec ./recipes/demo{RECIPE_FILE_EXTENSION}",
+            "train exec --code 'from trainsh import Recipe; recipe = Recipe(\"demo\"); recipe.empty(id=\"start\")'",
+            "train exec <<'EOF'",
+            "from trainsh import Recipe",
+            "recipe = Recipe('demo')",
+            "recipe.empty(id='start')",
+            "EOF",
+        ),
+        see_also=("train run", "train recipe", "train help"),
+    ),
+    CommandDoc(
+        key="resume",
+        label="Resume A Recipe",
+        group="Workflow",
+        command="train recipe resume",
+        summary="Resume the latest failed or interrupted run for one stored recipe path.",
+        usage_lines=("train recipe resume <name> [options]",),
+        options=("--set NAME=VALUE            Override one recipe variable while resuming.",),
+        notes=(
+            "Resume reuses the latest resumable state for the recipe path.",
+            "Host overrides are intentionally blocked when resuming; start a fresh run instead.",
+        ),
+        examples=(
+            "train recipe resume nanochat",
+            "train recipe resume nanochat --set MODEL=small",
+        ),
+        see_also=("train run", "train recipe status"),
+    ),
+    CommandDoc(
+        key="status",
+        label="Status, Logs, Jobs, And Scheduler History",
+        group="Workflow",
+        command="train recipe status / logs / jobs / schedule status",
+        summary="Choose the right runtime inspection view depending on whether a run is live, persisted, or scheduler-driven.",
+        usage_lines=(
+            "train recipe status [job-id|--last|--all]",
+            "train recipe logs [job-id|--last|--list|--prune [--dry-run]]",
+            "train recipe jobs [--all]",
+            "train recipe schedule status [--rows N] [--runtime-state PATH]",
+        ),
+        blocks=(
+            DocBlock(
+                "How To Choose",
+                (
+                    "status               Live/manual jobs, tmux sessions, current progress.",
+                    "logs                 Detailed persisted execution summaries.",
+                    "jobs                 Compact recent-jobs table.",
+                    "schedule status      Scheduler-triggered run history.",
+                ),
+            ),
+        ),
+        notes=(
+            "If you started the run manually, begin with `train recipe status`.",
+            "If you are checking cron-like scheduled activity, begin with `train recipe schedule status`.",
+            "Finished jobs show their outcome (e.g. failed, oom_killed, cuda_oom, preempted) and the failing step's exit code.",
+            "Step output is capped by `logs.max_step_output_bytes` / `logs.max_run_output_bytes`; a truncation marker shows where it stopped.",
+            "`logs --prune` applies `logs.retention_days` / `logs.keep_runs` now; the same cleanup runs whenever a recipe starts.",
+        ),
+        examples=(
+            "train recipe status",
+            "train recipe status --last",
+            "train recipe logs --last",
+            "train recipe logs --prune --dry-run",
+            "train recipe jobs --all",
+            "train recipe schedule status --rows 20",
+        ),
+        see_also=("train recipe run", "train recipe schedule"),
+    ),
+    CommandDoc(
+        key="logs",
+        label="Recipe Logs",
+        group="Workflow",
+        command="train recipe logs",
+        summary="Inspect persisted execution summaries and step-level output for completed or running jobs.",
+        usage_lines=(
+            "train recipe logs",
+            "train recipe logs --last",
+            "train recipe logs <job-id>",
+        ),
+        notes=(
+            "Use `train recipe logs` for detailed step-level output.",
+            "Failed `step_end` and `step_retry` events carry an `error_code` and `retryable`.",
+        ),
+        examples=(
+            "train recipe logs",
+            "train recipe logs --last",
+            "train recipe logs job12345",
+        ),
+        see_also=("train recipe status", "train recipe jobs"),
+    ),
+    CommandDoc(
+        key="jobs",
+        label="Recipe Jobs",
+        group="Workflow",
+        command="train recipe jobs",
+        summary="Show a compact recent-jobs table across manual and resumed recipe runs.",
+        usage_lines=("train recipe jobs [--all]",),
+        notes=("Use `train recipe jobs` for a compact recent-jobs table.",),
+        examples=("train recipe jobs", "train recipe jobs --all"),
+        see_also=("train recipe status", "train recipe logs"),
+    ),
+    CommandDoc(
+        key="schedule",
+        label="Schedule Recipes",
+        group="Workflow",
+        command="train recipe schedule",
+        summary="Discover scheduled recipes, run the scheduler once or forever, and inspect scheduler history.",
+        usage_lines=(
+            "train recipe schedule run [FILTER...] [options]",
+            "train recipe schedule list [FILTER...] [options]",
+            "train recipe schedule status [options]",
+        ),
+        options=(
+            "--recipe NAME                  Limit to one or more recipe ids or names.",
+            "--recipes-dir PATH             Override recipe discovery directory.",
+            "--runtime-state PATH           Override scheduler JSONL runtime state directory.",
+            "--once                         Run one scheduler pass (default).",
+            "--forever                      Keep polling on an interval.",
+            "--wait                         Wait for started runs to finish.",
+            "--force                        Ignore schedule and start matched recipes now.",
+            "--include-invalid              Include recipes that failed schedule discovery.",
+            "--loop-interval N              Poll interval in seconds for --forever mode.",
+            "--max-active-runs N            Global scheduler concurrency limit.",
+            "--max-active-runs-per-recipe N Per-recipe concurrency limit.",
+            "--iterations N                 Stop after N scheduler loops in --forever mode.",
+            "--rows N                       History rows for `status`.",
+        ),
+        notes=(
+            "`train recipe status` shows live/manual jobs; `train recipe schedule status` shows scheduler history.",
+            "Use `--force` to start matched scheduled recipes even if they are not currently due.",
+        ),
+        examples=(
+            "train recipe schedule list",
+            "train recipe schedule run --recipe nightly",
+            "train recipe schedule run --forever --loop-interval 60",
+            "train recipe schedule status --rows 50",
+        ),
+        see_also=("train recipe status", "train recipe"),
+    ),
+    CommandDoc(
+        key="dashboard",
+        label="Dashboard",
+        group="Workflow",
+        command="train dashboard",
+        summary="Show one aggregated snapshot of host reachability, running executions, active transfers, Vast.ai spend, and recent failures.",
+        usage_lines=(
+            "train dashboard",
+            "train dashboard --json",
+            "train dashboard --refresh [--no-probe] [--no-vast]",
+            "train dashboard --prometheus [--port 9464] [--bind 127.0.0.1] [--hosts a,b]",
+        ),
+        options=(
+            "--json             Print the raw snapshot as JSON.",
+            "--refresh          Ignore the cached snapshot and recompute it.",
+            "--no-probe         Skip SSH reachability probes for configured hosts.",
+            "--no-vast          Skip the Vast.ai instance listing.",
+            "--prometheus       Serve Prometheus gauges on /metrics until Ctrl+C.",
+            "--port/--bind      Exporter address (default `prometheus.port`/`prometheus.bind`, 9464 on 127.0.0.1).",
+            "--hosts a,b        Hosts to sample (default `prometheus.hosts`, else every configured host).",
+        ),
+        notes=(
+            "Snapshots are cached in the runtime state dir for `dashboard.cache_ttl_secs` seconds (default 30).",
+            "Host probes run in parallel; hosts report `unknown` when probing is skipped.",
+            "`--prometheus` exposes `trainsh_*` host, GPU, execution, transfer and queue gauges.",
+        ),
+        examples=(
+            "train dashboard",
+            "train dashboard --json --refresh",
+            "train dashboard --prometheus --bind 0.0.0.0 --hosts gpu-a,gpu-b",
+        ),
+        see_also=("train recipe status", "train vast list"),
+    ),
+)
@@ -0,0 +1,62 @@
+"""Help reference data types and the helpers its command docs share."""
+
+from __future__ import annotations
+
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Iterable
+
+from ..constants import RECIPE_FILE_EXTENSION
+
+
+@dataclass(frozen=True)
+class HelpEntry:
+    group: str
+    command: str
+    summary: str
+    usage: str
+
+
+@dataclass(frozen=True)
+class DocBlock:
+    title: str
+    lines: tuple[str, ...]
+
+
+@dataclass(frozen=True)
+class CommandDoc:
+    key: str
+    label: str
+    group: str
+    command: str
+    summary: str
+    usage_lines: tuple[str, ...]
+    blocks: tuple[DocBlock, ...] = ()
+    options: tuple[str, ...] = ()
+    notes: tuple[str, ...] = ()
+    examples: tuple[str, ...] = ()
+    see_also: tuple[str, ...] = ()
+
+
+def _bundled_examples() -> tuple[str, ...]:
+    examples_dir = Path(__file__).resolve().parents[1] / "examples"
+    names = sorted(path.stem for path in examples_dir.glob(f"*{RECIPE_FILE_EXTENSION}") if path.is_file())
+    return tuple(names)
+
+
+def _template_names() -> tuple[str, ...]:
+    try:
+        from .recipe_templates import list_template_names
+
+        return tuple(list_template_names())
+    except Exception:
+        return ("minimal",)
+
+
+def _joined(values: Iterable[str], *, sep: str = " | ", default: str = "-") -> str:
+    items = [str(value).strip() for value in values if str(value).strip()]
+    return sep.join(items) if items else default
+
+
+def _template_usage_fragment() -> str:
+    return _joined(_template_names(), sep="|", default="minimal")
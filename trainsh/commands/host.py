@@ -5,7 +5,6 @@ import sys
 import os
 from typing import Optional, List
 import re
-import subprocess
 
 from ..cli_utils import SubcommandSpec, dispatch_subcommand, prompt_input
 from .help_catalog import render_command_help
@@ -17,16 +16,34 @@ from .remote_run import (
     run_remote_git_clone,
 )
 from .host_flash_attn import parse_host_flash_attn_args, run_host_flash_attn
-from ..services.tunnel import TunnelSpec, build_local_tunnel_args, start_local_tunnel
+from .host_latency import cmd_latency
+from .host_metrics import cmd_metrics
+from .host_monitor import cmd_monitor, cmd_queue
+from .host_power import cmd_power_off, cmd_wake
+from .host_cloudflared import cmd_cloudflared
+from .host_processes import cmd_kill, cmd_ps
+from .host_logs import cmd_logs
+from .host_ssh_config import cmd_ssh_config
+from .host_tailscale import cmd_tailscale
+from .host_vast_link import cmd_link, cmd_refresh
+from .host_bootstrap import cmd_bootstrap
+from .host_broadcast import cmd_broadcast
+from .host_follow import cmd_follow
+from .host_paste import cmd_paste
+from .host_defaults import cmd_defaults
+from .host_disk import cmd_du
+from .host_integrity import cmd_manifest, cmd_verify
+from .host_vscode import cmd_vscode
+from .host_tunnel import cmd_tunnel
 from .host_interactive import (
     _normalize_connection_candidates,
     _prompt_connection_candidates,
     _prompt_int,
     _render_connection_candidate_line,
     cmd_add,
-    cmd_browse,
     cmd_edit,
 )
+from .host_files import cmd_browse
 
 SUBCOMMAND_SPECS = (
     SubcommandSpec("list", "List named reusable host definitions."),
@@ -34,11 +51,34 @@ SUBCOMMAND_SPECS = (
     SubcommandSpec("edit", "Modify an existing named host."),
     SubcommandSpec("show", "Inspect one host definition."),
     SubcommandSpec("ssh", "Open an SSH session using the stored connection settings."),
+    SubcommandSpec("logs", "List or print interactive sessions mirrored with `train host ssh --log`."),
+    SubcommandSpec("follow", "Follow log files on hosts in the background with `tail -F`."),
     SubcommandSpec("run", "Run one remote shell command using the stored connection settings."),
     SubcommandSpec("tunnel", "Open one local SSH port-forward tunnel to a host."),
     SubcommandSpec("clone", "Clone one git repository on a host using stored connection settings."),
-    SubcommandSpec("files", "Browse remote files over SFTP."),
+    SubcommandSpec("files", "Browse remote files over SFTP; preview or quick-edit small text files."),
     SubcommandSpec("check", "Check whether a host is reachable."),
+    SubcommandSpec("latency", "Time TCP connect, SSH banner and handshake per route and keep a history."),
+    SubcommandSpec("cloudflared", "Set up, verify, or keep alive a Cloudflare Access SSH tunnel."),
+    SubcommandSpec("tailscale", "List tailnet nodes, or link a host to a Tailscale node name."),
+    SubcommandSpec("ssh-config", "Export all managed hosts to an OpenSSH include for plain `ssh <alias>`."),
+    SubcommandSpec("link", "Bind a stored SSH host to a Vast.ai instance (`vast:<id>`)."),
+    SubcommandSpec("refresh", "Re-read linked hosts' SSH endpoints from Vast and update saved sessions."),
+    SubcommandSpec("wake", "Send a Wake-on-LAN packet and wait until the host answers over SSH."),
+    SubcommandSpec("power-off", "Shut down or suspend a host over SSH and wait until it is offline."),
+    SubcommandSpec("du", "Show what is using disk space under a path on a host, or delete one entry."),
+    SubcommandSpec("ps", "List processes on a host with the GPU memory each one holds."),
+    SubcommandSpec("kill", "Send a signal to one process on a host."),
+    SubcommandSpec("manifest", "Hash every file under a remote directory into a sha256 manifest."),
+    SubcommandSpec("verify", "Verify a downloaded local copy against a remote directory or saved manifest."),
+    SubcommandSpec("vscode", "Write a VS Code Remote-SSH entry or start a tunneled code-server."),
+    SubcommandSpec("metrics", "Stream CPU, memory, disk IO, network and GPU samples from hosts."),
+    SubcommandSpec("monitor", "Track host connectivity and replay queued operations on reconnect."),
+    SubcommandSpec("queue", "List, replay, or clear operations queued while offline."),
+    SubcommandSpec("bootstrap", "Apply a bootstrap profile (packages, timezone, tmux.conf) to one host."),
+    SubcommandSpec("broadcast", "Type the same input into a group of host tmux sessions at once."),
+    SubcommandSpec("paste", "Paste a file or stdin into a host tmux session in chunks, bracketed when supported."),
+    SubcommandSpec("defaults", "Show or set the host's session workdir, shell, tmux history limit and env."),
     SubcommandSpec("flash-attn", "Probe flash-attn compatibility and optionally install it on one host."),
     SubcommandSpec("remove", "Delete a stored host definition or destroy a Vast.ai instance."),
 )
@@ -50,16 +90,23 @@ AUTO_DISCOVERED_VAST_ENV = "_auto_discovered_vast"
 AUTO_DISCOVERED_RUNPOD_ENV = "_auto_discovered_runpod"
 
 
-def _load_configured_hosts() -> dict:
-    """Load hosts stored on disk."""
+def _read_hosts_file() -> dict:
+    """hosts.yaml as written."""
     from ..constants import HOSTS_FILE
     import yaml
 
     if not HOSTS_FILE.exists():
         return {}
-
     with open(HOSTS_FILE, "r") as f:
-        data = yaml.safe_load(f) or {}
+        return yaml.safe_load(f) or {}
+
+
+def _load_configured_hosts() -> dict:
+    """Load hosts stored on disk, expanding `${env:NAME}` references."""
+    from ..constants import HOSTS_FILE
+    from ..core.env_refs import expand_env_refs
+
+    data = expand_env_refs(_read_hosts_file(), where=HOSTS_FILE.name)
 
     hosts = {}
     for host_data in data.get("hosts", []):
@@ -135,15 +182,39 @@ def _build_runpod_host(pod, name: str):
     return build_host_from_runpod_pod(pod, name=name, auto_discovered=True)
 
 
-def _load_auto_vast_hosts(configured_hosts: dict) -> dict:
-    """Load temporary host entries from current Vast.ai instances."""
+def _list_vast_instances() -> list:
     from ..services.vast_api import get_vast_client
 
-    try:
-        client = get_vast_client()
-        instances = client.list_instances()
-    except Exception:
-        return {}
+    return get_vast_client().list_instances()
+
+
+def _list_runpod_pods() -> list:
+    from ..services.runpod_api import get_runpod_client
+
+    return get_runpod_client().list_pods()
+
+
+def _list_provider_resources() -> tuple[list, list]:
+    """Fetch Vast instances and RunPod Pods concurrently.
+
+    A provider that fails or does not answer within `host_refresh.timeout_secs`
+    contributes nothing instead of stalling the listing.
+    """
+    from ..services.host_refresh import run_per_host
+
+    fetchers = {"vast": _list_vast_instances, "runpod": _list_runpod_pods}
+    results = run_per_host(fetchers, lambda provider: fetchers[provider]())
+    listed = {name: (result.value or []) if result.status == "ok" else [] for name, result in results.items()}
+    return listed["vast"], listed["runpod"]
+
+
+def _load_auto_vast_hosts(configured_hosts: dict, instances: Optional[list] = None) -> dict:
+    """Load temporary host entries from current Vast.ai instances."""
+    if instances is None:
+        try:
+            instances = _list_vast_instances()
+        except Exception:
+            return {}
 
     auto_hosts = {}
     for instance in instances:
@@ -152,15 +223,13 @@ def _load_auto_vast_hosts(configured_hosts: dict) -> dict:
     return auto_hosts
 
 
-def _load_auto_runpod_hosts(configured_hosts: dict) -> dict:
+def _load_auto_runpod_hosts(configured_hosts: dict, pods: Optional[list] = None) -> dict:
     """Load temporary host entries from current RunPod Pods."""
-    from ..services.runpod_api import get_runpod_client
-
-    try:
-        client = get_runpod_client()
-        pods = client.list_pods()
-    except Exception:
-        return {}
+    if pods is None:
+        try:
+            pods = _list_runpod_pods()
+        except Exception:
+            return {}
 
     auto_hosts = {}
     for pod in pods:
@@ -174,8 +243,9 @@ def load_hosts(include_auto_vast: bool = True) -> dict:
     hosts = _load_configured_hosts()
     if not include_auto_vast:
         return hosts
-    hosts.update(_load_auto_vast_hosts(hosts))
-    hosts.update(_load_auto_runpod_hosts(hosts))
+    instances, pods = _list_provider_resources()
+    hosts.update(_load_auto_vast_hosts(hosts, instances))
+    hosts.update(_load_auto_runpod_hosts(hosts, pods))
     return hosts
 
 
@@ -219,6 +289,7 @@ def _host_to_dict(host) -> dict:
 def save_hosts(hosts: dict) -> None:
     """Save hosts to configuration."""
     from ..constants import HOSTS_FILE, CONFIG_DIR
+    from ..core.env_refs import restore_env_refs_by_name
     import yaml
 
     CONFIG_DIR.mkdir(parents=True, exist_ok=True)
@@ -228,21 +299,31 @@ def save_hosts(hosts: dict) -> None:
         for host in hosts.values()
         if not _is_auto_discovered_host(host)
     ]
-    data = {"hosts": persisted_hosts}
+    data = {"hosts": restore_env_refs_by_name(_read_hosts_file().get("hosts"), persisted_hosts)}
 
     with open(HOSTS_FILE, "w") as f:
         yaml.dump(data, f, default_flow_style=False, sort_keys=False)
 
+    from ..services.ssh_export import refresh_if_enabled
+
+    refresh_if_enabled()
+
 
 def cmd_list(args: List[str]) -> None:
     """List configured hosts."""
     from ..core.models import HostType
 
+    from ..services.projects import active_project, scope_note, scoped
+
     hosts = load_hosts()
+    project = active_project()
+    visible = scoped(hosts, "hosts", project)
+    hidden = len(hosts) - len(visible)
+    hosts = {name: hosts[name] for name in visible}
 
     if not hosts:
-        print("No hosts configured.")
-        print("Use 'train host add' to add a host.")
+        print("No hosts configured." if project is None else f"No hosts in project {project.name}.")
+        print("Use 'train host add' to add a host." if project is None else scope_note(project, "hosts", hidden))
         return
 
     print("Configured hosts:")
@@ -285,6 +366,8 @@ def cmd_list(args: List[str]) -> None:
         print(f"Auto-discovered Vast.ai hosts: {auto_vast_count}")
     if auto_runpod_count:
         print(f"Auto-discovered RunPod hosts: {auto_runpod_count}")
+    if project is not None:
+        print(scope_note(project, "hosts", hidden))
 
 
 def cmd_show(args: List[str]) -> None:
@@ -311,6 +394,8 @@ def cmd_show(args: List[str]) -> None:
     print(f"  Port: {host.port}")
     print(f"  Username: {host.username}")
     print(f"  Auth: {host.auth_method.value}")
+    if host.shell:
+        print(f"  Shell: {host.shell}")
     if _is_auto_discovered_host(host):
         print("  Auto-discovered: yes")
     connection_source = _host_connection_source(host)
@@ -327,6 +412,21 @@ def cmd_show(args: List[str]) -> None:
         print("  SSH Password: managed by train secrets")
     if host.jump_host:
         print(f"  Jump Host: {host.jump_host}")
+    if host.env_vars.get("tailscale_node"):
+        from ..services.tailscale import TailscaleError, node_address, resolve_node
+
+        node_name = host.env_vars["tailscale_node"]
+        try:
+            print(f"  Tailscale Node: {node_name} (online, {node_address(resolve_node(node_name))})")
+        except TailscaleError as exc:
+            print(f"  Tailscale Node: {node_name} ({exc})")
+    if host.env_vars.get("vast_link"):
+        print(f"  Vast Link: {host.env_vars['vast_link']} (refresh with `train host refresh {name}`)")
+    if host.env_vars.get("wol_mac"):
+        from ..services.host_power import power_settings
+
+        power = power_settings(host)
+        print(f"  Wake-on-LAN: {power['mac']} via {power['broadcast']}:{power['port']}")
     tunnel_type = host.env_vars.get("tunnel_type", "")
     if host.type == HostType.SSH and tunnel_type == "cloudflared":
         print("  Tunnel: cloudflared")
@@ -341,6 +441,19 @@ def cmd_show(args: List[str]) -> None:
         print("  Connection candidates:")
         for idx, candidate in enumerate(connection_candidates, start=1):
             print(f"    {_render_connection_candidate_line(idx, candidate)}")
+    from ..services.host_latency import host_latency_history, route_stats
+
+    latency = route_stats(host_latency_history(name))
+    if len(latency) > 1:
+        print("  Route latency:")
+        for item in latency:
+            median = "-" if item["median_ms"] is None else f"{item['median_ms']:.0f}ms"
+            note = " (fastest)" if item["fastest"] else "" if item["last_ok"] else " (failing)"
+            print(f"    {item['route']} {item['endpoint']}: {median}{note}")
+    if host.defaults:
+        summary = ", ".join(f"{key}={value}" for key, value in host.defaults.items() if key != "env")
+        env_names = ", ".join(host.defaults.get("env") or {})
+        print(f"  Session defaults: {summary or '-'}{f' (env: {env_names})' if env_names else ''}")
     if host.tags:
         print(f"  Tags: {', '.join(host.tags)}")
     if host.type == HostType.COLAB:
@@ -358,10 +471,13 @@ def cmd_ssh(args: List[str]) -> None:
     from ..core.models import HostType
 
     if not args:
-        print("Usage: train host ssh <name>")
+        print("Usage: train host ssh <name> [--log]")
         sys.exit(1)
 
+    from ..services.terminal_log import mirror_requested
+
     name = args[0]
+    log_name = name if mirror_requested(args[1:]) else ""
     hosts = load_hosts()
 
     if name not in hosts:
@@ -385,7 +501,12 @@ def cmd_ssh(args: List[str]) -> None:
         else:
             # ngrok - standard SSH with port
             ssh_cmd = f"ssh -p {host.port} {host.username}@{host.hostname}"
-        os.system(ssh_cmd)
+        if log_name:
+            from ..services.terminal_log import run_interactive
+
+            run_interactive(["sh", "-c", ssh_cmd], log_name)
+        else:
+            os.system(ssh_cmd)
     else:
         from ..services.ssh import SSHClient
         try:
@@ -393,7 +514,15 @@ def cmd_ssh(args: List[str]) -> None:
         except Exception as exc:
             print(f"Connection setup failed: {exc}")
             sys.exit(1)
-        exit_code = ssh.connect_interactive()
+        from ..services.bootstrap_profile import ensure_bootstrapped
+        from ..services.terminal_env import session_defaults
+
+        ensure_bootstrapped(host)
+        session_command = session_defaults(host).session_command()
+        if session_command:
+            exit_code = ssh.connect_interactive(session_command, log_name=log_name)
+        else:
+            exit_code = ssh.connect_interactive(log_name=log_name)
         if exit_code != 0:
             sys.exit(exit_code)
 
@@ -421,9 +550,13 @@ def cmd_test(args: List[str]) -> None:
         print(f"Connection setup failed: {exc}")
         sys.exit(1)
 
+    from ..services.connectivity import ConnectivityMonitor
+
     if ssh.test_connection():
+        ConnectivityMonitor().record(name, True)
         print("Connection successful!")
     else:
+        print(ConnectivityMonitor().explain_failure(name, "connection test failed"))
         print("Connection failed.")
         sys.exit(1)
 
@@ -437,107 +570,10 @@ def cmd_run(args: List[str]) -> None:
         print(f"Host not found: {name}")
         sys.exit(1)
 
-    run_remote_command(hosts[name], command, label=name)
+    from ..services.host_defaults import HostDefaults
 
-
-def cmd_tunnel(args: List[str]) -> None:
-    """Open one local SSH local-forward tunnel."""
-    if not args:
-        print(
-            "Usage: train host tunnel <name> --local-port <port> --remote-port <port> "
-            "[--bind-host <host>] [--remote-host <host>] [--background]"
-        )
-        sys.exit(1)
-
-    name = str(args[0]).strip()
-    hosts = load_hosts()
-    if name not in hosts:
-        print(f"Host not found: {name}")
-        sys.exit(1)
-
-    local_port = None
-    remote_port = None
-    bind_host = "127.0.0.1"
-    remote_host = "127.0.0.1"
-    background = False
-
-    i = 1
-    while i < len(args):
-        arg = args[i]
-        if arg == "--local-port":
-            if i + 1 >= len(args):
-                print("Missing value for --local-port.")
-                sys.exit(1)
-            i += 1
-            local_port = int(args[i])
-        elif arg.startswith("--local-port="):
-            local_port = int(arg.split("=", 1)[1])
-        elif arg == "--remote-port":
-            if i + 1 >= len(args):
-                print("Missing value for --remote-port.")
-                sys.exit(1)
-            i += 1
-            remote_port = int(args[i])
-        elif arg.startswith("--remote-port="):
-            remote_port = int(arg.split("=", 1)[1])
-        elif arg == "--bind-host":
-            if i + 1 >= len(args):
-                print("Missing value for --bind-host.")
-                sys.exit(1)
-            i += 1
-            bind_host = str(args[i]).strip() or bind_host
-        elif arg.startswith("--bind-host="):
-            bind_host = str(arg.split("=", 1)[1]).strip() or bind_host
-        elif arg == "--remote-host":
-            if i + 1 >= len(args):
-                print("Missing value for --remote-host.")
-                sys.exit(1)
-            i += 1
-            remote_host = str(args[i]).strip() or remote_host
-        elif arg.startswith("--remote-host="):
-            remote_host = str(arg.split("=", 1)[1]).strip() or remote_host
-        elif arg == "--background":
-            background = True
-        else:
-            print(f"Unknown option: {arg}")
-            sys.exit(1)
-        i += 1
-
-    if local_port is None or remote_port is None:
-        print(
-            "Usage: train host tunnel <name> --local-port <port> --remote-port <port> "
-            "[--bind-host <host>] [--remote-host <host>] [--background]"
-        )
-        sys.exit(1)
-
-    spec = TunnelSpec(
-        local_port=int(local_port),
-        remote_port=int(remote_port),
-        bind_host=bind_host,
-        remote_host=remote_host,
-    )
     host = hosts[name]
-    if background:
-        try:
-            process = start_local_tunnel(host, spec, wait_timeout=10.0)
-        except RuntimeError as exc:
-            print(f"Failed to open tunnel: {exc}")
-            sys.exit(1)
-        print(
-            f"Tunnel ready in background: {bind_host}:{local_port} -> {remote_host}:{remote_port} "
-            f"(pid {process.pid})"
-        )
-        return
-
-    print(f"Forwarding {bind_host}:{local_port} -> {remote_host}:{remote_port} via {name}")
-    args = build_local_tunnel_args(host, spec)
-    try:
-        result = subprocess.run(args)
-    except KeyboardInterrupt:
-        print("\nTunnel stopped.")
-        return
-    if result.returncode != 0:
-        sys.exit(result.returncode)
+    run_remote_command(host, HostDefaults.for_host(host).wrap_command(command), label=name)
 
 
 def cmd_clone(args: List[str]) -> None:
@@ -580,6 +616,9 @@ def cmd_rm(args: List[str]) -> None:
         print("Usage: train host remove <name>")
         sys.exit(1)
 
+    from ..services.viewer_mode import require_operator
+
+    require_operator("host.remove")
     name = args[0]
     hosts = load_hosts(include_auto_vast=False)
     target_host = hosts.get(name)
@@ -596,6 +635,8 @@ def cmd_rm(args: List[str]) -> None:
         print("Cancelled.")
         return
 
+    from ..services.audit_log import record
+
     if target_host.vast_instance_id:
         from ..services.vast_api import get_vast_client
 
@@ -604,6 +645,7 @@ def cmd_rm(args: List[str]) -> None:
         if name in hosts:
             del hosts[name]
             save_hosts(hosts)
+        record("host.remove", name, host="vast", detail=f"destroyed Vast.ai instance {target_host.vast_instance_id}")
         print(f"Vast.ai instance removed: {target_host.vast_instance_id}")
         return
 
@@ -615,11 +657,13 @@ def cmd_rm(args: List[str]) -> None:
         if name in hosts:
             del hosts[name]
             save_hosts(hosts)
+        record("host.remove", name, host="runpod", detail=f"deleted RunPod Pod {target_host.runpod_pod_id}")
         print(f"RunPod Pod removed: {target_host.runpod_pod_id}")
         return
 
     del hosts[name]
     save_hosts(hosts)
+    record("host.remove", name, host=name)
     print(f"Host removed: {name}")
 
 
@@ -640,11 +684,34 @@ def main(args: List[str]) -> Optional[str]:
         "edit": cmd_edit,
         "show": cmd_show,
         "ssh": cmd_ssh,
+        "logs": cmd_logs,
         "run": cmd_run,
         "tunnel": cmd_tunnel,
         "clone": cmd_clone,
         "files": cmd_browse,
         "check": cmd_test,
+        "cloudflared": cmd_cloudflared,
+        "tailscale": cmd_tailscale,
+        "ssh-config": cmd_ssh_config,
+        "link": cmd_link,
+        "refresh": cmd_refresh,
+        "wake": cmd_wake,
+        "power-off": cmd_power_off,
+        "du": cmd_du,
+        "ps": cmd_ps,
+        "kill": cmd_kill,
+        "manifest": cmd_manifest,
+        "verify": cmd_verify,
+        "vscode": cmd_vscode,
+        "metrics": cmd_metrics,
+        "latency": cmd_latency,
+        "monitor": cmd_monitor,
+        "queue": cmd_queue,
+        "bootstrap": cmd_bootstrap,
+        "defaults": cmd_defaults,
+        "broadcast": cmd_broadcast,
+        "follow": cmd_follow,
+        "paste": cmd_paste,
         "flash-attn": cmd_flash_attn,
         "remove": cmd_rm,
     }
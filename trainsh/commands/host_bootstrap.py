@@ -0,0 +1,92 @@
+"""`train host bootstrap`: apply a bootstrap profile to a stored host."""
+
+from __future__ import annotations
+
+import sys
+from typing import List
+
+from ..services.bootstrap_profile import (
+    apply_profile,
+    load_profiles,
+    profile_for_host,
+    profile_summary,
+)
+
+USAGE = "train host bootstrap <name> [--profile <profile>] [--force]\ntrain host bootstrap --list"
+
+
+def _list_profiles() -> None:
+    profiles = load_profiles()
+    if not profiles:
+        print("No bootstrap profiles configured (see `bootstrap.profiles` in config).")
+        return
+    for name, profile in sorted(profiles.items()):
+        print(f"  {name}: {'; '.join(profile_summary(profile)) or 'empty'}")
+
+
+def cmd_bootstrap(args: List[str]) -> None:
+    """Apply a bootstrap profile to a host, once per profile revision."""
+    from .host import load_hosts, save_hosts
+
+    if args and args[0] == "--list":
+        _list_profiles()
+        return
+    if not args or args[0].startswith("-"):
+        print(f"Usage: {USAGE}")
+        sys.exit(1)
+
+    name = args[0]
+    profile_name = ""
+    force = False
+    rest = args[1:]
+    i = 0
+    while i < len(rest):
+        arg = rest[i]
+        if arg == "--profile" and i + 1 < len(rest):
+            profile_name = rest[i + 1]
+            i += 2
+            continue
+        if arg.startswith("--profile="):
+            profile_name = arg.split("=", 1)[1]
+        elif arg == "--force":
+            force = True
+        else:
+            print(f"Unknown option: {arg}")
+            print(f"Usage: {USAGE}")
+            sys.exit(1)
+        i += 1
+
+    hosts = load_hosts()
+    if name not in hosts:
+        print(f"Host not found: {name}")
+        sys.exit(1)
+    host = hosts[name]
+
+    profiles = load_profiles()
+    profile_name = profile_name.strip() or profile_for_host(host)
+    if not profile_name:
+        print(f"No bootstrap profile for {name}; pass --profile or set bootstrap.default_profile.")
+        sys.exit(1)
+    if profile_name not in profiles:
+        print(f"Bootstrap profile not found: {profile_name}")
+        sys.exit(1)
+
+    if host.bootstrap_profile != profile_name:
+        host.bootstrap_profile = profile_name
+        configured = load_hosts(include_auto_vast=False)
+        if name in configured:
+            configured[name].bootstrap_profile = profile_name
+            save_hosts(configured)
+
+    print(f"Applying bootstrap profile {profile_name} to {host.display_name}...")
+    result = apply_profile(host, profile_name, profiles[profile_name], force=force)
+    if result.status == "already":
+        print("Already applied (use --force to run it again).")
+    elif result.status == "applied":
+        print("Bootstrap complete.")
+    else:
+        print(f"Bootstrap failed: {result.detail}")
+        sys.exit(1)
+
+
+__all__ = ["USAGE", "cmd_bootstrap"]
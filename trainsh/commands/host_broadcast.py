@@ -0,0 +1,80 @@
+"""`train host broadcast`: type the same input into a group of host terminals."""
+
+from __future__ import annotations
+
+import sys
+from typing import List
+
+USAGE = (
+    "train host broadcast list\n"
+    "train host broadcast create <group> <host[:session]>...\n"
+    "train host broadcast remove <group>\n"
+    "train host broadcast send <group> [--no-enter] -- <text>"
+)
+
+
+def _send(args: List[str]) -> None:
+    from ..services.terminal_broadcast import broadcast_write
+
+    enter = True
+    if "--" in args:
+        split = args.index("--")
+        options, words = args[:split], args[split + 1:]
+    else:
+        options, words = args[:1], args[1:]
+    if "--no-enter" in options:
+        enter = False
+        options = [item for item in options if item != "--no-enter"]
+    if len(options) != 1 or not words:
+        print(f"Usage: {USAGE}")
+        sys.exit(1)
+    group, text = options[0], " ".join(words)
+    try:
+        results = broadcast_write(group, text, enter=enter)
+    except (KeyError, ValueError) as exc:
+        print(exc.args[0] if exc.args else exc)
+        sys.exit(1)
+    for result in results:
+        print(result.line(text))
+    failed = sum(1 for result in results if not result.ok)
+    if failed:
+        print(f"{failed} of {len(results)} terminals did not receive the input.")
+        sys.exit(1)
+
+
+def cmd_broadcast(args: List[str]) -> None:
+    """Manage broadcast groups or send input to one."""
+    from ..services.terminal_broadcast import configured_groups, remove_group, save_group
+
+    action = args[0] if args else "list"
+    rest = args[1:]
+    if action == "list" and not rest:
+        groups = configured_groups()
+        if not groups:
+            print("No broadcast groups. Create one with `train host broadcast create <group> <host>...`.")
+            return
+        for name, targets in sorted(groups.items()):
+            print(f"  {name}: {', '.join(targets)}")
+        return
+    if action == "create" and len(rest) >= 2:
+        try:
+            targets = save_group(rest[0], rest[1:])
+        except ValueError as exc:
+            print(exc)
+            sys.exit(1)
+        print(f"Broadcast group {rest[0]}: {', '.join(str(item) for item in targets)}")
+        return
+    if action == "remove" and len(rest) == 1:
+        if not remove_group(rest[0]):
+            print(f"Broadcast group not found: {rest[0]}")
+            sys.exit(1)
+        print(f"Broadcast group removed: {rest[0]}")
+        return
+    if action == "send":
+        _send(rest)
+        return
+    print(f"Usage: {USAGE}")
+    sys.exit(1)
+
+
+__all__ = ["USAGE", "cmd_broadcast"]
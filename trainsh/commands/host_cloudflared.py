@@ -0,0 +1,149 @@
+"""`train host cloudflared`: set up, verify, and keep alive Cloudflare Access SSH tunnels."""
+
+from __future__ import annotations
+
+import sys
+from typing import Dict, List
+
+USAGE_LINES = (
+    "train host cloudflared setup <name> --hostname <ssh.example.com> [--bin PATH] [--install]",
+    "train host cloudflared verify <name>",
+    "train host cloudflared forward <name> [--local-port N] [--interval SECS]",
+)
+
+
+def _usage() -> None:
+    print("Usage:")
+    for line in USAGE_LINES:
+        print(f"  {line}")
+    sys.exit(1)
+
+
+def _parse(args: List[str], flags: tuple, valued: tuple):
+    positional: List[str] = []
+    options: Dict[str, object] = {}
+    i = 0
+    while i < len(args):
+        arg = args[i]
+        if arg in valued and i + 1 < len(args):
+            options[arg] = args[i + 1]
+            i += 2
+            continue
+        if arg in flags:
+            options[arg] = True
+        elif arg.startswith("-"):
+            _usage()
+        else:
+            positional.append(arg)
+        i += 1
+    if len(positional) != 1:
+        _usage()
+    return positional[0], options
+
+
+def _setup(args: List[str]) -> None:
+    from ..services.cloudflared import cloudflared_binary, configure_host, find_cloudflared, install_cloudflared
+    from .host import load_hosts, save_hosts
+
+    name, options = _parse(args, ("--install",), ("--hostname", "--bin"))
+    configured = load_hosts(include_auto_vast=False)
+    if name not in configured:
+        print(f"Host not found: {name}")
+        sys.exit(1)
+    host = configured[name]
+    hostname = str(options.get("--hostname") or (host.env_vars or {}).get("cloudflared_hostname") or "").strip()
+    if not hostname:
+        print("Missing --hostname: the Cloudflare Access hostname routed to the host's SSH port.")
+        sys.exit(1)
+    binary = str(options.get("--bin", "") or "")
+
+    if not find_cloudflared(binary or cloudflared_binary(host.env_vars)):
+        if not options.get("--install"):
+            print("cloudflared is not installed locally; re-run with --install or pass --bin PATH.")
+            sys.exit(1)
+        try:
+            installed = install_cloudflared()
+        except (ValueError, OSError) as exc:
+            print(f"cloudflared install failed: {exc}")
+            sys.exit(1)
+        print(f"Installed cloudflared to {installed}")
+        binary = binary or str(installed)
+
+    configure_host(host, hostname, binary)
+    save_hosts(configured)
+    print(f"{name} now connects through `cloudflared access ssh --hostname {hostname}`.")
+    print(f"Run `train host cloudflared verify {name}` to test it.")
+
+
+def _verify(args: List[str]) -> None:
+    from ..services.cloudflared import verify_tunnel
+    from .host import load_hosts
+
+    name, _ = _parse(args, (), ())
+    hosts = load_hosts()
+    if name not in hosts:
+        print(f"Host not found: {name}")
+        sys.exit(1)
+    report = verify_tunnel(hosts[name])
+    print(f"  Hostname:   {report['hostname'] or '-'}")
+    print(f"  cloudflared: {report['binary'] or 'not found'}{'  (' + report['version'] + ')' if report['version'] else ''}")
+    if report["ssh_ok"]:
+        print(f"  SSH:        ok ({report['latency_ms']} ms)")
+        return
+    print(f"  SSH:        failed: {report['error']}")
+    sys.exit(1)
+
+
+def _forward(args: List[str]) -> None:
+    from ..config import get_config_value
+    from ..services.cloudflared import TunnelSupervisor, cloudflared_binary, find_cloudflared
+    from .host import load_hosts
+
+    name, options = _parse(args, (), ("--local-port", "--interval"))
+    hosts = load_hosts()
+    if name not in hosts:
+        print(f"Host not found: {name}")
+        sys.exit(1)
+    env_vars = dict(hosts[name].env_vars or {})
+    if str(env_vars.get("tunnel_type", "")).lower() != "cloudflared":
+        print(f"{name} is not configured for cloudflared; run `train host cloudflared setup {name}` first.")
+        sys.exit(1)
+    binary = find_cloudflared(cloudflared_binary(env_vars))
+    if not binary:
+        print("cloudflared is not installed locally; run setup with --install.")
+        sys.exit(1)
+    try:
+        local_port = int(options.get("--local-port", 0))
+        interval = float(options.get("--interval", get_config_value("cloudflared.health_interval_secs", 30)))
+    except ValueError:
+        _usage()
+
+    def report(event: str, payload: dict) -> None:
+        if event == "up":
+            print(f"Tunnel up: ssh -p {payload['local_port']} {hosts[name].username or 'root'}@127.0.0.1", flush=True)
+        elif event == "down":
+            print(f"Tunnel down; reconnecting (restart {payload['restarts'] + 1})...", flush=True)
+        elif event == "failed":
+            print(f"Tunnel failed to start: {payload['detail']}", flush=True)
+
+    supervisor = TunnelSupervisor(
+        str(env_vars.get("cloudflared_hostname") or hosts[name].hostname),
+        binary=binary,
+        local_port=local_port,
+        interval=interval,
+        on_event=report,
+    )
+    print(f"Forwarding 127.0.0.1:{supervisor.local_port} -> {supervisor.hostname} (Ctrl-C to stop)")
+    try:
+        supervisor.run()
+    except KeyboardInterrupt:
+        supervisor.stop()
+    print(f"Tunnel stopped after {supervisor.restarts} restart(s).")
+
+
+def cmd_cloudflared(args: List[str]) -> None:
+    """Manage a host's cloudflared SSH tunnel."""
+    handlers = {"setup": _setup, "verify": _verify, "forward": _forward}
+    if not args or args[0] not in handlers:
+        _usage()
+    handlers[args[0]](args[1:])
@@ -0,0 +1,106 @@
+"""`train host defaults`: show or update a stored host's session defaults."""
+
+from __future__ import annotations
+
+import sys
+from typing import Any, Dict, List
+
+USAGE = (
+    "train host defaults <name> [--workdir DIR] [--shell SHELL] [--history-limit N]\n"
+    "                           [--env KEY=VALUE]... [--unset KEY]... [--clear]"
+)
+_VALUE_OPTIONS = {"--workdir": "workdir", "--shell": "shell", "--history-limit": "history_limit"}
+
+
+def _print_defaults(name: str, defaults: Dict[str, Any]) -> None:
+    from ..services.host_defaults import DEFAULT_HISTORY_LIMIT
+
+    if not defaults:
+        print(f"No session defaults for {name}.")
+        return
+    print(f"Session defaults for {name}:")
+    print(f"  workdir:       {defaults.get('workdir') or '-'}")
+    print(f"  shell:         {defaults.get('shell') or '-'}")
+    print(f"  history_limit: {defaults.get('history_limit') or f'- ({DEFAULT_HISTORY_LIMIT} for local sessions)'}")
+    for key, value in (defaults.get("env") or {}).items():
+        print(f"  env {key}={value}")
+
+
+def cmd_defaults(args: List[str]) -> None:
+    """Print a host's defaults, or change them with options (empty values clear a field)."""
+    from ..services.host_defaults import HostDefaults, validate_defaults
+    from .host import load_hosts, save_hosts
+
+    if not args or args[0].startswith("-"):
+        print(f"Usage: {USAGE}")
+        sys.exit(1)
+
+    name = args[0]
+    updates: Dict[str, Any] = {}
+    env_set: Dict[str, str] = {}
+    env_unset: List[str] = []
+    clear = False
+    rest = args[1:]
+    i = 0
+    while i < len(rest):
+        arg = rest[i]
+        option, _, inline = arg.partition("=")
+        if option in _VALUE_OPTIONS or option in ("--env", "--unset"):
+            if not inline and "=" not in arg:
+                if i + 1 >= len(rest):
+                    print(f"Missing value for {option}.")
+                    sys.exit(1)
+                i += 1
+                inline = rest[i]
+            if option in _VALUE_OPTIONS:
+                updates[_VALUE_OPTIONS[option]] = inline.strip()
+            elif option == "--env":
+                key, sep, value = inline.partition("=")
+                if not sep:
+                    print(f"Expected KEY=VALUE for --env, got: {inline}")
+                    sys.exit(1)
+                env_set[key.strip()] = value
+            else:
+                env_unset.append(inline.strip())
+        elif arg == "--clear":
+            clear = True
+        else:
+            print(f"Unknown option: {arg}")
+            print(f"Usage: {USAGE}")
+            sys.exit(1)
+        i += 1
+
+    configured = load_hosts(include_auto_vast=False)
+    if name not in configured:
+        hosts = load_hosts()
+        print(f"Host is auto-discovered and has no stored entry: {name}" if name in hosts else f"Host not found: {name}")
+        sys.exit(1)
+    host = configured[name]
+
+    if not (updates or env_set or env_unset or clear):
+        _print_defaults(name, HostDefaults.for_host(host).to_dict())
+        return
+
+    defaults = {} if clear else dict(host.defaults or {})
+    for key, value in updates.items():
+        if value:
+            defaults[key] = value
+        else:
+            defaults.pop(key, None)
+    env = dict(defaults.get("env") or {})
+    env.update(env_set)
+    for key in env_unset:
+        env.pop(key, None)
+    defaults["env"] = env
+    problems = validate_defaults(defaults)
+    if problems:
+        for problem in problems:
+            print(f"Invalid defaults: {problem}")
+        sys.exit(1)
+
+    host.defaults = HostDefaults.from_dict(defaults).to_dict()
+    save_hosts(configured)
+    _print_defaults(name, host.defaults)
+
+
+__all__ = ["USAGE", "cmd_defaults"]
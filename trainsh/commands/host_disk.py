@@ -0,0 +1,112 @@
+"""`train host du`: explore and clean up disk usage on a stored host."""
+
+from __future__ import annotations
+
+import json
+import sys
+from typing import List
+
+from ..cli_utils import prompt_input
+
+USAGE = (
+    "train host du <name> [path] [--depth N] [--top N] [--refresh] [--background] [--json]\n"
+    "train host du <name> --delete <path> [--yes]"
+)
+
+
+def _parse_int(option: str, value: str) -> int:
+    try:
+        return max(0, int(value))
+    except ValueError:
+        print(f"Invalid {option}: {value}")
+        sys.exit(1)
+
+
+def _delete(name: str, path: str, *, yes: bool) -> None:
+    from ..services.disk_usage import check_deletable, host_delete_path
+
+    reason = check_deletable(path)
+    if reason:
+        print(f"Not deleting: {reason}")
+        sys.exit(1)
+    if not yes:
+        confirm = prompt_input(f"Delete {path} on {name} recursively? Type the path to confirm: ")
+        if confirm is None or confirm.strip() != path:
+            print("Cancelled.")
+            return
+    try:
+        host_delete_path(name, path, confirm=True)
+    except (KeyError, RuntimeError, ValueError) as exc:
+        print(f"Delete failed: {exc.args[0] if exc.args else exc}")
+        sys.exit(1)
+    print(f"Deleted {path} on {name}.")
+
+
+def cmd_du(args: List[str]) -> None:
+    """Show a size tree for one path on a host, or delete one entry from it."""
+    from ..services.disk_usage import host_disk_usage, render_tree
+
+    if not args or args[0].startswith("-"):
+        print(f"Usage: {USAGE}")
+        sys.exit(1)
+
+    name = args[0]
+    path = "~"
+    depth = 2
+    top = 15
+    refresh = background = as_json = yes = False
+    delete_path = ""
+    rest = args[1:]
+    i = 0
+    while i < len(rest):
+        arg = rest[i]
+        if arg in ("--depth", "--top", "--delete") and i + 1 < len(rest):
+            if arg == "--depth":
+                depth = _parse_int(arg, rest[i + 1])
+            elif arg == "--top":
+                top = _parse_int(arg, rest[i + 1])
+            else:
+                delete_path = rest[i + 1]
+            i += 2
+            continue
+        if arg == "--refresh":
+            refresh = True
+        elif arg == "--background":
+            background = True
+        elif arg == "--json":
+            as_json = True
+        elif arg in ("-y", "--yes"):
+            yes = True
+        elif not arg.startswith("-") and path == "~":
+            path = arg
+        else:
+            print(f"Unknown option: {arg}")
+            print(f"Usage: {USAGE}")
+            sys.exit(1)
+        i += 1
+
+    if delete_path:
+        _delete(name, delete_path, yes=yes)
+        return
+
+    if not as_json:
+        print(f"Scanning {path} on {name} (depth {depth})...")
+    try:
+        result = host_disk_usage(name, path, depth, refresh=refresh, background=background)
+    except (KeyError, RuntimeError) as exc:
+        print(f"Disk usage scan failed: {exc.args[0] if exc.args else exc}")
+        sys.exit(1)
+
+    if as_json:
+        print(json.dumps(result, indent=2))
+        return
+    if result["status"] == "running":
+        print("Scan is running in the background on the host; run the same command again to collect it.")
+        return
+    for line in render_tree(result["tree"], top=top, max_depth=depth):
+        print(line)
+    if result.get("cached"):
+        print(f"(cached scan from {result['scanned_at']}; --refresh to rescan)")
+
+
+__all__ = ["USAGE", "cmd_du"]
@@ -0,0 +1,162 @@
+"""`train host files`: browse a host over SFTP, measure paths, and quick-edit files."""
+
+from __future__ import annotations
+
+import sys
+from typing import List
+
+
+def _print_path_stats(host, path: str) -> None:
+    from ..services.path_stats import host_path_stats
+    from ..services.transfer_progress import format_bytes
+
+    print(f"Measuring {path}...")
+    try:
+        stats = host_path_stats(host, path, top=5)
+    except (FileNotFoundError, RuntimeError) as exc:
+        print(f"Could not measure: {exc}")
+        return
+    partial = "" if stats["complete"] else " (partial: scan timed out)"
+    print(f"{format_bytes(stats['size'])} in {stats['files']:,} files{partial}")
+    for entry in stats["largest"]:
+        print(f"  {format_bytes(entry['size']):>10}  {entry['name']}")
+
+
+def cmd_browse(args: List[str]) -> None:
+    """Browse files on a remote host."""
+    from . import host as host_cmd
+
+    if not args:
+        print("Usage: train host files <name> [path]")
+        sys.exit(1)
+
+    name = args[0]
+    initial_path = args[1] if len(args) > 1 else "~"
+
+    hosts = host_cmd.load_hosts()
+
+    if name not in hosts:
+        print(f"Host not found: {name}")
+        sys.exit(1)
+
+    host = hosts[name]
+    print(f"Connecting to {host.display_name}...")
+
+    from ..services.sftp_browser import RemoteFileBrowser
+    from ..services.host_exec import client_for
+
+    try:
+        ssh = client_for(host)
+    except Exception as exc:
+        print(f"Connection setup failed: {exc}")
+        sys.exit(1)
+
+    if not ssh.test_connection():
+        print("Connection failed.")
+        sys.exit(1)
+
+    browser = RemoteFileBrowser(ssh)
+
+    print(f"\nFile Browser: {host.display_name}")
+    print("Commands: Enter=open  ..=up  q=quit  /=search  h=toggle hidden  s [N]=size")
+    print("-" * 60)
+
+    current_path = initial_path
+    search_query = ""
+    show_hidden = True
+
+    while True:
+        entries = browser.navigate(current_path)
+
+        if not show_hidden:
+            entries = [e for e in entries if not e.name.startswith(".")]
+        if search_query:
+            entries = [e for e in entries if search_query.lower() in e.name.lower()]
+
+        print(f"\n{current_path}")
+        print("-" * 40)
+
+        if not entries:
+            print("  (empty)")
+        else:
+            for i, entry in enumerate(entries):
+                icon = entry.icon
+                size = entry.display_size
+                print(f"  {i:3}. {icon} {entry.name:<30} {size:>10}")
+
+        print("-" * 40)
+
+        try:
+            cmd = input("> ").strip()
+        except (EOFError, KeyboardInterrupt):
+            print("\nExiting.")
+            break
+
+        if not cmd:
+            continue
+        elif cmd == "q":
+            break
+        elif cmd == "..":
+            if current_path not in ("/", "~"):
+                current_path = "/".join(current_path.rstrip("/").split("/")[:-1]) or "/"
+        elif cmd == "~":
+            current_path = browser.get_home_directory()
+        elif cmd == "h":
+            show_hidden = not show_hidden
+            print(f"Hidden files: {'shown' if show_hidden else 'hidden'}")
+        elif cmd == "s" or cmd.startswith("s "):
+            target = cmd[2:].strip()
+            if target.isdigit() and int(target) < len(entries):
+                _print_path_stats(host, entries[int(target)].path)
+            elif not target:
+                _print_path_stats(host, current_path)
+            else:
+                print(f"Invalid index: {target}")
+        elif cmd.startswith("/"):
+            search_query = cmd[1:]
+            print(f"Search: {search_query}" if search_query else "Search cleared")
+        elif cmd.isdigit():
+            idx = int(cmd)
+            if 0 <= idx < len(entries):
+                entry = entries[idx]
+                if entry.is_dir:
+                    current_path = entry.path
+                else:
+                    print(f"\nFile: {entry.path}")
+                    print(f"Size: {entry.display_size}")
+                    print(f"Permissions: {entry.permissions}")
+                    if getattr(entry, "link_target", ""):
+                        print(f"Link target: {entry.link_target}")
+                    if getattr(entry, "modified", None):
+                        print(f"Modified: {entry.modified:%Y-%m-%d %H:%M}")
+
+                    action = input("Action: (c)opy path, (v)iew head, (e)dit, (b)ack: ").strip().lower()
+                    if action == "c":
+                        print(f"Path: {entry.path}")
+                        try:
+                            import subprocess
+
+                            subprocess.run(["pbcopy"], input=entry.path.encode(), check=True)
+                            print("Copied to clipboard!")
+                        except Exception:
+                            pass
+                    elif action == "v":
+                        content = browser.read_file_head(entry.path, lines=30)
+                        print("-" * 40)
+                        print(content)
+                        print("-" * 40)
+                    elif action == "e":
+                        from .file_edit import edit_file
+
+                        edit_file(host, entry.path)
+            else:
+                print(f"Invalid index: {idx}")
+        elif cmd.startswith("cd "):
+            new_path = cmd[3:].strip()
+            if new_path:
+                if browser.path_exists(new_path):
+                    current_path = new_path
+                else:
+                    print(f"Path not found: {new_path}")
+        else:
+            print("Unknown command. Use: q, .., ~, h, /, or number to select")
@@ -0,0 +1,288 @@
+"""`train host follow`: follow log files on hosts in the background."""
+
+from __future__ import annotations
+
+import os
+import sys
+from typing import List, Tuple
+
+USAGE = (
+    "train host follow start <name> <path> [-n LINES] [--job JOB_ID] [--no-alerts] [--foreground]\n"
+    "train host follow list [name]\n"
+    "train host follow show <id> [-n LINES]\n"
+    "train host follow query <id> [--where EXPR] [--steps START:END] [-n LINES] [--json] [--no-color]\n"
+    "train host follow merge <id|name>... [--job JOB_ID] [--page N] [--page-size N] [--live] [--json]\n"
+    "train host follow stop <id>... | --all [name]\n"
+    "train host follow alert [list] | remove <rule>\n"
+    "train host follow alert add <rule> (--pattern REGEX | --keyword TEXT) [--action notify|command|cancel]\n"
+    "    [--command CMD] [--cooldown SECS] [--level LEVEL] [--host NAME]..."
+)
+_ALERT_OPTIONS = ("--pattern", "--keyword", "--action", "--command", "--cooldown", "--level", "--host")
+
+
+def _usage() -> None:
+    print(f"Usage: {USAGE}")
+    sys.exit(1)
+
+
+def _split_lines_option(args: List[str], default: int) -> Tuple[List[str], int, List[str]]:
+    """(positional, -n value, flags) for one follow subcommand."""
+    positional: List[str] = []
+    flags: List[str] = []
+    lines = default
+    i = 0
+    while i < len(args):
+        arg = args[i]
+        if arg in ("-n", "--lines"):
+            if i + 1 >= len(args) or not args[i + 1].isdigit():
+                _usage()
+            lines = int(args[i + 1])
+            i += 2
+            continue
+        if arg.startswith("-"):
+            flags.append(arg)
+        else:
+            positional.append(arg)
+        i += 1
+    return positional, lines, flags
+
+
+def _find(follow_id: str):
+    from ..services.log_follow import list_follows
+
+    matches = [record for record in list_follows() if record.follow_id.startswith(follow_id)]
+    if len(matches) != 1:
+        print(f"No follow matches: {follow_id}" if not matches else f"Follow id {follow_id} is ambiguous.")
+        sys.exit(1)
+    return matches[0]
+
+
+def _start(args: List[str]) -> None:
+    from ..core.executor_utils import _configured_host_for_spec
+    from ..services.log_follow import LogFollower, alert_evaluator, new_follow, save_follow, start_follow
+
+    job_id = ""
+    if "--job" in args:
+        index = args.index("--job")
+        if index + 1 >= len(args):
+            _usage()
+        job_id = args[index + 1]
+        args = args[:index] + args[index + 2:]
+    positional, lines, flags = _split_lines_option(args, 10)
+    if len(positional) != 2 or set(flags) - {"--foreground", "--no-alerts"}:
+        _usage()
+    alerts = "--no-alerts" not in flags
+    name, path = positional
+    if name != "local" and _configured_host_for_spec(name) is None:
+        print(f"Host not found: {name}")
+        sys.exit(1)
+    if "--foreground" in flags:
+        record = new_follow(name, path, pid=os.getpid(), job_id=job_id)
+        code = 130
+        try:
+            code = LogFollower(record, echo=print, alerts=alert_evaluator(record) if alerts else None).run(lines)
+        except KeyboardInterrupt:
+            pass
+        finally:
+            record.pid = 0
+            save_follow(record)
+        sys.exit(code)
+    record = start_follow(name, path, lines=lines, job_id=job_id, alerts=alerts)
+    print(f"Following {name}:{path} as {record.follow_id}")
+    print(f"  Lines: {record.log_path}")
+    print(f"  Stop with: train host follow stop {record.follow_id}")
+
+
+def _run(args: List[str]) -> None:
+    """Body of a background follower started by `start`."""
+    from ..services.log_follow import LogFollower, alert_evaluator, load_follow
+
+    positional, lines, flags = _split_lines_option(args, 10)
+    record = load_follow(positional[0]) if len(positional) == 1 else None
+    if record is None:
+        sys.exit(1)
+    # The starter may not have saved the pid yet; this process leads its own group.
+    record.pid = os.getpid()
+    alerts = None if "--no-alerts" in flags else alert_evaluator(record)
+    sys.exit(LogFollower(record, alerts=alerts).run(lines))
+
+
+def _list(args: List[str]) -> None:
+    from ..services.log_follow import is_running, list_follows
+
+    records = list_follows(args[0] if args else "")
+    if not records:
+        print("No log follows. Start one with `train host follow start <name> <path>`.")
+        return
+    print(f"{'ID':<10} {'Host':<16} {'State':<8} {'Lines':>8} {'Rotated':>7} {'Alerts':>6}  Path")
+    for record in records:
+        state = "running" if is_running(record) else "stopped"
+        print(
+            f"{record.follow_id:<10} {record.host:<16} {state:<8} {record.lines:>8} {record.rotations:>7} {record.alerts:>6}  {record.path}"
+        )
+
+
+def _show(args: List[str]) -> None:
+    from ..services.terminal_log import read_terminal_log
+
+    positional, lines, _flags = _split_lines_option(args, 0)
+    if len(positional) != 1:
+        _usage()
+    record = _find(positional[0])
+    if not record.log_path.exists():
+        print(f"No lines received yet for {record.follow_id}.")
+        return
+    sys.stdout.write(read_terminal_log(record.log_path, lines=lines, raw=True))
+
+
+def _query(args: List[str]) -> None:
+    import json
+
+    from ..services.log_query import colorize, log_query, parse_step_range
+
+    options = {"--where": "", "--steps": ""}
+    rest: List[str] = []
+    i = 0
+    while i < len(args):
+        if args[i] in options and i + 1 < len(args):
+            options[args[i]] = args[i + 1]
+            i += 2
+            continue
+        rest.append(args[i])
+        i += 1
+    positional, lines, flags = _split_lines_option(rest, 0)
+    if len(positional) != 1 or set(flags) - {"--json", "--no-color"}:
+        _usage()
+    record = _find(positional[0])
+    try:
+        step_range = parse_step_range(options["--steps"]) if options["--steps"] else (None, None)
+        entries = log_query(record.follow_id, options["--where"], step_range, limit=lines)
+    except ValueError as exc:
+        print(exc)
+        sys.exit(1)
+    if "--json" in flags:
+        for entry in entries:
+            print(json.dumps(entry.to_dict(), ensure_ascii=False))
+        return
+    color = "--no-color" not in flags and sys.stdout.isatty()
+    for entry in entries:
+        print(colorize(entry.text, entry.color) if color else entry.text)
+
+
+def _merge(args: List[str]) -> None:
+    import json
+
+    from ..services.log_aggregate import MergedStream, read_page, resolve_sources
+
+    options = {"--job": "", "--page": "0", "--page-size": "100"}
+    selectors: List[str] = []
+    flags: List[str] = []
+    i = 0
+    while i < len(args):
+        arg = args[i]
+        if arg in options and i + 1 < len(args):
+            options[arg] = args[i + 1]
+            i += 2
+            continue
+        if arg.startswith("-"):
+            flags.append(arg)
+        else:
+            selectors.append(arg)
+        i += 1
+    if set(flags) - {"--live", "--json"} or not (selectors or options["--job"]):
+        _usage()
+    if not (options["--page"].isdigit() and options["--page-size"].isdigit()):
+        _usage()
+    sources = resolve_sources(selectors, job_id=options["--job"])
+    if not sources:
+        print("No follows match; see `train host follow list`.")
+        sys.exit(1)
+
+    def emit(line) -> None:
+        print(json.dumps(line.to_dict(), ensure_ascii=False) if "--json" in flags else line.format(), flush=True)
+
+    if "--live" in flags:
+        try:
+            for line in MergedStream(sources):
+                emit(line)
+        except KeyboardInterrupt:
+            return
+        return
+    lines, page, pages = read_page(sources, int(options["--page"]), int(options["--page-size"]))
+    for line in lines:
+        emit(line)
+    if "--json" not in flags:
+        print(f"-- page {page}/{pages} of {len(sources)} follow(s)")
+
+
+def _stop(args: List[str]) -> None:
+    from ..services.log_follow import list_follows, remove_follow
+
+    if args and args[0] == "--all":
+        if len(args) > 2:
+            _usage()
+        records = list_follows(args[1] if len(args) == 2 else "")
+    elif args:
+        records = [_find(follow_id) for follow_id in args]
+    else:
+        _usage()
+    for record in records:
+        remove_follow(record)
+        print(f"Stopped {record.follow_id} ({record.host}:{record.path})")
+
+
+def _alert(args: List[str]) -> None:
+    from ..services.log_alerts import AlertRule, configured_rules, remove_rule, rule_problems, save_rule
+
+    action = args[0] if args else "list"
+    rest = args[1:]
+    if action == "list" and not rest:
+        rules = configured_rules()
+        if not rules:
+            print("No alert rules. Add one with `train host follow alert add <rule> --pattern REGEX`.")
+        for rule in rules:
+            scope = ", ".join(rule.hosts) or "all hosts"
+            target = f"{rule.action} `{rule.command}`" if rule.action == "command" else rule.action
+            print(f"  {rule.name}: /{rule.pattern}/ -> {target} (cooldown {rule.cooldown_secs:g}s, {scope})")
+        for problem in rule_problems():
+            print(f"  skipped: {problem}")
+        return
+    if action == "remove" and len(rest) == 1:
+        if not remove_rule(rest[0]):
+            print(f"Alert rule not found: {rest[0]}")
+            sys.exit(1)
+        print(f"Alert rule removed: {rest[0]}")
+        return
+    if action != "add" or not rest:
+        _usage()
+    data: dict = {"hosts": []}
+    i = 1
+    while i < len(rest):
+        option = rest[i]
+        if option not in _ALERT_OPTIONS or i + 1 >= len(rest):
+            _usage()
+        if option == "--host":
+            data["hosts"].append(rest[i + 1])
+        else:
+            data[option[2:].replace("cooldown", "cooldown_secs")] = rest[i + 1]
+        i += 2
+    try:
+        rule = AlertRule.from_dict(rest[0], data)
+        save_rule(rule)
+    except ValueError as exc:
+        print(exc)
+        sys.exit(1)
+    print(f"Alert rule {rule.name}: /{rule.pattern}/ -> {rule.action}")
+    print("  Running follows pick it up when restarted.")
+
+
+def cmd_follow(args: List[str]) -> None:
+    """Start, list, show or stop log follows."""
+    action = args[0] if args else "list"
+    handlers = {"start": _start, "run": _run, "list": _list, "show": _show, "query": _query, "merge": _merge, "stop": _stop, "alert": _alert}
+    if action not in handlers:
+        _usage()
+    handlers[action](args[1:])
+
+
+__all__ = ["USAGE", "cmd_follow"]
@@ -0,0 +1,131 @@
+"""`train host manifest|verify`: sha256 manifests of remote directories and local copy checks."""
+
+from __future__ import annotations
+
+import json
+import sys
+from typing import List
+
+MANIFEST_USAGE = "train host manifest <name> <remote_dir> [--output FILE] [--json]"
+VERIFY_USAGE = (
+    "train host verify <name> <remote_dir> <local_dir> [--save FILE] [--json]\n"
+    "train host verify --manifest FILE <local_dir> [--json]"
+)
+
+
+def _split_options(args: List[str], valued: tuple, flags: tuple, usage: str):
+    positional: List[str] = []
+    options = {}
+    i = 0
+    while i < len(args):
+        arg = args[i]
+        if arg in valued and i + 1 < len(args):
+            options[arg] = args[i + 1]
+            i += 2
+            continue
+        if arg in flags:
+            options[arg] = True
+        elif arg.startswith("-"):
+            print(f"Unknown option: {arg}")
+            print(f"Usage: {usage}")
+            sys.exit(1)
+        else:
+            positional.append(arg)
+        i += 1
+    return positional, options
+
+
+def _host_manifest(name: str, remote_dir: str):
+    from ..services.integrity import build_host_manifest
+    from .host import load_hosts
+
+    host = load_hosts().get(name)
+    if host is None:
+        print(f"Host not found: {name}")
+        sys.exit(1)
+    try:
+        return build_host_manifest(host, remote_dir)
+    except RuntimeError as exc:
+        print(f"Manifest failed: {exc}")
+        sys.exit(1)
+
+
+def cmd_manifest(args: List[str]) -> None:
+    """Hash every file under a remote directory and print or save the manifest."""
+    from ..services.integrity import write_manifest
+    from ..services.transfer_progress import format_bytes
+
+    positional, options = _split_options(args, ("--output", "-o"), ("--json",), MANIFEST_USAGE)
+    if len(positional) != 2:
+        print(f"Usage: {MANIFEST_USAGE}")
+        sys.exit(1)
+    name, remote_dir = positional
+    output = options.get("--output") or options.get("-o")
+    if not options.get("--json"):
+        print(f"Hashing {remote_dir} on {name}...")
+    manifest = _host_manifest(name, remote_dir)
+
+    if output:
+        write_manifest(manifest, output)
+    if options.get("--json"):
+        print(json.dumps(manifest, indent=2))
+        return
+    if not output:
+        for entry in manifest["files"]:
+            print(f"{entry['sha256']}  {entry['path']}")
+    print(f"{manifest['file_count']} files, {format_bytes(manifest['total_bytes'])}.")
+    if output:
+        print(f"Manifest written to {output}")
+
+
+def cmd_verify(args: List[str]) -> None:
+    """Verify a local copy against a remote directory or a saved manifest; exits 1 on problems."""
+    from ..services.integrity import load_manifest, verify_manifest, write_manifest
+
+    positional, options = _split_options(args, ("--manifest", "--save"), ("--json",), VERIFY_USAGE)
+    as_json = bool(options.get("--json"))
+    manifest_path = options.get("--manifest")
+    if manifest_path:
+        if len(positional) != 1:
+            print(f"Usage: {VERIFY_USAGE}")
+            sys.exit(1)
+        local_dir = positional[0]
+        try:
+            manifest = load_manifest(manifest_path)
+        except (OSError, ValueError) as exc:
+            print(f"Cannot read manifest: {exc}")
+            sys.exit(1)
+    else:
+        if len(positional) != 3:
+            print(f"Usage: {VERIFY_USAGE}")
+            sys.exit(1)
+        name, remote_dir, local_dir = positional
+        if not as_json:
+            print(f"Hashing {remote_dir} on {name}...")
+        manifest = _host_manifest(name, remote_dir)
+        if options.get("--save"):
+            write_manifest(manifest, options["--save"])
+
+    if not as_json:
+        print(f"Verifying {local_dir} against {manifest.get('source', manifest_path)}...")
+    report = verify_manifest(manifest, local_dir)
+    if as_json:
+        print(json.dumps(report, indent=2))
+    else:
+        for entry in report["missing"]:
+            print(f"  MISSING    {entry['path']}")
+        for entry in report["corrupted"]:
+            detail = f"size {entry['local_size']} != {entry['size']}" if entry["reason"] == "size" else "sha256 mismatch"
+            print(f"  CORRUPTED  {entry['path']} ({detail})")
+        for name in report["extra"]:
+            print(f"  EXTRA      {name}")
+        print(
+            f"{report['verified']}/{report['checked']} files verified, "
+            f"{len(report['missing'])} missing, {len(report['corrupted'])} corrupted, {len(report['extra'])} extra."
+        )
+        print("OK: local copy matches." if report["ok"] else "FAILED: local copy does not match.")
+    if not report["ok"]:
+        sys.exit(1)
+
+
+__all__ = ["MANIFEST_USAGE", "VERIFY_USAGE", "cmd_manifest", "cmd_verify"]
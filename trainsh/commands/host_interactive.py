@@ -25,6 +25,12 @@ def _store_private_key_secret(secret_name: str, source_path: str) -> None:
     store_secret_file(secret_name, source_path)
 
 
+def _default_ssh_key() -> str:
+    from ..config import get_config_value
+
+    return str(get_config_value("defaults.ssh_key_path", "~/.ssh/id_rsa") or "~/.ssh/id_rsa")
+
+
 def _password_secret_name(host_name: str) -> str:
     from ..services.secret_materialize import suggest_secret_name
 
@@ -284,7 +290,7 @@ def cmd_add(args: List[str]) -> None:
         env_vars = {}
         ssh_key_path = None
         if auth_method == AuthMethod.KEY:
-            default_key = "~/.ssh/id_rsa"
+            default_key = _default_ssh_key()
             ssh_key_path = host_cmd.prompt_input(
                 f"SSH key path [{default_key}] (or type 'secret' to import into secrets): ",
                 default=default_key,
@@ -416,6 +422,9 @@ def cmd_add(args: List[str]) -> None:
         print("Use 'train host ssh' to connect.")
     else:
         print(f"SSH command: ssh -p {host.port} {host.username}@{host.hostname}")
+        from ..services.bootstrap_profile import ensure_bootstrapped
+
+        ensure_bootstrapped(host)
 
 
 def cmd_edit(args: List[str]) -> None:
@@ -490,7 +499,7 @@ def cmd_edit(args: List[str]) -> None:
 
         env_vars = dict(host.env_vars or {})
         if auth_method == AuthMethod.KEY:
-            key_default = host.ssh_key_path or ("secret" if host.env_vars.get("ssh_key_secret") else "~/.ssh/id_rsa")
+            key_default = host.ssh_key_path or ("secret" if host.env_vars.get("ssh_key_secret") else _default_ssh_key())
             ssh_key_path = host_cmd.prompt_input(
                 f"SSH key path [{key_default}] (or type 'secret' to import into secrets): ",
                 default=key_default,
@@ -755,126 +764,3 @@ def cmd_edit(args: List[str]) -> None:
     hosts[new_name] = host
     host_cmd.save_hosts(hosts)
     print(f"Updated host: {new_name}")
-
-
-def cmd_browse(args: List[str]) -> None:
-    """Browse files on a remote host."""
-    host_cmd = _host_module()
-    if not args:
-        print("Usage: train host files <name> [path]")
-        sys.exit(1)
-
-    name = args[0]
-    initial_path = args[1] if len(args) > 1 else "~"
-
-    hosts = host_cmd.load_hosts()
-
-    if name not in hosts:
-        print(f"Host not found: {name}")
-        sys.exit(1)
-
-    host = hosts[name]
-    print(f"Connecting to {host.display_name}...")
-
-    from ..services.sftp_browser import RemoteFileBrowser
-    from ..services.ssh import SSHClient
-
-    try:
-        ssh = SSHClient.from_host(host)
-    except Exception as exc:
-        print(f"Connection setup failed: {exc}")
-        sys.exit(1)
-
-    if not ssh.test_connection():
-        print("Connection failed.")
-        sys.exit(1)
-
-    browser = RemoteFileBrowser(ssh)
-
-    print(f"\nFile Browser: {host.display_name}")
-    print("Commands: Enter=open  ..=up  q=quit  /=search  h=toggle hidden")
-    print("-" * 60)
-
-    current_path = initial_path
-    search_query = ""
-    show_hidden = True
-
-    while True:
-        entries = browser.navigate(current_path)
-
-        if not show_hidden:
-            entries = [e for e in entries if not e.name.startswith(".")]
-        if search_query:
-            entries = [e for e in entries if search_query.lower() in e.name.lower()]
-
-        print(f"\n{current_path}")
-        print("-" * 40)
-
-        if not entries:
-            print("  (empty)")
-        else:
-            for i, entry in enumerate(entries):
-                icon = entry.icon
-                size = entry.display_size
-                print(f"  {i:3}. {icon} {entry.name:<30} {size:>10}")
-
-        print("-" * 40)
-
-        try:
-            cmd = input("> ").strip()
-        except (EOFError, KeyboardInterrupt):
-            print("\nExiting.")
-            break
-
-        if not cmd:
-            continue
-        elif cmd == "q":
-            break
-        elif cmd == "..":
-            if current_path not in ("/", "~"):
-                current_path = "/".join(current_path.rstrip("/").split("/")[:-1]) or "/"
-        elif cmd == "~":
-            current_path = browser.get_home_directory()
-        elif cmd == "h":
-            show_hidden = not show_hidden
-            print(f"Hidden files: {'shown' if show_hidden else 'hidden'}")
-        elif cmd.startswith("/"):
-            search_query = cmd[1:]
-            print(f"Search: {search_query}" if search_query else "Search cleared")
-        elif cmd.isdigit():
-            idx = int(cmd)
-            if 0 <= idx < len(entries):
-                entry = entries[idx]
-                if entry.is_dir:
-                    current_path = entry.path
-                else:
-                    print(f"\nFile: {entry.path}")
-                    print(f"Size: {entry.display_size}")
-                    print(f"Permissions: {entry.permissions}")
-
-                    action = input("Action: (c)opy path, (v)iew head, (b)ack: ").strip().lower()
-                    if action == "c":
-                        print(f"Path: {entry.path}")
-                        try:
-                            import subprocess
-
-                            subprocess.run(["pbcopy"], input=entry.path.encode(), check=True)
-                            print("Copied to clipboard!")
-                        except Exception:
-                            pass
-                    elif action == "v":
-                        content = browser.read_file_head(entry.path, lines=30)
-                        print("-" * 40)
-                        print(content)
-                        print("-" * 40)
-            else:
-                print(f"Invalid index: {idx}")
-        elif cmd.startswith("cd "):
-            new_path = cmd[3:].strip()
-            if new_path:
-                if browser.path_exists(new_path):
-                    current_path = new_path
-                else:
-                    print(f"Path not found: {new_path}")
-        else:
-            print("Unknown command. Use: q, .., ~, h, /, or number to select")
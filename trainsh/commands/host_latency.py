@@ -0,0 +1,101 @@
+"""`train host latency`: time each SSH route of a host and show its history."""
+
+from __future__ import annotations
+
+import json
+import sys
+import time
+from typing import Any, Dict, List
+
+LATENCY_USAGE = "train host latency <name> [--count N] [--interval SECS] [--history] [--json]"
+
+
+def _ms(value: Any) -> str:
+    return "-" if value is None else f"{value:.0f}ms"
+
+
+def format_sample(sample: Dict[str, Any]) -> str:
+    route = f"{sample.get('route', '')} {sample.get('endpoint', '')}" + (" (proxied)" if sample.get("proxied") else "")
+    prefix = f"  {str(sample.get('ts', ''))[11:19]}  {route:<40}"
+    if not sample.get("ok"):
+        return f"{prefix} failed: {sample.get('error', '')}"
+    return f"{prefix} connect {_ms(sample.get('connect_ms'))}  banner {_ms(sample.get('banner_ms'))}  ssh {_ms(sample.get('handshake_ms'))}"
+
+
+def print_route_stats(stats: List[Dict[str, Any]]) -> None:
+    print("Routes (median ssh handshake of recent samples):")
+    for item in stats:
+        route = f"{item['route']} {item['endpoint']}" + (" (proxied)" if item["proxied"] else "")
+        status = "fastest" if item["fastest"] else ("ok" if item["last_ok"] else "failing")
+        print(f"  {route:<40} {_ms(item['median_ms']):>7}  {int(item['success_rate'] * 100):>3}% of {item['samples']}  {status}")
+
+
+def _parse_args(args: List[str]) -> tuple[str, int, float, bool, bool]:
+    name = ""
+    count = 1
+    interval = 5.0
+    history = False
+    as_json = False
+    index = 0
+    while index < len(args):
+        option = args[index]
+        index += 1
+        if option in ("--count", "--interval") and index < len(args):
+            value = args[index]
+            index += 1
+            try:
+                if option == "--count":
+                    count = max(1, int(value))
+                else:
+                    interval = max(0.0, float(value))
+            except ValueError:
+                print(f"Invalid {option}: {value}")
+                sys.exit(1)
+        elif option == "--history":
+            history = True
+        elif option == "--json":
+            as_json = True
+        elif option.startswith("-") or name:
+            print(f"Usage: {LATENCY_USAGE}")
+            sys.exit(1)
+        else:
+            name = option
+    if not name:
+        print(f"Usage: {LATENCY_USAGE}")
+        sys.exit(1)
+    return name, count, interval, history, as_json
+
+
+def cmd_latency(args: List[str]) -> None:
+    """Measure each route of a host (or print its stored history) and rank the routes."""
+    from ..services.host_latency import host_latency_history, measure_host, route_stats
+    from .host import load_hosts
+
+    name, count, interval, history, as_json = _parse_args(args)
+    if history:
+        samples = host_latency_history(name)
+        if as_json:
+            print(json.dumps({"host": name, "samples": samples, "routes": route_stats(samples)}, ensure_ascii=False, indent=2))
+            return
+        if not samples:
+            print(f"No latency history for {name}. Run 'train host latency {name}' to measure.")
+            return
+        for sample in samples[-20:]:
+            print(format_sample(sample))
+        print_route_stats(route_stats(samples))
+        return
+
+    hosts = load_hosts()
+    if name not in hosts:
+        print(f"Host not found: {name}")
+        sys.exit(1)
+    for round_index in range(count):
+        if round_index:
+            time.sleep(interval)
+        for sample in measure_host(name, hosts[name]):
+            print(json.dumps({"host": name, **sample}, ensure_ascii=False) if as_json else format_sample(sample), flush=True)
+    if not as_json:
+        print_route_stats(route_stats(host_latency_history(name)))
+
+
+__all__ = ["cmd_latency", "format_sample", "print_route_stats"]
@@ -0,0 +1,59 @@
+"""`train host logs`: read interactive sessions mirrored by `train host ssh --log`."""
+
+from __future__ import annotations
+
+import sys
+from datetime import datetime
+from typing import List
+
+USAGE = "train host logs [name] [-n LINES] [--raw] [--path]"
+
+
+def cmd_logs(args: List[str]) -> None:
+    """List terminal logs, or print one (escape sequences stripped unless --raw)."""
+    from ..services.terminal_log import list_terminal_logs, read_terminal_log, terminal_log_path
+
+    lines = 0
+    flags = {"--raw": False, "--path": False}
+    positional: List[str] = []
+    i = 0
+    while i < len(args):
+        arg = args[i]
+        if arg in ("-n", "--lines"):
+            if i + 1 >= len(args) or not args[i + 1].isdigit():
+                print(f"Usage: {USAGE}")
+                sys.exit(1)
+            lines = int(args[i + 1])
+            i += 2
+            continue
+        if arg in flags:
+            flags[arg] = True
+        elif arg.startswith("-") or positional:
+            print(f"Usage: {USAGE}")
+            sys.exit(1)
+        else:
+            positional.append(arg)
+        i += 1
+
+    if not positional:
+        entries = list_terminal_logs()
+        if not entries:
+            print("No terminal logs yet. Mirror a session with `train host ssh <name> --log`.")
+            return
+        print(f"{'Name':<28} {'Size':>10} {'Rotated':>7}  Updated")
+        for entry in entries:
+            updated = datetime.fromtimestamp(entry["modified"]).strftime("%Y-%m-%d %H:%M")
+            print(f"{entry['name']:<28} {entry['bytes']:>10} {entry['rotated']:>7}  {updated}")
+        return
+
+    path = terminal_log_path(positional[0])
+    if not path.exists():
+        print(f"No terminal log for {positional[0]}: {path}")
+        sys.exit(1)
+    if flags["--path"]:
+        print(path)
+        return
+    sys.stdout.write(read_terminal_log(path, lines=lines, raw=flags["--raw"]))
+
+
+__all__ = ["USAGE", "cmd_logs"]
@@ -0,0 +1,117 @@
+"""`train host metrics`: stream CPU, memory, disk IO, network and GPU samples."""
+
+from __future__ import annotations
+
+import json
+import sys
+import threading
+from typing import Any, Dict, List, Optional
+
+METRICS_USAGE = "train host metrics <name> [<name>...] [--interval SECS] [--count N] [--json]"
+
+
+def _rate(value: Optional[float]) -> str:
+    from ..services.transfer_progress import format_bytes
+
+    return "-" if value is None else f"{format_bytes(int(value))}/s"
+
+
+def format_sample(event: Dict[str, Any]) -> str:
+    """One human-readable line for a `host:metrics` event."""
+    from ..services.transfer_progress import format_bytes
+
+    prefix = f"  {str(event.get('ts', ''))[11:19]}  {event.get('host', ''):<14}"
+    if event.get("error"):
+        return f"{prefix} error: {event['error']}"
+    cpu = event.get("cpu_percent")
+    parts = [
+        f"cpu {'-' if cpu is None else f'{cpu:5.1f}%'}",
+        f"mem {format_bytes(event.get('mem_used', 0))}/{format_bytes(event.get('mem_total', 0))}",
+    ]
+    if event.get("swap_total"):
+        parts.append(f"swap {format_bytes(event.get('swap_used', 0))}")
+    parts.append(f"disk r {_rate(event.get('disk_read_bps'))} w {_rate(event.get('disk_write_bps'))}")
+    parts.append(f"net rx {_rate(event.get('net_rx_bps'))} tx {_rate(event.get('net_tx_bps'))}")
+    for gpu in event.get("gpus") or []:
+        util = gpu.get("utilization")
+        parts.append(
+            f"gpu{gpu.get('index')} {'-' if util is None else f'{util:.0f}%'} "
+            f"{(gpu.get('memory_used_mb') or 0) / 1024:.1f}/{(gpu.get('memory_total_mb') or 0) / 1024:.1f}G"
+        )
+    return f"{prefix} " + "  ".join(parts)
+
+
+def _parse_args(args: List[str]) -> tuple[List[str], Optional[float], int, bool]:
+    names: List[str] = []
+    interval: Optional[float] = None
+    count = 0
+    as_json = False
+    index = 0
+    while index < len(args):
+        option = args[index]
+        index += 1
+        if option in ("--interval", "--count") and index < len(args):
+            value = args[index]
+            index += 1
+            try:
+                if option == "--interval":
+                    interval = max(1.0, float(value))
+                else:
+                    count = max(1, int(value))
+            except ValueError:
+                print(f"Invalid {option}: {value}")
+                sys.exit(1)
+        elif option == "--json":
+            as_json = True
+        elif option.startswith("-"):
+            print(f"Usage: {METRICS_USAGE}")
+            sys.exit(1)
+        else:
+            names.append(option)
+    if not names:
+        print(f"Usage: {METRICS_USAGE}")
+        sys.exit(1)
+    return names, interval, count, as_json
+
+
+def cmd_metrics(args: List[str]) -> None:
+    """Sample each host on an interval until Ctrl+C or `--count` samples per host."""
+    from ..services.host_metrics import HostMetricsSampler, add_listener, remove_listener
+    from .host import load_hosts
+
+    names, interval, count, as_json = _parse_args(args)
+    hosts = load_hosts()
+    missing = [name for name in names if name not in hosts]
+    if missing:
+        print(f"Host not found: {', '.join(missing)}")
+        sys.exit(1)
+
+    seen: Dict[str, int] = {name: 0 for name in names}
+    done = threading.Event()
+    print_lock = threading.Lock()
+
+    def on_sample(event: Dict[str, Any]) -> None:
+        if event.get("host") not in seen:
+            return
+        with print_lock:
+            print(json.dumps(event, ensure_ascii=False) if as_json else format_sample(event), flush=True)
+            seen[event["host"]] += 1
+            if count and all(value >= count for value in seen.values()):
+                done.set()
+
+    samplers = [HostMetricsSampler(name, hosts[name], interval=interval) for name in names]
+    add_listener(on_sample)
+    if not as_json:
+        print(f"Sampling {len(samplers)} host(s) every {samplers[0].interval:g}s (Ctrl+C to stop)...")
+    try:
+        for sampler in samplers:
+            sampler.start()
+        while not done.wait(0.5):
+            pass
+    except KeyboardInterrupt:
+        if not as_json:
+            print("\nStopped.")
+    finally:
+        remove_listener(on_sample)
+        for sampler in samplers:
+            sampler.stop()
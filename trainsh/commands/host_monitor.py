@@ -0,0 +1,135 @@
+"""`train host monitor` and `train host queue` connectivity helpers."""
+
+from __future__ import annotations
+
+import sys
+import time
+from typing import List
+
+
+def _parse_interval(args: List[str]) -> tuple[bool, float, bool]:
+    from ..config import get_config_value
+
+    once = False
+    interval = 30.0
+    latency = bool(get_config_value("latency.monitor", False))
+    index = 0
+    while index < len(args):
+        option = args[index]
+        index += 1
+        if option == "--once":
+            once = True
+            continue
+        if option == "--latency":
+            latency = True
+            continue
+        if option == "--interval" and index < len(args):
+            try:
+                interval = max(1.0, float(args[index]))
+            except ValueError:
+                print(f"Invalid interval: {args[index]}")
+                sys.exit(1)
+            index += 1
+            continue
+        print("Usage: train host monitor [--once] [--interval SECS] [--latency]")
+        sys.exit(1)
+    return once, interval, latency
+
+
+def run_monitor_pass(monitor, hosts: dict, *, latency: bool = False) -> bool:
+    """Probe every host once, print transitions, and replay queued ops when online.
+
+    With `latency`, every route of each online host is also timed into its latency history.
+    """
+    from ..services.connectivity import network_available, probe_host
+
+    online_network = network_available()
+    if not online_network:
+        print("Network unavailable; skipping host probes.")
+        for name in sorted(hosts):
+            if monitor.record(name, False, "network unavailable"):
+                print(f"  {name}: offline")
+        return False
+
+    for name in sorted(hosts):
+        online, error = probe_host(hosts[name])
+        if monitor.record(name, online, error):
+            suffix = "" if online else f" ({error})" if error else ""
+            print(f"  {name}: {'online' if online else 'offline'}{suffix}")
+        if online and latency:
+            from ..services.host_latency import measure_host
+
+            try:
+                measure_host(name, hosts[name])
+            except Exception as exc:
+                print(f"  {name}: latency probe failed ({exc})")
+
+    if monitor.list_queue():
+        done, failed = monitor.drain_queue()
+        for entry in done:
+            print(f"  replayed {entry['kind']} {entry['params']}")
+        for entry in failed:
+            print(f"  still queued {entry['kind']}: {entry['last_error']}")
+    return True
+
+
+def cmd_monitor(args: List[str]) -> None:
+    """Watch host connectivity and replay queued operations on reconnect."""
+    from .host import load_hosts
+    from ..services.connectivity import ConnectivityMonitor
+
+    once, interval, latency = _parse_interval(args)
+    monitor = ConnectivityMonitor()
+    hosts = load_hosts(include_auto_vast=False)
+    print(f"Monitoring {len(hosts)} host(s)" + ("" if once else f" every {interval:g}s (Ctrl+C to stop)") + "...")
+
+    try:
+        while True:
+            run_monitor_pass(monitor, hosts, latency=latency)
+            if once:
+                break
+            time.sleep(interval)
+    except KeyboardInterrupt:
+        print("\nStopped.")
+
+    states = monitor.host_states()
+    for name in sorted(hosts):
+        state = states.get(name, {})
+        label = "online" if state.get("online") else "offline"
+        print(f"{name:<20} {label:<8} checked {str(state.get('checked_at', ''))[:19]}")
+
+
+def cmd_queue(args: List[str]) -> None:
+    """List, replay, or clear operations queued while offline."""
+    from ..services.connectivity import ConnectivityMonitor
+
+    action = args[0] if args else "list"
+    monitor = ConnectivityMonitor()
+
+    if action == "list":
+        entries = monitor.list_queue()
+        if not entries:
+            print("No queued operations.")
+            return
+        print("Queued operations:")
+        for entry in entries:
+            error = f"  last error: {entry['last_error']}" if entry.get("last_error") else ""
+            print(f"  {entry['id']}  {entry['kind']:<12} {entry['params']}  queued {entry['queued_at'][:19]}{error}")
+        return
+
+    if action == "run":
+        done, failed = monitor.drain_queue()
+        print(f"Replayed {len(done)} operation(s); {len(failed)} still queued.")
+        if failed:
+            sys.exit(1)
+        return
+
+    if action == "clear":
+        print(f"Cleared {monitor.clear_queue()} queued operation(s).")
+        return
+
+    print("Usage: train host queue [list|run|clear]")
+    sys.exit(1)
+
+
+__all__ = ["cmd_monitor", "cmd_queue", "run_monitor_pass"]
@@ -0,0 +1,89 @@
+"""`train host paste`: paste a file or stdin into a tmux session on a host."""
+
+from __future__ import annotations
+
+import dataclasses
+import sys
+from pathlib import Path
+from typing import List
+
+from ..cli_utils import prompt_input
+
+USAGE = "train host paste <host>:<session> [--file PATH] [--enter] [--no-bracket] [--yes]"
+
+
+def cmd_paste(args: List[str]) -> None:
+    """Paste text into a host's tmux session in chunks, bracketed when the program supports it."""
+    from ..services.terminal_broadcast import BroadcastTarget, _default_client
+    from ..services.terminal_paste import paste_policy, paste_text
+
+    path = ""
+    enter = False
+    bracketed = None
+    yes = False
+    positional: List[str] = []
+    i = 0
+    while i < len(args):
+        arg = args[i]
+        if arg in ("--file", "-f") and i + 1 < len(args):
+            path = args[i + 1]
+            i += 2
+            continue
+        if arg == "--enter":
+            enter = True
+        elif arg == "--no-bracket":
+            bracketed = False
+        elif arg in ("-y", "--yes"):
+            yes = True
+        elif arg.startswith("-"):
+            print(f"Usage: {USAGE}")
+            sys.exit(1)
+        else:
+            positional.append(arg)
+        i += 1
+    if len(positional) != 1:
+        print(f"Usage: {USAGE}")
+        sys.exit(1)
+    try:
+        target = BroadcastTarget.parse(positional[0])
+    except ValueError as exc:
+        print(exc)
+        sys.exit(1)
+    if not target.session:
+        print(f"Name the tmux session to paste into: {target.host}:<session>")
+        sys.exit(1)
+
+    if path and path != "-":
+        try:
+            text = Path(path).expanduser().read_text(encoding="utf-8")
+        except OSError as exc:
+            print(f"Cannot read {path}: {exc.strerror or exc}")
+            sys.exit(1)
+    else:
+        text = sys.stdin.read()
+    if not text:
+        print("Nothing to paste.")
+        sys.exit(1)
+
+    policy = paste_policy()
+    if bracketed is not None:
+        policy = dataclasses.replace(policy, bracketed=bracketed)
+
+    def confirm(label: str, data: str) -> bool:
+        if yes:
+            return True
+        answer = prompt_input(f"Paste {data.count(chr(10)) + 1} lines ({len(data.encode('utf-8'))} bytes) into {label}? [y/N]: ")
+        return answer is not None and answer.strip().lower() in ("y", "yes")
+
+    client = _default_client(target.host)
+    if not client.has_session(target.session):
+        print(f"No tmux session {target.session} on {target.host}.")
+        sys.exit(1)
+    result = paste_text(client, target.session, text, enter=enter, policy=policy, confirm=confirm)
+    if not result.ok:
+        print(f"Paste into {target} failed: {result.detail}")
+        sys.exit(1)
+    print(f"Pasted {result.bytes} bytes into {target} in {result.chunks} chunk(s).")
+
+
+__all__ = ["USAGE", "cmd_paste"]
@@ -0,0 +1,147 @@
+"""`train host wake` and `train host power-off`: Wake-on-LAN and SSH power control."""
+
+from __future__ import annotations
+
+import sys
+from typing import Dict, List
+
+from ..cli_utils import prompt_input
+
+WAKE_USAGE = "train host wake <name> [--mac MAC] [--broadcast ADDR] [--port N] [--timeout 5m] [--no-wait]"
+POWER_OFF_USAGE = "train host power-off <name> [--suspend] [--command CMD] [--timeout 2m] [--no-wait] [--yes]"
+
+
+def _parse(args: List[str], flags: tuple, valued: tuple, usage_line: str):
+    positional: List[str] = []
+    options: Dict[str, object] = {}
+    i = 0
+    while i < len(args):
+        arg = args[i]
+        if arg in valued and i + 1 < len(args):
+            options[arg] = args[i + 1]
+            i += 2
+            continue
+        if arg in flags:
+            options[arg] = True
+        elif arg.startswith("-") or positional:
+            print(f"Usage: {usage_line}")
+            sys.exit(1)
+        else:
+            positional.append(arg)
+        i += 1
+    if len(positional) != 1:
+        print(f"Usage: {usage_line}")
+        sys.exit(1)
+    return positional[0], options
+
+
+def _save_env(name: str, values: Dict[str, str]) -> None:
+    """Persist power settings on a stored host definition."""
+    from .host import load_hosts, save_hosts
+
+    configured = load_hosts(include_auto_vast=False)
+    if name not in configured:
+        print(f"Host not found: {name}")
+        sys.exit(1)
+    configured[name].env_vars = {**dict(configured[name].env_vars or {}), **values}
+    save_hosts(configured)
+    for key, value in values.items():
+        print(f"Saved {key}={value} for {name}.")
+
+
+def _timeout(options: Dict[str, object], default: int) -> int:
+    from ..services.vllm_service import parse_duration
+
+    try:
+        return parse_duration(options.get("--timeout"), default=default)
+    except ValueError:
+        print(f"Invalid --timeout: {options['--timeout']}")
+        sys.exit(1)
+
+
+def _progress(label: str):
+    def report(elapsed: float) -> None:
+        print(f"  {label}... {int(elapsed)}s", flush=True)
+
+    return report
+
+
+def cmd_wake(args: List[str]) -> None:
+    """Send a Wake-on-LAN packet and wait until the host answers over SSH."""
+    from ..services.host_power import host_wake, normalize_mac
+
+    name, options = _parse(args, ("--no-wait",), ("--mac", "--broadcast", "--port", "--timeout"), WAKE_USAGE)
+    updates: Dict[str, str] = {}
+    if options.get("--mac"):
+        try:
+            updates["wol_mac"] = normalize_mac(str(options["--mac"]))
+        except ValueError as exc:
+            print(exc)
+            sys.exit(1)
+    if options.get("--broadcast"):
+        updates["wol_broadcast"] = str(options["--broadcast"])
+    if options.get("--port"):
+        if not str(options["--port"]).isdigit():
+            print(f"Invalid --port: {options['--port']}")
+            sys.exit(1)
+        updates["wol_port"] = str(options["--port"])
+    if updates:
+        _save_env(name, updates)
+
+    timeout = _timeout(options, 300)
+    try:
+        result = host_wake(
+            name,
+            wait=not options.get("--no-wait"),
+            timeout=timeout,
+            on_poll=_progress(f"Waiting for {name}"),
+        )
+    except (KeyError, ValueError, OSError) as exc:
+        print(f"Wake failed: {exc.args[0] if exc.args else exc}")
+        sys.exit(1)
+    if not result["sent"]:
+        print(f"{name} is already reachable.")
+    elif result["reachable"] is None:
+        print(f"Sent Wake-on-LAN packet to {name}.")
+    elif result["reachable"]:
+        print(f"{name} is up after {int(result['elapsed'])}s.")
+    else:
+        print(f"Sent Wake-on-LAN packet, but {name} did not answer within {timeout}s.")
+        sys.exit(1)
+
+
+def cmd_power_off(args: List[str]) -> None:
+    """Shut down or suspend a host over SSH and wait until it goes offline."""
+    from ..services.host_power import host_power_off
+
+    name, options = _parse(args, ("--suspend", "--no-wait", "--yes", "-y"), ("--command", "--timeout"), POWER_OFF_USAGE)
+    suspend = bool(options.get("--suspend"))
+    if options.get("--command"):
+        _save_env(name, {"suspend_command" if suspend else "power_off_command": str(options["--command"])})
+
+    action = "Suspend" if suspend else "Power off"
+    if not (options.get("--yes") or options.get("-y")):
+        confirm = prompt_input(f"{action} {name}? [y/N]: ")
+        if confirm is None or confirm.strip().lower() not in ("y", "yes"):
+            print("Cancelled.")
+            return
+
+    timeout = _timeout(options, 120)
+    try:
+        result = host_power_off(
+            name,
+            suspend=suspend,
+            wait=not options.get("--no-wait"),
+            timeout=timeout,
+            on_poll=_progress(f"Waiting for {name} to go offline"),
+        )
+    except (KeyError, RuntimeError) as exc:
+        print(f"{action} failed: {exc.args[0] if exc.args else exc}")
+        sys.exit(1)
+    if result["down"] is None:
+        print(f"Sent `{result['command']}` to {name}.")
+    elif result["down"]:
+        print(f"{name} went offline after {int(result['elapsed'])}s.")
+    else:
+        print(f"{name} is still reachable after {timeout}s; check that `{result['command']}` works without a password.")
+        sys.exit(1)
@@ -0,0 +1,142 @@
+"""`train host ps` and `train host kill`: find and stop processes, GPU holders first."""
+
+from __future__ import annotations
+
+import json
+import sys
+from typing import Any, Dict, List
+
+from ..cli_utils import prompt_input
+
+PS_USAGE = "train host ps <name> [filter] [--gpu] [--limit N] [--json]"
+KILL_USAGE = "train host kill <name> <pid> [--signal TERM|KILL|INT|...] [--yes]"
+
+
+def _format_row(row: Dict[str, Any]) -> str:
+    from ..services.transfer_progress import format_bytes
+
+    gpu = "-"
+    if row["gpu_memory_mb"]:
+        gpus = ",".join(str(index) for index in row["gpus"]) or "?"
+        gpu = f"{row['gpu_memory_mb'] / 1024:.1f}G@{gpus}"
+    command = row["command"] if len(row["command"]) <= 70 else row["command"][:67] + "..."
+    return (
+        f"  {row['pid']:>7} {row['ppid']:>7} {row['user'][:10]:<10} {row['stat']:<5} "
+        f"{row['cpu_percent']:>5.1f} {format_bytes(row['rss']):>10} {gpu:>12}  {command}"
+    )
+
+
+def cmd_ps(args: List[str]) -> None:
+    """List processes on a host with the GPU memory each one holds."""
+    from ..services.host_processes import host_list_processes
+
+    if not args or args[0].startswith("-"):
+        print(f"Usage: {PS_USAGE}")
+        sys.exit(1)
+    name = args[0]
+    pattern = ""
+    gpu_only = as_json = False
+    limit = 30
+    rest = args[1:]
+    i = 0
+    while i < len(rest):
+        arg = rest[i]
+        if arg == "--limit" and i + 1 < len(rest):
+            try:
+                limit = max(0, int(rest[i + 1]))
+            except ValueError:
+                print(f"Invalid --limit: {rest[i + 1]}")
+                sys.exit(1)
+            i += 2
+            continue
+        if arg == "--gpu":
+            gpu_only = True
+        elif arg == "--json":
+            as_json = True
+        elif not arg.startswith("-") and not pattern:
+            pattern = arg
+        else:
+            print(f"Usage: {PS_USAGE}")
+            sys.exit(1)
+        i += 1
+
+    try:
+        rows = host_list_processes(name, pattern, gpu_only=gpu_only)
+    except (KeyError, RuntimeError) as exc:
+        print(f"Process list failed: {exc.args[0] if exc.args else exc}")
+        sys.exit(1)
+    if as_json:
+        print(json.dumps(rows, indent=2))
+        return
+    if not rows:
+        print("No matching processes.")
+        return
+    shown = rows[:limit] if limit else rows
+    print(f"  {'PID':>7} {'PPID':>7} {'USER':<10} {'STAT':<5} {'CPU%':>5} {'RSS':>10} {'GPU MEM':>12}  COMMAND")
+    for row in shown:
+        print(_format_row(row))
+    if len(shown) < len(rows):
+        print(f"  ... {len(rows) - len(shown)} more (use --limit 0 to show all)")
+    zombies = [row for row in shown if row["zombie"]]
+    for row in zombies:
+        print(f"  pid {row['pid']} is a zombie; kill its parent {row['ppid']} to reap it.")
+
+
+def cmd_kill(args: List[str]) -> None:
+    """Signal one process on a host after showing what it is."""
+    from ..services.host_processes import host_kill_process, host_list_processes, normalize_signal
+
+    positional: List[str] = []
+    signal = "TERM"
+    yes = False
+    i = 0
+    while i < len(args):
+        arg = args[i]
+        if arg in ("--signal", "-s") and i + 1 < len(args):
+            signal = args[i + 1]
+            i += 2
+            continue
+        if arg in ("-y", "--yes"):
+            yes = True
+        elif arg.startswith("-"):
+            print(f"Usage: {KILL_USAGE}")
+            sys.exit(1)
+        else:
+            positional.append(arg)
+        i += 1
+    if len(positional) != 2 or not positional[1].isdigit():
+        print(f"Usage: {KILL_USAGE}")
+        sys.exit(1)
+    name, pid = positional[0], int(positional[1])
+    try:
+        signal = normalize_signal(signal)
+    except ValueError as exc:
+        print(exc)
+        sys.exit(1)
+
+    if not yes:
+        try:
+            match = [row for row in host_list_processes(name) if row["pid"] == pid]
+        except (KeyError, RuntimeError) as exc:
+            print(f"Process list failed: {exc.args[0] if exc.args else exc}")
+            sys.exit(1)
+        if not match:
+            print(f"No process {pid} on {name}.")
+            sys.exit(1)
+        print(_format_row(match[0]))
+        confirm = prompt_input(f"Send SIG{signal} to pid {pid} on {name}? [y/N]: ")
+        if confirm is None or confirm.strip().lower() not in ("y", "yes"):
+            print("Cancelled.")
+            return
+
+    try:
+        result = host_kill_process(name, pid, signal)
+    except (KeyError, RuntimeError, ValueError) as exc:
+        print(f"Kill failed: {exc.args[0] if exc.args else exc}")
+        sys.exit(1)
+    if result["exited"]:
+        print(f"Process {pid} on {name} exited after SIG{signal}.")
+    elif result["state"] == "zombie":
+        print(f"Process {pid} is now a zombie; kill its parent to reap it.")
+    else:
+        print(f"Sent SIG{signal} to {pid}; it is still running (try --signal KILL).")
@@ -0,0 +1,31 @@
+"""`train host ssh-config`: export managed hosts to an OpenSSH include file."""
+
+from __future__ import annotations
+
+import sys
+from typing import List
+
+USAGE = "train host ssh-config [--print] [--no-include]"
+
+
+def cmd_ssh_config(args: List[str]) -> None:
+    """Write every managed host (live Vast/RunPod endpoints included) as a `Host <alias>` entry."""
+    from ..services.ssh_export import export_ssh_config, render_export
+    from .host import load_hosts
+
+    if any(arg not in ("--print", "--no-include") for arg in args):
+        print(f"Usage: {USAGE}")
+        sys.exit(1)
+    hosts = load_hosts()
+    if "--print" in args:
+        print(render_export(hosts)["text"], end="")
+        return
+
+    report = export_ssh_config(hosts, include="--no-include" not in args)
+    print(f"Wrote {len(report['aliases'])} host(s) to {report['path']}")
+    if report["include_added"]:
+        print(f"Added `Include {report['path']}` to ~/.ssh/config")
+    for name, alias in report["aliases"].items():
+        print(f"  ssh {alias}" + (f"  ({name})" if alias != name else ""))
+    for name, reason in report["skipped"].items():
+        print(f"  skipped {name}: {reason}")
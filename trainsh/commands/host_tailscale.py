@@ -0,0 +1,87 @@
+"""`train host tailscale`: list tailnet nodes and link hosts to a node name."""
+
+from __future__ import annotations
+
+import json
+import sys
+from typing import List
+
+USAGE = "train host tailscale [list [--json] | link <name> <node> | unlink <name>]"
+
+
+def _list(args: List[str]) -> None:
+    from ..services.tailscale import TailscaleError, list_nodes
+
+    if any(arg != "--json" for arg in args):
+        print(f"Usage: {USAGE}")
+        sys.exit(1)
+    try:
+        nodes = list_nodes()
+    except TailscaleError as exc:
+        print(exc)
+        sys.exit(1)
+    if "--json" in args:
+        print(json.dumps(nodes, indent=2))
+        return
+    print(f"  {'NODE':<24} {'STATUS':<8} {'ADDRESS':<16} {'OS':<8} MAGICDNS")
+    for node in nodes:
+        status = "self" if node["self"] else ("online" if node["online"] else "offline")
+        address = next((ip for ip in node["ips"] if ":" not in ip), "-")
+        print(f"  {node['name'][:24]:<24} {status:<8} {address:<16} {node['os'][:8]:<8} {node['dns_name']}")
+
+
+def _set_node(name: str, node_name: str) -> None:
+    from .host import load_hosts, save_hosts
+
+    configured = load_hosts(include_auto_vast=False)
+    if name not in configured:
+        print(f"Host not found: {name}")
+        sys.exit(1)
+    env_vars = dict(configured[name].env_vars or {})
+    if node_name:
+        env_vars["tailscale_node"] = node_name
+    else:
+        env_vars.pop("tailscale_node", None)
+    configured[name].env_vars = env_vars
+    save_hosts(configured)
+
+
+def _link(args: List[str]) -> None:
+    from ..services.tailscale import TailscaleError, find_node, list_nodes
+
+    if len(args) != 2:
+        print(f"Usage: {USAGE}")
+        sys.exit(1)
+    name, node_name = args
+    try:
+        node = find_node(list_nodes(), node_name)
+    except TailscaleError as exc:
+        print(exc)
+        sys.exit(1)
+    if node is None:
+        print(f"Tailscale node not found: {node_name}")
+        sys.exit(1)
+    _set_node(name, node["name"])
+    state = "online" if node["online"] else "offline"
+    print(f"Linked {name} to Tailscale node {node['name']} ({state}); its address is resolved on every connect.")
+
+
+def _unlink(args: List[str]) -> None:
+    if len(args) != 1:
+        print(f"Usage: {USAGE}")
+        sys.exit(1)
+    _set_node(args[0], "")
+    print(f"Unlinked {args[0]} from Tailscale; the stored hostname is used again.")
+
+
+def cmd_tailscale(args: List[str]) -> None:
+    """List tailnet nodes, or link/unlink a stored host to one."""
+    if not args or args[0] == "list" or args[0].startswith("-"):
+        _list(args[1:] if args and args[0] == "list" else args)
+    elif args[0] == "link":
+        _link(args[1:])
+    elif args[0] == "unlink":
+        _unlink(args[1:])
+    else:
+        print(f"Usage: {USAGE}")
+        sys.exit(1)
@@ -0,0 +1,114 @@
+"""`train host tunnel`: open one local SSH port-forward to a stored host."""
+
+from __future__ import annotations
+
+import subprocess
+import sys
+from typing import List
+
+from ..services.tunnel import TunnelSpec, build_local_tunnel_args, start_local_tunnel
+
+
+def cmd_tunnel(args: List[str]) -> None:
+    """Open one local SSH local-forward tunnel."""
+    if not args:
+        print(
+            "Usage: train host tunnel <name> --local-port <port> --remote-port <port> "
+            "[--bind-host <host>] [--remote-host <host>] [--background]"
+        )
+        sys.exit(1)
+
+    from .host import load_hosts
+
+    name = str(args[0]).strip()
+    hosts = load_hosts()
+    if name not in hosts:
+        print(f"Host not found: {name}")
+        sys.exit(1)
+
+    local_port = None
+    remote_port = None
+    bind_host = "127.0.0.1"
+    remote_host = "127.0.0.1"
+    background = False
+
+    i = 1
+    while i < len(args):
+        arg = args[i]
+        if arg == "--local-port":
+            if i + 1 >= len(args):
+                print("Missing value for --local-port.")
+                sys.exit(1)
+            i += 1
+            local_port = int(args[i])
+        elif arg.startswith("--local-port="):
+            local_port = int(arg.split("=", 1)[1])
+        elif arg == "--remote-port":
+            if i + 1 >= len(args):
+                print("Missing value for --remote-port.")
+                sys.exit(1)
+            i += 1
+            remote_port = int(args[i])
+        elif arg.startswith("--remote-port="):
+            remote_port = int(arg.split("=", 1)[1])
+        elif arg == "--bind-host":
+            if i + 1 >= len(args):
+                print("Missing value for --bind-host.")
+                sys.exit(1)
+            i += 1
+            bind_host = str(args[i]).strip() or bind_host
+        elif arg.startswith("--bind-host="):
+            bind_host = str(arg.split("=", 1)[1]).strip() or bind_host
+        elif arg == "--remote-host":
+            if i + 1 >= len(args):
+                print("Missing value for --remote-host.")
+                sys.exit(1)
+            i += 1
+            remote_host = str(args[i]).strip() or remote_host
+        elif arg.startswith("--remote-host="):
+            remote_host = str(arg.split("=", 1)[1]).strip() or remote_host
+        elif arg == "--background":
+            background = True
+        else:
+            print(f"Unknown option: {arg}")
+            sys.exit(1)
+        i += 1
+
+    if local_port is None or remote_port is None:
+        print(
+            "Usage: train host tunnel <name> --local-port <port> --remote-port <port> "
+            "[--bind-host <host>] [--remote-host <host>] [--background]"
+        )
+        sys.exit(1)
+
+    spec = TunnelSpec(
+        local_port=int(local_port),
+        remote_port=int(remote_port),
+        bind_host=bind_host,
+        remote_host=remote_host,
+    )
+    host = hosts[name]
+    if background:
+        try:
+            process = start_local_tunnel(host, spec, wait_timeout=10.0)
+        except RuntimeError as exc:
+            print(f"Failed to open tunnel: {exc}")
+            sys.exit(1)
+        print(
+            f"Tunnel ready in background: {bind_host}:{local_port} -> {remote_host}:{remote_port} "
+            f"(pid {process.pid})"
+        )
+        return
+
+    print(f"Forwarding {bind_host}:{local_port} -> {remote_host}:{remote_port} via {name}")
+    args = build_local_tunnel_args(host, spec)
+    try:
+        result = subprocess.run(args)
+    except KeyboardInterrupt:
+        print("\nTunnel stopped.")
+        return
+    if result.returncode != 0:
+        sys.exit(result.returncode)
+
+
+__all__ = ["cmd_tunnel"]
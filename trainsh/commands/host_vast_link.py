@@ -0,0 +1,169 @@
+"""`train host link` / `train host refresh`: follow a Vast.ai instance across restarts."""
+
+from __future__ import annotations
+
+import json
+import sys
+from typing import List
+
+LINK_USAGE = "train host link <name> vast:<id> | train host link <name> --unlink"
+REFRESH_USAGE = "train host refresh [<name>...] | train host refresh --all [--timeout SECS] [--json]"
+
+
+def cmd_link(args: List[str]) -> None:
+    """Bind a stored SSH host to `vast:<id>` so its endpoint follows the instance."""
+    from ..services.host_resolver import VAST_LINK_ENV, parse_vast_ref
+    from .host import load_hosts, save_hosts
+
+    if len(args) != 2:
+        print(f"Usage: {LINK_USAGE}")
+        sys.exit(1)
+    name, target = args
+    configured = load_hosts(include_auto_vast=False)
+    if name not in configured:
+        print(f"Host not found: {name}")
+        sys.exit(1)
+    env_vars = dict(configured[name].env_vars or {})
+    if target == "--unlink":
+        env_vars.pop(VAST_LINK_ENV, None)
+        configured[name].env_vars = env_vars
+        save_hosts(configured)
+        print(f"Unlinked {name}; its stored hostname and port are used as-is.")
+        return
+    try:
+        instance_id = parse_vast_ref(target)
+    except ValueError as exc:
+        print(exc)
+        sys.exit(1)
+    env_vars[VAST_LINK_ENV] = f"vast:{instance_id}"
+    configured[name].env_vars = env_vars
+    save_hosts(configured)
+    print(f"Linked {name} to vast:{instance_id}; its SSH endpoint is re-read from the Vast API on connect.")
+    _refresh(configured, [name])
+
+
+def _retarget_sessions(old_hostname: str, old_port: int, refreshed) -> List[str]:
+    """Point running or failed jobs' saved sessions at a host's new endpoint; return their ids."""
+    from ..constants import RUNTIME_STATE_DIR
+    from ..core.job_state import JobStateManager
+
+    if not old_hostname:
+        return []
+    new_spec = f"{refreshed.username or 'root'}@{refreshed.hostname} -p {refreshed.port}"
+    return JobStateManager(str(RUNTIME_STATE_DIR)).retarget_hosts(old_hostname, old_port, new_spec)
+
+
+def _refresh(configured: dict, names: List[str]) -> int:
+    """Refresh the named hosts in place, persist them, and return the number that failed."""
+    from ..services.host_refresh import run_per_host
+    from ..services.host_resolver import linked_vast_instance_id, refresh_vast_link
+    from .host import save_hosts
+
+    failures = 0
+    changed = False
+    results = run_per_host(names, lambda name: refresh_vast_link(configured[name]))
+    for name in names:
+        host = configured[name]
+        instance_id = linked_vast_instance_id(host)
+        old_hostname, old_port = host.hostname, int(host.port or 22)
+        result = results[name]
+        if result.status != "ok":
+            print(f"  {name}: vast:{instance_id} refresh failed: {result.error}")
+            failures += 1
+            continue
+        refreshed = result.value
+        configured[name] = refreshed
+        changed = True
+        endpoint = f"{refreshed.hostname}:{refreshed.port}"
+        if (refreshed.hostname, int(refreshed.port or 22)) == (old_hostname, old_port):
+            print(f"  {name}: vast:{instance_id} unchanged at {endpoint}")
+            continue
+        print(f"  {name}: vast:{instance_id} moved {old_hostname or '-'}:{old_port} -> {endpoint}")
+        jobs = _retarget_sessions(old_hostname, old_port, refreshed)
+        if jobs:
+            print(f"    Updated sessions of job(s) {', '.join(jobs)}; `train resume` reconnects to the new endpoint.")
+    if changed:
+        save_hosts(configured)
+    return failures
+
+
+def _refresh_all(args: List[str]) -> None:
+    """Probe every stored host concurrently, re-resolving Vast links, and stream one line or event per host."""
+    from ..services.host_refresh import refresh_all
+    from .host import load_hosts, save_hosts
+
+    timeout = None
+    as_json = False
+    index = 0
+    while index < len(args):
+        option = args[index]
+        index += 1
+        if option == "--all":
+            continue
+        if option == "--json":
+            as_json = True
+        elif option == "--timeout" and index < len(args):
+            try:
+                timeout = max(1.0, float(args[index]))
+            except ValueError:
+                print(f"Invalid --timeout: {args[index]}")
+                sys.exit(1)
+            index += 1
+        else:
+            print(f"Usage: {REFRESH_USAGE}")
+            sys.exit(1)
+
+    configured = load_hosts(include_auto_vast=False)
+    if not configured:
+        print("No hosts configured.")
+        return
+
+    def show(event) -> None:
+        if as_json:
+            print(json.dumps(event, ensure_ascii=False), flush=True)
+            return
+        detail = f" (moved from {event['moved_from']})" if event["moved_from"] else ""
+        error = f": {event['error']}" if event["error"] else ""
+        print(f"  [{event['done']}/{event['total']}] {event['host']:<20} {event['status']:<8} {event['endpoint']}{detail}{error}", flush=True)
+
+    if not as_json:
+        print(f"Refreshing {len(configured)} host(s)...")
+    events, refreshed = refresh_all(configured, timeout=timeout, on_event=show)
+    if refreshed:
+        for name, host in refreshed.items():
+            old = configured[name]
+            if (host.hostname, int(host.port or 22)) != (old.hostname, int(old.port or 22)):
+                _retarget_sessions(old.hostname, int(old.port or 22), host)
+        configured.update(refreshed)
+        save_hosts(configured)
+    if not as_json:
+        online = sum(1 for event in events.values() if event["status"] == "online")
+        print(f"{online} of {len(events)} host(s) online.")
+
+
+def cmd_refresh(args: List[str]) -> None:
+    """Re-resolve the SSH endpoint of hosts linked to a Vast.ai instance."""
+    from ..services.host_resolver import linked_vast_instance_id
+    from .host import load_hosts
+
+    if "--all" in args:
+        _refresh_all(args)
+        return
+    if any(arg.startswith("-") for arg in args):
+        print(f"Usage: {REFRESH_USAGE}")
+        sys.exit(1)
+    configured = load_hosts(include_auto_vast=False)
+    missing = [name for name in args if name not in configured]
+    if missing:
+        print(f"Host not found: {missing[0]}")
+        sys.exit(1)
+    names = list(args) or [name for name, host in configured.items() if linked_vast_instance_id(host)]
+    unlinked = [name for name in names if not linked_vast_instance_id(configured[name])]
+    if unlinked:
+        print(f"{unlinked[0]} is not linked to a Vast.ai instance; run `train host link {unlinked[0]} vast:<id>`.")
+        sys.exit(1)
+    if not names:
+        print("No stored hosts are linked to a Vast.ai instance.")
+        return
+    if _refresh(configured, names):
+        sys.exit(1)
@@ -0,0 +1,146 @@
+"""`train host vscode`: Remote-SSH config entries and optional code-server tunnels."""
+
+from __future__ import annotations
+
+import sys
+import time
+from typing import Any, Dict, List
+
+VSCODE_USAGE = (
+    "train host vscode <name> [--folder DIR] [--alias NAME] [--open]\n"
+    "train host vscode <name> --code-server [--install] [--folder DIR] [--port N] [--local-port N]\n"
+    "train host vscode <name> --stop"
+)
+
+
+def _parse_args(args: List[str]) -> Dict[str, Any]:
+    from ..services.vscode import CODE_SERVER_DEFAULT_PORT
+
+    if not args or args[0].startswith("-"):
+        print(f"Usage: {VSCODE_USAGE}")
+        sys.exit(1)
+    opts: Dict[str, Any] = {
+        "name": args[0],
+        "folder": "",
+        "alias": "",
+        "port": CODE_SERVER_DEFAULT_PORT,
+        "local_port": 0,
+        "code_server": False,
+        "install": False,
+        "open": False,
+        "stop": False,
+    }
+    valued = {"--folder": "folder", "--alias": "alias", "--port": "port", "--local-port": "local_port"}
+    flags = {"--code-server": "code_server", "--install": "install", "--open": "open", "--stop": "stop"}
+    i = 1
+    while i < len(args):
+        arg = args[i]
+        if arg in valued and i + 1 < len(args):
+            value: Any = args[i + 1]
+            if valued[arg] in ("port", "local_port"):
+                if not str(value).isdigit():
+                    print(f"Invalid {arg}: {value}")
+                    sys.exit(1)
+                value = int(value)
+            opts[valued[arg]] = value
+            i += 2
+            continue
+        if arg in flags:
+            opts[flags[arg]] = True
+        else:
+            print(f"Unknown option: {arg}")
+            print(f"Usage: {VSCODE_USAGE}")
+            sys.exit(1)
+        i += 1
+    if opts["install"]:
+        opts["code_server"] = True
+    return opts
+
+
+def _open_url(url: str) -> None:
+    import webbrowser
+
+    if not webbrowser.open(url):
+        print("Could not open the URL automatically; open it manually.")
+
+
+def _run_code_server(opts: Dict[str, Any], host) -> None:
+    from ..services.vscode import code_server_is_ready, open_code_server_tunnel, start_code_server
+
+    try:
+        state = start_code_server(opts["name"], host, folder=opts["folder"], port=opts["port"], install=opts["install"])
+    except RuntimeError as exc:
+        print(f"Failed to start code-server: {exc}")
+        if not opts["install"]:
+            print("Hint: pass --install to install code-server into ~/.local on the host.")
+        sys.exit(1)
+    deadline = time.time() + 60
+    while not code_server_is_ready(host, state["port"]):
+        if time.time() >= deadline:
+            print("code-server did not become ready; check the tmux session on the host.")
+            print(f"  Attach: train host ssh {opts['name']} then tmux attach -t trainsh-code-server")
+            sys.exit(1)
+        time.sleep(2)
+    try:
+        state = open_code_server_tunnel(opts["name"], host, state, local_port=opts["local_port"])
+    except RuntimeError as exc:
+        print(f"code-server is running but the tunnel failed: {exc}")
+        sys.exit(1)
+    url = f"http://127.0.0.1:{state['local_port']}/"
+    print(f"code-server: {url}")
+    print(f"  Password: {state['password']}")
+    print(f"  Stop: train host vscode {opts['name']} --stop")
+    if opts["open"]:
+        _open_url(url)
+
+
+def cmd_vscode(args: List[str]) -> None:
+    """Prepare a host for VS Code: SSH config entry, then a vscode:// or code-server URL."""
+    from ..services.vscode import (
+        ensure_ssh_include,
+        render_ssh_config_entry,
+        resolve_remote_folder,
+        ssh_alias,
+        stop_code_server,
+        vscode_remote_url,
+        write_ssh_entry,
+    )
+    from .host import load_hosts
+
+    opts = _parse_args(args)
+    host = load_hosts().get(opts["name"])
+    if host is None:
+        print(f"Host not found: {opts['name']}")
+        sys.exit(1)
+
+    if opts["stop"]:
+        try:
+            stopped = stop_code_server(opts["name"], host)
+        except RuntimeError as exc:
+            print(f"Failed to stop code-server: {exc}")
+            sys.exit(1)
+        print("Stopped code-server." if stopped else "code-server is not running.")
+        return
+
+    if opts["code_server"]:
+        _run_code_server(opts, host)
+        return
+
+    alias = opts["alias"] or ssh_alias(opts["name"])
+    try:
+        entry = render_ssh_config_entry(alias, host)
+    except Exception as exc:
+        print(f"Could not resolve SSH connection for {opts['name']}: {exc}")
+        sys.exit(1)
+    include = write_ssh_entry(alias, entry)
+    if ensure_ssh_include(include):
+        print(f"Added 'Include {include}' to ~/.ssh/config")
+    print(f"Wrote SSH entry '{alias}' to {include}")
+    if getattr(host.auth_method, "value", "") == "password":
+        print("Note: this host uses password auth; VS Code will prompt for it.")
+
+    url = vscode_remote_url(alias, resolve_remote_folder(host, opts["folder"]))
+    print(f"VS Code: {url}")
+    print(f"  Or: code --remote ssh-remote+{alias} <folder>")
+    if opts["open"]:
+        _open_url(url)
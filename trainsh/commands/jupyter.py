@@ -0,0 +1,262 @@
+"""Managed Jupyter Lab servers on hosts: start in tmux, tunnel, status, stop."""
+
+from __future__ import annotations
+
+import sys
+import time
+from typing import Any, Dict, List, Optional
+
+from ..cli_utils import SubcommandSpec, dispatch_subcommand
+from ..services.jupyter_service import (
+    DEFAULT_PORT,
+    JupyterServerRecord,
+    list_servers,
+    load_server,
+    open_tunnel,
+    refresh_status,
+    server_is_ready,
+    server_is_running,
+    start_server,
+    stop_server,
+)
+from ..services.vllm_service import parse_duration
+from .help_catalog import render_command_help
+from .help_cmd import reject_subcommand_help
+
+
+SUBCOMMAND_SPECS = (
+    SubcommandSpec("list", "List managed Jupyter servers."),
+    SubcommandSpec("start", "Start Jupyter Lab in tmux on a host and tunnel it to localhost."),
+    SubcommandSpec("status", "Show one server's state and local URL."),
+    SubcommandSpec("tunnel", "Re-open the local SSH tunnel to a running server."),
+    SubcommandSpec("stop", "Stop one server, close its tunnel, and remove its record."),
+)
+
+usage = render_command_help("jupyter")
+
+
+def _server_or_exit(name: str) -> JupyterServerRecord:
+    record = load_server(name)
+    if record is None:
+        print(f"Jupyter server not found: {name}")
+        raise SystemExit(1)
+    return record
+
+
+def _parse_start_args(args: List[str]) -> Dict[str, Any]:
+    if not args or args[0].startswith("-"):
+        print("Usage: train jupyter start <host> [options]")
+        raise SystemExit(1)
+    opts: Dict[str, Any] = {
+        "host_name": args[0],
+        "name": "",
+        "port": DEFAULT_PORT,
+        "local_port": 0,
+        "root_dir": "",
+        "python": "",
+        "tunnel": True,
+        "ready_timeout": "2m",
+        "replace": False,
+    }
+    valued = {
+        "--name": "name",
+        "--port": "port",
+        "--local-port": "local_port",
+        "--dir": "root_dir",
+        "--python": "python",
+        "--ready-timeout": "ready_timeout",
+    }
+    i = 1
+    while i < len(args):
+        arg = args[i]
+        key, sep, inline = arg.partition("=")
+        if key in valued:
+            if sep:
+                value = inline
+            else:
+                i += 1
+                if i >= len(args):
+                    print(f"Missing value for {key}.")
+                    raise SystemExit(1)
+                value = args[i]
+            if valued[key] in ("port", "local_port"):
+                try:
+                    value = int(value)
+                except ValueError:
+                    print(f"Invalid {key}: {value}")
+                    raise SystemExit(1)
+            opts[valued[key]] = value
+        elif arg == "--no-tunnel":
+            opts["tunnel"] = False
+        elif arg == "--replace":
+            opts["replace"] = True
+        else:
+            print(f"Unknown option: {arg}")
+            raise SystemExit(1)
+        i += 1
+    opts["name"] = opts["name"] or opts["host_name"]
+    return opts
+
+
+def _print_access(record: JupyterServerRecord) -> None:
+    if record.local_url:
+        print(f"  Local URL: {record.local_url}")
+    else:
+        print(f"  Tunnel: train jupyter tunnel {record.name}")
+    print(f"  Token: {record.token}")
+
+
+def cmd_list(args: List[str]) -> None:
+    """List managed Jupyter servers."""
+    _ = args
+    servers = list_servers()
+    if not servers:
+        print("No Jupyter servers found.")
+        print("Use 'train jupyter start <host>' to start one.")
+        return
+    print("Managed Jupyter servers:")
+    print("-" * 80)
+    print(f"  {'NAME':<18} {'HOST':<18} {'PORT':<6} {'STATUS':<10} LOCAL")
+    print("-" * 80)
+    for record in servers:
+        status = refresh_status(record)
+        local = f"127.0.0.1:{record.local_port}" if record.local_port else "-"
+        print(f"  {record.name:<18} {record.host_name:<18} {record.port:<6} {status:<10} {local}")
+    print("-" * 80)
+    print(f"Total: {len(servers)} servers")
+
+
+def cmd_start(args: List[str]) -> None:
+    """Start one managed Jupyter server, wait for it, and open a tunnel."""
+    from .host import load_hosts
+
+    opts = _parse_start_args(args)
+    host = load_hosts().get(opts["host_name"])
+    if host is None:
+        print(f"Host not found: {opts['host_name']}")
+        raise SystemExit(1)
+    existing = load_server(opts["name"])
+    if existing is not None:
+        if not opts["replace"] and refresh_status(existing) != "stopped":
+            print(f"Jupyter server already running: {existing.name} ({existing.status})")
+            _print_access(existing)
+            return
+        stop_server(existing)
+
+    try:
+        record = start_server(
+            opts["host_name"],
+            host,
+            name=opts["name"],
+            port=opts["port"],
+            root_dir=opts["root_dir"],
+            python=opts["python"],
+        )
+    except RuntimeError as exc:
+        print(f"Failed to start Jupyter: {exc}")
+        raise SystemExit(1)
+    print(f"Started Jupyter server: {record.name}")
+    print(f"  Host: {record.host_name}")
+    print(f"  Session: {record.session_name}")
+    print(f"  Remote port: {record.port}")
+
+    timeout = parse_duration(opts["ready_timeout"], default=120)
+    deadline = time.time() + max(1, timeout)
+    while not server_is_ready(record):
+        if not server_is_running(record) or time.time() >= deadline:
+            print("Jupyter did not become ready; check the tmux session on the host.")
+            print(f"  Attach: train host ssh {record.host_name} then tmux attach -t {record.session_name}")
+            raise SystemExit(1)
+        time.sleep(2)
+    record.status = "ready"
+
+    if opts["tunnel"]:
+        try:
+            open_tunnel(record, local_port=opts["local_port"])
+        except RuntimeError as exc:
+            print(f"Jupyter is running but the tunnel failed: {exc}")
+    _print_access(record)
+
+
+def cmd_status(args: List[str]) -> None:
+    """Show one server's status and how to reach it."""
+    if not args:
+        cmd_list([])
+        return
+    record = _server_or_exit(args[0])
+    status = refresh_status(record)
+    print(f"Jupyter server: {record.name}")
+    print(f"  Host: {record.host_name}")
+    print(f"  Session: {record.session_name}")
+    print(f"  Remote port: {record.port}")
+    if record.root_dir:
+        print(f"  Directory: {record.root_dir}")
+    print(f"  Status: {status}")
+    print(f"  Updated: {record.updated_at}")
+    _print_access(record)
+
+
+def cmd_tunnel(args: List[str]) -> None:
+    """Re-open the local tunnel, e.g. after a laptop sleep."""
+    if not args:
+        print("Usage: train jupyter tunnel <name> [--local-port N]")
+        raise SystemExit(1)
+    record = _server_or_exit(args[0])
+    local_port = 0
+    if len(args) == 3 and args[1] == "--local-port" and args[2].isdigit():
+        local_port = int(args[2])
+    elif len(args) != 1:
+        print("Usage: train jupyter tunnel <name> [--local-port N]")
+        raise SystemExit(1)
+    if refresh_status(record) == "stopped":
+        print(f"Jupyter server is not running: {record.name}")
+        raise SystemExit(1)
+    try:
+        open_tunnel(record, local_port=local_port)
+    except RuntimeError as exc:
+        print(f"Tunnel failed: {exc}")
+        raise SystemExit(1)
+    _print_access(record)
+
+
+def cmd_stop(args: List[str]) -> None:
+    """Stop one managed server."""
+    if not args:
+        print("Usage: train jupyter stop <name>")
+        raise SystemExit(1)
+    record = _server_or_exit(args[0])
+    try:
+        stop_server(record)
+    except Exception as exc:
+        print(f"Failed to stop Jupyter: {exc}")
+        raise SystemExit(1)
+    print(f"Stopped Jupyter server: {record.name}")
+
+
+def main(args: List[str]) -> Optional[str]:
+    """Main entry point for jupyter command."""
+    if not args:
+        print(usage)
+        return None
+    if args[0] in ("-h", "--help", "help"):
+        reject_subcommand_help()
+
+    commands = {
+        "list": cmd_list,
+        "start": cmd_start,
+        "status": cmd_status,
+        "tunnel": cmd_tunnel,
+        "stop": cmd_stop,
+    }
+    try:
+        handler = dispatch_subcommand(args[0], commands=commands)
+    except KeyError:
+        print(f"Unknown subcommand: {args[0]}")
+        print(usage)
+        raise SystemExit(1)
+    handler(args[1:])
+    return None
+
+
+if __name__ == "__main__":
+    main(sys.argv[1:])
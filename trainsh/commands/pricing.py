@@ -6,6 +6,7 @@ from typing import Optional
 
 from .help_catalog import render_command_help
 from .help_cmd import reject_subcommand_help
+from .pricing_compare import add_parsers as add_compare_parsers, cmd_compare, cmd_history, cmd_offers
 from ..services.pricing import (
     Currency,
     ExchangeRates,
@@ -32,9 +33,13 @@ def cmd_rates(args: argparse.Namespace) -> None:
 
     if args.refresh:
         print("Fetching exchange rates...")
-        rates = fetch_exchange_rates(fallback_to_defaults=True)
+        from ..services.rate_history import record_exchange_rates
+
+        fetched = fetch_exchange_rates(fallback_to_defaults=False)
+        rates = fetched or ExchangeRates()
         settings.exchange_rates = rates
         save_pricing_settings(settings)
+        record_exchange_rates(fetched)
         print(f"Updated at: {rates.updated_at}")
     else:
         rates = settings.exchange_rates
@@ -182,6 +187,107 @@ def cmd_vast(args: argparse.Namespace) -> None:
     print(f"\nMonthly estimate: {format_currency(total_month_conv, display_curr)}")
 
 
+def cmd_billing(args: argparse.Namespace) -> None:
+    """Monthly Vast.ai billed vs estimated cost report."""
+    import sys
+    from datetime import datetime
+    from ..services.vast_billing import (
+        BillingCache,
+        build_report,
+        estimate_from_events,
+        fetch_month_charges,
+        month_bounds,
+        write_csv,
+    )
+    from ..services.vast_events import VastInstanceWatcher
+
+    month = args.month or datetime.now().strftime("%Y-%m")
+    try:
+        start, end = month_bounds(month)
+    except ValueError:
+        print(f"Invalid month: {month} (expected YYYY-MM)")
+        sys.exit(1)
+
+    cache = BillingCache()
+    charges = None if args.refresh else cache.charges(month)
+    if charges is None:
+        from ..services.vast_api import VastAPIError, get_vast_client
+
+        try:
+            charges = fetch_month_charges(get_vast_client(), month, cache)
+        except VastAPIError as exc:
+            print(f"Could not fetch Vast.ai charges: {exc}")
+            sys.exit(1)
+
+    events = VastInstanceWatcher().history()
+    labels = {str(e.get("instance_id")): str(e["label"]) for e in events if e.get("label")}
+    estimates = estimate_from_events(events, start, end)
+    rows = build_report(month, charges, estimates, group_by=args.by, labels=labels)
+    if not rows:
+        print(f"No Vast.ai charges or estimates for {month}.")
+        return
+
+    heading = "Instance" if args.by == "instance" else "Label"
+    print(f"Vast.ai billing for {month} (USD)")
+    print("-" * 78)
+    print(f"{heading:<14} {'Label':<20} {'Billed':>10} {'Estimated':>10} {'Drift':>10} {'Drift %':>8}")
+    print("-" * 78)
+    for row in rows:
+        pct = "-" if row.drift_pct is None else f"{row.drift_pct:+.1f}%"
+        label = row.label if args.by == "instance" else ""
+        print(f"{row.key:<14} {label[:20]:<20} {row.billed_usd:>10.2f} {row.estimated_usd:>10.2f} {row.drift_usd:>+10.2f} {pct:>8}")
+    print("-" * 78)
+    billed = sum(row.billed_usd for row in rows)
+    estimated = sum(row.estimated_usd for row in rows)
+    print(f"{'Total':<14} {'':<20} {billed:>10.2f} {estimated:>10.2f} {billed - estimated:>+10.2f}")
+    if not events:
+        print("\nEstimates come from `train vast watch` history; none is recorded yet.")
+    if args.csv:
+        write_csv(args.csv, rows, group_by=args.by)
+        print(f"\nWrote {args.csv}")
+
+
+def cmd_report(args: argparse.Namespace) -> None:
+    """Cost totals per provider/host at historical exchange rates."""
+    import sys
+    from datetime import datetime, timedelta
+    from ..services.cost_report import pricing_report
+    from ..services.vast_billing import month_bounds
+
+    try:
+        if args.start or args.end:
+            start = datetime.strptime(args.start, "%Y-%m-%d") if args.start else datetime.now().replace(day=1)
+            end = datetime.strptime(args.end, "%Y-%m-%d") + timedelta(days=1) if args.end else datetime.now()
+            label = f"{start:%Y-%m-%d}..{end - timedelta(days=1):%Y-%m-%d}"
+        else:
+            label = args.month or datetime.now().strftime("%Y-%m")
+            start, end = month_bounds(label)
+    except ValueError:
+        print("Invalid date range (expected --month YYYY-MM or --from/--to YYYY-MM-DD)")
+        sys.exit(1)
+
+    currency = (args.currency or get_display_currency()).upper()
+    rows, totals = pricing_report(start, end, currency)
+    if not rows:
+        print(f"No recorded costs for {label}.")
+        return
+
+    print(f"Costs for {label} ({currency}, converted at each day's rate)")
+    print("-" * 64)
+    print(f"{'Provider':<10} {'Host':<26} {'USD':>10} {currency:>14}")
+    print("-" * 64)
+    for row in rows:
+        host = row.host + (" *" if row.estimated else "")
+        print(f"{row.provider:<10} {host[:26]:<26} {row.usd:>10.2f} {format_currency(row.amount, currency):>14}")
+    print("-" * 64)
+    for provider, amount in sorted(totals.items()):
+        print(f"{provider + ' total':<37} {sum(r.usd for r in rows if r.provider == provider):>10.2f} "
+              f"{format_currency(amount, currency):>14}")
+    print(f"{'Total':<37} {sum(r.usd for r in rows):>10.2f} {format_currency(sum(totals.values()), currency):>14}")
+    if any(row.estimated for row in rows):
+        print("\n* includes estimates from `train vast watch` for days without cached charges")
+
+
 def cmd_convert(args: argparse.Namespace) -> None:
     """Convert amount between currencies."""
     settings = load_pricing_settings()
@@ -227,6 +333,25 @@ def main(args: list) -> Optional[str]:
     # vast
     subparsers.add_parser("vast", help="Show Vast.ai instance costs")
 
+    # billing
+    billing_parser = subparsers.add_parser("billing", help="Vast.ai billed vs estimated costs")
+    billing_parser.add_argument("--month", "-m", metavar="YYYY-MM", help="Month to report (default: current)")
+    billing_parser.add_argument("--by", choices=("instance", "label"), default="instance",
+                                help="Group rows by instance id or label")
+    billing_parser.add_argument("--csv", metavar="PATH", help="Also write the report as CSV")
+    billing_parser.add_argument("--refresh", "-r", action="store_true",
+                                help="Re-fetch charges instead of using the cached month")
+
+    # report
+    report_parser = subparsers.add_parser("report", help="Costs per provider/host at historical rates")
+    report_parser.add_argument("--month", "-m", metavar="YYYY-MM", help="Month to report (default: current)")
+    report_parser.add_argument("--from", dest="start", metavar="YYYY-MM-DD", help="First day of a custom range")
+    report_parser.add_argument("--to", dest="end", metavar="YYYY-MM-DD", help="Last day of a custom range")
+    report_parser.add_argument("--currency", "-c", metavar="CODE", help="Report currency (default: display currency)")
+
+    # compare / offers / history
+    add_compare_parsers(subparsers)
+
     # convert
     conv_parser = subparsers.add_parser("convert", help="Convert between currencies")
     conv_parser.add_argument("amount", type=float, help="Amount to convert")
@@ -247,6 +372,16 @@ def main(args: list) -> Optional[str]:
         cmd_colab(parsed)
     elif parsed.command == "vast":
         cmd_vast(parsed)
+    elif parsed.command == "billing":
+        cmd_billing(parsed)
+    elif parsed.command == "report":
+        cmd_report(parsed)
+    elif parsed.command == "compare":
+        cmd_compare(parsed)
+    elif parsed.command == "offers":
+        cmd_offers(parsed)
+    elif parsed.command == "history":
+        cmd_history(parsed)
     elif parsed.command == "convert":
         cmd_convert(parsed)
 
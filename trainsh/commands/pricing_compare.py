@@ -0,0 +1,214 @@
+# GPU price commands: `train pricing compare`, `offers`, and `history`
+
+import argparse
+import sys
+
+from ..services.gpu_compare import compare_offers, gpu_matches, normalize_static_entry
+from ..services.gpu_price_history import gpu_price_history, price_trend, record_offer_prices
+from ..services.pricing import (
+    format_currency,
+    get_display_currency,
+    get_pricing_context,
+    load_pricing_settings,
+    save_pricing_settings,
+)
+
+
+def _search_vast(vram_gb, count, limit):
+    """Vast offers for the spec, or an empty list with a warning when unavailable."""
+    from ..services.vast_api import VastAPIError, get_vast_client
+
+    try:
+        client = get_vast_client()
+        # The API matches gpu_name exactly, so model matching happens locally.
+        offers = client.search_offers(num_gpus=count, min_gpu_ram=vram_gb, limit=limit)
+    except (RuntimeError, VastAPIError) as exc:
+        print(f"Skipping Vast.ai offers: {exc}")
+        return []
+    record_offer_prices(offers)
+    return offers
+
+
+def cmd_compare(args: argparse.Namespace) -> None:
+    """Cheapest options for a GPU spec across Vast.ai and static entries."""
+    if args.hours is not None and args.hours <= 0:
+        print("--hours must be positive")
+        sys.exit(1)
+    settings = load_pricing_settings()
+    static_entries = settings.gpu_offers
+    _settings, currency, rates = get_pricing_context(
+        product_currencies=[entry.get("currency", "USD") for entry in static_entries],
+        display_currency=(args.currency or get_display_currency()),
+    )
+    vast_offers = [] if args.no_vast else _search_vast(args.vram, args.count, args.limit * 4)
+    offers = compare_offers(
+        args.gpu,
+        vram_gb=args.vram,
+        count=args.count,
+        vast_offers=vast_offers,
+        static_entries=static_entries,
+        rates=rates,
+    )
+
+    spec = " ".join(filter(None, [
+        args.gpu,
+        f"{args.vram:g}GB+" if args.vram else "",
+        f"x{args.count}" if args.count else "",
+    ])) or "any GPU"
+    if not offers:
+        print(f"No offers match {spec}.")
+        if not static_entries:
+            print("Add other providers with: train pricing offers add <provider> <gpu> --price <per-hour>")
+        return
+
+    hours = args.hours
+    job_col = f"{hours:g}h total" if hours else ""
+    print(f"GPU offers for {spec} (in {currency})")
+    print("-" * 92)
+    print(f"{'Provider':<12} {'GPU':<18} {'GPUs':>4} {'VRAM':>6} {'/hr':>11} {'/GPU-hr':>11} {job_col:>12}  Ref")
+    print("-" * 92)
+    for offer in offers[:args.limit]:
+        vram = f"{offer.gpu_ram_gb:g}G" if offer.gpu_ram_gb else "?"
+        hourly = rates.convert(offer.usd_per_hour, "USD", currency)
+        per_gpu = rates.convert(offer.usd_per_gpu_hour, "USD", currency)
+        job = format_currency(rates.convert(offer.job_cost(hours), "USD", currency), currency) if hours else ""
+        print(
+            f"{offer.provider[:12]:<12} {offer.gpu_name[:18]:<18} {offer.num_gpus:>4} {vram:>6} "
+            f"{format_currency(hourly, currency, 3):>11} {format_currency(per_gpu, currency, 3):>11} {job:>12}  {offer.ref}"
+        )
+    if len(offers) > args.limit:
+        print(f"... {len(offers) - args.limit} more (use --limit)")
+
+
+def cmd_offers(args: argparse.Namespace) -> None:
+    """List, add, or remove static GPU price entries."""
+    settings = load_pricing_settings()
+    entries = settings.gpu_offers
+
+    if args.action == "add":
+        try:
+            entry = normalize_static_entry({
+                "provider": args.provider,
+                "gpu_name": args.gpu,
+                "num_gpus": args.count,
+                "gpu_ram_gb": args.vram,
+                "price_per_hour": args.price,
+                "currency": args.currency,
+                "capex": args.capex,
+                "lifetime_hours": args.lifetime_hours,
+                "note": args.note,
+            })
+        except ValueError as exc:
+            print(exc)
+            sys.exit(1)
+        entries[:] = [
+            item for item in entries
+            if not (item.get("provider") == entry["provider"] and item.get("gpu_name") == entry["gpu_name"]
+                    and int(item.get("num_gpus") or 1) == entry["num_gpus"])
+        ]
+        entries.append(entry)
+        save_pricing_settings(settings)
+        print(f"Saved {entry['provider']} {entry['gpu_name']} x{entry['num_gpus']}.")
+        return
+
+    if args.action == "remove":
+        kept = [
+            item for item in entries
+            if not (item.get("provider") == args.provider and gpu_matches(item.get("gpu_name"), args.gpu))
+        ]
+        if len(kept) == len(entries):
+            print(f"No GPU offer for {args.provider} {args.gpu}.")
+            sys.exit(1)
+        settings.gpu_offers = kept
+        save_pricing_settings(settings)
+        print(f"Removed {len(entries) - len(kept)} offer(s).")
+        return
+
+    if not entries:
+        print("No static GPU offers. Add one with: train pricing offers add <provider> <gpu> --price <per-hour>")
+        return
+    print(f"{'Provider':<12} {'GPU':<18} {'GPUs':>4} {'VRAM':>6} {'Price/hr':>12} {'Capex':>10} {'Life h':>8}  Note")
+    for item in entries:
+        vram = f"{item['gpu_ram_gb']:g}G" if item.get("gpu_ram_gb") else "?"
+        currency = item.get("currency", "USD")
+        price = format_currency(float(item.get("price_per_hour") or 0), currency, 3)
+        capex = format_currency(float(item["capex"]), currency, 0) if item.get("capex") else "-"
+        life = f"{item['lifetime_hours']:g}" if item.get("lifetime_hours") else "-"
+        print(
+            f"{item['provider'][:12]:<12} {item['gpu_name'][:18]:<18} {item.get('num_gpus', 1):>4} {vram:>6} "
+            f"{price:>12} {capex:>10} {life:>8}  {item.get('note', '')}"
+        )
+
+
+def cmd_history(args: argparse.Namespace) -> None:
+    """Observed Vast.ai per-GPU prices over time."""
+    if args.sample:
+        from ..services.gpu_price_history import GpuPriceHistory
+        from ..services.vast_api import VastAPIError, get_vast_client
+
+        try:
+            offers = get_vast_client().search_offers(limit=1000)
+        except (RuntimeError, VastAPIError) as exc:
+            print(f"Could not search Vast.ai offers: {exc}")
+            sys.exit(1)
+        print(f"Recorded prices for {GpuPriceHistory().record(offers)} GPU model(s).")
+        if not args.gpu:
+            return
+
+    rows = gpu_price_history(args.gpu or "", args.days)
+    if not rows:
+        print(f"No price samples for {args.gpu or 'any GPU'} in the last {args.days} days.")
+        print("Samples are taken by `train vast search`, `train pricing compare`, or `train pricing history --sample`.")
+        return
+    if not args.gpu:
+        latest = {}
+        for row in rows:
+            latest[row["gpu_name"]] = row
+        print(f"{'GPU':<22} {'Last day':<11} {'Min $/GPU-hr':>13} {'Median':>9}")
+        for name, row in sorted(latest.items(), key=lambda item: item[1]["median"]):
+            print(f"{name[:22]:<22} {row['day']:<11} {row['min']:>13.3f} {row['median']:>9.3f}")
+        return
+
+    print(f"Vast.ai $/GPU-hr for {args.gpu}, last {args.days} days")
+    print(f"{'Day':<11} {'GPU':<22} {'Min':>8} {'Median':>8} {'Samples':>8}")
+    for row in rows:
+        print(f"{row['day']:<11} {row['gpu_name'][:22]:<22} {row['min']:>8.3f} {row['median']:>8.3f} {row['samples']:>8}")
+    for name in sorted({row["gpu_name"] for row in rows}):
+        trend = price_trend([row for row in rows if row["gpu_name"] == name])
+        if trend is not None:
+            direction = "down" if trend < 0 else "up" if trend > 0 else "flat"
+            print(f"{name}: median {direction} {abs(trend):.1f}% over the period")
+
+
+def add_parsers(subparsers) -> None:
+    """Register `compare`, `offers`, and `history` on the pricing subparsers."""
+    compare_parser = subparsers.add_parser("compare", help="Compare GPU prices across providers")
+    compare_parser.add_argument("gpu", nargs="?", help="GPU model, matched loosely (e.g. 4090, A100)")
+    compare_parser.add_argument("--vram", type=float, metavar="GB", help="Minimum VRAM per GPU")
+    compare_parser.add_argument("--count", "-n", type=int, metavar="N", help="Exact number of GPUs")
+    compare_parser.add_argument("--hours", type=float, metavar="H", help="Also show the total for an H-hour job")
+    compare_parser.add_argument("--currency", "-c", metavar="CODE", help="Display currency")
+    compare_parser.add_argument("--limit", type=int, default=15, help="Rows to show (default: 15)")
+    compare_parser.add_argument("--no-vast", action="store_true", help="Only compare static entries")
+
+    offers_parser = subparsers.add_parser("offers", help="Manage static GPU price entries")
+    actions = offers_parser.add_subparsers(dest="action")
+    actions.add_parser("list", help="List static entries")
+    add_parser = actions.add_parser("add", help="Add or replace a static entry")
+    add_parser.add_argument("provider", help="Provider name (e.g. lambda, runpod, onprem)")
+    add_parser.add_argument("gpu", help="GPU model")
+    add_parser.add_argument("--price", type=float, metavar="AMOUNT", help="Price per hour for the whole machine")
+    add_parser.add_argument("--currency", metavar="CODE", help="Currency of --price/--capex (default: USD)")
+    add_parser.add_argument("--count", "-n", type=int, default=1, metavar="N", help="Number of GPUs")
+    add_parser.add_argument("--vram", type=float, metavar="GB", help="VRAM per GPU")
+    add_parser.add_argument("--capex", type=float, metavar="AMOUNT", help="Hardware cost to amortize (on-prem)")
+    add_parser.add_argument("--lifetime-hours", type=float, metavar="H", help="Hours to amortize --capex over")
+    add_parser.add_argument("--note", help="Free-form note shown in comparisons")
+    remove_parser = actions.add_parser("remove", help="Remove static entries")
+    remove_parser.add_argument("provider", help="Provider name")
+    remove_parser.add_argument("gpu", help="GPU model")
+
+    history_parser = subparsers.add_parser("history", help="Observed Vast.ai GPU price history")
+    history_parser.add_argument("gpu", nargs="?", help="GPU model, matched loosely (omit to list tracked models)")
+    history_parser.add_argument("--days", type=int, default=30, help="Days of history (default: 30)")
+    history_parser.add_argument("--sample", action="store_true", help="Search offers now and record a sample first")
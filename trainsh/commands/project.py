@@ -0,0 +1,215 @@
+# tmux-trainsh project command
+# Group hosts, recipes, storages and default variables per research project
+
+import sys
+from typing import Dict, List, Optional
+
+from ..cli_utils import SubcommandSpec, dispatch_subcommand, prompt_input
+from .help_catalog import render_command_help
+from .help_cmd import reject_subcommand_help
+
+SUBCOMMAND_SPECS = (
+    SubcommandSpec("list", "List projects and their member counts."),
+    SubcommandSpec("show", "Inspect one project and its recent jobs."),
+    SubcommandSpec("add", "Create a project or add members and variables to it."),
+    SubcommandSpec("use", "Scope listings and recipe runs to one project."),
+    SubcommandSpec("remove", "Detach members from a project, or delete it."),
+)
+
+usage = render_command_help("project")
+
+_MEMBER_FLAGS = {"--host": "hosts", "--recipe": "recipes", "--storage": "storages"}
+
+
+def _fail(message: str) -> None:
+    print(message)
+    print(usage)
+    sys.exit(1)
+
+
+def _parse_members(args: List[str], *, allow_values: bool) -> tuple:
+    """Collect `--host/--recipe/--storage NAME`, `--var` and `--description` options."""
+    members: Dict[str, List[str]] = {kind: [] for kind in _MEMBER_FLAGS.values()}
+    variables: Dict[str, str] = {}
+    description: Optional[str] = None
+    i = 0
+    while i < len(args):
+        arg = args[i]
+        if i + 1 >= len(args) or arg not in (*_MEMBER_FLAGS, "--var", "--description"):
+            _fail(f"Unknown option: {arg}")
+        value = args[i + 1]
+        i += 2
+        if arg in _MEMBER_FLAGS:
+            members[_MEMBER_FLAGS[arg]].append(value)
+        elif arg == "--description":
+            description = value
+        elif not allow_values:
+            variables[value] = ""
+        else:
+            key, sep, var_value = value.partition("=")
+            if not sep or not key:
+                _fail(f"Invalid --var value: {value} (expected NAME=VALUE)")
+            variables[key] = var_value
+    return members, variables, description
+
+
+def _recent_jobs(project, limit: int = 10) -> list:
+    from ..core.job_state import JobStateManager
+    from ..services.projects import recipe_key
+
+    recipes = {recipe_key(item) for item in project.recipes}
+    return [job for job in JobStateManager().list_all(limit=100) if recipe_key(job.recipe_name) in recipes][:limit]
+
+
+def cmd_list(args: List[str]) -> None:
+    """List projects."""
+    from ..services.projects import active_project_name, load_projects
+
+    del args
+    projects = load_projects()
+    if not projects:
+        print("No projects configured.")
+        print("Use 'train project add <name>' to create one.")
+        return
+    active = active_project_name()
+    print("Projects:")
+    print("-" * 60)
+    for name, project in projects.items():
+        mark = "*" if name == active else " "
+        counts = f"{len(project.hosts)} hosts, {len(project.recipes)} recipes, {len(project.storages)} storages"
+        print(f" {mark}{name:<20} {counts}  {project.description}".rstrip())
+    print("-" * 60)
+    print(f"Total: {len(projects)} projects")
+    if active:
+        print(f"Active: {active} (listings and recipe runs are scoped to it)")
+
+
+def cmd_show(args: List[str]) -> None:
+    """Show one project."""
+    from ..services.projects import load_projects
+
+    if len(args) != 1:
+        _fail("Usage: train project show <name>")
+    projects = load_projects()
+    if args[0] not in projects:
+        print(f"Project not found: {args[0]}")
+        sys.exit(1)
+    project = projects[args[0]]
+    print(f"Project: {project.name}")
+    if project.description:
+        print(f"  Description: {project.description}")
+    for kind in ("hosts", "recipes", "storages"):
+        print(f"  {kind.capitalize()}: {', '.join(getattr(project, kind)) or '-'}")
+    if project.variables:
+        print("  Variables:")
+        for key, value in project.variables.items():
+            print(f"    {key} = {value}")
+    jobs = _recent_jobs(project)
+    if jobs:
+        print("  Recent jobs:")
+        for job in jobs:
+            print(f"    {job.job_id[:8]:<10} {job.recipe_name[:23]:<25} {job.status:<10} {job.updated_at[:19]}")
+
+
+def cmd_add(args: List[str]) -> None:
+    """Create a project or extend an existing one."""
+    from ..services.projects import Project, load_projects, sanitize_project_name, save_projects
+
+    if not args or args[0].startswith("-"):
+        _fail("Usage: train project add <name> [options]")
+    name = sanitize_project_name(args[0])
+    if not name:
+        _fail(f"Invalid project name: {args[0]}")
+    members, variables, description = _parse_members(args[1:], allow_values=True)
+
+    projects = load_projects()
+    created = name not in projects
+    project = projects.get(name) or Project(name=name)
+    for kind, names in members.items():
+        project.add(kind, names)
+    project.variables.update(variables)
+    if description is not None:
+        project.description = description
+    projects[name] = project
+    save_projects(projects)
+    print(f"{'Created' if created else 'Updated'} project: {name}")
+
+
+def cmd_use(args: List[str]) -> None:
+    """Set or clear the active project."""
+    from ..config import set_config_value
+    from ..services.projects import load_projects
+
+    if len(args) != 1:
+        _fail("Usage: train project use <name>|--clear")
+    if args[0] == "--clear":
+        set_config_value("project.active", "")
+        print("No active project; listings show everything.")
+        return
+    if args[0] not in load_projects():
+        print(f"Project not found: {args[0]}")
+        sys.exit(1)
+    set_config_value("project.active", args[0])
+    print(f"Active project: {args[0]}")
+
+
+def cmd_rm(args: List[str]) -> None:
+    """Detach members from a project, or delete it when no options are given."""
+    from ..config import get_config_value, set_config_value
+    from ..services.projects import load_projects, save_projects
+
+    if not args or args[0].startswith("-"):
+        _fail("Usage: train project remove <name> [options]")
+    name = args[0]
+    projects = load_projects()
+    if name not in projects:
+        print(f"Project not found: {name}")
+        sys.exit(1)
+
+    if len(args) > 1:
+        members, variables, _ = _parse_members(args[1:], allow_values=False)
+        project = projects[name]
+        for kind, names in members.items():
+            project.discard(kind, names)
+        for key in variables:
+            project.variables.pop(key, None)
+        save_projects(projects)
+        print(f"Updated project: {name}")
+        return
+
+    confirm = prompt_input(f"Remove project '{name}'? Hosts, recipes and storages are kept. (y/N): ")
+    if confirm is None or confirm.lower() != "y":
+        print("Cancelled.")
+        return
+    del projects[name]
+    save_projects(projects)
+    if get_config_value("project.active", "") == name:
+        set_config_value("project.active", "")
+    print(f"Project removed: {name}")
+
+
+def main(args: List[str]) -> Optional[str]:
+    """Main entry point for project command."""
+    if not args:
+        print(usage)
+        return None
+    if args[0] in ("-h", "--help", "help"):
+        reject_subcommand_help()
+
+    commands = {
+        "list": cmd_list,
+        "show": cmd_show,
+        "add": cmd_add,
+        "use": cmd_use,
+        "remove": cmd_rm,
+    }
+
+    try:
+        handler = dispatch_subcommand(args[0], commands=commands)
+    except KeyError:
+        print(f"Unknown subcommand: {args[0]}")
+        print(usage)
+        sys.exit(1)
+
+    handler(args[1:])
+    return None
@@ -43,7 +43,7 @@ def _is_recipe_filename(filename: str) -> bool:
 def _candidate_recipe_paths(root: str, name: str) -> list[str]:
     if _is_recipe_filename(name):
         return [os.path.join(root, name)]
-    return [os.path.join(root, f"{name}{RECIPE_FILE_EXTENSION}")]
+    return [os.path.join(root, f"{name}{extension}") for extension in RECIPE_FILE_EXTENSIONS]
 
 
 def get_recipes_dir() -> str:
@@ -197,14 +197,19 @@ def _is_bundled_example(path: Optional[str]) -> bool:
 
 def cmd_list(args: List[str]) -> None:
     """List available recipes."""
+    from ..services.projects import active_project, scope_note, scoped
+
     del args
-    recipes = list_recipes()
-    examples = list_examples()
+    project = active_project()
+    all_recipes = list_recipes() + list_examples()
+    recipes = scoped(list_recipes(), "recipes", project)
+    examples = scoped(list_examples(), "recipes", project)
+    note = scope_note(project, "recipes", len(all_recipes) - len(recipes) - len(examples))
 
     print("Recipes:")
     if not recipes and not examples:
         print("No recipes found.")
-        print(f"Create recipes in: {get_recipes_dir()}")
+        print(note or f"Create recipes in: {get_recipes_dir()}")
         return
 
     if recipes:
@@ -223,6 +228,9 @@ def cmd_list(args: List[str]) -> None:
             print(f"  {example_name.rsplit('.', 1)[0]}")
         print("-" * 40)
         print(f"Total: {len(examples)} examples")
+    if note:
+        print()
+        print(note)
 
 
 def cmd_show(args: List[str]) -> None:
@@ -271,10 +279,10 @@ def cmd_show(args: List[str]) -> None:
         print(source.rstrip())
         return
 
-    from ..pyrecipe import load_python_recipe
+    from ..pyrecipe import load_recipe
 
     try:
-        loaded_recipe = load_python_recipe(recipe_path)
+        loaded_recipe = load_recipe(recipe_path)
         show_steps = [step.raw for step in loaded_recipe.steps]
 
         print(f"Recipe: {loaded_recipe.name}")
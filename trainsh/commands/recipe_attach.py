@@ -0,0 +1,91 @@
+"""`train recipe attach`: attach to a job's window and re-attach when the connection drops."""
+
+from __future__ import annotations
+
+import dataclasses
+import sys
+from typing import List
+
+USAGE = "Usage: train recipe attach <job-id> <window> [--no-reconnect]"
+_VALUE_OPTIONS = ("--host", "--session", "--command")
+
+
+def _window_target(job_id: str, window: str) -> tuple[str, str, str]:
+    """(job id, host spec, tmux session) of one window of a stored job."""
+    from ..core.job_state import JobStateManager
+    from .recipe_views import _resolve_job_id, _window_session_name
+
+    job = JobStateManager().load(_resolve_job_id(job_id, states=True))
+    if job is None:
+        print(f"No job matches: {job_id}")
+        sys.exit(1)
+    names = list(job.hosts)
+    if window not in job.hosts:
+        print(f"Job {job.job_id[:8]} has no window @{window} (windows: {', '.join(names) or 'none'})")
+        sys.exit(1)
+    return job.job_id, job.hosts[window], _window_session_name(job, window, names.index(window))
+
+
+def cmd_attach(args: List[str]) -> None:
+    """Attach to a remote window's tmux session under the `terminal` reconnect policy."""
+    from ..config import load_config
+    from ..core.executor_utils import _build_ssh_args
+    from ..core.remote_tmux import RemoteTmuxClient
+    from ..services.terminal_keepalive import TerminalSupervisor, keepalive_policy, shell_attach
+
+    options = {}
+    positional: List[str] = []
+    reconnect = True
+    i = 0
+    while i < len(args):
+        arg = args[i]
+        if arg in _VALUE_OPTIONS and i + 1 < len(args):
+            options[arg[2:]] = args[i + 1]
+            i += 2
+            continue
+        if arg == "--no-reconnect":
+            reconnect = False
+        elif arg.startswith("-") and arg != "-":
+            print(USAGE)
+            sys.exit(1)
+        else:
+            positional.append(arg)
+        i += 1
+    if len(positional) != 2:
+        print(USAGE)
+        sys.exit(1)
+    job_id, window = positional
+
+    if "host" in options and "session" in options:
+        host, session = options["host"], options["session"]
+    else:
+        job_id, host, session = _window_target(job_id, window)
+
+    if host == "local":
+        from ..core.local_tmux import LocalTmuxClient
+
+        sys.exit(shell_attach(LocalTmuxClient().build_attach_command(session, nested=False))())
+
+    config = load_config()
+    client = RemoteTmuxClient(host, _build_ssh_args)
+    command = options.get("command") or client.build_attach_command(session, status_mode="keep")
+    policy = keepalive_policy(config)
+    if not reconnect:
+        policy = dataclasses.replace(policy, reconnect=False)
+
+    def probe() -> bool:
+        # Exit 1 just means the session is gone; the attach command recreates it.
+        return client.run("has-session", "-t", session, timeout=max(5, policy.interval_secs)).returncode in (0, 1)
+
+    supervisor = TerminalSupervisor(
+        shell_attach(command),
+        probe,
+        window=window,
+        host=host,
+        run_id="" if job_id == "-" else job_id,
+        policy=policy,
+    )
+    sys.exit(supervisor.run())
+
+
+__all__ = ["cmd_attach"]
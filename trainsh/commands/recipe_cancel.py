@@ -0,0 +1,94 @@
+"""`train recipe cancel`: stop a running job and the remote commands it started."""
+
+from __future__ import annotations
+
+import os
+import signal
+import sys
+import time
+from typing import List
+
+USAGE = "Usage: train recipe cancel [job-id] [--keep-sessions]"
+RUNNER_EXIT_WAIT_SECS = 30
+
+
+def _wait_for_exit(pid: int, timeout: float) -> bool:
+    from ..core.job_state import _pid_alive
+
+    deadline = time.time() + timeout
+    while time.time() < deadline:
+        if not _pid_alive(pid):
+            return True
+        time.sleep(0.5)
+    return not _pid_alive(pid)
+
+
+def cancel_job(job, *, close_sessions: bool = True) -> List[str]:
+    """Interrupt the job's runner, then kill whatever it left running remotely."""
+    from ..core.executor_utils import _build_ssh_args
+    from ..core.job_state import JobStateManager, _pid_alive
+    from ..core.remote_cancel import cancel_inflight
+    from ..core.remote_tmux import RemoteTmuxClient
+    from ..core.local_tmux import LocalTmuxClient
+
+    manager = JobStateManager()
+    if job.pid and job.pid != os.getpid() and _pid_alive(job.pid):
+        print(f"Interrupting runner pid {job.pid}...")
+        os.kill(job.pid, signal.SIGINT)
+        if not _wait_for_exit(job.pid, RUNNER_EXIT_WAIT_SECS):
+            print(f"Runner pid {job.pid} did not exit; stopping its commands directly.")
+        job = manager.load(job.job_id) or job
+
+    reports: List[str] = []
+    for step_num, info in sorted(job.inflight_steps.items(), key=lambda item: int(item[0])):
+        host = str(info.get("host", "") or "local")
+        client = None
+        if close_sessions:
+            client = LocalTmuxClient() if host == "local" else RemoteTmuxClient(host, _build_ssh_args)
+        report = cancel_inflight(info, build_ssh_args=_build_ssh_args, tmux_client=client)
+        print(f"  step {step_num}: {report}")
+        reports.append(report)
+
+    job.inflight_steps = {}
+    job.status = "cancelled"
+    job.outcome = "cancelled"
+    manager.save(job)
+    return reports
+
+
+def cmd_cancel(args: List[str]) -> None:
+    """Cancel one running recipe job."""
+    from ..core.job_state import JobStateManager
+
+    close_sessions = True
+    job_id = ""
+    for arg in args:
+        if arg == "--keep-sessions":
+            close_sessions = False
+        elif arg.startswith("-") or job_id:
+            print(USAGE)
+            sys.exit(1)
+        else:
+            job_id = arg
+
+    running = JobStateManager().list_running()
+    if job_id:
+        running = [job for job in running if job.job_id.startswith(job_id)]
+    if not running:
+        print(f"No running job matches: {job_id}" if job_id else "No running recipe jobs.")
+        sys.exit(1 if job_id else 0)
+    if len(running) > 1:
+        print("Multiple running jobs; pass a job id:")
+        for job in running:
+            print(f"  {job.job_id[:8]}  {job.recipe_name}")
+        sys.exit(1)
+
+    job = running[0]
+    print(f"Cancelling job {job.job_id} ({job.recipe_name})")
+    reports = cancel_job(job, close_sessions=close_sessions)
+    if not reports:
+        print("  no in-flight commands")
+    print("Job cancelled.")
+
+
+__all__ = ["cancel_job", "cmd_cancel"]
@@ -44,6 +44,24 @@ def main(args: List[str]) -> Optional[str]:
         cmd_resume(subargs)
         return None
 
+    if subcommand == "reconnect":
+        from .recipe_reconnect import cmd_reconnect
+
+        cmd_reconnect(subargs)
+        return None
+
+    if subcommand == "attach":
+        from .recipe_attach import cmd_attach
+
+        cmd_attach(subargs)
+        return None
+
+    if subcommand == "cancel":
+        from .recipe_cancel import cmd_cancel
+
+        cmd_cancel(subargs)
+        return None
+
     if subcommand == "status":
         from .recipe_runtime import cmd_status
 
@@ -56,6 +74,84 @@ def main(args: List[str]) -> Optional[str]:
         cmd_logs(subargs)
         return None
 
+    if subcommand == "timeline":
+        from .recipe_views import cmd_timeline
+
+        cmd_timeline(subargs)
+        return None
+
+    if subcommand == "energy":
+        from .recipe_views import cmd_energy
+
+        cmd_energy(subargs)
+        return None
+
+    if subcommand == "tail":
+        from .recipe_views import cmd_tail
+
+        cmd_tail(subargs)
+        return None
+
+    if subcommand == "output":
+        from .recipe_views import cmd_output
+
+        cmd_output(subargs)
+        return None
+
+    if subcommand == "trust":
+        from .recipe_trust import cmd_trust
+
+        cmd_trust(subargs)
+        return None
+
+    if subcommand == "test":
+        from .recipe_test import cmd_test
+
+        cmd_test(subargs)
+        return None
+
+    if subcommand == "convert":
+        from .recipe_convert import cmd_convert
+
+        cmd_convert(subargs)
+        return None
+
+    if subcommand == "graph":
+        from .recipe_graph import cmd_graph
+
+        cmd_graph(subargs)
+        return None
+
+    if subcommand == "schema":
+        from .recipe_schema_cmd import cmd_schema
+
+        cmd_schema(subargs)
+        return None
+
+    if subcommand == "operations":
+        from .recipe_operations import cmd_operations
+
+        cmd_operations(subargs)
+        return None
+
+    if subcommand == "lint":
+        from .recipe_lint import cmd_lint
+
+        cmd_lint(subargs)
+        return None
+
+    if subcommand == "vars":
+        from .recipe_vars import cmd_vars
+
+        cmd_vars(subargs)
+        return None
+
+    if subcommand == "screen":
+        from .recipe_views import cmd_screen
+
+        cmd_screen(subargs)
+        return None
+
     if subcommand == "jobs":
         from .recipe_runtime import cmd_jobs
 
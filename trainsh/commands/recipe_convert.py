@@ -0,0 +1,73 @@
+"""`train recipe convert`: rewrite a recipe between the Python and YAML formats."""
+
+from __future__ import annotations
+
+from pathlib import Path
+from typing import List, Optional
+
+from .help_catalog import render_command_help
+
+HELP_FLAGS = {"-h", "--help", "help"}
+USAGE = "Usage: train recipe convert <name> [--to yaml|python] [-o FILE]"
+FORMATS = ("yaml", "python")
+
+
+def cmd_convert(args: List[str]) -> None:
+    """Print (or write) a recipe in the other format; YAML input becomes Python and vice versa."""
+    if args and args[0] in HELP_FLAGS:
+        print(render_command_help("recipe"))
+        return
+
+    from ..pyrecipe import load_recipe
+    from ..pyrecipe.yaml_recipe import dump_yaml_recipe, is_yaml_recipe, render_python_recipe
+    from .recipe import find_recipe
+
+    target: Optional[str] = None
+    output: Optional[str] = None
+    positional: List[str] = []
+    i = 0
+    while i < len(args):
+        arg = args[i]
+        if arg in ("--to", "-o", "--output"):
+            if i + 1 >= len(args):
+                print(f"Missing value for {arg}.")
+                raise SystemExit(1)
+            i += 1
+            if arg == "--to":
+                target = args[i]
+            else:
+                output = args[i]
+        elif arg.startswith("-"):
+            print(f"Unknown option: {arg}")
+            print(USAGE)
+            raise SystemExit(1)
+        else:
+            positional.append(arg)
+        i += 1
+    if len(positional) != 1:
+        print(USAGE)
+        raise SystemExit(1)
+    if target is not None and target not in FORMATS:
+        print(f"Unknown format: {target} (use {' or '.join(FORMATS)})")
+        raise SystemExit(1)
+
+    path = find_recipe(positional[0])
+    if not path:
+        print(f"Recipe not found: {positional[0]}")
+        raise SystemExit(1)
+    try:
+        recipe = load_recipe(path)
+    except Exception as exc:
+        print(f"Error loading recipe: {exc}")
+        raise SystemExit(1)
+
+    target = target or ("python" if is_yaml_recipe(path) else "yaml")
+    text = dump_yaml_recipe(recipe) if target == "yaml" else render_python_recipe(recipe)
+    if output:
+        Path(output).expanduser().write_text(text, encoding="utf-8")
+        print(f"Wrote {output}")
+        return
+    print(text, end="")
+
+
+__all__ = ["cmd_convert"]
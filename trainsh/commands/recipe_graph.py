@@ -0,0 +1,71 @@
+"""`train recipe graph`: print a recipe's step DAG as Mermaid or DOT."""
+
+from __future__ import annotations
+
+from pathlib import Path
+from typing import List, Optional
+
+from .help_catalog import render_command_help
+
+HELP_FLAGS = {"-h", "--help", "help"}
+USAGE = "Usage: train recipe graph <name> [--format mermaid|dot] [-o FILE]"
+
+
+def cmd_graph(args: List[str]) -> None:
+    """Render one recipe's DAG to stdout or a file."""
+    if args and args[0] in HELP_FLAGS:
+        print(render_command_help("recipe"))
+        return
+
+    from ..core.recipe_graph import GRAPH_FORMATS, render_recipe_graph
+    from ..pyrecipe import load_recipe
+    from .recipe import find_recipe
+
+    fmt = "mermaid"
+    output: Optional[str] = None
+    positional: List[str] = []
+    i = 0
+    while i < len(args):
+        arg = args[i]
+        if arg in ("--format", "-f", "-o", "--output"):
+            if i + 1 >= len(args):
+                print(f"Missing value for {arg}.")
+                raise SystemExit(1)
+            i += 1
+            if arg in ("--format", "-f"):
+                fmt = args[i]
+            else:
+                output = args[i]
+        elif arg.startswith("-"):
+            print(f"Unknown option: {arg}")
+            print(USAGE)
+            raise SystemExit(1)
+        else:
+            positional.append(arg)
+        i += 1
+    if len(positional) != 1:
+        print(USAGE)
+        raise SystemExit(1)
+    if fmt not in GRAPH_FORMATS:
+        print(f"Unknown format: {fmt} (use {' or '.join(GRAPH_FORMATS)})")
+        raise SystemExit(1)
+
+    path = find_recipe(positional[0])
+    if not path:
+        print(f"Recipe not found: {positional[0]}")
+        raise SystemExit(1)
+    try:
+        recipe = load_recipe(path)
+    except Exception as exc:
+        print(f"Error loading recipe: {exc}")
+        raise SystemExit(1)
+
+    text = render_recipe_graph(recipe, fmt)
+    if output:
+        Path(output).expanduser().write_text(text, encoding="utf-8")
+        print(f"Wrote {output}")
+        return
+    print(text, end="")
+
+
+__all__ = ["cmd_graph"]
@@ -0,0 +1,51 @@
+"""`train recipe lint`: best-practice warnings for recipes."""
+
+from __future__ import annotations
+
+import json
+from typing import List
+
+from .help_catalog import render_command_help
+
+HELP_FLAGS = {"-h", "--help", "help"}
+USAGE = "Usage: train recipe lint <name>... [--json] [--strict]"
+
+
+def cmd_lint(args: List[str]) -> None:
+    """Lint recipes; exit 1 on errors (or on warnings too with --strict)."""
+    if args and args[0] in HELP_FLAGS:
+        print(render_command_help("recipe"))
+        return
+
+    from ..core.recipe_lint import lint_file
+    from .recipe import find_recipe
+
+    flags = [arg for arg in args if arg.startswith("-")]
+    names = [arg for arg in args if not arg.startswith("-")]
+    if not names or set(flags) - {"--json", "--strict"}:
+        print(USAGE)
+        raise SystemExit(1)
+    failing = ("error", "warning") if "--strict" in flags else ("error",)
+    failed = False
+    report = {}
+    for name in names:
+        path = find_recipe(name)
+        if not path:
+            print(f"Recipe not found: {name}")
+            raise SystemExit(1)
+        findings = lint_file(path)
+        failed = failed or any(item.severity in failing for item in findings)
+        report[name] = findings
+    if "--json" in flags:
+        print(json.dumps({name: [item.to_dict() for item in findings] for name, findings in report.items()}, indent=2))
+    else:
+        for name, findings in report.items():
+            if not findings:
+                print(f"{name}: ok")
+            for item in findings:
+                print(f"{name}: {item}")
+    if failed:
+        raise SystemExit(1)
+
+
+__all__ = ["cmd_lint"]
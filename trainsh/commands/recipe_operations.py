@@ -0,0 +1,58 @@
+"""`train recipe operations`: list the step operations recipes can use."""
+
+from __future__ import annotations
+
+import json
+from typing import List
+
+from .help_catalog import render_command_help
+
+HELP_FLAGS = {"-h", "--help", "help"}
+USAGE = "Usage: train recipe operations [name] [--json]"
+
+
+def _print_details(operation: dict) -> None:
+    print(f"{operation['name']}  ({operation['step'] or 'step'}, runs via {operation['runs_via']})")
+    if operation["description"]:
+        print(f"  {operation['description']}")
+    if operation["alias_of"]:
+        print(f"  Same step as: {operation['alias_of']}")
+    print(f"  Targets: {', '.join(operation['targets'])}")
+    if not operation["fields"]:
+        return
+    print("  Fields:")
+    for item in operation["fields"]:
+        default = "required" if item["required"] else f"default {json.dumps(item['default'])}"
+        print(f"    {item['name']:<20} {item['type']:<28} {default}")
+
+
+def cmd_operations(args: List[str]) -> None:
+    """Print the operations catalog as a table, one operation's fields, or JSON."""
+    if args and args[0] in HELP_FLAGS:
+        print(render_command_help("recipe"))
+        return
+
+    from ..core.operations_catalog import operations_catalog
+
+    as_json = "--json" in args
+    rest = [arg for arg in args if arg != "--json"]
+    if len(rest) > 1 or any(arg.startswith("-") for arg in rest):
+        print(USAGE)
+        raise SystemExit(1)
+    operations = operations_catalog(rest[0] if rest else None)
+    if rest and not operations:
+        print(f"Unknown operation: {rest[0]}")
+        raise SystemExit(1)
+    if as_json:
+        print(json.dumps(operations, indent=2))
+        return
+    if rest:
+        for operation in operations:
+            _print_details(operation)
+        return
+    print(f"{'Operation':<26} {'Step':<28} {'Runs via':<9} Targets")
+    for operation in operations:
+        print(f"{operation['name']:<26} {operation['step']:<28} {operation['runs_via']:<9} {', '.join(operation['targets'])}")
+
+
+__all__ = ["cmd_operations"]
@@ -0,0 +1,162 @@
+"""`train recipe reconnect`: continue jobs whose `train` process died."""
+
+from __future__ import annotations
+
+import fcntl
+import os
+import subprocess
+import sys
+from typing import List, Optional
+
+from .runtime_dispatch import run_recipe_via_dag
+
+USAGE = "Usage: train recipe reconnect [job-id] [--dry-run]"
+LOG_DIRNAME = "reconnect"
+# One lock for every claim, so no per-job lock files pile up.
+CLAIM_LOCK = ".claim.lock"
+# Commands that start recipe work; only these scan for orphaned jobs on startup.
+_WORK_COMMANDS = {
+    ("run",),
+    ("exec",),
+    ("recipe", "run"),
+    ("recipe", "exec"),
+    ("recipe", "schedule", "run"),
+}
+
+
+def _describe(job) -> str:
+    inflight = ", ".join(f"step {num}" for num in sorted(job.inflight_steps, key=int)) or "none"
+    return (
+        f"{job.job_id[:8]}  {job.recipe_name:<20} step {job.current_step + 1}/{job.total_steps}"
+        f"  pid {job.pid}  in-flight: {inflight}"
+    )
+
+
+def reconnect_job(job) -> bool:
+    """Resume one orphaned job, re-attaching to commands still running in tmux."""
+    print(f"Reconnecting job {job.job_id} ({job.recipe_name})")
+    if job.inflight_steps:
+        print(f"Re-attaching to in-flight step(s): {', '.join(sorted(job.inflight_steps, key=int))}")
+    print("-" * 40)
+    result = run_recipe_via_dag(
+        job.recipe_path,
+        resume=True,
+        job_id=job.job_id,
+        initial_session_index=job.next_window_index,
+    )
+    print("-" * 40)
+    print("Recipe completed successfully!" if result.success else "Recipe execution failed.")
+    return bool(result.success)
+
+
+def _claim_and_spawn(manager, job, log_dir: str) -> str:
+    """Spawn a reconnect process for `job` unless another `train` claimed it first.
+
+    Callers hold the claim lock, so the check and the pid hand-over cannot
+    interleave with another startup's.
+    """
+    from ..core.job_state import _pid_alive
+
+    current = manager.load(job.job_id)
+    if current is None or current.status != "running" or (current.pid and _pid_alive(current.pid)):
+        return ""
+    log_path = os.path.join(log_dir, f"{job.job_id}.log")
+    with open(log_path, "a", encoding="utf-8") as log:
+        process = subprocess.Popen(
+            [sys.executable, "-m", "trainsh", "recipe", "reconnect", job.job_id],
+            stdin=subprocess.DEVNULL,
+            stdout=log,
+            stderr=subprocess.STDOUT,
+            start_new_session=True,
+        )
+    current.pid = process.pid
+    manager.save(current)
+    return log_path
+
+
+def reconnect_in_background(manager=None, *, state_dir=None) -> List[str]:
+    """Start a detached `train recipe reconnect <job>` for every orphaned job.
+
+    Each job is claimed by saving the new process id before returning, so the
+    next `train` invocation no longer sees it as orphaned.
+    """
+    from ..constants import RUNTIME_STATE_DIR
+    from ..core.job_state import JobStateManager
+
+    manager = manager or JobStateManager()
+    if not manager.list_orphaned():
+        return []
+    log_dir = os.path.join(str(state_dir or RUNTIME_STATE_DIR), LOG_DIRNAME)
+    os.makedirs(log_dir, exist_ok=True)
+    started = []
+    with open(os.path.join(log_dir, CLAIM_LOCK), "a", encoding="utf-8") as lock:
+        fcntl.flock(lock, fcntl.LOCK_EX)
+        for job in manager.list_orphaned():
+            log_path = _claim_and_spawn(manager, job, log_dir)
+            if not log_path:
+                continue
+            print(f"Reconnecting orphaned job {job.job_id[:8]} ({job.recipe_name}) in the background; log: {log_path}")
+            started.append(job.job_id)
+    return started
+
+
+def auto_reconnect(args: List[str]) -> Optional[List[str]]:
+    """Startup hook: reconnect orphaned jobs before a command that runs recipe work."""
+    from ..config import get_config_value
+
+    words = tuple(args[1:4])
+    if "-h" in args[2:] or "--help" in args[2:]:
+        return None
+    if not any(words[: len(command)] == command for command in _WORK_COMMANDS):
+        return None
+    if not get_config_value("recipe.auto_reconnect", True):
+        return None
+    try:
+        return reconnect_in_background()
+    except Exception as exc:
+        print(f"Warning: orphaned job scan failed: {exc}")
+        return None
+
+
+def cmd_reconnect(args: List[str]) -> None:
+    """Find running jobs without a live runner and continue them."""
+    from ..core.job_state import JobStateManager
+
+    dry_run = False
+    job_id = ""
+    for arg in args:
+        if arg == "--dry-run":
+            dry_run = True
+        elif arg.startswith("-") or job_id:
+            print(USAGE)
+            sys.exit(1)
+        else:
+            job_id = arg
+
+    # A startup scan hands the job over by saving this process's pid on it.
+    orphaned = JobStateManager().list_orphaned(claimed_by=os.getpid())
+    if job_id:
+        orphaned = [job for job in orphaned if job.job_id.startswith(job_id)]
+        if not orphaned:
+            print(f"No orphaned job matches: {job_id}")
+            print("Use 'train recipe status' to list jobs.")
+            sys.exit(1)
+
+    if not orphaned:
+        print("No orphaned recipe jobs.")
+        return
+
+    if dry_run or len(orphaned) > 1:
+        print("Orphaned recipe jobs:")
+        for job in orphaned:
+            print(f"  {_describe(job)}")
+        if not dry_run:
+            print("Pass a job id to choose which one to reconnect.")
+            sys.exit(1)
+        return
+
+    if not reconnect_job(orphaned[0]):
+        sys.exit(1)
+
+
+__all__ = ["auto_reconnect", "cmd_reconnect", "reconnect_in_background", "reconnect_job"]
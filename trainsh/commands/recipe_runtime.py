@@ -20,6 +20,8 @@ from .recipe_shared import (
     SET_OPTION_FLAGS,
     WORKER_OPTION_FLAGS,
     _parse_assignment,
+    _parse_duration_flag,
+    _parse_gpu_guard_flag,
     _parse_int_flag,
     _print_resume_usage,
     _print_exec_usage,
@@ -307,6 +309,28 @@ def _parse_runtime_options(rest_args: List[str]) -> tuple[dict, dict, list[str],
             except (json.JSONDecodeError, ValueError) as exc:
                 print(f"Invalid --executor-options: {exc}")
                 raise SystemExit(1)
+        elif arg == "--strict-vars":
+            executor_kwargs["strict_variables"] = True
+        elif arg == "--preflight":
+            executor_kwargs["preflight"] = True
+        elif arg == "--preflight-install":
+            executor_kwargs["preflight"] = "install"
+        elif arg.startswith("--gpu-guard="):
+            executor_kwargs["gpu_guard"] = _parse_gpu_guard_flag(arg.split("=", 1)[1])
+        elif arg == "--gpu-guard":
+            if i + 1 >= len(rest_args):
+                print("Missing value for --gpu-guard.")
+                raise SystemExit(1)
+            i += 1
+            executor_kwargs["gpu_guard"] = _parse_gpu_guard_flag(rest_args[i])
+        elif arg.startswith("--max-runtime="):
+            executor_kwargs["max_runtime"] = _parse_duration_flag(arg.split("=", 1)[1], flag_name="--max-runtime")
+        elif arg == "--max-runtime":
+            if i + 1 >= len(rest_args):
+                print("Missing value for --max-runtime.")
+                raise SystemExit(1)
+            i += 1
+            executor_kwargs["max_runtime"] = _parse_duration_flag(rest_args[i], flag_name="--max-runtime")
         elif arg.startswith("--callback="):
             parts = [part.strip() for part in arg.split("=", 1)[1].split(",") if part.strip()]
             if not parts:
@@ -331,6 +355,44 @@ def _parse_runtime_options(rest_args: List[str]) -> tuple[dict, dict, list[str],
     return host_overrides, var_overrides, pick_hosts, callbacks, executor, executor_kwargs
 
 
+def _load_recipe_for_run(recipe_path: str):
+    """Load the recipe once for pre-run checks; None lets the run report load errors."""
+    from ..pyrecipe import load_python_recipe
+    from ..pyrecipe.yaml_recipe import is_yaml_recipe, load_yaml_recipe
+
+    try:
+        return load_yaml_recipe(recipe_path) if is_yaml_recipe(recipe_path) else load_python_recipe(recipe_path)
+    except Exception:
+        return None
+
+
+def _prompt_missing_variables(recipe, var_overrides: dict) -> dict:
+    """Collect values for `${NAME}` references the recipe never defines."""
+    from ..config import get_config_value
+    from ..core.recipe_variables import find_unresolved_variables, variable_report
+
+    if recipe is None:
+        return {}
+    missing = find_unresolved_variables(recipe, var_overrides)
+    if not missing:
+        return {}
+    if not (get_config_value("recipe.prompt_missing_variables", False) and sys.stdin.isatty()):
+        print(f"Warning: undefined variables: {', '.join(missing)} (use --set NAME=VALUE)")
+        return {}
+
+    print("This recipe references undefined variables:")
+    report = {info.name: info for info in variable_report(recipe)}
+    collected = {}
+    for name in missing:
+        usages = report[name].usages if name in report else []
+        if usages:
+            print(f"  {name}: used in " + "; ".join(usage.describe() for usage in usages[:3]))
+        value = input(f"  {name} = ").strip()
+        if value:
+            collected[name] = value
+    return collected
+
+
 def _execute_recipe_path(
     recipe_path: str,
     *,
@@ -371,7 +433,17 @@ def _execute_recipe_path(
     ):
         return
 
+    from ..services.projects import project_for_recipe
+
+    project = project_for_recipe(recipe_path)
+    if project is not None and project.variables:
+        var_overrides = {**project.variables, **var_overrides}
+    loaded_recipe = _load_recipe_for_run(recipe_path)
+    var_overrides.update(_prompt_missing_variables(loaded_recipe, var_overrides))
+
     print(announce_text or f"Running recipe: {os.path.basename(recipe_path)}")
+    if project is not None:
+        print(f"Project: {project.name}")
     print("Commands run in remote tmux sessions (survive SSH disconnect)")
 
     if host_overrides:
@@ -395,6 +467,7 @@ def _execute_recipe_path(
         executor_name=executor,
         executor_kwargs=executor_kwargs,
         callbacks=callbacks,
+        recipe=loaded_recipe,
     )
     success = result.success
 
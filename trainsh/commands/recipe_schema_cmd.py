@@ -0,0 +1,62 @@
+"""`train recipe schema`: export the recipe JSON Schema or check documents against it."""
+
+from __future__ import annotations
+
+import json
+from pathlib import Path
+from typing import List
+
+from .help_catalog import render_command_help
+
+HELP_FLAGS = {"-h", "--help", "help"}
+USAGE = "Usage: train recipe schema [--print | -o FILE | --check FILE...]"
+
+
+def _check_files(paths: List[str]) -> int:
+    from ..core.recipe_schema import validate_text
+
+    failed = 0
+    for raw in paths:
+        path = Path(raw).expanduser()
+        try:
+            text = path.read_text(encoding="utf-8")
+        except OSError as exc:
+            print(f"{raw}: cannot read ({exc.strerror or exc})")
+            failed += 1
+            continue
+        issues = validate_text(text)
+        if not issues:
+            print(f"{raw}: ok")
+            continue
+        failed += 1
+        for issue in issues:
+            print(f"{raw}:{issue}")
+    return failed
+
+
+def cmd_schema(args: List[str]) -> None:
+    """Write the schema to the data dir (default), print it, or validate files."""
+    if args and args[0] in HELP_FLAGS:
+        print(render_command_help("recipe"))
+        return
+
+    from ..core.recipe_schema import recipe_schema, write_recipe_schema
+
+    if not args:
+        print(f"Wrote {write_recipe_schema()}")
+        return
+    if args[0] == "--print" and len(args) == 1:
+        print(json.dumps(recipe_schema(), indent=2))
+        return
+    if args[0] in ("-o", "--output") and len(args) == 2:
+        print(f"Wrote {write_recipe_schema(Path(args[1]))}")
+        return
+    if args[0] == "--check" and len(args) > 1:
+        if _check_files(args[1:]):
+            raise SystemExit(1)
+        return
+    print(USAGE)
+    raise SystemExit(1)
+
+
+__all__ = ["cmd_schema"]
@@ -2,6 +2,8 @@
 
 from __future__ import annotations
 
+import re
+
 from .help_catalog import render_command_help
 from .help_cmd import reject_subcommand_help
 
@@ -65,3 +67,19 @@ def _parse_int_flag(raw: str, *, flag_name: str) -> int:
     except ValueError:
         print(f"Invalid {flag_name} value, must be integer.")
         raise SystemExit(1)
+
+
+def _parse_duration_flag(raw: str, *, flag_name: str) -> str:
+    value = str(raw or "").strip().lower()
+    if not re.fullmatch(r"\d+[smh]?", value):
+        print(f"Invalid {flag_name} value, expected a duration like 90s, 30m or 6h.")
+        raise SystemExit(1)
+    return value
+
+
+def _parse_gpu_guard_flag(raw: str) -> str:
+    value = str(raw or "").strip().lower()
+    if value not in {"off", "warn", "confirm", "fail"}:
+        print("Invalid --gpu-guard value, expected off, warn, confirm or fail.")
+        raise SystemExit(1)
+    return value
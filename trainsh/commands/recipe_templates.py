@@ -8,7 +8,7 @@ from textwrap import dedent
 _MINIMAL_TEMPLATE = """\
 from trainsh import Recipe, local
 
-recipe = Recipe("__NAME__", schedule="@every 30m", callbacks=["console", "jsonl"])
+recipe = Recipe("__NAME__", schedule="@every 30m", callbacks=["console", "jsonl"], safe_variables=True)
 message = "Hello from trainsh"
 
 with local.tmux("main") as tmux:
@@ -20,7 +20,7 @@ with local.tmux("main") as tmux:
 _REMOTE_TRAIN_TEMPLATE = """\
 from trainsh import Host, Recipe, Storage
 
-recipe = Recipe("__NAME__", callbacks=["console", "jsonl"])
+recipe = Recipe("__NAME__", callbacks=["console", "jsonl"], safe_variables=True)
 gpu = Host("placeholder", name="gpu")
 dataset = Storage("r2:replace-dataset-bucket", name="dataset")
 artifacts = Storage("r2:replace-artifact-bucket", name="artifacts")
@@ -0,0 +1,99 @@
+"""`train recipe test`: run a recipe's DAG against a mock file instead of real hosts."""
+
+from __future__ import annotations
+
+import json
+from typing import Dict, List, Optional
+
+from .help_catalog import render_command_help
+from .recipe_shared import _parse_assignment
+
+HELP_FLAGS = {"-h", "--help", "help"}
+USAGE = "Usage: train recipe test <name> [--mock FILE] [--set NAME=VALUE] [--executor NAME] [--json] [-v]"
+
+
+def _print_report(report, recipe_name: str) -> None:
+    print(f"Recipe test: {recipe_name} (job {report.job_id})")
+    width = max((len(step_id) for step_id in report.steps), default=4)
+    for step_id, row in report.steps.items():
+        mark = "✓" if row["state"] in {"success", "skipped"} else "✗"
+        attempts = row["attempts"]
+        print(f"  {mark} {step_id:<{width}}  {row['state']:<15} {attempts} attempt{'s' if attempts != 1 else ''}")
+    if report.passed:
+        print("PASS")
+        return
+    print("FAIL")
+    for line in report.failures:
+        print(f"  - {line}")
+
+
+def cmd_test(args: List[str]) -> None:
+    """Run one recipe with canned step results and check its expectations."""
+    if args and args[0] in HELP_FLAGS:
+        print(render_command_help("recipe"))
+        return
+
+    from ..core.mock_executor import MockSpec, MockSpecError, load_mock_spec, run_mock_recipe
+    from .recipe import find_recipe
+
+    mock_path: Optional[str] = None
+    executor_name: Optional[str] = None
+    var_overrides: Dict[str, str] = {}
+    as_json = verbose = False
+    positional: List[str] = []
+    i = 0
+    while i < len(args):
+        arg = args[i]
+        if arg in ("--mock", "--set", "--executor"):
+            if i + 1 >= len(args):
+                print(f"Missing value for {arg}.")
+                raise SystemExit(1)
+            i += 1
+            if arg == "--mock":
+                mock_path = args[i]
+            elif arg == "--executor":
+                executor_name = args[i]
+            else:
+                key, value = _parse_assignment(args[i], flag_name="--set")
+                var_overrides[key] = value
+        elif arg == "--json":
+            as_json = True
+        elif arg in ("-v", "--verbose"):
+            verbose = True
+        elif arg.startswith("-"):
+            print(f"Unknown option: {arg}")
+            print(USAGE)
+            raise SystemExit(1)
+        else:
+            positional.append(arg)
+        i += 1
+    if len(positional) != 1:
+        print(USAGE)
+        raise SystemExit(1)
+
+    path = find_recipe(positional[0])
+    if not path:
+        print(f"Recipe not found: {positional[0]}")
+        raise SystemExit(1)
+    try:
+        spec = load_mock_spec(mock_path) if mock_path else MockSpec()
+    except MockSpecError as exc:
+        print(f"Invalid mock file: {exc}")
+        raise SystemExit(1)
+
+    report = run_mock_recipe(
+        path,
+        spec,
+        var_overrides=var_overrides,
+        log_callback=print if verbose and not as_json else None,
+        executor_name=executor_name,
+    )
+    if as_json:
+        print(json.dumps(report.to_dict(), indent=2))
+    else:
+        _print_report(report, positional[0])
+    if not report.passed:
+        raise SystemExit(1)
+
+
+__all__ = ["cmd_test"]
@@ -0,0 +1,50 @@
+"""`train recipe trust`: mark recipe files whose local commands skip `local_policy` checks."""
+
+from __future__ import annotations
+
+import os
+from typing import List
+
+from .help_catalog import render_command_help
+
+HELP_FLAGS = {"-h", "--help", "help"}
+
+
+def cmd_trust(args: List[str]) -> None:
+    """Trust, revoke or list trusted recipe files."""
+    if args and args[0] in HELP_FLAGS:
+        print(render_command_help("recipe"))
+        return
+
+    from ..core.local_policy import is_trusted, load_trust, revoke_recipe, trust_recipe
+    from .recipe import find_recipe
+
+    usage = "Usage: train recipe trust <name> [--revoke] | train recipe trust --list"
+    if args == ["--list"] or not args:
+        records = load_trust()
+        if not records:
+            print("No trusted recipes.")
+            return
+        for path, record in sorted(records.items()):
+            state = "trusted" if is_trusted(path) else ("changed" if os.path.exists(path) else "missing")
+            print(f"{state:<8}  {record.get('trusted_at', '')}  {path}")
+        return
+
+    revoke = "--revoke" in args
+    names = [arg for arg in args if arg != "--revoke"]
+    if len(names) != 1 or names[0].startswith("-"):
+        print(usage)
+        raise SystemExit(1)
+    path = find_recipe(names[0])
+    if not path:
+        print(f"Recipe not found: {names[0]}")
+        raise SystemExit(1)
+    path = os.path.abspath(path)
+    if revoke:
+        print(f"Revoked trust: {path}" if revoke_recipe(path) else f"Not trusted: {path}")
+        return
+    record = trust_recipe(path)
+    print(f"Trusted {path} (sha256 {record['sha256'][:12]}); editing the file revokes it.")
+
+
+__all__ = ["cmd_trust"]
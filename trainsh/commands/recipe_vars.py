@@ -0,0 +1,58 @@
+"""`train recipe vars`: list a recipe's variables, their defaults and where they are used."""
+
+from __future__ import annotations
+
+import json
+from typing import List
+
+from .help_catalog import render_command_help
+
+HELP_FLAGS = {"-h", "--help", "help"}
+USAGE = "Usage: train recipe vars <name> [--json]"
+
+
+def cmd_vars(args: List[str]) -> None:
+    """Print the variable report of one recipe."""
+    if args and args[0] in HELP_FLAGS:
+        print(render_command_help("recipe"))
+        return
+
+    from ..core.recipe_variables import recipe_variable_report
+    from .recipe import find_recipe
+
+    flags = [arg for arg in args if arg.startswith("-")]
+    names = [arg for arg in args if not arg.startswith("-")]
+    if len(names) != 1 or set(flags) - {"--json"}:
+        print(USAGE)
+        raise SystemExit(1)
+    path = find_recipe(names[0])
+    if not path:
+        print(f"Recipe not found: {names[0]}")
+        raise SystemExit(1)
+    try:
+        report = recipe_variable_report(path)
+    except Exception as exc:
+        print(f"Error loading recipe: {exc}")
+        raise SystemExit(1)
+
+    if flags:
+        print(json.dumps(report, indent=2))
+        return
+    if not report:
+        print("This recipe uses no variables.")
+        return
+    for info in report:
+        if info["has_default"]:
+            state = f"default {info['default']!r}"
+        elif info["produced_by"]:
+            state = "set while running"
+        else:
+            state = "required (--set NAME=VALUE)"
+        print(f"{info['name']}: {state}")
+        for step in info["produced_by"]:
+            print(f"  set by step '{step}'")
+        for usage in info["usages"]:
+            print(f"  used in {usage['description']}")
+
+
+__all__ = ["cmd_vars"]
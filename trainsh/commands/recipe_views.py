@@ -2,6 +2,7 @@
 
 from __future__ import annotations
 
+import os
 from typing import List
 
 from ..core.tmux_naming import get_window_session_name
@@ -10,6 +11,9 @@ from .recipe_shared import (
     _print_full_help,
 )
 
+# How many recent jobs a job-id prefix is matched against.
+JOB_LOOKUP_LIMIT = 200
+
 
 def cmd_logs(args: List[str]) -> None:
     """View execution logs."""
@@ -18,13 +22,31 @@ def cmd_logs(args: List[str]) -> None:
 
     from ..core.execution_log import ExecutionLogReader
 
+    if args and args[0] == "--prune":
+        from ..services.log_retention import apply_log_policy, log_policy
+
+        dry_run = "--dry-run" in args[1:]
+        policy = log_policy()
+        result = apply_log_policy(dry_run=dry_run)
+        age = f"{policy['retention_days']:g} days" if policy["retention_days"] else "no age limit"
+        print(f"Policy: keep {int(policy['keep_runs']) or 'all'} run(s), {age}.")
+        verb = "Would prune" if dry_run else "Pruned"
+        print(f"{verb} {len(result['pruned'])} run(s)" + (": " + ", ".join(result["pruned"]) if result["pruned"] else "."))
+        if result["rotated"]:
+            print("Rotated events.jsonl.")
+        return
+
     with ExecutionLogReader() as reader:
         if not args or args[0] in ("--list", "-l"):
+            from ..config import get_config_value
+            from ..services.wandb_api import DEFAULT_LIST_METRICS, format_metrics
+
             executions = reader.list_executions(limit=20)
 
             if not executions:
                 print("No execution logs found.")
                 return
+            metric_keys = get_config_value("wandb.list_metrics", list(DEFAULT_LIST_METRICS)) or []
 
             print("Recent executions:")
             print("-" * 98)
@@ -49,7 +71,9 @@ def cmd_logs(args: List[str]) -> None:
 
                 duration_str = f"{duration_ms}ms" if duration_ms else "-"
                 bindings = f"{host_count}/{storage_count}"
-                print(f"{job_id:<12} {recipe:<20} {started:<24} {status:<10} {bindings:<7} {duration_str}")
+                metrics = format_metrics(ex.get("metrics") or {}, metric_keys)
+                line = f"{job_id:<12} {recipe:<20} {started:<24} {status:<10} {bindings:<7} {duration_str}"
+                print(f"{line:<84} {metrics}" if metrics else line)
 
             print("-" * 98)
             print(f"Total: {len(executions)} executions")
@@ -93,6 +117,22 @@ def _show_execution_details(reader, job_id: str) -> None:
     if duration_ms:
         print(f"Duration: {duration_ms}ms ({duration_ms / 1000:.2f}s)")
 
+    energy = summary.get("energy") or {}
+    if energy:
+        per_host = ", ".join(
+            f"{host}: avg {item['avg_w']:.0f} W, peak {item['peak_w']:.0f} W" for host, item in energy.get("hosts", {}).items()
+        )
+        print(f"Energy: {energy['kwh']:.3f} kWh (approx.; {per_host})")
+
+    tracking = summary.get("tracking") or {}
+    if tracking.get("run_id"):
+        print(f"MLflow run: {tracking['run_id']} ({tracking.get('status', '')})")
+
+    trace = summary.get("trace") or {}
+    if trace.get("trace_id"):
+        sent = "" if trace.get("exported") else ", not exported"
+        print(f"Trace: {trace['trace_id']} ({trace.get('spans', 0)} spans{sent})")
+
     variables = summary.get("variables", {})
     if variables:
         print(f"\nVariables ({len(variables)}):")
@@ -171,13 +211,7 @@ def cmd_status(args: List[str]) -> None:
 
     if args and args[0] not in ("--list", "-l", "--all", "-a"):
         job_id = args[0]
-        job = state_manager.load(job_id)
-
-        if not job:
-            for candidate in state_manager.list_all():
-                if candidate.job_id.startswith(job_id):
-                    job = candidate
-                    break
+        job = state_manager.load(_resolve_job_id(job_id, states=True))
 
         if not job:
             print(f"Job not found: {job_id}")
@@ -203,7 +237,7 @@ def cmd_status(args: List[str]) -> None:
     for job in jobs:
         job_id = job.job_id[:8]
         recipe = job.recipe_name[:18]
-        status = job.status[:10]
+        status = _job_status_label(job)[:11]
         step = f"{job.current_step + 1}/{job.total_steps}"
         bindings = f"{len(job.hosts)}/{len(getattr(job, 'storages', {}))}"
         updated = job.updated_at[:23]
@@ -212,11 +246,41 @@ def cmd_status(args: List[str]) -> None:
     print("-" * 90)
     print(f"Total: {len(jobs)} jobs")
 
+    orphaned = state_manager.list_orphaned()
+    if orphaned:
+        print(f"{len(orphaned)} running job(s) lost their train process; run 'train recipe reconnect'.")
+
     if not all_jobs:
         print("\nUse '--all' to show completed/failed jobs.")
         print("Use '--last' to show the latest running job.")
 
 
+def _resolve_job_id(ref: str, *, states: bool = False) -> str:
+    """Full job id for `--last`, a full id or a unique prefix of a recent job.
+
+    Searches execution logs, or saved job states with `states`. An unknown id
+    comes back unchanged for the caller to report; an ambiguous prefix exits.
+    """
+    if states:
+        from ..core.job_state import JobStateManager
+
+        job_ids = [job.job_id for job in JobStateManager().list_all(limit=JOB_LOOKUP_LIMIT)]
+    else:
+        from ..core.execution_log import ExecutionLogReader
+
+        with ExecutionLogReader() as reader:
+            job_ids = [ex["job_id"] for ex in reader.list_executions(limit=JOB_LOOKUP_LIMIT)]
+    if ref == "--last":
+        return job_ids[0] if job_ids else ""
+    if ref in job_ids:
+        return ref
+    matches = [job_id for job_id in job_ids if job_id.startswith(ref)]
+    if len(matches) > 1:
+        print(f"Job id {ref} is ambiguous; use more characters.")
+        raise SystemExit(1)
+    return matches[0] if matches else ref
+
+
 def _window_session_name(job, window_name: str, fallback_index: int) -> str:
     """Resolve tmux session name for a recipe window."""
     mapped = getattr(job, "window_sessions", {}).get(window_name)
@@ -259,6 +323,11 @@ def _show_attach_commands(job) -> None:
             print(f"  @{window_name}: tmux attach -t {session_name}")
 
 
+def _job_status_label(job) -> str:
+    """Status for job tables: how a finished job ended, else its live status."""
+    return getattr(job, "outcome", "") or job.status
+
+
 def _show_job_details(job) -> None:
     """Show details of a specific job."""
     from ..core.execution_log import ExecutionLogReader
@@ -268,6 +337,12 @@ def _show_job_details(job) -> None:
     print(f"Recipe: {job.recipe_name}")
     print(f"Recipe Path: {job.recipe_path}")
     print(f"Status: {job.status}")
+    if getattr(job, "outcome", ""):
+        from ..core.exit_status import OOM_HINT, describe_exit
+
+        print(f"Outcome: {job.outcome} (exit code {describe_exit(job.exit_code)})")
+        if job.outcome in ("oom_killed", "cuda_oom"):
+            print(f"Hint: {OOM_HINT}")
     print(f"Progress: Step {job.current_step + 1}/{job.total_steps}")
     print(f"Created: {job.created_at}")
     print(f"Updated: {job.updated_at}")
@@ -293,6 +368,9 @@ def _show_job_details(job) -> None:
         for name, spec in storages.items():
             print(f"  @{name} = {spec}")
 
+    for child_id in getattr(job, "on_complete_job_ids", []) or []:
+        print(f"On complete: job {child_id}")
+
     if job.vast_instance_id:
         print(f"\nVast.ai Instance: {job.vast_instance_id}")
         if job.vast_start_time:
@@ -357,6 +435,12 @@ def _format_recent_event(event: dict) -> str:
     if event_name == "step_end":
         state = str(event.get("state") or ("success" if event.get("success") else "failed"))
         return f"{time_text} step {step_num} {state}"
+    if event_name == "step_retry":
+        return f"{time_text} step {step_num} attempt {event.get('try_number', '?')} failed, retrying"
+    if event_name == "step_wait":
+        return f"{time_text} step {step_num} waited {int(event.get('wait_ms', 0) or 0) // 1000}s for {event.get('kind', 'input')}"
+    if event_name == "step_output_truncated":
+        return f"{time_text} step {step_num} output truncated at {event.get('scope', 'step')} limit ({event.get('limit_bytes', '?')} bytes)"
     if event_name == "detail":
         return f"{time_text} {event.get('category', 'detail')}: {_short_text(event.get('message', ''))}"
     if event_name == "variable_set":
@@ -367,6 +451,10 @@ def _format_recent_event(event: dict) -> str:
         return f"{time_text} tmux {event.get('operation', '?')} {_short_text(event.get('target', ''))}"
     if event_name == "file_transfer":
         return f"{time_text} transfer {_short_text(event.get('source', ''))} -> {_short_text(event.get('dest', ''))}"
+    if event_name == "on_complete":
+        if event.get("skipped"):
+            return f"{time_text} on_complete {event.get('recipe', '?')} skipped ({event.get('skipped')})"
+        return f"{time_text} on_complete {event.get('recipe', '?')} -> job {event.get('child_job_id', '?')}"
     if event_name == "vast_api":
         return f"{time_text} vast {event.get('operation', '?')}"
     return f"{time_text} {event_name}"
@@ -378,14 +466,22 @@ def cmd_jobs(args: List[str]) -> None:
         _print_full_help(0)
 
     from ..core.job_state import JobStateManager
+    from ..services.projects import active_project, recipe_key, scope_note
 
     state_manager = JobStateManager()
     show_all = "--all" in args or "-a" in args
     limit = 100 if show_all else 20
-    jobs = state_manager.list_all(limit=limit)
+    project = active_project()
+    if project is None:
+        jobs = state_manager.list_all(limit=limit)
+    else:
+        recipes = {recipe_key(item) for item in project.recipes}
+        jobs = [job for job in state_manager.list_all(limit=500) if recipe_key(job.recipe_name) in recipes][:limit]
 
     if not jobs:
         print("No job states found.")
+        if project is not None:
+            print(scope_note(project, "jobs", 0))
         return
 
     print("Recipe Jobs:")
@@ -396,13 +492,265 @@ def cmd_jobs(args: List[str]) -> None:
     for job in jobs:
         job_id = job.job_id[:8]
         recipe = job.recipe_name[:23]
-        status = job.status[:10]
+        status = _job_status_label(job)[:11]
         step = f"{job.current_step + 1}/{job.total_steps}"
         updated = job.updated_at[:23]
         print(f"{job_id:<10} {recipe:<25} {status:<12} {step:<10} {updated:<25}")
 
     print("-" * 90)
     print(f"Total: {len(jobs)} jobs")
+    if project is not None:
+        print(scope_note(project, "jobs", 0))
 
     if not show_all and len(jobs) >= 20:
         print("\nUse '--all' to show all jobs.")
+
+
+_TIMELINE_MARKS = {
+    "running": "#",
+    "waiting": "~",
+    "queued": ".",
+    "retry_backoff": "r",
+    "input": "?",
+    "paused": " ",
+}
+
+
+def _timeline_bar(segments: List[dict], start, wall_ms: int, width: int) -> str:
+    from datetime import datetime
+
+    cells = [" "] * width
+    # Later kinds in _TIMELINE_MARKS are drawn over earlier ones (input over running).
+    order = list(_TIMELINE_MARKS)
+    for segment in sorted(segments, key=lambda item: order.index(item["kind"]) if item["kind"] in order else 0):
+        begin = (datetime.fromisoformat(segment["start"]) - start).total_seconds() * 1000
+        first = min(width - 1, max(0, int(begin * width / wall_ms)))
+        last = min(width, max(first + 1, int((begin + segment["ms"]) * width / wall_ms + 0.5)))
+        for index in range(first, last):
+            cells[index] = _TIMELINE_MARKS.get(segment["kind"], "#")
+    return "".join(cells)
+
+
+def cmd_timeline(args: List[str]) -> None:
+    """Show where a run's time went, step by step."""
+    if args and args[0] in HELP_FLAGS:
+        _print_full_help(0)
+
+    import json
+    from datetime import datetime
+
+    from ..core.executor_utils import _format_duration
+    from ..services.execution_timeline import SEGMENT_KINDS, execution_timeline
+
+    as_json = "--json" in args
+    positional = [arg for arg in args if arg != "--json"]
+    if not positional or (positional[0].startswith("-") and positional[0] != "--last"):
+        print("Usage: train recipe timeline <job-id|--last> [--json]")
+        raise SystemExit(1)
+
+    job_id = _resolve_job_id(positional[0])
+    timeline = execution_timeline(job_id) if job_id else None
+    if timeline is None:
+        print(f"Execution not found: {positional[0]}")
+        raise SystemExit(1)
+    if as_json:
+        print(json.dumps(timeline, indent=2))
+        return
+
+    wall_ms = max(1, int(timeline["wall_ms"]))
+    print(f"Job ID: {timeline['run_id']}")
+    print(f"Recipe: {timeline['recipe_name']}")
+    print(f"Status: {timeline['status'] or 'unknown'}")
+    print(f"Wall time: {_format_duration(wall_ms / 1000)}")
+    if not timeline["started_at"]:
+        return
+    start = datetime.fromisoformat(timeline["started_at"])
+    width = 40
+    print()
+    print(f"{'Step':<24} {'Tries':>5} {'State':<12} {'Time':>9}  |{'timeline':<{width}}|")
+    print("-" * (56 + width))
+    for step in timeline["steps"]:
+        segments = [item for item in timeline["segments"] if item["step_id"] == step["step_id"]]
+        label = _short_text(step["raw"] or step["step_id"], max_len=24)
+        print(
+            f"{label:<24} {len(step['attempts']):>5} {step['state'][:12]:<12} "
+            f"{_format_duration(step.get('total_ms', 0) / 1000):>9}  |{_timeline_bar(segments, start, wall_ms, width)}|"
+        )
+    outside = [item for item in timeline["segments"] if item["step_id"] == ""]
+    if outside:
+        print(f"{'(run)':<24} {'':>5} {'':<12} {'':>9}  |{_timeline_bar(outside, start, wall_ms, width)}|")
+    print("-" * (56 + width))
+    print("Legend: " + "  ".join(f"{mark!r}={kind}" for kind, mark in _TIMELINE_MARKS.items()))
+    print()
+    print("Where the time went:")
+    for kind in SEGMENT_KINDS:
+        value = timeline["breakdown"].get(kind, 0)
+        if value:
+            print(f"  {kind:<14} {_format_duration(value / 1000):>9}  {value * 100 / wall_ms:5.1f}%")
+    print("  (parallel steps overlap, so the rows can add up to more than the wall time)")
+
+
+def cmd_energy(args: List[str]) -> None:
+    """Show approximate GPU energy per month of finished runs."""
+    if args and args[0] in HELP_FLAGS:
+        _print_full_help(0)
+
+    import json
+
+    from ..core.runtime_store import RuntimeStore
+    from ..services.energy import monthly_energy
+
+    months = monthly_energy(RuntimeStore().list_runs())
+    if "--json" in args:
+        print(json.dumps(months, indent=2))
+        return
+    if not months:
+        print("No runs with energy data yet (sampled from nvidia-smi while recipes run).")
+        return
+    print(f"{'Month':<8} {'Runs':>5} {'kWh':>10}")
+    print("-" * 25)
+    for month, row in months.items():
+        print(f"{month:<8} {int(row['runs']):>5} {row['kwh']:>10.3f}")
+
+
+def cmd_tail(args: List[str]) -> None:
+    """Print the last output lines of one step."""
+    if args and args[0] in HELP_FLAGS:
+        _print_full_help(0)
+
+    import json
+
+    from ..core.execution_log import ExecutionLogReader
+
+    usage = "Usage: train recipe tail <job-id|--last> [step] [-n LINES] [--json]"
+    as_json = "--json" in args
+    lines = 50
+    positional: List[str] = []
+    rest = [arg for arg in args if arg != "--json"]
+    i = 0
+    while i < len(rest):
+        arg = rest[i]
+        if arg in ("-n", "--lines"):
+            if i + 1 >= len(rest) or not rest[i + 1].isdigit():
+                print(usage)
+                raise SystemExit(1)
+            lines = max(1, int(rest[i + 1]))
+            i += 2
+            continue
+        positional.append(arg)
+        i += 1
+    if not positional or (positional[0].startswith("-") and positional[0] != "--last") or len(positional) > 2:
+        print(usage)
+        raise SystemExit(1)
+
+    job_id = _resolve_job_id(positional[0])
+    with ExecutionLogReader() as reader:
+        step = positional[1] if len(positional) > 1 else None
+        result = reader.get_step_output_tail(job_id, step, lines) if job_id else None
+    if result is None:
+        target = f"step {step} of {positional[0]}" if step else positional[0]
+        print(f"No output found: {target}")
+        raise SystemExit(1)
+    if as_json:
+        print(json.dumps(result, indent=2))
+        return
+    label = result["step_id"] or f"step {result['step_num']}"
+    print(f"== {result['job_id']} {label} (last {len(result['lines'])} line(s), {result['source']}) ==")
+    for line in result["lines"]:
+        print(line)
+
+
+def cmd_output(args: List[str]) -> None:
+    """Print, locate or export the raw output of one step."""
+    if args and args[0] in HELP_FLAGS:
+        _print_full_help(0)
+
+    import shutil
+    import sys
+
+    from ..core.execution_log import ExecutionLogReader
+
+    usage = "Usage: train recipe output <job-id|--last> [step] [--error] [--path | -o FILE]"
+    output_type = "exception" if "--error" in args else "result"
+    show_path = "--path" in args
+    target = ""
+    positional: List[str] = []
+    rest = [arg for arg in args if arg not in ("--error", "--path")]
+    i = 0
+    while i < len(rest):
+        if rest[i] in ("-o", "--output"):
+            if i + 1 >= len(rest):
+                print(usage)
+                raise SystemExit(1)
+            target = rest[i + 1]
+            i += 2
+            continue
+        positional.append(rest[i])
+        i += 1
+    if not positional or (positional[0].startswith("-") and positional[0] != "--last") or len(positional) > 2:
+        print(usage)
+        raise SystemExit(1)
+
+    job_id = _resolve_job_id(positional[0])
+    with ExecutionLogReader() as reader:
+        step = positional[1] if len(positional) > 1 else None
+        resolved = reader.resolve_step(job_id, step) if job_id else None
+        if resolved is None:
+            print(f"Step not found: {step or 'latest'} in {positional[0]}")
+            raise SystemExit(1)
+        path = reader.get_step_log_path(job_id, resolved[0], output_type)
+        # Runs from before step files existed only have the JSONL copy.
+        text = None if path else (reader.get_step_output(job_id, resolved[0]) if output_type == "result" else "")
+
+    if path is None and not text:
+        print(f"No {'error ' if output_type == 'exception' else ''}output recorded for step {resolved[0]} of {job_id}")
+        raise SystemExit(1)
+    if show_path:
+        if path is None:
+            print(f"Step {resolved[0]} has no output file; use -o to export it from the log.")
+            raise SystemExit(1)
+        print(path)
+        return
+    if target:
+        target = os.path.expanduser(target)
+        if path is not None:
+            shutil.copyfile(path, target)
+        else:
+            with open(target, "w", encoding="utf-8") as handle:
+                handle.write(text or "")
+        print(f"Wrote step {resolved[0]} output to {target}", file=sys.stderr)
+        return
+    if path is not None:
+        with open(path, "r", encoding="utf-8", errors="replace") as handle:
+            shutil.copyfileobj(handle, sys.stdout)
+    else:
+        sys.stdout.write(text or "")
+
+
+def cmd_screen(args: List[str]) -> None:
+    """Print the last full-screen frame recorded for one step."""
+    if args and args[0] in HELP_FLAGS:
+        _print_full_help(0)
+
+    from ..core.execution_log import ExecutionLogReader
+
+    show_path = "--path" in args
+    positional = [arg for arg in args if arg != "--path"]
+    if not positional or (positional[0].startswith("-") and positional[0] != "--last") or len(positional) > 2:
+        print("Usage: train recipe screen <job-id|--last> [step] [--path]")
+        raise SystemExit(1)
+
+    job_id = _resolve_job_id(positional[0])
+    with ExecutionLogReader() as reader:
+        step = positional[1] if len(positional) > 1 else None
+        screen = reader.get_step_screen(job_id, step) if job_id else None
+
+    if screen is None:
+        print(f"No full-screen frame recorded for {'step ' + step if step else 'any step'} of {job_id or positional[0]}")
+        raise SystemExit(1)
+    if show_path:
+        print(screen["path"])
+        return
+    label = f"{screen['step_num']}" + (f" ({screen['step_id']})" if screen["step_id"] else "")
+    print(f"Step {label} screen at {screen['updated']}:")
+    print(screen["frame"], end="")
@@ -15,7 +15,8 @@ from ..services.git_auth import (
     normalize_git_auth_mode,
 )
 from ..services.secret_materialize import materialize_secret_file
-from ..services.ssh import SSHClient
+from ..services.connectivity import ConnectivityMonitor, HostOfflineError
+from ..services.host_exec import host_exec
 
 
 @dataclass(frozen=True)
@@ -132,19 +133,30 @@ def _print_ssh_target(host: Host, result) -> None:
 
 def run_remote_command(host: Host, command: str, *, label: str) -> None:
     """Execute one command on one resolved host and forward output locally."""
+    monitor = ConnectivityMonitor()
+    try:
+        monitor.ensure_online(label, host)
+    except HostOfflineError as exc:
+        print(str(exc))
+        raise SystemExit(1)
+
     print(f"Running on {label}...")
 
     try:
-        ssh = SSHClient.from_host(host)
+        result = host_exec(host, command, timeout=None)
     except Exception as exc:
         print(f"Connection setup failed: {exc}")
         raise SystemExit(1)
 
-    result = ssh.run(command)
-
     _print_ssh_target(host, result)
     _write_remote_result(result)
 
+    # OpenSSH reports connection failures as 255 with no remote output.
+    if result.exit_code in (255, -1) and not result.stdout:
+        print(monitor.explain_failure(label, (result.stderr or "").strip()))
+    else:
+        monitor.record(label, True)
+
     if result.exit_code != 0:
         raise SystemExit(result.exit_code if result.exit_code > 0 else 1)
 
@@ -153,12 +165,6 @@ def run_remote_git_clone(host: Host, request: RemoteCloneRequest, *, label: str)
     """Clone one repository on a remote host using optional GitHub token auth."""
     print(f"Cloning on {label}...")
 
-    try:
-        ssh = SSHClient.from_host(host)
-    except Exception as exc:
-        print(f"Connection setup failed: {exc}")
-        raise SystemExit(1)
-
     try:
         auth_mode = normalize_git_auth_mode(request.auth)
     except ValueError as exc:
@@ -195,13 +201,19 @@ def run_remote_git_clone(host: Host, request: RemoteCloneRequest, *, label: str)
         print(f"Secret {request.token_secret} is empty or unavailable")
         raise SystemExit(1)
 
-    if token_text:
-        result = ssh.run_with_input(
-            build_remote_git_auth_command(clone_command),
-            token_text if token_text.endswith("\n") else f"{token_text}\n",
-        )
-    else:
-        result = ssh.run(clone_command)
+    try:
+        if token_text:
+            result = host_exec(
+                host,
+                build_remote_git_auth_command(clone_command),
+                stdin=token_text if token_text.endswith("\n") else f"{token_text}\n",
+                timeout=None,
+            )
+        else:
+            result = host_exec(host, clone_command, timeout=None)
+    except Exception as exc:
+        print(f"Connection setup failed: {exc}")
+        raise SystemExit(1)
 
     _write_remote_result(result)
     if result.exit_code != 0:
@@ -110,7 +110,7 @@ def cmd_show(args: List[str]) -> None:
 def cmd_ssh(args: List[str]) -> None:
     """SSH into a Pod."""
     if not args:
-        print("Usage: train runpod ssh <pod_id>")
+        print("Usage: train runpod ssh <pod_id> [--log]")
         sys.exit(1)
 
     from ..services.runpod_api import get_runpod_client
@@ -132,7 +132,12 @@ def cmd_ssh(args: List[str]) -> None:
         sys.exit(1)
 
     print(f"Connecting to {target['hostname']}:{int(target['port'])}...")
-    os.system(ssh_target_to_command(target))
+    from ..services.terminal_log import mirror_requested, run_interactive
+
+    if mirror_requested(args[1:]):
+        run_interactive(["sh", "-c", ssh_target_to_command(target)], f"runpod-{pod_id}")
+    else:
+        os.system(ssh_target_to_command(target))
 
 
 def cmd_run(args: List[str]) -> None:
@@ -216,16 +221,25 @@ def cmd_rm(args: List[str]) -> None:
         print("Usage: train runpod remove <pod_id>")
         sys.exit(1)
 
+    from ..services.viewer_mode import require_operator
+
+    require_operator("runpod.destroy")
     pod_id = str(args[0]).strip()
     confirm = prompt_input(f"Delete Pod {pod_id}? This cannot be undone. (y/N): ")
     if confirm is None or confirm.lower() != "y":
         print("Cancelled.")
         return
 
+    from ..services.audit_log import record
     from ..services.runpod_api import get_runpod_client
 
     print(f"Deleting Pod {pod_id}...")
-    get_runpod_client().delete_pod(pod_id)
+    try:
+        get_runpod_client().delete_pod(pod_id)
+    except Exception as exc:
+        record("runpod.destroy", pod_id, host="runpod", detail=str(exc), status="failed")
+        raise
+    record("runpod.destroy", pod_id, host="runpod")
     print("Pod removed.")
 
 
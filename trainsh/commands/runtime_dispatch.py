@@ -28,8 +28,12 @@ def run_recipe_via_dag(
     callbacks: Optional[Sequence[str]] = None,
     callback_sinks: Optional[Sequence] = None,
     log_callback=None,
+    recipe=None,
 ) -> DagExecutionResult:
-    """Execute one recipe by passing it through the DAG discovery/executor path."""
+    """Execute one recipe by passing it through the DAG discovery/executor path.
+
+    `recipe` is the already-loaded recipe object, so the file is not executed twice.
+    """
     dag = load_recipe_dag(recipe_path)
     executor = DagExecutor(
         executor_name=executor_name,
@@ -47,6 +51,7 @@ def run_recipe_via_dag(
         var_overrides=var_overrides,
         resume=resume,
         initial_session_index=initial_session_index,
+        recipe=recipe,
     )
 
 
@@ -0,0 +1,74 @@
+# tmux-trainsh search command
+# Full-text search across recipes, hosts, sessions, secret names, and execution logs
+
+import json
+import sys
+from typing import List, Optional
+
+from .help_catalog import render_command_help
+from .help_cmd import reject_subcommand_help
+
+usage = render_command_help("search")
+
+
+def main(args: List[str]) -> Optional[str]:
+    """Main entry point for search command."""
+    from ..services.search_index import KINDS, search
+
+    if not args:
+        print(usage)
+        return None
+    if args[0] in ("-h", "--help", "help"):
+        reject_subcommand_help()
+
+    terms: List[str] = []
+    kinds: List[str] = []
+    limit = 20
+    as_json = reindex = False
+    i = 0
+    while i < len(args):
+        arg = args[i]
+        if arg in ("--kind", "--limit") and i + 1 < len(args):
+            value = args[i + 1]
+            if arg == "--kind":
+                for kind in value.split(","):
+                    if kind.rstrip("s") not in KINDS:
+                        print(f"Unknown kind: {kind} (expected {', '.join(KINDS)})")
+                        sys.exit(1)
+                    kinds.append(kind.rstrip("s"))
+            elif not value.isdigit():
+                print(f"Invalid --limit: {value}")
+                sys.exit(1)
+            else:
+                limit = int(value)
+            i += 2
+            continue
+        if arg == "--json":
+            as_json = True
+        elif arg == "--reindex":
+            reindex = True
+        elif arg.startswith("--"):
+            print(f"Unknown option: {arg}")
+            print(usage)
+            sys.exit(1)
+        else:
+            terms.append(arg)
+        i += 1
+
+    query = " ".join(terms)
+    if not query.strip():
+        print(usage)
+        sys.exit(1)
+    hits = search(query, kinds=kinds or None, limit=limit, reindex=reindex)
+    if as_json:
+        print(json.dumps(hits, indent=2, ensure_ascii=False))
+        return None
+    if not hits:
+        print(f"No matches for: {query}")
+        return None
+    for hit in hits:
+        print(f"{hit['kind']:<8} {hit['name'][:24]:<24} {hit['location']}")
+        if hit["snippet"] and hit["snippet"] != hit["name"]:
+            print(f"         {hit['snippet']}")
+    print(f"\n{len(hits)} match(es)" + (f" (showing first {limit}; use --limit N for more)" if len(hits) >= limit else ""))
+    return None
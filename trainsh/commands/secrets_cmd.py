@@ -145,6 +145,7 @@ def cmd_set(args: List[str]) -> None:
         sys.exit(1)
 
     from ..core.secrets import get_secrets_manager, resolve_secret_bundle_alias
+    from ..services.audit_log import record
 
     key = args[0].upper()
     bundle_alias = resolve_secret_bundle_alias(key)
@@ -156,6 +157,7 @@ def cmd_set(args: List[str]) -> None:
         secrets = get_secrets_manager()
         try:
             secrets.set_bundle(bundle_alias[0], payload)
+            record("secret.set", bundle_alias[0])
             print(f"Successfully set {bundle_alias[0]}")
         except RuntimeError as e:
             print(f"Error: {e}")
@@ -176,6 +178,7 @@ def cmd_set(args: List[str]) -> None:
 
     try:
         secrets.set(key, value)
+        record("secret.set", key)
         print(f"Successfully set {key}")
     except RuntimeError as e:
         print(f"Error: {e}")
@@ -206,10 +209,13 @@ def cmd_get(args: List[str]) -> None:
         print("Usage: train secrets get [--masked] <key>")
         sys.exit(1)
 
+    from ..services.audit_log import record
+
     key = positional[0].upper()
     secrets = get_secrets_manager()
 
     value = secrets.get(key)
+    record("secret.get", key, detail="masked" if masked else "revealed", status="ok" if value else "missing")
     if value:
         if masked:
             if len(value) > 8:
@@ -230,6 +236,9 @@ def cmd_delete(args: List[str]) -> None:
         sys.exit(1)
 
     from ..core.secrets import get_secrets_manager
+    from ..services.viewer_mode import require_operator
+
+    require_operator("secret.delete")
 
     key = args[0].upper()
 
@@ -239,8 +248,11 @@ def cmd_delete(args: List[str]) -> None:
         print("Cancelled.")
         return
 
+    from ..services.audit_log import record
+
     secrets = get_secrets_manager()
     secrets.delete(key)
+    record("secret.delete", key)
     print(f"Deleted {key}")
 
 
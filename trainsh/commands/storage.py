@@ -8,12 +8,17 @@ from typing import Optional, List
 from ..cli_utils import SubcommandSpec, dispatch_subcommand, prompt_input
 from .help_catalog import render_command_help
 from .help_cmd import reject_subcommand_help
+from .storage_files import cmd_cat, cmd_delete, cmd_edit, cmd_trash
 
 SUBCOMMAND_SPECS = (
     SubcommandSpec("list", "List configured storage backends."),
     SubcommandSpec("add", "Add a storage backend interactively."),
     SubcommandSpec("show", "Inspect one backend's configuration."),
     SubcommandSpec("check", "Check connectivity for one backend."),
+    SubcommandSpec("cat", "Print the start of one file on a backend."),
+    SubcommandSpec("edit", "Edit one small text file on a backend in $EDITOR."),
+    SubcommandSpec("delete", "Delete one path on a backend (soft delete into .trash/ by default)."),
+    SubcommandSpec("trash", "List, restore, or empty a backend's .trash/ area."),
     SubcommandSpec("remove", "Delete a stored backend."),
 )
 
@@ -62,16 +67,23 @@ def _prompt_secret(secret_prompt: str) -> Optional[str]:
     return value
 
 
-def load_storages() -> dict:
-    """Load storages from configuration."""
+def _read_storages_file() -> dict:
+    """storages.yaml as written."""
     from ..constants import STORAGES_FILE
     import yaml
 
     if not STORAGES_FILE.exists():
         return {}
-
     with open(STORAGES_FILE, "r") as f:
-        data = yaml.safe_load(f) or {}
+        return yaml.safe_load(f) or {}
+
+
+def load_storages() -> dict:
+    """Load storages from configuration, expanding `${env:NAME}` references."""
+    from ..constants import STORAGES_FILE
+    from ..core.env_refs import expand_env_refs
+
+    data = expand_env_refs(_read_storages_file(), where=STORAGES_FILE.name)
 
     storages = {}
     for storage_data in data.get("storages", []):
@@ -90,11 +102,13 @@ def _storage_to_dict(storage) -> dict:
 def save_storages(storages: dict) -> None:
     """Save storages to configuration."""
     from ..constants import STORAGES_FILE, CONFIG_DIR
+    from ..core.env_refs import restore_env_refs_by_name
     import yaml
 
     CONFIG_DIR.mkdir(parents=True, exist_ok=True)
 
-    data = {"storages": [_storage_to_dict(s) for s in storages.values()]}
+    persisted = [_storage_to_dict(s) for s in storages.values()]
+    data = {"storages": restore_env_refs_by_name(_read_storages_file().get("storages"), persisted)}
 
     with open(STORAGES_FILE, "w") as f:
         yaml.dump(data, f, default_flow_style=False, sort_keys=False)
@@ -102,11 +116,17 @@ def save_storages(storages: dict) -> None:
 
 def cmd_list(args: List[str]) -> None:
     """List configured storage backends."""
+    from ..services.projects import active_project, scope_note, scoped
+
     storages = load_storages()
+    project = active_project()
+    visible = scoped(storages, "storages", project)
+    hidden = len(storages) - len(visible)
+    storages = {name: storages[name] for name in visible}
 
     if not storages:
-        print("No storage backends configured.")
-        print("Use 'train storage add' to add one.")
+        print("No storage backends configured." if project is None else f"No storage backends in project {project.name}.")
+        print("Use 'train storage add' to add one." if project is None else scope_note(project, "storages", hidden))
         return
 
     print("Configured storage backends:")
@@ -118,6 +138,8 @@ def cmd_list(args: List[str]) -> None:
 
     print("-" * 50)
     print(f"Total: {len(storages)} backends")
+    if project is not None:
+        print(scope_note(project, "storages", hidden))
 
 
 def cmd_add(args: List[str]) -> None:
@@ -504,6 +526,9 @@ def cmd_rm(args: List[str]) -> None:
         print("Usage: train storage remove <name>")
         sys.exit(1)
 
+    from ..services.viewer_mode import require_operator
+
+    require_operator("storage.remove")
     name = args[0]
     storages = load_storages()
 
@@ -518,6 +543,9 @@ def cmd_rm(args: List[str]) -> None:
 
     del storages[name]
     save_storages(storages)
+    from ..services.audit_log import record
+
+    record("storage.remove", name)
     print(f"Storage removed: {name}")
 
 
@@ -689,6 +717,10 @@ def main(args: List[str]) -> Optional[str]:
         "add": cmd_add,
         "show": cmd_show,
         "check": cmd_test,
+        "cat": cmd_cat,
+        "edit": cmd_edit,
+        "delete": cmd_delete,
+        "trash": cmd_trash,
         "remove": cmd_rm,
     }
 
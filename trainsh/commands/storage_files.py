@@ -0,0 +1,168 @@
+# tmux-trainsh storage file commands
+# Preview, edit, delete, and trash paths on a storage backend
+
+import sys
+from typing import List, Optional
+
+
+def _prompt(message: str) -> Optional[str]:
+    from .storage import prompt_input
+
+    return prompt_input(message)
+
+
+def _storage_file_args(args: List[str], usage_line: str) -> tuple:
+    """Parse `<name> <path> [--bytes N]` into (storage, path, nbytes)."""
+    from ..services.file_access import DEFAULT_PREVIEW_BYTES
+
+    positional = []
+    nbytes = DEFAULT_PREVIEW_BYTES
+    i = 0
+    while i < len(args):
+        if args[i] == "--bytes" and i + 1 < len(args):
+            try:
+                nbytes = int(args[i + 1])
+            except ValueError:
+                print(f"Invalid --bytes: {args[i + 1]}")
+                sys.exit(1)
+            i += 2
+            continue
+        positional.append(args[i])
+        i += 1
+    if len(positional) != 2:
+        print(f"Usage: {usage_line}")
+        sys.exit(1)
+
+    name, path = positional
+    return _load_storage(name), path, nbytes
+
+
+def cmd_cat(args: List[str]) -> None:
+    """Print the first bytes of one storage file."""
+    from .file_edit import print_preview
+
+    storage, path, nbytes = _storage_file_args(args, "train storage cat <name> <path> [--bytes N]")
+    if not print_preview(storage, path, nbytes):
+        sys.exit(1)
+
+
+def cmd_edit(args: List[str]) -> None:
+    """Edit one small storage file in $EDITOR."""
+    from .file_edit import edit_file
+
+    storage, path, _nbytes = _storage_file_args(args, "train storage edit <name> <path>")
+    if not edit_file(storage, path):
+        sys.exit(1)
+
+
+def _load_storage(name: str):
+    from .storage import load_storages
+
+    storages = load_storages()
+    if name not in storages:
+        print(f"Storage not found: {name}")
+        sys.exit(1)
+    return storages[name]
+
+
+def cmd_delete(args: List[str]) -> None:
+    """Delete one storage path, moving it into `.trash/` unless --hard."""
+    usage_line = "train storage delete <name> <path> [--recursive] [--hard]"
+    positional = [arg for arg in args if not arg.startswith("-")]
+    flags = {arg for arg in args if arg.startswith("-")}
+    unknown = flags - {"-r", "--recursive", "--hard"}
+    if len(positional) != 2 or unknown:
+        print(f"Usage: {usage_line}")
+        sys.exit(1)
+    from ..services.viewer_mode import require_operator
+
+    require_operator("storage.delete")
+    name, path = positional
+    storage = _load_storage(name)
+    recursive = bool(flags & {"-r", "--recursive"})
+
+    from ..services.audit_log import record
+    from ..services.storage_trash import StorageTrash
+
+    if "--hard" not in flags:
+        try:
+            entry = StorageTrash(storage).delete(path, recursive=recursive)
+        except (OSError, RuntimeError, ValueError) as exc:
+            record("storage.delete", f"{name}:{path}", detail=f"trash: {exc}", status="failed")
+            print(f"Delete failed: {exc}")
+            sys.exit(1)
+        record("storage.delete", f"{name}:{path}", detail=f"moved to trash (id {entry['id']})")
+        print(f"Moved {path} to trash (id {entry['id']}).")
+        print(f"Undo with: train storage trash {name} restore {entry['id']}")
+        return
+
+    confirm = _prompt(f"Permanently delete {path} from {name}? (y/N): ")
+    if confirm is None or confirm.lower() != "y":
+        print("Cancelled.")
+        return
+    from ..services.storage_trash import hard_delete
+
+    try:
+        hard_delete(storage, path, recursive=recursive)
+    except (OSError, RuntimeError) as exc:
+        record("storage.delete", f"{name}:{path}", detail=f"hard: {exc}", status="failed")
+        print(f"Delete failed: {exc}")
+        sys.exit(1)
+    record("storage.delete", f"{name}:{path}", detail="hard delete")
+    print(f"Deleted {path}.")
+
+
+def cmd_trash(args: List[str]) -> None:
+    """List, restore, or empty a storage's trash."""
+    usage_line = (
+        "train storage trash <name> [list]\n"
+        "train storage trash <name> restore <id> [--to <path>]\n"
+        "train storage trash <name> empty [--older-than DAYS]"
+    )
+    if not args or args[0].startswith("-"):
+        print(f"Usage: {usage_line}")
+        sys.exit(1)
+    storage = _load_storage(args[0])
+    action = args[1] if len(args) > 1 else "list"
+    rest = args[2:]
+
+    from ..services.storage_trash import StorageTrash
+
+    try:
+        trash = StorageTrash(storage)
+        if action == "list":
+            entries = trash.list()
+            if not entries:
+                print("Trash is empty.")
+                return
+            for entry in entries:
+                expires = f"  expires {entry['expires_at'][:16]}" if entry.get("expires_at") else ""
+                print(f"  {entry['id']}  {entry['deleted_at'][:16]}  {entry['original_path']}{expires}")
+        elif action == "restore" and rest:
+            destination = None
+            if "--to" in rest:
+                index = rest.index("--to")
+                destination = rest[index + 1] if index + 1 < len(rest) else None
+            entry = trash.restore(rest[0], destination=destination)
+            print(f"Restored {entry['restored_to']}.")
+        elif action == "empty":
+            older_than = None
+            if "--older-than" in rest:
+                index = rest.index("--older-than")
+                older_than = float(rest[index + 1])
+            else:
+                confirm = _prompt("Permanently delete everything in the trash? (y/N): ")
+                if confirm is None or confirm.lower() != "y":
+                    print("Cancelled.")
+                    return
+            removed = trash.empty(older_than_days=older_than)
+            print(f"Permanently deleted {len(removed)} trash entr{'y' if len(removed) == 1 else 'ies'}.")
+        else:
+            print(f"Usage: {usage_line}")
+            sys.exit(1)
+    except KeyError as exc:
+        print(exc.args[0] if exc.args else exc)
+        sys.exit(1)
+    except (IndexError, OSError, RuntimeError, ValueError) as exc:
+        print(f"Trash {action} failed: {exc}")
+        sys.exit(1)
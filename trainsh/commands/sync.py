@@ -0,0 +1,125 @@
+# tmux-trainsh sync command
+# Share recipes, hosts, Vast templates and projects between machines via a storage backend
+
+import sys
+from typing import List, Optional
+
+from ..cli_utils import SubcommandSpec, dispatch_subcommand
+from .help_catalog import render_command_help
+from .help_cmd import reject_subcommand_help
+
+SUBCOMMAND_SPECS = (
+    SubcommandSpec("now", "Sync with the configured storage now."),
+    SubcommandSpec("status", "Show sync settings and local changes since the last sync."),
+    SubcommandSpec("log", "Show recent sync changes."),
+)
+
+usage = render_command_help("sync")
+
+_LABELS = {
+    "pushed": "pushed",
+    "pulled": "pulled",
+    "conflict": "conflict",
+    "deleted_local": "deleted here",
+    "deleted_remote": "deleted remotely",
+}
+
+
+def _describe(change: dict) -> str:
+    label = _LABELS.get(change.get("action", ""), change.get("action", ""))
+    if change.get("action") == "conflict":
+        label = f"conflict, kept {change.get('winner')}'s version"
+    return f"{change.get('path')}: {label}"
+
+
+def cmd_now(args: List[str]) -> None:
+    """Run one sync."""
+    from ..services.settings_sync import sync_now
+
+    unknown = [arg for arg in args if arg != "--dry-run"]
+    if unknown:
+        print(f"Unknown option: {unknown[0]}")
+        print(usage)
+        sys.exit(1)
+    dry_run = "--dry-run" in args
+    try:
+        result = sync_now(dry_run=dry_run)
+    except RuntimeError as exc:
+        print(f"Sync failed: {exc}")
+        sys.exit(1)
+    if not result.changes and not result.skipped:
+        print("Already in sync.")
+        return
+    for change in result.changes:
+        print(f"  {_describe(change)}")
+    for name in result.skipped:
+        print(f"  {name}: skipped (too large or unreadable remote copy)")
+    summary = ", ".join(f"{result.count(action)} {_LABELS[action]}" for action in _LABELS if result.count(action))
+    print(("Would sync: " if dry_run else "Synced: ") + (summary or "nothing"))
+    if result.count("conflict") and not dry_run:
+        print("The other version of each conflict is kept next to the file as *.conflict-<device>-<time>.")
+
+
+def cmd_status(args: List[str]) -> None:
+    """Show sync configuration and pending local changes."""
+    from ..services.settings_sync import load_state, pending_local_changes, sync_settings
+
+    del args
+    settings = sync_settings()
+    if not settings["storage"]:
+        print("Settings sync is off.")
+        print("Enable it with: train config set sync.storage <storage-name>")
+        return
+    state = load_state()
+    print(f"Storage: {settings['storage']}:{settings['path']}")
+    print(f"Device: {settings['device']}")
+    print(f"Last sync: {state.get('last_sync') or 'never'}")
+    pending = pending_local_changes()
+    if pending:
+        print(f"Local changes ({len(pending)}):")
+        for name in pending:
+            print(f"  {name}")
+    else:
+        print("No local changes since the last sync.")
+
+
+def cmd_log(args: List[str]) -> None:
+    """Show recent sync changes."""
+    from ..services.settings_sync import history
+
+    limit = 20
+    if args[:1] == ["--limit"] and len(args) > 1 and args[1].isdigit():
+        limit = int(args[1])
+    elif args:
+        print(f"Unknown option: {args[0]}")
+        print(usage)
+        sys.exit(1)
+    records = history(limit)
+    if not records:
+        print("No sync changes recorded.")
+        return
+    for record in records:
+        print(f"{str(record.get('ts', ''))[:19]}  {record.get('device', ''):<12} {_describe(record)}")
+
+
+def main(args: List[str]) -> Optional[str]:
+    """Main entry point for sync command."""
+    if not args:
+        print(usage)
+        return None
+    if args[0] in ("-h", "--help", "help"):
+        reject_subcommand_help()
+
+    commands = {
+        "now": cmd_now,
+        "status": cmd_status,
+        "log": cmd_log,
+    }
+    try:
+        handler = dispatch_subcommand(args[0], commands=commands)
+    except KeyError:
+        print(f"Unknown subcommand: {args[0]}")
+        print(usage)
+        sys.exit(1)
+    handler(args[1:])
+    return None
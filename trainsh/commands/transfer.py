@@ -1,6 +1,7 @@
 # tmux-trainsh transfer command
 # File transfer between hosts and storage
 
+import os
 import sys
 from typing import Optional, List
 
@@ -124,6 +125,47 @@ def parse_endpoint(spec: str) -> tuple[str, str, Optional[str]]:
     return ("local", spec, None)
 
 
+def _ensure_hosts_online(*endpoints) -> None:
+    """Stop before connecting to a host endpoint that just failed a probe."""
+    from ..services.connectivity import ConnectivityMonitor
+
+    names = [endpoint.host_id for endpoint in endpoints if endpoint.type == "host" and endpoint.host_id]
+    reason = ConnectivityMonitor().offline_reason(names) if names else ""
+    if reason:
+        print(reason)
+        sys.exit(1)
+
+
+def _run_preflight(source, destination) -> bool:
+    """Print source size, destination free space and ETA; False when it will not fit."""
+    from ..services.path_stats import transfer_preflight
+    from ..services.transfer_progress import format_bytes
+
+    try:
+        report = transfer_preflight(source, destination)
+    except (FileNotFoundError, KeyError, RuntimeError) as exc:
+        print(f"Preflight failed: {exc}")
+        return False
+    stats = report["source"]
+    if stats:
+        partial = "" if stats["complete"] else " (partial: scan timed out)"
+        print(f"Source: {format_bytes(stats['size'])} in {stats['files']:,} files{partial}")
+        for entry in stats["largest"]:
+            print(f"  {format_bytes(entry['size']):>10}  {entry['name']}")
+    else:
+        print("Source: not measured for storage endpoints")
+    if report["dest_free"] is not None:
+        print(f"Destination free: {format_bytes(report['dest_free'])}")
+    if report["eta_seconds"] is not None:
+        minutes, seconds = divmod(report["eta_seconds"], 60)
+        hours, minutes = divmod(minutes, 60)
+        print("Estimated time: " + (f"{hours}h{minutes:02d}m" if hours else f"{minutes}m{seconds:02d}s"))
+    if report["fits"] is False:
+        print("Error: Source is larger than the free space at the destination.")
+        return False
+    return True
+
+
 def main(args: List[str]) -> Optional[str]:
     """Main entry point for transfer command."""
     if not args:
@@ -131,6 +173,11 @@ def main(args: List[str]) -> Optional[str]:
         return None
     if args[0] in ("-h", "--help", "help"):
         reject_subcommand_help()
+    if args[0] == "queue":
+        from .transfer_queue import main as queue_main
+
+        queue_main(args[1:])
+        return None
 
     # Parse arguments
     delete = False
@@ -141,6 +188,10 @@ def main(args: List[str]) -> Optional[str]:
     checkers: Optional[int] = None
     upload_concurrency: Optional[int] = None
     chunk_size: Optional[str] = None
+    rsync_opts: dict = {}
+    tar_stream = False
+    remote_exec: Optional[bool] = None
+    preflight = False
 
     i = 0
     positional: List[str] = []
@@ -192,6 +243,27 @@ def main(args: List[str]) -> Optional[str]:
                 sys.exit(1)
             chunk_size = args[i + 1]
             i += 2
+        elif arg in ("--remote", "--local-relay"):
+            remote_exec = arg == "--remote"
+            i += 1
+        elif arg == "--tar-stream":
+            tar_stream = True
+            i += 1
+        elif arg == "--preflight":
+            preflight = True
+            i += 1
+        elif arg == "--no-compress":
+            rsync_opts["compress"] = False
+            i += 1
+        elif arg in ("--partial", "--append-verify"):
+            rsync_opts["partial"] = arg[2:]
+            i += 1
+        elif arg == "--bwlimit":
+            if i + 1 >= len(args):
+                print("Missing value for --bwlimit.")
+                sys.exit(1)
+            rsync_opts["bwlimit"] = args[i + 1]
+            i += 2
         elif not arg.startswith("-"):
             positional.append(arg)
             i += 1
@@ -204,15 +276,11 @@ def main(args: List[str]) -> Optional[str]:
         print("Error: Both source and destination are required.")
         print(usage)
         sys.exit(1)
-    if len(positional) > 2:
-        print(f"Unexpected extra arguments: {' '.join(positional[2:])}")
-        print(usage)
-        sys.exit(1)
 
     source_spec = positional[0]
-    dest_spec = positional[1]
+    dest_spec = positional[-1]
 
-    for spec in (source_spec, dest_spec):
+    for spec in positional:
         error = unsupported_inline_storage_error(spec)
         if error:
             print(f"Error: {error}")
@@ -223,6 +291,7 @@ def main(args: List[str]) -> Optional[str]:
     dst_cloud = _try_cloud_endpoint(dest_spec, "dst")
 
     from ..core.models import TransferEndpoint
+    from ..services.rsync_options import normalize_rsync_options
     from ..services.transfer_engine import TransferEngine, get_rclone_remote_name
 
     # Build rclone_opts dict — mutable, shared by reference with TransferEngine
@@ -255,6 +324,23 @@ def main(args: List[str]) -> Optional[str]:
         host_id=src_id if src_type == "host" else None,
         storage_id=src_id if src_type == "storage" else None,
     )
+    src_patterns = [src_path]
+    for spec in positional[1:-1]:
+        extra_type, extra_path, extra_id = parse_endpoint(spec)
+        if src_cloud or (extra_type, extra_id) != (src_type, src_id):
+            print("Error: Multiple sources must all be local paths or all on the same host.")
+            sys.exit(1)
+        src_patterns.append(extra_path)
+
+    from ..services.transfer_select import is_selection
+
+    selection = is_selection(src_patterns)
+    if selection and src_type == "storage":
+        print("Error: Globs and multiple sources need a local or host source.")
+        sys.exit(1)
+    if selection and delete:
+        print("Error: --delete needs a single source path.")
+        sys.exit(1)
 
     dst_endpoint = TransferEndpoint(
         type=dst_type,
@@ -263,7 +349,14 @@ def main(args: List[str]) -> Optional[str]:
         storage_id=dst_id if dst_type == "storage" else None,
     )
 
-    print(f"Transferring from {src_type}:{src_path} to {dst_type}:{dst_path}")
+    _ensure_hosts_online(src_endpoint, dst_endpoint)
+
+    if preflight and selection:
+        print("Note: --preflight measures a single source path; skipped for globs and multiple sources.")
+    elif preflight and not _run_preflight(src_endpoint, dst_endpoint) and not dry_run:
+        sys.exit(1)
+
+    print(f"Transferring from {src_type}:{', '.join(src_patterns)} to {dst_type}:{dst_path}")
     if dry_run:
         print("(dry run - no files will be transferred)")
 
@@ -301,10 +394,65 @@ def main(args: List[str]) -> Optional[str]:
         if chunk_size is not None:
             rclone_opts["s3_chunk_size"] = chunk_size
 
-    engine = TransferEngine(rclone_options=rclone_opts)
+    try:
+        normalize_rsync_options(rsync_opts)
+    except ValueError as exc:
+        print(f"Error: {exc}")
+        sys.exit(1)
+    if tar_stream and has_cloud:
+        print("Error: --tar-stream only applies to local/SSH transfers.")
+        sys.exit(1)
+    if not tar_stream and src_type == "local" and dst_type != "storage":
+        from ..services.tar_stream import suggest_tar_stream
+
+        hint = suggest_tar_stream(src_path)
+        if hint:
+            print(f"Hint: {hint}")
+    if remote_exec is None:
+        from ..config import get_config_value
+
+        remote_exec = bool(get_config_value("transfer.remote_exec", False))
+    relay_note = (
+        "Note: rclone runs on the host; data bypasses this machine."
+        if remote_exec
+        else "Note: Host <-> cloud storage transfers relay through a local temp directory."
+    )
+    progress_callback = None
+    queued_id = os.environ.get("TRAINSH_TRANSFER_ID", "").strip()
+    if queued_id:
+        from ..services.transfer_queue import TransferQueue
+
+        queue = TransferQueue()
+
+        def progress_callback(progress):
+            queue.record_progress(queued_id, progress.to_dict())
+
+    engine = TransferEngine(
+        progress_callback=progress_callback,
+        rclone_options=rclone_opts,
+        rsync_options=rsync_opts,
+        tar_stream=tar_stream,
+        remote_exec=remote_exec,
+    )
 
+    if selection:
+        from ..services.transfer_select import transfer_selection
+        from .host import load_hosts
+
+        result = transfer_selection(
+            engine,
+            src_endpoint,
+            src_patterns,
+            dst_endpoint,
+            hosts=load_hosts() if src_type == "host" or dst_type == "host" else {},
+            storages=storages,
+            exclude=exclude,
+            dry_run=dry_run,
+        )
+        for path in result.files:
+            print(f"  {path}")
     # For simple local/SSH transfers, use rsync directly
-    if src_type == "local" and dst_type == "local":
+    elif src_type == "local" and dst_type == "local":
         result = engine.rsync(
             source=src_path,
             destination=dst_path,
@@ -359,7 +507,7 @@ def main(args: List[str]) -> Optional[str]:
         elif src_type == "host" or dst_type == "host":
             from .host import load_hosts
 
-            print("Note: Host <-> cloud storage transfers relay through a local temp directory.")
+            print(relay_note)
             result = engine.transfer(
                 source=src_endpoint,
                 destination=dst_endpoint,
@@ -0,0 +1,204 @@
+"""`train transfer queue`: prioritized transfers with per-destination limits."""
+
+from __future__ import annotations
+
+import os
+import subprocess
+import sys
+import time
+from typing import Any, Dict, List
+
+from ..services.transfer_queue import TRANSFER_ID_ENV, TransferQueue
+
+QUEUE_USAGE = "train transfer queue [add|list|run|pause|resume|priority|move|cancel|clear] ..."
+ADD_USAGE = "train transfer queue add <source> <destination> [--priority N] [transfer options]"
+RUN_USAGE = "train transfer queue run [--follow] [--interval SECS]"
+
+
+def _usage(usage: str) -> None:
+    print(f"Usage: {usage}")
+    sys.exit(1)
+
+
+def _fail(message: str) -> None:
+    print(f"Error: {message}")
+    sys.exit(1)
+
+
+def _parse_int(text: str, usage: str) -> int:
+    try:
+        return int(text)
+    except ValueError:
+        _usage(usage)
+        raise
+
+
+def cmd_add(queue: TransferQueue, args: List[str]) -> None:
+    priority = 0
+    rest: List[str] = []
+    index = 0
+    while index < len(args):
+        option = args[index]
+        index += 1
+        if option == "--priority":
+            if index >= len(args):
+                _usage(ADD_USAGE)
+            priority = _parse_int(args[index], ADD_USAGE)
+            index += 1
+        else:
+            rest.append(option)
+    if len(rest) < 2 or rest[0].startswith("-") or rest[1].startswith("-"):
+        _usage(ADD_USAGE)
+    entry = queue.add(rest[0], rest[1], args=rest[2:], priority=priority)
+    print(f"Queued {entry['id']} (priority {entry['priority']}, destination {entry['host']}).")
+
+
+def cmd_list(queue: TransferQueue, args: List[str]) -> None:
+    entries = queue.entries()
+    state = "paused" if queue.paused else "active"
+    if not entries:
+        print(f"Transfer queue is empty ({state}).")
+        return
+    print(f"Transfer queue ({state}):")
+    for entry in entries:
+        flags = f" {' '.join(entry['args'])}" if entry.get("args") else ""
+        line = (
+            f"  {entry['id']}  {entry['status']:<9} p{entry.get('priority', 0):<3} "
+            f"{entry['host']:<14} {entry['source']} -> {entry['destination']}{flags}"
+        )
+        if entry.get("status") == "running" and entry.get("progress"):
+            line += f"  [{describe_progress(entry['progress'])}]"
+        if entry.get("status") in ("failed", "cancelled") and entry.get("message"):
+            line += f"  ({entry['message']})"
+        print(line)
+
+
+def describe_progress(progress: Dict[str, Any]) -> str:
+    from ..services.transfer_progress import format_progress
+    from ..services.transfer_support import TransferProgress
+
+    return format_progress(
+        TransferProgress(
+            bytes_transferred=int(progress.get("bytes_done") or 0),
+            total_bytes=int(progress.get("bytes_total") or 0),
+            percent=float(progress.get("percent") or 0),
+            files_done=int(progress.get("files_done") or 0),
+            files_total=int(progress.get("files_total") or 0),
+            speed_bps=float(progress.get("speed_bps") or 0),
+            eta_seconds=progress.get("eta_seconds"),
+        )
+    )
+
+
+def _start(queue: TransferQueue, entry: Dict[str, Any]) -> subprocess.Popen:
+    log_path = queue.log_path(entry["id"])
+    log_path.parent.mkdir(parents=True, exist_ok=True)
+    command = [sys.executable, "-m", "trainsh", "transfer", entry["source"], entry["destination"], *entry["args"]]
+    env = {**os.environ, TRANSFER_ID_ENV: entry["id"]}
+    with open(log_path, "w", encoding="utf-8") as log:
+        process = subprocess.Popen(command, stdout=log, stderr=subprocess.STDOUT, stdin=subprocess.DEVNULL, env=env)
+    print(f"Started {entry['id']}: {entry['source']} -> {entry['destination']}")
+    return process
+
+
+def _last_line(queue: TransferQueue, entry_id: str) -> str:
+    try:
+        lines = [line.strip() for line in queue.log_path(entry_id).read_text(encoding="utf-8").splitlines()]
+    except OSError:
+        return ""
+    lines = [line for line in lines if line]
+    return lines[-1] if lines else ""
+
+
+def cmd_run(queue: TransferQueue, args: List[str]) -> None:
+    """Start queued transfers as limits allow; exit when idle unless `--follow`."""
+    follow = False
+    interval = 2.0
+    index = 0
+    while index < len(args):
+        option = args[index]
+        index += 1
+        if option == "--follow":
+            follow = True
+        elif option == "--interval" and index < len(args):
+            try:
+                interval = max(0.1, float(args[index]))
+            except ValueError:
+                _usage(RUN_USAGE)
+            index += 1
+        else:
+            _usage(RUN_USAGE)
+
+    running: Dict[str, subprocess.Popen] = {}
+    try:
+        while True:
+            for entry_id, process in list(running.items()):
+                if queue.get(entry_id).get("status") == "cancelled" and process.poll() is None:
+                    process.terminate()
+                code = process.poll()
+                if code is None:
+                    continue
+                del running[entry_id]
+                entry = queue.finish(entry_id, success=code == 0, message=_last_line(queue, entry_id))
+                print(f"Finished {entry_id}: {entry['status']}")
+            while True:
+                entry = queue.claim_next()
+                if entry is None:
+                    break
+                running[entry["id"]] = _start(queue, entry)
+            if not running and not follow and (queue.paused or not queue.pending()):
+                break
+            time.sleep(interval)
+    except KeyboardInterrupt:
+        for entry_id, process in running.items():
+            process.terminate()
+            queue.finish(entry_id, success=False, message="Interrupted")
+        print("\nStopped.")
+
+
+def _entry_action(queue: TransferQueue, subcommand: str, args: List[str]) -> str:
+    usages = {
+        "priority": "train transfer queue priority <id> <N>",
+        "move": "train transfer queue move <id> top|bottom",
+        "cancel": "train transfer queue cancel <id>",
+    }
+    expected = 1 if subcommand == "cancel" else 2
+    if len(args) != expected:
+        _usage(usages[subcommand])
+    try:
+        if subcommand == "priority":
+            entry = queue.set_priority(args[0], _parse_int(args[1], usages[subcommand]))
+            return f"Priority of {entry['id']} set to {entry['priority']}."
+        if subcommand == "move":
+            entry = queue.move(args[0], args[1])
+            return f"Moved {entry['id']} to the {args[1]} of the priority {entry['priority']} band."
+        entry = queue.cancel(args[0])
+        return f"Cancelled {entry['id']}."
+    except (KeyError, ValueError) as exc:
+        _fail(str(exc.args[0]) if exc.args else str(exc))
+        raise
+
+
+def main(args: List[str]) -> None:
+    if not args or args[0] in ("-h", "--help", "help"):
+        _usage(QUEUE_USAGE)
+    subcommand, rest = args[0], args[1:]
+    queue = TransferQueue()
+    if subcommand == "add":
+        cmd_add(queue, rest)
+    elif subcommand in ("list", "ls"):
+        cmd_list(queue, rest)
+    elif subcommand == "run":
+        cmd_run(queue, rest)
+    elif subcommand in ("pause", "resume"):
+        queue.set_paused(subcommand == "pause")
+        print("Transfer queue paused; running transfers continue." if subcommand == "pause" else "Transfer queue resumed.")
+    elif subcommand in ("priority", "move", "cancel"):
+        print(_entry_action(queue, subcommand, rest))
+    elif subcommand == "clear":
+        print(f"Removed {queue.clear_finished()} finished transfer(s).")
+    else:
+        _usage(QUEUE_USAGE)
+
+
+__all__ = ["ADD_USAGE", "QUEUE_USAGE", "RUN_USAGE", "describe_progress", "main"]
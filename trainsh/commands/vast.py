@@ -1,14 +1,19 @@
 # tmux-trainsh vast command
 # Vast.ai instance management
 
+import json
 import sys
 import os
-from typing import Optional, List
+from typing import Optional, List, Tuple
 
 from ..cli_utils import SubcommandSpec, dispatch_subcommand, prompt_input
 from .help_catalog import render_command_help
 from .help_cmd import reject_subcommand_help
 from ..core.models import AuthMethod, Host, HostType
+from ..services.vast_api import VastAPIError
+from .vast_snapshot import cmd_snapshot
+from .vast_templates import cmd_create, cmd_template
+from .vast_watch import cmd_events, cmd_watch
 from .remote_run import (
     parse_remote_clone_args,
     parse_remote_run_args,
@@ -26,12 +31,19 @@ SUBCOMMAND_SPECS = (
     SubcommandSpec("stop", "Stop an instance."),
     SubcommandSpec("reboot", "Reboot an instance."),
     SubcommandSpec("remove", "Destroy an instance."),
+    SubcommandSpec("label", "Set instance labels."),
     SubcommandSpec("search", "Search available GPU offers."),
+    SubcommandSpec("create", "Create an instance from a saved template."),
+    SubcommandSpec("template", "List, show, add, or remove saved creation templates."),
+    SubcommandSpec("snapshot", "Capture a running instance into a provisioning recipe."),
+    SubcommandSpec("watch", "Poll instances and record state changes."),
+    SubcommandSpec("events", "Show recorded state changes for instances."),
     SubcommandSpec("keys", "List registered SSH public keys."),
     SubcommandSpec("attach-key", "Upload a local SSH public key."),
 )
 
 usage = render_command_help("vast")
+REPLAY_HINT = "Run 'train host monitor' or 'train host queue run' once you are back online."
 
 
 def _describe_ssh_target_source(source: str) -> str:
@@ -48,6 +60,73 @@ def _describe_ssh_target_source(source: str) -> str:
     return text
 
 
+def _queue_if_offline(kind: str, instance_id: int, error: Exception, *, quiet: bool = False, **params) -> bool:
+    """Queue an idempotent instance operation when the request failed offline."""
+    from ..config import get_config_value
+    from ..services.connectivity import ConnectivityMonitor, network_available
+
+    if not isinstance(error, VastAPIError) or error.status_code != 0:
+        return False
+    if not get_config_value("connectivity.queue_offline_ops", True) or network_available():
+        return False
+    entry = ConnectivityMonitor().enqueue(kind, {"instance_id": instance_id, **params})
+    if not quiet:
+        print(f"Network unavailable; queued {kind} for instance {instance_id} (id {entry['id']}).")
+        print(REPLAY_HINT)
+    return True
+
+
+def _defer_offline(kind: str, instance_id: int, error: Exception, params: dict) -> bool:
+    """`defer` hook for batch operations: queue the instance's request when offline."""
+    return _queue_if_offline(kind, instance_id, error, quiet=True, **params)
+
+
+def _parse_batch_ids(args: List[str], usage_line: str) -> Tuple[List[int], bool]:
+    """Instance ids and the --json flag of a command that accepts several ids."""
+    as_json = "--json" in args
+    ids: List[int] = []
+    for arg in args:
+        if arg == "--json":
+            continue
+        try:
+            ids.append(int(arg))
+        except ValueError:
+            print(f"Invalid instance ID: {arg}")
+            sys.exit(1)
+    if not ids:
+        print(f"Usage: {usage_line}")
+        sys.exit(1)
+    return list(dict.fromkeys(ids)), as_json
+
+
+def _run_batch(verb: str, run, as_json: bool):
+    """Run a batch operation, printing one progress line (or event) per instance and a summary."""
+
+    def progress(event):
+        if as_json:
+            print(json.dumps(event, ensure_ascii=False), flush=True)
+        elif event.get("queued"):
+            print(f"[{event['done']}/{event['total']}] {event['instance_id']}: queued until the network is back", flush=True)
+        elif event["ok"]:
+            print(f"[{event['done']}/{event['total']}] {event['instance_id']}: {verb}", flush=True)
+        else:
+            print(f"[{event['done']}/{event['total']}] {event['instance_id']}: failed ({event['error_code']}) {event['error']}", flush=True)
+
+    results = run(on_progress=progress)
+    failed = [item for item in results if not item.ok]
+    queued = [item for item in results if item.queued]
+    if not as_json:
+        print(f"{verb.capitalize()} {len(results) - len(failed) - len(queued)} of {len(results)} instance(s).")
+        if queued:
+            print("Queued offline: " + ", ".join(str(item.instance_id) for item in queued))
+            print(REPLAY_HINT)
+        if failed:
+            print("Failed: " + ", ".join(str(item.instance_id) for item in failed))
+    if failed:
+        sys.exit(1)
+    return results
+
+
 def cmd_list(args: List[str]) -> None:
     """List Vast.ai instances."""
     from ..services.vast_api import get_vast_client
@@ -82,7 +161,7 @@ def cmd_show(args: List[str]) -> None:
 def cmd_ssh(args: List[str]) -> None:
     """SSH into instance."""
     if not args:
-        print("Usage: train vast ssh <instance_id>")
+        print("Usage: train vast ssh <instance_id> [--log]")
         sys.exit(1)
 
     from ..services.vast_api import get_vast_client
@@ -111,7 +190,12 @@ def cmd_ssh(args: List[str]) -> None:
     source = _describe_ssh_target_source(str(target.get("source") or ""))
     print(f"Connecting to {ssh_host}:{ssh_port} (source: {source})...")
     ssh_cmd = ssh_target_to_command(target)
-    os.system(ssh_cmd)
+    from ..services.terminal_log import mirror_requested, run_interactive
+
+    if mirror_requested(args[1:]):
+        run_interactive(["sh", "-c", ssh_cmd], f"vast-{inst_id}")
+    else:
+        os.system(ssh_cmd)
 
 
 def cmd_run(args: List[str]) -> None:
@@ -173,47 +257,114 @@ def cmd_start(args: List[str]) -> None:
     client = get_vast_client()
 
     print(f"Starting instance {inst_id}...")
-    client.start_instance(inst_id)
+    try:
+        client.start_instance(inst_id)
+    except VastAPIError as exc:
+        if not _queue_if_offline("vast.start", inst_id, exc):
+            raise
+        return
     print("Instance started.")
 
 
 def cmd_stop(args: List[str]) -> None:
-    """Stop instance."""
-    if not args:
-        print("Usage: train vast stop <instance_id>")
-        sys.exit(1)
+    """Stop one or more instances."""
+    ids, as_json = _parse_batch_ids(args, "train vast stop <instance_id>... [--json]")
 
     from ..services.vast_api import get_vast_client
 
-    inst_id = int(args[0])
     client = get_vast_client()
+    if len(ids) > 1 or as_json:
+        from ..services.vast_batch import stop_instances
+
+        _run_batch("stopped", lambda **kw: stop_instances(client, ids, defer=_defer_offline, **kw), as_json)
+        return
+
+    inst_id = ids[0]
 
     print(f"Stopping instance {inst_id}...")
-    client.stop_instance(inst_id)
+    try:
+        client.stop_instance(inst_id)
+    except VastAPIError as exc:
+        if not _queue_if_offline("vast.stop", inst_id, exc):
+            raise
+        return
     print("Instance stopped. (Storage charges still apply)")
 
 
 def cmd_rm(args: List[str]) -> None:
-    """Remove instance."""
+    """Remove one or more instances."""
     if not args:
-        print("Usage: train vast remove <instance_id>")
+        print("Usage: train vast remove <instance_id>... [--json]")
         sys.exit(1)
 
-    inst_id = int(args[0])
+    from ..services.viewer_mode import require_operator
+
+    require_operator("vast.destroy")
+    ids, as_json = _parse_batch_ids(args, "train vast remove <instance_id>... [--json]")
 
-    confirm = prompt_input(f"Remove instance {inst_id}? This cannot be undone. (y/N): ")
+    targets = ", ".join(str(i) for i in ids)
+    noun = "instance" if len(ids) == 1 else "instances"
+    confirm = prompt_input(f"Remove {noun} {targets}? This cannot be undone. (y/N): ")
     if confirm is None or confirm.lower() != "y":
         print("Cancelled.")
         return
 
     from ..services.vast_api import get_vast_client
 
+    from ..services.audit_log import record
+
     client = get_vast_client()
+    if len(ids) > 1 or as_json:
+        from ..services.vast_batch import destroy_instances
+
+        def audited(**kwargs):
+            results = destroy_instances(client, ids, **kwargs)
+            for item in results:
+                record("vast.destroy", str(item.instance_id), host="vast", detail=item.error, status="ok" if item.ok else "failed")
+            return results
+
+        _run_batch("removed", audited, as_json)
+        return
+
+    inst_id = ids[0]
     print(f"Removing instance {inst_id}...")
-    client.rm_instance(inst_id)
+    try:
+        client.rm_instance(inst_id)
+    except Exception as exc:
+        record("vast.destroy", str(inst_id), host="vast", detail=str(exc), status="failed")
+        raise
+    record("vast.destroy", str(inst_id), host="vast")
     print("Instance removed.")
 
 
+def cmd_label(args: List[str]) -> None:
+    """Label one instance (`<id> <label>`) or several (`<id>=<label>...`)."""
+    usage_line = "train vast label <instance_id> <label> | <instance_id>=<label>... [--json]"
+    as_json = "--json" in args
+    args = [arg for arg in args if arg != "--json"]
+    if len(args) == 2 and "=" not in args[0]:
+        args = [f"{args[0]}={args[1]}"]
+    labels = {}
+    for arg in args:
+        instance_id, sep, label = arg.partition("=")
+        try:
+            labels[int(instance_id)] = label
+        except ValueError:
+            sep = ""
+        if not sep:
+            print(f"Usage: {usage_line}")
+            sys.exit(1)
+    if not labels:
+        print(f"Usage: {usage_line}")
+        sys.exit(1)
+
+    from ..services.vast_api import get_vast_client
+    from ..services.vast_batch import label_instances
+
+    client = get_vast_client()
+    _run_batch("labeled", lambda **kw: label_instances(client, labels, defer=_defer_offline, **kw), as_json)
+
+
 def cmd_reboot(args: List[str]) -> None:
     """Reboot instance."""
     if not args:
@@ -233,12 +384,14 @@ def cmd_reboot(args: List[str]) -> None:
 def cmd_search(args: List[str]) -> None:
     """Search for GPU offers."""
     from ..services.vast_api import get_vast_client
+    from ..services.gpu_price_history import record_offer_prices
     from ..services.pricing import format_currency
     from ..utils.vast_formatter import get_currency_settings
 
     print("Searching for GPU offers...")
     client = get_vast_client()
     offers = client.search_offers()
+    record_offer_prices(offers)
 
     if not offers:
         print("No offers found.")
@@ -335,9 +488,15 @@ def main(args: List[str]) -> Optional[str]:
         "stop": cmd_stop,
         "reboot": cmd_reboot,
         "search": cmd_search,
+        "create": cmd_create,
+        "template": cmd_template,
+        "snapshot": cmd_snapshot,
+        "watch": cmd_watch,
+        "events": cmd_events,
         "keys": cmd_keys,
         "attach-key": cmd_attach_key,
         "remove": cmd_rm,
+        "label": cmd_label,
     }
 
     try:
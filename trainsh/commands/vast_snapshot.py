@@ -0,0 +1,134 @@
+"""`train vast snapshot`: turn a running box into a provisioning recipe."""
+
+from __future__ import annotations
+
+import os
+import sys
+from typing import List, Optional, Tuple
+
+from ..core.models import AuthMethod, Host, HostType
+
+SNAPSHOT_USAGE = "train vast snapshot <instance-id|host> [--name NAME] [--output PATH | --print] [--template NAME] [--force]"
+
+
+def _fail(message: str) -> None:
+    print(message)
+    print(f"Usage: {SNAPSHOT_USAGE}")
+    sys.exit(1)
+
+
+def _resolve_target(target: str) -> Tuple[Host, Optional[object]]:
+    """A host to SSH into plus the Vast.ai instance record, when there is one."""
+    from ..services.vast_api import get_vast_client
+    from .host import load_hosts
+
+    instance_id = target if target.isdigit() else ""
+    host = None
+    if not instance_id:
+        host = load_hosts().get(target)
+        if host is None:
+            _fail(f"Host not found: {target}")
+        instance_id = str(host.vast_instance_id or "") if host.type == HostType.VASTAI else ""
+    if host is None:
+        host = Host(
+            name=f"vast-{instance_id}",
+            type=HostType.VASTAI,
+            username="root",
+            auth_method=AuthMethod.KEY,
+            vast_instance_id=instance_id,
+        )
+    instance = None
+    if instance_id:
+        instance = get_vast_client().get_instance(int(instance_id))
+        if instance is None:
+            _fail(f"Instance not found: {instance_id}")
+        if instance.actual_status != "running":
+            _fail(f"Instance not running (status: {instance.actual_status})")
+    return host, instance
+
+
+def cmd_snapshot(args: List[str]) -> None:
+    """Capture a running instance into a `.pyrecipe` (and optionally a template)."""
+    from ..services.instance_snapshot import apply_instance, capture, render_recipe, to_template
+    from ..services.ssh import SSHClient
+    from ..services.vast_templates import load_templates, sanitize_template_name, save_templates
+
+    if not args or args[0].startswith("-"):
+        _fail("Missing instance id or host name.")
+    target = args[0]
+    name = ""
+    output = ""
+    template_name = ""
+    to_stdout = False
+    force = False
+    rest = args[1:]
+    i = 0
+    while i < len(rest):
+        arg = rest[i]
+        if arg in {"--print", "--force"}:
+            to_stdout = to_stdout or arg == "--print"
+            force = force or arg == "--force"
+            i += 1
+            continue
+        if arg not in {"--name", "--output", "--template"}:
+            _fail(f"Unknown option: {arg}")
+        if i + 1 >= len(rest):
+            _fail(f"Missing value for {arg}")
+        value = rest[i + 1]
+        if arg == "--name":
+            name = value
+        elif arg == "--output":
+            output = value
+        else:
+            template_name = sanitize_template_name(value)
+            if not template_name:
+                _fail(f"Invalid template name: {value}")
+        i += 2
+
+    host, instance = _resolve_target(target)
+    name = sanitize_template_name(name or f"{host.name}-setup")
+    print(f"Inspecting {host.name}...", file=sys.stderr if to_stdout else sys.stdout)
+    try:
+        snapshot = capture(SSHClient.from_host(host))
+    except RuntimeError as exc:
+        print(f"Snapshot failed: {exc}")
+        sys.exit(1)
+    snapshot.source = host.name
+    if instance is not None:
+        apply_instance(snapshot, instance)
+
+    text = render_recipe(name, snapshot)
+    if to_stdout:
+        sys.stdout.write(text)
+    else:
+        if not output:
+            from .recipe import get_recipes_dir
+
+            output = os.path.join(get_recipes_dir(), f"{name}.pyrecipe")
+        output = os.path.expanduser(output)
+        if os.path.exists(output) and not force:
+            _fail(f"File exists: {output} (use --force to overwrite)")
+        os.makedirs(os.path.dirname(os.path.abspath(output)), exist_ok=True)
+        with open(output, "w") as f:
+            f.write(text)
+        print(f"Recipe written: {output}")
+        print(
+            f"  {len(snapshot.apt_packages)} apt package(s), {len(snapshot.pip_packages)} pip package(s), "
+            f"{len(snapshot.repos)} repo(s), {len(snapshot.env)} env var(s)"
+            + (", onstart script" if snapshot.onstart else "")
+        )
+        if snapshot.secret_env:
+            print(f"  Secrets referenced by name: {', '.join(snapshot.secret_env)} (set them with `train secrets set`)")
+
+    if template_name:
+        if not snapshot.image:
+            print("Template skipped: the image is only known for Vast.ai instances.", file=sys.stderr)
+            return
+        templates = load_templates()
+        existed = template_name in templates
+        templates[template_name] = to_template(template_name, snapshot)
+        save_templates(templates)
+        print(f"Template {'updated' if existed else 'saved'}: {template_name}", file=sys.stderr if to_stdout else sys.stdout)
+
+
+__all__ = ["SNAPSHOT_USAGE", "cmd_snapshot"]
@@ -0,0 +1,171 @@
+"""`train vast template` and `train vast create`: saved instance creation presets."""
+
+from __future__ import annotations
+
+import os
+import sys
+from typing import List
+
+from ..services.vast_templates import (
+    VastTemplate,
+    create_from_template,
+    describe_template,
+    load_templates,
+    sanitize_template_name,
+    save_templates,
+)
+
+TEMPLATE_USAGE = (
+    "train vast template list\n"
+    "train vast template show <name>\n"
+    "train vast template add <name> --image <image> [--disk GB] [--env KEY=VALUE]... [--onstart <script|@file>]\n"
+    "    [--gpu NAME] [--num-gpus N] [--min-gpu-ram GB] [--max-price USD_PER_HOUR] [--direct] [--label LABEL]\n"
+    "train vast template remove <name>"
+)
+CREATE_USAGE = "train vast create <template> [--offer <offer-id>] [--label <label>]"
+
+_VALUE_FLAGS = {
+    "--image": "image",
+    "--disk": "disk_gb",
+    "--onstart": "onstart",
+    "--gpu": "gpu_name",
+    "--num-gpus": "num_gpus",
+    "--min-gpu-ram": "min_gpu_ram",
+    "--max-price": "max_dph",
+    "--label": "label",
+}
+_NUMBER_FIELDS = {"disk_gb": float, "num_gpus": int, "min_gpu_ram": float, "max_dph": float}
+
+
+def _fail(message: str, usage: str = TEMPLATE_USAGE) -> None:
+    print(message)
+    print(f"Usage: {usage}")
+    sys.exit(1)
+
+
+def _parse_template(name: str, args: List[str], base: VastTemplate) -> VastTemplate:
+    values = base.to_dict()
+    values["name"] = name
+    env = dict(base.env)
+    i = 0
+    while i < len(args):
+        arg = args[i]
+        if arg == "--direct":
+            values["direct"] = True
+            i += 1
+            continue
+        if arg not in _VALUE_FLAGS and arg != "--env":
+            _fail(f"Unknown option: {arg}")
+        if i + 1 >= len(args):
+            _fail(f"Missing value for {arg}")
+        value = args[i + 1]
+        i += 2
+        if arg == "--env":
+            key, sep, env_value = value.partition("=")
+            if not sep or not key:
+                _fail(f"Invalid --env value: {value} (expected KEY=VALUE)")
+            env[key] = env_value
+            continue
+        field_name = _VALUE_FLAGS[arg]
+        if field_name == "onstart" and value.startswith("@"):
+            path = os.path.expanduser(value[1:])
+            try:
+                with open(path, "r") as f:
+                    value = f.read()
+            except OSError as exc:
+                _fail(f"Cannot read onstart script: {exc}")
+        if field_name in _NUMBER_FIELDS:
+            try:
+                values[field_name] = _NUMBER_FIELDS[field_name](value)
+            except ValueError:
+                _fail(f"Invalid value for {arg}: {value}")
+        else:
+            values[field_name] = value
+    values["env"] = env
+    return VastTemplate.from_dict(values)
+
+
+def cmd_template(args: List[str]) -> None:
+    """Manage saved Vast.ai creation templates."""
+    action = args[0] if args else "list"
+    rest = args[1:]
+    templates = load_templates()
+
+    if action == "list":
+        if not templates:
+            print("No Vast.ai templates saved.")
+            print("Use 'train vast template add <name> --image <image>' to add one.")
+            return
+        for name, template in sorted(templates.items()):
+            print(f"  {name:<20} {template.image}  ({template.disk_gb:g} GB)")
+        return
+
+    if action not in {"show", "add", "remove"} or not rest:
+        _fail("Missing template name." if action in {"show", "add", "remove"} else f"Unknown action: {action}")
+    name = sanitize_template_name(rest[0])
+    if not name:
+        _fail(f"Invalid template name: {rest[0]}")
+
+    if action == "show":
+        if name not in templates:
+            _fail(f"Vast template not found: {name}")
+        print(f"Template: {name}")
+        for line in describe_template(templates[name]):
+            print(f"  {line}")
+        return
+
+    if action == "remove":
+        if name not in templates:
+            _fail(f"Vast template not found: {name}")
+        del templates[name]
+        save_templates(templates)
+        print(f"Template removed: {name}")
+        return
+
+    existing = templates.get(name)
+    if existing is None and "--image" not in rest:
+        _fail("New templates need --image.")
+    templates[name] = _parse_template(name, rest[1:], existing or VastTemplate(name=name))
+    save_templates(templates)
+    print(f"Template {'updated' if existing else 'saved'}: {name}")
+
+
+def cmd_create(args: List[str]) -> None:
+    """Create a Vast.ai instance from a saved template."""
+    from ..services.vast_api import VastAPIError, get_vast_client
+
+    if not args or args[0].startswith("-"):
+        _fail("Missing template name.", CREATE_USAGE)
+    name = args[0]
+    offer_id = None
+    label = None
+    rest = args[1:]
+    i = 0
+    while i < len(rest):
+        if rest[i] in {"--offer", "--label"} and i + 1 < len(rest):
+            if rest[i] == "--offer":
+                if not rest[i + 1].isdigit():
+                    _fail(f"Invalid offer id: {rest[i + 1]}", CREATE_USAGE)
+                offer_id = int(rest[i + 1])
+            else:
+                label = rest[i + 1]
+            i += 2
+            continue
+        _fail(f"Unknown option: {rest[i]}", CREATE_USAGE)
+
+    templates = load_templates()
+    if name not in templates:
+        _fail(f"Vast template not found: {name}", CREATE_USAGE)
+
+    try:
+        instance_id, used_offer = create_from_template(
+            get_vast_client(), templates[name], offer_id=offer_id, label=label
+        )
+    except VastAPIError as exc:
+        print(f"Create failed: {exc}")
+        sys.exit(1)
+    print(f"Created instance {instance_id} from offer {used_offer} (template {name}).")
+    print(f"Use 'train vast show {instance_id}' to watch it start.")
+
+
+__all__ = ["CREATE_USAGE", "TEMPLATE_USAGE", "cmd_create", "cmd_template"]
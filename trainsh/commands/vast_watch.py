@@ -0,0 +1,122 @@
+"""`train vast watch` and `train vast events`: instance state history."""
+
+from __future__ import annotations
+
+import os
+import subprocess
+import sys
+import time
+from typing import Any, Dict, List
+
+from ..services.vast_events import EVENT_KINDS, VastInstanceWatcher, describe_event
+
+WATCH_USAGE = "train vast watch [--once] [--interval SECS] [--on KIND]... [--exec <command>]"
+EVENTS_USAGE = "train vast events [<id>] [--limit N]"
+
+
+def _usage(usage: str) -> None:
+    print(f"Usage: {usage}")
+    sys.exit(1)
+
+
+def run_event_hook(command: str, event: Dict[str, Any]) -> int:
+    """Run `--exec` for one event with the event fields in the environment."""
+    env = dict(os.environ)
+    env.update(
+        {
+            "TRAINSH_VAST_EVENT": str(event.get("kind", "")),
+            "TRAINSH_VAST_INSTANCE_ID": str(event.get("instance_id", "")),
+            "TRAINSH_VAST_STATUS": str(event.get("status", "") or ""),
+            "TRAINSH_VAST_SSH": str(event.get("ssh", "") or ""),
+        }
+    )
+    return subprocess.run(command, shell=True, env=env).returncode
+
+
+def _print_event(event: Dict[str, Any]) -> None:
+    print(f"  {str(event.get('ts', ''))[:19]}  #{event.get('instance_id')}  {event.get('kind'):<14} {describe_event(event)}")
+
+
+def cmd_watch(args: List[str]) -> None:
+    """Poll instances and record state changes; optionally run a command per event."""
+    from ..services.vast_api import VastAPIError, get_vast_client
+
+    once = False
+    interval = 30.0
+    kinds: List[str] = []
+    hook = ""
+    index = 0
+    while index < len(args):
+        option = args[index]
+        index += 1
+        if option == "--once":
+            once = True
+        elif option == "--interval" and index < len(args):
+            try:
+                interval = max(5.0, float(args[index]))
+            except ValueError:
+                _usage(WATCH_USAGE)
+            index += 1
+        elif option == "--on" and index < len(args):
+            if args[index] not in EVENT_KINDS:
+                print(f"Unknown event kind: {args[index]} (expected one of {', '.join(EVENT_KINDS)})")
+                sys.exit(1)
+            kinds.append(args[index])
+            index += 1
+        elif option == "--exec" and index < len(args):
+            hook = args[index]
+            index += 1
+        else:
+            _usage(WATCH_USAGE)
+
+    watcher = VastInstanceWatcher()
+    client = get_vast_client()
+    print("Watching Vast.ai instances" + ("" if once else f" every {interval:g}s (Ctrl+C to stop)") + "...")
+    try:
+        while True:
+            try:
+                events = watcher.observe(client.list_instances())
+            except VastAPIError as exc:
+                print(f"  poll failed: {exc}")
+                events = []
+            for event in events:
+                _print_event(event)
+                if hook and (not kinds or event.get("kind") in kinds):
+                    code = run_event_hook(hook, event)
+                    if code != 0:
+                        print(f"  hook exited with {code}")
+            if once:
+                break
+            time.sleep(interval)
+    except KeyboardInterrupt:
+        print("\nStopped.")
+
+
+def cmd_events(args: List[str]) -> None:
+    """Show recorded state changes for one instance, or all of them."""
+    instance_id = None
+    limit = 50
+    index = 0
+    while index < len(args):
+        option = args[index]
+        index += 1
+        if option == "--limit" and index < len(args):
+            if not args[index].isdigit():
+                _usage(EVENTS_USAGE)
+            limit = int(args[index])
+            index += 1
+        elif not option.startswith("-") and instance_id is None:
+            instance_id = option
+        else:
+            _usage(EVENTS_USAGE)
+
+    events = VastInstanceWatcher().history(instance_id)
+    if not events:
+        target = f" for instance {instance_id}" if instance_id else ""
+        print(f"No recorded events{target}. Run `train vast watch` to start recording.")
+        return
+    for event in events[-limit:]:
+        _print_event(event)
+
+
+__all__ = ["EVENTS_USAGE", "WATCH_USAGE", "cmd_events", "cmd_watch", "run_event_hook"]
@@ -0,0 +1,130 @@
+# tmux-trainsh wandb command
+# List W&B runs and link them to recipe executions
+
+from __future__ import annotations
+
+import json
+import sys
+from typing import List, Optional
+
+from ..cli_utils import dispatch_subcommand
+from .help_catalog import render_command_help
+from .help_cmd import reject_subcommand_help
+
+usage = render_command_help("wandb")
+
+
+def _parse_options(args: List[str], flags: set, valued: set) -> tuple[dict, List[str]]:
+    options: dict = {}
+    positional: List[str] = []
+    i = 0
+    while i < len(args):
+        arg = args[i]
+        if arg in valued and i + 1 < len(args):
+            options[arg] = args[i + 1]
+            i += 2
+            continue
+        if arg in flags:
+            options[arg] = True
+        elif arg.startswith("-") and arg != "--last":
+            print(f"Unknown option: {arg}")
+            print(usage)
+            sys.exit(1)
+        else:
+            positional.append(arg)
+        i += 1
+    return options, positional
+
+
+def _project(options: dict) -> str:
+    from ..config import get_config_value
+
+    project = str(options.get("--project") or get_config_value("wandb.project", "") or "").strip()
+    if not project:
+        print("No W&B project: pass --project ENTITY/PROJECT or set it with")
+        print("  train config set wandb.project ENTITY/PROJECT")
+        sys.exit(1)
+    return project
+
+
+def cmd_runs(args: List[str]) -> None:
+    """List recent runs of a W&B project."""
+    from ..services.wandb_api import get_wandb_client
+
+    options, positional = _parse_options(args, {"--json"}, {"--project", "-n"})
+    if positional:
+        options.setdefault("--project", positional[0])
+    limit = str(options.get("-n", "20"))
+    if not limit.isdigit():
+        print(f"Invalid -n: {limit}")
+        sys.exit(1)
+    runs = get_wandb_client().list_runs(_project(options), limit=int(limit))
+    if options.get("--json"):
+        print(json.dumps([{**vars(run), "url": run.url} for run in runs], indent=2))
+        return
+    if not runs:
+        print("No W&B runs found.")
+        return
+    print(f"{'Run':<10} {'Name':<24} {'State':<10} {'Created':<20} {'Host':<16} Metrics")
+    print("-" * 98)
+    for run in runs:
+        metrics = " ".join(f"{key}={value:.4g}" for key, value in list(run.final_metrics().items())[:3])
+        print(f"{run.id[:10]:<10} {run.name[:24]:<24} {run.state[:10]:<10} {run.created_at[:19]:<20} {run.host[:16]:<16} {metrics}")
+
+
+def cmd_link(args: List[str]) -> None:
+    """Match finished executions to W&B runs and store the linkage."""
+    from ..config import get_config_value
+    from ..core.runtime_store import RuntimeStore
+    from ..services.wandb_api import DEFAULT_MATCH_WINDOW_SECS, get_wandb_client, link_executions
+
+    options, positional = _parse_options(args, {"--dry-run", "--all"}, {"--project", "-n", "--window"})
+    store = RuntimeStore()
+    run_ids: Optional[List[str]] = None
+    if positional and positional[0] == "--last":
+        latest = store.list_runs(limit=1)
+        run_ids = [str(latest[0].get("run_id", ""))] if latest else []
+    elif positional:
+        known = [str(run.get("run_id", "")) for run in store.list_runs()]
+        run_ids = [next((item for item in known if item.startswith(prefix)), prefix) for prefix in positional]
+    elif not options.get("--all"):
+        run_ids = [str(run.get("run_id", "")) for run in store.list_runs(limit=20)]
+    window = str(options.get("--window") or get_config_value("wandb.match_window_secs", DEFAULT_MATCH_WINDOW_SECS))
+    limit = str(options.get("-n", "100"))
+    if not window.isdigit() or not limit.isdigit():
+        print("--window and -n take whole numbers.")
+        sys.exit(1)
+
+    runs = get_wandb_client().list_runs(_project(options), limit=int(limit))
+    dry_run = bool(options.get("--dry-run"))
+    linked = link_executions(store, runs, run_ids=run_ids, window_secs=int(window), dry_run=dry_run)
+    if not linked:
+        print("No executions matched a W&B run.")
+        return
+    verb = "Would link" if dry_run else "Linked"
+    for run_id, run in linked:
+        metrics = " ".join(f"{key}={value:.4g}" for key, value in list(run.final_metrics().items())[:3])
+        print(f"{verb} {run_id[:10]} -> {run.name} ({run.url}) {metrics}".rstrip())
+
+
+def main(args: List[str]) -> Optional[str]:
+    """Main entry point for wandb command."""
+    if not args:
+        print(usage)
+        return None
+    if args[0] in ("-h", "--help", "help"):
+        reject_subcommand_help()
+
+    try:
+        handler = dispatch_subcommand(args[0], commands={"runs": cmd_runs, "link": cmd_link})
+    except KeyError:
+        print(f"Unknown subcommand: {args[0]}")
+        print(usage)
+        sys.exit(1)
+
+    try:
+        handler(args[1:])
+    except (RuntimeError, ValueError) as exc:
+        print(f"Error: {exc}")
+        sys.exit(1)
+    return None
@@ -1,10 +1,15 @@
 # tmux-trainsh configuration loading
 
+import copy
 import os
 from typing import Any, Dict
 import yaml
 
 from .constants import CONFIG_DIR, CONFIG_FILE
+from .core.env_refs import expand_env_refs, restore_env_refs
+
+# Profile entries that describe the profile itself rather than override config keys.
+PROFILE_META_KEYS = ("description", "isolate_data")
 
 
 def ensure_config_dir() -> None:
@@ -16,20 +21,99 @@ def load_config() -> Dict[str, Any]:
     """
     Load the main configuration file.
 
+    `${env:NAME}` references in config.yaml are expanded here.
+
     Returns:
         Configuration dictionary
     """
     ensure_config_dir()
 
-    if not CONFIG_FILE.exists():
-        return get_default_config()
+    config = expand_env_refs(read_config_file(), where=CONFIG_FILE.name)
+
+    # Merge with defaults, then the active profile's overrides
+    defaults = get_default_config()
+    merged = merge_dicts(defaults, config)
+    overrides = profile_overrides(merged)
+    return merge_dicts(merged, overrides) if overrides else merged
 
+
+def read_config_file() -> Dict[str, Any]:
+    """config.yaml as written, without defaults or profile overrides."""
+    if not CONFIG_FILE.exists():
+        return {}
     with open(CONFIG_FILE, "r") as f:
-        config = yaml.safe_load(f) or {}
+        return yaml.safe_load(f) or {}
 
-    # Merge with defaults
-    defaults = get_default_config()
-    return merge_dicts(defaults, config)
+
+def active_profile_name() -> str:
+    """Active config profile, or "" for the base configuration."""
+    from .constants import read_active_profile
+
+    return read_active_profile()
+
+
+def profile_overrides(config: Dict[str, Any], name: str = "") -> Dict[str, Any]:
+    """Config keys a profile overrides, without its own metadata."""
+    name = name or active_profile_name()
+    profile = (config.get("profiles") or {}).get(name) if name else None
+    if not isinstance(profile, dict):
+        return {}
+    return {key: value for key, value in profile.items() if key not in PROFILE_META_KEYS}
+
+
+_MISSING = object()
+
+
+def _flatten(data: Dict[str, Any], prefix: str = "") -> Dict[str, Any]:
+    flat: Dict[str, Any] = {}
+    for key, value in data.items():
+        path = f"{prefix}{key}"
+        if isinstance(value, dict) and value:
+            flat.update(_flatten(value, f"{path}."))
+        else:
+            flat[path] = value
+    return flat
+
+
+def _lookup(data: Dict[str, Any], path: str) -> Any:
+    for key in path.split("."):
+        if not isinstance(data, dict) or key not in data:
+            return _MISSING
+        data = data[key]
+    return data
+
+
+def _assign(data: Dict[str, Any], path: str, value: Any) -> None:
+    keys = path.split(".")
+    for key in keys[:-1]:
+        if not isinstance(data.get(key), dict):
+            data[key] = {}
+        data = data[key]
+    if value is _MISSING:
+        data.pop(keys[-1], None)
+    else:
+        data[keys[-1]] = value
+
+
+def _unapply_profile(config: Dict[str, Any]) -> Dict[str, Any]:
+    """Undo the active profile overlay before writing config.yaml.
+
+    Keys the profile overrides keep their base value on disk; edits made to
+    them while the profile is active are stored in the profile instead.
+    """
+    name = active_profile_name()
+    overrides = profile_overrides(config, name)
+    if not overrides:
+        return config
+    base = merge_dicts(get_default_config(), read_config_file())
+    result = copy.deepcopy(config)
+    profile = result["profiles"][name]
+    for path, value in _flatten(overrides).items():
+        current = _lookup(result, path)
+        if current is not _MISSING and current != value:
+            _assign(profile, path, current)
+        _assign(result, path, _lookup(base, path))
+    return result
 
 
 def save_config(config: Dict[str, Any]) -> None:
@@ -39,7 +123,13 @@ def save_config(config: Dict[str, Any]) -> None:
     Args:
         config: Configuration dictionary
     """
+    write_config_file(_unapply_profile(config))
+
+
+def write_config_file(config: Dict[str, Any]) -> None:
+    """Write config.yaml as given (no profile handling), keeping unchanged `${env:...}` references."""
     ensure_config_dir()
+    config = restore_env_refs(read_config_file(), config)
 
     with open(CONFIG_FILE, "w") as f:
         yaml.dump(config, f, default_flow_style=False, sort_keys=False)
@@ -50,10 +140,33 @@ def get_default_config() -> Dict[str, Any]:
     return {
         "vast": {
             "auto_attach_ssh_key": True,
+            # Seconds to reuse a `vast:<id>` link's endpoint before asking the API again
+            "link_cache_secs": 60,
+            # Retries for rate-limited, 5xx or unreachable API calls (non-idempotent calls only retry 429).
+            "api_retries": 3,
+            # Base and cap of the jittered exponential backoff between retries.
+            "api_backoff_secs": 1.0,
+            "api_max_backoff_secs": 30,
+            # Minimum gap between API requests made with the same key, so polling loops stay under the rate limit.
+            "api_min_interval_secs": 0.25,
+            # Seconds GET responses are reused, by endpoint (0 disables); mutations drop stale entries.
+            "api_cache_ttls": {"instances": 5, "ssh": 300},
+            # Instances handled at once by multi-instance stop/remove/label.
+            "batch_parallelism": 4,
         },
         "ui": {
             "currency": "",
         },
+        "defaults": {
+            # Suggested key for `train host add` and attached to new Vast.ai instances.
+            "ssh_key_path": "~/.ssh/id_rsa",
+        },
+        "secrets": {
+            # Prepended to secret names on get/set, e.g. "WORK_" for a work profile's own VAST_API_KEY.
+            "key_prefix": "",
+        },
+        # Named overrides of this file (`train config profile`); see PROFILE_META_KEYS.
+        "profiles": {},
         "tmux": {
             # Auto-create local tmux splits that attach to recipe windows
             "auto_bridge": True,
@@ -100,6 +213,316 @@ def get_default_config() -> Dict[str, Any]:
             # If true, any channel failure fails the notify step.
             "fail_on_error": False,
         },
+        "connectivity": {
+            # TCP endpoints used to decide whether the local network is up after an
+            # SSH failure; point them at reachable hosts on restricted networks, or
+            # use [] to skip the check.
+            "probe_addresses": ["1.1.1.1:53", "8.8.8.8:53"],
+            # Reuse the last network check for this long instead of probing on every failure.
+            "network_check_ttl_secs": 30,
+            # Short-circuit commands against hosts that failed a probe this recently.
+            "offline_grace_secs": 15,
+            # Queue idempotent Vast operations (stop/start) while offline.
+            "queue_offline_ops": True,
+        },
+        "dashboard": {
+            # Reuse the last `train dashboard` snapshot for this many seconds.
+            "cache_ttl_secs": 30,
+        },
+        "prometheus": {
+            # `train dashboard --prometheus` listen address and port.
+            "bind": "127.0.0.1",
+            "port": 9464,
+            # Hosts sampled for GPU/CPU gauges; empty samples every configured host.
+            "hosts": [],
+        },
+        "storage": {
+            # `train storage delete` and recipe storage deletes move paths into the
+            # backend's `.trash/` instead (`trash=False` / `--hard` bypasses it).
+            "trash": {
+                # Default for recipe `storage_delete`; `train storage delete` always soft-deletes unless --hard.
+                "enabled": False,
+                # Trashed entries older than this are purged on the next soft delete (0 keeps them).
+                "retention_days": 7,
+            },
+        },
+        "disk_usage": {
+            # Reuse `train host du` scans for this many seconds.
+            "cache_ttl_secs": 900,
+            # Foreground scans slower than this continue as a background job on the host.
+            "scan_timeout_secs": 60,
+        },
+        # Named env-var sets for tmux sessions: {"hf": {"HF_TOKEN": "${secret:HF_TOKEN}"}}.
+        "env_sets": {},
+        "integrity": {
+            # Parallel sha256 workers for `train host manifest|verify`, on the host and locally.
+            "hash_jobs": 4,
+            # Hashing a large remote directory can take a while; 0 waits forever.
+            "remote_timeout_secs": 7200,
+        },
+        "docker": {
+            # Docker CLI on hosts; e.g. "sudo -n docker" when the SSH user is not in the docker group.
+            "command": "docker",
+        },
+        "gpu_guard": {
+            # Before a training/serving launch: off | warn | confirm (ask on a TTY, else fail) | fail.
+            "mode": "confirm",
+            # A selected GPU counts as busy once this much memory is already allocated on it.
+            "min_memory_mb": 1024,
+        },
+        "local_policy": {
+            # Commands recipes run on this machine. Regexes matched against each
+            # `;`/`&&`/`||`/`|` segment: deny always wins; a non-empty allow list
+            # blocks anything it does not match (skipped for trusted recipes).
+            "allow": [],
+            "deny": [],
+            # sudo/doas/su and `rm -r` outside the working directory in untrusted
+            # recipes: off | warn | confirm (ask on a TTY, else fail) | fail.
+            # Defaults to warn so unattended runs keep working; set confirm or
+            # fail to stop such commands.
+            "privileged": "warn",
+        },
+        "viewer": {
+            # Viewer mode: destroying instances, deleting hosts, storage paths or
+            # secrets, and destructive recipe steps are refused. Toggle with
+            # `train config viewer on|off`; the hash guards turning it off.
+            "enabled": False,
+            "passphrase_hash": "",
+        },
+        "cloudflared": {
+            # cloudflared binary for `tunnel_type: cloudflared` hosts without their own cloudflared_bin.
+            "command": "cloudflared",
+            # Seconds between health checks of `train host cloudflared forward` tunnels.
+            "health_interval_secs": 30,
+        },
+        "tailscale": {
+            # Tailscale CLI used to resolve hosts linked with `train host tailscale link`.
+            "command": "tailscale",
+        },
+        "host_metrics": {
+            # Seconds between /proc + nvidia-smi samples per host.
+            "interval_secs": 5,
+            # Samples kept per host in memory for host_metrics_recent().
+            "buffer_size": 720,
+        },
+        "wandb": {
+            # Default ENTITY/PROJECT for `train wandb runs` and `train wandb link`.
+            "project": "",
+            # Final W&B metrics shown next to linked runs in `train recipe logs`.
+            "list_metrics": ["loss"],
+            # Slack (seconds) around an execution's start/end when matching W&B runs by time.
+            "match_window_secs": 600,
+        },
+        "tracking": {
+            # MLflow tracking server (http://host:5000); empty turns run tracking off.
+            "mlflow_uri": "",
+            # Experiment for every run; empty uses the recipe name.
+            "experiment": "",
+            # Names whose `name=value` / `name: value` readings in step output become MLflow metrics.
+            "metrics": ["loss"],
+        },
+        "tracing": {
+            # OTLP/HTTP collector (http://host:4318); empty falls back to OTEL_EXPORTER_OTLP_ENDPOINT, else tracing is off.
+            "otlp_endpoint": "",
+            # `service.name` resource attribute of exported spans.
+            "service_name": "trainsh",
+            # Extra request headers, e.g. {"Authorization": "Bearer ..."} for hosted collectors.
+            "headers": {},
+        },
+        "energy": {
+            # Seconds between nvidia-smi power.draw samples during a run; 0 disables energy accounting.
+            "interval_secs": 30,
+        },
+        "project": {
+            # Project that scopes listings and supplies recipe variable defaults (`train project use`).
+            "active": "",
+        },
+        "sync": {
+            # Storage backend (name from `train storage list`) for `train sync`; empty disables sync.
+            "storage": "",
+            # Folder on that storage holding manifest.json and the synced files.
+            "path": "tmux-trainsh-sync",
+            # Name recorded with changes and conflict copies; defaults to the hostname.
+            "device": "",
+        },
+        "search": {
+            # Runs whose execution log events stay in the `train search` index.
+            "log_runs": 50,
+        },
+        "ssh_export": {
+            # OpenSSH include written by `train host ssh-config` with one `Host` per managed host.
+            "file": "~/.ssh/config.d/tmux-trainsh",
+            # Prepended to host names to form the ssh alias.
+            "alias_prefix": "",
+            # Rewrite the include whenever hosts.yaml is saved.
+            "auto_refresh": False,
+        },
+        "vscode": {
+            # Include file for generated `Host trainsh-*` entries used by VS Code Remote-SSH.
+            "ssh_config_file": "~/.ssh/trainsh_config",
+        },
+        "recipe": {
+            # Fail a step when interpolation leaves an undefined ${NAME} behind.
+            "strict_variables": False,
+            # Pass ${NAME} values to shell commands as quoted TRAINSH_VAR_* env vars
+            # instead of splicing them into the text; Recipe(safe_variables=...) overrides.
+            "safe_variables": False,
+            # Ask for undefined ${NAME} values before `train recipe run` starts (TTY only).
+            "prompt_missing_variables": False,
+            # Check hosts for tmux, git, rsync, ... before the first step runs.
+            "preflight": False,
+            # With preflight, install missing tools that have an unattended installer.
+            "preflight_install": False,
+            # Before `train run`/`exec` (and `recipe run|exec|schedule run`), continue
+            # running jobs whose `train` process died (`train recipe reconnect <job>`
+            # in the background).
+            "auto_reconnect": True,
+            # Output lines per step kept in memory for `train recipe tail`.
+            "output_tail_lines": 200,
+            # Batch step output into one write per this many seconds and keep only the
+            # latest wait/transfer progress poll per window (0 writes each as it comes).
+            "output_coalesce_secs": 0,
+        },
+        "terminal": {
+            # ssh ServerAliveInterval for interactive sessions; 0 leaves ssh's own setting.
+            "keepalive_interval_secs": 15,
+            # Missed keep-alives before ssh gives up on a dead connection.
+            "keepalive_count_max": 3,
+            # Re-attach recipe window panes to their tmux session after the connection drops.
+            "reconnect": True,
+            # First wait before a reconnect probe; doubles up to reconnect_max_delay_secs.
+            "reconnect_delay_secs": 2,
+            "reconnect_max_delay_secs": 30,
+            # Failed probes before the pane gives up (0 = keep trying).
+            "reconnect_max_attempts": 0,
+            # TERM and LC_ALL set in front of commands trainsh starts with a tty.
+            "term": "xterm-256color",
+            "locale": "en_US.UTF-8",
+            # Local variables interactive ssh offers the server (it keeps those its AcceptEnv allows).
+            "send_env": ["LANG", "LC_*", "COLORTERM"],
+            # Exported in login shells, recipe tmux sessions and tty commands on every host.
+            "default_env_vars": {},
+            # Wrap pastes in bracketed-paste markers when the pane's program asked for them.
+            "paste_bracketed": True,
+            # Pastes are written in chunks of at most this many bytes, split at line ends.
+            "paste_chunk_bytes": 4096,
+            # Pause between unbracketed chunks so the program can drain its input.
+            "paste_chunk_delay_ms": 20,
+            # `train host paste` asks before sending more than this (0 = never ask).
+            "paste_confirm_bytes": 256 * 1024,
+        },
+        "alerts": {
+            # Rules matched against lines of `train host follow`, e.g.
+            # {oom: {pattern: "CUDA out of memory", action: cancel, cooldown_secs: 600}}.
+            "rules": {},
+        },
+        "terminal_log": {
+            # Mirror every `train host|vast|runpod ssh` session, not only those run with --log.
+            "enabled": False,
+            # Rotate a terminal's log file past this size.
+            "max_bytes": 10 * 1024 * 1024,
+            # Rotated files kept per terminal.
+            "keep": 3,
+        },
+        "host_refresh": {
+            # Per-host (and per-provider listing) timeout for `train host refresh` and host discovery.
+            "timeout_secs": 20,
+            # Hosts probed at once.
+            "workers": 8,
+        },
+        "latency": {
+            # Route latency samples kept per host for `train host latency --history` and `train host show`.
+            "history_size": 100,
+            # Time every route of online hosts on each `train host monitor` pass (same as --latency).
+            "monitor": False,
+        },
+        "ssh": {
+            # Share one OpenSSH ControlMaster connection per host across `host_exec` calls.
+            "multiplex": True,
+            # Seconds an idle shared connection stays open after the last command.
+            "control_persist_secs": 60,
+        },
+        "sftp": {
+            # List remote directories over the sftp subsystem; false always parses `ls -la`.
+            "enabled": True,
+            # Seconds one sftp listing may take before falling back.
+            "timeout_secs": 30,
+        },
+        "path_stats": {
+            # Seconds `du`/`find` may run when sizing a path; slower scans report partial totals.
+            "timeout_secs": 60,
+        },
+        "logs": {
+            # Step output written to the run log, per step and per run (0 = unlimited).
+            # Past a cap the rest is dropped and a `step_output_truncated` marker is logged.
+            "max_step_output_bytes": 50 * 1024 * 1024,
+            "max_run_output_bytes": 200 * 1024 * 1024,
+            # Rotate runtime/events.jsonl into events.jsonl.1 … once it grows past this size.
+            "rotate_bytes": 256 * 1024 * 1024,
+            # Rotated event files to keep; older ones are deleted.
+            "rotate_keep": 4,
+            # Delete finished runs older than this many days when a recipe starts (0 keeps them).
+            "retention_days": 0,
+            # Keep only the newest N runs when a recipe starts (0 keeps all).
+            "keep_runs": 0,
+        },
+        "bootstrap": {
+            # Profile applied on `train host add` and the first `train host ssh`
+            # for hosts without their own `bootstrap_profile`. Empty disables it.
+            "default_profile": "",
+            "profiles": {
+                "basic": {
+                    "packages": ["tmux", "htop"],
+                    "timezone": "UTC",
+                    "tmux_conf": "set -g history-limit 100000\nset -g mouse on\n",
+                    "commands": [],
+                },
+            },
+        },
+        "transfer": {
+            # Run rclone on the host for host <-> cloud transfers and refuse local
+            # relays for host <-> host (`train transfer --remote`, recipe `remote_exec=True`).
+            "remote_exec": False,
+            # Defaults for rsync-based transfers; `train transfer` flags and the
+            # recipe `rsync={...}` transfer option override them per transfer.
+            "rsync": {
+                "compress": True,
+                # "partial" keeps interrupted files; "append-verify" also resumes them.
+                "partial": "",
+                # rsync --bwlimit value such as "20M"; empty means unlimited.
+                "bwlimit": "",
+                "exclude": [],
+            },
+            # `train transfer --tar-stream` / recipe `tar_stream=True`.
+            "tar_stream": {
+                "level": 3,
+                # Suggest tar streaming when a local source dir has this many files (0 disables).
+                "suggest_files": 100000,
+            },
+            # Throughput `train transfer --preflight` assumes for its ETA (MiB/s).
+            "estimate_mibps": 50,
+            # `train transfer queue`: concurrent transfers per destination host/bucket.
+            "queue": {
+                "max_per_host": 1,
+                # Per-destination overrides, e.g. {"gpu": 2, "r2:datasets": 4}.
+                "host_limits": {},
+            },
+        },
+        "timeouts": {
+            # Applied to steps that set neither `timeout` nor `execution_timeout`.
+            # 0 leaves that operation class unbounded.
+            # One-shot shell and ssh commands (`recipe.shell`, `ssh_command`, `uv_run`).
+            "command_secs": 3600,
+            # Commands typed into tmux sessions: training runs for hours or days.
+            "session_secs": 0,
+            # Dataset and checkpoint copies scale with their size.
+            "transfer_secs": 0,
+            "http_secs": 300,
+            # Wait steps already default to their own timeout.
+            "wait_secs": 0,
+            # Wall-clock limit for a whole recipe run (`--max-runtime` overrides).
+            "execution_secs": 0,
+        },
     }
 
 
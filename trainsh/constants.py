@@ -16,13 +16,44 @@ CONFIG_DIR = CONFIG_HOME / "tmux-trainsh"
 DATA_DIR = DATA_HOME / "tmux-trainsh"
 STATE_DIR = STATE_HOME / "tmux-trainsh"
 CONFIG_FILE = CONFIG_DIR / "config.yaml"
-HOSTS_FILE = CONFIG_DIR / "hosts.yaml"
-STORAGES_FILE = CONFIG_DIR / "storages.yaml"
+
+# Config profiles (`train config profile`); the active one is read once per process.
+PROFILES_DIR = CONFIG_DIR / "profiles"
+ACTIVE_PROFILE_FILE = CONFIG_DIR / "active_profile"
+
+
+def read_active_profile() -> str:
+    """`TRAINSH_PROFILE`, else the name saved by `train config profile switch`."""
+    name = os.environ.get("TRAINSH_PROFILE")
+    if name is None:
+        try:
+            name = ACTIVE_PROFILE_FILE.read_text(encoding="utf-8")
+        except OSError:
+            name = ""
+    return name.strip()
+
+
+ACTIVE_PROFILE = read_active_profile()
+# Profiles created with --isolate keep hosts, storages, projects, logs and run state apart.
+_ISOLATED = bool(ACTIVE_PROFILE) and (PROFILES_DIR / ACTIVE_PROFILE).is_dir()
+PROFILE_CONFIG_DIR = PROFILES_DIR / ACTIVE_PROFILE if _ISOLATED else CONFIG_DIR
+PROFILE_DATA_DIR = DATA_DIR / "profiles" / ACTIVE_PROFILE if _ISOLATED else DATA_DIR
+PROFILE_STATE_DIR = STATE_DIR / "profiles" / ACTIVE_PROFILE if _ISOLATED else STATE_DIR
+
+HOSTS_FILE = PROFILE_CONFIG_DIR / "hosts.yaml"
+STORAGES_FILE = PROFILE_CONFIG_DIR / "storages.yaml"
+VAST_TEMPLATES_FILE = PROFILE_CONFIG_DIR / "vast_templates.yaml"
+PROJECTS_FILE = PROFILE_CONFIG_DIR / "projects.yaml"
 RECIPES_DIR = DATA_DIR / "recipes"
-LOGS_DIR = DATA_DIR / "logs"
-RUNTIME_STATE_DIR = STATE_DIR / "runtime"
+# JSON Schema of recipe documents for editors (`train recipe schema`).
+RECIPE_SCHEMA_FILE = DATA_DIR / "schemas" / "recipe.schema.json"
+LOGS_DIR = PROFILE_DATA_DIR / "logs"
+RUNTIME_STATE_DIR = PROFILE_STATE_DIR / "runtime"
+# Under the host's $HOME: uploaded step scripts, one directory per content hash.
+SCRIPT_CACHE_DIR = ".cache/trainsh/scripts"
 RECIPE_FILE_EXTENSION = ".pyrecipe"
-RECIPE_FILE_EXTENSIONS = (RECIPE_FILE_EXTENSION,)
+YAML_RECIPE_EXTENSIONS = (".yaml", ".yml")
+RECIPE_FILE_EXTENSIONS = (RECIPE_FILE_EXTENSION, *YAML_RECIPE_EXTENSIONS)
 
 # Keyring service name
 KEYRING_SERVICE = "tmux-trainsh"
@@ -31,6 +62,7 @@ KEYRING_SERVICE = "tmux-trainsh"
 VAST_API_BASE = "https://console.vast.ai/api/v0"
 RUNPOD_REST_API_BASE = "https://rest.runpod.io/v1"
 RUNPOD_GRAPHQL_API_BASE = "https://api.runpod.io/graphql"
+WANDB_API_BASE = "https://api.wandb.ai"
 
 # Default settings
 DEFAULT_SSH_KEY_PATH = "~/.ssh/id_rsa"
@@ -51,6 +83,8 @@ class SecretKeys:
     OPENROUTER_API_KEY = "OPENROUTER_API_KEY"
     ANTHROPIC_API_KEY = "ANTHROPIC_API_KEY"
     GITHUB_TOKEN = "GITHUB_TOKEN"
+    WANDB_API_KEY = "WANDB_API_KEY"
+    MLFLOW_TRACKING_TOKEN = "MLFLOW_TRACKING_TOKEN"
     GOOGLE_DRIVE_CREDENTIALS = "GOOGLE_DRIVE_CREDENTIALS"
     AWS_ACCESS_KEY_ID = "AWS_ACCESS_KEY_ID"
     AWS_SECRET_ACCESS_KEY = "AWS_SECRET_ACCESS_KEY"
@@ -1,10 +1,83 @@
 # tmux-trainsh bridge execution helpers
 # Encapsulates local tmux bridge behavior to keep DSLExecutor focused on orchestration.
 
+import posixpath
 import re
+import secrets
+import shlex
 import subprocess
 import time
-from typing import Any, Callable, Dict, Iterable, Optional
+from typing import Any, Callable, Dict, Iterable, Optional, Tuple
+
+# Fallback capture window when the pane's start offset is unknown.
+MARKER_SCAN_LINES = 300
+# How long to trust the pane alone before also polling the step's status file,
+# and how often to poll it after that.
+STATUS_FILE_GRACE_SECS = 30
+STATUS_FILE_POLL_SECS = 10
+
+
+def new_marker_nonce() -> str:
+    """Random per-step token carried by the begin/done markers."""
+    return secrets.token_hex(8)
+
+
+def begin_marker(nonce: str) -> str:
+    return f"__train_begin_{nonce}__"
+
+
+def done_marker(nonce: str) -> str:
+    return f"__train_done_{nonce}__"
+
+
+def wrap_marked_command(commands: str, nonce: str, status_file: str = "") -> str:
+    """Bracket commands with begin/done markers; the done marker carries the exit code.
+
+    With `status_file`, "<nonce> <exit code>" is also written there, for when the
+    pane loses the marker (cleared screen, alternate-screen programs).
+    """
+    # printf assembles each marker at run time, so the echoed command line never
+    # contains a complete marker that could be mistaken for the real one.
+    wrapped = f"printf '__train_%s_%s__\\n' begin {nonce}; ( {commands} ); __train_rc=$?; "
+    if status_file:
+        quoted = shlex.quote(status_file)
+        directory = shlex.quote(posixpath.dirname(status_file) or ".")
+        wrapped = (
+            f"rm -f {quoted}; {wrapped}"
+            f"mkdir -p {directory} && printf '%s %s\\n' {nonce} \"$__train_rc\" > {quoted}; "
+        )
+    return wrapped + f"printf '\\n__train_%s_%s__%s\\n' done {nonce} \"$__train_rc\""
+
+
+def parse_status_file(text: str, nonce: str) -> Optional[int]:
+    """Exit code from a status file written for this nonce, else None."""
+    parts = str(text or "").split()
+    if len(parts) != 2 or parts[0] != nonce:
+        return None
+    try:
+        return int(parts[1])
+    except ValueError:
+        return None
+
+
+def parse_marked_exit(text: str, nonce: str, *, require_begin: bool = True) -> Tuple[bool, Optional[int]]:
+    """Find this step's done marker in pane text and return (found, exit_code).
+
+    Only a done marker on its own line, after this step's begin marker, counts;
+    `require_begin=False` accepts one whose begin marker has scrolled out of view.
+    """
+    begin = begin_marker(nonce)
+    done = re.compile(re.escape(done_marker(nonce)) + r"(-?\d+)")
+    started = not require_begin
+    for line in str(text or "").splitlines():
+        line = line.strip()
+        if line == begin:
+            started = True
+            continue
+        match = done.fullmatch(line)
+        if started and match:
+            return True, int(match.group(1))
+    return False, None
 
 
 class BridgeExecutionHelper:
@@ -19,6 +92,9 @@ class BridgeExecutionHelper:
         log: Callable[[str], None],
         log_detail: Callable[[str, str, Dict[str, Any]], None],
         format_duration: Callable[[float], str],
+        get_status_file: Optional[Callable[[], str]] = None,
+        note_exit_code: Optional[Callable[[int, str], None]] = None,
+        note_output: Optional[Callable[[str], None]] = None,
     ):
         self.tmux_bridge = tmux_bridge
         self.prefer_bridge_exec = prefer_bridge_exec
@@ -27,6 +103,9 @@ class BridgeExecutionHelper:
         self.log = log
         self.log_detail = log_detail
         self.format_duration = format_duration
+        self.get_status_file = get_status_file
+        self.note_exit_code = note_exit_code
+        self.note_output = note_output
 
     def build_bridge_attach_command(self, window: Any) -> str:
         """Build local shell command used by bridge pane to attach a window."""
@@ -48,6 +127,16 @@ class BridgeExecutionHelper:
             return
 
         attach_cmd = self.build_bridge_attach_command(window)
+        if window.host != "local":
+            from ..services.terminal_keepalive import supervised_attach_command
+
+            attach_cmd = supervised_attach_command(
+                attach_cmd,
+                job_id=str(getattr(self.tmux_bridge, "job_id", "") or ""),
+                window=window.name,
+                host=window.host,
+                session=window.remote_session,
+            )
         ok, msg = self.tmux_bridge.connect(window.name, attach_cmd)
         if ok:
             self.log(f"  Bridge @{window.name}: {msg}")
@@ -179,25 +268,95 @@ class BridgeExecutionHelper:
 
         return False, f"Timeout after {self.format_duration(timeout)}"
 
-    def _wait_bridge_marker(self, pane_id: str, marker: str, timeout: Optional[int]) -> tuple[bool, Optional[int]]:
-        """Wait until marker+exitcode appears in bridge pane output."""
+    def _pane_line_offset(self, pane_id: str) -> Optional[int]:
+        """Absolute line of the pane cursor (history + cursor row), or None if unknown."""
+        result = self.tmux_bridge.tmux.display_message(pane_id, "#{history_size} #{cursor_y}")
+        if getattr(result, "returncode", 1) != 0:
+            return None
+        try:
+            history, cursor = str(result.stdout).split()
+            return int(history) + int(cursor)
+        except (TypeError, ValueError):
+            return None
+
+    def _capture_since(self, pane_id: str, start_line: Optional[int]) -> tuple[Any, bool]:
+        """Capture pane text from `start_line` on; also report whether that start is exact."""
+        if start_line is not None:
+            result = self.tmux_bridge.tmux.display_message(
+                pane_id, "#{history_size} #{history_limit} #{pane_height}"
+            )
+            try:
+                history, limit, height = (int(value) for value in str(result.stdout).split())
+                # Rows before the step's start offset hold older output; skip them.
+                relative = start_line - history
+            except (TypeError, ValueError):
+                history = limit = height = relative = None
+            # A full history evicts lines from the top, so the absolute offset
+            # drifts; only trust it while it still lands inside the pane.
+            if (
+                getattr(result, "returncode", 1) == 0
+                and relative is not None
+                and history < limit
+                and -history <= relative < height
+            ):
+                return self.tmux_bridge.tmux.capture_pane(pane_id, start=str(relative)), True
+        return self.tmux_bridge.tmux.capture_pane(pane_id, start=f"-{MARKER_SCAN_LINES}"), False
+
+    def _wait_bridge_marker(
+        self,
+        pane_id: str,
+        nonce: str,
+        timeout: Optional[int],
+        start_line: Optional[int] = None,
+        status_file: str = "",
+        host: str = "local",
+    ) -> tuple[bool, Optional[int]]:
+        """Wait until this step's done marker (with exit code) appears in bridge pane output.
+
+        After a grace period the step's status file is polled as well.
+        """
         start = time.time()
         deadline = None if timeout is None or timeout <= 0 else start + timeout
-        pattern = re.compile(re.escape(marker) + r"(-?\d+)")
+        next_status_check = start + STATUS_FILE_GRACE_SECS
         while True:
-            result = self.tmux_bridge.tmux.capture_pane(pane_id, start="-300")
+            result, exact = self._capture_since(pane_id, start_line)
             if result.returncode == 0:
-                matches = pattern.findall(result.stdout or "")
-                if matches:
-                    try:
-                        return True, int(matches[-1])
-                    except ValueError:
-                        return True, None
-            if deadline is not None and time.time() >= deadline:
+                # From an exact start offset every row is newer than the step, so a
+                # begin marker that scrolled past the history limit is not required.
+                found, exit_code = parse_marked_exit(result.stdout or "", nonce, require_begin=not exact)
+                if found:
+                    return True, exit_code
+            now = time.time()
+            if status_file and now >= next_status_check:
+                next_status_check = now + STATUS_FILE_POLL_SECS
+                exit_code = self._read_status_file(host, status_file, nonce)
+                if exit_code is not None:
+                    self.log_detail("bridge_status_file", f"Exit status read from {status_file}", {
+                        "bridge_pane": pane_id,
+                        "status_file": status_file,
+                        "exit_code": exit_code,
+                    })
+                    return True, exit_code
+            if deadline is not None and now >= deadline:
                 break
             time.sleep(1)
         return False, None
 
+    def _read_status_file(self, host: str, path: str, nonce: str) -> Optional[int]:
+        """Exit code recorded in a step's status file on `host`, if it is there yet."""
+        try:
+            if host == "local":
+                with open(path, encoding="utf-8") as handle:
+                    text = handle.read()
+            else:
+                result = self.get_tmux_client(host).read_text(path)
+                if result.returncode != 0:
+                    return None
+                text = result.stdout
+        except Exception:
+            return None
+        return parse_status_file(text, nonce)
+
     def exec_via_bridge(
         self,
         window: Any,
@@ -219,12 +378,19 @@ class BridgeExecutionHelper:
                 self._tmux_send_keys_local_target(pane_id, commands)
                 return True, "Command sent (background via bridge)"
 
-            import uuid
-            marker = f"__train_done_{uuid.uuid4().hex[:8]}__"
-            wrapped_cmd = f"( {commands} ); __train_rc=$?; echo {marker}$__train_rc"
-            self._tmux_send_keys_local_target(pane_id, wrapped_cmd)
-
-            found, exit_code = self._wait_bridge_marker(pane_id, marker, timeout)
+            nonce = new_marker_nonce()
+            start_line = self._pane_line_offset(pane_id)
+            status_file = self.get_status_file() if self.get_status_file else ""
+            self._tmux_send_keys_local_target(pane_id, wrap_marked_command(commands, nonce, status_file))
+
+            found, exit_code = self._wait_bridge_marker(
+                pane_id,
+                nonce,
+                timeout,
+                start_line,
+                status_file=status_file,
+                host=str(getattr(window, "host", "local") or "local"),
+            )
             elapsed = int(time.time() - start_time)
 
             self.log_detail("bridge_exec", f"Bridge execute on {window.name}", {
@@ -233,14 +399,21 @@ class BridgeExecutionHelper:
                 "elapsed_sec": elapsed,
                 "found_marker": found,
                 "exit_code": exit_code,
+                "status_file": status_file,
             })
 
             if not found:
                 if timeout is None or timeout <= 0:
                     return False, "Command timed out"
                 return False, f"Command timed out after {timeout}s"
+            if self.note_exit_code and exit_code is not None:
+                self.note_exit_code(int(exit_code), str(getattr(window, "host", "local") or "local"))
             if exit_code == 0:
                 return True, f"Command completed ({elapsed}s)"
+            if self.note_output:
+                captured, _ = self._capture_since(pane_id, start_line)
+                if captured.returncode == 0:
+                    self.note_output(captured.stdout or "")
             return False, f"Command failed with exit code {exit_code}"
 
         except subprocess.TimeoutExpired:
@@ -57,6 +57,7 @@ class DagExecutor:
         var_overrides: Optional[Dict[str, str]] = None,
         resume: bool = False,
         initial_session_index: int = 0,
+        recipe: Any = None,
     ) -> DagExecutionResult:
         run_id = run_id or uuid4().hex
         run_type = str(run_type or "manual").strip().lower() or "manual"
@@ -79,6 +80,7 @@ class DagExecutor:
                 callback_sinks=self.callback_sinks,
                 log_callback=self.log_callback,
                 run_type=run_type,
+                recipe=recipe,
             )
             ended_at = datetime.now(timezone.utc)
             if success:
@@ -10,8 +10,8 @@ from pathlib import Path
 from typing import Any, Dict, List, Optional, Sequence
 
 from ..constants import CONFIG_DIR
-from ..constants import RECIPE_FILE_EXTENSION
-from ..pyrecipe.loader import load_python_recipe
+from ..constants import RECIPE_FILE_EXTENSIONS
+from ..pyrecipe.loader import load_recipe
 
 
 _METADATA_KEYS = {
@@ -161,7 +161,7 @@ class ParsedDag:
         return self.schedule_meta.is_due_capable
 
     def load_recipe(self) -> Any:
-        return load_python_recipe(str(self.path))
+        return load_recipe(str(self.path))
 
 
 class DagProcessor:
@@ -175,7 +175,7 @@ class DagProcessor:
         recursive: bool = True,
     ):
         self.dag_roots = [Path(p) for p in (dag_roots or [str(CONFIG_DIR / "recipes")])]
-        self.include_patterns = list(include_patterns or [f"**/*{RECIPE_FILE_EXTENSION}"])
+        self.include_patterns = list(include_patterns or [f"**/*{extension}" for extension in RECIPE_FILE_EXTENSIONS])
         self.recursive = recursive
 
     def discover_dags(self) -> List[ParsedDag]:
@@ -195,7 +195,7 @@ class DagProcessor:
                 continue
 
             if path.is_file():
-                if path.suffix.lower() == RECIPE_FILE_EXTENSION:
+                if path.suffix.lower() in RECIPE_FILE_EXTENSIONS:
                     files.append(path)
                 continue
 
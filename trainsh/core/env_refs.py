@@ -0,0 +1,126 @@
+"""`${env:NAME}` references in config.yaml, hosts.yaml and storages.yaml.
+
+References are expanded when a file is loaded, so one config can be shared
+between machines whose usernames and paths differ. `${env:NAME:-fallback}`
+uses `fallback` when `NAME` is unset; a bare reference to an unset variable
+raises `MissingEnvVarError` naming the file and key. When a file is written
+back, `restore_env_refs` puts the references back wherever the value still
+matches what they expand to, so edits never bake one machine's paths in.
+"""
+
+from __future__ import annotations
+
+import os
+import re
+from typing import Any, Mapping, Optional
+
+from .errors import AppError, ErrorCode
+
+ENV_REF_RE = re.compile(r"\$\{env:([A-Za-z_][A-Za-z0-9_]*)(?::-([^}]*))?\}")
+
+
+class MissingEnvVarError(AppError):
+    """A config value references an environment variable that is not set."""
+
+    def __init__(self, name: str, where: str):
+        super().__init__(
+            f"{where}: environment variable {name} is not set "
+            f"(export it or write ${{env:{name}:-default}})",
+            ErrorCode.CONFIG_INVALID,
+            module="config",
+            details={"variable": name, "where": where},
+        )
+        self.name = name
+        self.where = where
+
+
+def _key_path(prefix: str, key: Any) -> str:
+    if isinstance(key, int):
+        return f"{prefix}[{key}]"
+    return f"{prefix}.{key}" if prefix else str(key)
+
+
+def expand_env_string(text: str, *, where: str = "", environ: Optional[Mapping[str, str]] = None) -> str:
+    """`text` with every `${env:...}` reference replaced."""
+    env = os.environ if environ is None else environ
+
+    def replace(match: re.Match) -> str:
+        name, fallback = match.group(1), match.group(2)
+        if name in env:
+            return env[name]
+        if fallback is not None:
+            return fallback
+        raise MissingEnvVarError(name, where or "config")
+
+    return ENV_REF_RE.sub(replace, text)
+
+
+def _expand(data: Any, source: str, path: str, environ: Optional[Mapping[str, str]]) -> Any:
+    if isinstance(data, str):
+        if "${env:" not in data:
+            return data
+        where = f"{source}: {path}" if source and path else source or path
+        return expand_env_string(data, where=where, environ=environ)
+    if isinstance(data, dict):
+        return {key: _expand(value, source, _key_path(path, key), environ) for key, value in data.items()}
+    if isinstance(data, list):
+        return [_expand(value, source, _key_path(path, index), environ) for index, value in enumerate(data)]
+    return data
+
+
+def expand_env_refs(data: Any, *, where: str = "", environ: Optional[Mapping[str, str]] = None) -> Any:
+    """Copy of a loaded YAML document with references in every string value expanded.
+
+    `where` names the source (e.g. `hosts.yaml`); errors add the key path after it.
+    """
+    return _expand(data, where, "", environ)
+
+
+def restore_env_refs(raw: Any, current: Any, *, environ: Optional[Mapping[str, str]] = None) -> Any:
+    """`current` with the references from `raw` (the file as last written) put back.
+
+    A string goes back to its `raw` form when that still expands to the same
+    value; anything the user changed is written as given.
+    """
+    if isinstance(current, str) and isinstance(raw, str) and "${env:" in raw:
+        try:
+            expanded = expand_env_string(raw, environ=environ)
+        except MissingEnvVarError:
+            return current
+        return raw if expanded == current else current
+    if isinstance(current, dict) and isinstance(raw, dict):
+        return {
+            key: restore_env_refs(raw[key], value, environ=environ) if key in raw else value
+            for key, value in current.items()
+        }
+    if isinstance(current, list) and isinstance(raw, list):
+        return [
+            restore_env_refs(raw[index], value, environ=environ) if index < len(raw) else value
+            for index, value in enumerate(current)
+        ]
+    return current
+
+
+def restore_env_refs_by_name(raw_items: Any, items: list, *, environ: Optional[Mapping[str, str]] = None) -> list:
+    """`restore_env_refs` for a list of named entries (hosts, storages), matched by `name` then `id`."""
+    previous = {}
+    for item in raw_items if isinstance(raw_items, list) else []:
+        if isinstance(item, dict):
+            for key in ("id", "name"):
+                if item.get(key):
+                    previous[(key, item[key])] = item
+    restored = []
+    for item in items:
+        match = previous.get(("name", item.get("name"))) or previous.get(("id", item.get("id")))
+        restored.append(restore_env_refs(match, item, environ=environ) if match else item)
+    return restored
+
+
+__all__ = [
+    "ENV_REF_RE",
+    "MissingEnvVarError",
+    "expand_env_refs",
+    "expand_env_string",
+    "restore_env_refs",
+    "restore_env_refs_by_name",
+]
@@ -0,0 +1,165 @@
+"""Structured errors with machine-readable codes and retryability hints.
+
+`AppError` carries a stable `code`, whether retrying can help, and the
+provider/module it came from, so JSON output and run events can tell
+"ssh auth failed" from "network unreachable" from "instance not provisioned".
+Failures that only surface as text are mapped with `classify_message`.
+"""
+
+from __future__ import annotations
+
+import re
+from enum import Enum
+from typing import Any, Dict, Optional, Union
+
+
+class ErrorCode(str, Enum):
+    SSH_AUTH_FAILED = "ssh_auth_failed"
+    SSH_HOST_KEY_MISMATCH = "ssh_host_key_mismatch"
+    NETWORK_UNREACHABLE = "network_unreachable"
+    CONNECTION_REFUSED = "connection_refused"
+    CONNECTION_TIMEOUT = "connection_timeout"
+    HOST_OFFLINE = "host_offline"
+    INSTANCE_NOT_PROVISIONED = "instance_not_provisioned"
+    INSTANCE_NOT_FOUND = "instance_not_found"
+    OFFER_UNAVAILABLE = "offer_unavailable"
+    API_AUTH_FAILED = "api_auth_failed"
+    RATE_LIMITED = "rate_limited"
+    SERVICE_UNAVAILABLE = "service_unavailable"
+    BAD_REQUEST = "bad_request"
+    STORAGE_NOT_FOUND = "storage_not_found"
+    STORAGE_AUTH_FAILED = "storage_auth_failed"
+    TOOL_MISSING = "tool_missing"
+    TIMEOUT = "timeout"
+    PERMISSION_DENIED = "permission_denied"
+    OUT_OF_MEMORY = "out_of_memory"
+    CONFIG_INVALID = "config_invalid"
+    UNKNOWN = "unknown"
+
+
+# Codes where the same call can succeed later without anyone changing anything.
+RETRYABLE_CODES = frozenset(
+    {
+        ErrorCode.NETWORK_UNREACHABLE,
+        ErrorCode.CONNECTION_REFUSED,
+        ErrorCode.CONNECTION_TIMEOUT,
+        ErrorCode.HOST_OFFLINE,
+        ErrorCode.INSTANCE_NOT_PROVISIONED,
+        ErrorCode.OFFER_UNAVAILABLE,
+        ErrorCode.RATE_LIMITED,
+        ErrorCode.SERVICE_UNAVAILABLE,
+        ErrorCode.TIMEOUT,
+    }
+)
+
+# First match wins; checked against lowercased text.
+_MESSAGE_PATTERNS = (
+    (r"cuda out of memory|outofmemoryerror|cuda error: out of memory|cublas_status_alloc_failed", ErrorCode.OUT_OF_MEMORY),
+    (r"remote host identification has changed|host key verification failed", ErrorCode.SSH_HOST_KEY_MISMATCH),
+    (r"permission denied \(|too many authentication failures|authentication failed|sshpass", ErrorCode.SSH_AUTH_FAILED),
+    (r"api error \((401|403)\)|unauthorized|invalid api key", ErrorCode.API_AUTH_FAILED),
+    (r"api error \(429\)|rate limit|too many requests", ErrorCode.RATE_LIMITED),
+    (r"api error \(5\d\d\)|service unavailable|bad gateway", ErrorCode.SERVICE_UNAVAILABLE),
+    (r"no_such_instance|pod \S+ not found|instance \S+ not found", ErrorCode.INSTANCE_NOT_FOUND),
+    (r"ssh-ready|no ssh endpoint|still (loading|creating)", ErrorCode.INSTANCE_NOT_PROVISIONED),
+    (r"was offline at|is offline", ErrorCode.HOST_OFFLINE),
+    (r"could not resolve hostname|name or service not known|network is unreachable|no route to host|temporary failure in name resolution", ErrorCode.NETWORK_UNREACHABLE),
+    (r"connection refused", ErrorCode.CONNECTION_REFUSED),
+    (r"connection timed out|operation timed out|connect timeout", ErrorCode.CONNECTION_TIMEOUT),
+    (r"timed out|timeout", ErrorCode.TIMEOUT),
+    (r"directory not found|object not found|file not found|no such file|not found in bucket|path not found", ErrorCode.STORAGE_NOT_FOUND),
+    (r"accessdenied|access denied|signaturedoesnotmatch|invalidaccesskeyid|403 forbidden", ErrorCode.STORAGE_AUTH_FAILED),
+    (r"is required for|command not found|executable file not found", ErrorCode.TOOL_MISSING),
+    (r"viewer mode|permission denied", ErrorCode.PERMISSION_DENIED),
+)
+
+
+class AppError(RuntimeError):
+    """An error with a machine-readable `code`, a `retryable` hint and its origin."""
+
+    def __init__(
+        self,
+        message: str,
+        code: Union[ErrorCode, str] = ErrorCode.UNKNOWN,
+        *,
+        retryable: Optional[bool] = None,
+        provider: str = "",
+        module: str = "",
+        details: Optional[Dict[str, Any]] = None,
+    ):
+        super().__init__(message)
+        self.code = ErrorCode(code)
+        self.retryable = self.code in RETRYABLE_CODES if retryable is None else bool(retryable)
+        self.provider = provider
+        self.module = module
+        self.details = dict(details or {})
+
+    def to_dict(self) -> Dict[str, Any]:
+        return {
+            "code": self.code.value,
+            "message": str(self),
+            "retryable": self.retryable,
+            "provider": self.provider,
+            "module": self.module,
+            "details": self.details,
+        }
+
+
+def classify_message(text: str) -> ErrorCode:
+    """Best-effort code for a failure only known by its message."""
+    lowered = str(text or "").lower()
+    for pattern, code in _MESSAGE_PATTERNS:
+        if re.search(pattern, lowered):
+            return code
+    return ErrorCode.UNKNOWN
+
+
+def classify_http(status: int, body: str = "") -> ErrorCode:
+    """Code for an HTTP API failure; status 0 means the request never got a response."""
+    if status == 0:
+        code = classify_message(body)
+        return code if code != ErrorCode.UNKNOWN else ErrorCode.NETWORK_UNREACHABLE
+    if status in (401, 403):
+        return ErrorCode.API_AUTH_FAILED
+    if status == 404:
+        return ErrorCode.INSTANCE_NOT_FOUND
+    if status == 429:
+        return ErrorCode.RATE_LIMITED
+    if status >= 500:
+        return ErrorCode.SERVICE_UNAVAILABLE
+    return ErrorCode.BAD_REQUEST
+
+
+def classify_ssh(stderr: str, exit_code: int) -> ErrorCode:
+    """Code for a failed ssh invocation; exit 255 is ssh itself failing, not the remote command."""
+    if exit_code not in (255, -1):
+        return ErrorCode.UNKNOWN
+    code = classify_message(stderr)
+    if code == ErrorCode.PERMISSION_DENIED:
+        return ErrorCode.SSH_AUTH_FAILED
+    return code if code != ErrorCode.UNKNOWN else ErrorCode.NETWORK_UNREACHABLE
+
+
+def error_info(error: Union[BaseException, str]) -> Dict[str, Any]:
+    """`error_code`/`retryable`/`error_provider`/`error_module` fields for events and JSON output."""
+    if not isinstance(error, AppError):
+        # Only the tail of long step output describes the failure.
+        message = str(error)[-2000:]
+        error = AppError(message, classify_message(message))
+    return {
+        "error_code": error.code.value,
+        "retryable": error.retryable,
+        "error_provider": error.provider,
+        "error_module": error.module,
+    }
+
+
+__all__ = [
+    "AppError",
+    "ErrorCode",
+    "RETRYABLE_CODES",
+    "classify_http",
+    "classify_message",
+    "classify_ssh",
+    "error_info",
+]
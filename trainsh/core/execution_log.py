@@ -3,25 +3,79 @@
 
 from __future__ import annotations
 
+import threading
+import time
 from datetime import datetime
-from typing import Any, Dict, List, Optional
+from pathlib import Path
+from typing import Any, Dict, List, Optional, Set, Tuple
 
-from .runtime_store import RuntimeStore
+from ..services.session_env import redact
+from .output_tail import StepOutputTail, get_live_tail, register_live_tail, unregister_live_tail
+from .runtime_store import STEP_LOGS_DIRNAME, RuntimeStore
+
+MAX_OUTPUT_CHUNK = 50000
+# High-frequency progress events; with coalescing only the latest per target is kept per window.
+THROTTLED_EVENTS = frozenset({"wait_poll", "transfer:progress"})
+
+
+def step_log_path(root: Path, job_id: str, step_num: int, output_type: str = "result") -> Path:
+    """Raw output file of one step: `step_logs/<job>/step_NNNN.log` (`.error.log` for exceptions).
+
+    `output_type="screen"` is the latest full-screen frame (`.screen.log`).
+    """
+    suffix = ".log" if output_type == "result" else f".{'error' if output_type == 'exception' else output_type}.log"
+    return Path(root) / STEP_LOGS_DIRNAME / str(job_id) / f"step_{int(step_num):04d}{suffix}"
 
 
 class ExecutionLogger:
     """Detailed execution logger backed by JSONL events."""
 
-    def __init__(self, job_id: str, recipe_name: str, db_path: Optional[str] = None):
+    coalesce_secs = 0.0
+    max_step_bytes = 0
+    max_run_bytes = 0
+
+    def __init__(
+        self,
+        job_id: str,
+        recipe_name: str,
+        db_path: Optional[str] = None,
+        *,
+        tail_lines: int = 200,
+        coalesce_secs: float = 0.0,
+        max_step_bytes: int = 0,
+        max_run_bytes: int = 0,
+    ):
         self.job_id = job_id
         self.recipe_name = recipe_name
         self.store = RuntimeStore(db_path)
         self._step_count = 0
         self._closed = False
+        # Resolved secret values; scrubbed from every event before it is written.
+        self.redactions: Set[str] = set()
+        self.output_tail = StepOutputTail(max_lines=tail_lines)
+        register_live_tail(job_id, self.output_tail)
+        # 0 writes every output chunk and poll as it comes; >0 batches them per window.
+        self.coalesce_secs = max(0.0, float(coalesce_secs or 0))
+        self._pending_output: Dict[Tuple[int, str], List[str]] = {}
+        self._pending_events: Dict[Tuple[str, str], Dict[str, Any]] = {}
+        self._last_flush = time.monotonic()
+        self._buffer_lock = threading.Lock()
+        # Caps on step output written to the log (0 = unlimited); the tail keeps the latest lines either way.
+        self.max_step_bytes = max(0, int(max_step_bytes or 0))
+        self.max_run_bytes = max(0, int(max_run_bytes or 0))
+        self._step_bytes: Dict[int, int] = {}
+        self._run_bytes = 0
+        self._truncated: Set[Tuple[str, int]] = set()
+        # Steps whose output files are rewritten on the next chunk (a new attempt started).
+        self._fresh_steps: Set[int] = set()
+        # Steps that already logged a `step_screen` event this attempt.
+        self._screen_steps: Set[int] = set()
 
     def _write(self, event: str, *, step_num: Optional[int] = None, **payload: Any) -> None:
         if self._closed:
             return
+        if self.redactions:
+            payload = redact(payload, self.redactions)
         self.store.append_event(
             {
                 "run_id": self.job_id,
@@ -44,10 +98,116 @@ class ExecutionLogger:
 
     def step_start(self, step_num: int, raw: str, step_type: str, details: Dict[str, Any]) -> None:
         self._step_count = step_num
+        self._fresh_steps.add(step_num)
+        # A new attempt starts without the previous attempt's full-screen frame.
+        self._screen_steps.discard(step_num)
+        try:
+            step_log_path(self.store.root, self.job_id, step_num, "screen").unlink(missing_ok=True)
+        except OSError:
+            pass
         del raw, step_type, details
 
+    def step_screen(self, step_num: int, frame: str) -> None:
+        """Keep the latest frame of a step running a full-screen program.
+
+        Frames replace each other in `step_NNNN.screen.log` instead of being
+        appended as output; only the first one of an attempt writes an event.
+        """
+        if self._closed or not frame:
+            return
+        clean = redact(frame, self.redactions) if self.redactions else frame
+        path = step_log_path(self.store.root, self.job_id, step_num, "screen")
+        try:
+            path.parent.mkdir(parents=True, exist_ok=True)
+            partial = path.with_name(path.name + ".tmp")
+            partial.write_text(clean, encoding="utf-8")
+            partial.replace(path)
+        except OSError:
+            return
+        if step_num not in self._screen_steps:
+            self._screen_steps.add(step_num)
+            self._write("step_screen", step_num=step_num, lines=len(clean.splitlines()))
+
     def step_output(self, step_num: int, output: str, output_type: str = "result") -> None:
-        max_chunk = 50000
+        if self._closed or not output:
+            return
+        clean = redact(output, self.redactions) if self.redactions else output
+        self.output_tail.append(step_num, clean)
+        self._write_step_file(step_num, clean, output_type)
+        if self.coalesce_secs <= 0:
+            self._write_output(step_num, output, output_type)
+            return
+        with self._buffer_lock:
+            pending = self._pending_output.setdefault((step_num, output_type), [])
+            pending.append(output)
+            full = sum(len(item) for item in pending) >= MAX_OUTPUT_CHUNK
+        if full or self._window_elapsed():
+            self.flush()
+
+    def _write_step_file(self, step_num: int, output: str, output_type: str) -> None:
+        """Append raw output to the step's file; the first chunk of an attempt replaces older ones."""
+        with self._buffer_lock:
+            fresh = step_num in self._fresh_steps
+            self._fresh_steps.discard(step_num)
+            try:
+                path = step_log_path(self.store.root, self.job_id, step_num, output_type)
+                path.parent.mkdir(parents=True, exist_ok=True)
+                if fresh:
+                    for stale in path.parent.glob(f"step_{int(step_num):04d}.*"):
+                        if not stale.name.endswith(".screen.log"):
+                            stale.unlink(missing_ok=True)
+                with path.open("a", encoding="utf-8") as handle:
+                    handle.write(output)
+            except OSError:
+                pass
+
+    def _output_budget(self, step_num: int, size: int) -> Tuple[int, str]:
+        """Bytes of `size` still allowed, plus the cap ("step"/"run") hit for the first time."""
+        with self._buffer_lock:
+            allowed, scope = size, ""
+            if self.max_step_bytes:
+                left = max(0, self.max_step_bytes - self._step_bytes.get(step_num, 0))
+                if left < allowed:
+                    allowed, scope = left, "step"
+            if self.max_run_bytes:
+                left = max(0, self.max_run_bytes - self._run_bytes)
+                if left < allowed:
+                    allowed, scope = left, "run"
+            self._step_bytes[step_num] = self._step_bytes.get(step_num, 0) + allowed
+            self._run_bytes += allowed
+            marker = (scope, step_num if scope == "step" else 0)
+            if not scope or marker in self._truncated:
+                return allowed, ""
+            self._truncated.add(marker)
+            return allowed, scope
+
+    def _write_output(self, step_num: int, output: str, output_type: str) -> None:
+        # Scrub before chunking so a secret cannot straddle two events.
+        if self.redactions:
+            output = redact(output, self.redactions)
+        if self.max_step_bytes or self.max_run_bytes:
+            encoded = output.encode("utf-8")
+            allowed, scope = self._output_budget(step_num, len(encoded))
+            if allowed < len(encoded):
+                output = encoded[:allowed].decode("utf-8", errors="ignore")
+            if scope:
+                limit = self.max_step_bytes if scope == "step" else self.max_run_bytes
+                if output:
+                    self._write_output_chunks(step_num, output, output_type)
+                self._write(
+                    "step_output_truncated",
+                    step_num=step_num,
+                    output_type=output_type,
+                    scope=scope,
+                    limit_bytes=limit,
+                )
+                return
+            if not output:
+                return
+        self._write_output_chunks(step_num, output, output_type)
+
+    def _write_output_chunks(self, step_num: int, output: str, output_type: str) -> None:
+        max_chunk = MAX_OUTPUT_CHUNK
         if len(output) > max_chunk:
             total_chunks = (len(output) + max_chunk - 1) // max_chunk
             for i in range(0, len(output), max_chunk):
@@ -62,6 +222,29 @@ class ExecutionLogger:
             return
         self._write("step_output", step_num=step_num, output_type=output_type, output=output)
 
+    def _write_throttled(self, event: str, key: str, **payload: Any) -> None:
+        if self.coalesce_secs <= 0 or event not in THROTTLED_EVENTS:
+            self._write(event, **payload)
+            return
+        with self._buffer_lock:
+            self._pending_events[(event, key)] = payload
+        if self._window_elapsed():
+            self.flush()
+
+    def _window_elapsed(self) -> bool:
+        return time.monotonic() - self._last_flush >= self.coalesce_secs
+
+    def flush(self) -> None:
+        """Write batched output and the latest throttled progress events."""
+        with self._buffer_lock:
+            outputs, self._pending_output = self._pending_output, {}
+            events, self._pending_events = self._pending_events, {}
+            self._last_flush = time.monotonic()
+        for (step_num, output_type), parts in outputs.items():
+            self._write_output(step_num, "".join(parts), output_type)
+        for (event, _key), payload in events.items():
+            self._write(event, **payload)
+
     def step_end(
         self,
         step_num: int,
@@ -72,6 +255,8 @@ class ExecutionLogger:
     ) -> None:
         self._step_count = step_num
         del success, duration_ms, result, error
+        if self._pending_output or self._pending_events:
+            self.flush()
 
     def log_detail(self, category: str, message: str, data: Optional[Dict[str, Any]] = None) -> None:
         payload = {"category": category, "message": message}
@@ -126,7 +311,12 @@ class ExecutionLogger:
         duration_ms: int,
         success: bool,
         details: str,
+        progress: Optional[Dict[str, Any]] = None,
+        files: Optional[List[str]] = None,
     ) -> None:
+        extra: Dict[str, Any] = {"progress": progress} if progress else {}
+        if files:
+            extra["files"] = list(files)
         self._write(
             "file_transfer",
             source=source,
@@ -136,11 +326,16 @@ class ExecutionLogger:
             duration_ms=duration_ms,
             success=success,
             details=details,
+            **extra,
         )
 
+    def log_transfer_progress(self, source: str, dest: str, progress: Dict[str, Any]) -> None:
+        self._write_throttled("transfer:progress", f"{source}->{dest}", source=source, dest=dest, **progress)
+
     def log_wait(self, target: str, condition: str, elapsed_sec: int, remaining_sec: int, status: str) -> None:
-        self._write(
+        self._write_throttled(
             "wait_poll",
+            f"{target}:{condition}",
             target=target,
             condition=condition,
             elapsed_sec=elapsed_sec,
@@ -161,10 +356,17 @@ class ExecutionLogger:
         self.close()
 
     def close(self) -> None:
+        if self._closed:
+            return
+        self.flush()
         self._closed = True
+        unregister_live_tail(self.job_id, self.output_tail)
 
     def __del__(self):
-        self.close()
+        try:
+            self.close()
+        except Exception:
+            pass
 
 
 class ExecutionLogReader:
@@ -186,6 +388,7 @@ class ExecutionLogReader:
                 "file": "",
                 "host_count": len(row.get("hosts", {}) if isinstance(row.get("hosts"), dict) else {}),
                 "storage_count": len(row.get("storages", {}) if isinstance(row.get("storages"), dict) else {}),
+                "metrics": dict((row.get("wandb") or {}).get("metrics") or {}),
             }
             for row in rows
         ]
@@ -215,6 +418,101 @@ class ExecutionLogReader:
         chunks.sort(key=lambda item: item[0])
         return "".join(output for _, output in chunks)
 
+    def resolve_step(self, job_id: str, step: Any = None) -> Optional[Tuple[int, str]]:
+        """(step_num, step_id) for a step number or id; the latest started step when omitted."""
+        step_ids: Dict[int, str] = {}
+        for entry in self.read_execution(job_id):
+            if entry.get("event") == "step_start" and entry.get("step_num") is not None:
+                step_ids.pop(entry["step_num"], None)
+                step_ids[entry["step_num"]] = str(entry.get("step_id", "") or "")
+        if step is None or str(step) == "":
+            if not step_ids:
+                return None
+            step_num = list(step_ids)[-1]
+            return step_num, step_ids[step_num]
+        if str(step).isdigit():
+            return int(step), step_ids.get(int(step), "")
+        for step_num, step_id in step_ids.items():
+            if step_id == str(step):
+                return step_num, step_id
+        return None
+
+    def get_step_log_path(self, job_id: str, step: Any = None, output_type: str = "result") -> Optional[Path]:
+        """File with the raw output of one step, if the run wrote one."""
+        resolved = self.resolve_step(job_id, step)
+        if resolved is None:
+            return None
+        path = step_log_path(self.store.root, job_id, resolved[0], output_type)
+        return path if path.exists() else None
+
+    def get_step_screen(self, job_id: str, step: Any = None) -> Optional[dict]:
+        """Latest full-screen frame recorded for one step (default: the latest with one)."""
+        if step is None or str(step) == "":
+            screens = [
+                entry["step_num"] for entry in self.read_execution(job_id)
+                if entry.get("event") == "step_screen" and entry.get("step_num") is not None
+            ]
+            if not screens:
+                return None
+            step = screens[-1]
+        resolved = self.resolve_step(job_id, step)
+        path = self.get_step_log_path(job_id, step, "screen") if resolved else None
+        if path is None:
+            return None
+        return {
+            "job_id": job_id,
+            "step_num": resolved[0],
+            "step_id": resolved[1],
+            "path": path,
+            "updated": datetime.fromtimestamp(path.stat().st_mtime).isoformat(timespec="seconds"),
+            "frame": path.read_text(encoding="utf-8", errors="replace"),
+        }
+
+    def get_step_output_tail(self, job_id: str, step: Any = None, lines: int = 50) -> Optional[dict]:
+        """Last `lines` output lines of one step (default: the latest started).
+
+        Served from the in-memory ring when the run executes in this process,
+        otherwise replayed from the log through a ring of the same size.
+        """
+        lines = max(1, int(lines))
+        step_ids: Dict[int, str] = {}
+        started: List[int] = []
+        replay = StepOutputTail(max_lines=lines, max_bytes=lines * 4096)
+        for entry in self.read_execution(job_id):
+            event = entry.get("event")
+            step_num = entry.get("step_num")
+            if step_num is None:
+                continue
+            if event == "step_start":
+                step_ids[step_num] = str(entry.get("step_id", "") or "")
+                if step_num in started:
+                    started.remove(step_num)
+                started.append(step_num)
+            elif event == "step_output":
+                replay.append(step_num, str(entry.get("output", "")))
+        if not started and not replay.steps():
+            return None
+
+        if step is None or str(step) == "":
+            step_num = started[-1] if started else replay.steps()[-1]
+        elif str(step).isdigit():
+            step_num = int(step)
+        else:
+            step_num = next((num for num, step_id in step_ids.items() if step_id == str(step)), None)
+            if step_num is None:
+                return None
+
+        live = get_live_tail(job_id)
+        source = "live" if live is not None and step_num in live.steps() else "log"
+        tail = (live if source == "live" else replay).tail(step_num, lines)
+        return {
+            "job_id": job_id,
+            "step_num": step_num,
+            "step_id": step_ids.get(step_num, ""),
+            "source": source,
+            "lines": tail,
+        }
+
     def get_execution_summary(self, job_id: str) -> Optional[dict]:
         run_row = self.store.get_run(job_id)
         if not run_row:
@@ -232,6 +530,9 @@ class ExecutionLogReader:
             "variables": {},
             "hosts": run_row.get("hosts", {}) if isinstance(run_row.get("hosts"), dict) else {},
             "storages": run_row.get("storages", {}) if isinstance(run_row.get("storages"), dict) else {},
+            "energy": (run_row.get("metadata") or {}).get("energy") or {},
+            "tracking": (run_row.get("metadata") or {}).get("tracking") or {},
+            "trace": (run_row.get("metadata") or {}).get("trace") or {},
             "recent_events": [],
         }
 
@@ -289,4 +590,4 @@ class ExecutionLogReader:
         return False
 
 
-__all__ = ["ExecutionLogReader", "ExecutionLogger"]
+__all__ = ["ExecutionLogReader", "ExecutionLogger", "step_log_path"]
@@ -0,0 +1,196 @@
+"""Step callback execution for the DSL executor."""
+
+from __future__ import annotations
+
+from collections import defaultdict
+from typing import Any, Dict
+
+from ..pyrecipe.models import ProviderStep
+from .executor_runtime import _StepNode
+
+
+class ExecutorStepCallbackMixin:
+    def _run_step_callbacks(
+        self,
+        node: _StepNode,
+        *,
+        stage: str,
+        ok: bool,
+        output: str,
+        duration_ms: int,
+        try_number: int = 1,
+    ) -> None:
+        callbacks = node.on_success if ok else node.on_failure
+        if not callbacks:
+            return
+
+        context = {
+            "step_id": node.step_id,
+            "step_num": node.step_num,
+            "step_raw": node.step.raw if hasattr(node.step, "raw") else "",
+            "step_type": str(getattr(node.step, "type", "")),
+            "host": getattr(node.step, "host", ""),
+            "callback_host": getattr(node.step, "host", "local") or "local",
+            "retries": node.retries,
+            "retry_delay": node.retry_delay,
+            "trigger_rule": node.trigger_rule,
+            "pool": node.pool,
+            "priority": node.priority,
+            "ok": ok,
+            "output": output,
+            "duration_ms": duration_ms,
+            "stage": stage,
+            "try_number": max(1, int(try_number)),
+        }
+        for callback in list(callbacks):
+            self._run_step_callback(callback, context=context)
+
+    def _render_callback_value(self, value: Any, context: Dict[str, Any]) -> Any:
+        if isinstance(value, bytes):
+            value = value.decode("utf-8", errors="ignore")
+        if not isinstance(value, str):
+            return value
+        mapping = defaultdict(lambda: "", {k: "" if v is None else str(v) for k, v in context.items()})
+        try:
+            return value.format_map(mapping)
+        except Exception:
+            return value
+
+    def _normalize_callback_payload(self, value: Any, context: Dict[str, Any]) -> Any:
+        if isinstance(value, str):
+            return self._render_callback_value(value, context)
+        if isinstance(value, dict):
+            return {
+                key: self._normalize_callback_payload(val, context)
+                for key, val in value.items()
+            }
+        if isinstance(value, (list, tuple, set)):
+            return [self._normalize_callback_payload(item, context) for item in value]
+        return value
+
+    def _run_step_callback(self, callback: Any, context: Dict[str, Any]) -> None:
+        try:
+            if isinstance(callback, ProviderStep):
+                rendered = ProviderStep(
+                    provider=self._render_callback_value(callback.provider, context),
+                    operation=self._render_callback_value(callback.operation, context),
+                    params=self._normalize_callback_payload(dict(callback.params or {}), context),
+                    id=str(getattr(callback, "id", "callback")),
+                    depends_on=list(getattr(callback, "depends_on", []) or []),
+                    retries=int(getattr(callback, "retries", 0) or 0),
+                    retry_delay=getattr(callback, "retry_delay", 0),
+                    continue_on_failure=bool(getattr(callback, "continue_on_failure", False)),
+                    trigger_rule=str(getattr(callback, "trigger_rule", "all_success")),
+                    pool=str(getattr(callback, "pool", "default")),
+                    priority=int(getattr(callback, "priority", 0) or 0),
+                    execution_timeout=int(getattr(callback, "execution_timeout", 0) or 0),
+                    retry_exponential_backoff=float(getattr(callback, "retry_exponential_backoff", 0.0) or 0.0),
+                    max_active_tis_per_dagrun=getattr(callback, "max_active_tis_per_dagrun", None),
+                    deferrable=bool(getattr(callback, "deferrable", False)),
+                    on_success=list(getattr(callback, "on_success", []) or []),
+                    on_failure=list(getattr(callback, "on_failure", []) or []),
+                )
+                ok, output = self._exec_provider(rendered)
+                if not ok:
+                    self.log(
+                        f"Callback provider step failed (step {context.get('step_num')}): {output}"
+                    )
+                return
+
+            if callable(callback):
+                try:
+                    callback(context)
+                except TypeError:
+                    callback()
+                return
+
+            if isinstance(callback, str):
+                self._run_provider_or_shell_callback(
+                    {"command": callback, "host": context.get("callback_host", "local")},
+                    context,
+                )
+                return
+
+            if isinstance(callback, bytes):
+                self._run_provider_or_shell_callback(
+                    {
+                        "command": callback.decode("utf-8", errors="ignore"),
+                        "host": context.get("callback_host", "local"),
+                    },
+                    context,
+                )
+                return
+
+            if isinstance(callback, dict):
+                provider = callback.get("provider")
+                operation = callback.get("operation")
+                if provider and operation:
+                    params = dict(callback.get("params", {}) or {})
+                    if "host" in callback and "host" not in params:
+                        params["host"] = callback.get("host")
+                    params["provider"] = self._render_callback_value(str(provider), context)
+                    params["operation"] = self._render_callback_value(
+                        str(operation),
+                        context,
+                    )
+                    ok, output = self._exec_provider(
+                        type(
+                            "RuntimeProviderCallback",
+                            (),
+                            {
+                                "provider": params.pop("provider"),
+                                "operation": params.pop("operation"),
+                                "params": self._normalize_callback_payload(params, context),
+                            },
+                        )()
+                    )
+                    if not ok:
+                        self.log(
+                            f"Callback provider step failed (step {context.get('step_num')}): {output}"
+                        )
+                    return
+
+                if "command" in callback:
+                    normalized = dict(callback)
+                    normalized["command"] = self._normalize_callback_payload(
+                        callback.get("command"),
+                        context,
+                    )
+                    normalized.setdefault("host", context.get("callback_host"))
+                    self._run_provider_or_shell_callback(normalized, context)
+                    return
+                return
+
+        except Exception as exc:  # noqa: BLE001
+            self.log(f"Callback execution failed for step {context.get('step_num')}: {exc}")
+
+    def _run_provider_or_shell_callback(self, spec: Dict[str, Any], context: Dict[str, Any]) -> None:
+        command = self._render_callback_value(str(spec.get("command", "")).strip(), context)
+        if not command:
+            return
+        raw_host = spec.get("host", context.get("callback_host", "local"))
+        if raw_host is None:
+            raw_host = "local"
+        if isinstance(raw_host, bytes):
+            raw_host = raw_host.decode("utf-8", errors="ignore")
+        host = self._render_callback_value(str(raw_host), context)
+        host = str(host or "local")
+        timeout = self._normalize_provider_timeout(spec.get("timeout"), allow_zero=True)
+        timeout_secs = 30 if timeout in (None, 0) else timeout
+        cwd = self._normalize_callback_payload(spec.get("cwd"), context)
+        env = self._normalize_callback_payload(spec.get("env"), context)
+
+        params = {
+            "command": command,
+            "host": self._provider_host(host),
+            "timeout": timeout_secs,
+        }
+        if cwd is not None:
+            params["cwd"] = cwd
+        if env is not None:
+            params["env"] = env
+        ok, output = self._exec_provider_shell(params)
+        if not ok:
+            self.log(
+                f"Callback shell command failed (step {context.get('step_num')}): {output}"
+            )
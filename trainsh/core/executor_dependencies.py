@@ -4,13 +4,15 @@ from __future__ import annotations
 
 import concurrent.futures
 import queue
+import re
 import time
 from collections import defaultdict
-from typing import Dict, List, Optional
+from typing import Any, Dict, List, Optional, Set, Tuple
 
 from .executor_runtime import _DeferredEvent, _StepNode
+from .recipe_models import StepType
 from .task_state import FINISHED_STATES, TaskInstanceState
-from .ti_dependencies import DependencyContext
+from .ti_dependencies import CAPACITY_DEP_NAMES, DependencyContext
 
 
 class ExecutorDependencyMixin:
@@ -52,6 +54,72 @@ class ExecutorDependencyMixin:
         pool_name = str(pool_name or "default").strip() or "default"
         return int(self._pool_limits.get(pool_name, self._pool_limits.get("default", self.max_workers)))
 
+    def _step_timeout_class(self, step) -> str:
+        """Classify a step for `timeouts.<class>_secs` defaults."""
+        step_type = getattr(step, "type", None)
+        if step_type == StepType.EXECUTE:
+            return "session"
+        if step_type == StepType.TRANSFER:
+            return "transfer"
+        if step_type == StepType.WAIT:
+            return "wait"
+        provider = str(getattr(step, "provider", "") or "")
+        operation = str(getattr(step, "operation", "") or "")
+        if operation.startswith("wait"):
+            return "wait"
+        if provider == "http":
+            return "http"
+        if provider == "shell" or operation in {"ssh_command", "uv_run"}:
+            return "command"
+        if provider == "storage" and operation in {"upload", "download", "copy", "move", "sync"}:
+            return "transfer"
+        return ""
+
+    def _default_step_timeout(self, step) -> int:
+        """Return the configured timeout for a step that sets none of its own."""
+        op_class = self._step_timeout_class(step)
+        if not op_class:
+            return 0
+        params = getattr(step, "params", None)
+        own = getattr(step, "timeout", 0)
+        if isinstance(params, dict):
+            own = own or params.get("timeout") or params.get("timeout_secs") or 0
+        if own:
+            return 0
+        value = getattr(self, "default_timeouts", {}).get(f"{op_class}_secs", 0)
+        try:
+            return max(0, self._parse_duration(str(value or 0)))
+        except ValueError:
+            return 0
+
+    def _cancel_pending_steps(
+        self,
+        nodes: Dict[str, _StepNode],
+        states: Dict[str, str],
+        attempts: Dict[str, int],
+        reason: str,
+    ) -> None:
+        """Stop scheduling: mark queued and deferred steps removed, let running ones finish."""
+        for sid, state in list(states.items()):
+            if state not in {
+                TaskInstanceState.SCHEDULED,
+                TaskInstanceState.UP_FOR_RETRY,
+                TaskInstanceState.DEFERRED,
+            }:
+                continue
+            self._deferred_events.pop(sid, None)
+            states[sid] = TaskInstanceState.REMOVED
+            self._emit_step_end(
+                nodes[sid],
+                sid,
+                state=TaskInstanceState.REMOVED,
+                success=False,
+                duration_ms=0,
+                output=reason,
+                error=reason,
+                try_number=max(1, attempts.get(sid, 1)),
+            )
+
     def _build_step_graph(self) -> Tuple[Dict[str, _StepNode], List[str], bool]:
         """Build the execution graph for dependency-aware runs."""
         nodes: Dict[str, _StepNode] = {}
@@ -78,12 +146,20 @@ class ExecutorDependencyMixin:
                 trigger_rule=self._extract_step_trigger_rule(step),
                 pool=self._extract_step_pool(step),
                 priority=self._extract_step_priority(step),
-                execution_timeout=self._extract_step_execution_timeout(step),
+                execution_timeout=(
+                    self._extract_step_execution_timeout(step) or self._default_step_timeout(step)
+                ),
                 retry_exponential_backoff=self._extract_step_retry_exponential_backoff(step),
                 on_success=self._extract_step_callbacks(step, "on_success"),
                 on_failure=self._extract_step_callbacks(step, "on_failure"),
                 max_active_tis_per_dagrun=self._extract_step_max_active_tis_per_dagrun(step),
                 deferrable=self._extract_step_deferrable(step),
+                done_check=str(getattr(step, "done_check", "") or "").strip(),
+                retry_on_exit_codes=list(getattr(step, "retry_on_exit_codes", None) or []),
+                retry_on_output_regex=str(getattr(step, "retry_on_output_regex", "") or ""),
+                on_failure_run=str(getattr(step, "on_failure_run", "") or "").strip(),
+                always_run=bool(getattr(step, "always_run", False)),
+                group=str(getattr(step, "group", "") or "").strip(),
             )
             ordered_ids.append(step_id)
 
@@ -91,9 +167,109 @@ class ExecutorDependencyMixin:
             for dep_id in node.depends_on:
                 if dep_id not in nodes:
                     raise ValueError(f"unknown dependency '{dep_id}' for step '{node.step_id}'")
+            hook = node.on_failure_run
+            if hook and (hook not in nodes or hook == node.step_id):
+                raise ValueError(f"invalid on_failure_run '{hook}' for step '{node.step_id}'")
 
+        self._nest_group_members(nodes, ordered_ids)
+        self._detach_steps(nodes, self._failure_hook_ids(nodes) | self._cleanup_step_ids(nodes))
         return nodes, ordered_ids, has_dep
 
+    def _failure_hook_ids(self, nodes: Dict[str, _StepNode]) -> Set[str]:
+        return {node.on_failure_run for node in nodes.values() if node.on_failure_run}
+
+    def _cleanup_step_ids(self, nodes: Dict[str, _StepNode]) -> Set[str]:
+        return {sid for sid, node in nodes.items() if node.always_run}
+
+    def _detach_steps(self, nodes: Dict[str, _StepNode], hooks: Set[str]) -> None:
+        """Take failure hooks and cleanup steps out of the normal dependency chain.
+
+        Steps that depended on one depend on its own upstream instead, so an
+        unused hook or a deferred cleanup step does not block the rest of the recipe.
+        """
+        if not hooks:
+            return
+
+        def _resolve(dep_ids: List[str], seen: Set[str]) -> List[str]:
+            resolved: List[str] = []
+            for dep_id in dep_ids:
+                if dep_id in hooks:
+                    if dep_id in seen:
+                        continue
+                    inherited = _resolve(nodes[dep_id].depends_on, seen | {dep_id})
+                else:
+                    inherited = [dep_id]
+                resolved.extend(item for item in inherited if item not in resolved)
+            return resolved
+
+        for node in nodes.values():
+            if node.step_id not in hooks:
+                node.depends_on = _resolve(node.depends_on, set())
+
+    def _run_cleanup_steps(self, success: bool) -> bool:
+        """Run `always_run` steps in recipe order once the DAG has finished or stopped."""
+        try:
+            nodes, ordered_ids, _ = self._build_step_graph()
+        except ValueError:
+            return success
+        cleanup = [nodes[sid] for sid in ordered_ids if nodes[sid].always_run]
+        if not cleanup:
+            return success
+
+        self.log(f"Running {len(cleanup)} cleanup step(s) ({'success' if success else 'failure'} path)")
+        cleanup_ok = True
+        for node in cleanup:
+            ok, _output, _duration_ms = self._run_single_step_with_retries(
+                node,
+                step_id=node.step_id,
+                track_checkpoint=False,
+            )
+            if not ok and not node.continue_on_failure:
+                cleanup_ok = False
+        return success and cleanup_ok
+
+    def _retry_gate(self, node: _StepNode, step_id: str, output: str) -> bool:
+        allowed, reason = self._retry_allowed(node, step_id, output)
+        if not allowed:
+            self.log(f"✗ Step {node.step_num}: not retrying; {reason}")
+        return allowed
+
+    def _trigger_failure_hook(
+        self,
+        node: _StepNode,
+        states: Dict[str, str],
+        attempts: Dict[str, int],
+        held_hooks: Set[str],
+        triggered_hooks: Set[str],
+    ) -> None:
+        """Release a failed step's `on_failure_run` step for scheduling."""
+        hook = node.on_failure_run
+        if not hook or hook not in held_hooks:
+            return
+        held_hooks.discard(hook)
+        triggered_hooks.add(hook)
+        states[hook] = TaskInstanceState.SCHEDULED
+        attempts[hook] = 0
+        self.log(f"↪ Step {node.step_num} failed; running on_failure_run step '{hook}'")
+
+    def _retry_allowed(self, node: _StepNode, step_id: str, output: str) -> Tuple[bool, str]:
+        """Apply `retry_on_exit_codes` / `retry_on_output_regex`; no filters means always retry."""
+        with self._thread_lock:
+            exit_code = self._step_exit_codes.pop(step_id, None)
+            # tmux steps only report "exit code N"; their pane tail holds the real output.
+            pane_tail = self._step_outputs.pop(step_id, "")
+        output = "\n".join(part for part in (output, pane_tail) if part)
+        codes = list(node.retry_on_exit_codes or [])
+        pattern = node.retry_on_output_regex
+        if not codes and not pattern:
+            return True, ""
+        if codes and exit_code is not None and exit_code in codes:
+            return True, f"exit code {exit_code}"
+        if pattern and re.search(pattern, output or "", re.MULTILINE):
+            return True, f"output matches /{pattern}/"
+        code_text = "unknown" if exit_code is None else str(exit_code)
+        return False, f"exit code {code_text} and output match no retry_on filter"
+
     def _execute_sequential(self, resume_from: int = 0) -> bool:
         """Execute recipe one step at a time while honoring dependency semantics."""
         return self._execute_with_dependencies(resume_from=resume_from, worker_limit=1)
@@ -114,6 +290,8 @@ class ExecutorDependencyMixin:
         states: Dict[str, str] = {}
         attempts: Dict[str, int] = {}
         retry_ready_at: Dict[str, float] = {}
+        # When each step last became runnable; the gap until it starts is queue time.
+        ready_since: Dict[str, float] = {}
         for sid, node in nodes.items():
             if node.step_num <= resume_from:
                 states[sid] = TaskInstanceState.SUCCESS
@@ -121,21 +299,48 @@ class ExecutorDependencyMixin:
                 states[sid] = TaskInstanceState.SCHEDULED
                 attempts[sid] = 0
 
+        # Failure hooks wait, skipped, until a step names them in on_failure_run.
+        held_hooks: Set[str] = set()
+        triggered_hooks: Set[str] = set()
+        for sid in self._failure_hook_ids(nodes):
+            if states.get(sid) == TaskInstanceState.SCHEDULED:
+                states[sid] = TaskInstanceState.SKIPPED
+                held_hooks.add(sid)
+        # Cleanup steps run after the DAG in _run_cleanup_steps; group members
+        # run inside their group step.
+        for sid in self._cleanup_step_ids(nodes) | self._group_member_ids(nodes):
+            states[sid] = TaskInstanceState.SKIPPED
+
         if all(self._step_is_terminal(state) for state in states.values()):
             return True
 
         running: Dict[concurrent.futures.Future, tuple[str, int]] = {}
         fatal = False
+        max_runtime = int(getattr(self, "max_runtime_secs", 0) or 0)
+        started_at = self.ctx.start_time.timestamp() if self.ctx.start_time else time.time()
+        deadline = started_at + max_runtime if max_runtime else 0.0
+        timed_out = False
         self._deferred_events.clear()
 
         self._triggerer.start()
         try:
-            with concurrent.futures.ThreadPoolExecutor(max_workers=effective_workers) as thread_pool:
+            with concurrent.futures.ThreadPoolExecutor(
+                max_workers=effective_workers
+            ) as thread_pool, self._cancel_on_interrupt():
                 while True:
                     now = time.time()
                     changed = False
                     scheduled_this_round = False
 
+                    if deadline and not timed_out and now >= deadline:
+                        timed_out = True
+                        fatal = True
+                        reason = f"execution time limit reached ({max_runtime}s)"
+                        self.log(f"⏱ {reason}; waiting for {len(running)} running step(s) to finish")
+                        self._emit_event("execution_timeout", limit_secs=max_runtime, running=len(running))
+                        self._cancel_pending_steps(nodes, states, attempts, reason)
+                        changed = True
+
                     # Consume all triggerer events first.
                     while True:
                         try:
@@ -179,7 +384,7 @@ class ExecutorDependencyMixin:
                             )
                         else:
                             retries = max(0, int(node.retries or 0))
-                            if attempt <= retries:
+                            if attempt <= retries and self._retry_gate(node, sid, output):
                                 delay = self._compute_backoff_delay(node, attempt)
                                 if delay > 0:
                                     retry_ready_at[sid] = now + delay
@@ -189,6 +394,14 @@ class ExecutorDependencyMixin:
                                     retry_msg = "retry immediately"
 
                                 states[sid] = TaskInstanceState.UP_FOR_RETRY
+                                self._emit_step_retry(
+                                    node,
+                                    sid,
+                                    try_number=attempt,
+                                    duration_ms=duration_ms,
+                                    delay_secs=delay,
+                                    output=output,
+                                )
                                 self.log(
                                     f"↺ Step {node.step_num}: deferrable retry {attempt}/{retries} ({retry_msg})"
                                 )
@@ -205,7 +418,9 @@ class ExecutorDependencyMixin:
                                 )
                                 if not node.continue_on_failure:
                                     fatal = True
+                                    self._note_step_failed(sid)
                                 self._save_checkpoint(node.step_num - 1, status="failed")
+                                self._trigger_failure_hook(node, states, attempts, held_hooks, triggered_hooks)
 
                                 self._emit_step_end(
                                     node,
@@ -241,11 +456,29 @@ class ExecutorDependencyMixin:
                                 state = TaskInstanceState.FAILED
                                 duration_ms = 0
 
+                            attempts[sid] = max(attempts.get(sid, 0), attempt)
+
+                            if state == TaskInstanceState.SKIPPED:
+                                # Already done counts as success for downstream trigger rules.
+                                states[sid] = TaskInstanceState.SUCCESS
+                                retry_ready_at.pop(sid, None)
+                                self._emit_step_end(
+                                    node,
+                                    sid,
+                                    state=TaskInstanceState.SKIPPED,
+                                    success=True,
+                                    duration_ms=duration_ms,
+                                    output=output,
+                                    error="",
+                                    try_number=attempt,
+                                )
+                                self.log(f"⏭ Step {node.step_num} ({sid}) skipped: {output}")
+                                changed = True
+                                continue
+
                             if state not in {TaskInstanceState.SUCCESS, TaskInstanceState.FAILED}:
                                 state = TaskInstanceState.FAILED if output else TaskInstanceState.SUCCESS
 
-                            attempts[sid] = max(attempts.get(sid, 0), attempt)
-
                             if state == TaskInstanceState.SUCCESS:
                                 states[sid] = TaskInstanceState.SUCCESS
                                 retry_ready_at.pop(sid, None)
@@ -269,7 +502,7 @@ class ExecutorDependencyMixin:
                                 )
                             else:
                                 retries = max(0, int(node.retries or 0))
-                                if attempt <= retries:
+                                if attempt <= retries and self._retry_gate(node, sid, output):
                                     delay = self._compute_backoff_delay(node, attempt)
                                     if delay > 0:
                                         retry_ready_at[sid] = time.time() + delay
@@ -278,6 +511,14 @@ class ExecutorDependencyMixin:
                                         retry_ready_at[sid] = time.time()
                                         retry_msg = "retry immediately"
                                     states[sid] = TaskInstanceState.UP_FOR_RETRY
+                                    self._emit_step_retry(
+                                        node,
+                                        sid,
+                                        try_number=attempt,
+                                        duration_ms=duration_ms,
+                                        delay_secs=delay,
+                                        output=output,
+                                    )
                                     self.log(
                                         f"↺ Step {node.step_num}: failed, retry {attempt}/{retries} ({retry_msg})"
                                     )
@@ -294,7 +535,11 @@ class ExecutorDependencyMixin:
                                     )
                                     if not node.continue_on_failure:
                                         fatal = True
+                                        self._note_step_failed(sid)
                                     self._save_checkpoint(node.step_num - 1, status="failed")
+                                    self._trigger_failure_hook(
+                                        node, states, attempts, held_hooks, triggered_hooks
+                                    )
                                     self._emit_step_end(
                                         node,
                                         sid,
@@ -338,6 +583,10 @@ class ExecutorDependencyMixin:
                         if sid in running_step_ids or sid in self._deferred_events:
                             continue
 
+                        if sid in triggered_hooks:
+                            ready.append(sid)
+                            continue
+
                         node = nodes[sid]
                         decision = self._ti_dependency_evaluator.evaluate(node, context)
                         if decision.met is False:
@@ -366,6 +615,8 @@ class ExecutorDependencyMixin:
                                 )
                             continue
                         if decision.met is None:
+                            if all(status.passed or status.dep_name in CAPACITY_DEP_NAMES for status in decision.statuses):
+                                ready_since.setdefault(sid, time.time())
                             continue
 
                         ready.append(sid)
@@ -382,6 +633,7 @@ class ExecutorDependencyMixin:
 
                         node = nodes[sid]
                         attempt = attempts.get(sid, 0) + 1
+                        ready_since.setdefault(sid, time.time())
                         deferrable_check = (
                             self._build_defer_check(node, step_id=sid, attempt=attempt)
                             if node.deferrable
@@ -391,7 +643,13 @@ class ExecutorDependencyMixin:
                         if deferrable_check is not None:
                             check_fn, timeout_secs, poll_interval = deferrable_check
                             self._save_checkpoint(node.step_num - 1)
-                            self._emit_step_start(node, sid, try_number=attempt)
+                            self._emit_step_start(
+                                node,
+                                sid,
+                                try_number=attempt,
+                                queued_ms=int((time.time() - ready_since.pop(sid)) * 1000),
+                                deferred=True,
+                            )
                             task_id = self._triggerer.submit(
                                 step_id=sid,
                                 check_fn=check_fn,
@@ -411,8 +669,24 @@ class ExecutorDependencyMixin:
                         if not self._pool_manager.try_acquire(node.pool):
                             continue
 
+                        execution_timeout = node.execution_timeout
+                        if deadline:
+                            # Running steps may not outlive the run's wall-clock limit.
+                            remaining = max(1, int(deadline - time.time()))
+                            execution_timeout = min(execution_timeout or remaining, remaining)
+
+                        with self._thread_lock:
+                            self._step_exit_codes.pop(sid, None)
+                            self._step_exits.pop(sid, None)
+                            self._step_stops.pop(sid, None)
+                            self._step_outputs.pop(sid, None)
                         self._save_checkpoint(node.step_num - 1)
-                        self._emit_step_start(node, sid, try_number=attempt)
+                        self._emit_step_start(
+                            node,
+                            sid,
+                            try_number=attempt,
+                            queued_ms=int((time.time() - ready_since.pop(sid)) * 1000),
+                        )
                         states[sid] = TaskInstanceState.RUNNING
                         attempts[sid] = attempt
                         running_future = thread_pool.submit(
@@ -422,8 +696,9 @@ class ExecutorDependencyMixin:
                             step_id=sid,
                             try_number=attempt,
                             track_checkpoint=False,
-                            execution_timeout=node.execution_timeout,
+                            execution_timeout=execution_timeout,
                             emit_events=False,
+                            done_check=node.done_check,
                         )
                         running[running_future] = (sid, attempt)
                         scheduled_this_round = True
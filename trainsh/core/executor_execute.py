@@ -1,13 +1,24 @@
 # tmux-trainsh execute helpers
 # Encapsulates @session command execution.
 
+import hashlib
 import re
 import os
 import shlex
 import subprocess
+import sys
 import time
 from typing import Any, Callable, Optional
 
+from ..constants import SCRIPT_CACHE_DIR
+from ..services.tracing import KIND_CLIENT, span
+from .bridge_exec import parse_status_file
+from .recipe_models import SCRIPT_PLACEHOLDER
+from .remote_cancel import cancel_inflight, pid_file_for, status_file_for, wrap_tracked_command
+
+# Pane lines kept from a failed tmux command for `retry_on_output_regex`.
+PANE_TAIL_LINES = 200
+
 
 class ExecuteHelper:
     """Helper for execute steps."""
@@ -58,6 +69,73 @@ class ExecuteHelper:
             return ""
         return result.stdout or ""
 
+    def _stage_script(self, local_path: str, host: str) -> tuple[str, str]:
+        """Put a step script on `host` under its content hash; returns (error, path relative to $HOME)."""
+        try:
+            with open(local_path, "rb") as handle:
+                data = handle.read()
+        except OSError as exc:
+            return f"Cannot read script {local_path}: {exc.strerror or exc}", ""
+        digest = hashlib.sha256(data).hexdigest()[:16]
+        target = f"{SCRIPT_CACHE_DIR}/{digest}/{os.path.basename(local_path)}"
+        if host == "local":
+            path = os.path.join(os.path.expanduser("~"), target)
+            if not os.access(path, os.X_OK):
+                os.makedirs(os.path.dirname(path), exist_ok=True)
+                with open(f"{path}.tmp", "wb") as handle:
+                    handle.write(data)
+                os.chmod(f"{path}.tmp", 0o755)
+                os.replace(f"{path}.tmp", path)
+            return "", target
+
+        quoted, partial = shlex.quote(target), shlex.quote(f"{target}.tmp")
+        command = (
+            f"test -x {quoted} || {{ mkdir -p {shlex.quote(os.path.dirname(target))} && cat > {partial} "
+            f"&& chmod +x {partial} && mv {partial} {quoted}; }}"
+        )
+        try:
+            result = subprocess.run(
+                self.build_ssh_args(host, command=command, tty=False),
+                input=data,
+                capture_output=True,
+                timeout=120,
+            )
+        except (OSError, subprocess.TimeoutExpired) as exc:
+            return f"Failed to upload script {os.path.basename(local_path)}: {exc}", ""
+        if result.returncode != 0:
+            detail = (result.stderr or b"").decode("utf-8", errors="replace").strip()
+            return f"Failed to upload script {os.path.basename(local_path)}: {detail or f'exit {result.returncode}'}", ""
+        if self.executor.logger:
+            self.executor.logger.log_detail("script", f"Staged {os.path.basename(local_path)} on {host}", {
+                "host": host,
+                "path": target,
+            })
+        return "", target
+
+    def _note_pane_tail(self, tmux_client: Any, session: str) -> None:
+        """Keep the last lines of a failed command's pane for `retry_on_output_regex`."""
+        try:
+            result = tmux_client.capture_pane(session, start=f"-{PANE_TAIL_LINES}")
+        except Exception:
+            return
+        if result.returncode == 0:
+            self.executor._note_step_output(result.stdout or "")
+
+    def _read_exit_code(self, tmux_client: Any, host: str, path: str, signal: str) -> Optional[int]:
+        """Exit code a tracked command wrote to its status file, if it is there."""
+        try:
+            if host == "local":
+                with open(path, encoding="utf-8") as handle:
+                    text = handle.read()
+            else:
+                result = tmux_client.read_text(path)
+                if result.returncode != 0:
+                    return None
+                text = result.stdout
+        except Exception:
+            return None
+        return parse_status_file(text, signal)
+
     def _cleanup_captured_output(self, host: str, path: str) -> None:
         """Best-effort cleanup for one captured-output file."""
         target = str(path or "").strip()
@@ -90,10 +168,179 @@ class ExecuteHelper:
         self.executor.ctx.variables[capture_var] = output.rstrip("\r\n")
         self._cleanup_captured_output(host, capture_path)
 
+    def _reattach_inflight(self, tmux_client: Any, remote_session: str, timeout: Optional[int]) -> Optional[tuple[bool, str]]:
+        """Wait on a command left running by a previous process instead of re-sending it."""
+        step_num = self.executor._current_step_num()
+        info = self.executor.ctx.inflight.get(str(step_num))
+        if not info or info.get("remote_session") != remote_session:
+            return None
+        signal = str(info.get("signal", "") or "")
+        if not signal or not tmux_client.has_session(remote_session):
+            self.executor._clear_inflight(step_num)
+            return None
+
+        # tmux remembers a signal sent with no waiter, so this returns at once
+        # when the command already finished while nobody was attached.
+        start_time = time.time()
+        wait_result = tmux_client.wait_for(signal, timeout=timeout)
+        self.executor._clear_inflight(step_num)
+        elapsed = int(time.time() - start_time)
+        if self.executor.logger:
+            self.executor.logger.log_detail("execute_reattach", f"Re-attached to {remote_session}", {
+                "signal": signal,
+                "elapsed_sec": elapsed,
+                "wait_rc": wait_result.returncode,
+            })
+        if wait_result.returncode == 0:
+            return True, f"Re-attached; command completed ({elapsed}s)"
+        return False, "Re-attached; command failed or wait-for timed out"
+
+    def kill_inflight(self, info: dict, *, close_session: bool = False) -> str:
+        """Kill the process group behind one in-flight command."""
+        host = str(info.get("host", "") or "local")
+        return cancel_inflight(
+            info,
+            build_ssh_args=self.build_ssh_args,
+            tmux_client=self.executor.get_tmux_client(host) if close_session else None,
+        )
+
+    def _assign_gpus(self, step: Any, window_name: str, host: str, commands: str) -> tuple[str, str]:
+        """Validate and claim the step's GPUs; return (error, commands with CUDA_VISIBLE_DEVICES)."""
+        gpus = [self.executor._interpolate(str(item)).strip() for item in getattr(step, "gpus", None) or []]
+        gpus = [item for item in gpus if item]
+        if not gpus:
+            return "", commands
+        invalid = [item for item in gpus if not item.isdigit()]
+        if invalid:
+            return f"Invalid GPU index for {window_name}: {', '.join(invalid)}", commands
+
+        from . import gpu_guard
+
+        output = gpu_guard.probe_host(host, build_ssh_args=self.build_ssh_args)
+        if output is None:
+            self.executor.log(f"⚠ Could not list GPUs on {window_name}; GPU {','.join(gpus)} not validated")
+        else:
+            inventory = gpu_guard.gpu_indices(output)
+            missing = [item for item in gpus if item not in inventory]
+            if missing:
+                available = ", ".join(inventory) if inventory else "no NVIDIA GPUs"
+                return f"GPU {','.join(missing)} not found on {window_name} (host has {available})", commands
+
+        conflict = self.executor._claim_gpus(window_name, host, gpus)
+        if conflict:
+            return conflict, commands
+        return "", f"export CUDA_VISIBLE_DEVICES={','.join(gpus)}; {commands}"
+
+    def _guard_gpu(self, window_name: str, host: str, commands: str) -> Optional[tuple[bool, str]]:
+        """Stop (or ask before) a GPU launch onto a card that already holds memory."""
+        from . import gpu_guard
+
+        executor = self.executor
+        mode = getattr(executor, "gpu_guard_mode", "off")
+        if mode not in ("warn", "confirm", "fail") or executor.is_resuming or not gpu_guard.looks_like_gpu_launch(commands):
+            return None
+        if str(executor._current_step_num()) in executor.ctx.inflight:
+            return None
+        gpus = gpu_guard.requested_gpus(commands)
+        if gpus == []:
+            return None
+        busy = gpu_guard.check_host_gpus(
+            host,
+            gpus,
+            min_memory_mb=getattr(executor, "gpu_guard_min_memory_mb", 1024),
+            build_ssh_args=self.build_ssh_args,
+        )
+        if not busy:
+            return None
+
+        summary = gpu_guard.describe(busy)
+        executor.log(f"⚠ GPU already in use on {window_name}: {summary}")
+        executor._emit_event(
+            "gpu_busy",
+            step_num=executor._current_step_num(),
+            window=window_name,
+            host=host,
+            gpus=busy,
+            mode=mode,
+            confirmation_required=mode == "confirm",
+        )
+        if mode == "warn":
+            return None
+        if mode == "confirm" and sys.stdin.isatty():
+            from ..cli_utils import prompt_input
+
+            with executor._waiting_for_input("gpu_guard"):
+                answer = prompt_input("Launch anyway? [y/N]: ")
+            if answer is not None and answer.strip().lower() in ("y", "yes"):
+                return None
+        return False, (
+            f"GPU already in use on {window_name}: {summary}. "
+            "Pick a free GPU with CUDA_VISIBLE_DEVICES, or set gpu_guard.mode to warn/off (--gpu-guard off)."
+        )
+
+    def _interpolate_command(self, commands: str, host: str) -> str:
+        """Interpolate step commands in the dialect of the host that runs them."""
+        interpolate = getattr(self.executor, "_interpolate_command", None)
+        if interpolate is None:
+            return self.executor._interpolate(commands)
+        from .executor_utils import _host_shell
+
+        return interpolate(commands, "bash" if host == "local" else _host_shell(host))
+
+    def _local_workdir(self, window: Any) -> Optional[str]:
+        """Directory a local window's command runs in: its tmux pane path, else this process's cwd."""
+        if not getattr(window, "remote_session", None):
+            return os.getcwd()
+        try:
+            result = self.executor.get_tmux_client("local").display_message(window.remote_session, "#{pane_current_path}")
+        except Exception:
+            return None
+        path = str(getattr(result, "stdout", "") or "").strip()
+        return path if getattr(result, "returncode", 1) == 0 and path else None
+
+    def guard_local(self, commands: str, workdir: Optional[str] = None) -> Optional[tuple[bool, str]]:
+        """Apply `local_policy` to a command about to run on this machine from `workdir`."""
+        from .local_policy import check_command
+
+        executor = self.executor
+        settings = getattr(executor, "local_policy", None)
+        if not settings:
+            return None
+        decision = check_command(
+            commands,
+            settings,
+            workspace=workdir,
+            trusted=bool(getattr(executor, "recipe_trusted", False)),
+        )
+        if decision.action == "allow":
+            return None
+
+        executor.log(f"⚠ Local command {decision.reason}: {decision.segment}")
+        executor._emit_event(
+            "local_policy",
+            step_num=executor._current_step_num(),
+            action=decision.action,
+            reason=decision.reason,
+            segment=decision.segment,
+            confirmation_required=decision.action == "confirm",
+        )
+        if decision.action == "warn":
+            return None
+        if decision.action == "confirm" and sys.stdin.isatty():
+            from ..cli_utils import prompt_input
+
+            with executor._waiting_for_input("local_policy"):
+                answer = prompt_input("Run it anyway? [y/N]: ")
+            if answer is not None and answer.strip().lower() in ("y", "yes"):
+                return None
+        hint = "adjust local_policy.deny" if "deny pattern" in decision.reason else "trust the recipe with `train recipe trust <name>` or adjust local_policy"
+        return False, f"Blocked local command ({decision.reason}): {decision.segment}. To allow it, {hint}."
+
     def exec_execute(self, step: Any) -> tuple[bool, str]:
         """Execute command: @session > command."""
         window_name = step.host
-        commands = self.executor._interpolate(step.commands)
+        window = self.executor._resolve_window(window_name)
+        commands = self._interpolate_command(step.commands, window.host if window else "local")
         timeout = self._command_timeout(step)
 
         if self.executor.logger:
@@ -103,10 +350,28 @@ class ExecuteHelper:
                 "background": step.background,
             })
 
-        window = self.executor._resolve_window(window_name)
         if not window:
             return False, f"Unknown window: {window_name}"
 
+        if getattr(step, "script", ""):
+            error, target = self._stage_script(step.script, window.host)
+            if error:
+                return False, error
+            commands = commands.replace(SCRIPT_PLACEHOLDER, f'"$HOME/{target}"')
+
+        error, commands = self._assign_gpus(step, window_name, window.host, commands)
+        if error:
+            return False, error
+
+        guarded = self._guard_gpu(window_name, window.host, commands)
+        if guarded is not None:
+            return guarded
+
+        if window.host == "local":
+            guarded = self.guard_local(commands, self._local_workdir(window))
+            if guarded is not None:
+                return guarded
+
         bridge_result = self.executor._exec_via_bridge(
             window=window,
             commands=commands,
@@ -115,6 +380,8 @@ class ExecuteHelper:
             start_time=time.time(),
         )
         if bridge_result is not None:
+            if not step.background:
+                self.executor._status_hosts.add(window.host or "local")
             return bridge_result
 
         start_time = time.time()
@@ -134,6 +401,11 @@ class ExecuteHelper:
                     })
                 return result.returncode == 0, "Command sent (background)"
 
+            reattached = self._reattach_inflight(tmux_client, remote_session, timeout)
+            if reattached is not None:
+                self._store_captured_output(step, host)
+                return reattached
+
             if self.executor.is_resuming:
                 result = tmux_client.send_keys(remote_session, commands, enter=True, literal=True)
                 if result.returncode != 0:
@@ -152,12 +424,29 @@ class ExecuteHelper:
 
             import uuid
             signal = f"train_{uuid.uuid4().hex[:8]}"
-            wrapped_cmd = f"( {commands} ); tmux wait-for -S {signal}"
+            pidfile = pid_file_for(signal)
+            status_file = status_file_for(self.executor.ctx.job_id, signal)
+            self.executor._status_hosts.add(host)
+            wrapped_cmd = wrap_tracked_command(commands, signal, pidfile, status_file)
+            step_num = self.executor._current_step_num()
+            self.executor._track_inflight(step_num, {
+                "host": host,
+                "remote_session": remote_session,
+                "signal": signal,
+                "pidfile": pidfile,
+            })
             send_result = tmux_client.send_keys(remote_session, wrapped_cmd, enter=True, literal=True)
             if send_result.returncode != 0:
+                self.executor._clear_inflight(step_num)
                 return False, "Failed sending command to tmux session"
 
             wait_result = tmux_client.wait_for(signal, timeout=timeout)
+            cancelled = str(self.executor.ctx.inflight.get(str(step_num), {}).get("cancelled", "") or "")
+            kill_report = ""
+            if wait_result.returncode != 0 and not cancelled:
+                self.executor._note_step_stopped(self.executor._current_step_id(), "timeout")
+                kill_report = self.kill_inflight({"host": host, "pidfile": pidfile})
+            self.executor._clear_inflight(step_num)
             self._store_captured_output(step, host)
             elapsed = int(time.time() - start_time)
             if self.executor.logger:
@@ -165,42 +454,58 @@ class ExecuteHelper:
                     "elapsed_sec": elapsed,
                     "remote_session": remote_session,
                     "wait_rc": wait_result.returncode,
+                    "kill": kill_report,
                 })
+            if cancelled:
+                return False, f"Command cancelled: {cancelled}"
             if wait_result.returncode == 0:
+                exit_code = self._read_exit_code(tmux_client, host, status_file, signal)
+                if exit_code is not None:
+                    self.executor._note_exit_code(exit_code, host)
+                if exit_code:
+                    self._note_pane_tail(tmux_client, remote_session)
+                    return False, f"Command failed with exit code {exit_code} ({elapsed}s)"
                 return True, f"Command completed ({elapsed}s)"
-            return False, "Command failed or wait-for timed out"
+            self._note_pane_tail(tmux_client, remote_session)
+            return False, f"Command failed or wait-for timed out ({kill_report})"
         else:
             if host == "local":
                 try:
-                    result = subprocess.run(
-                        commands,
-                        shell=True,
-                        capture_output=True,
-                        text=True,
-                        timeout=timeout,
-                    )
+                    with span("local", **{"trainsh.operation": "execute"}):
+                        result = subprocess.run(
+                            commands,
+                            shell=True,
+                            capture_output=True,
+                            text=True,
+                            timeout=timeout,
+                        )
                     duration_ms = int((time.time() - start_time) * 1000)
+                    self.executor._note_exit_code(result.returncode, "local")
                     if self.executor.logger:
                         self.executor.logger.log_ssh("local", commands, result.returncode, result.stdout, result.stderr, duration_ms)
                     self._store_captured_output(step, "local")
                     return result.returncode == 0, result.stdout or result.stderr
                 except subprocess.TimeoutExpired:
+                    self.executor._note_step_stopped(self.executor._current_step_id(), "timeout")
                     return False, f"Command timed out after {timeout}s"
 
             ssh_args = self.build_ssh_args(host, command=commands, tty=False)
             try:
-                result = subprocess.run(
-                    ssh_args,
-                    capture_output=True,
-                    text=True,
-                    timeout=timeout,
-                )
+                with span("ssh", kind=KIND_CLIENT, **{"net.peer.name": host, "ssh.operation": "execute"}):
+                    result = subprocess.run(
+                        ssh_args,
+                        capture_output=True,
+                        text=True,
+                        timeout=timeout,
+                    )
                 duration_ms = int((time.time() - start_time) * 1000)
+                self.executor._note_exit_code(result.returncode, host)
                 if self.executor.logger:
                     self.executor.logger.log_ssh(host, commands, result.returncode, result.stdout, result.stderr, duration_ms)
                 self._store_captured_output(step, host)
                 return result.returncode == 0, result.stdout or result.stderr
             except subprocess.TimeoutExpired:
+                self.executor._note_step_stopped(self.executor._current_step_id(), "timeout")
                 return False, f"Command timed out after {timeout}s"
 
     def tmux_send_keys(self, host: str, session: str, text: str) -> None:
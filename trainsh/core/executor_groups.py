@@ -0,0 +1,120 @@
+"""Run `recipe.group(...)` blocks as one scheduled step with nested members."""
+
+from __future__ import annotations
+
+import time
+from collections import Counter
+from typing import Any, Dict, List, Optional, Set
+
+from .executor_runtime import _StepNode
+from .task_state import TaskInstanceState
+from .ti_dependencies import DependencyContext
+
+
+class ExecutorGroupMixin:
+    def _nest_group_members(self, nodes: Dict[str, _StepNode], ordered_ids: List[str]) -> None:
+        """Keep group members out of the top-level DAG.
+
+        A dependency that crosses a group boundary is lifted to the group step at
+        the dependent's own level: the group waits for everything its members need,
+        and later steps wait for the whole group.
+        """
+        for node in nodes.values():
+            if node.group and node.group not in nodes:
+                raise ValueError(f"unknown group '{node.group}' for step '{node.step_id}'")
+
+        def _lift(dep_id: str, level: str) -> Optional[str]:
+            current = dep_id
+            while nodes[current].group != level:
+                parent = nodes[current].group
+                if not parent:
+                    return None
+                current = parent
+            return current
+
+        # Members precede their group step in recipe order, so a group sees the
+        # dependencies its members handed up before it is itself lifted.
+        for sid in ordered_ids:
+            node = nodes[sid]
+            lifted: List[str] = []
+            for dep_id in node.depends_on:
+                target = _lift(dep_id, node.group)
+                if target is None:
+                    nodes[node.group].depends_on.append(dep_id)
+                elif target != sid and target not in lifted:
+                    lifted.append(target)
+            node.depends_on = lifted
+
+    def _group_member_ids(self, nodes: Dict[str, _StepNode]) -> Set[str]:
+        return {sid for sid, node in nodes.items() if node.group}
+
+    def _exec_group_run(self, params: Dict[str, Any]) -> tuple[bool, str]:
+        """Run one group's members in dependency order and report their combined status."""
+        group_id = self._current_step_id()
+        group_num = self._current_step_num()
+        try_number = self._current_try_number()
+        try:
+            nodes, ordered_ids, _ = self._build_step_graph()
+        except ValueError as exc:
+            return False, f"Group {group_id}: {exc}"
+        members = [sid for sid in ordered_ids if nodes[sid].group == group_id]
+        if not members:
+            return True, f"Group {group_id}: no steps"
+
+        self.log(f"▶ Group {group_id}: {len(members)} step(s)")
+        self._emit_event("group_start", step_id=group_id, members=members)
+        states: Dict[str, str] = {}
+        failed: List[str] = []
+        try:
+            for sid in members:
+                node = nodes[sid]
+                context = DependencyContext(
+                    states=states,
+                    running={},
+                    running_count=0,
+                    max_active_tasks=1,
+                    pool_limits=self._pool_limits,
+                    pool_usage={},
+                    now=time.time(),
+                )
+                if self._ti_dependency_evaluator.evaluate(node, context).met is not True:
+                    states[sid] = TaskInstanceState.SKIPPED
+                    self._emit_step_end(
+                        node,
+                        sid,
+                        state=TaskInstanceState.SKIPPED,
+                        success=False,
+                        duration_ms=0,
+                        output="skipped by trigger_rule",
+                        error="skipped by trigger_rule",
+                    )
+                    self.log(f"⏭ Step {node.step_num} ({sid}) skipped by trigger rule")
+                    continue
+
+                ok, _output, _duration_ms = self._run_single_step_with_retries(
+                    node,
+                    step_id=sid,
+                    track_checkpoint=False,
+                )
+                states[sid] = TaskInstanceState.SUCCESS if ok else TaskInstanceState.FAILED
+                if not ok and not node.continue_on_failure:
+                    failed.append(sid)
+        finally:
+            # Members reset the thread's step context; the group step still owns it.
+            self._set_active_step_context(step_id=group_id, step_num=group_num, try_number=try_number)
+
+        ok = not failed
+        counts = Counter(states.values())
+        self._emit_event(
+            "group_end",
+            step_id=group_id,
+            success=ok,
+            states=dict(states),
+            counts=dict(counts),
+            failed=failed,
+        )
+        summary = ", ".join(f"{count} {state}" for state, count in sorted(counts.items()))
+        return ok, f"Group {group_id} {'completed' if ok else 'failed'} ({summary})"
+
+
+__all__ = ["ExecutorGroupMixin"]
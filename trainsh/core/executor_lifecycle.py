@@ -0,0 +1,190 @@
+"""Job lifecycle bookkeeping for the DSL executor: audited dispatch, input waits, outcomes, energy, tracking, and tracing."""
+
+from __future__ import annotations
+
+from contextlib import contextmanager
+from datetime import datetime
+from pathlib import Path
+from typing import Any, Dict, Optional, Set, Tuple
+
+from ..config import load_config
+from ..services.audit_log import DESTRUCTIVE_PROVIDERS, record as record_audit
+from ..services.energy import EnergyMeter, read_power_draw
+from ..services.mlflow_tracking import MlflowAPIError, MlflowRunTracker, get_mlflow_client, tracking_settings
+from ..services.tracing import Tracer, export_spans
+from ..services.viewer_mode import PermissionDenied, is_viewer_mode
+from .execution_log import ExecutionLogReader, step_log_path
+from .executor_utils import _build_ssh_args
+from .exit_status import classify_exit, describe_exit, kernel_oom_stats, needs_kernel_log, read_kernel_log
+from .remote_cancel import remove_status_files
+
+
+class ExecutorLifecycleMixin:
+    def _execute_step(self, step) -> tuple[bool, str]:
+        """Execute a single step; destructive ones are audited and refused in viewer mode."""
+        step = self._coerce_step(step)
+        provider = (getattr(step, "provider", ""), getattr(step, "operation", ""))
+        if not getattr(step, "destructive", False) and provider not in DESTRUCTIVE_PROVIDERS:
+            return self._dispatch_step(step)
+        denied = is_viewer_mode(load_config())
+        if denied:
+            ok, output = False, str(PermissionDenied("step", f"Step {getattr(step, 'id', '')} is destructive and viewer mode is on."))
+        else:
+            ok, output = self._dispatch_step(step)
+        if provider[0]:
+            summary = f"{'.'.join(provider)} {(getattr(step, 'params', None) or {}).get('path', '')}"
+        else:
+            summary = getattr(step, "commands", "") or getattr(step, "raw", "")
+        record_audit(
+            "step",
+            f"{self.recipe.name}:{getattr(step, 'id', '')}",
+            host=str(getattr(step, "host", "") or (getattr(step, "params", None) or {}).get("storage", "") or "local"),
+            detail=summary,
+            status="denied" if denied else ("ok" if ok else "failed"),
+            source=f"job {self.ctx.job_id}",
+        )
+        return ok, output
+
+    @contextmanager
+    def _waiting_for_input(self, reason: str):
+        """Record the time a step spends blocked on an interactive prompt."""
+        started = datetime.now()
+        try:
+            yield
+        finally:
+            finished = datetime.now()
+            self._emit_event(
+                "step_wait",
+                step_num=self._current_step_num() or None,
+                step_id=self._current_step_id(),
+                try_number=self._current_try_number(),
+                kind="input",
+                reason=reason,
+                started_at=started.isoformat(),
+                finished_at=finished.isoformat(),
+                wait_ms=int((finished - started).total_seconds() * 1000),
+            )
+
+    def _record_outcome(self, success: bool) -> Tuple[str, Optional[int]]:
+        """Classify how the job ended and save the exit code and outcome on its state."""
+        cancelled = bool(self.job_state and self.job_state.status == "cancelled")
+        exit_code, host, stopped = (0, "", "") if success else (self._failed_exit or (None, "", ""))
+        kernel_log = ""
+        if not success and not cancelled and not stopped and host and needs_kernel_log(exit_code):
+            kernel_log = read_kernel_log(host, _build_ssh_args)
+        output = "" if success else self._step_ooms.get(self._failed_step, "")
+        outcome = classify_exit(exit_code, kernel_log, stopped="cancelled" if cancelled else stopped, output=output)
+        if not success and not cancelled:
+            self.log(f"Exit code {describe_exit(exit_code)}: {outcome}")
+        if outcome == "oom_killed":
+            self._emit_oom("host", host, kernel_oom_stats(kernel_log), step_id=self._failed_step)
+        if self.job_state:
+            self.job_state.exit_code = exit_code
+            self.job_state.outcome = outcome
+            if not success and not cancelled:
+                self.job_state.status = "failed"
+            self.state_manager.save(self.job_state)
+        return outcome, exit_code
+
+    def _remove_status_files(self) -> None:
+        """Delete the exit-status files this job's steps left on their hosts."""
+        while self._status_hosts:
+            remove_status_files(self._status_hosts.pop(), self.ctx.job_id, build_ssh_args=_build_ssh_args)
+
+    def _energy_hosts(self) -> Set[str]:
+        """Hosts the run has opened tmux sessions on or run commands on so far."""
+        with self._thread_lock:
+            hosts = {window.host for window in self.ctx.windows.values()}
+            hosts.update(host for _, host in self._step_exits.values())
+        return {host for host in hosts if host}
+
+    def _start_energy_meter(self) -> Optional[EnergyMeter]:
+        """Start sampling GPU power draw unless `energy.interval_secs` is 0."""
+        if not self.energy_interval:
+            return None
+        return EnergyMeter(
+            self._energy_hosts,
+            interval=self.energy_interval,
+            build_ssh_args=_build_ssh_args,
+            reader=read_power_draw,
+        ).start()
+
+    def _stop_energy_meter(self, meter: Optional[EnergyMeter]) -> Dict[str, Any]:
+        """Stop the meter and log the run's approximate energy use."""
+        energy = meter.stop() if meter else {}
+        if energy:
+            self.log(f"Energy: {energy['kwh']:.3f} kWh (approx., from nvidia-smi power draw)")
+        return energy
+
+    def _start_tracking(self) -> Optional[MlflowRunTracker]:
+        """Register the run with MLflow when `tracking` config or `recipe.tracking(...)` names a server."""
+        settings = tracking_settings(load_config(), getattr(self.recipe, "tracking_options", None))
+        if not settings["enabled"]:
+            return None
+        tracker = MlflowRunTracker(get_mlflow_client(settings["mlflow_uri"]), settings, log=self.log)
+        try:
+            run_id = tracker.start(self.recipe.name, self.ctx.job_id, dict(self.ctx.variables), dict(self.recipe.hosts))
+        except (MlflowAPIError, KeyError, ValueError) as exc:
+            self.log(f"⚠ MLflow tracking disabled for this run: {exc}")
+            return None
+        self.log(f"MLflow run: {run_id} ({settings['mlflow_uri']})")
+        return tracker
+
+    def _run_output_text(self) -> str:
+        """All step output of this run: each step's log file, else the output saved on its `step_end` event."""
+        if not self.logger:
+            return ""
+        self.logger.flush()
+        outputs: Dict[int, str] = {}
+        for entry in ExecutionLogReader(str(self.logger.store.root)).read_execution(self.ctx.job_id):
+            if entry.get("event") == "step_end" and entry.get("step_num") is not None:
+                outputs[int(entry["step_num"])] = str(entry.get("output") or entry.get("error") or "")
+        for step_num in sorted(outputs):
+            path = step_log_path(self.logger.store.root, self.ctx.job_id, step_num)
+            if path.exists():
+                outputs[step_num] = path.read_text(encoding="utf-8", errors="replace")
+        return "\n".join(outputs[step_num] for step_num in sorted(outputs))
+
+    def _finish_tracking(self, tracker: Optional[MlflowRunTracker], success: bool, outcome: str) -> Dict[str, Any]:
+        """Send parsed step-output metrics and artifacts, then close the MLflow run."""
+        if tracker is None:
+            return {}
+        text = self._run_output_text()
+        base = Path(self.recipe_path).parent if self.recipe_path else None
+        try:
+            result = tracker.finish(success=success, outcome=outcome, log_text=text, base_dir=base)
+        except (MlflowAPIError, KeyError, ValueError) as exc:
+            self.log(f"⚠ MLflow run {tracker.run_id} not closed: {exc}")
+            return {"run_id": tracker.run_id}
+        self._emit_event("tracking", provider="mlflow", **result)
+        return result
+
+    def _start_trace(self) -> None:
+        """Open the run's root span when `tracing.otlp_endpoint` (or OTEL_EXPORTER_OTLP_ENDPOINT) is set."""
+        if not self.tracing["enabled"]:
+            return
+        self.tracer = Tracer(
+            self.tracing["service_name"],
+            resource={"trainsh.recipe": self.recipe.name, "trainsh.job_id": self.ctx.job_id},
+        )
+        self._trace_root = self.tracer.start_span(
+            f"recipe {self.recipe.name}",
+            **{"trainsh.job_id": self.ctx.job_id, "trainsh.executor": self.executor_name, "trainsh.steps": len(self.recipe.steps)},
+        )
+
+    def _finish_trace(self, success: bool, outcome: str) -> Dict[str, Any]:
+        """Close the root span and send the run's spans to the OTLP collector."""
+        if self.tracer is None or self._trace_root is None:
+            return {}
+        self._trace_root.set("trainsh.outcome", outcome)
+        if not success:
+            self._trace_root.fail(outcome)
+        self.tracer.end_span(self._trace_root)
+        info: Dict[str, Any] = {"trace_id": self.tracer.trace_id, "spans": len(self.tracer.spans)}
+        try:
+            export_spans(self.tracer, self.tracing["otlp_endpoint"], headers=self.tracing["headers"])
+        except RuntimeError as exc:
+            self.log(f"⚠ Trace {self.tracer.trace_id} not exported: {exc}")
+            return {**info, "exported": False}
+        self.log(f"Trace: {self.tracer.trace_id} ({info['spans']} spans)")
+        return {**info, "exported": True}
@@ -15,19 +15,25 @@ import shlex
 import socket
 import queue
 from collections import defaultdict
-from typing import Optional, Dict, List, Callable, Sequence, Any, Tuple
+from typing import Optional, Dict, List, Callable, Sequence, Any, Set, Tuple
 from datetime import datetime
 
 from .executor_dependencies import _DeferredEvent
+from .executor_lifecycle import ExecutorLifecycleMixin
+from .executor_resume import apply_host_overrides, restore_resume_state
 from .executor_runtime import _StepNode, ExecutionContext, WindowInfo
 from .executor_scheduler import ExecutorSchedulingMixin
 from .executor_support import ExecutorSupportMixin
 from .provider_mixin import ExecutorProviderMixin
 
 from ..config import load_config
-from ..constants import RECIPE_FILE_EXTENSION
+from ..constants import RECIPE_FILE_EXTENSIONS
 from ..constants import CONFIG_DIR, RUNTIME_STATE_DIR
+from ..services.energy import energy_interval
+from ..services.tracing import child_span, tracing_settings
+from ..services.session_env import redact
 from .recipe_models import RecipeModel, RecipeStepModel, StepType
+from .recipe_variables import find_unresolved_variables
 from .bridge_exec import BridgeExecutionHelper
 from .executor_execute import ExecuteHelper
 from .executor_tmux import TmuxControlHelper
@@ -36,7 +42,9 @@ from .executor_runpod import RunpodControlHelper
 from .executor_vast import VastControlHelper
 from .executor_wait import WaitHelper
 from .execution_log import ExecutionLogger
+from .local_policy import is_trusted, policy_settings
 from .local_tmux import LocalTmuxClient
+from .remote_cancel import status_file_for
 from .remote_tmux import RemoteTmuxClient
 from .secrets import get_secrets_manager
 from .models import Host, Storage, StorageType
@@ -46,10 +54,7 @@ from .job_state import (
     JobStateManager,
     generate_job_id,
 )
-from .tmux_naming import (
-    parse_window_session_index,
-    get_window_session_name,
-)
+from .tmux_naming import get_window_session_name
 from .executor_utils import (
     _build_ssh_args,
     _format_duration,
@@ -67,7 +72,12 @@ from .runtime_store import to_jsonable
 from .triggerer import Triggerer
 
 
-class DSLExecutor(ExecutorSchedulingMixin, ExecutorProviderMixin, ExecutorSupportMixin):
+class DSLExecutor(
+    ExecutorSchedulingMixin,
+    ExecutorProviderMixin,
+    ExecutorSupportMixin,
+    ExecutorLifecycleMixin,
+):
     """
     Executes DSL recipes step by step.
 
@@ -104,6 +114,7 @@ class DSLExecutor(ExecutorSchedulingMixin, ExecutorProviderMixin, ExecutorSuppor
         executor_name: str = "sequential",
         executor_kwargs: Optional[Dict[str, Any]] = None,
         run_type: str = "manual",
+        runtime_state: Optional[str] = None,
     ):
         """
         Initialize executor.
@@ -115,6 +126,7 @@ class DSLExecutor(ExecutorSchedulingMixin, ExecutorProviderMixin, ExecutorSuppor
             recipe_path: Optional path to recipe file (for state persistence)
             is_resuming: Whether this is a resume execution (affects sync strategy)
             bridge_session: Optional detached bridge session name to reuse on resume
+            runtime_state: Directory for checkpoints, runs and xcom (defaults to the profile's)
         """
         self.recipe = recipe
         self.log_callback = log_callback or print
@@ -123,7 +135,8 @@ class DSLExecutor(ExecutorSchedulingMixin, ExecutorProviderMixin, ExecutorSuppor
         self.allow_host_execute = allow_host_execute
 
         # Job state management
-        self.state_manager = JobStateManager(str(RUNTIME_STATE_DIR))
+        self.runtime_state = str(runtime_state or RUNTIME_STATE_DIR)
+        self.state_manager = JobStateManager(self.runtime_state)
         self.job_state: Optional[JobState] = None
         from ..runtime import _coerce_max_workers, normalize_executor_name
 
@@ -136,11 +149,24 @@ class DSLExecutor(ExecutorSchedulingMixin, ExecutorProviderMixin, ExecutorSuppor
         self._ti_dependency_evaluator = TIDependencyEvaluator()
         self._triggerer = Triggerer()
         self._pool_manager = RuntimeStatePoolManager(
-            str(RUNTIME_STATE_DIR),
+            self.runtime_state,
             default_slots=self._pool_limits,
         )
         self._pool_manager.sync_slots(self._pool_limits)
         self._deferred_events: Dict[str, _DeferredEvent] = {}
+        self._step_exit_codes: Dict[str, int] = {}
+        # Each step's last exit code and host, and why train stopped a step itself.
+        self._step_exits: Dict[str, Tuple[int, str]] = {}
+        self._step_stops: Dict[str, str] = {}
+        # Pane tail of each step's failed tmux command, for `retry_on_output_regex`.
+        self._step_outputs: Dict[str, str] = {}
+        # (exit code, host, stop reason) of the step that failed the run.
+        self._failed_exit: Optional[Tuple[Optional[int], str, str]] = None
+        self._failed_step = ""
+        # CUDA out-of-memory line of each step whose last attempt hit one.
+        self._step_ooms: Dict[str, str] = {}
+        # Hosts holding this job's step status files, removed when the job ends.
+        self._status_hosts: Set[str] = set()
         self._step_runtime_ctx = threading.local()
 
         # Generate or use provided job ID
@@ -180,6 +206,34 @@ class DSLExecutor(ExecutorSchedulingMixin, ExecutorProviderMixin, ExecutorSuppor
             log_callback=self.log_callback,
         )
         self.prefer_bridge_exec = bool(tmux_cfg.get("prefer_bridge_exec", True))
+        strict_default = config.get("recipe", {}).get("strict_variables", False)
+        self.strict_variables = self._normalize_bool(
+            self.executor_kwargs.get("strict_variables", strict_default),
+            default=False,
+        )
+        self.safe_variables = self._normalize_bool(
+            self.executor_kwargs.get("safe_variables", config.get("recipe", {}).get("safe_variables", False)),
+            default=False,
+        )
+        # Recipe-level ${NAME}s nothing defines; strict mode fails steps that still use them.
+        self._undefined_variables = find_unresolved_variables(recipe, self.ctx.variables)
+        self.default_timeouts = dict(config.get("timeouts", {}) or {})
+        max_runtime = self.executor_kwargs.get(
+            "max_runtime", self.default_timeouts.get("execution_secs", 0)
+        )
+        try:
+            self.max_runtime_secs = max(0, self._parse_duration(str(max_runtime or 0)))
+        except ValueError:
+            self.max_runtime_secs = 0
+        self.preflight_mode = self._preflight_mode(config.get("recipe", {}))
+        self.output_settings = self._output_settings(config)
+        self.gpu_guard_mode, self.gpu_guard_min_memory_mb = self._gpu_guard_settings(config)
+        self.energy_interval = energy_interval(config)
+        self.tracing = tracing_settings(config)
+        self.tracer = None
+        self._trace_root = None
+        self.local_policy = policy_settings(config)
+        self.recipe_trusted = is_trusted(self.recipe_path)
         bridge_remote_status = str(tmux_cfg.get("bridge_remote_status", "off")).lower()
         if bridge_remote_status not in {"keep", "off", "bottom"}:
             bridge_remote_status = "off"
@@ -192,6 +246,9 @@ class DSLExecutor(ExecutorSchedulingMixin, ExecutorProviderMixin, ExecutorSuppor
             log=self.log,
             log_detail=self._log_detail,
             format_duration=_format_duration,
+            get_status_file=lambda: status_file_for(self.ctx.job_id, self._current_step_id() or self._current_step_num()),
+            note_exit_code=self._note_exit_code,
+            note_output=self._note_step_output,
         )
         self.tmux_control = TmuxControlHelper(self, WindowInfo)
         self.transfer_helper = TransferHelper(self, _resolve_vast_host, _resolve_runpod_host, _host_from_ssh_spec)
@@ -259,10 +316,13 @@ class DSLExecutor(ExecutorSchedulingMixin, ExecutorProviderMixin, ExecutorSuppor
 
         # Collect all windows (including local hosts)
         hosts = {}
+        host_refs = {}
         window_sessions = {}
         for name, window in self.ctx.windows.items():
             if window.host:
                 hosts[name] = window.host
+                if window.host in self.ctx.host_refs:
+                    host_refs[name] = self.ctx.host_refs[window.host]
             if window.remote_session:
                 window_sessions[name] = window.remote_session
 
@@ -281,6 +341,7 @@ class DSLExecutor(ExecutorSchedulingMixin, ExecutorProviderMixin, ExecutorSuppor
             status=status,
             variables=dict(self.ctx.variables),
             hosts=hosts,
+            host_refs=host_refs,
             storages=self._storage_snapshot(),
             window_sessions=window_sessions,
             next_window_index=self.ctx.next_window_index,
@@ -289,6 +350,11 @@ class DSLExecutor(ExecutorSchedulingMixin, ExecutorProviderMixin, ExecutorSuppor
             vast_start_time=vast_start_time,
             runpod_pod_id=runpod_pod_id,
             runpod_start_time=runpod_start_time,
+            inflight_steps=dict(self.ctx.inflight),
+            gpu_assignments=dict(self.ctx.gpu_assignments),
+            pid=os.getpid(),
+            parent_job_id=str(self.executor_kwargs.get("parent_job_id", "") or ""),
+            run_type=self.run_type,
         )
         self.job_state.tmux_session = self.job_state.bridge_session or next(
             (w.remote_session for w in self.ctx.windows.values() if w.remote_session),
@@ -296,6 +362,17 @@ class DSLExecutor(ExecutorSchedulingMixin, ExecutorProviderMixin, ExecutorSuppor
         )
         self.state_manager.save(self.job_state)
 
+    def _track_inflight(self, step_num: int, info: Dict[str, str]) -> None:
+        """Persist a running tmux command so a later resume can re-attach to it."""
+        with self._thread_lock:
+            self.ctx.inflight[str(step_num)] = dict(info)
+            current = self.job_state.current_step if self.job_state else max(0, int(step_num) - 1)
+            self._save_checkpoint(current)
+
+    def _clear_inflight(self, step_num: int) -> None:
+        with self._thread_lock:
+            self.ctx.inflight.pop(str(step_num), None)
+
     def allocate_window_session_name(self) -> str:
         """Allocate next tmux session name for tmux.open in this job."""
         index = self.ctx.next_window_index
@@ -315,6 +392,9 @@ class DSLExecutor(ExecutorSchedulingMixin, ExecutorProviderMixin, ExecutorSuppor
     def log(self, msg: str) -> None:
         """Log a message."""
         timestamp = datetime.now().strftime("%H:%M:%S")
+        redactions = getattr(self.logger, "redactions", None) if self.logger else None
+        if isinstance(redactions, set) and redactions:
+            msg = redact(msg, redactions)
         with self._thread_lock:
             self.log_callback(f"[{timestamp}] {msg}")
 
@@ -390,7 +470,8 @@ class DSLExecutor(ExecutorSchedulingMixin, ExecutorProviderMixin, ExecutorSuppor
         self.logger = ExecutionLogger(
             job_id=self.ctx.job_id,
             recipe_name=self.recipe.name,
-            db_path=str(RUNTIME_STATE_DIR),
+            db_path=self.runtime_state,
+            **self.output_settings,
         )
         self.logger.start(
             self.recipe.name,
@@ -404,29 +485,54 @@ class DSLExecutor(ExecutorSchedulingMixin, ExecutorProviderMixin, ExecutorSuppor
             variables=dict(self.ctx.variables),
             hosts=dict(self.recipe.hosts),
             storages=self._storage_snapshot(),
+            parent_job_id=str(self.executor_kwargs.get("parent_job_id", "") or ""),
+            parent_step_id=str(self.executor_kwargs.get("parent_step_id", "") or ""),
         )
+        missing = self._undefined_variables
+        if missing:
+            self.log(f"⚠ Undefined variables: {', '.join(missing)}")
+            self._emit_event("variables_required", names=missing, strict=self.strict_variables)
 
         from ..runtime import PARALLEL_EXECUTOR_ALIASES
 
         parallel_executors = PARALLEL_EXECUTOR_ALIASES
+        success = False
+        self._start_trace()
+        with child_span(self.tracer, self._trace_root, "preflight"):
+            preflight_ok = self._run_preflight()
+        energy_meter = self._start_energy_meter() if preflight_ok else None
+        tracker = self._start_tracking() if preflight_ok else None
         try:
-            if self.executor_name in parallel_executors:
+            if preflight_ok and self.executor_name in parallel_executors:
                 success = self._execute_with_dependencies(resume_from=resume_from)
-            else:
+            elif preflight_ok:
                 success = self._execute_sequential(resume_from=resume_from)
         finally:
+            # Also reached on Ctrl+C, so instances still get stopped.
+            if preflight_ok:
+                success = self._run_cleanup_steps(success)
             self._pool_manager.close()
+            energy = self._stop_energy_meter(energy_meter)
 
         # Finalize
         total_ms = int((datetime.now() - self.ctx.start_time).total_seconds() * 1000)
         if self.logger:
             self.logger.end(success, total_ms, dict(self.ctx.variables))
+        outcome, exit_code = self._record_outcome(success)
+        self._remove_status_files()
+        tracking = self._finish_tracking(tracker, success, outcome)
+        trace = self._finish_trace(success, outcome)
         self._emit_event(
             "execution_end",
             success=success,
+            outcome=outcome,
+            exit_code=exit_code,
             total_ms=total_ms,
             total_steps=len(self.recipe.steps),
             final_variables=dict(self.ctx.variables),
+            energy=energy,
+            tracking=tracking,
+            trace=trace,
         )
 
         if success:
@@ -436,6 +542,7 @@ class DSLExecutor(ExecutorSchedulingMixin, ExecutorProviderMixin, ExecutorSuppor
             status = "failed"
 
         self.log(f"Recipe {status} in {total_ms}ms")
+        self._run_completion_hooks(success, exit_code)
 
         return success
 
@@ -479,10 +586,7 @@ class DSLExecutor(ExecutorSchedulingMixin, ExecutorProviderMixin, ExecutorSuppor
     def __del__(self):
         self.close()
 
-    def _execute_step(self, step) -> tuple[bool, str]:
-        """Execute a single step."""
-        step = self._coerce_step(step)
-
+    def _dispatch_step(self, step) -> tuple[bool, str]:
         if isinstance(step, ProviderStep):
             return self._exec_provider(step)
 
@@ -555,6 +659,7 @@ def run_recipe(
     callbacks: Optional[Sequence[str]] = None,
     callback_sinks: Optional[Sequence] = None,
     run_type: str = "manual",
+    recipe: Any = None,
 ) -> bool:
     """
     Load and execute a recipe file.
@@ -569,6 +674,7 @@ def run_recipe(
         executor_kwargs: Executor-specific options (e.g., {"max_workers": 4})
         callbacks: Callback sink names (`console`, `jsonl`)
         run_type: Execution type (`manual`/`scheduled`)
+        recipe: Recipe already loaded from `path`, reused instead of loading it again
 
     Returns:
         True if successful
@@ -577,14 +683,16 @@ def run_recipe(
     from ..runtime import JsonlCallbackSink
 
     path = os.path.abspath(os.path.expanduser(path))
-    if not path.endswith(RECIPE_FILE_EXTENSION):
-        raise ValueError(f"run_recipe only supports Python recipe source files ({RECIPE_FILE_EXTENSION})")
+    if not path.endswith(RECIPE_FILE_EXTENSIONS):
+        raise ValueError(f"run_recipe only supports recipe files ({', '.join(RECIPE_FILE_EXTENSIONS)})")
     if resume and host_overrides:
         raise ValueError("Host overrides are not supported when resuming a Python recipe")
 
-    from ..pyrecipe import load_python_recipe
+    if recipe is None:
+        from ..pyrecipe import load_python_recipe
+        from ..pyrecipe.yaml_recipe import is_yaml_recipe, load_yaml_recipe
 
-    recipe = load_python_recipe(path)
+        recipe = load_yaml_recipe(path) if is_yaml_recipe(path) else load_python_recipe(path)
     merged_kwargs = dict(recipe.executor_kwargs or {})
     merged_kwargs.update(executor_kwargs or {})
     executor_kwargs = merged_kwargs
@@ -621,21 +729,18 @@ def run_recipe(
 
     if var_overrides:
         recipe.variables.update(var_overrides)
-
     if host_overrides:
-        for name, value in host_overrides.items():
-            if value.startswith("vast:"):
-                instance_id = value[5:]
-                recipe.hosts[name] = f"vast:{instance_id}"
-                if not var_overrides or "VAST_ID" not in var_overrides:
-                    recipe.variables["VAST_ID"] = instance_id
-            elif value.startswith("runpod:"):
-                pod_id = value[7:]
-                recipe.hosts[name] = f"runpod:{pod_id}"
-                if not var_overrides or "RUNPOD_ID" not in var_overrides:
-                    recipe.variables["RUNPOD_ID"] = pod_id
-            else:
-                recipe.hosts[name] = value
+        apply_host_overrides(recipe, host_overrides, var_overrides)
+
+    try:
+        from ..services.log_retention import apply_log_policy
+
+        cleanup = apply_log_policy(root=RUNTIME_STATE_DIR, config=load_config())
+        if cleanup["pruned"] and log_callback:
+            log_callback(f"Pruned {len(cleanup['pruned'])} old run log(s)")
+    except OSError as exc:
+        if log_callback:
+            log_callback(f"Log cleanup skipped: {exc}")
 
     sinks: List = [JsonlCallbackSink(str(RUNTIME_STATE_DIR))]
     sinks.extend(list(callback_sinks or []))
@@ -674,34 +779,7 @@ def run_recipe(
     if resume and job_id and saved_state:
         resume_state = state_manager.load(job_id)
         if resume_state:
-            for name, host_spec in resume_state.hosts.items():
-                remote_session = resume_state.window_sessions.get(name, "")
-                if not remote_session:
-                    idx = len(executor.ctx.windows)
-                    remote_session = get_window_session_name(recipe.name, job_id, idx)
-                executor.ctx.windows[name] = WindowInfo(
-                    name=name,
-                    host=host_spec,
-                    remote_session=remote_session,
-                )
-
-            max_idx = -1
-            for window in executor.ctx.windows.values():
-                if not window.remote_session:
-                    continue
-                parsed = parse_window_session_index(window.remote_session, recipe.name, job_id)
-                if parsed is not None and parsed > max_idx:
-                    max_idx = parsed
-            persisted_next = int(getattr(resume_state, "next_window_index", 0) or 0)
-            executor.ctx.next_window_index = max(
-                executor.ctx.next_window_index,
-                persisted_next,
-                max_idx + 1,
-                len(executor.ctx.windows),
-            )
-
-            if executor.ctx.windows:
-                executor.restore_tmux_bridge()
+            restore_resume_state(executor, resume_state, recipe, job_id, log_callback=log_callback)
 
     try:
         return get_executor(executor_name, **executor_kwargs).execute(
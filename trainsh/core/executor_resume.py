@@ -0,0 +1,85 @@
+"""Recipe overrides and resumed-job state restoration for `run_recipe`."""
+
+from __future__ import annotations
+
+from typing import Any, Callable, Dict, Optional
+
+from .executor_runtime import WindowInfo
+from .executor_utils import _resolve_vast_host
+from .tmux_naming import get_window_session_name, parse_window_session_index
+
+
+def apply_host_overrides(recipe: Any, host_overrides: Dict[str, str], var_overrides: Optional[Dict[str, str]] = None) -> None:
+    """Point recipe hosts at CLI overrides; `vast:`/`runpod:` ones also set VAST_ID/RUNPOD_ID."""
+    for name, value in host_overrides.items():
+        if value.startswith("vast:"):
+            instance_id = value[5:]
+            recipe.hosts[name] = f"vast:{instance_id}"
+            if not var_overrides or "VAST_ID" not in var_overrides:
+                recipe.variables["VAST_ID"] = instance_id
+        elif value.startswith("runpod:"):
+            pod_id = value[7:]
+            recipe.hosts[name] = f"runpod:{pod_id}"
+            if not var_overrides or "RUNPOD_ID" not in var_overrides:
+                recipe.variables["RUNPOD_ID"] = pod_id
+        else:
+            recipe.hosts[name] = value
+
+
+def restore_resume_state(
+    executor: Any,
+    resume_state: Any,
+    recipe: Any,
+    job_id: str,
+    *,
+    log_callback: Optional[Callable[[str], None]] = None,
+) -> None:
+    """Rebuild windows, in-flight steps, and GPU assignments from a saved job."""
+    host_refs = dict(getattr(resume_state, "host_refs", {}) or {})
+    for name, host_spec in resume_state.hosts.items():
+        host_ref = str(host_refs.get(name, "") or "")
+        if host_ref.startswith("vast:"):
+            fresh = _resolve_vast_host(host_ref[5:])
+            if fresh != host_spec and not fresh.startswith("vast-"):
+                if log_callback:
+                    log_callback(f"Vast.ai instance {host_ref[5:]} moved: {host_spec} -> {fresh}")
+                if recipe.hosts.get(name) == host_spec:
+                    recipe.hosts[name] = fresh
+                host_spec = fresh
+            executor.ctx.host_refs[host_spec] = host_ref
+        remote_session = resume_state.window_sessions.get(name, "")
+        if not remote_session:
+            idx = len(executor.ctx.windows)
+            remote_session = get_window_session_name(recipe.name, job_id, idx)
+        executor.ctx.windows[name] = WindowInfo(
+            name=name,
+            host=host_spec,
+            remote_session=remote_session,
+        )
+    inflight = dict(getattr(resume_state, "inflight_steps", {}) or {})
+    if inflight:
+        executor.ctx.inflight.update(inflight)
+    gpu_assignments = dict(getattr(resume_state, "gpu_assignments", {}) or {})
+    if gpu_assignments:
+        executor.ctx.gpu_assignments.update(gpu_assignments)
+
+    max_idx = -1
+    for window in executor.ctx.windows.values():
+        if not window.remote_session:
+            continue
+        parsed = parse_window_session_index(window.remote_session, recipe.name, job_id)
+        if parsed is not None and parsed > max_idx:
+            max_idx = parsed
+    persisted_next = int(getattr(resume_state, "next_window_index", 0) or 0)
+    executor.ctx.next_window_index = max(
+        executor.ctx.next_window_index,
+        persisted_next,
+        max_idx + 1,
+        len(executor.ctx.windows),
+    )
+
+    if executor.ctx.windows:
+        executor.restore_tmux_bridge()
+
+
+__all__ = ["apply_host_overrides", "restore_resume_state"]
@@ -4,6 +4,7 @@
 from __future__ import annotations
 
 import time
+from contextlib import nullcontext
 from datetime import datetime
 from typing import Any, Callable, List, Optional
 
@@ -21,6 +22,12 @@ class RunpodControlHelper:
         self.build_ssh_args = build_ssh_args
         self.format_duration = format_duration
 
+    def _prompt(self, message: str, reason: str) -> str:
+        """`input()` that records the time the step spends blocked on the user."""
+        waiting = getattr(self.executor, "_waiting_for_input", None)
+        with waiting(reason) if waiting else nullcontext():
+            return input(message)
+
     def _resolve_pod_id(self, value: Any) -> Optional[str]:
         """Resolve a RunPod Pod ID from a direct id or a recipe host alias."""
         text = str(value or "").strip()
@@ -316,7 +323,7 @@ class RunpodControlHelper:
             print("-" * 96)
 
             try:
-                choice = input(f"Enter number (1-{len(pods)}) or Pod ID: ").strip()
+                choice = self._prompt(f"Enter number (1-{len(pods)}) or Pod ID: ", "runpod_pick").strip()
             except (EOFError, KeyboardInterrupt):
                 return False, "Selection cancelled"
 
@@ -4,7 +4,7 @@ from __future__ import annotations
 
 from dataclasses import dataclass, field
 from datetime import datetime
-from typing import Callable, Dict, List, Optional
+from typing import Any, Callable, Dict, List, Optional
 
 from .recipe_models import RecipeModel
 
@@ -30,6 +30,12 @@ class ExecutionContext:
     next_window_index: int = 0
     start_time: Optional[datetime] = None
     log_callback: Optional[Callable[[str], None]] = None
+    # step_num -> {host, remote_session, signal} for tmux commands still running
+    inflight: Dict[str, Dict[str, str]] = field(default_factory=dict)
+    # window name -> {host, gpus} exported as CUDA_VISIBLE_DEVICES in that session
+    gpu_assignments: Dict[str, Dict[str, Any]] = field(default_factory=dict)
+    # resolved SSH spec -> vast:<id> it came from, so resume can follow a restarted instance
+    host_refs: Dict[str, str] = field(default_factory=dict)
 
 
 @dataclass
@@ -52,6 +58,12 @@ class _StepNode:
     on_failure: list = field(default_factory=list)
     max_active_tis_per_dagrun: Optional[int] = None
     deferrable: bool = False
+    done_check: str = ""
+    retry_on_exit_codes: List[int] = field(default_factory=list)
+    retry_on_output_regex: str = ""
+    on_failure_run: str = ""
+    always_run: bool = False
+    group: str = ""
 
 
 @dataclass
@@ -2,9 +2,16 @@
 
 from __future__ import annotations
 
+from .executor_callbacks import ExecutorStepCallbackMixin
 from .executor_dependencies import ExecutorDependencyMixin
+from .executor_groups import ExecutorGroupMixin
 from .executor_steps import ExecutorStepRuntimeMixin
 
 
-class ExecutorSchedulingMixin(ExecutorDependencyMixin, ExecutorStepRuntimeMixin):
+class ExecutorSchedulingMixin(
+    ExecutorDependencyMixin,
+    ExecutorGroupMixin,
+    ExecutorStepRuntimeMixin,
+    ExecutorStepCallbackMixin,
+):
     pass
@@ -1,14 +1,15 @@
-"""Step metadata and callback helpers for the DSL executor."""
+"""Step metadata, retry, and timeout helpers for the DSL executor."""
 
 from __future__ import annotations
 
 import concurrent.futures
 import time
-from collections import defaultdict
 from datetime import datetime
 from typing import Any, Callable, Dict, List, Optional, Tuple
 
 from ..pyrecipe.models import ProviderStep
+from ..services.tracing import child_span
+from .errors import error_info
 from .executor_runtime import _StepNode
 from .recipe_models import RecipeStepModel
 from .task_state import FINISHED_STATES, TaskInstanceState
@@ -186,7 +187,15 @@ class ExecutorStepRuntimeMixin:
             return delay
         return int(delay * (backoff ** (attempt - 1)))
 
-    def _emit_step_start(self, node: _StepNode, step_id: str, *, try_number: int = 1) -> None:
+    def _emit_step_start(
+        self,
+        node: _StepNode,
+        step_id: str,
+        *,
+        try_number: int = 1,
+        queued_ms: int = 0,
+        deferred: bool = False,
+    ) -> None:
         step = node.step
         step_details = self._build_step_details(step)
         self._emit_event(
@@ -197,6 +206,8 @@ class ExecutorStepRuntimeMixin:
             raw=step.raw,
             step_type=getattr(step.type, "value", str(step.type)),
             details=step_details,
+            queued_ms=max(0, int(queued_ms)),
+            deferred=deferred,
         )
         if self.logger:
             with self._thread_lock:
@@ -226,6 +237,31 @@ class ExecutorStepRuntimeMixin:
             duration_ms=duration_ms,
             output=output,
             error=error,
+            **({} if success else self._failure_fields(step_id, error or output)),
+        )
+
+    def _emit_step_retry(
+        self,
+        node: _StepNode,
+        step_id: str,
+        *,
+        try_number: int,
+        duration_ms: int,
+        delay_secs: int,
+        output: str,
+    ) -> None:
+        """Close a failed attempt that will be retried after `delay_secs`."""
+        self._emit_event(
+            "step_retry",
+            step_num=node.step_num,
+            step_id=step_id,
+            try_number=max(1, int(try_number)),
+            raw=node.step.raw,
+            state="up_for_retry",
+            duration_ms=duration_ms,
+            retry_delay_ms=max(0, int(delay_secs)) * 1000,
+            error=output[-2000:] if output else "",
+            **error_info(output),
         )
 
     def _build_step_details(self, step: object) -> Dict[str, object]:
@@ -258,6 +294,13 @@ class ExecutorStepRuntimeMixin:
             "pool": getattr(step, "pool", "default"),
             "retry_exponential_backoff": getattr(step, "retry_exponential_backoff", 0.0),
             "deferrable": getattr(step, "deferrable", False),
+            "done_check": getattr(step, "done_check", ""),
+            "retry_on_exit_codes": list(getattr(step, "retry_on_exit_codes", None) or []),
+            "retry_on_output_regex": getattr(step, "retry_on_output_regex", ""),
+            "on_failure_run": getattr(step, "on_failure_run", ""),
+            "always_run": bool(getattr(step, "always_run", False)),
+            "destructive": bool(getattr(step, "destructive", False)),
+            "group": getattr(step, "group", ""),
         }
 
     def _build_defer_check(
@@ -332,8 +375,12 @@ class ExecutorStepRuntimeMixin:
         track_checkpoint: bool = True,
         execution_timeout: int = 0,
         emit_events: bool = True,
+        done_check: str = "",
     ) -> Tuple[str, str, int]:
-        """Execute one step and return (state, output, duration_ms)."""
+        """Execute one step and return (state, output, duration_ms).
+
+        A passing ``done_check`` returns SKIPPED without running the step.
+        """
         step = self._coerce_step(step)
         step_id = step_id or ""
         step_num = int(step_num)
@@ -357,6 +404,27 @@ class ExecutorStepRuntimeMixin:
             self._save_checkpoint(step_num - 1)
 
         start = datetime.now()
+        if done_check:
+            done, detail = self._step_already_done(step, done_check)
+            if done:
+                duration_ms = int((datetime.now() - start).total_seconds() * 1000)
+                output = f"already done ({detail})"
+                if emit_events:
+                    self._emit_event(
+                        "step_end",
+                        step_num=step_num,
+                        step_id=step_id,
+                        try_number=max(1, int(try_number)),
+                        raw=step.raw,
+                        step_type=getattr(step.type, "value", ""),
+                        state=TaskInstanceState.SKIPPED,
+                        success=True,
+                        duration_ms=duration_ms,
+                        output=output,
+                        error="",
+                    )
+                return TaskInstanceState.SKIPPED, output, duration_ms
+
         try:
             timeout_secs = max(0, int(execution_timeout))
             ok, output = self._execute_step_with_timeout(
@@ -394,7 +462,10 @@ class ExecutorStepRuntimeMixin:
                     duration_ms=duration_ms,
                     output=output,
                     error="" if ok else output,
+                    **({} if ok else self._step_failure_info(step_id, step_num, output)),
                 )
+            elif not ok:
+                self._step_failure_info(step_id, step_num, output)
             return state, output, duration_ms
 
         except Exception as e:
@@ -417,9 +488,20 @@ class ExecutorStepRuntimeMixin:
                     duration_ms=0,
                     output=error_detail,
                     error=str(e),
+                    **error_info(e),
                 )
             return TaskInstanceState.FAILED, error_detail, 0
 
+    def _step_already_done(self, step: object, command: str) -> Tuple[bool, str]:
+        """Run a step's ``done_check`` on the step host; exit 0 means already done."""
+        name = str(getattr(step, "host", "") or "").strip()
+        if not name and isinstance(step, ProviderStep):
+            name = str((step.params or {}).get("host", "") or "").strip()
+        window = self.ctx.windows.get(name) if name else None
+        host = window.host if window else name
+        ok, _ = self._exec_provider_shell({"command": command, "host": host or "local", "timeout": 60}, record_exit=False)
+        return ok, f"done_check: {self._interpolate(command)}"
+
     def _run_single_step(
         self,
         step_num: int,
@@ -429,8 +511,9 @@ class ExecutorStepRuntimeMixin:
         try_number: int = 1,
         track_checkpoint: bool = True,
         execution_timeout: int = 0,
+        done_check: str = "",
     ) -> Tuple[bool, str, int]:
-        """Execute one step and return (ok, output, duration_ms)."""
+        """Execute one step and return (ok, output, duration_ms); a passing ``done_check`` counts as ok."""
         state, output, duration_ms = self._run_single_step_with_state(
             step_num,
             step,
@@ -439,8 +522,9 @@ class ExecutorStepRuntimeMixin:
             track_checkpoint=track_checkpoint,
             execution_timeout=execution_timeout,
             emit_events=True,
+            done_check=done_check,
         )
-        return state == TaskInstanceState.SUCCESS, output, duration_ms
+        return state in (TaskInstanceState.SUCCESS, TaskInstanceState.SKIPPED), output, duration_ms
 
     def _execute_step_with_timeout(
         self,
@@ -458,7 +542,16 @@ class ExecutorStepRuntimeMixin:
                 try_number=try_number,
             )
             try:
-                return self._execute_step(step)
+                with child_span(
+                    getattr(self, "tracer", None),
+                    getattr(self, "_trace_root", None),
+                    f"step {step_id or step_num}",
+                    **{"trainsh.step_num": step_num, "trainsh.try_number": try_number},
+                ) as span:
+                    ok, output = self._execute_step(step)
+                    if not ok:
+                        span.fail(str(output or "")[-200:])
+                    return ok, output
             finally:
                 self._clear_active_step_context()
 
@@ -470,6 +563,9 @@ class ExecutorStepRuntimeMixin:
             try:
                 return future.result(timeout=timeout_secs)
             except concurrent.futures.TimeoutError:
+                # Kill the remote command so the worker thread can finish.
+                self._note_step_stopped(step_id, "timeout")
+                self._cancel_inflight_steps(f"step timeout after {timeout_secs}s", step_num=step_num)
                 return False, f"Step timeout after {timeout_secs}s"
 
     def _run_single_step_with_retries(
@@ -505,6 +601,7 @@ class ExecutorStepRuntimeMixin:
                 try_number=attempt + 1,
                 track_checkpoint=track_checkpoint and attempt == 0,
                 execution_timeout=node.execution_timeout,
+                done_check=node.done_check,
             )
             last_output = output
             last_duration_ms = duration_ms
@@ -519,8 +616,8 @@ class ExecutorStepRuntimeMixin:
                 )
                 return True, output, duration_ms
 
-            if attempt < retries:
-                continue
+            if attempt >= retries or not self._retry_gate(node, step_id or node.step_id, output):
+                break
 
         self._run_step_callbacks(
             node,
@@ -528,191 +625,6 @@ class ExecutorStepRuntimeMixin:
             ok=False,
             output=last_output,
             duration_ms=last_duration_ms,
-            try_number=retries + 1,
+            try_number=attempt + 1,
         )
         return False, last_output, last_duration_ms
-
-    def _run_step_callbacks(
-        self,
-        node: _StepNode,
-        *,
-        stage: str,
-        ok: bool,
-        output: str,
-        duration_ms: int,
-        try_number: int = 1,
-    ) -> None:
-        callbacks = node.on_success if ok else node.on_failure
-        if not callbacks:
-            return
-
-        context = {
-            "step_id": node.step_id,
-            "step_num": node.step_num,
-            "step_raw": node.step.raw if hasattr(node.step, "raw") else "",
-            "step_type": str(getattr(node.step, "type", "")),
-            "host": getattr(node.step, "host", ""),
-            "callback_host": getattr(node.step, "host", "local") or "local",
-            "retries": node.retries,
-            "retry_delay": node.retry_delay,
-            "trigger_rule": node.trigger_rule,
-            "pool": node.pool,
-            "priority": node.priority,
-            "ok": ok,
-            "output": output,
-            "duration_ms": duration_ms,
-            "stage": stage,
-            "try_number": max(1, int(try_number)),
-        }
-        for callback in list(callbacks):
-            self._run_step_callback(callback, context=context)
-
-    def _render_callback_value(self, value: Any, context: Dict[str, Any]) -> Any:
-        if isinstance(value, bytes):
-            value = value.decode("utf-8", errors="ignore")
-        if not isinstance(value, str):
-            return value
-        mapping = defaultdict(lambda: "", {k: "" if v is None else str(v) for k, v in context.items()})
-        try:
-            return value.format_map(mapping)
-        except Exception:
-            return value
-
-    def _normalize_callback_payload(self, value: Any, context: Dict[str, Any]) -> Any:
-        if isinstance(value, str):
-            return self._render_callback_value(value, context)
-        if isinstance(value, dict):
-            return {
-                key: self._normalize_callback_payload(val, context)
-                for key, val in value.items()
-            }
-        if isinstance(value, (list, tuple, set)):
-            return [self._normalize_callback_payload(item, context) for item in value]
-        return value
-
-    def _run_step_callback(self, callback: Any, context: Dict[str, Any]) -> None:
-        try:
-            if isinstance(callback, ProviderStep):
-                rendered = ProviderStep(
-                    provider=self._render_callback_value(callback.provider, context),
-                    operation=self._render_callback_value(callback.operation, context),
-                    params=self._normalize_callback_payload(dict(callback.params or {}), context),
-                    id=str(getattr(callback, "id", "callback")),
-                    depends_on=list(getattr(callback, "depends_on", []) or []),
-                    retries=int(getattr(callback, "retries", 0) or 0),
-                    retry_delay=getattr(callback, "retry_delay", 0),
-                    continue_on_failure=bool(getattr(callback, "continue_on_failure", False)),
-                    trigger_rule=str(getattr(callback, "trigger_rule", "all_success")),
-                    pool=str(getattr(callback, "pool", "default")),
-                    priority=int(getattr(callback, "priority", 0) or 0),
-                    execution_timeout=int(getattr(callback, "execution_timeout", 0) or 0),
-                    retry_exponential_backoff=float(getattr(callback, "retry_exponential_backoff", 0.0) or 0.0),
-                    max_active_tis_per_dagrun=getattr(callback, "max_active_tis_per_dagrun", None),
-                    deferrable=bool(getattr(callback, "deferrable", False)),
-                    on_success=list(getattr(callback, "on_success", []) or []),
-                    on_failure=list(getattr(callback, "on_failure", []) or []),
-                )
-                ok, output = self._exec_provider(rendered)
-                if not ok:
-                    self.log(
-                        f"Callback provider step failed (step {context.get('step_num')}): {output}"
-                    )
-                return
-
-            if callable(callback):
-                try:
-                    callback(context)
-                except TypeError:
-                    callback()
-                return
-
-            if isinstance(callback, str):
-                self._run_provider_or_shell_callback(
-                    {"command": callback, "host": context.get("callback_host", "local")},
-                    context,
-                )
-                return
-
-            if isinstance(callback, bytes):
-                self._run_provider_or_shell_callback(
-                    {
-                        "command": callback.decode("utf-8", errors="ignore"),
-                        "host": context.get("callback_host", "local"),
-                    },
-                    context,
-                )
-                return
-
-            if isinstance(callback, dict):
-                provider = callback.get("provider")
-                operation = callback.get("operation")
-                if provider and operation:
-                    params = dict(callback.get("params", {}) or {})
-                    if "host" in callback and "host" not in params:
-                        params["host"] = callback.get("host")
-                    params["provider"] = self._render_callback_value(str(provider), context)
-                    params["operation"] = self._render_callback_value(
-                        str(operation),
-                        context,
-                    )
-                    ok, output = self._exec_provider(
-                        type(
-                            "RuntimeProviderCallback",
-                            (),
-                            {
-                                "provider": params.pop("provider"),
-                                "operation": params.pop("operation"),
-                                "params": self._normalize_callback_payload(params, context),
-                            },
-                        )()
-                    )
-                    if not ok:
-                        self.log(
-                            f"Callback provider step failed (step {context.get('step_num')}): {output}"
-                        )
-                    return
-
-                if "command" in callback:
-                    normalized = dict(callback)
-                    normalized["command"] = self._normalize_callback_payload(
-                        callback.get("command"),
-                        context,
-                    )
-                    normalized.setdefault("host", context.get("callback_host"))
-                    self._run_provider_or_shell_callback(normalized, context)
-                    return
-                return
-
-        except Exception as exc:  # noqa: BLE001
-            self.log(f"Callback execution failed for step {context.get('step_num')}: {exc}")
-
-    def _run_provider_or_shell_callback(self, spec: Dict[str, Any], context: Dict[str, Any]) -> None:
-        command = self._render_callback_value(str(spec.get("command", "")).strip(), context)
-        if not command:
-            return
-        raw_host = spec.get("host", context.get("callback_host", "local"))
-        if raw_host is None:
-            raw_host = "local"
-        if isinstance(raw_host, bytes):
-            raw_host = raw_host.decode("utf-8", errors="ignore")
-        host = self._render_callback_value(str(raw_host), context)
-        host = str(host or "local")
-        timeout = self._normalize_provider_timeout(spec.get("timeout"), allow_zero=True)
-        timeout_secs = 30 if timeout in (None, 0) else timeout
-        cwd = self._normalize_callback_payload(spec.get("cwd"), context)
-        env = self._normalize_callback_payload(spec.get("env"), context)
-
-        params = {
-            "command": command,
-            "host": self._provider_host(host),
-            "timeout": timeout_secs,
-        }
-        if cwd is not None:
-            params["cwd"] = cwd
-        if env is not None:
-            params["env"] = env
-        ok, output = self._exec_provider_shell(params)
-        if not ok:
-            self.log(
-                f"Callback shell command failed (step {context.get('step_num')}): {output}"
-            )
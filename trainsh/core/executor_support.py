@@ -2,17 +2,25 @@
 
 from __future__ import annotations
 
+import contextlib
 import re
 import shlex
 import subprocess
 import time
 from typing import Any, Dict, List, Optional
 
+from ..services.host_metrics import host_metrics_recent
+from .errors import ErrorCode, error_info
 from .executor_runtime import WindowInfo
+from .exit_status import OOM_EVENT, OOM_HINT, cuda_memory_stats, find_cuda_oom
 from .executor_utils import _resolve_runpod_host, _resolve_vast_host
 from .models import Host
+from .recipe_variables import UnresolvedVariableError, unresolved_in_text
 from .runtime_store import to_jsonable
 
+# Output kept per failed step for `retry_on_output_regex`.
+STEP_OUTPUT_TAIL_CHARS = 16000
+
 
 class ExecutorSupportMixin:
     def _log_detail(self, event: str, message: str, data: Dict[str, object]) -> None:
@@ -21,6 +29,215 @@ class ExecutorSupportMixin:
             with self._thread_lock:
                 self.logger.log_detail(event, message, data)
 
+    def _note_exit_code(self, returncode: int, host: str = "") -> None:
+        """Remember the running step's last exit code for `retry_on_exit_codes` and the job outcome."""
+        step_id = self._current_step_id()
+        if not step_id:
+            return
+        code = int(returncode)
+        with self._thread_lock:
+            self._step_exit_codes[step_id] = code
+            # subprocess reports signal deaths as -N; keep the shell's 128+N.
+            self._step_exits[step_id] = (128 - code if code < 0 else code, str(host or ""))
+
+    def _note_step_output(self, text: str) -> None:
+        """Keep the tail of the running step's command output for `retry_on_output_regex`."""
+        step_id = self._current_step_id()
+        if step_id and text:
+            with self._thread_lock:
+                self._step_outputs[step_id] = text[-STEP_OUTPUT_TAIL_CHARS:]
+
+    def _note_step_stopped(self, step_id: str, reason: str) -> None:
+        """Record that train itself stopped a step ("timeout" or "cancelled")."""
+        if step_id:
+            with self._thread_lock:
+                self._step_stops[step_id] = reason
+
+    def _note_step_failed(self, step_id: str) -> None:
+        """Keep the exit code of the first step whose failure fails the run."""
+        with self._thread_lock:
+            if self._failed_exit is None:
+                code, host = self._step_exits.get(step_id, (None, ""))
+                self._failed_exit = (code, host, self._step_stops.get(step_id, ""))
+                self._failed_step = step_id
+
+    def _step_failure_info(self, step_id: str, step_num: int, output: str) -> Dict[str, object]:
+        """Check a failed attempt for a CUDA out-of-memory error and return its error fields."""
+        with self._thread_lock:
+            # tmux steps only report "exit code N"; their pane tail holds the real output.
+            text = "\n".join(part for part in (str(output or ""), self._step_outputs.get(step_id, "")) if part)
+            line = find_cuda_oom(text)
+            if line and step_id:
+                self._step_ooms[step_id] = line
+            else:
+                self._step_ooms.pop(step_id, None)
+            host = self._step_exits.get(step_id, (None, ""))[1]
+        if line:
+            self._emit_oom("cuda", host, cuda_memory_stats(text), step_id=step_id, step_num=step_num, message=line)
+        return self._failure_fields(step_id, output)
+
+    def _failure_fields(self, step_id: str, output: object) -> Dict[str, object]:
+        """`error_info` fields for a failed step, marked `cuda_oom` when it ran out of GPU memory."""
+        info = error_info(output)
+        if step_id in self._step_ooms:
+            info.update(error_code=ErrorCode.OUT_OF_MEMORY.value, retryable=False, failure_reason="cuda_oom")
+        return info
+
+    def _emit_oom(self, kind: str, host: str, memory: Dict[str, object], **payload: Any) -> None:
+        """Log and emit `run:oom_detected` with the last GPU memory sample buffered for `host`."""
+        gpus: List[Dict[str, object]] = []
+        if host:
+            samples = host_metrics_recent(host, limit=1)
+            gpus = list(samples[-1].get("gpus") or []) if samples else []
+        where = f" on {host}" if host else ""
+        self.log(f"Out of memory{where} ({kind}): {OOM_HINT}")
+        self._emit_event(OOM_EVENT, kind=kind, host=host, memory=memory, gpus=gpus, hint=OOM_HINT, **payload)
+
+    def _cancel_inflight_steps(
+        self,
+        reason: str,
+        *,
+        step_num: Optional[int] = None,
+        close_sessions: bool = False,
+    ) -> List[str]:
+        """Kill commands still running for in-flight steps and log what was stopped."""
+        with self._thread_lock:
+            targets = [
+                (num, info)
+                for num, info in self.ctx.inflight.items()
+                if step_num is None or num == str(step_num)
+            ]
+            for _, info in targets:
+                # The killed subshell still fires its wait-for signal; this marks it failed.
+                info["cancelled"] = reason
+        reports: List[str] = []
+        for num, info in targets:
+            report = self.execute_helper.kill_inflight(info, close_session=close_sessions)
+            self.log(f"✖ Step {num} cancelled ({reason}): {report}")
+            self._emit_event("step_cancelled", step_num=int(num), reason=reason, report=report)
+            reports.append(report)
+        return reports
+
+    @contextlib.contextmanager
+    def _cancel_on_interrupt(self):
+        """On Ctrl+C, stop remote commands before worker threads are joined."""
+        try:
+            yield
+        except KeyboardInterrupt:
+            self._cancel_inflight_steps("interrupted")
+            current = self.job_state.current_step if self.job_state else 0
+            self._save_checkpoint(current, status="cancelled")
+            _, exit_code = self._record_outcome(False)
+            self._remove_status_files()
+            self._run_completion_hooks(False, exit_code, cancelled=True)
+            raise
+
+    def _preflight_mode(self, recipe_cfg: Dict[str, Any]) -> str:
+        """Return "", "check" or "install" from `--preflight*` or `recipe.preflight*` config."""
+        value = self.executor_kwargs.get("preflight")
+        if value is None:
+            if self._normalize_bool(recipe_cfg.get("preflight_install", False)):
+                value = "install"
+            else:
+                value = recipe_cfg.get("preflight", False)
+        if str(value).strip().lower() == "install":
+            return "install"
+        return "check" if self._normalize_bool(value) else ""
+
+    def _output_settings(self, config: Dict[str, Any]) -> Dict[str, Any]:
+        """Return ExecutionLogger kwargs from `recipe.output_*` and `logs.max_*_output_bytes` config."""
+        recipe_cfg = config.get("recipe", {}) or {}
+        logs_cfg = config.get("logs", {}) or {}
+        settings: Dict[str, Any] = {}
+        for name, raw, default, cast in (
+            ("tail_lines", recipe_cfg.get("output_tail_lines"), 200, int),
+            ("coalesce_secs", recipe_cfg.get("output_coalesce_secs"), 0.0, float),
+            ("max_step_bytes", logs_cfg.get("max_step_output_bytes"), 0, int),
+            ("max_run_bytes", logs_cfg.get("max_run_output_bytes"), 0, int),
+        ):
+            try:
+                settings[name] = max(0, cast(default if raw is None else raw))
+            except (TypeError, ValueError):
+                settings[name] = default
+        settings["tail_lines"] = max(1, settings["tail_lines"])
+        return settings
+
+    def _gpu_guard_settings(self, config: Dict[str, Any]) -> tuple[str, int]:
+        """Return (mode, min_memory_mb) from `--gpu-guard` or `gpu_guard.*` config."""
+        from .gpu_guard import normalize_mode
+
+        guard_cfg = config.get("gpu_guard", {}) or {}
+        mode = normalize_mode(self.executor_kwargs.get("gpu_guard", guard_cfg.get("mode", "confirm")))
+        try:
+            min_memory_mb = max(0, int(guard_cfg.get("min_memory_mb", 1024)))
+        except (TypeError, ValueError):
+            min_memory_mb = 1024
+        return mode, min_memory_mb
+
+    def _claim_gpus(self, window_name: str, host: str, gpus: List[str]) -> str:
+        """Record the GPUs a session uses; return why not when another session or run holds them."""
+        from .job_state import _pid_alive
+
+        wanted = set(gpus)
+        with self._thread_lock:
+            for other, info in self.ctx.gpu_assignments.items():
+                overlap = wanted & set(info.get("gpus", []))
+                if other != window_name and info.get("host") == host and overlap:
+                    return f"GPU {','.join(sorted(overlap))} on {host} is already assigned to session {other}"
+            for state in self.state_manager.list_running():
+                if state.job_id == self.ctx.job_id or (state.pid and not _pid_alive(state.pid)):
+                    continue
+                for other, info in state.gpu_assignments.items():
+                    overlap = wanted & set(info.get("gpus", []) or [])
+                    if info.get("host") == host and overlap:
+                        return (
+                            f"GPU {','.join(sorted(overlap))} on {host} is already assigned to "
+                            f"session {other} of {state.recipe_name} ({state.job_id})"
+                        )
+            changed = self.ctx.gpu_assignments.get(window_name) != {"host": host, "gpus": list(gpus)}
+            self.ctx.gpu_assignments[window_name] = {"host": host, "gpus": list(gpus)}
+            if changed and self.job_state is not None:
+                self._save_checkpoint(self.job_state.current_step)
+        if changed:
+            self._emit_event("gpu_assigned", window=window_name, host=host, gpus=list(gpus))
+        return ""
+
+    def _run_preflight(self) -> bool:
+        """Check hosts for the tools the recipe needs; optionally install what is missing."""
+        if not self.preflight_mode:
+            return True
+        from . import preflight
+        from .executor_utils import _build_ssh_args
+
+        failures: List[str] = []
+        for alias, tools in preflight.required_tools(self.recipe).items():
+            spec = "local" if alias == "local" else self._interpolate(str(self.recipe.hosts.get(alias, alias)))
+            if not preflight.checkable_host(spec):
+                self.log(f"Preflight: @{alias} is created at run time; not checked")
+                continue
+            reachable, missing = preflight.check_host_tools(spec, tools, build_ssh_args=_build_ssh_args)
+            if not reachable:
+                self.log(f"⚠ Preflight: could not reach {alias}; skipped")
+                continue
+            script = preflight.bootstrap_script(missing) if missing else None
+            if script and self.preflight_mode == "install":
+                self.log(f"Preflight: installing {', '.join(missing)} on {alias}")
+                ok, detail = preflight.run_bootstrap(spec, script, build_ssh_args=_build_ssh_args)
+                if not ok:
+                    self.log(f"⚠ Preflight: install on {alias} failed: {detail}")
+                _reachable, missing = preflight.check_host_tools(spec, tools, build_ssh_args=_build_ssh_args)
+            for tool in missing:
+                failures.append(f"{alias}: {tool} not found ({preflight.install_hint(tool)})")
+
+        if failures:
+            self.log("✖ Preflight failed; missing tools:")
+            for line in failures:
+                self.log(f"  {line}")
+            if self.preflight_mode != "install":
+                self.log("  Re-run with --preflight-install to install what can be installed.")
+        self._emit_event("preflight", ok=not failures, missing=failures)
+        return not failures
+
     def _build_bridge_attach_command(self, window: WindowInfo) -> str:
         """Build attach command for a bridge pane."""
         return self.bridge_exec.build_bridge_attach_command(window)
@@ -193,7 +410,11 @@ class ExecutorSupportMixin:
             host = host_ref
 
         if host.startswith("vast:"):
-            return _resolve_vast_host(host[5:])
+            resolved = _resolve_vast_host(host[5:])
+            host_refs = getattr(self.ctx, "host_refs", None)
+            if isinstance(host_refs, dict):
+                host_refs[resolved] = host
+            return resolved
         if host.startswith("runpod:"):
             return _resolve_runpod_host(host[7:])
         return host
@@ -208,6 +429,20 @@ class ExecutorSupportMixin:
         host = self._resolve_host(f"@{name}")
         return WindowInfo(name=name, host=host, remote_session=None)
 
+    def _secret(self, name: str) -> str:
+        """Resolve a secret, audit its first read this run and scrub its value from the logs."""
+        value = self.secrets.get(name) or ""
+        audited = self.__dict__.setdefault("_audited_secrets", set())
+        if name not in audited:
+            audited.add(name)
+            from ..services.audit_log import record
+
+            record("secret.get", name, detail=f"recipe {self.recipe.name}", status="ok" if value else "missing", source=f"job {self.ctx.job_id}")
+        logger = getattr(self, "logger", None)
+        if value and logger is not None and isinstance(getattr(logger, "redactions", None), set):
+            logger.redactions.add(value)
+        return value
+
     def _interpolate(self, text: str) -> str:
         """Interpolate variables and secrets.
 
@@ -221,8 +456,7 @@ class ExecutorSupportMixin:
             def replace_braced(match):
                 ref = match.group(1)
                 if ref.startswith('secret:'):
-                    secret_name = ref[7:]
-                    return self.secrets.get(secret_name) or ""
+                    return self._secret(ref[7:])
                 return self.ctx.variables.get(ref, match.group(0))
 
             text = re.sub(r'\$\{([^}]+)\}', replace_braced, text)
@@ -237,8 +471,38 @@ class ExecutorSupportMixin:
             if text == prev:
                 break  # nothing changed, fully resolved
 
+        if getattr(self, "strict_variables", False):
+            missing = unresolved_in_text(text, getattr(self, "_undefined_variables", ()))
+            if missing:
+                raise UnresolvedVariableError(
+                    f"Unresolved variable(s): {', '.join(missing)}. Pass --set NAME=VALUE to define them."
+                )
         return text
 
+    def _interpolate_command(self, text: str, shell: str = "bash") -> str:
+        """Interpolate a shell command; with `safe_variables`, bind values as env vars."""
+        if not getattr(self, "safe_variables", False) or shell != "bash":
+            return self._interpolate(text)
+        from .safe_variables import bind_variables
+
+        def resolve(ref: str) -> Optional[str]:
+            if ref.startswith("secret:") or ref in self.ctx.variables:
+                return self._interpolate("${" + ref + "}")
+            return None
+
+        command, warnings, _ = bind_variables(text, resolve)
+        for warning in dict.fromkeys(warnings):
+            self.log(f"  ⚠ {warning}")
+        if warnings and getattr(self, "logger", None):
+            self.logger.log_detail("safe_variables", "Variable quoting warnings", {"warnings": list(dict.fromkeys(warnings))})
+        if getattr(self, "strict_variables", False):
+            missing = unresolved_in_text(command, getattr(self, "_undefined_variables", ()))
+            if missing:
+                raise UnresolvedVariableError(
+                    f"Unresolved variable(s): {', '.join(missing)}. Pass --set NAME=VALUE to define them."
+                )
+        return command
+
     def _parse_endpoint(self, spec: str) -> 'TransferEndpoint':
         """Parse transfer endpoint via helper."""
         return self.transfer_helper.parse_endpoint(spec)
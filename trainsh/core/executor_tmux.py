@@ -2,8 +2,9 @@
 # Keeps tmux.open/tmux.close/tmux.config logic out of DSLExecutor.
 
 import os
+import shlex
 from pathlib import Path
-from typing import Any, List, Type
+from typing import Any, Dict, List, Type
 
 from ..config import get_default_config, load_config
 
@@ -26,6 +27,7 @@ class TmuxControlHelper:
 
         host_ref = args[0]
         window_name = args[2]
+        options = dict(item.split("=", 1) for item in args[3:] if "=" in item)
 
         host = self.executor._resolve_host(host_ref)
         remote_session_name = self.executor.allocate_window_session_name()
@@ -57,22 +59,70 @@ class TmuxControlHelper:
                 self.executor.log(f"  Local tmux session: {remote_session_name}")
                 self.executor.log(f"  Attach with: tmux attach -t {remote_session_name}")
                 self.executor._ensure_bridge_window(window_info)
+                if options.get("env"):
+                    ok, message = self.inject_env(window_info, options)
+                    if not ok:
+                        return False, message
                 return True, f"Created local tmux session: {remote_session_name}"
             except Exception as e:
                 return False, str(e)
 
+        from ..services.connectivity import host_offline_reason
+
+        offline = host_offline_reason([host_ref.lstrip("@"), host])
+        if offline:
+            return False, offline
+
+        from .executor_utils import _host_shell
+
+        if _host_shell(host) == "powershell":
+            # Windows hosts have no tmux: the window runs each command as its own
+            # SSH call and waits for its exit code.
+            if options.get("env"):
+                return False, f"env= is not supported on PowerShell host {host_ref}"
+            window_info.remote_session = None
+            self.executor.ctx.windows[window_name] = window_info
+            self.executor.log(f"  {window_name}: PowerShell host, commands run over SSH without tmux")
+            if self.executor.logger:
+                self.executor.logger.log_detail("window_registered", f"Window {window_name} registered", {
+                    "window_name": window_name,
+                    "host": host,
+                    "shell": "powershell",
+                })
+            return True, f"Registered {window_name} on PowerShell host (no tmux session)"
+
+        from ..services.host_defaults import set_history_limit
+        from ..services.terminal_env import fit_window, session_defaults
+        from .executor_utils import _configured_host_for_spec
+
+        defaults = session_defaults(_configured_host_for_spec(host))
         remote_tmux = self.executor.get_tmux_client(host)
         try:
             if not remote_tmux.has_session(remote_session_name):
-                result = remote_tmux.new_session(remote_session_name, detached=True)
+                result = remote_tmux.new_session(
+                    remote_session_name,
+                    detached=True,
+                    command=defaults.session_command(),
+                )
                 if result.returncode != 0:
                     return False, f"Failed to create remote tmux session: {result.stderr}"
+                if defaults.history_limit is not None:
+                    set_history_limit(remote_tmux, remote_session_name, defaults.history_limit)
+                # Sizing is cosmetic: a failure must not fail the step.
+                try:
+                    fit_window(remote_tmux, remote_session_name)
+                except Exception:
+                    pass
 
             self.executor.ctx.windows[window_name] = window_info
             attach_cmd = remote_tmux.build_attach_command(remote_session_name, status_mode="keep")
             self.executor.log(f"  Remote tmux session: {remote_session_name}")
             self.executor.log(f"  Attach with: {attach_cmd}")
             self.executor._ensure_bridge_window(window_info)
+            if options.get("env"):
+                ok, message = self.inject_env(window_info, options)
+                if not ok:
+                    return False, message
 
             if self.executor.logger:
                 self.executor.logger.log_detail("window_registered", f"Window {window_name} registered", {
@@ -88,6 +138,62 @@ class TmuxControlHelper:
                 self.executor.logger.log_detail("tmux_error", f"Failed to create session: {e}", {})
             return False, str(e)
 
+    def inject_env(self, window: Any, options: Dict[str, str]) -> tuple[bool, str]:
+        """Write the session's env sets to a private .env on its host, then source it in the session.
+
+        Without `env_file` the file is temporary and removed once sourced; with
+        `export=0` it is only written. Values never appear in the tmux command line.
+        """
+        from ..services.session_env import (
+            EPHEMERAL_ENV_DIR,
+            render_env_file,
+            resolve_env_sets,
+            source_command,
+            write_env_script,
+            write_local_env_file,
+        )
+
+        names = [item for item in options.get("env", "").split(",") if item]
+        try:
+            values = resolve_env_sets(names, getattr(self.executor.recipe, "env_sets", {}), self.executor._interpolate)
+        except (KeyError, ValueError) as exc:
+            return False, str(exc.args[0] if exc.args else exc)
+        env_file = options.get("env_file", "").strip()
+        export = options.get("export", "1") not in ("0", "false", "no")
+        path = env_file or f"{EPHEMERAL_ENV_DIR}/{window.remote_session}.env"
+        content = render_env_file(values)
+        try:
+            if window.host == "local":
+                write_local_env_file(path, content)
+            else:
+                from ..services.ssh import SSHClient
+                from .executor_utils import _host_from_ssh_spec
+
+                result = SSHClient.from_host(_host_from_ssh_spec(window.host)).run_with_input(
+                    f"sh -c {shlex.quote(write_env_script(path))}",
+                    content,
+                    timeout=60,
+                )
+                if not result.success:
+                    return False, f"Failed to write env file {path}: {result.stderr.strip()}"
+        except OSError as exc:
+            return False, f"Failed to write env file {path}: {exc}"
+
+        if export:
+            tmux = self.executor.get_tmux_client(window.host)
+            sent = tmux.send_keys(window.remote_session, source_command(path, remove=not env_file), enter=True, literal=True)
+            if sent.returncode != 0:
+                return False, f"Failed to export env in {window.name}"
+        if self.executor.logger:
+            self.executor.logger.log_detail("session_env", f"Injected env into {window.name}", {
+                "sets": names,
+                "variables": sorted(values),
+                "env_file": env_file,
+                "exported": export,
+            })
+        self.executor.log(f"  Env: {', '.join(sorted(values)) or '(empty)'}{f' -> {env_file}' if env_file else ''}")
+        return True, ""
+
     def cmd_tmux_close(self, args: List[str]) -> tuple[bool, str]:
         """Handle: tmux.close @session"""
         if not args:
@@ -104,6 +210,7 @@ class TmuxControlHelper:
         if not window.remote_session:
             self.executor.tmux_bridge.disconnect(window_name)
             self.executor.ctx.windows.pop(window_name, None)
+            getattr(self.executor.ctx, "gpu_assignments", {}).pop(window_name, None)
             return True, f"Unregistered window: {window_name}"
 
         if window.host == "local":
@@ -111,6 +218,7 @@ class TmuxControlHelper:
                 self.executor.local_tmux.kill_session(window.remote_session)
                 self.executor.tmux_bridge.disconnect(window_name)
                 self.executor.ctx.windows.pop(window_name, None)
+                getattr(self.executor.ctx, "gpu_assignments", {}).pop(window_name, None)
                 return True, f"Killed local tmux session: {window.remote_session}"
             except Exception as e:
                 return False, str(e)
@@ -124,6 +232,7 @@ class TmuxControlHelper:
                 })
             self.executor.tmux_bridge.disconnect(window_name)
             self.executor.ctx.windows.pop(window_name, None)
+            getattr(self.executor.ctx, "gpu_assignments", {}).pop(window_name, None)
             return True, f"Killed remote session: {window.remote_session}"
         except Exception as e:
             return False, str(e)
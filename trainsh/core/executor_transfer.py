@@ -2,7 +2,7 @@
 # Extracts transfer and endpoint parsing logic from DSLExecutor.
 
 import os
-from typing import Any, Callable, Dict, Iterable, List, Optional
+from typing import Any, Callable, Dict, Iterable, List, Optional, Sequence, Union
 
 from .models import Host
 from .storage_specs import (
@@ -47,18 +47,28 @@ class TransferHelper:
             delete=delete,
             exclude=exclude,
             operation=operation,
+            rsync_options=getattr(step, "rsync", None),
+            tar_stream=self._coerce_bool(getattr(step, "tar_stream", False)),
+            remote_exec=getattr(step, "remote_exec", None),
         )
 
     def transfer(
         self,
-        source: str,
+        source: Union[str, Sequence[str]],
         destination: str,
         *,
         delete: bool = False,
         exclude: Optional[Iterable[Any]] = None,
         operation: str = "copy",
+        rsync_options: Optional[Dict[str, Any]] = None,
+        tar_stream: bool = False,
+        remote_exec: Optional[bool] = None,
     ) -> tuple[bool, str]:
-        """Execute transfer between source and destination specs."""
+        """Execute transfer between source and destination specs.
+
+        `source` may be a glob or a list of specs on one side; they expand
+        on that side when the step runs.
+        """
         operation = (operation or "copy").strip().lower()
         if operation not in {"copy", "sync"}:
             return False, f"Unsupported transfer operation: {operation!r}"
@@ -67,10 +77,13 @@ class TransferHelper:
 
         from ..services.transfer_engine import TransferEngine
 
-        source = self.executor._interpolate(str(source or "").strip())
+        specs = [source] if isinstance(source, str) or source is None else list(source)
+        sources = [self.executor._interpolate(str(spec or "").strip()) for spec in specs]
+        sources = [spec for spec in sources if spec]
+        source = ", ".join(sources)
         destination = self.executor._interpolate(str(destination or "").strip())
 
-        for label, value in (("source", source), ("destination", destination)):
+        for label, value in [*(("source", spec) for spec in sources), ("destination", destination)]:
             error = unsupported_inline_storage_error(value)
             if error:
                 return False, f"{label.capitalize()} endpoint {value!r}: {error}"
@@ -78,6 +91,15 @@ class TransferHelper:
         if not source or not destination:
             return False, "Transfer requires both source and destination"
 
+        from ..services.rsync_options import normalize_rsync_options
+
+        if rsync_options is not None and not isinstance(rsync_options, dict):
+            return False, "Transfer 'rsync' options must be an object"
+        try:
+            rsync_options = normalize_rsync_options(rsync_options)
+        except ValueError as exc:
+            return False, str(exc)
+
         transfer_info = {
             "source": source,
             "destination": destination,
@@ -85,25 +107,86 @@ class TransferHelper:
             "operation": operation,
             "exclude": list(exclude or []),
         }
+        if rsync_options:
+            transfer_info["rsync"] = dict(rsync_options)
+        if tar_stream:
+            transfer_info["tar_stream"] = True
+        if remote_exec is None:
+            from ..config import get_config_value
+
+            remote_exec = get_config_value("transfer.remote_exec", False)
+        remote_exec = self._coerce_bool(remote_exec)
+        if remote_exec:
+            transfer_info["remote_exec"] = True
         if self.executor.logger:
             self.executor.logger.log_detail("transfer", f"Transferring {source} -> {destination}", transfer_info)
 
-        src_endpoint = self.parse_endpoint(source)
+        from ..services.transfer_select import is_selection, transfer_selection
+
+        src_endpoints = [self.parse_endpoint(spec) for spec in sources]
+        src_endpoint = src_endpoints[0]
         dst_endpoint = self.parse_endpoint(destination)
+        from ..services.connectivity import host_offline_reason
+
+        # Recipe hosts resolve to SSH specs; check the alias the user wrote as well.
+        host_names = [
+            name
+            for spec, endpoint in zip([*sources, destination], [*src_endpoints, dst_endpoint])
+            if endpoint.type == "host"
+            for name in (spec[1:].split(":", 1)[0] if spec.startswith("@") else "", endpoint.host_id)
+        ]
+        offline = host_offline_reason(host_names)
+        if offline:
+            return False, offline
+        patterns = [endpoint.path for endpoint in src_endpoints]
+        selection = is_selection(patterns)
+        if selection:
+            side = (src_endpoint.type, src_endpoint.host_id, src_endpoint.storage_id)
+            if any((endpoint.type, endpoint.host_id, endpoint.storage_id) != side for endpoint in src_endpoints):
+                return False, "Multiple transfer sources must all be local or all on the same host"
+            if delete:
+                return False, "Transfer with delete/sync needs a single source path"
+        if not selection and not tar_stream and src_endpoint.type == "local" and hasattr(self.executor, "log"):
+            from ..services.tar_stream import suggest_tar_stream
+
+            hint = suggest_tar_stream(src_endpoint.path)
+            if hint:
+                self.executor.log(f"  Hint: {hint}")
 
         import time
         start_time = time.time()
-        engine = TransferEngine()
+        logger = self.executor.logger
+        engine = TransferEngine(
+            progress_callback=(
+                (lambda progress: logger.log_transfer_progress(source, destination, progress.to_dict()))
+                if logger
+                else None
+            ),
+            rsync_options=rsync_options,
+            tar_stream=tar_stream,
+            remote_exec=remote_exec,
+        )
         hosts = self.build_transfer_hosts()
         storages = self.build_transfer_storages()
-        result = engine.transfer(
-            source=src_endpoint,
-            destination=dst_endpoint,
-            hosts=hosts,
-            storages=storages,
-            delete=bool(delete),
-            exclude=list(exclude or []),
-        )
+        if selection:
+            result = transfer_selection(
+                engine,
+                src_endpoint,
+                patterns,
+                dst_endpoint,
+                hosts=hosts,
+                storages=storages,
+                exclude=list(exclude or []),
+            )
+        else:
+            result = engine.transfer(
+                source=src_endpoint,
+                destination=dst_endpoint,
+                hosts=hosts,
+                storages=storages,
+                delete=bool(delete),
+                exclude=list(exclude or []),
+            )
         duration_ms = int((time.time() - start_time) * 1000)
 
         if self.executor.logger:
@@ -115,6 +198,8 @@ class TransferHelper:
                 duration_ms,
                 result.success,
                 result.message,
+                progress=result.progress.to_dict() if getattr(result, "progress", None) else None,
+                files=getattr(result, "files", None),
             )
 
         if result.success:
@@ -46,6 +46,14 @@ def _configured_host_for_spec(spec: str) -> Optional[Host]:
     return Host.from_dict(host.to_dict())
 
 
+def _host_shell(spec: str) -> str:
+    """Shell dialect of a host spec: "powershell" for configured Windows hosts, else "bash"."""
+    from ..services.remote_shell import host_shell
+
+    configured_host = _configured_host_for_spec(spec)
+    return host_shell(configured_host) if configured_host is not None else "bash"
+
+
 def _insert_tty_flag(args: List[str]) -> List[str]:
     """Insert `-t` immediately after the ssh binary when missing."""
     try:
@@ -88,7 +96,9 @@ def _build_ssh_args(spec: str, command: Optional[str] = None, tty: bool = False,
     if configured_host is not None:
         from ..services.ssh import SSHClient
 
-        env_prefix = "TERM=xterm-256color LC_ALL=en_US.UTF-8"
+        from ..services.terminal_env import env_preamble
+
+        env_prefix = env_preamble()
         resolved_command = command
         if set_term:
             if command:
@@ -96,20 +106,34 @@ def _build_ssh_args(spec: str, command: Optional[str] = None, tty: bool = False,
             else:
                 resolved_command = f"{env_prefix} exec bash -l"
 
+        from ..services.remote_shell import host_shell
+
         client = SSHClient.from_host(configured_host)
+        if host_shell(configured_host) == "powershell":
+            # POSIX env assignments mean nothing to PowerShell.
+            resolved_command = command
         args = client._build_ssh_args(resolved_command, interactive=tty)
         return _insert_tty_flag(args) if tty else args
 
     host, options = _split_ssh_spec(spec)
     args = ["ssh"]
     if tty:
+        from ..services.terminal_env import send_env_options
+        from ..services.terminal_keepalive import keepalive_options
+
         args.append("-t")
-    args.extend(options)
+        args.extend(send_env_options())
+        args.extend(options)
+        # After the spec's own options: ssh keeps the first value it sees.
+        args.extend(keepalive_options(existing=options))
+    else:
+        args.extend(options)
     args.append(host)
 
-    env_prefix = "TERM=xterm-256color LC_ALL=en_US.UTF-8"
-
     if set_term:
+        from ..services.terminal_env import env_preamble
+
+        env_prefix = env_preamble()
         if command:
             args.append(f"{env_prefix} {command}")
         else:
@@ -1,12 +1,14 @@
 # tmux-trainsh vast control helpers
 # Encapsulates vast.* command logic from executor main.
 
-import os
 import subprocess
 import time
+from contextlib import nullcontext
 from datetime import datetime
 from typing import Any, Callable, List, Optional
 
+from .vast_ssh_keys import ensure_ssh_key_attached
+
 
 class VastControlHelper:
     """Helper for vast.* control commands."""
@@ -21,6 +23,12 @@ class VastControlHelper:
         self.build_ssh_args = build_ssh_args
         self.format_duration = format_duration
 
+    def _prompt(self, message: str, reason: str) -> str:
+        """`input()` that records the time the step spends blocked on the user."""
+        waiting = getattr(self.executor, "_waiting_for_input", None)
+        with waiting(reason) if waiting else nullcontext():
+            return input(message)
+
     def _resolve_instance_id(self, value: Any) -> Optional[str]:
         """Resolve a Vast instance id from a direct id or a recipe host alias."""
         text = str(value or "").strip()
@@ -152,10 +160,11 @@ class VastControlHelper:
         skip_if_set = True
         auto_select = False
         create_if_missing = False
-        image = "pytorch/pytorch:latest"
-        disk_gb = 50.0
+        image = None
+        disk_gb = None
         label = None
         direct = False
+        template_name = None
 
         for arg in args:
             if "=" in arg:
@@ -202,6 +211,8 @@ class VastControlHelper:
                     label = value or None
                 elif key == "direct":
                     direct = value.lower() in ("1", "true", "yes", "y")
+                elif key == "template":
+                    template_name = value or None
                 continue
             if host_name is None:
                 host_name = self.executor._interpolate(arg)
@@ -214,6 +225,19 @@ class VastControlHelper:
         else:
             return False, "No host alias provided for vast.pick"
 
+        template = None
+        if template_name:
+            from ..services.vast_templates import get_template
+            try:
+                template = get_template(template_name)
+            except KeyError as exc:
+                return False, str(exc.args[0])
+            gpu_name, num_gpus = gpu_name or template.gpu_name, num_gpus or template.num_gpus
+            min_gpu_ram, max_dph = min_gpu_ram or template.min_gpu_ram, max_dph or template.max_dph
+            image, disk_gb = image or template.image, disk_gb or template.disk_gb
+            label, direct = label or template.label or None, direct or template.direct
+        image, disk_gb = image or "pytorch/pytorch:latest", disk_gb or 50.0
+
         pick_filters = {
             "host_name": host_name,
             "gpu_name": gpu_name,
@@ -228,6 +252,7 @@ class VastControlHelper:
             "disk_gb": disk_gb,
             "label": label,
             "direct": direct,
+            "template": template_name,
         }
         if self.executor.logger:
             self.executor.logger.log_detail("vast_pick", "Picking Vast instance", pick_filters)
@@ -316,6 +341,8 @@ class VastControlHelper:
                     disk=disk_gb,
                     label=label or host_name,
                     direct=direct,
+                    onstart=(template.onstart or None) if template else None,
+                    env=dict(template.env) if template else None,
                 )
                 self.executor.ctx.variables["_vast_instance_id"] = str(new_id)
                 self.executor.ctx.variables["VAST_ID"] = str(new_id)
@@ -396,7 +423,7 @@ class VastControlHelper:
             print(sep)
 
             try:
-                choice = input(f"Enter number (1-{len(instances)}) or instance ID: ").strip()
+                choice = self._prompt(f"Enter number (1-{len(instances)}) or instance ID: ", "vast_pick").strip()
             except (EOFError, KeyboardInterrupt):
                 return False, "Selection cancelled"
 
@@ -653,76 +680,7 @@ class VastControlHelper:
 
     def ensure_ssh_key_attached(self, client: Any, ssh_key_path: str) -> None:
         """Ensure local public SSH key is attached to Vast.ai account."""
-        pub_key_path = os.path.expanduser(ssh_key_path)
-        if not pub_key_path.endswith(".pub"):
-            pub_key_path += ".pub"
-
-        if not os.path.exists(pub_key_path):
-            self.executor.log(f"SSH public key not found: {pub_key_path}")
-            if self.executor.logger:
-                self.executor.logger.log_detail("ssh_key", f"Public key not found: {pub_key_path}", {})
-            return
-
-        with open(pub_key_path, "r") as f:
-            pub_key_content = f.read().strip()
-
-        if not pub_key_content:
-            self.executor.log(f"SSH public key is empty: {pub_key_path}")
-            return
-
-        key_parts = pub_key_content.split()
-        if len(key_parts) < 2:
-            self.executor.log(f"Invalid SSH public key format: {pub_key_path}")
-            return
-
-        key_type = key_parts[0]
-        key_data = key_parts[1]
-
-        try:
-            existing_keys = client.list_ssh_keys()
-            if self.executor.logger:
-                self.executor.logger.log_detail("ssh_key", f"Found {len(existing_keys)} existing keys on Vast.ai", {
-                    "existing_count": len(existing_keys)
-                })
-
-            key_exists = False
-            for existing_key in existing_keys:
-                existing_content = existing_key.get("ssh_key", "")
-                existing_parts = existing_content.split()
-                if len(existing_parts) >= 2 and existing_parts[1] == key_data:
-                    key_exists = True
-                    if self.executor.logger:
-                        self.executor.logger.log_detail("ssh_key", "SSH key already registered on Vast.ai", {
-                            "key_id": existing_key.get("id"),
-                            "label": existing_key.get("label"),
-                        })
-                    break
-
-            if not key_exists:
-                self.executor.log("Adding SSH key to Vast.ai account...")
-                try:
-                    client.add_ssh_key(pub_key_content, label="tmux-trainsh")
-                    self.executor.log("SSH key added successfully")
-                    if self.executor.logger:
-                        self.executor.logger.log_detail("ssh_key", "SSH key added to Vast.ai", {
-                            "key_type": key_type,
-                            "key_path": pub_key_path,
-                        })
-                except Exception as add_err:
-                    err_str = str(add_err).lower()
-                    if "already exists" in err_str or "duplicate" in err_str:
-                        self.executor.log("SSH key already exists on Vast.ai")
-                        if self.executor.logger:
-                            self.executor.logger.log_detail("ssh_key", "SSH key already exists (ignored)", {
-                                "key_type": key_type,
-                            })
-                    else:
-                        raise add_err
-
-        except Exception as e:
-            self.executor.log(f"Warning: Failed to manage SSH keys: {e}")
-            if self.executor.logger:
-                self.executor.logger.log_detail("ssh_key_warning", f"Failed to manage SSH keys: {e}", {})
+        ensure_ssh_key_attached(client, ssh_key_path, log=self.executor.log, logger=self.executor.logger)
 
     def cmd_vast_cost(self, args: List[str]) -> tuple[bool, str]:
         """Handle: vast.cost <instance_id>"""
@@ -7,6 +7,9 @@ import subprocess
 import time
 from typing import Any, Callable, Optional
 
+from ..services.tracing import KIND_CLIENT, span
+from .local_tmux import capture_alternate_screen
+
 
 class WaitHelper:
     """Helper for wait and tmux idle detection logic."""
@@ -26,7 +29,8 @@ class WaitHelper:
     def _run_remote_shell(self, host: str, cmd: str, timeout: int = 10) -> Any:
         """Run a shell command via SSH on remote host."""
         ssh_args = self.build_ssh_args(host, command=cmd, tty=False)
-        return subprocess.run(ssh_args, capture_output=True, text=True, timeout=timeout)
+        with span("ssh", kind=KIND_CLIENT, **{"net.peer.name": host, "ssh.operation": "wait"}):
+            return subprocess.run(ssh_args, capture_output=True, text=True, timeout=timeout)
 
     def run_tmux_cmd(self, host: str, cmd: str, timeout: int = 10) -> Any:
         """Run a raw tmux command on the target host."""
@@ -57,6 +61,16 @@ class WaitHelper:
             return '\n'.join(output_lines[-lines:]) if output_lines else ""
         return ""
 
+    def record_screen(self, host: str, session: str) -> bool:
+        """Store the pane's frame when it runs a full-screen program; True if it does."""
+        frame = capture_alternate_screen(self.executor.get_tmux_client(host), session)
+        if frame is None:
+            return False
+        if self.executor.logger:
+            self.executor.logger.step_screen(self.executor._current_step_num(), frame)
+        self.executor.log(f"    Full-screen program running; view it with: train recipe screen {self.executor.ctx.job_id}")
+        return True
+
     def is_pane_idle(self, host: str, session: str) -> bool:
         """Check if tmux pane is idle using current command + child process count."""
         tmux_client = self.executor.get_tmux_client(host)
@@ -158,11 +172,12 @@ class WaitHelper:
                 for line in process_tree.split('\n')[:5]:
                     self.executor.log(f"      {line[:100]}")
             try:
-                output = self.get_pane_recent_output(host, session, lines=2)
-                if output:
-                    self.executor.log("    Recent output:")
-                    for line in output.split('\n'):
-                        self.executor.log(f"      {line[:80]}")
+                if not self.record_screen(host, session):
+                    output = self.get_pane_recent_output(host, session, lines=2)
+                    if output:
+                        self.executor.log("    Recent output:")
+                        for line in output.split('\n'):
+                            self.executor.log(f"      {line[:80]}")
             except Exception:
                 pass
             time.sleep(poll_interval)
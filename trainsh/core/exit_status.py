@@ -0,0 +1,165 @@
+"""How a recipe run ended, from its exit code and the host's kernel log.
+
+A finished job is classified as `succeeded`, `failed`, `oom_killed`,
+`cuda_oom`, `preempted`, `killed`, `timeout` or `cancelled`. Exit codes follow shell rules
+(128+N for a process killed by signal N) and also accept the negative codes
+that `subprocess` reports. Steps train stopped itself are `timeout` or
+`cancelled` whatever their exit code. Other signal deaths are only called an
+OOM kill or a preemption when the kernel log of the host that ran the failing
+step says so; without that evidence they are `killed`. A failed step whose
+output carries a CUDA out-of-memory error is `cuda_oom`.
+"""
+
+from __future__ import annotations
+
+import re
+import signal
+import subprocess
+from typing import Dict, Optional
+
+OUTCOMES = ("succeeded", "failed", "oom_killed", "cuda_oom", "preempted", "killed", "timeout", "cancelled")
+
+# Event emitted when a step fails because a GPU or the host ran out of memory.
+OOM_EVENT = "run:oom_detected"
+OOM_HINT = "Lower the batch size (or use gradient accumulation / a GPU with more memory) and retry."
+
+# dmesg is restricted on some hosts; journalctl -k reads the same ring buffer.
+KERNEL_LOG_COMMAND = "{ dmesg 2>/dev/null || journalctl -k -n 200 --no-pager 2>/dev/null; } | tail -n 200"
+
+_OOM_PATTERN = re.compile(r"out of memory|oom-kill|oom_reaper|memory cgroup out of memory", re.IGNORECASE)
+_CUDA_OOM_PATTERN = re.compile(
+    r"CUDA out of memory|OutOfMemoryError|CUDA error: out of memory|CUBLAS_STATUS_ALLOC_FAILED|RESOURCE_EXHAUSTED: Out of memory",
+    re.IGNORECASE,
+)
+_SIZE = r"([\d.]+)\s*([KMGT]i?B)"
+_CUDA_STATS = (
+    ("requested_mb", (rf"Tried to allocate {_SIZE}",)),
+    ("total_mb", (rf"{_SIZE} total capacity", rf"total capacity of {_SIZE}")),
+    ("free_mb", (rf"of which {_SIZE} is free", rf"{_SIZE} free")),
+    ("in_use_mb", (rf"this process has {_SIZE} memory in use",)),
+    ("allocated_mb", (rf"{_SIZE} already allocated", rf"{_SIZE} is allocated by PyTorch")),
+    ("reserved_mb", (rf"{_SIZE} reserved in total by PyTorch", rf"{_SIZE} is reserved by PyTorch")),
+)
+_UNIT_MB = {"K": 1 / 1024, "M": 1, "G": 1024, "T": 1024 * 1024}
+_PREEMPT_PATTERN = re.compile(
+    r"preempt(ed|ion)|spot (instance )?(interruption|termination)|instance (is being )?reclaimed|system is going down|power key pressed",
+    re.IGNORECASE,
+)
+
+
+def signal_of(exit_code: Optional[int]) -> int:
+    """Signal number that ended the process, or 0 when it exited normally."""
+    if exit_code is None:
+        return 0
+    code = int(exit_code)
+    if code < 0:
+        return -code
+    if 128 < code < 160:
+        return code - 128
+    return 0
+
+
+def needs_kernel_log(exit_code: Optional[int]) -> bool:
+    """True when the kernel log can tell how a process died (killed by a signal)."""
+    return signal_of(exit_code) in (signal.SIGKILL, signal.SIGTERM)
+
+
+def find_cuda_oom(text: str) -> str:
+    """Last line of `text` reporting a CUDA out-of-memory error, or ""."""
+    for line in reversed(str(text or "").splitlines()):
+        if _CUDA_OOM_PATTERN.search(line):
+            return line.strip()
+    return ""
+
+
+def cuda_memory_stats(text: str) -> Dict[str, object]:
+    """GPU index and memory figures (in MiB) from a PyTorch out-of-memory message."""
+    stats: Dict[str, object] = {}
+    gpu = re.search(r"GPU (\d+)", text or "")
+    if gpu:
+        stats["gpu"] = int(gpu.group(1))
+    for key, patterns in _CUDA_STATS:
+        for pattern in patterns:
+            match = re.search(pattern, text or "")
+            if match:
+                stats[key] = round(float(match.group(1)) * _UNIT_MB[match.group(2)[0].upper()], 1)
+                break
+    return stats
+
+
+def kernel_oom_stats(kernel_log: str) -> Dict[str, object]:
+    """Pid, name and resident memory (MiB) of the last process the kernel OOM killer chose."""
+    matches = list(re.finditer(r"Killed process (\d+) \(([^)]*)\)(.*)", kernel_log or ""))
+    if not matches:
+        return {}
+    last = matches[-1]
+    stats: Dict[str, object] = {"pid": int(last.group(1)), "process": last.group(2)}
+    rss = re.search(r"anon-rss:(\d+)kB", last.group(3))
+    if rss:
+        stats["rss_mb"] = round(int(rss.group(1)) / 1024, 1)
+    return stats
+
+
+def classify_exit(exit_code: Optional[int], kernel_log: str = "", *, stopped: str = "", output: str = "") -> str:
+    """Outcome for a job that ended with `exit_code` (None when it is unknown).
+
+    `stopped` is "timeout" or "cancelled" when train itself signalled the step;
+    `output` is the failing step's output, checked for CUDA out-of-memory errors.
+    """
+    if stopped in ("timeout", "cancelled"):
+        return stopped
+    if exit_code == 0:
+        return "succeeded"
+    if find_cuda_oom(output):
+        return "cuda_oom"
+    sig = signal_of(exit_code)
+    if sig == signal.SIGKILL and _OOM_PATTERN.search(kernel_log or ""):
+        return "oom_killed"
+    if sig and _PREEMPT_PATTERN.search(kernel_log or ""):
+        return "preempted"
+    if sig in (signal.SIGKILL, signal.SIGTERM):
+        return "killed"
+    return "failed"
+
+
+def describe_exit(exit_code: Optional[int]) -> str:
+    """`137 (SIGKILL)`-style text for an exit code."""
+    if exit_code is None:
+        return "unknown"
+    sig = signal_of(exit_code)
+    if not sig:
+        return str(exit_code)
+    try:
+        name = signal.Signals(sig).name
+    except ValueError:
+        name = f"signal {sig}"
+    return f"{exit_code} ({name})"
+
+
+def read_kernel_log(host: str, build_ssh_args=None, timeout: int = 15) -> str:
+    """Recent kernel messages on `host` ("local" or an SSH spec); empty when unavailable."""
+    if host == "local" or build_ssh_args is None:
+        args = ["sh", "-c", KERNEL_LOG_COMMAND]
+    else:
+        args = build_ssh_args(host, command=KERNEL_LOG_COMMAND, tty=False)
+    try:
+        result = subprocess.run(args, capture_output=True, text=True, timeout=timeout)
+    except (OSError, subprocess.TimeoutExpired):
+        return ""
+    return result.stdout or ""
+
+
+__all__ = [
+    "KERNEL_LOG_COMMAND",
+    "OOM_EVENT",
+    "OOM_HINT",
+    "OUTCOMES",
+    "classify_exit",
+    "cuda_memory_stats",
+    "describe_exit",
+    "find_cuda_oom",
+    "kernel_oom_stats",
+    "needs_kernel_log",
+    "read_kernel_log",
+    "signal_of",
+]
@@ -0,0 +1,166 @@
+"""Refuse (or ask before) launching a GPU job onto a card another process already holds."""
+
+from __future__ import annotations
+
+import re
+import subprocess
+from typing import Any, Callable, Dict, List, Optional
+
+GUARD_MODES = ("off", "warn", "confirm", "fail")
+GUARD_TIMEOUT_SECS = 15
+_DONE_MARKER = "trainsh-gpu-guard-done"
+
+# Commands that start long-lived GPU work; setup steps (pip, git, ...) never match.
+_LAUNCH_RE = re.compile(
+    r"(?<![\w./-])(?:"
+    r"torchrun|accelerate\s+launch|deepspeed|vllm\s+serve"
+    r"|python[\d.]*\s+(?:-[uOB]+\s+)*(?:\S+\.py\b|-m\s+(?!pip\b|venv\b|ensurepip\b)\w)"
+    r")"
+)
+_VISIBLE_RE = re.compile(r"CUDA_VISIBLE_DEVICES=(['\"]?)([^\s'\";]*)\1")
+
+
+def normalize_mode(value: Any, default: str = "confirm") -> str:
+    text = str(value if value is not None else "").strip().lower()
+    if text in {"false", "no", "0", "none", "disabled"}:
+        return "off"
+    if text in {"true", "yes", "1", "on"}:
+        return default
+    return text if text in GUARD_MODES else default
+
+
+def looks_like_gpu_launch(command: str) -> bool:
+    """True for training/serving launches such as `torchrun`, `python train.py`, `vllm serve`."""
+    return bool(_LAUNCH_RE.search(str(command or "")))
+
+
+def requested_gpus(command: str) -> Optional[List[str]]:
+    """GPUs selected by the last `CUDA_VISIBLE_DEVICES=` in the command.
+
+    None means every GPU on the host; an empty list means the command hides all GPUs.
+    """
+    matches = _VISIBLE_RE.findall(str(command or ""))
+    if not matches:
+        return None
+    value = matches[-1][1].strip()
+    if not value or value == "-1":
+        return []
+    return [item.strip() for item in value.split(",") if item.strip()]
+
+
+def probe_script() -> str:
+    return (
+        "command -v nvidia-smi >/dev/null 2>&1 || { echo " + _DONE_MARKER + "; exit 0; }; "
+        "echo @@gpus; nvidia-smi --query-gpu=index,uuid,memory.used,memory.total --format=csv,noheader,nounits; "
+        "echo @@apps; nvidia-smi --query-compute-apps=pid,gpu_uuid,used_memory,process_name "
+        "--format=csv,noheader,nounits 2>/dev/null; "
+        f"echo {_DONE_MARKER}"
+    )
+
+
+def _int(value: str) -> int:
+    try:
+        return int(float(value.strip()))
+    except ValueError:
+        return 0
+
+
+def find_busy_gpus(output: str, gpus: Optional[List[str]], *, min_memory_mb: int) -> List[Dict[str, Any]]:
+    """GPUs from the probe output that are selected and already hold `min_memory_mb` or more."""
+    cards: List[Dict[str, Any]] = []
+    apps: List[List[str]] = []
+    section = ""
+    for line in str(output or "").splitlines():
+        line = line.strip()
+        if line.startswith("@@"):
+            section = line[2:]
+            continue
+        fields = [field.strip() for field in line.split(",")]
+        if section == "gpus" and len(fields) >= 4:
+            cards.append({
+                "index": fields[0],
+                "uuid": fields[1],
+                "memory_used_mb": _int(fields[2]),
+                "memory_total_mb": _int(fields[3]),
+                "processes": [],
+            })
+        elif section == "apps" and len(fields) >= 3:
+            apps.append(fields)
+
+    by_uuid = {card["uuid"]: card for card in cards}
+    for fields in apps:
+        card = by_uuid.get(fields[1])
+        if card is not None:
+            card["processes"].append({
+                "pid": _int(fields[0]),
+                "memory_mb": _int(fields[2]),
+                "name": fields[3] if len(fields) > 3 else "",
+            })
+
+    selected = cards if gpus is None else [
+        card for card in cards if card["index"] in gpus or card["uuid"] in gpus
+    ]
+    return [card for card in selected if card["memory_used_mb"] >= min_memory_mb]
+
+
+def gpu_indices(output: str) -> List[str]:
+    """GPU indices listed in the probe output."""
+    return [card["index"] for card in find_busy_gpus(output, None, min_memory_mb=0)]
+
+
+def probe_host(
+    host: str,
+    *,
+    build_ssh_args: Callable[..., List[str]],
+    timeout: int = GUARD_TIMEOUT_SECS,
+) -> Optional[str]:
+    """Raw probe output for one host, or None when the host could not be probed."""
+    script = probe_script()
+    args = ["sh", "-c", script] if host == "local" else build_ssh_args(host, command=script, tty=False)
+    try:
+        result = subprocess.run(args, capture_output=True, text=True, timeout=timeout)
+    except (OSError, subprocess.TimeoutExpired):
+        return None
+    output = result.stdout or ""
+    return output if _DONE_MARKER in output else None
+
+
+def check_host_gpus(
+    host: str,
+    gpus: Optional[List[str]],
+    *,
+    min_memory_mb: int,
+    build_ssh_args: Callable[..., List[str]],
+    timeout: int = GUARD_TIMEOUT_SECS,
+) -> Optional[List[Dict[str, Any]]]:
+    """Busy selected GPUs on one host, or None when the host could not be probed."""
+    output = probe_host(host, build_ssh_args=build_ssh_args, timeout=timeout)
+    if output is None:
+        return None
+    return find_busy_gpus(output, gpus, min_memory_mb=min_memory_mb)
+
+
+def describe(busy: List[Dict[str, Any]]) -> str:
+    """One-line summary, e.g. `GPU 0 (30.0/80.0 GiB; pid 4242 python)`."""
+    parts = []
+    for card in busy:
+        text = f"GPU {card['index']} ({card['memory_used_mb'] / 1024:.1f}/{card['memory_total_mb'] / 1024:.1f} GiB"
+        holders = ", ".join(
+            f"pid {proc['pid']} {proc['name']}".rstrip() for proc in card["processes"][:3]
+        )
+        parts.append(f"{text}; {holders})" if holders else f"{text})")
+    return ", ".join(parts)
+
+
+__all__ = [
+    "GUARD_MODES",
+    "check_host_gpus",
+    "describe",
+    "find_busy_gpus",
+    "gpu_indices",
+    "looks_like_gpu_launch",
+    "normalize_mode",
+    "probe_host",
+    "probe_script",
+    "requested_gpus",
+]
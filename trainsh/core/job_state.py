@@ -23,6 +23,7 @@ class JobState:
     status: str = "running"  # running, completed, failed, cancelled
     variables: Dict[str, str] = field(default_factory=dict)
     hosts: Dict[str, str] = field(default_factory=dict)
+    host_refs: Dict[str, str] = field(default_factory=dict)  # window -> vast:<id> it was resolved from
     storages: Dict[str, object] = field(default_factory=dict)
     window_sessions: Dict[str, str] = field(default_factory=dict)
     next_window_index: int = 0
@@ -32,6 +33,14 @@ class JobState:
     vast_start_time: Optional[str] = None
     runpod_pod_id: Optional[str] = None
     runpod_start_time: Optional[str] = None
+    inflight_steps: Dict[str, Dict[str, str]] = field(default_factory=dict)
+    gpu_assignments: Dict[str, Dict[str, object]] = field(default_factory=dict)
+    pid: int = 0
+    parent_job_id: str = ""
+    run_type: str = "manual"  # manual, scheduled, test
+    on_complete_job_ids: List[str] = field(default_factory=list)
+    exit_code: Optional[int] = None
+    outcome: str = ""  # succeeded, failed, oom_killed, cuda_oom, preempted, killed, timeout, cancelled
     created_at: str = ""
     updated_at: str = ""
     error: str = ""
@@ -62,6 +71,7 @@ class JobStateManager:
                 "status": state.status,
                 "variables": dict(state.variables),
                 "hosts": dict(state.hosts),
+                "host_refs": dict(state.host_refs),
                 "storages": dict(state.storages),
                 "window_sessions": dict(state.window_sessions),
                 "next_window_index": int(state.next_window_index),
@@ -71,6 +81,14 @@ class JobStateManager:
                 "vast_start_time": state.vast_start_time,
                 "runpod_pod_id": state.runpod_pod_id,
                 "runpod_start_time": state.runpod_start_time,
+                "inflight_steps": dict(state.inflight_steps),
+                "gpu_assignments": dict(state.gpu_assignments),
+                "pid": int(state.pid or 0),
+                "parent_job_id": state.parent_job_id,
+                "run_type": state.run_type,
+                "on_complete_job_ids": list(state.on_complete_job_ids),
+                "exit_code": state.exit_code,
+                "outcome": state.outcome,
                 "error": state.error,
                 "created_at": state.created_at,
                 "updated_at": state.updated_at,
@@ -90,6 +108,7 @@ class JobStateManager:
             status=str(row.get("status", "running")),
             variables=dict(row.get("variables", {}) or {}),
             hosts=dict(row.get("hosts", {}) or {}),
+            host_refs=dict(row.get("host_refs", {}) or {}),
             storages=dict(row.get("storages", {}) or {}),
             window_sessions=dict(row.get("window_sessions", {}) or {}),
             next_window_index=int(row.get("next_window_index", 0) or 0),
@@ -99,6 +118,14 @@ class JobStateManager:
             vast_start_time=row.get("vast_start_time"),
             runpod_pod_id=row.get("runpod_pod_id"),
             runpod_start_time=row.get("runpod_start_time"),
+            inflight_steps=dict(row.get("inflight_steps", {}) or {}),
+            gpu_assignments=dict(row.get("gpu_assignments", {}) or {}),
+            pid=int(row.get("pid", 0) or 0),
+            parent_job_id=str(row.get("parent_job_id", "") or ""),
+            run_type=str(row.get("run_type", "") or "manual"),
+            on_complete_job_ids=[str(item) for item in row.get("on_complete_job_ids", []) or []],
+            exit_code=None if row.get("exit_code") is None else int(row["exit_code"]),
+            outcome=str(row.get("outcome", "") or ""),
             created_at=str(row.get("created_at", "")),
             updated_at=str(row.get("updated_at", "")),
             error=str(row.get("error", "") or ""),
@@ -115,6 +142,7 @@ class JobStateManager:
         record = self.store.latest_checkpoint_for_recipe(
             recipe_path,
             statuses={"running", "failed"},
+            exclude_run_types={"test"},
         )
         return self.load(str(record.get("run_id", ""))) if record else None
 
@@ -126,6 +154,33 @@ class JobStateManager:
         rows = self.store.list_checkpoints(status="running")
         return [state for row in rows if (state := self.load(str(row.get("run_id", ""))))]
 
+    def list_orphaned(self, *, claimed_by: int = 0) -> List[JobState]:
+        """Running jobs whose owning `train` process is gone, plus those handed to `claimed_by`.
+
+        Mock runs from `train recipe test` never count: resuming one would run real steps.
+        """
+        return [
+            state
+            for state in self.list_running()
+            if state.run_type != "test" and state.pid and (state.pid == claimed_by or not _pid_alive(state.pid))
+        ]
+
+    def retarget_hosts(self, hostname: str, port: int, new_spec: str) -> List[str]:
+        """Point resumable jobs' windows on hostname:port at `new_spec`; returns the updated job ids."""
+        updated = []
+        for state in self.list_all(limit=200):
+            if state.status not in ("running", "failed"):
+                continue
+            changed = False
+            for name, spec in list(state.hosts.items()):
+                if spec != new_spec and spec_endpoint(spec) == (hostname, int(port)):
+                    state.hosts[name] = new_spec
+                    changed = True
+            if changed:
+                self.save(state)
+                updated.append(state.job_id)
+        return updated
+
     def cleanup_old(self, days: int = 7) -> int:
         cutoff = (datetime.now() - timedelta(days=days)).isoformat()
         return self.store.cleanup_checkpoints(
@@ -134,6 +189,43 @@ class JobStateManager:
         )
 
 
+def spec_endpoint(spec: str) -> tuple[str, int]:
+    """(hostname, port) of an SSH spec such as `root@1.2.3.4 -p 2222`."""
+    import shlex
+
+    try:
+        tokens = shlex.split(str(spec or ""))
+    except ValueError:
+        return "", 0
+    hostname, port = "", 22
+    i = 0
+    while i < len(tokens):
+        token = tokens[i]
+        if token == "-p" and i + 1 < len(tokens):
+            port = int(tokens[i + 1]) if tokens[i + 1].isdigit() else 22
+            i += 2
+            continue
+        if token.startswith("-"):
+            i += 2 if token in ("-i", "-J", "-o", "-F", "-l") else 1
+            continue
+        if not hostname:
+            hostname = token.split("@", 1)[-1]
+        i += 1
+    return hostname, port
+
+
+def _pid_alive(pid: int) -> bool:
+    try:
+        os.kill(int(pid), 0)
+    except ProcessLookupError:
+        return False
+    except PermissionError:
+        return True
+    except OSError:
+        return False
+    return True
+
+
 def generate_job_id() -> str:
     """Generate a unique job ID."""
     import uuid
@@ -0,0 +1,171 @@
+"""Policy for commands a recipe runs on this machine.
+
+`local_policy.deny` / `local_policy.allow` are regexes matched against each
+command segment (split on `;`, `&&`, `||`, `|`). Privileged segments (`sudo`,
+`doas`, `su`, `rm -rf` outside the working directory) follow
+`local_policy.privileged`: off | warn (default) | confirm (ask on a TTY, else
+fail) | fail.
+Bundled examples, and recipes trusted with `train recipe trust` until their file
+changes, skip the allowlist and the privileged check; deny patterns always apply.
+"""
+
+from __future__ import annotations
+
+import hashlib
+import json
+import os
+import re
+import shlex
+from dataclasses import dataclass
+from datetime import datetime
+from pathlib import Path
+from typing import Any, Dict, List, Optional
+
+from ..constants import PROFILE_CONFIG_DIR
+from .gpu_guard import normalize_mode
+
+TRUST_FILE = PROFILE_CONFIG_DIR / "trusted_recipes.json"
+_SEGMENT_SPLIT = re.compile(r"\|\||&&|[;|\n]")
+_ELEVATE = {"sudo", "doas", "su", "pkexec"}
+
+
+@dataclass
+class PolicyDecision:
+    """What to do with one local command: allow | warn | confirm | deny."""
+
+    action: str
+    reason: str = ""
+    segment: str = ""
+
+
+def policy_settings(config: Dict[str, Any]) -> Dict[str, Any]:
+    """`local_policy` config with invalid patterns dropped and the mode normalized."""
+    section = (config or {}).get("local_policy", {}) or {}
+
+    def patterns(key: str) -> List[re.Pattern]:
+        compiled = []
+        for item in section.get(key, []) or []:
+            try:
+                compiled.append(re.compile(str(item)))
+            except re.error:
+                continue
+        return compiled
+
+    return {
+        "privileged": normalize_mode(section.get("privileged", "warn")),
+        "allow": patterns("allow"),
+        "deny": patterns("deny"),
+    }
+
+
+def command_segments(command: str) -> List[str]:
+    return [part.strip() for part in _SEGMENT_SPLIT.split(str(command or "")) if part.strip()]
+
+
+def _privileged_reason(segment: str, workspace: Path) -> str:
+    try:
+        tokens = shlex.split(segment)
+    except ValueError:
+        tokens = segment.split()
+    while tokens and re.fullmatch(r"\w+=.*", tokens[0]):
+        tokens = tokens[1:]
+    if not tokens:
+        return ""
+    if os.path.basename(tokens[0]) in _ELEVATE:
+        return f"runs {os.path.basename(tokens[0])}"
+    if os.path.basename(tokens[0]) != "rm":
+        return ""
+    short = "".join(token[1:] for token in tokens[1:] if token.startswith("-") and not token.startswith("--"))
+    recursive = "r" in short.lower() or "--recursive" in tokens
+    if not recursive:
+        return ""
+    for target in (token for token in tokens[1:] if not token.startswith("-")):
+        resolved = Path(os.path.expanduser(target))
+        resolved = (resolved if resolved.is_absolute() else workspace / resolved).resolve()
+        if resolved != workspace and workspace not in resolved.parents:
+            return f"removes {target} outside {workspace}"
+    return ""
+
+
+def check_command(command: str, settings: Dict[str, Any], *, workspace: Optional[str] = None, trusted: bool = False) -> PolicyDecision:
+    """Decide whether a local command may run under the given policy settings."""
+    root = Path(workspace or os.getcwd()).resolve()
+    for segment in command_segments(command):
+        for pattern in settings.get("deny", []):
+            if pattern.search(segment):
+                return PolicyDecision("deny", f"matches deny pattern {pattern.pattern!r}", segment)
+        if trusted:
+            continue
+        allow = settings.get("allow", [])
+        if allow and not any(pattern.match(segment) for pattern in allow):
+            return PolicyDecision("deny", "not in local_policy.allow", segment)
+        mode = settings.get("privileged", "warn")
+        reason = _privileged_reason(segment, root) if mode != "off" else ""
+        if reason:
+            return PolicyDecision({"warn": "warn", "confirm": "confirm"}.get(mode, "deny"), f"privileged: {reason}", segment)
+    return PolicyDecision("allow")
+
+
+def _recipe_digest(path: Path) -> str:
+    return hashlib.sha256(path.read_bytes()).hexdigest()
+
+
+def load_trust(path: Optional[Path] = None) -> Dict[str, Dict[str, Any]]:
+    try:
+        data = json.loads(Path(path or TRUST_FILE).read_text(encoding="utf-8"))
+    except (OSError, ValueError):
+        return {}
+    return data if isinstance(data, dict) else {}
+
+
+def _save_trust(records: Dict[str, Dict[str, Any]], path: Optional[Path] = None) -> None:
+    target = Path(path or TRUST_FILE)
+    target.parent.mkdir(parents=True, exist_ok=True)
+    target.write_text(json.dumps(records, indent=2, sort_keys=True) + "\n", encoding="utf-8")
+
+
+def trust_recipe(recipe_path: str, *, path: Optional[Path] = None) -> Dict[str, Any]:
+    """Trust the current contents of a recipe file."""
+    recipe = Path(recipe_path).expanduser().resolve()
+    records = load_trust(path)
+    records[str(recipe)] = {"sha256": _recipe_digest(recipe), "trusted_at": datetime.now().isoformat(timespec="seconds")}
+    _save_trust(records, path)
+    return records[str(recipe)]
+
+
+def revoke_recipe(recipe_path: str, *, path: Optional[Path] = None) -> bool:
+    recipe = str(Path(recipe_path).expanduser().resolve())
+    records = load_trust(path)
+    if records.pop(recipe, None) is None:
+        return False
+    _save_trust(records, path)
+    return True
+
+
+def is_trusted(recipe_path: Optional[str], *, path: Optional[Path] = None) -> bool:
+    """True for bundled examples and for trusted recipe files unchanged since."""
+    if not recipe_path:
+        return False
+    from ..commands.recipe import _is_bundled_example
+
+    if _is_bundled_example(str(recipe_path)):
+        return True
+    recipe = Path(recipe_path).expanduser().resolve()
+    record = load_trust(path).get(str(recipe))
+    try:
+        return bool(record) and record.get("sha256") == _recipe_digest(recipe)
+    except OSError:
+        return False
+
+
+__all__ = [
+    "PolicyDecision",
+    "TRUST_FILE",
+    "check_command",
+    "command_segments",
+    "is_trusted",
+    "load_trust",
+    "policy_settings",
+    "revoke_recipe",
+    "trust_recipe",
+]
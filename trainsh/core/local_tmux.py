@@ -10,6 +10,8 @@ import subprocess
 from dataclasses import dataclass
 from typing import Optional
 
+from ..services.tracing import span
+
 
 @dataclass
 class TmuxCmdResult:
@@ -52,13 +54,14 @@ class LocalTmuxClient:
         if not self._tmux_binary_available:
             return self._unavailable()
         try:
-            cp = subprocess.run(
-                ["tmux", *args],
-                capture_output=True,
-                text=True,
-                timeout=timeout,
-                env=self._tmux_env(),
-            )
+            with span("tmux", **{"tmux.operation": args[0] if args else ""}):
+                cp = subprocess.run(
+                    ["tmux", *args],
+                    capture_output=True,
+                    text=True,
+                    timeout=timeout,
+                    env=self._tmux_env(),
+                )
             return TmuxCmdResult(cp.returncode, cp.stdout or "", cp.stderr or "")
         except subprocess.TimeoutExpired:
             return TmuxCmdResult(124, "", "tmux command timed out")
@@ -155,6 +158,9 @@ class LocalTmuxClient:
     def kill_pane(self, pane_id: str) -> TmuxCmdResult:
         return self.run("kill-pane", "-t", pane_id)
 
+    def resize_window(self, target: str, width: int, height: int) -> TmuxCmdResult:
+        return self.run("resize-window", "-t", target, "-x", str(width), "-y", str(height))
+
     def send_keys(self, target: str, text: str, enter: bool = True, literal: bool = True) -> TmuxCmdResult:
         if literal:
             result = self.run("send-keys", "-t", target, "-l", text)
@@ -182,3 +188,19 @@ class LocalTmuxClient:
         if nested:
             return f"TMUX= tmux attach -t {quoted} || TMUX= tmux new-session -A -s {quoted}"
         return f"tmux attach -t {quoted} || tmux new-session -A -s {quoted}"
+
+
+def capture_alternate_screen(client: object, target: str) -> Optional[str]:
+    """Visible frame of a pane running a full-screen program, or None in normal mode.
+
+    Programs such as htop, nvtop or curses installers draw on tmux's alternate
+    screen, where scrollback lines are meaningless; the current frame is what
+    describes their progress. Works with local and remote tmux clients.
+    """
+    mode = client.display_message(target, "#{alternate_on}")
+    if mode.returncode != 0 or str(mode.stdout).strip() != "1":
+        return None
+    frame = client.capture_pane(target)
+    if frame.returncode != 0:
+        return None
+    return "\n".join(line.rstrip() for line in str(frame.stdout).splitlines()).rstrip("\n") + "\n"
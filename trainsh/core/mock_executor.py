@@ -0,0 +1,365 @@
+"""Mock execution backend for `train recipe test`: run a recipe's DAG against canned step results.
+
+Steps that only touch recipe variables (set_var, xcom, branches on `var:`/`env:`
+conditions, groups, ...) run for real; everything that would reach a host,
+a cloud API or storage answers from the mock file instead, so conditions,
+retries and variable flow can be checked without starting an instance.
+"""
+
+from __future__ import annotations
+
+import os
+import tempfile
+import threading
+import uuid
+from dataclasses import dataclass, field
+from typing import Any, Callable, Dict, List, Optional, Tuple
+
+import yaml
+
+from ..constants import RECIPE_FILE_EXTENSIONS
+from .executor_main import DSLExecutor
+from .recipe_models import StepType
+
+# Provider operations that only read or write recipe state; they run for real under the mock.
+LOGIC_OPERATIONS = {
+    "util.set_var",
+    "util.xcom_push",
+    "util.xcom_pull",
+    "util.fail",
+    "util.empty",
+    "util.noop",
+    "group.run",
+}
+# These run for real only when their condition is a `var:`/`env:` check; host checks are mocked.
+CONDITION_OPERATIONS = {"util.branch", "util.short_circuit", "util.skip_if", "util.skip_if_not", "util.assert"}
+
+_RESPONSE_KEYS = {"exit_code", "output", "variables"}
+_EXPECT_KEYS = {"success", "steps", "attempts", "variables"}
+
+
+class MockSpecError(ValueError):
+    """A mock file that cannot be used."""
+
+
+@dataclass
+class MockResponse:
+    """Canned result of one step attempt."""
+
+    exit_code: int = 0
+    output: str = ""
+    variables: Dict[str, str] = field(default_factory=dict)
+
+
+@dataclass
+class MockSpec:
+    """Canned results per step id or operation, plus what the run is expected to do."""
+
+    default: MockResponse = field(default_factory=MockResponse)
+    steps: Dict[str, List[MockResponse]] = field(default_factory=dict)
+    operations: Dict[str, List[MockResponse]] = field(default_factory=dict)
+    done: List[str] = field(default_factory=list)
+    variables: Dict[str, str] = field(default_factory=dict)
+    expect: Dict[str, Any] = field(default_factory=dict)
+
+    def responses_for(self, step_id: str, operation: str) -> Optional[List[MockResponse]]:
+        """Responses for a step: by id, then `provider.operation`, then provider alone."""
+        if step_id in self.steps:
+            return self.steps[step_id]
+        if operation in self.operations:
+            return self.operations[operation]
+        return self.operations.get(operation.split(".", 1)[0])
+
+
+def _response(value: Any, where: str) -> MockResponse:
+    if value is None:
+        return MockResponse()
+    if isinstance(value, (int, str)) and not isinstance(value, bool):
+        # `train: 1` is shorthand for an exit code, `fetch: "text"` for output.
+        return MockResponse(exit_code=value) if isinstance(value, int) else MockResponse(output=value)
+    if not isinstance(value, dict):
+        raise MockSpecError(f"{where}: expected a mapping, got {type(value).__name__}")
+    unknown = sorted(set(value) - _RESPONSE_KEYS)
+    if unknown:
+        raise MockSpecError(f"{where}: unknown key(s) {', '.join(unknown)}")
+    try:
+        exit_code = int(value.get("exit_code", 0))
+    except (TypeError, ValueError):
+        raise MockSpecError(f"{where}: exit_code must be an integer") from None
+    variables = value.get("variables") or {}
+    if not isinstance(variables, dict):
+        raise MockSpecError(f"{where}: variables must be a mapping")
+    return MockResponse(
+        exit_code=exit_code,
+        output=str(value.get("output", "") or ""),
+        variables={str(key): str(val) for key, val in variables.items()},
+    )
+
+
+def _responses(value: Any, where: str) -> List[MockResponse]:
+    """One response, or a list of them for successive attempts (the last one repeats)."""
+    if isinstance(value, list):
+        if not value:
+            raise MockSpecError(f"{where}: empty response list")
+        return [_response(item, f"{where}[{index}]") for index, item in enumerate(value)]
+    return [_response(value, where)]
+
+
+def parse_mock_spec(data: Any) -> MockSpec:
+    """Validate a mock mapping (`default`, `steps`, `operations`, `done`, `variables`, `expect`)."""
+    data = {} if data is None else data
+    if not isinstance(data, dict):
+        raise MockSpecError("mock file must contain a mapping")
+    unknown = sorted(set(data) - {"default", "steps", "operations", "done", "variables", "expect"})
+    if unknown:
+        raise MockSpecError(f"unknown top-level key(s): {', '.join(unknown)}")
+    for key in ("steps", "operations", "variables", "expect"):
+        if not isinstance(data.get(key) or {}, dict):
+            raise MockSpecError(f"{key} must be a mapping")
+    expect = dict(data.get("expect") or {})
+    unknown = sorted(set(expect) - _EXPECT_KEYS)
+    if unknown:
+        raise MockSpecError(f"expect: unknown key(s) {', '.join(unknown)}")
+    done = data.get("done") or []
+    if not isinstance(done, list):
+        raise MockSpecError("done must be a list of step ids")
+    return MockSpec(
+        default=_response(data.get("default"), "default"),
+        steps={str(key): _responses(value, f"steps.{key}") for key, value in (data.get("steps") or {}).items()},
+        operations={
+            str(key).lower(): _responses(value, f"operations.{key}") for key, value in (data.get("operations") or {}).items()
+        },
+        done=[str(item) for item in done],
+        variables={str(key): str(value) for key, value in (data.get("variables") or {}).items()},
+        expect=expect,
+    )
+
+
+def load_mock_spec(path: str) -> MockSpec:
+    """Read a YAML (or JSON) mock file."""
+    try:
+        with open(os.path.expanduser(path), "r", encoding="utf-8") as handle:
+            data = yaml.safe_load(handle)
+    except OSError as exc:
+        raise MockSpecError(f"cannot read {path}: {exc}") from exc
+    except yaml.YAMLError as exc:
+        raise MockSpecError(f"invalid YAML in {path}: {exc}") from exc
+    return parse_mock_spec(data)
+
+
+@dataclass
+class MockCall:
+    """One mocked step attempt."""
+
+    step_id: str
+    try_number: int
+    operation: str
+    exit_code: int
+
+
+class MockExecutor(DSLExecutor):
+    """DSL executor whose host, cloud and storage work is answered by a `MockSpec`."""
+
+    def __init__(self, recipe, spec: MockSpec, **kwargs: Any):
+        super().__init__(recipe, **kwargs)
+        self.mock = spec
+        self.mock_calls: List[MockCall] = []
+        self._mock_lock = threading.Lock()
+
+    def _operation_key(self, step) -> str:
+        if getattr(step, "provider", "") or getattr(step, "command", "") == "provider":
+            provider, operation, _params = self._extract_provider_metadata(step)
+            return f"{provider}.{operation}"
+        kind = getattr(step, "type", None)
+        if kind == StepType.CONTROL:
+            return str(getattr(step, "command", "") or "control").lower()
+        return str(getattr(kind, "value", kind) or "step")
+
+    def _runs_for_real(self, step, operation: str) -> bool:
+        if operation in LOGIC_OPERATIONS:
+            return True
+        if operation in CONDITION_OPERATIONS:
+            _provider, _operation, params = self._extract_provider_metadata(step)
+            condition = self._interpolate(str(params.get("condition", ""))).strip()
+            return condition.startswith(("var:", "env:"))
+        return False
+
+    def _execute_step(self, step) -> tuple[bool, str]:
+        step = self._coerce_step(step)
+        step_id = self._current_step_id() or str(getattr(step, "id", "") or "")
+        operation = self._operation_key(step)
+        responses = self.mock.responses_for(step_id, operation)
+        if responses is None and self._runs_for_real(step, operation):
+            return super()._execute_step(step)
+
+        try_number = self._current_try_number()
+        responses = responses or [self.mock.default]
+        response = responses[min(try_number, len(responses)) - 1]
+        with self._mock_lock:
+            self.mock_calls.append(MockCall(step_id, try_number, operation, response.exit_code))
+        self._note_exit_code(response.exit_code)
+        self._note_step_output(response.output)
+        if response.exit_code == 0:
+            self.ctx.variables.update(response.variables)
+            capture_var = getattr(step, "capture_var", "") or (getattr(step, "params", None) or {}).get("capture_var")
+            if isinstance(capture_var, str) and capture_var:
+                self.ctx.variables[capture_var] = response.output.rstrip("\r\n")
+        self.log(f"Mock {operation} → exit {response.exit_code}")
+        return response.exit_code == 0, response.output or f"mock exit {response.exit_code}"
+
+    def _step_already_done(self, step: object, command: str) -> Tuple[bool, str]:
+        step_id = self._current_step_id() or str(getattr(step, "id", "") or "")
+        if step_id in self.mock.done:
+            return True, f"mock done_check: {command}"
+        return False, ""
+
+    def _extract_step_retry_delay(self, step) -> int:
+        """Retries fire immediately; the delay only matters against real hosts."""
+        return 0
+
+    def _run_preflight(self) -> bool:
+        return True
+
+    def _start_energy_meter(self):
+        return None
+
+    def _start_tracking(self):
+        return None
+
+    def _start_trace(self) -> None:
+        return None
+
+    def _run_completion_hooks(self, success: bool, exit_code: Optional[int] = None, *, cancelled: bool = False) -> List[str]:
+        """Name the `on_complete` recipes that would start instead of starting them."""
+        for hook in getattr(self.recipe, "completion_hooks", []) or []:
+            if hook.get("when", "always") in {"always", "completed" if success else "failed"}:
+                self.log(f"Mock on_complete: would run {hook.get('recipe', '?')}")
+        return []
+
+
+class _EventRecorder:
+    """Callback sink keeping each step's last `step_end` event."""
+
+    def __init__(self):
+        self.steps: Dict[str, Dict[str, Any]] = {}
+
+    def send(self, event) -> None:
+        if event.event == "step_end":
+            payload = dict(event.payload)
+            self.steps[str(payload.get("step_id") or event.step_num)] = {
+                "state": str(payload.get("state") or ""),
+                "attempts": int(event.try_number or 1),
+                "output": str(payload.get("output") or ""),
+            }
+
+
+@dataclass
+class MockRunReport:
+    """What a mock run did, and which expectations it missed."""
+
+    job_id: str
+    success: bool
+    steps: Dict[str, Dict[str, Any]]
+    variables: Dict[str, str]
+    calls: List[MockCall]
+    failures: List[str] = field(default_factory=list)
+
+    @property
+    def passed(self) -> bool:
+        return not self.failures
+
+    def to_dict(self) -> Dict[str, Any]:
+        return {
+            "job_id": self.job_id,
+            "success": self.success,
+            "passed": self.passed,
+            "failures": list(self.failures),
+            "steps": self.steps,
+            "variables": self.variables,
+            "calls": [call.__dict__ for call in self.calls],
+        }
+
+
+def check_expectations(report: MockRunReport, expect: Dict[str, Any]) -> List[str]:
+    """Compare a run with `expect`; with no expectations the run itself must succeed."""
+    failures: List[str] = []
+    wanted = expect.get("success", True)
+    if bool(wanted) != report.success:
+        failures.append(f"run {'succeeded' if report.success else 'failed'}, expected {'success' if wanted else 'failure'}")
+    for step_id, state in (expect.get("steps") or {}).items():
+        actual = report.steps.get(str(step_id), {}).get("state", "not run")
+        if str(state).lower() != actual:
+            failures.append(f"step {step_id}: {actual}, expected {state}")
+    for step_id, attempts in (expect.get("attempts") or {}).items():
+        actual = report.steps.get(str(step_id), {}).get("attempts", 0)
+        if int(attempts) != actual:
+            failures.append(f"step {step_id}: {actual} attempt(s), expected {attempts}")
+    for name, value in (expect.get("variables") or {}).items():
+        actual = report.variables.get(str(name))
+        if actual != str(value):
+            failures.append(f"variable {name}: {actual!r}, expected {str(value)!r}")
+    return failures
+
+
+def run_mock_recipe(
+    path: str,
+    spec: MockSpec,
+    *,
+    var_overrides: Optional[Dict[str, str]] = None,
+    log_callback: Optional[Callable[[str], None]] = None,
+    executor_name: Optional[str] = None,
+) -> MockRunReport:
+    """Run a recipe file under `MockExecutor`.
+
+    Checkpoints, runs and events go to a throwaway state directory, so a mock run
+    never shows up in `recipe resume`, orphan reconnects, the scheduler or `logs`.
+    """
+    from ..pyrecipe import load_recipe
+    from ..runtime import JsonlCallbackSink
+
+    path = os.path.abspath(os.path.expanduser(path))
+    if not path.endswith(RECIPE_FILE_EXTENSIONS):
+        raise ValueError(f"recipe tests only support recipe files ({', '.join(RECIPE_FILE_EXTENSIONS)})")
+    recipe = load_recipe(path)
+    recipe.variables.update(spec.variables)
+    recipe.variables.update(var_overrides or {})
+    recorder = _EventRecorder()
+    with tempfile.TemporaryDirectory(prefix="train-recipe-test-") as state_dir:
+        executor = MockExecutor(
+            recipe,
+            spec,
+            log_callback=log_callback or (lambda _msg: None),
+            job_id=f"test-{uuid.uuid4().hex[:8]}",
+            recipe_path=path,
+            callback_sinks=[JsonlCallbackSink(state_dir), recorder],
+            executor_name=executor_name or recipe.executor or "sequential",
+            executor_kwargs={**dict(recipe.executor_kwargs or {}), "preflight": False},
+            run_type="test",
+            runtime_state=state_dir,
+        )
+        try:
+            success = executor.execute()
+        finally:
+            executor.close()
+    report = MockRunReport(
+        job_id=executor.ctx.job_id,
+        success=success,
+        steps=recorder.steps,
+        variables={str(key): str(value) for key, value in executor.ctx.variables.items()},
+        calls=list(executor.mock_calls),
+    )
+    report.failures = check_expectations(report, spec.expect)
+    return report
+
+
+__all__ = [
+    "MockExecutor",
+    "MockResponse",
+    "MockRunReport",
+    "MockSpec",
+    "MockSpecError",
+    "check_expectations",
+    "load_mock_spec",
+    "parse_mock_spec",
+    "run_mock_recipe",
+]
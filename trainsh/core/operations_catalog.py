@@ -0,0 +1,182 @@
+"""Machine-readable catalog of the step operations a recipe can use.
+
+Generated from the `Recipe` authoring API rather than kept by hand: every public
+builder that takes `step_options` is one operation. Its signature gives the
+fields (required when they have no default), its docstring the description,
+and its body the step it adds (`provider.operation`, a control command such as
+`tmux.open`, or an execute/wait step). Steps that drive a tmux pane run via the
+terminal; everything else runs in the train process (the backend).
+"""
+
+from __future__ import annotations
+
+import ast
+import inspect
+import json
+import textwrap
+from dataclasses import asdict, dataclass, field
+from functools import lru_cache
+from typing import Any, Dict, List, Optional, Tuple
+
+# Builder arguments every step takes; they are step options, not operation fields.
+_COMMON_ARGS = ("self", "id", "depends_on", "step_options")
+# Escape hatches that add any provider or model step; their fields are open-ended.
+_GENERIC_BUILDERS = ("provider", "model_step")
+_TERMINAL_STEPS = ("execute", "wait")
+_REMOTE_ARGS = ("host", "session")
+
+
+@dataclass
+class OperationField:
+    name: str
+    type: str
+    required: bool
+    default: Any = None
+
+
+@dataclass
+class OperationInfo:
+    """One operation as the editor and external tools see it."""
+
+    name: str
+    step: str
+    description: str
+    runs_via: str
+    targets: List[str]
+    fields: List[OperationField] = field(default_factory=list)
+    alias_of: str = ""
+
+    def to_dict(self) -> Dict[str, Any]:
+        return asdict(self)
+
+
+def _recipe_class() -> type:
+    from ..pyrecipe.base import RecipeSpec
+
+    return RecipeSpec
+
+
+def _json_default(value: Any) -> Any:
+    try:
+        json.dumps(value)
+    except (TypeError, ValueError):
+        return repr(value)
+    return value
+
+
+def _fields(signature: inspect.Signature) -> List[OperationField]:
+    fields = []
+    for param in signature.parameters.values():
+        if param.name in _COMMON_ARGS or param.kind in (param.VAR_POSITIONAL, param.VAR_KEYWORD):
+            continue
+        annotation = param.annotation
+        if annotation is param.empty:
+            type_text = "Any"
+        else:
+            type_text = annotation if isinstance(annotation, str) else inspect.formatannotation(annotation)
+        required = param.default is param.empty
+        fields.append(OperationField(param.name, type_text, required, None if required else _json_default(param.default)))
+    return fields
+
+
+def _description(function: Any) -> str:
+    doc = inspect.getdoc(function) or ""
+    return " ".join(doc.split("\n\n", 1)[0].split())
+
+
+def _steps_added(function: Any, signature: inspect.Signature) -> Tuple[str, Tuple[str, ...]]:
+    """(step, calls) read from the builder body; `calls` are the public methods it delegates to."""
+    tree = ast.parse(textwrap.dedent(inspect.getsource(inspect.unwrap(function))))
+    calls: List[str] = []
+    for node in ast.walk(tree):
+        if isinstance(node, ast.Call):
+            func = node.func
+            if isinstance(func, ast.Name) and func.id == "ProviderStep":
+                keywords = {item.arg: item.value for item in node.keywords}
+                names = [keywords.get(key) for key in ("provider", "operation")]
+                if all(isinstance(item, ast.Constant) for item in names):
+                    return f"{names[0].value}.{names[1].value}", ()
+            if not (isinstance(func, ast.Attribute) and isinstance(func.value, ast.Name) and func.value.id == "self"):
+                continue
+            args = node.args
+            if func.attr == "provider" and len(args) >= 2 and isinstance(args[0], ast.Constant):
+                operation = args[1]
+                if isinstance(operation, ast.Constant):
+                    return f"{args[0].value}.{operation.value}", ()
+                if isinstance(operation, ast.Name) and operation.id in signature.parameters:
+                    # e.g. transfer(operation="copy") adds transfer.<operation>.
+                    return f"{args[0].value}.<{operation.id}>", ()
+            if func.attr == "_control_step" and args and isinstance(args[0], ast.Constant):
+                return str(args[0].value), ()
+            if not func.attr.startswith("_"):
+                calls.append(func.attr)
+        if isinstance(node, ast.Attribute) and isinstance(node.value, ast.Name) and node.value.id == "StepType":
+            return node.attr.lower(), ()
+    return "", tuple(calls)
+
+
+def _targets(step: str, fields: List[OperationField]) -> List[str]:
+    names = {item.name for item in fields}
+    provider = step.split(".", 1)[0]
+    if provider in ("vast", "runpod"):
+        return [provider]
+    if provider == "transfer":
+        return ["local", "remote", "storage"]
+    if "storage" in names or provider == "storage":
+        return ["storage"]
+    if names & set(_REMOTE_ARGS) or step.startswith("tmux."):
+        return ["local", "remote"]
+    return ["local"]
+
+
+def _runs_via(step: str) -> str:
+    return "terminal" if step in _TERMINAL_STEPS or step.startswith("tmux.") else "backend"
+
+
+@lru_cache(maxsize=1)
+def _catalog() -> Tuple[OperationInfo, ...]:
+    recipe = _recipe_class()
+    builders: Dict[str, Tuple[Any, inspect.Signature]] = {}
+    for name, function in inspect.getmembers(recipe, inspect.isfunction):
+        if name.startswith("_") or name in _GENERIC_BUILDERS:
+            continue
+        signature = inspect.signature(function)
+        if "step_options" in signature.parameters:
+            builders[name] = (function, signature)
+
+    found = {name: _steps_added(*builder) for name, builder in builders.items()}
+
+    def resolve(name: str, seen: Tuple[str, ...] = ()) -> Tuple[str, str]:
+        step, calls = found[name]
+        delegate = next((call for call in calls if call in builders and call not in seen), "")
+        if step or not delegate:
+            return step, ""
+        return resolve(delegate, (*seen, name))[0], delegate
+
+    operations = []
+    for name, (function, signature) in sorted(builders.items()):
+        step, alias_of = resolve(name)
+        fields = _fields(signature)
+        operations.append(
+            OperationInfo(
+                name=name.rstrip("_"),
+                step=step,
+                description=_description(function),
+                runs_via=_runs_via(step),
+                targets=_targets(step, fields),
+                fields=fields,
+                alias_of=alias_of,
+            )
+        )
+    return tuple(operations)
+
+
+def operations_catalog(name: Optional[str] = None) -> List[Dict[str, Any]]:
+    """Metadata of every recipe operation (or the one called `name`), sorted by name."""
+    operations = [item.to_dict() for item in _catalog()]
+    if name is None:
+        return operations
+    return [item for item in operations if item["name"] == name or item["step"] == name]
+
+
+__all__ = ["OperationField", "OperationInfo", "operations_catalog"]
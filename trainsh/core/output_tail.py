@@ -0,0 +1,88 @@
+# tmux-trainsh step output tail
+# Bounded in-memory ring of the most recent output lines of each step.
+
+from __future__ import annotations
+
+import threading
+from collections import deque
+from typing import Deque, Dict, List, Optional
+
+
+class StepOutputTail:
+    """Last `max_lines` lines (at most `max_bytes` characters) of output per step.
+
+    Memory stays bounded however noisy a step is; the complete output still
+    goes to the JSONL execution log.
+    """
+
+    def __init__(self, max_lines: int = 200, max_bytes: int = 256 * 1024):
+        self.max_lines = max(1, int(max_lines))
+        self.max_bytes = max(1, int(max_bytes))
+        self._lines: Dict[int, Deque[str]] = {}
+        self._sizes: Dict[int, int] = {}
+        # Text after the last newline, completed by the next append.
+        self._partial: Dict[int, str] = {}
+        self._lock = threading.Lock()
+
+    def append(self, step_num: int, text: str) -> None:
+        if not text:
+            return
+        with self._lock:
+            lines = self._lines.setdefault(step_num, deque())
+            pieces = (self._partial.pop(step_num, "") + text).split("\n")
+            partial = pieces.pop()
+            for line in pieces:
+                self._push(step_num, lines, line)
+            if partial:
+                self._partial[step_num] = partial[-self.max_bytes:]
+
+    def _push(self, step_num: int, lines: Deque[str], line: str) -> None:
+        line = line[-self.max_bytes:]
+        lines.append(line)
+        size = self._sizes.get(step_num, 0) + len(line)
+        while lines and (len(lines) > self.max_lines or size > self.max_bytes):
+            size -= len(lines.popleft())
+        self._sizes[step_num] = size
+
+    def tail(self, step_num: int, lines: Optional[int] = None) -> List[str]:
+        with self._lock:
+            result = list(self._lines.get(step_num, ()))
+            partial = self._partial.get(step_num)
+        if partial:
+            result.append(partial)
+        if lines is not None:
+            result = result[-max(0, int(lines)):] if lines else []
+        return result
+
+    def steps(self) -> List[int]:
+        with self._lock:
+            return sorted(set(self._lines) | set(self._partial))
+
+
+_LIVE: Dict[str, StepOutputTail] = {}
+_LIVE_LOCK = threading.Lock()
+
+
+def register_live_tail(run_id: str, tail: StepOutputTail) -> None:
+    with _LIVE_LOCK:
+        _LIVE[str(run_id)] = tail
+
+
+def unregister_live_tail(run_id: str, tail: Optional[StepOutputTail] = None) -> None:
+    with _LIVE_LOCK:
+        if tail is None or _LIVE.get(str(run_id)) is tail:
+            _LIVE.pop(str(run_id), None)
+
+
+def get_live_tail(run_id: str) -> Optional[StepOutputTail]:
+    """The ring of a run executing in this process, if any."""
+    with _LIVE_LOCK:
+        return _LIVE.get(str(run_id))
+
+
+__all__ = [
+    "StepOutputTail",
+    "get_live_tail",
+    "register_live_tail",
+    "unregister_live_tail",
+]
@@ -0,0 +1,199 @@
+"""Check that recipe hosts have the tools their steps need before a run starts."""
+
+from __future__ import annotations
+
+import re
+import shlex
+import subprocess
+from typing import Any, Callable, Dict, Iterable, List, Optional, Tuple
+
+from ..services.tracing import KIND_CLIENT, span
+from .recipe_models import StepType
+
+# Tools looked for inside step commands, in report order.
+COMMAND_TOOLS: Tuple[Tuple[str, "re.Pattern[str]"], ...] = (
+    ("git", re.compile(r"(?<![\w./-])git\s")),
+    ("rsync", re.compile(r"(?<![\w./-])rsync\s")),
+    ("nvidia-smi", re.compile(r"(?<![\w./-])nvidia-smi\b")),
+    ("huggingface-cli", re.compile(r"(?<![\w./-])(?:huggingface-cli|hf)\s")),
+    ("docker", re.compile(r"(?<![\w./-])docker\s")),
+)
+
+# Tools a bootstrap script can install with the system package manager.
+SYSTEM_PACKAGES = {"tmux": "tmux", "git": "git", "rsync": "rsync"}
+PIP_PACKAGES = {"huggingface-cli": "huggingface_hub[cli]"}
+MANUAL_HINTS = {
+    "nvidia-smi": "install the NVIDIA driver or use a CUDA base image",
+    "docker": "install Docker Engine (https://docs.docker.com/engine/install/)",
+}
+
+PREFLIGHT_TIMEOUT_SECS = 20
+_DONE_MARKER = "trainsh-preflight-done"
+
+
+def _host_alias(value: Any) -> str:
+    text = str(value or "").strip()
+    if text.startswith("@"):
+        return text[1:].split(":", 1)[0]
+    return ""
+
+
+def _add(required: Dict[str, List[str]], host: str, tools: Iterable[str]) -> None:
+    if not host:
+        return
+    bucket = required.setdefault(host, [])
+    for tool in tools:
+        if tool not in bucket:
+            bucket.append(tool)
+
+
+def command_tools(text: str) -> List[str]:
+    """Return known tools a shell command line calls."""
+    return [name for name, pattern in COMMAND_TOOLS if pattern.search(str(text or ""))]
+
+
+def _transfer_hosts(required: Dict[str, List[str]], source: Any, dest: Any) -> None:
+    sources = source if isinstance(source, (list, tuple)) else [source]
+    hosts = [alias for alias in (*map(_host_alias, sources), _host_alias(dest)) if alias]
+    if not hosts:
+        return
+    for alias in hosts + ["local"]:
+        _add(required, alias, ["rsync"])
+
+
+def required_tools(recipe: Any) -> Dict[str, List[str]]:
+    """Map host aliases (or `local`) to the tools the recipe's steps need there."""
+    required: Dict[str, List[str]] = {}
+    windows: Dict[str, str] = {}
+    for step in getattr(recipe, "steps", []) or []:
+        step_type = getattr(step, "type", None)
+        command = str(getattr(step, "command", "") or "")
+        args = list(getattr(step, "args", []) or [])
+        if command == "tmux.open" and len(args) >= 3:
+            alias = _host_alias(args[0]) or "local"
+            windows[args[2]] = alias
+            _add(required, alias, ["tmux"])
+        elif step_type == StepType.EXECUTE:
+            alias = windows.get(str(getattr(step, "host", "") or ""), "")
+            _add(required, alias, command_tools(getattr(step, "commands", "")))
+        elif step_type == StepType.TRANSFER:
+            _transfer_hosts(required, getattr(step, "source", ""), getattr(step, "dest", ""))
+
+        provider = str(getattr(step, "provider", "") or "")
+        params = getattr(step, "params", None)
+        if not provider or not isinstance(params, dict):
+            continue
+        operation = str(getattr(step, "operation", "") or "")
+        host = _host_alias(params.get("host")) or str(params.get("host", "") or "").strip() or "local"
+        if provider == "git":
+            _add(required, host, ["git"])
+        elif provider == "transfer":
+            _transfer_hosts(required, params.get("source"), params.get("destination", params.get("dest")))
+        elif provider in {"shell", "bash"} or operation in {"ssh_command", "uv_run"}:
+            _add(required, host, command_tools(params.get("command", params.get("script", ""))))
+    return {host: tools for host, tools in required.items() if tools}
+
+
+def checkable_host(spec: str) -> bool:
+    """Only concrete hosts can be checked before the run creates or starts them."""
+    spec = str(spec or "").strip()
+    if not spec or spec == "placeholder" or "${" in spec:
+        return False
+    return not spec.startswith(("vast:", "runpod:"))
+
+
+def check_host_tools(
+    host: str,
+    tools: List[str],
+    *,
+    build_ssh_args: Callable[..., List[str]],
+    timeout: int = PREFLIGHT_TIMEOUT_SECS,
+) -> Tuple[bool, List[str]]:
+    """Return (reachable, missing tools) for one host."""
+    probe = "; ".join(
+        f"command -v {shlex.quote(tool)} >/dev/null 2>&1 || echo missing:{tool}" for tool in tools
+    )
+    script = f"{probe}; echo {_DONE_MARKER}"
+    args = ["sh", "-c", script] if host == "local" else build_ssh_args(host, command=script, tty=False)
+    try:
+        # Little runs remotely here, so this span is mostly connection setup.
+        with span("ssh", kind=KIND_CLIENT, **{"net.peer.name": host, "ssh.operation": "preflight"}):
+            result = subprocess.run(args, capture_output=True, text=True, timeout=timeout)
+    except (OSError, subprocess.TimeoutExpired):
+        return False, []
+    output = result.stdout or ""
+    if _DONE_MARKER not in output:
+        return False, []
+    missing = [line.split(":", 1)[1] for line in output.splitlines() if line.startswith("missing:")]
+    return True, missing
+
+
+def install_hint(tool: str) -> str:
+    if tool in SYSTEM_PACKAGES:
+        return f"apt-get install -y {SYSTEM_PACKAGES[tool]}"
+    if tool in PIP_PACKAGES:
+        return f"pip install '{PIP_PACKAGES[tool]}'"
+    return MANUAL_HINTS.get(tool, f"install {tool}")
+
+
+SUDO_PREFIX = 'SUDO=""; [ "$(id -u)" = 0 ] || SUDO=sudo'
+
+
+def package_install_command(packages: List[str]) -> str:
+    """Shell snippet installing system packages with whichever manager the host has.
+
+    Expects `$SUDO` to be set, see `SUDO_PREFIX`.
+    """
+    names = " ".join(shlex.quote(item) for item in packages)
+    return (
+        "if command -v apt-get >/dev/null 2>&1; then "
+        f"$SUDO apt-get update -qq && DEBIAN_FRONTEND=noninteractive $SUDO apt-get install -y -qq {names}; "
+        f"elif command -v dnf >/dev/null 2>&1; then $SUDO dnf install -y {names}; "
+        f"elif command -v yum >/dev/null 2>&1; then $SUDO yum install -y {names}; "
+        f"elif command -v apk >/dev/null 2>&1; then $SUDO apk add {names}; "
+        f"elif command -v brew >/dev/null 2>&1; then brew install {names}; "
+        "else echo 'no supported package manager' >&2; exit 1; fi"
+    )
+
+
+def bootstrap_script(missing: List[str]) -> Optional[str]:
+    """Shell script installing the missing tools that can be installed unattended."""
+    system = [SYSTEM_PACKAGES[tool] for tool in missing if tool in SYSTEM_PACKAGES]
+    python = [PIP_PACKAGES[tool] for tool in missing if tool in PIP_PACKAGES]
+    if not system and not python:
+        return None
+    lines = [SUDO_PREFIX]
+    if system:
+        lines.append(package_install_command(system))
+    if python:
+        packages = " ".join(shlex.quote(item) for item in python)
+        lines.append(f"python3 -m pip install -q {packages}")
+    return " && ".join(lines)
+
+
+def run_bootstrap(
+    host: str,
+    script: str,
+    *,
+    build_ssh_args: Callable[..., List[str]],
+    timeout: int = 600,
+) -> Tuple[bool, str]:
+    args = ["sh", "-c", script] if host == "local" else build_ssh_args(host, command=script, tty=False)
+    try:
+        result = subprocess.run(args, capture_output=True, text=True, timeout=timeout)
+    except (OSError, subprocess.TimeoutExpired) as exc:
+        return False, str(exc)
+    detail = (result.stderr or result.stdout or "").strip().splitlines()
+    return result.returncode == 0, detail[-1] if detail else ""
+
+
+__all__ = [
+    "bootstrap_script",
+    "check_host_tools",
+    "checkable_host",
+    "command_tools",
+    "install_hint",
+    "package_install_command",
+    "required_tools",
+    "run_bootstrap",
+]
@@ -100,12 +100,9 @@ class ExecutorProviderConditionsMixin:
         message = str(params.get("message", "Skipped by latest_only"))
         fail_if_unknown = self._coerce_bool(params.get("fail_if_unknown", False), default=False)
 
-        from ..constants import RUNTIME_STATE_DIR
         from ..core.runtime_store import RuntimeStore
 
-        runtime_state = str(params.get("runtime_state", "")).strip()
-        if not runtime_state:
-            runtime_state = str(RUNTIME_STATE_DIR)
+        runtime_state = str(params.get("runtime_state", "")).strip() or self.runtime_state
 
         import os
         from pathlib import Path
@@ -226,3 +223,61 @@ class ExecutorProviderConditionsMixin:
                 "timeout": timeout,
             }
         )
+
+    def _integrity_manifest(self, params: Dict[str, Any]) -> Dict[str, Any]:
+        from ..services.integrity import build_host_manifest, build_local_manifest
+        from .executor_utils import _host_from_ssh_spec
+
+        path = self._interpolate(str(params.get("path", ""))).strip()
+        if not path:
+            raise ValueError("requires 'path'")
+        host = self._provider_host(params.get("host", "local"))
+        if host == "local":
+            return build_local_manifest(path)
+        return build_host_manifest(_host_from_ssh_spec(host), path)
+
+    def _exec_provider_integrity_manifest(self, params: Dict[str, Any]) -> tuple[bool, str]:
+        """Hash every file under a host directory, optionally saving the manifest locally."""
+        if not isinstance(params, dict):
+            return False, "Provider util.integrity_manifest params must be an object"
+        try:
+            manifest = self._integrity_manifest(params)
+        except ValueError as exc:
+            return False, f"Provider util.integrity_manifest {exc}"
+        except RuntimeError as exc:
+            return False, f"Manifest failed: {exc}"
+
+        output = self._interpolate(str(params.get("output", ""))).strip()
+        if output:
+            from ..services.integrity import write_manifest
+
+            write_manifest(manifest, output)
+        summary = f"Manifest of {manifest['source']}: {manifest['file_count']} files, {manifest['total_bytes']} bytes"
+        return True, f"{summary} -> {output}" if output else summary
+
+    def _exec_provider_integrity_verify(self, params: Dict[str, Any]) -> tuple[bool, str]:
+        """Verify a local directory against a saved manifest or a fresh host manifest."""
+        if not isinstance(params, dict):
+            return False, "Provider util.integrity_verify params must be an object"
+        from ..services.integrity import load_manifest, verify_manifest
+
+        local_dir = self._interpolate(str(params.get("local_dir", ""))).strip()
+        if not local_dir:
+            return False, "Provider util.integrity_verify requires 'local_dir'"
+        manifest_path = self._interpolate(str(params.get("manifest", ""))).strip()
+        try:
+            manifest = load_manifest(manifest_path) if manifest_path else self._integrity_manifest(params)
+        except ValueError as exc:
+            return False, f"Provider util.integrity_verify {exc}"
+        except (OSError, RuntimeError) as exc:
+            return False, f"Manifest failed: {exc}"
+
+        report = verify_manifest(manifest, local_dir)
+        summary = (
+            f"{report['verified']}/{report['checked']} files verified, "
+            f"{len(report['missing'])} missing, {len(report['corrupted'])} corrupted"
+        )
+        if report["ok"]:
+            return True, summary
+        problems = [entry["path"] for entry in report["missing"] + report["corrupted"]]
+        return False, f"{summary}: {', '.join(problems[:10])}{' ...' if len(problems) > 10 else ''}"
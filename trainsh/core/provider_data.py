@@ -9,7 +9,6 @@ import subprocess
 from datetime import datetime
 from typing import Any, Dict, List, Optional
 
-from ..constants import RUNTIME_STATE_DIR
 from .models import Storage, StorageType
 from .runtime_store import RuntimeStore
 
@@ -325,7 +324,7 @@ class ExecutorProviderDataMixin:
         map_index = self._coerce_int(params.get("map_index", 0), default=0)
         created_at = datetime.now().isoformat()
         execution_date = str(params.get("execution_date", created_at)).strip() or created_at
-        store_root = str(params.get("runtime_state", "")).strip() or str(RUNTIME_STATE_DIR)
+        store_root = str(params.get("runtime_state", "")).strip() or self.runtime_state
         try:
             RuntimeStore(store_root).append_xcom(
                 {
@@ -377,7 +376,7 @@ class ExecutorProviderDataMixin:
             params.get("include_prior_dates", False),
             default=False,
         )
-        store_root = str(params.get("runtime_state", "")).strip() or str(RUNTIME_STATE_DIR)
+        store_root = str(params.get("runtime_state", "")).strip() or self.runtime_state
 
         task_ids_raw = params.get("task_ids", params.get("task_id"))
         task_ids: List[str] = []
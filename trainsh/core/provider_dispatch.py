@@ -155,6 +155,8 @@ class ExecutorProviderDispatchMixin:
                 return self._exec_provider_storage_ensure_bucket(storage_params)
             if operation in {"delete", "remove", "rm"}:
                 return self._exec_provider_storage_delete(storage_params)
+            if operation in {"trash_list", "trash_restore", "trash_empty"}:
+                return self._exec_provider_storage_trash(operation, storage_params)
             if operation in {"rename", "move", "mv"}:
                 return self._exec_provider_storage_rename(storage_params)
             if operation == "transfer":
@@ -208,6 +210,10 @@ class ExecutorProviderDispatchMixin:
             return self._exec_provider_ssh_command(params)
         if provider == "util" and operation == "uv_run":
             return self._exec_provider_uv_run(params)
+        if provider == "util" and operation == "integrity_manifest":
+            return self._exec_provider_integrity_manifest(params)
+        if provider == "util" and operation == "integrity_verify":
+            return self._exec_provider_integrity_verify(params)
         if provider == "storage" and operation == "test":
             return self._exec_provider_storage_test(params)
         if provider == "storage" and operation in {"list", "ls"}:
@@ -230,6 +236,8 @@ class ExecutorProviderDispatchMixin:
             return self._exec_provider_storage_ensure_bucket(params)
         if provider == "storage" and operation == "delete":
             return self._exec_provider_storage_delete(params)
+        if provider == "storage" and operation in {"trash_list", "trash_restore", "trash_empty"}:
+            return self._exec_provider_storage_trash(operation, params)
         if provider == "storage" and operation == "rename":
             return self._exec_provider_storage_rename(params)
         if provider == "storage" and operation in {"copy", "sync", "move"}:
@@ -283,6 +291,14 @@ class ExecutorProviderDispatchMixin:
             return self._exec_provider_vast(operation, params)
         if provider in {"runpod", "runpods"} and operation in {"start", "stop", "pick", "wait", "cost"}:
             return self._exec_provider_runpod(operation, params)
+        if provider == "jupyter" and operation in {"start", "stop", "status"}:
+            return self._exec_provider_jupyter(operation, params)
+        if provider == "notebook" and operation in {"run", "execute"}:
+            return self._exec_provider_notebook(operation, params)
+        if provider == "group" and operation == "run":
+            return self._exec_group_run(params)
+        if provider == "recipe" and operation in {"run", "call"}:
+            return self._exec_provider_recipe_run(params)
         if provider == "git" and operation == "clone":
             return self._exec_provider_git_clone(params)
         if provider == "git" and operation == "pull":
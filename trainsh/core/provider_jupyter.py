@@ -0,0 +1,90 @@
+"""Managed Jupyter Lab server operations for providers."""
+
+from __future__ import annotations
+
+import time
+from typing import Any, Dict
+
+
+class ExecutorProviderJupyterMixin:
+    def _exec_provider_jupyter(self, operation: str, params: Dict[str, Any]) -> tuple[bool, str]:
+        """Start, stop, or inspect a managed Jupyter server on a host."""
+        from ..services import jupyter_service as jupyter
+        from ..services.vllm_service import parse_duration
+        from .executor_utils import _host_from_ssh_spec
+
+        if params is None:
+            params = {}
+        if not isinstance(params, dict):
+            return False, "Provider jupyter params must be an object"
+
+        op = operation.strip().lower()
+        host_name = self._interpolate(str(params.get("host", ""))).strip().lstrip("@")
+        name = self._interpolate(str(params.get("name", ""))).strip() or host_name
+        if not name:
+            return False, f"Provider jupyter.{op} requires 'host' or 'name'"
+
+        if op == "stop":
+            record = jupyter.load_server(name)
+            if record is None:
+                return True, f"Jupyter server not running: {name}"
+            try:
+                jupyter.stop_server(record)
+            except Exception as exc:
+                return False, f"Failed to stop Jupyter: {exc}"
+            return True, f"Stopped Jupyter server: {record.name}"
+
+        if op == "status":
+            record = jupyter.load_server(name)
+            status = jupyter.refresh_status(record) if record is not None else "stopped"
+            capture_var = params.get("capture_var")
+            if capture_var:
+                self.ctx.variables[str(capture_var)] = status
+            return True, f"Jupyter server {name}: {status}"
+
+        if not host_name:
+            return False, "Provider jupyter.start requires 'host'"
+        spec = self._provider_host(host_name)
+        if spec == "local":
+            return False, "Provider jupyter.start requires a remote host"
+
+        record = jupyter.load_server(name)
+        if record is not None and jupyter.refresh_status(record) == "stopped":
+            jupyter.stop_server(record)
+            record = None
+        if record is None:
+            try:
+                record = jupyter.start_server(
+                    host_name,
+                    _host_from_ssh_spec(spec),
+                    name=name,
+                    port=int(params.get("port", jupyter.DEFAULT_PORT) or jupyter.DEFAULT_PORT),
+                    root_dir=self._interpolate(str(params.get("root_dir", params.get("dir", "")))).strip(),
+                    python=self._interpolate(str(params.get("python", ""))).strip(),
+                )
+            except (RuntimeError, ValueError) as exc:
+                return False, f"Failed to start Jupyter: {exc}"
+        logger = getattr(self, "logger", None)
+        if logger is not None:
+            logger.redactions.add(record.token)
+
+        timeout = parse_duration(str(params.get("timeout", "2m")), default=120)
+        deadline = time.time() + max(1, timeout)
+        while not jupyter.server_is_ready(record):
+            if not jupyter.server_is_running(record) or time.time() >= deadline:
+                return False, f"Jupyter did not become ready in tmux session {record.session_name}"
+            time.sleep(2)
+        record.status = "ready"
+        jupyter.save_server(record)
+
+        if params.get("tunnel", True):
+            try:
+                jupyter.open_tunnel(record, local_port=int(params.get("local_port", 0) or 0))
+            except RuntimeError as exc:
+                return False, f"Jupyter is running but the tunnel failed: {exc}"
+
+        url = record.local_url
+        capture_var = params.get("capture_var")
+        if capture_var:
+            self.ctx.variables[str(capture_var)] = url
+        return True, f"Jupyter server {record.name} ready" + (f" at 127.0.0.1:{record.local_port}" if url else "")
@@ -6,7 +6,10 @@ from .provider_conditions import ExecutorProviderConditionsMixin
 from .provider_dispatch import ExecutorProviderDispatchMixin
 from .provider_data import ExecutorProviderDataMixin
 from .provider_http import ExecutorProviderHttpMixin
+from .provider_jupyter import ExecutorProviderJupyterMixin
+from .provider_notebook import ExecutorProviderNotebookMixin
 from .provider_notify import ExecutorProviderNotifyMixin
+from .provider_recipe import ExecutorProviderRecipeMixin
 from .provider_shell import ExecutorProviderShellOpsMixin
 from .provider_storage import ExecutorProviderStorageMixin
 
@@ -19,5 +22,8 @@ class ExecutorProviderMixin(
     ExecutorProviderConditionsMixin,
     ExecutorProviderShellOpsMixin,
     ExecutorProviderNotifyMixin,
+    ExecutorProviderJupyterMixin,
+    ExecutorProviderNotebookMixin,
+    ExecutorProviderRecipeMixin,
 ):
     pass
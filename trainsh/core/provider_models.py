@@ -0,0 +1,250 @@
+# tmux-trainsh cloud provider models
+# Vast.ai instances/offers and RunPod Pods/GPU types
+
+from dataclasses import dataclass
+from typing import Optional, List, Dict, Any
+
+
+# ============================================================================
+# Vast.ai Models
+# ============================================================================
+
+
+@dataclass
+class VastInstance:
+    """Represents a Vast.ai instance."""
+    id: int
+    actual_status: Optional[str] = None
+    cur_state: Optional[str] = None
+    next_state: Optional[str] = None
+    intended_status: Optional[str] = None
+    gpu_name: Optional[str] = None
+    gpu_arch: Optional[str] = None
+    gpu_totalram: Optional[float] = None
+    gpu_ram: Optional[float] = None
+    gpu_util: Optional[float] = None
+    gpu_temp: Optional[float] = None
+    num_gpus: Optional[int] = None
+    dph_total: Optional[float] = None
+    dph_base: Optional[float] = None
+    storage_cost: Optional[float] = None
+    storage_total_cost: Optional[float] = None
+    disk_space: Optional[float] = None
+    disk_usage: Optional[float] = None
+    ssh_idx: Optional[str] = None
+    ssh_host: Optional[str] = None
+    ssh_port: Optional[int] = None
+    machine_dir_ssh_port: Optional[int] = None
+    ports: Optional[Dict[str, Any]] = None
+    public_ipaddr: Optional[str] = None
+    direct_port_start: Optional[int] = None
+    direct_port_end: Optional[int] = None
+    image_runtype: Optional[str] = None
+    label: Optional[str] = None
+    template_name: Optional[str] = None
+    image_uuid: Optional[str] = None
+    cpu_name: Optional[str] = None
+    cpu_cores: Optional[int] = None
+    cpu_ram: Optional[float] = None
+    machine_id: Optional[int] = None
+    host_id: Optional[int] = None
+    start_date: Optional[float] = None
+    end_date: Optional[float] = None
+    duration: Optional[float] = None
+    geolocation: Optional[str] = None
+    reliability2: Optional[float] = None
+    country_code: Optional[str] = None
+
+    @property
+    def is_running(self) -> bool:
+        """Check if instance is running."""
+        return (self.actual_status or "").lower() == "running"
+
+    @property
+    def display_name(self) -> str:
+        """Get display name for the instance."""
+        return f"Vast.ai #{self.id}"
+
+    @property
+    def ssh_proxy_command(self) -> Optional[str]:
+        """Get proxy SSH command."""
+        if self.ssh_host and self.ssh_port:
+            return f"ssh -p {self.ssh_port} root@{self.ssh_host}"
+        return None
+
+    @staticmethod
+    def _mapped_host_port(ports: Optional[Dict[str, Any]], *keys: str) -> Optional[int]:
+        """Return the first concrete host port mapped for one container port."""
+        if not isinstance(ports, dict):
+            return None
+
+        for key in keys:
+            raw = ports.get(key)
+            if isinstance(raw, dict):
+                bindings = [raw]
+            elif isinstance(raw, list):
+                bindings = [item for item in raw if isinstance(item, dict)]
+            else:
+                bindings = []
+
+            for binding in bindings:
+                value = (
+                    binding.get("HostPort")
+                    or binding.get("host_port")
+                    or binding.get("public_port")
+                    or binding.get("port")
+                )
+                if value in (None, ""):
+                    continue
+                try:
+                    return int(str(value).strip())
+                except (TypeError, ValueError):
+                    continue
+        return None
+
+    @property
+    def ssh_direct_port(self) -> Optional[int]:
+        """Get the actual direct SSH host port, if Vast exposed one."""
+        return self._mapped_host_port(self.ports, "22/tcp", "22")
+
+    @property
+    def direct_port_range(self) -> Optional[str]:
+        """Get the allocated direct port range as display text."""
+        if self.direct_port_start is None and self.direct_port_end is None:
+            return None
+        if self.direct_port_start is None:
+            return str(self.direct_port_end)
+        if self.direct_port_end is None or self.direct_port_end == self.direct_port_start:
+            return str(self.direct_port_start)
+        return f"{self.direct_port_start}-{self.direct_port_end}"
+
+    @property
+    def ssh_direct_command(self) -> Optional[str]:
+        """Get direct SSH command (if available)."""
+        if self.public_ipaddr and self.ssh_direct_port is not None:
+            return f"ssh -p {self.ssh_direct_port} root@{self.public_ipaddr}"
+        return None
+
+    @property
+    def hourly_rate(self) -> float:
+        """Get hourly rate."""
+        return self.dph_total or 0.0
+
+    @property
+    def gpu_memory_gb(self) -> float:
+        """Get GPU memory in GB."""
+        if self.gpu_ram:
+            return self.gpu_ram / 1024.0
+        return 0.0
+
+    @property
+    def status_color(self) -> str:
+        """Get status color for display."""
+        status = (self.actual_status or "").lower()
+        if status == "running":
+            return "green"
+        elif status in ("loading", "starting"):
+            return "yellow"
+        elif status in ("stopped", "exited"):
+            return "gray"
+        return "gray"
+
+
+@dataclass
+class VastOffer:
+    """Represents a Vast.ai GPU offer."""
+    id: int
+    gpu_name: Optional[str] = None
+    num_gpus: Optional[int] = None
+    gpu_ram: Optional[float] = None
+    dph_total: Optional[float] = None
+    reliability2: Optional[float] = None
+    inet_down: Optional[float] = None
+    inet_up: Optional[float] = None
+    cpu_cores: Optional[int] = None
+    cpu_ram: Optional[float] = None
+
+    @property
+    def display_gpu_ram(self) -> str:
+        """Get formatted GPU RAM."""
+        if self.gpu_ram:
+            return f"{self.gpu_ram / 1024:.0f} GB"
+        return "N/A"
+
+    @property
+    def display_price(self) -> str:
+        """Get formatted price."""
+        if self.dph_total:
+            return f"${self.dph_total:.3f}/hr"
+        return "N/A"
+
+
+@dataclass
+class RunpodPod:
+    """Represents one RunPod Pod."""
+
+    id: str
+    desired_status: Optional[str] = None
+    name: Optional[str] = None
+    image_name: Optional[str] = None
+    public_ip: Optional[str] = None
+    port_mappings: Optional[Dict[str, Any]] = None
+    ports: Optional[List[str]] = None
+    gpu_type_id: Optional[str] = None
+    gpu_display_name: Optional[str] = None
+    gpu_count: Optional[int] = None
+    gpu_memory_gb: Optional[float] = None
+    cost_per_hr: Optional[float] = None
+    cloud_type: Optional[str] = None
+    container_disk_in_gb: Optional[int] = None
+    volume_in_gb: Optional[int] = None
+    template_id: Optional[str] = None
+
+    @property
+    def is_running(self) -> bool:
+        """Whether the Pod is running."""
+        return (self.desired_status or "").upper() == "RUNNING"
+
+    @property
+    def display_name(self) -> str:
+        """Get display name for the Pod."""
+        return f"RunPod #{self.id}"
+
+    @property
+    def hourly_rate(self) -> float:
+        """Get hourly rate."""
+        return float(self.cost_per_hr or 0.0)
+
+
+@dataclass
+class RunpodGPUType:
+    """Represents one RunPod GPU type and current price hints."""
+
+    id: str
+    display_name: Optional[str] = None
+    memory_gb: Optional[float] = None
+    secure_price: Optional[float] = None
+    community_price: Optional[float] = None
+    secure_spot_price: Optional[float] = None
+    min_bid_price: Optional[float] = None
+    uninterruptable_price: Optional[float] = None
+    min_vcpu: Optional[int] = None
+    min_memory_gb: Optional[float] = None
+    stock_status: Optional[str] = None
+    available_gpu_counts: Optional[List[int]] = None
+
+    @property
+    def best_hourly_price(self) -> float:
+        """Best known hourly price hint."""
+        candidates = [
+            value
+            for value in (
+                self.uninterruptable_price,
+                self.community_price,
+                self.secure_price,
+            )
+            if value not in (None, "")
+        ]
+        if not candidates:
+            return 0.0
+        return float(min(candidates))
@@ -0,0 +1,155 @@
+"""Run a Jupyter notebook as a step with papermill."""
+
+from __future__ import annotations
+
+import os
+import shlex
+import shutil
+import subprocess
+import threading
+import time
+import uuid
+from pathlib import Path
+from typing import Any, Dict
+
+from ..constants import RUNTIME_STATE_DIR
+from ..services.host_exec import host_exec
+from .executor_utils import _build_ssh_args, _host_from_ssh_spec
+from .remote_cancel import pid_file_for, wrap_pid_command
+from .runtime_store import STEP_LOGS_DIRNAME
+
+
+class ExecutorProviderNotebookMixin:
+    def _exec_provider_notebook(self, operation: str, params: Dict[str, Any]) -> tuple[bool, str]:
+        """Upload a notebook, run it with papermill and download the executed copy."""
+        from ..services.notebook_run import NOTEBOOK_OUTPUT_DIR, cell_progress, count_code_cells, papermill_command
+
+        if not isinstance(params, dict):
+            return False, "Provider notebook params must be an object"
+        notebook = self._interpolate(str(params.get("notebook", ""))).strip()
+        if not notebook:
+            return False, "Provider notebook.run requires 'notebook'"
+        timeout = self._normalize_provider_timeout(params.get("timeout"), allow_zero=True)
+        if timeout is None:
+            return False, f"Invalid timeout value: {params.get('timeout')!r}"
+
+        parameters: Dict[str, Any] = {}
+        for name in params.get("variables") or []:
+            if str(name) in self.ctx.variables:
+                parameters[str(name)] = self.ctx.variables[str(name)]
+        for key, value in (params.get("parameters") or {}).items():
+            parameters[str(key)] = self._interpolate(value) if isinstance(value, str) else value
+
+        host = self._provider_host(params.get("host", "local"))
+        error, staged = self.execute_helper._stage_script(notebook, host)
+        if error:
+            return False, error
+
+        step_num = self._current_step_num()
+        stem = Path(notebook).stem
+        cwd = self._interpolate(str(params.get("cwd", ""))).strip()
+        executed = f"{NOTEBOOK_OUTPUT_DIR}/{self.ctx.job_id}/step_{step_num:04d}.{stem}.ipynb"
+        command = papermill_command(
+            staged,
+            executed,
+            parameters=parameters,
+            kernel=self._interpolate(str(params.get("kernel", ""))).strip(),
+            cwd=cwd,
+            papermill=str(params.get("papermill", "") or "papermill"),
+        )
+        total = count_code_cells(notebook)
+        name = os.path.basename(notebook)
+        self.log(f"Running notebook {name} on {host} ({total} code cells)")
+
+        execute_helper = self.execute_helper
+        if host == "local":
+            guarded = execute_helper.guard_local(command, os.path.expanduser(cwd) if cwd else os.getcwd())
+            if guarded is not None:
+                return guarded
+
+        # Track papermill's PID so a timeout or `train cancel` stops it on the host, not just the ssh client.
+        pidfile = pid_file_for(f"train_nb_{uuid.uuid4().hex[:8]}")
+        tracked = wrap_pid_command(command, pidfile)
+        start = time.time()
+        args: Any = tracked if host == "local" else _build_ssh_args(host, command=tracked, tty=False)
+        try:
+            process = subprocess.Popen(
+                args,
+                shell=host == "local",
+                start_new_session=host == "local",
+                stdout=subprocess.PIPE,
+                stderr=subprocess.STDOUT,
+                text=True,
+            )
+        except OSError as exc:
+            return False, f"Failed to start papermill: {exc}"
+        self._track_inflight(step_num, {"host": host, "pidfile": pidfile})
+        timed_out = threading.Event()
+
+        def _kill() -> None:
+            timed_out.set()
+            self.log(f"Notebook {name} timed out: {execute_helper.kill_inflight({'host': host, 'pidfile': pidfile})}")
+            process.kill()
+
+        timer = threading.Timer(timeout, _kill) if timeout else None
+        if timer is not None:
+            timer.daemon = True
+            timer.start()
+        lines = []
+        try:
+            for line in process.stdout:
+                lines.append(line)
+                cell = cell_progress(line)
+                if cell is not None:
+                    self.log(f"Notebook {name}: cell {cell}/{total or '?'}")
+                    self._emit_event("notebook_cell", step_num=step_num, notebook=name, cell=cell, total=total)
+            returncode = process.wait()
+        finally:
+            process.stdout.close()
+            if timer is not None:
+                timer.cancel()
+            cancelled = str(self.ctx.inflight.get(str(step_num), {}).get("cancelled", "") or "")
+            self._clear_inflight(step_num)
+        output = "".join(lines)
+        if self.logger:
+            self.logger.log_ssh(host, command, returncode, output, "", int((time.time() - start) * 1000))
+        if timed_out.is_set():
+            self._note_step_stopped(self._current_step_id(), "timeout")
+            return False, f"Notebook {name} timed out after {timeout}s"
+        if cancelled:
+            return False, f"Notebook {name} cancelled ({cancelled})"
+        self._note_exit_code(returncode, host)
+        if returncode != 0:
+            return False, output.strip() or f"papermill exited with {returncode}"
+
+        local_output = self._interpolate(str(params.get("output", ""))).strip()
+        if local_output:
+            target = Path(os.path.expanduser(local_output))
+        else:
+            root = self.logger.store.root if self.logger else Path(RUNTIME_STATE_DIR)
+            target = Path(root) / STEP_LOGS_DIRNAME / str(self.ctx.job_id) / f"step_{step_num:04d}.{stem}.ipynb"
+        error = self._fetch_notebook(host, executed, target)
+        if error:
+            return False, error
+        capture_var = params.get("capture_var")
+        if capture_var:
+            self.ctx.variables[str(capture_var)] = str(target)
+        return True, f"Notebook {name} finished; executed copy at {target}"
+
+    def _fetch_notebook(self, host: str, remote_path: str, target: Path) -> str:
+        """Copy the executed notebook (relative to $HOME on `host`) to `target`; returns an error or ''."""
+        target.parent.mkdir(parents=True, exist_ok=True)
+        if host == "local":
+            try:
+                shutil.copyfile(os.path.join(os.path.expanduser("~"), remote_path), target)
+            except OSError as exc:
+                return f"Cannot copy executed notebook: {exc.strerror or exc}"
+            return ""
+        result = host_exec(_host_from_ssh_spec(host), f'cat "$HOME"/{shlex.quote(remote_path)}', timeout=300)
+        if not result.success:
+            return f"Cannot download executed notebook: {result.stderr.strip() or f'exit {result.exit_code}'}"
+        target.write_text(result.stdout, encoding="utf-8")
+        return ""
+
+
+__all__ = ["ExecutorProviderNotebookMixin"]
@@ -2,9 +2,9 @@
 
 from __future__ import annotations
 
-import shlex
 from typing import Any, Dict, List
 
+from ..services.python_run import python_command
 from ..utils.notifier import normalize_channels, parse_bool
 
 
@@ -100,16 +100,33 @@ class ExecutorProviderNotifyMixin:
         if not any((command, code, script)):
             return False, "Provider python requires 'command', 'code', or 'script'"
 
-        if code:
-            shell_command = "python - <<'PY'\n" + code + "\nPY"
-        elif script:
-            shell_command = f"python {shlex.quote(script)}"
-        else:
-            shell_command = f"python -c {shlex.quote(command)}"
+        requirements = params.get("requirements") or []
+        if isinstance(requirements, str):
+            requirements = [requirements]
+        try:
+            shell_command = python_command(
+                code=code,
+                script=script,
+                command=command,
+                interpreter=self._interpolate(str(params.get("interpreter", ""))).strip(),
+                requirements=[self._interpolate(str(item)) for item in requirements],
+            )
+        except ValueError as exc:
+            return False, f"Provider python {exc}"
+
+        try:
+            ok_exit_codes = [int(code) for code in params.get("ok_exit_codes") or [0]]
+        except (TypeError, ValueError):
+            return False, f"Provider python has invalid ok_exit_codes: {params.get('ok_exit_codes')!r}"
+        exit_code_var = params.get("exit_code_var")
 
         shell_params = dict(params)
         shell_params["command"] = shell_command
-        return self._exec_provider_shell(shell_params)
+        return self._exec_provider_shell(
+            shell_params,
+            ok_exit_codes=ok_exit_codes,
+            exit_code_var=exit_code_var if isinstance(exit_code_var, str) else "",
+        )
 
     def _exec_provider_vast(self, operation: str, params: Dict[str, Any]) -> tuple[bool, str]:
         """Execute vast provider operations."""
@@ -163,6 +180,7 @@ class ExecutorProviderNotifyMixin:
                 "disk": "disk_gb",
                 "label": "label",
                 "direct": "direct",
+                "template": "template",
             }
             for key, param_key in mapping.items():
                 value = params.get(key)
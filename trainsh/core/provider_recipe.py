@@ -0,0 +1,226 @@
+"""Provider operation that runs another recipe as one step."""
+
+from __future__ import annotations
+
+import os
+from collections import Counter
+from pathlib import Path
+from typing import Any, Dict, List, Optional
+
+from ..constants import RECIPE_FILE_EXTENSIONS
+
+MAX_RECIPE_DEPTH = 8
+
+
+class ExecutorProviderRecipeMixin:
+    def _resolve_subrecipe_path(self, name: str) -> Optional[str]:
+        """Resolve a child recipe next to the parent first, then by recipe name."""
+        candidates: List[str] = []
+        if self.recipe_path:
+            base_dir = os.path.dirname(os.path.abspath(self.recipe_path))
+            candidates.append(os.path.join(base_dir, name))
+            if not name.endswith(RECIPE_FILE_EXTENSIONS):
+                candidates.extend(os.path.join(base_dir, name + extension) for extension in RECIPE_FILE_EXTENSIONS)
+        for candidate in candidates:
+            if os.path.isfile(candidate):
+                return os.path.abspath(candidate)
+
+        from ..commands.recipe import find_recipe
+
+        found = find_recipe(os.path.expanduser(name))
+        return os.path.abspath(found) if found else None
+
+    def _subrecipe_host(self, value: str) -> str:
+        """Map a parent host alias to its current spec; anything else is a literal spec."""
+        value = self._interpolate(value)
+        return str(self.recipe.hosts.get(value, value))
+
+    def _recipe_stack(self) -> List[str]:
+        stack = [str(item) for item in self.executor_kwargs.get("recipe_stack", []) or []]
+        if self.recipe_path:
+            stack.append(os.path.abspath(self.recipe_path))
+        return stack
+
+    def _run_child_recipe(
+        self,
+        path: str,
+        child_job_id: str,
+        parent_step_id: str,
+        stack: List[str],
+        var_overrides: Dict[str, str],
+        host_overrides: Optional[Dict[str, str]] = None,
+    ) -> bool:
+        """Run `path` as job `child_job_id` linked to this job; True when it succeeds."""
+        from .dag_executor import DagExecutor
+        from .dag_processor import DagProcessor
+
+        label = _recipe_label(path)
+
+        def _nested_log(line: str) -> None:
+            with self._thread_lock:
+                self.log_callback(f"    ↳ [{label}] {line}")
+
+        runner = DagExecutor(
+            executor_name=None,
+            executor_kwargs={
+                "parent_job_id": self.ctx.job_id,
+                "parent_step_id": parent_step_id,
+                "recipe_stack": stack,
+            },
+            prefer_runtime_options=True,
+            log_callback=_nested_log,
+        )
+        try:
+            result = runner.run(
+                DagProcessor().process_dag_file(Path(path)),
+                run_id=child_job_id,
+                host_overrides=host_overrides or None,
+                var_overrides=var_overrides or None,
+            )
+        except Exception as exc:
+            result = None
+            _nested_log(f"error: {exc}")
+        if result is not None and result.error:
+            _nested_log(f"error: {result.error}")
+        return bool(result and result.success)
+
+    def _session_workdir(self) -> tuple[str, str]:
+        """(host, current directory) of the run's most recently opened tmux session that is still open."""
+        for window in reversed(list(self.ctx.windows.values())):
+            if not window.remote_session:
+                continue
+            try:
+                result = self.get_tmux_client(window.host).display_message(window.remote_session, "#{pane_current_path}")
+            except Exception:
+                continue
+            if result.returncode == 0 and (result.stdout or "").strip():
+                return window.host, result.stdout.strip()
+        return "", ""
+
+    def _run_completion_hooks(self, success: bool, exit_code: Optional[int] = None, *, cancelled: bool = False) -> List[str]:
+        """Start the recipes registered with `recipe.on_complete(...)` once this run has ended.
+
+        Bindings are interpolated with the run's final variables plus
+        `RUN_STATUS` (`completed`/`failed`/`cancelled`), `EXIT_CODE` (of the
+        failing step; empty when unknown), `JOB_ID`, and `SESSION_HOST` /
+        `SESSION_WORKDIR` of the last tmux session still open. Cancelled runs
+        fire `failed` hooks. Each spawned job id is saved on this job's state
+        and emitted as an `on_complete` event before it starts.
+        """
+        from .job_state import generate_job_id
+
+        hooks = list(getattr(self.recipe, "completion_hooks", []) or [])
+        if not hooks:
+            return []
+        status = "completed" if success else "cancelled" if cancelled else "failed"
+        session_host, workdir = self._session_workdir()
+        bindings = {
+            "RUN_STATUS": status,
+            "EXIT_CODE": "" if exit_code is None else str(exit_code),
+            "JOB_ID": self.ctx.job_id,
+            "SESSION_HOST": session_host,
+            "SESSION_WORKDIR": workdir,
+        }
+        spawned: List[str] = []
+        for hook in hooks:
+            if hook.get("when", "always") not in {"always", "completed" if success else "failed"}:
+                continue
+            saved = dict(self.ctx.variables)
+            self.ctx.variables.update(bindings)
+            try:
+                name = self._interpolate(str(hook.get("recipe", ""))).strip()
+                variables = {str(key): self._interpolate(str(value)) for key, value in (hook.get("variables") or {}).items()}
+                hosts = {str(key): self._subrecipe_host(str(value)) for key, value in (hook.get("hosts") or {}).items()}
+            finally:
+                self.ctx.variables.clear()
+                self.ctx.variables.update(saved)
+            path = self._resolve_subrecipe_path(name) if name else None
+            stack = self._recipe_stack()
+            if not path or path in stack or len(stack) >= MAX_RECIPE_DEPTH:
+                reason = "not found" if not path else "would call itself" if path in stack else "nested too deep"
+                self.log(f"⚠ on_complete recipe {name or '?'} {reason}; skipped")
+                self._emit_event("on_complete", recipe=name, status=status, skipped=reason)
+                continue
+            child_job_id = generate_job_id()
+            self.log(f"▶ on_complete: running {_recipe_label(path)} (job {child_job_id})")
+            self._emit_event(
+                "on_complete", recipe=name, recipe_path=path, status=status, child_job_id=child_job_id, variables=variables
+            )
+            if self.job_state:
+                self.job_state.on_complete_job_ids.append(child_job_id)
+                self.state_manager.save(self.job_state)
+            self._run_child_recipe(path, child_job_id, "on_complete", stack, variables, hosts)
+            spawned.append(child_job_id)
+        return spawned
+
+    def _exec_provider_recipe_run(self, params: Dict[str, Any]) -> tuple[bool, str]:
+        """Run a child recipe with mapped variables and hosts, linked to this job.
+
+        Children go through the same DAG runner as `train recipe run`, so they pick
+        up the child's own executor settings and callbacks.
+        """
+        from .job_state import generate_job_id
+        from .runtime_store import RuntimeStore
+
+        name = self._interpolate(str(params.get("recipe", "") or "")).strip()
+        if not name:
+            return False, "recipe.run requires 'recipe'"
+        path = self._resolve_subrecipe_path(name)
+        if not path:
+            return False, f"Recipe not found: {name}"
+
+        stack = self._recipe_stack()
+        if path in stack:
+            return False, f"Recursive recipe call: {os.path.basename(path)}"
+        if len(stack) >= MAX_RECIPE_DEPTH:
+            return False, f"Recipe nesting deeper than {MAX_RECIPE_DEPTH} levels"
+
+        variables = params.get("variables") or {}
+        hosts = params.get("hosts") or {}
+        if not isinstance(variables, dict) or not isinstance(hosts, dict):
+            return False, "recipe.run 'variables' and 'hosts' must be objects"
+        var_overrides = {str(key): self._interpolate(str(value)) for key, value in variables.items()}
+        host_overrides = {str(key): self._subrecipe_host(str(value)) for key, value in hosts.items()}
+
+        child_job_id = generate_job_id()
+        parent_step_id = self._current_step_id()
+        label = _recipe_label(path)
+        self.log(f"▶ Running recipe {label} (job {child_job_id})")
+        self._emit_event(
+            "subrecipe_start",
+            step_id=parent_step_id,
+            child_job_id=child_job_id,
+            recipe_path=path,
+            variables=var_overrides,
+            hosts=host_overrides,
+        )
+        ok = self._run_child_recipe(path, child_job_id, parent_step_id, stack, var_overrides, host_overrides)
+
+        store = RuntimeStore(self.runtime_state)
+        states = Counter(str(task.get("state", "")) for task in store.list_tasks(run_id=child_job_id))
+        self._emit_event(
+            "subrecipe_end",
+            step_id=parent_step_id,
+            child_job_id=child_job_id,
+            recipe_path=path,
+            success=ok,
+            steps=dict(states),
+        )
+
+        capture_var = str(params.get("capture_var", "") or "").strip()
+        if capture_var:
+            self.ctx.variables[capture_var] = child_job_id
+        summary = ", ".join(f"{count} {state}" for state, count in sorted(states.items())) or "no steps"
+        verdict = "completed" if ok else "failed"
+        return ok, f"Recipe {label} {verdict} (job {child_job_id}: {summary})"
+
+
+def _recipe_label(path: str) -> str:
+    label = os.path.basename(path)
+    for extension in RECIPE_FILE_EXTENSIONS:
+        if label.endswith(extension):
+            return label[: -len(extension)]
+    return label
+
+
+__all__ = ["ExecutorProviderRecipeMixin", "MAX_RECIPE_DEPTH"]
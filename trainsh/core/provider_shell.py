@@ -8,7 +8,7 @@ import socket
 import subprocess
 import time
 from datetime import datetime
-from typing import Any, Dict, List
+from typing import Any, Dict, Iterable, List
 
 from ..services.git_auth import (
     build_git_clone_command,
@@ -19,19 +19,35 @@ from ..services.git_auth import (
 )
 from ..services.secret_materialize import materialize_secret_file
 from ..utils.notifier import normalize_channels, parse_bool
-from .executor_utils import _build_ssh_args, _host_from_ssh_spec, _resolve_vast_host
+from ..services.remote_shell import wrap_for_shell
+from .executor_utils import _build_ssh_args, _host_from_ssh_spec, _host_shell, _resolve_vast_host
 
 
 class ExecutorProviderShellOpsMixin:
-    def _exec_provider_shell(self, params: Dict[str, Any]) -> tuple[bool, str]:
-        """Execute shell command in provider mode."""
+    def _exec_provider_shell(
+        self,
+        params: Dict[str, Any],
+        *,
+        record_exit: bool = True,
+        ok_exit_codes: Iterable[int] = (0,),
+        exit_code_var: str = "",
+    ) -> tuple[bool, str]:
+        """Execute shell command in provider mode.
+
+        Probes (``record_exit=False``) leave the step's exit code and stop reason alone.
+        ``ok_exit_codes`` and ``exit_code_var`` are only set by ``python.run``.
+        """
         if not isinstance(params, dict):
             return False, "Provider shell params must be an object"
 
         command = str(params.get("command", "")).strip()
         if not command:
             return False, "Provider shell requires 'command'"
-        command = self._interpolate(command)
+        if getattr(self, "safe_variables", False):
+            host = self._provider_host(params.get("host", "local"))
+            command = self._interpolate_command(command, "bash" if host == "local" else _host_shell(host))
+        else:
+            command = self._interpolate(command)
 
         timeout = self._normalize_provider_timeout(params.get("timeout"), allow_zero=True)
         run_timeout = None if timeout in (None, 0) else timeout
@@ -53,7 +69,13 @@ class ExecutorProviderShellOpsMixin:
         host = self._provider_host(params.get("host", "local"))
         run_command = command
         if host != "local" and cwd is not None:
-            run_command = f"cd {shlex.quote(str(cwd))} && ({command})"
+            run_command = wrap_for_shell(command, _host_shell(host), cwd=str(cwd))
+
+        execute_helper = getattr(self, "execute_helper", None)
+        if host == "local" and execute_helper is not None:
+            guarded = execute_helper.guard_local(command, cwd or os.getcwd())
+            if guarded is not None:
+                return guarded
 
         start = datetime.now()
         try:
@@ -78,6 +100,8 @@ class ExecutorProviderShellOpsMixin:
 
             duration_ms = int((datetime.now() - start).total_seconds() * 1000)
             output = result.stdout or result.stderr
+            if record_exit:
+                self._note_exit_code(result.returncode, host)
 
             if self.logger:
                 self.logger.log_ssh(
@@ -89,6 +113,8 @@ class ExecutorProviderShellOpsMixin:
                     duration_ms,
                 )
         except subprocess.TimeoutExpired:
+            if record_exit:
+                self._note_step_stopped(self._current_step_id(), "timeout")
             return False, f"Shell command timed out after {timeout}s"
         except Exception as exc:
             return False, str(exc)
@@ -97,8 +123,11 @@ class ExecutorProviderShellOpsMixin:
         if capture_var:
             if isinstance(capture_var, str):
                 self.ctx.variables[capture_var] = output
+        if exit_code_var:
+            self.ctx.variables[exit_code_var] = str(result.returncode)
 
-        return result.returncode == 0, output or (f"Shell command completed ({duration_ms}ms)" if result.returncode == 0 else "")
+        ok = result.returncode in set(ok_exit_codes)
+        return ok, output or (f"Shell command completed ({duration_ms}ms)" if ok else "")
 
     def _eval_condition(self, condition: str, *, host: str = "local") -> tuple[bool, str]:
         """Evaluate simple condition expression."""
@@ -360,6 +389,7 @@ class ExecutorProviderShellOpsMixin:
                 )
             duration_ms = int((datetime.now() - start).total_seconds() * 1000)
             output = result.stdout or result.stderr
+            self._note_exit_code(result.returncode, host)
             if self.logger:
                 self.logger.log_ssh(
                     host,
@@ -373,6 +403,7 @@ class ExecutorProviderShellOpsMixin:
                 f"Shell command completed ({duration_ms}ms)" if result.returncode == 0 else ""
             )
         except subprocess.TimeoutExpired:
+            self._note_step_stopped(self._current_step_id(), "timeout")
             return False, f"Shell command timed out after {timeout}s"
         except Exception as exc:
             return False, str(exc)
@@ -494,7 +525,7 @@ class ExecutorProviderShellOpsMixin:
         if source.startswith("env:"):
             value = os.environ.get(source[4:], default_value)
         elif source.startswith("secret:"):
-            value = self.secrets.get(source[7:]) or default_value
+            value = self._secret(source[7:]) or default_value
         elif source.startswith("var:"):
             value = self.ctx.variables.get(source[4:], default_value)
         elif source.startswith("command:"):
@@ -460,6 +460,17 @@ class ExecutorProviderStorageMixin:
 
         recursive = self._coerce_bool(params.get("recursive", False), default=False)
 
+        from ..services.storage_trash import StorageTrash, trash_settings
+
+        # The config default skips HF buckets, which have no trash; an explicit trash=True still errors.
+        default_trash = trash_settings()["enabled"] and storage.type != StorageType.HF
+        if self._coerce_bool(params.get("trash"), default=default_trash):
+            try:
+                entry = StorageTrash(storage).delete(path, recursive=recursive)
+            except (OSError, RuntimeError, ValueError) as exc:
+                return False, str(exc)
+            return True, f"Moved to trash: {path} (restore with id {entry['id']})"
+
         if storage.type == StorageType.LOCAL:
             target = self._storage_local_path(storage, path)
             if not os.path.exists(target):
@@ -484,6 +495,36 @@ class ExecutorProviderStorageMixin:
         op = "purge" if recursive else "delete"
         return self._exec_storage_rclone(storage, [op, self._storage_rclone_path(storage, path)])
 
+    def _exec_provider_storage_trash(self, operation: str, params: Dict[str, Any]) -> tuple[bool, str]:
+        """List, restore, or empty a storage's `.trash/` area."""
+        if not isinstance(params, dict):
+            return False, f"Provider storage.{operation} params must be an object"
+
+        storage = self._resolve_storage(params.get("storage"))
+        if storage is None:
+            return False, f"Provider storage.{operation} requires storage id"
+
+        from ..services.storage_trash import StorageTrash
+
+        try:
+            trash = StorageTrash(storage)
+            if operation == "trash_list":
+                return True, json.dumps(trash.list(), ensure_ascii=False)
+            if operation == "trash_restore":
+                entry_id = str(params.get("id", params.get("entry_id", ""))).strip()
+                if not entry_id:
+                    return False, "Provider storage.trash_restore requires 'id'"
+                destination = str(params.get("destination", "")).strip() or None
+                entry = trash.restore(entry_id, destination=destination)
+                return True, f"Restored {entry['original_path']} -> {entry['restored_to']}"
+            older_than = params.get("older_than_days")
+            removed = trash.empty(older_than_days=None if older_than in (None, "") else self._coerce_float(older_than))
+            return True, f"Emptied {len(removed)} trash entr{'y' if len(removed) == 1 else 'ies'}"
+        except KeyError as exc:
+            return False, str(exc.args[0] if exc.args else exc)
+        except (OSError, RuntimeError, ValueError) as exc:
+            return False, str(exc)
+
     def _exec_provider_storage_rename(self, params: Dict[str, Any]) -> tuple[bool, str]:
         """Rename within storage."""
         if not isinstance(params, dict):
@@ -492,7 +533,8 @@ class ExecutorProviderStorageMixin:
         storage = self._resolve_storage(params.get("storage"))
         if storage is None:
             return False, "Provider storage.rename requires storage id"
-        source = str(params.get("source", "")).strip()
+        source = params.get("source", "")
+        source = [str(item).strip() for item in source] if isinstance(source, (list, tuple)) else str(source).strip()
         destination = str(params.get("destination", "")).strip()
         if not source or not destination:
             return False, "Provider storage.rename requires source and destination"
@@ -528,10 +570,12 @@ class ExecutorProviderStorageMixin:
                 )
                 if not ok:
                     return False, message
-                ok, message = self._exec_provider_storage_delete({"storage": storage_name, "path": source})
+                ok, message = self._exec_provider_storage_delete({"storage": storage_name, "path": source, "trash": False})
                 if ok:
                     return True, message
-                return self._exec_provider_storage_delete({"storage": storage_name, "path": source, "recursive": True})
+                return self._exec_provider_storage_delete(
+                    {"storage": storage_name, "path": source, "recursive": True, "trash": False}
+                )
 
         return self._exec_storage_rclone(
             storage,
@@ -604,7 +648,8 @@ class ExecutorProviderStorageMixin:
         if not isinstance(params, dict):
             return False, "Provider transfer params must be an object"
 
-        source = str(params.get("source", "")).strip()
+        source = params.get("source", "")
+        source = [str(item).strip() for item in source] if isinstance(source, (list, tuple)) else str(source).strip()
         destination = str(params.get("destination", "")).strip()
         if not source or not destination:
             return False, "Provider transfer requires 'source' and 'destination'"
@@ -624,4 +669,7 @@ class ExecutorProviderStorageMixin:
             delete=delete,
             exclude=exclude,
             operation=operation,
+            rsync_options=params.get("rsync"),
+            tar_stream=self._coerce_bool(params.get("tar_stream", False), default=False),
+            remote_exec=params.get("remote_exec"),
         )
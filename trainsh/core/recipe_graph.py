@@ -0,0 +1,180 @@
+"""Render a loaded recipe's step DAG as Mermaid or Graphviz DOT text.
+
+Nodes show the step id, its operation and a short detail; condition steps are
+diamonds, `trigger_rule`s other than `all_success` label their incoming edges,
+`on_failure_run` handlers hang off a dashed edge and group members share a cluster.
+"""
+
+from __future__ import annotations
+
+import json
+from dataclasses import dataclass, field
+from typing import Any, Dict, List
+
+GRAPH_FORMATS = ("mermaid", "dot")
+MAX_DETAIL_CHARS = 48
+CONDITION_OPERATIONS = {
+    "util.branch",
+    "util.short_circuit",
+    "util.skip_if",
+    "util.skip_if_not",
+    "util.assert",
+    "util.wait_condition",
+}
+# Param keys that best summarise a provider step, in order of preference.
+_DETAIL_PARAMS = ("condition", "command", "script", "source", "url", "message", "host", "recipe", "name")
+
+
+@dataclass
+class GraphNode:
+    """One step as drawn in the graph."""
+
+    id: str
+    operation: str
+    detail: str = ""
+    condition: bool = False
+    always_run: bool = False
+    group: str = ""
+    depends_on: List[str] = field(default_factory=list)
+    trigger_rule: str = "all_success"
+    on_failure_run: str = ""
+
+    @property
+    def label_lines(self) -> List[str]:
+        lines = [self.id, self.operation]
+        if self.detail:
+            lines.append(self.detail)
+        if self.always_run:
+            lines.append("(always runs)")
+        return lines
+
+
+def _short(text: Any) -> str:
+    value = " ".join(str(text or "").split())
+    return value if len(value) <= MAX_DETAIL_CHARS else value[: MAX_DETAIL_CHARS - 1] + "…"
+
+
+def graph_nodes(recipe: Any) -> List[GraphNode]:
+    """One `GraphNode` per step of a loaded recipe, in definition order."""
+    nodes = []
+    for step in recipe.steps:
+        provider = getattr(step, "provider", None)
+        if provider is not None:
+            operation = f"{provider}.{step.operation}"
+            params = step.params or {}
+            detail = next((params[key] for key in _DETAIL_PARAMS if params.get(key)), "")
+            if isinstance(detail, (dict, list)):
+                detail = json.dumps(detail, ensure_ascii=False)
+            condition = operation in CONDITION_OPERATIONS
+        else:
+            operation = step.type.value
+            detail = step.raw
+            condition = bool(step.condition)
+        nodes.append(
+            GraphNode(
+                id=str(step.id),
+                operation=operation,
+                detail=_short(detail),
+                condition=condition,
+                always_run=bool(getattr(step, "always_run", False)),
+                group=str(getattr(step, "group", "") or ""),
+                depends_on=[str(dep) for dep in step.depends_on],
+                trigger_rule=str(getattr(step, "trigger_rule", "") or "all_success"),
+                on_failure_run=str(getattr(step, "on_failure_run", "") or ""),
+            )
+        )
+    return nodes
+
+
+def _mermaid_id(step_id: str) -> str:
+    return "s_" + "".join(ch if ch.isalnum() else "_" for ch in step_id)
+
+
+def _mermaid_text(lines: List[str]) -> str:
+    return "<br/>".join(line.replace('"', "#quot;") for line in lines)
+
+
+def _dot_text(value: str) -> str:
+    return value.replace("\\", "\\\\").replace('"', '\\"')
+
+
+def render_mermaid(recipe: Any) -> str:
+    """Mermaid `flowchart` of the recipe DAG."""
+    nodes = graph_nodes(recipe)
+    out = ["flowchart TD"]
+    groups: Dict[str, List[GraphNode]] = {}
+    for node in nodes:
+        groups.setdefault(node.group, []).append(node)
+    for group, members in groups.items():
+        indent = "    "
+        if group:
+            out.append(f'    subgraph {_mermaid_id("group_" + group)}["{_mermaid_text([group])}"]')
+            indent = "        "
+        for node in members:
+            text = _mermaid_text(node.label_lines)
+            shape = f'{{"{text}"}}' if node.condition else f'["{text}"]'
+            out.append(f"{indent}{_mermaid_id(node.id)}{shape}")
+        if group:
+            out.append("    end")
+    for node in nodes:
+        for dep in node.depends_on:
+            rule = "" if node.trigger_rule == "all_success" else f"|{node.trigger_rule}|"
+            out.append(f"    {_mermaid_id(dep)} -->{rule} {_mermaid_id(node.id)}")
+        if node.on_failure_run:
+            out.append(f"    {_mermaid_id(node.id)} -.->|on failure| {_mermaid_id(node.on_failure_run)}")
+    return "\n".join(out) + "\n"
+
+
+def render_dot(recipe: Any) -> str:
+    """Graphviz `digraph` of the recipe DAG."""
+    nodes = graph_nodes(recipe)
+    name = _dot_text(str(getattr(recipe, "name", "") or "recipe"))
+    out = [f'digraph "{name}" {{', "    rankdir=TB;", '    node [shape=box, fontname="Helvetica"];']
+    groups: Dict[str, List[GraphNode]] = {}
+    for node in nodes:
+        groups.setdefault(node.group, []).append(node)
+    for group, members in groups.items():
+        indent = "    "
+        if group:
+            out.append(f'    subgraph "cluster_{_dot_text(group)}" {{')
+            out.append(f'        label="{_dot_text(group)}";')
+            indent = "        "
+        for node in members:
+            label = "\\n".join(_dot_text(line) for line in node.label_lines)
+            attrs = [f'label="{label}"']
+            if node.condition:
+                attrs.append("shape=diamond")
+            if node.always_run:
+                attrs.append("style=bold")
+            out.append(f'{indent}"{_dot_text(node.id)}" [{", ".join(attrs)}];')
+        if group:
+            out.append("    }")
+    for node in nodes:
+        for dep in node.depends_on:
+            rule = "" if node.trigger_rule == "all_success" else f' [label="{_dot_text(node.trigger_rule)}"]'
+            out.append(f'    "{_dot_text(dep)}" -> "{_dot_text(node.id)}"{rule};')
+        if node.on_failure_run:
+            out.append(
+                f'    "{_dot_text(node.id)}" -> "{_dot_text(node.on_failure_run)}" [style=dashed, label="on failure"];'
+            )
+    out.append("}")
+    return "\n".join(out) + "\n"
+
+
+def render_recipe_graph(recipe: Any, fmt: str = "mermaid") -> str:
+    """Graph text of `recipe` in `fmt` (`mermaid` or `dot`)."""
+    if fmt == "mermaid":
+        return render_mermaid(recipe)
+    if fmt == "dot":
+        return render_dot(recipe)
+    raise ValueError(f"unknown graph format {fmt!r} (use {' or '.join(GRAPH_FORMATS)})")
+
+
+__all__ = [
+    "GRAPH_FORMATS",
+    "GraphNode",
+    "graph_nodes",
+    "render_dot",
+    "render_mermaid",
+    "render_recipe_graph",
+]
@@ -0,0 +1,341 @@
+"""Best-practice warnings for a loaded recipe, on top of schema validation.
+
+Each finding names a rule, a severity (`error`, `warning` or `info`) and the
+step it is about:
+
+- `no-timeout`: a command or wait step that could hang with no timeout.
+- `network-no-retry`: a network operation (HTTP, git, transfers, storage,
+  cloud GPUs) without retries.
+- `unquoted-variable`: `$VAR` outside double quotes in a shell command.
+- `unreachable`: a step no path from a root step leads to.
+- `plain-secret`: a secret-looking variable or parameter holding a literal
+  value instead of `${secret:NAME}`.
+- `destructive-without-backup`: a step that deletes data with no backup or
+  check step before it.
+- `unpinned-requirement`: a `python.run` requirement without an exact
+  `==` version, so reruns may install something else.
+"""
+
+from __future__ import annotations
+
+import re
+from collections import deque
+from dataclasses import dataclass
+from typing import Any, Dict, Iterable, List, Set
+
+from ..services.python_run import unpinned_requirements
+from .recipe_models import SCRIPT_PLACEHOLDER, StepType
+
+SEVERITIES = ("error", "warning", "info")
+# Provider operations that run a command or block until something happens.
+_LONG_RUNNING = {
+    "shell.run",
+    "bash.run",
+    "python.run",
+    "util.ssh_command",
+    "util.uv_run",
+    "util.wait_condition",
+    "util.wait_for_file",
+    "util.wait_file",
+    "util.wait_for_port",
+    "util.wait_port",
+    "storage.wait",
+    "storage.wait_count",
+    "http.wait_for_status",
+    "http.http_sensor",
+    "notebook.run",
+}
+_NETWORK_PROVIDERS = {"http", "git", "transfer", "storage", "cloud", "vast", "runpod", "slack", "discord", "telegram", "webhook", "email"}
+_NETWORK_OPERATIONS = {"util.hf_download", "util.fetch_exchange_rates"}
+# Waits poll on their own, so a retry would only repeat the whole wait.
+_POLLING_OPERATIONS = {"storage.wait", "storage.wait_count", "http.wait_for_status", "http.http_sensor", "vast.wait", "runpod.wait"}
+_COMMAND_PARAMS = {"shell.run": "command", "bash.run": "command", "util.ssh_command": "command", "util.uv_run": "command"}
+# Steps that count as a backup (data copied elsewhere) or a check before deleting.
+_SAFEGUARD_OPERATIONS = {
+    "util.assert",
+    "util.branch",
+    "util.short_circuit",
+    "util.skip_if",
+    "util.skip_if_not",
+    "util.integrity_manifest",
+    "util.integrity_verify",
+    "storage.upload",
+    "storage.download",
+    "storage.copy",
+}
+_SECRET_NAME_RE = re.compile(r"token|secret|passw(or)?d|api_?key|access_?key|private_?key|credential", re.IGNORECASE)
+# Keys that hold the *name* of a secret or variable, not its value.
+_SECRET_REF_SUFFIXES = ("_secret", "_env", "_var", "_name")
+_VARIABLE_RE = re.compile(r"\$(\{[^}]*\}|[A-Za-z_][A-Za-z0-9_]*)")
+
+
+@dataclass
+class LintFinding:
+    """One best-practice problem in a recipe."""
+
+    severity: str
+    rule: str
+    message: str
+    step: str = ""
+
+    def __str__(self) -> str:
+        where = f"{self.step}: " if self.step else ""
+        return f"{self.severity}: {where}{self.message} [{self.rule}]"
+
+    def to_dict(self) -> Dict[str, str]:
+        return {"severity": self.severity, "rule": self.rule, "step": self.step, "message": self.message}
+
+
+def _operation(step: Any) -> str:
+    provider = getattr(step, "provider", None)
+    if provider is None:
+        return ""
+    return f"{str(provider).lower()}.{str(step.operation).lower()}"
+
+
+def _params(step: Any) -> Dict[str, Any]:
+    params = getattr(step, "params", None)
+    return params if isinstance(params, dict) else {}
+
+
+def _has_timeout(step: Any) -> bool:
+    if getattr(step, "execution_timeout", 0):
+        return True
+    if _operation(step):
+        value = _params(step).get("timeout")
+        return value not in (None, "", 0, "0")
+    return bool(getattr(step, "timeout", 0))
+
+
+def _check_timeouts(step: Any) -> List[LintFinding]:
+    operation = _operation(step)
+    if operation:
+        if operation not in _LONG_RUNNING:
+            return []
+        what = operation
+    elif step.type == StepType.EXECUTE and not step.background:
+        what = "command"
+    elif step.type == StepType.WAIT:
+        what = "wait"
+    else:
+        return []
+    if _has_timeout(step):
+        return []
+    # Training commands may rightly run for days; a wait without a timeout can only hang.
+    severity = "info" if what in ("command", *_COMMAND_PARAMS, "python.run", "notebook.run") else "warning"
+    return [LintFinding(severity, "no-timeout", f"{what} has no timeout; a hung command blocks the run", str(step.id))]
+
+
+def _check_retries(step: Any) -> List[LintFinding]:
+    operation = _operation(step)
+    is_transfer = not operation and step.type == StepType.TRANSFER
+    network = is_transfer or operation in _NETWORK_OPERATIONS or operation.split(".", 1)[0] in _NETWORK_PROVIDERS
+    if not network or operation in _POLLING_OPERATIONS or getattr(step, "retries", 0):
+        return []
+    what = "transfer" if is_transfer else operation
+    return [LintFinding("warning", "network-no-retry", f"{what} talks to the network but has no retries", str(step.id))]
+
+
+def unquoted_variables(command: str) -> List[str]:
+    """`$VAR`/`${VAR}` references outside double quotes, where the shell splits and globs them."""
+    found: List[str] = []
+    quote = ""
+    i = 0
+    while i < len(command):
+        char = command[i]
+        if char == "\\" and quote != "'":
+            i += 2
+            continue
+        if char in ("'", '"'):
+            if not quote:
+                quote = char
+            elif quote == char:
+                quote = ""
+        elif char == "$" and not quote:
+            match = _VARIABLE_RE.match(command, i)
+            # `NAME=$VAR` is an assignment; the shell does not split it.
+            assignment = re.search(r"(^|\s)[A-Za-z_][A-Za-z0-9_]*=$", command[:i]) is not None
+            if match and not assignment:
+                reference = match.group(0)
+                name = reference.strip("${}")
+                if reference != SCRIPT_PLACEHOLDER and not name.startswith("secret:") and reference not in found:
+                    found.append(reference)
+            if match:
+                i = match.end()
+                continue
+        i += 1
+    return found
+
+
+def _check_quoting(step: Any) -> List[LintFinding]:
+    operation = _operation(step)
+    if operation:
+        command = _params(step).get(_COMMAND_PARAMS.get(operation, ""), "")
+    elif step.type == StepType.EXECUTE:
+        command = step.commands
+    else:
+        command = ""
+    if not isinstance(command, str) or not command:
+        return []
+    return [
+        LintFinding("warning", "unquoted-variable", f"{reference} is not quoted; write \"{reference}\"", str(step.id))
+        for reference in unquoted_variables(command)
+    ]
+
+
+def _check_requirements(step: Any) -> List[LintFinding]:
+    if _operation(step) != "python.run":
+        return []
+    requirements = _params(step).get("requirements") or []
+    if isinstance(requirements, str):
+        requirements = [requirements]
+    return [
+        LintFinding("warning", "unpinned-requirement", f"requirement {item!r} has no exact version; pin it as NAME==VERSION", str(step.id))
+        for item in unpinned_requirements(requirements)
+    ]
+
+
+def _is_secret_key(key: str) -> bool:
+    return bool(_SECRET_NAME_RE.search(key)) and not key.lower().endswith(_SECRET_REF_SUFFIXES)
+
+
+def _is_literal(value: Any) -> bool:
+    return isinstance(value, str) and bool(value.strip()) and "${" not in value
+
+
+def _secret_params(params: Dict[str, Any], prefix: str = "") -> Iterable[str]:
+    for key, value in params.items():
+        path = f"{prefix}{key}"
+        if isinstance(value, dict):
+            yield from _secret_params(value, f"{path}.")
+        elif _is_secret_key(str(key)) and _is_literal(value):
+            yield path
+
+
+def _check_secrets(recipe: Any) -> List[LintFinding]:
+    findings = [
+        LintFinding("error", "plain-secret", f"variable {name} holds a literal secret; use ${{secret:{name}}}")
+        for name, value in (getattr(recipe, "variables", None) or {}).items()
+        if _is_secret_key(str(name)) and _is_literal(value)
+    ]
+    for step in recipe.steps:
+        for path in _secret_params(_params(step)):
+            findings.append(
+                LintFinding("error", "plain-secret", f"param {path} holds a literal secret; use ${{secret:NAME}}", str(step.id))
+            )
+    return findings
+
+
+def _children(steps: List[Any]) -> Dict[str, List[str]]:
+    children: Dict[str, List[str]] = {str(step.id): [] for step in steps}
+    for step in steps:
+        for dep in step.depends_on:
+            children.setdefault(str(dep), []).append(str(step.id))
+        handler = str(getattr(step, "on_failure_run", "") or "")
+        if handler:
+            children.setdefault(str(step.id), []).append(handler)
+    return children
+
+
+def _check_reachability(steps: List[Any]) -> List[LintFinding]:
+    children = _children(steps)
+    seen: Set[str] = set()
+    queue = deque(str(step.id) for step in steps if not step.depends_on)
+    while queue:
+        step_id = queue.popleft()
+        if step_id in seen:
+            continue
+        seen.add(step_id)
+        queue.extend(children.get(step_id, []))
+    return [
+        LintFinding("warning", "unreachable", "no path from a root step leads here (dependency cycle?)", str(step.id))
+        for step in steps
+        if str(step.id) not in seen
+    ]
+
+
+def _is_destructive(step: Any) -> bool:
+    if getattr(step, "destructive", False):
+        return True
+    operation = _operation(step)
+    params = _params(step)
+    if operation in ("storage.delete", "cloud.delete"):
+        return not params.get("trash")
+    if operation == "storage.trash_empty":
+        return True
+    if operation == "storage.sync" or operation.startswith("transfer."):
+        return bool(params.get("delete"))
+    if not operation and step.type == StepType.TRANSFER:
+        return bool(step.step_model.delete)
+    return False
+
+
+def _is_safeguard(step: Any) -> bool:
+    operation = _operation(step)
+    if operation in _SAFEGUARD_OPERATIONS:
+        return True
+    if operation.startswith("transfer.") or not operation and step.type == StepType.TRANSFER:
+        return not _is_destructive(step)
+    return False
+
+
+def _check_destructive(steps: List[Any]) -> List[LintFinding]:
+    by_id = {str(step.id): step for step in steps}
+    findings = []
+    for step in steps:
+        if not _is_destructive(step):
+            continue
+        ancestors: Set[str] = set()
+        queue = deque(str(dep) for dep in step.depends_on)
+        while queue:
+            step_id = queue.popleft()
+            if step_id in ancestors:
+                continue
+            ancestors.add(step_id)
+            queue.extend(str(dep) for dep in getattr(by_id.get(step_id), "depends_on", []))
+        if any(_is_safeguard(by_id[item]) for item in ancestors if item in by_id):
+            continue
+        findings.append(
+            LintFinding(
+                "warning",
+                "destructive-without-backup",
+                "deletes data with no backup or check step before it",
+                str(step.id),
+            )
+        )
+    return findings
+
+
+def lint_recipe(recipe: Any) -> List[LintFinding]:
+    """Best-practice findings for a loaded recipe, most severe first."""
+    steps = list(recipe.steps)
+    findings: List[LintFinding] = []
+    for step in steps:
+        findings.extend(_check_timeouts(step))
+        findings.extend(_check_retries(step))
+        findings.extend(_check_quoting(step))
+        findings.extend(_check_requirements(step))
+    findings.extend(_check_reachability(steps))
+    findings.extend(_check_secrets(recipe))
+    findings.extend(_check_destructive(steps))
+    return sorted(findings, key=lambda item: SEVERITIES.index(item.severity))
+
+
+def lint_file(path: str) -> List[LintFinding]:
+    """Load a recipe file and lint it; a file that does not load is one `invalid` error."""
+    from ..pyrecipe import load_recipe
+
+    try:
+        recipe = load_recipe(path)
+    except Exception as exc:
+        return [LintFinding("error", "invalid", str(exc))]
+    return lint_recipe(recipe)
+
+
+__all__ = [
+    "LintFinding",
+    "SEVERITIES",
+    "lint_file",
+    "lint_recipe",
+    "unquoted_variables",
+]
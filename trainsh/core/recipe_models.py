@@ -4,7 +4,10 @@ from __future__ import annotations
 
 from dataclasses import dataclass, field
 from enum import Enum
-from typing import Any, Dict, List
+from typing import Any, Dict, List, Optional
+
+# Stands for the uploaded copy of an execute step's `script` in its commands.
+SCRIPT_PLACEHOLDER = "${TRAINSH_SCRIPT}"
 
 
 class StepType(Enum):
@@ -31,11 +34,18 @@ class RecipeStepModel:
     timeout: int = 0
     capture_var: str = ""
     capture_path: str = ""
+    # GPU indices exported as CUDA_VISIBLE_DEVICES for this command.
+    gpus: List[str] = field(default_factory=list)
+    # Local script file uploaded (by content hash) to the host before the command runs.
+    script: str = ""
     source: str = ""
     dest: str = ""
     delete: bool = False
     operation: str = "copy"
     exclude: List[str] = field(default_factory=list)
+    rsync: Dict[str, Any] = field(default_factory=dict)
+    tar_stream: bool = False
+    remote_exec: Optional[bool] = None
     target: str = ""
     pattern: str = ""
     condition: str = ""
@@ -50,6 +60,8 @@ class RecipeModel:
     hosts: Dict[str, str] = field(default_factory=dict)
     storages: Dict[str, Any] = field(default_factory=dict)
     steps: List[RecipeStepModel] = field(default_factory=list)
+    # Named env-var sets (values may hold ${secret:NAME}) for tmux.open env=...
+    env_sets: Dict[str, Dict[str, str]] = field(default_factory=dict)
 
 
-__all__ = ["RecipeModel", "RecipeStepModel", "StepType"]
+__all__ = ["RecipeModel", "RecipeStepModel", "SCRIPT_PLACEHOLDER", "StepType"]
@@ -0,0 +1,302 @@
+"""JSON Schema for declarative recipe documents, generated from the recipe step dataclasses.
+
+Editors load the exported file for completion and inline errors; `validate_document`
+interprets the same schema in-app, so both agree on what a recipe may contain.
+Only the keywords the generated schema uses are supported: `type`, `enum`,
+`properties`, `required`, `additionalProperties`, `items`, `minLength`, `minimum`,
+local `$ref`s and `oneOf` (branches told apart by their `required` keys).
+"""
+
+from __future__ import annotations
+
+import dataclasses
+import json
+import typing
+from dataclasses import dataclass
+from enum import Enum
+from pathlib import Path
+from typing import Any, Dict, List, Optional, Tuple
+
+import yaml
+
+from ..constants import RECIPE_SCHEMA_FILE
+from ..pyrecipe.models import ProviderStep
+from .recipe_models import RecipeStepModel
+
+SCHEMA_DIALECT = "https://json-schema.org/draft/2020-12/schema"
+TRIGGER_RULES = [
+    "all_success",
+    "all_done",
+    "all_failed",
+    "one_success",
+    "one_failed",
+    "none_failed",
+    "none_failed_or_skipped",
+]
+# ProviderStep fields that are not per-step options.
+_PROVIDER_FIELDS = ("id", "provider", "operation", "params", "depends_on")
+# Model fields the loader fills in itself.
+_INTERNAL_MODEL_FIELDS = ("line_num",)
+_DURATION = {"type": ["integer", "string"], "description": "Seconds, or a duration such as 30s, 5m, 2h."}
+_OVERRIDES: Dict[str, Dict[str, Any]] = {
+    "retry_delay": _DURATION,
+    "execution_timeout": _DURATION,
+    "timeout": _DURATION,
+    "trigger_rule": {"type": "string", "enum": TRIGGER_RULES},
+    "retries": {"type": "integer", "minimum": 0},
+    "priority": {"type": "integer"},
+}
+_DESCRIPTIONS = {
+    "retries": "Extra attempts after a failure.",
+    "retry_delay": "Wait before each retry.",
+    "retry_exponential_backoff": "Multiply the retry delay by this factor per attempt (0 = fixed delay).",
+    "retry_on_exit_codes": "Only retry when the step exits with one of these codes.",
+    "retry_on_output_regex": "Only retry when the failed output matches this regex.",
+    "continue_on_failure": "Let dependants run even when this step fails.",
+    "trigger_rule": "When the step may run, given its upstream states.",
+    "pool": "Concurrency pool the step takes a slot from.",
+    "execution_timeout": "Stop the step after this long.",
+    "done_check": "Shell check on the step host; exit 0 skips the step as already done.",
+    "on_failure_run": "Step id to run when this step fails.",
+    "always_run": "Run even when the recipe fails or is cancelled (cleanup).",
+    "destructive": "Record each run in the audit trail; refused in viewer mode.",
+    "group": "Id of the group step this step belongs to.",
+}
+
+
+@dataclass
+class SchemaIssue:
+    """One place where a document does not match the schema."""
+
+    path: str
+    message: str
+    line: Optional[int] = None
+
+    def __str__(self) -> str:
+        where = f"line {self.line}: " if self.line else ""
+        return f"{where}{self.path or '<root>'}: {self.message}"
+
+
+def _annotation_schema(annotation: Any) -> Dict[str, Any]:
+    """JSON Schema fragment for one dataclass field annotation."""
+    origin = typing.get_origin(annotation)
+    args = [arg for arg in typing.get_args(annotation) if arg is not type(None)]
+    if origin is typing.Union:
+        inner = _annotation_schema(args[0]) if len(args) == 1 else {}
+        if "type" in inner:
+            kinds = inner["type"] if isinstance(inner["type"], list) else [inner["type"]]
+            return {**inner, "type": [*kinds, "null"]}
+        return inner
+    if annotation in (list, List) or origin is list:
+        return {"type": "array", "items": _annotation_schema(args[0])} if args else {"type": "array"}
+    if annotation in (dict, Dict) or origin is dict:
+        return {"type": "object"}
+    if isinstance(annotation, type) and issubclass(annotation, Enum):
+        return {"type": "string", "enum": [member.value for member in annotation]}
+    simple = {str: "string", bool: "boolean", int: "integer", float: "number"}
+    return {"type": simple[annotation]} if annotation in simple else {}
+
+
+def _field_schemas(cls: type, skip: Tuple[str, ...]) -> Dict[str, Dict[str, Any]]:
+    hints = typing.get_type_hints(cls)
+    properties: Dict[str, Dict[str, Any]] = {}
+    for item in dataclasses.fields(cls):
+        if item.name in skip:
+            continue
+        schema = dict(_OVERRIDES.get(item.name) or _annotation_schema(hints[item.name]))
+        if item.name in _DESCRIPTIONS:
+            schema["description"] = _DESCRIPTIONS[item.name]
+        properties[item.name] = schema
+    return properties
+
+
+def recipe_schema() -> Dict[str, Any]:
+    """The recipe document schema; step options come from `ProviderStep`, step bodies from `RecipeStepModel`."""
+    options = _field_schemas(ProviderStep, _PROVIDER_FIELDS)
+    common = {
+        "id": {"type": "string", "minLength": 1, "description": "Unique step id; generated when omitted."},
+        "depends_on": {"type": "array", "items": {"type": "string"}, "description": "Step ids that must finish first."},
+    }
+    model_fields = _field_schemas(RecipeStepModel, _INTERNAL_MODEL_FIELDS)
+    return {
+        "$schema": SCHEMA_DIALECT,
+        "title": "tmux-trainsh recipe",
+        "type": "object",
+        "required": ["name", "steps"],
+        "additionalProperties": False,
+        "properties": {
+            "name": {"type": "string", "minLength": 1},
+            "variables": {"type": "object", "additionalProperties": {"type": ["string", "number", "boolean"]}},
+            "hosts": {"type": "object", "additionalProperties": {"type": "string"}},
+            "storages": {"type": "object", "additionalProperties": {"type": ["string", "object"]}},
+            "env_sets": {"type": "object", "additionalProperties": {"type": "object", "additionalProperties": {"type": "string"}}},
+            "executor": {"type": "string"},
+            "executor_kwargs": {"type": "object"},
+            "callbacks": {"type": "array", "items": {"type": "string"}},
+            "schedule": {"type": ["string", "null"]},
+            "defaults": {"$ref": "#/$defs/step_options"},
+            "steps": {"type": "array", "items": {"$ref": "#/$defs/step"}},
+        },
+        "$defs": {
+            "step_options": {"type": "object", "additionalProperties": False, "properties": options},
+            "provider_step": {
+                "type": "object",
+                "required": ["provider", "operation"],
+                "additionalProperties": False,
+                "properties": {
+                    **common,
+                    "provider": {"type": "string", "minLength": 1, "description": "Provider name, e.g. shell, vast, storage."},
+                    "operation": {"type": "string", "minLength": 1, "description": "Provider operation, e.g. run, start, upload."},
+                    "params": {"type": "object"},
+                    **options,
+                },
+            },
+            "model_step": {
+                "type": "object",
+                "required": ["type"],
+                "additionalProperties": False,
+                "properties": {**common, **model_fields, **options},
+            },
+            "step": {"oneOf": [{"$ref": "#/$defs/provider_step"}, {"$ref": "#/$defs/model_step"}]},
+        },
+    }
+
+
+def write_recipe_schema(path: Optional[Path] = None) -> Path:
+    """Write the schema for editors (default: the data dir) and return where it went."""
+    target = Path(path or RECIPE_SCHEMA_FILE).expanduser()
+    target.parent.mkdir(parents=True, exist_ok=True)
+    target.write_text(json.dumps(recipe_schema(), indent=2) + "\n", encoding="utf-8")
+    return target
+
+
+_JSON_TYPES = {
+    "string": lambda value: isinstance(value, str),
+    "integer": lambda value: isinstance(value, int) and not isinstance(value, bool),
+    "number": lambda value: isinstance(value, (int, float)) and not isinstance(value, bool),
+    "boolean": lambda value: isinstance(value, bool),
+    "object": lambda value: isinstance(value, dict),
+    "array": lambda value: isinstance(value, list),
+    "null": lambda value: value is None,
+}
+
+
+def _join(path: str, key: Any) -> str:
+    if isinstance(key, int):
+        return f"{path}[{key}]"
+    return f"{path}.{key}" if path else str(key)
+
+
+def _resolve(schema: Dict[str, Any], root: Dict[str, Any]) -> Dict[str, Any]:
+    while "$ref" in schema:
+        node: Any = root
+        for part in schema["$ref"].lstrip("#/").split("/"):
+            node = node[part]
+        schema = node
+    return schema
+
+
+def _check(value: Any, schema: Dict[str, Any], root: Dict[str, Any], path: str, issues: List[SchemaIssue]) -> None:
+    schema = _resolve(schema, root)
+    if "oneOf" in schema:
+        branches = [_resolve(branch, root) for branch in schema["oneOf"]]
+        if not isinstance(value, dict):
+            issues.append(SchemaIssue(path, "expected a mapping"))
+            return
+        for branch in branches:
+            if all(key in value for key in branch.get("required", [])):
+                _check(value, branch, root, path, issues)
+                return
+        wanted = " or ".join("+".join(branch.get("required", [])) for branch in branches)
+        issues.append(SchemaIssue(path, f"needs {wanted}"))
+        return
+
+    kinds = schema.get("type")
+    if kinds is not None:
+        kinds = kinds if isinstance(kinds, list) else [kinds]
+        if not any(_JSON_TYPES[kind](value) for kind in kinds):
+            issues.append(SchemaIssue(path, f"expected {' or '.join(kinds)}, got {type(value).__name__}"))
+            return
+    if "enum" in schema and value not in schema["enum"]:
+        issues.append(SchemaIssue(path, f"{value!r} is not one of {', '.join(map(str, schema['enum']))}"))
+    if "minLength" in schema and isinstance(value, str) and len(value) < schema["minLength"]:
+        issues.append(SchemaIssue(path, "must not be empty"))
+    if "minimum" in schema and isinstance(value, (int, float)) and value < schema["minimum"]:
+        issues.append(SchemaIssue(path, f"must be >= {schema['minimum']}"))
+
+    if isinstance(value, dict):
+        properties = schema.get("properties", {})
+        for key in schema.get("required", []):
+            if key not in value:
+                issues.append(SchemaIssue(path, f"missing required key '{key}'"))
+        extra = schema.get("additionalProperties", True)
+        for key, item in value.items():
+            if key in properties:
+                _check(item, properties[key], root, _join(path, key), issues)
+            elif extra is False:
+                issues.append(SchemaIssue(_join(path, key), "unknown key"))
+            elif isinstance(extra, dict):
+                _check(item, extra, root, _join(path, key), issues)
+    if isinstance(value, list) and "items" in schema:
+        for index, item in enumerate(value):
+            _check(item, schema["items"], root, _join(path, index), issues)
+
+
+def key_lines(text: str) -> Dict[str, int]:
+    """1-based line of every key and list item in a YAML (or JSON) document, by schema path."""
+    try:
+        node = yaml.compose(text)
+    except yaml.YAMLError:
+        return {}
+    lines: Dict[str, int] = {}
+
+    def walk(current: Any, path: str) -> None:
+        if isinstance(current, yaml.MappingNode):
+            for key_node, value_node in current.value:
+                child = _join(path, key_node.value)
+                lines[child] = key_node.start_mark.line + 1
+                walk(value_node, child)
+        elif isinstance(current, yaml.SequenceNode):
+            for index, item in enumerate(current.value):
+                child = _join(path, index)
+                lines[child] = item.start_mark.line + 1
+                walk(item, child)
+
+    if node is not None:
+        walk(node, "")
+    return lines
+
+
+def validate_document(data: Any, *, text: Optional[str] = None) -> List[SchemaIssue]:
+    """Schema issues of a parsed recipe document; pass its source `text` to get line numbers."""
+    root = recipe_schema()
+    issues: List[SchemaIssue] = []
+    _check(data, root, root, "", issues)
+    if text is not None:
+        lines = key_lines(text)
+        for issue in issues:
+            path = issue.path
+            while path and path not in lines:
+                path = path.rpartition("[")[0] if path.endswith("]") else path.rpartition(".")[0]
+            issue.line = lines.get(path)
+    return issues
+
+
+def validate_text(text: str) -> List[SchemaIssue]:
+    """Parse YAML/JSON recipe text and validate it."""
+    try:
+        data = yaml.safe_load(text)
+    except yaml.YAMLError as exc:
+        mark = getattr(exc, "problem_mark", None)
+        return [SchemaIssue("", f"invalid YAML: {getattr(exc, 'problem', None) or exc}", mark.line + 1 if mark else None)]
+    return validate_document(data, text=text)
+
+
+__all__ = [
+    "SchemaIssue",
+    "key_lines",
+    "recipe_schema",
+    "validate_document",
+    "validate_text",
+    "write_recipe_schema",
+]
@@ -0,0 +1,259 @@
+"""Detect `${NAME}` references that a recipe never defines.
+
+Only recipe-level text counts: variable values, transfer endpoints, patterns,
+conditions and non-shell provider params. Shell commands are skipped, since
+`${i}` or `${CUDA_HOME}` there is usually the remote shell's own variable.
+"""
+
+from __future__ import annotations
+
+import os
+import re
+from dataclasses import dataclass, field
+from typing import Any, Dict, Iterable, Iterator, List, Mapping, Optional, Set, Tuple
+
+BRACED_REF = re.compile(r"\$\{([A-Za-z_][A-Za-z0-9_]*)\}")
+
+_STEP_TEXT_FIELDS = ("source", "dest", "target", "pattern", "condition")
+_SHELL_PARAM_KEYS = ("command", "commands", "cmd", "script")
+_OUTPUT_PARAM_KEYS = ("capture_var", "output_var")
+# What a reference is passed as: `--data-dir ${X}`, `--lr=${X}` or `OUT=${X}`.
+_FLAG_BEFORE = re.compile(r"(--?[A-Za-z][\w-]*)(?:=|\s+)[\"']?$")
+_ASSIGNMENT_BEFORE = re.compile(r"(?:^|[\s;])([A-Za-z_][A-Za-z0-9_]*)=[\"']?$")
+
+
+class UnresolvedVariableError(ValueError):
+    """Raised in strict mode when interpolation leaves `${NAME}` behind."""
+
+
+def referenced_variables(text: Any) -> List[str]:
+    """Return `${NAME}` references in one string, in order of first use."""
+    seen: List[str] = []
+    for name in BRACED_REF.findall(str(text or "")):
+        if name not in seen:
+            seen.append(name)
+    return seen
+
+
+def _iter_strings(value: Any) -> Iterator[str]:
+    if isinstance(value, str):
+        yield value
+    elif isinstance(value, Mapping):
+        for item in value.values():
+            yield from _iter_strings(item)
+    elif isinstance(value, (list, tuple, set)):
+        for item in value:
+            yield from _iter_strings(item)
+
+
+def _step_strings(step: Any) -> Iterator[str]:
+    for field_name in _STEP_TEXT_FIELDS:
+        yield from _iter_strings(getattr(step, field_name, ""))
+    yield from _iter_strings(getattr(step, "args", []))
+    params = getattr(step, "params", None)
+    if isinstance(params, Mapping):
+        for key, value in params.items():
+            if key not in _SHELL_PARAM_KEYS:
+                yield from _iter_strings(value)
+
+
+def produced_variables(steps: Iterable[Any]) -> Set[str]:
+    """Return variable names that steps assign while the recipe runs."""
+    produced: Set[str] = set()
+    for step in steps:
+        capture_var = str(getattr(step, "capture_var", "") or "").strip()
+        if capture_var:
+            produced.add(capture_var)
+        params = getattr(step, "params", None)
+        if not isinstance(params, dict):
+            continue
+        for key in _OUTPUT_PARAM_KEYS:
+            if params.get(key):
+                produced.add(str(params[key]))
+        if getattr(step, "operation", "") == "set_var" and params.get("name"):
+            produced.add(str(params["name"]))
+    return produced
+
+
+def _runtime_variables(recipe: Any, steps: List[Any]) -> tuple[Set[str], tuple]:
+    """Names (and name prefixes) the executor sets for the steps this recipe has."""
+    providers = {str(getattr(step, "provider", "") or "") for step in steps}
+    operations = {str(getattr(step, "operation", "") or "") for step in steps}
+    host_specs = [str(spec) for spec in (getattr(recipe, "hosts", {}) or {}).values()]
+    names: Set[str] = set()
+    prefixes: List[str] = []
+    if "vast" in providers or any(spec.startswith("vast:") for spec in host_specs):
+        names.add("VAST_ID")
+        names.update(f"VAST_ID_{host}" for host in getattr(recipe, "hosts", {}) or {})
+    if "runpod" in providers or any(spec.startswith("runpod:") for spec in host_specs):
+        names.add("RUNPOD_ID")
+    if "fetch_exchange_rates" in operations:
+        names.update({"exchange_rate_base", "exchange_rate_updated_at"})
+        prefixes.append("rate_")
+    return names, tuple(prefixes)
+
+
+def find_unresolved_variables(
+    recipe: Any,
+    provided: Optional[Mapping[str, Any]] = None,
+    *,
+    environ: Optional[Mapping[str, str]] = None,
+) -> List[str]:
+    """Return recipe-level `${NAME}` references with no recipe, override, step, or env value."""
+    environ = os.environ if environ is None else environ
+    steps = list(getattr(recipe, "steps", []) or [])
+    variables: Dict[str, Any] = dict(getattr(recipe, "variables", {}) or {})
+    runtime_names, runtime_prefixes = _runtime_variables(recipe, steps)
+    known = set(variables) | set(provided or {}) | produced_variables(steps) | runtime_names
+
+    texts: List[str] = [str(value) for value in variables.values()]
+    for step in steps:
+        texts.extend(_step_strings(step))
+
+    missing: List[str] = []
+    for text in texts:
+        for name in referenced_variables(text):
+            if name in known or name in environ or name in missing:
+                continue
+            if runtime_prefixes and name.startswith(runtime_prefixes):
+                continue
+            missing.append(name)
+    return missing
+
+
+@dataclass
+class VariableUsage:
+    """One place a variable is referenced."""
+
+    step: str
+    field: str
+    context: str = ""
+
+    def describe(self) -> str:
+        where = f"step '{self.step}'" if self.step else "recipe variables"
+        return f"{where} as {self.context}" if self.context else f"{where} ({self.field})"
+
+    def to_dict(self) -> Dict[str, str]:
+        return {"step": self.step, "field": self.field, "context": self.context, "description": self.describe()}
+
+
+@dataclass
+class VariableInfo:
+    """What the run prompt shows for one variable: its default and where it is used."""
+
+    name: str
+    default: Optional[str] = None
+    produced_by: List[str] = field(default_factory=list)
+    usages: List[VariableUsage] = field(default_factory=list)
+
+    @property
+    def has_default(self) -> bool:
+        return self.default is not None
+
+    def to_dict(self) -> Dict[str, Any]:
+        return {
+            "name": self.name,
+            "default": self.default,
+            "has_default": self.has_default,
+            "produced_by": list(self.produced_by),
+            "usages": [usage.to_dict() for usage in self.usages],
+        }
+
+
+def usage_context(text: str, name: str) -> str:
+    """The flag or assignment `${name}` is first passed as in `text`, or ""."""
+    index = text.find("${" + name + "}")
+    if index < 0:
+        return ""
+    before = text[:index]
+    for pattern in (_FLAG_BEFORE, _ASSIGNMENT_BEFORE):
+        match = pattern.search(before)
+        if match:
+            return match.group(1)
+    return ""
+
+
+def _step_id(step: Any, index: int) -> str:
+    return str(getattr(step, "id", "") or f"#{index + 1}")
+
+
+def _step_fields(step: Any) -> Iterator[Tuple[str, str, bool]]:
+    """(field, text, is_shell) for every string a step interpolates."""
+    for field_name in _STEP_TEXT_FIELDS:
+        for text in _iter_strings(getattr(step, field_name, "")):
+            yield field_name, text, False
+    for text in _iter_strings(getattr(step, "args", [])):
+        yield "args", text, False
+    commands = getattr(step, "commands", "")
+    if isinstance(commands, str) and commands:
+        yield "commands", commands, True
+    params = getattr(step, "params", None)
+    if isinstance(params, Mapping):
+        for key, value in params.items():
+            for text in _iter_strings(value):
+                yield f"params.{key}", text, key in _SHELL_PARAM_KEYS
+
+
+def _producers(steps: List[Any]) -> Dict[str, List[str]]:
+    producers: Dict[str, List[str]] = {}
+    for index, step in enumerate(steps):
+        for name in produced_variables([step]):
+            producers.setdefault(name, []).append(_step_id(step, index))
+    return producers
+
+
+def variable_report(recipe: Any) -> List[VariableInfo]:
+    """Every variable a recipe declares or references, with its default and each place it is used.
+
+    Shell commands only count for declared or step-produced names; other `${...}` there
+    belongs to the remote shell (see the module docstring).
+    """
+    steps = list(getattr(recipe, "steps", []) or [])
+    variables: Dict[str, Any] = dict(getattr(recipe, "variables", {}) or {})
+    producers = _producers(steps)
+    report: Dict[str, VariableInfo] = {
+        name: VariableInfo(name, default=str(value), produced_by=producers.get(name, [])) for name, value in variables.items()
+    }
+
+    def add(name: str, usage: VariableUsage) -> None:
+        info = report.setdefault(name, VariableInfo(name, produced_by=producers.get(name, [])))
+        if usage not in info.usages:
+            info.usages.append(usage)
+
+    for owner, value in variables.items():
+        for name in referenced_variables(value):
+            add(name, VariableUsage("", f"variables.{owner}"))
+    for index, step in enumerate(steps):
+        for field_name, text, is_shell in _step_fields(step):
+            for name in referenced_variables(text):
+                if is_shell and name not in variables and name not in producers:
+                    continue
+                add(name, VariableUsage(_step_id(step, index), field_name, usage_context(text, name)))
+    return list(report.values())
+
+
+def recipe_variable_report(path: str) -> List[Dict[str, Any]]:
+    """`variable_report` of a recipe file, as plain dicts for the run prompt and tools."""
+    from ..pyrecipe import load_recipe
+
+    return [info.to_dict() for info in variable_report(load_recipe(path))]
+
+
+def unresolved_in_text(text: str, names: Iterable[str]) -> List[str]:
+    """Return which of the recipe's undefined `names` are still referenced in interpolated text."""
+    wanted = set(names)
+    return [name for name in referenced_variables(text) if name in wanted]
+
+
+__all__ = [
+    "UnresolvedVariableError",
+    "VariableInfo",
+    "VariableUsage",
+    "find_unresolved_variables",
+    "produced_variables",
+    "recipe_variable_report",
+    "referenced_variables",
+    "unresolved_in_text",
+    "usage_context",
+    "variable_report",
+]
@@ -0,0 +1,157 @@
+"""Stop commands that execute steps left running in tmux sessions."""
+
+from __future__ import annotations
+
+import posixpath
+import re
+import shlex
+import subprocess
+from typing import Any, Callable, Dict, List, Optional
+
+KILL_GRACE_SECS = 10
+
+
+def pid_file_for(signal: str) -> str:
+    """Return the remote file that records one tracked command's PID."""
+    return f"/tmp/{signal}.pid"
+
+
+def status_file_for(job_id: str, step: Any) -> str:
+    """Return the file a step's shell writes its exit status to."""
+    safe = [re.sub(r"[^A-Za-z0-9_.-]+", "_", str(part)).strip("._") or "x" for part in (job_id, step)]
+    return f"/tmp/trainsh/{safe[0]}/{safe[1]}.rc"
+
+
+def remove_status_files(host: str, job_id: str, *, build_ssh_args: Callable[..., List[str]]) -> bool:
+    """Delete a finished job's step status files on `host`."""
+    directory = posixpath.dirname(status_file_for(job_id, "x"))
+    script = f"rm -rf {shlex.quote(directory)}"
+    args = ["sh", "-c", script] if host == "local" else build_ssh_args(host, command=script, tty=False)
+    try:
+        return subprocess.run(args, capture_output=True, text=True, timeout=30).returncode == 0
+    except Exception:
+        return False
+
+
+def wrap_tracked_command(commands: str, signal: str, pidfile: str, status_file: str = "") -> str:
+    """Run commands in a subshell that records its PID and signals tmux when done.
+
+    With `status_file`, "<signal> <exit code>" is written there before the signal.
+    """
+    # `sh -c 'echo $PPID'` prints the subshell's PID; interactive shells start
+    # that subshell as its own process group, so nohup'd children stay in it.
+    quoted = shlex.quote(pidfile)
+    wrapped = f"( sh -c 'echo $PPID' > {quoted}; {commands} ); "
+    if status_file:
+        status = shlex.quote(status_file)
+        directory = shlex.quote(posixpath.dirname(status_file) or ".")
+        wrapped = (
+            f"rm -f {status}; {wrapped}__train_rc=$?; "
+            f"mkdir -p {directory} && printf '%s %s\\n' {signal} \"$__train_rc\" > {status}; "
+        )
+    return wrapped + f"rm -f {quoted}; tmux wait-for -S {signal}"
+
+
+def wrap_pid_command(commands: str, pidfile: str) -> str:
+    """Record the running shell's PID, then run commands and keep their exit code.
+
+    For commands run over a plain SSH channel rather than a tmux pane: sshd
+    starts that shell as a process group leader, so killing the group stops
+    everything the commands started.
+    """
+    quoted = shlex.quote(pidfile)
+    return f"echo $$ > {quoted}; ( {commands} ); __train_rc=$?; rm -f {quoted}; exit $__train_rc"
+
+
+def build_kill_script(pidfile: str, grace_secs: int = KILL_GRACE_SECS) -> str:
+    """Shell script: SIGTERM the tracked process group, SIGKILL after the grace period."""
+    quoted = shlex.quote(pidfile)
+    grace = max(0, int(grace_secs))
+    return (
+        f"f={quoted}; pid=$(cat \"$f\" 2>/dev/null); "
+        "if [ -z \"$pid\" ] || ! kill -0 \"$pid\" 2>/dev/null; then rm -f \"$f\"; echo none; exit 0; fi; "
+        "pgid=$(ps -o pgid= -p \"$pid\" | tr -d ' '); "
+        "if [ \"$pgid\" = \"$pid\" ]; then target=\"-$pid\"; else target=\"$pid\"; fi; "
+        "kill -TERM \"$target\" 2>/dev/null; pkill -TERM -P \"$pid\" 2>/dev/null; sig=TERM; i=0; "
+        f"while kill -0 \"$pid\" 2>/dev/null && [ \"$i\" -lt {grace} ]; do sleep 1; i=$((i+1)); done; "
+        "if kill -0 \"$pid\" 2>/dev/null; then "
+        "kill -KILL \"$target\" 2>/dev/null; pkill -KILL -P \"$pid\" 2>/dev/null; sig=KILL; fi; "
+        "rm -f \"$f\"; echo \"pid=$pid pgid=$pgid signal=$sig\""
+    )
+
+
+def parse_kill_report(output: str) -> str:
+    """Turn the kill script's last line into a log message."""
+    lines = [line.strip() for line in str(output or "").splitlines() if line.strip()]
+    last = lines[-1] if lines else ""
+    if last == "none":
+        return "no running process"
+    fields: Dict[str, str] = {}
+    for part in last.split():
+        key, sep, value = part.partition("=")
+        if sep:
+            fields[key] = value
+    if "pid" not in fields:
+        return "kill result unknown"
+    group = f" (process group {fields['pgid']})" if fields.get("pgid") == fields["pid"] else ""
+    return f"killed pid {fields['pid']}{group} with SIG{fields.get('signal', 'TERM')}"
+
+
+def kill_tracked_process(
+    host: str,
+    pidfile: str,
+    *,
+    build_ssh_args: Callable[..., List[str]],
+    grace_secs: int = KILL_GRACE_SECS,
+) -> str:
+    """Kill one tracked command on its host and describe what happened."""
+    if not pidfile:
+        return "no PID recorded"
+    script = build_kill_script(pidfile, grace_secs)
+    if host == "local":
+        args = ["sh", "-c", script]
+    else:
+        args = build_ssh_args(host, command=script, tty=False)
+    try:
+        result = subprocess.run(args, capture_output=True, text=True, timeout=grace_secs + 30)
+    except Exception as exc:
+        return f"kill failed: {exc}"
+    if result.returncode != 0 and not result.stdout:
+        return f"kill failed: {(result.stderr or '').strip() or f'exit {result.returncode}'}"
+    return parse_kill_report(result.stdout)
+
+
+def cancel_inflight(
+    info: Dict[str, str],
+    *,
+    build_ssh_args: Callable[..., List[str]],
+    tmux_client: Optional[Any] = None,
+    grace_secs: int = KILL_GRACE_SECS,
+) -> str:
+    """Kill one in-flight step and optionally remove its tmux session."""
+    report = kill_tracked_process(
+        str(info.get("host", "") or "local"),
+        str(info.get("pidfile", "") or ""),
+        build_ssh_args=build_ssh_args,
+        grace_secs=grace_secs,
+    )
+    session = str(info.get("remote_session", "") or "")
+    if tmux_client is not None and session:
+        result = tmux_client.kill_session(session)
+        if getattr(result, "returncode", 1) == 0:
+            report += f"; closed tmux session {session}"
+    return report
+
+
+__all__ = [
+    "KILL_GRACE_SECS",
+    "build_kill_script",
+    "cancel_inflight",
+    "kill_tracked_process",
+    "parse_kill_report",
+    "pid_file_for",
+    "remove_status_files",
+    "status_file_for",
+    "wrap_pid_command",
+    "wrap_tracked_command",
+]
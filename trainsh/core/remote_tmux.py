@@ -8,6 +8,7 @@ import subprocess
 import uuid
 from typing import Callable, Optional
 
+from ..services.tracing import KIND_CLIENT, span
 from .local_tmux import TmuxCmdResult
 
 
@@ -21,15 +22,17 @@ class RemoteTmuxClient:
     def _ssh_args(self, command: str, tty: bool = False, set_term: bool = False) -> list[str]:
         return self._build_ssh_args(self.host, command=command, tty=tty, set_term=set_term)
 
-    def _run_shell(self, command: str, timeout: Optional[int] = None) -> TmuxCmdResult:
+    def _run_shell(self, command: str, timeout: Optional[int] = None, *, operation: str = "shell") -> TmuxCmdResult:
         ssh_args = self._ssh_args(command, tty=False, set_term=False)
         try:
-            cp = subprocess.run(
-                ssh_args,
-                capture_output=True,
-                text=True,
-                timeout=timeout,
-            )
+            with span("ssh", kind=KIND_CLIENT, **{"net.peer.name": self.host, "ssh.operation": operation}) as trace:
+                cp = subprocess.run(
+                    ssh_args,
+                    capture_output=True,
+                    text=True,
+                    timeout=timeout,
+                )
+                trace.set("ssh.exit_code", cp.returncode)
         except subprocess.TimeoutExpired:
             return TmuxCmdResult(124, "", "remote tmux command timed out")
         except Exception as e:
@@ -39,7 +42,7 @@ class RemoteTmuxClient:
 
     def _run_tmux(self, args: list[str], timeout: Optional[int] = None) -> TmuxCmdResult:
         cmd = "tmux " + " ".join(shlex.quote(a) for a in args)
-        return self._run_shell(cmd, timeout=timeout)
+        return self._run_shell(cmd, timeout=timeout, operation=f"tmux {args[0]}" if args else "tmux")
 
     def build_shell_command(
         self,
@@ -167,6 +170,9 @@ class RemoteTmuxClient:
     def kill_pane(self, pane_id: str) -> TmuxCmdResult:
         return self._run_tmux(["kill-pane", "-t", pane_id])
 
+    def resize_window(self, target: str, width: int, height: int) -> TmuxCmdResult:
+        return self._run_tmux(["resize-window", "-t", target, "-x", str(width), "-y", str(height)])
+
     def send_keys(self, target: str, text: str, enter: bool = True, literal: bool = True) -> TmuxCmdResult:
         if literal:
             result = self._run_tmux(["send-keys", "-t", target, "-l", text])
@@ -189,6 +195,9 @@ class RemoteTmuxClient:
     def wait_for(self, signal: str, timeout: Optional[int] = None) -> TmuxCmdResult:
         return self._run_tmux(["wait-for", signal], timeout=timeout)
 
+    def read_text(self, path: str) -> TmuxCmdResult:
+        return self._run_shell(f"cat {shlex.quote(path)}", timeout=30)
+
     def write_text(self, path: str, content: str) -> TmuxCmdResult:
         delimiter = f"TRAINSH_EOF_{uuid.uuid4().hex}"
         while delimiter in content:
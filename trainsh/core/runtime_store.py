@@ -2,15 +2,23 @@
 
 from __future__ import annotations
 
+import contextlib
+import fcntl
 import json
 import os
+import shutil
 import threading
-from datetime import datetime
+from datetime import datetime, timedelta
 from pathlib import Path
-from typing import Any, Dict, Iterable, List, Optional
+from typing import Any, Dict, Iterable, Iterator, List, Optional
 
 from ..constants import RUNTIME_STATE_DIR
 
+# Per-run directory of raw step output files, next to the JSONL files.
+STEP_LOGS_DIRNAME = "step_logs"
+# Sidecar lock shared by every process: appends hold it shared, rewrites exclusive.
+LOCK_FILENAME = ".runtime.lock"
+
 
 def to_jsonable(value: Any) -> Any:
     if value is None or isinstance(value, (str, int, float, bool)):
@@ -72,9 +80,17 @@ class RuntimeStore:
         self.pools_path = self.root / "pools.json"
         self._lock = threading.RLock()
 
+    @contextlib.contextmanager
+    def _file_lock(self, *, exclusive: bool) -> Iterator[None]:
+        """Hold the store's cross-process lock so rewrites never drop another process's appends."""
+        self.root.mkdir(parents=True, exist_ok=True)
+        with open(self.root / LOCK_FILENAME, "a") as lock:
+            fcntl.flock(lock, fcntl.LOCK_EX if exclusive else fcntl.LOCK_SH)
+            yield
+
     def _append_jsonl(self, path: Path, record: Dict[str, Any]) -> None:
         payload = dict(to_jsonable(record))
-        with self._lock:
+        with self._lock, self._file_lock(exclusive=False):
             path.parent.mkdir(parents=True, exist_ok=True)
             with path.open("a", encoding="utf-8") as handle:
                 handle.write(json.dumps(payload, ensure_ascii=False))
@@ -142,11 +158,103 @@ class RuntimeStore:
     def append_event(self, record: Dict[str, Any]) -> None:
         self._append_jsonl(self.events_path, record)
 
+    def event_files(self) -> List[Path]:
+        """Rotated event files oldest first (events.jsonl.N … .1), then events.jsonl."""
+        rotated = []
+        for path in self.root.glob(f"{self.events_path.name}.*"):
+            suffix = path.name.rsplit(".", 1)[-1]
+            if suffix.isdigit():
+                rotated.append((int(suffix), path))
+        rotated.sort(reverse=True)
+        return [path for _index, path in rotated] + [self.events_path]
+
     def list_events(self, run_id: str) -> List[Dict[str, Any]]:
-        records = [record for record in self._iter_jsonl(self.events_path) if str(record.get("run_id", "")) == str(run_id)]
+        records = [
+            record
+            for path in self.event_files()
+            for record in self._iter_jsonl(path)
+            if str(record.get("run_id", "")) == str(run_id)
+        ]
         records.sort(key=_record_sort_key)
         return records
 
+    def rotate_events(self, *, max_bytes: int, keep: int) -> bool:
+        """Move events.jsonl to events.jsonl.1 once it exceeds `max_bytes`; drop files past `keep`."""
+        with self._lock, self._file_lock(exclusive=True):
+            try:
+                size = self.events_path.stat().st_size
+            except OSError:
+                return False
+            if max_bytes <= 0 or size <= max_bytes:
+                return False
+            keep = max(0, int(keep))
+            for path in self.event_files()[:-1]:
+                index = int(path.name.rsplit(".", 1)[-1])
+                if index >= keep:
+                    path.unlink(missing_ok=True)
+                else:
+                    path.rename(self.root / f"{self.events_path.name}.{index + 1}")
+            if keep:
+                self.events_path.rename(self.root / f"{self.events_path.name}.1")
+            else:
+                self.events_path.unlink(missing_ok=True)
+            return True
+
+    def _rewrite_jsonl(self, path: Path, run_ids: set[str]) -> None:
+        """Stream `path` into a copy without the records of `run_ids`."""
+        if not path.exists():
+            return
+        temp = path.with_name(f".{path.name}.tmp")
+        with path.open("r", encoding="utf-8") as source, temp.open("w", encoding="utf-8") as target:
+            for line in source:
+                try:
+                    record = json.loads(line)
+                except ValueError:
+                    continue
+                if isinstance(record, dict) and str(record.get("run_id", "")) in run_ids:
+                    continue
+                target.write(line if line.endswith("\n") else line + "\n")
+        os.replace(temp, path)
+
+    def prune_runs(
+        self,
+        *,
+        older_than_days: float = 0,
+        keep_last: int = 0,
+        now: Optional[datetime] = None,
+        dry_run: bool = False,
+    ) -> List[str]:
+        """Delete finished runs (records and step output files) by age and count; return their ids.
+
+        Runs still marked running are never pruned. 0 disables either limit.
+        """
+        runs = self.list_runs()
+        finished = [
+            record
+            for record in runs
+            if str(record.get("status", "")).lower() != "running" and record.get("success") is not None
+        ]
+        doomed: List[str] = []
+        if keep_last > 0:
+            newest = {str(record.get("run_id", "")) for record in runs[: int(keep_last)]}
+            doomed.extend(str(record.get("run_id", "")) for record in finished if str(record.get("run_id", "")) not in newest)
+        if older_than_days > 0:
+            cutoff = ((now or datetime.now()) - timedelta(days=float(older_than_days))).isoformat()
+            doomed.extend(
+                str(record.get("run_id", ""))
+                for record in finished
+                if str(record.get("ended_at") or record.get("started_at") or _record_sort_key(record)) < cutoff
+            )
+        doomed = sorted({run_id for run_id in doomed if run_id})
+        if doomed and not dry_run:
+            run_ids = set(doomed)
+            with self._lock, self._file_lock(exclusive=True):
+                for path in [self.runs_path, self.tasks_path, *self.event_files()]:
+                    self._rewrite_jsonl(path, run_ids)
+            for run_id in doomed:
+                shutil.rmtree(self.root / STEP_LOGS_DIRNAME / run_id, ignore_errors=True)
+        return doomed
+
     def save_checkpoint(self, record: Dict[str, Any]) -> None:
         self._append_jsonl(self.checkpoints_path, record)
 
@@ -174,6 +282,7 @@ class RuntimeStore:
         recipe_path: str,
         *,
         statuses: Optional[set[str]] = None,
+        exclude_run_types: Optional[set[str]] = None,
     ) -> Optional[Dict[str, Any]]:
         target = os.path.abspath(os.path.expanduser(recipe_path))
         matches = []
@@ -182,6 +291,8 @@ class RuntimeStore:
                 continue
             if statuses and str(record.get("status", "")) not in statuses:
                 continue
+            if exclude_run_types and str(record.get("run_type", "") or "manual") in exclude_run_types:
+                continue
             matches.append(record)
         return matches[0] if matches else None
 
@@ -241,7 +352,7 @@ class RuntimeStore:
         matches = [
             record
             for record in self.list_runs()
-            if str(record.get("dag_id", "")) == str(dag_id)
+            if str(record.get("dag_id", "")) == str(dag_id) and str(record.get("run_type", "")) != "test"
         ]
         if not matches:
             return None
@@ -260,6 +371,8 @@ class RuntimeStore:
                 continue
             if dag_id is not None and str(record.get("dag_id", "")) != str(dag_id):
                 continue
+            if str(record.get("run_type", "")) == "test":
+                continue
             count += 1
         return count
 
@@ -282,6 +395,8 @@ class RuntimeStore:
 
 
 __all__ = [
+    "LOCK_FILENAME",
+    "STEP_LOGS_DIRNAME",
     "RuntimeStore",
     "get_runtime_state_dir",
     "json_dumps",
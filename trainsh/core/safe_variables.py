@@ -0,0 +1,78 @@
+"""Pass recipe variables to shell commands as environment variables.
+
+With `safe_variables`, `${NAME}` / `$NAME` in a command becomes
+`${TRAINSH_VAR_NAME}` and the command starts with
+`export TRAINSH_VAR_NAME='<shell-quoted value>';`, so a value such as
+`x; rm -rf ~` stays data instead of becoming shell syntax. Secrets bind to
+`TRAINSH_SECRET_<NAME>` the same way.
+"""
+
+from __future__ import annotations
+
+import re
+import shlex
+from typing import Callable, Dict, List, Optional, Tuple
+
+VAR_PREFIX = "TRAINSH_VAR_"
+SECRET_PREFIX = "TRAINSH_SECRET_"
+_REF = re.compile(r"\$\{([^}]+)\}|\$(\w+)")
+
+
+def env_name(ref: str) -> str:
+    """Environment variable carrying one `${ref}` (variable or `secret:NAME`)."""
+    if ref.startswith("secret:"):
+        return SECRET_PREFIX + re.sub(r"\W", "_", ref[7:])
+    return VAR_PREFIX + re.sub(r"\W", "_", ref)
+
+
+def quote_context(text: str, pos: int) -> str:
+    """Shell quoting at `pos`: "single", "double" or "none"."""
+    state = "none"
+    index = 0
+    while index < pos:
+        char = text[index]
+        if state == "single":
+            if char == "'":
+                state = "none"
+        elif char == "\\":
+            index += 1
+        elif char == '"':
+            state = "none" if state == "double" else "double"
+        elif char == "'" and state == "none":
+            state = "single"
+        index += 1
+    return state
+
+
+def bind_variables(text: str, resolve: Callable[[str], Optional[str]]) -> Tuple[str, List[str], Dict[str, str]]:
+    """Rewrite known references to env vars; return (command, warnings, bindings).
+
+    `resolve(ref)` returns a reference's value, or None to leave it untouched
+    (shell variables such as `$HOME`).
+    """
+    bindings: Dict[str, str] = {}
+    warnings: List[str] = []
+
+    def replace(match: re.Match) -> str:
+        ref = match.group(1) or match.group(2)
+        value = resolve(ref)
+        if value is None:
+            return match.group(0)
+        name = env_name(ref)
+        bindings[name] = value
+        context = quote_context(text, match.start())
+        label = "${" + ref + "}"
+        if context == "single":
+            warnings.append(f"{label} is inside single quotes and will not expand; use double quotes")
+        elif context == "none":
+            warnings.append(f'{label} is unquoted and will be word-split and globbed; write "{label}"')
+        return "${" + name + "}"
+
+    body = _REF.sub(replace, text)
+    if not bindings:
+        return body, warnings, bindings
+    exports = " ".join(f"{name}={shlex.quote(value)}" for name, value in bindings.items())
+    return f"export {exports}; {body}", warnings, bindings
+
+
+__all__ = ["SECRET_PREFIX", "VAR_PREFIX", "bind_variables", "env_name", "quote_context"]
@@ -0,0 +1,69 @@
+# tmux-trainsh secrets backends
+# Backend interface and the OS keyring backend
+
+from abc import ABC, abstractmethod
+from typing import List, Optional
+
+from ..constants import SecretKeys
+
+
+# ---------------------------------------------------------------------------
+# Backend ABC
+# ---------------------------------------------------------------------------
+
+class SecretsBackend(ABC):
+    @abstractmethod
+    def get(self, key: str) -> Optional[str]: ...
+
+    @abstractmethod
+    def set(self, key: str, value: str) -> None: ...
+
+    @abstractmethod
+    def delete(self, key: str) -> None: ...
+
+    @abstractmethod
+    def list_set_keys(self) -> List[str]: ...
+
+
+# ---------------------------------------------------------------------------
+# Backend: System keyring (GNOME Keyring / macOS Keychain / Windows Credential Manager)
+# ---------------------------------------------------------------------------
+
+class KeyringBackend(SecretsBackend):
+    """Store each secret in the OS keyring (service='trainsh', username=key)."""
+
+    SERVICE = "trainsh"
+
+    def __init__(self) -> None:
+        import keyring as _kr
+        self._kr = _kr
+
+    def get(self, key: str) -> Optional[str]:
+        val = self._kr.get_password(self.SERVICE, key)
+        return val if val else None
+
+    def set(self, key: str, value: str) -> None:
+        self._kr.set_password(self.SERVICE, key, value)
+
+    def delete(self, key: str) -> None:
+        try:
+            self._kr.delete_password(self.SERVICE, key)
+        except self._kr.errors.PasswordDeleteError:
+            pass
+
+    def list_set_keys(self) -> List[str]:
+        # keyring API doesn't support enumeration; check predefined keys
+        predefined = [
+            SecretKeys.VAST_API_KEY,
+            SecretKeys.RUNPOD_API_KEY,
+            SecretKeys.POE_API_KEY,
+            SecretKeys.HF_TOKEN,
+            SecretKeys.OPENAI_API_KEY,
+            SecretKeys.OPENROUTER_API_KEY,
+            SecretKeys.ANTHROPIC_API_KEY,
+            SecretKeys.GITHUB_TOKEN,
+            SecretKeys.GOOGLE_DRIVE_CREDENTIALS,
+            SecretKeys.R2_CREDENTIALS,
+            SecretKeys.B2_CREDENTIALS,
+        ]
+        return [k for k in predefined if self.get(k) is not None]
@@ -7,11 +7,21 @@ import json
 import os
 import shutil
 import subprocess
-from abc import ABC, abstractmethod
 from pathlib import Path
 from typing import Dict, List, Optional
 
 from ..constants import CONFIG_DIR, CONFIG_FILE, SecretKeys
+from .secret_backends import KeyringBackend, SecretsBackend
+from .secret_bundles import (
+    normalize_secret_key,
+    parse_secret_bundle_value,
+    dump_secret_bundle_value,
+    normalize_storage_bundle_payload,
+    resolve_secret_bundle_alias,
+    bundle_component_alias_groups,
+    bundle_component_aliases,
+    bundle_component_cleanup_aliases,
+)
 
 
 # ---------------------------------------------------------------------------
@@ -35,232 +45,6 @@ def _save_config(cfg: dict) -> None:
         yaml.dump(cfg, f, default_flow_style=False, sort_keys=False)
 
 
-def normalize_secret_key(key: str) -> str:
-    """Normalize secret names for matching."""
-    return str(key or "").strip().upper()
-
-
-def parse_secret_bundle_value(value: Optional[str]) -> Optional[Dict[str, str]]:
-    """Parse a JSON-encoded secret bundle payload."""
-    if not value:
-        return None
-
-    text = str(value).strip()
-    candidates = [text]
-    if len(text) >= 2 and text[0] == text[-1] == '"':
-        candidates.append(text[1:-1].replace('""', '"'))
-
-    data = None
-    for candidate in candidates:
-        try:
-            parsed = json.loads(candidate)
-        except Exception:
-            continue
-        if isinstance(parsed, dict):
-            data = parsed
-            break
-        if isinstance(parsed, str):
-            try:
-                reparsed = json.loads(parsed)
-            except Exception:
-                continue
-            if isinstance(reparsed, dict):
-                data = reparsed
-                break
-
-    if not isinstance(data, dict):
-        return None
-
-    parsed: Dict[str, str] = {}
-    for raw_key, raw_value in data.items():
-        key = str(raw_key).strip()
-        if not key:
-            continue
-        parsed[key] = "" if raw_value is None else str(raw_value)
-    return parsed or None
-
-
-def dump_secret_bundle_value(payload: Dict[str, str]) -> str:
-    """Serialize a secret bundle payload to JSON."""
-    clean = {
-        str(key): str(value)
-        for key, value in payload.items()
-        if value is not None and str(value) != ""
-    }
-    return json.dumps(clean, ensure_ascii=False, sort_keys=True)
-
-
-def normalize_storage_bundle_payload(
-    provider: str,
-    payload: Optional[Dict[str, str]],
-) -> Optional[Dict[str, str]]:
-    """Normalize bundle fields to the canonical provider-specific names."""
-    if not payload:
-        return None
-
-    cleaned = {
-        str(key).strip(): "" if value is None else str(value).strip()
-        for key, value in payload.items()
-        if str(key).strip()
-    }
-
-    if provider == "r2":
-        normalized: Dict[str, str] = {}
-        account_id = cleaned.get("account_id", "")
-        access_key_id = cleaned.get("access_key_id", "")
-        secret_access_key = cleaned.get("secret_access_key", "")
-        endpoint = cleaned.get("endpoint") or cleaned.get("s3_api_endpoint", "")
-
-        if account_id:
-            normalized["account_id"] = account_id
-        if access_key_id:
-            normalized["access_key_id"] = access_key_id
-        if secret_access_key:
-            normalized["secret_access_key"] = secret_access_key
-        if endpoint:
-            normalized["endpoint"] = endpoint
-        return normalized or None
-
-    if provider == "b2":
-        normalized = {}
-        application_key_id = cleaned.get("application_key_id", "")
-        application_key = cleaned.get("application_key", "")
-        endpoint = cleaned.get("endpoint", "")
-
-        if application_key_id:
-            normalized["application_key_id"] = application_key_id
-        if application_key:
-            normalized["application_key"] = application_key
-        if endpoint:
-            normalized["endpoint"] = endpoint
-        return normalized or None
-
-    return cleaned or None
-
-
-def resolve_secret_bundle_alias(key: str) -> Optional[tuple[str, Optional[str], str]]:
-    """Map supported secret aliases onto the composite bundle keys."""
-    normalized = normalize_secret_key(key)
-
-    if normalized == SecretKeys.R2_CREDENTIALS:
-        return SecretKeys.R2_CREDENTIALS, None, "r2"
-    if normalized == SecretKeys.R2_ACCOUNT_ID:
-        return SecretKeys.R2_CREDENTIALS, "account_id", "r2"
-    if normalized == SecretKeys.R2_ACCESS_KEY_ID:
-        return SecretKeys.R2_CREDENTIALS, "access_key_id", "r2"
-    if normalized == SecretKeys.R2_SECRET_ACCESS_KEY:
-        return SecretKeys.R2_CREDENTIALS, "secret_access_key", "r2"
-    if normalized == "R2_ENDPOINT":
-        return SecretKeys.R2_CREDENTIALS, "endpoint", "r2"
-
-    if normalized == SecretKeys.B2_CREDENTIALS:
-        return SecretKeys.B2_CREDENTIALS, None, "b2"
-    if normalized == SecretKeys.B2_APPLICATION_KEY_ID:
-        return SecretKeys.B2_CREDENTIALS, "application_key_id", "b2"
-    if normalized == SecretKeys.B2_APPLICATION_KEY:
-        return SecretKeys.B2_CREDENTIALS, "application_key", "b2"
-    if normalized == "B2_ENDPOINT":
-        return SecretKeys.B2_CREDENTIALS, "endpoint", "b2"
-
-    if normalized.endswith("_R2_CREDENTIALS"):
-        return normalized, None, "r2"
-    if normalized.endswith("_B2_CREDENTIALS"):
-        return normalized, None, "b2"
-
-    if normalized.endswith("_ACCOUNT_ID"):
-        prefix = normalized[: -len("_ACCOUNT_ID")]
-        if prefix:
-            return f"{prefix}_R2_CREDENTIALS", "account_id", "r2"
-    if normalized.endswith("_ACCESS_KEY_ID"):
-        prefix = normalized[: -len("_ACCESS_KEY_ID")]
-        if prefix:
-            return f"{prefix}_R2_CREDENTIALS", "access_key_id", "r2"
-    if normalized.endswith("_SECRET_ACCESS_KEY"):
-        prefix = normalized[: -len("_SECRET_ACCESS_KEY")]
-        if prefix:
-            return f"{prefix}_R2_CREDENTIALS", "secret_access_key", "r2"
-    if normalized.endswith("_APPLICATION_KEY_ID"):
-        prefix = normalized[: -len("_APPLICATION_KEY_ID")]
-        if prefix:
-            return f"{prefix}_B2_CREDENTIALS", "application_key_id", "b2"
-    if normalized.endswith("_APPLICATION_KEY"):
-        prefix = normalized[: -len("_APPLICATION_KEY")]
-        if prefix:
-            return f"{prefix}_B2_CREDENTIALS", "application_key", "b2"
-
-    return None
-
-
-def bundle_component_alias_groups(composite_key: str, provider: str) -> Dict[str, tuple[str, ...]]:
-    """Return canonical and legacy component aliases for a composite bundle key."""
-    normalized = normalize_secret_key(composite_key)
-    if provider == "r2":
-        if normalized == SecretKeys.R2_CREDENTIALS:
-            return {
-                "account_id": (SecretKeys.R2_ACCOUNT_ID,),
-                "access_key_id": (SecretKeys.R2_ACCESS_KEY_ID,),
-                "secret_access_key": (SecretKeys.R2_SECRET_ACCESS_KEY,),
-                "endpoint": ("R2_ENDPOINT",),
-            }
-        prefix = normalized[: -len("_R2_CREDENTIALS")]
-        return {
-            "account_id": (f"{prefix}_ACCOUNT_ID",),
-            "access_key_id": (f"{prefix}_ACCESS_KEY_ID",),
-            "secret_access_key": (f"{prefix}_SECRET_ACCESS_KEY",),
-            "endpoint": (f"{prefix}_ENDPOINT",),
-        }
-
-    if normalized == SecretKeys.B2_CREDENTIALS:
-        return {
-            "application_key_id": (SecretKeys.B2_APPLICATION_KEY_ID,),
-            "application_key": (SecretKeys.B2_APPLICATION_KEY,),
-            "endpoint": ("B2_ENDPOINT",),
-        }
-    prefix = normalized[: -len("_B2_CREDENTIALS")]
-    return {
-        "application_key_id": (f"{prefix}_APPLICATION_KEY_ID",),
-        "application_key": (f"{prefix}_APPLICATION_KEY",),
-        "endpoint": (f"{prefix}_ENDPOINT",),
-    }
-
-
-def bundle_component_aliases(composite_key: str, provider: str) -> Dict[str, str]:
-    """Return component aliases covered by a composite secret bundle."""
-    return {
-        field: aliases[0]
-        for field, aliases in bundle_component_alias_groups(composite_key, provider).items()
-    }
-
-
-def bundle_component_cleanup_aliases(composite_key: str, provider: str) -> List[str]:
-    """Return all component aliases that should be cleared for a bundle."""
-    aliases: List[str] = []
-    for group in bundle_component_alias_groups(composite_key, provider).values():
-        for alias in group:
-            normalized = normalize_secret_key(alias)
-            if normalized not in aliases:
-                aliases.append(normalized)
-    return aliases
-
-
-# ---------------------------------------------------------------------------
-# Backend ABC
-# ---------------------------------------------------------------------------
-
-class SecretsBackend(ABC):
-    @abstractmethod
-    def get(self, key: str) -> Optional[str]: ...
-
-    @abstractmethod
-    def set(self, key: str, value: str) -> None: ...
-
-    @abstractmethod
-    def delete(self, key: str) -> None: ...
-
-    @abstractmethod
-    def list_set_keys(self) -> List[str]: ...
-
-
 # ---------------------------------------------------------------------------
 # Backend: 1Password (op CLI)
 # ---------------------------------------------------------------------------
@@ -489,51 +273,6 @@ class EncryptedFileBackend(SecretsBackend):
         return list(self._read_store().keys())
 
 
-# ---------------------------------------------------------------------------
-# Backend: System keyring (GNOME Keyring / macOS Keychain / Windows Credential Manager)
-# ---------------------------------------------------------------------------
-
-class KeyringBackend(SecretsBackend):
-    """Store each secret in the OS keyring (service='trainsh', username=key)."""
-
-    SERVICE = "trainsh"
-
-    def __init__(self) -> None:
-        import keyring as _kr
-        self._kr = _kr
-
-    def get(self, key: str) -> Optional[str]:
-        val = self._kr.get_password(self.SERVICE, key)
-        return val if val else None
-
-    def set(self, key: str, value: str) -> None:
-        self._kr.set_password(self.SERVICE, key, value)
-
-    def delete(self, key: str) -> None:
-        try:
-            self._kr.delete_password(self.SERVICE, key)
-        except self._kr.errors.PasswordDeleteError:
-            pass
-
-    def list_set_keys(self) -> List[str]:
-        # keyring API doesn't support enumeration; check predefined keys
-        from ..constants import SecretKeys
-        predefined = [
-            SecretKeys.VAST_API_KEY,
-            SecretKeys.RUNPOD_API_KEY,
-            SecretKeys.POE_API_KEY,
-            SecretKeys.HF_TOKEN,
-            SecretKeys.OPENAI_API_KEY,
-            SecretKeys.OPENROUTER_API_KEY,
-            SecretKeys.ANTHROPIC_API_KEY,
-            SecretKeys.GITHUB_TOKEN,
-            SecretKeys.GOOGLE_DRIVE_CREDENTIALS,
-            SecretKeys.R2_CREDENTIALS,
-            SecretKeys.B2_CREDENTIALS,
-        ]
-        return [k for k in predefined if self.get(k) is not None]
-
-
 # ---------------------------------------------------------------------------
 # Backend loading / selection
 # ---------------------------------------------------------------------------
@@ -757,6 +496,21 @@ class SecretsManager:
         self._cache: dict[str, str] = {}
         self._backend: Optional[SecretsBackend] = None
         self._backend_loaded = False
+        self._prefix: Optional[str] = None
+
+    def _scoped(self, key: str) -> str:
+        """Normalize *key* and apply the profile's `secrets.key_prefix`."""
+        normalized = normalize_secret_key(key)
+        if self._prefix is None:
+            try:
+                from ..config import get_config_value
+
+                self._prefix = normalize_secret_key(get_config_value("secrets.key_prefix", "") or "")
+            except Exception:
+                self._prefix = ""
+        if self._prefix and not normalized.startswith(self._prefix):
+            return self._prefix + normalized
+        return normalized
 
     def _get_backend(self) -> Optional[SecretsBackend]:
         if not self._backend_loaded:
@@ -809,7 +563,7 @@ class SecretsManager:
         return normalize_storage_bundle_payload(provider, legacy_payload or None)
 
     def get(self, key: str) -> Optional[str]:
-        normalized = normalize_secret_key(key)
+        normalized = self._scoped(key)
         bundle_alias = resolve_secret_bundle_alias(normalized)
         if bundle_alias is None:
             direct_value = self._get_direct_value(normalized)
@@ -834,12 +588,12 @@ class SecretsManager:
 
     def set(self, key: str, value: str) -> None:
         backend = self._require_backend()
-        normalized = normalize_secret_key(key)
+        normalized = self._scoped(key)
         backend.set(normalized, value)
         self._cache[normalized] = value
 
     def set_bundle(self, composite_key: str, payload: Dict[str, str]) -> None:
-        normalized = normalize_secret_key(composite_key)
+        normalized = self._scoped(composite_key)
         bundle_alias = resolve_secret_bundle_alias(normalized)
         provider = bundle_alias[2] if bundle_alias is not None else ("r2" if normalized.endswith("_R2_CREDENTIALS") else "b2")
         normalized_payload = normalize_storage_bundle_payload(provider, payload) or {}
@@ -857,7 +611,7 @@ class SecretsManager:
                     pass
 
     def delete(self, key: str) -> None:
-        normalized = normalize_secret_key(key)
+        normalized = self._scoped(key)
         bundle_alias = resolve_secret_bundle_alias(normalized)
         target_key = bundle_alias[0] if bundle_alias is not None else normalized
 
@@ -891,6 +645,8 @@ class SecretsManager:
             SecretKeys.OPENROUTER_API_KEY,
             SecretKeys.ANTHROPIC_API_KEY,
             SecretKeys.GITHUB_TOKEN,
+            SecretKeys.WANDB_API_KEY,
+            SecretKeys.MLFLOW_TRACKING_TOKEN,
             SecretKeys.GOOGLE_DRIVE_CREDENTIALS,
             SecretKeys.R2_CREDENTIALS,
             SecretKeys.B2_CREDENTIALS,
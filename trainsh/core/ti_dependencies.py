@@ -312,6 +312,12 @@ class NotInRetryPeriodDep(BaseTIDep):
         return self._waiting(self.NAME, f"Task in retry period until {wait_until:.0f}.")
 
 
+# Deps that only wait for a free worker or pool slot; a step blocked on these alone is queued.
+CAPACITY_DEP_NAMES = frozenset(
+    {PoolSlotsAvailableDep.NAME, DagTISlotsAvailableDep.NAME, TaskConcurrencyDep.NAME}
+)
+
+
 class TIDependencyEvaluator:
     """Evaluate all TI-like dependencies for one step."""
 
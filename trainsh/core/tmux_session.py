@@ -7,6 +7,7 @@ from dataclasses import dataclass, field
 from typing import Optional, Dict, List, Callable
 from pathlib import Path
 
+from ..services.host_defaults import DEFAULT_HISTORY_LIMIT, set_history_limit
 from .local_tmux import LocalTmuxClient, TmuxCmdResult
 
 
@@ -82,7 +83,7 @@ class TmuxSession:
         if not self._tmux.has_session(self.name):
             self._tmux.new_session(self.name, detached=True)
             # Set larger scrollback buffer (default is 2000)
-            self._run("set-option", "-t", self.name, "history-limit", "50000")
+            set_history_limit(self._tmux, self.name, DEFAULT_HISTORY_LIMIT)
 
     @property
     def exists(self) -> bool:
@@ -0,0 +1,80 @@
+"""Register the local SSH public key with a Vast.ai account."""
+
+from __future__ import annotations
+
+import os
+from typing import Any, Callable, Optional
+
+
+def ensure_ssh_key_attached(client: Any, ssh_key_path: str, *, log: Callable[[str], None], logger: Optional[Any] = None) -> None:
+    """Ensure local public SSH key is attached to Vast.ai account."""
+    pub_key_path = os.path.expanduser(ssh_key_path)
+    if not pub_key_path.endswith(".pub"):
+        pub_key_path += ".pub"
+
+    if not os.path.exists(pub_key_path):
+        log(f"SSH public key not found: {pub_key_path}")
+        if logger:
+            logger.log_detail("ssh_key", f"Public key not found: {pub_key_path}", {})
+        return
+
+    with open(pub_key_path, "r") as f:
+        pub_key_content = f.read().strip()
+
+    if not pub_key_content:
+        log(f"SSH public key is empty: {pub_key_path}")
+        return
+
+    key_parts = pub_key_content.split()
+    if len(key_parts) < 2:
+        log(f"Invalid SSH public key format: {pub_key_path}")
+        return
+
+    key_type = key_parts[0]
+    key_data = key_parts[1]
+
+    try:
+        existing_keys = client.list_ssh_keys()
+        if logger:
+            logger.log_detail("ssh_key", f"Found {len(existing_keys)} existing keys on Vast.ai", {
+                "existing_count": len(existing_keys)
+            })
+
+        key_exists = False
+        for existing_key in existing_keys:
+            existing_content = existing_key.get("ssh_key", "")
+            existing_parts = existing_content.split()
+            if len(existing_parts) >= 2 and existing_parts[1] == key_data:
+                key_exists = True
+                if logger:
+                    logger.log_detail("ssh_key", "SSH key already registered on Vast.ai", {
+                        "key_id": existing_key.get("id"),
+                        "label": existing_key.get("label"),
+                    })
+                break
+
+        if not key_exists:
+            log("Adding SSH key to Vast.ai account...")
+            try:
+                client.add_ssh_key(pub_key_content, label="tmux-trainsh")
+                log("SSH key added successfully")
+                if logger:
+                    logger.log_detail("ssh_key", "SSH key added to Vast.ai", {
+                        "key_type": key_type,
+                        "key_path": pub_key_path,
+                    })
+            except Exception as add_err:
+                err_str = str(add_err).lower()
+                if "already exists" in err_str or "duplicate" in err_str:
+                    log("SSH key already exists on Vast.ai")
+                    if logger:
+                        logger.log_detail("ssh_key", "SSH key already exists (ignored)", {
+                            "key_type": key_type,
+                        })
+                else:
+                    raise add_err
+
+    except Exception as e:
+        log(f"Warning: Failed to manage SSH keys: {e}")
+        if logger:
+            logger.log_detail("ssh_key_warning", f"Failed to manage SSH keys: {e}", {})
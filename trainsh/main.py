@@ -20,6 +20,7 @@ COMMAND_HINTS = {
     "schedule": "Use 'train recipe schedule <run|list|status>' for scheduled recipes.",
     "hosts": "Use 'train host' (singular) for named host definitions.",
     "storages": "Use 'train storage' (singular) for storage backends.",
+    "projects": "Use 'train project' (singular) for project groups.",
     "log": "Use 'train recipe logs' for detailed execution logs.",
     "job": "Use 'train recipe jobs' for a compact recent-jobs table.",
 }
@@ -78,21 +79,39 @@ def main(args: list[str]) -> Optional[str]:
     from .commands.pricing import main as pricing_main
     from .commands.update import main as update_main
     from .commands.config_cmd import main as config_main
+    from .commands.backup import main as backup_main
+    from .commands.sync import main as sync_main
     from .commands.vllm import main as vllm_main
+    from .commands.jupyter import main as jupyter_main
+    from .commands.docker import main as docker_main
+    from .commands.dashboard import main as dashboard_main
+    from .commands.project import main as project_main
+    from .commands.search import main as search_main
+    from .commands.audit import main as audit_main
+    from .commands.wandb import main as wandb_main
     handlers = {
         "recipe": recipe_main,
         "run": lambda args: recipe_main(["run", *args]),
         "exec": lambda args: recipe_main(["exec", *args]),
+        "dashboard": dashboard_main,
+        "project": project_main,
         "transfer": transfer_main,
         "host": host_main,
         "storage": storage_main,
         "secrets": secrets_main,
         "config": config_main,
+        "backup": backup_main,
+        "sync": sync_main,
         "vast": vast_main,
         "runpod": runpod_main,
         "colab": colab_main,
         "pricing": pricing_main,
         "vllm": vllm_main,
+        "jupyter": jupyter_main,
+        "docker": docker_main,
+        "search": search_main,
+        "audit": audit_main,
+        "wandb": wandb_main,
         "update": update_main,
     }
 
@@ -105,11 +124,28 @@ def main(args: list[str]) -> Optional[str]:
             print()
         print(usage)
         raise SystemExit(1)
-    return handler(cmd_args)
+    from .core.errors import AppError
+    from .services.viewer_mode import PermissionDenied
+
+    try:
+        return handler(cmd_args)
+    except PermissionDenied as exc:
+        from .services.audit_log import record
+
+        record(exc.action, " ".join(cmd_args), detail=str(exc), status="denied")
+        print(f"Permission denied: {exc}")
+        raise SystemExit(1)
+    except AppError as exc:
+        hint = ", retryable" if exc.retryable else ""
+        print(f"Error [{exc.code.value}{hint}]: {exc}")
+        raise SystemExit(1)
 
 
 def cli() -> None:
     """CLI entry point (called by uv/pip installed command)."""
+    from .commands.recipe_reconnect import auto_reconnect
+
+    auto_reconnect(sys.argv)
     result = main(sys.argv)
     if result:
         print(result)
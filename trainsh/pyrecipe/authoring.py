@@ -3,9 +3,9 @@
 from __future__ import annotations
 
 from .base import RecipeSpec as Recipe
-from .loader import load_python_recipe
+from .loader import load_python_recipe, load_recipe
 from .models import Host, HostPath, RunpodHost, Storage, StoragePath, VastHost, local
-from .session_steps import official_uv_install_command
+from .session_ref import official_uv_install_command
 
 __all__ = [
     "Host",
@@ -17,5 +17,6 @@ __all__ = [
     "VastHost",
     "local",
     "load_python_recipe",
+    "load_recipe",
     "official_uv_install_command",
 ]
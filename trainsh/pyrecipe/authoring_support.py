@@ -25,6 +25,12 @@ _STEP_OPTION_ALIASES = {
     "deferrable": "deferrable",
     "on_success": "on_success",
     "on_failure": "on_failure",
+    "done_check": "done_check",
+    "retry_on_exit_codes": "retry_on_exit_codes",
+    "retry_on_output_regex": "retry_on_output_regex",
+    "on_failure_run": "on_failure_run",
+    "always_run": "always_run",
+    "destructive": "destructive",
 }
 
 _EQ_CONDITION = re.compile(
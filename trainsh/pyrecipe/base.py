@@ -16,6 +16,7 @@ from ..core.models import Storage as RuntimeStorage
 from .control_steps import RecipeControlMixin
 from .models import Host, HostPath, PythonRecipeError, ProviderStep, RecipeStep, Storage, StoragePath
 from .namespaces import (
+    JupyterNamespace,
     NotifyNamespace,
     RunpodNamespace,
     VastNamespace,
@@ -32,6 +33,8 @@ from .references import wrap_step_handle
 
 
 _ACTIVE_RECIPE: "RecipeSpecCore | None" = None
+# Directory of the recipe file being loaded; relative step scripts resolve against it.
+_LOADING_DIR: Optional[str] = None
 
 
 def get_active_recipe():
@@ -65,9 +68,16 @@ class RecipeSpecCore:
         self.steps: List[RecipeStep] = []
         self.hosts: Dict[str, str] = {}
         self.storages: Dict[str, Any] = {}
+        self.env_sets: Dict[str, Dict[str, str]] = {}
+        # Recipes started after the run ends; see `on_complete(...)`.
+        self.completion_hooks: List[Dict[str, Any]] = []
+        # Per-recipe MLflow options from `tracking(...)`, over the `tracking` config section.
+        self.tracking_options: Dict[str, Any] = {}
+        self.source_dir = _LOADING_DIR or os.getcwd()
         self.vast = VastNamespace(self)
         self.runpod = RunpodNamespace(self)
         self.vllm = VllmNamespace(self)
+        self.jupyter = JupyterNamespace(self)
         self.notify = NotifyNamespace(self)
         if "".join(ch for ch in str(executor).lower() if ch.isalnum()) in {
             "k8s",
@@ -97,6 +107,8 @@ class RecipeSpecCore:
         self._used_ids = set()
         self._task_defaults: Dict[str, Any] = {}
         self._linear_contexts: list[dict[str, Any]] = []
+        self._cleanup_depth = 0
+        self._group_stack: list[str] = []
         self._resource_host_aliases: dict[Host, str] = {}
         self._resource_storage_aliases: dict[int, str] = {}
         self._session_registry: dict[str, dict[str, Any]] = {}
@@ -145,6 +157,58 @@ class RecipeSpecCore:
             if self._linear_contexts:
                 self._linear_contexts[-1]["last"] = completed.get("last")
 
+    @contextmanager
+    def cleanup(self):
+        """Mark steps added in this block `always_run`: they run after the DAG, even on failure."""
+        self._cleanup_depth += 1
+        try:
+            yield self
+        finally:
+            self._cleanup_depth -= 1
+
+    @contextmanager
+    def group(
+        self,
+        id: Optional[str] = None,
+        *,
+        depends_on: Any = None,
+        retries: Optional[int] = None,
+        continue_on_failure: Optional[bool] = None,
+        step_options: Optional[Dict[str, Any]] = None,
+    ):
+        """Nest steps added in this block under one group step.
+
+        Members run in their own dependency order when the group runs; the group
+        retries and fails as a unit, and later steps wait for the whole group.
+        """
+        from .authoring_support import normalize_after
+
+        group_id = self._next_step_id(id)
+        upstream = normalize_after(depends_on)
+        if upstream is None:
+            upstream = self._implicit_depends() or []
+        options = dict(step_options or {})
+        if retries is not None:
+            options["retries"] = retries
+        if continue_on_failure is not None:
+            options["continue_on_failure"] = continue_on_failure
+
+        first_member = len(self.steps)
+        self._group_stack.append(group_id)
+        try:
+            yield wrap_step_handle(self, group_id)
+        finally:
+            self._group_stack.pop()
+
+        members = [step.id for step in self.steps[first_member:] if step.group == group_id]
+        self._used_ids.discard(group_id)
+        self._add_step(
+            ProviderStep(provider="group", operation="run", params={"steps": members}, id=group_id),
+            id=group_id,
+            step_options=options,
+        )
+        self.steps[-1].depends_on = [str(dep) for dep in upstream]
+
     def defaults(
         self,
         *,
@@ -210,6 +274,12 @@ class RecipeSpecCore:
             "deferrable": False,
             "on_success": [],
             "on_failure": [],
+            "done_check": "",
+            "retry_on_exit_codes": [],
+            "retry_on_output_regex": "",
+            "on_failure_run": "",
+            "always_run": False,
+            "destructive": False,
         }
         if not init and self._task_defaults:
             merged.update(self._task_defaults)
@@ -314,6 +384,32 @@ class RecipeSpecCore:
 
         merged["on_success"] = self._normalize_step_callbacks(merged.get("on_success"))
         merged["on_failure"] = self._normalize_step_callbacks(merged.get("on_failure"))
+        merged["done_check"] = str(merged.get("done_check") or "").strip()
+
+        exit_codes = merged.get("retry_on_exit_codes") or []
+        if isinstance(exit_codes, (int, str)):
+            exit_codes = [exit_codes]
+        try:
+            merged["retry_on_exit_codes"] = [int(code) for code in exit_codes]
+        except (TypeError, ValueError):
+            raise PythonRecipeError(f"invalid retry_on_exit_codes: {exit_codes!r}")
+
+        pattern = str(merged.get("retry_on_output_regex") or "")
+        if pattern:
+            try:
+                re.compile(pattern)
+            except re.error as exc:
+                raise PythonRecipeError(f"invalid retry_on_output_regex: {exc}")
+        merged["retry_on_output_regex"] = pattern
+
+        from .authoring_support import normalize_after
+
+        hook = normalize_after(merged.get("on_failure_run") or None) or []
+        if len(hook) > 1:
+            raise PythonRecipeError("on_failure_run takes a single step id")
+        merged["on_failure_run"] = hook[0] if hook else ""
+        merged["always_run"] = self._normalize_bool(merged.get("always_run", False), default=False)
+        merged["destructive"] = self._normalize_bool(merged.get("destructive", False), default=False)
 
         return merged
 
@@ -370,6 +466,16 @@ class RecipeSpecCore:
             return int(text)
         raise PythonRecipeError(f"invalid timeout: {timeout!r}")
 
+    def _resolve_script(self, path: Any) -> str:
+        """Absolute path of a step script, relative to the recipe file; it must exist."""
+        text = os.fspath(path).strip() if path is not None else ""
+        if not text:
+            raise PythonRecipeError("script path cannot be empty")
+        resolved = os.path.abspath(os.path.join(self.source_dir, os.path.expanduser(text)))
+        if not os.path.isfile(resolved):
+            raise PythonRecipeError(f"script not found: {text} (looked in {self.source_dir})")
+        return resolved
+
     def _normalize_bool(self, value: Any, *, default: bool = False) -> bool:
         if isinstance(value, bool):
             return bool(value)
@@ -393,6 +499,18 @@ class RecipeSpecCore:
     def _clean_session(self, value: str) -> str:
         return value[1:] if value.startswith("@") else value
 
+    def _implicit_depends(self) -> Optional[List[str]]:
+        """Return the upstream a new step gets when it names no `depends_on`."""
+        if self._linear_contexts:
+            current_linear = self._linear_contexts[-1]
+            if current_linear.get("last") is not None:
+                return [current_linear["last"]]
+            if current_linear.get("depends_on"):
+                return list(current_linear["depends_on"])
+        if self.steps:
+            return [self.steps[-1].id]
+        return None
+
     def _add_step(
         self,
         step: object,
@@ -405,15 +523,12 @@ class RecipeSpecCore:
 
         resolved_id = self._next_step_id(id if id is not None else step_id)
         options = self._normalize_step_options(step_options or {})
+        if self._cleanup_depth:
+            options["always_run"] = True
         implicit_depends = normalize_after(depends_on)
-        if implicit_depends is None and self._linear_contexts:
-            current_linear = self._linear_contexts[-1]
-            if current_linear.get("last") is not None:
-                implicit_depends = [current_linear["last"]]
-            elif current_linear.get("depends_on"):
-                implicit_depends = list(current_linear["depends_on"])
-        if implicit_depends is None and self.steps:
-            implicit_depends = [self.steps[-1].id]
+        if implicit_depends is None:
+            implicit_depends = self._implicit_depends()
+        group = self._group_stack[-1] if self._group_stack else ""
         deps: List[str] = []
         for dependency in implicit_depends or []:
             dep_id = str(dependency).strip()
@@ -441,6 +556,13 @@ class RecipeSpecCore:
                     deferrable=options["deferrable"],
                     on_success=options["on_success"],
                     on_failure=options["on_failure"],
+                    done_check=options["done_check"],
+                    retry_on_exit_codes=options["retry_on_exit_codes"],
+                    retry_on_output_regex=options["retry_on_output_regex"],
+                    on_failure_run=options["on_failure_run"],
+                    always_run=options["always_run"],
+                    destructive=options["destructive"],
+                    group=group,
                 )
             )
             handle = wrap_step_handle(self, resolved_id)
@@ -463,6 +585,13 @@ class RecipeSpecCore:
             step.deferrable = options["deferrable"]
             step.on_success = options["on_success"]
             step.on_failure = options["on_failure"]
+            step.done_check = options["done_check"]
+            step.retry_on_exit_codes = options["retry_on_exit_codes"]
+            step.retry_on_output_regex = options["retry_on_output_regex"]
+            step.on_failure_run = options["on_failure_run"]
+            step.always_run = options["always_run"]
+            step.destructive = options["destructive"]
+            step.group = group
             self.steps.append(step)
             handle = wrap_step_handle(self, resolved_id)
             if self._linear_contexts:
@@ -523,6 +652,7 @@ class RecipeSpecCore:
         host_ref: Optional[str],
         cwd: Optional[str],
         env: Optional[Dict[str, Any]],
+        gpus: Iterable[str] = (),
     ) -> None:
         """Persist reusable session metadata by session name."""
         session_name = self._clean_session(str(name))
@@ -533,6 +663,7 @@ class RecipeSpecCore:
             "host_ref": host_ref,
             "cwd": cwd,
             "env": dict(env or {}),
+            "gpus": tuple(gpus or ()),
         }
 
     def lookup_session(self, name: str) -> Optional[dict[str, Any]]:
@@ -598,10 +729,15 @@ class RecipeSpecCore:
             return os.fspath(value)
         return str(value)
 
+    def _resolve_sources(self, value: Any) -> Any:
+        if isinstance(value, (list, tuple)):
+            return [self.resolve_endpoint(item) for item in value]
+        return self.resolve_endpoint(value)
+
     def copy(self, source: Any, destination: Any, **kwargs: Any) -> str:
         return type(self).transfer(
             self,
-            self.resolve_endpoint(source),
+            self._resolve_sources(source),
             self.resolve_endpoint(destination),
             operation="copy",
             **kwargs,
@@ -610,7 +746,7 @@ class RecipeSpecCore:
     def move(self, source: Any, destination: Any, **kwargs: Any) -> str:
         return type(self).transfer(
             self,
-            self.resolve_endpoint(source),
+            self._resolve_sources(source),
             self.resolve_endpoint(destination),
             operation="move",
             **kwargs,
@@ -619,7 +755,7 @@ class RecipeSpecCore:
     def sync(self, source: Any, destination: Any, **kwargs: Any) -> str:
         return type(self).transfer(
             self,
-            self.resolve_endpoint(source),
+            self._resolve_sources(source),
             self.resolve_endpoint(destination),
             operation="sync",
             **kwargs,
@@ -633,6 +769,7 @@ class RecipeSpecCore:
             hosts=dict(self.hosts.items()),
             storages=dict(self.storages.items()),
             steps=[item.to_step_model() for item in self.steps],
+            env_sets={name: dict(values) for name, values in self.env_sets.items()},
         )
 
     def step_count(self) -> int:
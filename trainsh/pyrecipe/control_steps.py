@@ -2,12 +2,34 @@
 
 from __future__ import annotations
 
+import dataclasses
+import json
+import os
+import shlex
 from typing import Iterable, Optional, Dict, Any
 
-from ..core.recipe_models import RecipeStepModel, StepType
+from ..core.recipe_models import SCRIPT_PLACEHOLDER, RecipeStepModel, StepType
 from .models import PythonRecipeError
 
 
+def model_step_raw(step_type: StepType, fields: Dict[str, Any]) -> str:
+    """Display line for a step built from raw model fields."""
+    if step_type == StepType.EXECUTE:
+        commands = str(fields.get("commands", ""))
+        if fields.get("script"):
+            commands = commands.replace(SCRIPT_PLACEHOLDER, os.path.basename(fields["script"]))
+        raw = f"@{fields.get('host', '')} > {commands}"
+        return raw + " &" if fields.get("background") else raw
+    if step_type == StepType.TRANSFER:
+        return f"{fields.get('source', '')} -> {fields.get('dest', '')}"
+    if step_type == StepType.WAIT:
+        raw = f"wait @{fields.get('target', '')}"
+        if fields.get("pattern"):
+            return f"{raw} {json.dumps(fields['pattern'])}"
+        return f"{raw} {fields['condition']}" if fields.get("condition") else raw
+    return " ".join([str(fields.get("command", "")), *map(str, fields.get("args") or [])]).strip()
+
+
 class RecipeControlMixin:
     """DSL control step helpers for tmux/sleep-like actions."""
 
@@ -26,21 +48,78 @@ class RecipeControlMixin:
             args=args,
         )
 
+    def model_step(
+        self,
+        type: str,
+        *,
+        id: Optional[str] = None,
+        depends_on: Optional[Iterable[str]] = None,
+        step_options: Optional[Dict[str, Any]] = None,
+        **fields: Any,
+    ) -> str:
+        """Add a step straight from `RecipeStepModel` fields (used by YAML recipes and `recipe convert`)."""
+        try:
+            step_type = StepType(str(type).strip().lower())
+        except ValueError:
+            raise PythonRecipeError(f"unknown step type: {type!r}") from None
+        known = {item.name for item in dataclasses.fields(RecipeStepModel)} - {"type"}
+        unknown = sorted(set(fields) - known)
+        if unknown:
+            raise PythonRecipeError(f"unknown {step_type.value} step field(s): {', '.join(unknown)}")
+        if "timeout" in fields:
+            fields["timeout"] = max(0, self._normalize_timeout(fields["timeout"]))
+        if fields.get("script"):
+            fields["script"] = self._resolve_script(fields["script"])
+            if step_type == StepType.EXECUTE and not fields.get("commands"):
+                script_args = [shlex.quote(str(arg)) for arg in fields.pop("args", None) or []]
+                fields["commands"] = " ".join([SCRIPT_PLACEHOLDER, *script_args])
+        fields.setdefault("line_num", 0)
+        fields.setdefault("raw", model_step_raw(step_type, fields))
+        return self._add_step(
+            RecipeStepModel(type=step_type, **fields),
+            id=id,
+            depends_on=depends_on,
+            step_options=step_options,
+        )
+
     def tmux_open(
         self,
         host: str,
         *,
         as_: Optional[str] = None,
+        env_sets: Optional[Iterable[str]] = None,
+        env_file: Optional[str] = None,
+        export_env: bool = True,
         id: Optional[str] = None,
         depends_on: Optional[Iterable[str]] = None,
         step_options: Optional[Dict[str, Any]] = None,
     ) -> str:
-        """Open a tmux session bound to host."""
+        """Open a tmux session bound to host.
+
+        `env_sets` names sets from :meth:`env_set` or config `env_sets`; they are
+        written to `env_file` on the host (mode 600) and/or exported in the session.
+        """
         host_ref = f"@{self._clean_session(host)}" if host else ""
         session_name = as_ or "main"
-        raw = f"tmux.open {host_ref} as {session_name}"
+        args = [host_ref, "as", session_name]
+        sets = [str(item).strip() for item in (env_sets or []) if str(item).strip()]
+        for name in sets:
+            if name not in getattr(self, "env_sets", {}):
+                from ..services.session_env import configured_env_sets
+
+                if name not in configured_env_sets():
+                    raise PythonRecipeError(f"unknown env set: {name}")
+        if sets:
+            args.append(f"env={','.join(sets)}")
+            if env_file:
+                args.append(f"env_file={str(env_file).strip()}")
+            if not export_env:
+                args.append("export=0")
+        elif env_file:
+            raise PythonRecipeError("tmux_open env_file requires env_sets")
+        raw = " ".join(["tmux.open", *args])
         return self._add_step(
-            self._control_step("tmux.open", [host_ref, "as", session_name], raw),
+            self._control_step("tmux.open", args, raw),
             id=id,
             depends_on=depends_on,
             step_options=step_options,
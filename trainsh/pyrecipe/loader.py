@@ -9,6 +9,10 @@ import sys
 from pathlib import Path
 from typing import List
 
+import yaml
+
+from ..constants import RECIPE_FILE_EXTENSION
+from . import base
 from .base import RecipeSpec
 
 
@@ -32,10 +36,13 @@ def load_python_recipe(path: str) -> RecipeSpec:
 
     module = importlib.util.module_from_spec(spec)
     loaded: List[RecipeSpec] = []
+    previous_dir = base._LOADING_DIR
     try:
         sys.modules[spec.name] = module  # type: ignore[arg-type]
+        base._LOADING_DIR = str(source.parent)
         spec.loader.exec_module(module)  # type: ignore[arg-type]
     finally:
+        base._LOADING_DIR = previous_dir
         sys.modules.pop(spec.name, None)  # type: ignore[arg-type]
 
     explicit = getattr(module, "recipe", None)
@@ -54,3 +61,23 @@ def load_python_recipe(path: str) -> RecipeSpec:
             "please expose only one as `recipe = ...`"
         )
     raise RuntimeError("no recipe defined in recipe source file")
+
+
+def _looks_like_yaml_recipe(path: Path) -> bool:
+    try:
+        data = yaml.safe_load(path.read_text(encoding="utf-8"))
+    except (OSError, UnicodeDecodeError, yaml.YAMLError):
+        return False
+    return isinstance(data, dict) and "steps" in data
+
+
+def load_recipe(path: str) -> RecipeSpec:
+    """Load a Python or YAML recipe file; the extension picks the format, else the content does."""
+    from .yaml_recipe import is_yaml_recipe, load_yaml_recipe
+
+    source = Path(path).expanduser()
+    if is_yaml_recipe(path):
+        return load_yaml_recipe(path)
+    if source.suffix.lower() != RECIPE_FILE_EXTENSION and source.is_file() and _looks_like_yaml_recipe(source):
+        return load_yaml_recipe(path)
+    return load_python_recipe(path)
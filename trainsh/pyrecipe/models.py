@@ -193,6 +193,14 @@ class RecipeStep:
     deferrable: bool = False
     on_success: list = field(default_factory=list)
     on_failure: list = field(default_factory=list)
+    done_check: str = ""
+    retry_on_exit_codes: List[int] = field(default_factory=list)
+    retry_on_output_regex: str = ""
+    on_failure_run: str = ""
+    always_run: bool = False
+    # Recorded in the audit trail (`train audit`) each time it runs.
+    destructive: bool = False
+    group: str = ""
 
     @property
     def raw(self) -> str:
@@ -234,6 +242,14 @@ class RecipeStep:
     def capture_path(self) -> str:
         return self.step_model.capture_path
 
+    @property
+    def gpus(self) -> List[str]:
+        return self.step_model.gpus
+
+    @property
+    def script(self) -> str:
+        return self.step_model.script
+
     @property
     def source(self) -> str:
         return self.step_model.source
@@ -279,6 +295,14 @@ class ProviderStep:
     deferrable: bool = False
     on_success: list = field(default_factory=list)
     on_failure: list = field(default_factory=list)
+    done_check: str = ""
+    retry_on_exit_codes: List[int] = field(default_factory=list)
+    retry_on_output_regex: str = ""
+    on_failure_run: str = ""
+    always_run: bool = False
+    # Recorded in the audit trail (`train audit`) each time it runs.
+    destructive: bool = False
+    group: str = ""
 
     @property
     def raw(self) -> str:
@@ -145,6 +145,88 @@ class NotifyNamespace:
         return self._recipe.webhook(message, **kwargs)
 
 
+class JupyterNamespace:
+    """Recipe-bound managed Jupyter Lab helpers."""
+
+    def __init__(self, recipe: "RecipeSpecCore"):
+        self._recipe = recipe
+
+    def _host_name(self, host: Any) -> str:
+        if host is None:
+            return ""
+        return self._recipe.resolve_host(host).lstrip("@")
+
+    def start(
+        self,
+        host: Any,
+        *,
+        name: Optional[str] = None,
+        port: int = 8888,
+        root_dir: Optional[str] = None,
+        python: Optional[str] = None,
+        tunnel: bool = True,
+        local_port: int = 0,
+        timeout: Any = "2m",
+        capture_var: Optional[str] = None,
+        id: Optional[str] = None,
+        depends_on: Optional[Iterable[str]] = None,
+        step_options: Optional[dict[str, Any]] = None,
+    ) -> str:
+        """Start Jupyter Lab in tmux on a host, wait for it, and tunnel it locally.
+
+        ``capture_var`` receives the local URL including the token.
+        """
+        host_name = self._host_name(host)
+        if not host_name:
+            raise PythonRecipeError("recipe.jupyter.start(...) requires a host")
+        params: dict[str, Any] = {"host": host_name, "port": int(port), "tunnel": bool(tunnel), "timeout": timeout}
+        if name:
+            params["name"] = name
+        if root_dir:
+            params["root_dir"] = root_dir
+        if python:
+            params["python"] = python
+        if local_port:
+            params["local_port"] = int(local_port)
+        if capture_var:
+            params["capture_var"] = capture_var
+        return self._recipe.provider(
+            "jupyter",
+            "start",
+            params=params,
+            id=id,
+            depends_on=depends_on,
+            step_options=step_options,
+        )
+
+    def stop(
+        self,
+        host: Any = None,
+        *,
+        name: Optional[str] = None,
+        id: Optional[str] = None,
+        depends_on: Optional[Iterable[str]] = None,
+        step_options: Optional[dict[str, Any]] = None,
+    ) -> str:
+        """Stop a managed Jupyter server started on ``host`` (or by ``name``)."""
+        params: dict[str, Any] = {}
+        host_name = self._host_name(host)
+        if host_name:
+            params["host"] = host_name
+        if name:
+            params["name"] = name
+        if not params:
+            raise PythonRecipeError("recipe.jupyter.stop(...) requires a host or name")
+        return self._recipe.provider(
+            "jupyter",
+            "stop",
+            params=params,
+            id=id,
+            depends_on=depends_on,
+            step_options=step_options,
+        )
+
+
 class VllmNamespace:
     """Recipe-bound vLLM convenience helpers."""
 
@@ -244,4 +326,4 @@ class VllmNamespace:
         return session.after(wait_step)
 
 
-__all__ = ["NotifyNamespace", "RunpodNamespace", "VastNamespace", "VllmNamespace"]
+__all__ = ["JupyterNamespace", "NotifyNamespace", "RunpodNamespace", "VastNamespace", "VllmNamespace"]
@@ -106,12 +106,22 @@ class RecipeProviderBasicMixin:
         host: Optional[str] = None,
         cwd: Optional[str] = None,
         env: Optional[Dict[str, Any]] = None,
+        interpreter: Optional[str] = None,
+        requirements: Optional[Iterable[str]] = None,
         capture_var: Optional[str] = None,
+        exit_code_var: Optional[str] = None,
+        ok_exit_codes: Optional[Iterable[int]] = None,
         id: Optional[str] = None,
         depends_on: Optional[Iterable[str]] = None,
         step_options: Optional[Dict[str, Any]] = None,
     ) -> str:
-        """Run python code or script."""
+        """Run python code or script.
+
+        ``interpreter`` is ``system``, ``uv``, ``conda:ENV`` or a python executable;
+        ``requirements`` (``pkg==1.2`` or ``requirements.txt``) go into a throwaway
+        venv. ``exit_code_var`` receives the exit code as a string, and exit codes in
+        ``ok_exit_codes`` (default ``[0]``) count as success.
+        """
         value = str(code_or_command).strip()
         params: Dict[str, Any] = {
             "timeout": timeout,
@@ -129,8 +139,19 @@ class RecipeProviderBasicMixin:
             params["env"] = env
         if host is not None:
             params["host"] = host
+        if interpreter:
+            params["interpreter"] = interpreter
+        if requirements is not None:
+            params["requirements"] = [requirements] if isinstance(requirements, str) else list(requirements)
         if capture_var is not None:
             params["capture_var"] = capture_var
+        if exit_code_var is not None:
+            params["exit_code_var"] = exit_code_var
+        if ok_exit_codes is not None:
+            try:
+                params["ok_exit_codes"] = [int(code) for code in ok_exit_codes]
+            except (TypeError, ValueError):
+                raise PythonRecipeError(f"invalid ok_exit_codes: {ok_exit_codes!r}")
         return self.provider(
             "python",
             "run",
@@ -225,11 +246,12 @@ class RecipeProviderBasicMixin:
         disk_gb: Optional[Any] = None,
         label: Optional[str] = None,
         direct: Optional[bool] = None,
+        template: Optional[str] = None,
         id: Optional[str] = None,
         depends_on: Optional[Iterable[str]] = None,
         step_options: Optional[Dict[str, Any]] = None,
     ) -> str:
-        """Pick vast instance by filter."""
+        """Pick vast instance by filter, or by a saved `train vast template`."""
         params: Dict[str, Any] = {
             "host": host,
             "skip_if_set": bool(skip_if_set),
@@ -254,6 +276,8 @@ class RecipeProviderBasicMixin:
             params["label"] = label
         if direct is not None:
             params["direct"] = bool(direct)
+        if template is not None:
+            params["template"] = template
         return self.provider(
             "vast",
             "pick",
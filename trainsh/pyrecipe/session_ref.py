@@ -0,0 +1,600 @@
+"""Session handles returned by `recipe.tmux(...)` and `host.tmux(...)`."""
+
+from __future__ import annotations
+
+import shlex
+from dataclasses import dataclass, field, replace
+from textwrap import dedent
+from typing import Any, Dict, Iterable, Optional, TYPE_CHECKING
+
+from ..core.recipe_models import SCRIPT_PLACEHOLDER
+from ..services.flash_attn_support import flash_attn_install_script
+from .authoring_support import normalize_after, split_step_call
+from .models import PythonRecipeError
+
+if TYPE_CHECKING:
+    from .base import RecipeSpecCore
+
+
+def normalize_gpus(value: Any) -> list[str]:
+    """Normalize `0`, `"0,1"` or `[0, 1]` to a list of GPU index strings."""
+    if value is None or value == "":
+        return []
+    if isinstance(value, (list, tuple, set)):
+        items = [str(item).strip() for item in value]
+    else:
+        items = [item.strip() for item in str(value).split(",")]
+    gpus: list[str] = []
+    for item in items:
+        if not item:
+            continue
+        if not item.isdigit() and not item.startswith("${"):
+            raise PythonRecipeError(f"invalid GPU index {item!r}; expected integers like 0 or '0,1'")
+        if item not in gpus:
+            gpus.append(item)
+    return gpus
+
+
+def official_uv_install_command(*, force: bool = False) -> str:
+    """Build a shell command that installs uv via the official Astral script."""
+    if force:
+        return (
+            'curl -LsSf https://astral.sh/uv/install.sh | sh && '
+            'export PATH="$HOME/.local/bin:$PATH" && hash -r && uv --version'
+        )
+    return (
+        'if ! command -v uv >/dev/null 2>&1 && [ ! -x "$HOME/.local/bin/uv" ]; then '
+        'curl -LsSf https://astral.sh/uv/install.sh | sh; '
+        'fi && export PATH="$HOME/.local/bin:$PATH" && hash -r && uv --version'
+    )
+
+
+@dataclass(frozen=True)
+class RecipeSessionRef:
+    """A lightweight proxy bound to one tmux session/window name."""
+
+    recipe: "RecipeSpecCore"
+    name: str
+    open_step_id: Optional[str] = None
+    host_ref: Optional[str] = None
+    default_depends_on: tuple[str, ...] = field(default_factory=tuple)
+    default_cwd: Optional[str] = None
+    default_env: dict[str, Any] = field(default_factory=dict)
+    default_gpus: tuple[str, ...] = field(default_factory=tuple)
+    close_on_exit: bool = False
+    close_step_options: Optional[Dict[str, Any]] = None
+    _linear_cm: Any = field(default=None, init=False, repr=False, compare=False)
+
+    def _merge_depends(self, depends_on: Optional[Iterable[str]]) -> list[str]:
+        merged: list[str] = []
+        seen = set()
+        for item in [*self.default_depends_on, *(depends_on or [])]:
+            dep = str(item).strip()
+            if not dep or dep in seen:
+                continue
+            seen.add(dep)
+            merged.append(dep)
+        return merged
+
+    def _require_host_ref(self) -> str:
+        host_ref = str(self.host_ref or "").strip()
+        if not host_ref:
+            raise PythonRecipeError(
+                "session transfer helpers require a session created with host=..."
+            )
+        return host_ref
+
+    def __enter__(self) -> "RecipeSessionRef":
+        cm = self.recipe._linear(depends_on=list(self.default_depends_on))
+        cm.__enter__()
+        object.__setattr__(self, "_linear_cm", cm)
+        return self
+
+    def __exit__(self, exc_type, exc, tb) -> bool:
+        cm = getattr(self, "_linear_cm", None)
+        result = False
+        try:
+            if cm is not None and self.close_on_exit:
+                self.close(step_options=self.close_step_options)
+        finally:
+            if cm is not None:
+                object.__setattr__(self, "_linear_cm", None)
+                result = bool(cm.__exit__(exc_type, exc, tb))
+        return result
+
+    def remote_path(self, value: Any) -> str:
+        """Resolve one remote endpoint on this session's host."""
+        return f"@{self._require_host_ref()}:{self.recipe.resolve_endpoint(value)}"
+
+    def after(self, *dependencies: Any) -> "RecipeSessionRef":
+        """Return a new session ref with extra default dependencies.
+
+        This only affects future steps created from the returned session ref.
+        The session open step is already registered, so retroactively wiring it
+        to later work can create immediate dependency cycles.
+        """
+        normalized: list[str] = []
+        for item in dependencies:
+            normalized.extend(normalize_after(item) or [])
+        return replace(
+            self,
+            default_depends_on=tuple(self._merge_depends(normalized)),
+        )
+
+    def cd(self, path: Optional[str]) -> "RecipeSessionRef":
+        """Return a new session ref with a default working directory."""
+        cwd = None if path is None else str(path).strip() or None
+        return replace(self, default_cwd=cwd)
+
+    def env(self, **values: Any) -> "RecipeSessionRef":
+        """Return a new session ref with merged default environment values."""
+        merged = dict(self.default_env or {})
+        for key, value in values.items():
+            merged[str(key)] = "" if value is None else str(value)
+        return replace(self, default_env=merged)
+
+    def gpus(self, *indices: Any) -> "RecipeSessionRef":
+        """Return a new session ref whose commands run with these GPU indices."""
+        return replace(self, default_gpus=tuple(normalize_gpus(list(indices))))
+
+    def _session_call(
+        self,
+        *,
+        id: Optional[str] = None,
+        depends_on: Optional[Iterable[str]] = None,
+        step_options: Optional[Dict[str, Any]] = None,
+        after: Any = None,
+        extra: Optional[Dict[str, Any]] = None,
+    ) -> tuple[Optional[str], Optional[list[str]], Optional[Dict[str, Any]]]:
+        payload = dict(extra or {})
+        if after is not None and "after" not in payload:
+            payload["after"] = after
+        params, authoring = split_step_call(payload)
+        if params:
+            unknown = ", ".join(sorted(params))
+            raise PythonRecipeError(f"unsupported session options: {unknown}")
+
+        resolved_id = id if id is not None else authoring.get("id")
+        merged_depends = self._merge_depends(
+            [*(depends_on or []), *(authoring.get("depends_on") or [])]
+        )
+        linear_seed = self.recipe.current_linear_seed_dependencies()
+        if linear_seed:
+            merged_depends = self._merge_depends([*merged_depends, *linear_seed])
+        merged_options = dict(step_options or {})
+        merged_options.update(authoring.get("step_options") or {})
+        return (
+            resolved_id,
+            merged_depends or None,
+            merged_options or None,
+        )
+
+    def _context_depends(self, depends_on: Optional[Iterable[str]] = None) -> list[str]:
+        merged_depends = self._merge_depends(depends_on)
+        linear_seed = self.recipe.current_linear_seed_dependencies()
+        if linear_seed:
+            merged_depends = self._merge_depends([*merged_depends, *linear_seed])
+        return merged_depends
+
+    def __call__(self, command: str, **kwargs: Any) -> str:
+        """Run one foreground command in this session."""
+        return self.run(command, **kwargs)
+
+    def run(
+        self,
+        command: Any,
+        *,
+        timeout: Any = 0,
+        background: bool = False,
+        stdout: Any = None,
+        tee: Any = None,
+        done_file: Optional[str] = None,
+        done_text: str = "ok\n",
+        capture_var: Optional[str] = None,
+        cwd: Optional[str] = None,
+        env: Optional[Dict[str, Any]] = None,
+        gpus: Any = None,
+        script: Any = None,
+        id: Optional[str] = None,
+        depends_on: Optional[Iterable[str]] = None,
+        step_options: Optional[Dict[str, Any]] = None,
+        after: Any = None,
+        **kwargs: Any,
+    ) -> str:
+        """Run one command in this session.
+
+        `gpus` (e.g. `[0, 1]`) overrides the session's GPUs for this command.
+        """
+        resolved_id, merged_depends, merged_options = self._session_call(
+            id=id,
+            depends_on=depends_on,
+            step_options=step_options,
+            after=after,
+            extra=kwargs,
+        )
+        return self.recipe.session_run(
+            self.name,
+            command,
+            timeout=timeout,
+            background=background,
+            stdout=stdout,
+            tee=tee,
+            done_file=done_file,
+            done_text=done_text,
+            capture_var=capture_var,
+            cwd=cwd or self.default_cwd,
+            env={**dict(self.default_env or {}), **dict(env or {})} if (self.default_env or env) else None,
+            gpus=self.default_gpus if gpus is None else gpus,
+            script=script,
+            id=resolved_id,
+            depends_on=merged_depends,
+            step_options=merged_options,
+        )
+
+    def run_script(self, path: Any, *args: Any, **kwargs: Any) -> str:
+        """Upload a local script file (relative to the recipe file) and run it with `args`.
+
+        The host keeps one copy per content hash under ~/.cache/trainsh/scripts,
+        so unchanged scripts are not sent again; accepts the same options as `run`.
+        """
+        command = " ".join([SCRIPT_PLACEHOLDER, *(shlex.quote(str(arg)) for arg in args)])
+        return self.run(command, script=path, **kwargs)
+
+    def bg(self, command: str, **kwargs: Any) -> str:
+        """Compact alias for a background command."""
+        return self.run(command, background=True, **kwargs)
+
+    def script(
+        self,
+        body: str,
+        *,
+        strict: bool = True,
+        background: bool = False,
+        timeout: Any = 0,
+        tee: Any = None,
+        done_file: Optional[str] = None,
+        done_text: str = "ok\n",
+        capture_var: Optional[str] = None,
+        cwd: Optional[str] = None,
+        env: Optional[Dict[str, Any]] = None,
+        id: Optional[str] = None,
+        depends_on: Optional[Iterable[str]] = None,
+        step_options: Optional[Dict[str, Any]] = None,
+        after: Any = None,
+        **kwargs: Any,
+    ) -> str:
+        """Run a multiline shell script through bash -lc."""
+        script_body = dedent(str(body)).strip("\n")
+        if strict:
+            script_body = f"set -euo pipefail\n{script_body}" if script_body else "set -euo pipefail"
+        command = f"bash -lc {shlex.quote(script_body)}"
+        return self.run(
+            command,
+            timeout=timeout,
+            background=background,
+            tee=tee,
+            done_file=done_file,
+            done_text=done_text,
+            capture_var=capture_var,
+            cwd=cwd,
+            env=env,
+            id=id,
+            depends_on=depends_on,
+            step_options=step_options,
+            after=after,
+            **kwargs,
+        )
+
+    def sh(self, body: str, **kwargs: Any) -> str:
+        """Compact alias for :meth:`script`."""
+        return self.script(body, **kwargs)
+
+    def copy_to(
+        self,
+        source: Any,
+        destination: Any,
+        *,
+        id: Optional[str] = None,
+        depends_on: Optional[Iterable[str]] = None,
+        step_options: Optional[Dict[str, Any]] = None,
+    ) -> str:
+        """Copy a local/storage source to this session host."""
+        return self.recipe.copy(
+            self.recipe.resolve_endpoint(source),
+            self.remote_path(destination),
+            id=id,
+            depends_on=self._context_depends(depends_on),
+            step_options=step_options,
+        )
+
+    def copy_from(
+        self,
+        source: Any,
+        destination: Any,
+        *,
+        id: Optional[str] = None,
+        depends_on: Optional[Iterable[str]] = None,
+        step_options: Optional[Dict[str, Any]] = None,
+    ) -> str:
+        """Copy from this session host to a local/storage destination."""
+        return self.recipe.copy(
+            self.remote_path(source),
+            self.recipe.resolve_endpoint(destination),
+            id=id,
+            depends_on=self._context_depends(depends_on),
+            step_options=step_options,
+        )
+
+    def sync_to(
+        self,
+        source: Any,
+        destination: Any,
+        *,
+        id: Optional[str] = None,
+        depends_on: Optional[Iterable[str]] = None,
+        step_options: Optional[Dict[str, Any]] = None,
+    ) -> str:
+        """Sync a local/storage source to this session host."""
+        return self.recipe.sync(
+            self.recipe.resolve_endpoint(source),
+            self.remote_path(destination),
+            id=id,
+            depends_on=self._context_depends(depends_on),
+            step_options=step_options,
+        )
+
+    def sync_from(
+        self,
+        source: Any,
+        destination: Any,
+        *,
+        id: Optional[str] = None,
+        depends_on: Optional[Iterable[str]] = None,
+        step_options: Optional[Dict[str, Any]] = None,
+    ) -> str:
+        """Sync from this session host to a local/storage destination."""
+        return self.recipe.sync(
+            self.remote_path(source),
+            self.recipe.resolve_endpoint(destination),
+            id=id,
+            depends_on=self._context_depends(depends_on),
+            step_options=step_options,
+        )
+
+    def move_to(
+        self,
+        source: Any,
+        destination: Any,
+        *,
+        id: Optional[str] = None,
+        depends_on: Optional[Iterable[str]] = None,
+        step_options: Optional[Dict[str, Any]] = None,
+    ) -> str:
+        """Move a local/storage source to this session host."""
+        return self.recipe.move(
+            self.recipe.resolve_endpoint(source),
+            self.remote_path(destination),
+            id=id,
+            depends_on=self._context_depends(depends_on),
+            step_options=step_options,
+        )
+
+    def move_from(
+        self,
+        source: Any,
+        destination: Any,
+        *,
+        id: Optional[str] = None,
+        depends_on: Optional[Iterable[str]] = None,
+        step_options: Optional[Dict[str, Any]] = None,
+    ) -> str:
+        """Move from this session host to a local/storage destination."""
+        return self.recipe.move(
+            self.remote_path(source),
+            self.recipe.resolve_endpoint(destination),
+            id=id,
+            depends_on=self._context_depends(depends_on),
+            step_options=step_options,
+        )
+
+    def upload(self, source: Any, destination: Any, **kwargs: Any) -> str:
+        """Alias for :meth:`copy_to`."""
+        return self.copy_to(source, destination, **kwargs)
+
+    def download(self, source: Any, destination: Any, **kwargs: Any) -> str:
+        """Alias for :meth:`copy_from`."""
+        return self.copy_from(source, destination, **kwargs)
+
+    def install_uv(
+        self,
+        *,
+        force: bool = False,
+        id: Optional[str] = None,
+        depends_on: Optional[Iterable[str]] = None,
+        step_options: Optional[Dict[str, Any]] = None,
+        after: Any = None,
+        **kwargs: Any,
+    ) -> str:
+        """Install uv in this tmux session using the official Astral install script."""
+        return self.run(
+            official_uv_install_command(force=force),
+            id=id,
+            depends_on=depends_on,
+            step_options=step_options,
+            after=after,
+            **kwargs,
+        )
+
+    def install_flash_attn(
+        self,
+        *,
+        version: str = "",
+        package_name: str = "flash-attn",
+        install_spec: str = "",
+        python_bin: str = "",
+        max_jobs: Optional[int] = None,
+        extra_env: Optional[Dict[str, Any]] = None,
+        force_build: bool = False,
+        background: bool = False,
+        timeout: Any = 0,
+        tee: Any = None,
+        done_file: Optional[str] = None,
+        done_text: str = "ok\n",
+        capture_var: Optional[str] = None,
+        cwd: Optional[str] = None,
+        env: Optional[Dict[str, Any]] = None,
+        id: Optional[str] = None,
+        depends_on: Optional[Iterable[str]] = None,
+        step_options: Optional[Dict[str, Any]] = None,
+        after: Any = None,
+        **kwargs: Any,
+    ) -> str:
+        """Install FlashAttention in this tmux session using the generated shell script."""
+        script_env = dict(extra_env or {})
+        return self.script(
+            flash_attn_install_script(
+                version=version,
+                python_bin=python_bin,
+                package_name=package_name,
+                install_spec=install_spec,
+                extra_env=script_env,
+                force_build=force_build,
+                max_jobs=max_jobs,
+            ),
+            strict=False,
+            background=background,
+            timeout=timeout,
+            tee=tee,
+            done_file=done_file,
+            done_text=done_text,
+            capture_var=capture_var,
+            cwd=cwd,
+            env=env,
+            id=id,
+            depends_on=depends_on,
+            step_options=step_options,
+            after=after,
+            **kwargs,
+        )
+
+    def capture_pane(
+        self,
+        *,
+        target: str,
+        lines: int = 400,
+        output: Any,
+        id: Optional[str] = None,
+        depends_on: Optional[Iterable[str]] = None,
+        step_options: Optional[Dict[str, Any]] = None,
+        after: Any = None,
+        **kwargs: Any,
+    ) -> str:
+        """Capture one tmux pane into a file on the session host."""
+        command = [
+            "tmux",
+            "capture-pane",
+            "-pt",
+            str(target),
+            "-S",
+            f"-{max(0, int(lines))}",
+        ]
+        return self.run(
+            command,
+            stdout=output,
+            id=id,
+            depends_on=depends_on,
+            step_options=step_options,
+            after=after,
+            **kwargs,
+        )
+
+    def wait(
+        self,
+        pattern: Optional[str] = None,
+        *,
+        file: Optional[str] = None,
+        port: Optional[int] = None,
+        idle: bool = False,
+        timeout: Any = "5m",
+        id: Optional[str] = None,
+        depends_on: Optional[Iterable[str]] = None,
+        step_options: Optional[Dict[str, Any]] = None,
+        after: Any = None,
+        **kwargs: Any,
+    ) -> str:
+        """Wait on this session using tmux-session semantics."""
+        resolved_id, merged_depends, merged_options = self._session_call(
+            id=id,
+            depends_on=depends_on,
+            step_options=step_options,
+            after=after,
+            extra=kwargs,
+        )
+        return self.recipe.session_wait(
+            self.name,
+            pattern=pattern,
+            file=file,
+            port=port,
+            idle=idle,
+            timeout=timeout,
+            id=resolved_id,
+            depends_on=merged_depends,
+            step_options=merged_options,
+        )
+
+    def wait_idle(
+        self,
+        *,
+        timeout: Any = "5m",
+        id: Optional[str] = None,
+        depends_on: Optional[Iterable[str]] = None,
+        step_options: Optional[Dict[str, Any]] = None,
+        after: Any = None,
+        **kwargs: Any,
+    ) -> str:
+        """Wait until the session pane becomes idle."""
+        return self.wait(
+            idle=True,
+            timeout=timeout,
+            id=id,
+            depends_on=depends_on,
+            step_options=step_options,
+            after=after,
+            **kwargs,
+        )
+
+    def idle(self, **kwargs: Any) -> str:
+        """Compact alias for idle waits."""
+        return self.wait_idle(**kwargs)
+
+    def file(self, path: str, **kwargs: Any) -> str:
+        """Compact alias for file waits."""
+        return self.wait(file=path, **kwargs)
+
+    def port(self, value: int, **kwargs: Any) -> str:
+        """Compact alias for port waits."""
+        return self.wait(port=value, **kwargs)
+
+    def close(
+        self,
+        *,
+        id: Optional[str] = None,
+        depends_on: Optional[Iterable[str]] = None,
+        step_options: Optional[Dict[str, Any]] = None,
+        after: Any = None,
+        **kwargs: Any,
+    ) -> str:
+        """Close this tmux session/window."""
+        resolved_id, merged_depends, merged_options = self._session_call(
+            id=id,
+            depends_on=depends_on,
+            step_options=step_options,
+            after=after,
+            extra=kwargs,
+        )
+        return self.recipe.tmux_close(
+            self.name,
+            id=resolved_id,
+            depends_on=merged_depends,
+            step_options=merged_options,
+        )
@@ -336,16 +336,89 @@ class RecipeStorageMixin:
         *,
         path: Any,
         recursive: bool = False,
+        trash: Optional[bool] = None,
         id: Optional[str] = None,
         depends_on: Optional[Iterable[str]] = None,
         step_options: Optional[Dict[str, Any]] = None,
     ) -> str:
-        """Delete storage entry."""
+        """Delete storage entry; ``trash=True`` moves it into the storage's ``.trash/`` instead.
+
+        ``trash=None`` follows ``storage.trash.enabled`` in config.
+        """
         cleaned_storage, target_path = self._storage_target(storage, path=path)
+        params: Dict[str, Any] = {"storage": cleaned_storage, "path": target_path, "recursive": recursive}
+        if trash is not None:
+            params["trash"] = bool(trash)
         return self.provider(
             "storage",
             "delete",
-            params={"storage": cleaned_storage, "path": target_path, "recursive": recursive},
+            params=params,
+            id=id,
+            depends_on=depends_on,
+            step_options=step_options,
+        )
+
+    def storage_trash_list(
+        self,
+        storage: Any,
+        *,
+        id: Optional[str] = None,
+        depends_on: Optional[Iterable[str]] = None,
+        step_options: Optional[Dict[str, Any]] = None,
+    ) -> str:
+        """List soft-deleted entries (JSON) in a storage's ``.trash/``."""
+        cleaned_storage, _path = self._storage_target(storage)
+        return self.provider(
+            "storage",
+            "trash_list",
+            params={"storage": cleaned_storage},
+            id=id,
+            depends_on=depends_on,
+            step_options=step_options,
+        )
+
+    def storage_trash_restore(
+        self,
+        storage: Any,
+        *,
+        entry: str,
+        destination: Optional[str] = None,
+        id: Optional[str] = None,
+        depends_on: Optional[Iterable[str]] = None,
+        step_options: Optional[Dict[str, Any]] = None,
+    ) -> str:
+        """Restore one trash entry to its original path (or ``destination``)."""
+        cleaned_storage, _path = self._storage_target(storage)
+        params: Dict[str, Any] = {"storage": cleaned_storage, "id": entry}
+        if destination:
+            params["destination"] = destination
+        return self.provider(
+            "storage",
+            "trash_restore",
+            params=params,
+            id=id,
+            depends_on=depends_on,
+            step_options=step_options,
+        )
+
+    def storage_trash_empty(
+        self,
+        storage: Any,
+        *,
+        older_than_days: Optional[float] = None,
+        id: Optional[str] = None,
+        depends_on: Optional[Iterable[str]] = None,
+        step_options: Optional[Dict[str, Any]] = None,
+    ) -> str:
+        """Permanently delete trash entries (all, or those older than ``older_than_days``)."""
+        cleaned_storage, _path = self._storage_target(storage)
+        params: Dict[str, Any] = {"storage": cleaned_storage}
+        if older_than_days is not None:
+            params["older_than_days"] = older_than_days
+        return self.provider(
+            "storage",
+            "trash_empty",
+            params=params,
             id=id,
             depends_on=depends_on,
             step_options=step_options,
@@ -463,6 +536,7 @@ class RecipeStorageMixin:
         *,
         path: Any,
         recursive: bool = False,
+        trash: Optional[bool] = None,
         id: Optional[str] = None,
         depends_on: Optional[Iterable[str]] = None,
         step_options: Optional[Dict[str, Any]] = None,
@@ -472,6 +546,7 @@ class RecipeStorageMixin:
             storage,
             path=path,
             recursive=recursive,
+            trash=trash,
             id=id,
             depends_on=depends_on,
             step_options=step_options,
@@ -2,7 +2,7 @@
 
 from __future__ import annotations
 
-from typing import Any, Dict, Iterable, Optional
+from typing import Any, Dict, Iterable, Optional, Sequence, Union
 
 
 class RecipeProviderTransferMixin:
@@ -10,27 +10,48 @@ class RecipeProviderTransferMixin:
 
     def transfer(
         self,
-        source: str,
+        source: Union[str, Sequence[str]],
         destination: str,
         *,
         operation: str = "copy",
         delete: bool = False,
         exclude: Optional[Iterable[str]] = None,
+        rsync: Optional[Dict[str, Any]] = None,
+        tar_stream: bool = False,
+        remote_exec: Optional[bool] = None,
         id: Optional[str] = None,
         depends_on: Optional[Iterable[str]] = None,
         step_options: Optional[Dict[str, Any]] = None,
     ) -> str:
-        """Transfer files/folders/objects using the configured transfer engine."""
+        """Transfer files/folders/objects using the configured transfer engine.
+
+        `rsync` tunes rsync-backed transfers, e.g. `{"partial": "append-verify",
+        "bwlimit": "20M", "compress": False}`. `tar_stream=True` streams
+        tar | zstd over SSH instead, for datasets with very many small files.
+        `remote_exec=True` runs rclone on the host for host <-> cloud copies
+        (default: the `transfer.remote_exec` config value).
+
+        `source` may be a glob such as `"@gpu:/ckpt/epoch_*.pt"` or a list of
+        paths on one side; matches are resolved when the step runs and each
+        lands in `destination` under its own name.
+        """
+        params: Dict[str, Any] = {
+            "source": source if isinstance(source, str) else [str(item) for item in source],
+            "destination": destination,
+            "delete": self._normalize_bool(delete),
+            "exclude": self._normalize_list(exclude),
+            "operation": str(operation).strip().lower(),
+        }
+        if rsync:
+            params["rsync"] = dict(rsync)
+        if tar_stream:
+            params["tar_stream"] = True
+        if remote_exec is not None:
+            params["remote_exec"] = self._normalize_bool(remote_exec)
         return self.provider(
             "transfer",
             operation,
-            params={
-                "source": source,
-                "destination": destination,
-                "delete": self._normalize_bool(delete),
-                "exclude": self._normalize_list(exclude),
-                "operation": str(operation).strip().lower(),
-            },
+            params=params,
             id=id,
             depends_on=depends_on,
             step_options=step_options,
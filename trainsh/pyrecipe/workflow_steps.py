@@ -4,6 +4,10 @@ from __future__ import annotations
 
 from typing import Any, Dict, Iterable, Optional
 
+from .models import PythonRecipeError
+
+_HOOK_WHEN = {"always": "always", "completed": "completed", "success": "completed", "failed": "failed", "failure": "failed"}
+
 
 class RecipeProviderWorkflowMixin:
     """Higher-level workflow helpers built on top of generic providers."""
@@ -420,3 +424,176 @@ class RecipeProviderWorkflowMixin:
             depends_on=depends_on,
             step_options=step_options,
         )
+
+    def run_notebook(
+        self,
+        notebook: str,
+        *,
+        host: Optional[str] = None,
+        parameters: Optional[Dict[str, Any]] = None,
+        variables: Optional[Iterable[str]] = None,
+        kernel: Optional[str] = None,
+        cwd: Optional[str] = None,
+        output: Optional[str] = None,
+        timeout: Any = 0,
+        capture_var: Optional[str] = None,
+        id: Optional[str] = None,
+        depends_on: Optional[Iterable[str]] = None,
+        step_options: Optional[Dict[str, Any]] = None,
+    ) -> str:
+        """Run a .ipynb with papermill on a host and download the executed copy.
+
+        ``parameters`` and the recipe ``variables`` named go into the notebook's
+        ``parameters`` cell; ``output`` is the local path of the executed copy
+        (default: next to the step logs), which ``capture_var`` receives.
+        """
+        params: Dict[str, Any] = {"notebook": self._resolve_script(notebook), "timeout": timeout}
+        if not params["notebook"].endswith(".ipynb"):
+            raise PythonRecipeError(f"run_notebook(...) expects a .ipynb file, got {notebook}")
+        if host is not None:
+            params["host"] = host
+        if parameters:
+            params["parameters"] = dict(parameters)
+        if variables is not None:
+            params["variables"] = list(variables)
+        for key, value in (("kernel", kernel), ("cwd", cwd), ("output", output), ("capture_var", capture_var)):
+            if value:
+                params[key] = value
+        return self.provider(
+            "notebook",
+            "run",
+            params=params,
+            id=id,
+            depends_on=depends_on,
+            step_options=step_options,
+        )
+
+    def integrity_manifest(
+        self,
+        host: str,
+        path: str,
+        *,
+        output: Optional[str] = None,
+        id: Optional[str] = None,
+        depends_on: Optional[Iterable[str]] = None,
+        step_options: Optional[Dict[str, Any]] = None,
+    ) -> str:
+        """Hash every file under `path` on a host; `output` saves the manifest locally."""
+        params: Dict[str, Any] = {"host": host, "path": path}
+        if output:
+            params["output"] = output
+        return self.provider(
+            "util",
+            "integrity_manifest",
+            params=params,
+            id=id,
+            depends_on=depends_on,
+            step_options=step_options,
+        )
+
+    def integrity_verify(
+        self,
+        local_dir: str,
+        *,
+        manifest: Optional[str] = None,
+        host: Optional[str] = None,
+        path: Optional[str] = None,
+        id: Optional[str] = None,
+        depends_on: Optional[Iterable[str]] = None,
+        step_options: Optional[Dict[str, Any]] = None,
+    ) -> str:
+        """Fail unless `local_dir` matches a saved `manifest` or a fresh one of `host:path`."""
+        params: Dict[str, Any] = {"local_dir": local_dir}
+        if manifest:
+            params["manifest"] = manifest
+        if host is not None:
+            params["host"] = host
+        if path is not None:
+            params["path"] = path
+        return self.provider(
+            "util",
+            "integrity_verify",
+            params=params,
+            id=id,
+            depends_on=depends_on,
+            step_options=step_options,
+        )
+
+    def run_recipe(
+        self,
+        recipe: str,
+        *,
+        variables: Optional[Dict[str, Any]] = None,
+        hosts: Optional[Dict[str, str]] = None,
+        capture_var: Optional[str] = None,
+        id: Optional[str] = None,
+        depends_on: Optional[Iterable[str]] = None,
+        step_options: Optional[Dict[str, Any]] = None,
+    ) -> str:
+        """Run another recipe as one step; `hosts` maps child aliases to this recipe's hosts."""
+        params: Dict[str, Any] = {"recipe": str(recipe)}
+        if variables:
+            params["variables"] = dict(variables)
+        if hosts:
+            params["hosts"] = {str(key): str(value) for key, value in hosts.items()}
+        if capture_var is not None:
+            params["capture_var"] = capture_var
+        return self.provider(
+            "recipe",
+            "run",
+            params=params,
+            id=id,
+            depends_on=depends_on,
+            step_options=step_options,
+        )
+
+    def on_complete(
+        self,
+        recipe: str,
+        *,
+        variables: Optional[Dict[str, Any]] = None,
+        hosts: Optional[Dict[str, str]] = None,
+        when: str = "always",
+    ) -> None:
+        """Run another recipe after this run ends, e.g. to fetch outputs, upload them and notify.
+
+        `variables` may reference the run's variables plus `${RUN_STATUS}`
+        (`completed`/`failed`/`cancelled`), `${EXIT_CODE}`, `${JOB_ID}`,
+        `${SESSION_HOST}` and `${SESSION_WORKDIR}`; `when` is `always`,
+        `completed` or `failed` (which includes cancelled runs). Spawned job
+        ids show in `train recipe status`.
+        """
+        mode = _HOOK_WHEN.get(str(when).strip().lower())
+        if mode is None:
+            raise PythonRecipeError(f"on_complete(when=...) must be always, completed or failed, not {when!r}")
+        hook: Dict[str, Any] = {"recipe": str(recipe), "when": mode, "variables": dict(variables or {})}
+        if hosts:
+            hook["hosts"] = {str(key): str(value) for key, value in hosts.items()}
+        self.completion_hooks.append(hook)
+
+    def tracking(
+        self,
+        *,
+        mlflow_uri: Optional[str] = None,
+        experiment: Optional[str] = None,
+        metrics: Optional[Iterable[str]] = None,
+        artifacts: Optional[Iterable[str]] = None,
+        enabled: bool = True,
+    ) -> None:
+        """Register each run of this recipe as an MLflow run (overrides the `tracking` config section).
+
+        Params are the recipe variables, `metrics` names are read as
+        `name=value` / `name: value` from step output, and `artifacts` are
+        local file globs uploaded when the run ends. `enabled=False` turns
+        tracking off for this recipe.
+        """
+        options: Dict[str, Any] = {"enabled": bool(enabled)}
+        if mlflow_uri is not None:
+            options["mlflow_uri"] = str(mlflow_uri)
+        if experiment is not None:
+            options["experiment"] = str(experiment)
+        if metrics is not None:
+            options["metrics"] = [str(item) for item in ([metrics] if isinstance(metrics, str) else metrics)]
+        if artifacts is not None:
+            options["artifacts"] = [str(item) for item in ([artifacts] if isinstance(artifacts, str) else artifacts)]
+        self.tracking_options = options
@@ -0,0 +1,260 @@
+"""YAML recipe files: load them into a `RecipeSpec`, and convert recipes between YAML and Python.
+
+A YAML recipe is the document described by `train recipe schema`. Steps without
+`depends_on` follow the previous step, as in Python recipes; `depends_on: []`
+makes a step a root.
+"""
+
+from __future__ import annotations
+
+import dataclasses
+import os
+import pprint
+from pathlib import Path
+from typing import Any, Dict, List
+
+import yaml
+
+from ..constants import YAML_RECIPE_EXTENSIONS
+from ..core.recipe_models import RecipeStepModel
+from ..core.recipe_schema import key_lines, validate_document
+from .base import RecipeSpec
+from .control_steps import model_step_raw
+from .models import ProviderStep, PythonRecipeError
+
+DEFAULT_CALLBACKS = ["console", "jsonl"]
+# Step options: every ProviderStep field that is not part of the step body.
+OPTION_FIELDS = [
+    item for item in dataclasses.fields(ProviderStep)
+    if item.name not in {"provider", "operation", "params", "id", "depends_on", "group"}
+]
+
+
+def _field_default(item: dataclasses.Field) -> Any:
+    return item.default_factory() if item.default_factory is not dataclasses.MISSING else item.default
+
+
+_MODEL_DEFAULTS = {
+    item.name: _field_default(item)
+    for item in dataclasses.fields(RecipeStepModel)
+    if item.name not in {"type", "line_num", "raw"}
+}
+
+
+def _scalar_text(value: Any) -> str:
+    if isinstance(value, bool):
+        return "true" if value else "false"
+    return str(value)
+
+
+def is_yaml_recipe(path: str) -> bool:
+    return Path(path).suffix.lower() in YAML_RECIPE_EXTENSIONS
+
+
+def recipe_from_document(
+    data: Any,
+    *,
+    source: str = "<recipe>",
+    text: str | None = None,
+    base_dir: str | None = None,
+) -> RecipeSpec:
+    """Build a recipe from a parsed YAML/JSON document; schema issues raise `PythonRecipeError`.
+
+    Relative step `script` paths resolve against `base_dir` (default: the working directory).
+    """
+    issues = validate_document(data, text=text)
+    if issues:
+        details = "\n".join(f"  {issue}" for issue in issues)
+        raise PythonRecipeError(f"{source}: invalid recipe\n{details}")
+    lines = key_lines(text) if text is not None else {}
+
+    recipe = RecipeSpec(
+        data["name"],
+        executor=data.get("executor") or "sequential",
+        executor_kwargs=data.get("executor_kwargs"),
+        callbacks=data.get("callbacks"),
+        schedule=data.get("schedule"),
+    )
+    if base_dir:
+        recipe.source_dir = base_dir
+    recipe.variables.update({str(key): _scalar_text(value) for key, value in (data.get("variables") or {}).items()})
+    recipe.hosts.update({str(key): str(value) for key, value in (data.get("hosts") or {}).items()})
+    recipe.storages.update(dict(data.get("storages") or {}))
+    for name, values in (data.get("env_sets") or {}).items():
+        recipe.env_sets[str(name)] = {str(key): str(value) for key, value in values.items()}
+    if data.get("defaults"):
+        recipe._task_defaults = recipe._normalize_step_options(dict(data["defaults"]), init=True)
+
+    for index, raw_step in enumerate(data["steps"]):
+        step = dict(raw_step)
+        where = f"steps[{index}]"
+        line = lines.get(where, 0)
+        step_id = step.pop("id", None)
+        depends_on = step.pop("depends_on", None)
+        group = str(step.pop("group", "") or "")
+        options = {item.name: step.pop(item.name) for item in OPTION_FIELDS if item.name in step}
+        try:
+            if "provider" in step:
+                recipe.provider(
+                    step.pop("provider"),
+                    step.pop("operation"),
+                    params=step.pop("params", None),
+                    id=step_id,
+                    depends_on=depends_on,
+                    step_options=options,
+                )
+            else:
+                recipe.model_step(
+                    step.pop("type"),
+                    id=step_id,
+                    depends_on=depends_on,
+                    step_options=options,
+                    line_num=line,
+                    **step,
+                )
+        except (PythonRecipeError, ValueError) as exc:
+            prefix = f"{source}:{line}" if line else source
+            raise PythonRecipeError(f"{prefix}: {where}: {exc}") from exc
+        if depends_on == []:
+            recipe.steps[-1].depends_on = []
+        recipe.steps[-1].group = group
+    return recipe
+
+
+def load_yaml_recipe(path: str) -> RecipeSpec:
+    """Load one recipe from a `.yaml`/`.yml` file."""
+    source = Path(path).expanduser()
+    if not source.exists():
+        raise FileNotFoundError(f"recipe file not found: {path}")
+    text = source.read_text(encoding="utf-8")
+    try:
+        data = yaml.safe_load(text)
+    except yaml.YAMLError as exc:
+        raise PythonRecipeError(f"{source}: invalid YAML: {exc}") from exc
+    return recipe_from_document(data, source=str(source), text=text, base_dir=str(source.parent.resolve()))
+
+
+def _storage_value(value: Any) -> Any:
+    return value.to_dict() if hasattr(value, "to_dict") else value
+
+
+def recipe_to_document(recipe: RecipeSpec) -> Dict[str, Any]:
+    """Plain document for a loaded recipe; values equal to their defaults are left out."""
+    document: Dict[str, Any] = {"name": recipe.name}
+    if recipe.variables:
+        document["variables"] = dict(recipe.variables)
+    if recipe.hosts:
+        document["hosts"] = dict(recipe.hosts)
+    if recipe.storages:
+        document["storages"] = {key: _storage_value(value) for key, value in recipe.storages.items()}
+    if recipe.env_sets:
+        document["env_sets"] = {key: dict(value) for key, value in recipe.env_sets.items()}
+    if recipe.executor and recipe.executor != "sequential":
+        document["executor"] = recipe.executor
+    if recipe.executor_kwargs:
+        document["executor_kwargs"] = dict(recipe.executor_kwargs)
+    if list(recipe.callbacks) != DEFAULT_CALLBACKS:
+        document["callbacks"] = list(recipe.callbacks)
+    if recipe.schedule:
+        document["schedule"] = recipe.schedule
+
+    steps: List[Dict[str, Any]] = []
+    previous: List[str] = []
+    for step in recipe.steps:
+        item: Dict[str, Any] = {"id": step.id}
+        if isinstance(step, ProviderStep):
+            item["provider"] = step.provider
+            item["operation"] = step.operation
+            if step.params:
+                item["params"] = dict(step.params)
+        else:
+            model = step.step_model
+            item["type"] = model.type.value
+            fields = {name: getattr(model, name) for name, default in _MODEL_DEFAULTS.items() if getattr(model, name) != default}
+            if model.script.startswith(os.path.join(recipe.source_dir, "")):
+                fields["script"] = os.path.relpath(model.script, recipe.source_dir)
+            item.update(fields)
+            if model.raw != model_step_raw(model.type, fields):
+                item["raw"] = model.raw
+        if list(step.depends_on) != previous:
+            item["depends_on"] = list(step.depends_on)
+        for option in OPTION_FIELDS:
+            value = getattr(step, option.name)
+            if value != _field_default(option):
+                item[option.name] = value
+        if step.group:
+            item["group"] = step.group
+        steps.append(item)
+        previous = [step.id]
+    document["steps"] = steps
+    return document
+
+
+class _RecipeDumper(yaml.SafeDumper):
+    """Block style for multi-line strings; anything else unknown is written as text."""
+
+
+def _represent_str(dumper: yaml.SafeDumper, value: str) -> yaml.Node:
+    style = "|" if "\n" in value else None
+    return dumper.represent_scalar("tag:yaml.org,2002:str", value, style=style)
+
+
+_RecipeDumper.add_representer(str, _represent_str)
+_RecipeDumper.add_multi_representer(object, lambda dumper, value: _represent_str(dumper, str(value)))
+
+
+def dump_yaml_recipe(recipe: RecipeSpec) -> str:
+    """YAML text of a loaded recipe."""
+    return yaml.dump(recipe_to_document(recipe), Dumper=_RecipeDumper, sort_keys=False, allow_unicode=True, width=1000)
+
+
+def _literal(value: Any) -> str:
+    return pprint.pformat(value, width=100, sort_dicts=False)
+
+
+def render_python_recipe(recipe: RecipeSpec) -> str:
+    """`.pyrecipe` source that rebuilds a loaded recipe with `provider(...)`/`model_step(...)` calls."""
+    document = recipe_to_document(recipe)
+    ctor = [repr(document["name"])]
+    for key in ("executor", "executor_kwargs", "callbacks", "schedule"):
+        if key in document:
+            ctor.append(f"{key}={_literal(document[key])}")
+    out = ["from trainsh import Recipe", "", f"recipe = Recipe({', '.join(ctor)})"]
+    for key in ("variables", "hosts", "storages", "env_sets"):
+        if key in document:
+            out.append(f"recipe.{key}.update({_literal(document[key])})")
+    out.append("")
+    for item in document["steps"]:
+        item = dict(item)
+        kwargs = [f"id={item.pop('id')!r}"]
+        depends_on = item.pop("depends_on", None)
+        if depends_on:
+            kwargs.append(f"depends_on={depends_on!r}")
+        group = item.pop("group", "")
+        options = {option.name: item.pop(option.name) for option in OPTION_FIELDS if option.name in item}
+        if options:
+            kwargs.append(f"step_options={_literal(options)}")
+        if "provider" in item:
+            head = [repr(item.pop("provider")), repr(item.pop("operation"))]
+            if item.get("params"):
+                kwargs.insert(0, f"params={_literal(item.pop('params'))}")
+            out.append(f"recipe.provider({', '.join(head + kwargs)})")
+        else:
+            head = [repr(item.pop("type"))]
+            fields = [f"{key}={_literal(value)}" for key, value in item.items()]
+            out.append(f"recipe.model_step({', '.join(head + kwargs + fields)})")
+        if depends_on == []:
+            out.append("recipe.steps[-1].depends_on = []")
+        if group:
+            out.append(f"recipe.steps[-1].group = {group!r}")
+    return "\n".join(out) + "\n"
+
+
+__all__ = [
+    "dump_yaml_recipe",
+    "is_yaml_recipe",
+    "load_yaml_recipe",
+    "recipe_from_document",
+    "recipe_to_document",
+    "render_python_recipe",
+]
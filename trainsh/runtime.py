@@ -173,6 +173,8 @@ class JsonlCallbackSink:
                         "metadata": dict(event.payload),
                         "hosts": event.payload.get("hosts", {}) if isinstance(event.payload.get("hosts"), dict) else {},
                         "storages": event.payload.get("storages", {}) if isinstance(event.payload.get("storages"), dict) else {},
+                        "parent_run_id": self._coerce_text(event.payload.get("parent_job_id"), ""),
+                        "parent_step_id": self._coerce_text(event.payload.get("parent_step_id"), ""),
                         "updated_at": now,
                     }
                 )
@@ -320,6 +322,8 @@ class JsonlCallbackSink:
                         "metadata": dict(event.payload),
                         "hosts": previous.get("hosts", {}),
                         "storages": previous.get("storages", {}),
+                        "parent_run_id": str(previous.get("parent_run_id", "") or ""),
+                        "parent_step_id": str(previous.get("parent_step_id", "") or ""),
                         "updated_at": now,
                     }
                 )
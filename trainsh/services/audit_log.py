@@ -0,0 +1,112 @@
+"""Append-only audit trail of destructive and credential-touching operations.
+
+Each line of `audit.jsonl` records when, who (OS user and machine), what
+(`action` such as `vast.destroy` or `secret.get`), the target and a short
+summary. Writing never fails the operation being audited.
+"""
+
+from __future__ import annotations
+
+import getpass
+import json
+import os
+import re
+import socket
+from datetime import datetime, timedelta
+from pathlib import Path
+from typing import Any, Dict, List, Optional
+
+from ..constants import RUNTIME_STATE_DIR
+
+AUDIT_FILE = RUNTIME_STATE_DIR / "audit.jsonl"
+# Provider operations audited even when the step is not marked `destructive`.
+DESTRUCTIVE_PROVIDERS = {("storage", "delete")}
+_SUMMARY_LIMIT = 200
+_RELATIVE = re.compile(r"^(\d+)\s*([mhdw])$")
+_UNITS = {"m": "minutes", "h": "hours", "d": "days", "w": "weeks"}
+
+
+def _summary(text: Any) -> str:
+    summary = " ".join(str(text or "").split())
+    return summary if len(summary) <= _SUMMARY_LIMIT else summary[: _SUMMARY_LIMIT - 1] + "…"
+
+
+def record(
+    action: str,
+    target: str = "",
+    *,
+    host: str = "local",
+    detail: Any = "",
+    status: str = "ok",
+    source: str = "cli",
+    path: Optional[Path] = None,
+) -> Optional[Dict[str, Any]]:
+    """Append one audit entry; returns it, or None when the file is not writable."""
+    try:
+        user = getpass.getuser()
+    except Exception:
+        user = ""
+    entry = {
+        "ts": datetime.now().isoformat(timespec="seconds"),
+        "user": user,
+        "machine": socket.gethostname(),
+        "pid": os.getpid(),
+        "source": source,
+        "action": action,
+        "target": str(target),
+        "host": host,
+        "detail": _summary(detail),
+        "status": status,
+    }
+    target_path = Path(path or AUDIT_FILE)
+    try:
+        target_path.parent.mkdir(parents=True, exist_ok=True)
+        fd = os.open(target_path, os.O_WRONLY | os.O_APPEND | os.O_CREAT, 0o600)
+        with os.fdopen(fd, "a", encoding="utf-8") as handle:
+            handle.write(json.dumps(entry, ensure_ascii=False) + "\n")
+    except OSError:
+        return None
+    return entry
+
+
+def parse_time(spec: str, *, now: Optional[datetime] = None) -> datetime:
+    """`30m`, `24h`, `7d`, `2w` (ago) or an ISO date/time."""
+    text = str(spec or "").strip().lower()
+    match = _RELATIVE.match(text)
+    if match:
+        return (now or datetime.now()) - timedelta(**{_UNITS[match.group(2)]: int(match.group(1))})
+    try:
+        return datetime.fromisoformat(text)
+    except ValueError:
+        raise ValueError(f"invalid time: {spec!r} (use 24h, 7d or YYYY-MM-DD)") from None
+
+
+def read_entries(
+    *,
+    since: Optional[datetime] = None,
+    until: Optional[datetime] = None,
+    action: str = "",
+    path: Optional[Path] = None,
+) -> List[Dict[str, Any]]:
+    """Entries oldest first, filtered by time range and action prefix (`vast` matches `vast.destroy`)."""
+    entries: List[Dict[str, Any]] = []
+    try:
+        lines = Path(path or AUDIT_FILE).read_text(encoding="utf-8").splitlines()
+    except OSError:
+        return entries
+    for line in lines:
+        try:
+            entry = json.loads(line)
+            ts = datetime.fromisoformat(entry["ts"])
+        except (ValueError, KeyError, TypeError):
+            continue
+        if (since and ts < since) or (until and ts > until):
+            continue
+        name = str(entry.get("action", ""))
+        if action and name != action and not name.startswith(action + "."):
+            continue
+        entries.append(entry)
+    return entries
+
+
+__all__ = ["AUDIT_FILE", "DESTRUCTIVE_PROVIDERS", "parse_time", "read_entries", "record"]
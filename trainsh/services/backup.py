@@ -0,0 +1,234 @@
+"""Encrypted backup and restore of tmux-trainsh config and state.
+
+A backup is one file: a magic header, a random salt, and a Fernet token
+(key derived from the passphrase with PBKDF2) wrapping a tar.gz of the
+config directory (config, hosts, storages, projects, profiles, Vast
+templates), the current project's recipes, and pricing history. Secret
+values are only included on request, exported from whichever secrets
+backend is active.
+"""
+
+from __future__ import annotations
+
+import base64
+import io
+import json
+import os
+import shutil
+import tarfile
+import time
+from dataclasses import dataclass, field
+from pathlib import Path, PurePosixPath
+from typing import Dict, List, Optional, Tuple
+
+MAGIC = b"TRAINSH-BACKUP1\n"
+_SALT_LEN = 16
+_KDF_ITERATIONS = 480_000
+# secrets.enc is bound to its own password; secrets travel as exported values instead.
+_SKIPPED_CONFIG_FILES = {"secrets.enc"}
+_PRICING_FILES = ("exchange_rates.jsonl", "gpu_prices.jsonl")
+CONFLICT_POLICIES = ("skip", "overwrite")
+
+
+@dataclass
+class RestoreReport:
+    """What `restore_backup` did (or would do, with dry_run) per file and secret."""
+
+    written: List[str] = field(default_factory=list)
+    unchanged: List[str] = field(default_factory=list)
+    conflicts: List[str] = field(default_factory=list)
+    replaced: List[str] = field(default_factory=list)
+    secrets_written: List[str] = field(default_factory=list)
+    secrets_conflicts: List[str] = field(default_factory=list)
+
+
+def _roots() -> Dict[str, Path]:
+    from ..commands.recipe import get_recipes_dir
+    from ..constants import CONFIG_DIR, STATE_DIR
+
+    # Recipes are the ones `train recipe list` shows for the current project.
+    return {"config": CONFIG_DIR, "recipes": Path(get_recipes_dir()), "state": STATE_DIR}
+
+
+def _fernet(passphrase: str, salt: bytes):
+    from cryptography.fernet import Fernet
+    from cryptography.hazmat.primitives import hashes
+    from cryptography.hazmat.primitives.kdf.pbkdf2 import PBKDF2HMAC
+
+    kdf = PBKDF2HMAC(algorithm=hashes.SHA256(), length=32, salt=salt, iterations=_KDF_ITERATIONS)
+    return Fernet(base64.urlsafe_b64encode(kdf.derive(passphrase.encode())))
+
+
+def collect_files() -> List[Tuple[str, Path]]:
+    """(archive name, local path) for everything a backup carries."""
+    roots = _roots()
+    files: List[Tuple[str, Path]] = []
+    config_dir = roots["config"]
+    if config_dir.is_dir():
+        for path in sorted(config_dir.rglob("*")):
+            if path.is_file() and path.name not in _SKIPPED_CONFIG_FILES and not path.name.endswith(".bak"):
+                files.append((f"config/{path.relative_to(config_dir).as_posix()}", path))
+    recipes_dir = roots["recipes"]
+    if recipes_dir.is_dir():
+        for path in sorted(recipes_dir.rglob("*")):
+            if path.is_file():
+                files.append((f"recipes/{path.relative_to(recipes_dir).as_posix()}", path))
+    for name in _PRICING_FILES:
+        path = roots["state"] / name
+        if path.is_file():
+            files.append((f"state/{name}", path))
+    return files
+
+
+def _export_secrets() -> Dict[str, str]:
+    from ..core.secrets import get_secrets_manager
+
+    backend = get_secrets_manager()._get_backend()
+    if backend is None:
+        return {}
+    values = {}
+    for key in backend.list_set_keys():
+        value = backend.get(key)
+        if key and value:
+            values[key] = value
+    return values
+
+
+def create_backup(dest: Path, passphrase: str, *, include_secrets: bool = False) -> Dict[str, object]:
+    """Write an encrypted backup to *dest*; returns its manifest."""
+    if not passphrase:
+        raise ValueError("A passphrase is required.")
+    from .. import __version__
+
+    files = collect_files()
+    secrets = _export_secrets() if include_secrets else {}
+    manifest = {
+        "version": __version__,
+        "created_at": time.strftime("%Y-%m-%dT%H:%M:%S"),
+        "files": [name for name, _path in files],
+        "secrets": sorted(secrets),
+    }
+    buffer = io.BytesIO()
+    with tarfile.open(fileobj=buffer, mode="w:gz") as tar:
+        for name, path in files:
+            tar.add(str(path), arcname=name, recursive=False)
+        extras = {"manifest.json": manifest, **({"secrets.json": secrets} if include_secrets else {})}
+        for name, payload in extras.items():
+            data = json.dumps(payload, indent=2).encode()
+            info = tarfile.TarInfo(name)
+            info.size = len(data)
+            info.mtime = int(time.time())
+            tar.addfile(info, io.BytesIO(data))
+    salt = os.urandom(_SALT_LEN)
+    token = _fernet(passphrase, salt).encrypt(buffer.getvalue())
+
+    dest = Path(dest).expanduser()
+    dest.parent.mkdir(parents=True, exist_ok=True)
+    tmp = dest.with_name(dest.name + ".tmp")
+    tmp.write_bytes(MAGIC + salt + token)
+    tmp.chmod(0o600)
+    os.replace(tmp, dest)
+    return manifest
+
+
+def _open_backup(path: Path, passphrase: str) -> tarfile.TarFile:
+    raw = Path(path).expanduser().read_bytes()
+    if not raw.startswith(MAGIC):
+        raise ValueError(f"Not a tmux-trainsh backup: {path}")
+    salt = raw[len(MAGIC):len(MAGIC) + _SALT_LEN]
+    try:
+        payload = _fernet(passphrase, salt).decrypt(raw[len(MAGIC) + _SALT_LEN:])
+    except Exception:
+        raise ValueError("Wrong passphrase or corrupted backup.") from None
+    return tarfile.open(fileobj=io.BytesIO(payload), mode="r:gz")
+
+
+def _target(name: str, roots: Dict[str, Path]) -> Optional[Path]:
+    """Local path for an archive member, or None for anything outside the known roots."""
+    parts = PurePosixPath(name).parts
+    if len(parts) < 2 or parts[0] not in roots or ".." in parts or PurePosixPath(name).is_absolute():
+        return None
+    return roots[parts[0]].joinpath(*parts[1:])
+
+
+def read_manifest(path: Path, passphrase: str) -> Dict[str, object]:
+    with _open_backup(path, passphrase) as tar:
+        member = tar.extractfile("manifest.json")
+        return json.loads(member.read()) if member else {}
+
+
+def restore_backup(
+    path: Path,
+    passphrase: str,
+    *,
+    on_conflict: str = "skip",
+    include_secrets: bool = True,
+    dry_run: bool = False,
+) -> RestoreReport:
+    """Restore a backup. Local files that differ are kept (`skip`) or replaced
+    after saving a `.bak` copy (`overwrite`); identical files are left alone."""
+    if on_conflict not in CONFLICT_POLICIES:
+        raise ValueError(f"Unknown conflict policy: {on_conflict}")
+    report = RestoreReport()
+    roots = _roots()
+    secrets: Dict[str, str] = {}
+    with _open_backup(path, passphrase) as tar:
+        for member in tar.getmembers():
+            if member.name == "secrets.json":
+                handle = tar.extractfile(member)
+                secrets = json.loads(handle.read()) if handle else {}
+                continue
+            target = _target(member.name, roots)
+            if target is None or not member.isfile():
+                continue
+            handle = tar.extractfile(member)
+            data = handle.read() if handle else b""
+            if target.exists():
+                if target.read_bytes() == data:
+                    report.unchanged.append(member.name)
+                    continue
+                if on_conflict == "skip":
+                    report.conflicts.append(member.name)
+                    continue
+                report.replaced.append(member.name)
+                if not dry_run:
+                    shutil.copy2(target, target.with_name(target.name + ".bak"))
+            else:
+                report.written.append(member.name)
+            if not dry_run:
+                target.parent.mkdir(parents=True, exist_ok=True)
+                target.write_bytes(data)
+                if member.name.startswith("config/"):
+                    target.chmod(0o600)
+    if include_secrets and secrets:
+        _restore_secrets(secrets, report, on_conflict=on_conflict, dry_run=dry_run)
+    return report
+
+
+def _restore_secrets(secrets: Dict[str, str], report: RestoreReport, *, on_conflict: str, dry_run: bool) -> None:
+    from ..core.secrets import get_secrets_manager
+
+    manager = get_secrets_manager()
+    backend = manager._get_backend() if dry_run else manager._require_backend()
+    for key, value in sorted(secrets.items()):
+        current = backend.get(key) if backend is not None else None
+        if current == value:
+            continue
+        if current and on_conflict == "skip":
+            report.secrets_conflicts.append(key)
+            continue
+        report.secrets_written.append(key)
+        if not dry_run:
+            backend.set(key, value)
+    if not dry_run:
+        manager.clear_cache()
+
+
+__all__ = [
+    "CONFLICT_POLICIES",
+    "RestoreReport",
+    "collect_files",
+    "create_backup",
+    "read_manifest",
+    "restore_backup",
+]
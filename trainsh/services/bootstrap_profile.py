@@ -0,0 +1,235 @@
+"""Bootstrap profiles: one-time provisioning applied to hosts on first connect."""
+
+from __future__ import annotations
+
+import contextlib
+import fcntl
+import hashlib
+import json
+import re
+import shlex
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Any, Dict, Iterator, List, Optional
+
+from ..constants import RUNTIME_STATE_DIR
+from ..core.preflight import SUDO_PREFIX, package_install_command
+
+CACHE_FILENAME = "bootstrap.json"
+MARKER_DIR = "$HOME/.cache/tmux-trainsh/bootstrap"
+BOOTSTRAP_TIMEOUT_SECS = 900
+ALREADY_APPLIED = "trainsh-bootstrap-already-applied"
+_TMUX_CONF_EOF = "TRAINSH_TMUX_CONF_EOF"
+
+
+@dataclass(frozen=True)
+class BootstrapResult:
+    """Outcome of applying one profile to one host."""
+
+    status: str  # applied | already | failed
+    detail: str = ""
+
+    @property
+    def ok(self) -> bool:
+        return self.status in {"applied", "already"}
+
+
+def load_profiles(config: Optional[Dict[str, Any]] = None) -> Dict[str, Dict[str, Any]]:
+    """Return configured profiles keyed by name."""
+    if config is None:
+        from ..config import load_config
+
+        config = load_config()
+    profiles = (config.get("bootstrap") or {}).get("profiles") or {}
+    return {str(name): dict(value or {}) for name, value in profiles.items() if isinstance(value, dict)}
+
+
+def profile_for_host(host: Any, config: Optional[Dict[str, Any]] = None) -> str:
+    """The host's own profile, else `bootstrap.default_profile`."""
+    own = str(getattr(host, "bootstrap_profile", "") or "").strip()
+    if own:
+        return own
+    if config is None:
+        from ..config import load_config
+
+        config = load_config()
+    return str((config.get("bootstrap") or {}).get("default_profile", "") or "").strip()
+
+
+def profile_fingerprint(profile: Dict[str, Any]) -> str:
+    """Short hash of a profile; editing the profile re-applies it."""
+    payload = json.dumps(profile, sort_keys=True, default=str)
+    return hashlib.sha1(payload.encode("utf-8")).hexdigest()[:12]
+
+
+def marker_path(name: str, profile: Dict[str, Any]) -> str:
+    safe = re.sub(r"[^A-Za-z0-9._-]+", "-", name).strip("-.") or "profile"
+    return f"{MARKER_DIR}/{safe}.{profile_fingerprint(profile)}"
+
+
+def build_bootstrap_script(name: str, profile: Dict[str, Any], *, force: bool = False) -> str:
+    """Shell script applying a profile once, tracked by a marker file on the host."""
+    marker = marker_path(name, profile)
+    lines = ["set -e", SUDO_PREFIX]
+    if not force:
+        lines.append(f'if [ -e "{marker}" ]; then echo {ALREADY_APPLIED}; exit 0; fi')
+
+    packages = [str(item) for item in profile.get("packages") or [] if str(item).strip()]
+    if packages:
+        lines.append(package_install_command(packages))
+
+    timezone = str(profile.get("timezone", "") or "").strip()
+    if timezone:
+        zone = shlex.quote(timezone)
+        lines.append(
+            f"if [ -e /usr/share/zoneinfo/{zone} ]; then "
+            f"$SUDO ln -sf /usr/share/zoneinfo/{zone} /etc/localtime && "
+            f"echo {zone} | $SUDO tee /etc/timezone >/dev/null; "
+            f"else echo 'unknown timezone: '{zone} >&2; exit 1; fi"
+        )
+
+    tmux_conf = str(profile.get("tmux_conf", "") or "")
+    if tmux_conf:
+        # Keep a timestamped copy of a differing ~/.tmux.conf before replacing it.
+        lines.append(f"cat > \"$HOME/.tmux.conf.trainsh-new\" <<'{_TMUX_CONF_EOF}'\n{tmux_conf.rstrip(chr(10))}\n{_TMUX_CONF_EOF}")
+        lines.append(
+            'if [ -e "$HOME/.tmux.conf" ] && ! cmp -s "$HOME/.tmux.conf" "$HOME/.tmux.conf.trainsh-new"; then '
+            'cp -p "$HOME/.tmux.conf" "$HOME/.tmux.conf.trainsh-backup.$(date +%Y%m%d%H%M%S)"; fi'
+        )
+        lines.append('mv "$HOME/.tmux.conf.trainsh-new" "$HOME/.tmux.conf"')
+
+    for command in profile.get("commands") or []:
+        if str(command).strip():
+            lines.append(str(command))
+
+    lines.append(f'mkdir -p "{MARKER_DIR}" && touch "{marker}"')
+    return "\n".join(lines) + "\n"
+
+
+def _cache_key(host: Any) -> str:
+    """Name plus endpoint, so a re-provisioned host is bootstrapped again."""
+    name = str(getattr(host, "name", "") or "")
+    hostname = str(getattr(host, "hostname", "") or "")
+    return f"{name}@{hostname}:{getattr(host, 'port', '') or ''}"
+
+
+@contextlib.contextmanager
+def _locked_cache(state_dir: Optional[Path] = None) -> Iterator[Dict[str, Any]]:
+    """Load the local bootstrap cache under a file lock and save it on exit."""
+    root = Path(state_dir or RUNTIME_STATE_DIR)
+    root.mkdir(parents=True, exist_ok=True)
+    path = root / CACHE_FILENAME
+    with open(root / f"{CACHE_FILENAME}.lock", "w") as lock:
+        fcntl.flock(lock, fcntl.LOCK_EX)
+        payload = _read_cache(path)
+        yield payload
+        path.write_text(json.dumps(payload, indent=2, sort_keys=True), encoding="utf-8")
+
+
+def _read_cache(path: Path) -> Dict[str, Any]:
+    try:
+        payload = json.loads(path.read_text(encoding="utf-8"))
+    except Exception:
+        return {}
+    return payload if isinstance(payload, dict) else {}
+
+
+def is_cached(host: Any, name: str, profile: Dict[str, Any], *, state_dir: Optional[Path] = None) -> bool:
+    """True when this profile revision was already applied to this host from here."""
+    path = Path(state_dir or RUNTIME_STATE_DIR) / CACHE_FILENAME
+    return _read_cache(path).get(_cache_key(host)) == marker_path(name, profile)
+
+
+def remember_applied(host: Any, name: str, profile: Dict[str, Any], *, state_dir: Optional[Path] = None) -> None:
+    try:
+        with _locked_cache(state_dir) as payload:
+            payload[_cache_key(host)] = marker_path(name, profile)
+    except OSError:
+        pass
+
+
+def apply_profile(
+    host: Any,
+    name: str,
+    profile: Dict[str, Any],
+    *,
+    force: bool = False,
+    state_dir: Optional[Path] = None,
+) -> BootstrapResult:
+    """Run a profile on a stored host over SSH."""
+    from .ssh import SSHClient
+
+    script = build_bootstrap_script(name, profile, force=force)
+    try:
+        client = SSHClient.from_host(host)
+        result = client.run(f"bash -c {shlex.quote(script)}", timeout=BOOTSTRAP_TIMEOUT_SECS)
+    except Exception as exc:
+        return BootstrapResult("failed", str(exc))
+    if not result.success:
+        detail = (result.stderr or result.stdout or "").strip().splitlines()
+        return BootstrapResult("failed", detail[-1] if detail else f"exit code {result.exit_code}")
+    remember_applied(host, name, profile, state_dir=state_dir)
+    if ALREADY_APPLIED in (result.stdout or ""):
+        return BootstrapResult("already")
+    return BootstrapResult("applied")
+
+
+def ensure_bootstrapped(
+    host: Any,
+    *,
+    config: Optional[Dict[str, Any]] = None,
+    log=print,
+    state_dir: Optional[Path] = None,
+) -> Optional[BootstrapResult]:
+    """Apply the host's profile if it has one; never raises.
+
+    A profile revision already applied to the same endpoint is skipped
+    without connecting; `train host bootstrap` always checks the host.
+    Returns None when no profile applies.
+    """
+    name = profile_for_host(host, config)
+    if not name:
+        return None
+    profiles = load_profiles(config)
+    if name not in profiles:
+        log(f"Warning: bootstrap profile not found: {name}")
+        return BootstrapResult("failed", f"unknown profile {name}")
+    if is_cached(host, name, profiles[name], state_dir=state_dir):
+        return BootstrapResult("already")
+    result = apply_profile(host, name, profiles[name], state_dir=state_dir)
+    if result.status == "applied":
+        log(f"Applied bootstrap profile: {name}")
+    elif result.status == "failed":
+        log(f"Warning: bootstrap profile {name} failed: {result.detail}")
+    return result
+
+
+def profile_summary(profile: Dict[str, Any]) -> List[str]:
+    """Short human-readable description of what a profile does."""
+    parts: List[str] = []
+    packages = [str(item) for item in profile.get("packages") or []]
+    if packages:
+        parts.append("packages: " + ", ".join(packages))
+    if profile.get("timezone"):
+        parts.append(f"timezone: {profile['timezone']}")
+    if profile.get("tmux_conf"):
+        parts.append("writes ~/.tmux.conf (backs up the old one)")
+    commands = profile.get("commands") or []
+    if commands:
+        parts.append(f"{len(commands)} command(s)")
+    return parts
+
+
+__all__ = [
+    "BootstrapResult",
+    "apply_profile",
+    "build_bootstrap_script",
+    "ensure_bootstrapped",
+    "is_cached",
+    "load_profiles",
+    "marker_path",
+    "profile_fingerprint",
+    "profile_for_host",
+    "profile_summary",
+    "remember_applied",
+]
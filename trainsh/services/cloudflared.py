@@ -0,0 +1,218 @@
+"""Cloudflare Access SSH tunnels: host setup, cloudflared install, verification, and supervised forwards."""
+
+from __future__ import annotations
+
+import os
+import platform
+import shutil
+import subprocess
+import threading
+import time
+import urllib.request
+from pathlib import Path
+from typing import Any, Callable, Dict, List, Optional
+
+from ..constants import LOGS_DIR
+from .tunnel import find_free_local_port, is_local_port_open, stop_process, wait_for_local_tunnel
+from .vllm_service import sanitize_service_name
+
+RELEASE_URL = "https://github.com/cloudflare/cloudflared/releases/latest/download/cloudflared-{system}-{arch}"
+_ARCHES = {"x86_64": "amd64", "amd64": "amd64", "aarch64": "arm64", "arm64": "arm64", "armv7l": "arm"}
+
+
+def cloudflared_binary(env_vars: Optional[Dict[str, Any]] = None) -> str:
+    """Per-host `cloudflared_bin`, else `cloudflared.command` from config."""
+    from ..config import get_config_value
+
+    configured = str((env_vars or {}).get("cloudflared_bin", "") or "").strip()
+    if configured:
+        return configured
+    return str(get_config_value("cloudflared.command", "cloudflared") or "cloudflared").strip() or "cloudflared"
+
+
+def proxy_command(env_vars: Dict[str, Any], default_hostname: str) -> Optional[str]:
+    """ProxyCommand for hosts with `tunnel_type: cloudflared`."""
+    if str(env_vars.get("tunnel_type", "")).strip().lower() != "cloudflared":
+        return None
+    hostname = str(env_vars.get("cloudflared_hostname", default_hostname)).strip()
+    if not hostname:
+        return None
+    return f"{cloudflared_binary(env_vars)} access ssh --hostname {hostname}"
+
+
+def find_cloudflared(binary: str) -> Optional[str]:
+    """Path of an executable cloudflared, also looking in ~/.local/bin where --install puts it."""
+    expanded = os.path.expanduser(binary)
+    if os.sep in expanded:
+        return expanded if os.access(expanded, os.X_OK) else None
+    found = shutil.which(expanded)
+    if found:
+        return found
+    local = Path.home() / ".local" / "bin" / expanded
+    return str(local) if os.access(local, os.X_OK) else None
+
+
+def download_url(system: str = "", machine: str = "") -> str:
+    system = (system or platform.system()).lower()
+    machine = (machine or platform.machine()).lower()
+    if system == "darwin":
+        raise ValueError("Install cloudflared on macOS with `brew install cloudflared`.")
+    if system != "linux" or machine not in _ARCHES:
+        raise ValueError(f"No cloudflared download for {system}/{machine}; see https://developers.cloudflare.com/cloudflare-one/connections/connect-networks/downloads/")
+    return RELEASE_URL.format(system="linux", arch=_ARCHES[machine])
+
+
+def install_cloudflared(dest_dir: Optional[Path] = None, *, url: str = "") -> Path:
+    """Download the cloudflared release binary into ~/.local/bin (or `dest_dir`)."""
+    target_dir = dest_dir or Path.home() / ".local" / "bin"
+    target_dir.mkdir(parents=True, exist_ok=True)
+    target = target_dir / "cloudflared"
+    partial = target.with_suffix(".part")
+    with urllib.request.urlopen(url or download_url(), timeout=120) as response, partial.open("wb") as handle:
+        shutil.copyfileobj(response, handle)
+    partial.chmod(0o755)
+    partial.replace(target)
+    return target
+
+
+def configure_host(host: Any, hostname: str, binary: str = "") -> Any:
+    """Switch a host to connect through `cloudflared access ssh --hostname <hostname>`."""
+    env_vars = dict(host.env_vars or {})
+    env_vars["tunnel_type"] = "cloudflared"
+    env_vars["cloudflared_hostname"] = hostname
+    if binary:
+        env_vars["cloudflared_bin"] = binary
+    env_vars.pop("proxy_command", None)
+    host.env_vars = env_vars
+    return host
+
+
+def verify_tunnel(host: Any, *, timeout: int = 30) -> Dict[str, Any]:
+    """Check the local cloudflared binary and an SSH round trip through the tunnel."""
+    from .ssh import SSHClient
+
+    env_vars = dict(host.env_vars or {})
+    report: Dict[str, Any] = {
+        "hostname": str(env_vars.get("cloudflared_hostname", host.hostname) or ""),
+        "binary": find_cloudflared(cloudflared_binary(env_vars)),
+        "version": "",
+        "ssh_ok": False,
+        "latency_ms": None,
+        "error": "",
+    }
+    if str(env_vars.get("tunnel_type", "")).lower() != "cloudflared":
+        report["error"] = "host is not configured for cloudflared (run `train host cloudflared setup`)"
+        return report
+    if not report["binary"]:
+        report["error"] = "cloudflared not found locally (use --install or set cloudflared.command)"
+        return report
+    try:
+        version = subprocess.run([report["binary"], "--version"], capture_output=True, text=True, timeout=10)
+        lines = (version.stdout or version.stderr or "").strip().splitlines()
+        report["version"] = lines[0] if lines else ""
+    except (OSError, subprocess.TimeoutExpired):
+        pass
+    started = time.monotonic()
+    result = SSHClient.from_host(host).run("echo connected", timeout=timeout)
+    report["ssh_ok"] = result.success and "connected" in result.stdout
+    if report["ssh_ok"]:
+        report["latency_ms"] = int((time.monotonic() - started) * 1000)
+    else:
+        detail = (result.stderr or "").strip().splitlines()
+        report["error"] = detail[-1] if detail else f"ssh exited {result.exit_code}"
+    return report
+
+
+def forward_args(binary: str, hostname: str, local_port: int, bind_host: str = "127.0.0.1") -> List[str]:
+    return [binary, "access", "tcp", "--hostname", hostname, "--url", f"{bind_host}:{int(local_port)}"]
+
+
+class TunnelSupervisor:
+    """Keep a `cloudflared access tcp` forward alive, restarting it when it dies or stops answering."""
+
+    def __init__(
+        self,
+        hostname: str,
+        *,
+        binary: str = "cloudflared",
+        local_port: int = 0,
+        bind_host: str = "127.0.0.1",
+        interval: float = 30,
+        max_backoff: float = 300,
+        on_event: Optional[Callable[[str, Dict[str, Any]], None]] = None,
+    ):
+        self.hostname = hostname
+        self.binary = binary
+        self.bind_host = bind_host
+        self.local_port = int(local_port) or find_free_local_port(bind_host)
+        self.interval = max(1.0, float(interval))
+        self.max_backoff = max(1.0, float(max_backoff))
+        self.on_event = on_event
+        self.process: Optional[subprocess.Popen] = None
+        self.log_path = LOGS_DIR / "cloudflared" / f"{sanitize_service_name(hostname)}.log"
+        self.restarts = 0
+        self._stop = threading.Event()
+
+    def _emit(self, event: str, **payload: Any) -> None:
+        if self.on_event is not None:
+            self.on_event(event, {"hostname": self.hostname, "local_port": self.local_port, **payload})
+
+    def start(self) -> bool:
+        # cloudflared logs continuously; a file keeps a full pipe from stalling it.
+        self.log_path.parent.mkdir(parents=True, exist_ok=True)
+        with self.log_path.open("a", encoding="utf-8") as log:
+            self.process = subprocess.Popen(
+                forward_args(self.binary, self.hostname, self.local_port, self.bind_host),
+                stdin=subprocess.DEVNULL,
+                stdout=log,
+                stderr=subprocess.STDOUT,
+                start_new_session=True,
+            )
+        ok, detail = wait_for_local_tunnel(self.process, bind_host=self.bind_host, local_port=self.local_port, timeout=20)
+        if not ok:
+            detail = f"{detail.replace('ssh exited', 'cloudflared exited')} (see {self.log_path})"
+        self._emit("up" if ok else "failed", detail=detail)
+        return ok
+
+    def healthy(self) -> bool:
+        return bool(self.process and self.process.poll() is None and is_local_port_open(self.bind_host, self.local_port))
+
+    def run(self, *, max_restarts: int = -1) -> int:
+        """Supervise until `stop()`; returns the number of restarts."""
+        backoff = 1.0
+        try:
+            self.start()
+            while not self._stop.is_set():
+                if self.healthy():
+                    backoff = 1.0
+                    self._stop.wait(self.interval)
+                    continue
+                if 0 <= max_restarts <= self.restarts:
+                    self._emit("gave_up", restarts=self.restarts)
+                    break
+                self._emit("down", restarts=self.restarts)
+                stop_process(self.process)
+                if self._stop.wait(backoff):
+                    break
+                backoff = min(self.max_backoff, backoff * 2)
+                self.restarts += 1
+                self.start()
+        finally:
+            stop_process(self.process)
+        return self.restarts
+
+    def stop(self) -> None:
+        self._stop.set()
+
+
+__all__ = [
+    "TunnelSupervisor",
+    "cloudflared_binary",
+    "configure_host",
+    "download_url",
+    "find_cloudflared",
+    "forward_args",
+    "install_cloudflared",
+    "proxy_command",
+    "verify_tunnel",
+]
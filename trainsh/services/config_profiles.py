@@ -0,0 +1,117 @@
+"""Named config profiles: per-profile overrides of config.yaml plus optional data isolation.
+
+A profile lives under `profiles.<name>` in config.yaml and overrides any config
+key (Vast settings, `ui.currency`, `notifications`, `defaults.ssh_key_path`,
+`secrets.key_prefix`, ...). With `isolate_data` it also gets its own hosts,
+storages, projects, Vast templates, logs and run state.
+"""
+
+from __future__ import annotations
+
+import re
+import shutil
+from typing import Any, Dict, List, Optional
+
+from ..config import (
+    PROFILE_META_KEYS,
+    active_profile_name,
+    get_default_config,
+    merge_dicts,
+    read_config_file,
+    write_config_file,
+)
+
+
+def sanitize_profile_name(value: str) -> str:
+    return re.sub(r"[^A-Za-z0-9._-]+", "-", str(value or "").strip()).strip("-.")
+
+
+def load_profiles() -> Dict[str, Dict[str, Any]]:
+    """Profiles as stored in config.yaml, keyed by name."""
+    profiles = read_config_file().get("profiles") or {}
+    return {str(name): dict(data or {}) for name, data in profiles.items()}
+
+
+def save_profile(name: str, data: Dict[str, Any]) -> None:
+    """Create or replace one profile; sets up its data directories when isolated."""
+    config = read_config_file()
+    profiles = dict(config.get("profiles") or {})
+    profiles[name] = data
+    config["profiles"] = profiles
+    write_config_file(config)
+    if data.get("isolate_data"):
+        profile_config_dir(name).mkdir(parents=True, exist_ok=True)
+
+
+def remove_profile(name: str, *, purge_data: bool = False) -> None:
+    config = read_config_file()
+    profiles = dict(config.get("profiles") or {})
+    profiles.pop(name, None)
+    config["profiles"] = profiles
+    write_config_file(config)
+    if active_profile_name() == name:
+        switch_profile("")
+    if purge_data:
+        from ..constants import DATA_DIR, STATE_DIR
+
+        for path in (profile_config_dir(name), DATA_DIR / "profiles" / name, STATE_DIR / "profiles" / name):
+            shutil.rmtree(path, ignore_errors=True)
+
+
+def profile_config_dir(name: str):
+    from ..constants import PROFILES_DIR
+
+    return PROFILES_DIR / name
+
+
+def effective_config(name: str) -> Dict[str, Any]:
+    """Merged config as a process running under profile *name* would see it."""
+    base = merge_dicts(get_default_config(), read_config_file())
+    profile = {key: value for key, value in (load_profiles().get(name) or {}).items() if key not in PROFILE_META_KEYS}
+    return merge_dicts(base, profile)
+
+
+def switch_profile(name: str) -> Optional[str]:
+    """Make *name* the active profile for later commands ("" returns to the base config).
+
+    Returns the previously active profile. `TRAINSH_PROFILE` still wins per shell.
+    """
+    from ..constants import ACTIVE_PROFILE_FILE, CONFIG_DIR, read_active_profile
+
+    name = str(name or "").strip()
+    if name and name not in load_profiles():
+        raise KeyError(name)
+    previous = read_active_profile()
+    CONFIG_DIR.mkdir(parents=True, exist_ok=True)
+    if name:
+        ACTIVE_PROFILE_FILE.write_text(name + "\n", encoding="utf-8")
+    else:
+        ACTIVE_PROFILE_FILE.unlink(missing_ok=True)
+    return previous
+
+
+def profile_summary(data: Dict[str, Any]) -> List[str]:
+    """Short `key=value` descriptions of a profile's overrides."""
+    flat: List[str] = []
+
+    def walk(value: Any, prefix: str) -> None:
+        if isinstance(value, dict) and value:
+            for key, item in value.items():
+                walk(item, f"{prefix}{key}.")
+        else:
+            flat.append(f"{prefix[:-1]}={value}")
+
+    walk({key: value for key, value in data.items() if key not in PROFILE_META_KEYS}, "")
+    return flat
+
+
+__all__ = [
+    "effective_config",
+    "load_profiles",
+    "profile_config_dir",
+    "profile_summary",
+    "remove_profile",
+    "sanitize_profile_name",
+    "save_profile",
+    "switch_profile",
+]
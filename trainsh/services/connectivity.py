@@ -0,0 +1,292 @@
+"""Host connectivity tracking, offline short-circuits, and queued retries."""
+
+from __future__ import annotations
+
+import contextlib
+import fcntl
+import json
+import socket
+import threading
+import uuid
+from datetime import datetime
+from pathlib import Path
+from typing import Any, Callable, Dict, Iterable, Iterator, List, Optional, Tuple
+
+from ..constants import RUNTIME_STATE_DIR
+from ..core.models import HostType
+from ..core.errors import AppError, ErrorCode
+from ..core.runtime_store import RuntimeStore, to_jsonable
+
+STATE_FILENAME = "connectivity.json"
+EVENT_RUN_ID = "connectivity"
+DEFAULT_PROBE_ADDRESSES = ("1.1.1.1:53", "8.8.8.8:53")
+DEFAULT_OFFLINE_GRACE_SECS = 15
+DEFAULT_NETWORK_CHECK_TTL_SECS = 30
+
+
+class HostOfflineError(AppError):
+    """Raised when a command targets a host known to be unreachable."""
+
+    def __init__(self, message: str):
+        super().__init__(message, ErrorCode.HOST_OFFLINE, module="connectivity")
+
+
+def _vast_stop(params: Dict[str, Any]) -> None:
+    from .vast_api import get_vast_client
+
+    get_vast_client().stop_instance(int(params["instance_id"]))
+
+
+def _vast_start(params: Dict[str, Any]) -> None:
+    from .vast_api import get_vast_client
+
+    get_vast_client().start_instance(int(params["instance_id"]))
+
+
+def _vast_label(params: Dict[str, Any]) -> None:
+    from .vast_api import get_vast_client
+
+    get_vast_client().label_instance(int(params["instance_id"]), str(params["label"]))
+
+
+# Only idempotent operations may be queued: replaying them twice is harmless.
+QUEUEABLE_OPERATIONS: Dict[str, Callable[[Dict[str, Any]], None]] = {
+    "vast.stop": _vast_stop,
+    "vast.start": _vast_start,
+    "vast.label": _vast_label,
+}
+
+
+def _parse_address(text: str) -> Tuple[str, int]:
+    host, _, port = str(text).rpartition(":")
+    return (host or str(text)), int(port or 53)
+
+
+def probe_addresses() -> List[str]:
+    """Configured `connectivity.probe_addresses`; an empty list turns the network check off."""
+    from ..config import get_config_value
+
+    addresses = get_config_value("connectivity.probe_addresses", DEFAULT_PROBE_ADDRESSES)
+    if isinstance(addresses, str):
+        addresses = [item.strip() for item in addresses.split(",")]
+    return [str(item) for item in addresses or [] if str(item).strip()]
+
+
+def network_available(addresses: Optional[List[str]] = None, *, timeout: float = 2.0) -> bool:
+    """Return whether any configured address accepts a TCP connection."""
+    if addresses is None:
+        addresses = probe_addresses()
+    if not addresses:
+        return True
+    for address in addresses:
+        try:
+            with socket.create_connection(_parse_address(address), timeout=timeout):
+                return True
+        except OSError:
+            continue
+    return False
+
+
+def probe_host(host) -> Tuple[bool, str]:
+    """Probe one host over SSH and return `(online, error)`."""
+    from .ssh import SSHClient
+
+    if host.type == HostType.LOCAL:
+        return True, ""
+    try:
+        result = SSHClient.from_host(host).run("echo 'connected'", timeout=15)
+    except Exception as exc:
+        return False, str(exc)
+    if result.success and "connected" in result.stdout:
+        return True, ""
+    return False, (result.stderr or "").strip() or f"exit code {result.exit_code}"
+
+
+def host_offline_reason(names: Iterable[str]) -> str:
+    """Why recipe steps should not connect to one of `names` right now, else ""."""
+    try:
+        return ConnectivityMonitor().offline_reason(name for name in names if name and name != "local")
+    except Exception:
+        return ""
+
+
+class ConnectivityMonitor:
+    """Persisted host online/offline state plus a queue of idempotent operations."""
+
+    def __init__(self, state_dir: Optional[Path] = None):
+        self.root = Path(state_dir or RUNTIME_STATE_DIR)
+        self.path = self.root / STATE_FILENAME
+        self.store = RuntimeStore(self.root)
+        self._lock = threading.RLock()
+
+    def _load(self) -> Dict[str, Any]:
+        if not self.path.exists():
+            return {"hosts": {}, "queue": []}
+        try:
+            payload = json.loads(self.path.read_text(encoding="utf-8"))
+        except Exception:
+            return {"hosts": {}, "queue": []}
+        payload.setdefault("hosts", {})
+        payload.setdefault("queue", [])
+        return payload
+
+    @contextlib.contextmanager
+    def _locked(self) -> Iterator[Dict[str, Any]]:
+        """Load state under an exclusive file lock and save it on exit."""
+        self.root.mkdir(parents=True, exist_ok=True)
+        with self._lock, open(self.root / f"{STATE_FILENAME}.lock", "w") as lock:
+            fcntl.flock(lock, fcntl.LOCK_EX)
+            payload = self._load()
+            yield payload
+            self.path.write_text(json.dumps(to_jsonable(payload), ensure_ascii=False, indent=2), encoding="utf-8")
+
+    def _emit(self, event: str, **payload: Any) -> None:
+        self.store.append_event(
+            {
+                "run_id": EVENT_RUN_ID,
+                "event": event,
+                "event_name": event,
+                "step_num": None,
+                "payload": payload,
+                "ts": datetime.now().isoformat(),
+            }
+        )
+
+    def host_states(self) -> Dict[str, Dict[str, Any]]:
+        return dict(self._load()["hosts"])
+
+    def record(self, name: str, online: bool, error: str = "") -> bool:
+        """Record one probe result; return True when the state changed."""
+        with self._locked() as payload:
+            previous = payload["hosts"].get(name, {})
+            changed = previous.get("online") is not online
+            payload["hosts"][name] = {
+                "online": online,
+                "error": "" if online else error,
+                "checked_at": datetime.now().isoformat(),
+                "since": datetime.now().isoformat() if changed else previous.get("since", ""),
+            }
+        if changed:
+            self._emit("host_online" if online else "host_offline", host=name, error=error)
+        return changed
+
+    def check(self, name: str, host) -> bool:
+        online, error = probe_host(host)
+        self.record(name, online, error)
+        return online
+
+    def offline_reason(self, names: Iterable[str], *, grace_secs: Optional[float] = None) -> str:
+        """Message for the first of `names` that failed a probe within the grace period, else ""."""
+        if grace_secs is None:
+            from ..config import get_config_value
+
+            grace_secs = float(get_config_value("connectivity.offline_grace_secs", DEFAULT_OFFLINE_GRACE_SECS) or 0)
+        if grace_secs <= 0:
+            return ""
+        states = self.host_states()
+        for name in dict.fromkeys(str(name) for name in names if name):
+            state = states.get(name)
+            if not state or state.get("online"):
+                continue
+            try:
+                checked = datetime.fromisoformat(str(state.get("checked_at", "")))
+            except ValueError:
+                continue
+            if (datetime.now() - checked).total_seconds() <= grace_secs:
+                detail = f" ({state['error']})" if state.get("error") else ""
+                return (
+                    f"Host {name} was offline at {checked.isoformat(timespec='seconds')}{detail}. "
+                    f"Run 'train host check {name}' to re-probe."
+                )
+        return ""
+
+    def ensure_online(self, name: str, host=None, *, grace_secs: Optional[float] = None) -> None:
+        """Raise HostOfflineError instead of letting SSH fail with a confusing error."""
+        if host is not None and host.type == HostType.LOCAL:
+            return
+        reason = self.offline_reason([name], grace_secs=grace_secs)
+        if reason:
+            raise HostOfflineError(reason)
+
+    def _network_available(self, ttl_secs: Optional[float] = None) -> bool:
+        """`network_available()`, reusing a result from the last `connectivity.network_check_ttl_secs`."""
+        if ttl_secs is None:
+            from ..config import get_config_value
+
+            ttl_secs = float(get_config_value("connectivity.network_check_ttl_secs", DEFAULT_NETWORK_CHECK_TTL_SECS) or 0)
+        cached = self._load().get("network") or {}
+        try:
+            age = (datetime.now() - datetime.fromisoformat(str(cached.get("checked_at", "")))).total_seconds()
+        except ValueError:
+            age = None
+        if age is not None and 0 <= age <= ttl_secs:
+            return bool(cached.get("available"))
+        available = network_available()
+        with self._locked() as payload:
+            payload["network"] = {"available": available, "checked_at": datetime.now().isoformat()}
+        return available
+
+    def explain_failure(self, name: str, error: str) -> str:
+        """Record an SSH connection failure and return a user-facing reason."""
+        self.record(name, False, error)
+        if not self._network_available():
+            return f"Network is unavailable; could not reach {name}. Reconnect and retry."
+        return f"Host {name} is unreachable: {error or 'connection failed'}"
+
+    def list_queue(self) -> List[Dict[str, Any]]:
+        return list(self._load()["queue"])
+
+    def enqueue(self, kind: str, params: Dict[str, Any]) -> Dict[str, Any]:
+        if kind not in QUEUEABLE_OPERATIONS:
+            raise ValueError(f"Operation cannot be queued: {kind}")
+        entry = {
+            "id": uuid.uuid4().hex[:8],
+            "kind": kind,
+            "params": dict(params),
+            "queued_at": datetime.now().isoformat(),
+            "attempts": 0,
+            "last_error": "",
+        }
+        with self._locked() as payload:
+            payload["queue"].append(entry)
+        self._emit("operation_queued", kind=kind, params=params, id=entry["id"])
+        return entry
+
+    def clear_queue(self) -> int:
+        with self._locked() as payload:
+            count = len(payload["queue"])
+            payload["queue"] = []
+        return count
+
+    def drain_queue(self) -> Tuple[List[Dict[str, Any]], List[Dict[str, Any]]]:
+        """Replay queued operations; return `(done, failed)` entries."""
+        with self._locked() as payload:
+            done: List[Dict[str, Any]] = []
+            failed: List[Dict[str, Any]] = []
+            for entry in payload["queue"]:
+                handler = QUEUEABLE_OPERATIONS.get(str(entry.get("kind", "")))
+                entry["attempts"] = int(entry.get("attempts", 0) or 0) + 1
+                try:
+                    if handler is None:
+                        raise ValueError(f"Unknown queued operation: {entry.get('kind')}")
+                    handler(dict(entry.get("params", {})))
+                except Exception as exc:
+                    entry["last_error"] = str(exc)
+                    failed.append(entry)
+                    continue
+                done.append(entry)
+            payload["queue"] = failed
+        for entry in done:
+            self._emit("operation_replayed", kind=entry["kind"], params=entry["params"], id=entry["id"])
+        return done, failed
+
+
+__all__ = [
+    "ConnectivityMonitor",
+    "HostOfflineError",
+    "QUEUEABLE_OPERATIONS",
+    "host_offline_reason",
+    "network_available",
+    "probe_addresses",
+    "probe_host",
+]
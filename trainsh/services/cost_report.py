@@ -0,0 +1,119 @@
+"""Cost totals per provider/host, converted at the exchange rate of each accrual day."""
+
+from __future__ import annotations
+
+from collections import defaultdict
+from dataclasses import dataclass
+from datetime import UTC, datetime, timedelta
+from typing import Any, Dict, Iterable, List, Optional, Tuple
+
+from .rate_history import RateHistory
+from .vast_billing import BillingCache, estimate_from_events
+
+
+@dataclass
+class CostRow:
+    """One provider/host total in USD and in the display currency."""
+
+    provider: str
+    host: str
+    usd: float = 0.0
+    amount: float = 0.0
+    estimated: bool = False
+
+
+def _months(start: datetime, end: datetime) -> List[str]:
+    months: List[str] = []
+    cursor = start.replace(day=1)
+    while cursor < end:
+        months.append(cursor.strftime("%Y-%m"))
+        cursor = (cursor.replace(day=28) + timedelta(days=4)).replace(day=1)
+    return months
+
+
+def vast_cost_entries(
+    start: datetime,
+    end: datetime,
+    *,
+    cache: Optional[BillingCache] = None,
+    events: Optional[Iterable[Dict[str, Any]]] = None,
+) -> List[Dict[str, Any]]:
+    """Per-day Vast.ai costs: cached billed charges, else `vast watch` estimates."""
+    cache = cache or BillingCache()
+    if events is None:
+        from .vast_events import VastInstanceWatcher
+
+        events = VastInstanceWatcher().history()
+    events = list(events)
+    labels = {str(e.get("instance_id")): str(e["label"]) for e in events if e.get("label")}
+    first_day, last_day = start.strftime("%Y-%m-%d"), end.strftime("%Y-%m-%d")
+
+    entries: List[Dict[str, Any]] = []
+    billed: set = set()
+    for month in _months(start, end):
+        for charge in cache.charges(month) or []:
+            if not first_day <= charge["day"] < last_day:
+                continue
+            instance_id = str(charge["instance_id"])
+            billed.add((instance_id, charge["day"]))
+            label = charge.get("label") or labels.get(instance_id, "")
+            entries.append({
+                "provider": "vast",
+                "host": label or f"vast:{instance_id}",
+                "day": charge["day"],
+                "amount_usd": float(charge["amount_usd"]),
+                "source": "billed",
+            })
+    for instance_id, days in estimate_from_events(events, start, end).items():
+        for day, amount in days.items():
+            if (instance_id, day) in billed:
+                continue
+            entries.append({
+                "provider": "vast",
+                "host": labels.get(instance_id) or f"vast:{instance_id}",
+                "day": day,
+                "amount_usd": amount,
+                "source": "estimated",
+            })
+    return entries
+
+
+def pricing_report(
+    start: datetime,
+    end: datetime,
+    currency: str,
+    *,
+    entries: Optional[List[Dict[str, Any]]] = None,
+    history: Optional[RateHistory] = None,
+) -> Tuple[List[CostRow], Dict[str, float]]:
+    """Rows per provider/host plus totals per provider, in `currency`.
+
+    Each day's cost is converted with the rates in effect on that day, so a
+    month of spend is not revalued at today's rate.
+    """
+    currency = currency.upper()
+    history = history or RateHistory()
+    if entries is None:
+        entries = vast_cost_entries(start, end)
+
+    day_rates: Dict[str, Any] = {}
+    rows: Dict[Tuple[str, str], CostRow] = {}
+    for entry in entries:
+        day = entry["day"]
+        if day not in day_rates:
+            noon = datetime.strptime(day, "%Y-%m-%d").replace(hour=12, tzinfo=UTC)
+            day_rates[day] = history.rates_at(noon)
+        key = (entry["provider"], entry["host"])
+        row = rows.setdefault(key, CostRow(provider=key[0], host=key[1]))
+        row.usd += entry["amount_usd"]
+        row.amount += day_rates[day].convert(entry["amount_usd"], "USD", currency)
+        row.estimated = row.estimated or entry.get("source") == "estimated"
+
+    totals: Dict[str, float] = defaultdict(float)
+    for row in rows.values():
+        totals[row.provider] += row.amount
+    ordered = sorted(rows.values(), key=lambda row: (row.provider, -row.amount, row.host))
+    return ordered, dict(totals)
+
+
+__all__ = ["CostRow", "pricing_report", "vast_cost_entries"]
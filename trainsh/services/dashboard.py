@@ -0,0 +1,258 @@
+"""Aggregated overview of hosts, executions, transfers, and cloud spend."""
+
+from __future__ import annotations
+
+import json
+from concurrent.futures import ThreadPoolExecutor
+from datetime import datetime
+from pathlib import Path
+from typing import Any, Dict, List, Optional
+
+from ..constants import RUNTIME_STATE_DIR
+from ..core.runtime_store import RuntimeStore, to_jsonable
+from .connectivity import ConnectivityMonitor, probe_host
+from .transfer_progress import PROGRESS_EVENT
+
+DEFAULT_CACHE_TTL_SECS = 30
+DEFAULT_FAILURE_LIMIT = 5
+CACHE_FILENAME = "dashboard.json"
+
+
+def _cache_path(state_dir: Optional[Path] = None) -> Path:
+    return Path(state_dir or RUNTIME_STATE_DIR) / CACHE_FILENAME
+
+
+def _load_cached(path: Path, max_age: float, options: Dict[str, Any]) -> Optional[Dict[str, Any]]:
+    """Cached snapshot when it is fresh and was built with the same `options`."""
+    if max_age <= 0 or not path.exists():
+        return None
+    try:
+        payload = json.loads(path.read_text(encoding="utf-8"))
+        generated = datetime.fromisoformat(str(payload.get("generated_at", "")))
+    except Exception:
+        return None
+    if payload.get("options") != options:
+        return None
+    if (datetime.now() - generated).total_seconds() > max_age:
+        return None
+    payload["cached"] = True
+    return payload
+
+
+def collect_host_status(
+    hosts: Dict[str, Any],
+    *,
+    probe: bool = True,
+    workers: int = 8,
+    monitor: Optional[ConnectivityMonitor] = None,
+) -> List[Dict[str, Any]]:
+    """Return reachability rows for configured hosts."""
+    names = sorted(hosts)
+    results: Dict[str, Optional[bool]] = {name: None for name in names}
+    if probe and names:
+        with ThreadPoolExecutor(max_workers=max(1, min(workers, len(names)))) as pool:
+            for name, (reachable, error) in zip(names, pool.map(lambda item: probe_host(hosts[item]), names)):
+                results[name] = reachable
+                if monitor is not None:
+                    monitor.record(name, reachable, error)
+    return [
+        {
+            "name": name,
+            "type": hosts[name].type.value,
+            "hostname": hosts[name].hostname,
+            "reachable": results[name],
+        }
+        for name in names
+    ]
+
+
+def _is_transfer_task(task: Dict[str, Any]) -> bool:
+    details = task.get("details") if isinstance(task.get("details"), dict) else {}
+    return bool(details.get("source") and details.get("dest"))
+
+
+def _latest_progress(store: RuntimeStore, run_id: str) -> Dict[tuple, Dict[str, Any]]:
+    """Last `transfer:progress` payload per (source, dest) of a run."""
+    latest: Dict[tuple, Dict[str, Any]] = {}
+    for event in store.list_events(run_id):
+        if event.get("event") != PROGRESS_EVENT:
+            continue
+        payload = dict(event.get("payload") or {})
+        key = (str(payload.pop("source", "")), str(payload.pop("dest", "")))
+        latest[key] = payload
+    return latest
+
+
+def collect_queued_transfers(state_dir: Optional[Path] = None) -> List[Dict[str, Any]]:
+    """Queued and running entries of the `train transfer` queue."""
+    from .transfer_queue import ACTIVE_STATUSES, TransferQueue
+
+    try:
+        entries = TransferQueue(state_dir).entries()
+    except Exception:
+        return []
+    return [
+        {
+            "run_id": "",
+            "transfer_id": str(entry.get("id", "")),
+            "status": str(entry.get("status", "")),
+            "source": str(entry.get("source", "")),
+            "dest": str(entry.get("destination", "")),
+            "started_at": str(entry.get("started_at", entry.get("created_at", ""))),
+            "progress": entry.get("progress"),
+        }
+        for entry in entries
+        if entry.get("status") in ACTIVE_STATUSES
+    ]
+
+
+def collect_executions(store: RuntimeStore) -> tuple[List[Dict[str, Any]], List[Dict[str, Any]]]:
+    """Return running executions with their current steps plus their active transfers."""
+    executions: List[Dict[str, Any]] = []
+    transfers: List[Dict[str, Any]] = []
+    for run in store.list_runs():
+        if str(run.get("state", run.get("status", ""))).lower() != "running":
+            continue
+        run_id = str(run.get("run_id", ""))
+        running = [task for task in store.list_tasks(run_id=run_id) if task.get("state") == "running"]
+        executions.append(
+            {
+                "run_id": run_id,
+                "recipe": str(run.get("recipe_name", "")),
+                "started_at": str(run.get("started_at", "")),
+                "current_steps": [str(task.get("task_id", "")) for task in running],
+            }
+        )
+        running_transfers = [task for task in running if _is_transfer_task(task)]
+        progress = _latest_progress(store, run_id) if running_transfers else {}
+        for task in running_transfers:
+            details = task["details"]
+            source, dest = str(details.get("source", "")), str(details.get("dest", ""))
+            transfers.append(
+                {
+                    "run_id": run_id,
+                    "step_id": str(task.get("task_id", "")),
+                    "status": "running",
+                    "source": source,
+                    "dest": dest,
+                    "started_at": str(task.get("start_date", "")),
+                    "progress": progress.get((source, dest)),
+                }
+            )
+    return executions, transfers
+
+
+def collect_recent_failures(store: RuntimeStore, *, limit: int = DEFAULT_FAILURE_LIMIT) -> List[Dict[str, Any]]:
+    """Return the most recent failed runs with their failed step ids."""
+    failures: List[Dict[str, Any]] = []
+    for run in store.list_runs():
+        if run.get("success") is not False:
+            continue
+        run_id = str(run.get("run_id", ""))
+        failed_steps = [
+            str(task.get("task_id", ""))
+            for task in store.list_tasks(run_id=run_id)
+            if task.get("state") in {"failed", "upstream_failed"}
+        ]
+        failures.append(
+            {
+                "run_id": run_id,
+                "recipe": str(run.get("recipe_name", "")),
+                "ended_at": str(run.get("ended_at", "")),
+                "failed_steps": failed_steps,
+            }
+        )
+        if len(failures) >= limit:
+            break
+    return failures
+
+
+def collect_vast_instances() -> Dict[str, Any]:
+    """Return Vast.ai instances with hourly cost, or an error message."""
+    try:
+        from .vast_api import get_vast_client
+
+        instances = get_vast_client().list_instances()
+    except Exception as exc:
+        return {"instances": [], "hourly_total_usd": 0.0, "error": str(exc)}
+
+    rows = [
+        {
+            "id": instance.id,
+            "label": instance.label or "",
+            "status": instance.actual_status or "",
+            "gpu": f"{instance.num_gpus or 0}x {instance.gpu_name or '?'}",
+            "dph_total": float(instance.dph_total or 0.0),
+        }
+        for instance in instances
+    ]
+    hourly = sum(row["dph_total"] for row in rows if row["status"] == "running")
+    return {"instances": rows, "hourly_total_usd": round(hourly, 4), "error": ""}
+
+
+def _permissions() -> Dict[str, Any]:
+    """Viewer-mode state, so frontends can grey out the actions it refuses."""
+    from .viewer_mode import DESTRUCTIVE_ACTIONS, is_viewer_mode
+
+    viewer = is_viewer_mode()
+    return {"viewer_mode": viewer, "disabled_actions": sorted(DESTRUCTIVE_ACTIONS) if viewer else []}
+
+
+def dashboard_snapshot(
+    *,
+    refresh: bool = False,
+    probe_hosts: bool = True,
+    include_vast: bool = True,
+    max_age: Optional[float] = None,
+    state_dir: Optional[Path] = None,
+) -> Dict[str, Any]:
+    """Build (or reuse a cached) aggregated status snapshot."""
+    from ..commands.host import load_hosts
+    from ..config import get_config_value
+
+    if max_age is None:
+        max_age = float(get_config_value("dashboard.cache_ttl_secs", DEFAULT_CACHE_TTL_SECS) or 0)
+    path = _cache_path(state_dir)
+    options = {"probe_hosts": bool(probe_hosts), "include_vast": bool(include_vast)}
+    if not refresh:
+        cached = _load_cached(path, max_age, options)
+        if cached is not None:
+            return {**cached, **_permissions()}
+
+    store = RuntimeStore(state_dir)
+    hosts = collect_host_status(
+        load_hosts(include_auto_vast=False),
+        probe=probe_hosts,
+        monitor=ConnectivityMonitor(state_dir),
+    )
+    executions, transfers = collect_executions(store)
+    transfers.extend(collect_queued_transfers(state_dir))
+    snapshot: Dict[str, Any] = {
+        "generated_at": datetime.now().isoformat(),
+        "cached": False,
+        "options": options,
+        "hosts": hosts,
+        "hosts_reachable": sum(1 for row in hosts if row["reachable"] is True),
+        "hosts_unreachable": sum(1 for row in hosts if row["reachable"] is False),
+        "executions": executions,
+        "transfers": transfers,
+        "vast": collect_vast_instances() if include_vast else {"instances": [], "hourly_total_usd": 0.0, "error": ""},
+        "recent_failures": collect_recent_failures(store),
+        **_permissions(),
+    }
+    try:
+        path.parent.mkdir(parents=True, exist_ok=True)
+        path.write_text(json.dumps(to_jsonable(snapshot), ensure_ascii=False, indent=2), encoding="utf-8")
+    except OSError:
+        pass
+    return snapshot
+
+
+__all__ = [
+    "collect_executions",
+    "collect_host_status",
+    "collect_queued_transfers",
+    "collect_recent_failures",
+    "collect_vast_instances",
+    "dashboard_snapshot",
+]
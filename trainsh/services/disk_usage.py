@@ -0,0 +1,257 @@
+"""ncdu-like disk usage scans of remote hosts, with a local cache and background jobs."""
+
+from __future__ import annotations
+
+import hashlib
+import json
+import posixpath
+import shlex
+from datetime import datetime
+from pathlib import Path
+from typing import Any, Dict, List, Optional
+
+from ..constants import RUNTIME_STATE_DIR
+from ..core.runtime_store import to_jsonable
+from .host_exec import host_exec, resolve_host
+from .tar_stream import shell_path
+
+DEFAULT_CACHE_TTL_SECS = 900
+DEFAULT_SCAN_TIMEOUT_SECS = 60
+CACHE_FILENAME = "disk_usage.json"
+JOB_DIR = "$HOME/.cache/tmux-trainsh/du"
+# Deleting one of these wholesale is never what a disk cleanup meant.
+PROTECTED_PATHS = frozenset(
+    {
+        "/", "/root", "/home", "/usr", "/etc", "/var", "/bin", "/sbin", "/lib", "/lib64",
+        "/opt", "/boot", "/dev", "/proc", "/sys", "/tmp", "/workspace",
+    }
+)
+
+
+def scan_command(path: str, depth: int) -> str:
+    """Foreground scan: `<KiB>\\t<path>` lines, root last."""
+    # du exits non-zero on unreadable entries; the sizes it did print still count.
+    return f"du -x -k -d {int(depth)} {shell_path(path)} 2>/dev/null || true"
+
+
+def job_command(key: str, path: str, depth: int) -> str:
+    """Start, poll, or collect a detached scan; prints `status=...` then any result."""
+    out = f'{JOB_DIR}/{key}.tsv'
+    job = shlex.quote(f'du -x -k -d {int(depth)} "$2" 2>/dev/null > "$1.tmp"; mv "$1.tmp" "$1"; rm -f "$1.pid"')
+    return "\n".join(
+        [
+            f'mkdir -p "{JOB_DIR}"',
+            f'OUT="{out}"',
+            'if [ -f "$OUT" ]; then echo status=done; cat "$OUT"; rm -f "$OUT"; exit 0; fi',
+            'if [ -f "$OUT.pid" ] && kill -0 "$(cat "$OUT.pid")" 2>/dev/null; then echo status=running; exit 0; fi',
+            f'nohup sh -c {job} du-scan "$OUT" {shell_path(path)} >/dev/null 2>&1 </dev/null &',
+            'echo $! > "$OUT.pid"',
+            "echo status=started",
+        ]
+    )
+
+
+def parse_du_output(text: str) -> Optional[Dict[str, Any]]:
+    """Build `{name, path, size, children}` (bytes, children largest first) from du lines."""
+    nodes: Dict[str, Dict[str, Any]] = {}
+    for line in text.splitlines():
+        size, sep, path = line.partition("\t")
+        if not sep or not size.strip().isdigit():
+            continue
+        path = path.rstrip("/") or "/"
+        nodes[path] = {"name": posixpath.basename(path) or path, "path": path, "size": int(size) * 1024, "children": []}
+    if not nodes:
+        return None
+    root = min(nodes, key=len)
+    for path, node in nodes.items():
+        parent = nodes.get(posixpath.dirname(path))
+        if path != root and parent is not None:
+            parent["children"].append(node)
+    for node in nodes.values():
+        node["children"].sort(key=lambda child: child["size"], reverse=True)
+    return nodes[root]
+
+
+def find_node(tree: Dict[str, Any], path: str) -> Optional[Dict[str, Any]]:
+    """Drill into a scanned tree by absolute path."""
+    path = path.rstrip("/") or "/"
+    if tree["path"] == path:
+        return tree
+    for child in tree["children"]:
+        if path == child["path"] or path.startswith(child["path"].rstrip("/") + "/"):
+            return find_node(child, path)
+    return None
+
+
+class DiskUsageCache:
+    """Scan results in `disk_usage.json` under the runtime state dir, keyed by host/path/depth."""
+
+    def __init__(self, state_dir: Optional[Path] = None):
+        self.path = Path(state_dir or RUNTIME_STATE_DIR) / CACHE_FILENAME
+
+    @staticmethod
+    def key(host_id: str, path: str, depth: int) -> str:
+        return f"{host_id}|{path}|{int(depth)}"
+
+    def _read(self) -> Dict[str, Any]:
+        try:
+            data = json.loads(self.path.read_text(encoding="utf-8"))
+        except Exception:
+            return {}
+        return data if isinstance(data, dict) else {}
+
+    def _write(self, data: Dict[str, Any]) -> None:
+        try:
+            self.path.parent.mkdir(parents=True, exist_ok=True)
+            self.path.write_text(json.dumps(to_jsonable(data), ensure_ascii=False), encoding="utf-8")
+        except OSError:
+            pass
+
+    def get(self, key: str, max_age: float) -> Optional[Dict[str, Any]]:
+        entry = self._read().get(key)
+        if not entry or max_age <= 0:
+            return None
+        try:
+            scanned = datetime.fromisoformat(str(entry.get("scanned_at", "")))
+        except ValueError:
+            return None
+        if (datetime.now() - scanned).total_seconds() > max_age:
+            return None
+        return entry
+
+    def put(self, key: str, entry: Dict[str, Any]) -> None:
+        data = self._read()
+        data[key] = entry
+        self._write(data)
+
+    def invalidate(self, host_id: str) -> None:
+        data = self._read()
+        self._write({key: value for key, value in data.items() if not key.startswith(f"{host_id}|")})
+
+
+def _job_key(host_id: str, path: str, depth: int) -> str:
+    return hashlib.sha1(DiskUsageCache.key(host_id, path, depth).encode("utf-8")).hexdigest()[:12]
+
+
+def host_disk_usage(
+    host_id: str,
+    path: str = "~",
+    depth: int = 2,
+    *,
+    refresh: bool = False,
+    background: bool = False,
+    state_dir: Optional[Path] = None,
+) -> Dict[str, Any]:
+    """Scan `path` on a host `depth` levels deep and return its size tree.
+
+    Fresh results come from the local cache (`disk_usage.cache_ttl_secs`). A scan
+    that outlives `disk_usage.scan_timeout_secs`, or any scan with `background`,
+    runs as a detached job on the host; the result then has `status` `running`
+    and a later call collects the tree.
+    """
+    from ..config import get_config_value
+
+    cache = DiskUsageCache(state_dir)
+    key = cache.key(host_id, path, depth)
+    max_age = float(get_config_value("disk_usage.cache_ttl_secs", DEFAULT_CACHE_TTL_SECS) or 0)
+    if not refresh:
+        cached = cache.get(key, max_age)
+        if cached is not None:
+            return {**cached, "cached": True}
+
+    host = resolve_host(host_id)
+    result: Dict[str, Any] = {"host": host_id, "path": path, "depth": int(depth), "cached": False}
+    output = ""
+    if not background:
+        timeout = int(get_config_value("disk_usage.scan_timeout_secs", DEFAULT_SCAN_TIMEOUT_SECS) or 0)
+        scan = host_exec(host, scan_command(path, depth), timeout=timeout or None)
+        if scan.success:
+            output = scan.stdout
+        elif not scan.timed_out:
+            raise RuntimeError(scan.stderr.strip() or f"du failed on {host_id} (exit {scan.exit_code})")
+        else:
+            background = True
+    if background:
+        job = host_exec(host, job_command(_job_key(host_id, path, depth), path, depth), timeout=30)
+        if not job.success:
+            raise RuntimeError(job.stderr.strip() or f"Could not start disk scan on {host_id}")
+        status, _, output = job.stdout.partition("\n")
+        if status.strip() != "status=done":
+            return {**result, "status": "running", "tree": None}
+
+    tree = parse_du_output(output)
+    if tree is None:
+        raise RuntimeError(f"No du output for {path} on {host_id}")
+    entry = {**result, "status": "ready", "scanned_at": datetime.now().isoformat(), "tree": tree}
+    cache.put(key, entry)
+    return entry
+
+
+def check_deletable(path: str) -> Optional[str]:
+    """Reason `path` must not be deleted, or None when it is a plausible cleanup target."""
+    text = str(path or "").strip()
+    if not text or text in ("~", "~/", "$HOME") or text.startswith("-"):
+        return f"refusing to delete {text or 'an empty path'}"
+    if not (text.startswith("/") or text.startswith("~/")):
+        return "path must be absolute or start with ~/"
+    normalized = posixpath.normpath(text)
+    if ".." in normalized.split("/") or any(ch in text for ch in "*?[$`"):
+        return "path must not contain '..', globs, or variables"
+    if normalized in PROTECTED_PATHS or normalized == "~" or posixpath.dirname(normalized) == "/home":
+        return f"refusing to delete protected path {normalized}"
+    return None
+
+
+def host_delete_path(
+    host_id: str,
+    path: str,
+    *,
+    confirm: bool = False,
+    state_dir: Optional[Path] = None,
+) -> Dict[str, Any]:
+    """Recursively delete one path on a host; requires `confirm` and a non-protected path."""
+    if not confirm:
+        raise PermissionError("Deletion requires explicit confirmation")
+    reason = check_deletable(path)
+    if reason:
+        raise ValueError(reason)
+    result = host_exec(host_id, f"rm -rf -- {shell_path(posixpath.normpath(path))}", timeout=600)
+    if not result.success:
+        raise RuntimeError(result.stderr.strip() or f"rm failed on {host_id} (exit {result.exit_code})")
+    DiskUsageCache(state_dir).invalidate(host_id)
+    return {"host": host_id, "path": path, "deleted": True}
+
+
+def render_tree(tree: Dict[str, Any], *, top: int = 15, max_depth: int = 2) -> List[str]:
+    """Indented `size  path` lines, largest first, `top` children per level."""
+    from .transfer_progress import format_bytes
+
+    lines: List[str] = []
+
+    def walk(node: Dict[str, Any], level: int) -> None:
+        label = node["path"] if level == 0 else node["name"] + ("/" if node["children"] else "")
+        lines.append(f"{format_bytes(node['size']):>10}  {'  ' * level}{label}")
+        if level >= max_depth:
+            return
+        for child in node["children"][:top]:
+            walk(child, level + 1)
+        hidden = node["children"][top:]
+        if hidden:
+            size = sum(child["size"] for child in hidden)
+            lines.append(f"{format_bytes(size):>10}  {'  ' * (level + 1)}({len(hidden)} more)")
+
+    walk(tree, 0)
+    return lines
+
+
+__all__ = [
+    "DiskUsageCache",
+    "check_deletable",
+    "find_node",
+    "host_delete_path",
+    "host_disk_usage",
+    "job_command",
+    "parse_du_output",
+    "render_tree",
+    "scan_command",
+]
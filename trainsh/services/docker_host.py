@@ -0,0 +1,177 @@
+"""Docker containers and images on hosts: list, lifecycle, log streaming, and exec."""
+
+from __future__ import annotations
+
+import json
+import re
+import shlex
+import subprocess
+from datetime import datetime
+from pathlib import Path
+from typing import Any, Callable, Dict, List, Optional
+
+from ..constants import LOGS_DIR
+from .host_exec import host_exec, resolve_host
+from .ssh import SSHClient
+from .vllm_service import sanitize_service_name
+
+CONTAINER_RE = re.compile(r"^[A-Za-z0-9][A-Za-z0-9_.-]*$")
+CONTAINER_ACTIONS = ("start", "stop", "restart", "rm")
+
+
+def docker_binary() -> str:
+    """`docker.command` from config, e.g. `sudo -n docker` or `podman`."""
+    from ..config import get_config_value
+
+    return str(get_config_value("docker.command", "docker") or "docker").strip() or "docker"
+
+
+def check_container(container: str) -> str:
+    text = str(container or "").strip()
+    if not CONTAINER_RE.match(text):
+        raise ValueError(f"Invalid container name or id: {container!r}")
+    return text
+
+
+def _run(host_id: str, command: str, *, timeout: int = 60) -> str:
+    result = host_exec(host_id, command, timeout=timeout)
+    if not result.success:
+        raise RuntimeError(result.stderr.strip() or f"docker failed on {host_id} (exit {result.exit_code})")
+    return result.stdout
+
+
+def _json_lines(text: str) -> List[Dict[str, Any]]:
+    rows = []
+    for line in text.splitlines():
+        line = line.strip()
+        if not line.startswith("{"):
+            continue
+        try:
+            rows.append(json.loads(line))
+        except ValueError:
+            continue
+    return rows
+
+
+def list_containers(host_id: str, *, all: bool = True) -> List[Dict[str, Any]]:
+    """Containers on a host (`docker ps --format json`), running first."""
+    flags = " -a" if all else ""
+    rows = _json_lines(_run(host_id, f"{docker_binary()} ps{flags} --no-trunc --format '{{{{json .}}}}'"))
+    containers = [
+        {
+            "id": str(row.get("ID", ""))[:12],
+            "name": row.get("Names", ""),
+            "image": row.get("Image", ""),
+            "state": str(row.get("State", "")).lower(),
+            "status": row.get("Status", ""),
+            "ports": row.get("Ports", ""),
+            "created": row.get("CreatedAt", ""),
+        }
+        for row in rows
+    ]
+    return sorted(containers, key=lambda item: (item["state"] != "running", item["name"]))
+
+
+def list_images(host_id: str) -> List[Dict[str, Any]]:
+    rows = _json_lines(_run(host_id, f"{docker_binary()} images --format '{{{{json .}}}}'"))
+    return [
+        {
+            "repository": row.get("Repository", ""),
+            "tag": row.get("Tag", ""),
+            "id": str(row.get("ID", "")).replace("sha256:", "")[:12],
+            "size": row.get("Size", ""),
+            "created": row.get("CreatedSince", ""),
+        }
+        for row in rows
+    ]
+
+
+def container_action(host_id: str, action: str, container: str, *, force: bool = False) -> str:
+    """Run `docker start|stop|restart|rm` for one container."""
+    if action not in CONTAINER_ACTIONS:
+        raise ValueError(f"Unsupported action: {action}")
+    name = check_container(container)
+    flag = " -f" if force and action == "rm" else ""
+    return _run(host_id, f"{docker_binary()} {action}{flag} {shlex.quote(name)}", timeout=120).strip()
+
+
+def logs_command(container: str, *, follow: bool = True, tail: int = 200, since: str = "") -> str:
+    parts = [docker_binary(), "logs", "--timestamps", f"--tail={int(tail)}" if tail >= 0 else "--tail=all"]
+    if follow:
+        parts.append("-f")
+    if since:
+        parts.append(f"--since={shlex.quote(since)}")
+    parts.append(shlex.quote(check_container(container)))
+    # docker writes the container's stderr to ours; merge so both streams are kept.
+    return " ".join(parts) + " 2>&1"
+
+
+def container_log_path(host_id: str, container: str) -> Path:
+    """Local log file for streamed container output."""
+    return LOGS_DIR / "docker" / sanitize_service_name(host_id) / f"{sanitize_service_name(container)}.log"
+
+
+def stream_logs(
+    host_id: str,
+    container: str,
+    *,
+    follow: bool = True,
+    tail: int = 200,
+    since: str = "",
+    on_line: Optional[Callable[[str], None]] = None,
+    log_path: Optional[Path] = None,
+) -> int:
+    """Stream `docker logs` over SSH, appending every line to the local log file."""
+    client = SSHClient.from_host(resolve_host(host_id))
+    args = client._build_ssh_args(
+        logs_command(container, follow=follow, tail=tail, since=since),
+        target=client.connection_targets[0],
+    )
+    target = log_path or container_log_path(host_id, container)
+    target.parent.mkdir(parents=True, exist_ok=True)
+    process = subprocess.Popen(args, stdout=subprocess.PIPE, stderr=subprocess.STDOUT, text=True, bufsize=1)
+    try:
+        with target.open("a", encoding="utf-8") as handle:
+            handle.write(f"# {datetime.now().isoformat()} docker logs {container} on {host_id}\n")
+            for line in process.stdout or []:
+                handle.write(line)
+                handle.flush()
+                if on_line is not None:
+                    on_line(line.rstrip("\n"))
+    except KeyboardInterrupt:
+        process.terminate()
+    finally:
+        if process.poll() is None:
+            process.terminate()
+        process.wait()
+        if process.stdout is not None:
+            process.stdout.close()
+    return process.returncode
+
+
+def exec_command(container: str, shell: str = "") -> str:
+    """Interactive shell in a container, preferring bash when no shell is given."""
+    name = shlex.quote(check_container(container))
+    if shell:
+        return f"{docker_binary()} exec -it {name} {shell}"
+    return f"{docker_binary()} exec -it {name} sh -c 'command -v bash >/dev/null && exec bash || exec sh'"
+
+
+def exec_shell(host_id: str, container: str, shell: str = "") -> int:
+    """Attach the local terminal to a shell inside the container."""
+    return SSHClient.from_host(resolve_host(host_id)).connect_interactive(exec_command(container, shell))
+
+
+__all__ = [
+    "CONTAINER_ACTIONS",
+    "check_container",
+    "container_action",
+    "container_log_path",
+    "docker_binary",
+    "exec_command",
+    "exec_shell",
+    "list_containers",
+    "list_images",
+    "logs_command",
+    "stream_logs",
+]
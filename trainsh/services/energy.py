@@ -0,0 +1,148 @@
+"""Approximate GPU energy per run from sampled nvidia-smi power draw."""
+
+from __future__ import annotations
+
+import subprocess
+import threading
+import time
+from collections import defaultdict
+from typing import Any, Callable, Dict, Iterable, List, Optional, Tuple
+
+DEFAULT_INTERVAL_SECS = 30.0
+POWER_COMMAND = "nvidia-smi --query-gpu=power.draw --format=csv,noheader,nounits 2>/dev/null"
+
+
+def energy_interval(config: Dict[str, Any]) -> float:
+    """`energy.interval_secs` from config; 0 turns power sampling off."""
+    try:
+        interval = float((config.get("energy") or {}).get("interval_secs", DEFAULT_INTERVAL_SECS))
+    except (TypeError, ValueError, AttributeError):
+        interval = DEFAULT_INTERVAL_SECS
+    return max(0.0, interval)
+
+
+def parse_power_draw(text: str) -> Optional[float]:
+    """Total watts over every GPU line; None when no GPU reported a number ("[N/A]" rows are skipped)."""
+    total = None
+    for line in (text or "").splitlines():
+        try:
+            watts = float(line.strip())
+        except ValueError:
+            continue
+        total = (total or 0.0) + watts
+    return total
+
+
+def read_power_draw(host: str, build_ssh_args=None, timeout: int = 15) -> Optional[float]:
+    """Current GPU power draw on `host` ("local" or an SSH spec) in watts."""
+    if host == "local" or build_ssh_args is None:
+        args = ["sh", "-c", POWER_COMMAND]
+    else:
+        args = build_ssh_args(host, command=POWER_COMMAND, tty=False)
+    try:
+        result = subprocess.run(args, capture_output=True, text=True, timeout=timeout)
+    except (OSError, subprocess.TimeoutExpired):
+        return None
+    return parse_power_draw(result.stdout)
+
+
+def integrate_kwh(samples: List[Tuple[float, float]]) -> float:
+    """Trapezoid integral of (seconds, watts) samples, in kWh."""
+    joules = 0.0
+    for (t0, w0), (t1, w1) in zip(samples, samples[1:]):
+        joules += max(0.0, t1 - t0) * (w0 + w1) / 2
+    return joules / 3_600_000
+
+
+class EnergyMeter:
+    """Sample power draw on the hosts a run is using and sum it into kWh."""
+
+    def __init__(
+        self,
+        hosts: Callable[[], Iterable[str]],
+        *,
+        interval: float = DEFAULT_INTERVAL_SECS,
+        build_ssh_args=None,
+        reader: Callable[..., Optional[float]] = read_power_draw,
+        clock: Callable[[], float] = time.monotonic,
+    ):
+        self.hosts = hosts
+        self.interval = max(1.0, float(interval))
+        self.build_ssh_args = build_ssh_args
+        self.reader = reader
+        self.clock = clock
+        self.samples: Dict[str, List[Tuple[float, float]]] = defaultdict(list)
+        self._stop = threading.Event()
+        self._thread: Optional[threading.Thread] = None
+
+    def sample_once(self) -> None:
+        """Read every current host once; hosts without an NVIDIA GPU are skipped."""
+        for host in sorted({str(host) for host in self.hosts() if host}):
+            watts = self.reader(host, self.build_ssh_args)
+            if watts is not None:
+                self.samples[host].append((self.clock(), watts))
+
+    def _loop(self) -> None:
+        while not self._stop.wait(self.interval):
+            try:
+                self.sample_once()
+            except Exception:
+                pass
+
+    def start(self) -> "EnergyMeter":
+        try:
+            self.sample_once()
+        except Exception:
+            pass
+        self._thread = threading.Thread(target=self._loop, name="energy-meter", daemon=True)
+        self._thread.start()
+        return self
+
+    def stop(self, timeout: float = 5.0) -> Dict[str, Any]:
+        """Stop sampling and return `summary()`; hosts may already be gone, so no closing sample."""
+        self._stop.set()
+        if self._thread is not None:
+            self._thread.join(timeout)
+            self._thread = None
+        return self.summary()
+
+    def summary(self) -> Dict[str, Any]:
+        """`{"kwh", "hosts": {host: {"kwh", "avg_w", "peak_w", "samples"}}}`; empty when nothing was sampled."""
+        hosts: Dict[str, Dict[str, Any]] = {}
+        for host, samples in self.samples.items():
+            watts = [w for _, w in samples]
+            hosts[host] = {
+                "kwh": round(integrate_kwh(samples), 6),
+                "avg_w": round(sum(watts) / len(watts), 1),
+                "peak_w": round(max(watts), 1),
+                "samples": len(samples),
+            }
+        if not hosts:
+            return {}
+        return {"kwh": round(sum(item["kwh"] for item in hosts.values()), 6), "hosts": hosts}
+
+
+def monthly_energy(runs: Iterable[Dict[str, Any]]) -> Dict[str, Dict[str, float]]:
+    """kWh and run count per `YYYY-MM` of run start, from the `energy` saved with each finished run."""
+    months: Dict[str, Dict[str, float]] = {}
+    for run in runs:
+        energy = (run.get("metadata") or {}).get("energy") or {}
+        if not energy:
+            continue
+        month = str(run.get("started_at") or run.get("ended_at") or "")[:7] or "unknown"
+        row = months.setdefault(month, {"kwh": 0.0, "runs": 0})
+        row["kwh"] = round(row["kwh"] + float(energy.get("kwh") or 0.0), 6)
+        row["runs"] += 1
+    return dict(sorted(months.items()))
+
+
+__all__ = [
+    "DEFAULT_INTERVAL_SECS",
+    "EnergyMeter",
+    "POWER_COMMAND",
+    "energy_interval",
+    "integrate_kwh",
+    "monthly_energy",
+    "parse_power_draw",
+    "read_power_draw",
+]
@@ -0,0 +1,231 @@
+"""Per-step timing of one recipe run, shaped for a Gantt-style timeline.
+
+Built from the runtime events the executor already records: `step_start`
+(with the queue time before it), `step_retry` / `step_end` closing each
+attempt, `step_wait` for interactive prompts, and the `execution_start` /
+`execution_end` pairs of a run that was interrupted and resumed. Times in
+`breakdown` answer "where did the hour go": running, waiting on conditions,
+queued for a worker or pool slot, sleeping before a retry, blocked on input,
+and paused between an interruption and `train recipe resume`.
+"""
+
+from __future__ import annotations
+
+from datetime import datetime, timedelta
+from pathlib import Path
+from typing import Any, Dict, List, Optional
+
+from ..core.runtime_store import RuntimeStore
+
+SEGMENT_KINDS = ("running", "waiting", "queued", "retry_backoff", "input", "paused")
+RUN_LANE = ""
+
+
+def _parse_ts(value: Any) -> Optional[datetime]:
+    try:
+        return datetime.fromisoformat(str(value))
+    except (TypeError, ValueError):
+        return None
+
+
+def _ms(start: datetime, end: datetime) -> int:
+    return max(0, int((end - start).total_seconds() * 1000))
+
+
+def _payload(record: Dict[str, Any]) -> Dict[str, Any]:
+    payload = record.get("payload")
+    return payload if isinstance(payload, dict) else {}
+
+
+def _step_key(record: Dict[str, Any], payload: Dict[str, Any]) -> str:
+    step_id = str(payload.get("step_id", "") or "").strip()
+    if step_id:
+        return step_id
+    step_num = record.get("step_num")
+    return f"step_{int(step_num):04d}" if step_num is not None else RUN_LANE
+
+
+def execution_timeline(
+    run_id: str,
+    *,
+    root: Optional[Path] = None,
+    now: Optional[datetime] = None,
+) -> Optional[Dict[str, Any]]:
+    """Attempts, waits and Gantt segments for one run; None if the run is unknown."""
+    store = RuntimeStore(root)
+    run = store.get_run(run_id)
+    events = store.list_events(run_id)
+    if run is None and not events:
+        return None
+    now = now or datetime.now()
+
+    steps: Dict[str, Dict[str, Any]] = {}
+    sessions: List[Dict[str, Any]] = []
+    segments: List[Dict[str, Any]] = []
+
+    def step_entry(key: str, record: Dict[str, Any], payload: Dict[str, Any]) -> Dict[str, Any]:
+        entry = steps.get(key)
+        if entry is None:
+            entry = steps[key] = {
+                "step_id": key,
+                "step_num": record.get("step_num"),
+                "raw": "",
+                "step_type": "",
+                "state": "",
+                "attempts": [],
+                "waits": [],
+            }
+        if payload.get("raw"):
+            entry["raw"] = str(payload["raw"])
+        if payload.get("step_type"):
+            entry["step_type"] = str(payload["step_type"])
+        return entry
+
+    def open_attempt(entry: Dict[str, Any]) -> Optional[Dict[str, Any]]:
+        attempts = entry["attempts"]
+        if attempts and not attempts[-1]["finished_at"]:
+            return attempts[-1]
+        return None
+
+    def close_attempt(attempt: Dict[str, Any], at: datetime, state: str) -> None:
+        attempt["finished_at"] = at.isoformat()
+        attempt["duration_ms"] = _ms(datetime.fromisoformat(attempt["started_at"]), at)
+        attempt["state"] = state
+
+    def close_all(at: datetime, state: str) -> None:
+        for entry in steps.values():
+            attempt = open_attempt(entry)
+            if attempt is not None:
+                close_attempt(attempt, at, state)
+                entry["state"] = state
+
+    seen = set()
+    for record in events:
+        event = str(record.get("event") or record.get("event_name") or "")
+        ts = _parse_ts(record.get("ts"))
+        if ts is None:
+            continue
+        payload = _payload(record)
+        # A run with more than one jsonl sink writes each lifecycle event twice.
+        identity = (event, record.get("ts"), record.get("step_num"), payload.get("step_id"), payload.get("try_number"))
+        if identity in seen:
+            continue
+        seen.add(identity)
+
+        if event == "execution_start":
+            close_all(ts, "interrupted")
+            if sessions and not sessions[-1]["ended_at"]:
+                sessions[-1]["ended_at"] = ts.isoformat()
+            sessions.append({"started_at": ts.isoformat(), "ended_at": "", "success": None})
+        elif event == "execution_end":
+            close_all(ts, "interrupted")
+            if sessions and not sessions[-1]["ended_at"]:
+                sessions[-1]["ended_at"] = ts.isoformat()
+                sessions[-1]["success"] = payload.get("success")
+        elif event == "step_start":
+            key = _step_key(record, payload)
+            entry = step_entry(key, record, payload)
+            previous = open_attempt(entry)
+            if previous is not None:
+                close_attempt(previous, ts, "interrupted")
+            entry["attempts"].append(
+                {
+                    "try_number": int(payload.get("try_number", 1) or 1),
+                    "started_at": ts.isoformat(),
+                    "finished_at": "",
+                    "duration_ms": 0,
+                    "state": "running",
+                    "queued_ms": int(payload.get("queued_ms", 0) or 0),
+                    "deferred": bool(payload.get("deferred", False)),
+                    "retry_delay_ms": 0,
+                }
+            )
+            entry["state"] = "running"
+        elif event in ("step_end", "step_retry"):
+            key = _step_key(record, payload)
+            entry = step_entry(key, record, payload)
+            state = str(payload.get("state", "") or "").lower() or ("success" if payload.get("success") else "failed")
+            attempt = open_attempt(entry)
+            if attempt is not None:
+                close_attempt(attempt, ts, state)
+                if event == "step_retry":
+                    attempt["retry_delay_ms"] = int(payload.get("retry_delay_ms", 0) or 0)
+            entry["state"] = state
+        elif event == "step_wait":
+            key = _step_key(record, payload)
+            entry = step_entry(key, record, payload) if key else None
+            started = _parse_ts(payload.get("started_at")) or ts
+            finished = _parse_ts(payload.get("finished_at")) or ts
+            wait = {
+                "kind": str(payload.get("kind", "") or "input"),
+                "reason": str(payload.get("reason", "") or ""),
+                "started_at": started.isoformat(),
+                "finished_at": finished.isoformat(),
+                "wait_ms": _ms(started, finished),
+            }
+            if entry is not None:
+                entry["waits"].append(wait)
+            segments.append({"step_id": key, "kind": wait["kind"], "start": wait["started_at"], "end": wait["finished_at"], "ms": wait["wait_ms"]})
+
+    running = bool(sessions) and not sessions[-1]["ended_at"] and (run or {}).get("success") is None
+    if not running:
+        close_all((_parse_ts(sessions[-1]["ended_at"]) if sessions else None) or now, "interrupted")
+
+    breakdown = {kind: 0 for kind in SEGMENT_KINDS}
+    for key, entry in steps.items():
+        waiting_step = entry["step_type"] == "wait"
+        attempts = entry["attempts"]
+        for index, attempt in enumerate(attempts):
+            start = datetime.fromisoformat(attempt["started_at"])
+            end = _parse_ts(attempt["finished_at"]) or now
+            base = {"step_id": key, "step_num": entry["step_num"], "try_number": attempt["try_number"]}
+            if attempt["queued_ms"]:
+                queued_from = start - timedelta(milliseconds=attempt["queued_ms"])
+                segments.append({**base, "kind": "queued", "start": queued_from.isoformat(), "end": start.isoformat(), "ms": attempt["queued_ms"]})
+                breakdown["queued"] += attempt["queued_ms"]
+            kind = "waiting" if attempt["deferred"] or waiting_step else "running"
+            duration = _ms(start, end)
+            attempt["duration_ms"] = duration
+            segments.append({**base, "kind": kind, "start": start.isoformat(), "end": end.isoformat(), "ms": duration})
+            breakdown[kind] += duration
+            if attempt["retry_delay_ms"]:
+                backoff_end = end + timedelta(milliseconds=attempt["retry_delay_ms"])
+                if index + 1 < len(attempts):
+                    next_start = datetime.fromisoformat(attempts[index + 1]["started_at"])
+                    next_start -= timedelta(milliseconds=attempts[index + 1]["queued_ms"])
+                    backoff_end = min(backoff_end, max(end, next_start))
+                backoff = _ms(end, backoff_end)
+                segments.append({**base, "kind": "retry_backoff", "start": end.isoformat(), "end": backoff_end.isoformat(), "ms": backoff})
+                breakdown["retry_backoff"] += backoff
+        entry["total_ms"] = sum(attempt["duration_ms"] for attempt in attempts)
+    # Prompts happen inside a running attempt; count that time as input, not running.
+    breakdown["input"] = sum(segment["ms"] for segment in segments if segment["kind"] == "input")
+    breakdown["running"] = max(0, breakdown["running"] - breakdown["input"])
+
+    for previous, current in zip(sessions, sessions[1:]):
+        ended = _parse_ts(previous["ended_at"])
+        started = _parse_ts(current["started_at"])
+        if ended and started and started > ended:
+            gap = _ms(ended, started)
+            segments.append({"step_id": RUN_LANE, "kind": "paused", "start": ended.isoformat(), "end": started.isoformat(), "ms": gap})
+            breakdown["paused"] += gap
+
+    first = _parse_ts(sessions[0]["started_at"]) if sessions else _parse_ts((run or {}).get("started_at"))
+    last = now if running else _parse_ts(sessions[-1]["ended_at"] if sessions else (run or {}).get("ended_at"))
+    segments.sort(key=lambda item: (item["start"], item["step_id"]))
+    ordered = sorted(steps.values(), key=lambda item: (item["step_num"] is None, item["step_num"] or 0, item["step_id"]))
+    return {
+        "run_id": str(run_id),
+        "recipe_name": str((run or {}).get("recipe_name", "")),
+        "status": "running" if running else str((run or {}).get("status", "") or ""),
+        "started_at": first.isoformat() if first else "",
+        "ended_at": "" if running or last is None else last.isoformat(),
+        "wall_ms": _ms(first, last) if first and last else 0,
+        "sessions": sessions,
+        "steps": [entry for entry in ordered if entry["step_id"] != RUN_LANE],
+        "segments": segments,
+        "breakdown": breakdown,
+    }
+
+
+__all__ = ["SEGMENT_KINDS", "execution_timeline"]
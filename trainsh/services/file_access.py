@@ -0,0 +1,190 @@
+"""Preview and quick-edit small files on hosts and storage backends."""
+
+from __future__ import annotations
+
+import base64
+import binascii
+import os
+import shlex
+import subprocess
+import tempfile
+from dataclasses import dataclass
+from typing import Union
+
+from ..core.errors import AppError, ErrorCode, classify_message
+from ..core.models import Host, Storage, StorageType
+from .host_exec import ExecResult, host_exec
+from .tar_stream import shell_path
+
+DEFAULT_PREVIEW_BYTES = 64 * 1024
+# Quick edits rewrite the whole file; anything bigger belongs in a real editor session.
+MAX_EDIT_BYTES = 1024 * 1024
+
+FileTarget = Union[Host, Storage]
+
+
+@dataclass
+class FileHead:
+    """First bytes of a file; `truncated` when the file continues past them."""
+
+    path: str
+    data: bytes
+    truncated: bool
+
+    @property
+    def binary(self) -> bool:
+        return b"\0" in self.data
+
+    @property
+    def text(self) -> str:
+        return self.data.decode("utf-8", errors="replace")
+
+
+def _host_read_script(path: str, limit: int) -> str:
+    target = shell_path(path)
+    message = shlex.quote(f"not a regular file: {path}")
+    return (
+        f"[ -f {target} ] || {{ echo {message} >&2; exit 2; }}\n"
+        f"head -c {limit} {target} | base64\n"
+    )
+
+
+def _host_write_script(path: str) -> str:
+    """Decode base64 stdin into a sibling temp file (mode/owner kept via cp -p), then rename it over the target."""
+    return "\n".join(
+        [
+            "set -e",
+            f"target={shell_path(path)}",
+            'tmp="$target.trainsh-edit.$$"',
+            'trap \'rm -f "$tmp"\' EXIT',
+            'if [ -e "$target" ]; then cp -p "$target" "$tmp"; fi',
+            'base64 -d > "$tmp"',
+            'mv -f "$tmp" "$target"',
+        ]
+    )
+
+
+def _storage_local_path(storage: Storage, path: str) -> str:
+    base = str(storage.config.get("path", "")).strip()
+    relative = str(path or "").strip().lstrip("/")
+    return os.path.join(os.path.expanduser(base), relative) if base else os.path.expanduser(relative)
+
+
+def _host_error(result: ExecResult, action: str) -> AppError:
+    """SSH failures keep their code; a failing remote command is classified by its stderr."""
+    error = result.error
+    message = result.stderr.strip() or f"{action} (exit {result.exit_code})"
+    code = error.code if error and error.code != ErrorCode.UNKNOWN else classify_message(message)
+    return AppError(message, code, provider="ssh", module="file_access", details=error.details if error else {})
+
+
+def storage_cli(storage: Storage, args: list, *, stdin: bytes = b"") -> bytes:
+    """Run rclone/hf for a storage and return stdout bytes; raises with stderr on failure."""
+    env = os.environ.copy()
+    if storage.type == StorageType.HF:
+        from .hf_storage import build_hf_env
+
+        env.update(build_hf_env(storage))
+        command = ["hf", "buckets", *args]
+    else:
+        from .transfer_support import build_rclone_env
+
+        env.update(build_rclone_env(storage))
+        command = ["rclone", *args]
+    context = {"provider": storage.type.value, "module": "file_access"}
+    try:
+        result = subprocess.run(command, input=stdin or None, capture_output=True, env=env, timeout=120)
+    except FileNotFoundError:
+        raise AppError(f"{command[0]} is required for {storage.name}", ErrorCode.TOOL_MISSING, **context) from None
+    except subprocess.TimeoutExpired:
+        raise AppError(f"{command[0]} timed out on {storage.name}", ErrorCode.TIMEOUT, **context) from None
+    if result.returncode != 0:
+        message = result.stderr.decode("utf-8", errors="replace").strip() or f"{command[0]} failed"
+        raise AppError(message, classify_message(message), **context)
+    return result.stdout
+
+
+def storage_uri(storage: Storage, path: str) -> str:
+    """`remote:bucket/path` for rclone backends, `hf://buckets/...` for HF."""
+    if storage.type == StorageType.HF:
+        from .hf_storage import resolve_hf_bucket_uri
+
+        return resolve_hf_bucket_uri(storage, path)
+    from .transfer_support import get_rclone_remote_name, resolve_storage_remote_path
+
+    return f"{get_rclone_remote_name(storage)}:{resolve_storage_remote_path(storage, path)}"
+
+
+def file_read_head(target: FileTarget, path: str, nbytes: int = DEFAULT_PREVIEW_BYTES) -> FileHead:
+    """Read up to `nbytes` from the start of a file on a host or storage backend."""
+    nbytes = max(1, int(nbytes))
+    # One extra byte tells whether the file continues.
+    limit = nbytes + 1
+    if isinstance(target, Host):
+        result = host_exec(target, f"sh -c {shlex.quote(_host_read_script(path, limit))}", timeout=60)
+        if not result.success:
+            raise _host_error(result, f"Could not read {path}")
+        try:
+            data = base64.b64decode("".join(result.stdout.split()))
+        except (binascii.Error, ValueError) as exc:
+            raise RuntimeError(f"Unexpected preview output for {path}: {exc}") from None
+    elif target.type == StorageType.LOCAL:
+        local = _storage_local_path(target, path)
+        if not os.path.isfile(local):
+            raise AppError(f"Storage file not found: {local}", ErrorCode.STORAGE_NOT_FOUND, provider="local", module="file_access")
+        with open(local, "rb") as handle:
+            data = handle.read(limit)
+    elif target.type == StorageType.HF:
+        data = storage_cli(target, ["cp", storage_uri(target, path), "-"])[:limit]
+    else:
+        data = storage_cli(target, ["cat", "--count", str(limit), storage_uri(target, path)])
+    return FileHead(path=path, data=data[:nbytes], truncated=len(data) > nbytes)
+
+
+def file_write(target: FileTarget, path: str, content: Union[str, bytes]) -> int:
+    """Replace a file's content on a host or storage backend; returns bytes written."""
+    data = content.encode("utf-8") if isinstance(content, str) else bytes(content)
+    if len(data) > MAX_EDIT_BYTES:
+        raise ValueError(f"Refusing to write {len(data)} bytes; quick edits are limited to {MAX_EDIT_BYTES}")
+    if isinstance(target, Host):
+        # stdin is text-based, so the bytes travel as base64.
+        result = host_exec(
+            target,
+            f"sh -c {shlex.quote(_host_write_script(path))}",
+            stdin=base64.b64encode(data).decode("ascii") + "\n",
+            timeout=60,
+        )
+        if not result.success:
+            raise _host_error(result, f"Could not write {path}")
+    elif target.type == StorageType.LOCAL:
+        local = _storage_local_path(target, path)
+        directory = os.path.dirname(local) or "."
+        os.makedirs(directory, exist_ok=True)
+        with tempfile.NamedTemporaryFile(dir=directory, prefix=".trainsh-edit.", delete=False) as handle:
+            handle.write(data)
+            temp_path = handle.name
+        if os.path.exists(local):
+            os.chmod(temp_path, os.stat(local).st_mode & 0o7777)
+        os.replace(temp_path, local)
+    elif target.type == StorageType.HF:
+        with tempfile.NamedTemporaryFile(delete=False) as handle:
+            handle.write(data)
+            temp_path = handle.name
+        try:
+            storage_cli(target, ["cp", temp_path, storage_uri(target, path)])
+        finally:
+            os.unlink(temp_path)
+    else:
+        storage_cli(target, ["rcat", storage_uri(target, path)], stdin=data)
+    return len(data)
+
+
+__all__ = [
+    "DEFAULT_PREVIEW_BYTES",
+    "FileHead",
+    "MAX_EDIT_BYTES",
+    "file_read_head",
+    "file_write",
+    "storage_cli",
+    "storage_uri",
+]
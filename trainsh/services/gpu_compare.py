@@ -0,0 +1,133 @@
+"""Compare GPU hourly prices across Vast.ai offers and user-maintained entries."""
+
+from __future__ import annotations
+
+from dataclasses import dataclass
+from typing import Any, Dict, Iterable, List, Optional
+
+from .pricing import ExchangeRates
+
+STATIC_FIELDS = ("provider", "gpu_name", "num_gpus", "gpu_ram_gb", "price_per_hour", "currency", "capex", "lifetime_hours", "note")
+
+
+@dataclass
+class ComparedOffer:
+    """One priced option normalized to USD per hour for the whole machine."""
+
+    provider: str
+    gpu_name: str
+    num_gpus: int
+    gpu_ram_gb: Optional[float]
+    usd_per_hour: float
+    source: str  # "vast" or "static"
+    ref: str = ""
+
+    @property
+    def usd_per_gpu_hour(self) -> float:
+        return self.usd_per_hour / max(self.num_gpus, 1)
+
+    def job_cost(self, hours: float) -> float:
+        return self.usd_per_hour * hours
+
+
+def _gpu_key(name: Any) -> str:
+    return "".join(ch for ch in str(name or "").upper() if ch.isalnum())
+
+
+def gpu_matches(name: Any, wanted: Optional[str]) -> bool:
+    """Loose GPU model match: `4090` matches `RTX_4090`, `a100` matches `A100 SXM4`."""
+    return not wanted or _gpu_key(wanted) in _gpu_key(name)
+
+
+def static_usd_per_hour(entry: Dict[str, Any], rates: ExchangeRates) -> float:
+    """Hourly USD for a static entry; on-prem hardware adds capex spread over its lifetime."""
+    currency = str(entry.get("currency") or "USD").upper()
+    hourly = float(entry.get("price_per_hour") or 0.0)
+    capex = float(entry.get("capex") or 0.0)
+    lifetime = float(entry.get("lifetime_hours") or 0.0)
+    if capex and lifetime > 0:
+        hourly += capex / lifetime
+    return rates.convert(hourly, currency, "USD")
+
+
+def normalize_static_entry(entry: Dict[str, Any]) -> Dict[str, Any]:
+    """Validate one user entry and drop unknown or empty fields."""
+    provider = str(entry.get("provider") or "").strip()
+    gpu_name = str(entry.get("gpu_name") or "").strip()
+    if not provider or not gpu_name:
+        raise ValueError("A GPU offer needs a provider and a GPU name.")
+    if not entry.get("price_per_hour") and not entry.get("capex"):
+        raise ValueError("A GPU offer needs --price or --capex.")
+    if entry.get("capex") and not entry.get("lifetime_hours"):
+        raise ValueError("--capex needs --lifetime-hours to amortize over.")
+    clean = {key: entry[key] for key in STATIC_FIELDS if entry.get(key) not in (None, "")}
+    clean["num_gpus"] = int(clean.get("num_gpus") or 1)
+    if "currency" in clean:
+        clean["currency"] = str(clean["currency"]).upper()
+    return clean
+
+
+def _from_vast(offer: Any) -> ComparedOffer:
+    ram_mb = getattr(offer, "gpu_ram", None)
+    return ComparedOffer(
+        provider="vast",
+        gpu_name=str(getattr(offer, "gpu_name", "") or ""),
+        num_gpus=int(getattr(offer, "num_gpus", 1) or 1),
+        gpu_ram_gb=round(ram_mb / 1024, 1) if ram_mb else None,
+        usd_per_hour=float(getattr(offer, "dph_total", 0.0) or 0.0),
+        source="vast",
+        ref=f"offer {getattr(offer, 'id', '')}",
+    )
+
+
+def _from_static(entry: Dict[str, Any], rates: ExchangeRates) -> ComparedOffer:
+    ram = entry.get("gpu_ram_gb")
+    ref = str(entry.get("note") or "")
+    if entry.get("capex"):
+        ref = (ref + " " if ref else "") + "(amortized)"
+    return ComparedOffer(
+        provider=str(entry.get("provider")),
+        gpu_name=str(entry.get("gpu_name")),
+        num_gpus=int(entry.get("num_gpus") or 1),
+        gpu_ram_gb=float(ram) if ram not in (None, "") else None,
+        usd_per_hour=static_usd_per_hour(entry, rates),
+        source="static",
+        ref=ref,
+    )
+
+
+def compare_offers(
+    gpu: Optional[str],
+    *,
+    vram_gb: Optional[float] = None,
+    count: Optional[int] = None,
+    vast_offers: Iterable[Any] = (),
+    static_entries: Iterable[Dict[str, Any]] = (),
+    rates: Optional[ExchangeRates] = None,
+) -> List[ComparedOffer]:
+    """All options matching the spec, cheapest per hour first.
+
+    `count` is matched exactly so totals compare like-for-like machines; an
+    unknown VRAM on a static entry is not held against it.
+    """
+    rates = rates or ExchangeRates()
+    candidates = [_from_vast(offer) for offer in vast_offers]
+    candidates += [_from_static(entry, rates) for entry in static_entries]
+    matched = [
+        item
+        for item in candidates
+        if gpu_matches(item.gpu_name, gpu)
+        and (not count or item.num_gpus == count)
+        and (not vram_gb or item.gpu_ram_gb is None or item.gpu_ram_gb >= vram_gb)
+        and item.usd_per_hour > 0
+    ]
+    return sorted(matched, key=lambda item: (item.usd_per_hour, item.source, item.provider))
+
+
+__all__ = [
+    "ComparedOffer",
+    "compare_offers",
+    "gpu_matches",
+    "normalize_static_entry",
+    "static_usd_per_hour",
+]
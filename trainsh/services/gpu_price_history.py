@@ -0,0 +1,124 @@
+"""Observed Vast.ai offer prices per GPU model, sampled whenever offers are searched."""
+
+from __future__ import annotations
+
+import json
+from collections import defaultdict
+from datetime import datetime, timedelta
+from pathlib import Path
+from statistics import median
+from typing import Any, Dict, Iterable, List, Optional
+
+from ..constants import STATE_DIR
+from .gpu_compare import gpu_matches
+
+HISTORY_FILENAME = "gpu_prices.jsonl"
+# Repeated searches closer together than this do not add another sample.
+MIN_SAMPLE_INTERVAL = timedelta(minutes=10)
+
+
+def _per_gpu_price(offer: Any) -> Optional[float]:
+    try:
+        price = float(getattr(offer, "dph_total", None))
+        count = int(getattr(offer, "num_gpus", None) or 1)
+    except (TypeError, ValueError):
+        return None
+    return price / count if price > 0 and count > 0 else None
+
+
+class GpuPriceHistory:
+    """JSONL log of {ts, gpu_name, min, median, count} per GPU model and poll."""
+
+    def __init__(self, path: Optional[Path] = None):
+        self.path = Path(path or STATE_DIR / HISTORY_FILENAME)
+
+    def samples(self, gpu_name: Optional[str] = None, *, since: Optional[datetime] = None) -> List[Dict[str, Any]]:
+        if not self.path.exists():
+            return []
+        items = []
+        for line in self.path.read_text(encoding="utf-8").splitlines():
+            try:
+                item = json.loads(line)
+                item["at"] = datetime.fromisoformat(item["ts"])
+            except (json.JSONDecodeError, KeyError, TypeError, ValueError):
+                continue
+            if since is not None and item["at"] < since:
+                continue
+            if gpu_matches(item.get("gpu_name"), gpu_name):
+                items.append(item)
+        return items
+
+    def record(self, offers: Iterable[Any], *, now: Optional[datetime] = None) -> int:
+        """Add one sample per GPU model seen in `offers`; returns how many were written."""
+        now = now or datetime.now()
+        prices: Dict[str, List[float]] = defaultdict(list)
+        for offer in offers:
+            name = getattr(offer, "gpu_name", None)
+            price = _per_gpu_price(offer)
+            if isinstance(name, str) and name.strip() and price is not None:
+                prices[name.strip()].append(price)
+        if not prices:
+            return 0
+
+        latest: Dict[str, datetime] = {}
+        for item in self.samples(since=now - MIN_SAMPLE_INTERVAL):
+            latest[item["gpu_name"]] = item["at"]
+        lines = [
+            json.dumps({
+                "ts": now.isoformat(timespec="seconds"),
+                "gpu_name": name,
+                "min": round(min(values), 4),
+                "median": round(median(values), 4),
+                "count": len(values),
+            })
+            for name, values in sorted(prices.items())
+            if name not in latest
+        ]
+        if lines:
+            self.path.parent.mkdir(parents=True, exist_ok=True)
+            with open(self.path, "a", encoding="utf-8") as f:
+                f.write("\n".join(lines) + "\n")
+        return len(lines)
+
+
+def gpu_price_history(
+    gpu_name: str,
+    days: int = 30,
+    *,
+    history: Optional[GpuPriceHistory] = None,
+    now: Optional[datetime] = None,
+) -> List[Dict[str, Any]]:
+    """Daily per-GPU $/hr for matching models: lowest minimum and median of medians."""
+    now = now or datetime.now()
+    history = history or GpuPriceHistory()
+    buckets: Dict[tuple, List[Dict[str, Any]]] = defaultdict(list)
+    for item in history.samples(gpu_name, since=now - timedelta(days=days)):
+        buckets[(item["gpu_name"], item["at"].strftime("%Y-%m-%d"))].append(item)
+    return [
+        {
+            "gpu_name": name,
+            "day": day,
+            "min": min(item["min"] for item in items),
+            "median": round(median(item["median"] for item in items), 4),
+            "samples": len(items),
+        }
+        for (name, day), items in sorted(buckets.items())
+    ]
+
+
+def price_trend(rows: List[Dict[str, Any]]) -> Optional[float]:
+    """Percent change of the daily median from the first to the last day."""
+    if len(rows) < 2 or not rows[0]["median"]:
+        return None
+    return 100.0 * (rows[-1]["median"] - rows[0]["median"]) / rows[0]["median"]
+
+
+def record_offer_prices(offers: Iterable[Any]) -> None:
+    """Best-effort hook for commands that search unfiltered-by-price offers."""
+    try:
+        GpuPriceHistory().record(offers)
+    except OSError:
+        pass
+
+
+__all__ = ["GpuPriceHistory", "gpu_price_history", "price_trend", "record_offer_prices"]
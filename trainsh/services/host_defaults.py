@@ -0,0 +1,125 @@
+"""Per-host session defaults: working directory, login shell, tmux history and env.
+
+They live in the host's `defaults` mapping in hosts.yaml and are applied by
+`train host ssh`, `train host run` and recipe `tmux.open` sessions on that host:
+
+    defaults:
+      workdir: /workspace/project
+      shell: zsh
+      history_limit: 200000
+      env: {HF_HOME: /workspace/.hf}
+"""
+
+from __future__ import annotations
+
+import shlex
+from dataclasses import dataclass, field
+from typing import Any, Dict, List, Optional
+
+from .session_env import ENV_NAME_RE
+
+# tmux scrollback for sessions we create when the host sets no `history_limit`.
+DEFAULT_HISTORY_LIMIT = 50000
+DEFAULT_KEYS = ("workdir", "shell", "history_limit", "env")
+
+
+@dataclass(frozen=True)
+class HostDefaults:
+    """Typed view of one host's `defaults` mapping."""
+
+    workdir: str = ""
+    shell: str = ""
+    history_limit: Optional[int] = None
+    env: Dict[str, str] = field(default_factory=dict)
+
+    @classmethod
+    def from_dict(cls, data: Optional[Dict[str, Any]]) -> "HostDefaults":
+        data = dict(data or {})
+        limit = data.get("history_limit")
+        return cls(
+            workdir=str(data.get("workdir") or "").strip(),
+            shell=str(data.get("shell") or "").strip(),
+            history_limit=int(limit) if limit not in (None, "") else None,
+            env={str(key): str(value) for key, value in dict(data.get("env") or {}).items()},
+        )
+
+    @classmethod
+    def for_host(cls, host: Any) -> "HostDefaults":
+        return cls.from_dict(getattr(host, "defaults", None))
+
+    def to_dict(self) -> Dict[str, Any]:
+        data: Dict[str, Any] = {}
+        if self.workdir:
+            data["workdir"] = self.workdir
+        if self.shell:
+            data["shell"] = self.shell
+        if self.history_limit is not None:
+            data["history_limit"] = self.history_limit
+        if self.env:
+            data["env"] = dict(self.env)
+        return data
+
+    @property
+    def effective_history_limit(self) -> int:
+        return self.history_limit if self.history_limit is not None else DEFAULT_HISTORY_LIMIT
+
+    def _prelude(self) -> List[str]:
+        parts = []
+        if self.workdir:
+            parts.append(f"cd {_shell_path(self.workdir)}")
+        if self.env:
+            parts.append("export " + " ".join(f"{key}={shlex.quote(value)}" for key, value in self.env.items()))
+        return parts
+
+    def wrap_command(self, command: str) -> str:
+        """`command` run from `workdir` with `env` exported."""
+        prelude = self._prelude()
+        return " && ".join([*prelude, command]) if prelude else command
+
+    def session_command(self) -> Optional[str]:
+        """Command that starts an interactive shell with these defaults, or None when none apply."""
+        if not (self.workdir or self.shell or self.env):
+            return None
+        shell = shlex.quote(self.shell) if self.shell else '"${SHELL:-sh}"'
+        return self.wrap_command(f"exec {shell} -l")
+
+
+def _shell_path(path: str) -> str:
+    """Quote a path but keep a leading `~/` expandable by the remote shell."""
+    if path == "~":
+        return '"$HOME"'
+    if path.startswith("~/"):
+        return '"$HOME"/' + shlex.quote(path[2:])
+    return shlex.quote(path)
+
+
+def validate_defaults(data: Dict[str, Any]) -> List[str]:
+    """Problems with a `defaults` mapping, empty when it is usable."""
+    problems = [f"unknown key: {key}" for key in data if key not in DEFAULT_KEYS]
+    limit = data.get("history_limit")
+    if limit not in (None, ""):
+        try:
+            if int(limit) <= 0:
+                problems.append("history_limit must be a positive integer")
+        except (TypeError, ValueError):
+            problems.append("history_limit must be a positive integer")
+    env = data.get("env") or {}
+    if not isinstance(env, dict):
+        problems.append("env must be a mapping of NAME: value")
+    else:
+        problems.extend(f"invalid env name: {key}" for key in env if not ENV_NAME_RE.match(str(key)))
+    return problems
+
+
+def set_history_limit(tmux: Any, session: str, limit: int) -> None:
+    """Raise a session's scrollback; tmux applies it to windows and panes opened afterwards."""
+    tmux.run("set-option", "-t", session, "history-limit", str(limit))
+
+
+__all__ = [
+    "DEFAULT_HISTORY_LIMIT",
+    "DEFAULT_KEYS",
+    "HostDefaults",
+    "set_history_limit",
+    "validate_defaults",
+]
@@ -0,0 +1,184 @@
+"""One way to run a command on a stored host and get a typed result back.
+
+`host_exec` resolves a host by name, quotes argv lists, injects environment
+variables and a working directory in the host's shell dialect, applies a
+timeout, and reuses one multiplexed SSH connection per host (OpenSSH
+`ControlMaster`) across calls, so a burst of small commands pays for one
+handshake.
+
+It covers commands run against stored hosts. Recipe steps address hosts by
+SSH spec, so the executor's probes (preflight, GPU guard, cancel, exit status,
+energy) keep their injected `build_ssh_args`; tmux control and log following
+hold a live ssh process and manage it themselves.
+"""
+
+from __future__ import annotations
+
+import re
+import shlex
+import time
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Any, Dict, Optional, Sequence, Union
+
+from ..constants import STATE_DIR
+from ..core.errors import AppError, ErrorCode, classify_ssh
+from ..core.models import Host
+from .remote_shell import host_shell, powershell_command, wrap_for_shell
+from .ssh import SSHClient
+
+MUX_DIR = STATE_DIR / "mux"
+DEFAULT_TIMEOUT_SECS = 60
+_ENV_NAME = re.compile(r"^[A-Za-z_][A-Za-z0-9_]*$")
+
+
+@dataclass
+class ExecResult:
+    """Outcome of one `host_exec` call."""
+
+    host: str
+    command: str
+    exit_code: int
+    stdout: str
+    stderr: str
+    duration_ms: float
+    timed_out: bool = False
+    target_hostname: Optional[str] = None
+    target_port: Optional[int] = None
+    target_source: Optional[str] = None
+
+    @property
+    def success(self) -> bool:
+        return self.exit_code == 0
+
+    @property
+    def error(self) -> Optional[AppError]:
+        if self.success:
+            return None
+        code = ErrorCode.TIMEOUT if self.timed_out else classify_ssh(self.stderr, self.exit_code)
+        return AppError(
+            self.stderr.strip() or f"command failed on {self.host} (exit {self.exit_code})",
+            code,
+            provider="ssh",
+            module="host_exec",
+            details={"host": self.host, "exit_code": self.exit_code},
+        )
+
+    def check(self) -> str:
+        """Return stdout, or raise the result's `AppError`."""
+        if not self.success:
+            raise self.error
+        return self.stdout
+
+    def to_dict(self) -> Dict[str, Any]:
+        return {
+            "host": self.host,
+            "command": self.command,
+            "exit_code": self.exit_code,
+            "stdout": self.stdout,
+            "stderr": self.stderr,
+            "duration_ms": self.duration_ms,
+            "timed_out": self.timed_out,
+        }
+
+
+def resolve_host(host_id: Union[str, Host]) -> Host:
+    """Stored (or auto-discovered) host by name; Host objects pass through."""
+    if isinstance(host_id, Host):
+        return host_id
+    from ..commands.host import load_hosts
+
+    host = load_hosts().get(host_id)
+    if host is None:
+        raise KeyError(f"Host not found: {host_id}")
+    return host
+
+
+def build_command(
+    command: Union[str, Sequence[str]],
+    *,
+    shell: str = "bash",
+    env: Optional[Dict[str, Any]] = None,
+    cwd: Optional[str] = None,
+) -> str:
+    """Remote command line with `env` exported and `cwd` entered, quoted for `shell`."""
+    text = command if isinstance(command, str) else shlex.join(str(part) for part in command)
+    for name in env or {}:
+        if not _ENV_NAME.match(str(name)):
+            raise ValueError(f"Invalid environment variable name: {name!r}")
+    if shell == "powershell":
+        assignments = [f"$env:{name} = '{str(value).replace(chr(39), chr(39) * 2)}'" for name, value in (env or {}).items()]
+        return powershell_command("; ".join([*assignments, text]), cwd) if assignments or cwd else text
+    exports = "".join(f"export {name}={shlex.quote(str(value))}; " for name, value in (env or {}).items())
+    return exports + wrap_for_shell(text, "bash", cwd) if exports or cwd else text
+
+
+def multiplex_settings() -> Dict[str, Any]:
+    from ..config import get_config_value
+
+    return {
+        "enabled": bool(get_config_value("ssh.multiplex", True)),
+        "persist_secs": int(get_config_value("ssh.control_persist_secs", 60) or 0),
+    }
+
+
+def client_for(host: Host) -> SSHClient:
+    """SSH client for `host`, sharing a ControlMaster connection when multiplexing is on."""
+    client = SSHClient.from_host(host)
+    settings = multiplex_settings()
+    if settings["enabled"] and settings["persist_secs"] > 0:
+        MUX_DIR.mkdir(parents=True, exist_ok=True, mode=0o700)
+        client.control_path = str(Path(MUX_DIR) / "%C")
+        client.control_persist = settings["persist_secs"]
+    return client
+
+
+def host_exec(
+    host_id: Union[str, Host],
+    command: Union[str, Sequence[str]],
+    *,
+    timeout: Optional[float] = DEFAULT_TIMEOUT_SECS,
+    env: Optional[Dict[str, Any]] = None,
+    cwd: Optional[str] = None,
+    stdin: str = "",
+    check: bool = False,
+) -> ExecResult:
+    """Run `command` on a host and return exit code, output and duration.
+
+    A string command is sent as-is; an argv list is shell-quoted. With `check`,
+    a failure raises `AppError` instead of being returned.
+    """
+    host = resolve_host(host_id)
+    name = host.name or host.hostname
+    line = build_command(command, shell=host_shell(host), env=env, cwd=cwd)
+    client = client_for(host)
+    started = time.monotonic()
+    if stdin:
+        raw = client.run_with_input(line, stdin, timeout=int(timeout) if timeout else None)
+    else:
+        raw = client.run(line, timeout=int(timeout) if timeout else None)
+    result = ExecResult(
+        host=name,
+        command=line,
+        exit_code=raw.exit_code,
+        stdout=raw.stdout,
+        stderr=raw.stderr,
+        duration_ms=round((time.monotonic() - started) * 1000, 1),
+        timed_out=raw.exit_code == -1 and raw.stderr == "Command timed out",
+        target_hostname=raw.target_hostname,
+        target_port=raw.target_port,
+        target_source=raw.target_source,
+    )
+    if check:
+        result.check()
+    return result
+
+
+__all__ = [
+    "ExecResult",
+    "build_command",
+    "client_for",
+    "host_exec",
+    "multiplex_settings",
+    "resolve_host",
+]
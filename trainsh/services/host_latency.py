@@ -0,0 +1,175 @@
+"""Per-route SSH latency samples and a short persisted history per host.
+
+Every connection route of a host (primary plus `connection_candidates`) is
+timed on its own. Direct routes also record the TCP connect time and the time
+until the server's `SSH-` banner arrives; every route records the full
+handshake (`ssh ... true`), which is what routes are ranked by.
+"""
+
+from __future__ import annotations
+
+import copy
+import json
+import socket
+import statistics
+import threading
+import time
+from datetime import datetime
+from pathlib import Path
+from typing import Any, Dict, List, Optional
+
+from ..constants import RUNTIME_STATE_DIR
+from ..core.models import HostType
+
+STATE_FILENAME = "latency.json"
+DEFAULT_HISTORY_SIZE = 100
+# Recent successful samples per route used to rank routes.
+RANK_WINDOW = 10
+
+_lock = threading.Lock()
+
+
+def _history_path(path: Optional[Path] = None) -> Path:
+    return Path(path or Path(RUNTIME_STATE_DIR) / STATE_FILENAME)
+
+
+def _ms(start: float) -> float:
+    return round((time.monotonic() - start) * 1000, 1)
+
+
+def measure_tcp(hostname: str, port: int, *, timeout: float = 5.0) -> Dict[str, Any]:
+    """TCP connect time and time until the SSH banner, both from the first SYN."""
+    start = time.monotonic()
+    try:
+        with socket.create_connection((hostname, int(port)), timeout=timeout) as sock:
+            connect_ms = _ms(start)
+            sock.settimeout(timeout)
+            banner = b""
+            while b"\n" not in banner and len(banner) < 256:
+                chunk = sock.recv(256)
+                if not chunk:
+                    break
+                banner += chunk
+    except OSError as exc:
+        return {"connect_ms": None, "banner_ms": None, "error": str(exc) or exc.__class__.__name__}
+    if not banner.startswith(b"SSH-"):
+        return {"connect_ms": connect_ms, "banner_ms": None, "error": "no SSH banner"}
+    return {"connect_ms": connect_ms, "banner_ms": _ms(start), "error": ""}
+
+
+def measure_route(client, target, *, timeout: float = 5.0) -> Dict[str, Any]:
+    """Time one connection route of an `SSHClient`."""
+    proxied = bool(target.proxy_command or target.jump_host)
+    sample: Dict[str, Any] = {
+        "ts": datetime.now().isoformat(),
+        "route": target.source,
+        "endpoint": f"{target.hostname}:{target.port}",
+        "proxied": proxied,
+        "connect_ms": None,
+        "banner_ms": None,
+        "handshake_ms": None,
+        "ok": False,
+        "error": "",
+    }
+    if not proxied:
+        sample.update(measure_tcp(target.hostname, target.port, timeout=timeout))
+        if sample["error"]:
+            return sample
+    single = copy.copy(client)
+    single.connection_targets = [target]
+    single.connect_timeout = max(1, int(timeout))
+    start = time.monotonic()
+    result = single.run("true", timeout=int(timeout) + 10)
+    if result.success:
+        sample.update(handshake_ms=_ms(start), ok=True)
+    else:
+        lines = (result.stderr or "").strip().splitlines()
+        sample["error"] = lines[-1] if lines else f"exit code {result.exit_code}"
+    return sample
+
+
+def measure_host(name: str, host, *, timeout: float = 5.0, path: Optional[Path] = None) -> List[Dict[str, Any]]:
+    """Time every route of `host`, store the samples and return them."""
+    from .ssh import SSHClient
+
+    if host.type == HostType.LOCAL:
+        return []
+    client = SSHClient.from_host(host)
+    samples = [measure_route(client, target, timeout=timeout) for target in client.connection_targets]
+    record_samples(name, samples, path=path)
+    return samples
+
+
+def _load(path: Path) -> Dict[str, List[Dict[str, Any]]]:
+    try:
+        payload = json.loads(path.read_text(encoding="utf-8"))
+    except (OSError, ValueError):
+        return {}
+    return payload if isinstance(payload, dict) else {}
+
+
+def record_samples(name: str, samples: List[Dict[str, Any]], *, path: Optional[Path] = None) -> None:
+    from ..config import get_config_value
+
+    if not samples:
+        return
+    keep = max(1, int(get_config_value("latency.history_size", DEFAULT_HISTORY_SIZE) or DEFAULT_HISTORY_SIZE))
+    target = _history_path(path)
+    with _lock:
+        payload = _load(target)
+        payload[name] = (list(payload.get(name, [])) + list(samples))[-keep:]
+        target.parent.mkdir(parents=True, exist_ok=True)
+        target.write_text(json.dumps(payload, ensure_ascii=False, indent=2), encoding="utf-8")
+
+
+def host_latency_history(name: str, *, limit: Optional[int] = None, path: Optional[Path] = None) -> List[Dict[str, Any]]:
+    """Stored samples for one host, oldest first."""
+    samples = list(_load(_history_path(path)).get(name, []))
+    return samples[-limit:] if limit else samples
+
+
+def route_stats(samples: List[Dict[str, Any]]) -> List[Dict[str, Any]]:
+    """Per-route summary of `samples`, fastest route first and flagged `fastest`."""
+    routes: Dict[str, List[Dict[str, Any]]] = {}
+    for sample in samples:
+        routes.setdefault(f"{sample.get('route')}|{sample.get('endpoint')}", []).append(sample)
+    stats = []
+    for items in routes.values():
+        ok = [item["handshake_ms"] for item in items if item.get("ok") and item.get("handshake_ms") is not None]
+        banners = [item["banner_ms"] for item in items if item.get("banner_ms") is not None]
+        last = items[-1]
+        stats.append(
+            {
+                "route": last.get("route", ""),
+                "endpoint": last.get("endpoint", ""),
+                "proxied": bool(last.get("proxied")),
+                "samples": len(items),
+                "success_rate": round(len(ok) / len(items), 2),
+                "median_ms": statistics.median(ok[-RANK_WINDOW:]) if ok else None,
+                "banner_ms": statistics.median(banners[-RANK_WINDOW:]) if banners else None,
+                "last_ok": bool(last.get("ok")),
+                "last_at": last.get("ts", ""),
+                "fastest": False,
+            }
+        )
+    stats.sort(key=lambda item: (not item["last_ok"], item["median_ms"] is None, item["median_ms"] or 0))
+    if stats and stats[0]["last_ok"] and stats[0]["median_ms"] is not None:
+        stats[0]["fastest"] = True
+    return stats
+
+
+def fastest_route(name: str, *, path: Optional[Path] = None) -> Optional[Dict[str, Any]]:
+    """The currently fastest working route of a host, from its history."""
+    stats = route_stats(host_latency_history(name, path=path))
+    return stats[0] if stats and stats[0]["fastest"] else None
+
+
+__all__ = [
+    "fastest_route",
+    "host_latency_history",
+    "measure_host",
+    "measure_route",
+    "measure_tcp",
+    "record_samples",
+    "route_stats",
+]
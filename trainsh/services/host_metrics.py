@@ -0,0 +1,290 @@
+"""Per-host CPU, memory, disk IO, network and GPU sampling over one SSH round trip."""
+
+from __future__ import annotations
+
+import re
+import threading
+from collections import deque
+from datetime import datetime
+from typing import Any, Callable, Deque, Dict, List, Optional
+
+from ..core.models import Host
+from .host_exec import host_exec
+
+METRICS_EVENT = "host:metrics"
+DEFAULT_INTERVAL_SECS = 5.0
+DEFAULT_BUFFER_SIZE = 720
+
+# Whole-disk rows only; partitions are skipped when their parent is listed.
+_VIRTUAL_DISK_RE = re.compile(r"^(loop|ram|zram|sr|fd|dm-|md)\d*")
+_GPU_QUERY = "index,name,utilization.gpu,memory.used,memory.total,temperature.gpu,power.draw"
+
+_buffers: Dict[str, Deque[Dict[str, Any]]] = {}
+_listeners: List[Callable[[Dict[str, Any]], None]] = []
+_lock = threading.RLock()
+
+
+def metrics_settings() -> tuple[float, int]:
+    """`host_metrics.interval_secs` and `host_metrics.buffer_size` from config."""
+    from ..config import get_config_value
+
+    try:
+        interval = float(get_config_value("host_metrics.interval_secs", DEFAULT_INTERVAL_SECS))
+    except (TypeError, ValueError):
+        interval = DEFAULT_INTERVAL_SECS
+    try:
+        size = int(get_config_value("host_metrics.buffer_size", DEFAULT_BUFFER_SIZE))
+    except (TypeError, ValueError):
+        size = DEFAULT_BUFFER_SIZE
+    return max(1.0, interval), max(1, size)
+
+
+def sample_script() -> str:
+    """One remote command that dumps every counter we need, section by section."""
+    return "; ".join(
+        [
+            "echo @@uptime",
+            "cut -d' ' -f1 /proc/uptime",
+            "echo @@stat",
+            "head -n 1 /proc/stat",
+            "echo @@mem",
+            "grep -E '^(MemTotal|MemAvailable|SwapTotal|SwapFree):' /proc/meminfo",
+            "echo @@disk",
+            "cat /proc/diskstats",
+            "echo @@net",
+            "tail -n +3 /proc/net/dev",
+            "echo @@gpu",
+            f"nvidia-smi --query-gpu={_GPU_QUERY} --format=csv,noheader,nounits 2>/dev/null || true",
+        ]
+    )
+
+
+def _number(text: str) -> Optional[float]:
+    try:
+        return float(text)
+    except (TypeError, ValueError):
+        return None
+
+
+def _whole_disks(names: List[str]) -> set:
+    disks = {name for name in names if not _VIRTUAL_DISK_RE.match(name)}
+    return {
+        name
+        for name in disks
+        if not any(other != name and re.fullmatch(rf"{re.escape(other)}p?\d+", name) for other in disks)
+    }
+
+
+def parse_sample(text: str) -> Dict[str, Any]:
+    """Raw counters from `sample_script()` output."""
+    sections: Dict[str, List[str]] = {}
+    current = ""
+    for line in text.splitlines():
+        if line.startswith("@@"):
+            current = line[2:].strip()
+            sections[current] = []
+        elif current and line.strip():
+            sections[current].append(line)
+
+    raw: Dict[str, Any] = {"uptime": _number((sections.get("uptime") or ["0"])[0].strip()) or 0.0}
+
+    fields = (sections.get("stat") or ["cpu"])[0].split()[1:]
+    values = [int(item) for item in fields[:8] if item.isdigit()]
+    if len(values) >= 5:
+        raw["cpu_total"] = sum(values)
+        raw["cpu_idle"] = values[3] + values[4]
+
+    mem: Dict[str, int] = {}
+    for line in sections.get("mem", []):
+        key, _, rest = line.partition(":")
+        parts = rest.split()
+        if parts and parts[0].isdigit():
+            mem[key.strip()] = int(parts[0]) * 1024
+    raw["mem"] = mem
+
+    rows = [line.split() for line in sections.get("disk", []) if len(line.split()) >= 10]
+    disks = _whole_disks([row[2] for row in rows])
+    raw["disk_read"] = sum(int(row[5]) * 512 for row in rows if row[2] in disks)
+    raw["disk_write"] = sum(int(row[9]) * 512 for row in rows if row[2] in disks)
+
+    rx = tx = 0
+    for line in sections.get("net", []):
+        name, _, rest = line.partition(":")
+        parts = rest.split()
+        if name.strip() == "lo" or len(parts) < 9:
+            continue
+        rx += int(parts[0])
+        tx += int(parts[8])
+    raw["net_rx"] = rx
+    raw["net_tx"] = tx
+
+    gpus = []
+    for line in sections.get("gpu", []):
+        parts = [item.strip() for item in line.split(",")]
+        if len(parts) < 7:
+            continue
+        gpus.append(
+            {
+                "index": int(_number(parts[0]) or 0),
+                "name": parts[1],
+                "utilization": _number(parts[2]),
+                "memory_used_mb": _number(parts[3]),
+                "memory_total_mb": _number(parts[4]),
+                "temperature_c": _number(parts[5]),
+                "power_w": _number(parts[6]),
+            }
+        )
+    raw["gpus"] = gpus
+    return raw
+
+
+def compute_metrics(raw: Dict[str, Any], previous: Optional[Dict[str, Any]] = None) -> Dict[str, Any]:
+    """Turn counters into a sample; rates stay None until a previous sample exists."""
+    mem = raw.get("mem") or {}
+    mem_total = mem.get("MemTotal", 0)
+    swap_total = mem.get("SwapTotal", 0)
+    metrics: Dict[str, Any] = {
+        "cpu_percent": None,
+        "mem_used": max(0, mem_total - mem.get("MemAvailable", mem_total)),
+        "mem_total": mem_total,
+        "swap_used": max(0, swap_total - mem.get("SwapFree", swap_total)),
+        "swap_total": swap_total,
+        "disk_read_bps": None,
+        "disk_write_bps": None,
+        "net_rx_bps": None,
+        "net_tx_bps": None,
+        "gpus": list(raw.get("gpus") or []),
+    }
+    if not previous:
+        return metrics
+    elapsed = float(raw.get("uptime") or 0) - float(previous.get("uptime") or 0)
+    if elapsed <= 0:
+        return metrics
+
+    total = raw.get("cpu_total", 0) - previous.get("cpu_total", 0)
+    idle = raw.get("cpu_idle", 0) - previous.get("cpu_idle", 0)
+    if total > 0:
+        metrics["cpu_percent"] = round(100.0 * (total - idle) / total, 1)
+    for key, counter in (
+        ("disk_read_bps", "disk_read"),
+        ("disk_write_bps", "disk_write"),
+        ("net_rx_bps", "net_rx"),
+        ("net_tx_bps", "net_tx"),
+    ):
+        delta = raw.get(counter, 0) - previous.get(counter, 0)
+        # Counters reset on reboot or interface changes; skip the bogus interval.
+        metrics[key] = round(delta / elapsed, 1) if delta >= 0 else None
+    return metrics
+
+
+def add_listener(callback: Callable[[Dict[str, Any]], None]) -> None:
+    with _lock:
+        _listeners.append(callback)
+
+
+def remove_listener(callback: Callable[[Dict[str, Any]], None]) -> None:
+    with _lock:
+        if callback in _listeners:
+            _listeners.remove(callback)
+
+
+def _emit(event: Dict[str, Any], buffer_size: int) -> None:
+    with _lock:
+        buffer = _buffers.get(event["host"])
+        if buffer is None or buffer.maxlen != buffer_size:
+            buffer = deque(buffer or (), maxlen=buffer_size)
+            _buffers[event["host"]] = buffer
+        buffer.append(event)
+        listeners = list(_listeners)
+    for callback in listeners:
+        try:
+            callback(event)
+        except Exception:
+            pass
+
+
+def host_metrics_recent(host_id: str, limit: Optional[int] = None) -> List[Dict[str, Any]]:
+    """Buffered `host:metrics` events for one host, oldest first."""
+    with _lock:
+        items = list(_buffers.get(str(host_id), ()))
+    return items[-limit:] if limit else items
+
+
+def clear_host_metrics(host_id: Optional[str] = None) -> None:
+    with _lock:
+        if host_id is None:
+            _buffers.clear()
+        else:
+            _buffers.pop(str(host_id), None)
+
+
+class HostMetricsSampler:
+    """Poll one host on an interval and emit `host:metrics` events into its ring buffer."""
+
+    def __init__(
+        self,
+        host_id: str,
+        host: Host,
+        *,
+        interval: Optional[float] = None,
+        buffer_size: Optional[int] = None,
+        timeout: int = 20,
+    ):
+        default_interval, default_size = metrics_settings()
+        self.host_id = str(host_id)
+        self.host = host
+        self.interval = max(1.0, float(interval or default_interval))
+        self.buffer_size = max(1, int(buffer_size or default_size))
+        self.timeout = timeout
+        self._previous: Optional[Dict[str, Any]] = None
+        self._stop = threading.Event()
+        self._thread: Optional[threading.Thread] = None
+
+    def sample_once(self) -> Dict[str, Any]:
+        """Take one sample and emit it; failures are emitted with an `error`."""
+        event: Dict[str, Any] = {"event": METRICS_EVENT, "host": self.host_id, "ts": datetime.now().isoformat()}
+        result = host_exec(self.host, sample_script(), timeout=self.timeout)
+        if not result.success:
+            event["error"] = result.stderr.strip() or f"sampler exited with {result.exit_code}"
+            self._previous = None
+        else:
+            raw = parse_sample(result.stdout)
+            event.update(compute_metrics(raw, self._previous))
+            self._previous = raw
+        _emit(event, self.buffer_size)
+        return event
+
+    def _loop(self) -> None:
+        while not self._stop.is_set():
+            try:
+                self.sample_once()
+            except Exception as exc:
+                _emit({"event": METRICS_EVENT, "host": self.host_id, "ts": datetime.now().isoformat(), "error": str(exc)}, self.buffer_size)
+            self._stop.wait(self.interval)
+
+    def start(self) -> "HostMetricsSampler":
+        if self._thread is None or not self._thread.is_alive():
+            self._stop.clear()
+            self._thread = threading.Thread(target=self._loop, name=f"host-metrics-{self.host_id}", daemon=True)
+            self._thread.start()
+        return self
+
+    def stop(self, timeout: float = 5.0) -> None:
+        self._stop.set()
+        if self._thread is not None:
+            self._thread.join(timeout)
+            self._thread = None
+
+
+__all__ = [
+    "METRICS_EVENT",
+    "HostMetricsSampler",
+    "add_listener",
+    "clear_host_metrics",
+    "compute_metrics",
+    "host_metrics_recent",
+    "metrics_settings",
+    "parse_sample",
+    "remove_listener",
+    "sample_script",
+]
@@ -0,0 +1,146 @@
+"""Wake-on-LAN and SSH power-off for on-prem hosts, with reachability polling."""
+
+from __future__ import annotations
+
+import re
+import shlex
+import socket
+import time
+from typing import Any, Callable, Dict, Optional
+
+from .host_exec import host_exec, resolve_host
+from .ssh import SSHClient
+
+DEFAULT_BROADCAST = "255.255.255.255"
+DEFAULT_WOL_PORT = 9
+DEFAULT_POWER_OFF_COMMAND = "sudo -n systemctl poweroff"
+DEFAULT_SUSPEND_COMMAND = "sudo -n systemctl suspend"
+_MAC_RE = re.compile(r"^[0-9a-f]{12}$")
+
+
+def normalize_mac(mac: str) -> str:
+    """`aa:bb:cc:dd:ee:ff` from any common separator style; raises ValueError."""
+    digits = re.sub(r"[\s:.-]", "", str(mac or "")).lower()
+    if not _MAC_RE.match(digits):
+        raise ValueError(f"Invalid MAC address: {mac!r}")
+    return ":".join(digits[i:i + 2] for i in range(0, 12, 2))
+
+
+def magic_packet(mac: str) -> bytes:
+    """Six 0xff bytes followed by the MAC repeated 16 times."""
+    raw = bytes.fromhex(normalize_mac(mac).replace(":", ""))
+    return b"\xff" * 6 + raw * 16
+
+
+def send_magic_packet(mac: str, *, broadcast: str = DEFAULT_BROADCAST, port: int = DEFAULT_WOL_PORT) -> None:
+    packet = magic_packet(mac)
+    with socket.socket(socket.AF_INET, socket.SOCK_DGRAM) as sock:
+        sock.setsockopt(socket.SOL_SOCKET, socket.SO_BROADCAST, 1)
+        sock.sendto(packet, (broadcast, int(port)))
+
+
+def power_settings(host: Any) -> Dict[str, Any]:
+    """Power options stored in the host's env_vars, with defaults filled in."""
+    env = dict(getattr(host, "env_vars", None) or {})
+    try:
+        port = int(env.get("wol_port") or DEFAULT_WOL_PORT)
+    except (TypeError, ValueError):
+        port = DEFAULT_WOL_PORT
+    return {
+        "mac": str(env.get("wol_mac", "") or ""),
+        "broadcast": str(env.get("wol_broadcast", "") or DEFAULT_BROADCAST),
+        "port": port,
+        "power_off_command": str(env.get("power_off_command", "") or DEFAULT_POWER_OFF_COMMAND),
+        "suspend_command": str(env.get("suspend_command", "") or DEFAULT_SUSPEND_COMMAND),
+    }
+
+
+def is_reachable(host: Any) -> bool:
+    try:
+        return SSHClient.from_host(host).test_connection()
+    except Exception:
+        return False
+
+
+def wait_reachable(
+    host: Any,
+    *,
+    up: bool = True,
+    timeout: float = 300,
+    interval: float = 5,
+    on_poll: Optional[Callable[[float], None]] = None,
+) -> Optional[float]:
+    """Poll SSH until the host is (or, with `up=False`, is no longer) reachable.
+
+    Returns seconds waited, or None on timeout.
+    """
+    start = time.monotonic()
+    while True:
+        elapsed = time.monotonic() - start
+        if is_reachable(host) == up:
+            return elapsed
+        if elapsed >= timeout:
+            return None
+        if on_poll is not None:
+            on_poll(elapsed)
+        time.sleep(max(0.0, min(interval, timeout - elapsed)))
+
+
+def host_wake(
+    host_id: str,
+    *,
+    wait: bool = True,
+    timeout: float = 300,
+    interval: float = 5,
+    on_poll: Optional[Callable[[float], None]] = None,
+) -> Dict[str, Any]:
+    """Send a Wake-on-LAN packet to a host and optionally wait for SSH to answer."""
+    host = resolve_host(host_id)
+    settings = power_settings(host)
+    if not settings["mac"]:
+        raise ValueError(f"No MAC address stored for {host_id}; pass --mac once to save it.")
+    if is_reachable(host):
+        return {"sent": False, "reachable": True, "elapsed": 0.0}
+    send_magic_packet(settings["mac"], broadcast=settings["broadcast"], port=settings["port"])
+    if not wait:
+        return {"sent": True, "reachable": None, "elapsed": 0.0}
+    elapsed = wait_reachable(host, timeout=timeout, interval=interval, on_poll=on_poll)
+    return {"sent": True, "reachable": elapsed is not None, "elapsed": elapsed}
+
+
+def host_power_off(
+    host_id: str,
+    *,
+    suspend: bool = False,
+    wait: bool = True,
+    timeout: float = 120,
+    interval: float = 5,
+    on_poll: Optional[Callable[[float], None]] = None,
+) -> Dict[str, Any]:
+    """Run the host's power-off (or suspend) command over SSH, then wait for it to drop off."""
+    host = resolve_host(host_id)
+    settings = power_settings(host)
+    command = settings["suspend_command" if suspend else "power_off_command"]
+    # Detach so the SSH session can return before the machine goes down.
+    detached = f"nohup sh -c {shlex.quote('sleep 1; ' + command)} >/dev/null 2>&1 &"
+    result = host_exec(host, detached, timeout=30)
+    if not result.success and result.exit_code != 255:
+        raise RuntimeError(result.stderr.strip() or f"power command failed on {host_id} (exit {result.exit_code})")
+    if not wait:
+        return {"command": command, "down": None, "elapsed": 0.0}
+    elapsed = wait_reachable(host, up=False, timeout=timeout, interval=interval, on_poll=on_poll)
+    return {"command": command, "down": elapsed is not None, "elapsed": elapsed}
+
+
+__all__ = [
+    "DEFAULT_POWER_OFF_COMMAND",
+    "DEFAULT_SUSPEND_COMMAND",
+    "host_power_off",
+    "host_wake",
+    "is_reachable",
+    "magic_packet",
+    "normalize_mac",
+    "power_settings",
+    "send_magic_packet",
+    "wait_reachable",
+]
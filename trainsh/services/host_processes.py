@@ -0,0 +1,172 @@
+"""Remote process listing with GPU memory attribution, and guarded kill."""
+
+from __future__ import annotations
+
+from typing import Any, Dict, List, Optional
+
+from .host_exec import host_exec
+
+ALLOWED_SIGNALS = ("TERM", "KILL", "INT", "HUP", "QUIT", "USR1", "USR2", "STOP", "CONT")
+PS_FIELDS = "pid=,ppid=,user=,stat=,pcpu=,pmem=,rss=,etime=,args="
+
+
+def list_command() -> str:
+    """ps plus nvidia-smi compute apps (memory) and pmon (SM %), in one call."""
+    return "; ".join(
+        [
+            "echo @@ps",
+            f"ps -eo {PS_FIELDS}",
+            "echo @@gpus",
+            "nvidia-smi --query-gpu=index,uuid --format=csv,noheader 2>/dev/null || true",
+            "echo @@apps",
+            "nvidia-smi --query-compute-apps=pid,gpu_uuid,used_memory --format=csv,noheader,nounits 2>/dev/null || true",
+            "echo @@pmon",
+            "nvidia-smi pmon -c 1 -s u 2>/dev/null || true",
+        ]
+    )
+
+
+def _int(text: str) -> Optional[int]:
+    try:
+        return int(float(text))
+    except (TypeError, ValueError):
+        return None
+
+
+def parse_process_output(text: str) -> List[Dict[str, Any]]:
+    """Process rows; GPU holders get `gpu_memory_mb`, `gpus` and `gpu_sm_percent`."""
+    sections: Dict[str, List[str]] = {}
+    current = ""
+    for line in text.splitlines():
+        if line.startswith("@@"):
+            current = line[2:].strip()
+            sections[current] = []
+        elif current and line.strip():
+            sections[current].append(line)
+
+    processes: Dict[int, Dict[str, Any]] = {}
+    for line in sections.get("ps", []):
+        parts = line.split(None, 8)
+        if len(parts) < 9 or _int(parts[0]) is None:
+            continue
+        pid = int(parts[0])
+        processes[pid] = {
+            "pid": pid,
+            "ppid": _int(parts[1]) or 0,
+            "user": parts[2],
+            "stat": parts[3],
+            "cpu_percent": float(parts[4]) if parts[4].replace(".", "", 1).isdigit() else 0.0,
+            "mem_percent": float(parts[5]) if parts[5].replace(".", "", 1).isdigit() else 0.0,
+            "rss": (_int(parts[6]) or 0) * 1024,
+            "elapsed": parts[7],
+            "command": parts[8],
+            "zombie": "Z" in parts[3],
+            "gpu_memory_mb": 0,
+            "gpus": [],
+            "gpu_sm_percent": None,
+        }
+
+    gpu_index = {}
+    for line in sections.get("gpus", []):
+        index, _, uuid = [item.strip() for item in line.partition(",")]
+        if uuid:
+            gpu_index[uuid] = _int(index)
+
+    for line in sections.get("apps", []):
+        parts = [item.strip() for item in line.split(",")]
+        pid = _int(parts[0]) if parts else None
+        if pid is None or len(parts) < 3:
+            continue
+        # PIDs from another PID namespace (containers) have no ps row.
+        row = processes.setdefault(
+            pid,
+            {
+                "pid": pid,
+                "ppid": 0,
+                "user": "?",
+                "stat": "?",
+                "cpu_percent": 0.0,
+                "mem_percent": 0.0,
+                "rss": 0,
+                "elapsed": "",
+                "command": "(not visible from this namespace)",
+                "zombie": False,
+                "gpu_memory_mb": 0,
+                "gpus": [],
+                "gpu_sm_percent": None,
+            },
+        )
+        row["gpu_memory_mb"] += _int(parts[2]) or 0
+        index = gpu_index.get(parts[1])
+        if index is not None and index not in row["gpus"]:
+            row["gpus"].append(index)
+
+    for line in sections.get("pmon", []):
+        parts = line.split()
+        if line.lstrip().startswith("#") or len(parts) < 4:
+            continue
+        pid, sm = _int(parts[1]), _int(parts[3])
+        if pid in processes and sm is not None:
+            processes[pid]["gpu_sm_percent"] = max(sm, processes[pid]["gpu_sm_percent"] or 0)
+
+    return sorted(processes.values(), key=lambda row: (-row["gpu_memory_mb"], -row["cpu_percent"], row["pid"]))
+
+
+def filter_processes(rows: List[Dict[str, Any]], pattern: str = "", *, gpu_only: bool = False) -> List[Dict[str, Any]]:
+    """Keep rows whose pid, user or command contains `pattern` (case-insensitive)."""
+    needle = str(pattern or "").strip().lower()
+    selected = []
+    for row in rows:
+        if gpu_only and not row["gpu_memory_mb"]:
+            continue
+        if needle and needle not in f"{row['pid']} {row['user']} {row['command']}".lower():
+            continue
+        selected.append(row)
+    return selected
+
+
+def host_list_processes(host_id: str, filter: str = "", *, gpu_only: bool = False) -> List[Dict[str, Any]]:
+    """Processes on a host, biggest GPU memory holders first."""
+    result = host_exec(host_id, list_command(), timeout=60)
+    if not result.success:
+        raise RuntimeError(result.stderr.strip() or f"ps failed on {host_id} (exit {result.exit_code})")
+    return filter_processes(parse_process_output(result.stdout), filter, gpu_only=gpu_only)
+
+
+def normalize_signal(value: Any) -> str:
+    name = str(value or "TERM").strip().upper()
+    if name.isdigit():
+        name = {"1": "HUP", "2": "INT", "3": "QUIT", "9": "KILL", "15": "TERM"}.get(name, name)
+    name = name[3:] if name.startswith("SIG") else name
+    if name not in ALLOWED_SIGNALS:
+        raise ValueError(f"Unsupported signal: {value} (expected one of {', '.join(ALLOWED_SIGNALS)})")
+    return name
+
+
+def host_kill_process(host_id: str, pid: int, signal: str = "TERM") -> Dict[str, Any]:
+    """Send `signal` to one pid on a host; reports whether the process is gone afterwards."""
+    pid = int(pid)
+    if pid <= 1:
+        raise ValueError(f"Refusing to signal pid {pid}")
+    name = normalize_signal(signal)
+    script = (
+        f"kill -s {name} {pid} || exit $?; "
+        f"for _ in 1 2 3 4 5; do kill -0 {pid} 2>/dev/null || {{ echo gone; exit 0; }}; sleep 0.2; done; "
+        f"ps -o stat= -p {pid} 2>/dev/null | grep -q Z && echo zombie || echo alive"
+    )
+    result = host_exec(host_id, ["sh", "-c", script], timeout=30)
+    if not result.success:
+        raise RuntimeError(result.stderr.strip() or f"kill failed on {host_id} (exit {result.exit_code})")
+    state = (result.stdout.strip().splitlines() or ["alive"])[-1]
+    return {"host": host_id, "pid": pid, "signal": name, "state": state, "exited": state == "gone"}
+
+
+__all__ = [
+    "ALLOWED_SIGNALS",
+    "filter_processes",
+    "host_kill_process",
+    "host_list_processes",
+    "list_command",
+    "normalize_signal",
+    "parse_process_output",
+]
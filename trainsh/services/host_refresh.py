@@ -0,0 +1,155 @@
+"""Touch many hosts concurrently without one dead host stalling the rest.
+
+`run_per_host` gives every host its own timeout, measured from when its
+work actually starts, and returns a result per host whether it finished,
+failed or timed out. `refresh_all` uses it to re-resolve Vast links and
+probe every stored host, emitting one `host:refresh` event per host.
+"""
+
+from __future__ import annotations
+
+import time
+from concurrent.futures import FIRST_COMPLETED, ThreadPoolExecutor, wait
+from dataclasses import dataclass
+from typing import Any, Callable, Dict, Iterable, Optional, Tuple
+
+REFRESH_EVENT = "host:refresh"
+DEFAULT_TIMEOUT_SECS = 20.0
+DEFAULT_WORKERS = 8
+
+
+@dataclass
+class HostResult:
+    name: str
+    status: str  # ok | error | timeout
+    value: Any = None
+    error: str = ""
+    elapsed_ms: float = 0.0
+
+
+def refresh_settings() -> Dict[str, float]:
+    from ..config import get_config_value
+
+    try:
+        timeout = float(get_config_value("host_refresh.timeout_secs", DEFAULT_TIMEOUT_SECS) or DEFAULT_TIMEOUT_SECS)
+        workers = int(get_config_value("host_refresh.workers", DEFAULT_WORKERS) or DEFAULT_WORKERS)
+    except (TypeError, ValueError):
+        timeout, workers = DEFAULT_TIMEOUT_SECS, DEFAULT_WORKERS
+    return {"timeout_secs": max(1.0, timeout), "workers": max(1, workers)}
+
+
+def run_per_host(
+    names: Iterable[str],
+    work: Callable[[str], Any],
+    *,
+    timeout: Optional[float] = None,
+    workers: Optional[int] = None,
+    on_result: Optional[Callable[[HostResult], None]] = None,
+) -> Dict[str, HostResult]:
+    """Run `work(name)` for every host concurrently; a slow host only times out itself.
+
+    Timed-out work keeps running in its thread but its result is discarded.
+    """
+    settings = refresh_settings() if timeout is None or workers is None else {}
+    timeout = float(timeout if timeout is not None else settings["timeout_secs"])
+    names = list(dict.fromkeys(names))
+    results: Dict[str, HostResult] = {}
+    if not names:
+        return results
+    started: Dict[str, float] = {}
+
+    def task(name: str) -> Any:
+        started[name] = time.monotonic()
+        return work(name)
+
+    def finish(result: HostResult) -> None:
+        results[result.name] = result
+        if on_result is not None:
+            on_result(result)
+
+    pool = ThreadPoolExecutor(max_workers=max(1, min(workers or settings["workers"], len(names))))
+    pending = {pool.submit(task, name): name for name in names}
+    try:
+        while pending:
+            done, _ = wait(list(pending), timeout=0.1, return_when=FIRST_COMPLETED)
+            for future in done:
+                name = pending.pop(future)
+                elapsed = round((time.monotonic() - started.get(name, time.monotonic())) * 1000, 1)
+                try:
+                    finish(HostResult(name, "ok", future.result(), elapsed_ms=elapsed))
+                except Exception as exc:
+                    finish(HostResult(name, "error", error=str(exc), elapsed_ms=elapsed))
+            now = time.monotonic()
+            for future, name in list(pending.items()):
+                if name in started and now - started[name] > timeout:
+                    del pending[future]
+                    finish(HostResult(name, "timeout", error=f"no answer within {timeout:g}s", elapsed_ms=round(timeout * 1000, 1)))
+    finally:
+        pool.shutdown(wait=False, cancel_futures=True)
+    return {name: results[name] for name in names}
+
+
+def refresh_all(
+    hosts: Dict[str, Any],
+    *,
+    timeout: Optional[float] = None,
+    on_event: Optional[Callable[[Dict[str, Any]], None]] = None,
+) -> Tuple[Dict[str, Dict[str, Any]], Dict[str, Any]]:
+    """Re-resolve Vast links and probe every host.
+
+    Returns one `host:refresh` event per host plus the re-resolved Vast-linked
+    hosts for the caller to persist; probe outcomes go to the connectivity state.
+    """
+    from .connectivity import ConnectivityMonitor, probe_host
+    from .host_resolver import linked_vast_instance_id, refresh_vast_link
+
+    monitor = ConnectivityMonitor()
+
+    def work(name: str) -> Dict[str, Any]:
+        host = hosts[name]
+        before = f"{host.hostname}:{host.port}"
+        if linked_vast_instance_id(host):
+            host = refresh_vast_link(host)
+        online, error = probe_host(host)
+        return {"host": host, "online": online, "error": error, "moved_from": before if f"{host.hostname}:{host.port}" != before else ""}
+
+    events: Dict[str, Dict[str, Any]] = {}
+    refreshed_hosts: Dict[str, Any] = {}
+
+    def on_result(result: HostResult) -> None:
+        value = result.value or {}
+        if result.status == "ok":
+            status = "online" if value["online"] else "offline"
+            error = value["error"]
+        else:
+            status, error = result.status, result.error
+        monitor.record(result.name, status == "online", error)
+        refreshed = value.get("host") or hosts[result.name]
+        event = {
+            "event": REFRESH_EVENT,
+            "host": result.name,
+            "status": status,
+            "endpoint": f"{refreshed.hostname}:{refreshed.port}" if refreshed.hostname else "",
+            "moved_from": value.get("moved_from", ""),
+            "error": error,
+            "elapsed_ms": result.elapsed_ms,
+            "done": len(events) + 1,
+            "total": len(hosts),
+        }
+        events[result.name] = event
+        if value.get("host") is not None and linked_vast_instance_id(value["host"]):
+            refreshed_hosts[result.name] = value["host"]
+        if on_event is not None:
+            on_event(event)
+
+    run_per_host(hosts, work, timeout=timeout, on_result=on_result)
+    return events, refreshed_hosts
+
+
+__all__ = [
+    "HostResult",
+    "REFRESH_EVENT",
+    "refresh_all",
+    "refresh_settings",
+    "run_per_host",
+]
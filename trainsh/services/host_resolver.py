@@ -7,12 +7,17 @@ import subprocess
 import time
 from typing import Optional
 
+from ..core.errors import AppError, ErrorCode
 from ..core.models import AuthMethod, Host, HostType
 from .runpod_connection import runpod_ssh_targets
 from .vast_connection import vast_ssh_targets
 
 AUTO_DISCOVERED_VAST_ENV = "_auto_discovered_vast"
 AUTO_DISCOVERED_RUNPOD_ENV = "_auto_discovered_runpod"
+VAST_LINK_ENV = "vast_link"
+
+# instance id -> (resolved at, ordered targets); keeps per-command SSH calls off the Vast API.
+_VAST_LINK_CACHE: dict[str, tuple[float, list[dict]]] = {}
 
 
 def _instance_connection_targets(instance) -> list[dict]:
@@ -251,15 +256,106 @@ def prepare_vast_host(
                 return _apply_connection_targets(resolved, ordered_targets, ready_key="vast_ssh_ready")
 
         if time.monotonic() >= deadline:
-            raise RuntimeError(
+            raise AppError(
                 f"Vast.ai instance {instance_id} did not become SSH-ready within "
-                f"{timeout_seconds}s (status: {status or 'unknown'})."
+                f"{timeout_seconds}s (status: {status or 'unknown'}).",
+                ErrorCode.INSTANCE_NOT_PROVISIONED,
+                provider="vast",
+                module="host_resolver",
             )
 
         time.sleep(max(poll_seconds, 1))
         instance = client.get_instance(instance_id)
 
 
+def parse_vast_ref(value: str) -> str:
+    """Instance id from `vast:<id>` or a bare id."""
+    text = str(value or "").strip()
+    if text.startswith("vast:"):
+        text = text[5:]
+    if not text.isdigit():
+        raise ValueError(f"Invalid Vast.ai reference: {value} (expected vast:<id>)")
+    return text
+
+
+def linked_vast_instance_id(host: Host) -> str:
+    """Vast instance backing a host: its own id, or the `vast_link` of a plain SSH host."""
+    if host.type == HostType.VASTAI and host.vast_instance_id:
+        return str(host.vast_instance_id)
+    try:
+        return parse_vast_ref((host.env_vars or {}).get(VAST_LINK_ENV, ""))
+    except ValueError:
+        return ""
+
+
+def _order_vast_targets(host: Host, targets: list[dict], *, probe: bool) -> list[dict]:
+    """Keep the stored endpoint first while Vast still lists it, else probe for a working one."""
+    current = (host.hostname, int(host.port or 22))
+    for index, target in enumerate(targets):
+        if (target.get("hostname"), int(target.get("port", 22) or 22)) == current:
+            return [targets[index], *targets[:index], *targets[index + 1 :]]
+    if probe:
+        ordered = _pick_reachable_targets(targets, username=host.username or "root", key_path=host.ssh_key_path)
+        if ordered is not None:
+            return ordered
+    return targets
+
+
+def refresh_vast_link(host: Host, *, probe: bool = True) -> Host:
+    """Re-read the SSH endpoint of a Vast-linked host from the Vast API.
+
+    Returns a copy pointing at the instance's current SSH host/port; raises
+    RuntimeError when the instance is not running or exposes no SSH endpoint.
+    """
+    instance_id = linked_vast_instance_id(host)
+    if not instance_id:
+        return host
+
+    from .vast_api import get_vast_client
+
+    instance = get_vast_client().get_instance(int(instance_id))
+    status = str(getattr(instance, "actual_status", "") or "").lower()
+    targets = _instance_connection_targets(instance)
+    if status != "running" or not targets:
+        raise AppError(
+            f"Vast.ai instance {instance_id} has no SSH endpoint (status: {status or 'unknown'}); "
+            f"start it with `train vast start {instance_id}`.",
+            ErrorCode.INSTANCE_NOT_PROVISIONED,
+            provider="vast",
+            module="host_resolver",
+        )
+
+    ordered = _order_vast_targets(host, targets, probe=probe)
+    _VAST_LINK_CACHE[instance_id] = (time.monotonic(), ordered)
+    resolved = Host.from_dict(host.to_dict())
+    resolved.vast_status = status
+    return _apply_connection_targets(resolved, ordered, ready_key="vast_ssh_ready")
+
+
+def prepare_vast_linked_host(host: Host, *, max_age: Optional[float] = None) -> Host:
+    """Point an SSH host linked to `vast:<id>` at the instance's current endpoint.
+
+    Lookups are cached for `vast.link_cache_secs`; when the Vast API is
+    unreachable the stored endpoint is used unchanged.
+    """
+    instance_id = linked_vast_instance_id(host)
+    if not instance_id or host.type == HostType.VASTAI:
+        return host
+
+    if max_age is None:
+        from ..config import get_config_value
+
+        max_age = float(get_config_value("vast.link_cache_secs", 60) or 0)
+    cached = _VAST_LINK_CACHE.get(instance_id)
+    if cached and time.monotonic() - cached[0] < max_age:
+        resolved = Host.from_dict(host.to_dict())
+        return _apply_connection_targets(resolved, _order_vast_targets(host, cached[1], probe=False), ready_key="vast_ssh_ready")
+    try:
+        return refresh_vast_link(host)
+    except Exception:
+        return host
+
+
 def prepare_runpod_host(
     host: Host,
     *,
@@ -305,11 +401,30 @@ def prepare_runpod_host(
                 return _apply_connection_targets(resolved, ordered_targets, ready_key="runpod_ssh_ready")
 
         if time.monotonic() >= deadline:
-            raise RuntimeError(
+            raise AppError(
                 f"RunPod Pod {pod_id} did not become SSH-ready within "
                 f"{timeout_seconds}s (status: {status or 'unknown'}). "
-                "Make sure the Pod exposes 22/tcp and has a public IP."
+                "Make sure the Pod exposes 22/tcp and has a public IP.",
+                ErrorCode.INSTANCE_NOT_PROVISIONED,
+                provider="runpod",
+                module="host_resolver",
             )
 
         time.sleep(max(poll_seconds, 1))
         pod = client.get_pod(pod_id)
+
+
+def prepare_tailscale_host(host: Host) -> Host:
+    """Point a host linked to a Tailscale node at the node's current address."""
+    node_name = str((host.env_vars or {}).get("tailscale_node", "") or "").strip()
+    if not node_name:
+        return host
+
+    from .tailscale import node_address, resolve_node
+
+    node = resolve_node(node_name)
+    resolved = Host.from_dict(host.to_dict())
+    targets = [{"hostname": node_address(node), "port": host.port, "source": "tailscale"}]
+    if node["dns_name"]:
+        targets.append({"hostname": node["dns_name"], "port": host.port, "source": "tailscale-dns"})
+    return _apply_connection_targets(resolved, targets, ready_key="tailscale_ready")
@@ -0,0 +1,188 @@
+"""sha256 manifests of remote output directories and verification of local copies."""
+
+from __future__ import annotations
+
+import hashlib
+import json
+import os
+import shlex
+from concurrent.futures import ThreadPoolExecutor
+from datetime import datetime
+from typing import Any, Dict, List, Optional
+
+from ..core.models import Host
+from .host_exec import host_exec
+from .tar_stream import shell_path
+
+MANIFEST_VERSION = 1
+DEFAULT_HASH_JOBS = 4
+DEFAULT_REMOTE_TIMEOUT_SECS = 7200
+CHUNK_BYTES = 4 * 1024 * 1024
+
+
+def integrity_settings() -> Dict[str, int]:
+    """`integrity` config: parallel `hash_jobs` and the remote `remote_timeout_secs`."""
+    from ..config import get_config_value
+
+    return {
+        "hash_jobs": max(1, int(get_config_value("integrity.hash_jobs", DEFAULT_HASH_JOBS) or 1)),
+        "remote_timeout_secs": int(
+            get_config_value("integrity.remote_timeout_secs", DEFAULT_REMOTE_TIMEOUT_SECS) or 0
+        ),
+    }
+
+
+def remote_manifest_script(path: str, jobs: int = DEFAULT_HASH_JOBS) -> str:
+    """Print `<sha256>\\t<size>\\t<relative path>` for every regular file under `path`."""
+    # One short printf per file stays atomic on the pipe even with parallel workers.
+    worker = shlex.quote(
+        'for f; do s=$(wc -c < "$f" | tr -d " "); '
+        'h=$($SHA < "$f" | cut -c1-64); printf "%s\\t%s\\t%s\\n" "$h" "$s" "${f#./}"; done'
+    )
+    message = shlex.quote(f"not a directory: {path}")
+    return "\n".join(
+        [
+            f"cd {shell_path(path)} 2>/dev/null || {{ echo {message} >&2; exit 2; }}",
+            "if command -v sha256sum >/dev/null 2>&1; then SHA=sha256sum; else SHA='shasum -a 256'; fi",
+            "export SHA",
+            f"find . -type f -print0 | xargs -0 -n 16 -P {max(1, int(jobs))} sh -c {worker} sh",
+        ]
+    )
+
+
+def parse_manifest_lines(text: str) -> List[Dict[str, Any]]:
+    """Manifest file entries from remote script output, sorted by path."""
+    files = []
+    for line in text.splitlines():
+        digest, size, path = (line.split("\t", 2) + ["", ""])[:3]
+        if len(digest) != 64 or not size.isdigit() or not path:
+            continue
+        files.append({"path": path, "size": int(size), "sha256": digest})
+    return sorted(files, key=lambda item: item["path"])
+
+
+def _manifest(source: str, root: str, files: List[Dict[str, Any]]) -> Dict[str, Any]:
+    return {
+        "version": MANIFEST_VERSION,
+        "source": source,
+        "root": root,
+        "created_at": datetime.now().isoformat(),
+        "file_count": len(files),
+        "total_bytes": sum(item["size"] for item in files),
+        "files": files,
+    }
+
+
+def build_host_manifest(host: Host, path: str, *, jobs: Optional[int] = None, timeout: Optional[int] = None) -> Dict[str, Any]:
+    """Hash every file under `path` on a host (the hashing runs on the host)."""
+    settings = integrity_settings()
+    script = remote_manifest_script(path, jobs or settings["hash_jobs"])
+    result = host_exec(
+        host,
+        f"sh -c {shlex.quote(script)}",
+        timeout=(timeout if timeout is not None else settings["remote_timeout_secs"]) or None,
+    )
+    if not result.success:
+        raise RuntimeError(result.stderr.strip() or f"Manifest failed on {host.display_name} (exit {result.exit_code})")
+    return _manifest(f"{host.display_name}:{path}", path, parse_manifest_lines(result.stdout))
+
+
+def sha256_file(path: str) -> str:
+    digest = hashlib.sha256()
+    with open(path, "rb") as handle:
+        for chunk in iter(lambda: handle.read(CHUNK_BYTES), b""):
+            digest.update(chunk)
+    return digest.hexdigest()
+
+
+def _local_files(root: str) -> List[str]:
+    found = []
+    for directory, _dirs, names in os.walk(root):
+        for name in names:
+            full = os.path.join(directory, name)
+            if os.path.isfile(full) and not os.path.islink(full):
+                found.append(os.path.relpath(full, root).replace(os.sep, "/"))
+    return sorted(found)
+
+
+def build_local_manifest(path: str, *, jobs: Optional[int] = None) -> Dict[str, Any]:
+    """Hash every file under a local directory."""
+    root = os.path.expanduser(path)
+    if not os.path.isdir(root):
+        raise RuntimeError(f"not a directory: {path}")
+    relative = _local_files(root)
+    with ThreadPoolExecutor(max_workers=jobs or integrity_settings()["hash_jobs"]) as pool:
+        digests = list(pool.map(lambda name: sha256_file(os.path.join(root, name)), relative))
+    files = [
+        {"path": name, "size": os.path.getsize(os.path.join(root, name)), "sha256": digest}
+        for name, digest in zip(relative, digests)
+    ]
+    return _manifest(f"local:{path}", path, files)
+
+
+def verify_manifest(manifest: Dict[str, Any], local_dir: str, *, jobs: Optional[int] = None) -> Dict[str, Any]:
+    """Check a local copy against a manifest.
+
+    Returns `missing` and `corrupted` (size or sha256 mismatch) entries, plus
+    `extra` local files the manifest does not list; `ok` ignores extras.
+    """
+    root = os.path.expanduser(local_dir)
+    missing: List[Dict[str, Any]] = []
+    corrupted: List[Dict[str, Any]] = []
+    to_hash: List[Dict[str, Any]] = []
+    for entry in manifest.get("files", []):
+        local = os.path.join(root, entry["path"])
+        if not os.path.isfile(local):
+            missing.append(entry)
+        elif os.path.getsize(local) != entry["size"]:
+            corrupted.append({**entry, "reason": "size", "local_size": os.path.getsize(local)})
+        else:
+            to_hash.append(entry)
+
+    with ThreadPoolExecutor(max_workers=jobs or integrity_settings()["hash_jobs"]) as pool:
+        digests = list(pool.map(lambda item: sha256_file(os.path.join(root, item["path"])), to_hash))
+    for entry, digest in zip(to_hash, digests):
+        if digest != entry["sha256"]:
+            corrupted.append({**entry, "reason": "sha256", "local_sha256": digest})
+
+    listed = {entry["path"] for entry in manifest.get("files", [])}
+    extra = [name for name in _local_files(root) if name not in listed] if os.path.isdir(root) else []
+    return {
+        "ok": not missing and not corrupted,
+        "source": manifest.get("source", ""),
+        "local_dir": local_dir,
+        "checked": len(manifest.get("files", [])),
+        "verified": len(to_hash) - sum(1 for item in corrupted if item["reason"] == "sha256"),
+        "missing": missing,
+        "corrupted": sorted(corrupted, key=lambda item: item["path"]),
+        "extra": extra,
+    }
+
+
+def write_manifest(manifest: Dict[str, Any], path: str) -> None:
+    with open(os.path.expanduser(path), "w", encoding="utf-8") as handle:
+        json.dump(manifest, handle, indent=2)
+        handle.write("\n")
+
+
+def load_manifest(path: str) -> Dict[str, Any]:
+    """Read a saved manifest; raises ValueError when it is not one."""
+    with open(os.path.expanduser(path), "r", encoding="utf-8") as handle:
+        data = json.load(handle)
+    if not isinstance(data, dict) or not isinstance(data.get("files"), list):
+        raise ValueError(f"Not a trainsh manifest: {path}")
+    return data
+
+
+__all__ = [
+    "MANIFEST_VERSION",
+    "build_host_manifest",
+    "build_local_manifest",
+    "integrity_settings",
+    "load_manifest",
+    "parse_manifest_lines",
+    "remote_manifest_script",
+    "sha256_file",
+    "verify_manifest",
+    "write_manifest",
+]
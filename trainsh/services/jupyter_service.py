@@ -0,0 +1,300 @@
+"""State and runtime helpers for managed Jupyter Lab servers on hosts."""
+
+from __future__ import annotations
+
+import json
+import os
+import secrets
+import shlex
+import signal
+from dataclasses import asdict, dataclass
+from datetime import datetime
+from pathlib import Path
+from typing import Any, Dict, Optional
+
+from ..constants import STATE_DIR
+from ..core.models import Host
+from .host_exec import host_exec
+from .tar_stream import shell_path
+from .tunnel import TunnelSpec, find_free_local_port, is_local_port_open, start_local_tunnel
+from .vllm_service import sanitize_service_name, tmux_client_for_host
+
+DEFAULT_PORT = 8888
+
+
+def _now_iso() -> str:
+    return datetime.now().isoformat()
+
+
+def generate_token() -> str:
+    return secrets.token_hex(24)
+
+
+def default_session_name(name: str) -> str:
+    return f"trainsh-jupyter-{sanitize_service_name(name)}"
+
+
+def _servers_dir() -> Path:
+    root = STATE_DIR / "jupyter" / "servers"
+    root.mkdir(parents=True, exist_ok=True)
+    return root
+
+
+@dataclass
+class JupyterServerRecord:
+    """Persisted metadata for one managed Jupyter Lab server."""
+
+    name: str
+    host_name: str
+    host: Dict[str, Any]
+    token: str = ""
+    port: int = DEFAULT_PORT
+    root_dir: str = ""
+    session_name: str = ""
+    command: str = ""
+    status: str = "starting"
+    local_port: int = 0
+    tunnel_pid: int = 0
+    created_at: str = ""
+    updated_at: str = ""
+
+    def __post_init__(self) -> None:
+        self.name = sanitize_service_name(self.name)
+        self.host_name = str(self.host_name or "").strip()
+        self.port = max(1, int(self.port or DEFAULT_PORT))
+        self.local_port = max(0, int(self.local_port or 0))
+        self.tunnel_pid = max(0, int(self.tunnel_pid or 0))
+        self.session_name = str(self.session_name or "").strip() or default_session_name(self.name)
+        if not self.created_at:
+            self.created_at = _now_iso()
+        if not self.updated_at:
+            self.updated_at = self.created_at
+
+    @property
+    def local_url(self) -> str:
+        """Browser URL through the local tunnel, token included; empty without a tunnel."""
+        if not self.local_port:
+            return ""
+        return f"http://127.0.0.1:{self.local_port}/lab?token={self.token}"
+
+    def to_dict(self) -> Dict[str, Any]:
+        return asdict(self)
+
+    @classmethod
+    def from_dict(cls, data: Dict[str, Any]) -> "JupyterServerRecord":
+        return cls(**dict(data or {}))
+
+
+def server_path(name: str) -> Path:
+    return _servers_dir() / f"{sanitize_service_name(name)}.json"
+
+
+def save_server(record: JupyterServerRecord) -> None:
+    """Persist one server record (owner-only: it holds the token)."""
+    record.updated_at = _now_iso()
+    target = server_path(record.name)
+    target.write_text(json.dumps(record.to_dict(), ensure_ascii=False, indent=2) + "\n", encoding="utf-8")
+    os.chmod(target, 0o600)
+
+
+def load_server(name: str) -> Optional[JupyterServerRecord]:
+    target = server_path(name)
+    if not target.exists():
+        return None
+    try:
+        payload = json.loads(target.read_text(encoding="utf-8"))
+    except Exception:
+        return None
+    return JupyterServerRecord.from_dict(payload) if isinstance(payload, dict) else None
+
+
+def delete_server(name: str) -> None:
+    try:
+        server_path(name).unlink()
+    except FileNotFoundError:
+        return
+
+
+def list_servers() -> list[JupyterServerRecord]:
+    records = []
+    for path in sorted(_servers_dir().glob("*.json")):
+        try:
+            payload = json.loads(path.read_text(encoding="utf-8"))
+        except Exception:
+            continue
+        if isinstance(payload, dict):
+            records.append(JupyterServerRecord.from_dict(payload))
+    records.sort(key=lambda item: (item.updated_at, item.name), reverse=True)
+    return records
+
+
+def resolve_server_host(record: JupyterServerRecord) -> Host:
+    """Resolve the server host from config, falling back to the stored snapshot."""
+    try:
+        from ..commands.host import load_hosts
+
+        host = load_hosts().get(record.host_name)
+        if host is not None:
+            return Host.from_dict(host.to_dict())
+    except Exception:
+        pass
+    return Host.from_dict(record.host)
+
+
+def build_jupyter_command(*, port: int, python: str = "") -> str:
+    """Shell command sent into the tmux session; the token comes from $JUPYTER_TOKEN."""
+    launcher = [python, "-m", "jupyterlab"] if python else ["jupyter", "lab"]
+    command = [
+        *launcher,
+        "--no-browser",
+        "--ip=127.0.0.1",
+        f"--port={int(port)}",
+        "--port-retries=0",
+    ]
+    return shlex.join(command)
+
+
+def start_server(
+    host_name: str,
+    host: Host,
+    *,
+    name: Optional[str] = None,
+    port: int = DEFAULT_PORT,
+    root_dir: str = "",
+    python: str = "",
+    token: Optional[str] = None,
+) -> JupyterServerRecord:
+    """Open a tmux session on the host and launch Jupyter Lab in it with a fresh token.
+
+    The token is exported from a private file so it never shows up in the pane.
+    """
+    record = JupyterServerRecord(
+        name=name or host_name,
+        host_name=host_name,
+        host=host.to_dict(),
+        token=token or generate_token(),
+        port=port,
+        root_dir=root_dir,
+        command=build_jupyter_command(port=port, python=python),
+    )
+    token_file = f"$HOME/.cache/tmux-trainsh/jupyter/{record.name}.token"
+    write = host_exec(
+        host,
+        "sh -c " + shlex.quote(
+            f'umask 077; mkdir -p "$(dirname "{token_file}")"; cat > "{token_file}"'
+        ),
+        stdin=record.token,
+        timeout=30,
+    )
+    if not write.success:
+        raise RuntimeError(write.stderr.strip() or "Failed to store the Jupyter token on the host")
+
+    client = tmux_client_for_host(host)
+    if client.has_session(record.session_name):
+        raise RuntimeError(f"Remote tmux session already exists: {record.session_name}")
+    result = client.new_session(record.session_name, detached=True)
+    if result.returncode != 0:
+        raise RuntimeError(result.stderr or "Failed to create remote tmux session")
+    launch = f'export JUPYTER_TOKEN="$(cat "{token_file}")"; {record.command}'
+    if record.root_dir:
+        launch = f"cd {shell_path(record.root_dir)} && {launch}"
+    sent = client.send_keys(record.session_name, launch, enter=True, literal=True)
+    if sent.returncode != 0:
+        client.kill_session(record.session_name)
+        raise RuntimeError(sent.stderr or "Failed to start Jupyter in tmux")
+    save_server(record)
+    return record
+
+
+def server_is_running(record: JupyterServerRecord) -> bool:
+    try:
+        return tmux_client_for_host(resolve_server_host(record)).has_session(record.session_name)
+    except Exception:
+        return False
+
+
+def server_is_ready(record: JupyterServerRecord, *, timeout: int = 10) -> bool:
+    """Probe `/api` (unauthenticated) on the host's loopback interface."""
+    probe = (
+        "python3 -c " + shlex.quote(
+            "import urllib.request; "
+            f"urllib.request.urlopen('http://127.0.0.1:{int(record.port)}/api', timeout=3)"
+        )
+    )
+    result = host_exec(resolve_server_host(record), probe, timeout=max(1, int(timeout)))
+    return bool(result.success)
+
+
+def tunnel_is_alive(record: JupyterServerRecord) -> bool:
+    return bool(record.local_port) and is_local_port_open("127.0.0.1", record.local_port)
+
+
+def open_tunnel(record: JupyterServerRecord, *, local_port: int = 0) -> JupyterServerRecord:
+    """Start a detached SSH tunnel to the server and remember its port and pid."""
+    if tunnel_is_alive(record) and not local_port:
+        return record
+    close_tunnel(record)
+    port = int(local_port or 0)
+    if not port:
+        port = record.port if not is_local_port_open("127.0.0.1", record.port) else find_free_local_port()
+    process = start_local_tunnel(resolve_server_host(record), TunnelSpec(local_port=port, remote_port=record.port))
+    record.local_port = port
+    record.tunnel_pid = int(process.pid)
+    save_server(record)
+    return record
+
+
+def close_tunnel(record: JupyterServerRecord) -> None:
+    if record.tunnel_pid:
+        try:
+            os.kill(record.tunnel_pid, signal.SIGTERM)
+        except (ProcessLookupError, PermissionError):
+            pass
+    record.tunnel_pid = 0
+    record.local_port = 0
+
+
+def stop_server(record: JupyterServerRecord) -> None:
+    """Close the tunnel, kill the tmux session, and forget the server."""
+    close_tunnel(record)
+    host = resolve_server_host(record)
+    client = tmux_client_for_host(host)
+    if client.has_session(record.session_name):
+        result = client.kill_session(record.session_name)
+        if result.returncode != 0:
+            raise RuntimeError(result.stderr or "Failed to stop remote tmux session")
+    host_exec(host, f'rm -f "$HOME/.cache/tmux-trainsh/jupyter/{record.name}.token"', timeout=30)
+    delete_server(record.name)
+
+
+def refresh_status(record: JupyterServerRecord) -> str:
+    """Probe the host and persist `ready`, `starting`, or `stopped`."""
+    running = server_is_running(record)
+    record.status = ("ready" if server_is_ready(record) else "starting") if running else "stopped"
+    if not tunnel_is_alive(record):
+        record.local_port = 0
+        record.tunnel_pid = 0
+    save_server(record)
+    return record.status
+
+
+__all__ = [
+    "DEFAULT_PORT",
+    "JupyterServerRecord",
+    "build_jupyter_command",
+    "close_tunnel",
+    "default_session_name",
+    "delete_server",
+    "generate_token",
+    "list_servers",
+    "load_server",
+    "open_tunnel",
+    "refresh_status",
+    "resolve_server_host",
+    "save_server",
+    "server_is_ready",
+    "server_is_running",
+    "start_server",
+    "stop_server",
+    "tunnel_is_alive",
+]
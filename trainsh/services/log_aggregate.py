@@ -0,0 +1,192 @@
+"""Merge several followed logs into one stream ordered by arrival time.
+
+Sources are `train host follow` records, picked by follow id, host name or the
+recipe job they were started for. Their `log:line` events are merged by
+timestamp and tagged with `host:file` so lines from every node of a job read
+as one log. `read_page` serves the merged history a page at a time;
+`MergedStream` tails the event log for new lines with a bounded buffer: when
+the reader falls more than `max_pending` lines behind, the oldest pending lines
+are dropped and a gap line says how many.
+"""
+
+from __future__ import annotations
+
+import json
+import time
+from collections import Counter
+from dataclasses import dataclass
+from pathlib import Path
+from typing import Any, Callable, Dict, Iterable, Iterator, List, Optional, Sequence, Tuple
+
+from .log_follow import LINE_EVENT, FollowRecord, list_follows
+
+GAP_SOURCE = "trainsh"
+DEFAULT_PAGE_SIZE = 100
+DEFAULT_MAX_PENDING = 1000
+
+
+@dataclass(frozen=True)
+class MergedLine:
+    ts: str
+    source: str
+    line: str
+    follow_id: str = ""
+
+    def format(self) -> str:
+        clock = self.ts[11:23] if len(self.ts) >= 19 else self.ts
+        return f"{clock} [{self.source}] {self.line}"
+
+    def to_dict(self) -> Dict[str, Any]:
+        return {"ts": self.ts, "source": self.source, "follow_id": self.follow_id, "line": self.line}
+
+
+def resolve_sources(selectors: Sequence[str] = (), *, job_id: str = "") -> List[FollowRecord]:
+    """Follows matching any selector (follow id prefix or host name) or started for `job_id`."""
+    chosen = []
+    for record in list_follows():
+        by_selector = any(record.follow_id.startswith(item) or record.host == item for item in selectors)
+        by_job = bool(job_id) and bool(record.job_id) and record.job_id.startswith(job_id)
+        if by_selector or by_job:
+            chosen.append(record)
+    return chosen
+
+
+def source_tags(sources: Iterable[FollowRecord]) -> Dict[str, str]:
+    """`host:file` per follow id, with the id appended where two follows would share a tag."""
+    sources = list(sources)
+    base = {record.follow_id: f"{record.host}:{Path(record.path).name}" for record in sources}
+    counts = Counter(base.values())
+    return {
+        follow_id: tag if counts[tag] == 1 else f"{tag}#{follow_id[:4]}"
+        for follow_id, tag in base.items()
+    }
+
+
+def _runtime_store(store: Any = None) -> Any:
+    if store is not None:
+        return store
+    from ..core.runtime_store import RuntimeStore, get_runtime_state_dir
+
+    return RuntimeStore(get_runtime_state_dir())
+
+
+def _to_line(record: Dict[str, Any], by_run: Dict[str, Tuple[str, str]]) -> Optional[MergedLine]:
+    if record.get("event") != LINE_EVENT:
+        return None
+    match = by_run.get(str(record.get("run_id", "")))
+    if match is None:
+        return None
+    follow_id, tag = match
+    payload = record.get("payload") or {}
+    return MergedLine(str(record.get("ts", "")), tag, str(payload.get("line", "")), follow_id)
+
+
+def _by_run(sources: Sequence[FollowRecord]) -> Dict[str, Tuple[str, str]]:
+    tags = source_tags(sources)
+    return {record.run_id: (record.follow_id, tags[record.follow_id]) for record in sources}
+
+
+def merged_lines(sources: Sequence[FollowRecord], *, store: Any = None) -> List[MergedLine]:
+    """Every line received so far from `sources`, oldest first."""
+    store = _runtime_store(store)
+    by_run = _by_run(sources)
+    lines = []
+    for path in store.event_files():
+        for record in store._iter_jsonl(path):
+            line = _to_line(record, by_run)
+            if line is not None:
+                lines.append(line)
+    # sorted() is stable: lines with the same timestamp keep their arrival order.
+    return sorted(lines, key=lambda item: item.ts)
+
+
+def read_page(
+    sources: Sequence[FollowRecord],
+    page: int = 0,
+    page_size: int = DEFAULT_PAGE_SIZE,
+    *,
+    store: Any = None,
+) -> Tuple[List[MergedLine], int, int]:
+    """(lines, page, pages) of the merged history; page 1 is the oldest, 0 the newest."""
+    lines = merged_lines(sources, store=store)
+    page_size = max(1, int(page_size))
+    pages = max(1, -(-len(lines) // page_size))
+    if page <= 0 or page > pages:
+        page = pages
+    start = (page - 1) * page_size
+    return lines[start:start + page_size], page, pages
+
+
+class MergedStream:
+    """Live merged lines from `sources`, read from the end of the event log onwards."""
+
+    def __init__(
+        self,
+        sources: Sequence[FollowRecord],
+        *,
+        store: Any = None,
+        max_pending: int = DEFAULT_MAX_PENDING,
+        poll_secs: float = 0.5,
+        sleep: Callable[[float], None] = time.sleep,
+    ):
+        self.store = _runtime_store(store)
+        self.by_run = _by_run(sources)
+        self.max_pending = max(1, int(max_pending))
+        self.poll_secs = poll_secs
+        self.sleep = sleep
+        self.dropped = 0
+        path = self.store.events_path
+        self.offset = path.stat().st_size if path.exists() else 0
+
+    def _read_new(self) -> List[Dict[str, Any]]:
+        path: Path = self.store.events_path
+        if not path.exists():
+            return []
+        size = path.stat().st_size
+        if size < self.offset:
+            # The event log was rotated; start over on the new file.
+            self.offset = 0
+        records = []
+        with path.open("rb") as handle:
+            handle.seek(self.offset)
+            data = handle.read()
+        # Keep a partially written last line for the next poll.
+        complete = data[: data.rfind(b"\n") + 1]
+        self.offset += len(complete)
+        for raw in complete.splitlines():
+            try:
+                record = json.loads(raw)
+            except ValueError:
+                continue
+            if isinstance(record, dict):
+                records.append(record)
+        return records
+
+    def poll(self) -> List[MergedLine]:
+        """Lines that arrived since the last poll, with a gap line first if some were dropped."""
+        lines = [line for line in (_to_line(record, self.by_run) for record in self._read_new()) if line is not None]
+        lines.sort(key=lambda item: item.ts)
+        if len(lines) <= self.max_pending:
+            return lines
+        skipped = len(lines) - self.max_pending
+        self.dropped += skipped
+        kept = lines[skipped:]
+        gap = MergedLine(kept[0].ts, GAP_SOURCE, f"... {skipped} lines skipped (reader fell behind)")
+        return [gap, *kept]
+
+    def __iter__(self) -> Iterator[MergedLine]:
+        while True:
+            lines = self.poll()
+            yield from lines
+            if not lines:
+                self.sleep(self.poll_secs)
+
+
+__all__ = [
+    "MergedLine",
+    "MergedStream",
+    "merged_lines",
+    "read_page",
+    "resolve_sources",
+    "source_tags",
+]
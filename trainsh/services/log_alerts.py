@@ -0,0 +1,252 @@
+"""Alert rules evaluated on lines streamed by `train host follow`.
+
+Rules live in config.yaml under `alerts.rules` as `{name: rule}`:
+
+    alerts:
+      rules:
+        oom:
+          pattern: "CUDA out of memory"   # regex; or `keyword:` for a literal
+          cooldown_secs: 600
+          action: cancel                  # notify (default) | command | cancel
+          hosts: [gpu-1, gpu-2]           # optional; all hosts when absent
+
+A rule fires at most once per cooldown for each follow. Every firing is
+recorded as a `log:alert` event under the follow's run id, with the outcome of
+its action.
+"""
+
+from __future__ import annotations
+
+import os
+import re
+import subprocess
+import time
+from dataclasses import dataclass
+from typing import Any, Callable, Dict, List, Optional, Tuple
+
+ALERT_EVENT = "log:alert"
+ACTIONS = ("notify", "command", "cancel")
+DEFAULT_COOLDOWN_SECS = 300
+RULE_NAME_RE = re.compile(r"^[A-Za-z0-9_.-]+$")
+
+
+@dataclass(frozen=True)
+class AlertRule:
+    name: str
+    pattern: str
+    cooldown_secs: float = DEFAULT_COOLDOWN_SECS
+    action: str = "notify"
+    command: str = ""
+    level: str = "warning"
+    hosts: Tuple[str, ...] = ()
+
+    @classmethod
+    def from_dict(cls, name: str, data: Dict[str, Any]) -> "AlertRule":
+        data = dict(data or {})
+        if data.get("keyword"):
+            pattern = re.escape(str(data["keyword"]))
+        else:
+            pattern = str(data.get("pattern") or "")
+        hosts = data.get("hosts") or ()
+        if isinstance(hosts, str):
+            hosts = [hosts]
+        return cls(
+            name=name,
+            pattern=pattern,
+            cooldown_secs=max(0.0, float(data.get("cooldown_secs", DEFAULT_COOLDOWN_SECS) or 0)),
+            action=str(data.get("action") or "notify").strip().lower(),
+            command=str(data.get("command") or ""),
+            level=str(data.get("level") or "warning"),
+            hosts=tuple(str(item) for item in hosts),
+        )
+
+    def problems(self) -> List[str]:
+        found = []
+        if not RULE_NAME_RE.match(self.name):
+            found.append(f"invalid rule name: {self.name!r}")
+        if not self.pattern:
+            found.append(f"{self.name}: needs a pattern or keyword")
+        else:
+            try:
+                re.compile(self.pattern)
+            except re.error as exc:
+                found.append(f"{self.name}: invalid pattern: {exc}")
+        if self.action not in ACTIONS:
+            found.append(f"{self.name}: action must be one of {', '.join(ACTIONS)}")
+        if self.action == "command" and not self.command:
+            found.append(f"{self.name}: action command needs `command`")
+        return found
+
+    def applies_to(self, host: str) -> bool:
+        return not self.hosts or host in self.hosts
+
+
+def configured_rules(config: Optional[Dict[str, Any]] = None) -> List[AlertRule]:
+    """Valid rules from config; broken ones are skipped (see `rule_problems`)."""
+    rules = []
+    for name, data in _rule_dicts(config).items():
+        rule = AlertRule.from_dict(name, data if isinstance(data, dict) else {})
+        if not rule.problems():
+            rules.append(rule)
+    return rules
+
+
+def rule_problems(config: Optional[Dict[str, Any]] = None) -> List[str]:
+    return [
+        problem
+        for name, data in _rule_dicts(config).items()
+        for problem in AlertRule.from_dict(name, data if isinstance(data, dict) else {}).problems()
+    ]
+
+
+def _rule_dicts(config: Optional[Dict[str, Any]]) -> Dict[str, Any]:
+    if config is None:
+        from ..config import load_config
+
+        config = load_config()
+    rules = (config.get("alerts") or {}).get("rules") or {}
+    return {str(name): data for name, data in rules.items()} if isinstance(rules, dict) else {}
+
+
+def save_rule(rule: AlertRule) -> None:
+    from ..config import set_config_value
+
+    problems = rule.problems()
+    if problems:
+        raise ValueError("; ".join(problems))
+    data: Dict[str, Any] = {"pattern": rule.pattern, "cooldown_secs": rule.cooldown_secs, "action": rule.action}
+    if rule.command:
+        data["command"] = rule.command
+    if rule.level != "warning":
+        data["level"] = rule.level
+    if rule.hosts:
+        data["hosts"] = list(rule.hosts)
+    set_config_value(f"alerts.rules.{rule.name}", data)
+
+
+def remove_rule(name: str) -> bool:
+    from ..config import load_config, save_config
+
+    config = load_config()
+    rules = (config.get("alerts") or {}).get("rules") or {}
+    if name not in rules:
+        return False
+    del rules[name]
+    save_config(config)
+    return True
+
+
+@dataclass
+class AlertFiring:
+    rule: str
+    line: str
+    action: str
+    ok: bool
+    detail: str = ""
+
+
+def _notify(rule: AlertRule, line: str, context: Dict[str, str]) -> Tuple[bool, str]:
+    from ..config import load_config
+    from ..utils.notifier import Notifier, normalize_channels
+
+    settings = load_config().get("notifications") or {}
+    if settings.get("enabled") is False:
+        return False, "notifications are disabled"
+    notifier = Notifier(log_callback=lambda _text: None, app_name=str(settings.get("app_name") or "train"))
+    return notifier.notify(
+        title=f"{rule.name} on {context.get('host', '')}",
+        message=line[:500],
+        level=rule.level if rule.level in ("info", "success", "warning", "error") else "warning",
+        channels=normalize_channels(settings.get("channels"), ["log", "system"]),
+        webhook_url=str(settings.get("webhook_url") or "") or None,
+        command=str(settings.get("command") or "") or None,
+        timeout_secs=int(settings.get("timeout_secs", 5) or 5),
+    )
+
+
+def _run_command(rule: AlertRule, line: str, context: Dict[str, str]) -> Tuple[bool, str]:
+    env = dict(os.environ)
+    env.update({
+        "TRAINSH_ALERT_RULE": rule.name,
+        "TRAINSH_ALERT_LINE": line,
+        "TRAINSH_ALERT_HOST": context.get("host", ""),
+        "TRAINSH_ALERT_PATH": context.get("path", ""),
+        "TRAINSH_ALERT_JOB": context.get("job_id", ""),
+    })
+    try:
+        result = subprocess.run(rule.command, shell=True, env=env, capture_output=True, text=True, timeout=60)
+    except subprocess.TimeoutExpired:
+        return False, "command timed out after 60s"
+    if result.returncode != 0:
+        return False, (result.stderr or "").strip() or f"exit {result.returncode}"
+    return True, "command ok"
+
+
+def _cancel_job(rule: AlertRule, line: str, context: Dict[str, str]) -> Tuple[bool, str]:
+    from ..commands.recipe_cancel import cancel_job
+    from ..core.job_state import JobStateManager
+
+    job_id = context.get("job_id", "")
+    if not job_id:
+        return False, "follow is not linked to a job (start it with --job)"
+    jobs = [job for job in JobStateManager().list_running() if job.job_id.startswith(job_id)]
+    if not jobs:
+        return False, f"job {job_id} is not running"
+    cancel_job(jobs[0], close_sessions=False)
+    return True, f"cancelled job {jobs[0].job_id[:8]}"
+
+
+ACTION_HANDLERS: Dict[str, Callable[[AlertRule, str, Dict[str, str]], Tuple[bool, str]]] = {
+    "notify": _notify,
+    "command": _run_command,
+    "cancel": _cancel_job,
+}
+
+
+class AlertEvaluator:
+    """Match lines against rules, respecting each rule's cooldown, and run the actions."""
+
+    def __init__(
+        self,
+        rules: List[AlertRule],
+        context: Optional[Dict[str, str]] = None,
+        *,
+        handlers: Optional[Dict[str, Callable[[AlertRule, str, Dict[str, str]], Tuple[bool, str]]]] = None,
+        clock: Callable[[], float] = time.monotonic,
+    ):
+        self.context = dict(context or {})
+        host = self.context.get("host", "")
+        self.rules = [(rule, re.compile(rule.pattern)) for rule in rules if rule.applies_to(host)]
+        self.handlers = handlers or ACTION_HANDLERS
+        self.clock = clock
+        self.last_fired: Dict[str, float] = {}
+
+    def evaluate(self, line: str) -> List[AlertFiring]:
+        firings = []
+        for rule, pattern in self.rules:
+            if not pattern.search(line):
+                continue
+            now = self.clock()
+            last = self.last_fired.get(rule.name)
+            if last is not None and now - last < rule.cooldown_secs:
+                continue
+            self.last_fired[rule.name] = now
+            try:
+                ok, detail = self.handlers[rule.action](rule, line, self.context)
+            except Exception as exc:
+                ok, detail = False, str(exc)
+            firings.append(AlertFiring(rule.name, line, rule.action, ok, detail))
+        return firings
+
+
+__all__ = [
+    "ACTIONS",
+    "ALERT_EVENT",
+    "AlertEvaluator",
+    "AlertFiring",
+    "AlertRule",
+    "configured_rules",
+    "remove_rule",
+    "rule_problems",
+    "save_rule",
+]
@@ -0,0 +1,301 @@
+"""Follow log files on hosts with `tail -F`, surviving rotation and dropped connections.
+
+Each follow is one background `train host follow run <id>` process recorded
+under the state dir, so a host can have several at once and each stops on its
+own. Lines are appended to ~/.local/share/tmux-trainsh/logs/follows/<id>.log
+and recorded as `log:line` events under run id `follow-<id>` (JSON lines also
+carry their indexed level/step/loss, see services.log_query); tail's notices
+about a replaced or truncated file become `log:rotated` events, and
+`alerts.rules` matches become `log:alert` events (see services.log_alerts).
+"""
+
+from __future__ import annotations
+
+import json
+import os
+import re
+import secrets
+import signal
+import subprocess
+import sys
+import threading
+import time
+from dataclasses import asdict, dataclass
+from datetime import datetime
+from pathlib import Path
+from typing import Any, Callable, Dict, Iterable, List, Optional
+
+from ..constants import LOGS_DIR, STATE_DIR
+from .log_alerts import ALERT_EVENT, AlertEvaluator, configured_rules
+from .log_query import index_fields, parse_json_line
+from .tar_stream import shell_path
+from .terminal_log import RotatingLogWriter
+
+LINE_EVENT = "log:line"
+ROTATED_EVENT = "log:rotated"
+FOLLOW_LOGS_DIR = LOGS_DIR / "follows"
+RECONNECT_DELAY_SECS = 5.0
+# Line counts in the record are refreshed this often for `train host follow list`.
+PROGRESS_EVERY = 100
+# GNU and busybox tail report a replaced or truncated file on stderr.
+_ROTATION_RE = re.compile(r"has been replaced|file truncated|has appeared|has become accessible", re.IGNORECASE)
+
+
+def _now_iso() -> str:
+    return datetime.now().isoformat()
+
+
+def _follows_dir() -> Path:
+    root = STATE_DIR / "log_follows"
+    root.mkdir(parents=True, exist_ok=True)
+    return root
+
+
+@dataclass
+class FollowRecord:
+    """One running (or finished) follow of a remote file."""
+
+    follow_id: str
+    host: str
+    path: str
+    pid: int = 0
+    lines: int = 0
+    rotations: int = 0
+    alerts: int = 0
+    # Recipe job the followed log belongs to; alert rules with `action: cancel` stop it.
+    job_id: str = ""
+    started_at: str = ""
+
+    def __post_init__(self) -> None:
+        if not self.started_at:
+            self.started_at = _now_iso()
+
+    @property
+    def run_id(self) -> str:
+        return f"follow-{self.follow_id}"
+
+    @property
+    def log_path(self) -> Path:
+        return FOLLOW_LOGS_DIR / f"{self.follow_id}.log"
+
+    def to_dict(self) -> Dict[str, Any]:
+        return asdict(self)
+
+    @classmethod
+    def from_dict(cls, data: Dict[str, Any]) -> "FollowRecord":
+        known = {key: value for key, value in dict(data or {}).items() if key in cls.__dataclass_fields__}
+        return cls(**known)
+
+
+def record_path(follow_id: str) -> Path:
+    return _follows_dir() / f"{follow_id}.json"
+
+
+def save_follow(record: FollowRecord) -> None:
+    record_path(record.follow_id).write_text(json.dumps(record.to_dict(), indent=2) + "\n", encoding="utf-8")
+
+
+def load_follow(follow_id: str) -> Optional[FollowRecord]:
+    try:
+        payload = json.loads(record_path(follow_id).read_text(encoding="utf-8"))
+    except (OSError, ValueError):
+        return None
+    return FollowRecord.from_dict(payload) if isinstance(payload, dict) else None
+
+
+def list_follows(host: str = "") -> List[FollowRecord]:
+    records = []
+    for path in sorted(_follows_dir().glob("*.json")):
+        record = load_follow(path.stem)
+        if record is not None and (not host or record.host == host):
+            records.append(record)
+    records.sort(key=lambda item: item.started_at)
+    return records
+
+
+def is_running(record: FollowRecord) -> bool:
+    if record.pid <= 0:
+        return False
+    try:
+        os.kill(record.pid, 0)
+    except ProcessLookupError:
+        return False
+    except PermissionError:
+        return True
+    return True
+
+
+def tail_command(path: str, lines: int = 10) -> str:
+    """Remote command following `path` by name, so a rotated file is reopened."""
+    return f"tail -n {max(0, int(lines))} -F {shell_path(path)}"
+
+
+def _emit(event: str, record: FollowRecord, **payload: Any) -> None:
+    from ..core.runtime_store import RuntimeStore, get_runtime_state_dir
+
+    RuntimeStore(get_runtime_state_dir()).append_event(
+        {
+            "run_id": record.run_id,
+            "event": event,
+            "event_name": event,
+            "step_num": None,
+            "payload": {"host": record.host, "path": record.path, **payload},
+            "ts": _now_iso(),
+        }
+    )
+
+
+class LogFollower:
+    """Run `tail -F` over SSH and hand every line to the follow's log file and events."""
+
+    def __init__(
+        self,
+        record: FollowRecord,
+        *,
+        spawn: Optional[Callable[[List[str]], Any]] = None,
+        emit: Callable[..., None] = _emit,
+        save: Callable[[FollowRecord], None] = save_follow,
+        echo: Optional[Callable[[str], None]] = None,
+        alerts: Optional[Any] = None,
+        sleep: Callable[[float], None] = time.sleep,
+        max_reconnects: int = 0,
+    ):
+        self.record = record
+        self.spawn = spawn or (lambda argv: subprocess.Popen(argv, stdout=subprocess.PIPE, stderr=subprocess.PIPE, text=True, bufsize=1))
+        self.emit = emit
+        self.save = save
+        self.echo = echo
+        self.alerts = alerts
+        self.sleep = sleep
+        self.max_reconnects = max_reconnects
+        self.stopped = False
+
+    def _argv(self, lines: int) -> List[str]:
+        from ..core.executor_utils import _build_ssh_args
+
+        if self.record.host == "local":
+            return ["sh", "-c", tail_command(self.record.path, lines)]
+        return _build_ssh_args(self.record.host, command=tail_command(self.record.path, lines))
+
+    def _watch_stderr(self, stream: Iterable[str]) -> None:
+        for notice in stream:
+            if _ROTATION_RE.search(notice):
+                self.record.rotations += 1
+                self.emit(ROTATED_EVENT, self.record, notice=notice.strip())
+                self.save(self.record)
+
+    def handle_line(self, line: str, writer: Any) -> None:
+        text = line.rstrip("\n")
+        writer.write((text + "\n").encode("utf-8", errors="replace"))
+        self.record.lines += 1
+        parsed = parse_json_line(text)
+        if parsed is None:
+            self.emit(LINE_EVENT, self.record, line=text)
+        else:
+            self.emit(LINE_EVENT, self.record, line=text, fields=index_fields(parsed))
+        if self.alerts is not None:
+            for firing in self.alerts.evaluate(text):
+                self.record.alerts += 1
+                self.emit(ALERT_EVENT, self.record, rule=firing.rule, line=text, action=firing.action, ok=firing.ok, detail=firing.detail)
+                self.save(self.record)
+                if self.echo:
+                    self.echo(f"[alert {firing.rule}] {firing.action}: {firing.detail}")
+        if self.record.lines % PROGRESS_EVERY == 0:
+            self.save(self.record)
+        if self.echo:
+            self.echo(text)
+
+    def run(self, lines: int = 10) -> int:
+        """Follow until stopped or, with `max_reconnects`, until SSH keeps failing; return the last exit code."""
+        writer = RotatingLogWriter(self.record.log_path)
+        reconnects = 0
+        code = 0
+        try:
+            while not self.stopped:
+                process = self.spawn(self._argv(lines))
+                watcher = threading.Thread(target=self._watch_stderr, args=(process.stderr,), daemon=True)
+                watcher.start()
+                for line in process.stdout:
+                    self.handle_line(line, writer)
+                code = process.wait()
+                watcher.join(timeout=1)
+                if self.stopped or code != 255:
+                    break
+                reconnects += 1
+                if self.max_reconnects and reconnects > self.max_reconnects:
+                    break
+                # Already-seen lines must not repeat after reconnecting.
+                lines = 0
+                self.sleep(RECONNECT_DELAY_SECS)
+        finally:
+            writer.close()
+            self.save(self.record)
+        return code
+
+
+def alert_evaluator(record: FollowRecord) -> AlertEvaluator:
+    """Evaluator for the configured `alerts.rules` that apply to this follow's host."""
+    context = {"host": record.host, "path": record.path, "job_id": record.job_id, "follow_id": record.follow_id}
+    return AlertEvaluator(configured_rules(), context)
+
+
+def new_follow(host: str, path: str, *, pid: int = 0, job_id: str = "") -> FollowRecord:
+    """Record a follow under a fresh id."""
+    record = FollowRecord(follow_id=secrets.token_hex(4), host=host, path=path, pid=pid, job_id=job_id)
+    save_follow(record)
+    return record
+
+
+def start_follow(host: str, path: str, *, lines: int = 10, job_id: str = "", alerts: bool = True) -> FollowRecord:
+    """Start a detached follower process and return its record."""
+    record = new_follow(host, path, job_id=job_id)
+    FOLLOW_LOGS_DIR.mkdir(parents=True, exist_ok=True)
+    process = subprocess.Popen(
+        [sys.executable, "-m", "trainsh", "host", "follow", "run", record.follow_id, "-n", str(lines)]
+        + ([] if alerts else ["--no-alerts"]),
+        stdin=subprocess.DEVNULL,
+        stdout=subprocess.DEVNULL,
+        stderr=subprocess.DEVNULL,
+        start_new_session=True,
+    )
+    record.pid = process.pid
+    save_follow(record)
+    return record
+
+
+def stop_follow(record: FollowRecord) -> bool:
+    """Stop one follower; True when a running process was signalled."""
+    stopped = False
+    if is_running(record):
+        try:
+            os.killpg(record.pid, signal.SIGTERM)
+            stopped = True
+        except (ProcessLookupError, PermissionError):
+            stopped = False
+    record.pid = 0
+    save_follow(record)
+    return stopped
+
+
+def remove_follow(record: FollowRecord) -> None:
+    stop_follow(record)
+    record_path(record.follow_id).unlink(missing_ok=True)
+
+
+__all__ = [
+    "FOLLOW_LOGS_DIR",
+    "FollowRecord",
+    "alert_evaluator",
+    "LINE_EVENT",
+    "LogFollower",
+    "ROTATED_EVENT",
+    "is_running",
+    "list_follows",
+    "load_follow",
+    "new_follow",
+    "remove_follow",
+    "save_follow",
+    "start_follow",
+    "stop_follow",
+    "tail_command",
+]
@@ -0,0 +1,237 @@
+"""Parse JSON-lines logs and filter them by field.
+
+Training frameworks often log one JSON object per line. `index_fields` pulls the
+common fields out under stable names (`level`, `step`, `loss`, `message`),
+whatever the framework calls them, and `log_query` filters a followed log with
+expressions such as `level>=warning and loss<2.5` or `message~nan`. Each entry
+carries a color hint for its level so callers can highlight it.
+"""
+
+from __future__ import annotations
+
+import json
+import re
+from dataclasses import dataclass, field
+from pathlib import Path
+from typing import Any, Callable, Dict, Iterable, List, Optional, Tuple
+
+FIELD_ALIASES: Dict[str, Tuple[str, ...]] = {
+    "level": ("level", "levelname", "lvl", "severity", "log_level"),
+    "step": ("step", "global_step", "iteration", "iter", "_step"),
+    "loss": ("loss", "train_loss", "train/loss", "training_loss"),
+    "message": ("message", "msg", "event", "text"),
+}
+LEVEL_RANKS = {"trace": 0, "debug": 10, "info": 20, "notice": 25, "warning": 30, "warn": 30, "error": 40, "critical": 50, "fatal": 50}
+LEVEL_COLORS = {"debug": "dim", "info": "", "warning": "yellow", "error": "red", "critical": "red"}
+_ANSI_CODES = {"dim": "2", "yellow": "33", "red": "31"}
+_CLAUSE_RE = re.compile(r"^\s*([A-Za-z_][\w./-]*)\s*(==|!=|>=|<=|=|>|<|~)\s*(.+?)\s*$")
+
+
+def parse_json_line(text: str) -> Optional[Dict[str, Any]]:
+    """The object on a JSON-lines line, or None for plain text."""
+    text = text.strip()
+    if not text.startswith("{"):
+        return None
+    try:
+        value = json.loads(text)
+    except ValueError:
+        return None
+    return value if isinstance(value, dict) else None
+
+
+def _number(value: Any) -> Optional[float]:
+    if isinstance(value, bool):
+        return None
+    try:
+        return float(value)
+    except (TypeError, ValueError):
+        return None
+
+
+def normalize_level(value: Any) -> str:
+    level = str(value or "").strip().lower()
+    if level == "warn":
+        return "warning"
+    if level == "fatal":
+        return "critical"
+    return level
+
+
+def index_fields(record: Dict[str, Any]) -> Dict[str, Any]:
+    """`level`, `step`, `loss` and `message` of one record, under those names, when present."""
+    fields: Dict[str, Any] = {}
+    for name, aliases in FIELD_ALIASES.items():
+        for alias in aliases:
+            if alias in record and record[alias] is not None:
+                fields[name] = record[alias]
+                break
+    if "level" in fields:
+        fields["level"] = normalize_level(fields["level"])
+    for name in ("step", "loss"):
+        if name in fields:
+            number = _number(fields[name])
+            if number is None:
+                del fields[name]
+            else:
+                fields[name] = int(number) if name == "step" and number.is_integer() else number
+    return fields
+
+
+def level_color(level: str) -> str:
+    """Color hint (`red`, `yellow`, `dim` or empty) for a level."""
+    return LEVEL_COLORS.get(normalize_level(level), "")
+
+
+def colorize(text: str, color: str) -> str:
+    code = _ANSI_CODES.get(color)
+    return f"\x1b[{code}m{text}\x1b[0m" if code else text
+
+
+@dataclass
+class LogEntry:
+    """One line of a log: its position, raw text, parsed record and indexed fields."""
+
+    number: int
+    text: str
+    record: Optional[Dict[str, Any]] = None
+    fields: Dict[str, Any] = field(default_factory=dict)
+
+    @property
+    def color(self) -> str:
+        return level_color(self.fields.get("level", ""))
+
+    def value(self, name: str) -> Any:
+        """Indexed field, then record key, then dotted path into the record (`metrics.loss`)."""
+        if name in self.fields:
+            return self.fields[name]
+        if self.record is None:
+            return self.text if name == "message" else None
+        if name in self.record:
+            return self.record[name]
+        value: Any = self.record
+        for part in name.split("."):
+            if not isinstance(value, dict) or part not in value:
+                return None
+            value = value[part]
+        return value
+
+    def to_dict(self) -> Dict[str, Any]:
+        return {"line": self.number, **self.fields, "color": self.color, "record": self.record, "text": self.text}
+
+
+def _compare(op: str, actual: Any, expected: str, name: str) -> bool:
+    if actual is None:
+        return op == "!="
+    if op == "~":
+        return re.search(expected, str(actual), re.IGNORECASE) is not None
+    if name == "level":
+        left = LEVEL_RANKS.get(normalize_level(actual))
+        right = LEVEL_RANKS.get(normalize_level(expected))
+    else:
+        left, right = _number(actual), _number(expected)
+    if left is None or right is None:
+        left, right = str(actual), expected
+    if op in ("==", "="):
+        return left == right
+    if op == "!=":
+        return left != right
+    try:
+        return {">": left > right, "<": left < right, ">=": left >= right, "<=": left <= right}[op]
+    except TypeError:
+        return False
+
+
+def compile_filter(expr: str) -> Callable[[LogEntry], bool]:
+    """Predicate for `field OP value [and field OP value ...]`; OP is ==, !=, >, <, >=, <= or ~ (regex)."""
+    expr = (expr or "").strip()
+    if not expr:
+        return lambda entry: True
+    clauses = []
+    for part in re.split(r"\s+and\s+", expr):
+        match = _CLAUSE_RE.match(part)
+        if not match:
+            raise ValueError(f"invalid filter clause: {part!r} (use field OP value, e.g. loss<2.5)")
+        name, op, value = match.groups()
+        value = value.strip().strip("'\"")
+        if op == "~":
+            try:
+                re.compile(value)
+            except re.error as exc:
+                raise ValueError(f"invalid pattern in {part!r}: {exc}") from exc
+        clauses.append((name, op, value))
+    return lambda entry: all(_compare(op, entry.value(name), value, name) for name, op, value in clauses)
+
+
+def parse_step_range(text: str) -> Tuple[Optional[int], Optional[int]]:
+    """`START:END`, `START:` or `:END` (inclusive) as a step range."""
+    start, sep, end = (text or "").partition(":")
+    if not sep:
+        raise ValueError(f"invalid step range: {text!r} (use START:END)")
+    try:
+        return (int(start) if start.strip() else None, int(end) if end.strip() else None)
+    except ValueError:
+        raise ValueError(f"invalid step range: {text!r} (use START:END)") from None
+
+
+def read_entries(lines: Iterable[str]) -> List[LogEntry]:
+    entries = []
+    for number, text in enumerate(lines, start=1):
+        text = text.rstrip("\n")
+        record = parse_json_line(text)
+        entries.append(LogEntry(number, text, record, index_fields(record) if record is not None else {}))
+    return entries
+
+
+def query_entries(
+    entries: Iterable[LogEntry],
+    filter_expr: str = "",
+    step_range: Tuple[Optional[int], Optional[int]] = (None, None),
+    *,
+    limit: int = 0,
+) -> List[LogEntry]:
+    """Entries matching `filter_expr` within `step_range`; the last `limit` when > 0."""
+    matches = compile_filter(filter_expr)
+    start, end = step_range
+    selected = []
+    for entry in entries:
+        if start is not None or end is not None:
+            step = entry.fields.get("step")
+            if step is None or (start is not None and step < start) or (end is not None and step > end):
+                continue
+        if matches(entry):
+            selected.append(entry)
+    return selected[-limit:] if limit > 0 else selected
+
+
+def log_query(
+    follow_id: str,
+    filter_expr: str = "",
+    step_range: Tuple[Optional[int], Optional[int]] = (None, None),
+    *,
+    limit: int = 0,
+) -> List[LogEntry]:
+    """Query the lines received by one `train host follow`."""
+    from .log_follow import load_follow
+
+    record = load_follow(follow_id)
+    if record is None:
+        raise KeyError(f"unknown follow: {follow_id}")
+    path = Path(record.log_path)
+    if not path.exists():
+        return []
+    with path.open(encoding="utf-8", errors="replace") as handle:
+        return query_entries(read_entries(handle), filter_expr, step_range, limit=limit)
+
+
+__all__ = [
+    "LogEntry",
+    "colorize",
+    "compile_filter",
+    "index_fields",
+    "level_color",
+    "log_query",
+    "parse_json_line",
+    "parse_step_range",
+    "query_entries",
+    "read_entries",
+]
@@ -0,0 +1,52 @@
+"""Keep the runtime event log from growing without bound.
+
+`apply_log_policy` rotates `events.jsonl` past `logs.rotate_bytes` and prunes
+finished runs by `logs.retention_days` / `logs.keep_runs`. It runs when a
+recipe starts and on demand via `train recipe logs --prune`.
+"""
+
+from __future__ import annotations
+
+from pathlib import Path
+from typing import Any, Dict, Optional
+
+from ..core.runtime_store import RuntimeStore
+
+
+def log_policy(config: Optional[Dict[str, Any]] = None) -> Dict[str, float]:
+    """`logs.*` rotation and retention settings with bad values treated as 0."""
+    if config is None:
+        from ..config import load_config
+
+        config = load_config()
+    logs_cfg = (config or {}).get("logs", {}) or {}
+    policy: Dict[str, float] = {}
+    for key in ("rotate_bytes", "rotate_keep", "retention_days", "keep_runs"):
+        try:
+            policy[key] = max(0.0, float(logs_cfg.get(key, 0) or 0))
+        except (TypeError, ValueError):
+            policy[key] = 0.0
+    return policy
+
+
+def apply_log_policy(
+    *,
+    root: Optional[Path] = None,
+    config: Optional[Dict[str, Any]] = None,
+    dry_run: bool = False,
+) -> Dict[str, Any]:
+    """Rotate and prune the runtime store; return what was (or would be) done."""
+    policy = log_policy(config)
+    store = RuntimeStore(root)
+    pruned = store.prune_runs(
+        older_than_days=policy["retention_days"],
+        keep_last=int(policy["keep_runs"]),
+        dry_run=dry_run,
+    )
+    rotated = False
+    if not dry_run:
+        rotated = store.rotate_events(max_bytes=int(policy["rotate_bytes"]), keep=int(policy["rotate_keep"]))
+    return {"pruned": pruned, "rotated": rotated}
+
+
+__all__ = ["apply_log_policy", "log_policy"]
@@ -0,0 +1,225 @@
+"""MLflow tracking over its REST API: one MLflow run per recipe execution."""
+
+from __future__ import annotations
+
+import glob
+import json
+import re
+import time
+from pathlib import Path
+from typing import Any, Callable, Dict, Iterable, List, Optional, Tuple
+from urllib.error import HTTPError, URLError
+from urllib.parse import quote, urlencode
+from urllib.request import Request, urlopen
+
+from ..constants import SecretKeys
+from ..core.errors import AppError, classify_http
+
+DEFAULT_METRICS = ("loss",)
+# MLflow rejects params longer than this on older servers, and batches over 1000 metrics.
+MAX_PARAM_CHARS = 500
+MAX_BATCH_METRICS = 1000
+_NUMBER = r"[-+]?(?:\d+\.?\d*|\.\d+)(?:[eE][-+]?\d+)?"
+
+
+class MlflowAPIError(AppError):
+    """Exception raised for MLflow tracking server errors."""
+
+    def __init__(self, status_code: int, message: str):
+        self.status_code = status_code
+        self.message = message
+        super().__init__(
+            f"MLflow API error ({status_code}): {message}",
+            classify_http(status_code, message),
+            provider="mlflow",
+            module="mlflow_tracking",
+            details={"status_code": status_code},
+        )
+
+
+def tracking_settings(config: Dict[str, Any], recipe_options: Optional[Dict[str, Any]] = None) -> Dict[str, Any]:
+    """The `tracking` config section overlaid with the recipe's `recipe.tracking(...)` options."""
+    settings: Dict[str, Any] = {"mlflow_uri": "", "experiment": "", "metrics": list(DEFAULT_METRICS), "artifacts": [], "enabled": True}
+    for source in ((config or {}).get("tracking") or {}, recipe_options or {}):
+        settings.update({key: value for key, value in source.items() if value is not None})
+    settings["mlflow_uri"] = str(settings.get("mlflow_uri") or "").rstrip("/")
+    settings["enabled"] = bool(settings.get("enabled")) and bool(settings["mlflow_uri"])
+    return settings
+
+
+def parse_metrics(text: str, keys: Iterable[str] = DEFAULT_METRICS) -> Dict[str, List[float]]:
+    """Every `key=value` / `key: value` reading of each metric in log text, in order."""
+    series: Dict[str, List[float]] = {}
+    for key in keys:
+        pattern = re.compile(rf"(?<![\w/.-]){re.escape(str(key))}\s*[=:]\s*({_NUMBER})")
+        values = [float(match.group(1)) for match in pattern.finditer(text or "")]
+        if values:
+            series[str(key)] = values
+    return series
+
+
+def collect_artifacts(patterns: Iterable[str], base: Optional[Path] = None) -> List[Path]:
+    """Local files matching the artifact globs (relative ones resolve against `base`)."""
+    files: List[Path] = []
+    for pattern in patterns:
+        path = Path(str(pattern)).expanduser()
+        if not path.is_absolute() and base is not None:
+            path = base / path
+        for match in sorted(glob.glob(str(path), recursive=True)):
+            candidate = Path(match)
+            if candidate.is_file() and candidate not in files:
+                files.append(candidate)
+    return files
+
+
+class MlflowClient:
+    """Minimal MLflow REST client for experiments, runs, batches and proxied artifacts."""
+
+    def __init__(self, tracking_uri: str, token: str = ""):
+        self.tracking_uri = str(tracking_uri or "").rstrip("/")
+        self.token = str(token or "").strip()
+
+    def _request(self, method: str, path: str, body: Any = None, *, params: Optional[Dict[str, str]] = None) -> Dict[str, Any]:
+        url = f"{self.tracking_uri}/{path.lstrip('/')}"
+        if params:
+            url = f"{url}?{urlencode(params)}"
+        headers = {"Content-Type": "application/json"}
+        if self.token:
+            headers["Authorization"] = f"Bearer {self.token}"
+        data = None
+        if isinstance(body, bytes):
+            data = body
+            headers["Content-Type"] = "application/octet-stream"
+        elif body is not None:
+            data = json.dumps(body).encode("utf-8")
+        req = Request(url, data=data, headers=headers, method=method)
+        try:
+            with urlopen(req, timeout=60) as response:
+                payload = response.read().decode("utf-8")
+        except HTTPError as exc:
+            error_body = exc.read().decode("utf-8") if exc.fp else ""
+            raise MlflowAPIError(exc.code, error_body)
+        except URLError as exc:
+            raise MlflowAPIError(0, str(exc.reason))
+        return json.loads(payload) if payload.strip() else {}
+
+    def experiment_id(self, name: str) -> str:
+        """Id of the named experiment, creating it when missing."""
+        try:
+            found = self._request("GET", "api/2.0/mlflow/experiments/get-by-name", params={"experiment_name": name})
+            return str(found["experiment"]["experiment_id"])
+        except MlflowAPIError as exc:
+            if exc.status_code != 404:
+                raise
+        created = self._request("POST", "api/2.0/mlflow/experiments/create", {"name": name})
+        return str(created["experiment_id"])
+
+    def create_run(self, experiment_id: str, run_name: str, *, start_ms: int, tags: Dict[str, str]) -> Tuple[str, str]:
+        """Start a run; returns (run id, artifact URI)."""
+        body = {
+            "experiment_id": experiment_id,
+            "run_name": run_name,
+            "start_time": int(start_ms),
+            "tags": [{"key": key, "value": str(value)} for key, value in tags.items()],
+        }
+        info = self._request("POST", "api/2.0/mlflow/runs/create", body)["run"]["info"]
+        return str(info["run_id"]), str(info.get("artifact_uri", ""))
+
+    def log_batch(
+        self,
+        run_id: str,
+        *,
+        params: Optional[Dict[str, str]] = None,
+        metrics: Optional[List[Dict[str, Any]]] = None,
+        tags: Optional[Dict[str, str]] = None,
+    ) -> None:
+        metrics = list(metrics or [])
+        first = True
+        while first or metrics:
+            chunk, metrics = metrics[:MAX_BATCH_METRICS], metrics[MAX_BATCH_METRICS:]
+            body: Dict[str, Any] = {"run_id": run_id, "metrics": chunk}
+            if first:
+                body["params"] = [{"key": key, "value": str(value)[:MAX_PARAM_CHARS]} for key, value in (params or {}).items()]
+                body["tags"] = [{"key": key, "value": str(value)} for key, value in (tags or {}).items()]
+            self._request("POST", "api/2.0/mlflow/runs/log-batch", body)
+            first = False
+
+    def log_artifact(self, artifact_uri: str, local_path: Path) -> None:
+        """Upload one file through the server's artifact proxy (`mlflow-artifacts:` URIs only)."""
+        prefix = "mlflow-artifacts:"
+        if not artifact_uri.startswith(prefix):
+            raise MlflowAPIError(400, f"artifact store {artifact_uri} is not proxied by the tracking server")
+        root = artifact_uri[len(prefix):].lstrip("/")
+        target = quote(f"{root}/{local_path.name}")
+        self._request("PUT", f"api/2.0/mlflow-artifacts/artifacts/{target}", local_path.read_bytes())
+
+    def finish_run(self, run_id: str, status: str, *, end_ms: int) -> None:
+        self._request("POST", "api/2.0/mlflow/runs/update", {"run_id": run_id, "status": status, "end_time": int(end_ms)})
+
+
+def get_mlflow_client(tracking_uri: str) -> MlflowClient:
+    """Build a client; the MLFLOW_TRACKING_TOKEN secret is sent as a bearer token when set."""
+    from ..core.secrets import get_secrets_manager
+
+    return MlflowClient(tracking_uri, get_secrets_manager().get(SecretKeys.MLFLOW_TRACKING_TOKEN) or "")
+
+
+class MlflowRunTracker:
+    """Register one execution as an MLflow run and close it with metrics and artifacts."""
+
+    def __init__(self, client: MlflowClient, settings: Dict[str, Any], *, log: Callable[[str], None] = print):
+        self.client = client
+        self.settings = settings
+        self.log = log
+        self.run_id = ""
+        self.artifact_uri = ""
+
+    def start(self, recipe_name: str, job_id: str, variables: Dict[str, Any], hosts: Dict[str, str]) -> str:
+        experiment = self.client.experiment_id(str(self.settings.get("experiment") or recipe_name))
+        tags = {"trainsh.job_id": job_id, "trainsh.recipe": recipe_name, "mlflow.runName": f"{recipe_name}-{job_id[:8]}"}
+        tags.update({f"trainsh.host.{name}": spec for name, spec in hosts.items()})
+        self.run_id, self.artifact_uri = self.client.create_run(
+            experiment, tags["mlflow.runName"], start_ms=int(time.time() * 1000), tags=tags
+        )
+        if variables:
+            self.client.log_batch(self.run_id, params={str(key): value for key, value in variables.items()})
+        return self.run_id
+
+    def finish(self, *, success: bool, outcome: str, log_text: str, base_dir: Optional[Path] = None) -> Dict[str, Any]:
+        """Log parsed metrics and artifacts, then mark the run FINISHED/FAILED/KILLED."""
+        now_ms = int(time.time() * 1000)
+        series = parse_metrics(log_text, self.settings.get("metrics") or DEFAULT_METRICS)
+        metrics = [
+            {"key": key, "value": value, "timestamp": now_ms, "step": index}
+            for key, values in series.items()
+            for index, value in enumerate(values)
+        ]
+        if metrics:
+            self.client.log_batch(self.run_id, metrics=metrics, tags={"trainsh.outcome": outcome})
+        uploaded = 0
+        for path in collect_artifacts(self.settings.get("artifacts") or [], base_dir):
+            try:
+                self.client.log_artifact(self.artifact_uri, path)
+                uploaded += 1
+            except (MlflowAPIError, OSError) as exc:
+                self.log(f"⚠ MLflow artifact {path} not uploaded: {exc}")
+        status = "FINISHED" if success else "KILLED" if outcome == "cancelled" else "FAILED"
+        self.client.finish_run(self.run_id, status, end_ms=now_ms)
+        return {
+            "run_id": self.run_id,
+            "status": status,
+            "metrics": {key: values[-1] for key, values in series.items()},
+            "artifacts": uploaded,
+        }
+
+
+__all__ = [
+    "DEFAULT_METRICS",
+    "MlflowAPIError",
+    "MlflowClient",
+    "MlflowRunTracker",
+    "collect_artifacts",
+    "get_mlflow_client",
+    "parse_metrics",
+    "tracking_settings",
+]
@@ -0,0 +1,69 @@
+"""Run Jupyter notebooks as recipe steps with papermill.
+
+The notebook is staged on the host by content hash (like step scripts), run with
+`papermill` so parameters land in its `parameters` cell, and the executed copy
+comes back as an artifact next to the job's step logs. papermill logs one
+`Executing Cell N` line per cell, which `cell_progress` turns into progress.
+"""
+
+from __future__ import annotations
+
+import json
+import re
+import shlex
+from pathlib import Path
+from typing import Any, Dict, Optional
+
+NOTEBOOK_OUTPUT_DIR = ".cache/trainsh/notebooks"
+_CELL_RE = re.compile(r"Executing Cell (\d+)")
+
+
+def count_code_cells(path: str) -> int:
+    """Code cells in a notebook file; 0 when it cannot be read."""
+    try:
+        data = json.loads(Path(path).read_text(encoding="utf-8"))
+    except (OSError, ValueError):
+        return 0
+    cells = data.get("cells") if isinstance(data, dict) else None
+    return sum(1 for cell in cells or [] if isinstance(cell, dict) and cell.get("cell_type") == "code")
+
+
+def cell_progress(line: str) -> Optional[int]:
+    """Cell number from a papermill log line, or None."""
+    match = _CELL_RE.search(line)
+    return int(match.group(1)) if match else None
+
+
+def _home_path(path: str) -> str:
+    """Shell word for a path relative to $HOME (absolute paths stay as they are)."""
+    if path.startswith("/"):
+        return shlex.quote(path)
+    return f'"$HOME"/{shlex.quote(path)}'
+
+
+def papermill_command(
+    notebook: str,
+    output: str,
+    *,
+    parameters: Optional[Dict[str, Any]] = None,
+    kernel: str = "",
+    cwd: str = "",
+    papermill: str = "papermill",
+) -> str:
+    """Shell command running `notebook` into `output`; both relative to $HOME unless absolute.
+
+    Parameters go as one JSON `-y` document so numbers and booleans keep their type.
+    """
+    parts = [papermill, _home_path(notebook), _home_path(output), "--log-output", "--log-level", "INFO", "--no-progress-bar"]
+    if kernel:
+        parts += ["-k", shlex.quote(kernel)]
+    if parameters:
+        parts += ["-y", shlex.quote(json.dumps(parameters, ensure_ascii=False))]
+    if cwd:
+        parts += ["--cwd", shlex.quote(cwd)]
+    output_dir = output.rsplit("/", 1)[0] if "/" in output else ""
+    command = " ".join(parts)
+    return f"mkdir -p {_home_path(output_dir)} && {command}" if output_dir else command
+
+
+__all__ = ["NOTEBOOK_OUTPUT_DIR", "cell_progress", "count_code_cells", "papermill_command"]
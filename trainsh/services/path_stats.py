@@ -0,0 +1,205 @@
+"""Total size, file count and largest entries of one path, remote or local.
+
+`host_path_stats` runs a bounded `du`/`find` on the host: each tool gets
+`path_stats.timeout_secs` (via `timeout` where the host has it) and a run
+that hits the limit comes back with `complete: False` and whatever it
+counted. The file browser shows it for a directory, and `train transfer
+--preflight` uses it to size the source, check free space at the
+destination and estimate how long the copy takes.
+"""
+
+from __future__ import annotations
+
+import heapq
+import os
+import time
+from typing import Any, Dict, List, Optional, Union
+
+from ..core.models import Host
+
+from .host_exec import host_exec
+from .tar_stream import shell_path
+
+DEFAULT_TIMEOUT_SECS = 60
+DEFAULT_TOP = 10
+DEFAULT_ESTIMATE_MIBPS = 50.0
+MISSING_EXIT = 3
+
+
+def path_stats_timeout() -> int:
+    from ..config import get_config_value
+
+    try:
+        return max(1, int(get_config_value("path_stats.timeout_secs", DEFAULT_TIMEOUT_SECS) or DEFAULT_TIMEOUT_SECS))
+    except (TypeError, ValueError):
+        return DEFAULT_TIMEOUT_SECS
+
+
+def stats_command(path: str, top: int = DEFAULT_TOP, budget: int = DEFAULT_TIMEOUT_SECS) -> str:
+    """Shell script printing `kind=`, `size_kb=`, `files=` and then `<KiB>\\t<path>` entry lines.
+
+    Every tool that hits `budget` reports `partial=1` on stderr.
+    """
+    return "\n".join(
+        [
+            f"P={shell_path(path.rstrip('/') or '/')}",
+            f'[ -e "$P" ] || {{ echo "No such path: $P" >&2; exit {MISSING_EXIT}; }}',
+            f"run() {{ if command -v timeout >/dev/null 2>&1; then timeout {int(budget)} \"$@\" 2>/dev/null; "
+            'else "$@" 2>/dev/null; fi; [ $? -eq 124 ] && echo partial=1 >&2; return 0; }',
+            'if [ -d "$P" ]; then echo kind=dir; else echo kind=file; fi',
+            'echo "size_kb=$(run du -x -k -s "$P" | cut -f1)"',
+            "echo \"files=$(run find \"$P\" -xdev -type f -print0 | tr -dc '\\000' | wc -c)\"",
+            f'if [ -d "$P" ]; then run du -x -k -a -d 1 "$P" | sort -rn | head -n {int(top) + 1}; fi',
+        ]
+    )
+
+
+def _int(value: str) -> int:
+    text = value.strip()
+    return int(text) if text.isdigit() else 0
+
+
+def parse_stats_output(text: str, path: str, *, top: int = DEFAULT_TOP) -> Dict[str, Any]:
+    """Turn `stats_command` output into `{kind, size, files, largest}` (sizes in bytes)."""
+    result: Dict[str, Any] = {"kind": "", "size": 0, "files": 0, "largest": []}
+    entries: List[Dict[str, Any]] = []
+    for line in text.splitlines():
+        size, sep, entry_path = line.partition("\t")
+        if sep and size.strip().isdigit():
+            entries.append({"path": entry_path, "name": entry_path.rstrip("/").rsplit("/", 1)[-1], "size": int(size) * 1024})
+            continue
+        key, sep, value = line.partition("=")
+        if key == "kind":
+            result["kind"] = value.strip()
+        elif key == "size_kb":
+            result["size"] = _int(value) * 1024
+        elif key == "files":
+            result["files"] = _int(value)
+    if entries:
+        # `du -d 1` also lists the directory itself: the shortest path.
+        root = min(entries, key=lambda entry: len(entry["path"]))
+        entries = [entry for entry in entries if entry is not root]
+    result["largest"] = entries[:top]
+    if result["kind"] == "file" and not result["files"]:
+        result["files"] = 1
+    return {"path": path, **result}
+
+
+def host_path_stats(host_id: Union[str, Host], path: str, *, top: int = DEFAULT_TOP, timeout: Optional[int] = None) -> Dict[str, Any]:
+    """Size, file count and `top` largest children of `path` on a host.
+
+    Raises `FileNotFoundError` for a missing path and `RuntimeError` when the
+    host cannot be reached.
+    """
+    budget = int(timeout or path_stats_timeout())
+    result = host_exec(host_id, stats_command(path, top, budget), timeout=budget * 3 + 30)
+    if result.exit_code == MISSING_EXIT:
+        raise FileNotFoundError(result.stderr.strip() or f"No such path on {host_id}: {path}")
+    if not result.success:
+        raise RuntimeError(result.stderr.strip() or f"Could not measure {path} on {host_id} (exit {result.exit_code})")
+    stats = parse_stats_output(result.stdout, path, top=top)
+    return {"host": result.host, **stats, "complete": "partial=1" not in result.stderr, "elapsed_ms": result.duration_ms}
+
+
+def local_path_stats(path: str, *, top: int = DEFAULT_TOP, timeout: Optional[int] = None) -> Dict[str, Any]:
+    """`host_path_stats` for a local path (same device, symlinks not followed)."""
+    root = os.path.expanduser(path)
+    if not os.path.lexists(root):
+        raise FileNotFoundError(f"No such path: {path}")
+    started = time.monotonic()
+    deadline = started + int(timeout or path_stats_timeout())
+    if not os.path.isdir(root) or os.path.islink(root):
+        size = os.lstat(root).st_size
+        return {"host": "", "path": path, "kind": "file", "size": size, "files": 1, "largest": [], "complete": True, "elapsed_ms": 0.0}
+    device = os.lstat(root).st_dev
+    children: Dict[str, int] = {}
+    size = files = 0
+    complete = True
+    for current, dirs, names in os.walk(root):
+        if time.monotonic() > deadline:
+            complete = False
+            break
+        dirs[:] = [d for d in dirs if os.lstat(os.path.join(current, d)).st_dev == device]
+        relative = os.path.relpath(current, root)
+        child = os.path.join(root, relative.split(os.sep, 1)[0]) if relative != "." else ""
+        for name in names:
+            try:
+                entry_size = os.lstat(os.path.join(current, name)).st_size
+            except OSError:
+                continue
+            size += entry_size
+            files += 1
+            key = child or os.path.join(root, name)
+            children[key] = children.get(key, 0) + entry_size
+    largest = [
+        {"path": entry, "name": os.path.basename(entry), "size": entry_size}
+        for entry, entry_size in heapq.nlargest(top, children.items(), key=lambda item: item[1])
+    ]
+    elapsed = round((time.monotonic() - started) * 1000, 1)
+    return {"host": "", "path": path, "kind": "dir", "size": size, "files": files, "largest": largest, "complete": complete, "elapsed_ms": elapsed}
+
+
+def host_free_bytes(host_id: Union[str, Host], path: str) -> Optional[int]:
+    """Bytes available on the filesystem that holds `path` (or its nearest existing parent)."""
+    script = "\n".join(
+        [
+            f"P={shell_path(path.rstrip('/') or '/')}",
+            'while [ ! -e "$P" ] && [ "$P" != / ]; do P=$(dirname "$P"); done',
+            "df -Pk \"$P\" | tail -1 | awk '{print $4}'",
+        ]
+    )
+    result = host_exec(host_id, script, timeout=30)
+    text = result.stdout.strip()
+    return int(text) * 1024 if result.success and text.isdigit() else None
+
+
+def local_free_bytes(path: str) -> Optional[int]:
+    import shutil
+
+    current = os.path.abspath(os.path.expanduser(path))
+    while not os.path.exists(current) and os.path.dirname(current) != current:
+        current = os.path.dirname(current)
+    try:
+        return shutil.disk_usage(current).free
+    except OSError:
+        return None
+
+
+def transfer_preflight(source: Any, destination: Any) -> Dict[str, Any]:
+    """Size a transfer source and check the destination has room for it.
+
+    `source`/`destination` are `TransferEndpoint`s; storage endpoints are not
+    measured. `eta_seconds` assumes `transfer.estimate_mibps` (default 50).
+    """
+    from ..config import get_config_value
+
+    report: Dict[str, Any] = {"source": None, "dest_free": None, "fits": None, "eta_seconds": None}
+    if source.type == "host":
+        report["source"] = host_path_stats(source.host_id, source.path, top=5)
+    elif source.type == "local":
+        report["source"] = local_path_stats(source.path, top=5)
+    if destination.type == "host":
+        report["dest_free"] = host_free_bytes(destination.host_id, destination.path)
+    elif destination.type == "local":
+        report["dest_free"] = local_free_bytes(destination.path)
+    size = report["source"]["size"] if report["source"] else None
+    if size is not None and report["dest_free"] is not None:
+        report["fits"] = size <= report["dest_free"]
+    try:
+        mibps = float(get_config_value("transfer.estimate_mibps", DEFAULT_ESTIMATE_MIBPS) or 0)
+    except (TypeError, ValueError):
+        mibps = 0.0
+    if size is not None and mibps > 0:
+        report["eta_seconds"] = int(size / (mibps * 1024 * 1024))
+    return report
+
+
+__all__ = [
+    "host_free_bytes",
+    "host_path_stats",
+    "local_free_bytes",
+    "local_path_stats",
+    "parse_stats_output",
+    "stats_command",
+    "transfer_preflight",
+]
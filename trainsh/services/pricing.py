@@ -261,6 +261,8 @@ class PricingSettings:
     colab_gpu_pricing: List[Dict[str, Any]] = field(default_factory=list)
     vast_rates: VastPricingRates = field(default_factory=VastPricingRates)
     exchange_rates: ExchangeRates = field(default_factory=ExchangeRates)
+    # User-maintained GPU prices from other clouds or on-prem (see gpu_compare).
+    gpu_offers: List[Dict[str, Any]] = field(default_factory=list)
     def __post_init__(self):
         if not self.colab_gpu_pricing:
             self.colab_gpu_pricing = [
@@ -308,6 +310,9 @@ def load_pricing_settings() -> PricingSettings:
                 updated_at=er.get("updated_at", ""),
             )
 
+        if isinstance(data.get("gpu_offers"), list):
+            settings.gpu_offers = [item for item in data["gpu_offers"] if isinstance(item, dict)]
+
         return settings
     except (yaml.YAMLError, KeyError):
         return PricingSettings()
@@ -323,6 +328,8 @@ def save_pricing_settings(settings: PricingSettings) -> None:
         "vast_rates": asdict(settings.vast_rates),
         "exchange_rates": asdict(settings.exchange_rates),
     }
+    if settings.gpu_offers:
+        data["gpu_offers"] = settings.gpu_offers
 
     with open(PRICING_FILE, "w") as f:
         yaml.dump(data, f, default_flow_style=False, sort_keys=False)
@@ -396,8 +403,11 @@ def ensure_exchange_rates(
 
     fresh = fetch_exchange_rates(fallback_to_defaults=False)
     if fresh is not None:
+        from .rate_history import record_exchange_rates
+
         settings.exchange_rates = fresh
         save_pricing_settings(settings)
+        record_exchange_rates(fresh)
         return fresh
 
     if isinstance(getattr(current, "rates", None), dict) and current.rates:
@@ -446,8 +456,12 @@ def format_price_per_hour(amount_usd: float, currency: str, rates: ExchangeRates
 
 def refresh_exchange_rates() -> ExchangeRates:
     """Fetch and save new exchange rates."""
-    rates = fetch_exchange_rates(fallback_to_defaults=True)
+    from .rate_history import record_exchange_rates
+
+    fetched = fetch_exchange_rates(fallback_to_defaults=False)
+    rates = fetched or ExchangeRates()
     settings = load_pricing_settings()
     settings.exchange_rates = rates
     save_pricing_settings(settings)
+    record_exchange_rates(fetched)
     return rates
@@ -0,0 +1,136 @@
+"""Projects: named groups of hosts, recipes, storages and default variables."""
+
+from __future__ import annotations
+
+import os
+import re
+from dataclasses import asdict, dataclass, field, fields
+from typing import Any, Dict, Iterable, List, Optional
+
+MEMBER_KINDS = ("hosts", "recipes", "storages")
+
+
+@dataclass
+class Project:
+    """One research project and the resources that belong to it."""
+
+    name: str
+    description: str = ""
+    hosts: List[str] = field(default_factory=list)
+    recipes: List[str] = field(default_factory=list)
+    storages: List[str] = field(default_factory=list)
+    variables: Dict[str, str] = field(default_factory=dict)
+
+    def to_dict(self) -> Dict[str, Any]:
+        return {key: value for key, value in asdict(self).items() if value not in ("", [], {})}
+
+    @classmethod
+    def from_dict(cls, data: Dict[str, Any]) -> "Project":
+        known = {item.name for item in fields(cls)}
+        values = {key: value for key, value in (data or {}).items() if key in known}
+        for kind in MEMBER_KINDS:
+            values[kind] = [str(item) for item in values.get(kind) or []]
+        values["variables"] = {str(key): str(value) for key, value in (values.get("variables") or {}).items()}
+        return cls(**values)
+
+    def add(self, kind: str, names: Iterable[str]) -> None:
+        members = getattr(self, kind)
+        members.extend(name for name in names if name not in members)
+
+    def discard(self, kind: str, names: Iterable[str]) -> None:
+        drop = set(names)
+        setattr(self, kind, [name for name in getattr(self, kind) if name not in drop])
+
+
+def sanitize_project_name(value: str) -> str:
+    return re.sub(r"[^A-Za-z0-9._-]+", "-", str(value or "").strip()).strip("-.")
+
+
+def recipe_key(value: str) -> str:
+    """Recipe name without directory or extension, as shown by `train recipe list`."""
+    return os.path.splitext(os.path.basename(str(value or "").strip()))[0]
+
+
+def load_projects() -> Dict[str, Project]:
+    """Load saved projects keyed by name."""
+    from ..constants import PROJECTS_FILE
+    import yaml
+
+    if not PROJECTS_FILE.exists():
+        return {}
+    with open(PROJECTS_FILE, "r") as f:
+        data = yaml.safe_load(f) or {}
+    projects = {}
+    for item in data.get("projects", []) or []:
+        project = Project.from_dict(item)
+        if project.name:
+            projects[project.name] = project
+    return projects
+
+
+def save_projects(projects: Dict[str, Project]) -> None:
+    from ..constants import CONFIG_DIR, PROJECTS_FILE
+    import yaml
+
+    CONFIG_DIR.mkdir(parents=True, exist_ok=True)
+    data = {"projects": [project.to_dict() for project in projects.values()]}
+    with open(PROJECTS_FILE, "w") as f:
+        yaml.dump(data, f, default_flow_style=False, sort_keys=False)
+
+
+def active_project_name() -> str:
+    """`TRAINSH_PROJECT`, else `project.active` from config."""
+    from ..config import get_config_value
+
+    return str(os.environ.get("TRAINSH_PROJECT") or get_config_value("project.active", "") or "").strip()
+
+
+def active_project() -> Optional[Project]:
+    name = active_project_name()
+    return load_projects().get(name) if name else None
+
+
+def project_for_recipe(recipe_path: str) -> Optional[Project]:
+    """The active project, else the one project that lists this recipe."""
+    active = active_project()
+    if active is not None:
+        return active
+    key = recipe_key(recipe_path)
+    owners = [project for project in load_projects().values() if key in {recipe_key(item) for item in project.recipes}]
+    return owners[0] if len(owners) == 1 else None
+
+
+def scoped(names: Iterable[str], kind: str, project: Optional[Project] = None) -> List[str]:
+    """Names of one member kind visible in the project; everything when no project is active."""
+    names = list(names)
+    project = active_project() if project is None else project
+    if project is None:
+        return names
+    if kind == "recipes":
+        members = {recipe_key(item) for item in project.recipes}
+        return [name for name in names if recipe_key(name) in members]
+    members = set(getattr(project, kind))
+    return [name for name in names if name in members]
+
+
+def scope_note(project: Optional[Project], kind: str, hidden: int) -> str:
+    """Footer line telling the user a listing was filtered by the active project."""
+    if project is None:
+        return ""
+    extra = f"; {hidden} other {kind} hidden" if hidden else ""
+    return f"Project: {project.name}{extra} (`train project use --clear` shows everything)"
+
+
+__all__ = [
+    "MEMBER_KINDS",
+    "Project",
+    "active_project",
+    "active_project_name",
+    "load_projects",
+    "project_for_recipe",
+    "recipe_key",
+    "sanitize_project_name",
+    "save_projects",
+    "scope_note",
+    "scoped",
+]
@@ -0,0 +1,221 @@
+"""Prometheus text-format exporter for host samples, running executions, transfers and queue depth."""
+
+from __future__ import annotations
+
+import threading
+from http.server import BaseHTTPRequestHandler, ThreadingHTTPServer
+from pathlib import Path
+from typing import Any, Callable, Dict, Iterable, List, Optional, Tuple
+
+from ..core.runtime_store import RuntimeStore
+from .dashboard import collect_executions, collect_queued_transfers
+from .host_metrics import HostMetricsSampler, host_metrics_recent
+
+DEFAULT_PORT = 9464
+DEFAULT_BIND = "127.0.0.1"
+CONTENT_TYPE = "text/plain; version=0.0.4; charset=utf-8"
+
+Sample = Tuple[Dict[str, str], float]
+
+# name -> help text; every family is a gauge.
+_FAMILIES = {
+    "trainsh_host_up": "1 when the last host sample succeeded, 0 when it failed.",
+    "trainsh_host_cpu_percent": "CPU utilisation of the host.",
+    "trainsh_host_memory_used_bytes": "Memory in use on the host.",
+    "trainsh_host_memory_total_bytes": "Total memory of the host.",
+    "trainsh_host_network_receive_bytes_per_second": "Network receive rate of the host.",
+    "trainsh_host_network_transmit_bytes_per_second": "Network transmit rate of the host.",
+    "trainsh_gpu_utilization_percent": "GPU utilisation.",
+    "trainsh_gpu_memory_used_bytes": "GPU memory in use.",
+    "trainsh_gpu_memory_total_bytes": "Total GPU memory.",
+    "trainsh_gpu_temperature_celsius": "GPU temperature.",
+    "trainsh_gpu_power_watts": "GPU power draw.",
+    "trainsh_executions_running": "Recipe executions currently running.",
+    "trainsh_execution_steps_running": "Steps currently running in one execution.",
+    "trainsh_transfers_active": "Transfers currently running.",
+    "trainsh_transfer_throughput_bytes_per_second": "Latest reported speed of a running transfer.",
+    "trainsh_transfer_progress_ratio": "Completed fraction (0-1) of a running transfer.",
+    "trainsh_queue_depth": "Entries waiting in a queue.",
+}
+
+_MIB = 1024 * 1024
+
+
+def _escape(value: Any) -> str:
+    return str(value).replace("\\", "\\\\").replace("\n", "\\n").replace('"', '\\"')
+
+
+def _number(value: float) -> str:
+    value = float(value)
+    return str(int(value)) if value.is_integer() else repr(value)
+
+
+def render_families(families: Dict[str, List[Sample]]) -> str:
+    """Prometheus text exposition of gauge families, in `_FAMILIES` order."""
+    lines: List[str] = []
+    for name, help_text in _FAMILIES.items():
+        samples = families.get(name)
+        if samples is None:
+            continue
+        lines.append(f"# HELP {name} {help_text}")
+        lines.append(f"# TYPE {name} gauge")
+        for labels, value in samples:
+            label_text = ",".join(f'{key}="{_escape(val)}"' for key, val in sorted(labels.items()))
+            lines.append(f"{name}{{{label_text}}} {_number(value)}" if label_text else f"{name} {_number(value)}")
+    return "\n".join(lines) + "\n"
+
+
+def host_families(samples: Dict[str, Dict[str, Any]]) -> Dict[str, List[Sample]]:
+    """Gauges from the latest `host:metrics` event of each host; rates missing from a sample are left out."""
+    families: Dict[str, List[Sample]] = {name: [] for name in _FAMILIES if name.startswith(("trainsh_host_", "trainsh_gpu_"))}
+    for host, event in sorted(samples.items()):
+        labels = {"host": host}
+        families["trainsh_host_up"].append((labels, 0.0 if event.get("error") else 1.0))
+        if event.get("error"):
+            continue
+        for family, key in (
+            ("trainsh_host_cpu_percent", "cpu_percent"),
+            ("trainsh_host_memory_used_bytes", "mem_used"),
+            ("trainsh_host_memory_total_bytes", "mem_total"),
+            ("trainsh_host_network_receive_bytes_per_second", "net_rx_bps"),
+            ("trainsh_host_network_transmit_bytes_per_second", "net_tx_bps"),
+        ):
+            if event.get(key) is not None:
+                families[family].append((labels, float(event[key])))
+        for gpu in event.get("gpus") or []:
+            gpu_labels = {"host": host, "gpu": str(gpu.get("index", 0)), "name": str(gpu.get("name", ""))}
+            for family, key, scale in (
+                ("trainsh_gpu_utilization_percent", "utilization", 1),
+                ("trainsh_gpu_memory_used_bytes", "memory_used_mb", _MIB),
+                ("trainsh_gpu_memory_total_bytes", "memory_total_mb", _MIB),
+                ("trainsh_gpu_temperature_celsius", "temperature_c", 1),
+                ("trainsh_gpu_power_watts", "power_w", 1),
+            ):
+                if gpu.get(key) is not None:
+                    families[family].append((gpu_labels, float(gpu[key]) * scale))
+    return families
+
+
+def runtime_families(state_dir: Optional[Path] = None) -> Dict[str, List[Sample]]:
+    """Running executions per recipe, active transfers with their throughput, and queue depths."""
+    store = RuntimeStore(state_dir)
+    executions, transfers = collect_executions(store)
+    transfers.extend(collect_queued_transfers(state_dir))
+
+    per_recipe: Dict[str, int] = {}
+    for row in executions:
+        per_recipe[row["recipe"]] = per_recipe.get(row["recipe"], 0) + 1
+    running = [row for row in transfers if row.get("status") == "running"]
+    families: Dict[str, List[Sample]] = {
+        "trainsh_executions_running": [({"recipe": recipe}, count) for recipe, count in sorted(per_recipe.items())],
+        "trainsh_execution_steps_running": [
+            ({"run_id": row["run_id"], "recipe": row["recipe"]}, len(row.get("current_steps") or [])) for row in executions
+        ],
+        "trainsh_transfers_active": [({}, len(running))],
+        "trainsh_transfer_throughput_bytes_per_second": [],
+        "trainsh_transfer_progress_ratio": [],
+        "trainsh_queue_depth": [
+            ({"queue": "transfers"}, sum(1 for row in transfers if row.get("status") == "queued")),
+            ({"queue": "executions"}, sum(1 for run in store.list_runs() if str(run.get("state", "")).lower() == "queued")),
+        ],
+    }
+    for row in running:
+        progress = row.get("progress") if isinstance(row.get("progress"), dict) else {}
+        labels = {
+            "id": row.get("transfer_id") or f"{row['run_id']}/{row.get('step_id', '')}",
+            "source": row["source"],
+            "dest": row["dest"],
+        }
+        families["trainsh_transfer_throughput_bytes_per_second"].append((labels, float(progress.get("speed_bps") or 0.0)))
+        if progress.get("percent") is not None:
+            families["trainsh_transfer_progress_ratio"].append((labels, float(progress["percent"]) / 100))
+    return families
+
+
+def collect_metrics(host_names: Iterable[str], state_dir: Optional[Path] = None) -> str:
+    """One scrape: the newest buffered sample per host plus fresh runtime-store gauges."""
+    samples: Dict[str, Dict[str, Any]] = {}
+    for name in host_names:
+        recent = host_metrics_recent(name, limit=1)
+        if recent:
+            samples[name] = recent[-1]
+    return render_families({**host_families(samples), **runtime_families(state_dir)})
+
+
+def _handler(render: Callable[[], str]) -> type:
+    class MetricsHandler(BaseHTTPRequestHandler):
+        def do_GET(self) -> None:
+            if self.path.split("?", 1)[0] not in ("/metrics", "/"):
+                self.send_error(404)
+                return
+            try:
+                body = render().encode("utf-8")
+            except Exception as exc:
+                self.send_error(500, str(exc))
+                return
+            self.send_response(200)
+            self.send_header("Content-Type", CONTENT_TYPE)
+            self.send_header("Content-Length", str(len(body)))
+            self.end_headers()
+            self.wfile.write(body)
+
+        def log_message(self, format: str, *args: Any) -> None:
+            """Scrapes arrive every few seconds; keep them off stderr."""
+
+    return MetricsHandler
+
+
+class MetricsExporter:
+    """Serve `/metrics` while sampling the given hosts in the background."""
+
+    def __init__(
+        self,
+        hosts: Dict[str, Any],
+        *,
+        port: int = DEFAULT_PORT,
+        bind: str = DEFAULT_BIND,
+        interval: Optional[float] = None,
+        state_dir: Optional[Path] = None,
+    ):
+        self.hosts = dict(hosts)
+        self.state_dir = state_dir
+        self.samplers = [HostMetricsSampler(name, host, interval=interval) for name, host in sorted(self.hosts.items())]
+        self.server = ThreadingHTTPServer((bind, int(port)), _handler(self.render))
+        self.server.daemon_threads = True
+        self._thread: Optional[threading.Thread] = None
+
+    @property
+    def address(self) -> Tuple[str, int]:
+        host, port = self.server.server_address[:2]
+        return str(host), int(port)
+
+    def render(self) -> str:
+        return collect_metrics(self.hosts, self.state_dir)
+
+    def start(self) -> "MetricsExporter":
+        for sampler in self.samplers:
+            sampler.start()
+        self._thread = threading.Thread(target=self.server.serve_forever, name="metrics-exporter", daemon=True)
+        self._thread.start()
+        return self
+
+    def stop(self) -> None:
+        self.server.shutdown()
+        self.server.server_close()
+        for sampler in self.samplers:
+            sampler.stop()
+        if self._thread is not None:
+            self._thread.join(5)
+            self._thread = None
+
+
+__all__ = [
+    "CONTENT_TYPE",
+    "DEFAULT_BIND",
+    "DEFAULT_PORT",
+    "MetricsExporter",
+    "collect_metrics",
+    "host_families",
+    "render_families",
+    "runtime_families",
+]
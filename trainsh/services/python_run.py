@@ -0,0 +1,94 @@
+"""Shell commands for `python.run` steps: interpreter choice and throwaway venvs.
+
+`interpreter` picks what runs the code:
+
+- `system` (default): `python` on PATH.
+- `uv`: `uv run --no-project python`; requirements go in as `--with`, so uv
+  builds and caches the environment itself.
+- `conda:NAME`: `conda run -n NAME python`.
+- anything else: a python executable (`python3.11`, `/opt/venv/bin/python`).
+
+For every interpreter but uv, requirements are installed into a venv made in a
+temp directory, which is removed when the command exits. pip's output goes to
+stderr so a captured stdout holds only what the code printed.
+"""
+
+from __future__ import annotations
+
+import re
+import shlex
+from typing import Iterable, List
+
+_PINNED_RE = re.compile(r"^[A-Za-z0-9_.\-\[\],]+\s*===?\s*[^\s*]+$")
+
+
+def interpreter_command(interpreter: str = "") -> str:
+    """Shell words that start a python interpreter for `interpreter`."""
+    value = str(interpreter or "").strip()
+    if value in ("", "system"):
+        return "python"
+    if value == "uv":
+        return "uv run --no-project python"
+    if value.startswith("conda:"):
+        env = value.split(":", 1)[1].strip()
+        if not env:
+            raise ValueError("interpreter 'conda:' needs an environment name")
+        return f"conda run --no-capture-output -n {shlex.quote(env)} python"
+    return shlex.quote(value)
+
+
+def _requirement_args(requirements: Iterable[str], *, flag: str, file_flag: str) -> List[str]:
+    args: List[str] = []
+    for item in requirements:
+        text = str(item).strip()
+        if not text:
+            continue
+        if text.endswith(".txt"):
+            args += [file_flag, shlex.quote(text)]
+        else:
+            args += [flag, shlex.quote(text)] if flag else [shlex.quote(text)]
+    return args
+
+
+def python_command(
+    *,
+    code: str = "",
+    script: str = "",
+    command: str = "",
+    interpreter: str = "",
+    requirements: Iterable[str] = (),
+) -> str:
+    """Shell command running inline `code`, a `script` path or a `-c` `command`."""
+    if code:
+        run = " - <<'PY'\n" + code + "\nPY"
+    elif script:
+        run = f" {shlex.quote(script)}"
+    else:
+        run = f" -c {shlex.quote(command)}"
+
+    requirements = list(requirements or [])
+    python = interpreter_command(interpreter)
+    if not requirements:
+        return python + run
+    if str(interpreter or "").strip() == "uv":
+        with_args = _requirement_args(requirements, flag="--with", file_flag="--with-requirements")
+        return f"uv run --no-project {' '.join(with_args)} python{run}"
+    install = " ".join(_requirement_args(requirements, flag="", file_flag="-r"))
+    return (
+        'venv="$(mktemp -d)" && trap \'rm -rf "$venv"\' EXIT'
+        f' && {python} -m venv "$venv"'
+        f' && "$venv/bin/python" -m pip install -q --disable-pip-version-check {install} >&2'
+        f' && "$venv/bin/python"{run}'
+    )
+
+
+def unpinned_requirements(requirements: Iterable[str]) -> List[str]:
+    """Requirements without an exact `==` version (requirement files are not checked)."""
+    return [
+        str(item).strip()
+        for item in requirements or []
+        if str(item).strip() and not str(item).strip().endswith(".txt") and not _PINNED_RE.match(str(item).strip())
+    ]
+
+
+__all__ = ["interpreter_command", "python_command", "unpinned_requirements"]
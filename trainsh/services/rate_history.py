@@ -0,0 +1,105 @@
+"""Timestamped exchange-rate snapshots for converting costs at accrual time."""
+
+from __future__ import annotations
+
+import json
+import urllib.error
+import urllib.request
+from datetime import UTC, date, datetime, timedelta
+from pathlib import Path
+from typing import Any, Dict, List, Optional
+
+from ..constants import STATE_DIR
+from .pricing import ExchangeRates, _parse_updated_at
+
+HISTORY_FILENAME = "exchange_rates.jsonl"
+RATE_CURRENCIES = "JPY,HKD,CNY,EUR,GBP,KRW,TWD"
+# A snapshot older than this before the accrual day is replaced by a fetched one.
+MAX_SNAPSHOT_AGE = timedelta(days=7)
+
+
+def _history_path() -> Path:
+    return STATE_DIR / HISTORY_FILENAME
+
+
+class RateHistory:
+    """Append-only log of exchange-rate snapshots, one JSON object per line."""
+
+    def __init__(self, path: Optional[Path] = None, *, fetch_day=None):
+        self.path = Path(path or _history_path())
+        self._fetch_day = fetch_day if fetch_day is not None else fetch_rates_for_day
+        self._snapshots: Optional[List[Dict[str, Any]]] = None
+
+    def snapshots(self) -> List[Dict[str, Any]]:
+        if self._snapshots is None:
+            items: List[Dict[str, Any]] = []
+            if self.path.exists():
+                for line in self.path.read_text(encoding="utf-8").splitlines():
+                    try:
+                        item = json.loads(line)
+                    except json.JSONDecodeError:
+                        continue
+                    when = _parse_updated_at(item.get("updated_at"))
+                    if when is not None and isinstance(item.get("rates"), dict):
+                        items.append({"at": when, "rates": item["rates"], "base": item.get("base", "USD")})
+            items.sort(key=lambda item: item["at"])
+            self._snapshots = items
+        return self._snapshots
+
+    def record(self, rates: ExchangeRates) -> None:
+        """Store one snapshot unless it has no usable timestamp or is already recorded."""
+        when = _parse_updated_at(rates.updated_at)
+        if when is None or any(item["at"] == when for item in self.snapshots()):
+            return
+        self.path.parent.mkdir(parents=True, exist_ok=True)
+        with open(self.path, "a", encoding="utf-8") as f:
+            f.write(json.dumps({"base": rates.base, "rates": rates.rates, "updated_at": when.isoformat()}) + "\n")
+        self._snapshots = None
+
+    def rates_at(self, when: datetime) -> ExchangeRates:
+        """Rates in effect at `when`, fetching that day's rates if none are close enough."""
+        if when.tzinfo is None:
+            when = when.replace(tzinfo=UTC)
+        snapshots = self.snapshots()
+        earlier = [item for item in snapshots if item["at"] <= when]
+        if earlier and when - earlier[-1]["at"] <= MAX_SNAPSHOT_AGE:
+            return self._to_rates(earlier[-1])
+        fetched = self._fetch_day(when.date()) if self._fetch_day else None
+        if fetched is not None:
+            self.record(fetched)
+            return fetched
+        nearest = earlier[-1:] or snapshots[:1]
+        if nearest:
+            return self._to_rates(nearest[0])
+        return ExchangeRates()
+
+    @staticmethod
+    def _to_rates(item: Dict[str, Any]) -> ExchangeRates:
+        return ExchangeRates(base=item.get("base", "USD"), rates=dict(item["rates"]), updated_at=item["at"].isoformat())
+
+
+def fetch_rates_for_day(day: date) -> Optional[ExchangeRates]:
+    """Published USD rates for one past day (frankfurter.app), or None offline."""
+    url = f"https://api.frankfurter.app/{day.isoformat()}?from=USD&to={RATE_CURRENCIES}"
+    try:
+        with urllib.request.urlopen(url, timeout=10) as response:
+            data = json.loads(response.read().decode())
+    except (urllib.error.URLError, json.JSONDecodeError, OSError):
+        return None
+    rates = {"USD": 1.0}
+    rates.update(data.get("rates") or {})
+    published = str(data.get("date") or day.isoformat())
+    return ExchangeRates(base="USD", rates=rates, updated_at=f"{published}T00:00:00+00:00")
+
+
+def record_exchange_rates(rates: Optional[ExchangeRates]) -> None:
+    """Best-effort hook called whenever fresh rates are fetched."""
+    if rates is None:
+        return
+    try:
+        RateHistory(fetch_day=False).record(rates)
+    except OSError:
+        pass
+
+
+__all__ = ["RateHistory", "fetch_rates_for_day", "record_exchange_rates"]
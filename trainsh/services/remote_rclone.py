@@ -0,0 +1,95 @@
+"""Run rclone on a remote host so host <-> cloud data skips the local machine."""
+
+from __future__ import annotations
+
+import shlex
+import subprocess
+from typing import List, Mapping, Optional, Sequence
+
+from .tar_stream import shell_path
+from .transfer_progress import ProgressReporter, RcloneProgressParser
+from .transfer_support import TransferResult
+
+# Installs the official static build into ~/.local/bin when rclone is missing.
+_ENSURE_RCLONE = r"""
+RCLONE="$(command -v rclone || true)"
+if [ -z "$RCLONE" ] && [ -x "$HOME/.local/bin/rclone" ]; then RCLONE="$HOME/.local/bin/rclone"; fi
+if [ -z "$RCLONE" ]; then
+  case "$(uname -m)" in
+    x86_64|amd64) arch=amd64 ;;
+    aarch64|arm64) arch=arm64 ;;
+    *) echo "rclone auto-install: unsupported architecture $(uname -m)" >&2; exit 127 ;;
+  esac
+  tmp="$(mktemp -d)"
+  echo "Installing rclone into ~/.local/bin" >&2
+  curl -fsSL -o "$tmp/rclone.zip" "https://downloads.rclone.org/rclone-current-linux-$arch.zip" || wget -q -O "$tmp/rclone.zip" "https://downloads.rclone.org/rclone-current-linux-$arch.zip"
+  (cd "$tmp" && (unzip -q rclone.zip || python3 -m zipfile -e rclone.zip .))
+  mkdir -p "$HOME/.local/bin"
+  cp "$tmp"/rclone-*/rclone "$HOME/.local/bin/rclone" && chmod 755 "$HOME/.local/bin/rclone"
+  rm -rf "$tmp"
+  RCLONE="$HOME/.local/bin/rclone"
+fi
+""".strip()
+
+def remote_script(rclone_args: Sequence[str], env: Mapping[str, str], *, local_paths: Sequence[str] = ()) -> str:
+    """Shell script (fed on stdin) that exports scoped credentials and runs rclone.
+
+    `local_paths` are arguments naming host-side paths; a leading `~/` is expanded
+    by the remote shell. Credentials never appear on a command line.
+    """
+    lines = ["set -e"]
+    lines.extend(f"export {key}={shlex.quote(str(value))}" for key, value in sorted(env.items()))
+    lines.append(_ENSURE_RCLONE)
+    rendered = [shell_path(arg) if arg in local_paths else shlex.quote(arg) for arg in rclone_args]
+    lines.append('exec "$RCLONE" ' + " ".join(rendered))
+    return "\n".join(lines) + "\n"
+
+
+def run_remote_rclone(ssh_args: List[str], script: str, *, reporter: Optional[ProgressReporter] = None) -> TransferResult:
+    """Run `script` via `sh -s` over SSH, reporting rclone stats as they arrive."""
+    reporter = reporter or ProgressReporter(backend="rclone")
+    parser = RcloneProgressParser()
+    try:
+        process = subprocess.Popen(
+            [*ssh_args, "sh -s"],
+            stdin=subprocess.PIPE,
+            stdout=subprocess.PIPE,
+            stderr=subprocess.STDOUT,
+            text=True,
+            bufsize=1,
+        )
+        assert process.stdin is not None and process.stdout is not None
+        process.stdin.write(script)
+        process.stdin.close()
+        output: List[str] = []
+        bytes_transferred = 0
+        for line in process.stdout:
+            line = line.rstrip()
+            if not line:
+                continue
+            output.append(line)
+            progress = parser.feed(line)
+            if progress:
+                bytes_transferred = progress.bytes_transferred or bytes_transferred
+                reporter.update(progress)
+            else:
+                print(f"  {line}", flush=True)
+        process.stdout.close()
+        code = process.wait()
+    except Exception as exc:
+        return TransferResult(success=False, exit_code=-1, message=str(exc))
+    return TransferResult(
+        success=code == 0,
+        exit_code=code,
+        message="Transfer complete (rclone on host)" if code == 0 else "\n".join(output[-5:]) or "Remote rclone failed",
+        bytes_transferred=bytes_transferred,
+        progress=reporter.finish(),
+    )
+
+
+def rclone_stats_args() -> List[str]:
+    """Line-oriented progress that survives a non-tty SSH session."""
+    return ["--stats", "5s", "--stats-one-line", "--stats-log-level", "NOTICE"]
+
+
+__all__ = ["rclone_stats_args", "remote_script", "run_remote_rclone"]
@@ -0,0 +1,80 @@
+"""Shell dialects of remote hosts: POSIX shells and Windows PowerShell.
+
+Hosts default to a POSIX shell. A host with `shell: powershell` (a Windows
+OpenSSH server) gets each command as a base64 `-EncodedCommand`, so no
+heredocs or POSIX quoting reach cmd.exe, and the command's exit code becomes
+the exit code of the SSH call.
+"""
+
+from __future__ import annotations
+
+import base64
+import shlex
+from typing import Any, Optional
+
+SHELL_TYPES = ("bash", "powershell")
+_ALIASES = {"": "bash", "sh": "bash", "posix": "bash", "pwsh": "powershell", "windows": "powershell"}
+
+
+def normalize_shell(value: Any) -> str:
+    """Canonical shell name for a host's `shell` setting; raises ValueError when unknown."""
+    text = str(value or "").strip().lower()
+    text = _ALIASES.get(text, text)
+    if text not in SHELL_TYPES:
+        raise ValueError(f"Unknown host shell {value!r}; expected one of: {', '.join(SHELL_TYPES)}")
+    return text
+
+
+def host_shell(host: Any) -> str:
+    """Shell of a Host model, falling back to bash for unknown values."""
+    try:
+        return normalize_shell(getattr(host, "shell", None))
+    except ValueError:
+        return "bash"
+
+
+def powershell_script(command: str, cwd: Optional[str] = None) -> str:
+    """PowerShell script that runs `command` and exits with its status."""
+    lines = ["$ProgressPreference = 'SilentlyContinue'", "$global:LASTEXITCODE = 0"]
+    if cwd:
+        quoted = str(cwd).replace("'", "''")
+        lines.append(f"Set-Location -LiteralPath '{quoted}' -ErrorAction Stop")
+    # `$?` covers cmdlets, `$LASTEXITCODE` covers native programs like python.exe.
+    lines.extend([
+        command,
+        "$__train_ok = $?",
+        "if ($LASTEXITCODE) { exit $LASTEXITCODE }",
+        "if (-not $__train_ok) { exit 1 }",
+        "exit 0",
+    ])
+    return "\n".join(lines)
+
+
+def powershell_command(command: str, cwd: Optional[str] = None) -> str:
+    """Remote command line running `command` through `powershell -EncodedCommand`."""
+    encoded = base64.b64encode(powershell_script(command, cwd).encode("utf-16-le")).decode("ascii")
+    return f"powershell -NoLogo -NoProfile -NonInteractive -EncodedCommand {encoded}"
+
+
+def is_powershell_command(command: str) -> bool:
+    return str(command or "").startswith("powershell -NoLogo -NoProfile -NonInteractive -EncodedCommand ")
+
+
+def wrap_for_shell(command: str, shell: str, cwd: Optional[str] = None) -> str:
+    """Remote command line for `command` in the host's shell, optionally run from `cwd`."""
+    if shell == "powershell":
+        return command if is_powershell_command(command) and not cwd else powershell_command(command, cwd)
+    if cwd:
+        return f"cd {shlex.quote(str(cwd))} && ({command})"
+    return command
+
+
+__all__ = [
+    "SHELL_TYPES",
+    "host_shell",
+    "is_powershell_command",
+    "normalize_shell",
+    "powershell_command",
+    "powershell_script",
+    "wrap_for_shell",
+]
@@ -0,0 +1,94 @@
+"""Per-transfer rsync tuning: compression, resumable partials, bandwidth, excludes."""
+
+from __future__ import annotations
+
+import re
+from typing import Any, Dict, List, Mapping, Optional
+
+PARTIAL_MODES = ("partial", "append-verify")
+_BWLIMIT_RE = re.compile(r"^\d+(\.\d+)?[bkmgBKMG]?$")
+
+
+def _coerce_bool(value: Any) -> bool:
+    if isinstance(value, bool):
+        return value
+    return str(value).strip().lower() in {"1", "true", "yes", "y", "on"}
+
+
+def normalize_rsync_options(raw: Optional[Mapping[str, Any]]) -> Dict[str, Any]:
+    """Validate rsync options; unknown keys are rejected so typos do not pass silently.
+
+    Keys: `compress` (bool), `partial` (`partial`, `append-verify`, or false),
+    `bwlimit` (rsync rate such as `20M` or KiB/s as a number), `exclude` (list).
+    """
+    options: Dict[str, Any] = {}
+    for key, value in dict(raw or {}).items():
+        if value is None:
+            continue
+        if key == "compress":
+            options["compress"] = _coerce_bool(value)
+        elif key == "partial":
+            if value is True or str(value).strip().lower() in {"true", "yes", "on"}:
+                value = "partial"
+            text = "" if value is False else str(value).strip().lower()
+            if text in {"", "false", "no", "off", "none"}:
+                options["partial"] = ""
+            elif text in PARTIAL_MODES:
+                options["partial"] = text
+            else:
+                raise ValueError(f"rsync partial must be one of {', '.join(PARTIAL_MODES)} (got {value!r})")
+        elif key == "bwlimit":
+            text = str(value).strip()
+            if text and not _BWLIMIT_RE.match(text):
+                raise ValueError(f"Invalid rsync bwlimit: {value!r} (e.g. 20M, 500K, 1024)")
+            options["bwlimit"] = text
+        elif key == "exclude":
+            items = [value] if isinstance(value, str) else list(value)
+            options["exclude"] = [str(item).strip() for item in items if str(item).strip()]
+        else:
+            raise ValueError(f"Unknown rsync option: {key}")
+    return options
+
+
+def resolve_rsync_options(
+    overrides: Optional[Mapping[str, Any]] = None,
+    *,
+    config: Optional[Dict[str, Any]] = None,
+) -> Dict[str, Any]:
+    """Config defaults (`transfer.rsync`) with per-transfer overrides applied on top."""
+    if config is None:
+        from ..config import load_config
+
+        config = load_config()
+    defaults = normalize_rsync_options((config.get("transfer") or {}).get("rsync"))
+    options = {"compress": True, "partial": "", "bwlimit": "", "exclude": []}
+    options.update(defaults)
+    extra = normalize_rsync_options(overrides)
+    excludes = list(options.get("exclude") or []) + list(extra.pop("exclude", []))
+    options.update(extra)
+    options["exclude"] = excludes
+    return options
+
+
+def rsync_option_args(options: Mapping[str, Any]) -> List[str]:
+    """Command-line flags for resolved options (excludes as single `--exclude=` tokens)."""
+    args: List[str] = []
+    if options.get("compress", True):
+        args.append("-z")
+    if options.get("partial") == "partial":
+        args.append("--partial")
+    elif options.get("partial") == "append-verify":
+        args.extend(["--partial", "--append-verify"])
+    if options.get("bwlimit"):
+        args.append(f"--bwlimit={options['bwlimit']}")
+    for pattern in options.get("exclude") or []:
+        args.append(f"--exclude={pattern}")
+    return args
+
+
+__all__ = [
+    "PARTIAL_MODES",
+    "normalize_rsync_options",
+    "resolve_rsync_options",
+    "rsync_option_args",
+]
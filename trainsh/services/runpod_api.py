@@ -17,16 +17,23 @@ from ..constants import (
     RUNPOD_GRAPHQL_API_BASE,
     RUNPOD_REST_API_BASE,
 )
+from ..core.errors import AppError, classify_http
 from ..core.models import RunpodGPUType, RunpodPod
 
 
-class RunpodAPIError(Exception):
+class RunpodAPIError(AppError):
     """Exception raised for RunPod API errors."""
 
     def __init__(self, status_code: int, message: str):
         self.status_code = status_code
         self.message = message
-        super().__init__(f"RunPod API error ({status_code}): {message}")
+        super().__init__(
+            f"RunPod API error ({status_code}): {message}",
+            classify_http(status_code, message),
+            provider="runpod",
+            module="runpod_api",
+            details={"status_code": status_code},
+        )
 
 
 @dataclass(frozen=True)
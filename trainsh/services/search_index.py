@@ -0,0 +1,229 @@
+"""Full-text search over recipes, hosts, sessions, secret names and execution logs.
+
+Everything is indexed into one SQLite FTS5 table under the state directory.
+Recipe files and the runtime event log are re-indexed incrementally (by
+mtime/size and byte offset); hosts, sessions and secret names are small and
+rebuilt on every search. Secret values are never read.
+"""
+
+from __future__ import annotations
+
+import json
+import re
+import sqlite3
+from pathlib import Path
+from typing import Any, Dict, Iterable, List, Optional, Tuple
+
+from ..constants import STATE_DIR
+
+KINDS = ("recipe", "host", "session", "secret", "log")
+_LOG_EVENTS = {"step_output", "detail", "step_end", "error"}
+
+
+def index_path() -> Path:
+    return STATE_DIR / "search.db"
+
+
+def _connect(path: Optional[Path] = None) -> sqlite3.Connection:
+    target = path or index_path()
+    target.parent.mkdir(parents=True, exist_ok=True)
+    conn = sqlite3.connect(str(target))
+    conn.execute(
+        "CREATE VIRTUAL TABLE IF NOT EXISTS docs USING fts5("
+        "kind UNINDEXED, name, location UNINDEXED, source UNINDEXED, text, tokenize='unicode61')"
+    )
+    conn.execute("CREATE TABLE IF NOT EXISTS sources (source TEXT PRIMARY KEY, stamp TEXT)")
+    return conn
+
+
+def fts_query(query: str) -> str:
+    """Quote each term so `flash-attn` or `cuda:12` match as phrases instead of FTS syntax."""
+    terms = [term.replace('"', '""') for term in str(query or "").split() if term.strip('"')]
+    return " AND ".join(f'"{term}"' for term in terms)
+
+
+def _replace_source(conn: sqlite3.Connection, source: str, stamp: str, rows: Iterable[Tuple[str, str, str, str]]) -> None:
+    conn.execute("DELETE FROM docs WHERE source = ?", (source,))
+    conn.executemany(
+        "INSERT INTO docs (kind, name, location, source, text) VALUES (?, ?, ?, ?, ?)",
+        [(kind, name, location, source, text) for kind, name, location, text in rows if text.strip()],
+    )
+    conn.execute("INSERT OR REPLACE INTO sources (source, stamp) VALUES (?, ?)", (source, stamp))
+
+
+def _stamp(conn: sqlite3.Connection, source: str) -> Optional[str]:
+    row = conn.execute("SELECT stamp FROM sources WHERE source = ?", (source,)).fetchone()
+    return row[0] if row else None
+
+
+def recipe_files() -> List[Path]:
+    from ..commands.recipe import get_examples_dir, get_recipes_dir, list_examples, list_recipes
+
+    files = [Path(get_recipes_dir()) / name for name in list_recipes()]
+    examples_dir = get_examples_dir()
+    if examples_dir:
+        files.extend(Path(examples_dir) / name for name in list_examples())
+    return files
+
+
+def _index_recipes(conn: sqlite3.Connection, files: List[Path]) -> None:
+    wanted = {f"recipe:{path}" for path in files}
+    for (source,) in conn.execute("SELECT source FROM sources WHERE source LIKE 'recipe:%'").fetchall():
+        if source not in wanted:
+            conn.execute("DELETE FROM docs WHERE source = ?", (source,))
+            conn.execute("DELETE FROM sources WHERE source = ?", (source,))
+    for path in files:
+        source = f"recipe:{path}"
+        try:
+            stat = path.stat()
+        except OSError:
+            continue
+        stamp = f"{stat.st_mtime_ns}:{stat.st_size}"
+        if _stamp(conn, source) == stamp:
+            continue
+        name = path.stem
+        lines = path.read_text(encoding="utf-8", errors="replace").splitlines()
+        rows = [("recipe", name, f"{path}:{number}", line.strip()) for number, line in enumerate(lines, 1)]
+        _replace_source(conn, source, stamp, [("recipe", name, str(path), name), *rows])
+
+
+def _host_rows() -> List[Tuple[str, str, str, str]]:
+    from ..commands.host import load_hosts
+
+    rows = []
+    for name, host in load_hosts(include_auto_vast=False).items():
+        env_vars = host.env_vars or {}
+        fields = [name, host.hostname, host.username, getattr(host.type, "value", ""), host.notes]
+        fields.extend(str(env_vars.get(key, "")) for key in ("tailscale_node", "cloudflared_hostname", "vast_link"))
+        rows.append(("host", name, "hosts.yaml", " ".join(str(item) for item in fields if item)))
+    return rows
+
+
+def _session_rows() -> List[Tuple[str, str, str, str]]:
+    from ..core.job_state import JobStateManager
+
+    rows = []
+    for job in JobStateManager().list_all(limit=200):
+        sessions = [job.tmux_session, job.bridge_session, *job.window_sessions.values()]
+        fields = [job.job_id, job.recipe_name, job.status, *job.window_sessions, *sessions, job.error]
+        rows.append(("session", job.job_id, f"job {job.job_id} ({job.recipe_name})", " ".join(item for item in fields if item)))
+    return rows
+
+
+def _secret_rows() -> List[Tuple[str, str, str, str]]:
+    from ..core.secrets import get_secrets_manager
+
+    try:
+        backend = get_secrets_manager()._get_backend()
+        names = backend.list_set_keys() if backend is not None else []
+    except Exception:
+        names = []
+    return [("secret", name, "train secrets", name) for name in names if name]
+
+
+def _event_text(record: Dict[str, Any]) -> str:
+    payload = record.get("payload") or {}
+    parts = [payload.get(key) for key in ("output", "message", "error", "result")]
+    return "\n".join(str(part) for part in parts if part)
+
+
+def _index_logs(conn: sqlite3.Connection, events_path: Path, *, max_runs: int) -> None:
+    """Append new runtime events; keep only the most recent `max_runs` runs."""
+    source_prefix = "log:"
+    try:
+        size = events_path.stat().st_size
+    except OSError:
+        return
+    stamp = _stamp(conn, "log-offset")
+    offset = int(stamp) if stamp and stamp.isdigit() else 0
+    if offset > size:
+        conn.execute("DELETE FROM docs WHERE source LIKE 'log:%'")
+        offset = 0
+    rows = []
+    with events_path.open("rb") as handle:
+        handle.seek(offset)
+        for raw in handle:
+            if not raw.endswith(b"\n"):
+                break
+            offset += len(raw)
+            try:
+                record = json.loads(raw)
+            except ValueError:
+                continue
+            if record.get("event") not in _LOG_EVENTS:
+                continue
+            text = _event_text(record)
+            run_id = str(record.get("run_id", "") or "")
+            if not text or not run_id:
+                continue
+            step = record.get("step_num")
+            location = f"run {run_id}" + (f" step {step}" if step is not None else "") + f" @ {str(record.get('ts', ''))[:19]}"
+            rows.append(("log", run_id, location, f"{source_prefix}{run_id}", text[:20000]))
+    conn.executemany("INSERT INTO docs (kind, name, location, source, text) VALUES (?, ?, ?, ?, ?)", rows)
+    conn.execute("INSERT OR REPLACE INTO sources (source, stamp) VALUES ('log-offset', ?)", (str(offset),))
+
+    runs = [row[0] for row in conn.execute("SELECT DISTINCT source FROM docs WHERE source LIKE 'log:%'").fetchall()]
+    if len(runs) > max_runs:
+        latest = {
+            row[0]
+            for row in conn.execute(
+                "SELECT source FROM docs WHERE source LIKE 'log:%' GROUP BY source ORDER BY MAX(rowid) DESC LIMIT ?",
+                (max_runs,),
+            ).fetchall()
+        }
+        conn.executemany("DELETE FROM docs WHERE source = ?", [(source,) for source in runs if source not in latest])
+
+
+def refresh_index(conn: sqlite3.Connection, *, kinds: Iterable[str] = KINDS) -> None:
+    from ..config import get_config_value
+    from ..constants import RUNTIME_STATE_DIR
+
+    kinds = set(kinds)
+    if "recipe" in kinds:
+        _index_recipes(conn, recipe_files())
+    for kind, loader in (("host", _host_rows), ("session", _session_rows), ("secret", _secret_rows)):
+        if kind in kinds:
+            try:
+                rows = loader()
+            except Exception:
+                continue
+            _replace_source(conn, kind, "", rows)
+    if "log" in kinds:
+        _index_logs(conn, Path(RUNTIME_STATE_DIR) / "events.jsonl", max_runs=int(get_config_value("search.log_runs", 50) or 50))
+    conn.commit()
+
+
+def search(
+    query: str,
+    *,
+    kinds: Optional[Iterable[str]] = None,
+    limit: int = 20,
+    reindex: bool = False,
+    path: Optional[Path] = None,
+) -> List[Dict[str, str]]:
+    """Ranked hits as {kind, name, location, snippet}."""
+    match = fts_query(query)
+    if not match:
+        return []
+    selected = [kind for kind in (kinds or KINDS) if kind in KINDS]
+    target = path or index_path()
+    if reindex and target.exists():
+        target.unlink()
+    conn = _connect(target)
+    try:
+        refresh_index(conn, kinds=selected)
+        placeholders = ",".join("?" for _ in selected)
+        rows = conn.execute(
+            "SELECT kind, name, location, snippet(docs, 4, '[', ']', '…', 12) FROM docs "
+            f"WHERE docs MATCH ? AND kind IN ({placeholders}) ORDER BY bm25(docs, 0, 5.0, 0, 0, 1.0) LIMIT ?",
+            (match, *selected, int(limit)),
+        ).fetchall()
+    finally:
+        conn.close()
+    return [
+        {"kind": kind, "name": name, "location": location, "snippet": re.sub(r"\s+", " ", snippet).strip()}
+        for kind, name, location, snippet in rows
+    ]
+
+
+__all__ = ["KINDS", "fts_query", "index_path", "recipe_files", "refresh_index", "search"]
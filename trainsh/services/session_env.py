@@ -0,0 +1,143 @@
+"""Named environment-variable sets injected into tmux sessions as a private `.env` file."""
+
+from __future__ import annotations
+
+import base64
+import json
+import os
+import re
+import shlex
+from urllib.parse import quote
+from typing import Any, Callable, Dict, Iterable, Mapping, Optional
+
+from .tar_stream import shell_path
+
+ENV_NAME_RE = re.compile(r"^[A-Za-z_][A-Za-z0-9_]*$")
+# Sourced and removed right away when a session only needs the exports.
+EPHEMERAL_ENV_DIR = "~/.cache/tmux-trainsh/env"
+REDACTED = "***"
+
+
+def configured_env_sets() -> Dict[str, Dict[str, str]]:
+    """`env_sets` from config.yaml: `{set_name: {VAR: value-or-${secret:NAME}}}`."""
+    from ..config import get_config_value
+
+    raw = get_config_value("env_sets", {}) or {}
+    if not isinstance(raw, dict):
+        return {}
+    return {str(name): {str(k): str(v) for k, v in dict(values or {}).items()} for name, values in raw.items()}
+
+
+def resolve_env_sets(
+    names: Iterable[str],
+    recipe_sets: Optional[Mapping[str, Mapping[str, Any]]],
+    interpolate: Callable[[str], str],
+) -> Dict[str, str]:
+    """Merge the named sets in order (later sets win) and interpolate secret refs.
+
+    Recipe-defined sets shadow config.yaml `env_sets` of the same name.
+    """
+    available = {**configured_env_sets(), **{str(k): dict(v) for k, v in dict(recipe_sets or {}).items()}}
+    merged: Dict[str, str] = {}
+    for name in names:
+        name = str(name).strip()
+        if not name:
+            continue
+        if name not in available:
+            raise KeyError(f"Unknown env set: {name}")
+        for key, value in available[name].items():
+            if not ENV_NAME_RE.match(str(key)):
+                raise ValueError(f"Invalid variable name in env set {name}: {key!r}")
+            merged[str(key)] = interpolate("" if value is None else str(value))
+    return merged
+
+
+def render_env_file(values: Mapping[str, str]) -> str:
+    """`KEY='value'` lines that both shells (`set -a; . file`) and dotenv loaders read."""
+    return "".join(f"{key}={shlex.quote(str(value))}\n" for key, value in values.items())
+
+
+def write_env_script(path: str) -> str:
+    """Remote script that writes stdin to `path` readable only by the owner."""
+    target = shell_path(path)
+    return "\n".join(
+        [
+            "set -e",
+            "umask 077",
+            f"mkdir -p \"$(dirname {target})\"",
+            f"cat > {target}",
+            f"chmod 600 {target}",
+        ]
+    )
+
+
+def source_command(path: str, *, remove: bool = False) -> str:
+    """Shell line that exports every variable in an env file into the current shell."""
+    target = shell_path(path)
+    command = f"set -a; . {target}; set +a"
+    return f"{command}; rm -f {target}" if remove else command
+
+
+def write_local_env_file(path: str, content: str) -> None:
+    target = os.path.expanduser(path)
+    os.makedirs(os.path.dirname(target) or ".", exist_ok=True)
+    fd = os.open(target, os.O_WRONLY | os.O_CREAT | os.O_TRUNC, 0o600)
+    with os.fdopen(fd, "w", encoding="utf-8") as handle:
+        handle.write(content)
+    os.chmod(target, 0o600)
+
+
+def secret_variants(secret: str) -> set[str]:
+    """A secret plus the encodings programs commonly print it in.
+
+    Covers base64 (standard and URL-safe, with or without padding), URL
+    percent-encoding and JSON string escaping.
+    """
+    raw = secret.encode("utf-8")
+    variants = {
+        secret,
+        quote(secret, safe=""),
+        json.dumps(secret)[1:-1],
+        base64.b64encode(raw).decode("ascii"),
+        base64.urlsafe_b64encode(raw).decode("ascii"),
+    }
+    variants.update([item.rstrip("=") for item in variants])
+    return {item for item in variants if len(item) >= 4}
+
+
+def redact(value: Any, secrets: Iterable[str]) -> Any:
+    """Replace every secret value (and its common encodings) inside strings, lists and dicts with `***`."""
+    needles = sorted(
+        {variant for item in secrets if item and len(str(item)) >= 4 for variant in secret_variants(str(item))},
+        key=len,
+        reverse=True,
+    )
+    if not needles:
+        return value
+
+    def scrub(item: Any) -> Any:
+        if isinstance(item, str):
+            for needle in needles:
+                item = item.replace(needle, REDACTED)
+            return item
+        if isinstance(item, dict):
+            return {key: scrub(val) for key, val in item.items()}
+        if isinstance(item, (list, tuple)):
+            return [scrub(val) for val in item]
+        return item
+
+    return scrub(value)
+
+
+__all__ = [
+    "EPHEMERAL_ENV_DIR",
+    "REDACTED",
+    "configured_env_sets",
+    "redact",
+    "render_env_file",
+    "resolve_env_sets",
+    "secret_variants",
+    "source_command",
+    "write_env_script",
+    "write_local_env_file",
+]
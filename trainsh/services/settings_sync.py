@@ -0,0 +1,326 @@
+"""Sync recipes, hosts, Vast templates and projects between machines through a storage backend.
+
+The remote side is `<sync.path>/manifest.json` plus one object per file under
+`<sync.path>/files/`. Each machine remembers the hashes it last agreed on with
+the remote (`sync_state.json`), so a sync can tell local edits from remote ones.
+When both sides changed, the newer edit wins and the other version is kept next
+to the file as `<name>.conflict-<device>-<time><ext>`. Secrets never leave the
+secrets backend: only files that reference them by name are synced.
+"""
+
+from __future__ import annotations
+
+import hashlib
+import json
+import os
+import socket
+import time
+from dataclasses import dataclass, field
+from datetime import datetime
+from pathlib import Path
+from typing import Any, Dict, List, Optional
+
+from ..core.runtime_store import RuntimeStore
+
+MANIFEST_NAME = "manifest.json"
+STATE_FILENAME = "sync_state.json"
+EVENT_RUN_ID = "settings-sync"
+CHANGE_EVENT = "settings_sync_changed"
+ACTIONS = ("pushed", "pulled", "conflict", "deleted_local", "deleted_remote")
+_READ_LIMIT = 64 * 1024 * 1024
+
+
+@dataclass
+class SyncResult:
+    """Changes made (or planned, with dry_run) by one `sync_now` call."""
+
+    changes: List[Dict[str, Any]] = field(default_factory=list)
+    skipped: List[str] = field(default_factory=list)
+
+    def count(self, action: str) -> int:
+        return sum(1 for change in self.changes if change["action"] == action)
+
+
+def sync_settings() -> Dict[str, Any]:
+    from ..config import get_config_value
+
+    settings = get_config_value("sync", {}) or {}
+    return {
+        "storage": str(settings.get("storage", "") or "").strip(),
+        "path": str(settings.get("path", "") or "tmux-trainsh-sync").strip("/"),
+        "device": str(settings.get("device", "") or socket.gethostname().split(".")[0] or "device"),
+    }
+
+
+def local_files() -> Dict[str, Path]:
+    """Synced files keyed by their remote name; the set is fixed, so a missing file counts as deleted."""
+    from ..constants import HOSTS_FILE, PROJECTS_FILE, RECIPE_FILE_EXTENSIONS, VAST_TEMPLATES_FILE
+
+    files = {
+        "config/hosts.yaml": Path(HOSTS_FILE),
+        "config/vast_templates.yaml": Path(VAST_TEMPLATES_FILE),
+        "config/projects.yaml": Path(PROJECTS_FILE),
+    }
+    recipes_dir = _recipes_dir()
+    if recipes_dir.is_dir():
+        for path in sorted(recipes_dir.rglob("*")):
+            if path.is_file() and path.suffix in RECIPE_FILE_EXTENSIONS and ".conflict-" not in path.name:
+                files[f"recipes/{path.relative_to(recipes_dir).as_posix()}"] = path
+    return files
+
+
+def _recipes_dir() -> Path:
+    """The recipes directory `train recipe list` shows (the current project's recipes/)."""
+    from ..commands.recipe import get_recipes_dir
+
+    return Path(get_recipes_dir())
+
+
+def _local_path(name: str) -> Optional[Path]:
+    known = local_files()
+    if name in known:
+        return known[name]
+    parts = Path(name).parts
+    if len(parts) >= 2 and parts[0] == "recipes" and ".." not in parts:
+        return _recipes_dir().joinpath(*parts[1:])
+    return None
+
+
+def _sha(data: bytes) -> str:
+    return hashlib.sha256(data).hexdigest()
+
+
+def _state_path() -> Path:
+    from ..constants import RUNTIME_STATE_DIR
+
+    return Path(RUNTIME_STATE_DIR) / STATE_FILENAME
+
+
+def load_state() -> Dict[str, Any]:
+    try:
+        return json.loads(_state_path().read_text(encoding="utf-8"))
+    except (OSError, ValueError):
+        return {}
+
+
+def _save_state(state: Dict[str, Any]) -> None:
+    path = _state_path()
+    path.parent.mkdir(parents=True, exist_ok=True)
+    path.write_text(json.dumps(state, indent=2, sort_keys=True), encoding="utf-8")
+
+
+def _load_storage(name: str):
+    from ..commands.storage import load_storages
+
+    storages = load_storages()
+    if name not in storages:
+        raise RuntimeError(f"Sync storage not found: {name} (set sync.storage to a name from `train storage list`)")
+    return storages[name]
+
+
+def _missing(exc: Exception) -> bool:
+    text = str(exc).lower()
+    return any(marker in text for marker in ("not found", "no such", "doesn't exist", "does not exist", "404"))
+
+
+def _read_remote(storage, path: str) -> Optional[bytes]:
+    from .file_access import file_read_head
+
+    try:
+        return file_read_head(storage, path, _READ_LIMIT).data
+    except RuntimeError as exc:
+        if _missing(exc):
+            return None
+        raise
+
+
+def _write_remote(storage, path: str, data: bytes) -> None:
+    from .file_access import file_write
+
+    file_write(storage, path, data)
+
+
+def _conflict_copy(path: Path, device: str, data: bytes) -> Path:
+    stamp = datetime.now().strftime("%Y%m%d-%H%M%S")
+    copy = path.with_name(f"{path.stem}.conflict-{device}-{stamp}{path.suffix}")
+    copy.parent.mkdir(parents=True, exist_ok=True)
+    copy.write_bytes(data)
+    return copy
+
+
+def _write_local(path: Path, data: bytes) -> None:
+    path.parent.mkdir(parents=True, exist_ok=True)
+    tmp = path.with_name(path.name + ".sync-tmp")
+    tmp.write_bytes(data)
+    os.replace(tmp, path)
+
+
+def record_events(changes: List[Dict[str, Any]], root: Optional[Path] = None) -> None:
+    store = RuntimeStore(root)
+    for change in changes:
+        store.append_event(
+            {
+                "run_id": EVENT_RUN_ID,
+                "event": CHANGE_EVENT,
+                "event_name": CHANGE_EVENT,
+                "step_num": None,
+                "payload": change,
+                "ts": change["ts"],
+            }
+        )
+
+
+def history(limit: int = 50, root: Optional[Path] = None) -> List[Dict[str, Any]]:
+    """Recorded sync changes, oldest first."""
+    records = [
+        record.get("payload") or {}
+        for record in RuntimeStore(root).list_events(EVENT_RUN_ID)
+        if record.get("event") == CHANGE_EVENT
+    ]
+    return records[-limit:] if limit else records
+
+
+def pending_local_changes() -> List[str]:
+    """Local files that changed since the last sync (no network access)."""
+    base = load_state().get("files", {})
+    pending = []
+    for name, path in local_files().items():
+        sha = _sha(path.read_bytes()) if path.is_file() else None
+        if sha != base.get(name):
+            pending.append(name)
+    pending.extend(name for name in base if name not in local_files() and base[name] is not None)
+    return sorted(set(pending))
+
+
+def sync_now(*, dry_run: bool = False) -> SyncResult:
+    """Two-way sync with the configured storage; records a change event per file."""
+    settings = sync_settings()
+    if not settings["storage"]:
+        raise RuntimeError("Settings sync is not configured (set sync.storage).")
+    storage = _load_storage(settings["storage"])
+    device = settings["device"]
+    manifest_path = f"{settings['path']}/{MANIFEST_NAME}"
+
+    raw_manifest = _read_remote(storage, manifest_path)
+    manifest = json.loads(raw_manifest) if raw_manifest else {"version": 1, "files": {}}
+    remote_files: Dict[str, Dict[str, Any]] = manifest.setdefault("files", {})
+    state = load_state()
+    if (state.get("storage"), state.get("path")) != (settings["storage"], settings["path"]):
+        state = {}
+    base: Dict[str, Optional[str]] = dict(state.get("files", {}))
+
+    local = local_files()
+    names = sorted(set(local) | set(remote_files) | set(base))
+    result = SyncResult()
+    now = time.time()
+
+    def change(name: str, action: str, **extra: Any) -> None:
+        result.changes.append({"path": name, "action": action, "device": device, "ts": datetime.now().isoformat(), **extra})
+
+    for name in names:
+        path = local.get(name) or _local_path(name)
+        if path is None:
+            continue
+        local_data = path.read_bytes() if path.is_file() else None
+        local_sha = _sha(local_data) if local_data is not None else None
+        entry = remote_files.get(name) or {}
+        remote_sha = None if entry.get("deleted") else entry.get("sha256")
+        base_sha = base.get(name)
+        object_path = f"{settings['path']}/files/{name}"
+
+        if local_sha == remote_sha:
+            base[name] = local_sha
+            continue
+        local_changed = local_sha != base_sha
+        remote_changed = remote_sha != base_sha
+        if local_changed and remote_changed and local_sha is not None and remote_sha is not None:
+            local_mtime = path.stat().st_mtime
+            remote_wins = float(entry.get("mtime", 0) or 0) > local_mtime
+            winner = entry.get("device", "remote") if remote_wins else device
+            change(name, "conflict", winner=winner)
+            if dry_run:
+                continue
+            if remote_wins:
+                _conflict_copy(path, device, local_data)
+                local_changed = False
+            else:
+                remote_data = _read_remote(storage, object_path)
+                if remote_data is not None:
+                    _conflict_copy(path, str(entry.get("device", "remote")), remote_data)
+                remote_changed = False
+        elif local_changed and remote_changed:
+            # An edit beats a deletion on the other side.
+            local_changed, remote_changed = (local_sha is not None, remote_sha is not None)
+
+        if local_changed and not remote_changed:
+            if local_sha is None:
+                change(name, "deleted_remote")
+                if not dry_run:
+                    remote_files[name] = {"deleted": True, "mtime": now, "device": device}
+            else:
+                if len(local_data) > _max_object_bytes():
+                    result.skipped.append(name)
+                    continue
+                if not any(item["path"] == name for item in result.changes):
+                    change(name, "pushed")
+                if not dry_run:
+                    _write_remote(storage, object_path, local_data)
+                    remote_files[name] = {"sha256": local_sha, "mtime": path.stat().st_mtime, "device": device}
+            if not dry_run:
+                base[name] = local_sha
+        elif remote_changed:
+            if remote_sha is None:
+                change(name, "deleted_local")
+                if not dry_run:
+                    path.unlink(missing_ok=True)
+            else:
+                remote_data = None if dry_run else _read_remote(storage, object_path)
+                if not dry_run and (remote_data is None or _sha(remote_data) != remote_sha):
+                    result.skipped.append(name)
+                    continue
+                if not any(item["path"] == name for item in result.changes):
+                    change(name, "pulled")
+                if not dry_run:
+                    _write_local(path, remote_data)
+                    mtime = float(entry.get("mtime", 0) or 0)
+                    if mtime:
+                        os.utime(path, (mtime, mtime))
+            if not dry_run:
+                base[name] = remote_sha
+
+    if dry_run:
+        return result
+    if any(item["action"] in ("pushed", "deleted_remote", "conflict") for item in result.changes) or raw_manifest is None:
+        manifest["updated_at"] = datetime.now().isoformat()
+        manifest["updated_by"] = device
+        _write_remote(storage, manifest_path, json.dumps(manifest, indent=2, sort_keys=True).encode())
+    _save_state(
+        {
+            "storage": settings["storage"],
+            "path": settings["path"],
+            "files": {name: sha for name, sha in base.items() if sha is not None},
+            "last_sync": datetime.now().isoformat(),
+        }
+    )
+    record_events(result.changes)
+    return result
+
+
+def _max_object_bytes() -> int:
+    from .file_access import MAX_EDIT_BYTES
+
+    return MAX_EDIT_BYTES
+
+
+__all__ = [
+    "ACTIONS",
+    "CHANGE_EVENT",
+    "SyncResult",
+    "history",
+    "load_state",
+    "local_files",
+    "pending_local_changes",
+    "record_events",
+    "sync_now",
+    "sync_settings",
+]
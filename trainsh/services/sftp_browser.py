@@ -20,6 +20,8 @@ class FileEntry:
     permissions: str = ""
     owner: str = ""
     group: str = ""
+    kind: str = ""  # file | dir | symlink | other; empty when parsed from ls
+    link_target: str = ""
 
     @property
     def display_size(self) -> str:
@@ -84,6 +86,8 @@ class RemoteFileBrowser:
         self.ssh = ssh_client
         self.cache: dict[str, List[FileEntry]] = {}
         self.current_path: str = "~"
+        # None until the first listing shows whether the host serves sftp.
+        self.sftp_available: Optional[bool] = None
 
     def list_directory(self, path: str = "~") -> List[FileEntry]:
         """
@@ -95,6 +99,26 @@ class RemoteFileBrowser:
         Returns:
             List of FileEntry objects
         """
+        from .sftp_list import SFTPError, sftp_list, sftp_settings
+
+        settings = sftp_settings()
+        if settings["enabled"] and self.sftp_available is not False:
+            try:
+                _, entries = sftp_list(self.ssh, path, timeout=settings["timeout_secs"])
+                self.sftp_available = True
+                return entries
+            except SFTPError as exc:
+                # A status code means the server answered and only this path failed.
+                if exc.code is not None:
+                    self.sftp_available = True
+                    return []
+                self.sftp_available = False
+            except Exception:
+                self.sftp_available = False
+        return self._list_with_ls(path)
+
+    def _list_with_ls(self, path: str) -> List[FileEntry]:
+        """Fallback listing by parsing `ls -la` (GNU ls only)."""
         # Expand ~ to actual home directory
         if path == "~":
             result = self.ssh.run("echo $HOME")
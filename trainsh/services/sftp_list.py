@@ -0,0 +1,323 @@
+"""Directory listing over the SSH `sftp` subsystem.
+
+Speaks just enough of SFTP version 3 (realpath, opendir/readdir, stat,
+readlink) to list a directory with real metadata: the server sends names
+and attributes as length-prefixed fields, so spaces, newlines and the host's
+`ls` flavour (GNU, BSD, busybox) no longer matter. The session rides the
+same `ssh` invocation as every other command, ProxyJump and ControlMaster
+included.
+"""
+
+from __future__ import annotations
+
+import posixpath
+import stat as stat_mod
+import struct
+import subprocess
+import threading
+from dataclasses import dataclass, field
+from datetime import datetime
+from typing import Any, Dict, List, Optional, Tuple
+
+SSH_FXP_INIT = 1
+SSH_FXP_VERSION = 2
+SSH_FXP_CLOSE = 4
+SSH_FXP_LSTAT = 7
+SSH_FXP_OPENDIR = 11
+SSH_FXP_READDIR = 12
+SSH_FXP_REALPATH = 16
+SSH_FXP_STAT = 17
+SSH_FXP_READLINK = 19
+SSH_FXP_STATUS = 101
+SSH_FXP_HANDLE = 102
+SSH_FXP_NAME = 104
+SSH_FXP_ATTRS = 105
+
+SSH_FX_EOF = 1
+
+ATTR_SIZE = 0x1
+ATTR_UIDGID = 0x2
+ATTR_PERMISSIONS = 0x4
+ATTR_ACMODTIME = 0x8
+ATTR_EXTENDED = 0x80000000
+
+DEFAULT_TIMEOUT_SECS = 30.0
+
+
+class SFTPError(RuntimeError):
+    """The sftp subsystem is unavailable or answered with an error."""
+
+    def __init__(self, message: str, code: Optional[int] = None):
+        super().__init__(message)
+        self.code = code
+
+
+@dataclass
+class SFTPAttrs:
+    size: Optional[int] = None
+    uid: Optional[int] = None
+    gid: Optional[int] = None
+    mode: Optional[int] = None
+    mtime: Optional[int] = None
+    extended: Dict[str, str] = field(default_factory=dict)
+
+    @property
+    def kind(self) -> str:
+        if self.mode is None:
+            return "other"
+        if stat_mod.S_ISDIR(self.mode):
+            return "dir"
+        if stat_mod.S_ISLNK(self.mode):
+            return "symlink"
+        if stat_mod.S_ISREG(self.mode):
+            return "file"
+        return "other"
+
+
+class _Reader:
+    def __init__(self, data: bytes):
+        self.data = data
+        self.pos = 0
+
+    def take(self, size: int) -> bytes:
+        if self.pos + size > len(self.data):
+            raise SFTPError("truncated sftp packet")
+        chunk = self.data[self.pos : self.pos + size]
+        self.pos += size
+        return chunk
+
+    def u32(self) -> int:
+        return struct.unpack(">I", self.take(4))[0]
+
+    def u64(self) -> int:
+        return struct.unpack(">Q", self.take(8))[0]
+
+    def raw(self) -> bytes:
+        return self.take(self.u32())
+
+    def text(self) -> str:
+        return self.raw().decode("utf-8", "surrogateescape")
+
+    def attrs(self) -> SFTPAttrs:
+        flags = self.u32()
+        attrs = SFTPAttrs()
+        if flags & ATTR_SIZE:
+            attrs.size = self.u64()
+        if flags & ATTR_UIDGID:
+            attrs.uid, attrs.gid = self.u32(), self.u32()
+        if flags & ATTR_PERMISSIONS:
+            attrs.mode = self.u32()
+        if flags & ATTR_ACMODTIME:
+            self.u32()
+            attrs.mtime = self.u32()
+        if flags & ATTR_EXTENDED:
+            for _ in range(self.u32()):
+                attrs.extended[self.text()] = self.text()
+        return attrs
+
+
+def _string(value: str) -> bytes:
+    data = value.encode("utf-8", "surrogateescape")
+    return struct.pack(">I", len(data)) + data
+
+
+def _owner_group(longname: str) -> Tuple[str, str]:
+    """Owner and group columns of the server's `ls -l`-style longname."""
+    parts = longname.split(None, 4)
+    return (parts[2], parts[3]) if len(parts) >= 5 else ("", "")
+
+
+class SFTPSession:
+    """One `ssh -s host sftp` process speaking SFTP version 3."""
+
+    def __init__(self, ssh_client: Any, *, timeout: float = DEFAULT_TIMEOUT_SECS):
+        args = list(ssh_client._build_ssh_args(None))
+        if not args:
+            raise SFTPError("no ssh command for this host")
+        # `-s` makes the trailing word a subsystem name instead of a command.
+        args[-1:-1] = ["-s"]
+        args.append("sftp")
+        try:
+            self.proc = subprocess.Popen(args, stdin=subprocess.PIPE, stdout=subprocess.PIPE, stderr=subprocess.PIPE)
+        except OSError as exc:
+            raise SFTPError(f"could not start ssh: {exc}") from exc
+        self._watchdog = threading.Timer(timeout, self.proc.kill)
+        self._watchdog.daemon = True
+        self._watchdog.start()
+        self._next_id = 0
+        self._send(SSH_FXP_INIT, struct.pack(">I", 3))
+        kind, _ = self._recv()
+        if kind != SSH_FXP_VERSION:
+            raise SFTPError("sftp subsystem did not answer INIT")
+
+    def __enter__(self) -> "SFTPSession":
+        return self
+
+    def __exit__(self, *exc: Any) -> None:
+        self.close()
+
+    def close(self) -> None:
+        self._watchdog.cancel()
+        if self.proc.poll() is None:
+            try:
+                self.proc.stdin.close()
+                self.proc.wait(timeout=2)
+            except Exception:
+                self.proc.kill()
+        for stream in (self.proc.stdout, self.proc.stderr):
+            if stream is not None:
+                stream.close()
+
+    def _send(self, kind: int, payload: bytes) -> None:
+        try:
+            self.proc.stdin.write(struct.pack(">IB", len(payload) + 1, kind) + payload)
+            self.proc.stdin.flush()
+        except (BrokenPipeError, OSError, ValueError) as exc:
+            raise SFTPError(self._exit_reason() or f"sftp connection lost: {exc}") from exc
+
+    def _read_exact(self, size: int) -> bytes:
+        data = b""
+        while len(data) < size:
+            chunk = self.proc.stdout.read(size - len(data))
+            if not chunk:
+                raise SFTPError(self._exit_reason() or "sftp connection closed")
+            data += chunk
+        return data
+
+    def _exit_reason(self) -> str:
+        if self.proc.poll() is None or self.proc.stderr is None:
+            return ""
+        return self.proc.stderr.read().decode("utf-8", "replace").strip()
+
+    def _recv(self) -> Tuple[int, _Reader]:
+        (length,) = struct.unpack(">I", self._read_exact(4))
+        body = self._read_exact(length)
+        return body[0], _Reader(body[1:])
+
+    def _request(self, kind: int, payload: bytes) -> Tuple[int, _Reader]:
+        self._next_id += 1
+        request_id = self._next_id
+        self._send(kind, struct.pack(">I", request_id) + payload)
+        reply, reader = self._recv()
+        if reader.u32() != request_id:
+            raise SFTPError("out-of-order sftp reply")
+        return reply, reader
+
+    @staticmethod
+    def _status(reader: _Reader) -> SFTPError:
+        code = reader.u32()
+        message = reader.text() if reader.pos < len(reader.data) else ""
+        return SFTPError(message or f"sftp status {code}", code)
+
+    def _expect(self, kind: int, payload: bytes, expected: int) -> _Reader:
+        reply, reader = self._request(kind, payload)
+        if reply == SSH_FXP_STATUS:
+            raise self._status(reader)
+        if reply != expected:
+            raise SFTPError(f"unexpected sftp reply {reply}")
+        return reader
+
+    def realpath(self, path: str) -> str:
+        reader = self._expect(SSH_FXP_REALPATH, _string(path), SSH_FXP_NAME)
+        reader.u32()
+        return reader.text()
+
+    def stat(self, path: str, *, follow: bool = True) -> SFTPAttrs:
+        return self._expect(SSH_FXP_STAT if follow else SSH_FXP_LSTAT, _string(path), SSH_FXP_ATTRS).attrs()
+
+    def readlink(self, path: str) -> str:
+        reader = self._expect(SSH_FXP_READLINK, _string(path), SSH_FXP_NAME)
+        reader.u32()
+        return reader.text()
+
+    def listdir(self, path: str) -> List[Tuple[str, str, SFTPAttrs]]:
+        """`(name, longname, attrs)` for every entry except `.` and `..`."""
+        handle = self._expect(SSH_FXP_OPENDIR, _string(path), SSH_FXP_HANDLE).raw()
+        entries: List[Tuple[str, str, SFTPAttrs]] = []
+        try:
+            while True:
+                reply, reader = self._request(SSH_FXP_READDIR, struct.pack(">I", len(handle)) + handle)
+                if reply == SSH_FXP_STATUS:
+                    error = self._status(reader)
+                    if error.code == SSH_FX_EOF:
+                        break
+                    raise error
+                if reply != SSH_FXP_NAME:
+                    raise SFTPError(f"unexpected sftp reply {reply}")
+                for _ in range(reader.u32()):
+                    name, longname, attrs = reader.text(), reader.text(), reader.attrs()
+                    if name not in (".", ".."):
+                        entries.append((name, longname, attrs))
+        finally:
+            self._request(SSH_FXP_CLOSE, struct.pack(">I", len(handle)) + handle)
+        return entries
+
+
+def resolve_path(session: SFTPSession, path: str) -> str:
+    """Absolute remote path; `~` and `~/...` are taken relative to the login directory."""
+    text = str(path or "~")
+    if text == "~" or text.startswith("~/"):
+        home = session.realpath(".")
+        return posixpath.join(home, text[2:]) if text != "~" else home
+    return text
+
+
+def sftp_list(ssh_client: Any, path: str = "~", *, timeout: float = DEFAULT_TIMEOUT_SECS) -> Tuple[str, List[Any]]:
+    """List `path` over SFTP; returns the resolved directory and its `FileEntry` rows.
+
+    Symlinks carry their target and count as directories when they point at one.
+    Raises `SFTPError` when the subsystem is unavailable or the path cannot be read.
+    """
+    from .sftp_browser import FileEntry
+
+    with SFTPSession(ssh_client, timeout=timeout) as session:
+        base = resolve_path(session, path)
+        rows = []
+        for name, longname, attrs in session.listdir(base):
+            full_path = posixpath.join(base, name)
+            kind = attrs.kind
+            link_target = ""
+            is_dir = kind == "dir"
+            if kind == "symlink":
+                try:
+                    link_target = session.readlink(full_path)
+                    is_dir = session.stat(full_path).kind == "dir"
+                except SFTPError:
+                    pass
+            owner, group = _owner_group(longname)
+            rows.append(
+                FileEntry(
+                    name=name,
+                    path=full_path,
+                    is_dir=is_dir,
+                    size=attrs.size or 0,
+                    modified=datetime.fromtimestamp(attrs.mtime) if attrs.mtime is not None else None,
+                    permissions=stat_mod.filemode(attrs.mode) if attrs.mode is not None else "",
+                    owner=owner or ("" if attrs.uid is None else str(attrs.uid)),
+                    group=group or ("" if attrs.gid is None else str(attrs.gid)),
+                    kind=kind,
+                    link_target=link_target,
+                )
+            )
+    rows.sort(key=lambda e: (not e.is_dir, e.name.lower()))
+    return base, rows
+
+
+def sftp_settings() -> Dict[str, Any]:
+    from ..config import get_config_value
+
+    try:
+        timeout = float(get_config_value("sftp.timeout_secs", DEFAULT_TIMEOUT_SECS) or DEFAULT_TIMEOUT_SECS)
+    except (TypeError, ValueError):
+        timeout = DEFAULT_TIMEOUT_SECS
+    return {"enabled": bool(get_config_value("sftp.enabled", True)), "timeout_secs": timeout}
+
+
+__all__ = [
+    "SFTPAttrs",
+    "SFTPError",
+    "SFTPSession",
+    "resolve_path",
+    "sftp_list",
+    "sftp_settings",
+]
@@ -8,7 +8,10 @@ from typing import Optional, List, Tuple, Any
 from dataclasses import dataclass
 from urllib.parse import urlparse
 
+from ..core.errors import AppError, classify_ssh
 from ..core.models import AuthMethod, Host, HostType
+from .remote_shell import host_shell, wrap_for_shell
+from .tracing import KIND_CLIENT, span
 
 
 @dataclass
@@ -25,6 +28,20 @@ class SSHResult:
     def success(self) -> bool:
         return self.exit_code == 0
 
+    @property
+    def error(self) -> Optional[AppError]:
+        """The failure as an `AppError`; its code is `unknown` when the remote command itself failed."""
+        if self.success:
+            return None
+        message = self.stderr.strip() or f"exit {self.exit_code}"
+        return AppError(
+            message,
+            classify_ssh(self.stderr, self.exit_code),
+            provider="ssh",
+            module="ssh",
+            details={"exit_code": self.exit_code, "hostname": self.target_hostname, "port": self.target_port},
+        )
+
 
 @dataclass(frozen=True)
 class SSHConnectionTarget:
@@ -54,6 +71,7 @@ class SSHClient:
         proxy_command: Optional[str] = None,
         connection_targets: Optional[List[SSHConnectionTarget]] = None,
         connect_timeout: int = 10,
+        shell: str = "bash",
     ):
         """
         Initialize the SSH client.
@@ -67,6 +85,7 @@ class SSHClient:
             proxy_command: OpenSSH ProxyCommand value
             connection_targets: Ordered connection candidates
             connect_timeout: Connection timeout in seconds
+            shell: Remote shell dialect ("bash" or "powershell")
         """
         self.hostname = hostname
         self.port = port
@@ -76,6 +95,10 @@ class SSHClient:
         self.jump_host = jump_host
         self.proxy_command = proxy_command
         self.connect_timeout = connect_timeout
+        self.shell = shell
+        # OpenSSH ControlMaster socket shared across calls (see host_exec.client_for).
+        self.control_path: Optional[str] = None
+        self.control_persist = 60
         self.connection_targets = connection_targets or [
             SSHConnectionTarget(
                 hostname=hostname,
@@ -97,6 +120,14 @@ class SSHClient:
             from .host_resolver import prepare_runpod_host
 
             host = prepare_runpod_host(host)
+        if (host.env_vars or {}).get("vast_link"):
+            from .host_resolver import prepare_vast_linked_host
+
+            host = prepare_vast_linked_host(host)
+        if (host.env_vars or {}).get("tailscale_node"):
+            from .host_resolver import prepare_tailscale_host
+
+            host = prepare_tailscale_host(host)
 
         env_vars = host.env_vars or {}
         key_path = host.ssh_key_path
@@ -127,6 +158,7 @@ class SSHClient:
             jump_host=host.jump_host,
             proxy_command=primary.proxy_command,
             connection_targets=targets,
+            shell=host_shell(host),
         )
 
     def _requires_sshpass(self) -> bool:
@@ -152,16 +184,9 @@ class SSHClient:
         default_hostname: str,
     ) -> Optional[str]:
         """Build ProxyCommand from cloudflared host settings."""
-        tunnel_type = str(env_vars.get("tunnel_type", "")).strip().lower()
-        if tunnel_type != "cloudflared":
-            return None
+        from .cloudflared import proxy_command
 
-        cloudflared_hostname = str(env_vars.get("cloudflared_hostname", default_hostname)).strip()
-        if not cloudflared_hostname:
-            return None
-
-        cloudflared_bin = str(env_vars.get("cloudflared_bin", "cloudflared")).strip() or "cloudflared"
-        return f"{cloudflared_bin} access ssh --hostname {cloudflared_hostname}"
+        return proxy_command(env_vars, default_hostname)
 
     @classmethod
     def _resolve_proxy_command(cls, host: Host, env_vars: dict) -> Optional[str]:
@@ -363,6 +388,12 @@ class SSHClient:
         if not interactive or self._can_use_sshpass() or not self._requires_sshpass():
             args.extend(["-o", "BatchMode=yes"])
         args.extend(["-o", f"ConnectTimeout={self.connect_timeout}"])
+        if self.control_path and not interactive:
+            args.extend(["-o", "ControlMaster=auto", "-o", f"ControlPath={self.control_path}", "-o", f"ControlPersist={self.control_persist}"])
+        if interactive:
+            from .terminal_env import send_env_options
+
+            args.extend(send_env_options())
 
         # Port
         if target_port != 22:
@@ -377,6 +408,12 @@ class SSHClient:
         # Explicit ProxyCommand takes precedence over ProxyJump.
         self._append_proxy_or_jump(args, chosen)
 
+        if interactive:
+            from .terminal_keepalive import keepalive_options
+
+            # Last, and only for options not already set: ssh keeps the first value it sees.
+            args.extend(keepalive_options(existing=args))
+
         # User@host
         if self.username:
             args.append(f"{self.username}@{target_host}")
@@ -385,7 +422,7 @@ class SSHClient:
 
         # Command
         if command:
-            args.append(command)
+            args.append(wrap_for_shell(command, self.shell))
 
         return args
 
@@ -436,13 +473,23 @@ class SSHClient:
             args = self._build_ssh_args(command, target=target)
 
             try:
-                result = subprocess.run(
-                    args,
-                    input=stdin_text if stdin_text else None,
-                    capture_output=capture_output,
-                    text=True,
-                    timeout=timeout,
-                )
+                # A live ControlMaster socket means no new handshake for this call.
+                multiplexed = bool(self.control_path and os.path.exists(self.control_path))
+                with span(
+                    "ssh",
+                    kind=KIND_CLIENT,
+                    **{"net.peer.name": target.hostname, "net.peer.port": target.port, "ssh.multiplexed": multiplexed},
+                ) as trace:
+                    result = subprocess.run(
+                        args,
+                        input=stdin_text if stdin_text else None,
+                        capture_output=capture_output,
+                        text=True,
+                        timeout=timeout,
+                    )
+                    trace.set("ssh.exit_code", result.returncode)
+                    if result.returncode == 255:
+                        trace.fail((result.stderr or "").strip()[-200:] or "connection failed")
                 ssh_result = SSHResult(
                     exit_code=result.returncode,
                     stdout=result.stdout or "",
@@ -501,21 +548,31 @@ class SSHClient:
             return " ".join(["sshpass", "-f", "<secret>", *args[3:]])
         return " ".join(args)
 
-    def connect_interactive(self) -> int:
+    def connect_interactive(self, command: Optional[str] = None, *, log_name: str = "") -> int:
         """
         Open an interactive SSH session using candidate fallback.
 
+        Args:
+            command: Optional remote command to run with a TTY instead of a login shell
+            log_name: Mirror the session into this terminal log (see services.terminal_log)
+
         Returns:
             Process exit code
         """
         last_code = 255
         for index, target in enumerate(self.connection_targets):
-            args = self._build_ssh_args(target=target, interactive=True)
-            result = subprocess.run(args)
-            last_code = result.returncode
-            if result.returncode == 255 and index < len(self.connection_targets) - 1:
+            args = self._build_ssh_args(command, target=target, interactive=True)
+            if command:
+                args.insert(args.index("ssh") + 1, "-t")
+            if log_name:
+                from .terminal_log import run_interactive
+
+                last_code = run_interactive(args, log_name)
+            else:
+                last_code = subprocess.run(args).returncode
+            if last_code == 255 and index < len(self.connection_targets) - 1:
                 continue
-            return result.returncode
+            return last_code
         return last_code
 
     def _build_scp_upload_args(
@@ -0,0 +1,109 @@
+"""Export managed hosts as an OpenSSH include so plain `ssh <alias>` reaches the same inventory."""
+
+from __future__ import annotations
+
+import os
+from pathlib import Path
+from typing import Any, Dict, Optional
+
+from ..core.models import Host, HostType
+from .vllm_service import sanitize_service_name
+from .vscode import ensure_ssh_include, render_ssh_config_entry
+
+DEFAULT_EXPORT_FILE = "~/.ssh/config.d/tmux-trainsh"
+HEADER = (
+    "# Generated by tmux-trainsh from hosts.yaml and live Vast.ai/RunPod instances.\n"
+    "# Rewritten on every host change; edit hosts with `train host edit` instead.\n"
+)
+
+
+def export_file() -> Path:
+    from ..config import get_config_value
+
+    raw = str(get_config_value("ssh_export.file", DEFAULT_EXPORT_FILE) or DEFAULT_EXPORT_FILE)
+    return Path(os.path.expanduser(raw))
+
+
+def export_alias(host_name: str) -> str:
+    from ..config import get_config_value
+
+    prefix = str(get_config_value("ssh_export.alias_prefix", "") or "")
+    return f"{prefix}{sanitize_service_name(host_name)}"
+
+
+def _static_host(host: Host) -> Host:
+    """Copy of a provider host that renders its last known endpoint without starting or probing it."""
+    copy = Host.from_dict(host.to_dict())
+    if copy.type in (HostType.VASTAI, HostType.RUNPOD):
+        copy.type = HostType.SSH
+    return copy
+
+
+def render_export(hosts: Dict[str, Host]) -> Dict[str, Any]:
+    """Include-file text plus the exported aliases and the hosts skipped with a reason."""
+    blocks = [HEADER]
+    aliases: Dict[str, str] = {}
+    skipped: Dict[str, str] = {}
+    for name in sorted(hosts):
+        host = hosts[name]
+        if host.type == HostType.LOCAL:
+            continue
+        if not host.hostname:
+            skipped[name] = "no SSH endpoint (instance stopped?)"
+            continue
+        alias = export_alias(name)
+        if alias in aliases.values():
+            skipped[name] = f"alias {alias} already used"
+            continue
+        try:
+            entry = render_ssh_config_entry(alias, _static_host(host))
+        except Exception as exc:
+            skipped[name] = str(exc) or exc.__class__.__name__
+            continue
+        aliases[name] = alias
+        blocks.append(f"# {name}\n{entry}")
+    return {"text": "\n".join(blocks), "aliases": aliases, "skipped": skipped}
+
+
+def export_ssh_config(
+    hosts: Optional[Dict[str, Host]] = None,
+    *,
+    path: Optional[Path] = None,
+    include: bool = True,
+) -> Dict[str, Any]:
+    """Rewrite the include file from the current inventory and make ~/.ssh/config include it."""
+    if hosts is None:
+        from ..commands.host import load_hosts
+
+        hosts = load_hosts()
+    target = path or export_file()
+    report = render_export(hosts)
+    target.parent.mkdir(parents=True, exist_ok=True)
+    partial = target.with_name(f".{target.name}.tmp")
+    partial.write_text(report["text"], encoding="utf-8")
+    os.chmod(partial, 0o600)
+    partial.replace(target)
+    report["path"] = target
+    report["include_added"] = ensure_ssh_include(target) if include else False
+    return report
+
+
+def refresh_if_enabled() -> None:
+    """Re-export after a host change when `ssh_export.auto_refresh` is on; never fails the caller."""
+    from ..config import get_config_value
+
+    if not get_config_value("ssh_export.auto_refresh", False):
+        return
+    try:
+        export_ssh_config()
+    except Exception:
+        pass
+
+
+__all__ = [
+    "export_alias",
+    "export_file",
+    "export_ssh_config",
+    "refresh_if_enabled",
+    "render_export",
+]
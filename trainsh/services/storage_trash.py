@@ -0,0 +1,208 @@
+"""Soft delete for storage backends: move into `.trash/` with a manifest, purge after retention."""
+
+from __future__ import annotations
+
+import json
+import os
+import posixpath
+import shutil
+import uuid
+from datetime import datetime, timedelta
+from typing import Any, Dict, List, Optional
+
+from ..core.models import Storage, StorageType
+from .file_access import storage_cli, storage_uri
+
+TRASH_DIR = ".trash"
+MANIFEST = f"{TRASH_DIR}/manifest.json"
+DEFAULT_RETENTION_DAYS = 7
+
+
+def trash_settings() -> Dict[str, Any]:
+    """`storage.trash` config: `enabled` (soft delete by default) and `retention_days`."""
+    from ..config import get_config_value
+
+    return {
+        "enabled": bool(get_config_value("storage.trash.enabled", False)),
+        "retention_days": float(get_config_value("storage.trash.retention_days", DEFAULT_RETENTION_DAYS) or 0),
+    }
+
+
+def _relative(path: str) -> str:
+    return posixpath.normpath("/" + str(path or "").strip()).lstrip("/")
+
+
+def _local_path(storage: Storage, path: str) -> str:
+    base = os.path.expanduser(str(storage.config.get("path", "")).strip() or ".")
+    return os.path.join(base, _relative(path))
+
+
+class StorageTrash:
+    """The `.trash/` area of one storage backend.
+
+    Entries live at `.trash/<id>/<basename>`; `.trash/manifest.json` records where
+    each came from and when. Local storages use the filesystem, everything else
+    server-side rclone moves. HF buckets have no server-side move and are refused.
+    """
+
+    def __init__(self, storage: Storage):
+        if storage.type == StorageType.HF:
+            raise ValueError(f"Trash is not supported for HF bucket storage {storage.name}")
+        self.storage = storage
+
+    # Backend primitives -------------------------------------------------
+
+    def _local(self, path: str) -> str:
+        return _local_path(self.storage, path)
+
+    def _exists(self, path: str) -> bool:
+        if self.storage.type == StorageType.LOCAL:
+            return os.path.lexists(self._local(path))
+        try:
+            storage_cli(self.storage, ["lsjson", "--stat", storage_uri(self.storage, path)])
+        except RuntimeError:
+            return False
+        return True
+
+    def _move(self, source: str, destination: str) -> None:
+        if self.storage.type == StorageType.LOCAL:
+            target = self._local(destination)
+            os.makedirs(os.path.dirname(target), exist_ok=True)
+            shutil.move(self._local(source), target)
+            return
+        storage_cli(self.storage, ["moveto", storage_uri(self.storage, source), storage_uri(self.storage, destination)])
+
+    def _purge(self, path: str) -> None:
+        if self.storage.type == StorageType.LOCAL:
+            target = self._local(path)
+            if os.path.isdir(target) and not os.path.islink(target):
+                shutil.rmtree(target)
+            elif os.path.lexists(target):
+                os.remove(target)
+            return
+        storage_cli(self.storage, ["purge", storage_uri(self.storage, path)])
+
+    def _read_manifest(self) -> List[Dict[str, Any]]:
+        try:
+            if self.storage.type == StorageType.LOCAL:
+                with open(self._local(MANIFEST), "r", encoding="utf-8") as handle:
+                    data = json.load(handle)
+            else:
+                data = json.loads(storage_cli(self.storage, ["cat", storage_uri(self.storage, MANIFEST)]) or b"[]")
+        except (OSError, RuntimeError, ValueError):
+            return []
+        return [entry for entry in data if isinstance(entry, dict)] if isinstance(data, list) else []
+
+    def _write_manifest(self, entries: List[Dict[str, Any]]) -> None:
+        payload = json.dumps(entries, ensure_ascii=False, indent=2).encode("utf-8")
+        if self.storage.type == StorageType.LOCAL:
+            target = self._local(MANIFEST)
+            os.makedirs(os.path.dirname(target), exist_ok=True)
+            with open(target, "wb") as handle:
+                handle.write(payload)
+            return
+        storage_cli(self.storage, ["rcat", storage_uri(self.storage, MANIFEST)], stdin=payload)
+
+    # Operations ---------------------------------------------------------
+
+    def delete(self, path: str, *, recursive: bool = False) -> Dict[str, Any]:
+        """Move `path` into the trash and record it; expired entries are purged first."""
+        relative = _relative(path)
+        if not relative or relative == TRASH_DIR or relative.startswith(f"{TRASH_DIR}/"):
+            raise ValueError(f"Refusing to trash {path or 'the storage root'}")
+        if not self._exists(relative):
+            raise FileNotFoundError(f"Storage path not found: {path}")
+        if self.storage.type == StorageType.LOCAL and os.path.isdir(self._local(relative)) and not recursive:
+            raise IsADirectoryError(f"Storage path is directory: {path} (set recursive=True to remove)")
+
+        self.purge_expired()
+        entry_id = datetime.now().strftime("%Y%m%d%H%M%S") + "-" + uuid.uuid4().hex[:6]
+        entry = {
+            "id": entry_id,
+            "original_path": relative,
+            "trash_path": f"{TRASH_DIR}/{entry_id}/{posixpath.basename(relative)}",
+            "deleted_at": datetime.now().isoformat(),
+            "recursive": bool(recursive),
+        }
+        self._move(relative, entry["trash_path"])
+        self._write_manifest([*self._read_manifest(), entry])
+        return entry
+
+    def list(self) -> List[Dict[str, Any]]:
+        """Trashed entries, newest first, each with its `expires_at`."""
+        retention = trash_settings()["retention_days"]
+        entries = []
+        for entry in self._read_manifest():
+            item = dict(entry)
+            if retention > 0:
+                try:
+                    deleted = datetime.fromisoformat(str(entry.get("deleted_at", "")))
+                    item["expires_at"] = (deleted + timedelta(days=retention)).isoformat()
+                except ValueError:
+                    pass
+            entries.append(item)
+        return sorted(entries, key=lambda item: str(item.get("deleted_at", "")), reverse=True)
+
+    def _find(self, entries: List[Dict[str, Any]], entry_id: str) -> Dict[str, Any]:
+        matches = [entry for entry in entries if str(entry.get("id", "")).startswith(entry_id)]
+        if len(matches) != 1:
+            raise KeyError(f"{'Ambiguous' if matches else 'Unknown'} trash entry: {entry_id}")
+        return matches[0]
+
+    def restore(self, entry_id: str, *, destination: Optional[str] = None) -> Dict[str, Any]:
+        """Move an entry back to its original path (or `destination`); never overwrites."""
+        entries = self._read_manifest()
+        entry = self._find(entries, entry_id)
+        target = _relative(destination) if destination else entry["original_path"]
+        if self._exists(target):
+            raise FileExistsError(f"Restore target already exists: {target}")
+        self._move(entry["trash_path"], target)
+        try:
+            self._purge(posixpath.dirname(entry["trash_path"]))
+        except RuntimeError:
+            pass  # bucket backends drop the now-empty prefix on their own
+        self._write_manifest([item for item in entries if item is not entry])
+        return {**entry, "restored_to": target}
+
+    def empty(self, *, older_than_days: Optional[float] = None) -> List[Dict[str, Any]]:
+        """Hard-delete entries older than `older_than_days` (all entries when None)."""
+        entries = self._read_manifest()
+        cutoff = None if older_than_days is None else datetime.now() - timedelta(days=older_than_days)
+        removed, kept = [], []
+        for entry in entries:
+            try:
+                deleted = datetime.fromisoformat(str(entry.get("deleted_at", "")))
+            except ValueError:
+                deleted = datetime.min
+            if cutoff is None or deleted <= cutoff:
+                self._purge(posixpath.dirname(entry["trash_path"]))
+                removed.append(entry)
+            else:
+                kept.append(entry)
+        if removed:
+            self._write_manifest(kept)
+        return removed
+
+    def purge_expired(self) -> List[Dict[str, Any]]:
+        """Hard-delete entries past `storage.trash.retention_days` (0 keeps them forever)."""
+        retention = trash_settings()["retention_days"]
+        return self.empty(older_than_days=retention) if retention > 0 else []
+
+
+def hard_delete(storage: Storage, path: str, *, recursive: bool = False) -> None:
+    """Permanently delete one path, bypassing the trash."""
+    if storage.type == StorageType.LOCAL:
+        target = _local_path(storage, path)
+        if os.path.isdir(target) and not os.path.islink(target):
+            if not recursive:
+                raise IsADirectoryError(f"Storage path is directory: {path} (pass --recursive)")
+            shutil.rmtree(target)
+        else:
+            os.remove(target)
+    elif storage.type == StorageType.HF:
+        storage_cli(storage, ["rm", storage_uri(storage, path), *(["--recursive"] if recursive else [])])
+    else:
+        storage_cli(storage, ["purge" if recursive else "deletefile", storage_uri(storage, path)])
+
+
+__all__ = ["DEFAULT_RETENTION_DAYS", "MANIFEST", "StorageTrash", "TRASH_DIR", "hard_delete", "trash_settings"]
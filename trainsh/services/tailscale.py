@@ -0,0 +1,97 @@
+"""Resolve hosts by Tailscale node name through the local `tailscale` CLI."""
+
+from __future__ import annotations
+
+import json
+import subprocess
+from typing import Any, Dict, List, Optional
+
+
+class TailscaleError(RuntimeError):
+    """Tailscale is unavailable, or the node is unknown or offline."""
+
+
+def tailscale_binary() -> str:
+    """`tailscale.command` from config, e.g. the macOS app's bundled CLI path."""
+    from ..config import get_config_value
+
+    return str(get_config_value("tailscale.command", "tailscale") or "tailscale").strip() or "tailscale"
+
+
+def tailscale_status(timeout: int = 10) -> Dict[str, Any]:
+    """Parsed `tailscale status --json`."""
+    binary = tailscale_binary()
+    try:
+        result = subprocess.run([binary, "status", "--json"], capture_output=True, text=True, timeout=timeout)
+    except FileNotFoundError:
+        raise TailscaleError(f"Tailscale CLI not found ({binary}); install Tailscale or set tailscale.command.")
+    except subprocess.TimeoutExpired:
+        raise TailscaleError("`tailscale status` timed out; is tailscaled running?")
+    try:
+        data = json.loads(result.stdout or "")
+    except ValueError:
+        detail = (result.stderr or result.stdout or "").strip().splitlines()
+        raise TailscaleError(detail[-1] if detail else f"`tailscale status` failed (exit {result.returncode})")
+    state = str(data.get("BackendState", "") or "")
+    if state and state != "Running":
+        raise TailscaleError(f"Tailscale is not connected on this machine (state: {state}); run `tailscale up`.")
+    return data
+
+
+def _node(peer: Dict[str, Any], *, is_self: bool = False) -> Dict[str, Any]:
+    dns_name = str(peer.get("DNSName", "") or "").rstrip(".")
+    return {
+        "name": str(peer.get("HostName", "") or ""),
+        "dns_name": dns_name,
+        "ips": [str(ip) for ip in peer.get("TailscaleIPs") or []],
+        "online": True if is_self else bool(peer.get("Online")),
+        "os": str(peer.get("OS", "") or ""),
+        "last_seen": str(peer.get("LastSeen", "") or ""),
+        "self": is_self,
+    }
+
+
+def list_nodes(status: Optional[Dict[str, Any]] = None) -> List[Dict[str, Any]]:
+    """Tailnet nodes from `tailscale status`, this machine first."""
+    data = tailscale_status() if status is None else status
+    nodes = [_node(data["Self"], is_self=True)] if data.get("Self") else []
+    peers = [_node(peer) for peer in (data.get("Peer") or {}).values()]
+    return nodes + sorted(peers, key=lambda node: (not node["online"], node["name"].lower()))
+
+
+def find_node(nodes: List[Dict[str, Any]], name: str) -> Optional[Dict[str, Any]]:
+    """Match a node by host name, MagicDNS short name, or full MagicDNS name."""
+    wanted = str(name or "").strip().rstrip(".").lower()
+    for node in nodes:
+        dns_name = node["dns_name"].lower()
+        if wanted in (node["name"].lower(), dns_name, dns_name.split(".", 1)[0]):
+            return node
+    return None
+
+
+def node_address(node: Dict[str, Any]) -> str:
+    """The node's Tailscale IPv4 address, else its first address or MagicDNS name."""
+    ips = node["ips"]
+    return next((ip for ip in ips if ":" not in ip), ips[0] if ips else node["dns_name"])
+
+
+def resolve_node(name: str, status: Optional[Dict[str, Any]] = None) -> Dict[str, Any]:
+    """Find an online node by name; raises TailscaleError when it is unknown or offline."""
+    node = find_node(list_nodes(status), name)
+    if node is None:
+        raise TailscaleError(f"Tailscale node not found: {name} (see `train host tailscale`).")
+    if not node["online"]:
+        seen = f", last seen {node['last_seen']}" if node["last_seen"] and not node["last_seen"].startswith("0001") else ""
+        raise TailscaleError(f"Tailscale node {node['name'] or name} is offline{seen}.")
+    return node
+
+
+__all__ = [
+    "TailscaleError",
+    "find_node",
+    "list_nodes",
+    "node_address",
+    "resolve_node",
+    "tailscale_binary",
+    "tailscale_status",
+]
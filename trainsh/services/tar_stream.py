@@ -0,0 +1,180 @@
+"""Zstd-compressed tar streaming for directories with very many small files."""
+
+from __future__ import annotations
+
+import os
+import shlex
+import subprocess
+import tempfile
+from typing import Any, Dict, List, Optional, Sequence
+
+from .transfer_progress import ProgressReporter
+from .transfer_support import TransferProgress, TransferResult
+
+DEFAULT_LEVEL = 3
+DEFAULT_SUGGEST_FILES = 100_000
+# Probe in a subshell first: an unknown `set` option would abort a POSIX sh.
+_CHUNK = 1024 * 1024
+_PIPEFAIL = "(set -o pipefail) 2>/dev/null && set -o pipefail; "
+
+
+def tar_stream_settings(config: Optional[Dict[str, Any]] = None) -> Dict[str, int]:
+    """Config `transfer.tar_stream` with defaults (zstd level, suggestion threshold)."""
+    if config is None:
+        from ..config import load_config
+
+        config = load_config()
+    raw = (config.get("transfer") or {}).get("tar_stream") or {}
+    try:
+        level = int(raw.get("level", DEFAULT_LEVEL))
+    except (TypeError, ValueError):
+        level = DEFAULT_LEVEL
+    try:
+        suggest_files = int(raw.get("suggest_files", DEFAULT_SUGGEST_FILES))
+    except (TypeError, ValueError):
+        suggest_files = DEFAULT_SUGGEST_FILES
+    return {"level": min(max(level, 1), 19), "suggest_files": max(suggest_files, 0)}
+
+
+def count_files(path: str, limit: int) -> int:
+    """Count regular files under a local directory, stopping once `limit` is reached."""
+    root = os.path.expanduser(path)
+    if not os.path.isdir(root):
+        return 0
+    count = 0
+    for _dirpath, _dirnames, filenames in os.walk(root):
+        count += len(filenames)
+        if limit and count >= limit:
+            return count
+    return count
+
+
+def suggest_tar_stream(path: str, *, config: Optional[Dict[str, Any]] = None) -> Optional[str]:
+    """Hint text when a local source directory holds more files than the configured threshold."""
+    threshold = tar_stream_settings(config)["suggest_files"]
+    if threshold <= 0:
+        return None
+    count = count_files(path, threshold)
+    if count < threshold:
+        return None
+    return (
+        f"Source has {count:,}+ files; per-file overhead dominates rsync here. "
+        "Consider --tar-stream (recipe: tar_stream=True)."
+    )
+
+
+def shell_path(path: str) -> str:
+    """Quote a path for a POSIX shell while keeping a leading `~/` expandable."""
+    if path == "~":
+        return '"$HOME"'
+    if path.startswith("~/"):
+        return '"$HOME"/' + shlex.quote(path[2:])
+    return shlex.quote(path)
+
+
+def pack_command(source: str, exclude: Sequence[str] = (), level: int = DEFAULT_LEVEL) -> str:
+    """Shell command writing `source` as a zstd tar stream to stdout.
+
+    A directory is archived by its contents; a single file by its basename.
+    """
+    src = shell_path(source.rstrip("/") or "/")
+    excludes = "".join(f" --exclude={shlex.quote(pattern)}" for pattern in exclude)
+    return (
+        f"{_PIPEFAIL}"
+        f"if [ -d {src} ]; then tar -C {src}{excludes} -cf - .; "
+        f'else tar -C "$(dirname {src})"{excludes} -cf - "$(basename {src})"; fi'
+        f" | zstd -q -T0 -{int(level)} -c"
+    )
+
+
+def unpack_command(destination: str) -> str:
+    """Shell command extracting a zstd tar stream from stdin into `destination`."""
+    dst = shell_path(destination.rstrip("/") or "/")
+    return f"{_PIPEFAIL}mkdir -p {dst} && zstd -q -d -c | tar -C {dst} -xf -"
+
+
+def run_pipeline(
+    producer: List[str],
+    consumer: List[str],
+    *,
+    reporter: Optional[ProgressReporter] = None,
+) -> TransferResult:
+    """Relay `producer` stdout into `consumer` stdin, counting streamed (compressed) bytes."""
+    reporter = reporter or ProgressReporter(backend="tar-stream")
+    streamed = 0
+    try:
+        with tempfile.TemporaryFile() as sender_err, tempfile.TemporaryFile() as receiver_err:
+            sender = subprocess.Popen(producer, stdout=subprocess.PIPE, stderr=sender_err)
+            receiver = subprocess.Popen(consumer, stdin=subprocess.PIPE, stdout=subprocess.DEVNULL, stderr=receiver_err)
+            assert sender.stdout is not None and receiver.stdin is not None
+            try:
+                for chunk in iter(lambda: sender.stdout.read(_CHUNK), b""):
+                    receiver.stdin.write(chunk)
+                    streamed += len(chunk)
+                    reporter.update(TransferProgress(bytes_transferred=streamed, backend="tar-stream"))
+            except BrokenPipeError:
+                pass
+            finally:
+                sender.stdout.close()
+                try:
+                    receiver.stdin.close()
+                except BrokenPipeError:
+                    pass
+            sender_code = sender.wait()
+            receiver_code = receiver.wait()
+            errors = []
+            for handle in (sender_err, receiver_err):
+                handle.seek(0)
+                text = handle.read().decode(errors="replace").strip()
+                if text:
+                    errors.append(text)
+    except Exception as exc:
+        return TransferResult(success=False, exit_code=-1, message=str(exc))
+
+    progress = reporter.finish()
+    if sender_code != 0 or receiver_code != 0:
+        return TransferResult(
+            success=False,
+            exit_code=sender_code or receiver_code,
+            message="\n".join(errors[-5:]) or "Tar stream failed",
+            bytes_transferred=streamed,
+            progress=progress,
+        )
+    return TransferResult(
+        success=True,
+        exit_code=0,
+        message="Transfer complete (tar+zstd stream)",
+        bytes_transferred=streamed,
+        progress=progress,
+    )
+
+
+def stream_command(
+    source: str,
+    destination: str,
+    *,
+    src_ssh: Optional[List[str]] = None,
+    dst_ssh: Optional[List[str]] = None,
+    exclude: Sequence[str] = (),
+    level: int = DEFAULT_LEVEL,
+) -> tuple[List[str], List[str]]:
+    """Producer/consumer argv; each side runs locally or through its ssh argv."""
+    pack = pack_command(source, exclude, level)
+    unpack = unpack_command(destination)
+    producer = [*src_ssh, pack] if src_ssh else ["sh", "-c", pack]
+    consumer = [*dst_ssh, unpack] if dst_ssh else ["sh", "-c", unpack]
+    return producer, consumer
+
+
+__all__ = [
+    "DEFAULT_LEVEL",
+    "DEFAULT_SUGGEST_FILES",
+    "count_files",
+    "pack_command",
+    "run_pipeline",
+    "shell_path",
+    "stream_command",
+    "suggest_tar_stream",
+    "tar_stream_settings",
+    "unpack_command",
+]
@@ -0,0 +1,152 @@
+"""Broadcast groups: send the same keystrokes to tmux sessions on several hosts.
+
+Groups live in config.yaml under `broadcast.groups` as `{name: [target, ...]}`,
+where a target is `<host>` or `<host>:<tmux-session>`. A target without a
+session types into `train-broadcast-<group>` on that host, created on first use.
+"""
+
+from __future__ import annotations
+
+import re
+from concurrent.futures import ThreadPoolExecutor
+from dataclasses import dataclass
+from typing import Any, Callable, Dict, List, Optional
+
+GROUP_NAME_RE = re.compile(r"^[A-Za-z0-9_.-]+$")
+DEFAULT_SESSION_PREFIX = "train-broadcast-"
+MAX_WORKERS = 8
+
+
+@dataclass(frozen=True)
+class BroadcastTarget:
+    host: str
+    session: str = ""
+
+    @classmethod
+    def parse(cls, text: str) -> "BroadcastTarget":
+        host, _, session = str(text).strip().partition(":")
+        if not host:
+            raise ValueError(f"invalid broadcast target: {text!r} (use <host> or <host>:<session>)")
+        return cls(host=host, session=session)
+
+    def __str__(self) -> str:
+        return f"{self.host}:{self.session}" if self.session else self.host
+
+
+@dataclass(frozen=True)
+class BroadcastResult:
+    """What one terminal of a group received."""
+
+    target: str
+    ok: bool
+    detail: str = ""
+
+    def line(self, data: str) -> str:
+        if not self.ok:
+            return f"[{self.target}] failed: {self.detail}"
+        return f"[{self.target}] sent: {data}"
+
+
+def configured_groups() -> Dict[str, List[str]]:
+    from ..config import get_config_value
+
+    groups = get_config_value("broadcast.groups", {}) or {}
+    if not isinstance(groups, dict):
+        return {}
+    return {str(name): [str(item) for item in (targets or [])] for name, targets in groups.items()}
+
+
+def save_group(name: str, targets: List[str]) -> List[BroadcastTarget]:
+    """Create or replace one group; targets are validated first."""
+    from ..config import set_config_value
+
+    if not GROUP_NAME_RE.match(name):
+        raise ValueError(f"invalid group name: {name!r} (letters, digits, '.', '_' and '-')")
+    parsed = [BroadcastTarget.parse(item) for item in targets]
+    if not parsed:
+        raise ValueError("a broadcast group needs at least one target")
+    set_config_value(f"broadcast.groups.{name}", [str(item) for item in parsed])
+    return parsed
+
+
+def remove_group(name: str) -> bool:
+    from ..config import load_config, save_config
+
+    config = load_config()
+    groups = (config.get("broadcast") or {}).get("groups") or {}
+    if name not in groups:
+        return False
+    del groups[name]
+    save_config(config)
+    return True
+
+
+def _default_client(host: str) -> Any:
+    if host == "local":
+        from ..core.local_tmux import LocalTmuxClient
+
+        return LocalTmuxClient()
+    from ..core.executor_utils import _build_ssh_args
+    from ..core.remote_tmux import RemoteTmuxClient
+
+    return RemoteTmuxClient(host, _build_ssh_args)
+
+
+def _write_one(
+    group: str,
+    target: BroadcastTarget,
+    data: str,
+    enter: bool,
+    client_factory: Callable[[str], Any],
+) -> BroadcastResult:
+    session = target.session or f"{DEFAULT_SESSION_PREFIX}{group}"
+    label = f"{target.host}:{session}"
+    try:
+        client = client_factory(target.host)
+        if not client.has_session(session):
+            if target.session:
+                return BroadcastResult(label, False, "no such tmux session")
+            created = client.new_session(session, detached=True)
+            if created.returncode != 0:
+                return BroadcastResult(label, False, (created.stderr or "").strip() or "could not create session")
+        result = client.send_keys(session, data, enter=enter, literal=True)
+    except Exception as exc:
+        return BroadcastResult(label, False, str(exc))
+    if result.returncode != 0:
+        return BroadcastResult(label, False, (result.stderr or "").strip() or f"tmux exited with {result.returncode}")
+    return BroadcastResult(label, True)
+
+
+def broadcast_write(
+    group: str,
+    data: str,
+    *,
+    enter: bool = True,
+    targets: Optional[List[str]] = None,
+    client_factory: Callable[[str], Any] = _default_client,
+) -> List[BroadcastResult]:
+    """Type `data` (plus Enter unless `enter=False`) into every terminal of `group`, in parallel.
+
+    Results come back in the group's target order.
+    """
+    if targets is None:
+        groups = configured_groups()
+        if group not in groups:
+            raise KeyError(f"unknown broadcast group: {group}")
+        targets = groups[group]
+    parsed = [BroadcastTarget.parse(item) for item in targets]
+    if not parsed:
+        return []
+    with ThreadPoolExecutor(max_workers=min(MAX_WORKERS, len(parsed))) as pool:
+        futures = [pool.submit(_write_one, group, target, data, enter, client_factory) for target in parsed]
+        return [future.result() for future in futures]
+
+
+__all__ = [
+    "BroadcastResult",
+    "BroadcastTarget",
+    "broadcast_write",
+    "configured_groups",
+    "remove_group",
+    "save_group",
+]
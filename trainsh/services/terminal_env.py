@@ -0,0 +1,112 @@
+"""TERM, locale and environment for SSH terminals, and tmux window sizing.
+
+Interactive ssh asks the server to accept `terminal.send_env` (`SendEnv`, so
+locale follows the local terminal where sshd allows it). Commands that set up
+a terminal themselves get an assignment preamble: `terminal.term`,
+`terminal.locale`, then `terminal.default_env_vars`. Login shells and recipe
+tmux sessions export `default_env_vars` under the host's own `defaults.env`.
+
+Sessions trainsh creates detached would start at tmux's 80x24; `fit_window`
+sizes them to the local terminal and sets `window-size latest` so every later
+client resize is followed.
+"""
+
+from __future__ import annotations
+
+import dataclasses
+import shlex
+import shutil
+from typing import Any, Dict, List, Optional, Tuple
+
+from .session_env import ENV_NAME_RE
+
+DEFAULT_TERM = "xterm-256color"
+DEFAULT_LOCALE = "en_US.UTF-8"
+DEFAULT_SEND_ENV = ("LANG", "LC_*", "COLORTERM")
+
+
+def _terminal_section(config: Optional[Dict[str, Any]]) -> Dict[str, Any]:
+    if config is None:
+        try:
+            from ..config import load_config
+
+            config = load_config()
+        except Exception:
+            config = {}
+    return dict(config.get("terminal") or {})
+
+
+def default_env_vars(config: Optional[Dict[str, Any]] = None) -> Dict[str, str]:
+    """`terminal.default_env_vars`, skipping names a shell could not export."""
+    env = _terminal_section(config).get("default_env_vars") or {}
+    if not isinstance(env, dict):
+        return {}
+    return {str(key): str(value) for key, value in env.items() if ENV_NAME_RE.match(str(key))}
+
+
+def send_env_options(config: Optional[Dict[str, Any]] = None) -> List[str]:
+    """ssh `-o SendEnv=...` for an interactive session; empty when `terminal.send_env` is empty."""
+    names = _terminal_section(config).get("send_env", list(DEFAULT_SEND_ENV))
+    if isinstance(names, str):
+        names = names.split()
+    names = [str(name) for name in names or [] if str(name).strip()]
+    if not names:
+        return []
+    return ["-o", "SendEnv=" + " ".join(names)]
+
+
+def env_preamble(config: Optional[Dict[str, Any]] = None) -> str:
+    """`TERM=... LC_ALL=... NAME=value` to put in front of a command started with a tty."""
+    section = _terminal_section(config)
+    assignments = {
+        "TERM": str(section.get("term") or DEFAULT_TERM),
+        "LC_ALL": str(section.get("locale") or DEFAULT_LOCALE),
+    }
+    assignments.update(default_env_vars(config))
+    return " ".join(f"{key}={shlex.quote(value)}" for key, value in assignments.items())
+
+
+def session_defaults(host: Any, config: Optional[Dict[str, Any]] = None) -> Any:
+    """The host's `HostDefaults` with `terminal.default_env_vars` underneath its own env."""
+    from .host_defaults import HostDefaults
+
+    defaults = HostDefaults.for_host(host)
+    extra = default_env_vars(config)
+    if not extra:
+        return defaults
+    return dataclasses.replace(defaults, env={**extra, **defaults.env})
+
+
+def local_terminal_size() -> Optional[Tuple[int, int]]:
+    """(columns, rows) of the terminal trainsh runs in, or None without one."""
+    size = shutil.get_terminal_size((0, 0))
+    if size.columns <= 0 or size.lines <= 0:
+        return None
+    return size.columns, size.lines
+
+
+def fit_window(tmux: Any, session: str, size: Optional[Tuple[int, int]] = None) -> bool:
+    """Resize a session's window to `size` (default: the local terminal) and let clients resize it.
+
+    Returns False when there was no size to apply.
+    """
+    size = size or local_terminal_size()
+    if size is None:
+        return False
+    columns, rows = size
+    # resize-window switches the window to manual sizing; hand it back to the latest client.
+    tmux.resize_window(session, columns, rows)
+    tmux.run("set-option", "-t", session, "window-size", "latest")
+    return True
+
+
+__all__ = [
+    "DEFAULT_LOCALE",
+    "DEFAULT_TERM",
+    "default_env_vars",
+    "env_preamble",
+    "fit_window",
+    "local_terminal_size",
+    "send_env_options",
+    "session_defaults",
+]
@@ -0,0 +1,187 @@
+"""Keep-alive and reconnection for interactive SSH terminals.
+
+Interactive sessions get `ServerAliveInterval`/`ServerAliveCountMax`, so a
+dead network makes ssh exit with 255 instead of leaving a frozen pane.
+`TerminalSupervisor` runs an attach command and, when it exits with 255,
+reports `terminal:connection_lost`, probes the host with backoff, then
+re-attaches to the same tmux session and reports `terminal:connection_restored`.
+"""
+
+from __future__ import annotations
+
+import shlex
+import subprocess
+import sys
+import time
+from dataclasses import dataclass
+from datetime import datetime
+from typing import Any, Callable, Dict, List, Optional, Sequence
+
+CONNECTION_LOST_EVENT = "terminal:connection_lost"
+CONNECTION_RESTORED_EVENT = "terminal:connection_restored"
+EVENT_RUN_ID = "terminal"
+SSH_CONNECTION_FAILED = 255
+
+
+@dataclass(frozen=True)
+class KeepalivePolicy:
+    """`terminal` config section."""
+
+    interval_secs: int = 15
+    count_max: int = 3
+    reconnect: bool = True
+    delay_secs: float = 2.0
+    max_delay_secs: float = 30.0
+    max_attempts: int = 0
+
+
+def keepalive_policy(config: Optional[Dict[str, Any]] = None) -> KeepalivePolicy:
+    """Policy from config.yaml `terminal`, falling back to the defaults."""
+    if config is None:
+        from ..config import load_config
+
+        config = load_config()
+    section = dict(config.get("terminal") or {})
+    default = KeepalivePolicy()
+    return KeepalivePolicy(
+        interval_secs=max(0, int(section.get("keepalive_interval_secs", default.interval_secs) or 0)),
+        count_max=max(1, int(section.get("keepalive_count_max", default.count_max) or default.count_max)),
+        reconnect=bool(section.get("reconnect", default.reconnect)),
+        delay_secs=max(0.1, float(section.get("reconnect_delay_secs", default.delay_secs) or default.delay_secs)),
+        max_delay_secs=max(0.1, float(section.get("reconnect_max_delay_secs", default.max_delay_secs) or default.max_delay_secs)),
+        max_attempts=max(0, int(section.get("reconnect_max_attempts", default.max_attempts) or 0)),
+    )
+
+
+def keepalive_options(policy: Optional[KeepalivePolicy] = None, *, existing: Sequence[str] = ()) -> List[str]:
+    """ssh `-o` options for an interactive session; empty when keep-alives are off.
+
+    Options already set in `existing` (e.g. a host spec's own `-o` flags) are
+    left out, so the user's values win.
+    """
+    try:
+        policy = policy or keepalive_policy()
+    except Exception:
+        policy = KeepalivePolicy()
+    if policy.interval_secs <= 0:
+        return []
+    taken = {item.split("=", 1)[0].removeprefix("-o").strip().lower() for item in existing if "=" in item}
+    options = []
+    for key, value in (("ServerAliveInterval", policy.interval_secs), ("ServerAliveCountMax", policy.count_max)):
+        if key.lower() not in taken:
+            options.extend(["-o", f"{key}={value}"])
+    return options
+
+
+def emit_terminal_event(event: str, *, run_id: str = "", **payload: Any) -> None:
+    """Append one terminal event to the runtime event log (under the job when known)."""
+    from ..core.runtime_store import RuntimeStore, get_runtime_state_dir
+
+    RuntimeStore(get_runtime_state_dir()).append_event(
+        {
+            "run_id": run_id or EVENT_RUN_ID,
+            "event": event,
+            "event_name": event,
+            "step_num": None,
+            "payload": payload,
+            "ts": datetime.now().isoformat(),
+        }
+    )
+
+
+class TerminalSupervisor:
+    """Run one attach command until it exits for a reason other than a lost connection."""
+
+    def __init__(
+        self,
+        attach: Callable[[], int],
+        probe: Callable[[], bool],
+        *,
+        window: str,
+        host: str,
+        run_id: str = "",
+        policy: Optional[KeepalivePolicy] = None,
+        emit: Callable[..., None] = emit_terminal_event,
+        sleep: Callable[[float], None] = time.sleep,
+        clock: Callable[[], float] = time.monotonic,
+        log: Callable[[str], None] = print,
+    ):
+        self.attach = attach
+        self.probe = probe
+        self.window = window
+        self.host = host
+        self.run_id = run_id
+        self.policy = policy or KeepalivePolicy()
+        self.emit = emit
+        self.sleep = sleep
+        self.clock = clock
+        self.log = log
+
+    def _event(self, event: str, **payload: Any) -> None:
+        try:
+            self.emit(event, run_id=self.run_id, window=self.window, host=self.host, **payload)
+        except Exception:
+            pass
+
+    def _wait_for_host(self) -> int:
+        """Probe with backoff; return the number of probes, or 0 after giving up."""
+        delay = self.policy.delay_secs
+        attempt = 0
+        while not self.policy.max_attempts or attempt < self.policy.max_attempts:
+            attempt += 1
+            self.log(f"[train] connection to {self.host} lost; retrying in {delay:g}s (attempt {attempt})")
+            self.sleep(delay)
+            if self.probe():
+                return attempt
+            delay = min(delay * 2, self.policy.max_delay_secs)
+        return 0
+
+    def run(self) -> int:
+        while True:
+            code = self.attach()
+            if code != SSH_CONNECTION_FAILED or not self.policy.reconnect:
+                return code
+            lost_at = self.clock()
+            self._event(CONNECTION_LOST_EVENT, exit_code=code)
+            attempts = self._wait_for_host()
+            if not attempts:
+                self.log(f"[train] giving up on {self.host} after {self.policy.max_attempts} attempts")
+                return code
+            self._event(CONNECTION_RESTORED_EVENT, attempts=attempts, downtime_secs=round(self.clock() - lost_at, 1))
+            self.log(f"[train] connection to {self.host} restored; re-attaching @{self.window}")
+
+
+def supervised_attach_command(attach_command: str, *, job_id: str, window: str, host: str, session: str) -> str:
+    """Shell command for a bridge pane that re-attaches `attach_command` after connection loss.
+
+    `attach_command` is returned unchanged when `terminal.reconnect` is off.
+    """
+    try:
+        enabled = keepalive_policy().reconnect
+    except Exception:
+        enabled = True
+    if not enabled:
+        return attach_command
+    args = [
+        sys.executable, "-m", "trainsh", "recipe", "attach", job_id or "-", window,
+        "--host", host, "--session", session, "--command", attach_command,
+    ]
+    return shlex.join(args)
+
+
+def shell_attach(command: str) -> Callable[[], int]:
+    """Attach callable running a shell command on the current terminal."""
+    return lambda: subprocess.run(command, shell=True).returncode
+
+
+__all__ = [
+    "CONNECTION_LOST_EVENT",
+    "CONNECTION_RESTORED_EVENT",
+    "KeepalivePolicy",
+    "TerminalSupervisor",
+    "emit_terminal_event",
+    "keepalive_options",
+    "keepalive_policy",
+    "shell_attach",
+    "supervised_attach_command",
+]
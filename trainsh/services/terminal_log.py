@@ -0,0 +1,159 @@
+"""Mirror interactive terminal sessions into local log files.
+
+Opt-in (`terminal_log.enabled` or `--log`): the SSH client runs under a
+pseudo-terminal and every byte it prints is also appended to
+`<logs>/terminals/<name>.log`, rotated by size into `.log.1`, `.log.2`, …
+Only what the terminal displays is recorded, not keystrokes.
+"""
+
+from __future__ import annotations
+
+import os
+import re
+import sys
+from datetime import datetime
+from pathlib import Path
+from typing import Any, Dict, List, Optional, Sequence
+
+from ..constants import LOGS_DIR
+from .vllm_service import sanitize_service_name
+
+TERMINAL_LOGS_DIR = LOGS_DIR / "terminals"
+DEFAULT_MAX_BYTES = 10 * 1024 * 1024
+DEFAULT_KEEP = 3
+# Cursor movement, colors and other escape sequences, stripped when reading logs back.
+_ANSI = re.compile(r"\x1b\[[0-?]*[ -/]*[@-~]|\x1b\][^\x07\x1b]*(?:\x07|\x1b\\)|\x1b[()][A-Za-z0-9]|\x1b[=>]")
+
+
+def terminal_log_settings(config: Optional[Dict[str, Any]] = None) -> Dict[str, Any]:
+    """`terminal_log` config: `enabled`, `max_bytes` per file and rotated files to `keep`."""
+    if config is None:
+        from ..config import load_config
+
+        config = load_config()
+    section = (config or {}).get("terminal_log", {}) or {}
+    settings: Dict[str, Any] = {"enabled": bool(section.get("enabled", False))}
+    for key, default in (("max_bytes", DEFAULT_MAX_BYTES), ("keep", DEFAULT_KEEP)):
+        try:
+            settings[key] = max(0, int(section.get(key, default)))
+        except (TypeError, ValueError):
+            settings[key] = default
+    return settings
+
+
+def terminal_log_path(name: str, root: Optional[Path] = None) -> Path:
+    return Path(root or TERMINAL_LOGS_DIR) / f"{sanitize_service_name(name)}.log"
+
+
+class RotatingLogWriter:
+    """Append-only byte log that moves `name.log` to `name.log.1` past `max_bytes`."""
+
+    def __init__(self, path: Path, *, max_bytes: int = DEFAULT_MAX_BYTES, keep: int = DEFAULT_KEEP):
+        self.path = Path(path)
+        self.max_bytes = max(0, int(max_bytes))
+        self.keep = max(0, int(keep))
+        self.path.parent.mkdir(parents=True, exist_ok=True)
+        self._handle = self.path.open("ab")
+        self._size = self.path.stat().st_size
+
+    def write(self, data: bytes) -> None:
+        if self.max_bytes and self._size + len(data) > self.max_bytes and self._size:
+            self.rotate()
+        self._handle.write(data)
+        self._handle.flush()
+        self._size += len(data)
+
+    def rotate(self) -> None:
+        self._handle.close()
+        for index in range(self.keep, 0, -1):
+            older = self.path.with_name(f"{self.path.name}.{index}")
+            if not older.exists():
+                continue
+            if index >= self.keep:
+                older.unlink()
+            else:
+                older.rename(self.path.with_name(f"{self.path.name}.{index + 1}"))
+        if self.keep:
+            self.path.rename(self.path.with_name(f"{self.path.name}.1"))
+        else:
+            self.path.unlink(missing_ok=True)
+        self._handle = self.path.open("ab")
+        self._size = 0
+
+    def close(self) -> None:
+        self._handle.close()
+
+
+def run_mirrored(argv: Sequence[str], log_path: Path, *, max_bytes: int = DEFAULT_MAX_BYTES, keep: int = DEFAULT_KEEP) -> int:
+    """Run `argv` on a pseudo-terminal, teeing its output into `log_path`; return its exit code."""
+    import pty
+
+    writer = RotatingLogWriter(log_path, max_bytes=max_bytes, keep=keep)
+    started = datetime.now()
+    writer.write(f"\n=== {started:%Y-%m-%d %H:%M:%S} {' '.join(argv)} ===\n".encode())
+
+    def master_read(fd: int) -> bytes:
+        data = os.read(fd, 4096)
+        if data:
+            writer.write(data)
+        return data
+
+    exit_code = 255
+    try:
+        exit_code = os.waitstatus_to_exitcode(pty.spawn(list(argv), master_read))
+    finally:
+        writer.write(f"\n=== exit {exit_code} after {int((datetime.now() - started).total_seconds())}s ===\n".encode())
+        writer.close()
+    return exit_code
+
+
+def list_terminal_logs(root: Optional[Path] = None) -> List[Dict[str, Any]]:
+    """Current log file of each mirrored terminal, newest first."""
+    directory = Path(root or TERMINAL_LOGS_DIR)
+    entries = []
+    for path in directory.glob("*.log"):
+        stat = path.stat()
+        rotated = len(list(directory.glob(f"{path.name}.*")))
+        entries.append({"name": path.stem, "path": path, "bytes": stat.st_size, "modified": stat.st_mtime, "rotated": rotated})
+    entries.sort(key=lambda item: item["modified"], reverse=True)
+    return entries
+
+
+def read_terminal_log(path: Path, *, lines: int = 0, raw: bool = False) -> str:
+    """Text of a terminal log with escape sequences removed; the last `lines` lines when > 0."""
+    text = Path(path).read_bytes().decode("utf-8", errors="replace")
+    if not raw:
+        text = _ANSI.sub("", text).replace("\r\n", "\n").replace("\r", "")
+    if lines > 0:
+        text = "\n".join(text.splitlines()[-lines:]) + "\n"
+    return text
+
+
+def mirror_requested(args: Sequence[str]) -> bool:
+    """`--log` on the command line, else `terminal_log.enabled`."""
+    return "--log" in args or terminal_log_settings()["enabled"]
+
+
+def run_interactive(argv: Sequence[str], name: str) -> int:
+    """Run an interactive command mirrored to `name`'s terminal log (plain run without a TTY)."""
+    if not sys.stdin.isatty():
+        import subprocess
+
+        return subprocess.run(list(argv)).returncode
+    settings = terminal_log_settings()
+    path = terminal_log_path(name)
+    print(f"Mirroring this session to {path}")
+    return run_mirrored(argv, path, max_bytes=settings["max_bytes"], keep=settings["keep"])
+
+
+__all__ = [
+    "RotatingLogWriter",
+    "TERMINAL_LOGS_DIR",
+    "list_terminal_logs",
+    "mirror_requested",
+    "read_terminal_log",
+    "run_interactive",
+    "run_mirrored",
+    "terminal_log_path",
+    "terminal_log_settings",
+]
@@ -0,0 +1,179 @@
+"""Paste large text into a tmux pane without garbling it.
+
+A bracketed paste is loaded into a tmux buffer chunk by chunk and written with
+`paste-buffer -p`, so tmux adds the bracketed-paste markers when the pane's
+program asked for them and feeds the pty as fast as it drains. Without
+bracketing, chunks go through `send-keys -l` with a short pause between them.
+Pastes above `terminal.paste_confirm_bytes` record `terminal:large_paste` and
+need confirmation first.
+"""
+
+from __future__ import annotations
+
+import time
+import uuid
+from dataclasses import dataclass
+from typing import Any, Callable, Dict, List, Optional
+
+LARGE_PASTE_EVENT = "terminal:large_paste"
+
+
+@dataclass(frozen=True)
+class PastePolicy:
+    """`terminal.paste_*` config keys."""
+
+    bracketed: bool = True
+    chunk_bytes: int = 4096
+    chunk_delay_ms: int = 20
+    confirm_bytes: int = 256 * 1024
+
+
+@dataclass(frozen=True)
+class PasteResult:
+    ok: bool
+    chunks: int = 0
+    bytes: int = 0
+    detail: str = ""
+
+
+def paste_policy(config: Optional[Dict[str, Any]] = None) -> PastePolicy:
+    """Policy from config.yaml `terminal`, falling back to the defaults."""
+    if config is None:
+        from ..config import load_config
+
+        config = load_config()
+    section = dict(config.get("terminal") or {})
+    default = PastePolicy()
+    return PastePolicy(
+        bracketed=bool(section.get("paste_bracketed", default.bracketed)),
+        chunk_bytes=max(256, int(section.get("paste_chunk_bytes", default.chunk_bytes) or default.chunk_bytes)),
+        chunk_delay_ms=max(0, int(section.get("paste_chunk_delay_ms", default.chunk_delay_ms) or 0)),
+        confirm_bytes=max(0, int(section.get("paste_confirm_bytes", default.confirm_bytes) or 0)),
+    )
+
+
+def _split_long_line(line: str, limit: int) -> List[str]:
+    pieces = []
+    data = line.encode("utf-8")
+    while len(data) > limit:
+        # Cut on a character boundary: drop a partial trailing UTF-8 sequence.
+        head = data[:limit].decode("utf-8", errors="ignore")
+        pieces.append(head)
+        data = data[len(head.encode("utf-8")):]
+    if data:
+        pieces.append(data.decode("utf-8"))
+    return pieces
+
+
+def split_chunks(text: str, chunk_bytes: int) -> List[str]:
+    """Split `text` into pieces of at most `chunk_bytes` UTF-8 bytes, at line ends where possible."""
+    chunks: List[str] = []
+    current = ""
+    size = 0
+    for line in text.splitlines(keepends=True):
+        length = len(line.encode("utf-8"))
+        if size and size + length > chunk_bytes:
+            chunks.append(current)
+            current, size = "", 0
+        if length > chunk_bytes:
+            chunks.extend(_split_long_line(line, chunk_bytes))
+            continue
+        current += line
+        size += length
+    if current:
+        chunks.append(current)
+    return chunks
+
+
+def needs_confirmation(text: str, policy: PastePolicy) -> bool:
+    return bool(policy.confirm_bytes) and len(text.encode("utf-8")) > policy.confirm_bytes
+
+
+def _emit_large_paste(target: str, text: str, accepted: bool, run_id: str) -> None:
+    from .terminal_keepalive import emit_terminal_event
+
+    try:
+        emit_terminal_event(
+            LARGE_PASTE_EVENT,
+            run_id=run_id,
+            target=target,
+            bytes=len(text.encode("utf-8")),
+            lines=text.count("\n") + 1,
+            accepted=accepted,
+        )
+    except Exception:
+        pass
+
+
+def _failed(result: Any, fallback: str) -> str:
+    return (getattr(result, "stderr", "") or "").strip() or fallback
+
+
+def _paste_bracketed(client: Any, target: str, chunks: List[str]) -> PasteResult:
+    buffer = f"trainsh-paste-{uuid.uuid4().hex[:8]}"
+    for index, chunk in enumerate(chunks):
+        args = ["set-buffer", "-b", buffer] + (["-a"] if index else []) + ["--", chunk]
+        result = client.run(*args)
+        if result.returncode != 0:
+            client.run("delete-buffer", "-b", buffer)
+            return PasteResult(False, index, detail=_failed(result, "could not load the paste buffer"))
+    result = client.run("paste-buffer", "-d", "-p", "-b", buffer, "-t", target)
+    if result.returncode != 0:
+        client.run("delete-buffer", "-b", buffer)
+        return PasteResult(False, len(chunks), detail=_failed(result, "paste-buffer failed"))
+    return PasteResult(True, len(chunks))
+
+
+def _paste_chunked(client: Any, target: str, chunks: List[str], delay_ms: int, sleep: Callable[[float], None]) -> PasteResult:
+    for index, chunk in enumerate(chunks):
+        if index and delay_ms:
+            sleep(delay_ms / 1000)
+        result = client.send_keys(target, chunk, enter=False, literal=True)
+        if result.returncode != 0:
+            return PasteResult(False, index, detail=_failed(result, f"tmux exited with {result.returncode}"))
+    return PasteResult(True, len(chunks))
+
+
+def paste_text(
+    client: Any,
+    target: str,
+    text: str,
+    *,
+    enter: bool = False,
+    policy: Optional[PastePolicy] = None,
+    confirm: Optional[Callable[[str, str], bool]] = None,
+    run_id: str = "",
+    sleep: Callable[[float], None] = time.sleep,
+) -> PasteResult:
+    """Paste `text` into tmux `target` through `client` (a local or remote tmux client).
+
+    `confirm(target, text)` is asked when the paste is above the confirmation
+    threshold; without it such pastes are refused.
+    """
+    policy = policy or paste_policy()
+    if needs_confirmation(text, policy):
+        accepted = bool(confirm and confirm(target, text))
+        _emit_large_paste(target, text, accepted, run_id)
+        if not accepted:
+            return PasteResult(False, detail=f"paste of {len(text.encode('utf-8'))} bytes not confirmed")
+    chunks = split_chunks(text, policy.chunk_bytes)
+    if policy.bracketed:
+        result = _paste_bracketed(client, target, chunks)
+    else:
+        result = _paste_chunked(client, target, chunks, policy.chunk_delay_ms, sleep)
+    if result.ok and enter:
+        sent = client.send_keys(target, "Enter", enter=False, literal=False)
+        if sent.returncode != 0:
+            return PasteResult(False, result.chunks, detail=_failed(sent, "could not send Enter"))
+    return PasteResult(result.ok, result.chunks, len(text.encode("utf-8")) if result.ok else 0, result.detail)
+
+
+__all__ = [
+    "LARGE_PASTE_EVENT",
+    "PastePolicy",
+    "PasteResult",
+    "needs_confirmation",
+    "paste_policy",
+    "paste_text",
+    "split_chunks",
+]
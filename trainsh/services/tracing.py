@@ -0,0 +1,229 @@
+"""Run tracing: spans for recipe steps, SSH calls and transfers, exported over OTLP/HTTP (JSON).
+
+Spans are only recorded while a run has a `Tracer` active; everywhere else
+`span(...)` hands back a no-op span, so instrumented code needs no checks.
+"""
+
+from __future__ import annotations
+
+import contextvars
+import json
+import os
+import secrets
+import threading
+import time
+from contextlib import contextmanager
+from dataclasses import dataclass, field
+from typing import Any, Dict, Iterator, List, Optional, Tuple
+from urllib.error import HTTPError, URLError
+from urllib.request import Request, urlopen
+
+DEFAULT_SERVICE_NAME = "trainsh"
+EXPORT_BATCH_SIZE = 512
+MAX_ATTRIBUTE_CHARS = 1024
+# OTLP span kinds and status codes.
+KIND_INTERNAL = 1
+KIND_CLIENT = 3
+STATUS_OK = 1
+STATUS_ERROR = 2
+
+
+@dataclass
+class Span:
+    """One timed operation; times are Unix nanoseconds."""
+
+    name: str
+    trace_id: str
+    span_id: str
+    parent_id: str = ""
+    kind: int = KIND_INTERNAL
+    start_ns: int = 0
+    end_ns: int = 0
+    attributes: Dict[str, Any] = field(default_factory=dict)
+    error: str = ""
+
+    def set(self, key: str, value: Any) -> None:
+        if value is not None:
+            self.attributes[key] = value
+
+    def fail(self, message: str) -> None:
+        self.error = str(message or "error")[:MAX_ATTRIBUTE_CHARS]
+
+    @property
+    def duration_ms(self) -> float:
+        return max(0, self.end_ns - self.start_ns) / 1e6
+
+
+class _NoopSpan:
+    """Stand-in yielded by `span(...)` when nothing is being traced."""
+
+    def set(self, key: str, value: Any) -> None:
+        del key, value
+
+    def fail(self, message: str) -> None:
+        del message
+
+
+_NOOP = _NoopSpan()
+_current: contextvars.ContextVar[Optional[Tuple["Tracer", Span]]] = contextvars.ContextVar("trainsh_span", default=None)
+
+
+class Tracer:
+    """Collect the spans of one run under a single trace id."""
+
+    def __init__(self, service_name: str = DEFAULT_SERVICE_NAME, *, resource: Optional[Dict[str, Any]] = None):
+        self.service_name = service_name or DEFAULT_SERVICE_NAME
+        self.resource = dict(resource or {})
+        self.trace_id = secrets.token_hex(16)
+        self.spans: List[Span] = []
+        self._lock = threading.Lock()
+
+    def start_span(self, name: str, *, parent: Optional[Span] = None, kind: int = KIND_INTERNAL, **attributes: Any) -> Span:
+        return Span(
+            name=name,
+            trace_id=self.trace_id,
+            span_id=secrets.token_hex(8),
+            parent_id=parent.span_id if parent else "",
+            kind=kind,
+            start_ns=time.time_ns(),
+            attributes={key: value for key, value in attributes.items() if value is not None},
+        )
+
+    def end_span(self, span: Span) -> None:
+        span.end_ns = span.end_ns or time.time_ns()
+        with self._lock:
+            self.spans.append(span)
+
+    @contextmanager
+    def span(self, name: str, *, parent: Optional[Span] = None, kind: int = KIND_INTERNAL, **attributes: Any) -> Iterator[Span]:
+        """Time a block as a child of `parent` (default: this thread's current span)."""
+        if parent is None:
+            current = _current.get()
+            parent = current[1] if current and current[0] is self else None
+        item = self.start_span(name, parent=parent, kind=kind, **attributes)
+        token = _current.set((self, item))
+        try:
+            yield item
+        except BaseException as exc:
+            if not item.error:
+                item.fail(f"{type(exc).__name__}: {exc}")
+            raise
+        finally:
+            _current.reset(token)
+            self.end_span(item)
+
+
+@contextmanager
+def span(name: str, *, kind: int = KIND_INTERNAL, **attributes: Any) -> Iterator[Any]:
+    """Child of the current span, or a no-op outside a traced run."""
+    current = _current.get()
+    if current is None:
+        yield _NOOP
+        return
+    with current[0].span(name, parent=current[1], kind=kind, **attributes) as item:
+        yield item
+
+
+@contextmanager
+def child_span(tracer: Optional[Tracer], parent: Optional[Span], name: str, **attributes: Any) -> Iterator[Any]:
+    """Child of an explicit `parent` that becomes current for the block; no-op without a tracer."""
+    if tracer is None:
+        yield _NOOP
+        return
+    with tracer.span(name, parent=parent, **attributes) as item:
+        yield item
+
+
+def tracing_settings(config: Dict[str, Any]) -> Dict[str, Any]:
+    """`tracing` config section; `OTEL_EXPORTER_OTLP_ENDPOINT` fills in a missing endpoint."""
+    section = dict((config or {}).get("tracing") or {})
+    endpoint = str(section.get("otlp_endpoint") or os.environ.get("OTEL_EXPORTER_OTLP_ENDPOINT") or "").strip()
+    headers = section.get("headers") if isinstance(section.get("headers"), dict) else {}
+    return {
+        "otlp_endpoint": endpoint,
+        "service_name": str(section.get("service_name") or DEFAULT_SERVICE_NAME),
+        "headers": {str(key): str(value) for key, value in headers.items()},
+        "enabled": bool(endpoint),
+    }
+
+
+def _attribute(key: str, value: Any) -> Dict[str, Any]:
+    if isinstance(value, bool):
+        encoded: Dict[str, Any] = {"boolValue": value}
+    elif isinstance(value, int):
+        encoded = {"intValue": str(value)}
+    elif isinstance(value, float):
+        encoded = {"doubleValue": value}
+    else:
+        encoded = {"stringValue": str(value)[:MAX_ATTRIBUTE_CHARS]}
+    return {"key": key, "value": encoded}
+
+
+def otlp_payload(tracer: Tracer, spans: List[Span]) -> Dict[str, Any]:
+    """OTLP/JSON `ExportTraceServiceRequest` body for `spans`."""
+    resource = {"service.name": tracer.service_name, **tracer.resource}
+    encoded = []
+    for item in spans:
+        body: Dict[str, Any] = {
+            "traceId": item.trace_id,
+            "spanId": item.span_id,
+            "name": item.name,
+            "kind": item.kind,
+            "startTimeUnixNano": str(item.start_ns),
+            "endTimeUnixNano": str(item.end_ns),
+            "attributes": [_attribute(key, value) for key, value in item.attributes.items()],
+            "status": {"code": STATUS_ERROR, "message": item.error} if item.error else {"code": STATUS_OK},
+        }
+        if item.parent_id:
+            body["parentSpanId"] = item.parent_id
+        encoded.append(body)
+    return {
+        "resourceSpans": [
+            {
+                "resource": {"attributes": [_attribute(key, value) for key, value in resource.items()]},
+                "scopeSpans": [{"scope": {"name": "trainsh"}, "spans": encoded}],
+            }
+        ]
+    }
+
+
+def traces_url(endpoint: str) -> str:
+    """Collector base URL (as in `OTEL_EXPORTER_OTLP_ENDPOINT`) to its `/v1/traces` path."""
+    endpoint = str(endpoint or "").rstrip("/")
+    return endpoint if endpoint.endswith("/v1/traces") else f"{endpoint}/v1/traces"
+
+
+def export_spans(tracer: Tracer, endpoint: str, *, headers: Optional[Dict[str, str]] = None, timeout: int = 10) -> int:
+    """POST every finished span to the collector in batches; returns how many were sent."""
+    spans = sorted(tracer.spans, key=lambda item: item.start_ns)
+    request_headers = {"Content-Type": "application/json", **(headers or {})}
+    sent = 0
+    for offset in range(0, len(spans), EXPORT_BATCH_SIZE):
+        batch = spans[offset:offset + EXPORT_BATCH_SIZE]
+        body = json.dumps(otlp_payload(tracer, batch)).encode("utf-8")
+        req = Request(traces_url(endpoint), data=body, headers=request_headers, method="POST")
+        try:
+            with urlopen(req, timeout=timeout) as response:
+                response.read()
+        except HTTPError as exc:
+            detail = exc.read().decode("utf-8", errors="replace") if exc.fp else ""
+            raise RuntimeError(f"OTLP collector returned {exc.code}: {detail[:200]}") from exc
+        except URLError as exc:
+            raise RuntimeError(f"OTLP collector unreachable: {exc.reason}") from exc
+        sent += len(batch)
+    return sent
+
+
+__all__ = [
+    "DEFAULT_SERVICE_NAME",
+    "KIND_CLIENT",
+    "KIND_INTERNAL",
+    "Span",
+    "Tracer",
+    "child_span",
+    "export_spans",
+    "otlp_payload",
+    "span",
+    "traces_url",
+    "tracing_settings",
+]
@@ -8,13 +8,12 @@ from typing import Optional, List, Callable
 
 from ..core.models import AuthMethod, Host, Storage, StorageType, TransferEndpoint, HostType
 from . import transfer_support as _transfer_support
-from .hf_storage import (
-    build_hf_env,
-    check_hf_available,
-    is_hf_bucket_uri,
-    local_path_for_cli,
-    resolve_hf_bucket_uri,
-)
+from .transfer_hf import HfTransferMixin
+from .rsync_options import resolve_rsync_options, rsync_option_args
+from . import remote_rclone as _remote_rclone
+from . import tar_stream as _tar_stream
+from .tracing import span
+from .transfer_progress import ProgressReporter, RcloneProgressParser, RsyncProgressParser, is_rclone_stats_noise
 from .transfer_support import (
     TransferPlan,
     TransferProgress,
@@ -42,7 +41,7 @@ def build_rclone_env(storage: Storage, remote_name: Optional[str] = None):
         _transfer_support.get_secrets_manager = original
 
 
-class TransferEngine:
+class TransferEngine(HfTransferMixin):
     """
     File transfer engine supporting rsync and rclone.
 
@@ -56,18 +55,72 @@ class TransferEngine:
         self,
         progress_callback: Optional[Callable[[TransferProgress], None]] = None,
         rclone_options: Optional[dict] = None,
+        rsync_options: Optional[dict] = None,
+        tar_stream: bool = False,
+        remote_exec: bool = False,
     ):
         """
         Initialize the transfer engine.
 
         Args:
-            progress_callback: Optional callback for progress updates
+            progress_callback: Optional callback for progress updates; every
+                backend reports the same TransferProgress model, throttled.
             rclone_options: Optional dict of rclone tuning options.
                 Supported keys: transfers, checkers, s3_upload_concurrency,
                 s3_chunk_size, include (list), exclude (list).
+            rsync_options: Optional per-transfer rsync overrides on top of the
+                `transfer.rsync` config. Supported keys: compress, partial
+                (partial|append-verify), bwlimit, exclude (list).
+            tar_stream: Replace rsync with a tar | zstd stream over SSH
+                (for directories holding very many small files).
+            remote_exec: Run rclone on the host for host <-> cloud transfers,
+                and never relay host <-> host rsync through this machine.
         """
         self.progress_callback = progress_callback
         self.rclone_options: dict = rclone_options if rclone_options is not None else {}
+        self.rsync_options: dict = rsync_options if rsync_options is not None else {}
+        self.tar_stream = tar_stream
+        self.remote_exec = remote_exec
+
+    def _progress_reporter(self, backend: str) -> ProgressReporter:
+        return ProgressReporter([self.progress_callback], backend=backend)
+
+    def _rsync_flags(self, compress: Optional[bool] = None) -> List[str]:
+        """Resolved rsync tuning flags (config defaults + this engine's overrides)."""
+        overrides = dict(self.rsync_options)
+        if compress is not None:
+            overrides["compress"] = compress
+        return rsync_option_args(resolve_rsync_options(overrides))
+
+    def _tar_stream_transfer(
+        self,
+        source: str,
+        destination: str,
+        src_host: Optional[Host] = None,
+        dst_host: Optional[Host] = None,
+        delete: bool = False,
+        exclude: Optional[List[str]] = None,
+        dry_run: bool = False,
+    ) -> TransferResult:
+        """Stream `tar | zstd` from source to destination; remote sides run over SSH.
+
+        Host-to-host streams relay through this machine, so the hosts need
+        no direct connectivity.
+        """
+        if delete:
+            return TransferResult(success=False, exit_code=-1, message="Tar stream mode cannot delete; use rsync for sync")
+        if dry_run:
+            return TransferResult(success=False, exit_code=-1, message="Tar stream mode does not support dry runs")
+        settings = _tar_stream.tar_stream_settings()
+        producer, consumer = _tar_stream.stream_command(
+            source if src_host else os.path.expanduser(source),
+            destination if dst_host else os.path.expanduser(destination),
+            src_ssh=self._build_ssh_args(src_host) if src_host else None,
+            dst_ssh=self._build_ssh_args(dst_host) if dst_host else None,
+            exclude=list(resolve_rsync_options(self.rsync_options).get("exclude") or []) + list(exclude or []),
+            level=settings["level"],
+        )
+        return _tar_stream.run_pipeline(producer, consumer, reporter=self._progress_reporter("tar-stream"))
 
     def rsync(
         self,
@@ -78,7 +131,7 @@ class TransferEngine:
         delete: bool = False,
         exclude: Optional[List[str]] = None,
         use_gitignore: bool = False,
-        compress: bool = True,
+        compress: Optional[bool] = None,
         dry_run: bool = False,
     ) -> TransferResult:
         """
@@ -92,20 +145,33 @@ class TransferEngine:
             delete: Delete files on destination not in source
             exclude: Patterns to exclude
             use_gitignore: Exclude files based on .gitignore
-            compress: Enable compression
+            compress: Enable compression (default: rsync options/config)
             dry_run: Simulate transfer
 
         Returns:
             TransferResult with status
         """
-        args = ["rsync", "-avz", "--progress", "--mkpath"]
+        if self.tar_stream:
+            try:
+                return self._tar_stream_transfer(
+                    source,
+                    destination,
+                    src_host=None if upload else host,
+                    dst_host=host if upload else None,
+                    delete=delete,
+                    exclude=exclude,
+                    dry_run=dry_run,
+                )
+            except ValueError as e:
+                return TransferResult(success=False, exit_code=-1, message=str(e))
+        try:
+            args = ["rsync", "-av", "--progress", "--mkpath", *self._rsync_flags(compress)]
+        except ValueError as e:
+            return TransferResult(success=False, exit_code=-1, message=str(e))
 
         if delete:
             args.append("--delete")
 
-        if compress:
-            args.append("-z")
-
         if dry_run:
             args.append("--dry-run")
 
@@ -152,6 +218,8 @@ class TransferEngine:
             output_lines = []
             bytes_transferred = 0
             stdout = process.stdout
+            parser = RsyncProgressParser()
+            reporter = self._progress_reporter("rsync")
 
             if stdout is not None:
                 try:
@@ -159,8 +227,10 @@ class TransferEngine:
                         line = line.rstrip()
                         output_lines.append(line)
 
-                        # Show progress lines (rsync progress format)
-                        if line and not line.startswith(' '):
+                        progress = parser.feed(line)
+                        if progress:
+                            reporter.update(progress)
+                        elif line and not line.startswith(' '):
                             print(f"  {line}", flush=True)
 
                         # Parse bytes from final summary
@@ -179,6 +249,7 @@ class TransferEngine:
                 exit_code=process.returncode,
                 message="\n".join(output_lines[-5:]) if process.returncode != 0 else "Transfer complete",
                 bytes_transferred=bytes_transferred,
+                progress=reporter.finish(),
             )
         except Exception as e:
             return TransferResult(
@@ -187,6 +258,32 @@ class TransferEngine:
                 message=str(e),
             )
 
+    def _rclone_args(self, operation: str, *, delete: bool = False, dry_run: bool = False) -> List[str]:
+        """`rclone <operation>` plus dry-run/delete flags and this engine's tuning options."""
+        args = ["rclone", operation]
+
+        if dry_run:
+            args.append("--dry-run")
+
+        if delete and operation == "sync":
+            args.append("--delete-after")
+
+        # Apply rclone tuning options from self.rclone_options
+        opts = self.rclone_options
+        if opts.get("transfers"):
+            args.extend(["--transfers", str(opts["transfers"])])
+        if opts.get("checkers"):
+            args.extend(["--checkers", str(opts["checkers"])])
+        if opts.get("s3_upload_concurrency"):
+            args.extend(["--s3-upload-concurrency", str(opts["s3_upload_concurrency"])])
+        if opts.get("s3_chunk_size"):
+            args.extend(["--s3-chunk-size", str(opts["s3_chunk_size"])])
+        for pat in opts.get("include", []):
+            args.extend(["--include", pat])
+        for pat in opts.get("exclude", []):
+            args.extend(["--exclude", pat])
+        return args
+
     def rclone(
         self,
         source: str,
@@ -214,31 +311,9 @@ class TransferEngine:
         Returns:
             TransferResult with status
         """
-        args = ["rclone", operation]
-
+        args = self._rclone_args(operation, delete=delete, dry_run=dry_run)
         if progress:
-            args.append("--progress")
-
-        if dry_run:
-            args.append("--dry-run")
-
-        if delete and operation == "sync":
-            args.append("--delete-after")
-
-        # Apply rclone tuning options from self.rclone_options
-        opts = self.rclone_options
-        if opts.get("transfers"):
-            args.extend(["--transfers", str(opts["transfers"])])
-        if opts.get("checkers"):
-            args.extend(["--checkers", str(opts["checkers"])])
-        if opts.get("s3_upload_concurrency"):
-            args.extend(["--s3-upload-concurrency", str(opts["s3_upload_concurrency"])])
-        if opts.get("s3_chunk_size"):
-            args.extend(["--s3-chunk-size", str(opts["s3_chunk_size"])])
-        for pat in opts.get("include", []):
-            args.extend(["--include", pat])
-        for pat in opts.get("exclude", []):
-            args.extend(["--exclude", pat])
+            args.insert(2, "--progress")
 
         args.extend([source, destination])
 
@@ -267,35 +342,24 @@ class TransferEngine:
             output_lines = []
             bytes_transferred = 0
             stdout = process.stdout
+            parser = RcloneProgressParser()
+            reporter = self._progress_reporter("rclone")
 
             if stdout is not None:
                 try:
                     for line in stdout:
                         line = line.rstrip()
                         output_lines.append(line)
-
-                        # Show progress lines
-                        if line:
-                            # rclone progress format: "Transferred: X / Y, ETA X"
+                        if not line:
+                            continue
+
+                        progress = parser.feed(line)
+                        if progress:
+                            if progress.bytes_transferred:
+                                bytes_transferred = progress.bytes_transferred
+                            reporter.update(progress)
+                        elif not is_rclone_stats_noise(line):
                             print(f"  {line}", flush=True)
-
-                            # Parse transferred bytes from rclone output
-                            match = re.search(r"Transferred:\s+([\d.]+)\s*(\w+)", line)
-                            if match:
-                                size_str = match.group(1)
-                                unit = match.group(2).upper()
-                                try:
-                                    size = float(size_str)
-                                    if unit == "KIB" or unit == "KB":
-                                        bytes_transferred = int(size * 1024)
-                                    elif unit == "MIB" or unit == "MB":
-                                        bytes_transferred = int(size * 1024 * 1024)
-                                    elif unit == "GIB" or unit == "GB":
-                                        bytes_transferred = int(size * 1024 * 1024 * 1024)
-                                    else:
-                                        bytes_transferred = int(size)
-                                except ValueError:
-                                    pass
                 finally:
                     close = getattr(stdout, "close", None)
                     if callable(close):
@@ -308,6 +372,7 @@ class TransferEngine:
                 exit_code=process.returncode,
                 message="\n".join(output_lines[-5:]) if process.returncode != 0 else "Transfer complete",
                 bytes_transferred=bytes_transferred,
+                progress=reporter.finish(),
             )
         except FileNotFoundError:
             return TransferResult(
@@ -322,243 +387,6 @@ class TransferEngine:
                 message=str(e),
             )
 
-    def hf(
-        self,
-        source: str,
-        destination: str,
-        *,
-        storage: Storage,
-        operation: str = "copy",
-        delete: bool = False,
-        dry_run: bool = False,
-        progress: bool = True,
-    ) -> TransferResult:
-        """Transfer files using the hf CLI for Hugging Face buckets."""
-        if not check_hf_available():
-            return TransferResult(
-                success=False,
-                exit_code=-1,
-                message="hf CLI not found. Install with: brew install hf",
-            )
-
-        args = ["hf", "buckets"]
-        local_source = None if is_hf_bucket_uri(source) else local_path_for_cli(source)
-        local_destination = None if is_hf_bucket_uri(destination) else local_path_for_cli(destination)
-        use_cp = self._should_use_hf_cp(
-            source=local_source or source,
-            destination=local_destination or destination,
-            operation=operation,
-        )
-        if use_cp and dry_run:
-            return TransferResult(
-                success=False,
-                exit_code=2,
-                message="Dry run is not supported for single-file HF bucket copies.",
-            )
-
-        args.append("cp" if use_cp else "sync")
-        args.extend([local_source or source, local_destination or destination])
-        if progress and not use_cp:
-            args.append("--quiet")
-        if delete and not use_cp:
-            args.append("--delete")
-        if dry_run and not use_cp:
-            args.append("--dry-run")
-        for pat in self.rclone_options.get("include", []):
-            if not use_cp:
-                args.extend(["--include", pat])
-        for pat in self.rclone_options.get("exclude", []):
-            if not use_cp:
-                args.extend(["--exclude", pat])
-
-        env = os.environ.copy()
-        env.update(build_hf_env(storage))
-
-        try:
-            process = subprocess.Popen(
-                args,
-                stdout=subprocess.PIPE,
-                stderr=subprocess.STDOUT,
-                text=True,
-                bufsize=1,
-                env=env,
-            )
-
-            output_lines = []
-            stdout = process.stdout
-
-            if stdout is not None:
-                try:
-                    for line in stdout:
-                        line = line.rstrip()
-                        output_lines.append(line)
-                        if line:
-                            print(f"  {line}", flush=True)
-                finally:
-                    close = getattr(stdout, "close", None)
-                    if callable(close):
-                        close()
-
-            process.wait()
-
-            return TransferResult(
-                success=process.returncode == 0,
-                exit_code=process.returncode,
-                message="\n".join(output_lines[-5:]) if process.returncode != 0 else "Transfer complete",
-            )
-        except FileNotFoundError:
-            return TransferResult(
-                success=False,
-                exit_code=-1,
-                message="hf CLI not found. Install with: brew install hf",
-            )
-        except Exception as e:
-            return TransferResult(
-                success=False,
-                exit_code=-1,
-                message=str(e),
-            )
-
-    def _should_use_hf_cp(self, source: str, destination: str, *, operation: str) -> bool:
-        """Pick `hf buckets cp` for likely single-file transfers."""
-        if operation != "copy":
-            return False
-
-        for candidate in (source, destination):
-            text = str(candidate or "").strip()
-            if not text or is_hf_bucket_uri(text):
-                continue
-            expanded = os.path.expanduser(text)
-            if os.path.isfile(expanded):
-                return True
-            if os.path.isdir(expanded):
-                return False
-
-        remote_path = source if is_hf_bucket_uri(source) else destination
-        basename = os.path.basename(str(remote_path or "").rstrip("/"))
-        return bool(basename and "." in basename)
-
-    def _transfer_local_with_hf_storage(
-        self,
-        *,
-        local_path: str,
-        storage: Storage,
-        storage_path: str,
-        upload: bool,
-        delete: bool,
-        dry_run: bool,
-    ) -> TransferResult:
-        """Transfer directly between local filesystem and an HF bucket."""
-        hf_uri = resolve_hf_bucket_uri(storage, storage_path)
-        if upload:
-            return self.hf(
-                source=local_path,
-                destination=hf_uri,
-                storage=storage,
-                operation="sync" if delete else "copy",
-                delete=delete,
-                dry_run=dry_run,
-            )
-        return self.hf(
-            source=hf_uri,
-            destination=local_path,
-            storage=storage,
-            operation="sync" if delete else "copy",
-            delete=delete,
-            dry_run=dry_run,
-        )
-
-    def _transfer_with_hf_storage(
-        self,
-        *,
-        source: TransferEndpoint,
-        destination: TransferEndpoint,
-        hosts: dict[str, Host],
-        storages: dict[str, Storage],
-        delete: bool,
-        exclude: Optional[List[str]],
-        dry_run: bool,
-    ) -> TransferResult:
-        """Handle transfers that involve at least one HF storage via local staging."""
-        src_storage = storages.get(source.storage_id) if source.storage_id else None
-        dst_storage = storages.get(destination.storage_id) if destination.storage_id else None
-        src_uses_hf = bool(src_storage and src_storage.type == StorageType.HF)
-        dst_uses_hf = bool(dst_storage and dst_storage.type == StorageType.HF)
-
-        if src_uses_hf and destination.type == "local":
-            return self._transfer_local_with_hf_storage(
-                local_path=destination.path,
-                storage=src_storage,
-                storage_path=source.path,
-                upload=False,
-                delete=delete,
-                dry_run=dry_run,
-            )
-        if dst_uses_hf and source.type == "local":
-            return self._transfer_local_with_hf_storage(
-                local_path=source.path,
-                storage=dst_storage,
-                storage_path=destination.path,
-                upload=True,
-                delete=delete,
-                dry_run=dry_run,
-            )
-
-        if dry_run:
-            return TransferResult(
-                success=False,
-                exit_code=2,
-                message="Dry run is not supported for relayed HF storage transfers.",
-            )
-
-        with tempfile.TemporaryDirectory(prefix="trainsh-hf-transfer-") as tmpdir:
-            stage_path = os.path.join(tmpdir, "payload")
-            if src_uses_hf:
-                pulled = self._transfer_local_with_hf_storage(
-                    local_path=stage_path,
-                    storage=src_storage,
-                    storage_path=source.path,
-                    upload=False,
-                    delete=False,
-                    dry_run=False,
-                )
-            else:
-                pulled = self.transfer(
-                    source=source,
-                    destination=TransferEndpoint(type="local", path=stage_path),
-                    hosts=hosts,
-                    storages=storages,
-                    delete=False,
-                    exclude=exclude,
-                    dry_run=False,
-                )
-            if not pulled.success:
-                return pulled
-
-            if dst_uses_hf:
-                pushed = self._transfer_local_with_hf_storage(
-                    local_path=stage_path,
-                    storage=dst_storage,
-                    storage_path=destination.path,
-                    upload=True,
-                    delete=delete,
-                    dry_run=False,
-                )
-            else:
-                pushed = self.transfer(
-                    source=TransferEndpoint(type="local", path=stage_path),
-                    destination=destination,
-                    hosts=hosts,
-                    storages=storages,
-                    delete=delete,
-                    exclude=exclude,
-                    dry_run=False,
-                )
-
-            if pushed.bytes_transferred <= 0:
-                pushed.bytes_transferred = pulled.bytes_transferred
-            return pushed
-
     def transfer(
         self,
         source: TransferEndpoint,
@@ -585,6 +413,30 @@ class TransferEngine:
         Returns:
             TransferResult with status
         """
+        attributes = {
+            "transfer.source": source.path,
+            "transfer.source.endpoint": source.host_id or source.storage_id or "local",
+            "transfer.destination": destination.path,
+            "transfer.destination.endpoint": destination.host_id or destination.storage_id or "local",
+            "transfer.dry_run": bool(dry_run),
+        }
+        with span("transfer", **attributes) as trace:
+            result = self._transfer_endpoints(source, destination, hosts, storages, delete, exclude, dry_run)
+            trace.set("transfer.bytes", int(result.bytes_transferred or 0))
+            if not result.success:
+                trace.fail(result.message)
+        return result
+
+    def _transfer_endpoints(
+        self,
+        source: TransferEndpoint,
+        destination: TransferEndpoint,
+        hosts: Optional[dict[str, Host]],
+        storages: Optional[dict[str, Storage]],
+        delete: bool,
+        exclude: Optional[List[str]],
+        dry_run: bool,
+    ) -> TransferResult:
         try:
             hosts = hosts or {}
             storages = storages or {}
@@ -683,6 +535,10 @@ class TransferEngine:
                 storage_id=destination.storage_id,
             )
 
+            if src_host and dst_host and self.tar_stream:
+                return self._tar_stream_transfer(
+                    source_endpoint.path, destination_endpoint.path, src_host, dst_host, delete, exclude, dry_run
+                )
             if src_host and dst_host:
                 # Host-to-host transfer
                 return self._transfer_host_to_host(
@@ -831,6 +687,17 @@ class TransferEngine:
         storages: dict[str, Storage],
     ) -> TransferResult:
         """Relay a host -> cloud-storage transfer through a local temp directory."""
+        if self.remote_exec:
+            return self._rclone_on_host(
+                src_host,
+                source.path,
+                self._resolve_endpoint_for_rclone(destination, hosts, storages),
+                storage=dst_storage,
+                upload=True,
+                delete=delete,
+                exclude=exclude,
+                dry_run=dry_run,
+            )
         if dry_run:
             return TransferResult(
                 success=False,
@@ -861,6 +728,34 @@ class TransferEngine:
                 pushed.bytes_transferred = pulled.bytes_transferred
             return pushed
 
+    def _rclone_on_host(
+        self,
+        host: Host,
+        source: str,
+        destination: str,
+        *,
+        storage: Storage,
+        upload: bool,
+        delete: bool = False,
+        exclude: Optional[List[str]] = None,
+        dry_run: bool = False,
+    ) -> TransferResult:
+        """Run rclone on `host` with only `storage`'s credentials; data never touches this machine."""
+        args = self._rclone_args("sync" if delete else "copy", delete=delete, dry_run=dry_run)
+        args.extend(_remote_rclone.rclone_stats_args())
+        for pattern in exclude or []:
+            args.extend(["--exclude", pattern])
+        args.extend([source, destination])
+        script = _remote_rclone.remote_script(
+            args[1:],
+            build_rclone_env(storage),
+            local_paths=(source,) if upload else (destination,),
+        )
+        print(f"Running rclone on {host.name or host.hostname}; data bypasses this machine.", flush=True)
+        return _remote_rclone.run_remote_rclone(
+            self._build_ssh_args(host), script, reporter=self._progress_reporter("rclone")
+        )
+
     def _transfer_cloud_storage_with_host(
         self,
         *,
@@ -875,6 +770,17 @@ class TransferEngine:
         storages: dict[str, Storage],
     ) -> TransferResult:
         """Relay a cloud-storage -> host transfer through a local temp directory."""
+        if self.remote_exec:
+            return self._rclone_on_host(
+                dst_host,
+                self._resolve_endpoint_for_rclone(source, hosts, storages),
+                destination.path,
+                storage=src_storage,
+                upload=False,
+                delete=delete,
+                exclude=exclude,
+                dry_run=dry_run,
+            )
         if dry_run:
             return TransferResult(
                 success=False,
@@ -920,7 +826,8 @@ class TransferEngine:
 
         Strategy:
         1. If src_host can SSH to dst_host: use remote rsync (direct)
-        2. Otherwise: use scp -3 through local relay
+        2. With remote_exec, if dst_host can SSH to src_host: dst_host pulls
+        3. Otherwise: use scp -3 through local relay (refused with remote_exec)
         """
         can_direct = self._check_host_connectivity(src_host, dst_host)
 
@@ -928,8 +835,17 @@ class TransferEngine:
             return self._rsync_remote_to_remote(
                 source, destination, src_host, dst_host, delete, exclude, dry_run
             )
-        else:
-            return self._scp_three_way(source, destination, src_host, dst_host, dry_run=dry_run)
+        if self.remote_exec:
+            if self._check_host_connectivity(dst_host, src_host):
+                return self._rsync_remote_to_remote(
+                    source, destination, src_host, dst_host, delete, exclude, dry_run, pull=True
+                )
+            return TransferResult(
+                success=False,
+                exit_code=-1,
+                message="Neither host can reach the other over SSH; remote execution refuses to relay locally.",
+            )
+        return self._scp_three_way(source, destination, src_host, dst_host, dry_run=dry_run)
 
     def _check_host_connectivity(self, src: Host, dst: Host) -> bool:
         """Check if src_host can SSH to dst_host directly."""
@@ -977,12 +893,17 @@ class TransferEngine:
         delete: bool = False,
         exclude: Optional[List[str]] = None,
         dry_run: bool = False,
+        pull: bool = False,
     ) -> TransferResult:
-        """Execute rsync from src_host to dst_host directly."""
+        """Execute rsync from src_host to dst_host directly (on dst_host when `pull`)."""
         src_host = self._prepare_host(src_host)
         dst_host = self._prepare_host(dst_host)
-        # Build rsync command to run on src_host
-        rsync_parts = ["rsync", "-avz", "--progress"]
+        run_host, peer = (dst_host, src_host) if pull else (src_host, dst_host)
+        # Build rsync command to run on run_host
+        try:
+            rsync_parts = ["rsync", "-av", "--progress", *map(shlex.quote, self._rsync_flags())]
+        except ValueError as e:
+            return TransferResult(success=False, exit_code=-1, message=str(e))
         if delete:
             rsync_parts.append("--delete")
         if dry_run:
@@ -990,22 +911,25 @@ class TransferEngine:
         for pattern in (exclude or []):
             rsync_parts.append(f"--exclude={pattern}")
 
-        # Destination spec
-        dst_spec = f"{dst_host.username}@{dst_host.hostname}" if dst_host.username else dst_host.hostname
-        ssh_remote = " ".join([*self._ssh_auth_prefix(dst_host), "ssh"])
-        if dst_host.port != 22:
-            rsync_parts.extend(["-e", f"'{ssh_remote} -p {dst_host.port}'"])
-        elif self._ssh_auth_prefix(dst_host):
+        # Peer spec
+        peer_spec = f"{peer.username}@{peer.hostname}" if peer.username else peer.hostname
+        ssh_remote = " ".join([*self._ssh_auth_prefix(peer), "ssh"])
+        if peer.port != 22:
+            rsync_parts.extend(["-e", f"'{ssh_remote} -p {peer.port}'"])
+        elif self._ssh_auth_prefix(peer):
             rsync_parts.extend(["-e", f"'{ssh_remote}'"])
 
-        rsync_parts.append(source.path)
-        rsync_parts.append(f"{dst_spec}:{destination.path}")
+        if pull:
+            rsync_parts.append(f"{peer_spec}:{source.path}")
+            rsync_parts.append(destination.path)
+        else:
+            rsync_parts.append(source.path)
+            rsync_parts.append(f"{peer_spec}:{destination.path}")
 
         rsync_cmd = " ".join(rsync_parts)
 
-        # Execute on src_host
-        src_ssh = self._build_ssh_args(src_host)
-        full_cmd = src_ssh + [rsync_cmd]
+        # Execute on run_host
+        full_cmd = self._build_ssh_args(run_host) + [rsync_cmd]
 
         try:
             # Run with real-time output
@@ -1020,14 +944,19 @@ class TransferEngine:
             output_lines = []
             bytes_transferred = 0
             stdout = process.stdout
+            parser = RsyncProgressParser()
+            reporter = self._progress_reporter("rsync")
 
             if stdout is not None:
                 try:
                     for line in stdout:
                         line = line.rstrip()
                         output_lines.append(line)
-                        # Show all non-empty lines
-                        if line:
+                        progress = parser.feed(line)
+                        if progress:
+                            reporter.update(progress)
+                        # Show all other non-empty lines
+                        elif line:
                             print(f"  {line}", flush=True)
                         # Parse bytes from final summary
                         match = re.search(r"sent ([\d,]+) bytes", line)
@@ -1045,6 +974,7 @@ class TransferEngine:
                 exit_code=process.returncode,
                 message="\n".join(output_lines[-5:]) if process.returncode != 0 else "Transfer complete",
                 bytes_transferred=bytes_transferred,
+                progress=reporter.finish(),
             )
         except subprocess.TimeoutExpired:
             return TransferResult(
@@ -1182,16 +1112,9 @@ class TransferEngine:
         if proxy_command:
             return proxy_command
 
-        tunnel_type = str(env_vars.get("tunnel_type", "")).strip().lower()
-        if tunnel_type != "cloudflared":
-            return None
-
-        cloudflared_hostname = str(env_vars.get("cloudflared_hostname", host.hostname)).strip()
-        if not cloudflared_hostname:
-            return None
+        from .cloudflared import proxy_command
 
-        cloudflared_bin = str(env_vars.get("cloudflared_bin", "cloudflared")).strip() or "cloudflared"
-        return f"{cloudflared_bin} access ssh --hostname {cloudflared_hostname}"
+        return proxy_command(env_vars, host.hostname)
 
     def _build_scp_spec(self, host: Host, path: str) -> str:
         """Build SCP path specification for a host."""
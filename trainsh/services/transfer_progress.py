@@ -0,0 +1,230 @@
+"""One progress model for rsync, rclone, and tar-stream transfers, with throttled reporting."""
+
+from __future__ import annotations
+
+import re
+import time
+from typing import Callable, Iterable, Optional
+
+from .transfer_support import TransferProgress
+
+PROGRESS_EVENT = "transfer:progress"
+DEFAULT_INTERVAL = 2.0
+
+_UNITS = {"": 1, "B": 1, "K": 1024, "M": 1024**2, "G": 1024**3, "T": 1024**4, "P": 1024**5}
+_SIZE = r"([\d.,]+)\s*([KMGTP]?)(?:i?B|bytes)?"
+
+
+def parse_size(number: str, unit: str = "") -> int:
+    """`1.5` + `G` -> bytes; rsync and rclone both use binary multiples."""
+    try:
+        return int(float(number.replace(",", "")) * _UNITS.get(unit.upper()[:1], 1))
+    except ValueError:
+        return 0
+
+
+def parse_duration(text: str) -> Optional[int]:
+    """Seconds from `0:01:23` (rsync) or `1h2m3s` (rclone); None when unknown."""
+    text = text.strip()
+    if re.fullmatch(r"\d+(:\d{2}){1,2}", text):
+        seconds = 0
+        for part in text.split(":"):
+            seconds = seconds * 60 + int(part)
+        return seconds
+    parts = re.findall(r"([\d.]+)([dhms])", text)
+    if not parts:
+        return None
+    scale = {"d": 86400, "h": 3600, "m": 60, "s": 1}
+    return int(sum(float(value) * scale[unit] for value, unit in parts))
+
+
+def format_bytes(value: float) -> str:
+    for unit in ("B", "KiB", "MiB", "GiB", "TiB"):
+        if abs(value) < 1024 or unit == "TiB":
+            return f"{value:.0f} {unit}" if unit == "B" else f"{value:.1f} {unit}"
+        value /= 1024
+    return f"{value:.1f} TiB"
+
+
+def format_progress(progress: TransferProgress) -> str:
+    """`1.2 GiB / 10.0 GiB (12%)  3/100 files  50.0 MiB/s  ETA 3m20s`."""
+    parts = [format_bytes(progress.bytes_transferred)]
+    if progress.total_bytes:
+        parts[0] += f" / {format_bytes(progress.total_bytes)} ({progress.percent:.0f}%)"
+    if progress.files_total:
+        parts.append(f"{progress.files_done}/{progress.files_total} files")
+    elif progress.files_done:
+        parts.append(f"{progress.files_done} files")
+    if progress.speed_bps:
+        parts.append(f"{format_bytes(progress.speed_bps)}/s")
+    if progress.eta_seconds is not None:
+        minutes, seconds = divmod(int(progress.eta_seconds), 60)
+        hours, minutes = divmod(minutes, 60)
+        parts.append("ETA " + (f"{hours}h{minutes:02d}m" if hours else f"{minutes}m{seconds:02d}s"))
+    return "  ".join(parts)
+
+
+class RsyncProgressParser:
+    """Aggregate `rsync --progress` (per file) or `--info=progress2` (whole transfer) lines."""
+
+    _LINE = re.compile(
+        r"^\s*([\d,]+)\s+(\d+)%\s+([\d.]+)([kKMGT]?)B/s\s+(\d+:\d{2}:\d{2})"
+        r"(?:\s+\(xfr#(\d+),\s*(?:to|ir)-chk=(\d+)/(\d+)\))?"
+    )
+
+    def __init__(self, *, aggregate: bool = False):
+        self.aggregate = aggregate
+        self.completed_bytes = 0
+
+    def feed(self, line: str) -> Optional[TransferProgress]:
+        match = self._LINE.match(line)
+        if not match:
+            return None
+        done = parse_size(match.group(1))
+        percent = float(match.group(2))
+        progress = TransferProgress(
+            percent=percent,
+            speed=f"{match.group(3)}{match.group(4)}B/s",
+            eta=match.group(5),
+            speed_bps=float(parse_size(match.group(3), match.group(4))),
+            backend="rsync",
+        )
+        if match.group(6):
+            remaining, total = int(match.group(7)), int(match.group(8))
+            progress.files_done = total - remaining
+            progress.files_total = total
+        if self.aggregate:
+            progress.bytes_transferred = done
+            progress.total_bytes = int(done * 100 / percent) if percent else 0
+            progress.eta_seconds = parse_duration(match.group(5))
+        else:
+            # Per-file lines: the file's bytes only count once it finishes (xfr#).
+            progress.bytes_transferred = self.completed_bytes + done
+            if match.group(6):
+                self.completed_bytes += done
+            progress.percent = 0.0
+        return progress
+
+
+class RcloneProgressParser:
+    """Merge rclone `--progress` blocks and `--stats-one-line` lines into one snapshot."""
+
+    _BYTES = re.compile(
+        rf"{_SIZE}\s*/\s*{_SIZE},\s*(\d+)%(?:,\s*([\d.]+)\s*([KMGTP]?)i?B/s)?(?:,\s*ETA\s*(\S+))?",
+        re.IGNORECASE,
+    )
+    _BYTES_ONLY = re.compile(rf"^Transferred:\s+{_SIZE}\s*$", re.IGNORECASE)
+    _FILES = re.compile(r"^Transferred:\s+(\d+)\s*/\s*(\d+),\s*\d+%\s*$", re.IGNORECASE)
+
+    def __init__(self):
+        self.current = TransferProgress(backend="rclone")
+
+    def feed(self, line: str) -> Optional[TransferProgress]:
+        text = line.strip()
+        files = self._FILES.match(text)
+        if files:
+            self.current.files_done, self.current.files_total = int(files.group(1)), int(files.group(2))
+            return self._snapshot()
+        match = self._BYTES.search(text)
+        if match:
+            current = self.current
+            current.bytes_transferred = parse_size(match.group(1), match.group(2))
+            current.total_bytes = parse_size(match.group(3), match.group(4))
+            current.percent = float(match.group(5))
+            if match.group(6):
+                current.speed_bps = float(parse_size(match.group(6), match.group(7)))
+                current.speed = f"{match.group(6)} {match.group(7)}iB/s"
+            if match.group(8):
+                current.eta = match.group(8)
+                current.eta_seconds = parse_duration(match.group(8))
+            return self._snapshot()
+        only = self._BYTES_ONLY.match(text)
+        if only:
+            self.current.bytes_transferred = parse_size(only.group(1), only.group(2))
+            return self._snapshot()
+        return None
+
+    def _snapshot(self) -> TransferProgress:
+        return TransferProgress(**vars(self.current))
+
+
+_RCLONE_STATS_PREFIXES = ("Checks:", "Deleted:", "Renamed:", "Elapsed time:", "Transferring:", "Errors:", "* ")
+
+
+def is_rclone_stats_noise(line: str) -> bool:
+    """Lines of rclone's `--progress` block that the unified progress line replaces."""
+    return line.strip().startswith(_RCLONE_STATS_PREFIXES)
+
+
+class ProgressReporter:
+    """Fill in speed/ETA where the backend leaves them out and forward at most every `interval` seconds.
+
+    Sinks get `TransferProgress`; with `echo`, one formatted line is printed per report.
+    """
+
+    def __init__(
+        self,
+        sinks: Iterable[Optional[Callable[[TransferProgress], None]]] = (),
+        *,
+        backend: str = "",
+        interval: float = DEFAULT_INTERVAL,
+        echo: bool = True,
+        clock: Callable[[], float] = time.monotonic,
+    ):
+        self.sinks = [sink for sink in sinks if sink is not None]
+        self.backend = backend
+        self.interval = interval
+        self.echo = echo
+        self.clock = clock
+        self.latest: Optional[TransferProgress] = None
+        self._started = clock()
+        self._last_emit: Optional[float] = None
+        self._pending = False
+
+    def update(self, progress: TransferProgress) -> None:
+        progress.backend = progress.backend or self.backend
+        elapsed = self.clock() - self._started
+        if not progress.speed_bps and elapsed >= 1 and progress.bytes_transferred:
+            progress.speed_bps = progress.bytes_transferred / elapsed
+        if progress.total_bytes and not progress.percent:
+            progress.percent = min(100.0, progress.bytes_transferred * 100 / progress.total_bytes)
+        if progress.eta_seconds is None and progress.total_bytes and progress.speed_bps:
+            remaining = max(0, progress.total_bytes - progress.bytes_transferred)
+            progress.eta_seconds = int(remaining / progress.speed_bps)
+        self.latest = progress
+        self._pending = True
+        now = self.clock()
+        if self._last_emit is None or now - self._last_emit >= self.interval:
+            self._emit(now)
+
+    def finish(self) -> Optional[TransferProgress]:
+        """Flush the last unreported snapshot; returns the final progress."""
+        if self._pending:
+            self._emit(self.clock())
+        return self.latest
+
+    def _emit(self, now: float) -> None:
+        self._last_emit = now
+        self._pending = False
+        assert self.latest is not None
+        if self.echo:
+            print(f"  {format_progress(self.latest)}", flush=True)
+        for sink in self.sinks:
+            try:
+                sink(self.latest)
+            except Exception:
+                pass
+
+
+__all__ = [
+    "DEFAULT_INTERVAL",
+    "PROGRESS_EVENT",
+    "ProgressReporter",
+    "RcloneProgressParser",
+    "RsyncProgressParser",
+    "format_bytes",
+    "format_progress",
+    "is_rclone_stats_noise",
+    "parse_duration",
+    "parse_size",
+]
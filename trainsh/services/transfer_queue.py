@@ -0,0 +1,253 @@
+"""Persistent transfer queue with priorities and per-destination concurrency limits."""
+
+from __future__ import annotations
+
+import contextlib
+import fcntl
+import json
+import os
+import uuid
+from datetime import datetime
+from pathlib import Path
+from typing import Any, Dict, Iterator, List, Optional, Sequence
+
+from ..constants import RUNTIME_STATE_DIR
+from ..core.runtime_store import RuntimeStore, to_jsonable
+from .transfer_progress import PROGRESS_EVENT
+
+QUEUE_FILENAME = "transfer_queue.json"
+LOG_DIRNAME = "transfer_queue"
+EVENT_RUN_ID = "transfers"
+# Set on queued `train transfer` subprocesses so they report into their entry.
+TRANSFER_ID_ENV = "TRAINSH_TRANSFER_ID"
+ACTIVE_STATUSES = ("queued", "running")
+FINISHED_STATUSES = ("done", "failed", "cancelled")
+_CLOUD_PREFIXES = ("hf:", "r2:", "b2:", "gcs:")
+
+
+def destination_key(spec: str) -> str:
+    """Group key for concurrency limits: host alias, storage name, or `local`."""
+    spec = str(spec or "").strip()
+    if spec.startswith("@") and ":" in spec:
+        return spec[1:].split(":", 1)[0]
+    if spec.startswith("host:"):
+        return spec[5:].split(":", 1)[0]
+    if spec.startswith("storage:"):
+        return "storage:" + spec[8:].split(":", 1)[0]
+    for prefix in _CLOUD_PREFIXES:
+        if spec.startswith(prefix):
+            bucket = spec[len(prefix):].replace(":", "/").split("/", 1)[0]
+            return f"{prefix}{bucket}"
+    return "local"
+
+
+def _pid_alive(pid: Any) -> bool:
+    try:
+        pid_value = int(pid or 0)
+    except (TypeError, ValueError):
+        return False
+    if pid_value <= 0:
+        return False
+    try:
+        os.kill(pid_value, 0)
+    except ProcessLookupError:
+        return False
+    except PermissionError:
+        return True
+    except OSError:
+        return False
+    return True
+
+
+class TransferQueue:
+    """Queued transfers stored in `transfer_queue.json` under the runtime state dir.
+
+    Higher `priority` runs first; ties keep insertion order unless moved. A
+    queued entry starts only while fewer than the destination's limit are
+    running (`transfer.queue.max_per_host`, overridden per key by
+    `transfer.queue.host_limits`). Pausing stops new starts only.
+    """
+
+    def __init__(self, state_dir: Optional[Path] = None, *, config: Optional[Dict[str, Any]] = None):
+        self.root = Path(state_dir or RUNTIME_STATE_DIR)
+        self.path = self.root / QUEUE_FILENAME
+        self.log_dir = self.root / LOG_DIRNAME
+        if config is None:
+            from ..config import load_config
+
+            config = load_config()
+        settings = (config.get("transfer") or {}).get("queue") or {}
+        self.max_per_host = max(1, int(settings.get("max_per_host", 1) or 1))
+        self.host_limits = {str(key): max(1, int(value)) for key, value in (settings.get("host_limits") or {}).items()}
+
+    @contextlib.contextmanager
+    def _locked(self) -> Iterator[Dict[str, Any]]:
+        """Load state under an exclusive file lock and save it on exit."""
+        self.root.mkdir(parents=True, exist_ok=True)
+        with open(self.root / f"{QUEUE_FILENAME}.lock", "w") as lock:
+            fcntl.flock(lock, fcntl.LOCK_EX)
+            state = self._read()
+            yield state
+            self.path.write_text(json.dumps(to_jsonable(state), ensure_ascii=False, indent=2), encoding="utf-8")
+
+    def _read(self) -> Dict[str, Any]:
+        state: Dict[str, Any] = {}
+        if self.path.exists():
+            try:
+                state = json.loads(self.path.read_text(encoding="utf-8"))
+            except Exception:
+                state = {}
+        if not isinstance(state, dict):
+            state = {}
+        state.setdefault("paused", False)
+        state.setdefault("seq", 0)
+        state.setdefault("entries", [])
+        return state
+
+    @staticmethod
+    def _ordered(entries: Sequence[Dict[str, Any]]) -> List[Dict[str, Any]]:
+        return sorted(entries, key=lambda entry: (-int(entry.get("priority", 0)), int(entry.get("seq", 0))))
+
+    @staticmethod
+    def _find(state: Dict[str, Any], entry_id: str) -> Dict[str, Any]:
+        matches = [entry for entry in state["entries"] if str(entry.get("id", "")).startswith(entry_id)]
+        if len(matches) != 1:
+            raise KeyError(f"{'Ambiguous' if matches else 'Unknown'} transfer id: {entry_id}")
+        return matches[0]
+
+    def limit_for(self, key: str) -> int:
+        return self.host_limits.get(key, self.max_per_host)
+
+    def log_path(self, entry_id: str) -> Path:
+        return self.log_dir / f"{entry_id}.log"
+
+    def add(self, source: str, destination: str, *, args: Sequence[str] = (), priority: int = 0) -> Dict[str, Any]:
+        """Queue one transfer; `args` are extra `train transfer` flags."""
+        with self._locked() as state:
+            state["seq"] += 1
+            entry = {
+                "id": uuid.uuid4().hex[:8],
+                "source": source,
+                "destination": destination,
+                "args": list(args),
+                "priority": int(priority),
+                "seq": state["seq"],
+                "host": destination_key(destination),
+                "status": "queued",
+                "created_at": datetime.now().isoformat(),
+            }
+            state["entries"].append(entry)
+        return dict(entry)
+
+    def entries(self) -> List[Dict[str, Any]]:
+        """Active entries in run order, then finished ones by completion time."""
+        state = self._read()
+        active = self._ordered([entry for entry in state["entries"] if entry.get("status") in ACTIVE_STATUSES])
+        active.sort(key=lambda entry: entry.get("status") != "running")
+        finished = [entry for entry in state["entries"] if entry.get("status") in FINISHED_STATUSES]
+        finished.sort(key=lambda entry: str(entry.get("finished_at", "")))
+        return active + finished
+
+    def get(self, entry_id: str) -> Dict[str, Any]:
+        return dict(self._find(self._read(), entry_id))
+
+    @property
+    def paused(self) -> bool:
+        return bool(self._read()["paused"])
+
+    def set_paused(self, paused: bool) -> None:
+        with self._locked() as state:
+            state["paused"] = bool(paused)
+
+    def set_priority(self, entry_id: str, priority: int) -> Dict[str, Any]:
+        with self._locked() as state:
+            entry = self._find(state, entry_id)
+            entry["priority"] = int(priority)
+        return dict(entry)
+
+    def move(self, entry_id: str, position: str) -> Dict[str, Any]:
+        """Move an entry to the `top` or `bottom` of its priority band."""
+        if position not in ("top", "bottom"):
+            raise ValueError(f"Position must be top or bottom (got {position!r})")
+        with self._locked() as state:
+            entry = self._find(state, entry_id)
+            seqs = [int(item.get("seq", 0)) for item in state["entries"]]
+            entry["seq"] = min(seqs) - 1 if position == "top" else max(seqs) + 1
+        return dict(entry)
+
+    def cancel(self, entry_id: str) -> Dict[str, Any]:
+        """Cancel a queued or running entry; a runner stops running ones on its next poll."""
+        with self._locked() as state:
+            entry = self._find(state, entry_id)
+            if entry.get("status") not in ACTIVE_STATUSES:
+                raise ValueError(f"Transfer {entry['id']} already {entry.get('status')}")
+            entry["status"] = "cancelled"
+            entry["finished_at"] = datetime.now().isoformat()
+        return dict(entry)
+
+    def clear_finished(self) -> int:
+        with self._locked() as state:
+            before = len(state["entries"])
+            state["entries"] = [entry for entry in state["entries"] if entry.get("status") in ACTIVE_STATUSES]
+            removed = before - len(state["entries"])
+        return removed
+
+    def claim_next(self, pid: Optional[int] = None) -> Optional[Dict[str, Any]]:
+        """Mark the next startable entry running, or return None (paused, empty, or at limits).
+
+        Entries left running by a dead runner are requeued first.
+        """
+        with self._locked() as state:
+            running: Dict[str, int] = {}
+            for entry in state["entries"]:
+                if entry.get("status") != "running":
+                    continue
+                if not _pid_alive(entry.get("pid")):
+                    entry["status"] = "queued"
+                    entry.pop("pid", None)
+                    continue
+                running[entry["host"]] = running.get(entry["host"], 0) + 1
+            if state["paused"]:
+                return None
+            for entry in self._ordered([item for item in state["entries"] if item.get("status") == "queued"]):
+                if running.get(entry["host"], 0) >= self.limit_for(entry["host"]):
+                    continue
+                entry["status"] = "running"
+                entry["pid"] = int(pid if pid is not None else os.getpid())
+                entry["started_at"] = datetime.now().isoformat()
+                return dict(entry)
+        return None
+
+    def finish(self, entry_id: str, *, success: bool, message: str = "") -> Dict[str, Any]:
+        """Record a running entry's outcome; a cancelled entry keeps its status."""
+        with self._locked() as state:
+            entry = self._find(state, entry_id)
+            if entry.get("status") == "running":
+                entry["status"] = "done" if success else "failed"
+                entry["finished_at"] = datetime.now().isoformat()
+            entry.pop("pid", None)
+            if message:
+                entry["message"] = message
+        return dict(entry)
+
+    def record_progress(self, entry_id: str, progress: Dict[str, Any]) -> None:
+        """Store the latest progress on the entry and emit a `transfer:progress` event."""
+        with self._locked() as state:
+            entry = self._find(state, entry_id)
+            entry["progress"] = dict(progress)
+        RuntimeStore(self.root).append_event(
+            {
+                "run_id": EVENT_RUN_ID,
+                "event": PROGRESS_EVENT,
+                "event_name": PROGRESS_EVENT,
+                "step_num": None,
+                "payload": {"transfer_id": entry["id"], **progress},
+                "ts": datetime.now().isoformat(),
+            }
+        )
+
+    def pending(self) -> int:
+        return sum(1 for entry in self._read()["entries"] if entry.get("status") == "queued")
+
+
+__all__ = ["EVENT_RUN_ID", "TRANSFER_ID_ENV", "TransferQueue", "destination_key"]
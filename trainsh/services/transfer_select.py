@@ -0,0 +1,171 @@
+"""Glob patterns and multi-path sources for transfers.
+
+A transfer source may be a glob (`checkpoints/epoch_*.pt`) or a list of
+paths. Patterns are expanded when the transfer runs, on the side that holds
+the files: locally with `glob`, on a host with one shell loop. Resolved
+directories carry a trailing `/`. Every match is copied into the destination
+directory under its basename, and the resolved list is returned on the
+`TransferResult`.
+"""
+
+from __future__ import annotations
+
+import glob
+import os
+import posixpath
+import re
+import shlex
+from dataclasses import replace
+from typing import Any, Dict, List, Optional, Sequence
+
+from ..core.models import TransferEndpoint
+from .transfer_support import TransferResult
+
+_GLOB_TOKEN = re.compile(r"(\*|\?|\[[^\]/]+\])")
+
+
+def has_glob(path: str) -> bool:
+    return bool(_GLOB_TOKEN.search(str(path or "")))
+
+
+def is_selection(paths: Sequence[str]) -> bool:
+    """True when `paths` needs expansion: several paths or any glob."""
+    return len(paths) > 1 or any(has_glob(path) for path in paths)
+
+
+def glob_shell_word(pattern: str) -> str:
+    """Shell word that quotes the literal parts of `pattern` and leaves `* ? [..]` active."""
+    prefix = ""
+    if pattern == "~" or pattern.startswith("~/"):
+        prefix, pattern = '"$HOME"', pattern[1:]
+    parts = []
+    for piece in _GLOB_TOKEN.split(pattern):
+        if not piece:
+            continue
+        parts.append(piece if _GLOB_TOKEN.fullmatch(piece) else shlex.quote(piece))
+    return prefix + "".join(parts)
+
+
+def _ordered(matches: List[str]) -> List[str]:
+    seen: Dict[str, None] = {}
+    for match in matches:
+        seen.setdefault(match, None)
+    return list(seen)
+
+
+def expand_local(patterns: Sequence[str]) -> List[str]:
+    """Local paths matching `patterns`, in pattern order; FileNotFoundError if one matches nothing."""
+    matches: List[str] = []
+    for pattern in patterns:
+        path = os.path.expanduser(pattern)
+        found = sorted(glob.glob(path)) if has_glob(path) else [path] if os.path.lexists(path) else []
+        if not found:
+            raise FileNotFoundError(f"No files match {pattern}")
+        matches.extend(match.rstrip("/") + "/" if os.path.isdir(match) else match for match in found)
+    return _ordered(matches)
+
+
+def expand_command(patterns: Sequence[str]) -> str:
+    """Shell loop printing `<pattern index>\\t<path>\\0` for every existing match."""
+    loops = [
+        f'for f in {glob_shell_word(pattern)}; do if [ -e "$f" ] || [ -L "$f" ]; then '
+        f'd=; [ -d "$f" ] && d=/; printf "%s\\t%s%s\\0" {index} "${{f%/}}" "$d"; fi; done'
+        for index, pattern in enumerate(patterns)
+    ]
+    return "\n".join(loops)
+
+
+def parse_expand_output(text: str, patterns: Sequence[str]) -> List[str]:
+    matched = set()
+    matches: List[str] = []
+    for record in text.split("\0"):
+        index, sep, path = record.partition("\t")
+        if not sep or not index.isdigit():
+            continue
+        matched.add(int(index))
+        matches.append(path)
+    for index, pattern in enumerate(patterns):
+        if index not in matched:
+            raise FileNotFoundError(f"No files match {pattern}")
+    return _ordered(matches)
+
+
+def expand_host(host: Any, patterns: Sequence[str]) -> List[str]:
+    """Paths on `host` (name or Host) matching `patterns`, expanded by the host's shell."""
+    from .host_exec import host_exec
+
+    result = host_exec(host, expand_command(patterns), timeout=60)
+    if not result.success:
+        raise RuntimeError(result.stderr.strip() or f"Could not expand {', '.join(patterns)} (exit {result.exit_code})")
+    return parse_expand_output(result.stdout, patterns)
+
+
+def expand_sources(source: TransferEndpoint, patterns: Sequence[str], hosts: Optional[Dict[str, Any]] = None) -> List[str]:
+    """Resolve `patterns` on the side of `source`; storage sources are not expanded."""
+    if source.type == "local":
+        return expand_local(patterns)
+    if source.type == "host":
+        return expand_host((hosts or {}).get(source.host_id) or source.host_id, patterns)
+    raise ValueError("Globs and multiple sources need a local or host source")
+
+
+def destination_for(destination: TransferEndpoint, path: str) -> str:
+    """Where one resolved source path lands: `destination.path` joined with its basename.
+
+    rclone always copies a single file into the destination directory, so
+    files bound for storage keep the directory itself.
+    """
+    name = posixpath.basename(path.rstrip("/")) or path
+    if destination.type == "storage" and not path.endswith("/"):
+        return destination.path
+    if destination.type == "local":
+        return os.path.join(os.path.expanduser(destination.path), name)
+    base = destination.path.rstrip("/")
+    return f"{base}/{name}" if base or destination.path.startswith("/") else name
+
+
+def transfer_selection(
+    engine: Any,
+    source: TransferEndpoint,
+    patterns: Sequence[str],
+    destination: TransferEndpoint,
+    *,
+    hosts: Optional[Dict[str, Any]] = None,
+    storages: Optional[Dict[str, Any]] = None,
+    exclude: Optional[List[str]] = None,
+    dry_run: bool = False,
+) -> TransferResult:
+    """Expand `patterns` and copy each match into `destination`, stopping at the first failure."""
+    try:
+        files = expand_sources(source, patterns, hosts)
+    except (FileNotFoundError, KeyError, RuntimeError, ValueError) as exc:
+        return TransferResult(success=False, exit_code=-1, message=str(exc))
+    total = 0
+    for done, path in enumerate(files):
+        result = engine.transfer(
+            source=replace(source, path=path),
+            destination=replace(destination, path=destination_for(destination, path)),
+            hosts=hosts,
+            storages=storages,
+            exclude=exclude,
+            dry_run=dry_run,
+        )
+        total += result.bytes_transferred
+        if not result.success:
+            message = f"{path}: {result.message} ({done} of {len(files)} paths copied)"
+            return TransferResult(success=False, exit_code=result.exit_code, message=message, bytes_transferred=total, files=files)
+    return TransferResult(success=True, exit_code=0, message=f"{len(files)} paths", bytes_transferred=total, files=files)
+
+
+__all__ = [
+    "destination_for",
+    "expand_command",
+    "expand_host",
+    "expand_local",
+    "expand_sources",
+    "glob_shell_word",
+    "has_glob",
+    "is_selection",
+    "parse_expand_output",
+    "transfer_selection",
+]
@@ -5,8 +5,8 @@ from __future__ import annotations
 import os
 import re
 import subprocess
-from dataclasses import dataclass
-from typing import Callable, Dict, Optional
+from dataclasses import dataclass, field
+from typing import Any, Callable, Dict, List, Optional
 
 from ..constants import SecretKeys
 from ..core.models import Host, Storage, StorageType, TransferEndpoint
@@ -261,7 +261,11 @@ def get_rclone_remote_name(storage: Storage) -> str:
 
 @dataclass
 class TransferProgress:
-    """Progress information for a transfer."""
+    """Progress information for a transfer.
+
+    `speed`/`eta` keep the backend's own text; the numeric fields are the
+    backend-neutral model (0 or None when the backend cannot tell).
+    """
 
     bytes_transferred: int = 0
     total_bytes: int = 0
@@ -269,6 +273,23 @@ class TransferProgress:
     speed: str = ""
     eta: str = ""
     current_file: str = ""
+    files_done: int = 0
+    files_total: int = 0
+    speed_bps: float = 0.0
+    eta_seconds: Optional[int] = None
+    backend: str = ""
+
+    def to_dict(self) -> Dict[str, Any]:
+        return {
+            "backend": self.backend,
+            "bytes_done": self.bytes_transferred,
+            "bytes_total": self.total_bytes,
+            "percent": round(self.percent, 1),
+            "files_done": self.files_done,
+            "files_total": self.files_total,
+            "speed_bps": round(self.speed_bps, 1),
+            "eta_seconds": self.eta_seconds,
+        }
 
 
 @dataclass
@@ -279,6 +300,9 @@ class TransferResult:
     exit_code: int
     message: str
     bytes_transferred: int = 0
+    progress: Optional[TransferProgress] = None
+    # Source paths a glob or multi-path transfer resolved to.
+    files: List[str] = field(default_factory=list)
 
 
 class TransferPlan:
@@ -421,22 +445,9 @@ def rsync_with_progress(
 
 def _parse_rsync_progress(line: str) -> Optional[TransferProgress]:
     """Parse rsync --info=progress2 output line."""
-    match = re.search(
-        r"([\d,]+)\s+(\d+)%\s+([\d.]+\w+/s)\s+([\d:]+(?:\s+\(xfr.*\))?)",
-        line,
-    )
-    if match:
-        bytes_str = match.group(1).replace(",", "")
-        try:
-            return TransferProgress(
-                bytes_transferred=int(bytes_str),
-                percent=float(match.group(2)),
-                speed=match.group(3),
-                eta=match.group(4).split("(")[0].strip(),
-            )
-        except ValueError:
-            pass
-    return None
+    from .transfer_progress import RsyncProgressParser
+
+    return RsyncProgressParser(aggregate=True).feed(line)
 
 
 def check_rsync_available() -> bool:
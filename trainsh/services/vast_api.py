@@ -2,23 +2,98 @@
 # REST API client for Vast.ai GPU marketplace
 
 import json
+import random
+import threading
+import time
 from dataclasses import dataclass
-from typing import Optional, List, Dict, Any
+from typing import Optional, List, Dict, Any, Tuple
+from urllib.parse import urlencode
 from urllib.request import Request, urlopen
 from urllib.error import HTTPError, URLError
 import ssl
 
 from ..constants import VAST_API_BASE
+from ..core.errors import AppError, ErrorCode, classify_http
 from ..core.models import VastInstance, VastOffer
 
 
-class VastAPIError(Exception):
+class VastAPIError(AppError):
     """Exception raised for Vast.ai API errors."""
 
-    def __init__(self, status_code: int, message: str):
+    def __init__(self, status_code: int, message: str, code: Optional[ErrorCode] = None):
         self.status_code = status_code
         self.message = message
-        super().__init__(f"Vast.ai API error ({status_code}): {message}")
+        super().__init__(
+            f"Vast.ai API error ({status_code}): {message}",
+            code or classify_http(status_code, message),
+            provider="vast",
+            module="vast_api",
+            details={"status_code": status_code},
+        )
+
+
+@dataclass
+class RetryPolicy:
+    """How `VastAPIClient` retries failed requests and paces the ones it sends."""
+
+    retries: int = 3
+    backoff_secs: float = 1.0
+    max_backoff_secs: float = 30.0
+    min_interval_secs: float = 0.25
+
+    @classmethod
+    def from_config(cls) -> "RetryPolicy":
+        from ..config import get_config_value
+
+        return cls(
+            retries=max(0, int(get_config_value("vast.api_retries", cls.retries))),
+            backoff_secs=float(get_config_value("vast.api_backoff_secs", cls.backoff_secs)),
+            max_backoff_secs=float(get_config_value("vast.api_max_backoff_secs", cls.max_backoff_secs)),
+            min_interval_secs=float(get_config_value("vast.api_min_interval_secs", cls.min_interval_secs)),
+        )
+
+    def delay(self, attempt: int, retry_after: Optional[float] = None) -> float:
+        """Full-jitter backoff for retry `attempt` (0-based); a server `Retry-After` wins."""
+        if retry_after is not None:
+            return min(self.max_backoff_secs, max(0.0, retry_after))
+        return random.uniform(0, min(self.max_backoff_secs, self.backoff_secs * (2 ** attempt)))
+
+
+class _RateLimiter:
+    """Keeps requests made with one API key at least `min_interval` apart, across threads."""
+
+    def __init__(self):
+        self._lock = threading.Lock()
+        self._next_at: Dict[str, float] = {}
+
+    def wait(self, key: str, min_interval: float) -> None:
+        with self._lock:
+            now = time.monotonic()
+            start = max(now, self._next_at.get(key, 0.0))
+            self._next_at[key] = start + max(0.0, min_interval)
+        if start > now:
+            time.sleep(start - now)
+
+    def hold(self, key: str, seconds: float) -> None:
+        """Push every caller sharing `key` back after the API asked us to slow down."""
+        with self._lock:
+            self._next_at[key] = max(self._next_at.get(key, 0.0), time.monotonic() + seconds)
+
+
+_rate_limiter = _RateLimiter()
+
+# Seconds a GET response stays fresh, by the endpoint's first path segment.
+DEFAULT_CACHE_TTLS = {"instances": 5.0, "ssh": 300.0}
+# Cached endpoint families a mutation of another family makes stale (renting an offer adds an instance).
+_INVALIDATES = {"asks": ("instances",)}
+
+
+def _retry_after(error: HTTPError) -> Optional[float]:
+    value = error.headers.get("Retry-After") if error.headers else None
+    try:
+        return float(value) if value is not None else None
+    except ValueError:
+        return None
 
 
 class VastAPIClient:
@@ -32,29 +107,80 @@ class VastAPIClient:
     - Manage SSH keys
     """
 
-    def __init__(self, api_key: str):
+    def __init__(
+        self,
+        api_key: str,
+        retry_policy: Optional[RetryPolicy] = None,
+        cache_ttls: Optional[Dict[str, float]] = None,
+    ):
         """
         Initialize the API client.
 
         Args:
             api_key: Vast.ai API key
+            retry_policy: Retry and pacing settings (defaults to `RetryPolicy()`)
+            cache_ttls: Response cache TTLs per endpoint family (defaults to `DEFAULT_CACHE_TTLS`)
         """
         self.api_key = api_key
         self.base_url = VAST_API_BASE
+        self.retry_policy = retry_policy or RetryPolicy()
+        self.cache_ttls = dict(DEFAULT_CACHE_TTLS if cache_ttls is None else cache_ttls)
+        self._cache: Dict[str, Tuple[float, Any]] = {}
+        self._cache_lock = threading.Lock()
+
+    # =========================================================================
+    # Response Cache
+    # =========================================================================
+
+    def _cache_get(self, endpoint: str) -> Optional[Any]:
+        ttl = float(self.cache_ttls.get(endpoint.split("/")[0], 0) or 0)
+        with self._cache_lock:
+            entry = self._cache.get(endpoint)
+        if entry is None or ttl <= 0 or time.monotonic() - entry[0] > ttl:
+            return None
+        return entry[1]
+
+    def _cache_put(self, endpoint: str, value: Any) -> None:
+        if float(self.cache_ttls.get(endpoint.split("/")[0], 0) or 0) > 0:
+            with self._cache_lock:
+                self._cache[endpoint] = (time.monotonic(), value)
+
+    def _cached_request(self, endpoint: str) -> Any:
+        cached = self._cache_get(endpoint)
+        if cached is None:
+            cached = self._request(endpoint)
+            self._cache_put(endpoint, cached)
+        return cached
+
+    def invalidate(self, *families: str) -> None:
+        """Drop cached responses for the given endpoint families (all when none given)."""
+        with self._cache_lock:
+            for key in list(self._cache):
+                if not families or key.split("/")[0] in families:
+                    del self._cache[key]
 
     def _request(
         self,
         endpoint: str,
         method: str = "GET",
         data: Optional[Dict[str, Any]] = None,
+        params: Optional[Dict[str, Any]] = None,
+        idempotent: Optional[bool] = None,
     ) -> Dict[str, Any]:
         """
         Make an HTTP request to the API.
 
+        Requests are paced by the shared rate limiter. Rate-limited (429)
+        responses are always retried; 5xx responses and network errors are
+        retried only for idempotent requests, so a timed-out rental is never
+        sent twice.
+
         Args:
             endpoint: API endpoint (without base URL)
             method: HTTP method
             data: Request body data
+            params: Query string parameters
+            idempotent: Whether repeating the request is safe (default: GET/DELETE)
 
         Returns:
             Parsed JSON response
@@ -63,6 +189,8 @@ class VastAPIClient:
             VastAPIError: If the API returns an error
         """
         url = f"{self.base_url}/{endpoint}/"
+        if params:
+            url = f"{url}?{urlencode(params)}"
         headers = {
             "Authorization": f"Bearer {self.api_key}",
             "Content-Type": "application/json",
@@ -75,19 +203,39 @@ class VastAPIClient:
         # Create SSL context that allows unverified certificates (for testing)
         ctx = ssl.create_default_context()
 
-        req = Request(url, data=body, headers=headers, method=method)
-
-        try:
-            with urlopen(req, context=ctx) as response:
-                response_data = response.read().decode("utf-8")
-                if response_data:
-                    return json.loads(response_data)
-                return {}
-        except HTTPError as e:
-            error_body = e.read().decode("utf-8") if e.fp else ""
-            raise VastAPIError(e.code, error_body)
-        except URLError as e:
-            raise VastAPIError(0, str(e.reason))
+        if idempotent is None:
+            idempotent = method in ("GET", "DELETE")
+        if method != "GET":
+            family = endpoint.split("/")[0]
+            self.invalidate(family, *_INVALIDATES.get(family, ()))
+        policy = self.retry_policy
+        attempt = 0
+        while True:
+            _rate_limiter.wait(self.api_key, policy.min_interval_secs)
+            req = Request(url, data=body, headers=headers, method=method)
+            retry_after = None
+            try:
+                with urlopen(req, context=ctx) as response:
+                    response_data = response.read().decode("utf-8")
+                    if response_data:
+                        return json.loads(response_data)
+                    return {}
+            except HTTPError as e:
+                error_body = e.read().decode("utf-8") if e.fp else ""
+                error = VastAPIError(e.code, error_body)
+                retry_after = _retry_after(e)
+                should_retry = e.code == 429 or (idempotent and e.code >= 500)
+            except URLError as e:
+                error = VastAPIError(0, str(e.reason))
+                should_retry = idempotent
+            if not should_retry or attempt >= policy.retries:
+                raise error
+            delay = policy.delay(attempt, retry_after)
+            if error.status_code == 429:
+                _rate_limiter.hold(self.api_key, delay)
+            else:
+                time.sleep(delay)
+            attempt += 1
 
     # =========================================================================
     # Instance Operations
@@ -100,7 +248,7 @@ class VastAPIClient:
         Returns:
             List of VastInstance objects
         """
-        response = self._request("instances")
+        response = self._cached_request("instances")
         instances = response.get("instances", [])
         return [self._parse_instance(i) for i in instances]
 
@@ -108,13 +256,19 @@ class VastAPIClient:
         """
         Get details for a specific instance.
 
+        Served from a fresh `list_instances` response when there is one.
+
         Args:
             instance_id: The instance ID
 
         Returns:
             VastInstance object
         """
-        response = self._request(f"instances/{instance_id}")
+        listed = self._cache_get("instances")
+        for raw in (listed or {}).get("instances", []) or []:
+            if str(raw.get("id")) == str(instance_id):
+                return self._parse_instance(raw)
+        response = self._cached_request(f"instances/{instance_id}")
         raw = response.get("instances")
         if raw is None:
             raise VastAPIError(404, f'{{"success":false,"error":"no_such_instance","msg":"Instance {instance_id} not found."}}')
@@ -131,6 +285,7 @@ class VastAPIClient:
             f"instances/{instance_id}",
             method="PUT",
             data={"state": "running"},
+            idempotent=True,
         )
 
     def stop_instance(self, instance_id: int) -> None:
@@ -144,6 +299,7 @@ class VastAPIClient:
             f"instances/{instance_id}",
             method="PUT",
             data={"state": "stopped"},
+            idempotent=True,
         )
 
     def rm_instance(self, instance_id: int) -> None:
@@ -185,6 +341,7 @@ class VastAPIClient:
             f"instances/{instance_id}",
             method="PUT",
             data={"label": label},
+            idempotent=True,
         )
 
     def execute_command(self, instance_id: int, command: str) -> Dict[str, Any]:
@@ -247,6 +404,7 @@ class VastAPIClient:
             "search/asks",
             method="PUT",
             data={"q": query, "limit": limit},
+            idempotent=True,
         )
 
         offers = response.get("offers", [])
@@ -260,6 +418,7 @@ class VastAPIClient:
         label: Optional[str] = None,
         onstart: Optional[str] = None,
         direct: bool = False,
+        env: Optional[Dict[str, str]] = None,
     ) -> int:
         """
         Create a new instance from an offer.
@@ -271,6 +430,7 @@ class VastAPIClient:
             label: Optional label for the instance
             onstart: Optional startup script
             direct: Use direct SSH connection
+            env: Environment variables for the container
 
         Returns:
             The new instance/contract ID
@@ -278,7 +438,7 @@ class VastAPIClient:
         data = {
             "client_id": "me",
             "image": image,
-            "env": {},
+            "env": dict(env or {}),
             "disk": disk,
             "label": label or "",
             "onstart": onstart or "",
@@ -291,10 +451,28 @@ class VastAPIClient:
         new_contract = response.get("new_contract")
 
         if not new_contract:
-            raise VastAPIError(0, "No contract ID returned")
+            raise VastAPIError(0, "No contract ID returned", ErrorCode.SERVICE_UNAVAILABLE)
 
         return new_contract
 
+    # =========================================================================
+    # Billing
+    # =========================================================================
+
+    def list_charges(self, start: float, end: float) -> List[Dict[str, Any]]:
+        """
+        List billed charges between two Unix timestamps.
+
+        Returns:
+            Raw invoice rows of type "charge"
+        """
+        response = self._request(
+            "users/me/invoices",
+            params={"owner": "me", "sdate": int(start), "edate": int(end), "inc_charges": "true"},
+        )
+        rows = response.get("invoices", response.get("charges", [])) or []
+        return [row for row in rows if str(row.get("type", "charge")) == "charge"]
+
     # =========================================================================
     # SSH Key Operations
     # =========================================================================
@@ -306,7 +484,7 @@ class VastAPIClient:
         Returns:
             List of SSH key objects
         """
-        response = self._request("ssh")
+        response = self._cached_request("ssh")
         # API may return list directly or dict with ssh_keys
         if isinstance(response, list):
             return response
@@ -421,10 +599,16 @@ class VastAPIClient:
         )
 
 
+_clients: Dict[str, VastAPIClient] = {}
+
+
 def get_vast_client() -> VastAPIClient:
     """
     Get a Vast.ai API client using stored credentials.
 
+    Clients are shared per API key, so their response cache spans commands
+    within one process.
+
     Returns:
         VastAPIClient instance
 
@@ -442,4 +626,11 @@ def get_vast_client() -> VastAPIClient:
             "Run: train secrets set VAST_API_KEY"
         )
 
-    return VastAPIClient(api_key)
+    client = _clients.get(api_key)
+    if client is None:
+        from ..config import get_config_value
+
+        ttls = get_config_value("vast.api_cache_ttls", None)
+        client = VastAPIClient(api_key, RetryPolicy.from_config(), ttls if isinstance(ttls, dict) else None)
+        _clients[api_key] = client
+    return client
@@ -0,0 +1,129 @@
+"""Run one Vast.ai operation on many instances with bounded parallelism.
+
+Each instance is attempted independently: a failure is recorded in its
+`BatchResult` instead of aborting the rest, and a `vast:batch` progress
+event is emitted as every instance finishes. A call that returns `QUEUED`
+deferred its work (e.g. queued while offline) and is reported as such.
+"""
+
+from __future__ import annotations
+
+from concurrent.futures import ThreadPoolExecutor, as_completed
+from dataclasses import dataclass
+from typing import Any, Callable, Dict, List, Optional, Sequence, Tuple
+
+from ..core.errors import error_info
+
+BATCH_EVENT = "vast:batch"
+QUEUED = "queued"
+
+ProgressCallback = Callable[[Dict[str, Any]], None]
+# `(kind, instance_id, error, params)` -> True when the operation was queued instead.
+DeferCallback = Callable[[str, int, Exception, Dict[str, Any]], bool]
+
+
+@dataclass
+class BatchResult:
+    instance_id: int
+    ok: bool
+    error: str = ""
+    error_code: str = ""
+    retryable: bool = False
+    queued: bool = False
+
+    def to_dict(self) -> Dict[str, Any]:
+        return {
+            "instance_id": self.instance_id,
+            "ok": self.ok,
+            "queued": self.queued,
+            "error": self.error,
+            "error_code": self.error_code,
+            "retryable": self.retryable,
+        }
+
+
+def batch_parallelism() -> int:
+    from ..config import get_config_value
+
+    try:
+        return max(1, int(get_config_value("vast.batch_parallelism", 4)))
+    except (TypeError, ValueError):
+        return 4
+
+
+def run_batch(
+    action: str,
+    items: Sequence[Tuple[int, Callable[[], Any]]],
+    *,
+    max_workers: Optional[int] = None,
+    on_progress: Optional[ProgressCallback] = None,
+) -> List[BatchResult]:
+    """Run `(instance_id, call)` pairs concurrently; results come back in input order."""
+    total = len(items)
+    results: Dict[int, BatchResult] = {}
+    if not total:
+        return []
+    workers = max(1, min(max_workers or batch_parallelism(), total))
+    with ThreadPoolExecutor(max_workers=workers) as pool:
+        futures = {pool.submit(call): (index, instance_id) for index, (instance_id, call) in enumerate(items)}
+        for future in as_completed(futures):
+            index, instance_id = futures[future]
+            try:
+                result = BatchResult(instance_id, True, queued=future.result() == QUEUED)
+            except Exception as exc:
+                info = error_info(exc)
+                result = BatchResult(instance_id, False, str(exc), info["error_code"], info["retryable"])
+            results[index] = result
+            if on_progress is not None:
+                on_progress({"event": BATCH_EVENT, "action": action, "done": len(results), "total": total, **result.to_dict()})
+    return [results[index] for index in range(total)]
+
+
+def _deferrable(kind: str, instance_id: int, call: Callable[[], Any], params: Dict[str, Any], defer: Optional[DeferCallback]):
+    if defer is None:
+        return call
+
+    def run():
+        try:
+            return call()
+        except Exception as exc:
+            if defer(kind, instance_id, exc, params):
+                return QUEUED
+            raise
+
+    return run
+
+
+def stop_instances(client: Any, ids: Sequence[int], *, defer: Optional[DeferCallback] = None, **kwargs: Any) -> List[BatchResult]:
+    return run_batch(
+        "stop",
+        [(i, _deferrable("vast.stop", i, lambda i=i: client.stop_instance(i), {}, defer)) for i in ids],
+        **kwargs,
+    )
+
+
+def destroy_instances(client: Any, ids: Sequence[int], **kwargs: Any) -> List[BatchResult]:
+    return run_batch("destroy", [(i, lambda i=i: client.rm_instance(i)) for i in ids], **kwargs)
+
+
+def label_instances(client: Any, labels: Dict[int, str], *, defer: Optional[DeferCallback] = None, **kwargs: Any) -> List[BatchResult]:
+    return run_batch(
+        "label",
+        [
+            (i, _deferrable("vast.label", i, lambda i=i, label=label: client.label_instance(i, label), {"label": label}, defer))
+            for i, label in labels.items()
+        ],
+        **kwargs,
+    )
+
+
+__all__ = [
+    "BATCH_EVENT",
+    "BatchResult",
+    "QUEUED",
+    "batch_parallelism",
+    "destroy_instances",
+    "label_instances",
+    "run_batch",
+    "stop_instances",
+]
@@ -0,0 +1,217 @@
+"""Monthly Vast.ai billing report: billed charges reconciled with local estimates."""
+
+from __future__ import annotations
+
+import csv
+import json
+import re
+from collections import defaultdict
+from dataclasses import dataclass
+from datetime import datetime, timedelta
+from pathlib import Path
+from typing import Any, Dict, Iterable, List, Optional, Tuple
+
+from ..constants import RUNTIME_STATE_DIR
+from ..core.runtime_store import to_jsonable
+
+CHARGES_FILENAME = "vast_billing.json"
+_INSTANCE_RE = re.compile(r"instance\D{0,3}(\d+)", re.IGNORECASE)
+
+
+@dataclass
+class BillingRow:
+    """Billed and estimated USD for one instance (or label) in one month."""
+
+    month: str
+    key: str
+    label: str = ""
+    billed_usd: float = 0.0
+    estimated_usd: float = 0.0
+
+    @property
+    def drift_usd(self) -> float:
+        return self.billed_usd - self.estimated_usd
+
+    @property
+    def drift_pct(self) -> Optional[float]:
+        if not self.estimated_usd:
+            return None
+        return 100.0 * self.drift_usd / self.estimated_usd
+
+
+def month_bounds(month: str) -> Tuple[datetime, datetime]:
+    """First instant of `YYYY-MM` and of the following month."""
+    start = datetime.strptime(month, "%Y-%m")
+    end = (start.replace(day=28) + timedelta(days=4)).replace(day=1)
+    return start, end
+
+
+def _as_datetime(value: Any) -> Optional[datetime]:
+    if value in (None, ""):
+        return None
+    if isinstance(value, (int, float)):
+        return datetime.fromtimestamp(float(value))
+    try:
+        return datetime.fromisoformat(str(value))
+    except ValueError:
+        try:
+            return datetime.fromtimestamp(float(value))
+        except ValueError:
+            return None
+
+
+def normalize_charge(row: Dict[str, Any]) -> Optional[Dict[str, Any]]:
+    """Reduce one raw invoice row to {instance_id, day, amount_usd, label}."""
+    when = _as_datetime(row.get("timestamp", row.get("start", row.get("time"))))
+    try:
+        amount = float(row.get("amount", row.get("cost", 0)) or 0)
+    except (TypeError, ValueError):
+        return None
+    if when is None or not amount:
+        return None
+    instance_id = str(row.get("instance_id") or row.get("contract_id") or "").strip()
+    if not instance_id:
+        match = _INSTANCE_RE.search(str(row.get("description", "")))
+        instance_id = match.group(1) if match else "other"
+    return {
+        "instance_id": instance_id,
+        "day": when.strftime("%Y-%m-%d"),
+        "amount_usd": abs(amount),
+        "label": str(row.get("label", "") or ""),
+    }
+
+
+class BillingCache:
+    """Normalized charges per month, so reports work offline after one refresh."""
+
+    def __init__(self, state_dir: Optional[Path] = None):
+        self.path = Path(state_dir or RUNTIME_STATE_DIR) / CHARGES_FILENAME
+
+    def _load(self) -> Dict[str, Any]:
+        if not self.path.exists():
+            return {"months": {}}
+        try:
+            return json.loads(self.path.read_text(encoding="utf-8"))
+        except Exception:
+            return {"months": {}}
+
+    def charges(self, month: str) -> Optional[List[Dict[str, Any]]]:
+        entry = self._load().get("months", {}).get(month)
+        return None if entry is None else list(entry.get("charges", []))
+
+    def store(self, month: str, charges: List[Dict[str, Any]]) -> None:
+        payload = self._load()
+        payload.setdefault("months", {})[month] = {
+            "fetched_at": datetime.now().isoformat(),
+            "charges": charges,
+        }
+        self.path.parent.mkdir(parents=True, exist_ok=True)
+        self.path.write_text(json.dumps(to_jsonable(payload), indent=2), encoding="utf-8")
+
+
+def fetch_month_charges(client: Any, month: str, cache: Optional[BillingCache] = None) -> List[Dict[str, Any]]:
+    start, end = month_bounds(month)
+    charges = [item for item in map(normalize_charge, client.list_charges(start.timestamp(), end.timestamp())) if item]
+    (cache or BillingCache()).store(month, charges)
+    return charges
+
+
+def _split_by_day(start: datetime, end: datetime, rate: float, out: Dict[str, float]) -> None:
+    cursor = start
+    while cursor < end:
+        next_day = (cursor + timedelta(days=1)).replace(hour=0, minute=0, second=0, microsecond=0)
+        chunk_end = min(end, next_day)
+        out[cursor.strftime("%Y-%m-%d")] += rate * (chunk_end - cursor).total_seconds() / 3600.0
+        cursor = chunk_end
+
+
+def estimate_from_events(
+    events: Iterable[Dict[str, Any]],
+    start: datetime,
+    end: datetime,
+    *,
+    now: Optional[datetime] = None,
+) -> Dict[str, Dict[str, float]]:
+    """Estimated USD per instance per day from `train vast watch` history.
+
+    Each event carries the instance's status and $/hr at that moment; running
+    time between consecutive events is billed at the rate seen at its start.
+    """
+    now = now or datetime.now()
+    by_instance: Dict[str, List[Tuple[datetime, Dict[str, Any]]]] = defaultdict(list)
+    for event in events:
+        when = _as_datetime(event.get("ts"))
+        if when is not None:
+            by_instance[str(event.get("instance_id"))].append((when, event))
+
+    estimates: Dict[str, Dict[str, float]] = {}
+    for instance_id, timeline in by_instance.items():
+        timeline.sort(key=lambda item: item[0])
+        days: Dict[str, float] = defaultdict(float)
+        running_since: Optional[datetime] = None
+        rate = 0.0
+        for when, event in timeline + [(now, {"status": None})]:
+            if running_since is not None:
+                lo, hi = max(running_since, start), min(when, end)
+                if lo < hi:
+                    _split_by_day(lo, hi, rate, days)
+            running_since = when if event.get("status") == "running" else None
+            rate = float(event.get("dph_total") or rate or 0.0)
+        if days:
+            estimates[instance_id] = dict(days)
+    return estimates
+
+
+def build_report(
+    month: str,
+    charges: List[Dict[str, Any]],
+    estimates: Dict[str, Dict[str, float]],
+    *,
+    group_by: str = "instance",
+    labels: Optional[Dict[str, str]] = None,
+) -> List[BillingRow]:
+    """Rows for one month grouped by instance id or label, largest bill first."""
+    labels = dict(labels or {})
+    for charge in charges:
+        if charge.get("label"):
+            labels.setdefault(str(charge["instance_id"]), str(charge["label"]))
+
+    def _key(instance_id: str) -> Tuple[str, str]:
+        label = labels.get(instance_id, "")
+        if group_by == "label":
+            return (label or "(unlabeled)"), label
+        return instance_id, label
+
+    rows: Dict[str, BillingRow] = {}
+    for charge in charges:
+        key, label = _key(str(charge["instance_id"]))
+        row = rows.setdefault(key, BillingRow(month=month, key=key, label=label))
+        row.billed_usd += float(charge.get("amount_usd", 0.0))
+    for instance_id, days in estimates.items():
+        key, label = _key(instance_id)
+        row = rows.setdefault(key, BillingRow(month=month, key=key, label=label))
+        row.estimated_usd += sum(amount for day, amount in days.items() if day.startswith(month))
+    return sorted(rows.values(), key=lambda row: (-row.billed_usd, row.key))
+
+
+def write_csv(path: str, rows: List[BillingRow], *, group_by: str = "instance") -> None:
+    with open(path, "w", newline="", encoding="utf-8") as f:
+        writer = csv.writer(f)
+        keys = ["month", "instance_id", "label"] if group_by == "instance" else ["month", "label"]
+        writer.writerow(keys + ["billed_usd", "estimated_usd", "drift_usd", "drift_pct"])
+        for row in rows:
+            pct = "" if row.drift_pct is None else f"{row.drift_pct:.1f}"
+            head = [row.month, row.key, row.label] if group_by == "instance" else [row.month, row.key]
+            writer.writerow(head + [f"{row.billed_usd:.4f}", f"{row.estimated_usd:.4f}", f"{row.drift_usd:.4f}", pct])
+
+
+__all__ = [
+    "BillingCache",
+    "BillingRow",
+    "build_report",
+    "estimate_from_events",
+    "fetch_month_charges",
+    "month_bounds",
+    "normalize_charge",
+    "write_csv",
+]
@@ -0,0 +1,170 @@
+"""Track Vast.ai instance state changes and keep a per-instance event history."""
+
+from __future__ import annotations
+
+import json
+import socket
+import threading
+from datetime import datetime
+from pathlib import Path
+from typing import Any, Callable, Dict, Iterable, List, Optional
+
+from ..constants import RUNTIME_STATE_DIR
+from ..core.runtime_store import RuntimeStore, to_jsonable
+
+STATE_FILENAME = "vast_instances.json"
+EVENT_RUN_ID = "vast"
+STATE_CHANGED_EVENT = "vast_instance_state_changed"
+EVENT_KINDS = ("created", "running", "stopped", "status_changed", "price_changed", "ssh_available", "destroyed")
+
+
+def ssh_port_open(host: str, port: int, *, timeout: float = 3.0) -> bool:
+    """Whether the instance's SSH endpoint accepts TCP connections."""
+    if not host or not port:
+        return False
+    try:
+        with socket.create_connection((host, int(port)), timeout=timeout):
+            return True
+    except OSError:
+        return False
+
+
+def _snapshot(instance: Any) -> Dict[str, Any]:
+    return {
+        "status": str(getattr(instance, "actual_status", "") or "unknown").lower(),
+        "dph_total": getattr(instance, "dph_total", None),
+        "ssh_host": getattr(instance, "ssh_host", None),
+        "ssh_port": getattr(instance, "ssh_port", None),
+        "label": getattr(instance, "label", None),
+        "gpu": f"{getattr(instance, 'num_gpus', None) or 1}x {getattr(instance, 'gpu_name', None) or '?'}",
+    }
+
+
+class VastInstanceWatcher:
+    """Diff successive instance listings into events.
+
+    The last snapshot lives in `vast_instances.json`; events go to the runtime
+    event log under run id `vast`, so history survives between `watch` runs.
+    """
+
+    def __init__(
+        self,
+        state_dir: Optional[Path] = None,
+        *,
+        ssh_probe: Callable[[str, int], bool] = ssh_port_open,
+    ):
+        self.root = Path(state_dir or RUNTIME_STATE_DIR)
+        self.path = self.root / STATE_FILENAME
+        self.store = RuntimeStore(self.root)
+        self.ssh_probe = ssh_probe
+        self._lock = threading.RLock()
+
+    def _load(self) -> Dict[str, Dict[str, Any]]:
+        if not self.path.exists():
+            return {}
+        try:
+            payload = json.loads(self.path.read_text(encoding="utf-8"))
+        except Exception:
+            return {}
+        return payload.get("instances", {}) if isinstance(payload, dict) else {}
+
+    def _save(self, instances: Dict[str, Dict[str, Any]]) -> None:
+        self.root.mkdir(parents=True, exist_ok=True)
+        payload = {"instances": instances, "updated_at": datetime.now().isoformat()}
+        self.path.write_text(json.dumps(to_jsonable(payload), ensure_ascii=False, indent=2), encoding="utf-8")
+
+    def _event(self, instance_id: str, kind: str, current: Dict[str, Any], previous: Dict[str, Any]) -> Dict[str, Any]:
+        event = {
+            "instance_id": instance_id,
+            "kind": kind,
+            "status": current.get("status"),
+            "previous_status": previous.get("status"),
+            "dph_total": current.get("dph_total"),
+            "previous_dph_total": previous.get("dph_total"),
+            "label": current.get("label") or previous.get("label"),
+            "ts": datetime.now().isoformat(),
+        }
+        if kind == "ssh_available":
+            event["ssh"] = f"{current.get('ssh_host')}:{current.get('ssh_port')}"
+        return event
+
+    def observe(self, instances: Iterable[Any]) -> List[Dict[str, Any]]:
+        """Record one listing; return the events it produced, oldest first.
+
+        The very first listing only sets the baseline and produces no events.
+        """
+        with self._lock:
+            baseline = not self.path.exists()
+            previous_all = self._load()
+            current_all: Dict[str, Dict[str, Any]] = {}
+            events: List[Dict[str, Any]] = []
+            for instance in instances:
+                instance_id = str(getattr(instance, "id", ""))
+                current = _snapshot(instance)
+                previous = previous_all.get(instance_id, {})
+                current["ssh_ready"] = bool(previous.get("ssh_ready")) and current["status"] == "running"
+                if not previous:
+                    events.append(self._event(instance_id, "created", current, previous))
+                elif previous.get("status") != current["status"]:
+                    kind = current["status"] if current["status"] in {"running", "stopped"} else "status_changed"
+                    events.append(self._event(instance_id, kind, current, previous))
+                if previous and previous.get("dph_total") != current["dph_total"]:
+                    events.append(self._event(instance_id, "price_changed", current, previous))
+                if (
+                    current["status"] == "running"
+                    and not current["ssh_ready"]
+                    and self.ssh_probe(current.get("ssh_host") or "", current.get("ssh_port") or 0)
+                ):
+                    current["ssh_ready"] = True
+                    events.append(self._event(instance_id, "ssh_available", current, previous))
+                current_all[instance_id] = current
+            if baseline:
+                events = []
+            for instance_id, previous in previous_all.items():
+                if instance_id not in current_all:
+                    events.append(self._event(instance_id, "destroyed", {"status": "destroyed"}, previous))
+            self._save(current_all)
+
+        for event in events:
+            self.store.append_event(
+                {
+                    "run_id": EVENT_RUN_ID,
+                    "event": STATE_CHANGED_EVENT,
+                    "event_name": STATE_CHANGED_EVENT,
+                    "step_num": None,
+                    "payload": event,
+                    "ts": event["ts"],
+                }
+            )
+        return events
+
+    def history(self, instance_id: Optional[str] = None) -> List[Dict[str, Any]]:
+        """Recorded events, optionally for one instance, oldest first."""
+        records = [
+            record.get("payload") or {}
+            for record in self.store.list_events(EVENT_RUN_ID)
+            if record.get("event") == STATE_CHANGED_EVENT
+        ]
+        if instance_id is None:
+            return records
+        return [record for record in records if str(record.get("instance_id")) == str(instance_id)]
+
+
+def describe_event(event: Dict[str, Any]) -> str:
+    kind = event.get("kind", "")
+    if kind == "price_changed":
+        return f"price ${event.get('previous_dph_total') or 0:.3f}/hr -> ${event.get('dph_total') or 0:.3f}/hr"
+    if kind == "ssh_available":
+        return f"SSH reachable at {event.get('ssh')}"
+    if kind in {"running", "stopped", "status_changed", "destroyed"} and event.get("previous_status"):
+        return f"{event.get('previous_status')} -> {event.get('status')}"
+    return str(event.get("status") or kind)
+
+
+__all__ = [
+    "EVENT_KINDS",
+    "STATE_CHANGED_EVENT",
+    "VastInstanceWatcher",
+    "describe_event",
+    "ssh_port_open",
+]
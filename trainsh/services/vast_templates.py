@@ -0,0 +1,169 @@
+"""Saved Vast.ai instance creation templates."""
+
+from __future__ import annotations
+
+import re
+from dataclasses import asdict, dataclass, field, fields
+from typing import Any, Dict, List, Optional, Tuple
+
+from ..constants import DEFAULT_VAST_DISK_GB, DEFAULT_VAST_IMAGE
+
+
+@dataclass
+class VastTemplate:
+    """Image, disk, environment and offer filters used to create one kind of instance."""
+
+    name: str
+    image: str = DEFAULT_VAST_IMAGE
+    disk_gb: float = DEFAULT_VAST_DISK_GB
+    env: Dict[str, str] = field(default_factory=dict)
+    onstart: str = ""
+    gpu_name: Optional[str] = None
+    num_gpus: Optional[int] = None
+    min_gpu_ram: Optional[float] = None
+    max_dph: Optional[float] = None
+    direct: bool = False
+    label: str = ""
+
+    def to_dict(self) -> Dict[str, Any]:
+        return {key: value for key, value in asdict(self).items() if value not in (None, "", {})}
+
+    @classmethod
+    def from_dict(cls, data: Dict[str, Any]) -> "VastTemplate":
+        known = {item.name for item in fields(cls)}
+        values = {key: value for key, value in (data or {}).items() if key in known}
+        values["env"] = {str(key): str(value) for key, value in (values.get("env") or {}).items()}
+        return cls(**values)
+
+
+def sanitize_template_name(value: str) -> str:
+    return re.sub(r"[^A-Za-z0-9._-]+", "-", str(value or "").strip()).strip("-.")
+
+
+def load_templates() -> Dict[str, VastTemplate]:
+    """Load saved templates keyed by name."""
+    from ..constants import VAST_TEMPLATES_FILE
+    import yaml
+
+    if not VAST_TEMPLATES_FILE.exists():
+        return {}
+    with open(VAST_TEMPLATES_FILE, "r") as f:
+        data = yaml.safe_load(f) or {}
+    templates = {}
+    for item in data.get("templates", []) or []:
+        template = VastTemplate.from_dict(item)
+        if template.name:
+            templates[template.name] = template
+    return templates
+
+
+def save_templates(templates: Dict[str, VastTemplate]) -> None:
+    from ..constants import CONFIG_DIR, VAST_TEMPLATES_FILE
+    import yaml
+
+    CONFIG_DIR.mkdir(parents=True, exist_ok=True)
+    data = {"templates": [template.to_dict() for template in templates.values()]}
+    with open(VAST_TEMPLATES_FILE, "w") as f:
+        yaml.dump(data, f, default_flow_style=False, sort_keys=False)
+
+
+def get_template(name: str) -> VastTemplate:
+    templates = load_templates()
+    if name not in templates:
+        raise KeyError(f"Vast template not found: {name}")
+    return templates[name]
+
+
+def pick_offer(client: Any, template: VastTemplate) -> Any:
+    """Cheapest offer matching the template filters, most reliable first on ties."""
+    offers = client.search_offers(
+        gpu_name=template.gpu_name,
+        num_gpus=template.num_gpus,
+        min_gpu_ram=template.min_gpu_ram,
+        max_dph=template.max_dph,
+        limit=20,
+    )
+    if not offers:
+        return None
+    return sorted(
+        offers,
+        key=lambda offer: (
+            float(getattr(offer, "dph_total", 0.0) or 0.0),
+            -float(getattr(offer, "reliability2", 0.0) or 0.0),
+        ),
+    )[0]
+
+
+def create_from_template(
+    client: Any,
+    template: VastTemplate,
+    *,
+    offer_id: Optional[int] = None,
+    label: Optional[str] = None,
+) -> Tuple[int, Optional[int]]:
+    """Create an instance from a template; returns (instance id, offer id used).
+
+    Without an explicit offer the cheapest offer matching the template's filters is
+    rented. An explicit offer is still checked against the template's price cap.
+    """
+    from ..core.errors import ErrorCode
+    from .vast_api import VastAPIError
+
+    if offer_id is None:
+        offer = pick_offer(client, template)
+        if offer is None:
+            raise VastAPIError(0, f"No Vast.ai offers match template {template.name}", ErrorCode.OFFER_UNAVAILABLE)
+        offer_id = int(offer.id)
+    elif template.max_dph:
+        matches = [
+            offer
+            for offer in client.search_offers(max_dph=template.max_dph, limit=200)
+            if int(getattr(offer, "id", 0) or 0) == int(offer_id)
+        ]
+        if not matches:
+            raise VastAPIError(0, f"Offer {offer_id} is unavailable or above ${template.max_dph:.3f}/hr", ErrorCode.OFFER_UNAVAILABLE)
+
+    instance_id = client.create_instance(
+        offer_id=offer_id,
+        image=template.image,
+        disk=template.disk_gb,
+        label=label or template.label or template.name,
+        onstart=template.onstart or None,
+        direct=template.direct,
+        env=dict(template.env),
+    )
+    return int(instance_id), offer_id
+
+
+def describe_template(template: VastTemplate) -> List[str]:
+    lines = [f"Image: {template.image}", f"Disk: {template.disk_gb:g} GB"]
+    filters = []
+    if template.gpu_name:
+        filters.append(template.gpu_name)
+    if template.num_gpus:
+        filters.append(f">= {template.num_gpus} GPU(s)")
+    if template.min_gpu_ram:
+        filters.append(f">= {template.min_gpu_ram:g} GB VRAM")
+    if template.max_dph:
+        filters.append(f"<= ${template.max_dph:.3f}/hr")
+    if filters:
+        lines.append("Offers: " + ", ".join(filters))
+    if template.env:
+        lines.append("Env: " + ", ".join(sorted(template.env)))
+    if template.onstart:
+        lines.append(f"Onstart: {len(template.onstart.splitlines())} line(s)")
+    if template.direct:
+        lines.append("Direct SSH: yes")
+    return lines
+
+
+__all__ = [
+    "VastTemplate",
+    "create_from_template",
+    "describe_template",
+    "get_template",
+    "load_templates",
+    "pick_offer",
+    "sanitize_template_name",
+    "save_templates",
+]
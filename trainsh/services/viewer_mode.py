@@ -0,0 +1,108 @@
+"""Viewer mode: hand the CLI to someone who should watch runs, not destroy things.
+
+While `viewer.enabled` is set, destructive actions raise `PermissionDenied`
+before they touch anything. Turning the mode off may require the passphrase
+whose PBKDF2 hash is stored in `viewer.passphrase_hash`.
+"""
+
+from __future__ import annotations
+
+import hashlib
+import hmac
+import secrets
+from typing import Any, Dict, Optional
+
+from ..core.errors import AppError, ErrorCode
+
+# Actions refused in viewer mode, with the command that performs each.
+DESTRUCTIVE_ACTIONS = {
+    "vast.destroy": "train vast remove",
+    "runpod.destroy": "train runpod remove",
+    "host.remove": "train host remove",
+    "storage.remove": "train storage remove",
+    "storage.delete": "train storage delete",
+    "secret.delete": "train secrets remove",
+    "config.change": "train config set|reset, train config profile add|switch|remove",
+    "step": "destructive recipe steps",
+}
+_ITERATIONS = 200_000
+
+
+class PermissionDenied(AppError):
+    """A destructive action was attempted in viewer mode."""
+
+    def __init__(self, action: str, message: str = ""):
+        self.action = action
+        super().__init__(
+            message or f"{action} is disabled in viewer mode (turn it off with `train config viewer off`).",
+            ErrorCode.PERMISSION_DENIED,
+            module="viewer_mode",
+            details={"action": action},
+        )
+
+
+def viewer_settings(config: Optional[Dict[str, Any]] = None) -> Dict[str, Any]:
+    if config is None:
+        from ..config import load_config
+
+        config = load_config()
+    section = (config or {}).get("viewer", {}) or {}
+    return {"enabled": bool(section.get("enabled", False)), "passphrase_hash": str(section.get("passphrase_hash", "") or "")}
+
+
+def is_viewer_mode(config: Optional[Dict[str, Any]] = None) -> bool:
+    return viewer_settings(config)["enabled"]
+
+
+def require_operator(action: str, config: Optional[Dict[str, Any]] = None) -> None:
+    """Raise `PermissionDenied` when `action` runs in viewer mode."""
+    if is_viewer_mode(config):
+        raise PermissionDenied(action)
+
+
+def hash_passphrase(passphrase: str, *, salt: Optional[str] = None) -> str:
+    salt = salt or secrets.token_hex(16)
+    digest = hashlib.pbkdf2_hmac("sha256", passphrase.encode("utf-8"), bytes.fromhex(salt), _ITERATIONS)
+    return f"pbkdf2_sha256${_ITERATIONS}${salt}${digest.hex()}"
+
+
+def verify_passphrase(passphrase: str, stored: str) -> bool:
+    try:
+        _scheme, iterations, salt, expected = stored.split("$")
+        digest = hashlib.pbkdf2_hmac("sha256", passphrase.encode("utf-8"), bytes.fromhex(salt), int(iterations))
+    except ValueError:
+        return False
+    return hmac.compare_digest(digest.hex(), expected)
+
+
+def enable_viewer_mode(passphrase: str = "") -> None:
+    from ..config import load_config, save_config
+
+    config = load_config()
+    config["viewer"] = {"enabled": True, "passphrase_hash": hash_passphrase(passphrase) if passphrase else ""}
+    save_config(config)
+
+
+def disable_viewer_mode(passphrase: str = "") -> None:
+    """Turn viewer mode off; raises `PermissionDenied` on a wrong passphrase."""
+    from ..config import load_config, save_config
+
+    config = load_config()
+    stored = viewer_settings(config)["passphrase_hash"]
+    if stored and not verify_passphrase(passphrase, stored):
+        raise PermissionDenied("viewer.off", "Wrong passphrase; viewer mode stays on.")
+    config["viewer"] = {"enabled": False, "passphrase_hash": ""}
+    save_config(config)
+
+
+__all__ = [
+    "DESTRUCTIVE_ACTIONS",
+    "PermissionDenied",
+    "disable_viewer_mode",
+    "enable_viewer_mode",
+    "hash_passphrase",
+    "is_viewer_mode",
+    "require_operator",
+    "verify_passphrase",
+    "viewer_settings",
+]
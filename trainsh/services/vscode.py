@@ -0,0 +1,279 @@
+"""VS Code Remote-SSH config entries and managed code-server sessions on hosts."""
+
+from __future__ import annotations
+
+import json
+import os
+import re
+import shlex
+import signal
+from pathlib import Path
+from typing import Any, Dict, Optional
+from urllib.parse import quote
+
+from ..constants import STATE_DIR
+from ..core.models import Host
+from .host_exec import host_exec
+from .jupyter_service import generate_token
+from .ssh import SSHClient
+from .tar_stream import shell_path
+from .tunnel import TunnelSpec, find_free_local_port, is_local_port_open, start_local_tunnel
+from .vllm_service import sanitize_service_name, tmux_client_for_host
+
+DEFAULT_SSH_CONFIG_FILE = "~/.ssh/trainsh_config"
+CODE_SERVER_DEFAULT_PORT = 8080
+CODE_SERVER_SESSION = "trainsh-code-server"
+CODE_SERVER_PASSWORD_FILE = "$HOME/.cache/tmux-trainsh/code-server.password"
+
+
+def ssh_config_file() -> Path:
+    """Include file holding every generated `Host trainsh-*` entry."""
+    from ..config import get_config_value
+
+    raw = str(get_config_value("vscode.ssh_config_file", DEFAULT_SSH_CONFIG_FILE) or DEFAULT_SSH_CONFIG_FILE)
+    return Path(os.path.expanduser(raw))
+
+
+def ssh_alias(host_name: str) -> str:
+    return f"trainsh-{sanitize_service_name(host_name)}"
+
+
+def render_ssh_config_entry(alias: str, host: Host) -> str:
+    """OpenSSH `Host` block for the resolved connection (Vast/RunPod included)."""
+    client = SSHClient.from_host(host)
+    target = client.connection_targets[0]
+    lines = [f"Host {alias}", f"    HostName {target.hostname}"]
+    if target.port and int(target.port) != 22:
+        lines.append(f"    Port {int(target.port)}")
+    if client.username:
+        lines.append(f"    User {client.username}")
+    if client.key_path:
+        lines.append(f"    IdentityFile {os.path.expanduser(client.key_path)}")
+        lines.append("    IdentitiesOnly yes")
+    if target.proxy_command:
+        lines.append(f"    ProxyCommand {target.proxy_command}")
+    elif target.jump_host:
+        lines.append(f"    ProxyJump {target.jump_host.strip()}")
+    lines.extend(
+        [
+            "    StrictHostKeyChecking accept-new",
+            "    ServerAliveInterval 30",
+            "    ServerAliveCountMax 3",
+        ]
+    )
+    return "\n".join(lines) + "\n"
+
+
+def _markers(alias: str) -> tuple[str, str]:
+    return f"# >>> {alias} (tmux-trainsh)", f"# <<< {alias} (tmux-trainsh)"
+
+
+def write_ssh_entry(alias: str, entry: str, path: Optional[Path] = None) -> Path:
+    """Insert or replace the marked block for `alias` in the include file."""
+    target = path or ssh_config_file()
+    target.parent.mkdir(parents=True, exist_ok=True)
+    existing = target.read_text(encoding="utf-8") if target.exists() else ""
+    begin, end = _markers(alias)
+    block = f"{begin}\n{entry.rstrip()}\n{end}\n"
+    pattern = re.compile(rf"^{re.escape(begin)}\n.*?^{re.escape(end)}\n?", re.MULTILINE | re.DOTALL)
+    if pattern.search(existing):
+        updated = pattern.sub(lambda _match: block, existing, count=1)
+    else:
+        updated = existing + ("\n" if existing and not existing.endswith("\n") else "") + block
+    target.write_text(updated, encoding="utf-8")
+    os.chmod(target, 0o600)
+    return target
+
+
+def ensure_ssh_include(include: Path, config_path: Optional[Path] = None) -> bool:
+    """Prepend `Include <file>` to ~/.ssh/config unless present; True when it was added.
+
+    OpenSSH only honours Include lines that appear before the first `Host` block.
+    """
+    config = config_path or Path(os.path.expanduser("~/.ssh/config"))
+    config.parent.mkdir(parents=True, exist_ok=True)
+    existing = config.read_text(encoding="utf-8") if config.exists() else ""
+    wanted = {str(include), str(include).replace(os.path.expanduser("~"), "~", 1)}
+    for line in existing.splitlines():
+        parts = line.strip().split(None, 1)
+        if len(parts) == 2 and parts[0].lower() == "include" and parts[1].strip() in wanted:
+            return False
+    config.write_text(f"Include {include}\n\n{existing}", encoding="utf-8")
+    os.chmod(config, 0o600)
+    return True
+
+
+def remote_home(host: Host) -> str:
+    result = host_exec(host, 'printf %s "$HOME"', timeout=30)
+    return result.stdout.strip() if result.success else ""
+
+
+def resolve_remote_folder(host: Host, folder: str) -> str:
+    """Absolute folder for the VS Code URL; `~` and empty resolve against $HOME."""
+    folder = str(folder or "").strip()
+    if folder.startswith("/"):
+        return folder
+    home = remote_home(host) or "/root"
+    if folder in ("", "~"):
+        return home
+    return f"{home.rstrip('/')}/{folder[2:] if folder.startswith('~/') else folder}"
+
+
+def vscode_remote_url(alias: str, folder: str) -> str:
+    return f"vscode://vscode-remote/ssh-remote+{alias}{quote(folder)}"
+
+
+def _state_path(host_name: str) -> Path:
+    root = STATE_DIR / "vscode"
+    root.mkdir(parents=True, exist_ok=True)
+    return root / f"{sanitize_service_name(host_name)}.json"
+
+
+def load_code_server(host_name: str) -> Optional[Dict[str, Any]]:
+    path = _state_path(host_name)
+    if not path.exists():
+        return None
+    try:
+        payload = json.loads(path.read_text(encoding="utf-8"))
+    except Exception:
+        return None
+    return payload if isinstance(payload, dict) else None
+
+
+def _save_code_server(host_name: str, state: Dict[str, Any]) -> None:
+    path = _state_path(host_name)
+    path.write_text(json.dumps(state, indent=2) + "\n", encoding="utf-8")
+    os.chmod(path, 0o600)
+
+
+def code_server_install_script() -> str:
+    """Install the standalone code-server release into ~/.local unless one is on PATH."""
+    return (
+        'export PATH="$HOME/.local/bin:$PATH"; '
+        "command -v code-server >/dev/null 2>&1 || "
+        "curl -fsSL https://code-server.dev/install.sh | sh -s -- --method=standalone --prefix=\"$HOME/.local\""
+    )
+
+
+def code_server_command(*, port: int, folder: str) -> str:
+    """Command sent into tmux; the password comes from a private file, not the pane."""
+    command = (
+        f'export PATH="$HOME/.local/bin:$PATH" PASSWORD="$(cat "{CODE_SERVER_PASSWORD_FILE}")"; '
+        f"code-server --bind-addr 127.0.0.1:{int(port)} --auth password --disable-telemetry"
+    )
+    return f"{command} {shell_path(folder)}" if folder else command
+
+
+def code_server_is_ready(host: Host, port: int) -> bool:
+    result = host_exec(
+        host,
+        f"curl -fsS -o /dev/null http://127.0.0.1:{int(port)}/healthz",
+        timeout=15,
+    )
+    return bool(result.success)
+
+
+def start_code_server(
+    host_name: str,
+    host: Host,
+    *,
+    folder: str = "",
+    port: int = CODE_SERVER_DEFAULT_PORT,
+    install: bool = False,
+) -> Dict[str, Any]:
+    """Start code-server in a tmux session on the host, reusing a running one."""
+    if install:
+        result = host_exec(host, code_server_install_script(), timeout=900)
+        if not result.success:
+            raise RuntimeError(result.stderr.strip() or "code-server install failed")
+
+    state = load_code_server(host_name) or {}
+    client = tmux_client_for_host(host)
+    if state.get("password") and client.has_session(CODE_SERVER_SESSION):
+        return state
+
+    state = {"host_name": host_name, "port": int(port), "folder": folder, "password": generate_token(), "local_port": 0, "tunnel_pid": 0}
+    write = host_exec(
+        host,
+        "sh -c " + shlex.quote(
+            f'umask 077; mkdir -p "$(dirname "{CODE_SERVER_PASSWORD_FILE}")"; cat > "{CODE_SERVER_PASSWORD_FILE}"'
+        ),
+        stdin=state["password"],
+        timeout=30,
+    )
+    if not write.success:
+        raise RuntimeError(write.stderr.strip() or "Failed to store the code-server password on the host")
+    if client.has_session(CODE_SERVER_SESSION):
+        client.kill_session(CODE_SERVER_SESSION)
+    created = client.new_session(CODE_SERVER_SESSION, detached=True)
+    if created.returncode != 0:
+        raise RuntimeError(created.stderr or "Failed to create remote tmux session")
+    sent = client.send_keys(CODE_SERVER_SESSION, code_server_command(port=port, folder=folder), enter=True, literal=True)
+    if sent.returncode != 0:
+        client.kill_session(CODE_SERVER_SESSION)
+        raise RuntimeError(sent.stderr or "Failed to start code-server in tmux")
+    _save_code_server(host_name, state)
+    return state
+
+
+def open_code_server_tunnel(host_name: str, host: Host, state: Dict[str, Any], *, local_port: int = 0) -> Dict[str, Any]:
+    """Forward code-server to localhost and remember the tunnel."""
+    if state.get("local_port") and is_local_port_open("127.0.0.1", int(state["local_port"])) and not local_port:
+        return state
+    _close_tunnel(state)
+    remote_port = int(state.get("port") or CODE_SERVER_DEFAULT_PORT)
+    port = int(local_port or 0)
+    if not port:
+        port = remote_port if not is_local_port_open("127.0.0.1", remote_port) else find_free_local_port()
+    process = start_local_tunnel(host, TunnelSpec(local_port=port, remote_port=remote_port))
+    state["local_port"] = port
+    state["tunnel_pid"] = int(process.pid)
+    _save_code_server(host_name, state)
+    return state
+
+
+def _close_tunnel(state: Dict[str, Any]) -> None:
+    pid = int(state.get("tunnel_pid") or 0)
+    if pid:
+        try:
+            os.kill(pid, signal.SIGTERM)
+        except (ProcessLookupError, PermissionError):
+            pass
+    state["tunnel_pid"] = 0
+    state["local_port"] = 0
+
+
+def stop_code_server(host_name: str, host: Host) -> bool:
+    """Close the tunnel and kill the code-server session; False when nothing was running."""
+    state = load_code_server(host_name)
+    if state is not None:
+        _close_tunnel(state)
+        _state_path(host_name).unlink()
+    client = tmux_client_for_host(host)
+    running = client.has_session(CODE_SERVER_SESSION)
+    if running:
+        result = client.kill_session(CODE_SERVER_SESSION)
+        if result.returncode != 0:
+            raise RuntimeError(result.stderr or "Failed to stop remote tmux session")
+    host_exec(host, f'rm -f "{CODE_SERVER_PASSWORD_FILE}"', timeout=30)
+    return running or state is not None
+
+
+__all__ = [
+    "CODE_SERVER_DEFAULT_PORT",
+    "CODE_SERVER_SESSION",
+    "code_server_command",
+    "code_server_install_script",
+    "code_server_is_ready",
+    "ensure_ssh_include",
+    "load_code_server",
+    "open_code_server_tunnel",
+    "render_ssh_config_entry",
+    "resolve_remote_folder",
+    "ssh_alias",
+    "ssh_config_file",
+    "start_code_server",
+    "stop_code_server",
+    "vscode_remote_url",
+    "write_ssh_entry",
+]
@@ -0,0 +1,278 @@
+"""Weights & Biases API client and linking of train executions to W&B runs."""
+
+from __future__ import annotations
+
+import base64
+import json
+from dataclasses import dataclass, field
+from datetime import datetime, timedelta, timezone
+from typing import Any, Dict, Iterable, List, Optional
+from urllib.error import HTTPError, URLError
+from urllib.request import Request, urlopen
+
+from ..constants import WANDB_API_BASE, SecretKeys
+from ..core.errors import AppError, classify_http
+from ..core.runtime_store import RuntimeStore
+
+DEFAULT_MATCH_WINDOW_SECS = 600
+DEFAULT_LIST_METRICS = ("loss",)
+
+_RUNS_QUERY = """
+query Runs($entity: String!, $project: String!, $first: Int) {
+  project(name: $project, entityName: $entity) {
+    runs(first: $first, order: "-created_at") {
+      edges { node { name displayName state createdAt heartbeatAt host tags summaryMetrics } }
+    }
+  }
+}
+"""
+
+
+class WandbAPIError(AppError):
+    """Exception raised for W&B API errors."""
+
+    def __init__(self, status_code: int, message: str):
+        self.status_code = status_code
+        self.message = message
+        super().__init__(
+            f"W&B API error ({status_code}): {message}",
+            classify_http(status_code, message),
+            provider="wandb",
+            module="wandb_api",
+            details={"status_code": status_code},
+        )
+
+
+@dataclass
+class WandbRun:
+    """One W&B run as returned by the project runs query."""
+
+    id: str
+    name: str
+    entity: str
+    project: str
+    state: str = ""
+    created_at: str = ""
+    heartbeat_at: str = ""
+    host: str = ""
+    tags: List[str] = field(default_factory=list)
+    summary: Dict[str, Any] = field(default_factory=dict)
+
+    @property
+    def url(self) -> str:
+        return f"https://wandb.ai/{self.entity}/{self.project}/runs/{self.id}"
+
+    def final_metrics(self) -> Dict[str, float]:
+        """Numeric summary values, without W&B's own `_step`/`_runtime`-style keys."""
+        return {
+            key: value
+            for key, value in self.summary.items()
+            if not key.startswith("_") and isinstance(value, (int, float)) and not isinstance(value, bool)
+        }
+
+
+def split_project(path: str) -> tuple[str, str]:
+    """`entity/project` into its two parts."""
+    entity, _, project = str(path or "").strip().strip("/").partition("/")
+    if not entity or not project or "/" in project:
+        raise ValueError(f"W&B project must look like ENTITY/PROJECT, got {path!r}")
+    return entity, project
+
+
+class WandbAPIClient:
+    """W&B GraphQL client (read-only)."""
+
+    def __init__(self, api_key: str, base_url: str = WANDB_API_BASE):
+        self.api_key = str(api_key or "").strip()
+        self.base_url = base_url.rstrip("/")
+
+    def _graphql_request(self, query: str, variables: Dict[str, Any]) -> Dict[str, Any]:
+        token = base64.b64encode(f"api:{self.api_key}".encode("utf-8")).decode("ascii")
+        req = Request(
+            f"{self.base_url}/graphql",
+            data=json.dumps({"query": query, "variables": variables}).encode("utf-8"),
+            headers={"Authorization": f"Basic {token}", "Content-Type": "application/json"},
+            method="POST",
+        )
+        try:
+            with urlopen(req, timeout=30) as response:
+                payload = json.loads(response.read().decode("utf-8") or "{}")
+        except HTTPError as exc:
+            error_body = exc.read().decode("utf-8") if exc.fp else ""
+            raise WandbAPIError(exc.code, error_body)
+        except URLError as exc:
+            raise WandbAPIError(0, str(exc.reason))
+
+        errors = payload.get("errors") or []
+        if errors:
+            message = "; ".join(str(item.get("message", item)) for item in errors)
+            raise WandbAPIError(400, message or "GraphQL request failed")
+        data = payload.get("data")
+        if not isinstance(data, dict):
+            raise WandbAPIError(0, "Invalid GraphQL response")
+        return data
+
+    def list_runs(self, project_path: str, *, limit: int = 50) -> List[WandbRun]:
+        """Most recent runs of `entity/project`, newest first."""
+        entity, project = split_project(project_path)
+        data = self._graphql_request(_RUNS_QUERY, {"entity": entity, "project": project, "first": int(limit)})
+        if not data.get("project"):
+            raise WandbAPIError(404, f"Project {project_path} not found")
+        edges = ((data["project"].get("runs") or {}).get("edges")) or []
+        return [self._parse_run(edge.get("node") or {}, entity, project) for edge in edges]
+
+    @staticmethod
+    def _parse_run(node: Dict[str, Any], entity: str, project: str) -> WandbRun:
+        summary = node.get("summaryMetrics") or {}
+        if isinstance(summary, str):
+            try:
+                summary = json.loads(summary or "{}")
+            except ValueError:
+                summary = {}
+        return WandbRun(
+            id=str(node.get("name", "")),
+            name=str(node.get("displayName") or node.get("name") or ""),
+            entity=entity,
+            project=project,
+            state=str(node.get("state", "")),
+            created_at=str(node.get("createdAt", "")),
+            heartbeat_at=str(node.get("heartbeatAt", "")),
+            host=str(node.get("host", "") or ""),
+            tags=[str(tag) for tag in node.get("tags") or []],
+            summary=summary if isinstance(summary, dict) else {},
+        )
+
+
+def get_wandb_client() -> WandbAPIClient:
+    """Build a W&B client from configured secrets."""
+    from ..core.secrets import get_secrets_manager
+
+    api_key = get_secrets_manager().get(SecretKeys.WANDB_API_KEY)
+    if not api_key:
+        raise RuntimeError(
+            "W&B API key not configured. "
+            "Run: train secrets set WANDB_API_KEY"
+        )
+    return WandbAPIClient(api_key)
+
+
+def _local_time(text: str, *, naive_is_utc: bool = False) -> Optional[datetime]:
+    """Naive local time for an ISO timestamp; W&B writes naive UTC, train writes naive local time."""
+    try:
+        value = datetime.fromisoformat(str(text or "").replace("Z", "+00:00"))
+    except ValueError:
+        return None
+    if value.tzinfo is None:
+        if not naive_is_utc:
+            return value
+        value = value.replace(tzinfo=timezone.utc)
+    return value.astimezone().replace(tzinfo=None)
+
+
+def match_score(execution: Dict[str, Any], run: WandbRun, *, window_secs: int = DEFAULT_MATCH_WINDOW_SECS) -> int:
+    """How well `run` fits an execution record from the runtime store; 0 means no match.
+
+    A run tagged with the job id always matches. Otherwise the run must start
+    inside the execution (allowing `window_secs` of slack on both ends), and a
+    matching host name or recipe tag adds to the score.
+    """
+    job_id = str(execution.get("run_id", ""))
+    tags = {tag.lower() for tag in run.tags}
+    if job_id and job_id.lower() in tags:
+        return 1000
+    started = _local_time(str(execution.get("started_at", "")))
+    created = _local_time(run.created_at, naive_is_utc=True)
+    if started is None or created is None:
+        return 0
+    ended = _local_time(str(execution.get("ended_at", ""))) or datetime.now()
+    slack = timedelta(seconds=max(0, int(window_secs)))
+    if not started - slack <= created <= ended + slack:
+        return 0
+    score = 10
+    hosts = execution.get("hosts") if isinstance(execution.get("hosts"), dict) else {}
+    run_host = run.host.lower()
+    if run_host and any(run_host in f"{name} {spec}".lower() for name, spec in hosts.items()):
+        score += 20
+    recipe = str(execution.get("recipe_name", "")).lower()
+    if recipe and recipe in tags:
+        score += 20
+    # Closer starts win ties: one point per minute short of the window.
+    score += max(0, int((slack - abs(created - started)).total_seconds() // 60))
+    return score
+
+
+def best_match(execution: Dict[str, Any], runs: Iterable[WandbRun], *, window_secs: int = DEFAULT_MATCH_WINDOW_SECS) -> Optional[WandbRun]:
+    """Highest-scoring run for an execution, or None when none matches."""
+    scored = [(match_score(execution, run, window_secs=window_secs), run) for run in runs]
+    scored = [item for item in scored if item[0] > 0]
+    return max(scored, key=lambda item: item[0])[1] if scored else None
+
+
+def link_execution(store: RuntimeStore, run_id: str, run: WandbRun) -> Dict[str, Any]:
+    """Save the W&B run and its final metrics on the execution's run record."""
+    link = {
+        "run_id": run.id,
+        "name": run.name,
+        "project": f"{run.entity}/{run.project}",
+        "url": run.url,
+        "state": run.state,
+        "metrics": run.final_metrics(),
+        "linked_at": datetime.now().isoformat(),
+    }
+    existing = store.get_run(run_id) or {"run_id": run_id}
+    store.append_run({**existing, "wandb": link, "updated_at": link["linked_at"]})
+    return link
+
+
+def link_executions(
+    store: RuntimeStore,
+    runs: List[WandbRun],
+    *,
+    run_ids: Optional[Iterable[str]] = None,
+    window_secs: int = DEFAULT_MATCH_WINDOW_SECS,
+    dry_run: bool = False,
+) -> List[tuple[str, WandbRun]]:
+    """Match finished executions (all, or `run_ids`) to W&B runs; each W&B run links at most once."""
+    wanted = set(run_ids) if run_ids is not None else None
+    taken: set = set()
+    linked: List[tuple[str, WandbRun]] = []
+    for execution in sorted(store.list_runs(), key=lambda item: str(item.get("started_at", ""))):
+        run_id = str(execution.get("run_id", ""))
+        if wanted is not None and run_id not in wanted:
+            continue
+        if not execution.get("ended_at"):
+            continue
+        match = best_match(execution, [run for run in runs if run.id not in taken], window_secs=window_secs)
+        if match is None:
+            continue
+        taken.add(match.id)
+        linked.append((run_id, match))
+        if not dry_run:
+            link_execution(store, run_id, match)
+    return linked
+
+
+def format_metrics(metrics: Dict[str, Any], keys: Iterable[str] = DEFAULT_LIST_METRICS) -> str:
+    """`loss=0.41`-style text for the chosen metric keys present in `metrics`."""
+    parts = []
+    for key in keys:
+        value = metrics.get(key)
+        if isinstance(value, (int, float)) and not isinstance(value, bool):
+            parts.append(f"{key}={value:.4g}")
+    return " ".join(parts)
+
+
+__all__ = [
+    "DEFAULT_LIST_METRICS",
+    "DEFAULT_MATCH_WINDOW_SECS",
+    "WandbAPIClient",
+    "WandbAPIError",
+    "WandbRun",
+    "best_match",
+    "format_metrics",
+    "get_wandb_client",
+    "link_execution",
+    "link_executions",
+    "match_score",
+    "split_project",
+]